@@ -2,6 +2,60 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20250123_000001_create_tables;
 mod m20250126_000001_create_system_settings;
+mod m20250127_000001_create_homework_exemptions;
+mod m20250128_000001_create_user_admin_permissions;
+mod m20250129_000001_create_organizations;
+mod m20250130_000001_create_usage_tables;
+mod m20250131_000001_add_branding_settings;
+mod m20250201_000001_create_homework_share_links;
+mod m20250202_000001_add_homework_submission_rules;
+mod m20250203_000001_add_notification_delivered_at;
+mod m20250204_000001_create_user_audit_logs;
+mod m20250205_000001_create_grade_mentions;
+mod m20250206_000001_add_homework_file_kind;
+mod m20250207_000001_create_homework_solutions;
+mod m20250208_000001_create_class_im_channels;
+mod m20250209_000001_create_role_requests;
+mod m20250210_000001_create_certificates;
+mod m20250211_000001_create_student_goals;
+mod m20250212_000001_create_exam_access_logs;
+mod m20250213_000001_add_registration_settings;
+mod m20250214_000001_add_captcha_settings;
+mod m20250215_000001_create_notification_templates;
+mod m20250216_000001_create_homework_parts;
+mod m20250217_000001_add_submission_grading_sla;
+mod m20250218_000001_add_file_retention;
+mod m20250219_000001_create_outbox_events;
+mod m20250220_000001_create_moderation_flags;
+mod m20250221_000001_create_submission_self_assessments;
+mod m20250222_000001_add_homework_grade_context;
+mod m20250223_000001_create_homework_prerequisites;
+mod m20250224_000001_create_messages;
+mod m20250225_000001_create_submission_workflows;
+mod m20250226_000001_create_resubmission_requests;
+mod m20250227_000001_create_class_stats_daily;
+mod m20250228_000001_create_login_history;
+mod m20250301_000001_create_activity_events;
+mod m20250302_000001_create_reactions;
+mod m20250303_000001_create_grade_drafts;
+mod m20250304_000001_add_class_real_name_policy;
+mod m20250305_000001_create_quarantined_files;
+mod m20250306_000001_add_submission_content_metrics;
+mod m20250307_000001_create_missing_grade_policies;
+mod m20250308_000001_create_class_api_tokens;
+mod m20250309_000001_add_file_code_language;
+mod m20250310_000001_create_homework_links;
+mod m20250311_000001_create_viewer_grants;
+mod m20250312_000001_create_homework_feedback;
+mod m20250313_000001_add_file_storage_tier;
+mod m20250314_000001_create_class_leaderboards;
+mod m20250315_000001_create_submission_references;
+mod m20250316_000001_add_homework_grace_minutes;
+mod m20250317_000001_create_content_reports;
+mod m20250318_000001_create_rubrics;
+mod m20250319_000001_create_class_role_labels;
+mod m20250320_000001_create_homework_counters;
+mod m20250321_000001_create_scheduled_exports;
 
 pub struct Migrator;
 
@@ -11,6 +65,60 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20250123_000001_create_tables::Migration),
             Box::new(m20250126_000001_create_system_settings::Migration),
+            Box::new(m20250127_000001_create_homework_exemptions::Migration),
+            Box::new(m20250128_000001_create_user_admin_permissions::Migration),
+            Box::new(m20250129_000001_create_organizations::Migration),
+            Box::new(m20250130_000001_create_usage_tables::Migration),
+            Box::new(m20250131_000001_add_branding_settings::Migration),
+            Box::new(m20250201_000001_create_homework_share_links::Migration),
+            Box::new(m20250202_000001_add_homework_submission_rules::Migration),
+            Box::new(m20250203_000001_add_notification_delivered_at::Migration),
+            Box::new(m20250204_000001_create_user_audit_logs::Migration),
+            Box::new(m20250205_000001_create_grade_mentions::Migration),
+            Box::new(m20250206_000001_add_homework_file_kind::Migration),
+            Box::new(m20250207_000001_create_homework_solutions::Migration),
+            Box::new(m20250208_000001_create_class_im_channels::Migration),
+            Box::new(m20250209_000001_create_role_requests::Migration),
+            Box::new(m20250210_000001_create_certificates::Migration),
+            Box::new(m20250211_000001_create_student_goals::Migration),
+            Box::new(m20250212_000001_create_exam_access_logs::Migration),
+            Box::new(m20250213_000001_add_registration_settings::Migration),
+            Box::new(m20250214_000001_add_captcha_settings::Migration),
+            Box::new(m20250215_000001_create_notification_templates::Migration),
+            Box::new(m20250216_000001_create_homework_parts::Migration),
+            Box::new(m20250217_000001_add_submission_grading_sla::Migration),
+            Box::new(m20250218_000001_add_file_retention::Migration),
+            Box::new(m20250219_000001_create_outbox_events::Migration),
+            Box::new(m20250220_000001_create_moderation_flags::Migration),
+            Box::new(m20250221_000001_create_submission_self_assessments::Migration),
+            Box::new(m20250222_000001_add_homework_grade_context::Migration),
+            Box::new(m20250223_000001_create_homework_prerequisites::Migration),
+            Box::new(m20250224_000001_create_messages::Migration),
+            Box::new(m20250225_000001_create_submission_workflows::Migration),
+            Box::new(m20250226_000001_create_resubmission_requests::Migration),
+            Box::new(m20250227_000001_create_class_stats_daily::Migration),
+            Box::new(m20250228_000001_create_login_history::Migration),
+            Box::new(m20250301_000001_create_activity_events::Migration),
+            Box::new(m20250302_000001_create_reactions::Migration),
+            Box::new(m20250303_000001_create_grade_drafts::Migration),
+            Box::new(m20250304_000001_add_class_real_name_policy::Migration),
+            Box::new(m20250305_000001_create_quarantined_files::Migration),
+            Box::new(m20250306_000001_add_submission_content_metrics::Migration),
+            Box::new(m20250307_000001_create_missing_grade_policies::Migration),
+            Box::new(m20250308_000001_create_class_api_tokens::Migration),
+            Box::new(m20250309_000001_add_file_code_language::Migration),
+            Box::new(m20250310_000001_create_homework_links::Migration),
+            Box::new(m20250311_000001_create_viewer_grants::Migration),
+            Box::new(m20250312_000001_create_homework_feedback::Migration),
+            Box::new(m20250313_000001_add_file_storage_tier::Migration),
+            Box::new(m20250314_000001_create_class_leaderboards::Migration),
+            Box::new(m20250315_000001_create_submission_references::Migration),
+            Box::new(m20250316_000001_add_homework_grace_minutes::Migration),
+            Box::new(m20250317_000001_create_content_reports::Migration),
+            Box::new(m20250318_000001_create_rubrics::Migration),
+            Box::new(m20250319_000001_create_class_role_labels::Migration),
+            Box::new(m20250320_000001_create_homework_counters::Migration),
+            Box::new(m20250321_000001_create_scheduled_exports::Migration),
         ]
     }
 }
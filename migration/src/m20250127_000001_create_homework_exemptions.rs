@@ -0,0 +1,119 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业豁免表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkExemptions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkExemptions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkExemptions::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkExemptions::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HomeworkExemptions::Reason).text().null())
+                    .col(
+                        ColumnDef::new(HomeworkExemptions::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkExemptions::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HomeworkExemptions::Table, HomeworkExemptions::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HomeworkExemptions::Table, HomeworkExemptions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 唯一约束：同一学生同一作业只能有一条豁免记录
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_exemptions_unique")
+                    .table(HomeworkExemptions::Table)
+                    .col(HomeworkExemptions::HomeworkId)
+                    .col(HomeworkExemptions::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_exemptions_user_id")
+                    .table(HomeworkExemptions::Table)
+                    .col(HomeworkExemptions::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkExemptions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkExemptions {
+    #[sea_orm(iden = "homework_exemptions")]
+    Table,
+    Id,
+    HomeworkId,
+    UserId,
+    Reason,
+    CreatedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,194 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 用量计数器表 ====================
+        // org_id 为 0 表示默认租户（唯一索引中不能使用 NULL）
+        manager
+            .create_table(
+                Table::create()
+                    .table(UsageCounters::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UsageCounters::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UsageCounters::OrgId)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageCounters::Period)
+                            .string_len(7)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UsageCounters::Metric)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UsageCounters::Value)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageCounters::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_usage_counters_unique")
+                    .table(UsageCounters::Table)
+                    .col(UsageCounters::OrgId)
+                    .col(UsageCounters::Period)
+                    .col(UsageCounters::Metric)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 用量报表表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(UsageReports::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UsageReports::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::OrgId)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::ReportDate)
+                            .string_len(10)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::TotalUsers)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::ActiveUsers)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::StorageBytes)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::UploadedBytes)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::Submissions)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UsageReports::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_usage_reports_unique")
+                    .table(UsageReports::Table)
+                    .col(UsageReports::OrgId)
+                    .col(UsageReports::ReportDate)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_usage_reports_report_date")
+                    .table(UsageReports::Table)
+                    .col(UsageReports::ReportDate)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UsageReports::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UsageCounters::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UsageCounters {
+    #[sea_orm(iden = "usage_counters")]
+    Table,
+    Id,
+    OrgId,
+    Period,
+    Metric,
+    Value,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum UsageReports {
+    #[sea_orm(iden = "usage_reports")]
+    Table,
+    Id,
+    OrgId,
+    ReportDate,
+    TotalUsers,
+    ActiveUsers,
+    StorageBytes,
+    UploadedBytes,
+    Submissions,
+    CreatedAt,
+}
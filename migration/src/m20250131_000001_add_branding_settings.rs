@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 品牌设置默认值：(key, value, value_type, description)
+const BRANDING_SETTINGS: [(&str, &str, &str, &str); 4] = [
+    (
+        "branding.logo_token",
+        "",
+        "string",
+        "Logo 文件下载令牌（为空使用默认 Logo）",
+    ),
+    (
+        "branding.primary_color",
+        "#1677ff",
+        "string",
+        "主题色（#RRGGBB）",
+    ),
+    ("branding.login_message", "", "string", "登录页提示信息"),
+    ("branding.support_contact", "", "string", "技术支持联系方式"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 插入品牌设置 ====================
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        for (key, value, value_type, description) in BRANDING_SETTINGS {
+            let insert = Query::insert()
+                .into_table(SystemSettings::Table)
+                .columns([
+                    SystemSettings::Key,
+                    SystemSettings::Value,
+                    SystemSettings::ValueType,
+                    SystemSettings::Description,
+                    SystemSettings::UpdatedAt,
+                ])
+                .values_panic([
+                    key.into(),
+                    value.into(),
+                    value_type.into(),
+                    description.into(),
+                    now.into(),
+                ])
+                .to_owned();
+
+            manager.exec_stmt(insert).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(SystemSettings::Table)
+            .and_where(Expr::col(SystemSettings::Key).is_in(BRANDING_SETTINGS.map(|(key, ..)| key)))
+            .to_owned();
+
+        manager.exec_stmt(delete).await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SystemSettings {
+    #[sea_orm(iden = "system_settings")]
+    Table,
+    Key,
+    Value,
+    ValueType,
+    Description,
+    UpdatedAt,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业增加提交方式与内容长度限制 ====================
+        // submission_mode: text / attachment / both，既有作业默认 both
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .add_column(
+                        ColumnDef::new(Homeworks::SubmissionMode)
+                            .string_len(16)
+                            .not_null()
+                            .default("both"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // max_content_length: 文本内容最大字符数，为空表示不限制
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .add_column(ColumnDef::new(Homeworks::MaxContentLength).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .drop_column(Homeworks::MaxContentLength)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .drop_column(Homeworks::SubmissionMode)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    SubmissionMode,
+    MaxContentLength,
+}
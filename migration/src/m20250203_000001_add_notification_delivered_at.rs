@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 通知增加 WebSocket 送达时间 ====================
+        // 为空表示尚未通过 WebSocket 送达，重连时补发
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notifications::Table)
+                    .add_column(
+                        ColumnDef::new(Notifications::DeliveredAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 既有通知视为已送达，避免首次重连时补发全部历史通知
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Notifications::Table)
+                    .value(
+                        Notifications::DeliveredAt,
+                        Expr::col(Notifications::CreatedAt),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_notifications_user_delivered")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::DeliveredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_notifications_user_delivered")
+                    .table(Notifications::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notifications::Table)
+                    .drop_column(Notifications::DeliveredAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notifications {
+    #[sea_orm(iden = "notifications")]
+    Table,
+    UserId,
+    CreatedAt,
+    DeliveredAt,
+}
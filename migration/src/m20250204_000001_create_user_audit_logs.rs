@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 用户操作审计日志表 ====================
+        // 不对 user_id 建外键：用户被删除后仍需保留审计记录
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserAuditLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserAuditLogs::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserAuditLogs::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserAuditLogs::Action)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserAuditLogs::Detail).text().null())
+                    .col(
+                        ColumnDef::new(UserAuditLogs::OperatorId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserAuditLogs::IpAddress)
+                            .string_len(45)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserAuditLogs::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_audit_logs_user_id")
+                    .table(UserAuditLogs::Table)
+                    .col(UserAuditLogs::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_audit_logs_created_at")
+                    .table(UserAuditLogs::Table)
+                    .col(UserAuditLogs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserAuditLogs::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserAuditLogs {
+    #[sea_orm(iden = "user_audit_logs")]
+    Table,
+    Id,
+    UserId,
+    Action,
+    Detail,
+    OperatorId,
+    IpAddress,
+    CreatedAt,
+}
@@ -0,0 +1,118 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 评语 @提及 表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(GradeMentions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GradeMentions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeMentions::GradeId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeMentions::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeMentions::MentionedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeMentions::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_grade_mentions_grade")
+                            .from(GradeMentions::Table, GradeMentions::GradeId)
+                            .to(Grades::Table, Grades::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_grade_mentions_user")
+                            .from(GradeMentions::Table, GradeMentions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_grade_mentions_grade_user")
+                    .table(GradeMentions::Table)
+                    .col(GradeMentions::GradeId)
+                    .col(GradeMentions::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_grade_mentions_user_id")
+                    .table(GradeMentions::Table)
+                    .col(GradeMentions::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GradeMentions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GradeMentions {
+    #[sea_orm(iden = "grade_mentions")]
+    Table,
+    Id,
+    GradeId,
+    UserId,
+    MentionedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Grades {
+    #[sea_orm(iden = "grades")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
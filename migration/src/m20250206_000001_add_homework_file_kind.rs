@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业附件增加用途 ====================
+        // reference: 参考资料, template: 提交模板, solution: 参考答案（截止或评分后可见）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(HomeworkFiles::Table)
+                    .add_column(
+                        ColumnDef::new(HomeworkFiles::AttachmentKind)
+                            .string_len(16)
+                            .not_null()
+                            .default("reference"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(HomeworkFiles::Table)
+                    .drop_column(HomeworkFiles::AttachmentKind)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkFiles {
+    #[sea_orm(iden = "homework_files")]
+    Table,
+    AttachmentKind,
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业参考答案表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkSolutions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkSolutions::HomeworkId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(HomeworkSolutions::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(HomeworkSolutions::RevealPolicy)
+                            .string_len(16)
+                            .not_null()
+                            .default("deadline"),
+                    )
+                    .col(ColumnDef::new(HomeworkSolutions::PublishedAt).big_integer())
+                    .col(ColumnDef::new(HomeworkSolutions::RevealedAt).big_integer())
+                    .col(
+                        ColumnDef::new(HomeworkSolutions::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkSolutions::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkSolutions::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_solutions_homework")
+                            .from(HomeworkSolutions::Table, HomeworkSolutions::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 定时任务只扫描尚未公开的参考答案
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_solutions_revealed_at")
+                    .table(HomeworkSolutions::Table)
+                    .col(HomeworkSolutions::RevealedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkSolutions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkSolutions {
+    #[sea_orm(iden = "homework_solutions")]
+    Table,
+    HomeworkId,
+    Content,
+    RevealPolicy,
+    PublishedAt,
+    RevealedAt,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
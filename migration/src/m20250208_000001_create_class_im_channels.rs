@@ -0,0 +1,233 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级 IM 通知渠道表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassImChannels::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassImChannels::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassImChannels::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassImChannels::Name)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassImChannels::Provider)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ClassImChannels::Target).text().not_null())
+                    .col(ColumnDef::new(ClassImChannels::Secret).text())
+                    .col(ColumnDef::new(ClassImChannels::Events).text().not_null())
+                    .col(ColumnDef::new(ClassImChannels::Templates).text())
+                    .col(
+                        ColumnDef::new(ClassImChannels::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(ClassImChannels::LastError).text())
+                    .col(ColumnDef::new(ClassImChannels::LastDeliveredAt).big_integer())
+                    .col(
+                        ColumnDef::new(ClassImChannels::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassImChannels::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassImChannels::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_im_channels_class")
+                            .from(ClassImChannels::Table, ClassImChannels::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_class_im_channels_class_id")
+                    .table(ClassImChannels::Table)
+                    .col(ClassImChannels::ClassId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== IM 消息投递队列表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImDeliveries::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ImDeliveries::ChannelId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImDeliveries::Event)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImDeliveries::ReferenceId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImDeliveries::Message).text().not_null())
+                    .col(
+                        ColumnDef::new(ImDeliveries::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(ImDeliveries::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ImDeliveries::NextAttemptAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImDeliveries::LastError).text())
+                    .col(
+                        ColumnDef::new(ImDeliveries::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImDeliveries::DeliveredAt).big_integer())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_im_deliveries_channel")
+                            .from(ImDeliveries::Table, ImDeliveries::ChannelId)
+                            .to(ClassImChannels::Table, ClassImChannels::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 同一渠道同一事件只投递一次（截止提醒依赖此约束去重）
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_im_deliveries_channel_event_ref")
+                    .table(ImDeliveries::Table)
+                    .col(ImDeliveries::ChannelId)
+                    .col(ImDeliveries::Event)
+                    .col(ImDeliveries::ReferenceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_im_deliveries_status_next_attempt")
+                    .table(ImDeliveries::Table)
+                    .col(ImDeliveries::Status)
+                    .col(ImDeliveries::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImDeliveries::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ClassImChannels::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClassImChannels {
+    #[sea_orm(iden = "class_im_channels")]
+    Table,
+    Id,
+    ClassId,
+    Name,
+    Provider,
+    Target,
+    Secret,
+    Events,
+    Templates,
+    Enabled,
+    LastError,
+    LastDeliveredAt,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ImDeliveries {
+    #[sea_orm(iden = "im_deliveries")]
+    Table,
+    Id,
+    ChannelId,
+    Event,
+    ReferenceId,
+    Message,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    LastError,
+    CreatedAt,
+    DeliveredAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 角色申请表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(RoleRequests::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RoleRequests::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RoleRequests::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RoleRequests::OrgId).big_integer().null())
+                    .col(
+                        ColumnDef::new(RoleRequests::RequestedRole)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RoleRequests::Reason).text().not_null())
+                    .col(
+                        ColumnDef::new(RoleRequests::AttachmentFileId)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(RoleRequests::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(RoleRequests::ReviewerId)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(RoleRequests::ReviewComment).text().null())
+                    .col(
+                        ColumnDef::new(RoleRequests::ReviewedAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(RoleRequests::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_requests_user")
+                            .from(RoleRequests::Table, RoleRequests::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_requests_attachment")
+                            .from(RoleRequests::Table, RoleRequests::AttachmentFileId)
+                            .to(Files::Table, Files::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 审核队列按状态 + 时间查询
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_role_requests_status_created_at")
+                    .table(RoleRequests::Table)
+                    .col(RoleRequests::Status)
+                    .col(RoleRequests::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_role_requests_user_id")
+                    .table(RoleRequests::Table)
+                    .col(RoleRequests::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RoleRequests::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum RoleRequests {
+    #[sea_orm(iden = "role_requests")]
+    Table,
+    Id,
+    UserId,
+    OrgId,
+    RequestedRole,
+    Reason,
+    AttachmentFileId,
+    Status,
+    ReviewerId,
+    ReviewComment,
+    ReviewedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Files {
+    #[sea_orm(iden = "files")]
+    Table,
+    Id,
+}
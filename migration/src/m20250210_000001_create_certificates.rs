@@ -0,0 +1,234 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级结业证书设置表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassCertificateSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::ClassId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::MinScorePercent)
+                            .double()
+                            .not_null()
+                            .default(60.0),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::Title)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::BodyTemplate)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::SignerName)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::SignatureFileId)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::UpdatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassCertificateSettings::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_certificate_settings_class")
+                            .from(
+                                ClassCertificateSettings::Table,
+                                ClassCertificateSettings::ClassId,
+                            )
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_certificate_settings_signature")
+                            .from(
+                                ClassCertificateSettings::Table,
+                                ClassCertificateSettings::SignatureFileId,
+                            )
+                            .to(Files::Table, Files::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 结业证书表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(Certificates::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Certificates::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Certificates::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Certificates::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Certificates::VerificationCode)
+                            .string_len(32)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Certificates::AveragePercent)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Certificates::HomeworkCount)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Certificates::IssuedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_certificates_class")
+                            .from(Certificates::Table, Certificates::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_certificates_user")
+                            .from(Certificates::Table, Certificates::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 每个学生在每个班级最多一张证书
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_certificates_class_user")
+                    .table(Certificates::Table)
+                    .col(Certificates::ClassId)
+                    .col(Certificates::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Certificates::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassCertificateSettings::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClassCertificateSettings {
+    #[sea_orm(iden = "class_certificate_settings")]
+    Table,
+    ClassId,
+    Enabled,
+    MinScorePercent,
+    Title,
+    BodyTemplate,
+    SignerName,
+    SignatureFileId,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Certificates {
+    #[sea_orm(iden = "certificates")]
+    Table,
+    Id,
+    ClassId,
+    UserId,
+    VerificationCode,
+    AveragePercent,
+    HomeworkCount,
+    IssuedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Files {
+    #[sea_orm(iden = "files")]
+    Table,
+    Id,
+}
@@ -0,0 +1,114 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 学生目标表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(StudentGoals::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StudentGoals::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(StudentGoals::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StudentGoals::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StudentGoals::TargetPercent)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StudentGoals::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StudentGoals::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_student_goals_user")
+                            .from(StudentGoals::Table, StudentGoals::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_student_goals_class")
+                            .from(StudentGoals::Table, StudentGoals::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 每个学生在每个班级最多一个目标
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_student_goals_user_class")
+                    .table(StudentGoals::Table)
+                    .col(StudentGoals::UserId)
+                    .col(StudentGoals::ClassId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StudentGoals::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum StudentGoals {
+    #[sea_orm(iden = "student_goals")]
+    Table,
+    Id,
+    UserId,
+    ClassId,
+    TargetPercent,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
@@ -0,0 +1,155 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业增加考试模式 ====================
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .add_column(
+                        ColumnDef::new(Homeworks::ExamMode)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 考试访问日志表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExamAccessLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::Event)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::IpAddress)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::UserAgent)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::SubmissionId)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::Detail)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExamAccessLogs::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_exam_access_logs_homework")
+                            .from(ExamAccessLogs::Table, ExamAccessLogs::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_exam_access_logs_user")
+                            .from(ExamAccessLogs::Table, ExamAccessLogs::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 按作业、学生查询访问记录
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_exam_access_logs_homework_user")
+                    .table(ExamAccessLogs::Table)
+                    .col(ExamAccessLogs::HomeworkId)
+                    .col(ExamAccessLogs::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExamAccessLogs::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .drop_column(Homeworks::ExamMode)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+    ExamMode,
+}
+
+#[derive(DeriveIden)]
+enum ExamAccessLogs {
+    #[sea_orm(iden = "exam_access_logs")]
+    Table,
+    Id,
+    HomeworkId,
+    UserId,
+    Event,
+    IpAddress,
+    UserAgent,
+    SubmissionId,
+    Detail,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 注册策略默认值：(key, value, value_type, description)
+const REGISTRATION_SETTINGS: [(&str, &str, &str, &str); 4] = [
+    (
+        "registration.mode",
+        "open",
+        "string",
+        "注册模式（open/closed/invite_only）",
+    ),
+    (
+        "registration.email_domains",
+        "[]",
+        "json_array",
+        "允许注册的邮箱域名（为空不限制）",
+    ),
+    (
+        "registration.default_role",
+        "user",
+        "string",
+        "自助注册用户的角色（user/teacher）",
+    ),
+    (
+        "registration.captcha_required",
+        "false",
+        "boolean",
+        "注册时是否需要人机验证",
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 插入注册策略设置 ====================
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        for (key, value, value_type, description) in REGISTRATION_SETTINGS {
+            let insert = Query::insert()
+                .into_table(SystemSettings::Table)
+                .columns([
+                    SystemSettings::Key,
+                    SystemSettings::Value,
+                    SystemSettings::ValueType,
+                    SystemSettings::Description,
+                    SystemSettings::UpdatedAt,
+                ])
+                .values_panic([
+                    key.into(),
+                    value.into(),
+                    value_type.into(),
+                    description.into(),
+                    now.into(),
+                ])
+                .to_owned();
+
+            manager.exec_stmt(insert).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(SystemSettings::Table)
+            .and_where(
+                Expr::col(SystemSettings::Key).is_in(REGISTRATION_SETTINGS.map(|(key, ..)| key)),
+            )
+            .to_owned();
+
+        manager.exec_stmt(delete).await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SystemSettings {
+    #[sea_orm(iden = "system_settings")]
+    Table,
+    Key,
+    Value,
+    ValueType,
+    Description,
+    UpdatedAt,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 人机验证默认值：(key, value, value_type, description)
+const CAPTCHA_SETTINGS: [(&str, &str, &str, &str); 5] = [
+    (
+        "captcha.provider",
+        "none",
+        "string",
+        "人机验证服务商（none/hcaptcha/recaptcha/turnstile）",
+    ),
+    (
+        "captcha.site_key",
+        "",
+        "string",
+        "人机验证站点密钥（前端使用）",
+    ),
+    ("captcha.secret_key", "", "string", "人机验证服务端密钥"),
+    (
+        "captcha.login_mode",
+        "after_failures",
+        "string",
+        "登录验证策略（off/always/after_failures）",
+    ),
+    (
+        "captcha.login_failure_threshold",
+        "3",
+        "integer",
+        "连续登录失败多少次后需要人机验证",
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 插入人机验证设置 ====================
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        for (key, value, value_type, description) in CAPTCHA_SETTINGS {
+            let insert = Query::insert()
+                .into_table(SystemSettings::Table)
+                .columns([
+                    SystemSettings::Key,
+                    SystemSettings::Value,
+                    SystemSettings::ValueType,
+                    SystemSettings::Description,
+                    SystemSettings::UpdatedAt,
+                ])
+                .values_panic([
+                    key.into(),
+                    value.into(),
+                    value_type.into(),
+                    description.into(),
+                    now.into(),
+                ])
+                .to_owned();
+
+            manager.exec_stmt(insert).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(SystemSettings::Table)
+            .and_where(Expr::col(SystemSettings::Key).is_in(CAPTCHA_SETTINGS.map(|(key, ..)| key)))
+            .to_owned();
+
+        manager.exec_stmt(delete).await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SystemSettings {
+    #[sea_orm(iden = "system_settings")]
+    Table,
+    Key,
+    Value,
+    ValueType,
+    Description,
+    UpdatedAt,
+}
@@ -0,0 +1,156 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 通知语言设置键
+const LOCALE_SETTING_KEY: &str = "notification.locale";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 通知模板表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationTemplates::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationTemplates::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationTemplates::NotificationType)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationTemplates::Locale)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationTemplates::Title)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(NotificationTemplates::Content).text().null())
+                    .col(
+                        ColumnDef::new(NotificationTemplates::UpdatedBy)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationTemplates::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationTemplates::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_templates_updated_by")
+                            .from(
+                                NotificationTemplates::Table,
+                                NotificationTemplates::UpdatedBy,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 每种通知类型在每种语言下最多一个模板
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_notification_templates_type_locale")
+                    .table(NotificationTemplates::Table)
+                    .col(NotificationTemplates::NotificationType)
+                    .col(NotificationTemplates::Locale)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 插入通知语言设置 ====================
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let insert = Query::insert()
+            .into_table(SystemSettings::Table)
+            .columns([
+                SystemSettings::Key,
+                SystemSettings::Value,
+                SystemSettings::ValueType,
+                SystemSettings::Description,
+                SystemSettings::UpdatedAt,
+            ])
+            .values_panic([
+                LOCALE_SETTING_KEY.into(),
+                "zh-CN".into(),
+                "string".into(),
+                "通知模板使用的语言（无对应模板时使用内置文案）".into(),
+                now.into(),
+            ])
+            .to_owned();
+        manager.exec_stmt(insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(SystemSettings::Table)
+            .and_where(Expr::col(SystemSettings::Key).eq(LOCALE_SETTING_KEY))
+            .to_owned();
+        manager.exec_stmt(delete).await?;
+
+        manager
+            .drop_table(Table::drop().table(NotificationTemplates::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationTemplates {
+    #[sea_orm(iden = "notification_templates")]
+    Table,
+    Id,
+    NotificationType,
+    Locale,
+    Title,
+    Content,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum SystemSettings {
+    #[sea_orm(iden = "system_settings")]
+    Table,
+    Key,
+    Value,
+    ValueType,
+    Description,
+    UpdatedAt,
+}
@@ -0,0 +1,145 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业分题表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkParts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkParts::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkParts::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HomeworkParts::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(HomeworkParts::Title)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HomeworkParts::Description).text().null())
+                    .col(ColumnDef::new(HomeworkParts::MaxScore).double().not_null())
+                    .col(ColumnDef::new(HomeworkParts::Deadline).big_integer().null())
+                    .col(
+                        ColumnDef::new(HomeworkParts::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkParts::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_parts_homework")
+                            .from(HomeworkParts::Table, HomeworkParts::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_parts_homework_position")
+                    .table(HomeworkParts::Table)
+                    .col(HomeworkParts::HomeworkId)
+                    .col(HomeworkParts::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 提交关联分题 ====================
+        // 为空表示整份作业提交（未拆分的作业）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(ColumnDef::new(Submissions::PartId).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_submissions_part_id")
+                    .table(Submissions::Table)
+                    .col(Submissions::PartId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_submissions_part_id")
+                    .table(Submissions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submissions::Table)
+                    .drop_column(Submissions::PartId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(HomeworkParts::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkParts {
+    #[sea_orm(iden = "homework_parts")]
+    Table,
+    Id,
+    HomeworkId,
+    Position,
+    Title,
+    Description,
+    MaxScore,
+    Deadline,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    PartId,
+}
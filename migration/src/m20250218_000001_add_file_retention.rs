@@ -0,0 +1,140 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级归档时间 ====================
+        // 归档后开始计算附件保留期，为空表示未归档
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .add_column(ColumnDef::new(Classes::ArchivedAt).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 班级文件保留策略覆盖表 ====================
+        // 天数为空表示沿用系统默认值，0 表示永久保留
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassRetentionSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassRetentionSettings::ClassId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRetentionSettings::SubmissionFilesDays)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRetentionSettings::HomeworkFilesDays)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRetentionSettings::UpdatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRetentionSettings::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRetentionSettings::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_retention_settings_class")
+                            .from(
+                                ClassRetentionSettings::Table,
+                                ClassRetentionSettings::ClassId,
+                            )
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 孤立文件清理按上传时间筛选
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_files_created_at")
+                    .table(Files::Table)
+                    .col(Files::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_files_created_at")
+                    .table(Files::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassRetentionSettings::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .drop_column(Classes::ArchivedAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+    ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum ClassRetentionSettings {
+    #[sea_orm(iden = "class_retention_settings")]
+    Table,
+    ClassId,
+    SubmissionFilesDays,
+    HomeworkFilesDays,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Files {
+    #[sea_orm(iden = "files")]
+    Table,
+    CreatedAt,
+}
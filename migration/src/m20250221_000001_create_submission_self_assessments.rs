@@ -0,0 +1,177 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业增加自评要求 ====================
+        // require_self_assessment: 学生提交时必须附带自评，既有作业默认不要求
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .add_column(
+                        ColumnDef::new(Homeworks::RequireSelfAssessment)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 提交自评表 ====================
+        // 每次提交至多一份自评，随提交一并删除
+        manager
+            .create_table(
+                Table::create()
+                    .table(SubmissionSelfAssessments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::SubmissionId)
+                            .big_integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::Confidence)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::TimeSpentMinutes)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::Difficulties)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionSelfAssessments::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_submission_self_assessments_submission")
+                            .from(
+                                SubmissionSelfAssessments::Table,
+                                SubmissionSelfAssessments::SubmissionId,
+                            )
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_submission_self_assessments_homework")
+                            .from(
+                                SubmissionSelfAssessments::Table,
+                                SubmissionSelfAssessments::HomeworkId,
+                            )
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_submission_self_assessments_user")
+                            .from(
+                                SubmissionSelfAssessments::Table,
+                                SubmissionSelfAssessments::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_submission_self_assessments_homework")
+                    .table(SubmissionSelfAssessments::Table)
+                    .col(SubmissionSelfAssessments::HomeworkId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(SubmissionSelfAssessments::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .drop_column(Homeworks::RequireSelfAssessment)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SubmissionSelfAssessments {
+    #[sea_orm(iden = "submission_self_assessments")]
+    Table,
+    Id,
+    SubmissionId,
+    HomeworkId,
+    UserId,
+    Confidence,
+    TimeSpentMinutes,
+    Difficulties,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+    RequireSelfAssessment,
+}
+
+#[derive(DeriveIden)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
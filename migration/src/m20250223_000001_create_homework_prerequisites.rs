@@ -0,0 +1,126 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业前置条件表 ====================
+        // 学生需先满足前置作业的要求（已提交 / 已及格）才能提交本作业
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkPrerequisites::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkPrerequisites::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkPrerequisites::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkPrerequisites::PrerequisiteId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    // submitted: 已提交即可；passed: 最新提交得分达到及格线
+                    .col(
+                        ColumnDef::new(HomeworkPrerequisites::Requirement)
+                            .string_len(16)
+                            .not_null()
+                            .default("submitted"),
+                    )
+                    // 及格分数，为空时取前置作业满分的 60%
+                    .col(
+                        ColumnDef::new(HomeworkPrerequisites::MinScore)
+                            .double()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkPrerequisites::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                HomeworkPrerequisites::Table,
+                                HomeworkPrerequisites::HomeworkId,
+                            )
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                HomeworkPrerequisites::Table,
+                                HomeworkPrerequisites::PrerequisiteId,
+                            )
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 唯一约束：同一作业不能重复声明同一前置作业
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_prerequisites_unique")
+                    .table(HomeworkPrerequisites::Table)
+                    .col(HomeworkPrerequisites::HomeworkId)
+                    .col(HomeworkPrerequisites::PrerequisiteId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_prerequisites_prerequisite_id")
+                    .table(HomeworkPrerequisites::Table)
+                    .col(HomeworkPrerequisites::PrerequisiteId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkPrerequisites::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkPrerequisites {
+    #[sea_orm(iden = "homework_prerequisites")]
+    Table,
+    Id,
+    HomeworkId,
+    PrerequisiteId,
+    Requirement,
+    MinScore,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级增加学生互发私信开关 ====================
+        // allow_student_messages: 默认只允许学生与教师之间私信
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .add_column(
+                        ColumnDef::new(Classes::AllowStudentMessages)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 私信表 ====================
+        // 私信限定在班级内，会话由（班级, 双方用户）确定
+        manager
+            .create_table(
+                Table::create()
+                    .table(Messages::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Messages::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Messages::ClassId).big_integer().not_null())
+                    .col(ColumnDef::new(Messages::SenderId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(Messages::RecipientId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Messages::Content).text().not_null())
+                    .col(ColumnDef::new(Messages::ReadAt).big_integer().null())
+                    .col(ColumnDef::new(Messages::CreatedAt).big_integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Messages::Table, Messages::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Messages::Table, Messages::SenderId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Messages::Table, Messages::RecipientId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 会话消息查询
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_messages_class_sender_recipient")
+                    .table(Messages::Table)
+                    .col(Messages::ClassId)
+                    .col(Messages::SenderId)
+                    .col(Messages::RecipientId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 未读计数
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_messages_recipient_read_at")
+                    .table(Messages::Table)
+                    .col(Messages::RecipientId)
+                    .col(Messages::ReadAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Messages::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .drop_column(Classes::AllowStudentMessages)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    #[sea_orm(iden = "messages")]
+    Table,
+    Id,
+    ClassId,
+    SenderId,
+    RecipientId,
+    Content,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+    AllowStudentMessages,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 提交工作流状态 ====================
+        // 班级自定义状态（如「退回修改」），为空时沿用内置状态 status；评分时清空
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(
+                        ColumnDef::new(Submissions::WorkflowStatus)
+                            .string_len(32)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 班级提交状态工作流表 ====================
+        // 未配置的班级使用默认工作流（pending / late / graded）
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassSubmissionWorkflows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassSubmissionWorkflows::ClassId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    // JSON 数组：自定义状态 [{ key, label }]
+                    .col(
+                        ColumnDef::new(ClassSubmissionWorkflows::Statuses)
+                            .text()
+                            .not_null(),
+                    )
+                    // JSON 数组：允许的状态转换 [{ from, to, roles }]
+                    .col(
+                        ColumnDef::new(ClassSubmissionWorkflows::Transitions)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassSubmissionWorkflows::UpdatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassSubmissionWorkflows::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassSubmissionWorkflows::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_submission_workflows_class")
+                            .from(
+                                ClassSubmissionWorkflows::Table,
+                                ClassSubmissionWorkflows::ClassId,
+                            )
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassSubmissionWorkflows::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submissions::Table)
+                    .drop_column(Submissions::WorkflowStatus)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    WorkflowStatus,
+}
+
+#[derive(DeriveIden)]
+enum ClassSubmissionWorkflows {
+    #[sea_orm(iden = "class_submission_workflows")]
+    Table,
+    ClassId,
+    Statuses,
+    Transitions,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
@@ -0,0 +1,158 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 退回重交请求表 ====================
+        // 教师退回提交后，学生在 reopen_until 之前重新提交不计迟交；重新提交后记录 fulfilled_at
+        manager
+            .create_table(
+                Table::create()
+                    .table(ResubmissionRequests::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::SubmissionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::StudentId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::RequestedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::Comment)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::ReopenUntil)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::FulfilledAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResubmissionRequests::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                ResubmissionRequests::Table,
+                                ResubmissionRequests::SubmissionId,
+                            )
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                ResubmissionRequests::Table,
+                                ResubmissionRequests::HomeworkId,
+                            )
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ResubmissionRequests::Table, ResubmissionRequests::StudentId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                ResubmissionRequests::Table,
+                                ResubmissionRequests::RequestedBy,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 学生提交时查询未完成的退回请求
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_resubmission_requests_homework_student")
+                    .table(ResubmissionRequests::Table)
+                    .col(ResubmissionRequests::HomeworkId)
+                    .col(ResubmissionRequests::StudentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ResubmissionRequests::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ResubmissionRequests {
+    #[sea_orm(iden = "resubmission_requests")]
+    Table,
+    Id,
+    SubmissionId,
+    HomeworkId,
+    StudentId,
+    RequestedBy,
+    Comment,
+    ReopenUntil,
+    FulfilledAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
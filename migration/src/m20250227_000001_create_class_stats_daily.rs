@@ -0,0 +1,132 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级每日统计快照表 ====================
+        // 每天零点记录各班级截至前一天结束时的累计指标，供教师仪表盘绘制趋势图
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassStatsDaily::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::StatDate)
+                            .string_len(10)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::MemberCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::StudentCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::SubmissionCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::GradedCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::AverageScorePercent)
+                            .double()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassStatsDaily::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ClassStatsDaily::Table, ClassStatsDaily::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_class_stats_daily_unique")
+                    .table(ClassStatsDaily::Table)
+                    .col(ClassStatsDaily::ClassId)
+                    .col(ClassStatsDaily::StatDate)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_class_stats_daily_stat_date")
+                    .table(ClassStatsDaily::Table)
+                    .col(ClassStatsDaily::StatDate)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClassStatsDaily::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClassStatsDaily {
+    #[sea_orm(iden = "class_stats_daily")]
+    Table,
+    Id,
+    ClassId,
+    StatDate,
+    MemberCount,
+    StudentCount,
+    SubmissionCount,
+    GradedCount,
+    AverageScorePercent,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
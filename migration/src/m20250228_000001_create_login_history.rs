@@ -0,0 +1,150 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 登录历史表 ====================
+        // 记录每次登录尝试（成功与密码错误），成功登录时与近期登录比较并标记新设备、新网络
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LoginHistory::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(LoginHistory::Success).boolean().not_null())
+                    .col(
+                        ColumnDef::new(LoginHistory::IpAddress)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .col(ColumnDef::new(LoginHistory::Country).string_len(2).null())
+                    .col(
+                        ColumnDef::new(LoginHistory::UserAgent)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::Browser)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(LoginHistory::Os).string_len(32).not_null())
+                    .col(
+                        ColumnDef::new(LoginHistory::DeviceType)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::NewDevice)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::NewIp)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::NewCountry)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::Suspicious)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(LoginHistory::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LoginHistory::Table, LoginHistory::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_login_history_user_created")
+                    .table(LoginHistory::Table)
+                    .col(LoginHistory::UserId)
+                    .col(LoginHistory::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_login_history_suspicious")
+                    .table(LoginHistory::Table)
+                    .col(LoginHistory::Suspicious)
+                    .col(LoginHistory::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LoginHistory::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginHistory {
+    #[sea_orm(iden = "login_history")]
+    Table,
+    Id,
+    UserId,
+    Success,
+    IpAddress,
+    Country,
+    UserAgent,
+    Browser,
+    Os,
+    DeviceType,
+    NewDevice,
+    NewIp,
+    NewCountry,
+    Suspicious,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级动态表 ====================
+        // 发布作业、班级公告、发布评分、成员加入时写入，班级动态流按 ID 倒序游标分页读取
+        manager
+            .create_table(
+                Table::create()
+                    .table(ActivityEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ActivityEvents::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ActivityEvents::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ActivityEvents::EventType)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ActivityEvents::ActorId).big_integer().null())
+                    .col(
+                        ColumnDef::new(ActivityEvents::ReferenceId)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ActivityEvents::Title)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ActivityEvents::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ActivityEvents::Table, ActivityEvents::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ActivityEvents::Table, ActivityEvents::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_activity_events_class_id")
+                    .table(ActivityEvents::Table)
+                    .col(ActivityEvents::ClassId)
+                    .col(ActivityEvents::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_activity_events_reference")
+                    .table(ActivityEvents::Table)
+                    .col(ActivityEvents::EventType)
+                    .col(ActivityEvents::ReferenceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ActivityEvents::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ActivityEvents {
+    #[sea_orm(iden = "activity_events")]
+    Table,
+    Id,
+    ClassId,
+    EventType,
+    ActorId,
+    ReferenceId,
+    Title,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 表情回应表 ====================
+        // 班级公告与评语的表情回应，每个用户对同一目标的同一表情只保留一条
+        manager
+            .create_table(
+                Table::create()
+                    .table(Reactions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Reactions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Reactions::UserId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(Reactions::TargetType)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Reactions::TargetId).big_integer().not_null())
+                    .col(ColumnDef::new(Reactions::Emoji).string_len(32).not_null())
+                    .col(
+                        ColumnDef::new(Reactions::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Reactions::Table, Reactions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_reactions_unique")
+                    .table(Reactions::Table)
+                    .col(Reactions::TargetType)
+                    .col(Reactions::TargetId)
+                    .col(Reactions::UserId)
+                    .col(Reactions::Emoji)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Reactions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Reactions {
+    #[sea_orm(iden = "reactions")]
+    Table,
+    Id,
+    UserId,
+    TargetType,
+    TargetId,
+    Emoji,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
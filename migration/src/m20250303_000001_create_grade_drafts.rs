@@ -0,0 +1,121 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 评分草稿表 ====================
+        // 教师批改过程中自动保存的分数与评语，每位评分人每份提交一条；正式评分后删除，过期草稿定期清理
+        manager
+            .create_table(
+                Table::create()
+                    .table(GradeDrafts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GradeDrafts::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeDrafts::SubmissionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeDrafts::GraderId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GradeDrafts::Score).double().null())
+                    .col(ColumnDef::new(GradeDrafts::Comment).text().null())
+                    .col(
+                        ColumnDef::new(GradeDrafts::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeDrafts::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GradeDrafts::Table, GradeDrafts::SubmissionId)
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GradeDrafts::Table, GradeDrafts::GraderId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_grade_drafts_unique")
+                    .table(GradeDrafts::Table)
+                    .col(GradeDrafts::GraderId)
+                    .col(GradeDrafts::SubmissionId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_grade_drafts_updated_at")
+                    .table(GradeDrafts::Table)
+                    .col(GradeDrafts::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GradeDrafts::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GradeDrafts {
+    #[sea_orm(iden = "grade_drafts")]
+    Table,
+    Id,
+    SubmissionId,
+    GraderId,
+    Score,
+    Comment,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
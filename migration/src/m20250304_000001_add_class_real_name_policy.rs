@@ -0,0 +1,99 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级增加实名要求 ====================
+        // require_real_name: 学生加入时须填写班级内姓名
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .add_column(
+                        ColumnDef::new(Classes::RequireRealName)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // profile_name_pattern: 班级内姓名须匹配的正则（如学号+姓名），为空表示不限制格式
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .add_column(
+                        ColumnDef::new(Classes::ProfileNamePattern)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 班级成员增加班级内姓名 ====================
+        // profile_name: 班级内展示的姓名，优先于用户的全局显示名
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClassUsers::Table)
+                    .add_column(
+                        ColumnDef::new(ClassUsers::ProfileName)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClassUsers::Table)
+                    .drop_column(ClassUsers::ProfileName)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .drop_column(Classes::ProfileNamePattern)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Classes::Table)
+                    .drop_column(Classes::RequireRealName)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    RequireRealName,
+    ProfileNamePattern,
+}
+
+#[derive(DeriveIden)]
+enum ClassUsers {
+    #[sea_orm(iden = "class_users")]
+    Table,
+    ProfileName,
+}
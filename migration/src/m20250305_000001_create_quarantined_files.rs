@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 隔离文件表 ====================
+        // 未通过内容校验的上传文件单独存放，等待管理员审核；通过后转为正式文件记录，删除后连同文件一并清除
+        manager
+            .create_table(
+                Table::create()
+                    .table(QuarantinedFiles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::OriginalName)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::StoredName)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::FileType)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::FileSize)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::Reason)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(QuarantinedFiles::Detail).text().null())
+                    .col(ColumnDef::new(QuarantinedFiles::OrgId).big_integer().null())
+                    .col(
+                        ColumnDef::new(QuarantinedFiles::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(QuarantinedFiles::Table, QuarantinedFiles::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_quarantined_files_created_at")
+                    .table(QuarantinedFiles::Table)
+                    .col(QuarantinedFiles::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QuarantinedFiles::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum QuarantinedFiles {
+    #[sea_orm(iden = "quarantined_files")]
+    Table,
+    Id,
+    UserId,
+    OriginalName,
+    StoredName,
+    FileType,
+    FileSize,
+    Reason,
+    Detail,
+    OrgId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
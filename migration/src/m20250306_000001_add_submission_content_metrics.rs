@@ -0,0 +1,135 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 提交增加正文统计 ====================
+        // word_count / char_count: 提交时计算的词数与字符数（不含空白）
+        // attachment_count: 提交关联的附件数
+        for column in [
+            Submissions::WordCount,
+            Submissions::CharCount,
+            Submissions::AttachmentCount,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Submissions::Table)
+                        .add_column(ColumnDef::new(column).integer().not_null().default(0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 回填既有提交的附件数
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "UPDATE submissions SET attachment_count = \
+             (SELECT COUNT(*) FROM submission_files WHERE submission_files.submission_id = submissions.id)",
+        )
+        .await?;
+
+        // 回填既有提交的字数（统计规则与写入时一致）
+        let rows = db
+            .query_all_raw(
+                manager.get_database_backend().build(
+                    Query::select()
+                        .columns([Submissions::Id, Submissions::Content])
+                        .from(Submissions::Table)
+                        .and_where(Expr::col(Submissions::Content).is_not_null())
+                        .to_owned(),
+                ),
+            )
+            .await?;
+        for row in rows {
+            let id: i64 = row.try_get("", "id")?;
+            let content: String = row.try_get("", "content")?;
+            let (word_count, char_count) = content_metrics(&content);
+            if char_count == 0 {
+                continue;
+            }
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(Submissions::Table)
+                        .value(Submissions::WordCount, word_count)
+                        .value(Submissions::CharCount, char_count)
+                        .and_where(Expr::col(Submissions::Id).eq(id))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Submissions::AttachmentCount,
+            Submissions::CharCount,
+            Submissions::WordCount,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Submissions::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// 词数与字符数，与 `SubmissionContentMetrics::from_content` 的规则相同
+///
+/// 迁移不依赖主程序代码，这里保留一份副本，之后主程序规则变化不影响本迁移。
+fn content_metrics(content: &str) -> (i32, i32) {
+    let mut word_count = 0;
+    let mut char_count = 0;
+    let mut in_word = false;
+    for c in content.chars() {
+        if c.is_whitespace() {
+            in_word = false;
+            continue;
+        }
+        char_count += 1;
+        let is_cjk = matches!(
+            c,
+            '\u{3040}'..='\u{30FF}'
+                | '\u{3400}'..='\u{4DBF}'
+                | '\u{4E00}'..='\u{9FFF}'
+                | '\u{AC00}'..='\u{D7AF}'
+                | '\u{F900}'..='\u{FAFF}'
+                | '\u{20000}'..='\u{2FA1F}'
+        );
+        if is_cjk {
+            word_count += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() || (in_word && matches!(c, '\'' | '’' | '-')) {
+            if !in_word {
+                word_count += 1;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+        }
+    }
+    (word_count, char_count)
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    Id,
+    Content,
+    WordCount,
+    CharCount,
+    AttachmentCount,
+}
@@ -0,0 +1,129 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 缺交零分策略表 ====================
+        // 每份作业至多一条；到达 cutoff_at 后定时任务为未提交的学生记零分，applied_at 记录执行时间
+        manager
+            .create_table(
+                Table::create()
+                    .table(MissingGradePolicies::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MissingGradePolicies::HomeworkId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MissingGradePolicies::CutoffAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MissingGradePolicies::AppliedAt).big_integer())
+                    .col(
+                        ColumnDef::new(MissingGradePolicies::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MissingGradePolicies::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MissingGradePolicies::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_missing_grade_policies_homework")
+                            .from(
+                                MissingGradePolicies::Table,
+                                MissingGradePolicies::HomeworkId,
+                            )
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 定时任务只扫描尚未执行的策略
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_missing_grade_policies_applied_cutoff")
+                    .table(MissingGradePolicies::Table)
+                    .col(MissingGradePolicies::AppliedAt)
+                    .col(MissingGradePolicies::CutoffAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 评分增加自动记分标记 ====================
+        // auto_assigned: 缺交零分策略自动记录的零分，教师修改分数后清除
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Grades::Table)
+                    .add_column(
+                        ColumnDef::new(Grades::AutoAssigned)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Grades::Table)
+                    .drop_column(Grades::AutoAssigned)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MissingGradePolicies::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum MissingGradePolicies {
+    #[sea_orm(iden = "missing_grade_policies")]
+    Table,
+    HomeworkId,
+    CutoffAt,
+    AppliedAt,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Grades {
+    #[sea_orm(iden = "grades")]
+    Table,
+    AutoAssigned,
+}
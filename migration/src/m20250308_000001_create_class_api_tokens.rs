@@ -0,0 +1,211 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级 API 令牌表 ====================
+        // 只保存令牌的 SHA-256 摘要；token_prefix 为令牌开头几位，便于教师辨认
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassApiTokens::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassApiTokens::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokens::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokens::Name)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokens::TokenHash)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokens::TokenPrefix)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokens::ReadOnly)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokens::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ClassApiTokens::ExpiresAt).big_integer())
+                    .col(ColumnDef::new(ClassApiTokens::LastUsedAt).big_integer())
+                    .col(ColumnDef::new(ClassApiTokens::RevokedAt).big_integer())
+                    .col(
+                        ColumnDef::new(ClassApiTokens::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_api_tokens_class")
+                            .from(ClassApiTokens::Table, ClassApiTokens::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_api_tokens_created_by")
+                            .from(ClassApiTokens::Table, ClassApiTokens::CreatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_class_api_tokens_class_id")
+                    .table(ClassApiTokens::Table)
+                    .col(ClassApiTokens::ClassId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 班级 API 令牌调用记录表 ====================
+        // 记录令牌发起的写请求，便于追溯自动化脚本的操作
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassApiTokenLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassApiTokenLogs::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokenLogs::TokenId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokenLogs::Method)
+                            .string_len(10)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokenLogs::Path)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassApiTokenLogs::Status)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ClassApiTokenLogs::IpAddress).string_len(64))
+                    .col(
+                        ColumnDef::new(ClassApiTokenLogs::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_api_token_logs_token")
+                            .from(ClassApiTokenLogs::Table, ClassApiTokenLogs::TokenId)
+                            .to(ClassApiTokens::Table, ClassApiTokens::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_class_api_token_logs_token_created")
+                    .table(ClassApiTokenLogs::Table)
+                    .col(ClassApiTokenLogs::TokenId)
+                    .col(ClassApiTokenLogs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClassApiTokenLogs::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ClassApiTokens::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClassApiTokens {
+    #[sea_orm(iden = "class_api_tokens")]
+    Table,
+    Id,
+    ClassId,
+    Name,
+    TokenHash,
+    TokenPrefix,
+    ReadOnly,
+    CreatedBy,
+    ExpiresAt,
+    LastUsedAt,
+    RevokedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ClassApiTokenLogs {
+    #[sea_orm(iden = "class_api_token_logs")]
+    Table,
+    Id,
+    TokenId,
+    Method,
+    Path,
+    Status,
+    IpAddress,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
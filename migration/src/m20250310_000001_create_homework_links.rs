@@ -0,0 +1,210 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业外部资源表 ====================
+        // 讲解视频、在线文档等外部链接，与上传的附件并列展示
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkLinks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkLinks::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinks::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    // video: 讲解视频；document: 外部文档；other: 其他链接
+                    .col(
+                        ColumnDef::new(HomeworkLinks::LinkType)
+                            .string_len(16)
+                            .not_null()
+                            .default("other"),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinks::Title)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinks::Url)
+                            .string_len(2048)
+                            .not_null(),
+                    )
+                    // 在作业内的排列顺序（从 1 开始）
+                    .col(ColumnDef::new(HomeworkLinks::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(HomeworkLinks::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinks::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinks::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_links_homework")
+                            .from(HomeworkLinks::Table, HomeworkLinks::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_links_homework_position")
+                    .table(HomeworkLinks::Table)
+                    .col(HomeworkLinks::HomeworkId)
+                    .col(HomeworkLinks::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 外部资源打开记录表 ====================
+        // 每个学生每个链接一行，记录首次/最近打开时间与次数
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkLinkOpens::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkLinkOpens::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinkOpens::LinkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinkOpens::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinkOpens::OpenCount)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinkOpens::FirstOpenedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkLinkOpens::LastOpenedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_link_opens_link")
+                            .from(HomeworkLinkOpens::Table, HomeworkLinkOpens::LinkId)
+                            .to(HomeworkLinks::Table, HomeworkLinks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_link_opens_user")
+                            .from(HomeworkLinkOpens::Table, HomeworkLinkOpens::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 唯一约束：每个学生每个链接只保留一行
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_link_opens_unique")
+                    .table(HomeworkLinkOpens::Table)
+                    .col(HomeworkLinkOpens::LinkId)
+                    .col(HomeworkLinkOpens::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkLinkOpens::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(HomeworkLinks::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkLinks {
+    #[sea_orm(iden = "homework_links")]
+    Table,
+    Id,
+    HomeworkId,
+    LinkType,
+    Title,
+    Url,
+    Position,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum HomeworkLinkOpens {
+    #[sea_orm(iden = "homework_link_opens")]
+    Table,
+    Id,
+    LinkId,
+    UserId,
+    OpenCount,
+    FirstOpenedAt,
+    LastOpenedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
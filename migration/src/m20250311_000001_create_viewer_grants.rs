@@ -0,0 +1,126 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 家长/导师查看授权表 ====================
+        // 学生生成邀请后 viewer_id 为空，家长/导师接受邀请后写入 viewer_id
+        manager
+            .create_table(
+                Table::create()
+                    .table(ViewerGrants::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ViewerGrants::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ViewerGrants::StudentId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ViewerGrants::ViewerId).big_integer().null())
+                    .col(
+                        ColumnDef::new(ViewerGrants::Token)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    // 学生填写的备注（如“妈妈”“导师王老师”）
+                    .col(ColumnDef::new(ViewerGrants::Note).string_len(100).null())
+                    // 邀请过期时间，接受后不再生效
+                    .col(
+                        ColumnDef::new(ViewerGrants::ExpiresAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ViewerGrants::AcceptedAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(ViewerGrants::RevokedAt).big_integer().null())
+                    .col(
+                        ColumnDef::new(ViewerGrants::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_viewer_grants_student")
+                            .from(ViewerGrants::Table, ViewerGrants::StudentId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_viewer_grants_viewer")
+                            .from(ViewerGrants::Table, ViewerGrants::ViewerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_viewer_grants_student_id")
+                    .table(ViewerGrants::Table)
+                    .col(ViewerGrants::StudentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_viewer_grants_viewer_student")
+                    .table(ViewerGrants::Table)
+                    .col(ViewerGrants::ViewerId)
+                    .col(ViewerGrants::StudentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ViewerGrants::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ViewerGrants {
+    #[sea_orm(iden = "viewer_grants")]
+    Table,
+    Id,
+    StudentId,
+    ViewerId,
+    Token,
+    Note,
+    ExpiresAt,
+    AcceptedAt,
+    RevokedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,160 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业增加预计用时与难度 ====================
+        // estimated_minutes: 教师预计的完成用时（分钟），为空表示未设置
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .add_column(ColumnDef::new(Homeworks::EstimatedMinutes).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // difficulty: 教师预设的难度（1-5），为空表示未设置
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .add_column(ColumnDef::new(Homeworks::Difficulty).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 作业用时与难度反馈表 ====================
+        // 学生在作业评分后填写实际用时与体感难度，每个学生每个作业一行，可修改
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkFeedback::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::HomeworkId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::ActualMinutes)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::PerceivedDifficulty)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkFeedback::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_feedback_homework")
+                            .from(HomeworkFeedback::Table, HomeworkFeedback::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_feedback_user")
+                            .from(HomeworkFeedback::Table, HomeworkFeedback::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 唯一约束：每个学生每个作业只保留一条反馈
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_homework_feedback_unique")
+                    .table(HomeworkFeedback::Table)
+                    .col(HomeworkFeedback::HomeworkId)
+                    .col(HomeworkFeedback::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkFeedback::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .drop_column(Homeworks::Difficulty)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Homeworks::Table)
+                    .drop_column(Homeworks::EstimatedMinutes)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkFeedback {
+    #[sea_orm(iden = "homework_feedback")]
+    Table,
+    Id,
+    HomeworkId,
+    UserId,
+    ActualMinutes,
+    PerceivedDifficulty,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+    EstimatedMinutes,
+    Difficulty,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
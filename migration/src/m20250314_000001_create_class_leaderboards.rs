@@ -0,0 +1,194 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级排行榜设置表 ====================
+        // 每个班级一行，未配置时视为未启用
+        // metric: average_percent（平均得分率）/ total_score（总分）
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassLeaderboardSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::ClassId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::TopN)
+                            .integer()
+                            .not_null()
+                            .default(10),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::Metric)
+                            .string_len(16)
+                            .not_null()
+                            .default("average_percent"),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::UpdatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardSettings::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_leaderboard_settings_class")
+                            .from(
+                                ClassLeaderboardSettings::Table,
+                                ClassLeaderboardSettings::ClassId,
+                            )
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 排行榜署名选择表 ====================
+        // 学生选择在排行榜中显示姓名时写入一行，未选择的学生匿名显示
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassLeaderboardOptIns::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassLeaderboardOptIns::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardOptIns::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardOptIns::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassLeaderboardOptIns::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_leaderboard_opt_ins_class")
+                            .from(
+                                ClassLeaderboardOptIns::Table,
+                                ClassLeaderboardOptIns::ClassId,
+                            )
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_leaderboard_opt_ins_user")
+                            .from(
+                                ClassLeaderboardOptIns::Table,
+                                ClassLeaderboardOptIns::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 唯一约束：每个学生在每个班级只保留一条
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_class_leaderboard_opt_ins_unique")
+                    .table(ClassLeaderboardOptIns::Table)
+                    .col(ClassLeaderboardOptIns::ClassId)
+                    .col(ClassLeaderboardOptIns::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassLeaderboardOptIns::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassLeaderboardSettings::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClassLeaderboardSettings {
+    #[sea_orm(iden = "class_leaderboard_settings")]
+    Table,
+    ClassId,
+    Enabled,
+    TopN,
+    Metric,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ClassLeaderboardOptIns {
+    #[sea_orm(iden = "class_leaderboard_opt_ins")]
+    Table,
+    Id,
+    ClassId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,127 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 提交外部引用表 ====================
+        // kind: git（仓库地址 + 提交哈希）/ document（在线文档链接）
+        // commit_message、committed_at: 提交时从代码托管平台接口抓取的快照，抓取失败时为空
+        manager
+            .create_table(
+                Table::create()
+                    .table(SubmissionReferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SubmissionReferences::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::SubmissionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::Url)
+                            .string_len(2048)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::CommitHash)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::CommitMessage)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::CommittedAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::MetadataFetchedAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionReferences::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_submission_references_submission")
+                            .from(
+                                SubmissionReferences::Table,
+                                SubmissionReferences::SubmissionId,
+                            )
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_submission_references_submission")
+                    .table(SubmissionReferences::Table)
+                    .col(SubmissionReferences::SubmissionId)
+                    .col(SubmissionReferences::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SubmissionReferences::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SubmissionReferences {
+    #[sea_orm(iden = "submission_references")]
+    Table,
+    Id,
+    SubmissionId,
+    Position,
+    Kind,
+    Url,
+    CommitHash,
+    CommitMessage,
+    CommittedAt,
+    MetadataFetchedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Submissions {
+    #[sea_orm(iden = "submissions")]
+    Table,
+    Id,
+}
@@ -0,0 +1,184 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 内容举报表 ====================
+        // content_type: grade_comment（content_id 为评分 ID）/ announcement（班级动态 ID）/ message（私信 ID）
+        // status: pending / resolved；resolution: dismiss / delete_content / warn_user
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentReports::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentReports::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ReporterId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::AuthorId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ContentType)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ContentId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::Reason)
+                            .string_len(500)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::Excerpt)
+                            .string_len(500)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::Resolution)
+                            .string_len(16)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ResolutionNote)
+                            .string_len(500)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ResolvedBy)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ResolvedAt)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_content_reports_class")
+                            .from(ContentReports::Table, ContentReports::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_content_reports_reporter")
+                            .from(ContentReports::Table, ContentReports::ReporterId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_content_reports_author")
+                            .from(ContentReports::Table, ContentReports::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 教师按班级查看待处理举报
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_content_reports_class_status")
+                    .table(ContentReports::Table)
+                    .col(ContentReports::ClassId)
+                    .col(ContentReports::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 处理时查找同一内容的其他举报
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_content_reports_content")
+                    .table(ContentReports::Table)
+                    .col(ContentReports::ContentType)
+                    .col(ContentReports::ContentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentReports::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ContentReports {
+    #[sea_orm(iden = "content_reports")]
+    Table,
+    Id,
+    ClassId,
+    ReporterId,
+    AuthorId,
+    ContentType,
+    ContentId,
+    Reason,
+    Excerpt,
+    Status,
+    Resolution,
+    ResolutionNote,
+    ResolvedBy,
+    ResolvedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
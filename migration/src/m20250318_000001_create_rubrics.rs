@@ -0,0 +1,283 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 评分量规库 ====================
+        // criteria 为当前版本评分维度的 JSON 数组，每个版本另存于 rubric_versions
+        // org_id 取创建者的组织；org_visible 为真时同组织教师可查看与导入
+        manager
+            .create_table(
+                Table::create()
+                    .table(Rubrics::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Rubrics::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Rubrics::OwnerId).big_integer().not_null())
+                    .col(ColumnDef::new(Rubrics::OrgId).big_integer().null())
+                    .col(ColumnDef::new(Rubrics::Title).string_len(200).not_null())
+                    .col(ColumnDef::new(Rubrics::Description).text().null())
+                    .col(ColumnDef::new(Rubrics::Criteria).text().not_null())
+                    .col(
+                        ColumnDef::new(Rubrics::Version)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(Rubrics::OrgVisible)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Rubrics::ShareCode)
+                            .string_len(16)
+                            .null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Rubrics::SourceRubricId).big_integer().null())
+                    .col(ColumnDef::new(Rubrics::CreatedAt).big_integer().not_null())
+                    .col(ColumnDef::new(Rubrics::UpdatedAt).big_integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rubrics_owner")
+                            .from(Rubrics::Table, Rubrics::OwnerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rubrics_source")
+                            .from(Rubrics::Table, Rubrics::SourceRubricId)
+                            .to(Rubrics::Table, Rubrics::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_rubrics_owner")
+                    .table(Rubrics::Table)
+                    .col(Rubrics::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 查询同组织公开的量规
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_rubrics_org_visible")
+                    .table(Rubrics::Table)
+                    .col(Rubrics::OrgId)
+                    .col(Rubrics::OrgVisible)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 量规版本表 ====================
+        // 每次修改评分维度新增一行，版本号在量规内从 1 递增
+        manager
+            .create_table(
+                Table::create()
+                    .table(RubricVersions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RubricVersions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RubricVersions::RubricId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RubricVersions::Version).integer().not_null())
+                    .col(ColumnDef::new(RubricVersions::Criteria).text().not_null())
+                    .col(
+                        ColumnDef::new(RubricVersions::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RubricVersions::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rubric_versions_rubric")
+                            .from(RubricVersions::Table, RubricVersions::RubricId)
+                            .to(Rubrics::Table, Rubrics::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_rubric_versions_unique")
+                    .table(RubricVersions::Table)
+                    .col(RubricVersions::RubricId)
+                    .col(RubricVersions::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 作业量规快照表 ====================
+        // 每个作业至多一份；设置时复制量规内容，量规库后续修改不影响已设置的作业
+        // 来源量规删除后 rubric_id 置空，快照保留
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkRubrics::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::HomeworkId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::RubricId)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::RubricVersion)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::Title)
+                            .string_len(200)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HomeworkRubrics::Criteria).text().not_null())
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::AttachedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkRubrics::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_rubrics_homework")
+                            .from(HomeworkRubrics::Table, HomeworkRubrics::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_rubrics_rubric")
+                            .from(HomeworkRubrics::Table, HomeworkRubrics::RubricId)
+                            .to(Rubrics::Table, Rubrics::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkRubrics::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RubricVersions::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Rubrics::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Rubrics {
+    #[sea_orm(iden = "rubrics")]
+    Table,
+    Id,
+    OwnerId,
+    OrgId,
+    Title,
+    Description,
+    Criteria,
+    Version,
+    OrgVisible,
+    ShareCode,
+    SourceRubricId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RubricVersions {
+    #[sea_orm(iden = "rubric_versions")]
+    Table,
+    Id,
+    RubricId,
+    Version,
+    Criteria,
+    CreatedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum HomeworkRubrics {
+    #[sea_orm(iden = "homework_rubrics")]
+    Table,
+    HomeworkId,
+    RubricId,
+    RubricVersion,
+    Title,
+    Criteria,
+    AttachedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
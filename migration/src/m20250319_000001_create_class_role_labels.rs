@@ -0,0 +1,103 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 班级成员增加角色名称 ====================
+        // role_label: 班级自定义角色名称标识，为空时按角色显示默认名称
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClassUsers::Table)
+                    .add_column(ColumnDef::new(ClassUsers::RoleLabel).string_len(32).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // ==================== 班级角色名称配置表 ====================
+        // 未配置的班级使用内置名称（学生 / 课代表 / 教师）
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClassRoleLabels::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClassRoleLabels::ClassId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    // JSON 数组：角色名称 [{ key, label, role }]
+                    .col(ColumnDef::new(ClassRoleLabels::Labels).text().not_null())
+                    .col(
+                        ColumnDef::new(ClassRoleLabels::UpdatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRoleLabels::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ClassRoleLabels::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_class_role_labels_class")
+                            .from(ClassRoleLabels::Table, ClassRoleLabels::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClassRoleLabels::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClassUsers::Table)
+                    .drop_column(ClassUsers::RoleLabel)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClassUsers {
+    #[sea_orm(iden = "class_users")]
+    Table,
+    RoleLabel,
+}
+
+#[derive(DeriveIden)]
+enum ClassRoleLabels {
+    #[sea_orm(iden = "class_role_labels")]
+    Table,
+    ClassId,
+    Labels,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
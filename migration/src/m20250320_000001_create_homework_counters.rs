@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 作业统计计数表 ====================
+        // 提交与评分写入时在同一事务中增量维护；没有记录的作业在读取时全量计算后补写
+        manager
+            .create_table(
+                Table::create()
+                    .table(HomeworkCounters::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HomeworkCounters::HomeworkId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    // 已提交人数（最新提交被退回的学生不计）
+                    .col(
+                        ColumnDef::new(HomeworkCounters::SubmittedCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    // 已评分人数（已提交且任一版本有评分）
+                    .col(
+                        ColumnDef::new(HomeworkCounters::GradedCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(HomeworkCounters::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_homework_counters_homework")
+                            .from(HomeworkCounters::Table, HomeworkCounters::HomeworkId)
+                            .to(Homeworks::Table, Homeworks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HomeworkCounters::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HomeworkCounters {
+    #[sea_orm(iden = "homework_counters")]
+    Table,
+    HomeworkId,
+    SubmittedCount,
+    GradedCount,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Homeworks {
+    #[sea_orm(iden = "homeworks")]
+    Table,
+    Id,
+}
@@ -0,0 +1,158 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ==================== 定时导出表 ====================
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScheduledExports::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ScheduledExports::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledExports::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    // 导出内容：class_report
+                    .col(
+                        ColumnDef::new(ScheduledExports::Kind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    // 执行频率：daily / weekly
+                    .col(
+                        ColumnDef::new(ScheduledExports::Frequency)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    // 每周执行的星期（0 = 周一），仅 weekly 使用
+                    .col(ColumnDef::new(ScheduledExports::Weekday).integer())
+                    // 执行的整点（UTC）
+                    .col(ColumnDef::new(ScheduledExports::Hour).integer().not_null())
+                    // 投递方式：email / webhook
+                    .col(
+                        ColumnDef::new(ScheduledExports::Delivery)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    // 邮箱地址或 Webhook 地址
+                    .col(ColumnDef::new(ScheduledExports::Target).text().not_null())
+                    .col(
+                        ColumnDef::new(ScheduledExports::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledExports::NextRunAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ScheduledExports::LastRunAt).big_integer())
+                    .col(ColumnDef::new(ScheduledExports::LastError).text())
+                    // 连续失败次数，达到上限后自动停用
+                    .col(
+                        ColumnDef::new(ScheduledExports::ConsecutiveFailures)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledExports::CreatedBy)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledExports::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledExports::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_scheduled_exports_class")
+                            .from(ScheduledExports::Table, ScheduledExports::ClassId)
+                            .to(Classes::Table, Classes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_scheduled_exports_class_id")
+                    .table(ScheduledExports::Table)
+                    .col(ScheduledExports::ClassId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_scheduled_exports_enabled_next_run")
+                    .table(ScheduledExports::Table)
+                    .col(ScheduledExports::Enabled)
+                    .col(ScheduledExports::NextRunAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ScheduledExports::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ScheduledExports {
+    #[sea_orm(iden = "scheduled_exports")]
+    Table,
+    Id,
+    ClassId,
+    Kind,
+    Frequency,
+    Weekday,
+    Hour,
+    Delivery,
+    Target,
+    Enabled,
+    NextRunAt,
+    LastRunAt,
+    LastError,
+    ConsecutiveFailures,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Classes {
+    #[sea_orm(iden = "classes")]
+    Table,
+    Id,
+}
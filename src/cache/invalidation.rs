@@ -0,0 +1,184 @@
+//! 基于 PostgreSQL LISTEN/NOTIFY 的跨节点缓存失效
+//!
+//! 多个节点共用同一 PostgreSQL 时，认证用户缓存、管理权限缓存、组织状态缓存和动态配置
+//! 都保存在各节点本地。存储层在相关写操作成功后通过 `pg_notify` 发布 [`CacheInvalidation`]，
+//! 每个节点（包括发布者自身）的监听任务收到后清除对应的本地缓存。
+//!
+//! 仅在 `cache.pg_notify.enabled = true` 且数据库为 PostgreSQL 时启用。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sea_orm::sqlx::postgres::PgListener;
+use tracing::{error, info, warn};
+
+use crate::cache::ObjectCache;
+use crate::config::AppConfig;
+use crate::middlewares::{RequireJWT, RequirePermission, TenantGuard};
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+
+/// 监听连接断开后的重连间隔
+const RELISTEN_DELAY: Duration = Duration::from_secs(5);
+
+/// 缓存失效消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheInvalidation {
+    /// 用户信息变更或删除
+    User(i64),
+    /// 用户管理权限变更
+    Permissions(i64),
+    /// 组织变更或删除
+    Organization(i64),
+    /// 系统设置变更
+    Settings,
+}
+
+impl CacheInvalidation {
+    /// 编码为 NOTIFY 负载，如 `user:42`、`settings`
+    pub fn to_payload(&self) -> String {
+        match self {
+            Self::User(id) => format!("user:{id}"),
+            Self::Permissions(id) => format!("permissions:{id}"),
+            Self::Organization(id) => format!("organization:{id}"),
+            Self::Settings => "settings".to_string(),
+        }
+    }
+
+    /// 解析 NOTIFY 负载，无法识别时返回 None
+    pub fn parse(payload: &str) -> Option<Self> {
+        if payload == "settings" {
+            return Some(Self::Settings);
+        }
+        let (kind, id) = payload.split_once(':')?;
+        let id = id.parse().ok()?;
+        match kind {
+            "user" => Some(Self::User(id)),
+            "permissions" => Some(Self::Permissions(id)),
+            "organization" => Some(Self::Organization(id)),
+            _ => None,
+        }
+    }
+}
+
+/// 数据库 URL 是否指向 PostgreSQL
+fn is_postgres_url(url: &str) -> bool {
+    url.starts_with("postgres://") || url.starts_with("postgresql://")
+}
+
+/// 清除一条失效消息对应的本地缓存
+async fn apply(event: CacheInvalidation, storage: &Arc<dyn Storage>, cache: &Arc<dyn ObjectCache>) {
+    match event {
+        CacheInvalidation::User(user_id) => {
+            cache.remove(&RequireJWT::user_cache_key(user_id)).await;
+        }
+        CacheInvalidation::Permissions(user_id) => RequirePermission::invalidate(user_id).await,
+        CacheInvalidation::Organization(org_id) => TenantGuard::invalidate(org_id).await,
+        CacheInvalidation::Settings => reload_settings(storage).await,
+    }
+}
+
+/// 从数据库重新加载动态配置
+async fn reload_settings(storage: &Arc<dyn Storage>) {
+    match storage.list_all_settings().await {
+        Ok(settings) => {
+            for setting in settings {
+                DynamicConfig::update(&setting.key, &setting.value).await;
+            }
+        }
+        Err(e) => error!("Failed to reload dynamic config after invalidation: {}", e),
+    }
+}
+
+/// 监听中断期间可能错过消息，清空全部相关本地缓存
+async fn invalidate_all(storage: &Arc<dyn Storage>, cache: &Arc<dyn ObjectCache>) {
+    cache.invalidate_all().await;
+    RequirePermission::invalidate_all();
+    TenantGuard::invalidate_all();
+    reload_settings(storage).await;
+}
+
+/// 启动 PostgreSQL 缓存失效监听任务
+///
+/// 未启用或数据库不是 PostgreSQL 时不做任何事。
+pub fn spawn_pg_invalidation_listener(storage: Arc<dyn Storage>, cache: Arc<dyn ObjectCache>) {
+    let config = AppConfig::get();
+    let pg_notify = &config.cache.pg_notify;
+    if !pg_notify.enabled {
+        return;
+    }
+    if !is_postgres_url(&config.database.url) {
+        warn!("cache.pg_notify is enabled but the database is not PostgreSQL, ignoring");
+        return;
+    }
+
+    let url = config.database.url.clone();
+    let channel = pg_notify.channel.clone();
+
+    tokio::spawn(async move {
+        loop {
+            match PgListener::connect(&url).await {
+                Ok(mut listener) => {
+                    if let Err(e) = listener.listen(&channel).await {
+                        error!("Failed to LISTEN on channel '{}': {}", channel, e);
+                    } else {
+                        info!("Listening for cache invalidation on channel '{}'", channel);
+                        loop {
+                            match listener.try_recv().await {
+                                Ok(Some(notification)) => {
+                                    match CacheInvalidation::parse(notification.payload()) {
+                                        Some(event) => apply(event, &storage, &cache).await,
+                                        None => warn!(
+                                            "Ignoring unknown cache invalidation payload: {}",
+                                            notification.payload()
+                                        ),
+                                    }
+                                }
+                                // 连接已断开，下次 try_recv 时自动重连并重新 LISTEN
+                                Ok(None) => {
+                                    warn!(
+                                        "Cache invalidation listener connection lost, reconnecting"
+                                    );
+                                    invalidate_all(&storage, &cache).await;
+                                }
+                                Err(e) => {
+                                    error!("Cache invalidation listener failed: {}", e);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to connect cache invalidation listener: {}", e);
+                }
+            }
+
+            warn!(
+                "Cache invalidation listener stopped, retrying in {}s",
+                RELISTEN_DELAY.as_secs()
+            );
+            invalidate_all(&storage, &cache).await;
+            tokio::time::sleep(RELISTEN_DELAY).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_round_trip() {
+        for event in [
+            CacheInvalidation::User(42),
+            CacheInvalidation::Permissions(7),
+            CacheInvalidation::Organization(3),
+            CacheInvalidation::Settings,
+        ] {
+            assert_eq!(CacheInvalidation::parse(&event.to_payload()), Some(event));
+        }
+        assert_eq!(CacheInvalidation::parse("user:abc"), None);
+        assert_eq!(CacheInvalidation::parse("class:1"), None);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod invalidation;
 pub mod macros;
 pub mod object_cache;
 pub mod register;
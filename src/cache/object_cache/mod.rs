@@ -1,2 +1,3 @@
 pub mod moka;
 pub mod redis;
+pub mod tiered;
@@ -0,0 +1,165 @@
+//! 两级缓存（Moka + Redis）
+//!
+//! 本地 Moka 以较短的 TTL 缓存热点数据，Redis 作为各节点共享的二级缓存。
+//! 写入/删除采用写穿透：先写 Redis，再更新本地，并通过 Redis pub/sub 广播失效消息，
+//! 其他节点收到后丢弃本地副本，下次读取时从 Redis 重新加载。
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use moka::future::Cache;
+use redis::AsyncCommands;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use super::redis::RedisObjectCache;
+use crate::cache::{CacheResult, ObjectCache};
+use crate::config::AppConfig;
+use crate::declare_object_cache_plugin;
+
+declare_object_cache_plugin!("tiered", TieredObjectCache);
+
+/// 失效消息中表示"清空全部"的键
+const INVALIDATE_ALL_KEY: &str = "*";
+/// 订阅断开后的重连间隔
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+pub struct TieredObjectCache {
+    local: Cache<String, String>,
+    remote: RedisObjectCache,
+    client: redis::Client,
+    channel: String,
+    node_id: String,
+}
+
+impl TieredObjectCache {
+    pub fn new() -> Result<Self, String> {
+        let config = AppConfig::get();
+        let tiered = &config.cache.tiered;
+
+        let remote = RedisObjectCache::new()?;
+        let client = redis::Client::open(config.cache.redis.url.clone())
+            .map_err(|e| format!("Failed to create Redis client: {e}"))?;
+
+        let local = Cache::builder()
+            .max_capacity(tiered.local_max_capacity)
+            .time_to_live(Duration::from_secs(tiered.local_ttl))
+            .build();
+
+        let cache = Self {
+            local,
+            remote,
+            client,
+            channel: format!("{}{}", config.cache.redis.key_prefix, tiered.channel),
+            node_id: Uuid::new_v4().simple().to_string(),
+        };
+        cache.spawn_invalidation_listener();
+
+        debug!(
+            "TieredObjectCache initialized (local TTL: {}s, node: {})",
+            tiered.local_ttl, cache.node_id
+        );
+        Ok(cache)
+    }
+
+    /// 订阅失效频道，丢弃其他节点已修改的本地副本
+    fn spawn_invalidation_listener(&self) {
+        let local = self.local.clone();
+        let client = self.client.clone();
+        let channel = self.channel.clone();
+        let node_id = self.node_id.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match client.get_async_pubsub().await {
+                    Ok(mut pubsub) => {
+                        if let Err(e) = pubsub.subscribe(&channel).await {
+                            error!("Failed to subscribe cache channel '{}': {}", channel, e);
+                        } else {
+                            let mut messages = pubsub.on_message();
+                            while let Some(msg) = messages.next().await {
+                                let Ok(payload) = msg.get_payload::<String>() else {
+                                    continue;
+                                };
+                                let Some((sender, key)) = payload.split_once('|') else {
+                                    continue;
+                                };
+                                if sender == node_id {
+                                    continue;
+                                }
+                                if key == INVALIDATE_ALL_KEY {
+                                    local.invalidate_all();
+                                } else {
+                                    local.invalidate(key).await;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to open Redis pub/sub connection: {}", e);
+                    }
+                }
+
+                warn!(
+                    "Cache invalidation subscription lost, retrying in {}s",
+                    RESUBSCRIBE_DELAY.as_secs()
+                );
+                // 订阅中断期间可能错过失效消息，清空本地缓存以免读取旧数据
+                local.invalidate_all();
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        });
+    }
+
+    /// 广播失效消息
+    async fn publish_invalidation(&self, key: &str) {
+        let mut conn = match self.client.get_multiplexed_async_connection().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Failed to get Redis connection: {}", e);
+                return;
+            }
+        };
+
+        let payload = format!("{}|{}", self.node_id, key);
+        if let Err(e) = conn.publish::<_, _, ()>(&self.channel, payload).await {
+            error!("Failed to publish cache invalidation for '{}': {}", key, e);
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectCache for TieredObjectCache {
+    async fn get_raw(&self, key: &str) -> CacheResult<String> {
+        if let Some(value) = self.local.get(key).await {
+            debug!("Local cache hit: {}", key);
+            return CacheResult::Found(value);
+        }
+
+        let result = self.remote.get_raw(key).await;
+        if let CacheResult::Found(value) = &result {
+            self.local.insert(key.to_string(), value.clone()).await;
+        }
+        result
+    }
+
+    async fn insert_raw(&self, key: String, value: String, ttl: u64) {
+        self.remote
+            .insert_raw(key.clone(), value.clone(), ttl)
+            .await;
+        self.local.insert(key.clone(), value).await;
+        self.publish_invalidation(&key).await;
+    }
+
+    async fn remove(&self, key: &str) {
+        self.remote.remove(key).await;
+        self.local.invalidate(key).await;
+        self.publish_invalidation(key).await;
+    }
+
+    async fn invalidate_all(&self) {
+        self.remote.invalidate_all().await;
+        self.local.invalidate_all();
+        self.publish_invalidation(INVALIDATE_ALL_KEY).await;
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// 应用配置结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +13,34 @@ pub struct AppConfig {
     pub upload: UploadConfig,
     #[serde(default)]
     pub argon2: Argon2Config,
+    #[serde(default)]
+    pub pagination: PaginationConfig,
+    #[serde(default)]
+    pub im: ImConfig,
+    #[serde(default)]
+    pub captcha: CaptchaConfig,
+    #[serde(default)]
+    pub auth_cookie: AuthCookieConfig,
+    #[serde(default)]
+    pub frontend: FrontendConfig,
+    #[serde(default)]
+    pub job_queues: JobQueuesConfig,
+    #[serde(default)]
+    pub websocket: WebSocketConfig,
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+    #[serde(default)]
+    pub preview: PreviewConfig,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub tiering: TieringConfig,
+    #[serde(default)]
+    pub outbound: OutboundConfig,
+    #[serde(default)]
+    pub submission_references: SubmissionReferenceConfig,
+    #[serde(default)]
+    pub scheduled_exports: ScheduledExportConfig,
 }
 
 /// 应用设置
@@ -32,6 +61,10 @@ pub struct ServerConfig {
     pub max_workers: usize,
     pub timeouts: TimeoutConfig,
     pub limits: LimitConfig,
+    /// 可信反向代理（CIDR 或单个 IP，`unix` 表示 Unix 套接字对端），
+    /// 仅来自这些地址的请求才会采信 X-Forwarded-* 头
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
 }
 
 /// 超时配置
@@ -74,6 +107,10 @@ pub struct CacheConfig {
     pub default_ttl: u64,
     pub redis: RedisConfig,
     pub memory: MemoryConfig,
+    #[serde(default)]
+    pub tiered: TieredCacheConfig,
+    #[serde(default)]
+    pub pg_notify: PgNotifyConfig,
 }
 
 /// Redis 配置
@@ -90,6 +127,42 @@ pub struct MemoryConfig {
     pub max_capacity: u64,
 }
 
+/// 两级缓存配置（type = "tiered" 时生效）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TieredCacheConfig {
+    pub local_ttl: u64,          // 本地缓存 TTL (秒)，Redis 使用 default_ttl
+    pub local_max_capacity: u64, // 本地缓存容量
+    pub channel: String,         // 失效广播频道（自动加上 Redis 键前缀）
+}
+
+impl Default for TieredCacheConfig {
+    fn default() -> Self {
+        Self {
+            local_ttl: 30,
+            local_max_capacity: 10000,
+            channel: "cache:invalidate".to_string(),
+        }
+    }
+}
+
+/// PostgreSQL LISTEN/NOTIFY 缓存失效配置（仅 PostgreSQL 数据库生效）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PgNotifyConfig {
+    pub enabled: bool,   // 是否启用
+    pub channel: String, // LISTEN/NOTIFY 频道名
+}
+
+impl Default for PgNotifyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channel: "hwsystem_cache_invalidate".to_string(),
+        }
+    }
+}
+
 /// CORS 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorsConfig {
@@ -104,6 +177,8 @@ pub struct UploadConfig {
     pub dir: String,                // 上传目录
     pub max_size: usize,            // 单文件最大字节数
     pub allowed_types: Vec<String>, // 允许的MIME类型或扩展名
+    #[serde(default)]
+    pub max_request_size: usize, // 单次上传请求的最大字节数，0 表示按单文件上限自动计算
 }
 
 /// Argon2 密码哈希配置
@@ -123,3 +198,366 @@ impl Default for Argon2Config {
         }
     }
 }
+
+/// 分页配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaginationConfig {
+    pub default_page_size: i64, // 未指定 size 时的每页数量
+    pub max_page_size: i64,     // 每页数量上限，超出时返回 400
+    /// 按接口覆盖（键为接口名，如 users、homeworks、notifications）
+    pub overrides: HashMap<String, PaginationOverride>,
+}
+
+/// 单个接口的分页覆盖配置，未设置的项沿用全局值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaginationOverride {
+    pub default_page_size: Option<i64>,
+    pub max_page_size: Option<i64>,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 20,
+            max_page_size: 100,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+/// 班级 IM 机器人推送配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImConfig {
+    pub deadline_reminder_hours: i64, // 作业截止前多少小时发送提醒
+    pub max_attempts: i32,            // 单条消息最多投递次数（含首次）
+    pub request_timeout: u64,         // 调用机器人接口的超时 (秒)
+}
+
+impl Default for ImConfig {
+    fn default() -> Self {
+        Self {
+            deadline_reminder_hours: 24,
+            max_attempts: 5,
+            request_timeout: 10,
+        }
+    }
+}
+
+/// 人机验证配置（服务商与密钥在系统设置 `captcha.*` 中配置）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptchaConfig {
+    pub verify_timeout: u64,  // 调用服务商校验接口的超时 (秒)
+    pub bypass_token: String, // 服务间调用携带 X-Captcha-Bypass 头时免验证，为空表示禁用
+}
+
+impl Default for CaptchaConfig {
+    fn default() -> Self {
+        Self {
+            verify_timeout: 5,
+            bypass_token: String::new(),
+        }
+    }
+}
+
+/// Cookie 认证配置（浏览器客户端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthCookieConfig {
+    pub enabled: bool,     // 登录与刷新时通过 HttpOnly Cookie 下发访问令牌
+    pub secure: bool,      // Cookie 仅通过 HTTPS 发送（本地 HTTP 调试时可关闭）
+    pub same_site: String, // SameSite 策略：strict / lax
+    pub domain: String,    // Cookie 作用域名，为空表示当前主机
+}
+
+impl Default for AuthCookieConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            secure: true,
+            same_site: "strict".to_string(),
+            domain: String::new(),
+        }
+    }
+}
+
+/// 前端静态资源配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrontendConfig {
+    pub root_dir: String,   // 覆盖嵌入资源的前端目录，文件存在时优先使用
+    pub asset_max_age: u64, // 带内容哈希的静态资源缓存时间 (秒)
+}
+
+impl Default for FrontendConfig {
+    fn default() -> Self {
+        Self {
+            root_dir: "./frontend-custom".to_string(),
+            asset_max_age: 31_536_000,
+        }
+    }
+}
+
+/// 队列积压超过上限时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueOverflow {
+    /// 立即拒绝新任务
+    #[default]
+    Reject,
+    /// 等待队列腾出空位，超过 `max_wait` 仍未腾出时拒绝
+    Delay,
+}
+
+impl std::fmt::Display for QueueOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueueOverflow::Reject => write!(f, "reject"),
+            QueueOverflow::Delay => write!(f, "delay"),
+        }
+    }
+}
+
+/// 后台任务队列配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobQueuesConfig {
+    pub concurrency: usize,      // 每个队列同时执行的任务数
+    pub max_depth: usize,        // 每个队列的积压上限（排队 + 执行中）
+    pub overflow: QueueOverflow, // 积压超过上限时的处理方式
+    pub max_wait: u64,           // delay 模式下最长等待时间 (秒)
+    /// 按队列覆盖（键为队列名，如 exports、notifications、certificates）
+    pub queues: HashMap<String, JobQueueOverride>,
+}
+
+/// 单个队列的覆盖配置，未设置的项沿用全局值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobQueueOverride {
+    pub concurrency: Option<usize>,
+    pub max_depth: Option<usize>,
+    pub overflow: Option<QueueOverflow>,
+    pub max_wait: Option<u64>,
+}
+
+impl Default for JobQueuesConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            max_depth: 1000,
+            overflow: QueueOverflow::Reject,
+            max_wait: 10,
+            queues: HashMap::new(),
+        }
+    }
+}
+
+/// WebSocket 推送配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSocketConfig {
+    pub batch_window_ms: u64,         // 合并通知的时间窗口 (毫秒)，0 表示不合并
+    pub batch_max_size: usize,        // 单个批量帧最多包含的通知数
+    pub compression_threshold: usize, // 超过该字节数的消息才压缩
+    pub max_connections: usize,       // 全局并发连接上限，0 表示不限制
+    pub max_connections_per_user: usize, // 单个用户的并发连接上限，0 表示不限制
+    pub idle_timeout: u64,            // 客户端无上行消息的空闲超时 (秒)，0 表示不限制
+    pub metrics_token: String,        // 访问 /ws/metrics 的 Bearer 令牌，为空表示禁用
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            batch_window_ms: 50,
+            batch_max_size: 50,
+            compression_threshold: 1024,
+            max_connections: 10000,
+            max_connections_per_user: 5,
+            idle_timeout: 0,
+            metrics_token: String::new(),
+        }
+    }
+}
+
+/// 内容审核配置（处理方式、违禁词与外部接口在系统设置 `moderation.*` 中配置）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    pub api_timeout: u64, // 调用外部审核接口的超时 (秒)
+    pub fail_open: bool,  // 外部接口不可用时放行内容（否则按命中处理）
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            api_timeout: 5,
+            fail_open: true,
+        }
+    }
+}
+
+/// 外部文档预览服务类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewProvider {
+    /// 通用转换服务：`POST {url}?format=pdf|html`，multipart 字段 `file`，响应体为转换结果
+    #[default]
+    Generic,
+    /// Gotenberg：`POST {url}/forms/libreoffice/convert`，仅支持 PDF
+    Gotenberg,
+    /// Collabora Online：`POST {url}/cool/convert-to/{format}`
+    Collabora,
+}
+
+impl std::fmt::Display for PreviewProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreviewProvider::Generic => write!(f, "generic"),
+            PreviewProvider::Gotenberg => write!(f, "gotenberg"),
+            PreviewProvider::Collabora => write!(f, "collabora"),
+        }
+    }
+}
+
+/// 文档预览配置（Office 文档经外部服务转换为 PDF/HTML 后缓存）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewConfig {
+    pub enabled: bool,             // 是否启用文档转换预览
+    pub provider: PreviewProvider, // 转换服务类型
+    pub url: String,               // 转换服务地址
+    pub api_key: String,           // 调用转换服务时携带的 Bearer 令牌，为空表示不携带
+    pub timeout: u64,              // 调用转换服务的超时 (秒)
+    pub max_size: usize,           // 允许转换的源文件最大字节数
+    pub cache_dir: String,         // 转换结果缓存目录
+    pub code_max_size: usize,      // 在线查看代码文件的最大字节数
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: PreviewProvider::Generic,
+            url: String::new(),
+            api_key: String::new(),
+            timeout: 60,
+            max_size: 20 * 1024 * 1024,
+            cache_dir: "previews".to_string(),
+            code_max_size: 1024 * 1024,
+        }
+    }
+}
+
+/// 数据库在线备份配置（仅 SQLite）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub dir: String, // 备份文件目录
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            dir: "backups".to_string(),
+        }
+    }
+}
+
+/// 附件冷存储分层配置
+///
+/// 已归档班级的附件按系统设置 `tiering.cold_after_days` 迁移到冷存储目录
+/// （可挂载低速磁盘或对象存储），下载时透明读取。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TieringConfig {
+    pub enabled: bool,          // 是否启用冷存储迁移
+    pub cold_dir: String,       // 冷存储目录
+    pub hot_read_timeout: u64,  // 读取上传目录文件的超时 (秒)
+    pub cold_read_timeout: u64, // 读取冷存储文件的超时 (秒)
+}
+
+impl Default for TieringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cold_dir: "uploads-cold".to_string(),
+            hot_read_timeout: 10,
+            cold_read_timeout: 30,
+        }
+    }
+}
+
+/// 外部 HTTP 调用配置
+///
+/// 单次请求超时沿用各集成自己的配置节（`captcha.verify_timeout`、`moderation.api_timeout`、
+/// `preview.timeout`、`im.request_timeout`、`submission_references.api_timeout`、
+/// `scheduled_exports.request_timeout`）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutboundConfig {
+    pub failure_threshold: u32, // 连续失败多少次后熔断，0 表示不熔断
+    pub open_duration: u64,     // 熔断持续时间 (秒)，之后放行一次试探请求
+    pub retry_backoff_ms: u64,  // 首次重试前的等待时间 (毫秒)，之后逐次翻倍
+    /// 按目标（captcha / moderation / preview / im / git / export）覆盖重试次数
+    pub retries: HashMap<String, u32>,
+}
+
+impl Default for OutboundConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: 30,
+            retry_backoff_ms: 200,
+            retries: HashMap::new(),
+        }
+    }
+}
+
+/// 提交外部引用配置
+///
+/// 学生提交 GitHub / GitLab（gitlab.com）仓库引用时，可从平台接口抓取提交说明与提交时间作为快照。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubmissionReferenceConfig {
+    pub fetch_metadata: bool, // 提交时抓取 Git 提交元数据
+    pub api_timeout: u64,     // 调用代码托管平台接口的超时 (秒)
+    pub github_token: String, // 访问 GitHub 接口的令牌（私有仓库或提高限额），为空表示匿名访问
+    pub gitlab_token: String, // 访问 GitLab 接口的令牌，为空表示匿名访问
+}
+
+impl Default for SubmissionReferenceConfig {
+    fn default() -> Self {
+        Self {
+            fetch_metadata: false,
+            api_timeout: 5,
+            github_token: String::new(),
+            gitlab_token: String::new(),
+        }
+    }
+}
+
+/// 定时导出配置
+///
+/// 邮件投递通过 HTTP 邮件中继发送（POST JSON，附件为 base64），未配置中继时不能创建邮件投递的定时导出。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduledExportConfig {
+    pub request_timeout: u64,     // 投递到邮件中继或 Webhook 的超时 (秒)
+    pub max_failures: i32,        // 连续失败多少次后自动停用，0 表示不停用
+    pub mail_relay_url: String,   // 邮件中继地址，为空表示不支持邮件投递
+    pub mail_relay_token: String, // 调用邮件中继的 Bearer 令牌，为空表示不携带
+    pub mail_from: String,        // 发件人地址
+}
+
+impl Default for ScheduledExportConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: 30,
+            max_failures: 3,
+            mail_relay_url: String::new(),
+            mail_relay_token: String::new(),
+            mail_from: "no-reply@localhost".to_string(),
+        }
+    }
+}
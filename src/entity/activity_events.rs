@@ -0,0 +1,62 @@
+//! 班级动态实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "activity_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub event_type: String,
+    pub actor_id: Option<i64>,
+    pub reference_id: Option<i64>,
+    pub title: String,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::ActorId",
+        to = "super::users::Column::Id"
+    )]
+    Actor,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_activity_event(self) -> crate::models::classes::entities::ActivityEvent {
+        use crate::models::classes::entities::{ActivityEvent, ActivityEventType};
+        use chrono::{DateTime, Utc};
+
+        ActivityEvent {
+            id: self.id,
+            class_id: self.class_id,
+            event_type: self
+                .event_type
+                .parse()
+                .unwrap_or(ActivityEventType::Announcement),
+            actor_id: self.actor_id,
+            reference_id: self.reference_id,
+            title: self.title,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            reactions: Default::default(),
+        }
+    }
+}
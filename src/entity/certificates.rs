@@ -0,0 +1,65 @@
+//! 结业证书实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "certificates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub user_id: i64,
+    #[sea_orm(unique)]
+    pub verification_code: String,
+    pub average_percent: f64,
+    pub homework_count: i32,
+    pub issued_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_certificate(self) -> crate::models::certificates::entities::Certificate {
+        use crate::models::certificates::entities::Certificate;
+        use chrono::{DateTime, Utc};
+
+        Certificate {
+            id: self.id,
+            class_id: self.class_id,
+            user_id: self.user_id,
+            verification_code: self.verification_code,
+            average_percent: self.average_percent,
+            homework_count: self.homework_count,
+            issued_at: DateTime::<Utc>::from_timestamp(self.issued_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! 班级 API 令牌调用记录实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_api_token_logs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub token_id: i64,
+    pub method: String,
+    pub path: String,
+    pub status: i32,
+    pub ip_address: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::class_api_tokens::Entity",
+        from = "Column::TokenId",
+        to = "super::class_api_tokens::Column::Id"
+    )]
+    Token,
+}
+
+impl Related<super::class_api_tokens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Token.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_class_api_token_log(self) -> crate::models::classes::entities::ClassApiTokenLog {
+        use crate::models::classes::entities::ClassApiTokenLog;
+        use chrono::{DateTime, Utc};
+
+        ClassApiTokenLog {
+            id: self.id,
+            token_id: self.token_id,
+            method: self.method,
+            path: self.path,
+            status: self.status,
+            ip_address: self.ip_address,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
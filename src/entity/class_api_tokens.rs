@@ -0,0 +1,75 @@
+//! 班级 API 令牌实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_api_tokens")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub name: String,
+    // 令牌的 SHA-256 摘要（十六进制）
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub read_only: bool,
+    pub created_by: i64,
+    pub expires_at: Option<i64>,
+    pub last_used_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(has_many = "super::class_api_token_logs::Entity")]
+    Logs,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl Related<super::class_api_token_logs::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Logs.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_class_api_token(self) -> crate::models::classes::entities::ClassApiToken {
+        use crate::models::classes::entities::ClassApiToken;
+        use chrono::{DateTime, Utc};
+
+        ClassApiToken {
+            id: self.id,
+            class_id: self.class_id,
+            name: self.name,
+            token_prefix: self.token_prefix,
+            read_only: self.read_only,
+            created_by: self.created_by,
+            expires_at: self
+                .expires_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            last_used_at: self
+                .last_used_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            revoked_at: self
+                .revoked_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
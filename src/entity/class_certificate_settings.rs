@@ -0,0 +1,78 @@
+//! 班级结业证书设置实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_certificate_settings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub class_id: i64,
+    pub enabled: bool,
+    pub min_score_percent: f64,
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub body_template: String,
+    pub signer_name: Option<String>,
+    pub signature_file_id: Option<i64>,
+    pub updated_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(
+        belongs_to = "super::files::Entity",
+        from = "Column::SignatureFileId",
+        to = "super::files::Column::Id"
+    )]
+    SignatureFile,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl Related<super::files::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SignatureFile.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    /// 转换为业务模型，签名图片文件由调用方查询后传入
+    pub fn into_certificate_settings(
+        self,
+        file: Option<&super::files::Model>,
+    ) -> crate::models::certificates::entities::CertificateSettings {
+        use crate::models::certificates::entities::{CertificateSettings, CertificateSignature};
+        use chrono::{DateTime, Utc};
+
+        CertificateSettings {
+            class_id: self.class_id,
+            enabled: self.enabled,
+            min_score_percent: self.min_score_percent,
+            title: self.title,
+            body_template: self.body_template,
+            signer_name: self.signer_name,
+            signature_file_id: self.signature_file_id,
+            signature: file.map(|f| CertificateSignature {
+                download_token: f.download_token.clone(),
+                original_name: f.original_name.clone(),
+            }),
+            updated_by: self.updated_by,
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! 班级 IM 通知渠道实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_im_channels")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub name: String,
+    pub provider: String,
+    #[sea_orm(column_type = "Text")]
+    pub target: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub secret: Option<String>,
+    // 逗号分隔的事件列表
+    #[sea_orm(column_type = "Text")]
+    pub events: String,
+    // JSON 对象：事件 -> 模板
+    #[sea_orm(column_type = "Text", nullable)]
+    pub templates: Option<String>,
+    pub enabled: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<i64>,
+    pub created_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(has_many = "super::im_deliveries::Entity")]
+    ImDeliveries,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl Related<super::im_deliveries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ImDeliveries.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_im_channel(self) -> crate::models::classes::entities::ClassImChannel {
+        use crate::models::classes::entities::ClassImChannel;
+        use chrono::{DateTime, Utc};
+
+        ClassImChannel {
+            id: self.id,
+            class_id: self.class_id,
+            name: self.name,
+            provider: self
+                .provider
+                .parse()
+                .unwrap_or(crate::models::classes::entities::ImProvider::Wecom),
+            target: self.target,
+            secret: self.secret,
+            events: self
+                .events
+                .split(',')
+                .filter_map(|event| event.trim().parse().ok())
+                .collect(),
+            templates: self
+                .templates
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            enabled: self.enabled,
+            last_error: self.last_error,
+            last_delivered_at: self
+                .last_delivered_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_by: self.created_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! 班级排行榜设置实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_leaderboard_settings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub class_id: i64,
+    pub enabled: bool,
+    pub top_n: i32,
+    pub metric: String,
+    pub updated_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_leaderboard_settings(
+        self,
+    ) -> crate::models::classes::entities::ClassLeaderboardSettings {
+        use crate::models::classes::entities::{ClassLeaderboardSettings, LeaderboardMetric};
+        use chrono::{DateTime, Utc};
+
+        ClassLeaderboardSettings {
+            class_id: self.class_id,
+            enabled: self.enabled,
+            top_n: self.top_n,
+            metric: self
+                .metric
+                .parse()
+                .unwrap_or(LeaderboardMetric::AveragePercent),
+            updated_by: self.updated_by,
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
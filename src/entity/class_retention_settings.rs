@@ -0,0 +1,51 @@
+//! 班级文件保留策略实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_retention_settings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub class_id: i64,
+    pub submission_files_days: Option<i32>,
+    pub homework_files_days: Option<i32>,
+    pub updated_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_class_retention_override(
+        self,
+    ) -> crate::models::files::entities::ClassRetentionOverride {
+        use crate::models::files::entities::ClassRetentionOverride;
+        use chrono::{DateTime, Utc};
+
+        ClassRetentionOverride {
+            class_id: self.class_id,
+            submission_files_days: self.submission_files_days,
+            homework_files_days: self.homework_files_days,
+            updated_by: self.updated_by,
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! 班级角色名称配置实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_role_labels")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub class_id: i64,
+    // JSON 数组：角色名称
+    #[sea_orm(column_type = "Text")]
+    pub labels: String,
+    pub updated_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_class_role_labels(self) -> crate::models::class_users::entities::ClassRoleLabels {
+        use crate::models::class_users::entities::ClassRoleLabels;
+
+        ClassRoleLabels {
+            labels: serde_json::from_str(&self.labels).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! 班级每日统计快照实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_stats_daily")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub stat_date: String,
+    pub member_count: i64,
+    pub student_count: i64,
+    pub submission_count: i64,
+    pub graded_count: i64,
+    pub average_score_percent: Option<f64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_class_stats_daily(self) -> crate::models::classes::entities::ClassStatsDaily {
+        use crate::models::classes::entities::ClassStatsDaily;
+
+        ClassStatsDaily {
+            stat_date: self.stat_date,
+            member_count: self.member_count,
+            student_count: self.student_count,
+            submission_count: self.submission_count,
+            graded_count: self.graded_count,
+            average_score_percent: self.average_score_percent,
+        }
+    }
+}
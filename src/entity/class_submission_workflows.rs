@@ -0,0 +1,51 @@
+//! 班级提交状态工作流实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "class_submission_workflows")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub class_id: i64,
+    // JSON 数组：自定义状态
+    #[sea_orm(column_type = "Text")]
+    pub statuses: String,
+    // JSON 数组：允许的状态转换
+    #[sea_orm(column_type = "Text")]
+    pub transitions: String,
+    pub updated_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_submission_workflow(
+        self,
+    ) -> crate::models::submissions::entities::SubmissionWorkflow {
+        use crate::models::submissions::entities::SubmissionWorkflow;
+
+        SubmissionWorkflow {
+            statuses: serde_json::from_str(&self.statuses).unwrap_or_default(),
+            transitions: serde_json::from_str(&self.transitions).unwrap_or_default(),
+        }
+    }
+}
@@ -10,6 +10,8 @@ pub struct Model {
     pub class_id: i64,
     pub user_id: i64,
     pub role: String,
+    pub profile_name: Option<String>,
+    pub role_label: Option<String>,
     pub joined_at: i64,
 }
 
@@ -57,6 +59,8 @@ impl Model {
                 .role
                 .parse::<ClassUserRole>()
                 .unwrap_or(ClassUserRole::Student),
+            profile_name: self.profile_name,
+            role_label: self.role_label,
             joined_at: DateTime::<Utc>::from_timestamp(self.joined_at, 0).unwrap_or_default(),
         }
     }
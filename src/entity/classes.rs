@@ -13,6 +13,11 @@ pub struct Model {
     pub teacher_id: i64,
     #[sea_orm(unique)]
     pub invite_code: String,
+    pub org_id: Option<i64>,
+    pub archived_at: Option<i64>,
+    pub allow_student_messages: bool,
+    pub require_real_name: bool,
+    pub profile_name_pattern: Option<String>,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -63,6 +68,13 @@ impl Model {
             description: self.description,
             teacher_id: self.teacher_id,
             invite_code: self.invite_code,
+            org_id: self.org_id,
+            archived_at: self
+                .archived_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            allow_student_messages: self.allow_student_messages,
+            require_real_name: self.require_real_name,
+            profile_name_pattern: self.profile_name_pattern,
             created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
             updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
         }
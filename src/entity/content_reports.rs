@@ -0,0 +1,85 @@
+//! 内容举报实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "content_reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub reporter_id: i64,
+    pub author_id: i64,
+    pub content_type: String,
+    pub content_id: i64,
+    pub reason: String,
+    pub excerpt: String,
+    pub status: String,
+    pub resolution: Option<String>,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<i64>,
+    pub resolved_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::ReporterId",
+        to = "super::users::Column::Id"
+    )]
+    Reporter,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::AuthorId",
+        to = "super::users::Column::Id"
+    )]
+    Author,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_content_report(self) -> crate::models::moderation::entities::ContentReport {
+        use crate::models::moderation::entities::{
+            ContentReport, ContentReportStatus, ReportContentType,
+        };
+        use chrono::{DateTime, Utc};
+
+        ContentReport {
+            id: self.id,
+            class_id: self.class_id,
+            reporter_id: self.reporter_id,
+            author_id: self.author_id,
+            content_type: self
+                .content_type
+                .parse()
+                .unwrap_or(ReportContentType::GradeComment),
+            content_id: self.content_id,
+            reason: self.reason,
+            excerpt: self.excerpt,
+            status: self.status.parse().unwrap_or(ContentReportStatus::Pending),
+            resolution: self.resolution.and_then(|r| r.parse().ok()),
+            resolution_note: self.resolution_note,
+            resolved_by: self.resolved_by,
+            resolved_at: self
+                .resolved_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! 考试访问日志实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "exam_access_logs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub homework_id: i64,
+    pub user_id: i64,
+    pub event: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub submission_id: Option<i64>,
+    pub detail: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_exam_access_log(self) -> crate::models::homeworks::entities::ExamAccessLog {
+        use crate::models::homeworks::entities::{ExamAccessEvent, ExamAccessLog};
+        use chrono::{DateTime, Utc};
+
+        ExamAccessLog {
+            id: self.id,
+            homework_id: self.homework_id,
+            user_id: self.user_id,
+            event: self.event.parse().unwrap_or(ExamAccessEvent::View),
+            ip_address: self.ip_address,
+            user_agent: self.user_agent,
+            submission_id: self.submission_id,
+            detail: self.detail,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
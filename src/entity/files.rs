@@ -16,6 +16,10 @@ pub struct Model {
     #[sea_orm(unique)]
     pub download_token: String,
     pub citation_count: i32,
+    pub org_id: Option<i64>,
+    pub code_language: Option<String>,
+    pub storage_tier: String,
+    pub tiered_at: Option<i64>,
     pub created_at: i64,
 }
 
@@ -69,6 +73,12 @@ impl Model {
             file_path: self.file_path,
             download_token: self.download_token,
             citation_count: self.citation_count,
+            org_id: self.org_id,
+            code_language: self.code_language,
+            storage_tier: self.storage_tier.parse().unwrap_or_default(),
+            tiered_at: self
+                .tiered_at
+                .and_then(|t| DateTime::<Utc>::from_timestamp(t, 0)),
             created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
         }
     }
@@ -15,6 +15,8 @@ pub struct Model {
     pub comment: Option<String>,
     pub graded_at: i64,
     pub updated_at: i64,
+    /// 缺交零分策略自动记录的零分
+    pub auto_assigned: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -61,6 +63,10 @@ impl Model {
             comment: self.comment,
             graded_at: DateTime::<Utc>::from_timestamp(self.graded_at, 0).unwrap_or_default(),
             updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+            auto_assigned: self.auto_assigned,
+            mentions: Vec::new(),
+            class_context: None,
+            reactions: Default::default(),
         }
     }
 }
@@ -0,0 +1,64 @@
+//! 作业用时与难度反馈实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_feedback")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub homework_id: i64,
+    pub user_id: i64,
+    pub actual_minutes: i32,
+    pub perceived_difficulty: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_homework_feedback(self) -> crate::models::homeworks::entities::HomeworkFeedback {
+        use crate::models::homeworks::entities::HomeworkFeedback;
+        use chrono::{DateTime, Utc};
+
+        HomeworkFeedback {
+            id: self.id,
+            homework_id: self.homework_id,
+            user_id: self.user_id,
+            actual_minutes: self.actual_minutes,
+            perceived_difficulty: self.perceived_difficulty,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -9,6 +9,7 @@ pub struct Model {
     pub homework_id: i64,
     #[sea_orm(primary_key, auto_increment = false)]
     pub file_id: i64,
+    pub attachment_kind: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,63 @@
+//! 作业外部资源打开记录实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_link_opens")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub link_id: i64,
+    pub user_id: i64,
+    pub open_count: i32,
+    pub first_opened_at: i64,
+    pub last_opened_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homework_links::Entity",
+        from = "Column::LinkId",
+        to = "super::homework_links::Column::Id"
+    )]
+    Link,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::homework_links::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Link.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_homework_link_open(self) -> crate::models::homeworks::entities::HomeworkLinkOpen {
+        use crate::models::homeworks::entities::HomeworkLinkOpen;
+        use chrono::{DateTime, Utc};
+
+        HomeworkLinkOpen {
+            link_id: self.link_id,
+            user_id: self.user_id,
+            open_count: self.open_count,
+            first_opened_at: DateTime::<Utc>::from_timestamp(self.first_opened_at, 0)
+                .unwrap_or_default(),
+            last_opened_at: DateTime::<Utc>::from_timestamp(self.last_opened_at, 0)
+                .unwrap_or_default(),
+        }
+    }
+}
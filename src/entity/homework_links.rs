@@ -0,0 +1,63 @@
+//! 作业外部资源实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_links")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub homework_id: i64,
+    pub link_type: String,
+    pub title: String,
+    pub url: String,
+    pub position: i32,
+    pub created_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+    #[sea_orm(has_many = "super::homework_link_opens::Entity")]
+    Opens,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl Related<super::homework_link_opens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Opens.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_homework_link(self) -> crate::models::homeworks::entities::HomeworkLink {
+        use crate::models::homeworks::entities::HomeworkLink;
+        use chrono::{DateTime, Utc};
+
+        HomeworkLink {
+            id: self.id,
+            homework_id: self.homework_id,
+            link_type: self.link_type.parse().unwrap_or_default(),
+            title: self.title,
+            url: self.url,
+            position: self.position,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! 作业分题实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_parts")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub homework_id: i64,
+    pub position: i32,
+    pub title: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub max_score: f64,
+    pub deadline: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+    #[sea_orm(has_many = "super::submissions::Entity")]
+    Submissions,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl Related<super::submissions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Submissions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_homework_part(self) -> crate::models::homeworks::entities::HomeworkPart {
+        use crate::models::homeworks::entities::HomeworkPart;
+        use chrono::{DateTime, Utc};
+
+        HomeworkPart {
+            id: self.id,
+            homework_id: self.homework_id,
+            position: self.position,
+            title: self.title,
+            description: self.description,
+            max_score: self.max_score,
+            deadline: self
+                .deadline
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! 作业前置条件实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_prerequisites")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub homework_id: i64,
+    pub prerequisite_id: i64,
+    pub requirement: String,
+    pub min_score: Option<f64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::PrerequisiteId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Prerequisite,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_prerequisite(self) -> crate::models::homeworks::entities::HomeworkPrerequisite {
+        use crate::models::homeworks::entities::HomeworkPrerequisite;
+        use chrono::{DateTime, Utc};
+
+        HomeworkPrerequisite {
+            id: self.id,
+            homework_id: self.homework_id,
+            prerequisite_id: self.prerequisite_id,
+            requirement: self.requirement.parse().unwrap_or_default(),
+            min_score: self.min_score,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! 作业量规快照实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_rubrics")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub homework_id: i64,
+    pub rubric_id: Option<i64>,
+    pub rubric_version: i32,
+    pub title: String,
+    // JSON 数组：设置时复制的评分维度
+    #[sea_orm(column_type = "Text")]
+    pub criteria: String,
+    pub attached_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+    #[sea_orm(
+        belongs_to = "super::rubrics::Entity",
+        from = "Column::RubricId",
+        to = "super::rubrics::Column::Id"
+    )]
+    Rubric,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl Related<super::rubrics::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Rubric.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_homework_rubric(self) -> crate::models::rubrics::entities::HomeworkRubric {
+        use crate::models::rubrics::entities::{HomeworkRubric, RubricCriterion, total_points};
+        use chrono::{DateTime, Utc};
+
+        let criteria: Vec<RubricCriterion> =
+            serde_json::from_str(&self.criteria).unwrap_or_default();
+        HomeworkRubric {
+            homework_id: self.homework_id,
+            rubric_id: self.rubric_id,
+            rubric_version: self.rubric_version,
+            title: self.title,
+            total_points: total_points(&criteria),
+            criteria,
+            attached_by: self.attached_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! 作业参考答案实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "homework_solutions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub homework_id: i64,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub reveal_policy: String,
+    pub published_at: Option<i64>,
+    pub revealed_at: Option<i64>,
+    pub created_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_homework_solution(self) -> crate::models::homeworks::entities::HomeworkSolution {
+        use crate::models::homeworks::entities::HomeworkSolution;
+        use chrono::{DateTime, Utc};
+
+        HomeworkSolution {
+            homework_id: self.homework_id,
+            content: self.content,
+            reveal_policy: self.reveal_policy.parse().unwrap_or_default(),
+            published_at: self
+                .published_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            revealed_at: self
+                .revealed_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_by: self.created_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -14,6 +14,14 @@ pub struct Model {
     pub max_score: f64,
     pub deadline: Option<i64>,
     pub allow_late: bool,
+    pub submission_mode: String,
+    pub max_content_length: Option<i32>,
+    pub exam_mode: bool,
+    pub require_self_assessment: bool,
+    pub show_grade_context: bool,
+    pub estimated_minutes: Option<i32>,
+    pub difficulty: Option<i32>,
+    pub grace_minutes: Option<i32>,
     pub created_by: i64,
     pub created_at: i64,
     pub updated_at: i64,
@@ -68,7 +76,7 @@ impl ActiveModelBehavior for ActiveModel {}
 // 从数据库模型转换为业务模型
 impl Model {
     pub fn into_homework(self) -> crate::models::homeworks::entities::Homework {
-        use crate::models::homeworks::entities::Homework;
+        use crate::models::homeworks::entities::{Homework, SubmissionMode};
         use chrono::{DateTime, Utc};
 
         Homework {
@@ -81,6 +89,17 @@ impl Model {
                 .deadline
                 .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
             allow_late: self.allow_late,
+            submission_mode: self
+                .submission_mode
+                .parse::<SubmissionMode>()
+                .unwrap_or_default(),
+            max_content_length: self.max_content_length,
+            exam_mode: self.exam_mode,
+            require_self_assessment: self.require_self_assessment,
+            show_grade_context: self.show_grade_context,
+            estimated_minutes: self.estimated_minutes,
+            difficulty: self.difficulty,
+            grace_minutes: self.grace_minutes,
             created_by: self.created_by,
             created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
             updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
@@ -0,0 +1,54 @@
+//! IM 消息投递队列实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "im_deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub channel_id: i64,
+    pub event: String,
+    pub reference_id: i64,
+    #[sea_orm(column_type = "Text")]
+    pub message: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: i64,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub delivered_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::class_im_channels::Entity",
+        from = "Column::ChannelId",
+        to = "super::class_im_channels::Column::Id"
+    )]
+    Channel,
+}
+
+impl Related<super::class_im_channels::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Channel.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_im_delivery(self) -> crate::models::classes::entities::ImDelivery {
+        crate::models::classes::entities::ImDelivery {
+            id: self.id,
+            channel_id: self.channel_id,
+            event: self.event,
+            reference_id: self.reference_id,
+            message: self.message,
+            attempts: self.attempts,
+        }
+    }
+}
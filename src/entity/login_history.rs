@@ -0,0 +1,66 @@
+//! 登录历史实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "login_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub success: bool,
+    pub ip_address: Option<String>,
+    pub country: Option<String>,
+    pub user_agent: Option<String>,
+    pub browser: String,
+    pub os: String,
+    pub device_type: String,
+    pub new_device: bool,
+    pub new_ip: bool,
+    pub new_country: bool,
+    pub suspicious: bool,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_login_history(self) -> crate::models::users::entities::LoginHistory {
+        use crate::models::users::entities::LoginHistory;
+        use chrono::{DateTime, Utc};
+
+        LoginHistory {
+            id: self.id,
+            user_id: self.user_id,
+            success: self.success,
+            ip_address: self.ip_address,
+            country: self.country,
+            user_agent: self.user_agent,
+            browser: self.browser,
+            os: self.os,
+            device_type: self.device_type,
+            new_device: self.new_device,
+            new_ip: self.new_ip,
+            new_country: self.new_country,
+            suspicious: self.suspicious,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
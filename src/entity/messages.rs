@@ -0,0 +1,67 @@
+//! 私信实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "messages")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub sender_id: i64,
+    pub recipient_id: i64,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub read_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::SenderId",
+        to = "super::users::Column::Id"
+    )]
+    Sender,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::RecipientId",
+        to = "super::users::Column::Id"
+    )]
+    Recipient,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_message(self) -> crate::models::messages::entities::Message {
+        use crate::models::messages::entities::Message;
+        use chrono::{DateTime, Utc};
+
+        Message {
+            id: self.id,
+            class_id: self.class_id,
+            sender_id: self.sender_id,
+            recipient_id: self.recipient_id,
+            content: self.content,
+            read_at: self
+                .read_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
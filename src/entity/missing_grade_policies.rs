@@ -0,0 +1,54 @@
+//! 缺交零分策略实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "missing_grade_policies")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub homework_id: i64,
+    pub cutoff_at: i64,
+    pub applied_at: Option<i64>,
+    pub created_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+}
+
+impl Related<super::homeworks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Homework.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_missing_grade_policy(
+        self,
+    ) -> crate::models::homeworks::entities::MissingGradePolicy {
+        use crate::models::homeworks::entities::MissingGradePolicy;
+        use chrono::{DateTime, Utc};
+
+        MissingGradePolicy {
+            homework_id: self.homework_id,
+            cutoff_at: DateTime::<Utc>::from_timestamp(self.cutoff_at, 0).unwrap_or_default(),
+            applied_at: self
+                .applied_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_by: self.created_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
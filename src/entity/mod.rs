@@ -5,15 +5,64 @@
 
 pub mod prelude;
 
+pub mod activity_events;
+pub mod certificates;
+pub mod class_api_token_logs;
+pub mod class_api_tokens;
+pub mod class_certificate_settings;
+pub mod class_im_channels;
+pub mod class_leaderboard_opt_ins;
+pub mod class_leaderboard_settings;
+pub mod class_retention_settings;
+pub mod class_role_labels;
+pub mod class_stats_daily;
+pub mod class_submission_workflows;
 pub mod class_users;
 pub mod classes;
+pub mod content_reports;
+pub mod exam_access_logs;
 pub mod files;
+pub mod grade_drafts;
+pub mod grade_mentions;
 pub mod grades;
+pub mod homework_counters;
+pub mod homework_exemptions;
+pub mod homework_feedback;
 pub mod homework_files;
+pub mod homework_link_opens;
+pub mod homework_links;
+pub mod homework_parts;
+pub mod homework_prerequisites;
+pub mod homework_rubrics;
+pub mod homework_share_links;
+pub mod homework_solutions;
 pub mod homeworks;
+pub mod im_deliveries;
+pub mod login_history;
+pub mod messages;
+pub mod missing_grade_policies;
+pub mod moderation_flags;
+pub mod notification_templates;
 pub mod notifications;
+pub mod organizations;
+pub mod outbox_events;
+pub mod quarantined_files;
+pub mod reactions;
+pub mod resubmission_requests;
+pub mod role_requests;
+pub mod rubric_versions;
+pub mod rubrics;
+pub mod scheduled_exports;
+pub mod student_goals;
 pub mod submission_files;
+pub mod submission_references;
+pub mod submission_self_assessments;
 pub mod submissions;
 pub mod system_settings;
 pub mod system_settings_audit;
+pub mod usage_counters;
+pub mod usage_reports;
+pub mod user_admin_permissions;
+pub mod user_audit_logs;
 pub mod users;
+pub mod viewer_grants;
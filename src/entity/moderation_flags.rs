@@ -0,0 +1,83 @@
+//! 内容审核标记实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "moderation_flags")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub user_id: i64,
+    pub content_type: String,
+    pub content_id: Option<i64>,
+    pub source: String,
+    pub reason: String,
+    pub excerpt: String,
+    pub blocked: bool,
+    pub status: String,
+    pub reviewed_by: Option<i64>,
+    pub reviewed_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_moderation_flag(self) -> crate::models::moderation::entities::ModerationFlag {
+        use crate::models::moderation::entities::{
+            ModerationContentType, ModerationFlag, ModerationFlagStatus, ModerationSource,
+        };
+        use chrono::{DateTime, Utc};
+
+        ModerationFlag {
+            id: self.id,
+            class_id: self.class_id,
+            user_id: self.user_id,
+            content_type: self
+                .content_type
+                .parse()
+                .unwrap_or(ModerationContentType::Submission),
+            content_id: self.content_id,
+            source: self.source.parse().unwrap_or(ModerationSource::WordList),
+            reason: self.reason,
+            excerpt: self.excerpt,
+            blocked: self.blocked,
+            status: self.status.parse().unwrap_or(ModerationFlagStatus::Pending),
+            reviewed_by: self.reviewed_by,
+            reviewed_at: self
+                .reviewed_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
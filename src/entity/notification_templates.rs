@@ -0,0 +1,60 @@
+//! 通知模板实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "notification_templates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub notification_type: String,
+    pub locale: String,
+    pub title: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub content: Option<String>,
+    pub updated_by: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UpdatedBy",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_notification_template(
+        self,
+    ) -> crate::models::notifications::entities::NotificationTemplate {
+        use crate::models::notifications::entities::{NotificationTemplate, NotificationType};
+        use chrono::{DateTime, Utc};
+
+        NotificationTemplate {
+            id: self.id,
+            notification_type: self
+                .notification_type
+                .parse()
+                .unwrap_or(NotificationType::HomeworkCreated),
+            locale: self.locale,
+            title: self.title,
+            content: self.content,
+            updated_by: self.updated_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
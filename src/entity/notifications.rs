@@ -17,6 +17,7 @@ pub struct Model {
     pub reference_id: Option<i64>,
     pub is_read: bool,
     pub created_at: i64,
+    pub delivered_at: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,38 @@
+//! 事务发件箱实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "outbox_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub event_type: String,
+    #[sea_orm(column_type = "Text")]
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: i64,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub delivered_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 转换为待投递的发件箱记录
+    pub fn into_outbox_message(self) -> crate::models::outbox::entities::OutboxMessage {
+        crate::models::outbox::entities::OutboxMessage {
+            id: self.id,
+            event: serde_json::from_str(&self.payload)
+                .map_err(|e| format!("无法解析事件负载: {e}")),
+            event_type: self.event_type,
+            attempts: self.attempts,
+        }
+    }
+}
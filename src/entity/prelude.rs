@@ -1,24 +1,181 @@
 //! 预导入模块，方便使用
 
+pub use super::activity_events::{
+    ActiveModel as ActivityEventActiveModel, Entity as ActivityEvents, Model as ActivityEventModel,
+};
+pub use super::certificates::{
+    ActiveModel as CertificateActiveModel, Entity as Certificates, Model as CertificateModel,
+};
+pub use super::class_api_token_logs::{
+    ActiveModel as ClassApiTokenLogActiveModel, Entity as ClassApiTokenLogs,
+    Model as ClassApiTokenLogModel,
+};
+pub use super::class_api_tokens::{
+    ActiveModel as ClassApiTokenActiveModel, Entity as ClassApiTokens, Model as ClassApiTokenModel,
+};
+pub use super::class_certificate_settings::{
+    ActiveModel as ClassCertificateSettingActiveModel, Entity as ClassCertificateSettings,
+    Model as ClassCertificateSettingModel,
+};
+pub use super::class_im_channels::{
+    ActiveModel as ClassImChannelActiveModel, Entity as ClassImChannels,
+    Model as ClassImChannelModel,
+};
+pub use super::class_leaderboard_opt_ins::{
+    ActiveModel as ClassLeaderboardOptInActiveModel, Entity as ClassLeaderboardOptIns,
+    Model as ClassLeaderboardOptInModel,
+};
+pub use super::class_leaderboard_settings::{
+    ActiveModel as ClassLeaderboardSettingActiveModel, Entity as ClassLeaderboardSettings,
+    Model as ClassLeaderboardSettingModel,
+};
+pub use super::class_retention_settings::{
+    ActiveModel as ClassRetentionSettingActiveModel, Entity as ClassRetentionSettings,
+    Model as ClassRetentionSettingModel,
+};
+pub use super::class_role_labels::{
+    ActiveModel as ClassRoleLabelActiveModel, Entity as ClassRoleLabels,
+    Model as ClassRoleLabelModel,
+};
+pub use super::class_stats_daily::{
+    ActiveModel as ClassStatsDailyActiveModel, Entity as ClassStatsDaily,
+    Model as ClassStatsDailyModel,
+};
+pub use super::class_submission_workflows::{
+    ActiveModel as ClassSubmissionWorkflowActiveModel, Entity as ClassSubmissionWorkflows,
+    Model as ClassSubmissionWorkflowModel,
+};
 pub use super::class_users::{
     ActiveModel as ClassUserActiveModel, Entity as ClassUsers, Model as ClassUserModel,
 };
 pub use super::classes::{ActiveModel as ClassActiveModel, Entity as Classes, Model as ClassModel};
+pub use super::content_reports::{
+    ActiveModel as ContentReportActiveModel, Entity as ContentReports, Model as ContentReportModel,
+};
+pub use super::exam_access_logs::{
+    ActiveModel as ExamAccessLogActiveModel, Entity as ExamAccessLogs, Model as ExamAccessLogModel,
+};
 pub use super::files::{ActiveModel as FileActiveModel, Entity as Files, Model as FileModel};
+pub use super::grade_drafts::{
+    ActiveModel as GradeDraftActiveModel, Entity as GradeDrafts, Model as GradeDraftModel,
+};
+pub use super::grade_mentions::{
+    ActiveModel as GradeMentionActiveModel, Entity as GradeMentions, Model as GradeMentionModel,
+};
 pub use super::grades::{ActiveModel as GradeActiveModel, Entity as Grades, Model as GradeModel};
+pub use super::homework_counters::{
+    ActiveModel as HomeworkCounterActiveModel, Entity as HomeworkCounters,
+    Model as HomeworkCounterModel,
+};
+pub use super::homework_exemptions::{
+    ActiveModel as HomeworkExemptionActiveModel, Entity as HomeworkExemptions,
+    Model as HomeworkExemptionModel,
+};
+pub use super::homework_feedback::{
+    ActiveModel as HomeworkFeedbackActiveModel, Entity as HomeworkFeedback,
+    Model as HomeworkFeedbackModel,
+};
 pub use super::homework_files::{
     ActiveModel as HomeworkFileActiveModel, Entity as HomeworkFiles, Model as HomeworkFileModel,
 };
+pub use super::homework_link_opens::{
+    ActiveModel as HomeworkLinkOpenActiveModel, Entity as HomeworkLinkOpens,
+    Model as HomeworkLinkOpenModel,
+};
+pub use super::homework_links::{
+    ActiveModel as HomeworkLinkActiveModel, Entity as HomeworkLinks, Model as HomeworkLinkModel,
+};
+pub use super::homework_parts::{
+    ActiveModel as HomeworkPartActiveModel, Entity as HomeworkParts, Model as HomeworkPartModel,
+};
+pub use super::homework_prerequisites::{
+    ActiveModel as HomeworkPrerequisiteActiveModel, Entity as HomeworkPrerequisites,
+    Model as HomeworkPrerequisiteModel,
+};
+pub use super::homework_rubrics::{
+    ActiveModel as HomeworkRubricActiveModel, Entity as HomeworkRubrics,
+    Model as HomeworkRubricModel,
+};
+pub use super::homework_share_links::{
+    ActiveModel as HomeworkShareLinkActiveModel, Entity as HomeworkShareLinks,
+    Model as HomeworkShareLinkModel,
+};
+pub use super::homework_solutions::{
+    ActiveModel as HomeworkSolutionActiveModel, Entity as HomeworkSolutions,
+    Model as HomeworkSolutionModel,
+};
 pub use super::homeworks::{
     ActiveModel as HomeworkActiveModel, Entity as Homeworks, Model as HomeworkModel,
 };
+pub use super::im_deliveries::{
+    ActiveModel as ImDeliveryActiveModel, Entity as ImDeliveries, Model as ImDeliveryModel,
+};
+pub use super::login_history::{
+    ActiveModel as LoginHistoryActiveModel, Entity as LoginHistories, Model as LoginHistoryModel,
+};
+pub use super::messages::{
+    ActiveModel as MessageActiveModel, Entity as Messages, Model as MessageModel,
+};
+pub use super::missing_grade_policies::{
+    ActiveModel as MissingGradePolicyActiveModel, Entity as MissingGradePolicies,
+    Model as MissingGradePolicyModel,
+};
+pub use super::moderation_flags::{
+    ActiveModel as ModerationFlagActiveModel, Entity as ModerationFlags,
+    Model as ModerationFlagModel,
+};
+pub use super::notification_templates::{
+    ActiveModel as NotificationTemplateActiveModel, Entity as NotificationTemplates,
+    Model as NotificationTemplateModel,
+};
 pub use super::notifications::{
     ActiveModel as NotificationActiveModel, Entity as Notifications, Model as NotificationModel,
 };
+pub use super::organizations::{
+    ActiveModel as OrganizationActiveModel, Entity as Organizations, Model as OrganizationModel,
+};
+pub use super::outbox_events::{
+    ActiveModel as OutboxEventActiveModel, Entity as OutboxEvents, Model as OutboxEventModel,
+};
+pub use super::quarantined_files::{
+    ActiveModel as QuarantinedFileActiveModel, Entity as QuarantinedFiles,
+    Model as QuarantinedFileModel,
+};
+pub use super::reactions::{
+    ActiveModel as ReactionActiveModel, Entity as Reactions, Model as ReactionModel,
+};
+pub use super::resubmission_requests::{
+    ActiveModel as ResubmissionRequestActiveModel, Entity as ResubmissionRequests,
+    Model as ResubmissionRequestModel,
+};
+pub use super::role_requests::{
+    ActiveModel as RoleRequestActiveModel, Entity as RoleRequests, Model as RoleRequestModel,
+};
+pub use super::rubric_versions::{
+    ActiveModel as RubricVersionActiveModel, Entity as RubricVersions, Model as RubricVersionModel,
+};
+pub use super::rubrics::{
+    ActiveModel as RubricActiveModel, Entity as Rubrics, Model as RubricModel,
+};
+pub use super::scheduled_exports::{
+    ActiveModel as ScheduledExportActiveModel, Entity as ScheduledExports,
+    Model as ScheduledExportModel,
+};
+pub use super::student_goals::{
+    ActiveModel as StudentGoalActiveModel, Entity as StudentGoals, Model as StudentGoalModel,
+};
 pub use super::submission_files::{
     ActiveModel as SubmissionFileActiveModel, Entity as SubmissionFiles,
     Model as SubmissionFileModel,
 };
+pub use super::submission_references::{
+    ActiveModel as SubmissionReferenceActiveModel, Entity as SubmissionReferences,
+    Model as SubmissionReferenceModel,
+};
+pub use super::submission_self_assessments::{
+    ActiveModel as SubmissionSelfAssessmentActiveModel, Entity as SubmissionSelfAssessments,
+    Model as SubmissionSelfAssessmentModel,
+};
 pub use super::submissions::{
     ActiveModel as SubmissionActiveModel, Entity as Submissions, Model as SubmissionModel,
 };
@@ -29,4 +186,20 @@ pub use super::system_settings_audit::{
     ActiveModel as SystemSettingAuditActiveModel, Entity as SystemSettingsAudit,
     Model as SystemSettingAuditModel,
 };
+pub use super::usage_counters::{
+    ActiveModel as UsageCounterActiveModel, Entity as UsageCounters, Model as UsageCounterModel,
+};
+pub use super::usage_reports::{
+    ActiveModel as UsageReportActiveModel, Entity as UsageReports, Model as UsageReportModel,
+};
+pub use super::user_admin_permissions::{
+    ActiveModel as UserAdminPermissionActiveModel, Entity as UserAdminPermissions,
+    Model as UserAdminPermissionModel,
+};
+pub use super::user_audit_logs::{
+    ActiveModel as UserAuditLogActiveModel, Entity as UserAuditLogs, Model as UserAuditLogModel,
+};
 pub use super::users::{ActiveModel as UserActiveModel, Entity as Users, Model as UserModel};
+pub use super::viewer_grants::{
+    ActiveModel as ViewerGrantActiveModel, Entity as ViewerGrants, Model as ViewerGrantModel,
+};
@@ -0,0 +1,62 @@
+//! 隔离文件实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "quarantined_files")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub original_name: String,
+    pub stored_name: String,
+    pub file_type: String,
+    pub file_size: i64,
+    pub reason: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub detail: Option<String>,
+    pub org_id: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_quarantined_file(self) -> crate::models::files::entities::QuarantinedFile {
+        use crate::models::files::entities::{QuarantineReason, QuarantinedFile};
+        use chrono::{DateTime, Utc};
+
+        QuarantinedFile {
+            id: self.id,
+            user_id: self.user_id,
+            original_name: self.original_name,
+            stored_name: self.stored_name,
+            file_type: self.file_type,
+            file_size: self.file_size,
+            reason: self
+                .reason
+                .parse()
+                .unwrap_or(QuarantineReason::MagicMismatch),
+            detail: self.detail,
+            org_id: self.org_id,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! 退回重交请求实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "resubmission_requests")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub submission_id: i64,
+    pub homework_id: i64,
+    pub student_id: i64,
+    pub requested_by: i64,
+    #[sea_orm(column_type = "Text")]
+    pub comment: String,
+    pub reopen_until: i64,
+    pub fulfilled_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::submissions::Entity",
+        from = "Column::SubmissionId",
+        to = "super::submissions::Column::Id"
+    )]
+    Submission,
+    #[sea_orm(
+        belongs_to = "super::homeworks::Entity",
+        from = "Column::HomeworkId",
+        to = "super::homeworks::Column::Id"
+    )]
+    Homework,
+}
+
+impl Related<super::submissions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Submission.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_resubmission_request(
+        self,
+    ) -> crate::models::submissions::entities::ResubmissionRequest {
+        use crate::models::submissions::entities::ResubmissionRequest;
+        use chrono::{DateTime, Utc};
+
+        ResubmissionRequest {
+            id: self.id,
+            submission_id: self.submission_id,
+            homework_id: self.homework_id,
+            student_id: self.student_id,
+            requested_by: self.requested_by,
+            comment: self.comment,
+            reopen_until: DateTime::<Utc>::from_timestamp(self.reopen_until, 0).unwrap_or_default(),
+            fulfilled_at: self
+                .fulfilled_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
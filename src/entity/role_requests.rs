@@ -0,0 +1,89 @@
+//! 角色申请实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "role_requests")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub org_id: Option<i64>,
+    pub requested_role: String,
+    #[sea_orm(column_type = "Text")]
+    pub reason: String,
+    pub attachment_file_id: Option<i64>,
+    pub status: String,
+    pub reviewer_id: Option<i64>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_comment: Option<String>,
+    pub reviewed_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::files::Entity",
+        from = "Column::AttachmentFileId",
+        to = "super::files::Column::Id"
+    )]
+    AttachmentFile,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::files::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AttachmentFile.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    /// 转换为业务模型，申请人与附件信息由调用方查询后传入
+    pub fn into_role_request(
+        self,
+        user: Option<&super::users::Model>,
+        file: Option<&super::files::Model>,
+    ) -> crate::models::users::entities::RoleRequest {
+        use crate::models::users::entities::{
+            RoleRequest, RoleRequestAttachment, RoleRequestStatus, UserRole,
+        };
+        use chrono::{DateTime, Utc};
+
+        RoleRequest {
+            id: self.id,
+            user_id: self.user_id,
+            org_id: self.org_id,
+            username: user.map(|u| u.username.clone()).unwrap_or_default(),
+            display_name: user.and_then(|u| u.display_name.clone()),
+            requested_role: self.requested_role.parse().unwrap_or(UserRole::Teacher),
+            reason: self.reason,
+            attachment: file.map(|f| RoleRequestAttachment {
+                download_token: f.download_token.clone(),
+                original_name: f.original_name.clone(),
+                file_size: f.file_size,
+            }),
+            status: self.status.parse().unwrap_or(RoleRequestStatus::Pending),
+            reviewer_id: self.reviewer_id,
+            review_comment: self.review_comment,
+            reviewed_at: self
+                .reviewed_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
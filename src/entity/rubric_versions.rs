@@ -0,0 +1,54 @@
+//! 评分量规版本实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "rubric_versions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub rubric_id: i64,
+    pub version: i32,
+    // JSON 数组：该版本的评分维度
+    #[sea_orm(column_type = "Text")]
+    pub criteria: String,
+    pub created_by: i64,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::rubrics::Entity",
+        from = "Column::RubricId",
+        to = "super::rubrics::Column::Id"
+    )]
+    Rubric,
+}
+
+impl Related<super::rubrics::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Rubric.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_rubric_version(self) -> crate::models::rubrics::entities::RubricVersion {
+        use crate::models::rubrics::entities::{RubricCriterion, RubricVersion, total_points};
+        use chrono::{DateTime, Utc};
+
+        let criteria: Vec<RubricCriterion> =
+            serde_json::from_str(&self.criteria).unwrap_or_default();
+        RubricVersion {
+            rubric_id: self.rubric_id,
+            version: self.version,
+            total_points: total_points(&criteria),
+            criteria,
+            created_by: self.created_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
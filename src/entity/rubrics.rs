@@ -0,0 +1,77 @@
+//! 评分量规实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "rubrics")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub owner_id: i64,
+    pub org_id: Option<i64>,
+    pub title: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    // JSON 数组：当前版本的评分维度
+    #[sea_orm(column_type = "Text")]
+    pub criteria: String,
+    pub version: i32,
+    pub org_visible: bool,
+    #[sea_orm(unique)]
+    pub share_code: Option<String>,
+    pub source_rubric_id: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::OwnerId",
+        to = "super::users::Column::Id"
+    )]
+    Owner,
+    #[sea_orm(has_many = "super::rubric_versions::Entity")]
+    Versions,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Owner.def()
+    }
+}
+
+impl Related<super::rubric_versions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Versions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_rubric(self) -> crate::models::rubrics::entities::Rubric {
+        use crate::models::rubrics::entities::{Rubric, RubricCriterion, total_points};
+        use chrono::{DateTime, Utc};
+
+        let criteria: Vec<RubricCriterion> =
+            serde_json::from_str(&self.criteria).unwrap_or_default();
+        Rubric {
+            id: self.id,
+            owner_id: self.owner_id,
+            org_id: self.org_id,
+            title: self.title,
+            description: self.description,
+            total_points: total_points(&criteria),
+            criteria,
+            version: self.version,
+            org_visible: self.org_visible,
+            share_code: self.share_code,
+            source_rubric_id: self.source_rubric_id,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
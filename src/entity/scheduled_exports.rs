@@ -0,0 +1,88 @@
+//! 班级定时导出实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "scheduled_exports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub class_id: i64,
+    pub kind: String,
+    pub frequency: String,
+    // 0 = 周一，仅 weekly 使用
+    pub weekday: Option<i32>,
+    // UTC 整点
+    pub hour: i32,
+    pub delivery: String,
+    #[sea_orm(column_type = "Text")]
+    pub target: String,
+    pub enabled: bool,
+    pub next_run_at: i64,
+    pub last_run_at: Option<i64>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub consecutive_failures: i32,
+    pub created_by: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::classes::Entity",
+        from = "Column::ClassId",
+        to = "super::classes::Column::Id"
+    )]
+    Class,
+}
+
+impl Related<super::classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Class.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_scheduled_export(self) -> crate::models::classes::entities::ClassScheduledExport {
+        use crate::models::classes::entities::{
+            ClassScheduledExport, ScheduledExportDelivery, ScheduledExportFrequency,
+            ScheduledExportKind,
+        };
+        use chrono::{DateTime, Utc};
+
+        ClassScheduledExport {
+            id: self.id,
+            class_id: self.class_id,
+            kind: self
+                .kind
+                .parse()
+                .unwrap_or(ScheduledExportKind::ClassReport),
+            frequency: self
+                .frequency
+                .parse()
+                .unwrap_or(ScheduledExportFrequency::Weekly),
+            weekday: self.weekday,
+            hour: self.hour,
+            delivery: self
+                .delivery
+                .parse()
+                .unwrap_or(ScheduledExportDelivery::Webhook),
+            target: self.target,
+            enabled: self.enabled,
+            next_run_at: DateTime::<Utc>::from_timestamp(self.next_run_at, 0).unwrap_or_default(),
+            last_run_at: self
+                .last_run_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            last_error: self.last_error,
+            consecutive_failures: self.consecutive_failures,
+            created_by: self.created_by,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! 提交外部引用实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "submission_references")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub submission_id: i64,
+    pub position: i32,
+    pub kind: String,
+    pub url: String,
+    pub commit_hash: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub commit_message: Option<String>,
+    pub committed_at: Option<i64>,
+    pub metadata_fetched_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::submissions::Entity",
+        from = "Column::SubmissionId",
+        to = "super::submissions::Column::Id"
+    )]
+    Submission,
+}
+
+impl Related<super::submissions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Submission.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_submission_reference(
+        self,
+    ) -> crate::models::submissions::entities::SubmissionReference {
+        use crate::models::submissions::entities::{SubmissionReference, SubmissionReferenceKind};
+        use chrono::{DateTime, Utc};
+
+        SubmissionReference {
+            id: self.id,
+            kind: self
+                .kind
+                .parse()
+                .unwrap_or(SubmissionReferenceKind::Document),
+            url: self.url,
+            commit_hash: self.commit_hash,
+            commit_message: self.commit_message,
+            committed_at: self
+                .committed_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            metadata_fetched_at: self
+                .metadata_fetched_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+        }
+    }
+}
@@ -9,12 +9,23 @@ pub struct Model {
     pub id: i64,
     pub homework_id: i64,
     pub creator_id: i64,
+    pub part_id: Option<i64>,
     pub version: i32,
     #[sea_orm(column_type = "Text", nullable)]
     pub content: Option<String>,
     pub status: String,
+    /// 班级工作流中的自定义状态
+    pub workflow_status: Option<String>,
     pub is_late: bool,
     pub submitted_at: i64,
+    /// 批改超时提醒发送时间
+    pub grading_sla_notified_at: Option<i64>,
+    /// 正文词数
+    pub word_count: i32,
+    /// 正文字符数（不含空白）
+    pub char_count: i32,
+    /// 附件数
+    pub attachment_count: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -31,6 +42,12 @@ pub enum Relation {
         to = "super::users::Column::Id"
     )]
     Creator,
+    #[sea_orm(
+        belongs_to = "super::homework_parts::Entity",
+        from = "Column::PartId",
+        to = "super::homework_parts::Column::Id"
+    )]
+    Part,
     #[sea_orm(has_one = "super::grades::Entity")]
     Grade,
     #[sea_orm(has_many = "super::submission_files::Entity")]
@@ -49,6 +66,12 @@ impl Related<super::users::Entity> for Entity {
     }
 }
 
+impl Related<super::homework_parts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Part.def()
+    }
+}
+
 impl Related<super::grades::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Grade.def()
@@ -73,12 +96,14 @@ impl Model {
             id: self.id,
             homework_id: self.homework_id,
             creator_id: self.creator_id,
+            part_id: self.part_id,
             version: self.version,
             content: self.content,
             status: self
                 .status
                 .parse::<SubmissionStatus>()
                 .unwrap_or(SubmissionStatus::Pending),
+            workflow_status: self.workflow_status,
             is_late: self.is_late,
             submitted_at: DateTime::<Utc>::from_timestamp(self.submitted_at, 0).unwrap_or_default(),
         }
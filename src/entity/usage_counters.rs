@@ -0,0 +1,36 @@
+//! 用量计数器实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "usage_counters")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub org_id: i64,
+    pub period: String,
+    pub metric: String,
+    pub value: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_usage_counter(self) -> Option<crate::models::usage::entities::UsageCounter> {
+        use crate::models::usage::entities::{UsageCounter, UsageMetric};
+        use chrono::{DateTime, Utc};
+
+        Some(UsageCounter {
+            org_id: self.org_id,
+            period: self.period,
+            metric: self.metric.parse::<UsageMetric>().ok()?,
+            value: self.value,
+            updated_at: DateTime::<Utc>::from_timestamp(self.updated_at, 0).unwrap_or_default(),
+        })
+    }
+}
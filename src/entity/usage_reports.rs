@@ -0,0 +1,43 @@
+//! 用量报表实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "usage_reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub org_id: i64,
+    pub report_date: String,
+    pub total_users: i64,
+    pub active_users: i64,
+    pub storage_bytes: i64,
+    pub uploaded_bytes: i64,
+    pub submissions: i64,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_usage_report(self) -> crate::models::usage::entities::UsageReport {
+        use crate::models::usage::entities::UsageReport;
+        use chrono::{DateTime, Utc};
+
+        UsageReport {
+            id: self.id,
+            org_id: self.org_id,
+            report_date: self.report_date,
+            total_users: self.total_users,
+            active_users: self.active_users,
+            storage_bytes: self.storage_bytes,
+            uploaded_bytes: self.uploaded_bytes,
+            submissions: self.submissions,
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
@@ -17,6 +17,7 @@ pub struct Model {
     pub status: String,
     pub avatar_url: Option<String>,
     pub last_login: Option<i64>,
+    pub org_id: Option<i64>,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -101,6 +102,7 @@ impl Model {
                 .unwrap_or(UserStatus::Active),
             display_name: self.display_name,
             avatar_url: self.avatar_url,
+            org_id: self.org_id,
             last_login: self
                 .last_login
                 .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
@@ -0,0 +1,61 @@
+//! 家长/导师查看授权实体
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "viewer_grants")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub student_id: i64,
+    pub viewer_id: Option<i64>,
+    #[sea_orm(unique)]
+    pub token: String,
+    pub note: Option<String>,
+    pub expires_at: i64,
+    pub accepted_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::StudentId",
+        to = "super::users::Column::Id"
+    )]
+    Student,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Student.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 从数据库模型转换为业务模型
+impl Model {
+    pub fn into_viewer_grant(self) -> crate::models::viewers::entities::ViewerGrant {
+        use crate::models::viewers::entities::ViewerGrant;
+        use chrono::{DateTime, Utc};
+
+        ViewerGrant {
+            id: self.id,
+            student_id: self.student_id,
+            viewer_id: self.viewer_id,
+            token: self.token,
+            note: self.note,
+            expires_at: DateTime::<Utc>::from_timestamp(self.expires_at, 0).unwrap_or_default(),
+            accepted_at: self
+                .accepted_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            revoked_at: self
+                .revoked_at
+                .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            created_at: DateTime::<Utc>::from_timestamp(self.created_at, 0).unwrap_or_default(),
+        }
+    }
+}
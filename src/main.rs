@@ -1,5 +1,4 @@
-use actix_cors::Cors;
-use actix_web::middleware::{Compress, DefaultHeaders};
+use actix_web::middleware::{Compress, DefaultHeaders, from_fn};
 use actix_web::{App, HttpServer, web};
 use dotenv::dotenv;
 use human_panic::setup_panic;
@@ -7,6 +6,7 @@ use tracing::{debug, warn};
 
 // 从 lib.rs 导入模块
 use rust_hwsystem_next::config::AppConfig;
+use rust_hwsystem_next::middlewares;
 use rust_hwsystem_next::models::AppStartTime;
 use rust_hwsystem_next::routes;
 use rust_hwsystem_next::runtime::lifetime;
@@ -81,13 +81,9 @@ async fn main() -> std::io::Result<()> {
     // Start the HTTP server
     let server = HttpServer::new(move || {
         App::new()
-            .wrap(
-                Cors::default()
-                    .allow_any_origin()
-                    .allow_any_method()
-                    .allow_any_header()
-                    .max_age(config.cors.max_age),
-            )
+            // 跨域来源与预检缓存时间随系统设置实时更新
+            .wrap(middlewares::cors::cors())
+            .wrap(from_fn(middlewares::cors::apply_max_age))
             .wrap(Compress::default())
             .wrap(
                 DefaultHeaders::new()
@@ -106,17 +102,7 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::PayloadConfig::new(
                 config.server.limits.max_payload_size,
             )) // 设置最大请求体大小
-            .configure(routes::configure_auth_routes) // 配置认证相关路由
-            .configure(routes::configure_user_routes) // 配置用户相关路由
-            .configure(routes::configure_class_users_routes) //配置班级成员相关路由
-            .configure(routes::configure_classes_routes) // 配置班级相关路由
-            .configure(routes::configure_submissions_routes) // 配置提交相关路由（必须在 homeworks 之前，因为有 /homeworks/{id}/submissions 路由）
-            .configure(routes::configure_homeworks_routes) // 配置作业相关路由
-            .configure(routes::configure_grades_routes) // 配置评分相关路由
-            .configure(routes::configure_notifications_routes) // 配置通知相关路由
-            .configure(routes::configure_websocket_routes) // 配置 WebSocket 路由
-            .configure(routes::configure_file_routes) // 配置文件相关路由
-            .configure(routes::configure_system_routes) // 配置系统相关路由
+            .configure(routes::configure_api_routes) // 按版本配置 API 路由（/api/v1、/api/v2）
             .configure(routes::configure_frontend_routes) // 配置前端静态资源路由（放在最后作为 fallback）
     })
     .keep_alive(std::time::Duration::from_secs(
@@ -0,0 +1,249 @@
+/*!
+ * API 版本响应头中间件
+ *
+ * 为每个版本化的 API 响应附加版本信息；当版本（或单个端点）被标记为弃用时，
+ * 按 RFC 9745 / RFC 8594 附加 `Deprecation`、`Sunset` 与 `Link` 响应头，
+ * 提醒客户端迁移到后继版本。
+ *
+ * ## 使用方法
+ *
+ * ```rust,ignore
+ * use actix_web::web;
+ * use crate::middlewares::{ApiVersion, ApiVersionHeaders};
+ *
+ * // 整个版本
+ * web::scope(ApiVersion::V1.prefix()).wrap(ApiVersionHeaders::new(ApiVersion::V1));
+ *
+ * // 单个端点弃用
+ * web::resource("/legacy")
+ *     .wrap(ApiVersionHeaders::new(ApiVersion::V1).deprecated(1767225600, None));
+ * ```
+ */
+
+use actix_service::{Service, Transform};
+use actix_web::{
+    Error,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+};
+use chrono::{DateTime, Utc};
+use futures_util::future::{LocalBoxFuture, Ready, ready};
+use std::rc::Rc;
+
+/// API 版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+/// 弃用信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiDeprecation {
+    /// 弃用时间（Unix 时间戳）
+    pub deprecated_at: i64,
+    /// 计划下线时间（Unix 时间戳）
+    pub sunset_at: Option<i64>,
+}
+
+impl ApiVersion {
+    /// 当前挂载的全部版本
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    /// 版本标识，如 `v1`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    /// 路由前缀，如 `/api/v1`
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/api/v1",
+            ApiVersion::V2 => "/api/v2",
+        }
+    }
+
+    /// 版本级弃用信息，None 表示未弃用
+    ///
+    /// 弃用某个版本时只需在此处填写时间，所有该版本的响应都会带上弃用头。
+    pub fn deprecation(&self) -> Option<ApiDeprecation> {
+        match self {
+            ApiVersion::V1 => None,
+            ApiVersion::V2 => None,
+        }
+    }
+
+    /// 后继版本
+    pub fn successor(&self) -> Option<ApiVersion> {
+        match self {
+            ApiVersion::V1 => Some(ApiVersion::V2),
+            ApiVersion::V2 => None,
+        }
+    }
+}
+
+/// 格式化为 HTTP-date（IMF-fixdate）
+fn format_http_date(ts: i64) -> Option<String> {
+    DateTime::<Utc>::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+}
+
+/// 写入版本与弃用相关响应头
+fn apply_version_headers(
+    headers: &mut HeaderMap,
+    version: ApiVersion,
+    deprecation: Option<ApiDeprecation>,
+) {
+    headers.insert(
+        HeaderName::from_static("api-version"),
+        HeaderValue::from_static(version.as_str()),
+    );
+
+    let Some(deprecation) = deprecation else {
+        return;
+    };
+
+    if let Ok(value) = HeaderValue::from_str(&format!("@{}", deprecation.deprecated_at)) {
+        headers.insert(HeaderName::from_static("deprecation"), value);
+    }
+
+    if let Some(sunset) = deprecation.sunset_at.and_then(format_http_date)
+        && let Ok(value) = HeaderValue::from_str(&sunset)
+    {
+        headers.insert(HeaderName::from_static("sunset"), value);
+    }
+
+    if let Some(successor) = version.successor()
+        && let Ok(value) = HeaderValue::from_str(&format!(
+            "<{}>; rel=\"successor-version\"",
+            successor.prefix()
+        ))
+    {
+        headers.insert(actix_web::http::header::LINK, value);
+    }
+}
+
+/// API 版本响应头中间件
+#[derive(Clone)]
+pub struct ApiVersionHeaders {
+    version: ApiVersion,
+    deprecation: Option<ApiDeprecation>,
+}
+
+impl ApiVersionHeaders {
+    /// 使用版本级弃用配置创建中间件
+    pub fn new(version: ApiVersion) -> Self {
+        Self {
+            version,
+            deprecation: version.deprecation(),
+        }
+    }
+
+    /// 将被包裹的端点标记为弃用（覆盖版本级配置）
+    pub fn deprecated(mut self, deprecated_at: i64, sunset_at: Option<i64>) -> Self {
+        self.deprecation = Some(ApiDeprecation {
+            deprecated_at,
+            sunset_at,
+        });
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiVersionHeaders
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ApiVersionHeadersMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiVersionHeadersMiddleware {
+            service: Rc::new(service),
+            version: self.version,
+            deprecation: self.deprecation,
+        }))
+    }
+}
+
+pub struct ApiVersionHeadersMiddleware<S> {
+    service: Rc<S>,
+    version: ApiVersion,
+    deprecation: Option<ApiDeprecation>,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiVersionHeadersMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let srv = self.service.clone();
+        let version = self.version;
+        let deprecation = self.deprecation;
+
+        Box::pin(async move {
+            let mut res = srv.call(req).await?;
+            apply_version_headers(res.headers_mut(), version, deprecation);
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_prefix() {
+        assert_eq!(ApiVersion::V1.prefix(), "/api/v1");
+        assert_eq!(ApiVersion::V2.prefix(), "/api/v2");
+        assert_eq!(ApiVersion::V1.successor(), Some(ApiVersion::V2));
+        assert_eq!(ApiVersion::V2.successor(), None);
+    }
+
+    #[test]
+    fn test_headers_without_deprecation() {
+        let mut headers = HeaderMap::new();
+        apply_version_headers(&mut headers, ApiVersion::V2, None);
+        assert_eq!(headers.get("api-version").unwrap(), "v2");
+        assert!(headers.get("deprecation").is_none());
+        assert!(headers.get("sunset").is_none());
+    }
+
+    #[test]
+    fn test_headers_with_deprecation() {
+        let mut headers = HeaderMap::new();
+        let deprecation = ApiDeprecation {
+            deprecated_at: 1767225600,
+            sunset_at: Some(1782864000),
+        };
+        apply_version_headers(&mut headers, ApiVersion::V1, Some(deprecation));
+        assert_eq!(headers.get("deprecation").unwrap(), "@1767225600");
+        assert_eq!(
+            headers.get("sunset").unwrap(),
+            "Wed, 01 Jul 2026 00:00:00 GMT"
+        );
+        assert_eq!(
+            headers.get("link").unwrap(),
+            "</api/v2>; rel=\"successor-version\""
+        );
+    }
+}
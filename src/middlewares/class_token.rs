@@ -0,0 +1,345 @@
+/*!
+ * 班级 API 令牌中间件
+ *
+ * 教师可以为班级签发 API 令牌（`hwc_` 开头），供课程自动化脚本使用，例如从代码仓库每周发布作业。
+ * 令牌以签发教师的身份访问接口，但只能访问该班级的资源：
+ *
+ * - `/classes/{class_id}/...`（不含删除班级与令牌管理接口）
+ * - `/homeworks/{id}/...`、`/homeworks?class_id=`，创建作业时由业务层校验请求体中的 `class_id`
+ * - `/submissions/{id}/...`、`/submissions?homework_id=`（不能代学生提交）
+ *
+ * 只读令牌只能发起 GET 请求。令牌发起的写请求记录到 `class_api_token_logs`，便于追溯。
+ *
+ * 此中间件挂载在版本 scope 上，先于各路由的 RequireJWT 执行；认证成功后把用户信息与
+ * [`ClassTokenScope`] 放入请求扩展，RequireJWT 看到 [`ClassTokenScope`] 时不再校验 JWT。
+ * 不以 `hwc_` 开头的请求原样放行。
+ *
+ * ## 使用方法
+ *
+ * ```rust,ignore
+ * use crate::middlewares::ClassTokenGuard;
+ *
+ * if !ClassTokenGuard::can_access_class(&request, class_id) {
+ *     return Ok(HttpResponse::Forbidden().json(...));
+ * }
+ * ```
+ */
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use actix_service::{Service, Transform};
+use actix_web::{
+    Error, HttpMessage, HttpRequest,
+    body::EitherBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::{Method, StatusCode},
+    web,
+};
+use futures_util::future::{LocalBoxFuture, Ready, ready};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use super::{TenantGuard, create_error_response};
+use crate::models::ErrorCode;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::users::entities::UserStatus;
+use crate::storage::Storage;
+use crate::utils::ClientInfo;
+
+/// 班级 API 令牌前缀
+pub const CLASS_TOKEN_PREFIX: &str = "hwc_";
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// 通过班级 API 令牌认证的请求范围
+#[derive(Debug, Clone, Copy)]
+pub struct ClassTokenScope {
+    pub token_id: i64,
+    pub class_id: i64,
+    pub read_only: bool,
+}
+
+/// 令牌的 SHA-256 摘要（十六进制），数据库只保存摘要
+pub fn hash_class_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// 请求访问的班级
+enum Target {
+    /// 路径或查询参数可确定班级
+    Class(i64),
+    /// 班级在请求体中，由业务层通过 [`ClassTokenGuard::can_access_class`] 校验
+    CheckedByService,
+    /// 令牌不能访问的接口
+    Denied,
+}
+
+#[derive(Clone)]
+pub struct ClassTokenGuard;
+
+impl ClassTokenGuard {
+    /// 当前请求是否通过班级 API 令牌认证
+    pub fn scope(req: &HttpRequest) -> Option<ClassTokenScope> {
+        req.extensions().get::<ClassTokenScope>().copied()
+    }
+
+    /// 判断当前请求能否访问指定班级（非令牌请求总是返回 true）
+    pub fn can_access_class(req: &HttpRequest, class_id: i64) -> bool {
+        Self::scope(req).is_none_or(|scope| scope.class_id == class_id)
+    }
+}
+
+fn class_token(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.strip_prefix(BEARER_PREFIX))
+        .filter(|token| token.starts_with(CLASS_TOKEN_PREFIX))
+        .map(str::to_string)
+}
+
+/// 版本前缀之后的路径段，如 `/api/v1/classes/3` -> `["classes", "3"]`
+fn api_segments(path: &str) -> Vec<&str> {
+    path.strip_prefix("/api/")
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(_, rest)| rest.split('/').filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+fn query_id(req: &ServiceRequest, name: &str) -> Option<i64> {
+    web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|query| query.get(name).and_then(|v| v.parse().ok()))
+}
+
+async fn homework_class(storage: &Arc<dyn Storage>, homework_id: i64) -> Target {
+    match storage.get_homework_by_id(homework_id).await {
+        Ok(Some(homework)) => Target::Class(homework.class_id),
+        _ => Target::Denied,
+    }
+}
+
+/// 解析请求访问的班级
+async fn resolve_target(req: &ServiceRequest, storage: &Arc<dyn Storage>) -> Target {
+    let segments = api_segments(req.path());
+    let method = req.method();
+    let id = |index: usize| segments.get(index).and_then(|s| s.parse::<i64>().ok());
+
+    match segments.first().copied() {
+        Some("classes") => match id(1) {
+            // 令牌不能删除班级，也不能管理令牌
+            Some(_) if segments.len() == 2 && method == Method::DELETE => Target::Denied,
+            Some(_) if segments.get(2) == Some(&"api-tokens") => Target::Denied,
+            Some(class_id) => Target::Class(class_id),
+            None => Target::Denied,
+        },
+        Some("homeworks") => match (segments.len(), id(1)) {
+            (1, _) if method == Method::GET => match query_id(req, "class_id") {
+                Some(class_id) => Target::Class(class_id),
+                None => Target::Denied,
+            },
+            (1, _) if method == Method::POST => Target::CheckedByService,
+            // 不能代学生提交
+            (_, Some(_)) if segments.get(2) == Some(&"submissions") && method == Method::POST => {
+                Target::Denied
+            }
+            (_, Some(homework_id)) => homework_class(storage, homework_id).await,
+            _ => Target::Denied,
+        },
+        Some("submissions") => match (segments.len(), id(1)) {
+            (1, _) if method == Method::GET => match query_id(req, "homework_id") {
+                Some(homework_id) => homework_class(storage, homework_id).await,
+                None => Target::Denied,
+            },
+            (_, Some(submission_id)) => match storage.get_submission_by_id(submission_id).await {
+                Ok(Some(submission)) => homework_class(storage, submission.homework_id).await,
+                _ => Target::Denied,
+            },
+            _ => Target::Denied,
+        },
+        _ => Target::Denied,
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ClassTokenGuard
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ClassTokenGuardMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ClassTokenGuardMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct ClassTokenGuardMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ClassTokenGuardMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let srv = self.service.clone();
+        Box::pin(async move {
+            let Some(token) = class_token(&req).filter(|_| req.method() != Method::OPTIONS) else {
+                return Ok(srv.call(req).await?.map_into_left_body());
+            };
+
+            let storage = req
+                .app_data::<web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone();
+
+            // 1. 校验令牌
+            let now = chrono::Utc::now();
+            let api_token = match storage
+                .get_class_api_token_by_hash(&hash_class_token(&token))
+                .await
+            {
+                Ok(Some(api_token)) if api_token.is_active(now) => api_token,
+                _ => {
+                    info!("Class API token rejected for request to {}", req.path());
+                    return Ok(req.into_response(
+                        create_error_response(
+                            StatusCode::UNAUTHORIZED,
+                            ErrorCode::Unauthorized,
+                            "Unauthorized: invalid class API token",
+                        )
+                        .map_into_right_body(),
+                    ));
+                }
+            };
+
+            // 2. 令牌以签发者身份访问，签发者须仍处于启用状态
+            let user = match storage.get_user_by_id(api_token.created_by).await {
+                Ok(Some(user)) if user.status == UserStatus::Active => user,
+                _ => {
+                    return Ok(req.into_response(
+                        create_error_response(
+                            StatusCode::UNAUTHORIZED,
+                            ErrorCode::Unauthorized,
+                            "Unauthorized: token issuer is not active",
+                        )
+                        .map_into_right_body(),
+                    ));
+                }
+            };
+            if let Err(message) = TenantGuard::ensure_org_active(&storage, user.org_id).await {
+                return Ok(req.into_response(
+                    create_error_response(
+                        StatusCode::UNAUTHORIZED,
+                        ErrorCode::Unauthorized,
+                        &format!("Unauthorized: {message}"),
+                    )
+                    .map_into_right_body(),
+                ));
+            }
+
+            // 3. 校验访问范围
+            let is_write = !matches!(*req.method(), Method::GET | Method::HEAD);
+            let allowed = !(api_token.read_only && is_write)
+                && match resolve_target(&req, &storage).await {
+                    Target::Class(class_id) => class_id == api_token.class_id,
+                    Target::CheckedByService => true,
+                    Target::Denied => false,
+                };
+            if !allowed {
+                return Ok(req.into_response(
+                    create_error_response(
+                        StatusCode::FORBIDDEN,
+                        ErrorCode::ClassApiTokenForbidden,
+                        "Class API token is not allowed to access this endpoint",
+                    )
+                    .map_into_right_body(),
+                ));
+            }
+
+            if let Err(e) = storage
+                .touch_class_api_token(api_token.id, now.timestamp())
+                .await
+            {
+                warn!("Failed to update class API token usage: {e}");
+            }
+
+            req.extensions_mut().insert(ClassTokenScope {
+                token_id: api_token.id,
+                class_id: api_token.class_id,
+                read_only: api_token.read_only,
+            });
+            req.extensions_mut().insert(TenantScope::for_user(&user));
+            req.extensions_mut().insert(user);
+
+            let method = req.method().to_string();
+            let path = req.path().to_string();
+            let ip_address = ClientInfo::from_request(req.request()).ip_string();
+            let res = srv.call(req).await?;
+
+            // 4. 记录写请求，操作归属到令牌
+            if is_write
+                && let Err(e) = storage
+                    .create_class_api_token_log(
+                        api_token.id,
+                        &method,
+                        &path,
+                        res.status().as_u16() as i32,
+                        ip_address,
+                    )
+                    .await
+            {
+                warn!("Failed to record class API token request: {e}");
+            }
+
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_segments() {
+        assert_eq!(
+            api_segments("/api/v1/classes/3/homeworks/import"),
+            vec!["classes", "3", "homeworks", "import"]
+        );
+        assert_eq!(api_segments("/api/v2/homeworks/"), vec!["homeworks"]);
+        assert!(api_segments("/healthz").is_empty());
+    }
+
+    #[test]
+    fn test_hash_class_token() {
+        let hash = hash_class_token("hwc_example");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_class_token("hwc_example"));
+        assert_ne!(hash, hash_class_token("hwc_other"));
+    }
+}
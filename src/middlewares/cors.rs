@@ -0,0 +1,110 @@
+/*!
+ * 跨域（CORS）中间件
+ *
+ * 允许的来源与预检缓存时间取自系统设置 `cors.allowed_origins`、`cors.max_age`
+ * （未设置时使用配置文件），管理员修改后立即生效，无需重启。
+ *
+ * ## 使用方法
+ *
+ * ```rust,ignore
+ * App::new()
+ *     .wrap(middlewares::cors::cors())
+ *     .wrap(middleware::from_fn(middlewares::cors::apply_max_age))
+ * ```
+ */
+
+use actix_cors::Cors;
+use actix_web::{
+    Error,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{ACCESS_CONTROL_MAX_AGE, HeaderValue},
+    middleware::Next,
+};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::config::AppConfig;
+
+/// 允许的跨域来源（为空表示允许所有来源）
+static ALLOWED_ORIGINS: Lazy<RwLock<Vec<String>>> =
+    Lazy::new(|| RwLock::new(AppConfig::get().cors.allowed_origins.clone()));
+
+/// 预检请求缓存时间（秒）
+static MAX_AGE: Lazy<AtomicUsize> = Lazy::new(|| AtomicUsize::new(AppConfig::get().cors.max_age));
+
+/// 更新允许的跨域来源
+pub fn set_allowed_origins(origins: Vec<String>) {
+    *ALLOWED_ORIGINS.write().unwrap_or_else(|e| e.into_inner()) = origins;
+}
+
+/// 更新预检请求缓存时间（秒）
+pub fn set_max_age(secs: usize) {
+    MAX_AGE.store(secs, Ordering::Relaxed);
+}
+
+/// 判断来源是否允许跨域访问
+fn origin_allowed(origin: &HeaderValue) -> bool {
+    let origins = ALLOWED_ORIGINS.read().unwrap_or_else(|e| e.into_inner());
+    origins.is_empty()
+        || origin
+            .to_str()
+            .is_ok_and(|origin| origins.iter().any(|o| o == "*" || o == origin))
+}
+
+/// 构建 CORS 中间件，来源每次请求时按当前设置判断
+///
+/// 启用 Cookie 认证时允许跨域请求携带凭据（Cookie）。
+pub fn cors() -> Cors {
+    let cors = Cors::default()
+        .allowed_origin_fn(|origin, _| origin_allowed(origin))
+        .allow_any_method()
+        .allow_any_header()
+        .max_age(MAX_AGE.load(Ordering::Relaxed));
+
+    if AppConfig::get().auth_cookie.enabled {
+        cors.supports_credentials()
+    } else {
+        cors
+    }
+}
+
+/// 将预检响应的 `Access-Control-Max-Age` 改写为当前设置
+///
+/// actix-cors 在构建时固定缓存时间，需包裹在 [`cors`] 外层使用。
+pub async fn apply_max_age(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let mut res = next.call(req).await?;
+    if res.headers().contains_key(ACCESS_CONTROL_MAX_AGE) {
+        res.headers_mut().insert(
+            ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from(MAX_AGE.load(Ordering::Relaxed)),
+        );
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_allowed() {
+        let origin = HeaderValue::from_static("https://app.example.com");
+
+        set_allowed_origins(vec![]);
+        assert!(origin_allowed(&origin));
+
+        set_allowed_origins(vec!["https://other.example.com".to_string()]);
+        assert!(!origin_allowed(&origin));
+
+        set_allowed_origins(vec!["https://app.example.com".to_string()]);
+        assert!(origin_allowed(&origin));
+
+        set_allowed_origins(vec!["*".to_string()]);
+        assert!(origin_allowed(&origin));
+    }
+}
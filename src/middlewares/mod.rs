@@ -1,16 +1,25 @@
+pub mod api_version;
+pub mod class_token;
+pub mod cors;
 pub mod rate_limit;
 pub mod require_class_role;
 pub mod require_jwt;
+pub mod require_permission;
 pub mod require_role;
+pub mod tenant;
 
 use actix_web::{
     HttpResponse,
     http::{StatusCode, header::CONTENT_TYPE},
 };
+pub use api_version::{ApiVersion, ApiVersionHeaders};
+pub use class_token::{ClassTokenGuard, ClassTokenScope};
 pub use rate_limit::RateLimit;
 pub use require_class_role::RequireClassRole;
 pub use require_jwt::RequireJWT;
+pub use require_permission::RequirePermission;
 pub use require_role::RequireRole;
+pub use tenant::TenantGuard;
 
 use crate::models::{ApiResponse, ErrorCode};
 
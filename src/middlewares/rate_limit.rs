@@ -19,9 +19,17 @@
  *
  * ## 限制规则
  *
- * - 默认使用客户端 IP 作为限制键
+ * - 默认使用客户端 IP 作为限制键（仅采信可信代理的转发头，见 `server.trusted_proxies`）
  * - 支持自定义限制键（如用户 ID）
  * - 超过限制返回 429 Too Many Requests
+ * - 预设的请求上限可通过系统设置 `rate_limit.<前缀>` 覆盖，修改后立即生效
+ *
+ * ## 响应头
+ *
+ * 固定窗口计数：窗口内的请求额度可以连续突发用完，窗口结束时计数整体重置。
+ * 被限制的端点在每个响应中返回 `X-RateLimit-Limit`、`X-RateLimit-Remaining` 与
+ * `X-RateLimit-Reset`（距窗口重置的秒数），客户端可据此自行降速；429 响应额外返回
+ * `Retry-After`，并在 `data` 中携带同样的限额信息。
  */
 
 use actix_service::{Service, Transform};
@@ -30,26 +38,58 @@ use actix_web::{
     body::EitherBody,
     dev::{ServiceRequest, ServiceResponse},
     http::StatusCode,
-    http::header::CONTENT_TYPE,
+    http::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
 };
 use futures_util::future::{LocalBoxFuture, Ready, ready};
 use moka::future::Cache;
 use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::RwLock;
 use std::time::Duration;
 use tracing::warn;
+use ts_rs::TS;
 
 use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::ClientInfo;
 
 /// 全局速率限制缓存
-/// 键: IP:路由前缀，值: 请求计数
-static RATE_LIMIT_CACHE: Lazy<Cache<String, u32>> = Lazy::new(|| {
+/// 键: IP:路由前缀，值: (窗口内请求计数, 窗口开始时间戳)
+///
+/// 窗口是否结束由窗口开始时间判断，缓存过期只用于回收空闲的键，因此窗口最长 1 小时。
+static RATE_LIMIT_CACHE: Lazy<Cache<String, (u32, i64)>> = Lazy::new(|| {
     Cache::builder()
-        .time_to_live(Duration::from_secs(60)) // 1分钟过期
+        .time_to_live(Duration::from_secs(3600))
         .max_capacity(100_000)
         .build()
 });
 
+/// 按限制键前缀覆盖的请求上限（来自系统设置）
+static LIMIT_OVERRIDES: Lazy<RwLock<HashMap<String, u32>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 设置某类端点的请求上限，覆盖代码中的预设值
+pub fn set_limit_override(prefix: &str, max_requests: u32) {
+    LIMIT_OVERRIDES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(prefix.to_string(), max_requests);
+}
+
+/// 获取生效的请求上限
+fn effective_limit(prefix: &str, default: u32) -> u32 {
+    if prefix.is_empty() {
+        return default;
+    }
+    LIMIT_OVERRIDES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(prefix)
+        .copied()
+        .unwrap_or(default)
+}
+
 /// 速率限制配置
 #[derive(Clone)]
 pub struct RateLimit {
@@ -101,11 +141,21 @@ impl RateLimit {
         Self::new(10, 60).with_prefix("invite_code")
     }
 
+    /// 班级短码加入限制：5次/分钟/IP（短码空间小，需严格限制枚举）
+    pub fn short_code() -> Self {
+        Self::new(5, 60).with_prefix("short_code")
+    }
+
     /// 文件上传限制：10次/分钟/用户
     pub fn file_upload() -> Self {
         Self::new(10, 60).with_prefix("upload")
     }
 
+    /// 公开分享链接限制：30次/分钟/IP（防止暴力枚举令牌）
+    pub fn shared_link() -> Self {
+        Self::new(30, 60).with_prefix("shared_link")
+    }
+
     /// 通用 API 限制：100次/分钟/用户
     pub fn api() -> Self {
         Self::new(100, 60).with_prefix("api")
@@ -114,54 +164,12 @@ impl RateLimit {
 
 /// 从请求中提取客户端 IP
 ///
-/// 安全注意事项：
-/// - 如果服务部署在反向代理后面，需要在反向代理中配置正确的 X-Forwarded-For / X-Real-IP 头
-/// - 此实现会验证 IP 格式，防止伪造的无效头导致问题
-/// - 在不可信网络中直接暴露服务时，攻击者可能伪造转发头来绕过限制
+/// 仅当对端为 `server.trusted_proxies` 中的可信代理时才采信转发头，
+/// 防止客户端伪造 X-Forwarded-For 绕过限制。
 fn extract_client_ip(req: &ServiceRequest) -> String {
-    // 尝试从连接信息获取真实 IP（最可信）
-    let connection_ip = req
-        .connection_info()
-        .realip_remote_addr()
-        .map(|s| s.to_string());
-
-    // 如果连接信息有有效 IP，优先使用
-    if let Some(ref ip) = connection_ip
-        && is_valid_ip(ip)
-    {
-        return ip.clone();
-    }
-
-    // 从 X-Forwarded-For 头获取（用于反向代理场景）
-    // 只取第一个 IP（最接近客户端的）
-    if let Some(forwarded) = req.headers().get("X-Forwarded-For")
-        && let Ok(value) = forwarded.to_str()
-        && let Some(ip) = value.split(',').next()
-    {
-        let ip = ip.trim();
-        if is_valid_ip(ip) {
-            return ip.to_string();
-        }
-    }
-
-    // 从 X-Real-IP 头获取
-    if let Some(real_ip) = req.headers().get("X-Real-IP")
-        && let Ok(ip) = real_ip.to_str()
-    {
-        let ip = ip.trim();
-        if is_valid_ip(ip) {
-            return ip.to_string();
-        }
-    }
-
-    // 如果都没有有效 IP，使用连接信息的默认值
-    connection_ip.unwrap_or_else(|| "unknown".to_string())
-}
-
-/// 验证 IP 地址格式是否有效
-fn is_valid_ip(ip: &str) -> bool {
-    use std::net::IpAddr;
-    ip.parse::<IpAddr>().is_ok()
+    ClientInfo::from_request(req.request())
+        .ip_string()
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 /// 从请求中提取用户 ID（如果已认证）
@@ -171,15 +179,20 @@ fn extract_user_id(req: &ServiceRequest) -> Option<i64> {
 }
 
 /// 创建速率限制错误响应
-fn create_rate_limit_response(retry_after: u64) -> HttpResponse {
-    HttpResponse::build(StatusCode::TOO_MANY_REQUESTS)
+fn create_rate_limit_response(info: RateLimitInfo) -> HttpResponse {
+    let mut response = HttpResponse::build(StatusCode::TOO_MANY_REQUESTS)
         .insert_header((CONTENT_TYPE, "application/json; charset=utf-8"))
-        .insert_header(("Retry-After", retry_after.to_string()))
-        .insert_header(("X-RateLimit-Remaining", "0"))
-        .json(ApiResponse::<()>::error_empty(
+        .insert_header(("Retry-After", info.reset.to_string()))
+        .json(ApiResponse::error(
             ErrorCode::RateLimitExceeded,
-            "请求过于频繁，请稍后再试",
-        ))
+            info.clone(),
+            format!(
+                "请求过于频繁，请在 {} 秒后重试（每 {} 秒最多 {} 次请求）",
+                info.reset, info.window_secs, info.limit
+            ),
+        ));
+    info.write_headers(response.headers_mut());
+    response
 }
 
 impl<S, B> Transform<S, ServiceRequest> for RateLimit
@@ -228,11 +241,13 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let srv = self.service.clone();
-        let max_requests = self.max_requests;
+        let max_requests = effective_limit(&self.key_prefix, self.max_requests);
         let window_secs = self.window_secs;
         let key_prefix = self.key_prefix.clone();
 
         Box::pin(async move {
+            let now = chrono::Utc::now().timestamp();
+
             // 构建限制键
             let identifier = extract_user_id(&req)
                 .map(|id| format!("user:{}", id))
@@ -244,8 +259,13 @@ where
                 format!("{}:{}", key_prefix, identifier)
             };
 
-            // 获取当前计数
-            let current_count = RATE_LIMIT_CACHE.get(&cache_key).await.unwrap_or(0);
+            // 获取当前窗口的计数，上一个窗口已结束时重新计数
+            let (current_count, window_start) = RATE_LIMIT_CACHE
+                .get(&cache_key)
+                .await
+                .filter(|(_, start)| now - start < window_secs as i64)
+                .unwrap_or((0, now));
+            let reset = (window_start + window_secs as i64 - now).max(0) as u64;
 
             // 检查是否超过限制
             if current_count >= max_requests {
@@ -253,36 +273,70 @@ where
                     "Rate limit exceeded for key: {} (count: {}/{})",
                     cache_key, current_count, max_requests
                 );
-                return Ok(req
-                    .into_response(create_rate_limit_response(window_secs).map_into_right_body()));
+                let info = RateLimitInfo::new(max_requests, 0, reset, window_secs);
+                return Ok(
+                    req.into_response(create_rate_limit_response(info).map_into_right_body())
+                );
             }
 
             // 增加计数
             RATE_LIMIT_CACHE
-                .insert(cache_key.clone(), current_count + 1)
+                .insert(cache_key.clone(), (current_count + 1, window_start))
                 .await;
 
-            // 添加速率限制头
-            let remaining = max_requests.saturating_sub(current_count + 1);
-            req.extensions_mut().insert(RateLimitInfo {
-                remaining,
-                limit: max_requests,
-                reset: window_secs,
-            });
-
-            // 继续处理请求
-            let res = srv.call(req).await?.map_into_left_body();
-            Ok(res)
+            let info = RateLimitInfo::new(
+                max_requests,
+                max_requests.saturating_sub(current_count + 1),
+                reset,
+                window_secs,
+            );
+            req.extensions_mut().insert(info.clone());
+
+            // 继续处理请求，并在响应中写入限额头
+            let mut res = srv.call(req).await?;
+            info.write_headers(res.headers_mut());
+            Ok(res.map_into_left_body())
         })
     }
 }
 
-/// 速率限制信息（可在响应中添加）
-#[derive(Clone)]
+/// 速率限制信息（写入响应头，429 响应中作为 data 返回）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/api.ts")]
 pub struct RateLimitInfo {
-    pub remaining: u32,
+    /// 窗口内允许的最大请求数
     pub limit: u32,
+    /// 当前窗口剩余可用请求数
+    pub remaining: u32,
+    /// 距当前窗口重置的秒数
     pub reset: u64,
+    /// 窗口长度（秒）
+    pub window_secs: u64,
+    /// 计数策略，固定为 `fixed_window`：额度可在窗口内突发用完，窗口结束时整体重置
+    pub policy: String,
+}
+
+impl RateLimitInfo {
+    fn new(limit: u32, remaining: u32, reset: u64, window_secs: u64) -> Self {
+        Self {
+            limit,
+            remaining,
+            reset,
+            window_secs,
+            policy: "fixed_window".to_string(),
+        }
+    }
+
+    /// 写入 `X-RateLimit-*` 响应头
+    fn write_headers(&self, headers: &mut HeaderMap) {
+        for (name, value) in [
+            ("x-ratelimit-limit", self.limit as u64),
+            ("x-ratelimit-remaining", self.remaining as u64),
+            ("x-ratelimit-reset", self.reset),
+        ] {
+            headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -303,4 +357,22 @@ mod tests {
         let upload = RateLimit::file_upload();
         assert_eq!(upload.max_requests, 10);
     }
+
+    #[test]
+    fn test_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        RateLimitInfo::new(5, 3, 42, 60).write_headers(&mut headers);
+        assert_eq!(headers.get("X-RateLimit-Limit").unwrap(), "5");
+        assert_eq!(headers.get("X-RateLimit-Remaining").unwrap(), "3");
+        assert_eq!(headers.get("X-RateLimit-Reset").unwrap(), "42");
+    }
+
+    #[test]
+    fn test_limit_override() {
+        assert_eq!(effective_limit("override_test", 5), 5);
+        set_limit_override("override_test", 20);
+        assert_eq!(effective_limit("override_test", 5), 20);
+        // 无前缀的限制器不受覆盖影响
+        assert_eq!(effective_limit("", 5), 5);
+    }
 }
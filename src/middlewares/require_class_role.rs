@@ -34,15 +34,16 @@
 
 use actix_service::{Service, Transform};
 use actix_web::{
-    Error, HttpMessage,
+    Error, HttpMessage, HttpRequest,
     body::EitherBody,
     dev::{ServiceRequest, ServiceResponse},
     http::StatusCode,
 };
 use futures_util::future::{LocalBoxFuture, Ready, ready};
-use std::{rc::Rc, sync::Arc};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
 
 use crate::{
+    cache::{CacheResult, ObjectCache, traits::TypedObjectCache},
     models::{
         ErrorCode,
         class_users::entities::{ClassUser, ClassUserRole},
@@ -51,7 +52,20 @@ use crate::{
     storage::Storage,
 };
 
-use super::create_error_response;
+use super::{TenantGuard, create_error_response};
+
+/// 成员关系缓存时间（秒），成员变更时主动失效，TTL 仅作兜底
+const CLASS_USER_CACHE_TTL: u64 = 30;
+
+/// 成员关系缓存键，值为 `Option<ClassUser>`（None 表示不是班级成员）
+fn class_user_cache_key(class_id: i64, user_id: i64) -> String {
+    format!("class_user:{class_id}:{user_id}")
+}
+
+/// 请求内的成员关系备忘，保证同一请求对同一成员关系只查询一次
+/// 键: (class_id, user_id)
+#[derive(Default)]
+struct ClassUserMemo(HashMap<(i64, i64), Option<ClassUser>>);
 
 #[derive(Clone)]
 pub struct RequireClassRole {
@@ -160,21 +174,38 @@ where
                 }
             };
 
-            // 3. 管理员直接放行
+            let storage = req
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone();
+
+            // 3. 管理员直接放行，组织管理员只能访问本组织的班级
             if user_claims.role == UserRole::Admin {
-                return Ok(srv.call(req).await?.map_into_left_body());
+                if TenantGuard::can_access_class(req.request(), &storage, class_id).await {
+                    return Ok(srv.call(req).await?.map_into_left_body());
+                }
+                return Ok(req.into_response(
+                    create_error_response(
+                        StatusCode::NOT_FOUND,
+                        ErrorCode::ClassNotFound,
+                        "Class not found",
+                    )
+                    .map_into_right_body(),
+                ));
             }
 
             // 4. 查询用户在班级中的成员关系和角色
-            let class_user = match get_class_user_by_user_id_and_class_id(
-                &req,
+            let class_user = match RequireClassRole::class_user(
+                req.request(),
+                &storage,
                 user_claims.id,
                 class_id,
             )
             .await
             {
-                Some(cs) => cs,
-                None => {
+                Ok(Some(cs)) => cs,
+                _ => {
                     return Ok(req.into_response(
                         create_error_response(
                             StatusCode::FORBIDDEN,
@@ -220,25 +251,66 @@ impl RequireClassRole {
     pub fn extract_user_class_user(req: &actix_web::HttpRequest) -> Option<ClassUser> {
         req.extensions().get::<ClassUser>().cloned()
     }
-}
 
-async fn get_class_user_by_user_id_and_class_id(
-    req: &ServiceRequest,
-    user_id: i64,
-    class_id: i64,
-) -> Option<ClassUser> {
-    let storage = req
-        .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
-        .expect("Storage not found in app data")
-        .get_ref()
-        .clone();
-
-    match storage
-        .get_class_user_by_user_id_and_class_id(user_id, class_id)
-        .await
-    {
-        Ok(Some(class_user)) => Some(class_user),
-        Ok(None) => None,
-        Err(_) => None,
+    /// 查询用户在班级中的成员关系（带缓存）
+    ///
+    /// 先查请求内备忘，再查短 TTL 缓存，最后回源存储；中间件与处理函数应统一使用此函数，
+    /// 避免同一请求重复查询成员关系。存储查询失败时不写入缓存。
+    pub async fn class_user(
+        request: &HttpRequest,
+        storage: &Arc<dyn Storage>,
+        user_id: i64,
+        class_id: i64,
+    ) -> crate::errors::Result<Option<ClassUser>> {
+        let memo_key = (class_id, user_id);
+        let memoized = request
+            .extensions()
+            .get::<ClassUserMemo>()
+            .and_then(|memo| memo.0.get(&memo_key).cloned());
+        if let Some(class_user) = memoized {
+            return Ok(class_user);
+        }
+
+        let cache = request
+            .app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>()
+            .map(|c| c.get_ref().clone());
+        let cache_key = class_user_cache_key(class_id, user_id);
+
+        let cached = match &cache {
+            Some(cache) => cache.get::<Option<ClassUser>>(&cache_key).await,
+            None => CacheResult::NotFound,
+        };
+        let class_user = match cached {
+            CacheResult::Found(class_user) => class_user,
+            _ => {
+                let class_user = storage
+                    .get_class_user_by_user_id_and_class_id(user_id, class_id)
+                    .await?;
+                if let Some(cache) = &cache {
+                    cache
+                        .insert(cache_key, class_user.clone(), CLASS_USER_CACHE_TTL)
+                        .await;
+                }
+                class_user
+            }
+        };
+
+        let mut extensions = request.extensions_mut();
+        if let Some(memo) = extensions.get_mut::<ClassUserMemo>() {
+            memo.0.insert(memo_key, class_user.clone());
+        } else {
+            let mut memo = ClassUserMemo::default();
+            memo.0.insert(memo_key, class_user.clone());
+            extensions.insert(memo);
+        }
+
+        Ok(class_user)
+    }
+
+    /// 成员关系变更（加入、退出、角色调整）后清除缓存
+    pub async fn invalidate(request: &HttpRequest, class_id: i64, user_id: i64) {
+        if let Some(cache) = request.app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>() {
+            cache.remove(&class_user_cache_key(class_id, user_id)).await;
+        }
     }
 }
@@ -52,11 +52,18 @@
  *
  * ## 认证流程
  *
- * 1. 客户端在请求头中包含 `Authorization: Bearer <JWT_TOKEN>`
- * 2. 中间件提取并验证JWT令牌
+ * 1. 客户端在请求头中包含 `Authorization: Bearer <JWT_TOKEN>`；浏览器客户端也可使用
+ *    Cookie 认证（见配置 `auth_cookie`），由 `access_token` Cookie 携带令牌
+ * 2. 中间件提取并验证JWT令牌；通过 Cookie 认证的写请求还需在 `X-CSRF-Token` 头中
+ *    回传 `csrf_token` Cookie 的值（双提交校验），否则返回 403
  * 3. 如果令牌有效，将用户信息存储在请求扩展中，继续处理请求
  * 4. 如果令牌无效或缺失，返回401未授权错误
  *
+ * 已由 [`ClassTokenGuard`](super::ClassTokenGuard) 通过班级 API 令牌认证的请求直接放行。
+ *
+ * 家长/导师（`viewer` 角色）账号只能访问 `/auth` 与 `/viewer` 下的接口，其余受保护路由
+ * 一律返回 403（错误码 4051）。
+ *
  * ## 配置
  *
  * 确保在环境变量中设置了 `JWT_SECRET`，JWT服务将使用此密钥来验证令牌。
@@ -64,16 +71,18 @@
 
 use crate::cache::{CacheResult, ObjectCache};
 use crate::config::AppConfig;
+use crate::models::organizations::entities::TenantScope;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode, users::entities};
 use crate::storage::Storage;
+use crate::utils::jwt::{ACCESS_TOKEN_COOKIE, CSRF_COOKIE, CSRF_HEADER, JwtUtils};
 use actix_service::{Service, Transform};
 use actix_web::{
     Error, HttpMessage, HttpResponse,
     body::EitherBody,
     dev::{ServiceRequest, ServiceResponse},
-    http::StatusCode,
     http::header::CONTENT_TYPE,
+    http::{Method, StatusCode},
 };
 use futures_util::future::{LocalBoxFuture, Ready, ready};
 use std::{rc::Rc, sync::Arc};
@@ -82,6 +91,9 @@ use tracing::{debug, info};
 const BEARER_PREFIX: &str = "Bearer ";
 const AUTHORIZATION_HEADER: &str = "Authorization";
 
+/// 家长/导师账号可访问的接口前缀（相对于 `/api/{version}`）
+const VIEWER_ALLOWED_PREFIXES: &[&str] = &["/auth/", "/viewer/"];
+
 #[derive(Clone)]
 pub struct RequireJWT;
 
@@ -103,16 +115,55 @@ fn create_error_response(status: StatusCode, message: &str) -> HttpResponse {
     }
 }
 
-// 辅助函数：提取并验证 JWT access token
-async fn extract_and_validate_jwt(req: &ServiceRequest) -> Result<entities::User, String> {
-    let token = req
-        .headers()
+// 辅助函数：从 Authorization 头提取 access token
+fn bearer_token(req: &ServiceRequest) -> Option<String> {
+    req.headers()
         .get(AUTHORIZATION_HEADER)
         .and_then(|h| h.to_str().ok())
         .and_then(|s| s.strip_prefix(BEARER_PREFIX))
+        .map(str::to_string)
+}
+
+// 辅助函数：家长/导师账号是否可以访问该路径
+fn viewer_path_allowed(path: &str) -> bool {
+    let relative = path
+        .strip_prefix("/api/")
+        .and_then(|rest| rest.find('/').map(|i| &rest[i..]))
+        .unwrap_or(path);
+    VIEWER_ALLOWED_PREFIXES
+        .iter()
+        .any(|prefix| relative.starts_with(prefix))
+}
+
+// 辅助函数：通过 Cookie 认证的写请求需通过 CSRF 双提交校验
+fn check_csrf(req: &ServiceRequest) -> Result<(), &'static str> {
+    if bearer_token(req).is_some() || req.cookie(ACCESS_TOKEN_COOKIE).is_none() {
+        return Ok(());
+    }
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(());
+    }
+
+    let header = req.headers().get(CSRF_HEADER).and_then(|h| h.to_str().ok());
+    let cookie = req.cookie(CSRF_COOKIE);
+    if JwtUtils::csrf_token_matches(header, cookie.as_ref().map(|c| c.value())) {
+        Ok(())
+    } else {
+        Err("CSRF token missing or mismatched")
+    }
+}
+
+// 辅助函数：提取并验证 JWT access token（优先 Authorization 头，其次 Cookie）
+async fn extract_and_validate_jwt(req: &ServiceRequest) -> Result<entities::User, String> {
+    let token = bearer_token(req)
+        .or_else(|| {
+            req.cookie(ACCESS_TOKEN_COOKIE)
+                .map(|c| c.value().to_string())
+        })
         .ok_or_else(|| "Missing or invalid Authorization header".to_string())?;
+    let token = token.as_str();
 
-    crate::utils::jwt::JwtUtils::verify_access_token(token).map_err(|err| {
+    JwtUtils::verify_access_token(token).map_err(|err| {
         info!("JWT token validation failed: {}", err);
         "Invalid JWT token".to_string()
     })?;
@@ -123,27 +174,13 @@ async fn extract_and_validate_jwt(req: &ServiceRequest) -> Result<entities::User
         .get_ref()
         .clone();
 
-    // 从缓存中获取用户信息
-    match cache.get_raw(&format!("user:{token}")).await {
-        CacheResult::Found(json) => match serde_json::from_str::<entities::User>(&json) {
-            Ok(user) => return Ok(user),
-            Err(_) => {
-                cache.remove(&format!("user:{token}")).await;
-                info!("Failed to deserialize user from cache for token: {}", token);
-            }
-        },
-        _ => {
-            info!("User not found in cache for token: {}", token);
-        }
-    };
-
     let storage = req
         .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
         .expect("Storage not found in app data")
         .get_ref()
         .clone();
 
-    let claims = crate::utils::jwt::JwtUtils::decode_token(token).map_err(|err| {
+    let claims = JwtUtils::decode_token(token).map_err(|err| {
         info!("Failed to decode JWT token: {}", err);
         "Invalid JWT token format".to_string()
     })?;
@@ -153,6 +190,25 @@ async fn extract_and_validate_jwt(req: &ServiceRequest) -> Result<entities::User
         .parse::<i64>()
         .map_err(|_| "Invalid user ID in JWT".to_string())?;
 
+    // 从缓存中获取用户信息（按用户 ID 缓存，便于用户变更后按 ID 失效）
+    let cache_key = RequireJWT::user_cache_key(user_id);
+    match cache.get_raw(&cache_key).await {
+        CacheResult::Found(json) => match serde_json::from_str::<entities::User>(&json) {
+            Ok(user) => {
+                // 所属组织被停用后立即拒绝访问
+                super::TenantGuard::ensure_org_active(&storage, user.org_id).await?;
+                return Ok(user);
+            }
+            Err(_) => {
+                cache.remove(&cache_key).await;
+                info!("Failed to deserialize cached user: {}", user_id);
+            }
+        },
+        _ => {
+            info!("User not found in cache: {}", user_id);
+        }
+    };
+
     let user = storage
         .get_user_by_id(user_id)
         .await
@@ -163,15 +219,13 @@ async fn extract_and_validate_jwt(req: &ServiceRequest) -> Result<entities::User
         return Err("User is not active".to_string());
     }
 
+    super::TenantGuard::ensure_org_active(&storage, user.org_id).await?;
+
     // 将用户信息存入缓存
     let app_config = AppConfig::get();
     if let Ok(user_json) = serde_json::to_string(&user) {
         cache
-            .insert_raw(
-                format!("user:{token}"),
-                user_json,
-                app_config.cache.default_ttl,
-            )
+            .insert_raw(cache_key, user_json, app_config.cache.default_ttl)
             .await;
     }
 
@@ -226,11 +280,41 @@ where
                 ));
             }
 
+            // 已由 ClassTokenGuard 通过班级 API 令牌认证
+            let via_class_token = req.extensions().contains::<super::ClassTokenScope>();
+            if via_class_token {
+                return Ok(srv.call(req).await?.map_into_left_body());
+            }
+
+            if let Err(message) = check_csrf(&req) {
+                info!("CSRF check failed for request to {}", req.path());
+                return Ok(req.into_response(
+                    super::create_error_response(
+                        StatusCode::FORBIDDEN,
+                        ErrorCode::CsrfTokenInvalid,
+                        message,
+                    )
+                    .map_into_right_body(),
+                ));
+            }
+
             // 验证 JWT token
             match extract_and_validate_jwt(&req).await {
                 Ok(user) => {
                     debug!("JWT authentication successful for ID: {}", user.id);
+                    if user.role == UserRole::Viewer && !viewer_path_allowed(req.path()) {
+                        info!("Viewer {} denied access to {}", user.id, req.path());
+                        return Ok(req.into_response(
+                            super::create_error_response(
+                                StatusCode::FORBIDDEN,
+                                ErrorCode::ViewerAccessDenied,
+                                "只读查看账号无权访问该接口",
+                            )
+                            .map_into_right_body(),
+                        ));
+                    }
                     // 可以在这里将用户信息添加到请求扩展中，供后续处理程序使用
+                    req.extensions_mut().insert(TenantScope::for_user(&user));
                     req.extensions_mut().insert(user);
                     let res = srv.call(req).await?.map_into_left_body();
                     Ok(res)
@@ -256,6 +340,18 @@ where
 
 // 辅助函数：从请求中提取用户信息
 impl RequireJWT {
+    /// 认证用户缓存的键
+    pub fn user_cache_key(user_id: i64) -> String {
+        format!("user:{user_id}")
+    }
+
+    /// 用户信息变更后清除认证用户缓存
+    pub async fn invalidate_user(req: &actix_web::HttpRequest, user_id: i64) {
+        if let Some(cache) = req.app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>() {
+            cache.remove(&Self::user_cache_key(user_id)).await;
+        }
+    }
+
     /// 从请求扩展中提取用户Claims信息
     /// 此函数应该在应用了RequireJWT中间件的路由处理程序中使用
     pub fn extract_user_claims(req: &actix_web::HttpRequest) -> Option<entities::User> {
@@ -276,3 +372,17 @@ impl RequireJWT {
             .map(|user| user.role.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewer_path_allowed() {
+        assert!(viewer_path_allowed("/api/v1/auth/me"));
+        assert!(viewer_path_allowed("/api/v2/viewer/students/3/summary"));
+        assert!(!viewer_path_allowed("/api/v1/homeworks"));
+        assert!(!viewer_path_allowed("/api/v1/viewers/1"));
+        assert!(!viewer_path_allowed("/api/v1/classes/1/roster/export"));
+    }
+}
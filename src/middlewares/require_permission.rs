@@ -0,0 +1,215 @@
+/*!
+ * 基于管理权限的访问控制中间件
+ *
+ * 此中间件必须在 RequireJWT 中间件之后使用，用于验证用户是否拥有特定的管理权限。
+ * 平台管理员视为拥有全部权限；组织管理员默认拥有只作用于本组织的权限（用户、班级管理），
+ * 全平台共享的权限与其他用户一样需要在 `user_admin_permissions` 表中被授予，
+ * 从而实现管理职责的有限委派。
+ *
+ * ## 使用方法
+ *
+ * ```rust,ignore
+ * use actix_web::web;
+ * use crate::middlewares::{RequireJWT, RequirePermission};
+ * use crate::models::users::entities::AdminPermission;
+ *
+ * web::scope("/users")
+ *     .wrap(RequirePermission::new(AdminPermission::UserManage))
+ *     .wrap(RequireJWT);
+ * ```
+ */
+
+use actix_service::{Service, Transform};
+use actix_web::{
+    Error, HttpMessage,
+    body::EitherBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::StatusCode,
+};
+use futures_util::future::{LocalBoxFuture, Ready, ready};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::{rc::Rc, sync::Arc, time::Duration};
+use tracing::info;
+
+use crate::{
+    models::{
+        ErrorCode,
+        users::entities::{AdminPermission, User, UserRole},
+    },
+    storage::Storage,
+};
+
+use super::create_error_response;
+
+/// 用户权限缓存
+/// 键: user_id，值: 已授予的权限
+static PERMISSION_CACHE: Lazy<Cache<i64, Arc<Vec<AdminPermission>>>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(Duration::from_secs(60))
+        .max_capacity(10_000)
+        .build()
+});
+
+#[derive(Clone)]
+pub struct RequirePermission {
+    permission: AdminPermission,
+}
+
+impl RequirePermission {
+    /// 创建需要特定管理权限的中间件
+    pub fn new(permission: AdminPermission) -> Self {
+        Self { permission }
+    }
+
+    /// 查询用户被授予的管理权限（带缓存）
+    pub async fn user_permissions(
+        storage: &Arc<dyn Storage>,
+        user_id: i64,
+    ) -> Arc<Vec<AdminPermission>> {
+        if let Some(cached) = PERMISSION_CACHE.get(&user_id).await {
+            return cached;
+        }
+
+        let permissions = Arc::new(
+            storage
+                .get_user_admin_permissions(user_id)
+                .await
+                .unwrap_or_default(),
+        );
+        PERMISSION_CACHE.insert(user_id, permissions.clone()).await;
+        permissions
+    }
+
+    /// 判断用户是否拥有某项管理权限（平台管理员拥有全部权限）
+    pub async fn has_permission(
+        storage: &Arc<dyn Storage>,
+        user: &User,
+        permission: AdminPermission,
+    ) -> bool {
+        if user.is_platform_admin() {
+            return true;
+        }
+        if user.role == UserRole::Admin && permission.is_tenant_scoped() {
+            return true;
+        }
+        Self::user_permissions(storage, user.id)
+            .await
+            .contains(&permission)
+    }
+
+    /// 获取按权限提升后的有效系统角色
+    ///
+    /// 拥有指定管理权限的用户在对应业务中按 Admin 处理，便于复用已有的 Admin 分支。
+    pub async fn effective_role(
+        storage: &Arc<dyn Storage>,
+        user: &User,
+        permission: AdminPermission,
+    ) -> UserRole {
+        if Self::has_permission(storage, user, permission).await {
+            UserRole::Admin
+        } else {
+            user.role.clone()
+        }
+    }
+
+    /// 权限变更后清除缓存
+    pub async fn invalidate(user_id: i64) {
+        PERMISSION_CACHE.invalidate(&user_id).await;
+    }
+
+    /// 清除全部用户的权限缓存
+    pub fn invalidate_all() {
+        PERMISSION_CACHE.invalidate_all();
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequirePermission
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequirePermissionMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequirePermissionMiddleware {
+            service: Rc::new(service),
+            permission: self.permission,
+        }))
+    }
+}
+
+pub struct RequirePermissionMiddleware<S> {
+    service: Rc<S>,
+    permission: AdminPermission,
+}
+
+impl<S, B> Service<ServiceRequest> for RequirePermissionMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let srv = self.service.clone();
+        let permission = self.permission;
+
+        Box::pin(async move {
+            // 从请求扩展中获取用户信息
+            let user = match req.extensions().get::<User>().cloned() {
+                Some(user) => user,
+                None => {
+                    info!(
+                        "Permission check failed: No user claims found in request. Make sure RequireJWT middleware is applied first."
+                    );
+                    return Ok(req.into_response(
+                        create_error_response(
+                            StatusCode::UNAUTHORIZED,
+                            ErrorCode::Unauthorized,
+                            "Authentication required",
+                        )
+                        .map_into_right_body(),
+                    ));
+                }
+            };
+
+            let storage = req
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone();
+
+            if RequirePermission::has_permission(&storage, &user, permission).await {
+                let res = srv.call(req).await?.map_into_left_body();
+                Ok(res)
+            } else {
+                info!(
+                    "Access denied for user {} (role: {:?}). Required permission: {}",
+                    user.id, user.role, permission
+                );
+                Ok(req.into_response(
+                    create_error_response(
+                        StatusCode::FORBIDDEN,
+                        ErrorCode::Forbidden,
+                        "Access denied.",
+                    )
+                    .map_into_right_body(),
+                ))
+            }
+        })
+    }
+}
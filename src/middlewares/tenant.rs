@@ -0,0 +1,172 @@
+/*!
+ * 多租户隔离辅助
+ *
+ * 每个用户、班级、文件都归属于某个组织（`org_id`，为空表示默认租户）。
+ * RequireJWT 在认证成功后调用 [`TenantGuard::ensure_org_active`] 拒绝已停用组织的用户，
+ * 并把当前用户的 [`TenantScope`] 放入请求扩展；业务层据此为存储层查询附加 `org_id`
+ * 过滤条件，或在按 ID 访问资源时通过 [`TenantGuard::can_access`] 阻止跨组织访问。
+ *
+ * 不属于任何组织的系统管理员为平台管理员，可跨租户访问；
+ * 属于某个组织的系统管理员为组织管理员，只能管理本组织。
+ * 涉及全部租户数据的运维接口用 [`require_platform_admin`] 限制为仅平台管理员可用。
+ *
+ * ## 使用方法
+ *
+ * ```rust,ignore
+ * use crate::middlewares::TenantGuard;
+ *
+ * if !TenantGuard::can_access(&request, class.org_id) {
+ *     return Ok(HttpResponse::NotFound().json(...));
+ * }
+ *
+ * // 仅平台管理员（须在 RequireJWT 之后执行）
+ * web::scope("/admin/database")
+ *     .wrap(middleware::from_fn(tenant::require_platform_admin))
+ *     .wrap(RequireJWT)
+ * ```
+ */
+
+use actix_web::{
+    Error, HttpMessage, HttpRequest,
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::StatusCode,
+    middleware::Next,
+};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    models::{ErrorCode, organizations::entities::TenantScope, users::entities::User},
+    storage::Storage,
+};
+
+use super::{RequireJWT, create_error_response};
+
+/// 组织启用状态缓存
+/// 键: org_id，值: 是否启用
+static ORG_ACTIVE_CACHE: Lazy<Cache<i64, bool>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(Duration::from_secs(60))
+        .max_capacity(10_000)
+        .build()
+});
+
+pub struct TenantGuard;
+
+impl TenantGuard {
+    /// 获取当前请求的租户范围
+    /// 此函数应该在应用了RequireJWT中间件的路由处理程序中使用
+    pub fn scope(req: &HttpRequest) -> TenantScope {
+        if let Some(scope) = req.extensions().get::<TenantScope>() {
+            return *scope;
+        }
+        // 未经 RequireJWT 的请求只能访问默认租户
+        RequireJWT::extract_user_claims(req)
+            .map(|user| TenantScope::for_user(&user))
+            .unwrap_or(TenantScope::Org(None))
+    }
+
+    /// 判断当前请求能否访问属于 org_id 的资源
+    pub fn can_access(req: &HttpRequest, org_id: Option<i64>) -> bool {
+        Self::scope(req).allows(org_id)
+    }
+
+    /// 判断当前请求能否访问班级及其作业、提交与评分（按班级所属组织）
+    ///
+    /// 平台管理员不查询班级直接放行；班级不存在或查询失败时返回 false。
+    pub async fn can_access_class(
+        req: &HttpRequest,
+        storage: &Arc<dyn Storage>,
+        class_id: i64,
+    ) -> bool {
+        let scope = Self::scope(req);
+        if scope == TenantScope::All {
+            return true;
+        }
+        matches!(
+            storage.get_class_by_id(class_id).await,
+            Ok(Some(class)) if scope.allows(class.org_id)
+        )
+    }
+
+    /// 判断当前请求能否访问作业及其提交与评分（按作业所在班级的组织）
+    pub async fn can_access_homework(
+        req: &HttpRequest,
+        storage: &Arc<dyn Storage>,
+        homework_id: i64,
+    ) -> bool {
+        if Self::scope(req) == TenantScope::All {
+            return true;
+        }
+        match storage.get_homework_by_id(homework_id).await {
+            Ok(Some(homework)) => Self::can_access_class(req, storage, homework.class_id).await,
+            _ => false,
+        }
+    }
+
+    /// 校验组织是否存在且处于启用状态（带缓存）
+    pub async fn ensure_org_active(
+        storage: &Arc<dyn Storage>,
+        org_id: Option<i64>,
+    ) -> Result<(), String> {
+        let Some(org_id) = org_id else {
+            return Ok(());
+        };
+
+        let active = match ORG_ACTIVE_CACHE.get(&org_id).await {
+            Some(active) => active,
+            None => {
+                let active = storage
+                    .get_organization_by_id(org_id)
+                    .await
+                    .map_err(|_| "Failed to retrieve organization".to_string())?
+                    .map(|org| org.is_active)
+                    .unwrap_or(false);
+                ORG_ACTIVE_CACHE.insert(org_id, active).await;
+                active
+            }
+        };
+
+        if active {
+            Ok(())
+        } else {
+            Err("Organization is disabled".to_string())
+        }
+    }
+
+    /// 组织状态变更后清除缓存
+    pub async fn invalidate(org_id: i64) {
+        ORG_ACTIVE_CACHE.invalidate(&org_id).await;
+    }
+
+    /// 清除全部组织的启用状态缓存
+    pub fn invalidate_all() {
+        ORG_ACTIVE_CACHE.invalidate_all();
+    }
+}
+
+/// 仅允许平台管理员访问的中间件（配合 `middleware::from_fn` 使用）
+///
+/// 用于数据库备份、一致性修复等涉及全部租户数据的运维接口，组织管理员返回 403。
+pub async fn require_platform_admin<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let allowed = req
+        .extensions()
+        .get::<User>()
+        .is_some_and(User::is_platform_admin);
+    if !allowed {
+        return Ok(req.into_response(
+            create_error_response(
+                StatusCode::FORBIDDEN,
+                ErrorCode::PermissionDenied,
+                "仅平台管理员可执行此操作",
+            )
+            .map_into_right_body(),
+        ));
+    }
+    Ok(next.call(req).await?.map_into_left_body())
+}
@@ -0,0 +1,5 @@
+// 统计分析请求模型
+pub mod requests;
+
+// 统计分析响应模型
+pub mod responses;
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+// 统计区间查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/analytics.ts")]
+pub struct AnalyticsRangeParams {
+    // 起始日期（YYYY-MM-DD，含），默认结束日期前 29 天
+    pub from: Option<String>,
+    // 结束日期（YYYY-MM-DD，含），默认今天
+    pub to: Option<String>,
+}
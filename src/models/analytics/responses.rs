@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 单个班级在统计区间内的对比指标
+///
+/// 统计区间内布置（按作业创建时间）的作业参与计算；比率在分母为 0 时为 null。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/analytics.ts")]
+pub struct ClassComparisonItem {
+    pub class_id: i64,
+    pub class_name: String,
+    pub teacher_id: i64,
+    /// 学生人数（不含教师）
+    pub student_count: i64,
+    pub homework_count: i64,
+    /// 已提交的（学生, 作业）数
+    pub submitted_count: i64,
+    /// 提交率：已提交数 / (学生数 × 作业数)
+    pub submission_rate: Option<f64>,
+    /// 首次提交即迟交的数量
+    pub late_count: i64,
+    /// 迟交率：迟交数 / 已提交数
+    pub late_rate: Option<f64>,
+    pub graded_count: i64,
+    /// 平均得分率（百分比，按各作业满分折算）
+    pub average_score_percent: Option<f64>,
+    /// 平均批改耗时（小时，提交到评分）
+    pub average_grading_hours: Option<f64>,
+}
+
+/// 班级对比分析响应
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/analytics.ts")]
+pub struct ClassComparisonResponse {
+    pub from: String,
+    pub to: String,
+    /// 按提交率升序排列，提交率最低（最需关注）的班级在前
+    pub items: Vec<ClassComparisonItem>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 单个教师的批改时效
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/analytics.ts")]
+pub struct TeacherGradingLatencyItem {
+    pub teacher_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    /// 统计区间内完成的评分数
+    pub graded_count: i64,
+    /// 平均批改耗时（小时，提交到评分）
+    pub average_grading_hours: Option<f64>,
+    /// 当前超过批改时限仍未批改的提交数
+    pub overdue_count: i64,
+}
+
+/// 教师批改时效响应
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/analytics.ts")]
+pub struct GradingLatencyResponse {
+    pub from: String,
+    pub to: String,
+    /// 批改时限（天）
+    pub sla_days: i64,
+    /// 按超时数、平均耗时降序排列
+    pub items: Vec<TeacherGradingLatencyItem>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
@@ -12,6 +12,26 @@ pub struct LoginRequest {
     /// 是否记住我
     #[serde(default)]
     pub remember_me: bool,
+    /// 人机验证令牌，连续登录失败或策略要求时必填
+    #[serde(default)]
+    pub captcha_token: Option<String>,
+}
+
+// 自助注册请求（角色由注册策略决定，不接受客户端指定）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/auth.ts")]
+pub struct RegisterRequest {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// 班级邀请码，仅邀请制注册时必填
+    pub invite_code: Option<String>,
+    /// 人机验证令牌，注册策略要求验证时必填
+    pub captcha_token: Option<String>,
+    /// 学生发出的查看邀请令牌，填写时注册为家长/导师（viewer）账号
+    pub viewer_invitation: Option<String>,
 }
 
 // 用户自更新请求（普通用户修改自己的资料）
@@ -23,3 +43,11 @@ pub struct UpdateProfileRequest {
     pub password: Option<String>,
     pub avatar_url: Option<String>,
 }
+
+// 成绩单导出参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/auth.ts")]
+pub struct TranscriptParams {
+    /// 班级ID
+    pub class_id: i64,
+}
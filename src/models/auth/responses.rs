@@ -1,5 +1,5 @@
-use crate::models::users::entities::User;
-use serde::Serialize;
+use crate::models::users::entities::{GoalProgress, User};
+use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
 // 用户响应模型
@@ -30,3 +30,58 @@ pub struct UserInfoResponse {
 pub struct TokenVerificationResponse {
     pub is_valid: bool,
 }
+
+/// 即将截止的作业
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/auth.ts")]
+pub struct DashboardDeadline {
+    pub homework_id: i64,
+    pub class_id: i64,
+    pub class_name: String,
+    pub title: String,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    /// 是否已提交
+    pub submitted: bool,
+}
+
+/// 最近评分
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/auth.ts")]
+pub struct DashboardGrade {
+    pub homework_id: i64,
+    pub homework_title: String,
+    pub submission_id: i64,
+    pub score: f64,
+    pub max_score: f64,
+    pub graded_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 班级作业完成进度
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/auth.ts")]
+pub struct DashboardClassProgress {
+    pub class_id: i64,
+    pub class_name: String,
+    /// 应完成的作业数（不含被豁免的作业）
+    pub total_homeworks: i64,
+    /// 已提交的作业数
+    pub submitted_homeworks: i64,
+    /// 完成百分比（0-100），无作业时为 100
+    pub completion_percent: f64,
+}
+
+/// 学生首页仪表盘
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/auth.ts")]
+pub struct StudentDashboard {
+    /// 未来 7 天内截止的作业（按截止时间升序）
+    pub upcoming_deadlines: Vec<DashboardDeadline>,
+    /// 最近评分（按评分时间降序）
+    pub recent_grades: Vec<DashboardGrade>,
+    pub unread_notifications: i64,
+    pub class_progress: Vec<DashboardClassProgress>,
+    /// 学习目标进度（未设置目标的班级不出现）
+    #[serde(default)]
+    pub goals: Vec<GoalProgress>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
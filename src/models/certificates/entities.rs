@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 默认证书标题
+pub const DEFAULT_CERTIFICATE_TITLE: &str = "结业证书";
+
+/// 默认证书正文模板
+///
+/// 可用占位符：`{student_name}`、`{class_name}`、`{homework_count}`、`{average}`、`{issued_date}`
+pub const DEFAULT_CERTIFICATE_TEMPLATE: &str = "{student_name} 同学已完成「{class_name}」全部 {homework_count} 项作业，平均得分率 {average}%，成绩合格，特发此证。";
+
+/// 证书签名图片
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct CertificateSignature {
+    pub download_token: String,
+    pub original_name: String,
+}
+
+/// 班级结业证书设置
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct CertificateSettings {
+    pub class_id: i64,
+    pub enabled: bool,
+    /// 每项作业的最低得分率（百分比，0~100）
+    pub min_score_percent: f64,
+    pub title: String,
+    pub body_template: String,
+    pub signer_name: Option<String>,
+    #[serde(skip_serializing, default)]
+    #[ts(skip)]
+    pub signature_file_id: Option<i64>,
+    pub signature: Option<CertificateSignature>,
+    pub updated_by: i64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 已颁发的结业证书
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct Certificate {
+    pub id: i64,
+    pub class_id: i64,
+    pub user_id: i64,
+    pub verification_code: String,
+    /// 颁发时的平均得分率（百分比）
+    pub average_percent: f64,
+    /// 颁发时需完成的作业数
+    pub homework_count: i32,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 学生在班级中的作业完成进度
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct CertificateProgress {
+    /// 需完成的作业数（已排除豁免的作业）
+    pub required_count: i64,
+    /// 已评分的作业数
+    pub graded_count: i64,
+    /// 得分率达到门槛的作业数
+    pub passed_count: i64,
+    /// 已评分作业的平均得分率（百分比）
+    pub average_percent: f64,
+}
+
+impl CertificateProgress {
+    /// 是否满足颁发条件：所有需完成的作业均已评分且达到门槛
+    pub fn is_eligible(&self) -> bool {
+        self.required_count > 0 && self.passed_count == self.required_count
+    }
+}
@@ -0,0 +1,8 @@
+// 结业证书实体定义
+pub mod entities;
+
+// 结业证书请求模型
+pub mod requests;
+
+// 结业证书响应模型
+pub mod responses;
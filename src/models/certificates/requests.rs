@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+/// 更新班级结业证书设置请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct UpdateCertificateSettingsRequest {
+    pub enabled: bool,
+    /// 每项作业的最低得分率（百分比，0~100）
+    pub min_score_percent: f64,
+    pub title: Option<String>,
+    pub body_template: Option<String>,
+    pub signer_name: Option<String>,
+    /// 签名图片的文件下载令牌（JPEG 或不含透明通道的 PNG），为空表示不使用签名图片
+    pub signature: Option<String>,
+}
+
+/// 证书设置存储层参数
+#[derive(Debug, Clone)]
+pub struct CertificateSettingsInput {
+    pub enabled: bool,
+    pub min_score_percent: f64,
+    pub title: String,
+    pub body_template: String,
+    pub signer_name: Option<String>,
+    pub signature_file_id: Option<i64>,
+}
@@ -0,0 +1,28 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::entities::{Certificate, CertificateProgress};
+
+/// 学生证书状态响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct CertificateStatusResponse {
+    /// 班级是否启用结业证书
+    pub enabled: bool,
+    pub min_score_percent: f64,
+    pub progress: CertificateProgress,
+    pub certificate: Option<Certificate>,
+}
+
+/// 证书公开验证响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/certificate.ts")]
+pub struct CertificateVerifyResponse {
+    pub verification_code: String,
+    pub title: String,
+    pub student_name: String,
+    pub class_name: String,
+    pub average_percent: f64,
+    pub homework_count: i32,
+    pub issued_at: String,
+}
@@ -25,6 +25,24 @@ impl ClassUserRole {
     pub fn all_roles() -> &'static [&'static ClassUserRole] {
         &[&Self::Student, &Self::ClassRepresentative, &Self::Teacher]
     }
+
+    /// 权限高低（教师 > 课代表 > 学生），合并重复账号时保留较高的角色
+    pub fn rank(&self) -> u8 {
+        match self {
+            Self::Student => 0,
+            Self::ClassRepresentative => 1,
+            Self::Teacher => 2,
+        }
+    }
+
+    /// 内置显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Student => "学生",
+            Self::ClassRepresentative => "课代表",
+            Self::Teacher => "教师",
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for ClassUserRole {
@@ -67,6 +85,33 @@ impl std::str::FromStr for ClassUserRole {
     }
 }
 
+/// 班级成员批量操作
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub enum ClassUserBatchAction {
+    SetRole, // 批量修改角色
+    Remove,  // 批量移出班级
+}
+
+impl std::fmt::Display for ClassUserBatchAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClassUserBatchAction::SetRole => write!(f, "set_role"),
+            ClassUserBatchAction::Remove => write!(f, "remove"),
+        }
+    }
+}
+
+/// 批量操作的执行结果
+#[derive(Debug, Clone, Default)]
+pub struct ClassUserBatchOutcome {
+    // 不是班级成员的用户
+    pub missing: Vec<i64>,
+    // 实际被修改或移出的用户（角色本就相同的成员不在其中）
+    pub changed: Vec<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
 pub struct ClassUser {
@@ -74,5 +119,93 @@ pub struct ClassUser {
     pub class_id: i64,
     pub user_id: i64,
     pub role: ClassUserRole,
+    // 班级内姓名，设置后在班级内代替全局显示名
+    pub profile_name: Option<String>,
+    // 班级自定义角色名称标识，未设置时按角色显示默认名称
+    pub role_label: Option<String>,
     pub joined_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// 班级自定义角色名称
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct ClassRoleLabel {
+    // 名称标识（小写字母、数字、下划线）
+    pub key: String,
+    // 显示名称，如「助教」
+    pub label: String,
+    // 对应的内置角色，权限按该角色校验
+    pub role: ClassUserRole,
+}
+
+/// 班级角色名称配置
+///
+/// 每个名称映射到一个内置角色，权限始终按内置角色在服务端校验。同一角色的第一个名称即该角色的
+/// 默认显示名称；成员可单独指定同角色的其他名称。未配置的班级使用内置名称（学生 / 课代表 / 教师）。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct ClassRoleLabels {
+    pub labels: Vec<ClassRoleLabel>,
+}
+
+impl ClassRoleLabels {
+    pub const MAX_LABELS: usize = 20;
+    pub const MAX_KEY_LENGTH: usize = 32;
+    pub const MAX_LABEL_LENGTH: usize = 32;
+
+    /// 是否为默认配置
+    pub fn is_default(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// 按标识查找名称
+    pub fn find(&self, key: &str) -> Option<&ClassRoleLabel> {
+        self.labels.iter().find(|l| l.key == key)
+    }
+
+    /// 成员的显示名称：成员指定的同角色名称优先，其次为该角色的默认名称，最后为内置名称
+    pub fn display_name(&self, role: &ClassUserRole, role_label: Option<&str>) -> String {
+        role_label
+            .and_then(|key| self.find(key))
+            .filter(|l| &l.role == role)
+            .or_else(|| self.labels.iter().find(|l| &l.role == role))
+            .map(|l| l.label.clone())
+            .unwrap_or_else(|| role.display_name().to_string())
+    }
+
+    /// 校验配置，返回错误说明
+    pub fn validate(&self) -> Result<(), String> {
+        if self.labels.len() > Self::MAX_LABELS {
+            return Err(format!("角色名称不能超过 {} 个", Self::MAX_LABELS));
+        }
+
+        let mut keys = std::collections::HashSet::new();
+        for label in &self.labels {
+            let valid_key = !label.key.is_empty()
+                && label.key.len() <= Self::MAX_KEY_LENGTH
+                && label
+                    .key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if !valid_key {
+                return Err(format!(
+                    "名称标识 '{}' 无效：须为 1-{} 位小写字母、数字或下划线",
+                    label.key,
+                    Self::MAX_KEY_LENGTH
+                ));
+            }
+            if !keys.insert(label.key.as_str()) {
+                return Err(format!("名称标识 '{}' 重复", label.key));
+            }
+            let label_length = label.label.chars().count();
+            if label_length == 0 || label_length > Self::MAX_LABEL_LENGTH {
+                return Err(format!(
+                    "角色名称 '{}' 的显示名称须为 1-{} 个字符",
+                    label.key,
+                    Self::MAX_LABEL_LENGTH
+                ));
+            }
+        }
+        Ok(())
+    }
+}
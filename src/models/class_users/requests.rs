@@ -1,4 +1,7 @@
-use crate::models::{class_users::entities::ClassUserRole, common::PaginationQuery};
+use crate::models::{
+    class_users::entities::{ClassRoleLabel, ClassUserBatchAction, ClassUserRole},
+    common::PaginationQuery,
+};
 use serde::Deserialize;
 use ts_rs::TS;
 
@@ -7,12 +10,37 @@ use ts_rs::TS;
 #[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
 pub struct JoinClassRequest {
     pub invite_code: String,
+    // 班级内姓名，班级要求实名时必填
+    pub profile_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
 pub struct UpdateClassUserRequest {
-    pub role: Option<ClassUserRole>, // 更新用户角色
+    pub role: Option<ClassUserRole>,  // 更新用户角色
+    pub profile_name: Option<String>, // 更新班级内姓名，传空字符串清除
+    pub role_label: Option<String>, // 指定班级自定义角色名称，角色随之变为名称对应的角色；传空字符串清除
+}
+
+/// 班级成员批量操作请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct BatchClassUsersRequest {
+    pub action: ClassUserBatchAction,
+    pub user_ids: Vec<i64>,
+    // `set_role` 时必填
+    pub role: Option<ClassUserRole>,
+}
+
+impl BatchClassUsersRequest {
+    pub const MAX_USERS: usize = 200;
+}
+
+/// 更新班级角色名称请求（传空数组恢复内置名称）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct UpdateClassRoleLabelsRequest {
+    pub labels: Vec<ClassRoleLabel>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -3,7 +3,7 @@ use ts_rs::TS;
 
 use crate::models::{
     PaginationInfo,
-    class_users::entities::{ClassUser, ClassUserRole},
+    class_users::entities::{ClassRoleLabel, ClassUser, ClassUserBatchAction, ClassUserRole},
 };
 
 /// 用户简要信息
@@ -24,6 +24,12 @@ pub struct ClassUserDetail {
     pub class_id: i64,
     pub user_id: i64,
     pub role: ClassUserRole,
+    /// 班级内姓名（设置后 `user.display_name` 即为该姓名）
+    pub profile_name: Option<String>,
+    /// 班级自定义角色名称标识
+    pub role_label: Option<String>,
+    /// 角色显示名称（已按班级角色名称配置解析）
+    pub role_name: String,
     pub joined_at: chrono::DateTime<chrono::Utc>,
     pub user: UserInfo,
 }
@@ -43,3 +49,43 @@ pub struct ClassUserDetailListResponse {
     pub pagination: PaginationInfo,
     pub items: Vec<ClassUserDetail>,
 }
+
+/// 内置角色的显示名称
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct ClassRoleNameItem {
+    pub role: ClassUserRole,
+    pub name: String,
+}
+
+/// 班级角色名称配置响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct ClassRoleLabelsResponse {
+    pub class_id: i64,
+    pub customized: bool,
+    pub labels: Vec<ClassRoleLabel>,
+    // 各内置角色当前的默认显示名称
+    pub role_names: Vec<ClassRoleNameItem>,
+}
+
+/// 批量操作中单个用户的结果
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct ClassUserBatchItem {
+    pub user_id: i64,
+    pub success: bool,
+    // 失败原因
+    pub error: Option<String>,
+}
+
+/// 班级成员批量操作响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class-user.ts")]
+pub struct BatchClassUsersResponse {
+    pub class_id: i64,
+    pub action: ClassUserBatchAction,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub items: Vec<ClassUserBatchItem>,
+}
@@ -14,8 +14,723 @@ pub struct Class {
     pub teacher_id: i64,
     // 邀请码
     pub invite_code: String,
+    // 所属组织
+    pub org_id: Option<i64>,
+    // 归档时间（未归档为空）
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    // 是否允许学生之间互发私信
+    pub allow_student_messages: bool,
+    // 是否要求学生加入时填写班级内姓名
+    pub require_real_name: bool,
+    // 班级内姓名须匹配的正则（如学号+姓名），为空表示不限制格式
+    pub profile_name_pattern: Option<String>,
     // 创建时间
     pub created_at: chrono::DateTime<chrono::Utc>,
     // 更新时间
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// 班级每日统计快照（截至当天结束时的累计值）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassStatsDaily {
+    // 统计日期（YYYY-MM-DD，UTC）
+    pub stat_date: String,
+    // 班级成员数（含教师）
+    pub member_count: i64,
+    // 学生数（含课代表）
+    pub student_count: i64,
+    // 累计提交数（含重交版本）
+    pub submission_count: i64,
+    // 累计评分数
+    pub graded_count: i64,
+    // 平均得分率（0-100，尚无评分为空）
+    pub average_score_percent: Option<f64>,
+}
+
+/// 班级学生某一天的作业负荷（聚合数据，不含具体学生）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassWorkloadDay {
+    // 日期（YYYY-MM-DD，UTC）
+    pub date: String,
+    // 当天截止的作业数（本班学生所在的全部班级）
+    pub homework_count: i64,
+    // 当天有作业截止的本班学生数
+    pub students_with_due: i64,
+    // 单个学生当天最多的截止作业数
+    pub max_per_student: i64,
+}
+
+/// 单个作业的用时与难度校准（教师预估与学生反馈的对比，聚合数据，不含具体学生）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct HomeworkCalibration {
+    pub homework_id: i64,
+    pub title: String,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    // 教师预计用时（分钟）
+    pub estimated_minutes: Option<i32>,
+    // 教师预设难度（1-5）
+    pub difficulty: Option<i32>,
+    // 学生反馈条数
+    pub feedback_count: i64,
+    // 学生实际用时的平均值与中位数（分钟）
+    pub average_actual_minutes: Option<f64>,
+    pub median_actual_minutes: Option<f64>,
+    // 学生体感难度平均值
+    pub average_perceived_difficulty: Option<f64>,
+    // 实际平均用时 / 预计用时，大于 1 表示预估偏少
+    pub time_ratio: Option<f64>,
+    // 平均体感难度 - 预设难度，大于 0 表示预设偏易
+    pub difficulty_delta: Option<f64>,
+}
+
+/// 排行榜排名依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum LeaderboardMetric {
+    /// 各作业得分率的平均值（百分比）
+    AveragePercent,
+    /// 各作业得分之和
+    TotalScore,
+}
+
+impl std::fmt::Display for LeaderboardMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderboardMetric::AveragePercent => write!(f, "average_percent"),
+            LeaderboardMetric::TotalScore => write!(f, "total_score"),
+        }
+    }
+}
+
+impl std::str::FromStr for LeaderboardMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "average_percent" => Ok(LeaderboardMetric::AveragePercent),
+            "total_score" => Ok(LeaderboardMetric::TotalScore),
+            _ => Err(format!("Invalid leaderboard metric: {s}")),
+        }
+    }
+}
+
+/// 班级排行榜设置
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassLeaderboardSettings {
+    pub class_id: i64,
+    pub enabled: bool,
+    /// 向学生展示的名次数
+    pub top_n: i32,
+    pub metric: LeaderboardMetric,
+    pub updated_by: i64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 学生在班级中的成绩汇总（排行榜计算用，含实名，不直接返回给学生）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardStanding {
+    pub user_id: i64,
+    /// 班级内姓名，未填写时为昵称或用户名
+    pub name: String,
+    /// 是否选择在排行榜中显示姓名
+    pub opted_in: bool,
+    /// 计入的作业数
+    pub homework_count: i64,
+    pub total_score: f64,
+    pub average_percent: f64,
+}
+
+/// IM 机器人平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ImProvider {
+    /// 企业微信群机器人（target 为 Webhook 地址）
+    Wecom,
+    /// 钉钉群机器人（target 为 Webhook 地址，secret 为加签密钥，可选）
+    Dingtalk,
+    /// Telegram Bot（target 为 chat_id，secret 为 Bot Token）
+    Telegram,
+}
+
+impl ImProvider {
+    /// 该平台是否必须提供 secret
+    pub fn requires_secret(&self) -> bool {
+        matches!(self, ImProvider::Telegram)
+    }
+}
+
+impl std::fmt::Display for ImProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImProvider::Wecom => write!(f, "wecom"),
+            ImProvider::Dingtalk => write!(f, "dingtalk"),
+            ImProvider::Telegram => write!(f, "telegram"),
+        }
+    }
+}
+
+impl std::str::FromStr for ImProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wecom" => Ok(ImProvider::Wecom),
+            "dingtalk" => Ok(ImProvider::Dingtalk),
+            "telegram" => Ok(ImProvider::Telegram),
+            _ => Err(format!("Invalid IM provider: {s}")),
+        }
+    }
+}
+
+/// 可转发到 IM 群的班级事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ImEvent {
+    /// 新作业发布
+    HomeworkCreated,
+    /// 作业即将截止
+    HomeworkDeadline,
+}
+
+impl std::fmt::Display for ImEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImEvent::HomeworkCreated => write!(f, "homework_created"),
+            ImEvent::HomeworkDeadline => write!(f, "homework_deadline"),
+        }
+    }
+}
+
+impl std::str::FromStr for ImEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "homework_created" => Ok(ImEvent::HomeworkCreated),
+            "homework_deadline" => Ok(ImEvent::HomeworkDeadline),
+            _ => Err(format!("Invalid IM event: {s}")),
+        }
+    }
+}
+
+/// 班级 IM 通知渠道（包含密钥，仅供服务端使用，对外返回 `ClassImChannelItem`）
+#[derive(Debug, Clone)]
+pub struct ClassImChannel {
+    pub id: i64,
+    pub class_id: i64,
+    pub name: String,
+    pub provider: ImProvider,
+    // Webhook 地址或 Telegram chat_id
+    pub target: String,
+    // 钉钉加签密钥或 Telegram Bot Token
+    pub secret: Option<String>,
+    pub events: Vec<ImEvent>,
+    // 按事件自定义的消息模板，未设置的事件使用默认模板
+    pub templates: std::collections::HashMap<ImEvent, String>,
+    pub enabled: bool,
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 定时导出的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ScheduledExportKind {
+    /// 班级成绩报告（XLSX，与手动导出相同）
+    ClassReport,
+}
+
+impl std::fmt::Display for ScheduledExportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduledExportKind::ClassReport => write!(f, "class_report"),
+        }
+    }
+}
+
+impl std::str::FromStr for ScheduledExportKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "class_report" => Ok(ScheduledExportKind::ClassReport),
+            _ => Err(format!("Invalid scheduled export kind: {s}")),
+        }
+    }
+}
+
+/// 定时导出的执行频率
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ScheduledExportFrequency {
+    /// 每天在指定整点执行
+    Daily,
+    /// 每周在指定星期的整点执行
+    Weekly,
+}
+
+impl ScheduledExportFrequency {
+    /// `after` 之后的下一次执行时间
+    ///
+    /// `weekday` 为 0（周一）到 6（周日），仅 weekly 使用；`hour` 为 UTC 整点。
+    pub fn next_run(
+        &self,
+        weekday: Option<i32>,
+        hour: i32,
+        after: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        use chrono::{Datelike, Duration, TimeZone, Utc};
+
+        let at_hour = |date: chrono::NaiveDate| {
+            Utc.from_utc_datetime(&date.and_hms_opt(hour.clamp(0, 23) as u32, 0, 0).unwrap())
+        };
+        let today = after.date_naive();
+        match self {
+            ScheduledExportFrequency::Daily => {
+                let candidate = at_hour(today);
+                if candidate > after {
+                    candidate
+                } else {
+                    candidate + Duration::days(1)
+                }
+            }
+            ScheduledExportFrequency::Weekly => {
+                let target = weekday.unwrap_or(0).clamp(0, 6) as i64;
+                let current = today.weekday().num_days_from_monday() as i64;
+                let candidate = at_hour(today + Duration::days((target - current).rem_euclid(7)));
+                if candidate > after {
+                    candidate
+                } else {
+                    candidate + Duration::days(7)
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ScheduledExportFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduledExportFrequency::Daily => write!(f, "daily"),
+            ScheduledExportFrequency::Weekly => write!(f, "weekly"),
+        }
+    }
+}
+
+impl std::str::FromStr for ScheduledExportFrequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(ScheduledExportFrequency::Daily),
+            "weekly" => Ok(ScheduledExportFrequency::Weekly),
+            _ => Err(format!("Invalid scheduled export frequency: {s}")),
+        }
+    }
+}
+
+/// 定时导出的投递方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ScheduledExportDelivery {
+    /// 通过邮件中继以附件发送（target 为邮箱地址）
+    Email,
+    /// 以 multipart 表单 POST 到 Webhook（target 为 https 地址）
+    Webhook,
+}
+
+impl std::fmt::Display for ScheduledExportDelivery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduledExportDelivery::Email => write!(f, "email"),
+            ScheduledExportDelivery::Webhook => write!(f, "webhook"),
+        }
+    }
+}
+
+impl std::str::FromStr for ScheduledExportDelivery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(ScheduledExportDelivery::Email),
+            "webhook" => Ok(ScheduledExportDelivery::Webhook),
+            _ => Err(format!("Invalid scheduled export delivery: {s}")),
+        }
+    }
+}
+
+/// 班级定时导出
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassScheduledExport {
+    pub id: i64,
+    pub class_id: i64,
+    pub kind: ScheduledExportKind,
+    pub frequency: ScheduledExportFrequency,
+    // 0 = 周一，仅 weekly 使用
+    pub weekday: Option<i32>,
+    // UTC 整点
+    pub hour: i32,
+    pub delivery: ScheduledExportDelivery,
+    // 邮箱地址或 Webhook 地址
+    pub target: String,
+    pub enabled: bool,
+    pub next_run_at: chrono::DateTime<chrono::Utc>,
+    pub last_run_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_error: Option<String>,
+    // 连续失败次数，达到上限后自动停用
+    pub consecutive_failures: i32,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 班级 API 令牌（不含令牌本身，令牌只在创建时返回一次）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassApiToken {
+    pub id: i64,
+    pub class_id: i64,
+    pub name: String,
+    // 令牌开头几位，便于辨认
+    pub token_prefix: String,
+    // 只读令牌只能发起 GET 请求
+    pub read_only: bool,
+    // 令牌以创建者的身份访问接口
+    pub created_by: i64,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ClassApiToken {
+    /// 未撤销且未过期
+    pub fn is_active(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// 新的班级 API 令牌
+#[derive(Debug, Clone)]
+pub struct NewClassApiToken {
+    pub class_id: i64,
+    pub name: String,
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub read_only: bool,
+    pub created_by: i64,
+    pub expires_at: Option<i64>,
+}
+
+/// 班级 API 令牌发起的写请求记录
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassApiTokenLog {
+    pub id: i64,
+    pub token_id: i64,
+    pub method: String,
+    pub path: String,
+    // 响应状态码
+    pub status: i32,
+    pub ip_address: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// IM 消息投递状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ImDeliveryStatus {
+    /// 等待投递（含等待重试）
+    Pending,
+    /// 已送达
+    Delivered,
+    /// 重试次数耗尽
+    Failed,
+}
+
+impl std::fmt::Display for ImDeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImDeliveryStatus::Pending => write!(f, "pending"),
+            ImDeliveryStatus::Delivered => write!(f, "delivered"),
+            ImDeliveryStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// 待投递的 IM 消息
+#[derive(Debug, Clone)]
+pub struct ImDelivery {
+    pub id: i64,
+    pub channel_id: i64,
+    pub event: String,
+    pub reference_id: i64,
+    pub message: String,
+    pub attempts: i32,
+}
+
+/// 新的 IM 投递任务
+#[derive(Debug, Clone)]
+pub struct NewImDelivery {
+    pub channel_id: i64,
+    pub event: ImEvent,
+    // 关联实体 ID（作业 ID），与渠道和事件一起去重
+    pub reference_id: i64,
+    pub message: String,
+}
+
+/// 班级动态类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub enum ActivityEventType {
+    /// 发布作业
+    HomeworkCreated,
+    /// 班级公告
+    Announcement,
+    /// 作业发布了评分
+    GradeReleased,
+    /// 新成员加入
+    MemberJoined,
+}
+
+impl std::fmt::Display for ActivityEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActivityEventType::HomeworkCreated => write!(f, "homework_created"),
+            ActivityEventType::Announcement => write!(f, "announcement"),
+            ActivityEventType::GradeReleased => write!(f, "grade_released"),
+            ActivityEventType::MemberJoined => write!(f, "member_joined"),
+        }
+    }
+}
+
+impl std::str::FromStr for ActivityEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "homework_created" => Ok(ActivityEventType::HomeworkCreated),
+            "announcement" => Ok(ActivityEventType::Announcement),
+            "grade_released" => Ok(ActivityEventType::GradeReleased),
+            "member_joined" => Ok(ActivityEventType::MemberJoined),
+            _ => Err(format!("Invalid activity event type: {s}")),
+        }
+    }
+}
+
+/// 班级动态
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ActivityEvent {
+    pub id: i64,
+    pub class_id: i64,
+    pub event_type: ActivityEventType,
+    // 操作者（发布人、评分教师或加入的成员），用户删除后为空
+    pub actor_id: Option<i64>,
+    // 关联实体 ID（作业 ID；公告与成员加入为空）
+    pub reference_id: Option<i64>,
+    // 作业标题、公告标题或成员名称
+    pub title: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    // 表情回应（仅班级公告）
+    #[serde(default)]
+    pub reactions: crate::models::reactions::entities::ReactionSummary,
+}
+
+/// 新的班级动态
+#[derive(Debug, Clone)]
+pub struct NewActivityEvent {
+    pub class_id: i64,
+    pub event_type: ActivityEventType,
+    pub actor_id: Option<i64>,
+    pub reference_id: Option<i64>,
+    pub title: String,
+}
+
+/// 班级导出包格式版本，导入时拒绝更高版本的导出包
+pub const CLASS_BUNDLE_VERSION: u32 = 1;
+
+/// 班级导出包（zip 包中的 `bundle.json`）
+///
+/// 附件以 `files/{文件 ID}/{原始文件名}` 存放在同一 zip 包中，`path` 字段引用该路径。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundle {
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub class: ClassBundleClass,
+    #[serde(default)]
+    pub homeworks: Vec<ClassBundleHomework>,
+    #[serde(default)]
+    pub announcements: Vec<ClassBundleAnnouncement>,
+    // 是否包含学生提交
+    #[serde(default)]
+    pub include_submissions: bool,
+}
+
+/// 导出包中的班级信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundleClass {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// 导出包中的附件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassBundleFile {
+    pub path: String,
+    pub original_name: String,
+    pub file_size: i64,
+    // 作业附件的用途（提交附件为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<crate::models::homeworks::entities::AttachmentKind>,
+}
+
+/// 导出包中的作业
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundleHomework {
+    pub title: String,
+    pub description: Option<String>,
+    pub max_score: f64,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    pub allow_late: bool,
+    pub submission_mode: crate::models::homeworks::entities::SubmissionMode,
+    pub max_content_length: Option<i32>,
+    #[serde(default)]
+    pub exam_mode: bool,
+    #[serde(default)]
+    pub require_self_assessment: bool,
+    #[serde(default)]
+    pub show_grade_context: bool,
+    #[serde(default)]
+    pub estimated_minutes: Option<i32>,
+    #[serde(default)]
+    pub difficulty: Option<i32>,
+    #[serde(default)]
+    pub grace_minutes: Option<i32>,
+    #[serde(default)]
+    pub attachments: Vec<ClassBundleFile>,
+    // 分题（评分细则），按顺序排列
+    #[serde(default)]
+    pub parts: Vec<ClassBundlePart>,
+    pub solution: Option<ClassBundleSolution>,
+    #[serde(default)]
+    pub submissions: Vec<ClassBundleSubmission>,
+}
+
+/// 导出包中的分题
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundlePart {
+    pub title: String,
+    pub description: Option<String>,
+    pub max_score: f64,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 导出包中的参考答案
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundleSolution {
+    pub content: String,
+    pub reveal_policy: crate::models::homeworks::entities::SolutionRevealPolicy,
+}
+
+/// 导出包中的班级公告
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundleAnnouncement {
+    pub title: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 导出包中的学生提交（按用户名关联目标部署中的用户）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundleSubmission {
+    pub username: String,
+    // 所属分题在 `parts` 中的序号（从 1 开始）
+    pub part_position: Option<i32>,
+    pub version: i32,
+    pub content: String,
+    pub status: String,
+    pub is_late: bool,
+    pub submitted_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub attachments: Vec<ClassBundleFile>,
+    pub grade: Option<ClassBundleGrade>,
+}
+
+/// 导出包中的评分
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassBundleGrade {
+    pub score: f64,
+    pub comment: Option<String>,
+    pub graded_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 某学生在班级内的数据擦除范围（各项为受影响的行数）
+///
+/// 预览与实际擦除使用同一份统计，确认令牌据此签名，数据在预览后发生变化时需重新预览。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassErasureCounts {
+    /// 本班作业下的全部提交版本
+    pub submissions: u64,
+    /// 上述提交的评分（含评语）与评分草稿
+    pub grades: u64,
+    pub grade_drafts: u64,
+    /// 提交附件关联（文件本身由孤儿文件清理任务回收）
+    pub submission_files: u64,
+    pub submission_references: u64,
+    pub self_assessments: u64,
+    pub resubmission_requests: u64,
+    pub exam_access_logs: u64,
+    pub homework_feedback: u64,
+    /// 本班内该学生发出或收到的私信
+    pub messages: u64,
+    pub moderation_flags: u64,
+    /// 该学生提交的举报与针对其内容的举报
+    pub content_reports: u64,
+    /// 该学生触发的班级动态
+    pub activity_events: u64,
+    pub student_goals: u64,
+    pub leaderboard_opt_ins: u64,
+}
+
+impl ClassErasureCounts {
+    /// 受影响的总行数
+    pub fn total(&self) -> u64 {
+        [
+            self.submissions,
+            self.grades,
+            self.grade_drafts,
+            self.submission_files,
+            self.submission_references,
+            self.self_assessments,
+            self.resubmission_requests,
+            self.exam_access_logs,
+            self.homework_feedback,
+            self.messages,
+            self.moderation_flags,
+            self.content_reports,
+            self.activity_events,
+            self.student_goals,
+            self.leaderboard_opt_ins,
+        ]
+        .iter()
+        .sum()
+    }
+}
@@ -1,5 +1,12 @@
+use super::entities::{
+    ActivityEventType, ImEvent, ImProvider, LeaderboardMetric, ScheduledExportDelivery,
+    ScheduledExportFrequency, ScheduledExportKind,
+};
+use crate::models::class_users::entities::ClassUserRole;
 use crate::models::common::PaginationQuery;
+use crate::models::organizations::entities::TenantScope;
 use serde::Deserialize;
+use std::collections::HashMap;
 use ts_rs::TS;
 
 // 班级查询参数（来自HTTP请求）
@@ -35,6 +42,14 @@ pub struct CreateClassRequest {
 pub struct UpdateClassRequest {
     pub name: Option<String>,
     pub description: Option<String>,
+    // 归档或取消归档班级，归档后开始计算附件保留期
+    pub archived: Option<bool>,
+    // 是否允许学生之间互发私信
+    pub allow_student_messages: Option<bool>,
+    // 是否要求学生加入时填写班级内姓名
+    pub require_real_name: Option<bool>,
+    // 班级内姓名格式正则，传空字符串清除
+    pub profile_name_pattern: Option<String>,
     #[ts(skip)]
     pub _teacher_id: Option<i64>, // TODO: 未来计划实现班级转让
 }
@@ -47,4 +62,185 @@ pub struct ClassListQuery {
     pub size: Option<i64>,
     pub teacher_id: Option<i64>,
     pub search: Option<String>,
+    // 租户范围，由服务层根据当前用户填充
+    #[serde(skip)]
+    #[ts(skip)]
+    pub tenant: TenantScope,
+}
+
+// 班级活跃度报告查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassActivityReportParams {
+    /// 统计最近 N 天的提交（默认 14，最大 365）
+    pub days: Option<i64>,
+    /// 导出格式：csv / xlsx；不填时返回 JSON
+    pub format: Option<String>,
+}
+
+// 班级统计趋势查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassStatsHistoryParams {
+    /// 返回最近 N 天的快照（默认 30，最大 365）
+    pub days: Option<i64>,
+}
+
+// 班级作业负荷查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassWorkloadParams {
+    /// 从今天起统计 N 周（默认 4，最大 12）
+    pub weeks: Option<i64>,
+}
+
+// 班级动态流查询参数（按 ID 倒序游标分页）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassFeedQuery {
+    /// 只返回 ID 小于该值的动态，用于向前翻页
+    pub before_id: Option<i64>,
+    pub size: Option<i64>,
+    /// 只返回指定类型的动态
+    pub event_type: Option<ActivityEventType>,
+}
+
+// 创建班级 IM 通知渠道请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct CreateClassImChannelRequest {
+    pub name: String,
+    pub provider: ImProvider,
+    /// Webhook 地址（企业微信/钉钉）或 chat_id（Telegram）
+    pub target: String,
+    /// 钉钉加签密钥或 Telegram Bot Token
+    pub secret: Option<String>,
+    pub events: Vec<ImEvent>,
+    /// 按事件自定义消息模板
+    #[serde(default)]
+    pub templates: HashMap<ImEvent, String>,
+    pub enabled: Option<bool>,
+}
+
+// 更新班级 IM 通知渠道请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct UpdateClassImChannelRequest {
+    pub name: Option<String>,
+    pub target: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<ImEvent>>,
+    pub templates: Option<HashMap<ImEvent, String>>,
+    pub enabled: Option<bool>,
+}
+
+// 创建班级定时导出请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct CreateClassScheduledExportRequest {
+    /// 导出内容，默认 class_report
+    pub kind: Option<ScheduledExportKind>,
+    pub frequency: ScheduledExportFrequency,
+    /// 0（周一）到 6（周日），weekly 必填
+    pub weekday: Option<i32>,
+    /// UTC 整点（0-23）
+    pub hour: i32,
+    pub delivery: ScheduledExportDelivery,
+    /// 邮箱地址（email）或 https Webhook 地址（webhook）
+    pub target: String,
+    pub enabled: Option<bool>,
+}
+
+// 更新班级定时导出请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct UpdateClassScheduledExportRequest {
+    pub frequency: Option<ScheduledExportFrequency>,
+    pub weekday: Option<i32>,
+    pub hour: Option<i32>,
+    pub delivery: Option<ScheduledExportDelivery>,
+    pub target: Option<String>,
+    /// 重新启用时清零连续失败次数
+    pub enabled: Option<bool>,
+}
+
+// 创建班级 API 令牌请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct CreateClassApiTokenRequest {
+    pub name: String,
+    /// 是否只读，默认 true
+    pub read_only: Option<bool>,
+    /// 过期时间，不传表示长期有效
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// 使用班级短码加入班级请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct JoinClassByShortCodeRequest {
+    pub code: String,
+    // 班级内姓名，班级要求实名时必填
+    pub profile_name: Option<String>,
+}
+
+// 班级导出包查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassBundleExportParams {
+    /// 是否包含学生提交与评分（默认 false）
+    pub include_submissions: Option<bool>,
+}
+
+// 班级名单导出查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassRosterExportParams {
+    /// 导出格式：pdf（默认，用于打印）/ xlsx
+    pub format: Option<String>,
+    /// 附加列，逗号分隔：email / display_name / last_login / signature / remark
+    pub columns: Option<String>,
+    /// 只导出指定班级角色的成员（如只导出学生作为签到表）
+    pub role: Option<ClassUserRole>,
+}
+
+// 班级导入包查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassBundleImportParams {
+    /// 班级教师，规则与创建班级相同（管理员必填）
+    pub teacher_id: Option<i64>,
+    /// 新班级名称，不传时沿用导出包中的名称
+    pub name: Option<String>,
+}
+
+/// 更新班级排行榜设置请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct UpdateClassLeaderboardRequest {
+    pub enabled: bool,
+    /// 向学生展示的名次数（1-100），默认 10
+    #[ts(optional)]
+    pub top_n: Option<i32>,
+    /// 排名依据，默认 average_percent
+    #[ts(optional)]
+    pub metric: Option<LeaderboardMetric>,
+}
+
+/// 设置本人是否在排行榜中显示姓名
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct LeaderboardOptInRequest {
+    pub opt_in: bool,
+}
+
+/// 执行学生数据擦除请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassErasureRequest {
+    /// 预览时返回的确认令牌
+    pub confirm_token: String,
+    /// 擦除原因（记入审计日志）
+    #[ts(optional)]
+    pub reason: Option<String>,
 }
@@ -1,7 +1,12 @@
-use super::entities::Class;
+use super::entities::{
+    ActivityEvent, Class, ClassApiToken, ClassApiTokenLog, ClassErasureCounts, ClassImChannel,
+    ClassScheduledExport, ClassStatsDaily, ClassWorkloadDay, HomeworkCalibration, ImEvent,
+    ImProvider, LeaderboardMetric,
+};
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::common::PaginationInfo;
 use serde::Serialize;
+use std::collections::HashMap;
 use ts_rs::TS;
 
 /// 教师简要信息
@@ -40,3 +45,264 @@ pub struct ClassDetailListResponse {
     pub pagination: PaginationInfo,
     pub items: Vec<ClassDetail>,
 }
+
+/// 学生活跃度
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct StudentActivity {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: ClassUserRole,
+    pub last_login: Option<chrono::DateTime<chrono::Utc>>,
+    /// 统计窗口内的提交次数
+    pub recent_submissions: i64,
+    /// 已提交的作业数（有截止时间的作业，按最新版本统计）
+    pub submitted_homeworks: i64,
+    /// 其中迟交的作业数
+    pub late_homeworks: i64,
+    /// 平均迟交时长（小时，按时提交计 0），无可统计作业时为空
+    pub avg_lateness_hours: Option<f64>,
+    /// 与本班相关的未读通知数
+    pub unread_notifications: i64,
+}
+
+/// 班级活跃度报告
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassActivityReport {
+    pub class_id: i64,
+    pub days: i64,
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    /// 按活跃度升序排列（最不活跃的学生在前）
+    pub items: Vec<StudentActivity>,
+}
+
+/// 班级统计趋势
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassStatsHistoryResponse {
+    pub class_id: i64,
+    pub days: i64,
+    /// 按日期升序排列；快照尚未生成的日期（包括当天）没有数据点
+    pub items: Vec<ClassStatsDaily>,
+}
+
+/// 班级学生作业负荷热力图
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassWorkloadResponse {
+    pub class_id: i64,
+    pub weeks: i64,
+    /// 从今天起按日期升序，没有作业截止的日期计数为 0
+    pub items: Vec<ClassWorkloadDay>,
+}
+
+/// 班级作业用时与难度校准报告
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassWorkloadCalibrationResponse {
+    pub class_id: i64,
+    pub summary: WorkloadCalibrationSummary,
+    /// 按截止时间升序，未设置截止时间的作业排在最后
+    pub items: Vec<HomeworkCalibration>,
+}
+
+/// 校准报告汇总（只统计同时设置了预估且收到反馈的作业）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct WorkloadCalibrationSummary {
+    /// 参与统计的作业数
+    pub homework_count: i64,
+    /// 全部作业收到的反馈条数
+    pub feedback_count: i64,
+    /// 各作业用时比的平均值
+    pub average_time_ratio: Option<f64>,
+    /// 各作业难度差的平均值
+    pub average_difficulty_delta: Option<f64>,
+    /// 用时比高于阈值（预估偏少）的作业数
+    pub underestimated_count: i64,
+    /// 用时比低于阈值（预估偏多）的作业数
+    pub overestimated_count: i64,
+}
+
+/// 班级动态流（新动态在前）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassFeedResponse {
+    pub class_id: i64,
+    pub items: Vec<ActivityEvent>,
+    /// 是否还有更早的动态
+    pub has_more: bool,
+}
+
+/// 班级 IM 通知渠道（不返回密钥，Webhook 地址脱敏）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassImChannelItem {
+    pub id: i64,
+    pub class_id: i64,
+    pub name: String,
+    pub provider: ImProvider,
+    pub target: String,
+    pub has_secret: bool,
+    pub events: Vec<ImEvent>,
+    pub templates: HashMap<ImEvent, String>,
+    pub enabled: bool,
+    /// 最近一次投递失败的原因，投递成功后清空
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<ClassImChannel> for ClassImChannelItem {
+    fn from(channel: ClassImChannel) -> Self {
+        Self {
+            id: channel.id,
+            class_id: channel.class_id,
+            name: channel.name,
+            provider: channel.provider,
+            target: mask_target(&channel.target),
+            has_secret: channel.secret.is_some(),
+            events: channel.events,
+            templates: channel.templates,
+            enabled: channel.enabled,
+            last_error: channel.last_error,
+            last_delivered_at: channel.last_delivered_at,
+            created_by: channel.created_by,
+            created_at: channel.created_at,
+            updated_at: channel.updated_at,
+        }
+    }
+}
+
+/// Webhook 地址中的 key/access_token 等同于密钥，只保留前缀便于辨认
+fn mask_target(target: &str) -> String {
+    match target.split_once('?') {
+        Some((base, _)) => format!("{base}?***"),
+        None => target.to_string(),
+    }
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassImChannelListResponse {
+    pub items: Vec<ClassImChannelItem>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassScheduledExportListResponse {
+    pub items: Vec<ClassScheduledExport>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassApiTokenListResponse {
+    pub items: Vec<ClassApiToken>,
+}
+
+/// 新创建的班级 API 令牌，`token` 只在此时返回
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct CreatedClassApiToken {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub info: ClassApiToken,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassApiTokenLogListResponse {
+    pub items: Vec<ClassApiTokenLog>,
+}
+
+/// 班级短码（课堂展示用，定期轮换）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassShortCodeResponse {
+    pub class_id: i64,
+    pub code: String,
+    /// 过期时间，过期后再次请求会生成新短码
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// 轮换间隔（分钟）
+    pub rotation_minutes: i64,
+}
+
+/// 班级导入结果
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassBundleImportResponse {
+    pub class: Class,
+    pub homeworks: usize,
+    pub announcements: usize,
+    pub submissions: usize,
+    /// 目标部署中找不到对应用户而跳过的提交数
+    pub skipped_submissions: usize,
+    /// 未能导入的附件、分题等条目说明
+    pub warnings: Vec<String>,
+}
+
+/// 班级排行榜
+///
+/// 学生查看时只返回前 `top_n` 名；未选择署名的同学 `display_name` 为空，不返回 `user_id`。
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassLeaderboardResponse {
+    pub class_id: i64,
+    pub enabled: bool,
+    pub metric: LeaderboardMetric,
+    pub top_n: i32,
+    /// 有已公布成绩的学生数
+    pub participant_count: i64,
+    pub entries: Vec<LeaderboardEntry>,
+    /// 当前学生本人的名次（不在前 `top_n` 名时也返回），教师查看时为空
+    pub me: Option<LeaderboardEntry>,
+    /// 当前学生是否已选择署名
+    pub opted_in: bool,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 排行榜条目
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct LeaderboardEntry {
+    /// 名次，同分同名次
+    pub rank: i64,
+    /// 显示名称，学生查看且对方未选择署名时为空（匿名）
+    pub display_name: Option<String>,
+    /// 仅教师查看时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub user_id: Option<i64>,
+    pub score: f64,
+    pub homework_count: i64,
+    pub is_me: bool,
+}
+
+/// 学生数据擦除预览（不修改任何数据）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassErasurePreview {
+    pub class_id: i64,
+    pub user_id: i64,
+    pub counts: ClassErasureCounts,
+    pub total: u64,
+    /// 执行擦除时回传，过期或数据变化后需重新预览
+    pub confirm_token: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 学生数据擦除结果
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/class.ts")]
+pub struct ClassErasureResult {
+    pub class_id: i64,
+    pub user_id: i64,
+    pub counts: ClassErasureCounts,
+    pub total: u64,
+}
@@ -20,12 +20,20 @@ pub enum ErrorCode {
     InternalServerError = 1005, // 内部服务器错误
     NotImplemented = 1006,      // 未实现的功能
     Conflict = 1009,            // 冲突 (资源已存在)
+    PageSizeExceeded = 1010,    // 每页数量超出上限
     RateLimitExceeded = 1029,   // 请求过于频繁
 
     // Auth 错误
-    AuthFailed = 2000,              // 身份验证失败
-    RegisterFailed = 2001,          // 注册失败
-    PasswordPolicyViolation = 2002, // 密码不符合策略要求
+    AuthFailed = 2000,                        // 身份验证失败
+    RegisterFailed = 2001,                    // 注册失败
+    PasswordPolicyViolation = 2002,           // 密码不符合策略要求
+    RegistrationClosed = 2003,                // 已关闭自助注册
+    RegistrationInviteRequired = 2004,        // 邀请制注册缺少有效邀请码
+    RegistrationEmailDomainNotAllowed = 2005, // 邮箱域名不在允许范围内
+    CaptchaRequired = 2006,                   // 需要人机验证
+    CaptchaInvalid = 2007,                    // 人机验证未通过
+    CaptchaUnavailable = 2008,                // 人机验证服务不可用
+    CsrfTokenInvalid = 2009,                  // CSRF 令牌缺失或不匹配
 
     // 文件相关错误
     FileNotFound = 3000,              // 文件未找到
@@ -33,6 +41,13 @@ pub enum ErrorCode {
     FileTypeNotAllowed = 3002,        // 文件类型不被允许
     FileSizeExceeded = 3003,          // 文件大小超出限制
     MultifileUploadNotAllowed = 3004, // 不允许多文件上传
+    FilePreviewUnsupported = 3005,    // 该文件类型不支持预览
+    FilePreviewUnavailable = 3006,    // 文档预览服务不可用
+    FileQuarantined = 3007,           // 文件未通过内容校验，已提交管理员审核
+    QuarantinedFileNotFound = 3008,   // 隔离文件未找到
+    FileInUse = 3009,                 // 文件仍被引用，不能删除
+    FileReadTimeout = 3010,           // 读取文件超时（冷存储）
+    UploadFieldNotAllowed = 3011,     // 上传请求包含不支持的字段
 
     // 用户相关错误
     UserNotFound = 4000,            // 用户未找到
@@ -48,18 +63,50 @@ pub enum ErrorCode {
     UserEmailAlreadyExists = 4013, // 用户邮箱已存在
     UserPasswordInvalid = 4014,    // 密码不符合策略要求
 
+    RoleRequestNotFound = 4020,        // 角色申请未找到
+    RoleRequestPending = 4021,         // 已有待审核的角色申请
+    RoleRequestNotAllowed = 4022,      // 当前角色无需申请
+    RoleRequestAlreadyReviewed = 4023, // 角色申请已被审核
+
+    StudentGoalNotFound = 4030, // 学习目标未找到
+
+    UserMergeInvalid = 4040, // 账号合并请求无效
+    UserMergeFailed = 4041,  // 账号合并失败
+
+    ViewerInvitationInvalid = 4050, // 查看邀请无效、已使用或已过期
+    ViewerAccessDenied = 4051,      // 只读查看账号无权访问
+    ViewerGrantNotFound = 4052,     // 查看授权未找到
+
     // 班级相关错误
-    ClassNotFound = 5000,          // 班级未找到
-    ClassAlreadyExists = 5001,     // 班级已存在
-    ClassCreationFailed = 5002,    // 班级创建失败
-    ClassUpdateFailed = 5003,      // 班级更新失败
-    ClassDeleteFailed = 5004,      // 班级删除失败
-    ClassPermissionDenied = 5005,  // 班级权限被拒绝
-    ClassJoinFailed = 5010,        // 加入班级失败
-    ClassInviteCodeInvalid = 5011, // 班级邀请码无效
-    ClassAlreadyJoined = 5012,     // 已经加入该班级
-    ClassJoinForbidden = 5013,     // 加入班级被禁止
-    ClassUserNotFound = 5014,      // 班级用户未找到
+    ClassNotFound = 5000,                // 班级未找到
+    ClassAlreadyExists = 5001,           // 班级已存在
+    ClassCreationFailed = 5002,          // 班级创建失败
+    ClassUpdateFailed = 5003,            // 班级更新失败
+    ClassDeleteFailed = 5004,            // 班级删除失败
+    ClassPermissionDenied = 5005,        // 班级权限被拒绝
+    ClassJoinFailed = 5010,              // 加入班级失败
+    ClassInviteCodeInvalid = 5011,       // 班级邀请码无效
+    ClassAlreadyJoined = 5012,           // 已经加入该班级
+    ClassJoinForbidden = 5013,           // 加入班级被禁止
+    ClassUserNotFound = 5014,            // 班级用户未找到
+    ClassShortCodeInvalid = 5015,        // 班级短码无效或已过期
+    ClassProfileNameRequired = 5016,     // 班级要求实名，须填写班级内姓名
+    ClassProfileNameInvalid = 5017,      // 班级内姓名不符合格式要求
+    ClassImChannelNotFound = 5020,       // IM 通知渠道未找到
+    ClassImDeliveryFailed = 5021,        // IM 消息发送失败
+    CertificateNotEnabled = 5030,        // 班级未启用结业证书
+    CertificateNotEarned = 5031,         // 尚未满足结业证书颁发条件
+    CertificateNotFound = 5032,          // 结业证书不存在
+    CertificateSignatureInvalid = 5033,  // 签名图片格式不受支持
+    ClassApiTokenNotFound = 5040,        // 班级 API 令牌未找到
+    ClassApiTokenForbidden = 5041,       // 班级 API 令牌无权访问该接口
+    ClassLeaderboardDisabled = 5050,     // 班级未启用排行榜
+    ClassErasureInvalid = 5060,          // 数据擦除对象无效
+    ClassErasureConfirmInvalid = 5061,   // 擦除确认令牌无效、已过期或数据已变化
+    ClassRoleLabelInvalid = 5070,        // 班级角色名称无效或不存在
+    ClassUserBatchInvalid = 5080,        // 成员批量操作请求无效
+    ClassScheduledExportNotFound = 5090, // 定时导出未找到
+    ClassScheduledExportInvalid = 5091,  // 定时导出配置无效
 
     // 通用权限错误
     PermissionDenied = 6000, // 权限被拒绝
@@ -72,21 +119,96 @@ pub enum ErrorCode {
     ExportFailed = 7010,            // 导出失败
 
     // 作业相关错误
-    HomeworkNotFound = 8000,     // 作业未找到
-    HomeworkCreateFailed = 8001, // 作业创建失败
-    HomeworkUpdateFailed = 8002, // 作业更新失败
-    HomeworkDeleteFailed = 8003, // 作业删除失败
+    HomeworkNotFound = 8000,            // 作业未找到
+    HomeworkCreateFailed = 8001,        // 作业创建失败
+    HomeworkUpdateFailed = 8002,        // 作业更新失败
+    HomeworkDeleteFailed = 8003,        // 作业删除失败
+    HomeworkExemptionNotFound = 8004,   // 作业豁免记录未找到
+    HomeworkShareLinkNotFound = 8005,   // 作业分享链接未找到或已失效
+    HomeworkSolutionLocked = 8006,      // 参考答案尚未开放
+    HomeworkSolutionNotFound = 8007,    // 参考答案未设置
+    HomeworkPartInvalid = 8008,         // 分题定义无效
+    HomeworkPartNotFound = 8009,        // 分题不存在或不属于该作业
+    HomeworkPartInUse = 8010,           // 分题已有提交，不能删除
+    HomeworkPrerequisiteInvalid = 8011, // 前置条件定义无效
+    HomeworkPrerequisiteCycle = 8012,   // 前置条件形成循环依赖
+    MissingGradePolicyNotFound = 8013,  // 缺交零分策略未设置
+    AutoGradeNotFound = 8014,           // 该学生没有自动记录的零分
+    HomeworkLinkInvalid = 8015,         // 外部资源定义无效
+    HomeworkLinkNotFound = 8016,        // 外部资源不存在或不属于该作业
+    HomeworkFeedbackInvalid = 8017,     // 用时 / 难度反馈内容无效
+    HomeworkFeedbackNotAllowed = 8018,  // 作业评分后才能填写用时 / 难度反馈
 
     // 提交相关错误
-    SubmissionNotFound = 9000,     // 提交未找到
-    SubmissionCreateFailed = 9001, // 提交创建失败
-    SubmissionDeleteFailed = 9002, // 提交删除失败
+    SubmissionNotFound = 9000,                // 提交未找到
+    SubmissionCreateFailed = 9001,            // 提交创建失败
+    SubmissionDeleteFailed = 9002,            // 提交删除失败
+    SubmissionContentTooLong = 9003,          // 提交内容超出长度限制
+    SubmissionTextNotAllowed = 9004,          // 该作业不接受文本内容
+    SubmissionAttachmentNotAllowed = 9005,    // 该作业不接受附件
+    SubmissionAttachmentRequired = 9006,      // 该作业必须上传附件
+    SubmissionContentRequired = 9007,         // 该作业必须填写文本内容
+    SubmissionDiffMismatch = 9008,            // 对比的提交不属于同一学生的同一作业
+    SubmissionPartRequired = 9009,            // 多部分作业必须指定分题
+    SelfAssessmentRequired = 9010,            // 该作业提交时必须填写自评
+    SelfAssessmentInvalid = 9011,             // 自评内容无效
+    SubmissionPrerequisitesNotMet = 9012,     // 尚未完成前置作业
+    SubmissionStatusTransitionInvalid = 9013, // 工作流不允许该状态转换
+    SubmissionWorkflowInvalid = 9014,         // 提交状态工作流定义无效
+    ResubmissionRequestInvalid = 9015,        // 退回意见或重新开放期限无效
+    SubmissionReferenceInvalid = 9016,        // 外部引用（仓库 / 文档链接）无效
 
     // 成绩相关错误
-    GradeNotFound = 10000,     // 成绩未找到
-    GradeCreateFailed = 10001, // 成绩创建失败
-    GradeUpdateFailed = 10002, // 成绩更新失败
+    GradeNotFound = 10000,        // 成绩未找到
+    GradeCreateFailed = 10001,    // 成绩创建失败
+    GradeUpdateFailed = 10002,    // 成绩更新失败
+    GradeMentionInvalid = 10003,  // 评语提及的用户无效
+    GradeScoreOutOfRange = 10004, // 分数超出分题满分范围
+    GradeDraftNotFound = 10005,   // 评分草稿不存在
 
     // 通知相关错误
-    NotificationNotFound = 11000, // 通知未找到
+    NotificationNotFound = 11000,         // 通知未找到
+    NotificationTemplateInvalid = 11001,  // 通知模板无效
+    NotificationTemplateNotFound = 11002, // 通知模板未找到
+    NotificationRequestInvalid = 11003,   // 群发通知请求无效
+
+    // 组织相关错误
+    OrganizationNotFound = 12000,    // 组织未找到
+    OrganizationSlugExists = 12001,  // 组织标识已存在
+    OrganizationDisabled = 12002,    // 组织已停用
+    OrganizationNotEmpty = 12003,    // 组织下仍有用户
+    OrganizationSlugInvalid = 12004, // 组织标识无效
+
+    // 系统设置相关错误
+    SettingValueInvalid = 13000,            // 配置值无效
+    SettingKeyUnknown = 13001,              // 未登记的配置项
+    SettingVersionConflict = 13002,         // 配置版本冲突
+    DatabaseMaintenanceUnsupported = 13003, // 当前数据库不支持该维护操作
+
+    // 后台任务相关错误
+    JobNotFound = 14000,  // 任务不存在或已过期
+    JobNotReady = 14001,  // 任务尚未完成
+    JobQueueFull = 14002, // 任务队列繁忙
+
+    // 内容审核相关错误
+    ContentModerationBlocked = 15000,    // 内容未通过审核，已拦截
+    ModerationFlagNotFound = 15001,      // 审核标记未找到
+    ContentReportNotFound = 15002,       // 举报不存在
+    ContentReportTargetNotFound = 15003, // 被举报的内容不存在
+    ContentReportDuplicate = 15004,      // 已举报过该内容，等待处理
+    ContentReportInvalid = 15005,        // 举报或处理请求无效
+
+    // 私信相关错误
+    MessageNotAllowed = 16000,     // 不允许向该用户发送私信
+    MessageContentInvalid = 16001, // 私信内容无效
+
+    // 表情回应相关错误
+    ReactionTargetNotFound = 17000, // 回应目标不存在
+    ReactionEmojiInvalid = 17001,   // 表情无效
+
+    // 评分量规相关错误
+    RubricNotFound = 18000,         // 量规不存在或无权使用
+    RubricInvalid = 18001,          // 量规定义无效
+    RubricShareCodeInvalid = 18002, // 分享码无效
+    HomeworkRubricNotFound = 18003, // 作业未设置量规
 }
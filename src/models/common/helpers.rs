@@ -6,7 +6,7 @@ use actix_web::{HttpResponse, http::StatusCode};
 use serde::Serialize;
 use ts_rs::TS;
 
-use super::{ApiResponse, ErrorCode};
+use super::{ApiResponse, ErrorCode, PageSizeLimitError};
 
 /// 成功响应（带数据）
 pub fn success<T: Serialize + TS>(data: T, message: impl Into<String>) -> HttpResponse {
@@ -65,3 +65,12 @@ pub fn rate_limit_exceeded(message: impl Into<String>) -> HttpResponse {
 pub fn conflict(message: impl Into<String>) -> HttpResponse {
     error(StatusCode::CONFLICT, ErrorCode::Conflict, message)
 }
+
+/// 400 每页数量超出上限，data 中携带上限值
+pub fn page_size_exceeded(page_size_limit: i64) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error(
+        ErrorCode::PageSizeExceeded,
+        PageSizeLimitError { page_size_limit },
+        format!("每页数量不能超过 {page_size_limit}"),
+    ))
+}
@@ -12,5 +12,5 @@ pub mod helpers;
 // 重新导出
 pub use error_code::ErrorCode;
 pub use helpers::*;
-pub use pagination::{PaginationInfo, PaginationQuery};
+pub use pagination::{PageSizeLimitError, PaginationInfo, PaginationPolicy, PaginationQuery};
 pub use response::ApiResponse;
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::config::{AppConfig, PaginationConfig};
+
 // 分页查询参数
 #[derive(Debug, Clone, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/pagination.ts")]
@@ -10,11 +12,10 @@ pub struct PaginationQuery {
         deserialize_with = "deserialize_string_to_i64"
     )]
     pub page: i64,
-    #[serde(
-        default = "default_size",
-        deserialize_with = "deserialize_string_to_i64"
-    )]
-    pub size: i64,
+    // 未指定时使用接口的默认每页数量
+    #[serde(default, deserialize_with = "deserialize_optional_string_to_i64")]
+    #[ts(optional)]
+    pub size: Option<i64>,
 }
 
 // 分页响应信息
@@ -35,6 +36,13 @@ pub struct PaginatedResponse<T: TS> {
     pub pagination: PaginationInfo,
 }
 
+// 超出每页数量上限时的错误数据
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/pagination.ts")]
+pub struct PageSizeLimitError {
+    pub page_size_limit: i64,
+}
+
 // 自定义反序列化函数，支持字符串到i64的转换
 fn deserialize_string_to_i64<'de, D>(deserializer: D) -> Result<i64, D::Error>
 where
@@ -83,35 +91,123 @@ where
     deserializer.deserialize_any(I64Visitor)
 }
 
-fn default_page() -> i64 {
-    1
+fn deserialize_optional_string_to_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_string_to_i64(deserializer).map(Some)
 }
 
-fn default_size() -> i64 {
-    20
+fn default_page() -> i64 {
+    1
 }
 
 impl Default for PaginationQuery {
     fn default() -> Self {
-        Self { page: 1, size: 20 }
+        Self {
+            page: 1,
+            size: None,
+        }
     }
 }
 
-impl PaginationQuery {
-    /// 验证并规范化分页参数
-    /// - page 必须 >= 1
-    /// - size 必须在 1-100 之间
-    pub fn validate(&mut self) {
-        // 分页号至少为1
-        if self.page < 1 {
-            self.page = 1;
+/// 分页策略
+///
+/// 由 `[pagination]` 配置和按接口覆盖项决定默认每页数量与上限。
+/// 服务层用 [`PaginationPolicy::check`] 拒绝超限请求，存储层用
+/// [`PaginationPolicy::resolve`] 计算实际分页参数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationPolicy {
+    pub default_size: i64,
+    pub max_size: i64,
+}
+
+impl PaginationPolicy {
+    /// 从配置计算指定接口的分页策略
+    pub fn from_config(config: &PaginationConfig, endpoint: &str) -> Self {
+        let overrides = config.overrides.get(endpoint);
+        let max_size = overrides
+            .and_then(|o| o.max_page_size)
+            .unwrap_or(config.max_page_size)
+            .max(1);
+        let default_size = overrides
+            .and_then(|o| o.default_page_size)
+            .unwrap_or(config.default_page_size)
+            .clamp(1, max_size);
+        Self {
+            default_size,
+            max_size,
         }
+    }
+
+    /// 获取指定接口的分页策略（读取全局配置）
+    pub fn for_endpoint(endpoint: &str) -> Self {
+        Self::from_config(&AppConfig::get().pagination, endpoint)
+    }
 
-        // 每页数量限制
-        if self.size < 1 {
-            self.size = 1;
-        } else if self.size > 100 {
-            self.size = 100;
+    /// 检查请求的每页数量，超出上限时返回上限值
+    pub fn check(&self, size: Option<i64>) -> Result<(), i64> {
+        match size {
+            Some(size) if size > self.max_size => Err(self.max_size),
+            _ => Ok(()),
         }
     }
+
+    /// 计算实际的 (page, size)
+    ///
+    /// 服务层已拒绝超限请求，这里仍做兜底截断，供内部调用使用。
+    pub fn resolve(&self, page: Option<i64>, size: Option<i64>) -> (u64, u64) {
+        let page = page.unwrap_or(1).max(1) as u64;
+        let size = size.unwrap_or(self.default_size).clamp(1, self.max_size) as u64;
+        (page, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PaginationOverride;
+
+    fn config() -> PaginationConfig {
+        let mut config = PaginationConfig::default();
+        config.overrides.insert(
+            "notifications".to_string(),
+            PaginationOverride {
+                default_page_size: Some(50),
+                max_page_size: Some(200),
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let config = config();
+        assert_eq!(
+            PaginationPolicy::from_config(&config, "users"),
+            PaginationPolicy {
+                default_size: 20,
+                max_size: 100
+            }
+        );
+        assert_eq!(
+            PaginationPolicy::from_config(&config, "notifications"),
+            PaginationPolicy {
+                default_size: 50,
+                max_size: 200
+            }
+        );
+    }
+
+    #[test]
+    fn test_policy_check_and_resolve() {
+        let policy = PaginationPolicy::from_config(&config(), "users");
+        assert_eq!(policy.check(None), Ok(()));
+        assert_eq!(policy.check(Some(100)), Ok(()));
+        assert_eq!(policy.check(Some(101)), Err(100));
+
+        assert_eq!(policy.resolve(None, None), (1, 20));
+        assert_eq!(policy.resolve(Some(0), Some(0)), (1, 1));
+        assert_eq!(policy.resolve(Some(3), Some(10000)), (3, 100));
+    }
 }
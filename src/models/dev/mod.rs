@@ -0,0 +1,5 @@
+// 开发模式示例数据请求模型
+pub mod requests;
+
+// 开发模式示例数据响应模型
+pub mod responses;
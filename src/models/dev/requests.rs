@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+use crate::models::users::entities::UserRole;
+
+/// 示例数据查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/dev.ts")]
+pub struct DevFixtureQuery {
+    /// 以哪个角色的视角生成示例（默认 user）
+    pub role: Option<UserRole>,
+}
@@ -0,0 +1,25 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::models::users::entities::UserRole;
+
+/// 可用的示例接口
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/dev.ts")]
+pub struct DevFixtureInfo {
+    /// 示例名称，用于 `/dev/fixtures/{endpoint}`
+    pub name: String,
+    pub method: String,
+    /// 对应的真实接口路径（相对于 `/api/v1`）
+    pub path: String,
+    /// 可以访问真实接口的角色，其他角色返回 403 示例
+    pub roles: Vec<UserRole>,
+    pub description: String,
+}
+
+/// 示例接口列表
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/dev.ts")]
+pub struct DevFixtureListResponse {
+    pub items: Vec<DevFixtureInfo>,
+}
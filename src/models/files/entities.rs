@@ -22,6 +22,236 @@ pub struct File {
     pub download_token: String,
     // 引用计数
     pub citation_count: i32,
+    // 所属组织
+    pub org_id: Option<i64>,
+    // 代码语言（上传时识别，非代码文件为空）
+    pub code_language: Option<String>,
+    // 存储层级
+    pub storage_tier: StorageTier,
+    // 迁移到冷存储的时间
+    pub tiered_at: Option<chrono::DateTime<chrono::Utc>>,
     // 创建时间
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// 文件存储层级
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub enum StorageTier {
+    /// 上传目录，读取快
+    #[default]
+    Hot,
+    /// 冷存储目录，容量大、读取慢
+    Cold,
+}
+
+impl std::fmt::Display for StorageTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageTier::Hot => write!(f, "hot"),
+            StorageTier::Cold => write!(f, "cold"),
+        }
+    }
+}
+
+impl std::str::FromStr for StorageTier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hot" => Ok(StorageTier::Hot),
+            "cold" => Ok(StorageTier::Cold),
+            _ => Err(format!("Invalid storage tier: {s}")),
+        }
+    }
+}
+
+/// 文档预览格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub enum PreviewFormat {
+    #[default]
+    Pdf,
+    Html,
+}
+
+impl PreviewFormat {
+    /// 缓存文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PreviewFormat::Pdf => "pdf",
+            PreviewFormat::Html => "html",
+        }
+    }
+
+    /// 响应的 Content-Type
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            PreviewFormat::Pdf => "application/pdf",
+            PreviewFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
+impl std::fmt::Display for PreviewFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// 文件保留分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub enum RetentionCategory {
+    /// 已归档班级中的提交附件
+    SubmissionAttachment,
+    /// 已归档班级中的作业附件
+    HomeworkAttachment,
+    /// 未被任何业务引用的孤立上传
+    Orphan,
+}
+
+impl std::fmt::Display for RetentionCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetentionCategory::SubmissionAttachment => write!(f, "submission_attachment"),
+            RetentionCategory::HomeworkAttachment => write!(f, "homework_attachment"),
+            RetentionCategory::Orphan => write!(f, "orphan"),
+        }
+    }
+}
+
+/// 文件被引用的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub enum FileReferenceKind {
+    /// 作业附件
+    Homework,
+    /// 提交附件
+    Submission,
+    /// 用户头像
+    Avatar,
+    /// 结业证书签名图片
+    CertificateSignature,
+    /// 角色申请附件
+    RoleRequest,
+}
+
+/// 文件的一处引用
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct FileReference {
+    pub kind: FileReferenceKind,
+    /// 引用对象的 ID：作业、提交、用户、班级或角色申请
+    pub target_id: i64,
+    /// 作业附件与提交附件所属的作业
+    pub homework_id: Option<i64>,
+    /// 作业附件、提交附件与证书签名所属的班级
+    pub class_id: Option<i64>,
+    /// 展示用标题（作业标题或班级名称）
+    pub title: Option<String>,
+}
+
+/// 系统级文件保留策略（天数为 0 表示永久保留）
+///
+/// 头像、结业证书签名、角色申请附件仍被引用时始终保留，
+/// 被替换或解除引用后按孤立文件处理。
+#[derive(Debug, Clone, Copy)]
+pub struct FileRetentionPolicy {
+    /// 提交附件在班级归档后的保留天数
+    pub submission_files_days: i64,
+    /// 作业附件在班级归档后的保留天数
+    pub homework_files_days: i64,
+    /// 孤立上传自上传起的保留天数
+    pub orphan_files_days: i64,
+}
+
+/// 班级文件保留策略覆盖（天数为空表示沿用系统默认值，0 表示永久保留）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct ClassRetentionOverride {
+    pub class_id: i64,
+    pub submission_files_days: Option<i32>,
+    pub homework_files_days: Option<i32>,
+    pub updated_by: i64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 即将按保留策略删除的文件
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct RetentionCandidate {
+    pub file_id: i64,
+    pub download_token: String,
+    pub original_name: String,
+    pub file_size: i64,
+    pub category: RetentionCategory,
+    /// 决定到期时间的班级，孤立文件为空
+    pub class_id: Option<i64>,
+    /// 到期（可被删除）时间
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    // 文件所属组织，用于报告的租户过滤
+    #[serde(skip)]
+    #[ts(skip)]
+    pub org_id: Option<i64>,
+}
+
+/// 文件被隔离的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub enum QuarantineReason {
+    /// 文件头魔术字节与扩展名不匹配
+    MagicMismatch,
+}
+
+impl std::fmt::Display for QuarantineReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuarantineReason::MagicMismatch => write!(f, "magic_mismatch"),
+        }
+    }
+}
+
+impl std::str::FromStr for QuarantineReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "magic_mismatch" => Ok(QuarantineReason::MagicMismatch),
+            _ => Err(format!("Invalid quarantine reason: {s}")),
+        }
+    }
+}
+
+/// 待管理员审核的隔离文件
+///
+/// 隔离文件存放在上传目录的 `quarantine` 子目录中，审核通过后转为正式文件。
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct QuarantinedFile {
+    pub id: i64,
+    /// 上传者用户ID
+    pub user_id: i64,
+    pub original_name: String,
+    // 隔离目录中的存储文件名
+    #[serde(skip)]
+    #[ts(skip)]
+    pub stored_name: String,
+    /// 上传时声明的文件类型（MIME）
+    pub file_type: String,
+    /// 文件大小(字节)
+    pub file_size: i64,
+    pub reason: QuarantineReason,
+    /// 校验详情（如扩展名）
+    pub detail: Option<String>,
+    // 上传者所属组织，用于列表的租户过滤
+    #[serde(skip)]
+    #[ts(skip)]
+    pub org_id: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -1,5 +1,8 @@
 // 文件实体模型
 pub mod entities;
 
+// 文件请求模型
+pub mod requests;
+
 // 文件响应模型
 pub mod responses;
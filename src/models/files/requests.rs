@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+use super::entities::{PreviewFormat, QuarantineReason};
+
+/// 更新班级文件保留策略请求
+///
+/// 天数为空表示沿用系统默认值，0 表示永久保留。
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct UpdateClassRetentionRequest {
+    /// 提交附件在班级归档后的保留天数
+    pub submission_files_days: Option<i32>,
+    /// 作业附件在班级归档后的保留天数
+    pub homework_files_days: Option<i32>,
+}
+
+/// 即将删除文件报告查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct RetentionReportQuery {
+    /// 报告未来 N 天内到期的文件（默认 30，最大 365）
+    pub days: Option<i64>,
+}
+
+/// 本人上传文件列表查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct MyFileListQuery {
+    pub page: Option<i64>,
+    pub size: Option<i64>,
+    /// 仅列出未被引用（可删除）的文件
+    pub unreferenced: Option<bool>,
+}
+
+/// 文档预览查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct FilePreviewQuery {
+    /// 预览格式，默认 pdf
+    #[serde(default)]
+    pub format: PreviewFormat,
+}
+
+/// 隔离文件写入参数（存储层使用）
+#[derive(Debug, Clone)]
+pub struct QuarantinedFileInput {
+    pub user_id: i64,
+    pub original_name: String,
+    pub stored_name: String,
+    pub file_type: String,
+    pub file_size: i64,
+    pub reason: QuarantineReason,
+    pub detail: Option<String>,
+}
@@ -1,6 +1,12 @@
 use serde::Serialize;
 use ts_rs::TS;
 
+use super::entities::{
+    ClassRetentionOverride, File, FileReference, FileReferenceKind, QuarantineReason,
+    QuarantinedFile, RetentionCandidate,
+};
+use crate::models::common::PaginationInfo;
+
 /// 文件信息（用于附件列表展示）
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
@@ -13,6 +19,36 @@ pub struct FileInfo {
     pub file_size: i64,
     /// 文件类型 (MIME)
     pub file_type: String,
+    /// 代码语言，非代码文件为空
+    pub code_language: Option<String>,
+}
+
+impl From<File> for FileInfo {
+    fn from(file: File) -> Self {
+        Self {
+            download_token: file.download_token,
+            original_name: file.original_name,
+            file_size: file.file_size,
+            file_type: file.file_type,
+            code_language: file.code_language,
+        }
+    }
+}
+
+/// 代码文件内容（用于语法高亮预览）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct CodeFileResponse {
+    /// 原始文件名
+    pub file_name: String,
+    /// 代码语言（highlight.js 语言名），无法识别时为空
+    pub language: Option<String>,
+    /// 文件大小(字节)
+    pub size: i64,
+    /// 行数
+    pub line_count: usize,
+    /// 文件内容（已去除 UTF-8 BOM）
+    pub content: String,
 }
 
 /// FileAttachment
@@ -27,6 +63,101 @@ pub struct FileUploadResponse {
     pub size: i64,
     /// 文件类型
     pub content_type: String,
+    /// 代码语言，非代码文件为空
+    pub code_language: Option<String>,
+    /// 文件内容的 SHA-256（十六进制），仅上传接口返回，隔离文件放行时为空
+    pub sha256: Option<String>,
     /// 创建时间
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// 班级文件保留策略
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct ClassRetentionResponse {
+    pub class_id: i64,
+    /// 班级归档时间，未归档时附件不会到期
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// 班级覆盖设置，未配置时为空
+    pub overrides: Option<ClassRetentionOverride>,
+    /// 生效的提交附件保留天数（0 表示永久保留）
+    pub submission_files_days: i64,
+    /// 生效的作业附件保留天数（0 表示永久保留）
+    pub homework_files_days: i64,
+}
+
+/// 即将删除文件报告
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct RetentionReportResponse {
+    /// 报告范围（天）
+    pub days: i64,
+    /// 文件总大小(字节)
+    pub total_size: i64,
+    pub items: Vec<RetentionCandidate>,
+}
+
+/// 上传文件被隔离时返回的信息
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct FileQuarantinedResponse {
+    /// 隔离记录 ID
+    pub quarantine_id: i64,
+    /// 原始文件名
+    pub file_name: String,
+    /// 文件大小(字节)
+    pub size: i64,
+    pub reason: QuarantineReason,
+}
+
+/// 隔离文件审核队列
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct QuarantinedFileListResponse {
+    pub items: Vec<QuarantinedFile>,
+}
+
+/// 本人上传的文件及其引用位置
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct MyFileItem {
+    pub download_token: String,
+    pub original_name: String,
+    pub file_size: i64,
+    pub file_type: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 为空表示未被引用，可以删除
+    pub references: Vec<FileReference>,
+}
+
+/// 按引用位置汇总的存储用量（同一文件被多处引用时在每类中都计入）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct FileUsageBreakdown {
+    pub kind: FileReferenceKind,
+    pub file_count: i64,
+    pub total_size: i64,
+}
+
+/// 本人存储用量
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct FileUsageSummary {
+    pub file_count: i64,
+    /// 全部上传文件的总大小(字节)
+    pub total_size: i64,
+    pub unreferenced_count: i64,
+    /// 未被引用文件的总大小(字节)，删除后可释放
+    pub unreferenced_size: i64,
+    pub breakdown: Vec<FileUsageBreakdown>,
+}
+
+/// 本人上传文件列表
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/file.ts")]
+pub struct MyFileListResponse {
+    pub items: Vec<MyFileItem>,
+    pub pagination: PaginationInfo,
+    /// 用量统计始终覆盖全部文件，不受 `unreferenced` 过滤影响
+    pub usage: FileUsageSummary,
+}
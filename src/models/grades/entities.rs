@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::models::reactions::entities::ReactionSummary;
+
 /// 评分实体
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
@@ -12,4 +14,62 @@ pub struct Grade {
     pub comment: Option<String>,
     pub graded_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// 缺交零分策略自动记录的零分（教师修改后为 false）
+    #[serde(default)]
+    pub auto_assigned: bool,
+    /// 评语中 @提及 的用户
+    #[serde(default)]
+    pub mentions: Vec<GradeMention>,
+    /// 班级相对位置（作业开启 `show_grade_context` 后学生本人查看时返回）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub class_context: Option<GradeClassContext>,
+    /// 评语的表情回应
+    #[serde(default)]
+    pub reactions: ReactionSummary,
+}
+
+/// 成绩分布统计（每位学生取最新一次已评分提交）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeStatistics {
+    pub count: i64,
+    pub mean: f64,
+    pub median: f64,
+    /// 总体标准差
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// 成绩在班级中的相对位置
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeClassContext {
+    /// 百分位（0-100）：低于该分数的人数加同分人数的一半，占总人数的百分比
+    pub percentile: f64,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub statistics: GradeStatistics,
+}
+
+/// 评语中被 @提及 的用户
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeMention {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+}
+
+/// 评分草稿（批改过程中自动保存，正式评分后删除）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeDraft {
+    pub submission_id: i64,
+    pub grader_id: i64,
+    pub score: Option<f64>,
+    pub comment: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
 }
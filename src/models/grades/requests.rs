@@ -1,4 +1,5 @@
 use crate::models::common::PaginationQuery;
+use crate::models::organizations::entities::TenantScope;
 use serde::Deserialize;
 use ts_rs::TS;
 
@@ -19,6 +20,33 @@ pub struct UpdateGradeRequest {
     pub comment: Option<String>,
 }
 
+/// 保存评分草稿请求（整体覆盖已有草稿）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct SaveGradeDraftRequest {
+    pub score: Option<f64>,
+    pub comment: Option<String>,
+}
+
+impl SaveGradeDraftRequest {
+    pub const MAX_COMMENT_LENGTH: usize = 10000;
+
+    /// 校验草稿分数与评语长度；草稿不校验分题满分，正式评分时再校验
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(score) = self.score
+            && !(score.is_finite() && score >= 0.0)
+        {
+            return Err("分数必须为非负数".to_string());
+        }
+        if let Some(comment) = &self.comment
+            && comment.chars().count() > Self::MAX_COMMENT_LENGTH
+        {
+            return Err(format!("评语不能超过 {} 个字符", Self::MAX_COMMENT_LENGTH));
+        }
+        Ok(())
+    }
+}
+
 /// 评分列表查询参数
 #[derive(Debug, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
@@ -30,6 +58,16 @@ pub struct GradeListParams {
     pub grader_id: Option<i64>,
 }
 
+/// 成绩分布查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeDistributionQuery {
+    /// 分题 ID（多部分作业按分题统计，不传统计未拆分的整份作业）
+    pub part_id: Option<i64>,
+    /// 直方图分组数（按满分等分，默认 10，最多 50）
+    pub bins: Option<u32>,
+}
+
 /// 评分列表存储层查询参数
 #[derive(Debug, Clone, Deserialize)]
 pub struct GradeListQuery {
@@ -38,4 +76,7 @@ pub struct GradeListQuery {
     pub submission_id: Option<i64>,
     pub grader_id: Option<i64>,
     pub homework_id: Option<i64>,
+    // 租户范围，由服务层根据当前用户填充
+    #[serde(skip)]
+    pub tenant: TenantScope,
 }
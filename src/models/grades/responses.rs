@@ -3,7 +3,7 @@ use ts_rs::TS;
 
 use crate::models::PaginationInfo;
 
-use super::entities::Grade;
+use super::entities::{Grade, GradeStatistics};
 
 /// 评分者信息
 #[derive(Debug, Serialize, TS)]
@@ -33,3 +33,32 @@ pub struct GradeListResponse {
     pub items: Vec<Grade>,
     pub pagination: PaginationInfo,
 }
+
+/// 成绩直方图分组（左闭右开，最后一组包含满分）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeHistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: i64,
+}
+
+/// 作业成绩分布响应（教师视角）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeDistributionResponse {
+    pub homework_id: i64,
+    pub part_id: Option<i64>,
+    pub max_score: f64,
+    /// 尚无评分时为空
+    pub statistics: Option<GradeStatistics>,
+    pub histogram: Vec<GradeHistogramBin>,
+}
+
+/// 评语提及校验失败时的错误数据
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/grade.ts")]
+pub struct GradeMentionError {
+    /// 不存在或不是班级成员的用户名
+    pub invalid_usernames: Vec<String>,
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::models::ErrorCode;
+
 /// 作业用户状态（学生视角）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
@@ -12,6 +14,8 @@ pub enum HomeworkUserStatus {
     Submitted,
     /// 已批改
     Graded,
+    /// 已豁免（无需提交）
+    Exempted,
 }
 
 /// 截止日期过滤器
@@ -29,6 +33,130 @@ pub enum DeadlineFilter {
     All,
 }
 
+/// 作业提交方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+#[derive(Default)]
+pub enum SubmissionMode {
+    /// 仅文本
+    Text,
+    /// 仅附件
+    Attachment,
+    /// 文本和附件均可
+    #[default]
+    Both,
+}
+
+impl SubmissionMode {
+    /// 是否接受文本内容
+    pub fn allows_text(&self) -> bool {
+        matches!(self, SubmissionMode::Text | SubmissionMode::Both)
+    }
+
+    /// 是否接受附件
+    pub fn allows_attachments(&self) -> bool {
+        matches!(self, SubmissionMode::Attachment | SubmissionMode::Both)
+    }
+}
+
+impl std::fmt::Display for SubmissionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionMode::Text => write!(f, "text"),
+            SubmissionMode::Attachment => write!(f, "attachment"),
+            SubmissionMode::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl std::str::FromStr for SubmissionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(SubmissionMode::Text),
+            "attachment" => Ok(SubmissionMode::Attachment),
+            "both" => Ok(SubmissionMode::Both),
+            _ => Err(format!("Invalid submission mode: {s}")),
+        }
+    }
+}
+
+/// 作业附件用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum AttachmentKind {
+    /// 参考资料
+    #[default]
+    Reference,
+    /// 提交模板（学生填写后提交）
+    Template,
+    /// 参考答案（截止后或本人已评分后才可查看）
+    Solution,
+}
+
+impl std::fmt::Display for AttachmentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttachmentKind::Reference => write!(f, "reference"),
+            AttachmentKind::Template => write!(f, "template"),
+            AttachmentKind::Solution => write!(f, "solution"),
+        }
+    }
+}
+
+impl std::str::FromStr for AttachmentKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reference" => Ok(AttachmentKind::Reference),
+            "template" => Ok(AttachmentKind::Template),
+            "solution" => Ok(AttachmentKind::Solution),
+            _ => Err(format!("Invalid attachment kind: {s}")),
+        }
+    }
+}
+
+/// 参考答案公开条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum SolutionRevealPolicy {
+    /// 截止时间过后公开
+    #[default]
+    Deadline,
+    /// 截止后且所有提交均已评分时公开
+    AllGraded,
+    /// 教师手动公开
+    Manual,
+}
+
+impl std::fmt::Display for SolutionRevealPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionRevealPolicy::Deadline => write!(f, "deadline"),
+            SolutionRevealPolicy::AllGraded => write!(f, "all_graded"),
+            SolutionRevealPolicy::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+impl std::str::FromStr for SolutionRevealPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deadline" => Ok(SolutionRevealPolicy::Deadline),
+            "all_graded" => Ok(SolutionRevealPolicy::AllGraded),
+            "manual" => Ok(SolutionRevealPolicy::Manual),
+            _ => Err(format!("Invalid solution reveal policy: {s}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
 pub struct Homework {
@@ -46,6 +174,25 @@ pub struct Homework {
     pub deadline: Option<chrono::DateTime<chrono::Utc>>,
     // 是否允许迟交
     pub allow_late: bool,
+    // 提交方式（文本/附件/两者皆可）
+    pub submission_mode: SubmissionMode,
+    // 提交文本内容的最大字符数（None 表示不限制）
+    pub max_content_length: Option<i32>,
+    // 考试模式：记录学生查看与提交的访问日志
+    #[serde(default)]
+    pub exam_mode: bool,
+    // 提交时须附带自评（把握程度、用时、遇到的困难）
+    #[serde(default)]
+    pub require_self_assessment: bool,
+    // 学生查看成绩时附带班级相对位置（百分位、均值、中位数、标准差）
+    #[serde(default)]
+    pub show_grade_context: bool,
+    // 预计用时（分钟）
+    pub estimated_minutes: Option<i32>,
+    // 难度（1-5）
+    pub difficulty: Option<i32>,
+    // 迟交宽限期（分钟），为空时沿用系统设置
+    pub grace_minutes: Option<i32>,
     // 创建者 ID
     pub created_by: i64,
     // 作业创建时间
@@ -53,3 +200,593 @@ pub struct Homework {
     // 作业更新时间
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
+
+impl Homework {
+    /// 计算实际生效的迟交策略，作业未单独设置宽限期时沿用系统默认值
+    pub fn late_policy(&self, default_grace_minutes: i64) -> HomeworkLatePolicy {
+        HomeworkLatePolicy {
+            allow_late: self.allow_late,
+            grace_minutes: self
+                .grace_minutes
+                .map_or(default_grace_minutes, i64::from)
+                .max(0),
+            grace_inherited: self.grace_minutes.is_none(),
+            late_after: None,
+        }
+        .with_deadline(self.deadline)
+    }
+
+    /// 参考答案是否已对学生开放：截止时间已过，或该学生的提交已评分
+    pub fn solution_unlocked(&self, now: chrono::DateTime<chrono::Utc>, graded: bool) -> bool {
+        graded || self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+
+    /// 校验提交内容是否符合作业的提交方式与长度限制
+    pub fn validate_submission(
+        &self,
+        content: &str,
+        attachment_count: usize,
+    ) -> Result<(), (ErrorCode, String)> {
+        let has_text = !content.trim().is_empty();
+
+        if has_text && !self.submission_mode.allows_text() {
+            return Err((
+                ErrorCode::SubmissionTextNotAllowed,
+                "该作业仅接受附件提交".to_string(),
+            ));
+        }
+        if attachment_count > 0 && !self.submission_mode.allows_attachments() {
+            return Err((
+                ErrorCode::SubmissionAttachmentNotAllowed,
+                "该作业仅接受文本提交".to_string(),
+            ));
+        }
+        match self.submission_mode {
+            SubmissionMode::Text if !has_text => {
+                return Err((
+                    ErrorCode::SubmissionContentRequired,
+                    "请填写提交内容".to_string(),
+                ));
+            }
+            SubmissionMode::Attachment if attachment_count == 0 => {
+                return Err((
+                    ErrorCode::SubmissionAttachmentRequired,
+                    "请至少上传一个附件".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        if let Some(max) = self.max_content_length {
+            let len = content.chars().count();
+            if len > max as usize {
+                return Err((
+                    ErrorCode::SubmissionContentTooLong,
+                    format!("提交内容不能超过 {max} 个字符（当前 {len} 个）"),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 作业实际生效的迟交策略
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLatePolicy {
+    // 是否允许迟交
+    pub allow_late: bool,
+    // 生效的宽限期（分钟）
+    pub grace_minutes: i64,
+    // 宽限期是否沿用系统设置 `homework.default_grace_minutes`
+    pub grace_inherited: bool,
+    // 晚于该时间的提交计为迟交（截止时间 + 宽限期，无截止时间时为空）
+    pub late_after: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl HomeworkLatePolicy {
+    /// 以指定截止时间（如分题截止时间）计算迟交判定时间
+    pub fn with_deadline(mut self, deadline: Option<chrono::DateTime<chrono::Utc>>) -> Self {
+        self.late_after =
+            deadline.map(|deadline| deadline + chrono::Duration::minutes(self.grace_minutes));
+        self
+    }
+
+    /// 在 `at` 提交是否计为迟交
+    pub fn is_late(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        self.late_after.is_some_and(|late_after| at > late_after)
+    }
+}
+
+/// 作业分题（多部分作业的子任务，各自独立计分与截止）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPart {
+    pub id: i64,
+    pub homework_id: i64,
+    // 在作业内的排列顺序（从 1 开始）
+    pub position: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub max_score: f64,
+    // 分题截止时间（为空时沿用作业截止时间）
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HomeworkPart {
+    /// 实际生效的截止时间
+    pub fn effective_deadline(&self, homework: &Homework) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.deadline.or(homework.deadline)
+    }
+}
+
+/// 前置作业的完成要求
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum PrerequisiteRequirement {
+    /// 已提交即可
+    #[default]
+    Submitted,
+    /// 最新提交得分达到及格线
+    Passed,
+}
+
+impl std::fmt::Display for PrerequisiteRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrerequisiteRequirement::Submitted => write!(f, "submitted"),
+            PrerequisiteRequirement::Passed => write!(f, "passed"),
+        }
+    }
+}
+
+impl std::str::FromStr for PrerequisiteRequirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "submitted" => Ok(PrerequisiteRequirement::Submitted),
+            "passed" => Ok(PrerequisiteRequirement::Passed),
+            _ => Err(format!("Invalid prerequisite requirement: {s}")),
+        }
+    }
+}
+
+/// 作业前置条件（学生需先完成前置作业才能提交本作业）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPrerequisite {
+    pub id: i64,
+    pub homework_id: i64,
+    // 前置作业 ID（同班级）
+    pub prerequisite_id: i64,
+    pub requirement: PrerequisiteRequirement,
+    // 及格分数（仅 `passed` 生效，为空时取前置作业满分的 60%）
+    pub min_score: Option<f64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HomeworkPrerequisite {
+    /// 默认及格线占满分的比例
+    pub const DEFAULT_PASS_RATIO: f64 = 0.6;
+
+    /// 实际生效的及格分数
+    pub fn pass_score(&self, prerequisite_max_score: f64) -> f64 {
+        self.min_score
+            .unwrap_or(prerequisite_max_score * Self::DEFAULT_PASS_RATIO)
+    }
+
+    /// 学生在前置作业上的完成情况是否满足要求
+    ///
+    /// `submitted` 表示是否有提交，`score` 为最新提交的得分（未评分为空）。
+    pub fn is_met(&self, prerequisite_max_score: f64, submitted: bool, score: Option<f64>) -> bool {
+        match self.requirement {
+            PrerequisiteRequirement::Submitted => submitted,
+            PrerequisiteRequirement::Passed => {
+                score.is_some_and(|s| s >= self.pass_score(prerequisite_max_score))
+            }
+        }
+    }
+}
+
+/// 作业外部资源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum HomeworkLinkType {
+    /// 讲解视频
+    Video,
+    /// 外部文档（在线文档、网页教程等）
+    Document,
+    /// 其他链接
+    #[default]
+    Other,
+}
+
+impl std::fmt::Display for HomeworkLinkType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HomeworkLinkType::Video => write!(f, "video"),
+            HomeworkLinkType::Document => write!(f, "document"),
+            HomeworkLinkType::Other => write!(f, "other"),
+        }
+    }
+}
+
+impl std::str::FromStr for HomeworkLinkType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "video" => Ok(HomeworkLinkType::Video),
+            "document" => Ok(HomeworkLinkType::Document),
+            "other" => Ok(HomeworkLinkType::Other),
+            _ => Err(format!("Invalid homework link type: {s}")),
+        }
+    }
+}
+
+/// 作业外部资源（讲解视频、外部文档等链接，与上传的附件并列展示）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLink {
+    pub id: i64,
+    pub homework_id: i64,
+    pub link_type: HomeworkLinkType,
+    pub title: String,
+    pub url: String,
+    // 在作业内的排列顺序（从 1 开始）
+    pub position: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 学生打开外部资源的记录（每个学生每个链接一条）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkOpen {
+    pub link_id: i64,
+    pub user_id: i64,
+    pub open_count: i32,
+    pub first_opened_at: chrono::DateTime<chrono::Utc>,
+    pub last_opened_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 学生评分后填写的实际用时与体感难度（每个学生每个作业一条）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkFeedback {
+    pub id: i64,
+    pub homework_id: i64,
+    pub user_id: i64,
+    // 实际用时（分钟）
+    pub actual_minutes: i32,
+    // 体感难度（1-5）
+    pub perceived_difficulty: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 作业参考答案（文本部分，文件通过 `solution` 类型的附件关联）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkSolution {
+    pub homework_id: i64,
+    pub content: String,
+    pub reveal_policy: SolutionRevealPolicy,
+    // 教师手动公开的时间
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    // 实际公开（并已通知学生）的时间
+    pub revealed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HomeworkSolution {
+    /// 是否满足公开条件
+    ///
+    /// 已公开或已手动发布的答案始终可见；`all_graded` 表示该作业所有学生的最新提交均已评分。
+    /// 未设置截止时间的作业只能手动公开。
+    pub fn is_due(
+        &self,
+        homework: &Homework,
+        now: chrono::DateTime<chrono::Utc>,
+        all_graded: bool,
+    ) -> bool {
+        if self.revealed_at.is_some() || self.published_at.is_some() {
+            return true;
+        }
+        let deadline_passed = homework.deadline.is_some_and(|deadline| deadline <= now);
+        match self.reveal_policy {
+            SolutionRevealPolicy::Deadline => deadline_passed,
+            SolutionRevealPolicy::AllGraded => deadline_passed && all_graded,
+            SolutionRevealPolicy::Manual => false,
+        }
+    }
+}
+
+/// 缺交零分策略：到达最终截止时间后，为没有任何提交的学生自动记零分
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct MissingGradePolicy {
+    pub homework_id: i64,
+    // 最终截止时间，不早于作业截止时间
+    pub cutoff_at: chrono::DateTime<chrono::Utc>,
+    // 定时任务执行（记零分并通知学生）的时间，每份作业只执行一次
+    pub applied_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 作业豁免记录（某学生无需完成某作业）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkExemption {
+    pub id: i64,
+    pub homework_id: i64,
+    pub user_id: i64,
+    pub reason: Option<String>,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 作业分享链接（未注册用户通过令牌只读访问作业描述和附件）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkShareLink {
+    pub id: i64,
+    pub homework_id: i64,
+    pub token: String,
+    pub created_by: i64,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HomeworkShareLink {
+    /// 链接当前是否可用（未撤销且未过期）
+    pub fn is_active(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+/// 考试访问事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum ExamAccessEvent {
+    /// 查看作业详情
+    View,
+    /// 提交尝试（成功时关联提交 ID，失败时记录原因）
+    Submit,
+    /// 与上一次访问的 IP 不同
+    IpChange,
+    /// 提交时上报的客户端时钟与服务器时间偏差过大，或签名时间戳无效
+    ClockSkew,
+}
+
+impl std::fmt::Display for ExamAccessEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExamAccessEvent::View => write!(f, "view"),
+            ExamAccessEvent::Submit => write!(f, "submit"),
+            ExamAccessEvent::IpChange => write!(f, "ip_change"),
+            ExamAccessEvent::ClockSkew => write!(f, "clock_skew"),
+        }
+    }
+}
+
+impl std::str::FromStr for ExamAccessEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "view" => Ok(ExamAccessEvent::View),
+            "submit" => Ok(ExamAccessEvent::Submit),
+            "ip_change" => Ok(ExamAccessEvent::IpChange),
+            "clock_skew" => Ok(ExamAccessEvent::ClockSkew),
+            _ => Err(format!("Invalid exam access event: {s}")),
+        }
+    }
+}
+
+/// 考试访问异常标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum ExamAnomaly {
+    /// 使用过多个 IP 访问
+    MultipleIps,
+    /// 首次查看到首次成功提交的间隔过短
+    FastSubmission,
+    /// 未查看作业即提交
+    SubmitWithoutView,
+    /// 客户端时钟异常（疑似篡改本机时间）
+    ClockSkew,
+}
+
+/// 考试访问日志（考试模式作业中学生的查看与提交记录）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ExamAccessLog {
+    pub id: i64,
+    pub homework_id: i64,
+    pub user_id: i64,
+    pub event: ExamAccessEvent,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub submission_id: Option<i64>,
+    /// 提交失败原因或 IP 变化说明
+    pub detail: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn homework(mode: SubmissionMode, max_content_length: Option<i32>) -> Homework {
+        Homework {
+            id: 1,
+            class_id: 1,
+            title: "hw".to_string(),
+            description: None,
+            max_score: 100.0,
+            deadline: None,
+            allow_late: false,
+            submission_mode: mode,
+            max_content_length,
+            exam_mode: false,
+            require_self_assessment: false,
+            show_grade_context: false,
+            estimated_minutes: None,
+            difficulty: None,
+            grace_minutes: None,
+            created_by: 1,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn code(result: Result<(), (ErrorCode, String)>) -> Option<ErrorCode> {
+        result.err().map(|(code, _)| code)
+    }
+
+    #[test]
+    fn test_submission_mode_text() {
+        let hw = homework(SubmissionMode::Text, None);
+        assert!(hw.validate_submission("answer", 0).is_ok());
+        assert_eq!(
+            code(hw.validate_submission("answer", 1)),
+            Some(ErrorCode::SubmissionAttachmentNotAllowed)
+        );
+        assert_eq!(
+            code(hw.validate_submission("  ", 0)),
+            Some(ErrorCode::SubmissionContentRequired)
+        );
+    }
+
+    #[test]
+    fn test_submission_mode_attachment() {
+        let hw = homework(SubmissionMode::Attachment, None);
+        assert!(hw.validate_submission("", 1).is_ok());
+        assert_eq!(
+            code(hw.validate_submission("answer", 1)),
+            Some(ErrorCode::SubmissionTextNotAllowed)
+        );
+        assert_eq!(
+            code(hw.validate_submission("", 0)),
+            Some(ErrorCode::SubmissionAttachmentRequired)
+        );
+    }
+
+    #[test]
+    fn test_max_content_length() {
+        let hw = homework(SubmissionMode::Both, Some(3));
+        assert!(hw.validate_submission("作业内", 0).is_ok());
+        assert_eq!(
+            code(hw.validate_submission("作业内容", 0)),
+            Some(ErrorCode::SubmissionContentTooLong)
+        );
+    }
+
+    #[test]
+    fn test_solution_unlocked() {
+        let now = chrono::Utc::now();
+        let mut hw = homework(SubmissionMode::Both, None);
+        assert!(!hw.solution_unlocked(now, false));
+        assert!(hw.solution_unlocked(now, true));
+
+        hw.deadline = Some(now + chrono::Duration::hours(1));
+        assert!(!hw.solution_unlocked(now, false));
+        hw.deadline = Some(now - chrono::Duration::hours(1));
+        assert!(hw.solution_unlocked(now, false));
+    }
+
+    #[test]
+    fn test_late_policy_grace() {
+        let now = chrono::Utc::now();
+        let mut hw = homework(SubmissionMode::Both, None);
+        assert!(!hw.late_policy(5).is_late(now));
+
+        // 截止 2 分钟后提交：系统默认宽限 5 分钟内不计迟交
+        hw.deadline = Some(now - chrono::Duration::minutes(2));
+        let policy = hw.late_policy(5);
+        assert!(policy.grace_inherited);
+        assert!(!policy.is_late(now));
+        assert!(hw.late_policy(0).is_late(now));
+
+        // 作业单独设置的宽限期优先于系统默认
+        hw.grace_minutes = Some(0);
+        let policy = hw.late_policy(5);
+        assert!(!policy.grace_inherited);
+        assert_eq!(policy.grace_minutes, 0);
+        assert!(policy.is_late(now));
+
+        // 分题截止时间同样叠加宽限期
+        let policy = policy.with_deadline(Some(now + chrono::Duration::minutes(1)));
+        assert!(!policy.is_late(now));
+        assert!(policy.with_deadline(None).late_after.is_none());
+    }
+
+    #[test]
+    fn test_solution_is_due() {
+        let now = chrono::Utc::now();
+        let mut hw = homework(SubmissionMode::Both, None);
+        let mut solution = HomeworkSolution {
+            homework_id: 1,
+            content: "answer".to_string(),
+            reveal_policy: SolutionRevealPolicy::Deadline,
+            published_at: None,
+            revealed_at: None,
+            created_by: 1,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // 无截止时间时只能手动公开
+        assert!(!solution.is_due(&hw, now, true));
+
+        hw.deadline = Some(now - chrono::Duration::hours(1));
+        assert!(solution.is_due(&hw, now, false));
+
+        solution.reveal_policy = SolutionRevealPolicy::AllGraded;
+        assert!(!solution.is_due(&hw, now, false));
+        assert!(solution.is_due(&hw, now, true));
+
+        solution.reveal_policy = SolutionRevealPolicy::Manual;
+        assert!(!solution.is_due(&hw, now, true));
+        solution.published_at = Some(now);
+        assert!(solution.is_due(&hw, now, false));
+    }
+
+    #[test]
+    fn test_prerequisite_is_met() {
+        let mut prerequisite = HomeworkPrerequisite {
+            id: 1,
+            homework_id: 2,
+            prerequisite_id: 1,
+            requirement: PrerequisiteRequirement::Submitted,
+            min_score: None,
+            created_at: chrono::Utc::now(),
+        };
+        assert!(prerequisite.is_met(100.0, true, None));
+        assert!(!prerequisite.is_met(100.0, false, None));
+
+        // 默认及格线为满分的 60%
+        prerequisite.requirement = PrerequisiteRequirement::Passed;
+        assert!(!prerequisite.is_met(100.0, true, None));
+        assert!(!prerequisite.is_met(100.0, true, Some(59.5)));
+        assert!(prerequisite.is_met(100.0, true, Some(60.0)));
+
+        prerequisite.min_score = Some(80.0);
+        assert!(!prerequisite.is_met(100.0, true, Some(79.0)));
+        assert!(prerequisite.is_met(100.0, true, Some(80.0)));
+    }
+}
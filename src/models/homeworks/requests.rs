@@ -1,5 +1,9 @@
 use crate::models::common::pagination::PaginationQuery;
-use crate::models::homeworks::entities::{DeadlineFilter, HomeworkUserStatus};
+use crate::models::homeworks::entities::{
+    AttachmentKind, DeadlineFilter, ExamAccessEvent, HomeworkLinkType, HomeworkUserStatus,
+    PrerequisiteRequirement, SolutionRevealPolicy, SubmissionMode,
+};
+use crate::models::organizations::entities::TenantScope;
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use ts_rs::TS;
@@ -14,7 +18,111 @@ pub struct CreateHomeworkRequest {
     pub max_score: Option<f64>,
     pub deadline: Option<DateTime<Utc>>, // ISO 8601 格式，如 "2026-01-24T12:00:00Z"
     pub allow_late: Option<bool>,
-    pub attachments: Option<Vec<String>>, // download_token 列表
+    pub submission_mode: Option<SubmissionMode>, // 默认 both
+    pub max_content_length: Option<i32>,         // 文本内容最大字符数，不传表示不限制
+    pub exam_mode: Option<bool>,                 // 考试模式，默认 false
+    pub require_self_assessment: Option<bool>,   // 提交时须附带自评，默认 false
+    pub show_grade_context: Option<bool>,        // 学生成绩附带班级相对位置，默认 false
+    pub estimated_minutes: Option<i32>,          // 预计用时（分钟），不传表示未设置
+    pub difficulty: Option<i32>,                 // 难度（1-5），不传表示未设置
+    pub grace_minutes: Option<i32>,              // 迟交宽限期（分钟），不传沿用系统设置
+    pub attachments: Option<Vec<HomeworkAttachmentInput>>,
+}
+
+/// 批量创建作业请求（同一份作业发布到多个班级）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct BatchCreateHomeworkRequest {
+    pub class_ids: Vec<i64>,
+    pub title: String,
+    pub description: Option<String>,
+    pub max_score: Option<f64>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub allow_late: Option<bool>,
+    pub submission_mode: Option<SubmissionMode>,
+    pub max_content_length: Option<i32>,
+    pub exam_mode: Option<bool>,
+    pub require_self_assessment: Option<bool>,
+    pub show_grade_context: Option<bool>,
+    pub estimated_minutes: Option<i32>,
+    pub difficulty: Option<i32>,
+    pub grace_minutes: Option<i32>,
+    pub attachments: Option<Vec<HomeworkAttachmentInput>>,
+}
+
+impl BatchCreateHomeworkRequest {
+    /// 生成指定班级的创建请求
+    pub fn for_class(&self, class_id: i64) -> CreateHomeworkRequest {
+        CreateHomeworkRequest {
+            class_id,
+            title: self.title.clone(),
+            description: self.description.clone(),
+            max_score: self.max_score,
+            deadline: self.deadline,
+            allow_late: self.allow_late,
+            submission_mode: self.submission_mode,
+            max_content_length: self.max_content_length,
+            exam_mode: self.exam_mode,
+            require_self_assessment: self.require_self_assessment,
+            show_grade_context: self.show_grade_context,
+            estimated_minutes: self.estimated_minutes,
+            difficulty: self.difficulty,
+            grace_minutes: self.grace_minutes,
+            attachments: self.attachments.clone(),
+        }
+    }
+}
+
+/// 作业导入包清单（YAML）
+///
+/// 顶层为 `homeworks` 列表；附件与说明文件使用相对于清单所在 zip 包根目录的路径。
+#[derive(Debug, Deserialize)]
+pub struct HomeworkBundleManifest {
+    pub homeworks: Vec<HomeworkBundleItem>,
+}
+
+/// 导入包中的单个作业定义
+#[derive(Debug, Deserialize)]
+pub struct HomeworkBundleItem {
+    pub title: String,
+    /// 作业说明（Markdown），与 `description_file` 二选一
+    pub description: Option<String>,
+    /// 作业说明文件的相对路径（Markdown）
+    pub description_file: Option<String>,
+    pub max_score: Option<f64>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub allow_late: Option<bool>,
+    pub submission_mode: Option<SubmissionMode>,
+    pub max_content_length: Option<i32>,
+    pub exam_mode: Option<bool>,
+    pub require_self_assessment: Option<bool>,
+    pub show_grade_context: Option<bool>,
+    pub estimated_minutes: Option<i32>,
+    pub difficulty: Option<i32>,
+    pub grace_minutes: Option<i32>,
+    #[serde(default)]
+    pub attachments: Vec<HomeworkBundleAttachment>,
+}
+
+/// 导入包中的附件：直接写相对路径（视为参考资料），或同时指定用途
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum HomeworkBundleAttachment {
+    Path(String),
+    WithKind {
+        path: String,
+        #[serde(default)]
+        kind: AttachmentKind,
+    },
+}
+
+impl HomeworkBundleAttachment {
+    pub fn parts(&self) -> (&str, AttachmentKind) {
+        match self {
+            HomeworkBundleAttachment::Path(path) => (path, AttachmentKind::default()),
+            HomeworkBundleAttachment::WithKind { path, kind } => (path, *kind),
+        }
+    }
 }
 
 /// 更新作业请求
@@ -26,7 +134,201 @@ pub struct UpdateHomeworkRequest {
     pub max_score: Option<f64>,
     pub deadline: Option<DateTime<Utc>>, // ISO 8601 格式
     pub allow_late: Option<bool>,
-    pub attachments: Option<Vec<String>>, // download_token 列表
+    pub submission_mode: Option<SubmissionMode>,
+    pub max_content_length: Option<i32>, // 传 0 表示取消限制
+    pub exam_mode: Option<bool>,
+    pub require_self_assessment: Option<bool>,
+    pub show_grade_context: Option<bool>,
+    pub estimated_minutes: Option<i32>, // 传 0 表示清除
+    pub difficulty: Option<i32>,        // 传 0 表示清除
+    pub grace_minutes: Option<i32>,     // 传 -1 表示恢复沿用系统设置
+    pub attachments: Option<Vec<HomeworkAttachmentInput>>,
+}
+
+/// 作业预计用时上限：一周
+pub const MAX_ESTIMATED_MINUTES: i32 = 7 * 24 * 60;
+/// 作业难度上限（1-5）
+pub const MAX_DIFFICULTY: i32 = 5;
+
+/// 校验预计用时与难度，0 表示不设置（更新时表示清除）
+pub fn validate_workload_estimate(
+    estimated_minutes: Option<i32>,
+    difficulty: Option<i32>,
+) -> Result<(), String> {
+    if estimated_minutes.is_some_and(|m| !(0..=MAX_ESTIMATED_MINUTES).contains(&m)) {
+        return Err(format!(
+            "预计用时须在 0 到 {MAX_ESTIMATED_MINUTES} 分钟之间"
+        ));
+    }
+    if difficulty.is_some_and(|d| !(0..=MAX_DIFFICULTY).contains(&d)) {
+        return Err(format!("难度须在 1 到 {MAX_DIFFICULTY} 之间"));
+    }
+    Ok(())
+}
+
+/// 作业迟交宽限期上限：一天
+pub const MAX_GRACE_MINUTES: i32 = 24 * 60;
+
+/// 校验迟交宽限期，-1 表示沿用系统设置 `homework.default_grace_minutes`
+pub fn validate_grace_minutes(grace_minutes: Option<i32>) -> Result<(), String> {
+    if grace_minutes.is_some_and(|m| !(-1..=MAX_GRACE_MINUTES).contains(&m)) {
+        return Err(format!("迟交宽限期须在 0 到 {MAX_GRACE_MINUTES} 分钟之间"));
+    }
+    Ok(())
+}
+
+/// 学生提交实际用时与体感难度
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkFeedbackRequest {
+    /// 实际用时（分钟）
+    pub actual_minutes: i32,
+    /// 体感难度（1-5）
+    pub perceived_difficulty: i32,
+}
+
+impl HomeworkFeedbackRequest {
+    /// 校验反馈内容，返回错误说明
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_ESTIMATED_MINUTES).contains(&self.actual_minutes) {
+            return Err(format!(
+                "实际用时须在 1 到 {MAX_ESTIMATED_MINUTES} 分钟之间"
+            ));
+        }
+        if !(1..=MAX_DIFFICULTY).contains(&self.perceived_difficulty) {
+            return Err(format!("体感难度须在 1 到 {MAX_DIFFICULTY} 之间"));
+        }
+        Ok(())
+    }
+}
+
+/// 作业附件：直接传 download_token（视为参考资料），或同时指定用途
+#[derive(Debug, Clone, Deserialize, TS)]
+#[serde(untagged)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub enum HomeworkAttachmentInput {
+    Token(String),
+    WithKind {
+        token: String,
+        #[serde(default)]
+        kind: AttachmentKind,
+    },
+}
+
+impl HomeworkAttachmentInput {
+    pub fn into_parts(self) -> (String, AttachmentKind) {
+        match self {
+            HomeworkAttachmentInput::Token(token) => (token, AttachmentKind::default()),
+            HomeworkAttachmentInput::WithKind { token, kind } => (token, kind),
+        }
+    }
+}
+
+/// 创建作业豁免请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct CreateHomeworkExemptionRequest {
+    pub user_id: i64,
+    pub reason: Option<String>,
+}
+
+/// 创建作业分享链接请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct CreateHomeworkShareLinkRequest {
+    pub expires_at: Option<DateTime<Utc>>, // ISO 8601 格式，默认 7 天后过期
+}
+
+/// 设置作业参考答案请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct UpsertHomeworkSolutionRequest {
+    pub content: String,
+    #[serde(default)]
+    pub reveal_policy: SolutionRevealPolicy, // 默认截止后公开
+}
+
+/// 设置缺交零分策略请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct UpsertMissingGradePolicyRequest {
+    pub cutoff_at: DateTime<Utc>, // ISO 8601 格式，不早于作业截止时间
+}
+
+/// 分题定义（带 `id` 表示修改已有分题，不带则新建）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPartInput {
+    pub id: Option<i64>,
+    pub title: String,
+    pub description: Option<String>,
+    pub max_score: f64,
+    pub deadline: Option<DateTime<Utc>>, // 不传表示沿用作业截止时间
+}
+
+/// 设置作业分题请求（整体替换，按数组顺序排列；未出现的分题将被删除）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ReplaceHomeworkPartsRequest {
+    pub parts: Vec<HomeworkPartInput>,
+}
+
+/// 外部资源定义（带 `id` 表示修改已有链接，不带则新建）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkInput {
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub link_type: HomeworkLinkType, // 默认 other
+    pub title: String,
+    pub url: String, // 仅允许 http / https
+}
+
+/// 设置作业外部资源请求（整体替换，按数组顺序排列；未出现的链接及其打开记录将被删除）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ReplaceHomeworkLinksRequest {
+    pub links: Vec<HomeworkLinkInput>,
+}
+
+/// 前置条件定义
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPrerequisiteInput {
+    pub homework_id: i64, // 前置作业 ID，须与本作业同班级
+    #[serde(default)]
+    pub requirement: PrerequisiteRequirement, // 默认已提交即可
+    pub min_score: Option<f64>, // 仅 passed 生效，不传取满分的 60%
+}
+
+/// 设置作业前置条件请求（整体替换，空数组表示清除）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ReplaceHomeworkPrerequisitesRequest {
+    pub prerequisites: Vec<HomeworkPrerequisiteInput>,
+}
+
+/// 截止时间冲突检查请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct CheckDeadlineRequest {
+    /// 拟设置的截止时间
+    pub deadline: DateTime<Utc>,
+    /// 冲突窗口（小时，前后各算），不传使用系统设置 `homework.deadline_conflict_window_hours`
+    pub window_hours: Option<i64>,
+    /// 修改已有作业时排除该作业本身
+    pub exclude_homework_id: Option<i64>,
+}
+
+impl CheckDeadlineRequest {
+    pub const MAX_WINDOW_HOURS: i64 = 336;
+}
+
+/// 分题得分查询参数（教师可指定学生）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPartScoresQuery {
+    pub user_id: Option<i64>,
 }
 
 /// 作业列表查询参数（HTTP 请求）
@@ -52,6 +354,8 @@ pub struct HomeworkListQuery {
     pub created_by: Option<i64>,
     pub search: Option<String>,
     pub include_stats: Option<bool>,
+    // 租户范围，由服务层根据当前用户填充
+    pub tenant: TenantScope,
 }
 
 /// 跨班级作业列表查询参数（HTTP 请求）
@@ -81,3 +385,15 @@ pub struct AllHomeworksQuery {
     pub search: Option<String>,
     pub include_stats: Option<bool>,
 }
+
+/// 考试访问日志写入参数（存储层使用）
+#[derive(Debug, Clone)]
+pub struct ExamAccessLogInput {
+    pub homework_id: i64,
+    pub user_id: i64,
+    pub event: ExamAccessEvent,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub submission_id: Option<i64>,
+    pub detail: Option<String>,
+}
@@ -1,6 +1,12 @@
 use crate::models::common::pagination::PaginationInfo;
 use crate::models::files::responses::FileInfo;
-use crate::models::homeworks::entities::Homework;
+use crate::models::homeworks::entities::{
+    AttachmentKind, ExamAccessLog, ExamAnomaly, Homework, HomeworkExemption, HomeworkFeedback,
+    HomeworkLatePolicy, HomeworkLink, HomeworkPart, HomeworkPrerequisite, HomeworkShareLink,
+    HomeworkSolution,
+};
+use crate::models::jobs::entities::JobInfo;
+use crate::models::users::entities::User;
 use serde::Serialize;
 use ts_rs::TS;
 
@@ -13,6 +19,17 @@ pub struct HomeworkCreator {
     pub avatar_url: Option<String>,
 }
 
+impl From<User> for HomeworkCreator {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
 pub struct HomeworkResponse {
@@ -40,6 +57,16 @@ pub struct HomeworkWithCreator {
     pub creator: Option<HomeworkCreator>,
 }
 
+/// 创建作业响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct CreateHomeworkResponse {
+    #[serde(flatten)]
+    pub homework: Homework,
+    /// 班级学生通知的后台投递任务，可通过 `GET /jobs/{id}` 查询进度
+    pub notification_job: JobInfo,
+}
+
 /// 我的提交摘要（用于作业列表显示提交状态）
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
@@ -74,6 +101,20 @@ pub struct HomeworkListItem {
     pub my_submission: Option<MySubmissionSummary>,
     /// 作业统计摘要（仅教师/管理员视角且请求 include_stats=true 时有值）
     pub stats_summary: Option<HomeworkStatsSummary>,
+    /// 当前用户是否被豁免该作业（仅学生视角有意义）
+    pub is_exempted: bool,
+    /// 当前用户尚未满足前置条件、暂不能提交（仅学生视角有意义）
+    pub is_locked: bool,
+}
+
+/// 作业附件（带用途）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkAttachment {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub file: FileInfo,
+    pub kind: AttachmentKind,
 }
 
 /// 作业详情（包含附件和创建者）
@@ -82,10 +123,65 @@ pub struct HomeworkListItem {
 pub struct HomeworkDetail {
     #[serde(flatten)]
     pub homework: Homework,
-    pub attachments: Vec<FileInfo>,
+    /// 实际生效的迟交策略（含沿用的系统默认宽限期）
+    pub late_policy: HomeworkLatePolicy,
+    /// 当前用户可见的附件（未开放的参考答案不在其中）
+    pub attachments: Vec<HomeworkAttachment>,
+    /// 尚未开放的参考答案数量
+    pub locked_solutions: i64,
+    /// 参考答案文本（未设置或尚未公开时为空）
+    pub solution: Option<HomeworkSolution>,
+    /// 已设置参考答案但尚未对当前用户公开
+    pub solution_locked: bool,
+    /// 分题列表（未拆分的作业为空）
+    pub parts: Vec<HomeworkPart>,
+    /// 外部资源（讲解视频、外部文档等，按顺序排列）
+    pub links: Vec<HomeworkLink>,
     pub creator: Option<HomeworkCreator>,
 }
 
+/// 批量创建作业的单个班级结果
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct BatchCreateHomeworkItemResult {
+    pub class_id: i64,
+    pub success: bool,
+    pub homework: Option<Homework>,
+    pub message: Option<String>,
+}
+
+/// 批量创建作业响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct BatchCreateHomeworkResponse {
+    pub total: usize,
+    pub success: usize,
+    pub failed: usize,
+    pub results: Vec<BatchCreateHomeworkItemResult>,
+}
+
+/// 导入作业的单条结果
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkImportItemResult {
+    /// 在清单 `homeworks` 列表中的序号（从 0 开始）
+    pub index: usize,
+    pub title: String,
+    pub success: bool,
+    pub homework: Option<Homework>,
+    pub message: Option<String>,
+}
+
+/// 导入作业响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkImportResponse {
+    pub total: usize,
+    pub success: usize,
+    pub failed: usize,
+    pub results: Vec<HomeworkImportItemResult>,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
 pub struct HomeworkListResponse {
@@ -130,3 +226,249 @@ pub struct AllHomeworksResponse {
     /// 服务器时间（ISO 8601），用于前端统一时间判断
     pub server_time: String,
 }
+
+/// 作业豁免列表项（包含被豁免学生信息）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkExemptionItem {
+    #[serde(flatten)]
+    pub exemption: HomeworkExemption,
+    pub user: Option<HomeworkCreator>,
+}
+
+/// 作业豁免列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkExemptionListResponse {
+    pub items: Vec<HomeworkExemptionItem>,
+}
+
+/// 作业分享链接列表项
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkShareLinkItem {
+    #[serde(flatten)]
+    pub link: HomeworkShareLink,
+    /// 链接当前是否可用（未撤销且未过期）
+    pub is_active: bool,
+}
+
+/// 作业分享链接列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkShareLinkListResponse {
+    pub items: Vec<HomeworkShareLinkItem>,
+}
+
+/// 分享作业的附件（不暴露 download_token）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct SharedHomeworkAttachment {
+    pub id: i64,
+    pub original_name: String,
+    pub file_size: i64,
+    pub file_type: String,
+    /// 附件下载地址（无需登录）
+    pub download_url: String,
+}
+
+/// 通过分享链接访问的作业（只读，不包含提交信息）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct SharedHomeworkResponse {
+    pub title: String,
+    pub description: Option<String>,
+    pub max_score: f64,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    pub allow_late: bool,
+    pub class_name: Option<String>,
+    pub attachments: Vec<SharedHomeworkAttachment>,
+    /// 分享链接过期时间
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 学生考试访问汇总
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ExamAccessSummary {
+    pub user_id: i64,
+    pub user: Option<HomeworkCreator>,
+    pub view_count: i64,
+    pub submit_attempts: i64,
+    pub first_view_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub first_submit_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// 首次查看到首次成功提交的间隔（秒）
+    pub seconds_to_submit: Option<i64>,
+    /// 访问过的全部 IP（按首次出现顺序）
+    pub ip_addresses: Vec<String>,
+    pub anomalies: Vec<ExamAnomaly>,
+}
+
+/// 考试访问汇总列表（按学生）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ExamAccessSummaryListResponse {
+    pub homework_id: i64,
+    pub items: Vec<ExamAccessSummary>,
+}
+
+/// 单个学生的考试访问详情
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ExamAccessDetailResponse {
+    pub summary: ExamAccessSummary,
+    pub logs: Vec<ExamAccessLog>,
+}
+
+/// 作业分题列表
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPartListResponse {
+    pub homework_id: i64,
+    pub items: Vec<HomeworkPart>,
+    /// 各分题满分之和（即作业满分）
+    pub total_max_score: f64,
+}
+
+/// 作业外部资源列表
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkListResponse {
+    pub homework_id: i64,
+    pub items: Vec<HomeworkLink>,
+}
+
+/// 学生本人的用时与难度反馈（附带教师预估，便于对照填写）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkFeedbackResponse {
+    pub homework_id: i64,
+    pub estimated_minutes: Option<i32>,
+    pub difficulty: Option<i32>,
+    /// 本人最新提交已评分，可以填写或修改反馈
+    pub can_submit: bool,
+    pub feedback: Option<HomeworkFeedback>,
+}
+
+/// 打开外部资源的结果（前端随后跳转到 `url`）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkOpenResponse {
+    pub link_id: i64,
+    pub url: String,
+}
+
+/// 单个外部资源的打开情况
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkOpenStat {
+    #[serde(flatten)]
+    pub link: HomeworkLink,
+    /// 打开过的学生数
+    pub opened_students: i64,
+    /// 学生打开总次数
+    pub total_opens: i64,
+    /// 打开率（百分比）
+    pub open_rate: f64,
+}
+
+/// 学生打开外部资源的情况
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkStudentOpens {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    /// 打开过的链接 ID
+    pub opened_link_ids: Vec<i64>,
+    pub last_opened_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 外部资源打开统计（教师查看学生是否打开了学习材料）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkLinkStatsResponse {
+    pub homework_id: i64,
+    /// 需要完成作业的学生数（不含被豁免学生）
+    pub total_students: i64,
+    pub links: Vec<HomeworkLinkOpenStat>,
+    /// 每个学生的打开情况，未打开任何链接的学生排在前面
+    pub students: Vec<HomeworkLinkStudentOpens>,
+}
+
+/// 学生在单个分题上的提交与得分（取该分题最新提交）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPartScore {
+    pub part_id: i64,
+    pub title: String,
+    pub max_score: f64,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    pub submission_id: Option<i64>,
+    pub is_late: bool,
+    pub score: Option<f64>,
+}
+
+/// 学生多部分作业得分汇总
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPartScoresResponse {
+    pub homework_id: i64,
+    pub user_id: i64,
+    pub parts: Vec<HomeworkPartScore>,
+    /// 已评分分题的得分之和（尚无评分时为空）
+    pub total_score: Option<f64>,
+    pub max_score: f64,
+    /// 所有分题均已提交且评分
+    pub fully_graded: bool,
+}
+
+/// 作业前置条件项（附前置作业概要与当前学生的完成情况）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPrerequisiteItem {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub prerequisite: HomeworkPrerequisite,
+    pub title: String,
+    /// 实际生效的及格分数（仅 `passed` 有值）
+    pub pass_score: Option<f64>,
+    /// 当前学生是否已满足（教师/管理员视角为空）
+    pub satisfied: Option<bool>,
+}
+
+/// 作业前置条件列表
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPrerequisiteListResponse {
+    pub homework_id: i64,
+    pub items: Vec<HomeworkPrerequisiteItem>,
+    /// 当前学生尚未满足全部前置条件
+    pub locked: bool,
+}
+
+/// 截止时间冲突的作业
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct DeadlineConflictItem {
+    pub homework_id: i64,
+    pub title: String,
+    pub class_id: i64,
+    pub class_name: String,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    /// 是否为同一班级的作业
+    pub same_class: bool,
+    /// 受影响的本班学生数（同班作业为全部学生）
+    pub shared_student_count: i64,
+}
+
+/// 截止时间冲突检查结果
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct DeadlineConflictResponse {
+    pub class_id: i64,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    pub window_hours: i64,
+    /// 按截止时间升序
+    pub conflicts: Vec<DeadlineConflictItem>,
+}
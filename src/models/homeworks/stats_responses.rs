@@ -14,6 +14,54 @@ pub struct HomeworkStatsResponse {
     pub score_stats: Option<ScoreStats>,
     pub score_distribution: Vec<ScoreRange>,
     pub unsubmitted_students: Vec<UnsubmittedStudent>,
+    /// 分题统计（未拆分的作业为空）
+    pub parts: Vec<HomeworkPartStats>,
+    /// 学生自评汇总（匿名，无人填写时为空）
+    pub self_assessment: Option<SelfAssessmentStats>,
+    /// 提交正文长度汇总（无人提交时为空）
+    pub content_stats: Option<ContentStats>,
+}
+
+/// 提交正文长度汇总（按学生最新提交统计，分题作业为各分题之和）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct ContentStats {
+    pub word_count: LengthStats,
+    pub char_count: LengthStats,
+    pub attachment_count: LengthStats,
+}
+
+/// 长度统计
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct LengthStats {
+    pub average: f64,
+    pub max: i64,
+    pub min: i64,
+}
+
+/// 学生自评汇总（仅统计每位学生最新提交的自评）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct SelfAssessmentStats {
+    pub response_count: i64,
+    pub average_confidence: f64,
+    pub average_time_spent_minutes: f64,
+    /// 各把握程度的人数，下标 0 对应把握程度 1
+    pub confidence_distribution: Vec<i64>,
+}
+
+/// 单个分题的统计
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/homework.ts")]
+pub struct HomeworkPartStats {
+    pub part_id: i64,
+    pub title: String,
+    pub max_score: f64,
+    pub submitted_count: i64,
+    pub graded_count: i64,
+    pub late_count: i64,
+    pub average_score: Option<f64>,
 }
 
 /// 分数统计
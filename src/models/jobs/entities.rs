@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+// 后台任务状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/job.ts")]
+pub enum JobStatus {
+    Pending,   // 排队中
+    Running,   // 执行中
+    Completed, // 已完成
+    Failed,    // 执行失败
+}
+
+impl JobStatus {
+    // 是否已结束（完成或失败）
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed)
+    }
+}
+
+// 后台任务信息
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/job.ts")]
+pub struct JobInfo {
+    // 任务ID
+    pub id: String,
+    // 任务类型（如 transcript）
+    pub kind: String,
+    // 任务状态
+    pub status: JobStatus,
+    // 失败原因
+    pub error: Option<String>,
+    // 结果下载地址（完成后有值）
+    pub download_url: Option<String>,
+    // 创建时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    // 结束时间
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// 后台任务队列状态
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/job.ts")]
+pub struct JobQueueStats {
+    // 队列名
+    pub name: String,
+    // 并发上限
+    pub concurrency: usize,
+    // 积压上限
+    pub max_depth: usize,
+    // 积压超限时的处理方式（reject / delay）
+    pub overflow: String,
+    // 当前积压（排队 + 执行中）
+    pub depth: usize,
+    // 执行中
+    pub running: usize,
+    // 排队中
+    pub queued: usize,
+    // 累计接受的任务数
+    pub submitted: usize,
+    // 累计结束的任务数
+    pub completed: usize,
+    // 累计被拒绝的任务数
+    pub rejected: usize,
+}
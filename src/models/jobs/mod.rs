@@ -0,0 +1,2 @@
+// 后台任务实体定义
+pub mod entities;
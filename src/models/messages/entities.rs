@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 班级内私信
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct Message {
+    pub id: i64,
+    pub class_id: i64,
+    pub sender_id: i64,
+    pub recipient_id: i64,
+    pub content: String,
+    /// 接收方阅读时间，未读为空
+    pub read_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Message {
+    /// 会话中相对 `user_id` 的另一方
+    pub fn peer_id(&self, user_id: i64) -> i64 {
+        if self.sender_id == user_id {
+            self.recipient_id
+        } else {
+            self.sender_id
+        }
+    }
+}
@@ -0,0 +1,8 @@
+// 私信实体定义
+pub mod entities;
+
+// 私信请求模型
+pub mod requests;
+
+// 私信响应模型
+pub mod responses;
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+/// 发送私信请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct SendMessageRequest {
+    pub class_id: i64,
+    pub recipient_id: i64,
+    pub content: String,
+}
+
+/// 会话列表查询参数
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct ConversationListQuery {
+    /// 只看某个班级的会话
+    pub class_id: Option<i64>,
+}
+
+/// 会话消息查询参数（按 ID 倒序游标分页）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct ConversationMessagesQuery {
+    /// 只返回 ID 小于该值的消息，用于向前翻页
+    pub before_id: Option<i64>,
+    pub size: Option<i64>,
+}
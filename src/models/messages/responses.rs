@@ -0,0 +1,59 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::entities::Message;
+
+/// 会话对方信息
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct MessagePeer {
+    pub id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// 会话摘要
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct ConversationSummary {
+    pub class_id: i64,
+    pub class_name: String,
+    pub peer: MessagePeer,
+    pub last_message: Message,
+    /// 对方发来的未读消息数
+    pub unread_count: i64,
+}
+
+/// 会话列表响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct ConversationListResponse {
+    pub items: Vec<ConversationSummary>,
+    pub total_unread: i64,
+}
+
+/// 会话消息响应（新消息在前）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct ConversationMessagesResponse {
+    pub class_id: i64,
+    pub peer: MessagePeer,
+    pub items: Vec<Message>,
+    /// 是否还有更早的消息
+    pub has_more: bool,
+}
+
+/// 标记会话已读响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct MarkConversationReadResponse {
+    pub marked: i64,
+}
+
+/// 未读私信数响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/message.ts")]
+pub struct UnreadMessageCountResponse {
+    pub unread_count: i64,
+}
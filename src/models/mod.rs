@@ -7,6 +7,9 @@ pub mod auth;
 // 业务模块
 pub mod users;
 
+// 组织模块
+pub mod organizations;
+
 // 文件模块
 pub mod files;
 
@@ -28,9 +31,42 @@ pub mod grades;
 // 通知模块
 pub mod notifications;
 
+// 结业证书模块
+pub mod certificates;
+
 // 系统模块
 pub mod system;
 
+// 用量统计模块
+pub mod usage;
+
+// 后台任务模块
+pub mod jobs;
+
+// 统计分析模块
+pub mod analytics;
+
+// 事务发件箱模块
+pub mod outbox;
+
+// 内容审核模块
+pub mod moderation;
+
+// 私信模块
+pub mod messages;
+
+// 表情回应模块
+pub mod reactions;
+
+// 评分量规模块
+pub mod rubrics;
+
+// 家长/导师查看授权模块
+pub mod viewers;
+
+// 开发模式示例数据模块
+pub mod dev;
+
 // 重新导出通用类型
 pub use common::{ApiResponse, ErrorCode, PaginationInfo};
 
@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 内容审核处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ModerationMode {
+    /// 不审核
+    Off,
+    /// 允许发布，记录标记供教师复核
+    Flag,
+    /// 拒绝发布，同时记录标记
+    Block,
+}
+
+impl std::fmt::Display for ModerationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationMode::Off => write!(f, "off"),
+            ModerationMode::Flag => write!(f, "flag"),
+            ModerationMode::Block => write!(f, "block"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModerationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ModerationMode::Off),
+            "flag" => Ok(ModerationMode::Flag),
+            "block" => Ok(ModerationMode::Block),
+            _ => Err(format!("Invalid moderation mode: {s}")),
+        }
+    }
+}
+
+/// 被审核的内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ModerationContentType {
+    /// 提交的文本内容
+    Submission,
+    /// 评分评语
+    GradeComment,
+    /// 班级私信
+    Message,
+}
+
+impl std::fmt::Display for ModerationContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationContentType::Submission => write!(f, "submission"),
+            ModerationContentType::GradeComment => write!(f, "grade_comment"),
+            ModerationContentType::Message => write!(f, "message"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModerationContentType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "submission" => Ok(ModerationContentType::Submission),
+            "grade_comment" => Ok(ModerationContentType::GradeComment),
+            "message" => Ok(ModerationContentType::Message),
+            _ => Err(format!("Invalid moderation content type: {s}")),
+        }
+    }
+}
+
+/// 命中来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ModerationSource {
+    /// 系统设置中的违禁词列表
+    WordList,
+    /// 外部审核接口
+    External,
+}
+
+impl std::fmt::Display for ModerationSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationSource::WordList => write!(f, "word_list"),
+            ModerationSource::External => write!(f, "external"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModerationSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "word_list" => Ok(ModerationSource::WordList),
+            "external" => Ok(ModerationSource::External),
+            _ => Err(format!("Invalid moderation source: {s}")),
+        }
+    }
+}
+
+/// 审核标记的复核状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ModerationFlagStatus {
+    /// 待教师复核
+    Pending,
+    /// 误报，已忽略
+    Dismissed,
+    /// 确认违规
+    Confirmed,
+}
+
+impl std::fmt::Display for ModerationFlagStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationFlagStatus::Pending => write!(f, "pending"),
+            ModerationFlagStatus::Dismissed => write!(f, "dismissed"),
+            ModerationFlagStatus::Confirmed => write!(f, "confirmed"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModerationFlagStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ModerationFlagStatus::Pending),
+            "dismissed" => Ok(ModerationFlagStatus::Dismissed),
+            "confirmed" => Ok(ModerationFlagStatus::Confirmed),
+            _ => Err(format!("Invalid moderation flag status: {s}")),
+        }
+    }
+}
+
+/// 内容审核标记
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ModerationFlag {
+    pub id: i64,
+    pub class_id: i64,
+    /// 内容作者
+    pub user_id: i64,
+    pub content_type: ModerationContentType,
+    /// 内容记录 ID（提交 ID 或评分 ID），被拦截的内容为空
+    pub content_id: Option<i64>,
+    pub source: ModerationSource,
+    /// 命中的违禁词或外部接口给出的原因
+    pub reason: String,
+    /// 内容摘录（最多 500 字符）
+    pub excerpt: String,
+    /// 内容是否被拦截（未发布）
+    pub blocked: bool,
+    pub status: ModerationFlagStatus,
+    pub reviewed_by: Option<i64>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 可被举报的内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ReportContentType {
+    /// 提交的评语（content_id 为评分 ID）
+    GradeComment,
+    /// 班级公告（content_id 为班级动态 ID）
+    Announcement,
+    /// 班级私信（content_id 为私信 ID）
+    Message,
+}
+
+impl std::fmt::Display for ReportContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportContentType::GradeComment => write!(f, "grade_comment"),
+            ReportContentType::Announcement => write!(f, "announcement"),
+            ReportContentType::Message => write!(f, "message"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportContentType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "grade_comment" => Ok(ReportContentType::GradeComment),
+            "announcement" => Ok(ReportContentType::Announcement),
+            "message" => Ok(ReportContentType::Message),
+            _ => Err(format!("Invalid report content type: {s}")),
+        }
+    }
+}
+
+/// 举报处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ContentReportStatus {
+    /// 待处理
+    Pending,
+    /// 已处理
+    Resolved,
+}
+
+impl std::fmt::Display for ContentReportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentReportStatus::Pending => write!(f, "pending"),
+            ContentReportStatus::Resolved => write!(f, "resolved"),
+        }
+    }
+}
+
+impl std::str::FromStr for ContentReportStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ContentReportStatus::Pending),
+            "resolved" => Ok(ContentReportStatus::Resolved),
+            _ => Err(format!("Invalid content report status: {s}")),
+        }
+    }
+}
+
+/// 举报处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub enum ContentReportAction {
+    /// 驳回举报，内容保留
+    Dismiss,
+    /// 删除被举报的内容
+    DeleteContent,
+    /// 保留内容，向作者发送警告通知
+    WarnUser,
+}
+
+impl std::fmt::Display for ContentReportAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentReportAction::Dismiss => write!(f, "dismiss"),
+            ContentReportAction::DeleteContent => write!(f, "delete_content"),
+            ContentReportAction::WarnUser => write!(f, "warn_user"),
+        }
+    }
+}
+
+impl std::str::FromStr for ContentReportAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dismiss" => Ok(ContentReportAction::Dismiss),
+            "delete_content" => Ok(ContentReportAction::DeleteContent),
+            "warn_user" => Ok(ContentReportAction::WarnUser),
+            _ => Err(format!("Invalid content report action: {s}")),
+        }
+    }
+}
+
+/// 内容举报
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ContentReport {
+    pub id: i64,
+    pub class_id: i64,
+    pub reporter_id: i64,
+    /// 被举报内容的作者
+    pub author_id: i64,
+    pub content_type: ReportContentType,
+    pub content_id: i64,
+    /// 举报理由
+    pub reason: String,
+    /// 举报时的内容摘录（最多 500 字符），内容删除后仍保留
+    pub excerpt: String,
+    pub status: ContentReportStatus,
+    /// 处理方式，待处理时为空
+    pub resolution: Option<ContentReportAction>,
+    /// 处理备注（警告用户时一并发送给作者）
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<i64>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 被举报内容的归属信息（存储层查询结果）
+#[derive(Debug, Clone)]
+pub struct ReportedContent {
+    pub class_id: i64,
+    /// 内容作者
+    pub author_id: i64,
+    /// 除班级教师外唯一能查看该内容的用户（评语为提交者，私信为接收方）；公告为空，全体班级成员可见
+    pub viewer_id: Option<i64>,
+    /// 内容文本
+    pub text: String,
+}
@@ -0,0 +1,8 @@
+// 内容审核实体定义
+pub mod entities;
+
+// 内容审核请求模型
+pub mod requests;
+
+// 内容审核响应模型
+pub mod responses;
@@ -0,0 +1,101 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+use super::entities::{
+    ContentReportAction, ContentReportStatus, ModerationContentType, ModerationFlagStatus,
+    ModerationSource, ReportContentType,
+};
+
+/// 审核标记列表查询参数
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ModerationFlagListQuery {
+    /// 按复核状态筛选，不传返回全部
+    pub status: Option<ModerationFlagStatus>,
+}
+
+/// 复核审核标记请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ReviewModerationFlagRequest {
+    /// dismissed 或 confirmed
+    pub status: ModerationFlagStatus,
+}
+
+/// 审核标记写入参数（存储层使用）
+#[derive(Debug, Clone)]
+pub struct ModerationFlagInput {
+    pub class_id: i64,
+    pub user_id: i64,
+    pub content_type: ModerationContentType,
+    pub content_id: Option<i64>,
+    pub source: ModerationSource,
+    pub reason: String,
+    pub excerpt: String,
+    pub blocked: bool,
+}
+
+/// 举报内容请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct CreateContentReportRequest {
+    pub content_type: ReportContentType,
+    pub content_id: i64,
+    /// 举报理由
+    pub reason: String,
+}
+
+impl CreateContentReportRequest {
+    /// 举报理由最大长度（字符）
+    pub const MAX_REASON_CHARS: usize = 500;
+
+    /// 校验举报理由：去除首尾空白后不能为空且不超过最大长度
+    pub fn validate(&self) -> Result<(), String> {
+        let chars = self.reason.trim().chars().count();
+        if chars == 0 {
+            return Err("请填写举报理由".to_string());
+        }
+        if chars > Self::MAX_REASON_CHARS {
+            return Err(format!(
+                "举报理由不能超过 {} 个字符",
+                Self::MAX_REASON_CHARS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 举报列表查询参数
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ContentReportListQuery {
+    /// 按处理状态筛选，不传返回全部
+    pub status: Option<ContentReportStatus>,
+}
+
+/// 处理举报请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ResolveContentReportRequest {
+    pub action: ContentReportAction,
+    /// 处理备注，最多 500 字符；警告用户时作为警告内容发送给作者
+    #[ts(optional)]
+    pub note: Option<String>,
+}
+
+impl ResolveContentReportRequest {
+    /// 处理备注最大长度（字符）
+    pub const MAX_NOTE_CHARS: usize = 500;
+}
+
+/// 举报写入参数（存储层使用）
+#[derive(Debug, Clone)]
+pub struct ContentReportInput {
+    pub class_id: i64,
+    pub reporter_id: i64,
+    pub author_id: i64,
+    pub content_type: ReportContentType,
+    pub content_id: i64,
+    pub reason: String,
+    pub excerpt: String,
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::entities::{ContentReport, ModerationFlag};
+
+/// 班级审核标记列表响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ModerationFlagListResponse {
+    pub class_id: i64,
+    pub items: Vec<ModerationFlag>,
+}
+
+/// 班级举报列表响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/moderation.ts")]
+pub struct ContentReportListResponse {
+    pub class_id: i64,
+    pub items: Vec<ContentReport>,
+}
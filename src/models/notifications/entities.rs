@@ -7,31 +7,105 @@ use ts_rs::TS;
 #[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
 pub enum NotificationType {
     // 作业相关
-    HomeworkCreated,  // 新作业发布
-    HomeworkUpdated,  // 作业更新
-    HomeworkDeadline, // 作业即将截止
+    HomeworkCreated,   // 新作业发布
+    HomeworkUpdated,   // 作业更新
+    HomeworkDeadline,  // 作业即将截止
+    SolutionPublished, // 参考答案已公开
 
     // 提交相关
-    SubmissionReceived, // 收到新提交（通知教师）
+    SubmissionReceived,    // 收到新提交（通知教师）
+    GradingOverdue,        // 提交超过批改时限（通知教师）
+    ResubmissionRequested, // 提交被退回重交（通知学生）
 
     // 评分相关
-    GradeReceived, // 收到评分（通知学生）
-    GradeUpdated,  // 评分修改（通知学生）
+    GradeReceived,        // 收到评分（通知学生）
+    GradeUpdated,         // 评分修改（通知学生）
+    MissingGradeAssigned, // 缺交作业被自动记零分（通知学生）
 
     // 班级相关
-    ClassJoined,      // 加入班级
-    ClassRoleChanged, // 班级角色变更
+    ClassJoined,       // 加入班级
+    ClassRoleChanged,  // 班级角色变更
+    ClassAnnouncement, // 教师群发的班级通知
+
+    // 提及相关
+    Mentioned, // 在评语中被 @提及
+
+    // 角色申请相关
+    RoleRequestSubmitted, // 收到新的角色申请（通知管理员）
+    RoleRequestReviewed,  // 角色申请已审核（通知申请人）
+
+    // 结业证书相关
+    CertificateIssued, // 获得结业证书（通知学生）
+
+    // 文件相关
+    FileQuarantineReviewed, // 被隔离的上传文件已审核（通知上传者）
+
+    // 私信相关
+    MessageReceived, // 离线时收到班级私信
+
+    // 举报相关
+    ContentReported, // 班级内有新的内容举报（通知班级教师）
+    ContentWarning,  // 发布的内容被举报后收到警告（通知作者）
+
+    // 导出相关
+    ScheduledExportFailed, // 定时导出生成或投递失败（通知创建者）
+
+    // 账号安全相关
+    NewDeviceLogin, // 账号在新设备上登录
 }
 
 impl NotificationType {
     pub const HOMEWORK_CREATED: &'static str = "homework_created";
     pub const HOMEWORK_UPDATED: &'static str = "homework_updated";
     pub const HOMEWORK_DEADLINE: &'static str = "homework_deadline";
+    pub const SOLUTION_PUBLISHED: &'static str = "solution_published";
     pub const SUBMISSION_RECEIVED: &'static str = "submission_received";
+    pub const GRADING_OVERDUE: &'static str = "grading_overdue";
+    pub const RESUBMISSION_REQUESTED: &'static str = "resubmission_requested";
     pub const GRADE_RECEIVED: &'static str = "grade_received";
     pub const GRADE_UPDATED: &'static str = "grade_updated";
+    pub const MISSING_GRADE_ASSIGNED: &'static str = "missing_grade_assigned";
     pub const CLASS_JOINED: &'static str = "class_joined";
     pub const CLASS_ROLE_CHANGED: &'static str = "class_role_changed";
+    pub const CLASS_ANNOUNCEMENT: &'static str = "class_announcement";
+    pub const MENTIONED: &'static str = "mentioned";
+    pub const ROLE_REQUEST_SUBMITTED: &'static str = "role_request_submitted";
+    pub const ROLE_REQUEST_REVIEWED: &'static str = "role_request_reviewed";
+    pub const CERTIFICATE_ISSUED: &'static str = "certificate_issued";
+    pub const FILE_QUARANTINE_REVIEWED: &'static str = "file_quarantine_reviewed";
+    pub const MESSAGE_RECEIVED: &'static str = "message_received";
+    pub const CONTENT_REPORTED: &'static str = "content_reported";
+    pub const CONTENT_WARNING: &'static str = "content_warning";
+    pub const SCHEDULED_EXPORT_FAILED: &'static str = "scheduled_export_failed";
+    pub const NEW_DEVICE_LOGIN: &'static str = "new_device_login";
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            NotificationType::HomeworkCreated,
+            NotificationType::HomeworkUpdated,
+            NotificationType::HomeworkDeadline,
+            NotificationType::SolutionPublished,
+            NotificationType::SubmissionReceived,
+            NotificationType::GradingOverdue,
+            NotificationType::ResubmissionRequested,
+            NotificationType::GradeReceived,
+            NotificationType::GradeUpdated,
+            NotificationType::MissingGradeAssigned,
+            NotificationType::ClassJoined,
+            NotificationType::ClassRoleChanged,
+            NotificationType::ClassAnnouncement,
+            NotificationType::Mentioned,
+            NotificationType::RoleRequestSubmitted,
+            NotificationType::RoleRequestReviewed,
+            NotificationType::CertificateIssued,
+            NotificationType::FileQuarantineReviewed,
+            NotificationType::MessageReceived,
+            NotificationType::ContentReported,
+            NotificationType::ContentWarning,
+            NotificationType::ScheduledExportFailed,
+            NotificationType::NewDeviceLogin,
+        ]
+    }
 }
 
 impl<'de> Deserialize<'de> for NotificationType {
@@ -50,11 +124,36 @@ impl std::fmt::Display for NotificationType {
             NotificationType::HomeworkCreated => write!(f, "{}", Self::HOMEWORK_CREATED),
             NotificationType::HomeworkUpdated => write!(f, "{}", Self::HOMEWORK_UPDATED),
             NotificationType::HomeworkDeadline => write!(f, "{}", Self::HOMEWORK_DEADLINE),
+            NotificationType::SolutionPublished => write!(f, "{}", Self::SOLUTION_PUBLISHED),
             NotificationType::SubmissionReceived => write!(f, "{}", Self::SUBMISSION_RECEIVED),
+            NotificationType::GradingOverdue => write!(f, "{}", Self::GRADING_OVERDUE),
+            NotificationType::ResubmissionRequested => {
+                write!(f, "{}", Self::RESUBMISSION_REQUESTED)
+            }
             NotificationType::GradeReceived => write!(f, "{}", Self::GRADE_RECEIVED),
             NotificationType::GradeUpdated => write!(f, "{}", Self::GRADE_UPDATED),
+            NotificationType::MissingGradeAssigned => {
+                write!(f, "{}", Self::MISSING_GRADE_ASSIGNED)
+            }
             NotificationType::ClassJoined => write!(f, "{}", Self::CLASS_JOINED),
             NotificationType::ClassRoleChanged => write!(f, "{}", Self::CLASS_ROLE_CHANGED),
+            NotificationType::ClassAnnouncement => write!(f, "{}", Self::CLASS_ANNOUNCEMENT),
+            NotificationType::Mentioned => write!(f, "{}", Self::MENTIONED),
+            NotificationType::RoleRequestSubmitted => {
+                write!(f, "{}", Self::ROLE_REQUEST_SUBMITTED)
+            }
+            NotificationType::RoleRequestReviewed => write!(f, "{}", Self::ROLE_REQUEST_REVIEWED),
+            NotificationType::CertificateIssued => write!(f, "{}", Self::CERTIFICATE_ISSUED),
+            NotificationType::FileQuarantineReviewed => {
+                write!(f, "{}", Self::FILE_QUARANTINE_REVIEWED)
+            }
+            NotificationType::MessageReceived => write!(f, "{}", Self::MESSAGE_RECEIVED),
+            NotificationType::ContentReported => write!(f, "{}", Self::CONTENT_REPORTED),
+            NotificationType::ContentWarning => write!(f, "{}", Self::CONTENT_WARNING),
+            NotificationType::ScheduledExportFailed => {
+                write!(f, "{}", Self::SCHEDULED_EXPORT_FAILED)
+            }
+            NotificationType::NewDeviceLogin => write!(f, "{}", Self::NEW_DEVICE_LOGIN),
         }
     }
 }
@@ -67,11 +166,26 @@ impl std::str::FromStr for NotificationType {
             "homework_created" => Ok(NotificationType::HomeworkCreated),
             "homework_updated" => Ok(NotificationType::HomeworkUpdated),
             "homework_deadline" => Ok(NotificationType::HomeworkDeadline),
+            "solution_published" => Ok(NotificationType::SolutionPublished),
             "submission_received" => Ok(NotificationType::SubmissionReceived),
+            "grading_overdue" => Ok(NotificationType::GradingOverdue),
+            "resubmission_requested" => Ok(NotificationType::ResubmissionRequested),
             "grade_received" => Ok(NotificationType::GradeReceived),
             "grade_updated" => Ok(NotificationType::GradeUpdated),
+            "missing_grade_assigned" => Ok(NotificationType::MissingGradeAssigned),
             "class_joined" => Ok(NotificationType::ClassJoined),
             "class_role_changed" => Ok(NotificationType::ClassRoleChanged),
+            "class_announcement" => Ok(NotificationType::ClassAnnouncement),
+            "mentioned" => Ok(NotificationType::Mentioned),
+            "role_request_submitted" => Ok(NotificationType::RoleRequestSubmitted),
+            "role_request_reviewed" => Ok(NotificationType::RoleRequestReviewed),
+            "certificate_issued" => Ok(NotificationType::CertificateIssued),
+            "file_quarantine_reviewed" => Ok(NotificationType::FileQuarantineReviewed),
+            "message_received" => Ok(NotificationType::MessageReceived),
+            "content_reported" => Ok(NotificationType::ContentReported),
+            "content_warning" => Ok(NotificationType::ContentWarning),
+            "scheduled_export_failed" => Ok(NotificationType::ScheduledExportFailed),
+            "new_device_login" => Ok(NotificationType::NewDeviceLogin),
             _ => Err(format!("Invalid notification type: {s}")),
         }
     }
@@ -86,6 +200,7 @@ pub enum ReferenceType {
     Submission,
     Grade,
     Class,
+    RoleRequest,
 }
 
 impl<'de> Deserialize<'de> for ReferenceType {
@@ -105,6 +220,7 @@ impl std::fmt::Display for ReferenceType {
             ReferenceType::Submission => write!(f, "submission"),
             ReferenceType::Grade => write!(f, "grade"),
             ReferenceType::Class => write!(f, "class"),
+            ReferenceType::RoleRequest => write!(f, "role_request"),
         }
     }
 }
@@ -118,6 +234,7 @@ impl std::str::FromStr for ReferenceType {
             "submission" => Ok(ReferenceType::Submission),
             "grade" => Ok(ReferenceType::Grade),
             "class" => Ok(ReferenceType::Class),
+            "role_request" => Ok(ReferenceType::RoleRequest),
             _ => Err(format!("Invalid reference type: {s}")),
         }
     }
@@ -137,3 +254,17 @@ pub struct Notification {
     pub is_read: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// 通知模板实体（管理员自定义的通知文案）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct NotificationTemplate {
+    pub id: i64,
+    pub notification_type: NotificationType,
+    pub locale: String,
+    pub title: String,
+    pub content: Option<String>,
+    pub updated_by: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 use ts_rs::TS;
 
+use crate::models::class_users::entities::ClassUserRole;
 use crate::models::common::pagination::PaginationQuery;
 
 /// 通知列表查询参数
@@ -24,3 +25,76 @@ pub struct CreateNotificationRequest {
     pub reference_type: Option<String>,
     pub reference_id: Option<i64>,
 }
+
+/// 群发通知的接收人筛选条件
+///
+/// 各条件同时生效；均为空时发送给班级全部成员（发送者本人除外）。
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct NotificationRecipientFilter {
+    /// 仅限指定用户（非班级成员会被忽略）
+    pub user_ids: Option<Vec<i64>>,
+    /// 仅限指定班级角色
+    pub role: Option<ClassUserRole>,
+    /// 仅限尚未提交该作业的学生（最新提交被退回视为未提交，豁免学生除外）
+    pub not_submitted_homework_id: Option<i64>,
+}
+
+/// 群发班级通知请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct SendNotificationRequest {
+    /// 班级 ID
+    pub class_id: i64,
+    /// 通知标题
+    pub title: String,
+    /// 通知内容
+    pub content: Option<String>,
+    /// 接收人筛选条件
+    #[serde(default)]
+    pub filter: NotificationRecipientFilter,
+}
+
+impl SendNotificationRequest {
+    pub const MAX_TITLE_LENGTH: usize = 200;
+    pub const MAX_CONTENT_LENGTH: usize = 2000;
+    pub const MAX_USER_IDS: usize = 1000;
+
+    /// 校验标题、内容与显式接收人数量
+    pub fn validate(&self) -> Result<(), String> {
+        let title_length = self.title.trim().chars().count();
+        if title_length == 0 || title_length > Self::MAX_TITLE_LENGTH {
+            return Err(format!(
+                "通知标题不能为空且不超过 {} 个字符",
+                Self::MAX_TITLE_LENGTH
+            ));
+        }
+        if let Some(content) = &self.content
+            && content.chars().count() > Self::MAX_CONTENT_LENGTH
+        {
+            return Err(format!(
+                "通知内容不能超过 {} 个字符",
+                Self::MAX_CONTENT_LENGTH
+            ));
+        }
+        if let Some(user_ids) = &self.filter.user_ids
+            && (user_ids.is_empty() || user_ids.len() > Self::MAX_USER_IDS)
+        {
+            return Err(format!(
+                "指定接收人时数量应为 1 至 {} 个",
+                Self::MAX_USER_IDS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 创建/更新通知模板请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct UpsertNotificationTemplateRequest {
+    /// 标题模板，使用 `{变量名}` 作为占位符
+    pub title: String,
+    /// 内容模板，为空表示通知不带内容
+    pub content: Option<String>,
+}
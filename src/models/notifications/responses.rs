@@ -1,7 +1,7 @@
 use serde::Serialize;
 use ts_rs::TS;
 
-use super::entities::Notification;
+use super::entities::{Notification, NotificationTemplate, NotificationType};
 use crate::models::common::pagination::PaginationInfo;
 
 /// 通知列表响应
@@ -25,3 +25,35 @@ pub struct UnreadCountResponse {
 pub struct MarkAllReadResponse {
     pub marked_count: i64,
 }
+
+/// 群发通知响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct SendNotificationResponse {
+    /// 实际收到通知的人数
+    pub recipient_count: usize,
+}
+
+/// 单个通知类型的模板信息
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct NotificationTemplateInfo {
+    pub notification_type: NotificationType,
+    /// 模板中可使用的变量
+    pub variables: Vec<String>,
+    /// 内置默认标题
+    pub default_title: String,
+    /// 内置默认内容
+    pub default_content: Option<String>,
+    /// 已保存的自定义模板（按语言区分）
+    pub overrides: Vec<NotificationTemplate>,
+}
+
+/// 通知模板列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/notification.ts")]
+pub struct NotificationTemplateListResponse {
+    /// 当前生效的通知语言（系统设置 `notification.locale`）
+    pub active_locale: String,
+    pub items: Vec<NotificationTemplateInfo>,
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::users::entities::{User, UserRole};
+
+// 组织（租户）实体
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct Organization {
+    // 组织ID
+    pub id: i64,
+    // 组织名称
+    pub name: String,
+    // 唯一标识（URL 友好）
+    pub slug: String,
+    // 组织描述
+    pub description: Option<String>,
+    // 是否启用，停用后组织内用户无法访问系统
+    pub is_active: bool,
+    // 创建时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    // 更新时间
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 租户范围（存储层查询过滤条件）
+//
+// - `All`：不限制租户，仅平台管理员使用
+// - `Org(None)`：默认租户（未归属任何组织的数据）
+// - `Org(Some(id))`：指定组织
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantScope {
+    All,
+    Org(Option<i64>),
+}
+
+// 查询参数反序列化时的占位值，服务层未填充时只能访问默认租户，不会放开跨组织访问
+impl Default for TenantScope {
+    fn default() -> Self {
+        TenantScope::Org(None)
+    }
+}
+
+impl TenantScope {
+    // 根据当前用户确定租户范围
+    pub fn for_user(user: &User) -> Self {
+        if user.is_platform_admin() {
+            TenantScope::All
+        } else {
+            TenantScope::Org(user.org_id)
+        }
+    }
+
+    // 判断该范围是否可以访问属于 org_id 的资源
+    pub fn allows(&self, org_id: Option<i64>) -> bool {
+        match self {
+            TenantScope::All => true,
+            TenantScope::Org(scope) => *scope == org_id,
+        }
+    }
+}
+
+impl User {
+    // 平台管理员：不属于任何组织的系统管理员，可跨租户管理
+    pub fn is_platform_admin(&self) -> bool {
+        self.role == UserRole::Admin && self.org_id.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_allows() {
+        assert!(TenantScope::All.allows(Some(1)));
+        assert!(TenantScope::All.allows(None));
+        assert!(TenantScope::Org(Some(1)).allows(Some(1)));
+        assert!(!TenantScope::Org(Some(1)).allows(Some(2)));
+        assert!(!TenantScope::Org(Some(1)).allows(None));
+        assert!(TenantScope::Org(None).allows(None));
+        assert!(!TenantScope::Org(None).allows(Some(1)));
+        // 未填充的范围不能访问任何组织
+        assert!(!TenantScope::default().allows(Some(1)));
+    }
+}
@@ -0,0 +1,8 @@
+// 组织实体定义
+pub mod entities;
+
+// 组织请求模型
+pub mod requests;
+
+// 组织响应模型
+pub mod responses;
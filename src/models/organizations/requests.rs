@@ -0,0 +1,42 @@
+use crate::models::common::PaginationQuery;
+use serde::Deserialize;
+use ts_rs::TS;
+
+// 组织查询参数（来自HTTP请求）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct OrganizationListParams {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub pagination: PaginationQuery,
+    pub search: Option<String>,
+}
+
+// 组织列表查询参数（用于存储层）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct OrganizationListQuery {
+    pub page: Option<i64>,
+    pub size: Option<i64>,
+    pub search: Option<String>,
+}
+
+// 创建组织请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+}
+
+// 更新组织请求
+//
+// 组织管理员只能修改名称与描述；启用/停用仅平台管理员可操作
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+}
@@ -0,0 +1,23 @@
+use super::entities::Organization;
+use crate::models::common::PaginationInfo;
+use serde::Serialize;
+use ts_rs::TS;
+
+// 组织详情响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct OrganizationResponse {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub organization: Organization,
+    // 组织内用户数
+    pub user_count: i64,
+}
+
+// 组织列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/organization.ts")]
+pub struct OrganizationListResponse {
+    pub items: Vec<Organization>,
+    pub pagination: PaginationInfo,
+}
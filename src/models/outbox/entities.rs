@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::classes::entities::ImEvent;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+
+/// 发件箱事件，与业务变更在同一事务中写入，由转发任务投递
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxEvent {
+    /// 站内通知（写入通知表并通过 WebSocket 推送）
+    Notification {
+        user_ids: Vec<i64>,
+        notification_type: NotificationType,
+        // 通知模板变量，投递时渲染
+        vars: Vec<(String, String)>,
+        reference_type: Option<ReferenceType>,
+        reference_id: Option<i64>,
+    },
+    /// 班级学生通知，接收人在投递时解析
+    ClassNotification {
+        class_id: i64,
+        notification_type: NotificationType,
+        vars: Vec<(String, String)>,
+        reference_type: Option<ReferenceType>,
+        reference_id: Option<i64>,
+        // 跟踪投递进度的后台任务 ID（见 services::jobs::register_job）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        job_id: Option<String>,
+    },
+    /// 作业相关的 IM 群消息（写入 IM 投递队列）
+    HomeworkImEvent { homework_id: i64, event: ImEvent },
+}
+
+impl OutboxEvent {
+    /// 事件类型，写入 `event_type` 列便于排查
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OutboxEvent::Notification { .. } => "notification",
+            OutboxEvent::ClassNotification { .. } => "class_notification",
+            OutboxEvent::HomeworkImEvent { .. } => "homework_im_event",
+        }
+    }
+
+    /// 跟踪投递进度的后台任务 ID
+    pub fn job_id(&self) -> Option<&str> {
+        match self {
+            OutboxEvent::ClassNotification { job_id, .. } => job_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// 发件箱事件状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// 等待投递（含等待重试）
+    Pending,
+    /// 已投递
+    Delivered,
+    /// 重试次数耗尽
+    Failed,
+}
+
+impl std::fmt::Display for OutboxStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutboxStatus::Pending => write!(f, "pending"),
+            OutboxStatus::Delivered => write!(f, "delivered"),
+            OutboxStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// 待投递的发件箱记录
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub id: i64,
+    pub event_type: String,
+    // 负载无法解析时为错误信息，转发任务直接标记失败
+    pub event: Result<OutboxEvent, String>,
+    pub attempts: i32,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 表情回应的目标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/reaction.ts")]
+pub enum ReactionTargetType {
+    /// 班级公告（target_id 为班级动态 ID）
+    Announcement,
+    /// 评语（target_id 为评分 ID）
+    GradeComment,
+}
+
+impl std::fmt::Display for ReactionTargetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReactionTargetType::Announcement => write!(f, "announcement"),
+            ReactionTargetType::GradeComment => write!(f, "grade_comment"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReactionTargetType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "announcement" => Ok(ReactionTargetType::Announcement),
+            "grade_comment" => Ok(ReactionTargetType::GradeComment),
+            _ => Err(format!("Invalid reaction target type: {s}")),
+        }
+    }
+}
+
+/// 单个表情的回应人数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/reaction.ts")]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: i64,
+}
+
+/// 目标的表情回应汇总
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/reaction.ts")]
+pub struct ReactionSummary {
+    /// 按首次回应时间排序
+    pub counts: Vec<ReactionCount>,
+    /// 当前用户回应过的表情
+    pub mine: Vec<String>,
+}
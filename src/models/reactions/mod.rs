@@ -0,0 +1,8 @@
+// 表情回应实体定义
+pub mod entities;
+
+// 表情回应请求模型
+pub mod requests;
+
+// 表情回应响应模型
+pub mod responses;
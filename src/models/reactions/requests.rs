@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+use super::entities::ReactionTargetType;
+
+/// 添加或取消表情回应请求（添加为请求体，取消为查询参数）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/reaction.ts")]
+pub struct ReactionRequest {
+    pub target_type: ReactionTargetType,
+    pub target_id: i64,
+    pub emoji: String,
+}
+
+impl ReactionRequest {
+    /// 单个表情的最大字符数（组合表情由多个码点组成）
+    pub const MAX_EMOJI_CHARS: usize = 8;
+
+    /// 校验表情：去除首尾空白后不能为空，不能包含 ASCII 字符或空白
+    pub fn validate(&self) -> Result<(), String> {
+        let emoji = self.emoji.trim();
+        let chars = emoji.chars().count();
+        if chars == 0
+            || chars > Self::MAX_EMOJI_CHARS
+            || emoji.chars().any(|c| c.is_ascii() || c.is_whitespace())
+        {
+            return Err(format!(
+                "表情无效：只能是单个表情符号（不超过 {} 个字符）",
+                Self::MAX_EMOJI_CHARS
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::entities::{ReactionCount, ReactionTargetType};
+
+/// 表情回应变化（WebSocket `reaction_updated` 推送）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/reaction.ts")]
+pub struct ReactionUpdate {
+    pub target_type: ReactionTargetType,
+    pub target_id: i64,
+    /// 添加或取消回应的用户
+    pub user_id: i64,
+    pub emoji: String,
+    /// true 为添加，false 为取消
+    pub added: bool,
+    /// 变化后的回应人数
+    pub counts: Vec<ReactionCount>,
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 评分等级（如“优秀 / 良好 / 待改进”）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct RubricLevel {
+    pub label: String,
+    pub points: f64,
+    pub description: Option<String>,
+}
+
+/// 评分维度
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct RubricCriterion {
+    pub title: String,
+    pub description: Option<String>,
+    /// 该维度满分
+    pub points: f64,
+    /// 可选的评分等级，按分值从高到低排列
+    #[serde(default)]
+    pub levels: Vec<RubricLevel>,
+}
+
+/// 各维度满分之和
+pub fn total_points(criteria: &[RubricCriterion]) -> f64 {
+    criteria.iter().map(|c| c.points).sum()
+}
+
+/// 量规库中的量规（内容为当前版本）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct Rubric {
+    pub id: i64,
+    pub owner_id: i64,
+    /// 所属组织（创建者的组织），为空表示默认租户
+    pub org_id: Option<i64>,
+    pub title: String,
+    pub description: Option<String>,
+    pub criteria: Vec<RubricCriterion>,
+    pub total_points: f64,
+    /// 当前版本号，修改评分维度时递增
+    pub version: i32,
+    /// 是否对同组织教师可见
+    pub org_visible: bool,
+    /// 分享码，仅量规所有者可见
+    pub share_code: Option<String>,
+    /// 从他人量规导入时记录来源量规
+    pub source_rubric_id: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 量规的历史版本
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct RubricVersion {
+    pub rubric_id: i64,
+    pub version: i32,
+    pub criteria: Vec<RubricCriterion>,
+    pub total_points: f64,
+    pub created_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 作业使用的量规快照
+///
+/// 设置时从量规库复制，之后量规库中的修改或删除不影响作业。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct HomeworkRubric {
+    pub homework_id: i64,
+    /// 来源量规，量规被删除后为空
+    pub rubric_id: Option<i64>,
+    /// 复制时的量规版本
+    pub rubric_version: i32,
+    pub title: String,
+    pub criteria: Vec<RubricCriterion>,
+    pub total_points: f64,
+    pub attached_by: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,8 @@
+// 评分量规实体定义
+pub mod entities;
+
+// 评分量规请求模型
+pub mod requests;
+
+// 评分量规响应模型
+pub mod responses;
@@ -0,0 +1,89 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+use super::entities::RubricCriterion;
+
+/// 量规列表范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub enum RubricScope {
+    /// 自己的量规
+    Mine,
+    /// 同组织教师公开的量规（不含自己的）
+    Org,
+}
+
+/// 量规列表查询参数
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct RubricListQuery {
+    /// 不传时返回自己的量规与同组织公开的量规
+    pub scope: Option<RubricScope>,
+}
+
+/// 创建量规请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct CreateRubricRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub criteria: Vec<RubricCriterion>,
+    /// 是否对同组织教师可见，默认否
+    #[serde(default)]
+    pub org_visible: bool,
+}
+
+/// 更新量规请求，未传的字段保持不变
+///
+/// 修改评分维度会生成新版本，已设置到作业上的量规快照不受影响。
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct UpdateRubricRequest {
+    pub title: Option<String>,
+    /// 传空字符串清除说明
+    pub description: Option<String>,
+    pub criteria: Option<Vec<RubricCriterion>>,
+    pub org_visible: Option<bool>,
+}
+
+/// 导入量规请求，`share_code` 与 `rubric_id` 二选一
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct ImportRubricRequest {
+    /// 同事提供的分享码
+    pub share_code: Option<String>,
+    /// 同组织公开的量规 ID
+    pub rubric_id: Option<i64>,
+}
+
+/// 为作业设置量规请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct SetHomeworkRubricRequest {
+    pub rubric_id: i64,
+    /// 使用的量规版本，不传使用当前版本
+    pub version: Option<i32>,
+}
+
+/// 量规写入参数（存储层使用）
+#[derive(Debug, Clone)]
+pub struct RubricInput {
+    pub owner_id: i64,
+    pub org_id: Option<i64>,
+    pub title: String,
+    pub description: Option<String>,
+    pub criteria: Vec<RubricCriterion>,
+    pub org_visible: bool,
+    pub source_rubric_id: Option<i64>,
+}
+
+/// 量规更新参数（存储层使用），字段均已校验
+#[derive(Debug, Clone)]
+pub struct RubricUpdate {
+    pub title: Option<String>,
+    /// `Some(None)` 清除说明
+    pub description: Option<Option<String>>,
+    pub criteria: Option<Vec<RubricCriterion>>,
+    pub org_visible: Option<bool>,
+}
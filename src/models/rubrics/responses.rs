@@ -0,0 +1,19 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::entities::{Rubric, RubricVersion};
+
+/// 量规列表响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct RubricListResponse {
+    pub items: Vec<Rubric>,
+}
+
+/// 量规版本列表响应（新版本在前）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/rubric.ts")]
+pub struct RubricVersionListResponse {
+    pub rubric_id: i64,
+    pub items: Vec<RubricVersion>,
+}
@@ -1,20 +1,24 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::models::class_users::entities::ClassUserRole;
+
 /// 提交状态
 #[derive(Debug, Clone, Serialize, PartialEq, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
 pub enum SubmissionStatus {
-    Pending, // 待批改
-    Graded,  // 已批改
-    Late,    // 迟交
+    Pending,  // 待批改
+    Graded,   // 已批改
+    Late,     // 迟交
+    Returned, // 退回重交
 }
 
 impl SubmissionStatus {
     pub const PENDING: &'static str = "pending";
     pub const GRADED: &'static str = "graded";
     pub const LATE: &'static str = "late";
+    pub const RETURNED: &'static str = "returned";
 }
 
 impl<'de> Deserialize<'de> for SubmissionStatus {
@@ -27,8 +31,9 @@ impl<'de> Deserialize<'de> for SubmissionStatus {
             "pending" => Ok(SubmissionStatus::Pending),
             "graded" => Ok(SubmissionStatus::Graded),
             "late" => Ok(SubmissionStatus::Late),
+            "returned" => Ok(SubmissionStatus::Returned),
             _ => Err(serde::de::Error::custom(format!(
-                "无效的提交状态: '{s}'. 支持的状态: pending, graded, late"
+                "无效的提交状态: '{s}'. 支持的状态: pending, graded, late, returned"
             ))),
         }
     }
@@ -40,6 +45,7 @@ impl std::fmt::Display for SubmissionStatus {
             SubmissionStatus::Pending => write!(f, "pending"),
             SubmissionStatus::Graded => write!(f, "graded"),
             SubmissionStatus::Late => write!(f, "late"),
+            SubmissionStatus::Returned => write!(f, "returned"),
         }
     }
 }
@@ -52,6 +58,7 @@ impl std::str::FromStr for SubmissionStatus {
             "pending" => Ok(SubmissionStatus::Pending),
             "graded" => Ok(SubmissionStatus::Graded),
             "late" => Ok(SubmissionStatus::Late),
+            "returned" => Ok(SubmissionStatus::Returned),
             _ => Err(format!("Invalid submission status: {s}")),
         }
     }
@@ -64,9 +71,338 @@ pub struct Submission {
     pub id: i64,
     pub homework_id: i64,
     pub creator_id: i64,
+    // 所属分题（未拆分的作业为空）
+    pub part_id: Option<i64>,
     pub version: i32,
     pub content: Option<String>,
     pub status: SubmissionStatus,
+    // 班级工作流中的自定义状态，为空时沿用 status
+    pub workflow_status: Option<String>,
     pub is_late: bool,
     pub submitted_at: chrono::DateTime<chrono::Utc>,
 }
+
+impl Submission {
+    /// 当前生效的状态：自定义状态优先，否则为内置状态
+    pub fn effective_status(&self) -> String {
+        self.workflow_status
+            .clone()
+            .unwrap_or_else(|| self.status.to_string())
+    }
+}
+
+/// 提交正文的字数统计（写入时计算并随提交保存）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubmissionContentMetrics {
+    /// 词数：中日韩文字每字计一词，其余连续的字母数字计一词
+    pub word_count: i32,
+    /// 字符数（不含空白）
+    pub char_count: i32,
+}
+
+impl SubmissionContentMetrics {
+    pub fn from_content(content: &str) -> Self {
+        let mut word_count = 0;
+        let mut char_count = 0;
+        let mut in_word = false;
+        for c in content.chars() {
+            if c.is_whitespace() {
+                in_word = false;
+                continue;
+            }
+            char_count += 1;
+            if is_cjk(c) {
+                word_count += 1;
+                in_word = false;
+            } else if c.is_alphanumeric() || (in_word && matches!(c, '\'' | '’' | '-')) {
+                // 撇号、连字符不拆分单词（如 don't、well-known）
+                if !in_word {
+                    word_count += 1;
+                    in_word = true;
+                }
+            } else {
+                in_word = false;
+            }
+        }
+        Self {
+            word_count,
+            char_count,
+        }
+    }
+}
+
+/// 是否为按字计词的中日韩文字
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+/// 班级自定义的提交状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionWorkflowStatus {
+    // 状态标识（小写字母、数字、下划线），不能与内置状态重名
+    pub key: String,
+    // 显示名称
+    pub label: String,
+}
+
+/// 工作流中允许的状态转换
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionWorkflowTransition {
+    // 起始状态（内置或自定义）
+    pub from: String,
+    // 目标状态（只能是自定义状态，回到 graded 需通过评分）
+    pub to: String,
+    // 可执行该转换的班级角色，默认仅教师；学生只能转换自己的提交
+    #[serde(default = "SubmissionWorkflowTransition::default_roles")]
+    pub roles: Vec<ClassUserRole>,
+}
+
+impl SubmissionWorkflowTransition {
+    fn default_roles() -> Vec<ClassUserRole> {
+        vec![ClassUserRole::Teacher]
+    }
+}
+
+/// 班级提交状态工作流
+///
+/// 内置状态 pending / late / graded / returned 始终存在，班级只需声明额外的自定义状态及允许的转换。
+/// 未自定义的班级使用默认工作流（无自定义状态、无手动转换）。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionWorkflow {
+    pub statuses: Vec<SubmissionWorkflowStatus>,
+    pub transitions: Vec<SubmissionWorkflowTransition>,
+}
+
+impl SubmissionWorkflow {
+    pub const BUILTIN_STATUSES: &'static [&'static str] = &[
+        SubmissionStatus::PENDING,
+        SubmissionStatus::LATE,
+        SubmissionStatus::GRADED,
+        SubmissionStatus::RETURNED,
+    ];
+    pub const MAX_STATUSES: usize = 20;
+    pub const MAX_TRANSITIONS: usize = 100;
+    pub const MAX_KEY_LENGTH: usize = 32;
+    pub const MAX_LABEL_LENGTH: usize = 32;
+
+    /// 是否为默认工作流
+    pub fn is_default(&self) -> bool {
+        self.statuses.is_empty()
+    }
+
+    /// 状态的显示名称，未知状态返回 None
+    pub fn label<'a>(&'a self, key: &str) -> Option<&'a str> {
+        match key {
+            SubmissionStatus::PENDING => Some("待批改"),
+            SubmissionStatus::LATE => Some("迟交"),
+            SubmissionStatus::GRADED => Some("已批改"),
+            SubmissionStatus::RETURNED => Some("退回重交"),
+            _ => self
+                .statuses
+                .iter()
+                .find(|s| s.key == key)
+                .map(|s| s.label.as_str()),
+        }
+    }
+
+    /// 查找从 `from` 到 `to` 的转换
+    pub fn transition(&self, from: &str, to: &str) -> Option<&SubmissionWorkflowTransition> {
+        self.transitions
+            .iter()
+            .find(|t| t.from == from && t.to == to)
+    }
+
+    /// 校验工作流定义，返回错误说明
+    pub fn validate(&self) -> Result<(), String> {
+        if self.statuses.len() > Self::MAX_STATUSES {
+            return Err(format!("自定义状态不能超过 {} 个", Self::MAX_STATUSES));
+        }
+        if self.transitions.len() > Self::MAX_TRANSITIONS {
+            return Err(format!("状态转换不能超过 {} 条", Self::MAX_TRANSITIONS));
+        }
+        if self.statuses.is_empty() && !self.transitions.is_empty() {
+            return Err("未定义自定义状态时不能声明状态转换".to_string());
+        }
+
+        let mut keys = std::collections::HashSet::new();
+        for status in &self.statuses {
+            let valid_key = !status.key.is_empty()
+                && status.key.len() <= Self::MAX_KEY_LENGTH
+                && status
+                    .key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if !valid_key {
+                return Err(format!(
+                    "状态标识 '{}' 无效：须为 1-{} 位小写字母、数字或下划线",
+                    status.key,
+                    Self::MAX_KEY_LENGTH
+                ));
+            }
+            if Self::BUILTIN_STATUSES.contains(&status.key.as_str()) {
+                return Err(format!("状态标识 '{}' 与内置状态重名", status.key));
+            }
+            if !keys.insert(status.key.as_str()) {
+                return Err(format!("状态标识 '{}' 重复", status.key));
+            }
+            let label_length = status.label.trim().chars().count();
+            if label_length == 0 || label_length > Self::MAX_LABEL_LENGTH {
+                return Err(format!(
+                    "状态 '{}' 的名称须为 1-{} 个字符",
+                    status.key,
+                    Self::MAX_LABEL_LENGTH
+                ));
+            }
+        }
+
+        let mut pairs = std::collections::HashSet::new();
+        for transition in &self.transitions {
+            if self.label(&transition.from).is_none() {
+                return Err(format!("转换的起始状态 '{}' 未定义", transition.from));
+            }
+            if !keys.contains(transition.to.as_str()) {
+                return Err(format!("转换的目标状态 '{}' 须为自定义状态", transition.to));
+            }
+            if transition.from == transition.to {
+                return Err(format!("状态 '{}' 不能转换为自身", transition.from));
+            }
+            if transition.roles.is_empty() {
+                return Err(format!(
+                    "转换 {} -> {} 至少需要一个可执行角色",
+                    transition.from, transition.to
+                ));
+            }
+            if !pairs.insert((transition.from.as_str(), transition.to.as_str())) {
+                return Err(format!(
+                    "转换 {} -> {} 重复",
+                    transition.from, transition.to
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 提交自评（学生随提交填写，教师批改时可见）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionSelfAssessment {
+    pub submission_id: i64,
+    // 把握程度（1-5，5 表示非常有把握）
+    pub confidence: i32,
+    // 完成作业所用时间（分钟）
+    pub time_spent_minutes: i32,
+    // 遇到的困难
+    pub difficulties: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 提交外部引用类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub enum SubmissionReferenceKind {
+    /// Git 仓库地址 + 提交哈希
+    Git,
+    /// 在线文档链接
+    Document,
+}
+
+impl std::fmt::Display for SubmissionReferenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionReferenceKind::Git => write!(f, "git"),
+            SubmissionReferenceKind::Document => write!(f, "document"),
+        }
+    }
+}
+
+impl std::str::FromStr for SubmissionReferenceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "git" => Ok(SubmissionReferenceKind::Git),
+            "document" => Ok(SubmissionReferenceKind::Document),
+            _ => Err(format!("Invalid submission reference kind: {s}")),
+        }
+    }
+}
+
+/// 提交外部引用（代替或补充附件的仓库 / 在线文档）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionReference {
+    pub id: i64,
+    pub kind: SubmissionReferenceKind,
+    pub url: String,
+    // 提交哈希（仅 git）
+    pub commit_hash: Option<String>,
+    // 提交说明快照（从代码托管平台抓取）
+    pub commit_message: Option<String>,
+    // 提交时间快照
+    pub committed_at: Option<chrono::DateTime<chrono::Utc>>,
+    // 抓取快照的时间，未抓取或抓取失败时为空
+    pub metadata_fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 教师对提交的退回重交请求
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct ResubmissionRequest {
+    pub id: i64,
+    // 被退回的提交
+    pub submission_id: i64,
+    pub homework_id: i64,
+    pub student_id: i64,
+    // 发起退回的教师
+    pub requested_by: i64,
+    // 退回意见
+    pub comment: String,
+    // 重新开放截止时间：此前重新提交不计迟交
+    pub reopen_until: chrono::DateTime<chrono::Utc>,
+    // 学生重新提交的时间，未重交为空
+    pub fulfilled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 超过批改时限仍未批改的提交（每个学生每个分题只取最新版本）
+#[derive(Debug, Clone)]
+pub struct GradingSlaBreach {
+    pub submission_id: i64,
+    pub homework_id: i64,
+    pub homework_title: String,
+    /// 作业创建者，负责批改
+    pub teacher_id: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(content: &str) -> (i32, i32) {
+        let m = SubmissionContentMetrics::from_content(content);
+        (m.word_count, m.char_count)
+    }
+
+    #[test]
+    fn test_content_metrics() {
+        assert_eq!(metrics(""), (0, 0));
+        assert_eq!(metrics("  \n\t "), (0, 0));
+        assert_eq!(metrics("Hello, world!"), (2, 12));
+        assert_eq!(metrics("don't use well-known x2"), (4, 20));
+        assert_eq!(metrics("解答见附件"), (5, 5));
+        assert_eq!(metrics("第 3 题：f(x) = 2x"), (6, 11));
+    }
+}
@@ -1,4 +1,9 @@
 use crate::models::common::PaginationQuery;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::entities::{
+    SubmissionReferenceKind, SubmissionWorkflowStatus, SubmissionWorkflowTransition,
+};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use ts_rs::TS;
 
@@ -7,8 +12,88 @@ use ts_rs::TS;
 #[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
 pub struct CreateSubmissionRequest {
     pub homework_id: i64,
+    /// 提交的分题（多部分作业必填）
+    pub part_id: Option<i64>,
     pub content: String,
     pub attachments: Option<Vec<String>>,
+    /// 考试模式：最近一次 `GET /system/time` 返回的签名时间戳
+    pub signed_timestamp: Option<String>,
+    /// 考试模式：收到该签名时间戳时客户端的本地时间（毫秒时间戳）
+    pub client_time: Option<i64>,
+    /// 提交自评（作业要求自评时必填）
+    pub self_assessment: Option<SelfAssessmentInput>,
+    /// 外部引用（Git 仓库 / 在线文档），可代替或补充附件
+    pub references: Option<Vec<SubmissionReferenceInput>>,
+}
+
+/// 提交外部引用
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionReferenceInput {
+    pub kind: SubmissionReferenceKind,
+    /// 仓库或文档链接（http / https）
+    pub url: String,
+    /// 提交哈希（git 必填，7-64 位十六进制）
+    pub commit: Option<String>,
+}
+
+/// 写入存储层的外部引用（含提交时抓取的元数据快照）
+#[derive(Debug, Clone)]
+pub struct SubmissionReferenceSnapshot {
+    pub kind: SubmissionReferenceKind,
+    pub url: String,
+    pub commit_hash: Option<String>,
+    pub commit_message: Option<String>,
+    pub committed_at: Option<i64>,
+    pub metadata_fetched_at: Option<i64>,
+}
+
+/// 提交自评表单
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SelfAssessmentInput {
+    /// 把握程度（1-5）
+    pub confidence: i32,
+    /// 所用时间（分钟）
+    pub time_spent_minutes: i32,
+    /// 遇到的困难（可选，最多 2000 字符）
+    pub difficulties: Option<String>,
+}
+
+impl SelfAssessmentInput {
+    pub const MIN_CONFIDENCE: i32 = 1;
+    pub const MAX_CONFIDENCE: i32 = 5;
+    /// 用时上限：一周
+    pub const MAX_TIME_SPENT_MINUTES: i32 = 7 * 24 * 60;
+    pub const MAX_DIFFICULTIES_LENGTH: usize = 2000;
+
+    /// 校验自评内容，返回错误说明
+    pub fn validate(&self) -> Result<(), String> {
+        if !(Self::MIN_CONFIDENCE..=Self::MAX_CONFIDENCE).contains(&self.confidence) {
+            return Err(format!(
+                "把握程度须在 {} 到 {} 之间",
+                Self::MIN_CONFIDENCE,
+                Self::MAX_CONFIDENCE
+            ));
+        }
+        if !(0..=Self::MAX_TIME_SPENT_MINUTES).contains(&self.time_spent_minutes) {
+            return Err(format!(
+                "用时须在 0 到 {} 分钟之间",
+                Self::MAX_TIME_SPENT_MINUTES
+            ));
+        }
+        if self
+            .difficulties
+            .as_ref()
+            .is_some_and(|d| d.chars().count() > Self::MAX_DIFFICULTIES_LENGTH)
+        {
+            return Err(format!(
+                "困难描述不能超过 {} 个字符",
+                Self::MAX_DIFFICULTIES_LENGTH
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// 更新提交请求
@@ -39,6 +124,9 @@ pub struct SubmissionListQuery {
     pub homework_id: Option<i64>,
     pub creator_id: Option<i64>,
     pub status: Option<String>,
+    // 租户范围，由服务层根据当前用户填充
+    #[serde(skip)]
+    pub tenant: TenantScope,
 }
 
 /// 提交概览分页查询参数
@@ -49,4 +137,99 @@ pub struct SubmissionSummaryQuery {
     pub size: Option<i64>,
     /// 筛选是否已批改：true=已批改，false=待批改，None=全部
     pub graded: Option<bool>,
+    /// 筛选是否超过批改时限：true=超时未批改，false=未超时，None=全部
+    pub overdue: Option<bool>,
+}
+
+/// 提交概览存储层筛选条件
+#[derive(Debug, Clone, Copy)]
+pub struct SubmissionSummaryFilter {
+    /// 是否已批改
+    pub graded: Option<bool>,
+    /// 是否超过批改时限
+    pub overdue: Option<bool>,
+    /// 批改时限截止时间戳：早于该时间提交且未评分即视为超时
+    pub overdue_before: i64,
+    /// 标记该评分人已保存草稿的提交（课代表查看时为空）
+    pub draft_grader_id: Option<i64>,
+}
+
+/// 提交版本对比查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionDiffQuery {
+    /// 作为对比基准的提交 ID（旧版本）
+    pub against: i64,
+    /// 每个差异块保留的上下文行数（默认 3）
+    pub context: Option<usize>,
+}
+
+/// 更新班级提交状态工作流请求（statuses 为空表示恢复默认工作流）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct UpdateSubmissionWorkflowRequest {
+    #[serde(default)]
+    pub statuses: Vec<SubmissionWorkflowStatus>,
+    #[serde(default)]
+    pub transitions: Vec<SubmissionWorkflowTransition>,
+}
+
+/// 提交状态转换请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct TransitionSubmissionStatusRequest {
+    /// 目标状态（工作流中的自定义状态）
+    pub status: String,
+}
+
+/// 退回重交请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct RequestResubmissionRequest {
+    /// 退回意见（必填）
+    pub comment: String,
+    /// 重新开放截止时间，ISO 8601 格式；不得超过当前时间起 30 天
+    pub reopen_until: DateTime<Utc>,
+}
+
+impl RequestResubmissionRequest {
+    pub const MAX_COMMENT_LENGTH: usize = 2000;
+    pub const MAX_REOPEN_DAYS: i64 = 30;
+
+    /// 校验退回意见与重新开放期限
+    pub fn validate(&self, now: DateTime<Utc>) -> Result<(), String> {
+        let comment_length = self.comment.trim().chars().count();
+        if comment_length == 0 {
+            return Err("退回意见不能为空".to_string());
+        }
+        if comment_length > Self::MAX_COMMENT_LENGTH {
+            return Err(format!(
+                "退回意见不能超过 {} 个字符",
+                Self::MAX_COMMENT_LENGTH
+            ));
+        }
+        if self.reopen_until <= now {
+            return Err("重新开放截止时间必须晚于当前时间".to_string());
+        }
+        if self.reopen_until > now + chrono::Duration::days(Self::MAX_REOPEN_DAYS) {
+            return Err(format!("重新开放期限不能超过 {} 天", Self::MAX_REOPEN_DAYS));
+        }
+        Ok(())
+    }
+}
+
+/// 从班级导出包恢复的提交（保留原版本号、状态与提交时间）
+#[derive(Debug, Clone)]
+pub struct ArchivedSubmissionInput {
+    pub homework_id: i64,
+    pub creator_id: i64,
+    pub part_id: Option<i64>,
+    pub version: i32,
+    pub content: String,
+    pub status: String,
+    pub is_late: bool,
+    pub submitted_at: i64,
+    pub file_ids: Vec<i64>,
+    /// 评分：(评分人, 分数, 评语, 评分时间)
+    pub grade: Option<(i64, f64, Option<String>, i64)>,
 }
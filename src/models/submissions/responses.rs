@@ -3,9 +3,13 @@ use ts_rs::TS;
 
 use crate::models::PaginationInfo;
 use crate::models::files::responses::FileInfo;
+use crate::models::submissions::entities::{
+    SubmissionReference, SubmissionSelfAssessment, SubmissionWorkflowTransition,
+};
+use crate::models::users::entities::User;
 
 /// 提交者信息
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
 pub struct SubmissionCreator {
     pub id: i64,
@@ -14,6 +18,29 @@ pub struct SubmissionCreator {
     pub avatar_url: Option<String>,
 }
 
+impl SubmissionCreator {
+    /// 提交者账号已不存在时的占位信息
+    pub fn unknown(id: i64) -> Self {
+        Self {
+            id,
+            username: "未知用户".to_string(),
+            display_name: None,
+            avatar_url: None,
+        }
+    }
+}
+
+impl From<User> for SubmissionCreator {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+        }
+    }
+}
+
 /// 提交关联的作业信息
 #[derive(Debug, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
@@ -39,6 +66,10 @@ pub struct SubmissionResponse {
     pub version: i32,
     pub is_late: bool,
     pub homework: Option<SubmissionHomeworkInfo>,
+    /// 学生自评（课代表不可见）
+    pub self_assessment: Option<SubmissionSelfAssessment>,
+    /// 外部引用（Git 仓库 / 在线文档）
+    pub references: Vec<SubmissionReference>,
 }
 
 /// 提交中的评分信息
@@ -59,11 +90,17 @@ pub struct SubmissionListItem {
     pub homework_id: i64,
     pub creator_id: i64,
     pub creator: SubmissionCreator,
+    pub part_id: Option<i64>,
     pub version: i32,
     pub content: Option<String>,
     pub status: String,
     pub is_late: bool,
     pub submitted_at: String,
+    /// 正文词数（中日韩文字每字计一词）
+    pub word_count: i32,
+    /// 正文字符数（不含空白）
+    pub char_count: i32,
+    pub attachment_count: i32,
 }
 
 /// 提交列表响应
@@ -86,7 +123,9 @@ pub struct UserSubmissionHistoryItem {
     pub is_late: bool,
     pub submitted_at: String,
     pub attachments: Vec<FileInfo>,
+    pub references: Vec<SubmissionReference>,
     pub grade: Option<SubmissionGradeInfo>,
+    pub self_assessment: Option<SubmissionSelfAssessment>,
 }
 
 /// 用户提交历史响应（无分页）
@@ -107,6 +146,9 @@ pub struct LatestSubmissionInfo {
     pub status: String,
     pub is_late: bool,
     pub submitted_at: String,
+    pub word_count: i32,
+    pub char_count: i32,
+    pub attachment_count: i32,
 }
 
 /// 提交概览项（按学生聚合）
@@ -117,6 +159,10 @@ pub struct SubmissionSummaryItem {
     pub latest_submission: LatestSubmissionInfo,
     pub grade: Option<SubmissionGradeInfo>,
     pub total_versions: i32,
+    /// 最新提交是否已超过批改时限仍未评分
+    pub grading_overdue: bool,
+    /// 当前评分人是否已为最新提交保存评分草稿
+    pub has_draft: bool,
 }
 
 /// 提交概览响应
@@ -126,3 +172,97 @@ pub struct SubmissionSummaryResponse {
     pub items: Vec<SubmissionSummaryItem>,
     pub pagination: PaginationInfo,
 }
+
+// ============ 提交状态工作流 ============
+
+/// 工作流状态项
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionWorkflowStatusItem {
+    pub key: String,
+    pub label: String,
+    /// 是否为内置状态（pending / late / graded）
+    pub builtin: bool,
+}
+
+/// 班级提交状态工作流响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionWorkflowResponse {
+    pub class_id: i64,
+    /// 班级是否自定义了工作流
+    pub customized: bool,
+    /// 全部状态（内置状态在前）
+    pub statuses: Vec<SubmissionWorkflowStatusItem>,
+    pub transitions: Vec<SubmissionWorkflowTransition>,
+}
+
+/// 提交状态转换结果
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionStatusTransitionResponse {
+    pub submission_id: i64,
+    pub from: String,
+    pub to: String,
+    pub label: String,
+}
+
+// ============ 提交版本对比 ============
+
+/// 差异行类型
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionDiffLineKind {
+    /// 两个版本中都存在的上下文行
+    Equal,
+    /// 新版本中新增的行
+    Insert,
+    /// 旧版本中删除的行
+    Delete,
+}
+
+/// 差异行（行号从 1 开始）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionDiffLine {
+    pub kind: SubmissionDiffLineKind,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub content: String,
+}
+
+/// 差异块（与 unified diff 的 hunk 对应）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionDiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<SubmissionDiffLine>,
+}
+
+/// 参与对比的提交版本
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionDiffVersion {
+    pub id: i64,
+    pub version: i32,
+    pub submitted_at: String,
+}
+
+/// 提交版本对比响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/submission.ts")]
+pub struct SubmissionDiffResponse {
+    pub homework_id: i64,
+    pub creator_id: i64,
+    /// 对比基准（旧版本）
+    pub base: SubmissionDiffVersion,
+    /// 当前版本（新版本）
+    pub target: SubmissionDiffVersion,
+    pub added_lines: usize,
+    pub removed_lines: usize,
+    pub hunks: Vec<SubmissionDiffHunk>,
+}
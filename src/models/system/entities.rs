@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::models::moderation::entities::ModerationMode;
+use crate::models::users::entities::UserRole;
+
 /// 配置值类型
 #[derive(Debug, Clone, Serialize, PartialEq, TS)]
 #[serde(rename_all = "snake_case")]
@@ -63,9 +66,50 @@ pub enum KnownSettingKey {
     RefreshTokenExpiry,
     RefreshTokenRememberMeExpiry,
     UploadMaxSize,
+    UploadMaxRequestSize,
     UploadAllowedTypes,
     CorsAllowedOrigins,
     CorsMaxAge,
+    BrandingLogoToken,
+    BrandingPrimaryColor,
+    BrandingLoginMessage,
+    BrandingSupportContact,
+    RateLimitLogin,
+    RateLimitRegister,
+    RateLimitRefresh,
+    RateLimitInviteCode,
+    RateLimitUpload,
+    RateLimitSharedLink,
+    RateLimitShortCode,
+    SolutionRevealInterval,
+    ImReminderInterval,
+    GradingSlaInterval,
+    FileRetentionInterval,
+    FileTieringInterval,
+    MissingGradeInterval,
+    ScheduledExportInterval,
+    RegistrationMode,
+    RegistrationEmailDomains,
+    RegistrationDefaultRole,
+    RegistrationCaptchaRequired,
+    CaptchaProvider,
+    CaptchaSiteKey,
+    CaptchaSecretKey,
+    CaptchaLoginMode,
+    CaptchaLoginFailureThreshold,
+    NotificationLocale,
+    ClassShortCodeRotation,
+    GradingSlaDays,
+    HomeworkDeadlineConflictWindow,
+    HomeworkDefaultGraceMinutes,
+    RetentionSubmissionFilesDays,
+    RetentionHomeworkFilesDays,
+    RetentionOrphanFilesDays,
+    TieringColdAfterDays,
+    ModerationMode,
+    ModerationBannedWords,
+    ModerationApiUrl,
+    ModerationApiKey,
 }
 
 impl KnownSettingKey {
@@ -76,9 +120,52 @@ impl KnownSettingKey {
             KnownSettingKey::RefreshTokenExpiry => "jwt.refresh_token_expiry",
             KnownSettingKey::RefreshTokenRememberMeExpiry => "jwt.refresh_token_remember_me_expiry",
             KnownSettingKey::UploadMaxSize => "upload.max_size",
+            KnownSettingKey::UploadMaxRequestSize => "upload.max_request_size",
             KnownSettingKey::UploadAllowedTypes => "upload.allowed_types",
             KnownSettingKey::CorsAllowedOrigins => "cors.allowed_origins",
             KnownSettingKey::CorsMaxAge => "cors.max_age",
+            KnownSettingKey::BrandingLogoToken => "branding.logo_token",
+            KnownSettingKey::BrandingPrimaryColor => "branding.primary_color",
+            KnownSettingKey::BrandingLoginMessage => "branding.login_message",
+            KnownSettingKey::BrandingSupportContact => "branding.support_contact",
+            KnownSettingKey::RateLimitLogin => "rate_limit.login",
+            KnownSettingKey::RateLimitRegister => "rate_limit.register",
+            KnownSettingKey::RateLimitRefresh => "rate_limit.refresh",
+            KnownSettingKey::RateLimitInviteCode => "rate_limit.invite_code",
+            KnownSettingKey::RateLimitUpload => "rate_limit.upload",
+            KnownSettingKey::RateLimitSharedLink => "rate_limit.shared_link",
+            KnownSettingKey::RateLimitShortCode => "rate_limit.short_code",
+            KnownSettingKey::SolutionRevealInterval => "jobs.solution_reveal_interval",
+            KnownSettingKey::ImReminderInterval => "jobs.im_reminder_interval",
+            KnownSettingKey::GradingSlaInterval => "jobs.grading_sla_interval",
+            KnownSettingKey::FileRetentionInterval => "jobs.file_retention_interval",
+            KnownSettingKey::FileTieringInterval => "jobs.file_tiering_interval",
+            KnownSettingKey::MissingGradeInterval => "jobs.missing_grade_interval",
+            KnownSettingKey::ScheduledExportInterval => "jobs.scheduled_export_interval",
+            KnownSettingKey::RegistrationMode => "registration.mode",
+            KnownSettingKey::RegistrationEmailDomains => "registration.email_domains",
+            KnownSettingKey::RegistrationDefaultRole => "registration.default_role",
+            KnownSettingKey::RegistrationCaptchaRequired => "registration.captcha_required",
+            KnownSettingKey::CaptchaProvider => "captcha.provider",
+            KnownSettingKey::CaptchaSiteKey => "captcha.site_key",
+            KnownSettingKey::CaptchaSecretKey => "captcha.secret_key",
+            KnownSettingKey::CaptchaLoginMode => "captcha.login_mode",
+            KnownSettingKey::CaptchaLoginFailureThreshold => "captcha.login_failure_threshold",
+            KnownSettingKey::NotificationLocale => "notification.locale",
+            KnownSettingKey::ClassShortCodeRotation => "class.short_code_rotation",
+            KnownSettingKey::GradingSlaDays => "grading.sla_days",
+            KnownSettingKey::HomeworkDeadlineConflictWindow => {
+                "homework.deadline_conflict_window_hours"
+            }
+            KnownSettingKey::HomeworkDefaultGraceMinutes => "homework.default_grace_minutes",
+            KnownSettingKey::RetentionSubmissionFilesDays => "retention.submission_files_days",
+            KnownSettingKey::RetentionHomeworkFilesDays => "retention.homework_files_days",
+            KnownSettingKey::RetentionOrphanFilesDays => "retention.orphan_files_days",
+            KnownSettingKey::TieringColdAfterDays => "tiering.cold_after_days",
+            KnownSettingKey::ModerationMode => "moderation.mode",
+            KnownSettingKey::ModerationBannedWords => "moderation.banned_words",
+            KnownSettingKey::ModerationApiUrl => "moderation.api_url",
+            KnownSettingKey::ModerationApiKey => "moderation.api_key",
         }
     }
 
@@ -89,12 +176,181 @@ impl KnownSettingKey {
             KnownSettingKey::RefreshTokenExpiry => SettingValueType::Integer,
             KnownSettingKey::RefreshTokenRememberMeExpiry => SettingValueType::Integer,
             KnownSettingKey::UploadMaxSize => SettingValueType::Integer,
+            KnownSettingKey::UploadMaxRequestSize => SettingValueType::Integer,
             KnownSettingKey::UploadAllowedTypes => SettingValueType::JsonArray,
             KnownSettingKey::CorsAllowedOrigins => SettingValueType::JsonArray,
             KnownSettingKey::CorsMaxAge => SettingValueType::Integer,
+            KnownSettingKey::BrandingLogoToken => SettingValueType::String,
+            KnownSettingKey::BrandingPrimaryColor => SettingValueType::String,
+            KnownSettingKey::BrandingLoginMessage => SettingValueType::String,
+            KnownSettingKey::BrandingSupportContact => SettingValueType::String,
+            KnownSettingKey::RateLimitLogin => SettingValueType::Integer,
+            KnownSettingKey::RateLimitRegister => SettingValueType::Integer,
+            KnownSettingKey::RateLimitRefresh => SettingValueType::Integer,
+            KnownSettingKey::RateLimitInviteCode => SettingValueType::Integer,
+            KnownSettingKey::RateLimitUpload => SettingValueType::Integer,
+            KnownSettingKey::RateLimitSharedLink => SettingValueType::Integer,
+            KnownSettingKey::RateLimitShortCode => SettingValueType::Integer,
+            KnownSettingKey::SolutionRevealInterval => SettingValueType::Integer,
+            KnownSettingKey::ImReminderInterval => SettingValueType::Integer,
+            KnownSettingKey::GradingSlaInterval => SettingValueType::Integer,
+            KnownSettingKey::FileRetentionInterval => SettingValueType::Integer,
+            KnownSettingKey::FileTieringInterval => SettingValueType::Integer,
+            KnownSettingKey::MissingGradeInterval => SettingValueType::Integer,
+            KnownSettingKey::ScheduledExportInterval => SettingValueType::Integer,
+            KnownSettingKey::RegistrationMode => SettingValueType::String,
+            KnownSettingKey::RegistrationEmailDomains => SettingValueType::JsonArray,
+            KnownSettingKey::RegistrationDefaultRole => SettingValueType::String,
+            KnownSettingKey::RegistrationCaptchaRequired => SettingValueType::Boolean,
+            KnownSettingKey::CaptchaProvider => SettingValueType::String,
+            KnownSettingKey::CaptchaSiteKey => SettingValueType::String,
+            KnownSettingKey::CaptchaSecretKey => SettingValueType::String,
+            KnownSettingKey::CaptchaLoginMode => SettingValueType::String,
+            KnownSettingKey::CaptchaLoginFailureThreshold => SettingValueType::Integer,
+            KnownSettingKey::NotificationLocale => SettingValueType::String,
+            KnownSettingKey::ClassShortCodeRotation => SettingValueType::Integer,
+            KnownSettingKey::GradingSlaDays => SettingValueType::Integer,
+            KnownSettingKey::HomeworkDeadlineConflictWindow => SettingValueType::Integer,
+            KnownSettingKey::HomeworkDefaultGraceMinutes => SettingValueType::Integer,
+            KnownSettingKey::RetentionSubmissionFilesDays => SettingValueType::Integer,
+            KnownSettingKey::RetentionHomeworkFilesDays => SettingValueType::Integer,
+            KnownSettingKey::RetentionOrphanFilesDays => SettingValueType::Integer,
+            KnownSettingKey::TieringColdAfterDays => SettingValueType::Integer,
+            KnownSettingKey::ModerationMode => SettingValueType::String,
+            KnownSettingKey::ModerationBannedWords => SettingValueType::JsonArray,
+            KnownSettingKey::ModerationApiUrl => SettingValueType::String,
+            KnownSettingKey::ModerationApiKey => SettingValueType::String,
+        }
+    }
+
+    /// 配置值约束
+    pub fn constraints(&self) -> SettingConstraints {
+        match self {
+            KnownSettingKey::SystemName => SettingConstraints::max_length(100),
+            KnownSettingKey::AccessTokenExpiry => SettingConstraints::range(1, 1440),
+            KnownSettingKey::RefreshTokenExpiry => SettingConstraints::range(1, 365),
+            KnownSettingKey::RefreshTokenRememberMeExpiry => SettingConstraints::range(1, 365),
+            KnownSettingKey::UploadMaxSize | KnownSettingKey::UploadMaxRequestSize => {
+                SettingConstraints::range(1, 1024 * 1024 * 1024)
+            }
+            KnownSettingKey::CorsMaxAge => SettingConstraints::range(0, 86400),
+            KnownSettingKey::BrandingLogoToken => SettingConstraints::max_length(128),
+            KnownSettingKey::BrandingPrimaryColor => SettingConstraints::max_length(7),
+            KnownSettingKey::BrandingLoginMessage => SettingConstraints::max_length(500),
+            KnownSettingKey::BrandingSupportContact => SettingConstraints::max_length(200),
+            KnownSettingKey::RateLimitLogin
+            | KnownSettingKey::RateLimitRegister
+            | KnownSettingKey::RateLimitRefresh
+            | KnownSettingKey::RateLimitInviteCode
+            | KnownSettingKey::RateLimitUpload
+            | KnownSettingKey::RateLimitSharedLink
+            | KnownSettingKey::RateLimitShortCode => SettingConstraints::range(1, 10000),
+            KnownSettingKey::SolutionRevealInterval
+            | KnownSettingKey::ImReminderInterval
+            | KnownSettingKey::GradingSlaInterval
+            | KnownSettingKey::FileRetentionInterval
+            | KnownSettingKey::FileTieringInterval
+            | KnownSettingKey::MissingGradeInterval
+            | KnownSettingKey::ScheduledExportInterval => SettingConstraints::range(30, 86400),
+            KnownSettingKey::RegistrationMode => SettingConstraints::one_of(&[
+                RegistrationMode::Open,
+                RegistrationMode::Closed,
+                RegistrationMode::InviteOnly,
+            ]),
+            // 自助注册不允许直接获得管理员角色
+            KnownSettingKey::RegistrationDefaultRole => {
+                SettingConstraints::one_of(&[UserRole::USER, UserRole::TEACHER])
+            }
+            KnownSettingKey::CaptchaProvider => SettingConstraints::one_of(&[
+                CaptchaProvider::None,
+                CaptchaProvider::Hcaptcha,
+                CaptchaProvider::Recaptcha,
+                CaptchaProvider::Turnstile,
+            ]),
+            KnownSettingKey::CaptchaSiteKey | KnownSettingKey::CaptchaSecretKey => {
+                SettingConstraints::max_length(200)
+            }
+            KnownSettingKey::CaptchaLoginMode => SettingConstraints::one_of(&[
+                LoginCaptchaMode::Off,
+                LoginCaptchaMode::Always,
+                LoginCaptchaMode::AfterFailures,
+            ]),
+            KnownSettingKey::CaptchaLoginFailureThreshold => SettingConstraints::range(1, 100),
+            KnownSettingKey::NotificationLocale => SettingConstraints::max_length(16),
+            KnownSettingKey::ClassShortCodeRotation => SettingConstraints::range(1, 1440),
+            KnownSettingKey::GradingSlaDays => SettingConstraints::range(1, 90),
+            KnownSettingKey::HomeworkDeadlineConflictWindow => SettingConstraints::range(1, 336),
+            KnownSettingKey::HomeworkDefaultGraceMinutes => SettingConstraints::range(0, 1440),
+            // 0 表示永久保留
+            KnownSettingKey::RetentionSubmissionFilesDays
+            | KnownSettingKey::RetentionHomeworkFilesDays => SettingConstraints::range(0, 3650),
+            KnownSettingKey::RetentionOrphanFilesDays => SettingConstraints::range(1, 365),
+            KnownSettingKey::TieringColdAfterDays => SettingConstraints::range(0, 3650),
+            KnownSettingKey::ModerationMode => SettingConstraints::one_of(&[
+                ModerationMode::Off,
+                ModerationMode::Flag,
+                ModerationMode::Block,
+            ]),
+            KnownSettingKey::ModerationApiUrl => SettingConstraints::max_length(500),
+            KnownSettingKey::ModerationApiKey => SettingConstraints::max_length(200),
+            KnownSettingKey::UploadAllowedTypes
+            | KnownSettingKey::CorsAllowedOrigins
+            | KnownSettingKey::RegistrationEmailDomains
+            | KnownSettingKey::RegistrationCaptchaRequired
+            | KnownSettingKey::ModerationBannedWords => SettingConstraints::default(),
         }
     }
 
+    /// 按类型与约束校验配置值
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let key = self.as_str();
+        let constraints = self.constraints();
+
+        match self.value_type() {
+            SettingValueType::String => {
+                let len = value.chars().count();
+                if let Some(max) = constraints.max_length
+                    && len > max
+                {
+                    return Err(format!("{key} 不能超过 {max} 个字符"));
+                }
+                if let Some(allowed) = &constraints.allowed_values
+                    && !allowed.iter().any(|v| v == value)
+                {
+                    return Err(format!("{key} 必须为以下值之一: {}", allowed.join(", ")));
+                }
+            }
+            SettingValueType::Integer => {
+                let n: i64 = value.parse().map_err(|_| format!("{key} 必须为整数"))?;
+                if let Some(min) = constraints.min
+                    && n < min
+                {
+                    return Err(format!("{key} 不能小于 {min}"));
+                }
+                if let Some(max) = constraints.max
+                    && n > max
+                {
+                    return Err(format!("{key} 不能大于 {max}"));
+                }
+            }
+            SettingValueType::Boolean => {
+                if value != "true" && value != "false" {
+                    return Err(format!("{key} 必须为 true 或 false"));
+                }
+            }
+            SettingValueType::JsonArray => {
+                let items: Vec<String> = serde_json::from_str(value)
+                    .map_err(|_| format!("{key} 必须为字符串 JSON 数组"))?;
+                if let Some(allowed) = &constraints.allowed_values
+                    && let Some(item) = items.iter().find(|i| !allowed.contains(i))
+                {
+                    return Err(format!("{key} 包含无效值: {item}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn all() -> Vec<Self> {
         vec![
             KnownSettingKey::SystemName,
@@ -102,9 +358,50 @@ impl KnownSettingKey {
             KnownSettingKey::RefreshTokenExpiry,
             KnownSettingKey::RefreshTokenRememberMeExpiry,
             KnownSettingKey::UploadMaxSize,
+            KnownSettingKey::UploadMaxRequestSize,
             KnownSettingKey::UploadAllowedTypes,
             KnownSettingKey::CorsAllowedOrigins,
             KnownSettingKey::CorsMaxAge,
+            KnownSettingKey::BrandingLogoToken,
+            KnownSettingKey::BrandingPrimaryColor,
+            KnownSettingKey::BrandingLoginMessage,
+            KnownSettingKey::BrandingSupportContact,
+            KnownSettingKey::RateLimitLogin,
+            KnownSettingKey::RateLimitRegister,
+            KnownSettingKey::RateLimitRefresh,
+            KnownSettingKey::RateLimitInviteCode,
+            KnownSettingKey::RateLimitUpload,
+            KnownSettingKey::RateLimitSharedLink,
+            KnownSettingKey::RateLimitShortCode,
+            KnownSettingKey::SolutionRevealInterval,
+            KnownSettingKey::ImReminderInterval,
+            KnownSettingKey::GradingSlaInterval,
+            KnownSettingKey::FileRetentionInterval,
+            KnownSettingKey::FileTieringInterval,
+            KnownSettingKey::MissingGradeInterval,
+            KnownSettingKey::ScheduledExportInterval,
+            KnownSettingKey::RegistrationMode,
+            KnownSettingKey::RegistrationEmailDomains,
+            KnownSettingKey::RegistrationDefaultRole,
+            KnownSettingKey::RegistrationCaptchaRequired,
+            KnownSettingKey::CaptchaProvider,
+            KnownSettingKey::CaptchaSiteKey,
+            KnownSettingKey::CaptchaSecretKey,
+            KnownSettingKey::CaptchaLoginMode,
+            KnownSettingKey::CaptchaLoginFailureThreshold,
+            KnownSettingKey::NotificationLocale,
+            KnownSettingKey::ClassShortCodeRotation,
+            KnownSettingKey::GradingSlaDays,
+            KnownSettingKey::HomeworkDeadlineConflictWindow,
+            KnownSettingKey::HomeworkDefaultGraceMinutes,
+            KnownSettingKey::RetentionSubmissionFilesDays,
+            KnownSettingKey::RetentionHomeworkFilesDays,
+            KnownSettingKey::RetentionOrphanFilesDays,
+            KnownSettingKey::TieringColdAfterDays,
+            KnownSettingKey::ModerationMode,
+            KnownSettingKey::ModerationBannedWords,
+            KnownSettingKey::ModerationApiUrl,
+            KnownSettingKey::ModerationApiKey,
         ]
     }
 }
@@ -121,14 +418,237 @@ impl std::str::FromStr for KnownSettingKey {
                 Ok(KnownSettingKey::RefreshTokenRememberMeExpiry)
             }
             "upload.max_size" => Ok(KnownSettingKey::UploadMaxSize),
+            "upload.max_request_size" => Ok(KnownSettingKey::UploadMaxRequestSize),
             "upload.allowed_types" => Ok(KnownSettingKey::UploadAllowedTypes),
             "cors.allowed_origins" => Ok(KnownSettingKey::CorsAllowedOrigins),
             "cors.max_age" => Ok(KnownSettingKey::CorsMaxAge),
+            "branding.logo_token" => Ok(KnownSettingKey::BrandingLogoToken),
+            "branding.primary_color" => Ok(KnownSettingKey::BrandingPrimaryColor),
+            "branding.login_message" => Ok(KnownSettingKey::BrandingLoginMessage),
+            "branding.support_contact" => Ok(KnownSettingKey::BrandingSupportContact),
+            "rate_limit.login" => Ok(KnownSettingKey::RateLimitLogin),
+            "rate_limit.register" => Ok(KnownSettingKey::RateLimitRegister),
+            "rate_limit.refresh" => Ok(KnownSettingKey::RateLimitRefresh),
+            "rate_limit.invite_code" => Ok(KnownSettingKey::RateLimitInviteCode),
+            "rate_limit.upload" => Ok(KnownSettingKey::RateLimitUpload),
+            "rate_limit.shared_link" => Ok(KnownSettingKey::RateLimitSharedLink),
+            "rate_limit.short_code" => Ok(KnownSettingKey::RateLimitShortCode),
+            "jobs.solution_reveal_interval" => Ok(KnownSettingKey::SolutionRevealInterval),
+            "jobs.im_reminder_interval" => Ok(KnownSettingKey::ImReminderInterval),
+            "jobs.grading_sla_interval" => Ok(KnownSettingKey::GradingSlaInterval),
+            "jobs.file_retention_interval" => Ok(KnownSettingKey::FileRetentionInterval),
+            "jobs.file_tiering_interval" => Ok(KnownSettingKey::FileTieringInterval),
+            "jobs.missing_grade_interval" => Ok(KnownSettingKey::MissingGradeInterval),
+            "jobs.scheduled_export_interval" => Ok(KnownSettingKey::ScheduledExportInterval),
+            "registration.mode" => Ok(KnownSettingKey::RegistrationMode),
+            "registration.email_domains" => Ok(KnownSettingKey::RegistrationEmailDomains),
+            "registration.default_role" => Ok(KnownSettingKey::RegistrationDefaultRole),
+            "registration.captcha_required" => Ok(KnownSettingKey::RegistrationCaptchaRequired),
+            "captcha.provider" => Ok(KnownSettingKey::CaptchaProvider),
+            "captcha.site_key" => Ok(KnownSettingKey::CaptchaSiteKey),
+            "captcha.secret_key" => Ok(KnownSettingKey::CaptchaSecretKey),
+            "captcha.login_mode" => Ok(KnownSettingKey::CaptchaLoginMode),
+            "captcha.login_failure_threshold" => Ok(KnownSettingKey::CaptchaLoginFailureThreshold),
+            "notification.locale" => Ok(KnownSettingKey::NotificationLocale),
+            "class.short_code_rotation" => Ok(KnownSettingKey::ClassShortCodeRotation),
+            "grading.sla_days" => Ok(KnownSettingKey::GradingSlaDays),
+            "homework.deadline_conflict_window_hours" => {
+                Ok(KnownSettingKey::HomeworkDeadlineConflictWindow)
+            }
+            "homework.default_grace_minutes" => Ok(KnownSettingKey::HomeworkDefaultGraceMinutes),
+            "retention.submission_files_days" => Ok(KnownSettingKey::RetentionSubmissionFilesDays),
+            "retention.homework_files_days" => Ok(KnownSettingKey::RetentionHomeworkFilesDays),
+            "retention.orphan_files_days" => Ok(KnownSettingKey::RetentionOrphanFilesDays),
+            "tiering.cold_after_days" => Ok(KnownSettingKey::TieringColdAfterDays),
+            "moderation.mode" => Ok(KnownSettingKey::ModerationMode),
+            "moderation.banned_words" => Ok(KnownSettingKey::ModerationBannedWords),
+            "moderation.api_url" => Ok(KnownSettingKey::ModerationApiUrl),
+            "moderation.api_key" => Ok(KnownSettingKey::ModerationApiKey),
             _ => Err(format!("Unknown setting key: {s}")),
         }
     }
 }
 
+/// 配置值约束（范围、长度、可选值）
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct SettingConstraints {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub max_length: Option<usize>,
+    pub allowed_values: Option<Vec<String>>,
+}
+
+impl SettingConstraints {
+    fn range(min: i64, max: i64) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+            ..Default::default()
+        }
+    }
+
+    fn max_length(max_length: usize) -> Self {
+        Self {
+            max_length: Some(max_length),
+            ..Default::default()
+        }
+    }
+
+    fn one_of<T: ToString>(values: &[T]) -> Self {
+        Self {
+            allowed_values: Some(values.iter().map(ToString::to_string).collect()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 注册模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub enum RegistrationMode {
+    /// 开放注册
+    Open,
+    /// 关闭自助注册，仅管理员可创建用户
+    Closed,
+    /// 凭班级邀请码注册，注册后自动加入该班级
+    InviteOnly,
+}
+
+impl std::fmt::Display for RegistrationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistrationMode::Open => write!(f, "open"),
+            RegistrationMode::Closed => write!(f, "closed"),
+            RegistrationMode::InviteOnly => write!(f, "invite_only"),
+        }
+    }
+}
+
+impl std::str::FromStr for RegistrationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(RegistrationMode::Open),
+            "closed" => Ok(RegistrationMode::Closed),
+            "invite_only" => Ok(RegistrationMode::InviteOnly),
+            _ => Err(format!("Invalid registration mode: {s}")),
+        }
+    }
+}
+
+/// 人机验证服务商
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub enum CaptchaProvider {
+    /// 未启用人机验证
+    None,
+    Hcaptcha,
+    Recaptcha,
+    /// Cloudflare Turnstile
+    Turnstile,
+}
+
+impl CaptchaProvider {
+    /// 服务端校验令牌的接口地址
+    pub fn verify_url(&self) -> Option<&'static str> {
+        match self {
+            CaptchaProvider::None => None,
+            CaptchaProvider::Hcaptcha => Some("https://api.hcaptcha.com/siteverify"),
+            CaptchaProvider::Recaptcha => Some("https://www.google.com/recaptcha/api/siteverify"),
+            CaptchaProvider::Turnstile => {
+                Some("https://challenges.cloudflare.com/turnstile/v0/siteverify")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CaptchaProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptchaProvider::None => write!(f, "none"),
+            CaptchaProvider::Hcaptcha => write!(f, "hcaptcha"),
+            CaptchaProvider::Recaptcha => write!(f, "recaptcha"),
+            CaptchaProvider::Turnstile => write!(f, "turnstile"),
+        }
+    }
+}
+
+impl std::str::FromStr for CaptchaProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(CaptchaProvider::None),
+            "hcaptcha" => Ok(CaptchaProvider::Hcaptcha),
+            "recaptcha" => Ok(CaptchaProvider::Recaptcha),
+            "turnstile" => Ok(CaptchaProvider::Turnstile),
+            _ => Err(format!("Invalid captcha provider: {s}")),
+        }
+    }
+}
+
+/// 登录时的人机验证策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub enum LoginCaptchaMode {
+    /// 登录不需要验证
+    Off,
+    /// 每次登录都需要验证
+    Always,
+    /// 同一 IP 或账号连续登录失败达到阈值后才需要验证
+    AfterFailures,
+}
+
+impl std::fmt::Display for LoginCaptchaMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoginCaptchaMode::Off => write!(f, "off"),
+            LoginCaptchaMode::Always => write!(f, "always"),
+            LoginCaptchaMode::AfterFailures => write!(f, "after_failures"),
+        }
+    }
+}
+
+impl std::str::FromStr for LoginCaptchaMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(LoginCaptchaMode::Off),
+            "always" => Ok(LoginCaptchaMode::Always),
+            "after_failures" => Ok(LoginCaptchaMode::AfterFailures),
+            _ => Err(format!("Invalid login captcha mode: {s}")),
+        }
+    }
+}
+
+/// 待写入的配置变更（已通过校验）
+#[derive(Debug, Clone)]
+pub struct SettingChange {
+    pub key: String,
+    pub value: String,
+    /// 配置项不存在时以该类型创建（自定义配置项）
+    pub value_type: SettingValueType,
+}
+
+/// 批量更新配置的结果
+#[derive(Debug, Clone)]
+pub enum SettingsUpdateOutcome {
+    /// 已写入；`changed` 仅包含值发生变化的配置项
+    Applied {
+        changed: Vec<SystemSetting>,
+        unchanged: Vec<String>,
+        version: i64,
+    },
+    /// 配置版本与请求中的 `expected_version` 不一致，未做任何修改
+    VersionConflict { current_version: i64 },
+}
+
 /// 系统设置实体
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
@@ -153,3 +673,103 @@ pub struct SettingAudit {
     pub changed_at: chrono::DateTime<chrono::Utc>,
     pub ip_address: Option<String>,
 }
+
+/// SQLite WAL 检查点模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub enum WalCheckpointMode {
+    /// 不等待读写，尽可能多地写回
+    Passive,
+    /// 等待写入结束后全部写回
+    Full,
+    /// 全部写回后等待读取结束，使下次写入从 WAL 开头开始
+    Restart,
+    /// 同 restart，并将 WAL 文件截断为 0 字节
+    #[default]
+    Truncate,
+}
+
+impl std::fmt::Display for WalCheckpointMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WalCheckpointMode::Passive => write!(f, "passive"),
+            WalCheckpointMode::Full => write!(f, "full"),
+            WalCheckpointMode::Restart => write!(f, "restart"),
+            WalCheckpointMode::Truncate => write!(f, "truncate"),
+        }
+    }
+}
+
+impl WalCheckpointMode {
+    /// `PRAGMA wal_checkpoint(...)` 的参数
+    pub fn pragma_arg(&self) -> &'static str {
+        match self {
+            WalCheckpointMode::Passive => "PASSIVE",
+            WalCheckpointMode::Full => "FULL",
+            WalCheckpointMode::Restart => "RESTART",
+            WalCheckpointMode::Truncate => "TRUNCATE",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_settings_are_range_checked() {
+        let key = KnownSettingKey::AccessTokenExpiry;
+        assert!(key.validate("60").is_ok());
+        assert!(key.validate("0").is_err());
+        assert!(key.validate("1441").is_err());
+        assert!(key.validate("abc").is_err());
+    }
+
+    #[test]
+    fn string_and_array_settings_are_checked() {
+        assert!(KnownSettingKey::SystemName.validate("作业系统").is_ok());
+        assert!(
+            KnownSettingKey::SystemName
+                .validate(&"a".repeat(101))
+                .is_err()
+        );
+        assert!(
+            KnownSettingKey::UploadAllowedTypes
+                .validate(r#"[".pdf", ".zip"]"#)
+                .is_ok()
+        );
+        assert!(
+            KnownSettingKey::UploadAllowedTypes
+                .validate(".pdf,.zip")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn registration_settings_are_checked() {
+        assert!(
+            KnownSettingKey::RegistrationMode
+                .validate("invite_only")
+                .is_ok()
+        );
+        assert!(KnownSettingKey::RegistrationMode.validate("vip").is_err());
+        assert!(
+            KnownSettingKey::RegistrationDefaultRole
+                .validate("admin")
+                .is_err()
+        );
+        assert!(
+            KnownSettingKey::RegistrationCaptchaRequired
+                .validate("yes")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn every_known_key_round_trips() {
+        for key in KnownSettingKey::all() {
+            assert_eq!(key.as_str().parse::<KnownSettingKey>(), Ok(key));
+        }
+    }
+}
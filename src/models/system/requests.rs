@@ -1,11 +1,16 @@
 use serde::Deserialize;
 use ts_rs::TS;
 
+use super::entities::WalCheckpointMode;
+
 /// 更新配置请求
 #[derive(Debug, Clone, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
 pub struct UpdateSettingRequest {
     pub value: String,
+    /// 允许写入未登记的自定义配置项
+    #[serde(default)]
+    pub custom: bool,
 }
 
 /// 批量更新配置请求
@@ -13,6 +18,8 @@ pub struct UpdateSettingRequest {
 #[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
 pub struct BatchUpdateSettingsRequest {
     pub settings: Vec<UpdateSettingItem>,
+    /// 期望的当前配置版本，不一致时拒绝更新（乐观锁）
+    pub expected_version: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize, TS)]
@@ -20,6 +27,9 @@ pub struct BatchUpdateSettingsRequest {
 pub struct UpdateSettingItem {
     pub key: String,
     pub value: String,
+    /// 允许写入未登记的自定义配置项
+    #[serde(default)]
+    pub custom: bool,
 }
 
 /// 审计日志查询参数
@@ -34,5 +44,54 @@ pub struct SettingAuditQuery {
 /// WebSocket 连接查询参数
 #[derive(Debug, Deserialize)]
 pub struct WsQuery {
-    pub token: String,
+    /// 访问令牌（已不推荐，建议连接后发送 auth 消息）
+    pub token: Option<String>,
+    /// 补发该时间（Unix 时间戳）之后未送达的通知
+    pub since: Option<i64>,
+    /// 客户端支持 `notifications` 批量消息
+    pub batch: Option<bool>,
+    /// 下行消息压缩方式，目前支持 `deflate`
+    pub compression: Option<String>,
+}
+
+/// 数据一致性检查请求
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IntegrityCheckRequest {
+    /// 是否自动修复安全问题（删除孤立的班级成员与评分、重算文件引用计数）
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// WAL 检查点查询参数
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct WalCheckpointQuery {
+    /// 检查点模式，默认 truncate（写回后截断 WAL 文件）
+    #[serde(default)]
+    pub mode: WalCheckpointMode,
+}
+
+/// 压测数据生成请求（仅开发环境）
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenerateDevDataRequest {
+    /// 班级数量（每个班级配一名独立教师）
+    pub classes: u32,
+    /// 每个班级的学生数量
+    pub students_per_class: u32,
+    /// 每个班级的作业数量
+    pub homeworks_per_class: u32,
+    /// 学生提交作业的比例（0~1）
+    #[serde(default = "default_submission_rate")]
+    pub submission_rate: f64,
+    /// 已提交作业中完成评分的比例（0~1）
+    #[serde(default = "default_graded_rate")]
+    pub graded_rate: f64,
+}
+
+fn default_submission_rate() -> f64 {
+    0.8
+}
+
+fn default_graded_rate() -> f64 {
+    0.5
 }
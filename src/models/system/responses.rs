@@ -1,8 +1,12 @@
 use serde::Serialize;
 use ts_rs::TS;
 
-use super::entities::{SettingAudit, SystemSetting};
+use super::entities::{
+    CaptchaProvider, LoginCaptchaMode, RegistrationMode, SettingAudit, SettingConstraints,
+    SettingValueType, SystemSetting, WalCheckpointMode,
+};
 use crate::models::common::PaginationInfo;
+use crate::models::users::entities::UserRole;
 
 #[derive(Debug, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
@@ -14,12 +18,94 @@ pub struct SystemSettingsResponse {
     pub log_level: String,               // 日志级别
 }
 
+/// 品牌设置响应（公开，登录前可访问）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct BrandingResponse {
+    pub system_name: String,             // 系统名称
+    pub logo_url: Option<String>,        // Logo 地址（为空使用默认 Logo）
+    pub primary_color: String,           // 主题色（#RRGGBB）
+    pub login_message: Option<String>,   // 登录页提示信息
+    pub support_contact: Option<String>, // 技术支持联系方式
+}
+
+/// 注册策略响应（公开，供前端调整注册表单）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct RegistrationPolicyResponse {
+    pub mode: RegistrationMode,     // 注册模式
+    pub email_domains: Vec<String>, // 允许的邮箱域名，为空表示不限制
+    pub default_role: UserRole,     // 注册用户的角色
+    pub captcha_required: bool,     // 是否需要人机验证
+}
+
+/// 人机验证配置响应（公开，供前端加载验证组件）
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct CaptchaConfigResponse {
+    pub provider: CaptchaProvider,    // 服务商，none 表示未启用
+    pub site_key: Option<String>,     // 前端组件使用的站点密钥
+    pub login_mode: LoginCaptchaMode, // 登录验证策略
+    pub login_failure_threshold: i64, // after_failures 模式下触发验证的失败次数
+    pub register_required: bool,      // 注册是否需要验证
+}
+
+/// 服务器时间响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct ServerTimeResponse {
+    pub server_time: i64,         // 服务器当前时间（毫秒时间戳）
+    pub signed_timestamp: String, // 签名时间戳，考试模式提交时回传
+}
+
 /// WebSocket 状态响应
 #[derive(Debug, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
 pub struct WebSocketStatusResponse {
     pub online_users: usize,
     pub status: String,
+    pub metrics: WsMetricsSnapshot,
+    pub connections: WsConnectionMetrics,
+}
+
+/// WebSocket 发送统计（进程启动以来累计）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct WsMetricsSnapshot {
+    /// 发送的帧数
+    pub frames_sent: u64,
+    /// 实际发送的字节数（压缩后）
+    pub bytes_sent: u64,
+    /// 序列化后的原始字节数（压缩前）
+    pub bytes_uncompressed: u64,
+    /// 压缩发送的帧数
+    pub compressed_frames: u64,
+    /// 合并发送的批量通知帧数
+    pub batched_frames: u64,
+    /// 通过批量帧送出的通知数
+    pub batched_notifications: u64,
+}
+
+/// WebSocket 连接统计（连接数为当前值，其余为进程启动以来累计）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct WsConnectionMetrics {
+    /// 当前连接数
+    pub active_connections: usize,
+    /// 建立的连接数
+    pub connections_opened: u64,
+    /// 断开的连接数
+    pub connections_closed: u64,
+    /// 因空闲超时关闭的连接数
+    pub idle_timeouts: u64,
+    /// 因全局连接上限被拒绝的连接数
+    pub rejected_global_limit: u64,
+    /// 因单用户连接上限被拒绝的连接数
+    pub rejected_user_limit: u64,
+    /// 广播接收落后（Lagged）的次数
+    pub lag_events: u64,
+    /// 因落后被丢弃的消息数
+    pub lagged_messages: u64,
 }
 
 /// 管理员配置列表响应
@@ -27,6 +113,32 @@ pub struct WebSocketStatusResponse {
 #[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
 pub struct AdminSettingsListResponse {
     pub settings: Vec<SystemSetting>,
+    pub version: i64, // 当前配置版本
+}
+
+/// 批量更新配置响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct BatchUpdateSettingsResponse {
+    pub updated: Vec<SystemSetting>, // 值发生变化的配置项
+    pub unchanged: Vec<String>,      // 值未变化、未写入的配置项
+    pub version: i64,                // 更新后的配置版本
+}
+
+/// 配置项定义
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct SettingDefinition {
+    pub key: String,
+    pub value_type: SettingValueType,
+    pub constraints: SettingConstraints,
+}
+
+/// 配置项定义列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct SettingSchemaResponse {
+    pub settings: Vec<SettingDefinition>,
 }
 
 /// 单个配置响应
@@ -43,3 +155,112 @@ pub struct SettingAuditListResponse {
     pub audits: Vec<SettingAudit>,
     pub pagination: PaginationInfo,
 }
+
+/// 压测数据生成结果（仅开发环境）
+#[derive(Debug, Clone, Serialize)]
+pub struct DevDataSummary {
+    /// 本批数据的标识，出现在生成的用户名、班级名中
+    pub batch: String,
+    pub teachers: i64,
+    pub students: i64,
+    pub classes: i64,
+    pub class_members: i64,
+    pub homeworks: i64,
+    pub submissions: i64,
+    pub grades: i64,
+    /// 生成耗时（毫秒）
+    pub elapsed_ms: i64,
+}
+
+/// 数据一致性检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheckKind {
+    /// 班级或用户已不存在的班级成员记录
+    OrphanedClassUsers,
+    /// 所属作业已不存在的提交
+    SubmissionsWithoutHomework,
+    /// 文件引用计数与实际引用数不一致
+    CitationCountMismatch,
+    /// 指向已删除提交的评分
+    GradesWithoutSubmission,
+    /// 作业提交/评分计数与实际提交、评分不一致
+    HomeworkCounterMismatch,
+}
+
+/// 单项一致性检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityCheckResult {
+    pub check: IntegrityCheckKind,
+    /// 发现的问题记录数
+    pub found: i64,
+    /// 是否属于可自动修复的安全问题
+    pub repairable: bool,
+    /// 本次修复的记录数（未开启修复时为 0）
+    pub repaired: i64,
+    /// 问题记录 ID 示例（最多 100 个）
+    pub sample_ids: Vec<i64>,
+}
+
+/// 数据一致性检查报告（后台任务产出）
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    pub repair: bool,
+    pub checks: Vec<IntegrityCheckResult>,
+    /// 发现的问题总数
+    pub total_found: i64,
+    /// 修复的问题总数
+    pub total_repaired: i64,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    /// 检查耗时（毫秒）
+    pub elapsed_ms: i64,
+}
+
+/// 数据库文件状态
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct DatabaseStatus {
+    /// 数据库类型（sqlite / postgres / mysql）
+    pub backend: String,
+    // 以下字段仅 SQLite 提供
+    /// 数据库文件路径，内存数据库为空
+    pub file_path: Option<String>,
+    /// 日志模式（wal / delete / memory 等）
+    pub journal_mode: Option<String>,
+    /// 数据库大小(字节)：page_count × page_size
+    pub database_size: Option<i64>,
+    /// WAL 文件大小(字节)，不存在时为 0
+    pub wal_size: Option<i64>,
+    pub page_size: Option<i64>,
+    pub page_count: Option<i64>,
+    /// 空闲页数，可通过备份（VACUUM INTO）得到紧凑的副本
+    pub freelist_count: Option<i64>,
+}
+
+/// WAL 检查点结果
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct WalCheckpointResponse {
+    pub mode: WalCheckpointMode,
+    /// 是否因读写冲突未能完成（passive 以外的模式）
+    pub busy: bool,
+    /// WAL 中的帧数（非 WAL 模式为 -1）
+    pub log_frames: i64,
+    /// 已写回数据库的帧数（非 WAL 模式为 -1）
+    pub checkpointed_frames: i64,
+    /// 检查点后的 WAL 文件大小(字节)
+    pub wal_size: Option<i64>,
+}
+
+/// 数据库在线备份结果
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/system.ts")]
+pub struct DatabaseBackupResponse {
+    /// 备份文件名（位于配置的 `backup.dir` 目录）
+    pub file_name: String,
+    /// 备份文件大小(字节)
+    pub size: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 备份耗时（毫秒）
+    pub elapsed_ms: i64,
+}
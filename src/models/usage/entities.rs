@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+// 默认租户在用量表中的组织 ID（唯一索引中不能使用 NULL）
+pub const DEFAULT_TENANT_ORG_ID: i64 = 0;
+
+// 将用户/资源的 org_id 映射为用量表中的组织 ID
+pub fn usage_org_id(org_id: Option<i64>) -> i64 {
+    org_id.unwrap_or(DEFAULT_TENANT_ORG_ID)
+}
+
+// 按月累计的用量指标
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub enum UsageMetric {
+    UploadedBytes, // 上传字节数
+    Submissions,   // 提交次数
+}
+
+impl std::fmt::Display for UsageMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageMetric::UploadedBytes => write!(f, "uploaded_bytes"),
+            UsageMetric::Submissions => write!(f, "submissions"),
+        }
+    }
+}
+
+impl std::str::FromStr for UsageMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uploaded_bytes" => Ok(UsageMetric::UploadedBytes),
+            "submissions" => Ok(UsageMetric::Submissions),
+            _ => Err(format!("Invalid usage metric: {s}")),
+        }
+    }
+}
+
+// 计数周期（UTC 自然月，格式 YYYY-MM）
+pub fn usage_period(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.format("%Y-%m").to_string()
+}
+
+// 用量计数器
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct UsageCounter {
+    // 组织ID（0 表示默认租户）
+    pub org_id: i64,
+    // 计数周期（YYYY-MM）
+    pub period: String,
+    // 指标
+    pub metric: UsageMetric,
+    // 累计值
+    pub value: i64,
+    // 更新时间
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 每日用量报表
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct UsageReport {
+    pub id: i64,
+    // 组织ID（0 表示默认租户）
+    pub org_id: i64,
+    // 报表日期（YYYY-MM-DD，UTC）
+    pub report_date: String,
+    // 用户总数
+    pub total_users: i64,
+    // 活跃用户数（30 天内登录过）
+    pub active_users: i64,
+    // 已用存储空间（字节）
+    pub storage_bytes: i64,
+    // 当月上传字节数
+    pub uploaded_bytes: i64,
+    // 当月提交次数
+    pub submissions: i64,
+    // 生成时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_usage_period() {
+        let at = chrono::Utc
+            .with_ymd_and_hms(2026, 1, 31, 23, 59, 59)
+            .unwrap();
+        assert_eq!(usage_period(at), "2026-01");
+    }
+
+    #[test]
+    fn test_usage_org_id() {
+        assert_eq!(usage_org_id(None), DEFAULT_TENANT_ORG_ID);
+        assert_eq!(usage_org_id(Some(3)), 3);
+    }
+}
@@ -0,0 +1,8 @@
+// 用量实体定义
+pub mod entities;
+
+// 用量请求模型
+pub mod requests;
+
+// 用量响应模型
+pub mod responses;
@@ -0,0 +1,37 @@
+use crate::models::common::PaginationQuery;
+use serde::Deserialize;
+use ts_rs::TS;
+
+// 用量报表查询参数（来自HTTP请求）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct UsageReportListParams {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub pagination: PaginationQuery,
+    // 组织ID（0 表示默认租户；平台管理员不传则查询全部组织）
+    pub org_id: Option<i64>,
+    // 起始日期（YYYY-MM-DD，含）
+    pub from: Option<String>,
+    // 结束日期（YYYY-MM-DD，含）
+    pub to: Option<String>,
+}
+
+// 用量报表查询参数（用于存储层）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct UsageReportListQuery {
+    pub page: Option<i64>,
+    pub size: Option<i64>,
+    pub org_id: Option<i64>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+// 当前用量查询参数
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct CurrentUsageParams {
+    // 组织ID（0 表示默认租户）
+    pub org_id: Option<i64>,
+}
@@ -0,0 +1,31 @@
+use super::entities::{UsageCounter, UsageReport};
+use crate::models::common::PaginationInfo;
+use serde::Serialize;
+use ts_rs::TS;
+
+// 用量报表列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct UsageReportListResponse {
+    pub items: Vec<UsageReport>,
+    pub pagination: PaginationInfo,
+}
+
+// 当前周期用量响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct CurrentUsageResponse {
+    pub org_id: i64,
+    // 当前计数周期（YYYY-MM）
+    pub period: String,
+    pub counters: Vec<UsageCounter>,
+}
+
+// 手动生成报表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/usage.ts")]
+pub struct GenerateUsageReportsResponse {
+    pub report_date: String,
+    // 生成的报表条数（每个组织一条）
+    pub generated: i64,
+}
@@ -9,12 +9,14 @@ pub enum UserRole {
     User,    // 普通用户
     Teacher, // 教师
     Admin,   // 管理员
+    Viewer,  // 家长/导师（经学生授权只读查看其成绩与截止日期）
 }
 
 impl UserRole {
     pub const USER: &'static str = "user";
     pub const TEACHER: &'static str = "teacher";
     pub const ADMIN: &'static str = "admin";
+    pub const VIEWER: &'static str = "viewer";
 
     pub fn admin_roles() -> &'static [&'static UserRole] {
         &[&Self::Admin]
@@ -28,6 +30,9 @@ impl UserRole {
     pub fn all_roles() -> &'static [&'static UserRole] {
         &[&Self::User, &Self::Teacher, &Self::Admin]
     }
+    pub fn viewer_roles() -> &'static [&'static UserRole] {
+        &[&Self::Viewer]
+    }
 }
 
 impl<'de> Deserialize<'de> for UserRole {
@@ -40,8 +45,9 @@ impl<'de> Deserialize<'de> for UserRole {
             UserRole::USER => Ok(UserRole::User),
             UserRole::TEACHER => Ok(UserRole::Teacher),
             UserRole::ADMIN => Ok(UserRole::Admin),
+            UserRole::VIEWER => Ok(UserRole::Viewer),
             _ => Err(serde::de::Error::custom(format!(
-                "无效的用户角色: '{s}'. 支持的角色: user, teacher, admin"
+                "无效的用户角色: '{s}'. 支持的角色: user, teacher, admin, viewer"
             ))),
         }
     }
@@ -53,6 +59,7 @@ impl std::fmt::Display for UserRole {
             UserRole::User => write!(f, "{}", UserRole::USER),
             UserRole::Teacher => write!(f, "{}", UserRole::TEACHER),
             UserRole::Admin => write!(f, "{}", UserRole::ADMIN),
+            UserRole::Viewer => write!(f, "{}", UserRole::VIEWER),
         }
     }
 }
@@ -65,6 +72,7 @@ impl std::str::FromStr for UserRole {
             "user" => Ok(UserRole::User),
             "teacher" => Ok(UserRole::Teacher),
             "admin" => Ok(UserRole::Admin),
+            "viewer" => Ok(UserRole::Viewer),
             _ => Err(format!("Invalid user role: {s}")),
         }
     }
@@ -120,6 +128,82 @@ impl std::str::FromStr for UserStatus {
     }
 }
 
+// 管理权限（可委派给非管理员用户的细粒度管理职责）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub enum AdminPermission {
+    UserManage,     // 用户管理
+    ClassManage,    // 班级管理
+    SystemSettings, // 系统设置
+    AuditView,      // 审计日志查看
+}
+
+impl AdminPermission {
+    pub fn all() -> &'static [AdminPermission] {
+        &[
+            Self::UserManage,
+            Self::ClassManage,
+            Self::SystemSettings,
+            Self::AuditView,
+        ]
+    }
+
+    /// 是否只作用于本组织的数据（组织管理员默认拥有）
+    ///
+    /// 系统设置与审计日志是全平台共享的，不属于任何组织。
+    pub fn is_tenant_scoped(&self) -> bool {
+        matches!(self, Self::UserManage | Self::ClassManage)
+    }
+}
+
+impl std::fmt::Display for AdminPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdminPermission::UserManage => write!(f, "user_manage"),
+            AdminPermission::ClassManage => write!(f, "class_manage"),
+            AdminPermission::SystemSettings => write!(f, "system_settings"),
+            AdminPermission::AuditView => write!(f, "audit_view"),
+        }
+    }
+}
+
+impl std::str::FromStr for AdminPermission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user_manage" => Ok(AdminPermission::UserManage),
+            "class_manage" => Ok(AdminPermission::ClassManage),
+            "system_settings" => Ok(AdminPermission::SystemSettings),
+            "audit_view" => Ok(AdminPermission::AuditView),
+            _ => Err(format!("Invalid admin permission: {s}")),
+        }
+    }
+}
+
+// 批量用户操作类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub enum BulkUserAction {
+    Disable,    // 停用（状态改为 suspended）
+    Enable,     // 启用（状态改为 active）
+    AssignRole, // 修改角色
+    Delete,     // 删除
+}
+
+impl std::fmt::Display for BulkUserAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkUserAction::Disable => write!(f, "disable"),
+            BulkUserAction::Enable => write!(f, "enable"),
+            BulkUserAction::AssignRole => write!(f, "assign_role"),
+            BulkUserAction::Delete => write!(f, "delete"),
+        }
+    }
+}
+
 // 用户实体
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
@@ -134,6 +218,9 @@ pub struct User {
     pub status: UserStatus,
     pub display_name: Option<String>,
     pub avatar_url: Option<String>,
+    // 所属组织，None 表示默认租户
+    #[serde(default)]
+    pub org_id: Option<i64>,
     pub last_login: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -192,3 +279,166 @@ impl User {
         .map_err(|e| format!("生成 token 对失败: {e}"))
     }
 }
+
+// 角色申请状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub enum RoleRequestStatus {
+    Pending,  // 待审核
+    Approved, // 已通过
+    Rejected, // 已拒绝
+}
+
+impl std::fmt::Display for RoleRequestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoleRequestStatus::Pending => write!(f, "pending"),
+            RoleRequestStatus::Approved => write!(f, "approved"),
+            RoleRequestStatus::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+impl std::str::FromStr for RoleRequestStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(RoleRequestStatus::Pending),
+            "approved" => Ok(RoleRequestStatus::Approved),
+            "rejected" => Ok(RoleRequestStatus::Rejected),
+            _ => Err(format!("Invalid role request status: {s}")),
+        }
+    }
+}
+
+// 角色申请附件（申请人上传的证明材料）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct RoleRequestAttachment {
+    pub download_token: String,
+    pub original_name: String,
+    pub file_size: i64,
+}
+
+// 角色申请（普通用户申请升级为教师，由管理员审核）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct RoleRequest {
+    pub id: i64,
+    pub user_id: i64,
+    // 申请人所属组织，None 表示默认租户
+    #[serde(default)]
+    pub org_id: Option<i64>,
+    // 申请人用户名与显示名，便于审核队列展示
+    pub username: String,
+    pub display_name: Option<String>,
+    pub requested_role: UserRole,
+    pub reason: String,
+    pub attachment: Option<RoleRequestAttachment>,
+    pub status: RoleRequestStatus,
+    pub reviewer_id: Option<i64>,
+    pub review_comment: Option<String>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 学习目标（学生为所在班级设定的目标平均得分率）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct StudentGoal {
+    pub id: i64,
+    pub user_id: i64,
+    pub class_id: i64,
+    // 目标平均得分率（0-100）
+    pub target_percent: f64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 学习目标状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub enum GoalStatus {
+    NotStarted,  // 尚无已评分作业
+    Achieved,    // 剩余作业全部 0 分也能达成
+    OnTrack,     // 当前得分率已达到目标
+    Behind,      // 当前得分率低于目标，但仍可达成
+    Unreachable, // 剩余作业全部满分也无法达成
+}
+
+impl std::fmt::Display for GoalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoalStatus::NotStarted => write!(f, "not_started"),
+            GoalStatus::Achieved => write!(f, "achieved"),
+            GoalStatus::OnTrack => write!(f, "on_track"),
+            GoalStatus::Behind => write!(f, "behind"),
+            GoalStatus::Unreachable => write!(f, "unreachable"),
+        }
+    }
+}
+
+// 学习目标的成绩汇总（存储层按班级汇总，进度预测由统计服务计算）
+//
+// 作业按满分加权：每项作业取最新一次已评分的提交，被豁免的作业不计入，
+// 尚未评分的作业计入剩余分值。
+#[derive(Debug, Clone)]
+pub struct GoalStanding {
+    pub class_id: i64,
+    pub class_name: String,
+    pub target_percent: f64,
+    // 已评分作业的得分合计
+    pub earned_score: f64,
+    // 已评分作业的满分合计
+    pub graded_max_score: f64,
+    pub graded_count: i64,
+    // 未评分作业的满分合计
+    pub remaining_max_score: f64,
+    pub remaining_count: i64,
+}
+
+// 学习目标进度
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct GoalProgress {
+    pub class_id: i64,
+    pub class_name: String,
+    pub target_percent: f64,
+    // 当前加权平均得分率，尚无已评分作业时为 None
+    pub current_percent: Option<f64>,
+    pub graded_count: i64,
+    pub remaining_count: i64,
+    pub remaining_max_score: f64,
+    // 剩余作业需要达到的平均得分率，已达成或无剩余作业时为 None
+    pub required_percent: Option<f64>,
+    // 剩余作业全部满分时可达到的得分率
+    pub best_possible_percent: Option<f64>,
+    pub status: GoalStatus,
+}
+
+// 登录历史（成功与失败均记录；新设备、新网络等标记只针对成功登录计算）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct LoginHistory {
+    pub id: i64,
+    pub user_id: i64,
+    pub success: bool,
+    pub ip_address: Option<String>,
+    // 国家/地区代码（ISO 3166-1 alpha-2），仅在可信代理提供时记录
+    pub country: Option<String>,
+    pub user_agent: Option<String>,
+    // 由 User-Agent 解析出的浏览器、操作系统与设备类型
+    pub browser: String,
+    pub os: String,
+    pub device_type: String,
+    // 与近期成功登录相比是否为新设备 / 新网段 / 新国家
+    pub new_device: bool,
+    pub new_ip: bool,
+    pub new_country: bool,
+    // 可疑登录：新国家，或新设备且来自新网段
+    pub suspicious: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
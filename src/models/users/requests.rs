@@ -1,5 +1,7 @@
-use super::entities::{UserRole, UserStatus};
+use super::entities::{AdminPermission, BulkUserAction, RoleRequestStatus, UserRole, UserStatus};
 use crate::models::common::PaginationQuery;
+use crate::models::organizations::entities::TenantScope;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use ts_rs::TS;
 
@@ -16,7 +18,7 @@ pub struct UserListParams {
 }
 
 // 用户创建请求
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
 pub struct CreateUserRequest {
     pub username: String,
@@ -25,6 +27,9 @@ pub struct CreateUserRequest {
     pub role: UserRole,
     pub display_name: Option<String>,
     pub avatar_url: Option<String>,
+    // 所属组织，仅平台管理员可指定；组织管理员创建的用户自动归属其组织
+    #[serde(default)]
+    pub org_id: Option<i64>,
 }
 
 // 用户更新请求
@@ -48,6 +53,10 @@ pub struct UserListQuery {
     pub role: Option<UserRole>,
     pub status: Option<UserStatus>,
     pub search: Option<String>,
+    // 租户范围，由服务层根据当前用户填充
+    #[serde(skip)]
+    #[ts(skip)]
+    pub tenant: TenantScope,
 }
 
 // 用户导出参数
@@ -72,3 +81,120 @@ pub struct ImportTemplateParams {
     #[serde(default = "default_export_format")]
     pub format: String,
 }
+
+// 设置用户管理权限请求（整体替换）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct UpdateAdminPermissionsRequest {
+    pub permissions: Vec<AdminPermission>,
+}
+
+// 批量用户操作的筛选条件（与 user_ids 二选一）
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct BulkUserFilter {
+    pub role: Option<UserRole>,
+    pub status: Option<UserStatus>,
+    pub search: Option<String>,
+    /// 最后登录早于该时间（含从未登录且创建时间早于该时间的用户），ISO 8601 格式
+    pub last_login_before: Option<DateTime<Utc>>,
+}
+
+// 批量用户操作请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct BulkUserRequest {
+    pub action: BulkUserAction,
+    /// 目标角色，仅 assign_role 时必填
+    pub role: Option<UserRole>,
+    /// 按 ID 指定目标用户
+    pub user_ids: Option<Vec<i64>>,
+    /// 按条件筛选目标用户
+    pub filter: Option<BulkUserFilter>,
+}
+
+// 合并重复账号请求（源账号的数据移到目标账号，源账号随后被停用）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct MergeUsersRequest {
+    pub source_user_id: i64,
+    pub target_user_id: i64,
+}
+
+// 提交角色申请请求（普通用户申请成为教师）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct CreateRoleRequestRequest {
+    /// 申请说明（任教学校、课程等证明信息）
+    pub reason: String,
+    /// 证明材料附件的文件 token（需先通过文件上传接口上传）
+    pub attachment: Option<String>,
+}
+
+// 角色申请列表查询参数（来自HTTP请求）
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct RoleRequestListParams {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub pagination: PaginationQuery,
+    pub status: Option<RoleRequestStatus>,
+}
+
+// 角色申请列表查询（存储层使用）
+#[derive(Debug, Clone)]
+pub struct RoleRequestListQuery {
+    pub page: Option<i64>,
+    pub size: Option<i64>,
+    pub status: Option<RoleRequestStatus>,
+    /// 仅查询指定申请人的记录
+    pub user_id: Option<i64>,
+    pub tenant: TenantScope,
+}
+
+// 审核角色申请请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct ReviewRoleRequestRequest {
+    /// true 为通过，false 为拒绝
+    pub approve: bool,
+    /// 审核意见，拒绝时建议填写
+    pub comment: Option<String>,
+}
+
+// 设置学习目标请求
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct SetStudentGoalRequest {
+    /// 目标平均得分率（0-100）
+    pub target_percent: f64,
+}
+
+// 登录历史查询参数（管理员可按用户与标记筛选，个人查询时由服务层固定为本人）
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct LoginHistoryQuery {
+    pub page: Option<i64>,
+    pub size: Option<i64>,
+    pub user_id: Option<i64>,
+    pub success: Option<bool>,
+    /// 仅查询可疑登录
+    pub suspicious: Option<bool>,
+    // 租户范围，由服务层根据当前用户填充
+    #[serde(skip)]
+    #[ts(skip)]
+    pub tenant: TenantScope,
+}
+
+// 登录历史写入参数（认证服务使用，新设备等标记由存储层计算）
+#[derive(Debug, Clone)]
+pub struct CreateLoginHistory {
+    pub user_id: i64,
+    pub success: bool,
+    pub ip_address: Option<String>,
+    pub country: Option<String>,
+    pub user_agent: Option<String>,
+    pub browser: String,
+    pub os: String,
+    pub device_type: String,
+}
@@ -1,4 +1,4 @@
-use super::entities::User;
+use super::entities::{AdminPermission, BulkUserAction, LoginHistory, RoleRequest, User};
 use crate::models::common::PaginationInfo;
 use serde::Serialize;
 use ts_rs::TS;
@@ -18,6 +18,14 @@ pub struct UserListResponse {
     pub pagination: PaginationInfo,
 }
 
+// 登录历史列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct LoginHistoryListResponse {
+    pub items: Vec<LoginHistory>,
+    pub pagination: PaginationInfo,
+}
+
 // 导入行错误
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
@@ -57,3 +65,66 @@ pub struct UserStatsResponse {
     /// 服务器时间（ISO 8601）
     pub server_time: String,
 }
+
+// 用户管理权限响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct AdminPermissionsResponse {
+    pub user_id: i64,
+    /// 是否为超级管理员（拥有全部权限）
+    pub is_super_admin: bool,
+    pub permissions: Vec<AdminPermission>,
+}
+
+// 批量操作单项结果
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct BulkUserItemResult {
+    pub user_id: i64,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+// 批量用户操作响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct BulkUserResponse {
+    pub action: BulkUserAction,
+    pub total: usize,
+    pub success: usize,
+    pub failed: usize,
+    pub results: Vec<BulkUserItemResult>,
+}
+
+// 合并重复账号响应（各项为从源账号移到目标账号的记录数）
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct MergeUsersResponse {
+    pub source_user_id: i64,
+    pub target_user_id: i64,
+    /// 转移的班级成员关系
+    pub class_memberships: u64,
+    /// 两个账号同在一个班级时合并的成员关系（保留较高的班级角色和较早的加入时间）
+    pub merged_memberships: u64,
+    /// 转移的班级（源账号为班主任）
+    pub owned_classes: u64,
+    pub submissions: u64,
+    /// 两个账号都有提交、按提交时间重新编号版本的作业数
+    pub renumbered_homeworks: u64,
+    /// 转移的评分（源账号作为评分人）
+    pub grades: u64,
+    pub files: u64,
+    pub notifications: u64,
+    // 成员关系有变化的班级，用于清除成员缓存
+    #[serde(skip)]
+    #[ts(skip)]
+    pub class_ids: Vec<i64>,
+}
+
+// 角色申请列表响应
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/user.ts")]
+pub struct RoleRequestListResponse {
+    pub items: Vec<RoleRequest>,
+    pub pagination: PaginationInfo,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 家长/导师查看授权
+///
+/// 学生生成邀请后 `viewer_id` 为空；家长/导师凭令牌接受邀请后授权生效，
+/// 学生可随时撤销。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerGrant {
+    pub id: i64,
+    pub student_id: i64,
+    pub viewer_id: Option<i64>,
+    /// 邀请令牌
+    pub token: String,
+    /// 学生填写的备注
+    pub note: Option<String>,
+    /// 邀请过期时间
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub accepted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 授权状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub enum ViewerGrantStatus {
+    /// 邀请待接受
+    Pending,
+    /// 授权生效中
+    Active,
+    /// 邀请未被接受且已过期
+    Expired,
+    /// 已撤销
+    Revoked,
+}
+
+impl ViewerGrant {
+    pub fn status(&self, now: chrono::DateTime<chrono::Utc>) -> ViewerGrantStatus {
+        if self.revoked_at.is_some() {
+            ViewerGrantStatus::Revoked
+        } else if self.viewer_id.is_some() {
+            ViewerGrantStatus::Active
+        } else if self.expires_at > now {
+            ViewerGrantStatus::Pending
+        } else {
+            ViewerGrantStatus::Expired
+        }
+    }
+}
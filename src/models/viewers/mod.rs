@@ -0,0 +1,8 @@
+// 家长/导师查看授权实体定义
+pub mod entities;
+
+// 家长/导师查看授权请求模型
+pub mod requests;
+
+// 家长/导师查看授权响应模型
+pub mod responses;
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use ts_rs::TS;
+
+/// 创建查看邀请请求
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct CreateViewerInvitationRequest {
+    /// 备注（如“妈妈”），便于学生区分多个授权
+    pub note: Option<String>,
+    /// 邀请有效天数，默认 7 天
+    pub expires_in_days: Option<i64>,
+}
+
+/// 接受查看邀请请求（已有家长/导师账号时使用）
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct AcceptViewerInvitationRequest {
+    pub token: String,
+}
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::entities::{ViewerGrant, ViewerGrantStatus};
+use crate::models::auth::responses::{DashboardClassProgress, DashboardDeadline, DashboardGrade};
+use crate::models::users::entities::User;
+
+/// 授权双方的基本信息
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerUserInfo {
+    pub id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+}
+
+impl From<User> for ViewerUserInfo {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+        }
+    }
+}
+
+/// 学生查看的授权条目
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerGrantItem {
+    #[serde(flatten)]
+    pub grant: ViewerGrant,
+    pub status: ViewerGrantStatus,
+    /// 已接受邀请的家长/导师
+    pub viewer: Option<ViewerUserInfo>,
+}
+
+/// 授权列表响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerGrantListResponse {
+    pub items: Vec<ViewerGrantItem>,
+}
+
+/// 家长/导师可查看的学生
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerStudentItem {
+    pub grant_id: i64,
+    pub student: ViewerUserInfo,
+    pub granted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 可查看学生列表响应
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerStudentListResponse {
+    pub items: Vec<ViewerStudentItem>,
+}
+
+/// 学生每周汇总（家长/导师只读视图）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/generated/viewer.ts")]
+pub struct ViewerStudentSummary {
+    pub student: ViewerUserInfo,
+    /// 统计区间：过去 7 天的评分、未来 7 天的截止日期
+    pub period_start: chrono::DateTime<chrono::Utc>,
+    pub period_end: chrono::DateTime<chrono::Utc>,
+    /// 未来 7 天内截止的作业（按截止时间升序）
+    pub upcoming_deadlines: Vec<DashboardDeadline>,
+    /// 过去 7 天内的评分（按评分时间降序）
+    pub recent_grades: Vec<DashboardGrade>,
+    pub class_progress: Vec<DashboardClassProgress>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,40 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::analytics::requests::AnalyticsRangeParams;
+use crate::models::users::entities::UserRole;
+use crate::services::AnalyticsService;
+
+// 懒加载的全局 AnalyticsService 实例
+static ANALYTICS_SERVICE: Lazy<AnalyticsService> = Lazy::new(AnalyticsService::new_lazy);
+
+// HTTP处理程序
+pub async fn compare_classes(
+    req: HttpRequest,
+    query: web::Query<AnalyticsRangeParams>,
+) -> ActixResult<HttpResponse> {
+    ANALYTICS_SERVICE
+        .compare_classes(&req, query.into_inner())
+        .await
+}
+
+pub async fn grading_latency(
+    req: HttpRequest,
+    query: web::Query<AnalyticsRangeParams>,
+) -> ActixResult<HttpResponse> {
+    ANALYTICS_SERVICE
+        .grading_latency(&req, query.into_inner())
+        .await
+}
+
+// 配置路由
+pub fn configure_analytics_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/admin/analytics")
+            .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+            .wrap(middlewares::RequireJWT)
+            .route("/classes", web::get().to(compare_classes))
+            .route("/grading", web::get().to(grading_latency)),
+    );
+}
@@ -2,9 +2,14 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use once_cell::sync::Lazy;
 
 use crate::middlewares::{self, RateLimit};
-use crate::models::auth::requests::{LoginRequest, UpdateProfileRequest};
-use crate::models::users::requests::CreateUserRequest;
+use crate::models::auth::requests::{
+    LoginRequest, RegisterRequest, TranscriptParams, UpdateProfileRequest,
+};
+use crate::models::files::requests::MyFileListQuery;
+use crate::models::users::requests::LoginHistoryQuery;
 use crate::services::AuthService;
+use crate::services::auth::captcha;
+use crate::utils::SafeFileToken;
 
 // 懒加载的全局 AuthService 实例
 static AUTH_SERVICE: Lazy<AuthService> = Lazy::new(AuthService::new_lazy);
@@ -22,7 +27,7 @@ pub async fn refresh_token(request: HttpRequest) -> ActixResult<HttpResponse> {
 
 pub async fn register(
     req: HttpRequest,
-    user_data: web::Json<CreateUserRequest>,
+    user_data: web::Json<RegisterRequest>,
 ) -> ActixResult<HttpResponse> {
     AUTH_SERVICE.register(user_data.into_inner(), &req).await
 }
@@ -44,6 +49,42 @@ pub async fn update_profile(
         .await
 }
 
+pub async fn export_transcript(
+    req: HttpRequest,
+    query: web::Query<TranscriptParams>,
+) -> ActixResult<HttpResponse> {
+    AUTH_SERVICE
+        .export_transcript(query.into_inner(), &req)
+        .await
+}
+
+pub async fn get_dashboard(req: HttpRequest) -> ActixResult<HttpResponse> {
+    AUTH_SERVICE.get_dashboard(&req).await
+}
+
+pub async fn get_login_history(
+    req: HttpRequest,
+    query: web::Query<LoginHistoryQuery>,
+) -> ActixResult<HttpResponse> {
+    AUTH_SERVICE
+        .get_my_login_history(query.into_inner(), &req)
+        .await
+}
+
+pub async fn list_my_files(
+    req: HttpRequest,
+    query: web::Query<MyFileListQuery>,
+) -> ActixResult<HttpResponse> {
+    AUTH_SERVICE.list_my_files(query.into_inner(), &req).await
+}
+
+pub async fn delete_my_file(
+    req: HttpRequest,
+    file_token: SafeFileToken,
+) -> ActixResult<HttpResponse> {
+    AUTH_SERVICE.delete_my_file(file_token.0, &req).await
+}
+
 pub async fn logout() -> ActixResult<HttpResponse> {
     AUTH_SERVICE.logout().await
 }
@@ -51,7 +92,7 @@ pub async fn logout() -> ActixResult<HttpResponse> {
 // 配置路由
 pub fn configure_auth_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/auth")
+        web::scope("/auth")
             // 登录端点：5次/分钟/IP
             .service(
                 web::resource("/login")
@@ -70,6 +111,8 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig) {
                     .wrap(RateLimit::refresh_token())
                     .route(web::post().to(refresh_token)),
             )
+            // 人机验证配置：公开，登录/注册前获取
+            .route("/captcha", web::get().to(captcha::get_captcha_config))
             // 登出端点：不需要 JWT 验证
             .route("/logout", web::post().to(logout))
             .service(
@@ -77,7 +120,12 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig) {
                     .wrap(middlewares::RequireJWT)
                     .route("/verify-token", web::get().to(verify_token))
                     .route("/me", web::get().to(get_user))
-                    .route("/me", web::put().to(update_profile)),
+                    .route("/me", web::put().to(update_profile))
+                    .route("/me/transcript", web::get().to(export_transcript))
+                    .route("/me/dashboard", web::get().to(get_dashboard))
+                    .route("/me/login-history", web::get().to(get_login_history))
+                    .route("/me/files", web::get().to(list_my_files))
+                    .route("/me/files/{file_token}", web::delete().to(delete_my_file)),
             ),
     );
 }
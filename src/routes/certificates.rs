@@ -0,0 +1,73 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares::{self, RateLimit};
+use crate::models::certificates::requests::UpdateCertificateSettingsRequest;
+use crate::services::CertificateService;
+use crate::utils::{SafeCertificateCode, SafeClassIdI64};
+
+// 懒加载的全局 CERTIFICATE_SERVICE 实例
+static CERTIFICATE_SERVICE: Lazy<CertificateService> = Lazy::new(CertificateService::new_lazy);
+
+pub async fn get_certificate_settings(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CERTIFICATE_SERVICE.get_settings(&req, path.0).await
+}
+
+pub async fn update_certificate_settings(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    body: web::Json<UpdateCertificateSettingsRequest>,
+) -> ActixResult<HttpResponse> {
+    CERTIFICATE_SERVICE
+        .update_settings(&req, path.0, body.into_inner())
+        .await
+}
+
+pub async fn get_my_certificate_status(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CERTIFICATE_SERVICE.get_my_status(&req, path.0).await
+}
+
+pub async fn download_my_certificate(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CERTIFICATE_SERVICE
+        .download_my_certificate(&req, path.0)
+        .await
+}
+
+pub async fn verify_certificate(
+    req: HttpRequest,
+    code: SafeCertificateCode,
+) -> ActixResult<HttpResponse> {
+    CERTIFICATE_SERVICE.verify_certificate(&req, &code.0).await
+}
+
+// 配置路由
+pub fn configure_certificates_routes(cfg: &mut web::ServiceConfig) {
+    // 班级证书 - 设置仅班级教师/管理员，状态与下载仅班级学生（业务层校验）
+    cfg.service(
+        web::scope("/classes/{class_id}/certificate")
+            .wrap(middlewares::RequireJWT)
+            .service(
+                web::resource("/settings")
+                    .route(web::get().to(get_certificate_settings))
+                    .route(web::put().to(update_certificate_settings)),
+            )
+            .route("/status", web::get().to(get_my_certificate_status))
+            .route("/download", web::get().to(download_my_certificate)),
+    );
+
+    // 公开证书验证 - 无需登录，按 IP 限流
+    cfg.service(
+        web::scope("/certificates/verify/{code}")
+            .wrap(RateLimit::shared_link())
+            .route("", web::get().to(verify_certificate)),
+    );
+}
@@ -4,7 +4,8 @@ use once_cell::sync::Lazy;
 use crate::middlewares;
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::class_users::requests::{
-    ClassUserListParams, JoinClassRequest, UpdateClassUserRequest,
+    BatchClassUsersRequest, ClassUserListParams, JoinClassRequest, UpdateClassRoleLabelsRequest,
+    UpdateClassUserRequest,
 };
 use crate::models::users::entities::UserRole;
 use crate::services::ClassUserService;
@@ -75,10 +76,39 @@ pub async fn delete_class_user(
         .await
 }
 
+pub async fn batch_class_users(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    batch_data: web::Json<BatchClassUsersRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_STUDENT_SERVICE
+        .batch_class_users(&req, path.0, batch_data.into_inner())
+        .await
+}
+
+pub async fn get_class_role_labels(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_STUDENT_SERVICE
+        .get_class_role_labels(&req, path.0)
+        .await
+}
+
+pub async fn update_class_role_labels(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    update_data: web::Json<UpdateClassRoleLabelsRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_STUDENT_SERVICE
+        .update_class_role_labels(&req, path.0, update_data.into_inner())
+        .await
+}
+
 // 配置路由
 pub fn configure_class_users_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/classes/{class_id}/students")
+        web::scope("/classes/{class_id}/students")
             .wrap(middlewares::RequireJWT)
             .service(
                 web::resource("")
@@ -124,5 +154,27 @@ pub fn configure_class_users_routes(cfg: &mut web::ServiceConfig) {
                             )),
                     ),
             ),
+    )
+    .service(
+        web::resource("/classes/{class_id}/users/batch")
+            .wrap(middlewares::RequireJWT)
+            .route(
+                web::post()
+                    .to(batch_class_users)
+                    // 批量修改角色或移出成员 - 仅班级教师权限
+                    .wrap(middlewares::RequireClassRole::new_any(
+                        ClassUserRole::class_teacher_roles(),
+                    )),
+            ),
+    );
+}
+
+// 班级角色名称 - 班级成员可查看，教师/管理员可修改（业务层校验）
+pub fn configure_class_role_labels_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/classes/{class_id}/role-labels")
+            .wrap(middlewares::RequireJWT)
+            .route(web::get().to(get_class_role_labels))
+            .route(web::put().to(update_class_role_labels)),
     );
 }
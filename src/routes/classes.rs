@@ -1,8 +1,16 @@
+use actix_multipart::Multipart;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use once_cell::sync::Lazy;
 
 use crate::middlewares::{self, RateLimit};
-use crate::models::classes::requests::{ClassQueryParams, CreateClassRequest, UpdateClassRequest};
+use crate::models::classes::requests::{
+    ClassActivityReportParams, ClassBundleExportParams, ClassBundleImportParams,
+    ClassErasureRequest, ClassFeedQuery, ClassQueryParams, ClassRosterExportParams,
+    ClassStatsHistoryParams, ClassWorkloadParams, CreateClassApiTokenRequest,
+    CreateClassImChannelRequest, CreateClassRequest, CreateClassScheduledExportRequest,
+    JoinClassByShortCodeRequest, LeaderboardOptInRequest, UpdateClassImChannelRequest,
+    UpdateClassLeaderboardRequest, UpdateClassRequest, UpdateClassScheduledExportRequest,
+};
 use crate::models::users::entities::UserRole;
 use crate::services::ClassService;
 use crate::utils::{SafeClassCode, SafeClassIdI64};
@@ -31,6 +39,22 @@ pub async fn get_class_by_code(req: HttpRequest, code: SafeClassCode) -> ActixRe
     CLASS_SERVICE.get_class_by_code(&req, code.0).await
 }
 
+pub async fn join_by_short_code(
+    req: HttpRequest,
+    join_data: web::Json<JoinClassByShortCodeRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .join_by_short_code(&req, join_data.into_inner())
+        .await
+}
+
+pub async fn get_short_code(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE.get_short_code(&req, class_id.0).await
+}
+
 pub async fn get_class(req: HttpRequest, class_id: SafeClassIdI64) -> ActixResult<HttpResponse> {
     CLASS_SERVICE.get_class(&req, class_id.0).await
 }
@@ -56,10 +80,258 @@ pub async fn export_class_report(
     CLASS_SERVICE.export_class_report(&req, class_id.0).await
 }
 
+pub async fn export_class_bundle(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    query: web::Query<ClassBundleExportParams>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .export_class_bundle(&req, class_id.0, query.into_inner())
+        .await
+}
+
+pub async fn export_roster(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    query: web::Query<ClassRosterExportParams>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .export_roster(&req, class_id.0, query.into_inner())
+        .await
+}
+
+pub async fn import_class_bundle(
+    req: HttpRequest,
+    query: web::Query<ClassBundleImportParams>,
+    payload: Multipart,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .import_class_bundle(&req, query.into_inner(), payload)
+        .await
+}
+
+pub async fn get_activity_report(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    query: web::Query<ClassActivityReportParams>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .get_activity_report(&req, class_id.0, query.into_inner())
+        .await
+}
+
+pub async fn get_stats_history(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    query: web::Query<ClassStatsHistoryParams>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .get_stats_history(&req, class_id.0, query.into_inner())
+        .await
+}
+
+pub async fn get_workload(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    query: web::Query<ClassWorkloadParams>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .get_workload(&req, class_id.0, query.into_inner())
+        .await
+}
+
+pub async fn get_workload_calibration(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .get_workload_calibration(&req, class_id.0)
+        .await
+}
+
+pub async fn get_leaderboard(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE.get_leaderboard(&req, class_id.0).await
+}
+
+pub async fn update_leaderboard_settings(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    body: web::Json<UpdateClassLeaderboardRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .update_leaderboard_settings(&req, class_id.0, body.into_inner())
+        .await
+}
+
+pub async fn set_leaderboard_opt_in(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    body: web::Json<LeaderboardOptInRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .set_leaderboard_opt_in(&req, class_id.0, body.into_inner())
+        .await
+}
+
+pub async fn preview_erasure(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, user_id) = path.into_inner();
+    CLASS_SERVICE.preview_erasure(&req, class_id, user_id).await
+}
+
+pub async fn erase_student_data(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    body: web::Json<ClassErasureRequest>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, user_id) = path.into_inner();
+    CLASS_SERVICE
+        .erase_student_data(&req, class_id, user_id, body.into_inner())
+        .await
+}
+
+pub async fn get_feed(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    query: web::Query<ClassFeedQuery>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .get_feed(&req, class_id.0, query.into_inner())
+        .await
+}
+
+pub async fn list_im_channels(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE.list_im_channels(&req, class_id.0).await
+}
+
+pub async fn create_im_channel(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    data: web::Json<CreateClassImChannelRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .create_im_channel(&req, class_id.0, data.into_inner())
+        .await
+}
+
+pub async fn update_im_channel(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    data: web::Json<UpdateClassImChannelRequest>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, channel_id) = path.into_inner();
+    CLASS_SERVICE
+        .update_im_channel(&req, class_id, channel_id, data.into_inner())
+        .await
+}
+
+pub async fn delete_im_channel(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, channel_id) = path.into_inner();
+    CLASS_SERVICE
+        .delete_im_channel(&req, class_id, channel_id)
+        .await
+}
+
+pub async fn test_im_channel(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, channel_id) = path.into_inner();
+    CLASS_SERVICE
+        .test_im_channel(&req, class_id, channel_id)
+        .await
+}
+
+pub async fn list_scheduled_exports(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE.list_scheduled_exports(&req, class_id.0).await
+}
+
+pub async fn create_scheduled_export(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    data: web::Json<CreateClassScheduledExportRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .create_scheduled_export(&req, class_id.0, data.into_inner())
+        .await
+}
+
+pub async fn update_scheduled_export(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    data: web::Json<UpdateClassScheduledExportRequest>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, export_id) = path.into_inner();
+    CLASS_SERVICE
+        .update_scheduled_export(&req, class_id, export_id, data.into_inner())
+        .await
+}
+
+pub async fn delete_scheduled_export(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, export_id) = path.into_inner();
+    CLASS_SERVICE
+        .delete_scheduled_export(&req, class_id, export_id)
+        .await
+}
+
+pub async fn list_api_tokens(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE.list_api_tokens(&req, class_id.0).await
+}
+
+pub async fn create_api_token(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    data: web::Json<CreateClassApiTokenRequest>,
+) -> ActixResult<HttpResponse> {
+    CLASS_SERVICE
+        .create_api_token(&req, class_id.0, data.into_inner())
+        .await
+}
+
+pub async fn revoke_api_token(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, token_id) = path.into_inner();
+    CLASS_SERVICE
+        .revoke_api_token(&req, class_id, token_id)
+        .await
+}
+
+pub async fn list_api_token_logs(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, token_id) = path.into_inner();
+    CLASS_SERVICE
+        .list_api_token_logs(&req, class_id, token_id)
+        .await
+}
+
 // 配置路由
 pub fn configure_classes_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/classes")
+        web::scope("/classes")
             .wrap(middlewares::RequireJWT)
             .service(
                 // 用户查询自己的班级列表，管理员可以查询所有班级
@@ -81,6 +353,25 @@ pub fn configure_classes_routes(cfg: &mut web::ServiceConfig) {
                             .wrap(middlewares::RequireRole::new(&UserRole::User)),
                     ),
             )
+            .service(
+                web::resource("/join/short-code")
+                    // 短码仅 6 位数字，严格限制：5次/分钟/IP
+                    .wrap(RateLimit::short_code())
+                    .route(
+                        web::post()
+                            .to(join_by_short_code)
+                            // 学生使用课堂短码加入班级
+                            .wrap(middlewares::RequireRole::new_any(UserRole::user_roles())),
+                    ),
+            )
+            .service(
+                web::resource("/import-bundle").route(
+                    web::post()
+                        .to(import_class_bundle)
+                        // 权限规则与创建班级相同
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
             .service(
                 web::resource("/{class_id}")
                     .route(
@@ -109,6 +400,147 @@ pub fn configure_classes_routes(cfg: &mut web::ServiceConfig) {
                         // 教师、课代表、管理员可以导出报表（权限在 service 层进一步验证）
                         .wrap(middlewares::RequireRole::new_any(UserRole::all_roles())),
                 ),
+            )
+            .service(
+                web::resource("/{class_id}/export-bundle").route(
+                    web::get()
+                        .to(export_class_bundle)
+                        // 班级教师、管理员可以导出（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            .service(
+                web::resource("/{class_id}/roster/export").route(
+                    web::get()
+                        .to(export_roster)
+                        // 班级教师、管理员可以导出（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            .service(
+                web::resource("/{class_id}/activity-report").route(
+                    web::get()
+                        .to(get_activity_report)
+                        // 班级教师、管理员可以查看（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            .service(
+                web::resource("/{class_id}/stats-history").route(
+                    web::get()
+                        .to(get_stats_history)
+                        // 班级教师、管理员可以查看（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            .service(
+                web::resource("/{class_id}/workload").route(
+                    web::get()
+                        .to(get_workload)
+                        // 班级教师、管理员可以查看（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            .service(
+                web::resource("/{class_id}/workload-calibration").route(
+                    web::get()
+                        .to(get_workload_calibration)
+                        // 班级教师、管理员可以查看（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            // 班级排行榜 - 班级成员、管理员查看，班级教师设置（权限在 service 层进一步验证）
+            .service(
+                web::resource("/{class_id}/leaderboard")
+                    .route(
+                        web::get()
+                            .to(get_leaderboard)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::all_roles())),
+                    )
+                    .route(
+                        web::put()
+                            .to(update_leaderboard_settings)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                    ),
+            )
+            .service(
+                web::resource("/{class_id}/leaderboard/opt-in").route(
+                    web::put()
+                        .to(set_leaderboard_opt_in)
+                        // 班级学生（含课代表）设置本人署名（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::user_roles())),
+                ),
+            )
+            // 学生数据擦除 - 班级教师、管理员（权限在 service 层进一步验证）
+            .service(
+                web::resource("/{class_id}/students/{user_id}/erasure")
+                    .route(web::get().to(preview_erasure))
+                    .route(web::post().to(erase_student_data))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{class_id}/feed").route(
+                    web::get()
+                        .to(get_feed)
+                        // 班级成员、管理员可以查看（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::all_roles())),
+                ),
+            )
+            .service(
+                web::resource("/{class_id}/short-code").route(
+                    web::post()
+                        .to(get_short_code)
+                        // 班级教师、管理员生成课堂短码（权限在 service 层进一步验证）
+                        .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                ),
+            )
+            // IM 通知渠道 - 班级教师、管理员（权限在 service 层进一步验证）
+            .service(
+                web::resource("/{class_id}/im-channels")
+                    .route(web::get().to(list_im_channels))
+                    .route(web::post().to(create_im_channel))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{class_id}/im-channels/{channel_id}")
+                    .route(web::put().to(update_im_channel))
+                    .route(web::delete().to(delete_im_channel))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{class_id}/im-channels/{channel_id}/test")
+                    .route(web::post().to(test_im_channel))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 定时导出 - 班级教师、管理员（权限在 service 层进一步验证）
+            .service(
+                web::resource("/{class_id}/scheduled-exports")
+                    .route(web::get().to(list_scheduled_exports))
+                    .route(web::post().to(create_scheduled_export))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{class_id}/scheduled-exports/{export_id}")
+                    .route(web::put().to(update_scheduled_export))
+                    .route(web::delete().to(delete_scheduled_export))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 班级 API 令牌 - 班级教师、管理员（权限在 service 层进一步验证）
+            .service(
+                web::resource("/{class_id}/api-tokens")
+                    .route(web::get().to(list_api_tokens))
+                    .route(web::post().to(create_api_token))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{class_id}/api-tokens/{token_id}")
+                    .route(web::delete().to(revoke_api_token))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{class_id}/api-tokens/{token_id}/logs")
+                    .route(web::get().to(list_api_token_logs))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
             ),
     );
 }
@@ -0,0 +1,36 @@
+use actix_web::{HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::config::AppConfig;
+use crate::models::dev::requests::DevFixtureQuery;
+use crate::services::DevService;
+
+// 懒加载的全局 DEV_SERVICE 实例
+static DEV_SERVICE: Lazy<DevService> = Lazy::new(DevService::new_lazy);
+
+// 列出示例接口
+pub async fn list_fixtures() -> ActixResult<HttpResponse> {
+    DEV_SERVICE.list_fixtures().await
+}
+
+// 获取示例响应
+pub async fn get_fixture(
+    endpoint: web::Path<String>,
+    query: web::Query<DevFixtureQuery>,
+) -> ActixResult<HttpResponse> {
+    DEV_SERVICE.get_fixture(&endpoint, query.into_inner()).await
+}
+
+// 配置路由（仅开发环境）
+pub fn configure_dev_routes(cfg: &mut web::ServiceConfig) {
+    if !AppConfig::get().is_development() {
+        return;
+    }
+
+    // 示例数据只包含固定的虚构内容，无需登录
+    cfg.service(
+        web::scope("/dev/fixtures")
+            .route("", web::get().to(list_fixtures))
+            .route("/{endpoint}", web::get().to(get_fixture)),
+    );
+}
@@ -2,8 +2,12 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, middleware, we
 use once_cell::sync::Lazy;
 
 use crate::middlewares::{self, RateLimit};
+use crate::models::files::requests::{
+    FilePreviewQuery, RetentionReportQuery, UpdateClassRetentionRequest,
+};
+use crate::models::users::entities::UserRole;
 use crate::services::FileService;
-use crate::utils::SafeFileToken;
+use crate::utils::{SafeClassIdI64, SafeFileToken, SafeIDI64};
 
 // 懒加载的全局 FileService 实例
 static FILE_SERVICE: Lazy<FileService> = Lazy::new(FileService::new_lazy);
@@ -21,10 +25,84 @@ pub async fn handle_download(
 ) -> ActixResult<HttpResponse> {
     FILE_SERVICE.handle_download(&request, file_token.0).await
 }
+
+pub async fn handle_preview(
+    request: HttpRequest,
+    file_token: SafeFileToken,
+    query: web::Query<FilePreviewQuery>,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE
+        .handle_preview(&request, file_token.0, query.into_inner())
+        .await
+}
+
+pub async fn get_code_file(
+    request: HttpRequest,
+    file_token: SafeFileToken,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE.get_code_file(&request, file_token.0).await
+}
+
+pub async fn list_quarantined_files(request: HttpRequest) -> ActixResult<HttpResponse> {
+    FILE_SERVICE.list_quarantined_files(&request).await
+}
+
+pub async fn approve_quarantined_file(
+    request: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE
+        .approve_quarantined_file(&request, path.0)
+        .await
+}
+
+pub async fn delete_quarantined_file(
+    request: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE.delete_quarantined_file(&request, path.0).await
+}
+
+pub async fn get_retention_report(
+    request: HttpRequest,
+    query: web::Query<RetentionReportQuery>,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE
+        .get_retention_report(&request, query.into_inner())
+        .await
+}
+
+pub async fn get_class_retention(
+    request: HttpRequest,
+    path: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE.get_class_retention(&request, path.0).await
+}
+
+pub async fn update_class_retention(
+    request: HttpRequest,
+    path: SafeClassIdI64,
+    body: web::Json<UpdateClassRetentionRequest>,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE
+        .update_class_retention(&request, path.0, body.into_inner())
+        .await
+}
+
+pub async fn get_class_retention_report(
+    request: HttpRequest,
+    path: SafeClassIdI64,
+    query: web::Query<RetentionReportQuery>,
+) -> ActixResult<HttpResponse> {
+    FILE_SERVICE
+        .get_class_retention_report(&request, path.0, query.into_inner())
+        .await
+}
+
 // 配置路由
 pub fn configure_file_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/files")
+        web::scope("/files")
             .wrap(middlewares::RequireJWT)
             .wrap(middleware::Compress::default())
             // 文件上传：10次/分钟/用户
@@ -33,6 +111,36 @@ pub fn configure_file_routes(cfg: &mut web::ServiceConfig) {
                     .wrap(RateLimit::file_upload())
                     .route(web::post().to(handle_upload)),
             )
-            .route("/download/{file_token}", web::get().to(handle_download)),
+            .route("/download/{file_token}", web::get().to(handle_download))
+            .route("/preview/{file_token}", web::get().to(handle_preview))
+            .route("/code/{file_token}", web::get().to(get_code_file))
+            // 即将按保留策略删除的文件：仅管理员
+            .service(
+                web::resource("/retention/upcoming")
+                    .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+                    .route(web::get().to(get_retention_report)),
+            )
+            // 隔离文件审核队列：仅管理员
+            .service(
+                web::scope("/quarantine")
+                    .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+                    .route("", web::get().to(list_quarantined_files))
+                    .route("/{id}", web::delete().to(delete_quarantined_file))
+                    .route("/{id}/approve", web::post().to(approve_quarantined_file)),
+            ),
+    );
+}
+
+// 班级文件保留策略 - 仅班级教师/管理员（业务层校验）
+pub fn configure_class_retention_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/classes/{class_id}/retention")
+            .wrap(middlewares::RequireJWT)
+            .service(
+                web::resource("")
+                    .route(web::get().to(get_class_retention))
+                    .route(web::put().to(update_class_retention)),
+            )
+            .route("/upcoming", web::get().to(get_class_retention_report)),
     );
 }
@@ -1,16 +1,23 @@
 //! 前端静态资源路由
 //!
 //! 使用 rust-embed 嵌入前端构建产物，支持：
-//! - SPA fallback（未找到的路由返回 index.html）
-//! - 自定义前端目录覆盖（开发用）
+//! - SPA history 模式 fallback（未找到的页面路由返回 index.html，
+//!   缺失的静态文件与 API 路径返回 404）
+//! - 文件名带内容哈希的资源长期缓存（immutable），其余文件按 ETag 协商缓存
+//! - 预压缩文件（`.br` / `.gz`）按 `Accept-Encoding` 优先返回
+//! - 可配置的前端目录覆盖嵌入资源（配置 `frontend.root_dir`）
 //! - PWA 资源服务
 //! - %BASE_PATH% 占位符替换
 
+use actix_web::http::header::{ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, ETAG, VARY};
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use rust_embed::Embed;
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path};
 
 use crate::config::AppConfig;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::etag::if_none_match;
 
 /// 嵌入前端静态资源
 /// 编译时从 frontend/dist/ 目录读取文件
@@ -54,28 +61,80 @@ fn get_mime_type(path: &str) -> &'static str {
     }
 }
 
-/// 检查是否应该设置缓存
-fn should_cache(path: &str) -> bool {
-    let ext = Path::new(path)
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("");
+/// 哈希段最短长度（Vite/Rollup 默认生成 8 位）
+const MIN_HASH_LENGTH: usize = 8;
+
+/// 预压缩文件：(Accept-Encoding 标识, 文件后缀)
+const PRECOMPRESSED: &[(&str, &str)] = &[("br", ".br"), ("gzip", ".gz")];
+
+/// 静态文件内容与内容哈希
+struct StaticFile {
+    data: Vec<u8>,
+    hash: [u8; 32],
+}
+
+impl StaticFile {
+    /// ETag（内容哈希前 16 字节），预压缩版本附加编码后缀
+    fn etag(&self, encoding: Option<&str>) -> String {
+        let hex: String = self.hash[..16].iter().map(|b| format!("{b:02x}")).collect();
+        match encoding {
+            Some(encoding) => format!("\"{hex}-{encoding}\""),
+            None => format!("\"{hex}\""),
+        }
+    }
+}
+
+/// 文件名是否带内容哈希（如 `index-3f2a9c1b.js`、`logo.a1b2c3d4.svg`）
+fn is_hashed_asset(path: &str) -> bool {
+    let Some(stem) = Path::new(path).file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    stem.rsplit(['-', '.']).next().is_some_and(|segment| {
+        stem.len() > segment.len()
+            && segment.len() >= MIN_HASH_LENGTH
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && segment.chars().any(|c| c.is_ascii_digit())
+    })
+}
 
-    // 静态资源（带 hash 的）可以长期缓存
-    matches!(
-        ext,
-        "js" | "css"
-            | "woff"
-            | "woff2"
-            | "ttf"
-            | "eot"
-            | "png"
-            | "jpg"
-            | "jpeg"
-            | "gif"
-            | "svg"
-            | "webp"
-    )
+/// 未找到文件时的处理方式
+#[derive(Debug, PartialEq)]
+enum Fallback {
+    /// 页面路由，返回 index.html 交给前端路由处理
+    Index,
+    /// 缺失的静态文件，返回 404
+    NotFound,
+    /// 未匹配的 API 路径，返回 JSON 404
+    Api,
+}
+
+fn fallback_for(path: &str) -> Fallback {
+    if path == "api" || path.starts_with("api/") {
+        return Fallback::Api;
+    }
+    let last_segment = path.rsplit('/').next().unwrap_or_default();
+    if last_segment.contains('.') {
+        Fallback::NotFound
+    } else {
+        Fallback::Index
+    }
+}
+
+/// 客户端是否接受指定编码
+fn accepts_encoding(req: &HttpRequest, encoding: &str) -> bool {
+    req.headers()
+        .get(ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|value| {
+            value.split(',').any(|item| {
+                let mut parts = item.split(';');
+                let name = parts.next().unwrap_or_default().trim();
+                let rejected = parts.any(|p| matches!(p.trim(), "q=0" | "q=0.0" | "q=0.00"));
+                name.eq_ignore_ascii_case(encoding) && !rejected
+            })
+        })
 }
 
 /// 处理 HTML 文件中的占位符替换
@@ -87,20 +146,31 @@ fn process_html(content: &[u8], _config: &AppConfig) -> Vec<u8> {
     processed.into_bytes()
 }
 
-/// 尝试从自定义目录读取文件（开发用）
-fn try_custom_file(path: &str) -> Option<Vec<u8>> {
-    let custom_path = format!("./frontend-custom/{}", path);
-    std::fs::read(&custom_path).ok()
+/// 尝试从配置的前端目录读取文件（拒绝 `..` 等越界路径）
+fn try_custom_file(path: &str, config: &AppConfig) -> Option<StaticFile> {
+    let relative = Path::new(path);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+    let data = std::fs::read(Path::new(&config.frontend.root_dir).join(relative)).ok()?;
+    let hash = Sha256::digest(&data).into();
+    Some(StaticFile { data, hash })
 }
 
 /// 尝试从嵌入的资源中获取文件
-fn get_embedded_file(path: &str) -> Option<Vec<u8>> {
-    FrontendAssets::get(path).map(|f| f.data.to_vec())
+fn get_embedded_file(path: &str) -> Option<StaticFile> {
+    FrontendAssets::get(path).map(|f| StaticFile {
+        hash: f.metadata.sha256_hash(),
+        data: f.data.to_vec(),
+    })
 }
 
 /// 获取文件内容（优先自定义目录，然后嵌入资源）
-fn get_file(path: &str) -> Option<Vec<u8>> {
-    try_custom_file(path).or_else(|| get_embedded_file(path))
+fn get_file(path: &str, config: &AppConfig) -> Option<StaticFile> {
+    try_custom_file(path, config).or_else(|| get_embedded_file(path))
 }
 
 /// 前端资源请求处理
@@ -109,44 +179,98 @@ pub async fn serve_frontend(req: HttpRequest) -> ActixResult<HttpResponse> {
     let config = AppConfig::get();
 
     // 尝试获取请求的文件
-    let (content, file_path) = if path.is_empty() || path == "/" {
+    let (file, file_path) = if path.is_empty() {
         // 根路径返回 index.html
-        (get_file("index.html"), "index.html")
-    } else if let Some(content) = get_file(path) {
+        (get_file("index.html", config), "index.html")
+    } else if let Some(file) = get_file(path, config) {
         // 找到请求的文件
-        (Some(content), path)
+        (Some(file), path)
     } else {
-        // SPA fallback: 未找到的路由返回 index.html
-        (get_file("index.html"), "index.html")
+        match fallback_for(path) {
+            // SPA fallback: 未找到的页面路由返回 index.html
+            Fallback::Index => (get_file("index.html", config), "index.html"),
+            Fallback::NotFound => {
+                return Ok(HttpResponse::NotFound()
+                    .content_type("text/plain; charset=utf-8")
+                    .insert_header((CACHE_CONTROL, "no-cache"))
+                    .body("Not Found"));
+            }
+            Fallback::Api => {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::NotFound,
+                    "API endpoint not found",
+                )));
+            }
+        }
     };
 
-    match content {
-        Some(mut data) => {
-            let mime = get_mime_type(file_path);
+    let Some(file) = file else {
+        return Ok(frontend_not_found());
+    };
 
-            // 处理 HTML 文件中的占位符
-            if mime.starts_with("text/html") {
-                data = process_html(&data, config);
-            }
+    let mime = get_mime_type(file_path);
+    let is_html = mime.starts_with("text/html");
 
-            let mut response = HttpResponse::Ok();
-            response.content_type(mime);
+    // 优先返回客户端支持的预压缩版本（HTML 需替换占位符，不使用预压缩文件）
+    let (file, encoding) = if is_html {
+        (file, None)
+    } else {
+        PRECOMPRESSED
+            .iter()
+            .filter(|(encoding, _)| accepts_encoding(&req, encoding))
+            .find_map(|(encoding, suffix)| {
+                get_file(&format!("{file_path}{suffix}"), config).map(|f| (f, Some(*encoding)))
+            })
+            .unwrap_or((file, None))
+    };
 
-            // 设置缓存头
-            if should_cache(file_path) {
-                response.insert_header(("Cache-Control", "public, max-age=31536000, immutable"));
-            } else {
-                response.insert_header(("Cache-Control", "no-cache, no-store, must-revalidate"));
-            }
+    let cache_control = if is_hashed_asset(file_path) {
+        format!(
+            "public, max-age={}, immutable",
+            config.frontend.asset_max_age
+        )
+    } else {
+        // 入口 HTML 与未带哈希的文件每次协商，保证发布后立即生效
+        "no-cache".to_string()
+    };
+    let etag = file.etag(encoding);
 
-            Ok(response.body(data))
-        }
-        None => {
-            // 如果连 index.html 都没有，返回一个简单的提示
-            Ok(HttpResponse::NotFound()
-                .content_type("text/html; charset=utf-8")
-                .body(
-                    r#"<!DOCTYPE html>
+    let not_modified = if_none_match(&req, &etag);
+    let mut response = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header((CACHE_CONTROL, cache_control))
+        .insert_header((ETAG, etag));
+    if !is_html {
+        response.insert_header((VARY, "Accept-Encoding"));
+    }
+
+    if not_modified {
+        return Ok(response.finish());
+    }
+
+    response.content_type(mime);
+    if let Some(encoding) = encoding {
+        response.insert_header((CONTENT_ENCODING, encoding));
+    }
+
+    let data = if is_html {
+        process_html(&file.data, config)
+    } else {
+        file.data
+    };
+    Ok(response.body(data))
+}
+
+/// 前端资源未构建时的提示页
+fn frontend_not_found() -> HttpResponse {
+    HttpResponse::NotFound()
+        .content_type("text/html; charset=utf-8")
+        .body(
+            r#"<!DOCTYPE html>
 <html>
 <head>
     <meta charset="utf-8">
@@ -159,9 +283,7 @@ pub async fn serve_frontend(req: HttpRequest) -> ActixResult<HttpResponse> {
     <pre>cd frontend && npm run build</pre>
 </body>
 </html>"#,
-                ))
-        }
-    }
+        )
 }
 
 /// 配置前端路由
@@ -187,11 +309,35 @@ mod tests {
     }
 
     #[test]
-    fn test_should_cache() {
-        assert!(should_cache("app.js"));
-        assert!(should_cache("style.css"));
-        assert!(should_cache("logo.png"));
-        assert!(!should_cache("index.html"));
-        assert!(!should_cache("manifest.json"));
+    fn test_is_hashed_asset() {
+        assert!(is_hashed_asset("assets/index-3f2a9c1b.js"));
+        assert!(is_hashed_asset("assets/style.a1b2c3d4.css"));
+        assert!(is_hashed_asset("assets/vendor-B_x9Qz1k.js"));
+        assert!(!is_hashed_asset("app.js"));
+        assert!(!is_hashed_asset("logo.png"));
+        assert!(!is_hashed_asset("index.html"));
+        assert!(!is_hashed_asset("manifest.json"));
+        assert!(!is_hashed_asset("assets/components.js"));
+    }
+
+    #[test]
+    fn test_fallback_for() {
+        assert_eq!(fallback_for("classes/12/homeworks"), Fallback::Index);
+        assert_eq!(
+            fallback_for("assets/missing-3f2a9c1b.js"),
+            Fallback::NotFound
+        );
+        assert_eq!(fallback_for("favicon.ico"), Fallback::NotFound);
+        assert_eq!(fallback_for("api/v9/users"), Fallback::Api);
+        assert_eq!(fallback_for("apis"), Fallback::Index);
+    }
+
+    #[test]
+    fn test_accepts_encoding() {
+        let req = actix_web::test::TestRequest::default()
+            .insert_header((ACCEPT_ENCODING, "gzip, deflate, br;q=0"))
+            .to_http_request();
+        assert!(accepts_encoding(&req, "gzip"));
+        assert!(!accepts_encoding(&req, "br"));
     }
 }
@@ -68,7 +68,7 @@ pub async fn update_grade(
 // 配置路由
 pub fn configure_grades_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/grades")
+        web::scope("/grades")
             .wrap(middlewares::RequireJWT)
             .service(
                 web::resource("")
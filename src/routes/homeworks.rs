@@ -1,14 +1,21 @@
+use actix_multipart::Multipart;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use once_cell::sync::Lazy;
 
-use crate::middlewares::{self, RequireJWT};
+use crate::middlewares::{self, RateLimit, RequireJWT};
+use crate::models::grades::requests::GradeDistributionQuery;
 use crate::models::homeworks::requests::{
-    AllHomeworksParams, CreateHomeworkRequest, HomeworkListParams, UpdateHomeworkRequest,
+    AllHomeworksParams, BatchCreateHomeworkRequest, CheckDeadlineRequest,
+    CreateHomeworkExemptionRequest, CreateHomeworkRequest, CreateHomeworkShareLinkRequest,
+    HomeworkFeedbackRequest, HomeworkListParams, HomeworkPartScoresQuery,
+    ReplaceHomeworkLinksRequest, ReplaceHomeworkPartsRequest, ReplaceHomeworkPrerequisitesRequest,
+    UpdateHomeworkRequest, UpsertHomeworkSolutionRequest, UpsertMissingGradePolicyRequest,
 };
+use crate::models::rubrics::requests::SetHomeworkRubricRequest;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
 use crate::services::HomeworkService;
-use crate::utils::SafeIDI64;
+use crate::utils::{SafeClassIdI64, SafeIDI64, SafeShareToken};
 
 // 懒加载的全局 HomeworkService 实例
 static HOMEWORK_SERVICE: Lazy<HomeworkService> = Lazy::new(HomeworkService::new_lazy);
@@ -43,6 +50,68 @@ pub async fn create_homework(
         .await
 }
 
+// 批量创建作业（多个班级）
+pub async fn batch_create_homeworks(
+    req: HttpRequest,
+    body: web::Json<BatchCreateHomeworkRequest>,
+) -> ActixResult<HttpResponse> {
+    let user_id = match RequireJWT::extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    HOMEWORK_SERVICE
+        .batch_create_homeworks(&req, user_id, body.into_inner())
+        .await
+}
+
+// 从导入包批量创建班级作业
+pub async fn import_homeworks(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    payload: Multipart,
+) -> ActixResult<HttpResponse> {
+    let user_id = match RequireJWT::extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    HOMEWORK_SERVICE
+        .import_homeworks(&req, user_id, class_id.0, payload)
+        .await
+}
+
+// 检查截止时间冲突
+pub async fn check_deadline(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    body: web::Json<CheckDeadlineRequest>,
+) -> ActixResult<HttpResponse> {
+    let user_id = match RequireJWT::extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    HOMEWORK_SERVICE
+        .check_deadline(&req, user_id, class_id.0, body.into_inner())
+        .await
+}
+
 // 获取作业详情
 pub async fn get_homework(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
     HOMEWORK_SERVICE.get_homework(&req, path.0).await
@@ -116,10 +185,341 @@ pub async fn list_all_homeworks(
         .await
 }
 
+// 列出作业豁免
+pub async fn list_homework_exemptions(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .list_homework_exemptions(&req, path.0)
+        .await
+}
+
+// 豁免学生
+pub async fn create_homework_exemption(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<CreateHomeworkExemptionRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .create_homework_exemption(&req, path.0, body.into_inner())
+        .await
+}
+
+// 考试访问记录汇总
+pub async fn list_exam_access(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.list_exam_access(&req, path.0).await
+}
+
+// 单个学生的考试访问记录
+pub async fn get_student_exam_access(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (homework_id, user_id) = path.into_inner();
+    HOMEWORK_SERVICE
+        .get_student_exam_access(&req, homework_id, user_id)
+        .await
+}
+
+// 取消豁免
+pub async fn delete_homework_exemption(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (homework_id, user_id) = path.into_inner();
+    HOMEWORK_SERVICE
+        .delete_homework_exemption(&req, homework_id, user_id)
+        .await
+}
+
+// 列出作业分享链接
+pub async fn list_homework_share_links(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .list_homework_share_links(&req, path.0)
+        .await
+}
+
+// 创建作业分享链接
+pub async fn create_homework_share_link(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<CreateHomeworkShareLinkRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .create_homework_share_link(&req, path.0, body.into_inner())
+        .await
+}
+
+// 撤销作业分享链接
+pub async fn revoke_homework_share_link(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (homework_id, link_id) = path.into_inner();
+    HOMEWORK_SERVICE
+        .revoke_homework_share_link(&req, homework_id, link_id)
+        .await
+}
+
+// 获取作业参考答案（教师视角）
+pub async fn get_homework_solution(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.get_homework_solution(&req, path.0).await
+}
+
+// 设置作业参考答案
+pub async fn upsert_homework_solution(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<UpsertHomeworkSolutionRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .upsert_homework_solution(&req, path.0, body.into_inner())
+        .await
+}
+
+// 删除作业参考答案
+pub async fn delete_homework_solution(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .delete_homework_solution(&req, path.0)
+        .await
+}
+
+// 手动公开作业参考答案
+pub async fn publish_homework_solution(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .publish_homework_solution(&req, path.0)
+        .await
+}
+
+// 获取缺交零分策略
+pub async fn get_missing_grade_policy(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .get_missing_grade_policy(&req, path.0)
+        .await
+}
+
+// 设置缺交零分策略
+pub async fn upsert_missing_grade_policy(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<UpsertMissingGradePolicyRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .upsert_missing_grade_policy(&req, path.0, body.into_inner())
+        .await
+}
+
+// 删除缺交零分策略
+pub async fn delete_missing_grade_policy(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .delete_missing_grade_policy(&req, path.0)
+        .await
+}
+
+// 撤销学生的自动零分
+pub async fn revert_auto_assigned_grade(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (homework_id, user_id) = path.into_inner();
+    HOMEWORK_SERVICE
+        .revert_auto_assigned_grade(&req, homework_id, user_id)
+        .await
+}
+
+// 列出作业分题
+pub async fn list_homework_parts(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.list_homework_parts(&req, path.0).await
+}
+
+// 设置作业分题
+pub async fn replace_homework_parts(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<ReplaceHomeworkPartsRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .replace_homework_parts(&req, path.0, body.into_inner())
+        .await
+}
+
+// 获取作业评分量规
+pub async fn get_homework_rubric(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.get_homework_rubric(&req, path.0).await
+}
+
+// 设置作业评分量规
+pub async fn set_homework_rubric(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<SetHomeworkRubricRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .set_homework_rubric(&req, path.0, body.into_inner())
+        .await
+}
+
+// 移除作业评分量规
+pub async fn delete_homework_rubric(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.delete_homework_rubric(&req, path.0).await
+}
+
+// 列出作业外部资源
+pub async fn list_homework_links(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.list_homework_links(&req, path.0).await
+}
+
+// 设置作业外部资源
+pub async fn replace_homework_links(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<ReplaceHomeworkLinksRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .replace_homework_links(&req, path.0, body.into_inner())
+        .await
+}
+
+// 打开作业外部资源（记录学生打开情况）
+pub async fn open_homework_link(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (homework_id, link_id) = path.into_inner();
+    HOMEWORK_SERVICE
+        .open_homework_link(&req, homework_id, link_id)
+        .await
+}
+
+// 查看作业外部资源打开统计
+pub async fn get_homework_link_stats(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.get_homework_link_stats(&req, path.0).await
+}
+
+// 查看本人的用时与难度反馈
+pub async fn get_homework_feedback(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.get_homework_feedback(&req, path.0).await
+}
+
+// 填写或修改用时与难度反馈
+pub async fn submit_homework_feedback(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<HomeworkFeedbackRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .submit_homework_feedback(&req, path.0, body.into_inner())
+        .await
+}
+
+// 列出作业前置条件
+pub async fn list_homework_prerequisites(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .list_homework_prerequisites(&req, path.0)
+        .await
+}
+
+// 设置作业前置条件
+pub async fn replace_homework_prerequisites(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<ReplaceHomeworkPrerequisitesRequest>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .replace_homework_prerequisites(&req, path.0, body.into_inner())
+        .await
+}
+
+// 查看分题得分汇总
+pub async fn get_homework_part_scores(
+    req: HttpRequest,
+    path: SafeIDI64,
+    query: web::Query<HomeworkPartScoresQuery>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .get_homework_part_scores(&req, path.0, query.into_inner())
+        .await
+}
+
+// 作业成绩分布（均值、中位数、标准差与直方图）
+pub async fn get_grade_distribution(
+    req: HttpRequest,
+    path: SafeIDI64,
+    query: web::Query<GradeDistributionQuery>,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .get_grade_distribution(&req, path.0, query.into_inner())
+        .await
+}
+
+// 通过分享链接查看作业（公开）
+pub async fn get_shared_homework(
+    req: HttpRequest,
+    token: SafeShareToken,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE.get_shared_homework(&req, &token.0).await
+}
+
+// 通过分享链接下载附件（公开）
+pub async fn download_shared_file(
+    req: HttpRequest,
+    token: SafeShareToken,
+    file_id: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    HOMEWORK_SERVICE
+        .download_shared_file(&req, &token.0, file_id.0)
+        .await
+}
+
+// 配置班级作业导入与截止时间检查路由（必须在 classes 之前注册）
+pub fn configure_class_homeworks_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/classes/{class_id}/homeworks")
+            .wrap(middlewares::RequireJWT)
+            .service(
+                web::resource("/import")
+                    // 导入作业 - 仅教师和管理员（业务层校验班级教师身份）
+                    .route(web::post().to(import_homeworks))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/check-deadline")
+                    // 截止时间冲突检查 - 仅教师和管理员（业务层校验班级教师身份）
+                    .route(web::post().to(check_deadline))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            ),
+    );
+}
+
 // 配置路由
 pub fn configure_homeworks_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/homeworks")
+        web::scope("/homeworks")
             .wrap(middlewares::RequireJWT)
             .service(
                 web::resource("")
@@ -142,6 +542,12 @@ pub fn configure_homeworks_routes(cfg: &mut web::ServiceConfig) {
             )
             // 跨班级作业列表 - 所有登录用户可访问（业务层根据角色返回不同数据）
             .service(web::resource("/all").route(web::get().to(list_all_homeworks)))
+            // 批量创建作业 - 仅教师和管理员
+            .service(
+                web::resource("/batch-create")
+                    .route(web::post().to(batch_create_homeworks))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
             .service(
                 web::resource("/{id}")
                     // 获取作业详情 - 所有登录用户可访问（业务层会验证班级成员资格）
@@ -168,6 +574,144 @@ pub fn configure_homeworks_routes(cfg: &mut web::ServiceConfig) {
                 web::resource("/{id}/stats/export")
                     // 权限在业务层检查（允许教师、课代表、管理员）
                     .route(web::get().to(export_homework_stats)),
+            )
+            // 成绩分布 - 仅教师和管理员（业务层校验班级教师身份）
+            .service(
+                web::resource("/{id}/grade-distribution")
+                    .route(web::get().to(get_grade_distribution))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 作业豁免 - 仅教师和管理员（业务层校验班级教师身份）
+            .service(
+                web::resource("/{id}/exemptions")
+                    .route(web::get().to(list_homework_exemptions))
+                    .route(web::post().to(create_homework_exemption))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{id}/exemptions/{user_id}")
+                    .route(web::delete().to(delete_homework_exemption))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 考试访问记录 - 仅教师和管理员（业务层校验班级教师身份）
+            .service(
+                web::resource("/{id}/exam-access")
+                    .route(web::get().to(list_exam_access))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{id}/exam-access/{user_id}")
+                    .route(web::get().to(get_student_exam_access))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 作业分享链接 - 仅教师和管理员（业务层校验班级教师身份）
+            .service(
+                web::resource("/{id}/share-links")
+                    .route(web::get().to(list_homework_share_links))
+                    .route(web::post().to(create_homework_share_link))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{id}/share-links/{link_id}")
+                    .route(web::delete().to(revoke_homework_share_link))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 分题 - 班级成员可查看（业务层验证），仅教师和管理员可修改
+            .service(
+                web::resource("/{id}/parts")
+                    .route(web::get().to(list_homework_parts))
+                    .route(
+                        web::put()
+                            .to(replace_homework_parts)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                    ),
+            )
+            .service(
+                web::resource("/{id}/parts/scores").route(web::get().to(get_homework_part_scores)),
+            )
+            // 评分量规 - 班级成员可查看（业务层验证），仅教师和管理员可设置
+            .service(
+                web::resource("/{id}/rubric")
+                    .route(web::get().to(get_homework_rubric))
+                    .route(
+                        web::put()
+                            .to(set_homework_rubric)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                    )
+                    .route(
+                        web::delete()
+                            .to(delete_homework_rubric)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                    ),
+            )
+            // 外部资源 - 班级成员可查看与打开（业务层验证），仅教师和管理员可修改与查看统计
+            .service(
+                web::resource("/{id}/links")
+                    .route(web::get().to(list_homework_links))
+                    .route(
+                        web::put()
+                            .to(replace_homework_links)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                    ),
+            )
+            .service(
+                web::resource("/{id}/links/stats")
+                    .route(web::get().to(get_homework_link_stats))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{id}/links/{link_id}/open")
+                    .route(web::post().to(open_homework_link)),
+            )
+            // 用时与难度反馈 - 仅班级学生（业务层验证），评分后可填写
+            .service(
+                web::resource("/{id}/feedback")
+                    .route(web::get().to(get_homework_feedback))
+                    .route(web::put().to(submit_homework_feedback)),
+            )
+            // 前置条件 - 班级成员可查看（学生附带完成情况），仅教师和管理员可修改
+            .service(
+                web::resource("/{id}/prerequisites")
+                    .route(web::get().to(list_homework_prerequisites))
+                    .route(
+                        web::put()
+                            .to(replace_homework_prerequisites)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+                    ),
+            )
+            // 参考答案 - 仅教师和管理员（学生通过作业详情查看已公开的答案）
+            .service(
+                web::resource("/{id}/solution")
+                    .route(web::get().to(get_homework_solution))
+                    .route(web::put().to(upsert_homework_solution))
+                    .route(web::delete().to(delete_homework_solution))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{id}/solution/publish")
+                    .route(web::post().to(publish_homework_solution))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            // 缺交零分 - 仅教师和管理员（业务层校验班级教师身份）
+            .service(
+                web::resource("/{id}/missing-grade-policy")
+                    .route(web::get().to(get_missing_grade_policy))
+                    .route(web::put().to(upsert_missing_grade_policy))
+                    .route(web::delete().to(delete_missing_grade_policy))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
+            )
+            .service(
+                web::resource("/{id}/missing-grades/{user_id}")
+                    .route(web::delete().to(revert_auto_assigned_grade))
+                    .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles())),
             ),
     );
+
+    // 公开分享链接 - 无需登录，按 IP 限流
+    cfg.service(
+        web::scope("/shared/homeworks/{token}")
+            .wrap(RateLimit::shared_link())
+            .route("", web::get().to(get_shared_homework))
+            .route("/files/{id}", web::get().to(download_shared_file)),
+    );
 }
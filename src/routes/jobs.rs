@@ -0,0 +1,28 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::services::JobService;
+use crate::utils::SafeJobId;
+
+// 懒加载的全局 JobService 实例
+static JOB_SERVICE: Lazy<JobService> = Lazy::new(JobService::new_lazy);
+
+// HTTP处理程序
+pub async fn get_job(req: HttpRequest, job_id: SafeJobId) -> ActixResult<HttpResponse> {
+    JOB_SERVICE.get_job(&req, &job_id.0).await
+}
+
+pub async fn download_job(req: HttpRequest, job_id: SafeJobId) -> ActixResult<HttpResponse> {
+    JOB_SERVICE.download_job(&req, &job_id.0).await
+}
+
+// 配置路由
+pub fn configure_jobs_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/jobs")
+            .wrap(middlewares::RequireJWT)
+            .route("/{job_id}", web::get().to(get_job))
+            .route("/{job_id}/download", web::get().to(download_job)),
+    );
+}
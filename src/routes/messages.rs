@@ -0,0 +1,77 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::messages::requests::{
+    ConversationListQuery, ConversationMessagesQuery, SendMessageRequest,
+};
+use crate::services::MessageService;
+
+// 懒加载的全局 MESSAGE_SERVICE 实例
+static MESSAGE_SERVICE: Lazy<MessageService> = Lazy::new(MessageService::new_lazy);
+
+// 发送私信
+pub async fn send_message(
+    req: HttpRequest,
+    body: web::Json<SendMessageRequest>,
+) -> ActixResult<HttpResponse> {
+    MESSAGE_SERVICE.send_message(&req, body.into_inner()).await
+}
+
+// 列出会话
+pub async fn list_conversations(
+    req: HttpRequest,
+    query: web::Query<ConversationListQuery>,
+) -> ActixResult<HttpResponse> {
+    MESSAGE_SERVICE
+        .list_conversations(&req, query.into_inner())
+        .await
+}
+
+// 查询会话消息
+pub async fn list_conversation_messages(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    query: web::Query<ConversationMessagesQuery>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, peer_id) = path.into_inner();
+    MESSAGE_SERVICE
+        .list_conversation_messages(&req, class_id, peer_id, query.into_inner())
+        .await
+}
+
+// 标记会话已读
+pub async fn mark_conversation_read(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, peer_id) = path.into_inner();
+    MESSAGE_SERVICE
+        .mark_conversation_read(&req, class_id, peer_id)
+        .await
+}
+
+// 获取未读私信数
+pub async fn get_unread_count(req: HttpRequest) -> ActixResult<HttpResponse> {
+    MESSAGE_SERVICE.get_unread_count(&req).await
+}
+
+// 配置路由
+pub fn configure_messages_routes(cfg: &mut web::ServiceConfig) {
+    // 班级私信 - 班级成员（权限在 service 层进一步验证）
+    cfg.service(
+        web::scope("/messages")
+            .wrap(middlewares::RequireJWT)
+            .route("", web::post().to(send_message))
+            .route("/unread-count", web::get().to(get_unread_count))
+            .route("/conversations", web::get().to(list_conversations))
+            .route(
+                "/conversations/{class_id}/{user_id}",
+                web::get().to(list_conversation_messages),
+            )
+            .route(
+                "/conversations/{class_id}/{user_id}/read",
+                web::put().to(mark_conversation_read),
+            ),
+    );
+}
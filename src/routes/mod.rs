@@ -2,10 +2,14 @@ pub mod auth;
 
 pub mod users;
 
+pub mod organizations;
+
 pub mod classes;
 
 pub mod class_users;
 
+pub mod certificates;
+
 pub mod files;
 
 pub mod homeworks;
@@ -18,19 +22,113 @@ pub mod notifications;
 
 pub mod system;
 
+pub mod usage;
+
+pub mod analytics;
+
+pub mod jobs;
+
+pub mod messages;
+
+pub mod moderation;
+
+pub mod reactions;
+
+pub mod rubrics;
+
+pub mod viewers;
+
 pub mod frontend;
 
 pub mod websocket;
 
+pub mod dev;
+
+pub mod v2;
+
+pub use analytics::configure_analytics_routes;
 pub use auth::configure_auth_routes;
-pub use class_users::configure_class_users_routes;
+pub use certificates::configure_certificates_routes;
+pub use class_users::{configure_class_role_labels_routes, configure_class_users_routes};
 pub use classes::configure_classes_routes;
-pub use files::configure_file_routes;
+pub use dev::configure_dev_routes;
+pub use files::{configure_class_retention_routes, configure_file_routes};
 pub use frontend::configure_frontend_routes;
 pub use grades::configure_grades_routes;
-pub use homeworks::configure_homeworks_routes;
+pub use homeworks::{configure_class_homeworks_routes, configure_homeworks_routes};
+pub use jobs::configure_jobs_routes;
+pub use messages::configure_messages_routes;
+pub use moderation::configure_moderation_routes;
 pub use notifications::configure_notifications_routes;
-pub use submissions::configure_submissions_routes;
-pub use system::configure_system_routes;
+pub use organizations::configure_organization_routes;
+pub use reactions::configure_reactions_routes;
+pub use rubrics::configure_rubrics_routes;
+pub use submissions::{configure_class_submission_workflow_routes, configure_submissions_routes};
+pub use system::{configure_integrity_routes, configure_system_routes};
+pub use usage::configure_usage_routes;
 pub use users::configure_user_routes;
+pub use viewers::configure_viewers_routes;
 pub use websocket::configure_websocket_routes;
+
+use actix_web::{HttpResponse, web};
+
+use crate::middlewares::{ApiVersion, ApiVersionHeaders, ClassTokenGuard};
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 注册 v1 版本的全部业务路由（路径相对于版本前缀）
+///
+/// 注意注册顺序：更具体的前缀需要先于其父级前缀注册。
+pub fn configure_v1_routes(cfg: &mut web::ServiceConfig) {
+    cfg.configure(configure_auth_routes) // 配置认证相关路由
+        .configure(configure_user_routes) // 配置用户相关路由
+        .configure(configure_organization_routes) // 配置组织相关路由
+        .configure(configure_class_users_routes) // 配置班级成员相关路由（必须在 classes 之前）
+        .configure(configure_class_role_labels_routes) // 配置班级角色名称路由（必须在 classes 之前）
+        .configure(configure_certificates_routes) // 配置结业证书相关路由（必须在 classes 之前）
+        .configure(configure_class_homeworks_routes) // 配置班级作业导入路由（必须在 classes 之前）
+        .configure(configure_class_retention_routes) // 配置班级文件保留策略路由（必须在 classes 之前）
+        .configure(configure_moderation_routes) // 配置班级内容审核标记与举报路由（必须在 classes 之前）
+        .configure(configure_class_submission_workflow_routes) // 配置班级提交状态工作流路由（必须在 classes 之前）
+        .configure(configure_classes_routes) // 配置班级相关路由
+        .configure(configure_submissions_routes) // 配置提交相关路由（必须在 homeworks 之前，因为有 /homeworks/{id}/submissions 路由）
+        .configure(configure_homeworks_routes) // 配置作业相关路由
+        .configure(configure_rubrics_routes) // 配置评分量规库路由
+        .configure(configure_grades_routes) // 配置评分相关路由
+        .configure(configure_notifications_routes) // 配置通知相关路由
+        .configure(configure_messages_routes) // 配置私信相关路由
+        .configure(configure_reactions_routes) // 配置表情回应相关路由
+        .configure(configure_websocket_routes) // 配置 WebSocket 路由
+        .configure(configure_file_routes) // 配置文件相关路由
+        .configure(configure_usage_routes) // 配置用量统计相关路由
+        .configure(configure_analytics_routes) // 配置统计分析相关路由
+        .configure(configure_integrity_routes) // 配置数据维护路由（一致性检查、SQLite 备份）
+        .configure(configure_jobs_routes) // 配置后台任务相关路由
+        .configure(configure_viewers_routes) // 配置家长/导师只读查看路由
+        .configure(configure_dev_routes) // 配置开发模式示例数据路由（仅开发环境）
+        .configure(configure_system_routes); // 配置系统相关路由
+}
+
+/// 版本前缀下未匹配的 API 路径统一返回 JSON 404，而不是落入前端 fallback
+async fn api_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(ErrorCode::NotFound, "接口不存在"))
+}
+
+/// 按版本挂载全部 API 路由
+///
+/// 每个版本挂载在独立的 `/api/{version}` scope 下，并附加版本/弃用响应头；
+/// 班级 API 令牌在各路由的 RequireJWT 之前由 [`ClassTokenGuard`] 认证。
+pub fn configure_api_routes(cfg: &mut web::ServiceConfig) {
+    for version in ApiVersion::ALL {
+        let scope = web::scope(version.prefix())
+            .wrap(ClassTokenGuard)
+            .wrap(ApiVersionHeaders::new(version))
+            .default_service(web::to(api_not_found));
+
+        let scope = match version {
+            ApiVersion::V1 => scope.configure(configure_v1_routes),
+            ApiVersion::V2 => scope.configure(v2::configure_v2_routes),
+        };
+
+        cfg.service(scope);
+    }
+}
@@ -0,0 +1,87 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::moderation::requests::{
+    ContentReportListQuery, CreateContentReportRequest, ModerationFlagListQuery,
+    ResolveContentReportRequest, ReviewModerationFlagRequest,
+};
+use crate::models::users::entities::UserRole;
+use crate::services::ModerationService;
+use crate::utils::SafeClassIdI64;
+
+// 懒加载的全局 MODERATION_SERVICE 实例
+static MODERATION_SERVICE: Lazy<ModerationService> = Lazy::new(ModerationService::new_lazy);
+
+pub async fn list_moderation_flags(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    query: web::Query<ModerationFlagListQuery>,
+) -> ActixResult<HttpResponse> {
+    MODERATION_SERVICE
+        .list_flags(&req, path.0, query.into_inner())
+        .await
+}
+
+pub async fn review_moderation_flag(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    body: web::Json<ReviewModerationFlagRequest>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, flag_id) = path.into_inner();
+    MODERATION_SERVICE
+        .review_flag(&req, class_id, flag_id, body.into_inner())
+        .await
+}
+
+pub async fn create_content_report(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    body: web::Json<CreateContentReportRequest>,
+) -> ActixResult<HttpResponse> {
+    MODERATION_SERVICE
+        .create_report(&req, path.0, body.into_inner())
+        .await
+}
+
+pub async fn list_content_reports(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    query: web::Query<ContentReportListQuery>,
+) -> ActixResult<HttpResponse> {
+    MODERATION_SERVICE
+        .list_reports(&req, path.0, query.into_inner())
+        .await
+}
+
+pub async fn resolve_content_report(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    body: web::Json<ResolveContentReportRequest>,
+) -> ActixResult<HttpResponse> {
+    let (class_id, report_id) = path.into_inner();
+    MODERATION_SERVICE
+        .resolve_report(&req, class_id, report_id, body.into_inner())
+        .await
+}
+
+// 配置路由
+pub fn configure_moderation_routes(cfg: &mut web::ServiceConfig) {
+    // 班级审核标记 - 班级教师、管理员（权限在 service 层进一步验证）
+    cfg.service(
+        web::scope("/classes/{class_id}/moderation-flags")
+            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles()))
+            .wrap(middlewares::RequireJWT)
+            .route("", web::get().to(list_moderation_flags))
+            .route("/{flag_id}", web::put().to(review_moderation_flag)),
+    );
+
+    // 内容举报 - 班级成员提交，班级教师、管理员处理（权限在 service 层进一步验证）
+    cfg.service(
+        web::scope("/classes/{class_id}/reports")
+            .wrap(middlewares::RequireJWT)
+            .route("", web::post().to(create_content_report))
+            .route("", web::get().to(list_content_reports))
+            .route("/{report_id}", web::put().to(resolve_content_report)),
+    );
+}
@@ -2,7 +2,10 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use once_cell::sync::Lazy;
 
 use crate::middlewares::{self, RequireJWT};
-use crate::models::notifications::requests::NotificationListQuery;
+use crate::models::notifications::requests::{
+    NotificationListQuery, SendNotificationRequest, UpsertNotificationTemplateRequest,
+};
+use crate::models::users::entities::AdminPermission;
 use crate::models::{ApiResponse, ErrorCode};
 use crate::services::NotificationService;
 use crate::utils::SafeIDI64;
@@ -30,6 +33,16 @@ pub async fn list_notifications(
         .await
 }
 
+// 群发班级通知
+pub async fn send_notification(
+    req: HttpRequest,
+    body: web::Json<SendNotificationRequest>,
+) -> ActixResult<HttpResponse> {
+    NOTIFICATION_SERVICE
+        .send_notification(&req, body.into_inner())
+        .await
+}
+
 // 获取未读数量
 pub async fn get_unread_count(req: HttpRequest) -> ActixResult<HttpResponse> {
     let user_id = match RequireJWT::extract_user_id(&req) {
@@ -70,12 +83,51 @@ pub async fn delete_notification(req: HttpRequest, path: SafeIDI64) -> ActixResu
     NOTIFICATION_SERVICE.delete_notification(&req, path.0).await
 }
 
+// 列出通知模板
+pub async fn list_templates(req: HttpRequest) -> ActixResult<HttpResponse> {
+    NOTIFICATION_SERVICE.list_templates(&req).await
+}
+
+// 创建或更新通知模板
+pub async fn upsert_template(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    body: web::Json<UpsertNotificationTemplateRequest>,
+) -> ActixResult<HttpResponse> {
+    let (notification_type, locale) = path.into_inner();
+    NOTIFICATION_SERVICE
+        .upsert_template(&req, &notification_type, &locale, body.into_inner())
+        .await
+}
+
+// 删除通知模板
+pub async fn delete_template(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> ActixResult<HttpResponse> {
+    let (notification_type, locale) = path.into_inner();
+    NOTIFICATION_SERVICE
+        .delete_template(&req, &notification_type, &locale)
+        .await
+}
+
 // 配置路由
 pub fn configure_notifications_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/notifications")
+        web::scope("/notifications")
             .wrap(middlewares::RequireJWT)
+            // 通知模板管理 - 需要系统设置权限（必须在 /{id} 之前注册）
+            .service(
+                web::scope("/templates")
+                    .wrap(middlewares::RequirePermission::new(
+                        AdminPermission::SystemSettings,
+                    ))
+                    .route("", web::get().to(list_templates))
+                    .route("/{type}/{locale}", web::put().to(upsert_template))
+                    .route("/{type}/{locale}", web::delete().to(delete_template)),
+            )
             .route("", web::get().to(list_notifications))
+            .route("", web::post().to(send_notification))
             .route("/unread-count", web::get().to(get_unread_count))
             .route("/read-all", web::put().to(mark_all_as_read))
             .route("/{id}/read", web::put().to(mark_as_read))
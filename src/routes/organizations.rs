@@ -0,0 +1,76 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::organizations::requests::{
+    CreateOrganizationRequest, OrganizationListParams, UpdateOrganizationRequest,
+};
+use crate::models::users::entities::UserRole;
+use crate::services::OrganizationService;
+use crate::utils::SafeIDI64;
+
+// 懒加载的全局 OrganizationService 实例
+static ORGANIZATION_SERVICE: Lazy<OrganizationService> = Lazy::new(OrganizationService::new_lazy);
+
+// HTTP处理程序
+pub async fn list_organizations(
+    req: HttpRequest,
+    query: web::Query<OrganizationListParams>,
+) -> ActixResult<HttpResponse> {
+    ORGANIZATION_SERVICE
+        .list_organizations(query.into_inner(), &req)
+        .await
+}
+
+pub async fn create_organization(
+    req: HttpRequest,
+    body: web::Json<CreateOrganizationRequest>,
+) -> ActixResult<HttpResponse> {
+    ORGANIZATION_SERVICE
+        .create_organization(body.into_inner(), &req)
+        .await
+}
+
+pub async fn get_my_organization(req: HttpRequest) -> ActixResult<HttpResponse> {
+    ORGANIZATION_SERVICE.get_my_organization(&req).await
+}
+
+pub async fn get_organization(req: HttpRequest, org_id: SafeIDI64) -> ActixResult<HttpResponse> {
+    ORGANIZATION_SERVICE.get_organization(org_id.0, &req).await
+}
+
+pub async fn update_organization(
+    req: HttpRequest,
+    org_id: SafeIDI64,
+    body: web::Json<UpdateOrganizationRequest>,
+) -> ActixResult<HttpResponse> {
+    ORGANIZATION_SERVICE
+        .update_organization(org_id.0, body.into_inner(), &req)
+        .await
+}
+
+pub async fn delete_organization(req: HttpRequest, org_id: SafeIDI64) -> ActixResult<HttpResponse> {
+    ORGANIZATION_SERVICE
+        .delete_organization(org_id.0, &req)
+        .await
+}
+
+// 配置路由
+pub fn configure_organization_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/organizations")
+            .wrap(middlewares::RequireJWT)
+            // 所有登录用户可查看自己所属的组织
+            .service(web::resource("/me").route(web::get().to(get_my_organization)))
+            .service(web::resource("/{id}").route(web::get().to(get_organization)))
+            // 组织管理路由（平台管理员；组织管理员仅可更新本组织）
+            .service(
+                web::scope("")
+                    .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+                    .route("", web::get().to(list_organizations))
+                    .route("", web::post().to(create_organization))
+                    .route("/{id}", web::put().to(update_organization))
+                    .route("/{id}", web::delete().to(delete_organization)),
+            ),
+    );
+}
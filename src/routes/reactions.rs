@@ -0,0 +1,38 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::reactions::requests::ReactionRequest;
+use crate::services::ReactionService;
+
+// 懒加载的全局 REACTION_SERVICE 实例
+static REACTION_SERVICE: Lazy<ReactionService> = Lazy::new(ReactionService::new_lazy);
+
+// 添加表情回应
+pub async fn add_reaction(
+    req: HttpRequest,
+    body: web::Json<ReactionRequest>,
+) -> ActixResult<HttpResponse> {
+    REACTION_SERVICE.add_reaction(&req, body.into_inner()).await
+}
+
+// 取消表情回应
+pub async fn remove_reaction(
+    req: HttpRequest,
+    query: web::Query<ReactionRequest>,
+) -> ActixResult<HttpResponse> {
+    REACTION_SERVICE
+        .remove_reaction(&req, query.into_inner())
+        .await
+}
+
+// 配置路由
+pub fn configure_reactions_routes(cfg: &mut web::ServiceConfig) {
+    // 表情回应 - 能查看目标内容的用户（权限在 service 层进一步验证）
+    cfg.service(
+        web::scope("/reactions")
+            .wrap(middlewares::RequireJWT)
+            .route("", web::post().to(add_reaction))
+            .route("", web::delete().to(remove_reaction)),
+    );
+}
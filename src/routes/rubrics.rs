@@ -0,0 +1,92 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::rubrics::requests::{
+    CreateRubricRequest, ImportRubricRequest, RubricListQuery, UpdateRubricRequest,
+};
+use crate::models::users::entities::UserRole;
+use crate::services::RubricService;
+use crate::utils::SafeIDI64;
+
+// 懒加载的全局 RUBRIC_SERVICE 实例
+static RUBRIC_SERVICE: Lazy<RubricService> = Lazy::new(RubricService::new_lazy);
+
+pub async fn list_rubrics(
+    req: HttpRequest,
+    query: web::Query<RubricListQuery>,
+) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.list_rubrics(&req, query.into_inner()).await
+}
+
+pub async fn create_rubric(
+    req: HttpRequest,
+    body: web::Json<CreateRubricRequest>,
+) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.create_rubric(&req, body.into_inner()).await
+}
+
+pub async fn import_rubric(
+    req: HttpRequest,
+    body: web::Json<ImportRubricRequest>,
+) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.import_rubric(&req, body.into_inner()).await
+}
+
+pub async fn get_rubric(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.get_rubric(&req, path.0).await
+}
+
+pub async fn update_rubric(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<UpdateRubricRequest>,
+) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE
+        .update_rubric(&req, path.0, body.into_inner())
+        .await
+}
+
+pub async fn delete_rubric(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.delete_rubric(&req, path.0).await
+}
+
+pub async fn list_rubric_versions(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.list_rubric_versions(&req, path.0).await
+}
+
+pub async fn create_rubric_share_code(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.create_share_code(&req, path.0).await
+}
+
+pub async fn revoke_rubric_share_code(
+    req: HttpRequest,
+    path: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    RUBRIC_SERVICE.revoke_share_code(&req, path.0).await
+}
+
+// 配置路由
+pub fn configure_rubrics_routes(cfg: &mut web::ServiceConfig) {
+    // 评分量规库 - 仅教师和管理员（量规归属与可见性在 service 层验证）
+    cfg.service(
+        web::scope("/rubrics")
+            .wrap(middlewares::RequireRole::new_any(UserRole::teacher_roles()))
+            .wrap(middlewares::RequireJWT)
+            .route("", web::get().to(list_rubrics))
+            .route("", web::post().to(create_rubric))
+            .route("/import", web::post().to(import_rubric))
+            .route("/{id}", web::get().to(get_rubric))
+            .route("/{id}", web::put().to(update_rubric))
+            .route("/{id}", web::delete().to(delete_rubric))
+            .route("/{id}/versions", web::get().to(list_rubric_versions))
+            .route("/{id}/share-code", web::post().to(create_rubric_share_code))
+            .route(
+                "/{id}/share-code",
+                web::delete().to(revoke_rubric_share_code),
+            ),
+    );
+}
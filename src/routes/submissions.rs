@@ -2,12 +2,14 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use once_cell::sync::Lazy;
 
 use crate::middlewares::{self, RequireJWT};
+use crate::models::grades::requests::SaveGradeDraftRequest;
 use crate::models::submissions::requests::{
-    CreateSubmissionRequest, SubmissionListQuery, SubmissionSummaryQuery,
+    CreateSubmissionRequest, RequestResubmissionRequest, SubmissionDiffQuery, SubmissionListQuery,
+    SubmissionSummaryQuery, TransitionSubmissionStatusRequest, UpdateSubmissionWorkflowRequest,
 };
 use crate::models::{ApiResponse, ErrorCode};
 use crate::services::SubmissionService;
-use crate::utils::{SafeHomeworkIdI64, SafeIDI64};
+use crate::utils::{SafeClassIdI64, SafeHomeworkIdI64, SafeIDI64};
 
 // 懒加载的全局 SubmissionService 实例
 static SUBMISSION_SERVICE: Lazy<SubmissionService> = Lazy::new(SubmissionService::new_lazy);
@@ -47,6 +49,17 @@ pub async fn get_submission(req: HttpRequest, path: SafeIDI64) -> ActixResult<Ht
     SUBMISSION_SERVICE.get_submission(&req, path.0).await
 }
 
+// 对比提交版本
+pub async fn diff_submissions(
+    req: HttpRequest,
+    path: SafeIDI64,
+    query: web::Query<SubmissionDiffQuery>,
+) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE
+        .diff_submissions(&req, path.0, query.into_inner())
+        .await
+}
+
 // 获取我的最新提交
 pub async fn get_my_latest_submission(
     req: HttpRequest,
@@ -111,7 +124,7 @@ pub async fn get_submission_summary(
     query: web::Query<SubmissionSummaryQuery>,
 ) -> ActixResult<HttpResponse> {
     SUBMISSION_SERVICE
-        .get_submission_summary(&req, path.0, query.page, query.size, query.graded)
+        .get_submission_summary(&req, path.0, query.into_inner())
         .await
 }
 
@@ -131,21 +144,94 @@ pub async fn get_submission_grade(req: HttpRequest, path: SafeIDI64) -> ActixRes
     SUBMISSION_SERVICE.get_submission_grade(&req, path.0).await
 }
 
+// 获取评分草稿
+pub async fn get_grade_draft(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE.get_grade_draft(&req, path.0).await
+}
+
+// 保存评分草稿
+pub async fn save_grade_draft(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<SaveGradeDraftRequest>,
+) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE
+        .save_grade_draft(&req, path.0, body.into_inner())
+        .await
+}
+
+// 丢弃评分草稿
+pub async fn delete_grade_draft(req: HttpRequest, path: SafeIDI64) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE.delete_grade_draft(&req, path.0).await
+}
+
+// 转换提交状态（按班级工作流）
+pub async fn transition_submission_status(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<TransitionSubmissionStatusRequest>,
+) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE
+        .transition_submission_status(&req, path.0, body.into_inner())
+        .await
+}
+
+// 退回提交，要求学生重新提交
+pub async fn request_resubmission(
+    req: HttpRequest,
+    path: SafeIDI64,
+    body: web::Json<RequestResubmissionRequest>,
+) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE
+        .request_resubmission(&req, path.0, body.into_inner())
+        .await
+}
+
+// 获取班级提交状态工作流
+pub async fn get_class_submission_workflow(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE
+        .get_class_submission_workflow(&req, path.0)
+        .await
+}
+
+// 更新班级提交状态工作流
+pub async fn update_class_submission_workflow(
+    req: HttpRequest,
+    path: SafeClassIdI64,
+    body: web::Json<UpdateSubmissionWorkflowRequest>,
+) -> ActixResult<HttpResponse> {
+    SUBMISSION_SERVICE
+        .update_class_submission_workflow(&req, path.0, body.into_inner())
+        .await
+}
+
 // 配置路由
 pub fn configure_submissions_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/submissions")
+        web::scope("/submissions")
             .wrap(middlewares::RequireJWT)
             .route("", web::get().to(list_submissions))
             .route("", web::post().to(create_submission))
             .route("/{id}", web::get().to(get_submission))
             .route("/{id}", web::delete().to(delete_submission))
-            .route("/{id}/grade", web::get().to(get_submission_grade)),
+            .route("/{id}/diff", web::get().to(diff_submissions))
+            .route("/{id}/status", web::post().to(transition_submission_status))
+            .route(
+                "/{id}/request-resubmission",
+                web::post().to(request_resubmission),
+            )
+            .route("/{id}/grade", web::get().to(get_submission_grade))
+            .route("/{id}/grade-draft", web::get().to(get_grade_draft))
+            .route("/{id}/grade-draft", web::put().to(save_grade_draft))
+            .route("/{id}/grade-draft", web::delete().to(delete_grade_draft)),
     );
 
     // 作业相关的提交路由
     cfg.service(
-        web::scope("/api/v1/homeworks/{homework_id}/submissions")
+        web::scope("/homeworks/{homework_id}/submissions")
             .wrap(middlewares::RequireJWT)
             .route("/my/latest", web::get().to(get_my_latest_submission))
             .route("/my", web::get().to(list_my_submissions))
@@ -156,3 +242,13 @@ pub fn configure_submissions_routes(cfg: &mut web::ServiceConfig) {
             ),
     );
 }
+
+// 班级提交状态工作流 - 班级成员可查看，教师/管理员可修改（业务层校验）
+pub fn configure_class_submission_workflow_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/classes/{class_id}/submission-workflow")
+            .wrap(middlewares::RequireJWT)
+            .route(web::get().to(get_class_submission_workflow))
+            .route(web::put().to(update_class_submission_workflow)),
+    );
+}
@@ -1,10 +1,13 @@
-use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, middleware, web};
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, guard, middleware, web};
 use once_cell::sync::Lazy;
 
+use crate::config::AppConfig;
 use crate::middlewares;
-use crate::models::users::entities::UserRole;
+use crate::models::users::entities::{AdminPermission, UserRole};
 use crate::services::SystemService;
-use crate::services::system::settings;
+use crate::services::system::{
+    branding, database, dev_data, integrity, job_queues, registration, settings, time,
+};
 
 // 懒加载的全局 SystemService 实例
 static SYSTEM_SERVICE: Lazy<SystemService> = Lazy::new(SystemService::new_lazy);
@@ -15,19 +18,96 @@ pub async fn get_settings(request: HttpRequest) -> ActixResult<HttpResponse> {
 
 // 配置路由
 pub fn configure_system_routes(cfg: &mut web::ServiceConfig) {
+    let mut protected = web::scope("")
+        .wrap(middlewares::RequireJWT)
+        // 批量更新设置 - 需要系统设置权限（按方法区分，必须在只读路由之前注册）
+        .service(
+            web::resource("/settings")
+                .guard(guard::Put())
+                .route(web::put().to(settings::batch_update_settings))
+                .wrap(middlewares::RequirePermission::new(
+                    AdminPermission::SystemSettings,
+                )),
+        )
+        // 公开设置（只读，登录用户可访问）
+        .route("/settings", web::get().to(get_settings))
+        // 后台任务队列状态 - 仅管理员
+        .service(
+            web::resource("/admin/job-queues")
+                .route(web::get().to(job_queues::get_job_queues))
+                .wrap(middlewares::RequireRole::new(&UserRole::Admin)),
+        )
+        // 管理员设置路由
+        .service(
+            web::scope("/admin/settings")
+                // 审计日志 - 需要审计查看权限（必须在 /{key} 之前注册）
+                .service(
+                    web::resource("/audit")
+                        .route(web::get().to(settings::get_setting_audits))
+                        .wrap(middlewares::RequirePermission::new(
+                            AdminPermission::AuditView,
+                        )),
+                )
+                // 设置读写 - 需要系统设置权限
+                .service(
+                    web::scope("")
+                        .wrap(middlewares::RequirePermission::new(
+                            AdminPermission::SystemSettings,
+                        ))
+                        .route("", web::get().to(settings::get_admin_settings))
+                        .route("/schema", web::get().to(settings::get_setting_schema))
+                        .route("/{key}", web::put().to(settings::update_setting)),
+                ),
+        );
+
+    // 压测数据生成 - 仅开发环境注册，仅管理员
+    if AppConfig::get().is_development() {
+        protected = protected.service(
+            web::resource("/dev/generate-data")
+                .route(web::post().to(dev_data::generate_dev_data))
+                .wrap(middlewares::RequireRole::new(&UserRole::Admin)),
+        );
+    }
+
     cfg.service(
-        web::scope("/api/v1/system")
+        web::scope("/system")
             .wrap(middleware::Compress::default())
+            // 品牌设置（公开，登录前可访问）
+            .route("/branding", web::get().to(branding::get_branding))
+            .route("/branding/logo", web::get().to(branding::get_branding_logo))
+            // 服务器时间（公开，客户端校时与考试模式签名时间戳）
+            .route("/time", web::get().to(time::get_server_time))
+            // 注册策略（公开，注册前可访问）
+            .route(
+                "/registration-policy",
+                web::get().to(registration::get_registration_policy),
+            )
+            .service(protected),
+    );
+}
+
+// 配置数据维护路由
+pub fn configure_integrity_routes(cfg: &mut web::ServiceConfig) {
+    // 数据一致性检查 - 检查与修复覆盖全部组织，仅平台管理员
+    cfg.service(
+        web::resource("/admin/integrity-check")
+            .route(web::post().to(integrity::run_integrity_check))
+            .wrap(middleware::from_fn(
+                middlewares::tenant::require_platform_admin,
+            ))
+            .wrap(middlewares::RequireRole::new(&UserRole::Admin))
+            .wrap(middlewares::RequireJWT),
+    );
+    // SQLite 数据库状态、WAL 检查点与在线备份 - 备份包含全部组织的数据，仅平台管理员
+    cfg.service(
+        web::scope("/admin/database")
+            .wrap(middleware::from_fn(
+                middlewares::tenant::require_platform_admin,
+            ))
+            .wrap(middlewares::RequireRole::new(&UserRole::Admin))
             .wrap(middlewares::RequireJWT)
-            // 公开设置（只读，登录用户可访问）
-            .route("/settings", web::get().to(get_settings))
-            // 管理员设置路由
-            .service(
-                web::scope("/admin/settings")
-                    .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
-                    .route("", web::get().to(settings::get_admin_settings))
-                    .route("/{key}", web::put().to(settings::update_setting))
-                    .route("/audit", web::get().to(settings::get_setting_audits)),
-            ),
+            .route("", web::get().to(database::get_database_status))
+            .route("/checkpoint", web::post().to(database::checkpoint_wal))
+            .route("/backup", web::post().to(database::backup_database)),
     );
 }
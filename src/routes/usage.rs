@@ -0,0 +1,43 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::usage::requests::{CurrentUsageParams, UsageReportListParams};
+use crate::models::users::entities::UserRole;
+use crate::services::UsageService;
+
+// 懒加载的全局 UsageService 实例
+static USAGE_SERVICE: Lazy<UsageService> = Lazy::new(UsageService::new_lazy);
+
+// HTTP处理程序
+pub async fn list_usage_reports(
+    req: HttpRequest,
+    query: web::Query<UsageReportListParams>,
+) -> ActixResult<HttpResponse> {
+    USAGE_SERVICE.list_reports(query.into_inner(), &req).await
+}
+
+pub async fn generate_usage_reports(req: HttpRequest) -> ActixResult<HttpResponse> {
+    USAGE_SERVICE.generate_reports(&req).await
+}
+
+pub async fn get_current_usage(
+    req: HttpRequest,
+    query: web::Query<CurrentUsageParams>,
+) -> ActixResult<HttpResponse> {
+    USAGE_SERVICE
+        .get_current_usage(query.into_inner(), &req)
+        .await
+}
+
+// 配置路由
+pub fn configure_usage_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/usage")
+            .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+            .wrap(middlewares::RequireJWT)
+            .route("/current", web::get().to(get_current_usage))
+            .route("/reports", web::get().to(list_usage_reports))
+            .route("/reports/generate", web::post().to(generate_usage_reports)),
+    );
+}
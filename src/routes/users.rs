@@ -3,12 +3,15 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 use once_cell::sync::Lazy;
 
 use crate::middlewares;
-use crate::models::users::entities::UserRole;
+use crate::models::users::entities::{AdminPermission, UserRole};
 use crate::models::users::requests::{
-    CreateUserRequest, ImportTemplateParams, UpdateUserRequest, UserExportParams, UserListParams,
+    BulkUserRequest, CreateRoleRequestRequest, CreateUserRequest, ImportTemplateParams,
+    LoginHistoryQuery, MergeUsersRequest, ReviewRoleRequestRequest, RoleRequestListParams,
+    SetStudentGoalRequest, UpdateAdminPermissionsRequest, UpdateUserRequest, UserExportParams,
+    UserListParams,
 };
 use crate::services::UserService;
-use crate::utils::SafeIDI64;
+use crate::utils::{SafeClassIdI64, SafeIDI64};
 
 // 懒加载的全局 UserService 实例
 static USER_SERVICE: Lazy<UserService> = Lazy::new(UserService::new_lazy);
@@ -46,6 +49,20 @@ pub async fn delete_user(req: HttpRequest, user_id: SafeIDI64) -> ActixResult<Ht
     USER_SERVICE.delete_user(user_id.0, &req).await
 }
 
+pub async fn bulk_users(
+    req: HttpRequest,
+    body: web::Json<BulkUserRequest>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE.bulk_users(body.into_inner(), &req).await
+}
+
+pub async fn merge_users(
+    req: HttpRequest,
+    body: web::Json<MergeUsersRequest>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE.merge_users(body.into_inner(), &req).await
+}
+
 pub async fn export_users(
     req: HttpRequest,
     query: web::Query<UserExportParams>,
@@ -67,25 +84,157 @@ pub async fn get_my_stats(req: HttpRequest) -> ActixResult<HttpResponse> {
     USER_SERVICE.get_my_stats(&req).await
 }
 
+pub async fn get_my_permissions(req: HttpRequest) -> ActixResult<HttpResponse> {
+    USER_SERVICE.get_my_permissions(&req).await
+}
+
+pub async fn get_user_permissions(
+    req: HttpRequest,
+    user_id: SafeIDI64,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE.get_user_permissions(user_id.0, &req).await
+}
+
+pub async fn update_user_permissions(
+    req: HttpRequest,
+    user_id: SafeIDI64,
+    body: web::Json<UpdateAdminPermissionsRequest>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .update_user_permissions(user_id.0, body.into_inner(), &req)
+        .await
+}
+
+pub async fn create_role_request(
+    req: HttpRequest,
+    body: web::Json<CreateRoleRequestRequest>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .create_role_request(body.into_inner(), &req)
+        .await
+}
+
+pub async fn list_my_role_requests(
+    req: HttpRequest,
+    query: web::Query<RoleRequestListParams>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .list_my_role_requests(query.into_inner(), &req)
+        .await
+}
+
+pub async fn list_role_requests(
+    req: HttpRequest,
+    query: web::Query<RoleRequestListParams>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .list_role_requests(query.into_inner(), &req)
+        .await
+}
+
+pub async fn list_login_history(
+    req: HttpRequest,
+    query: web::Query<LoginHistoryQuery>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .list_login_history(query.into_inner(), &req)
+        .await
+}
+
+pub async fn review_role_request(
+    req: HttpRequest,
+    id: SafeIDI64,
+    body: web::Json<ReviewRoleRequestRequest>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .review_role_request(id.0, body.into_inner(), &req)
+        .await
+}
+
+pub async fn list_my_goals(req: HttpRequest) -> ActixResult<HttpResponse> {
+    USER_SERVICE.list_my_goals(&req).await
+}
+
+pub async fn set_my_goal(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+    body: web::Json<SetStudentGoalRequest>,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE
+        .set_my_goal(class_id.0, body.into_inner(), &req)
+        .await
+}
+
+pub async fn delete_my_goal(
+    req: HttpRequest,
+    class_id: SafeClassIdI64,
+) -> ActixResult<HttpResponse> {
+    USER_SERVICE.delete_my_goal(class_id.0, &req).await
+}
+
 // 配置路由
 pub fn configure_user_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/users")
+        web::scope("/users")
             .wrap(middlewares::RequireJWT)
             // 所有登录用户可访问的路由
             .service(web::resource("/me/stats").route(web::get().to(get_my_stats)))
-            // 管理员专属路由
+            .service(web::resource("/me/permissions").route(web::get().to(get_my_permissions)))
+            // 角色申请 - 登录用户提交与查看自己的申请
+            .service(
+                web::resource("/me/role-requests")
+                    .route(web::get().to(list_my_role_requests))
+                    .route(web::post().to(create_role_request)),
+            )
+            // 学习目标 - 学生为所在班级设定目标得分率（业务层校验班级学生身份）
+            .service(web::resource("/me/goals").route(web::get().to(list_my_goals)))
+            .service(
+                web::resource("/me/goals/{class_id}")
+                    .route(web::put().to(set_my_goal))
+                    .route(web::delete().to(delete_my_goal)),
+            )
+            // 管理权限授予 - 查看需要用户管理权限，修改仅限系统管理员
+            .service(
+                web::resource("/{id}/permissions")
+                    .route(web::get().to(get_user_permissions).wrap(
+                        middlewares::RequirePermission::new(AdminPermission::UserManage),
+                    ))
+                    .route(
+                        web::put()
+                            .to(update_user_permissions)
+                            .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles())),
+                    ),
+            )
+            // 用户管理路由（管理员或被授予用户管理权限的用户）
             .service(
                 web::scope("")
-                    .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+                    .wrap(middlewares::RequirePermission::new(
+                        AdminPermission::UserManage,
+                    ))
                     .route("", web::get().to(list_users))
                     .route("", web::post().to(create_user))
+                    .route("/bulk", web::post().to(bulk_users))
                     .route("/export", web::get().to(export_users))
                     .route("/import", web::post().to(import_users))
                     .route("/import/template", web::get().to(download_import_template))
+                    // 登录历史与可疑登录（必须在 /{id} 之前注册）
+                    .route("/login-history", web::get().to(list_login_history))
+                    // 角色申请审核队列（必须在 /{id} 之前注册）
+                    .route("/role-requests", web::get().to(list_role_requests))
+                    .route(
+                        "/role-requests/{id}/review",
+                        web::post().to(review_role_request),
+                    )
                     .route("/{id}", web::get().to(get_user))
                     .route("/{id}", web::put().to(update_user))
                     .route("/{id}", web::delete().to(delete_user)),
             ),
     );
+    // 重复账号合并 - 仅管理员
+    cfg.service(
+        web::resource("/admin/users/merge")
+            .route(web::post().to(merge_users))
+            .wrap(middlewares::RequireRole::new_any(UserRole::admin_roles()))
+            .wrap(middlewares::RequireJWT),
+    );
 }
@@ -0,0 +1,37 @@
+/*!
+ * v2 API 路由
+ *
+ * v2 用于发布不兼容的响应结构变更。尚未改动的模块直接复用 v1 的处理器，
+ * 需要变更的模块在 `configure_v2_overrides` 中注册同前缀的 scope 即可覆盖 v1。
+ *
+ * 覆盖以 scope 为粒度：注册后 v1 中该前缀下的端点在 v2 中不再可达，
+ * 因此覆盖 scope 内需要完整注册该模块的全部端点（未变更的可直接指向 v1 处理器）。
+ *
+ * ## 新增 v2 端点
+ *
+ * ```rust,ignore
+ * fn configure_v2_overrides(cfg: &mut web::ServiceConfig) {
+ *     cfg.service(
+ *         web::scope("/homeworks")
+ *             .wrap(middlewares::RequireJWT)
+ *             .route("", web::get().to(list_homeworks_v2)),
+ *     );
+ * }
+ * ```
+ *
+ * 覆盖后可在 v1 对应端点上包裹
+ * `ApiVersionHeaders::new(ApiVersion::V1).deprecated(..)` 提示客户端迁移。
+ */
+
+use actix_web::web;
+
+use super::configure_v1_routes;
+
+/// v2 中结构有变化的端点（先注册，优先于 v1 复用的同路径端点匹配）
+fn configure_v2_overrides(_cfg: &mut web::ServiceConfig) {}
+
+/// 配置 v2 路由
+pub fn configure_v2_routes(cfg: &mut web::ServiceConfig) {
+    cfg.configure(configure_v2_overrides)
+        .configure(configure_v1_routes);
+}
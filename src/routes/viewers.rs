@@ -0,0 +1,95 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+use once_cell::sync::Lazy;
+
+use crate::middlewares;
+use crate::models::users::entities::UserRole;
+use crate::models::viewers::requests::{
+    AcceptViewerInvitationRequest, CreateViewerInvitationRequest,
+};
+use crate::services::ViewerService;
+
+// 懒加载的全局 VIEWER_SERVICE 实例
+static VIEWER_SERVICE: Lazy<ViewerService> = Lazy::new(ViewerService::new_lazy);
+
+// 创建查看邀请
+pub async fn create_invitation(
+    req: HttpRequest,
+    body: web::Json<CreateViewerInvitationRequest>,
+) -> ActixResult<HttpResponse> {
+    VIEWER_SERVICE
+        .create_invitation(&req, body.into_inner())
+        .await
+}
+
+// 列出查看授权
+pub async fn list_grants(req: HttpRequest) -> ActixResult<HttpResponse> {
+    VIEWER_SERVICE.list_grants(&req).await
+}
+
+// 撤销查看授权
+pub async fn revoke_grant(req: HttpRequest, path: web::Path<i64>) -> ActixResult<HttpResponse> {
+    VIEWER_SERVICE.revoke_grant(&req, path.into_inner()).await
+}
+
+// 接受查看邀请
+pub async fn accept_invitation(
+    req: HttpRequest,
+    body: web::Json<AcceptViewerInvitationRequest>,
+) -> ActixResult<HttpResponse> {
+    VIEWER_SERVICE
+        .accept_invitation(&req, body.into_inner())
+        .await
+}
+
+// 列出可查看的学生
+pub async fn list_students(req: HttpRequest) -> ActixResult<HttpResponse> {
+    VIEWER_SERVICE.list_students(&req).await
+}
+
+// 查看学生每周汇总
+pub async fn get_student_summary(
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> ActixResult<HttpResponse> {
+    VIEWER_SERVICE
+        .get_student_summary(&req, path.into_inner())
+        .await
+}
+
+// 配置路由
+pub fn configure_viewers_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/viewer")
+            .wrap(middlewares::RequireJWT)
+            // 学生管理自己发出的查看授权
+            .route(
+                "/invitations",
+                web::post()
+                    .to(create_invitation)
+                    .wrap(middlewares::RequireRole::new(&UserRole::User)),
+            )
+            .route(
+                "/grants",
+                web::get()
+                    .to(list_grants)
+                    .wrap(middlewares::RequireRole::new(&UserRole::User)),
+            )
+            .route(
+                "/grants/{grant_id}",
+                web::delete()
+                    .to(revoke_grant)
+                    .wrap(middlewares::RequireRole::new(&UserRole::User)),
+            )
+            // 家长/导师只读查看（授权在 service 层按学生逐一验证）
+            .service(
+                web::scope("")
+                    .wrap(middlewares::RequireRole::new_any(UserRole::viewer_roles()))
+                    .route("/invitations/accept", web::post().to(accept_invitation))
+                    .route("/students", web::get().to(list_students))
+                    .route(
+                        "/students/{student_id}/summary",
+                        web::get().to(get_student_summary),
+                    ),
+            ),
+    );
+}
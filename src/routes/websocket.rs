@@ -1,104 +1,67 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 
-use crate::cache::{CacheResult, ObjectCache};
+use crate::config::AppConfig;
 use crate::middlewares::{self, RateLimit};
 use crate::models::system::requests::WsQuery;
 use crate::models::system::responses::WebSocketStatusResponse;
-use crate::models::users::entities::User;
 use crate::models::{ApiResponse, ErrorCode};
-use crate::services::websocket::WebSocketService;
+use crate::services::outbound;
+use crate::services::websocket::{self, WebSocketService, WsCompression, WsOptions};
 use crate::storage::Storage;
+use crate::utils::jwt::ACCESS_TOKEN_COOKIE;
+use crate::utils::password::constant_time_eq;
 use std::sync::Arc;
 
 /// WebSocket 连接处理
+///
+/// 推荐连接后通过 `auth` 消息认证；URL 中携带 `token` 的旧方式仍然兼容。
+/// 使用 Cookie 认证的浏览器客户端在握手时自动携带 `access_token` Cookie。
 pub async fn ws_handler(
     req: HttpRequest,
     query: web::Query<WsQuery>,
     body: web::Payload,
 ) -> ActixResult<HttpResponse> {
-    // 从 query 参数获取 token
-    let token = &query.token;
+    let storage = req
+        .app_data::<web::Data<Arc<dyn Storage>>>()
+        .expect("Storage not found")
+        .get_ref()
+        .clone();
 
-    // 验证 token
-    let user = match validate_token_and_get_user(&req, token).await {
-        Ok(user) => user,
-        Err(response) => return Ok(response),
+    // 兼容旧客户端：在升级前校验 query 中的 token；Cookie 认证时使用 Cookie 中的令牌
+    let cookie_token = req
+        .cookie(ACCESS_TOKEN_COOKIE)
+        .map(|c| c.value().to_string());
+    let auth = match query.token.as_deref().or(cookie_token.as_deref()) {
+        Some(token) => match websocket::auth::authenticate(&storage, token).await {
+            Ok(auth) => Some(auth),
+            Err(message) => {
+                return Ok(
+                    HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
+                        ErrorCode::Unauthorized,
+                        message,
+                    )),
+                );
+            }
+        },
+        None => None,
     };
 
     // 升级到 WebSocket
     let (response, session, stream) = actix_ws::handle(&req, body)?;
 
     // 在后台任务中处理 WebSocket 连接
+    let options = WsOptions {
+        replay_since: query.since,
+        batch: query.batch.unwrap_or(false),
+        compression: WsCompression::negotiate(query.compression.as_deref()),
+    };
     actix_web::rt::spawn(async move {
-        WebSocketService::handle_connection(user.id, session, stream).await;
+        WebSocketService::handle_connection(storage, auth, options, session, stream).await;
     });
 
     Ok(response)
 }
 
-/// 验证 token 并获取用户
-async fn validate_token_and_get_user(req: &HttpRequest, token: &str) -> Result<User, HttpResponse> {
-    // 验证 JWT token
-    crate::utils::jwt::JwtUtils::verify_access_token(token).map_err(|_| {
-        HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
-            ErrorCode::Unauthorized,
-            "Invalid token",
-        ))
-    })?;
-
-    // 尝试从缓存获取用户
-    let cache = req
-        .app_data::<web::Data<Arc<dyn ObjectCache>>>()
-        .expect("Cache not found")
-        .get_ref()
-        .clone();
-
-    if let CacheResult::Found(json) = cache.get_raw(&format!("user:{token}")).await
-        && let Ok(user) = serde_json::from_str::<User>(&json)
-    {
-        return Ok(user);
-    }
-
-    // 从数据库获取用户
-    let storage = req
-        .app_data::<web::Data<Arc<dyn Storage>>>()
-        .expect("Storage not found")
-        .get_ref()
-        .clone();
-
-    let claims = crate::utils::jwt::JwtUtils::decode_token(token).map_err(|_| {
-        HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
-            ErrorCode::Unauthorized,
-            "Invalid token format",
-        ))
-    })?;
-
-    let user_id = claims.sub.parse::<i64>().map_err(|_| {
-        HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
-            ErrorCode::Unauthorized,
-            "Invalid user ID",
-        ))
-    })?;
-
-    let user = storage
-        .get_user_by_id(user_id)
-        .await
-        .map_err(|_| {
-            HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                ErrorCode::InternalServerError,
-                "Failed to get user",
-            ))
-        })?
-        .ok_or_else(|| {
-            HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
-                ErrorCode::Unauthorized,
-                "User not found",
-            ))
-        })?;
-
-    Ok(user)
-}
-
 /// WebSocket 状态端点
 pub async fn ws_status() -> ActixResult<HttpResponse> {
     let online_count = crate::services::websocket::get_online_count();
@@ -106,15 +69,56 @@ pub async fn ws_status() -> ActixResult<HttpResponse> {
         WebSocketStatusResponse {
             online_users: online_count,
             status: "ok".to_string(),
+            metrics: websocket::ws_metrics(),
+            connections: websocket::connection_metrics(),
         },
         "WebSocket 服务正常",
     )))
 }
 
+/// Prometheus 抓取端点
+///
+/// 使用配置 `websocket.metrics_token` 作为 Bearer 令牌认证，未配置时端点不可用。
+/// 同时导出外部 HTTP 调用（`hwsystem_outbound_*`）的统计。
+pub async fn ws_metrics(req: HttpRequest) -> ActixResult<HttpResponse> {
+    let expected = &AppConfig::get().websocket.metrics_token;
+    if expected.is_empty() {
+        return Ok(
+            HttpResponse::NotFound().json(ApiResponse::<()>::error_empty(
+                ErrorCode::NotFound,
+                "WebSocket metrics endpoint is disabled",
+            )),
+        );
+    }
+
+    let authorized = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()));
+    if !authorized {
+        return Ok(
+            HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
+                ErrorCode::Unauthorized,
+                "Invalid metrics token",
+            )),
+        );
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(format!(
+            "{}{}",
+            websocket::metrics::render_prometheus(),
+            outbound::metrics::render_prometheus()
+        )))
+}
+
 /// 配置 WebSocket 路由
 pub fn configure_websocket_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/api/v1/ws")
+        web::scope("/ws")
             // WebSocket 连接 - 添加速率限制防止 DDoS（20次/分钟/IP）
             .route(
                 "",
@@ -126,6 +130,8 @@ pub fn configure_websocket_routes(cfg: &mut web::ServiceConfig) {
             .route(
                 "/status",
                 web::get().to(ws_status).wrap(middlewares::RequireJWT),
-            ),
+            )
+            // Prometheus 抓取端点 - 使用独立的抓取令牌
+            .route("/metrics", web::get().to(ws_metrics)),
     );
 }
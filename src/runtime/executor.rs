@@ -0,0 +1,274 @@
+//! 后台任务执行器
+//!
+//! 后台任务按用途提交到命名队列（如 `exports`、`notifications`），每个队列有独立的
+//! 并发上限与积压上限（配置见 `[job_queues]`）。积压（排队 + 执行中）达到上限时，
+//! 按 `overflow` 立即拒绝或等待空位；各队列的积压与计数可通过 [`queue_stats`] 查看。
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use tokio::sync::{Notify, Semaphore};
+
+use crate::config::{AppConfig, JobQueuesConfig, QueueOverflow};
+use crate::models::jobs::entities::JobQueueStats;
+
+/// 导出类任务（成绩单、压测数据等，结果通过任务接口下载）
+pub const EXPORTS_QUEUE: &str = "exports";
+/// 站内通知发送
+pub const NOTIFICATIONS_QUEUE: &str = "notifications";
+/// 结业证书颁发检查
+pub const CERTIFICATES_QUEUE: &str = "certificates";
+
+/// 已创建的队列，首次使用时按配置创建
+static QUEUES: Lazy<DashMap<String, Arc<JobQueue>>> = Lazy::new(DashMap::new);
+
+/// 队列积压已达上限
+#[derive(Debug, Clone)]
+pub struct QueueFull {
+    pub queue: String,
+}
+
+impl fmt::Display for QueueFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "任务队列 {} 繁忙，请稍后重试", self.queue)
+    }
+}
+
+impl std::error::Error for QueueFull {}
+
+/// 命名任务队列
+pub struct JobQueue {
+    name: String,
+    concurrency: usize,
+    max_depth: usize,
+    overflow: QueueOverflow,
+    max_wait: Duration,
+    workers: Arc<Semaphore>,
+    // 排队 + 执行中的任务数
+    depth: AtomicUsize,
+    running: AtomicUsize,
+    submitted: AtomicUsize,
+    completed: AtomicUsize,
+    rejected: AtomicUsize,
+    // 任务结束时唤醒一个等待空位的提交方
+    released: Notify,
+}
+
+/// 任务结束（含 panic）时释放队列占用
+struct Finished(Arc<JobQueue>);
+
+impl Drop for Finished {
+    fn drop(&mut self) {
+        let queue = &self.0;
+        queue.running.fetch_sub(1, Ordering::SeqCst);
+        queue.depth.fetch_sub(1, Ordering::SeqCst);
+        queue.completed.fetch_add(1, Ordering::Relaxed);
+        queue.released.notify_one();
+    }
+}
+
+impl JobQueue {
+    fn new(
+        name: &str,
+        concurrency: usize,
+        max_depth: usize,
+        overflow: QueueOverflow,
+        max_wait: Duration,
+    ) -> Self {
+        let concurrency = concurrency.max(1);
+        Self {
+            name: name.to_string(),
+            concurrency,
+            max_depth: max_depth.max(concurrency),
+            overflow,
+            max_wait,
+            workers: Arc::new(Semaphore::new(concurrency)),
+            depth: AtomicUsize::new(0),
+            running: AtomicUsize::new(0),
+            submitted: AtomicUsize::new(0),
+            completed: AtomicUsize::new(0),
+            rejected: AtomicUsize::new(0),
+            released: Notify::new(),
+        }
+    }
+
+    fn from_config(name: &str, config: &JobQueuesConfig) -> Self {
+        let overrides = config.queues.get(name);
+        Self::new(
+            name,
+            overrides
+                .and_then(|o| o.concurrency)
+                .unwrap_or(config.concurrency),
+            overrides
+                .and_then(|o| o.max_depth)
+                .unwrap_or(config.max_depth),
+            overrides
+                .and_then(|o| o.overflow)
+                .unwrap_or(config.overflow),
+            Duration::from_secs(
+                overrides
+                    .and_then(|o| o.max_wait)
+                    .unwrap_or(config.max_wait),
+            ),
+        )
+    }
+
+    /// 积压未达上限时占用一个位置
+    fn try_reserve(&self) -> bool {
+        self.depth
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |depth| {
+                (depth < self.max_depth).then_some(depth + 1)
+            })
+            .is_ok()
+    }
+
+    async fn reserve(&self) -> Result<(), QueueFull> {
+        if self.try_reserve() {
+            return Ok(());
+        }
+
+        if self.overflow == QueueOverflow::Delay {
+            let deadline = tokio::time::Instant::now() + self.max_wait;
+            loop {
+                let released = self.released.notified();
+                if self.try_reserve() {
+                    return Ok(());
+                }
+                if tokio::time::timeout_at(deadline, released).await.is_err() {
+                    break;
+                }
+            }
+            if self.try_reserve() {
+                return Ok(());
+            }
+        }
+
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+        Err(QueueFull {
+            queue: self.name.clone(),
+        })
+    }
+
+    /// 提交任务；积压达到上限时按队列策略拒绝或等待空位
+    pub async fn submit<F>(self: &Arc<Self>, task: F) -> Result<(), QueueFull>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.reserve().await?;
+        self.submitted.fetch_add(1, Ordering::Relaxed);
+
+        let queue = self.clone();
+        tokio::spawn(async move {
+            // 信号量不会被关闭
+            let Ok(_permit) = queue.workers.clone().acquire_owned().await else {
+                return;
+            };
+            queue.running.fetch_add(1, Ordering::SeqCst);
+            let _finished = Finished(queue.clone());
+            task.await;
+        });
+
+        Ok(())
+    }
+
+    /// 队列当前状态
+    pub fn stats(&self) -> JobQueueStats {
+        let depth = self.depth.load(Ordering::SeqCst);
+        let running = self.running.load(Ordering::SeqCst);
+        JobQueueStats {
+            name: self.name.clone(),
+            concurrency: self.concurrency,
+            max_depth: self.max_depth,
+            overflow: self.overflow.to_string(),
+            depth,
+            running,
+            queued: depth.saturating_sub(running),
+            submitted: self.submitted.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 获取命名队列，首次使用时按配置创建
+pub fn queue(name: &str) -> Arc<JobQueue> {
+    QUEUES
+        .entry(name.to_string())
+        .or_insert_with(|| Arc::new(JobQueue::from_config(name, &AppConfig::get().job_queues)))
+        .clone()
+}
+
+/// 提交不关心结果的任务（如发送通知），队列繁忙被拒绝时只记录日志
+pub fn spawn<F>(queue_name: &str, task: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let queue = queue(queue_name);
+    tokio::spawn(async move {
+        if let Err(e) = queue.submit(task).await {
+            tracing::warn!("Background task dropped: {e}");
+        }
+    });
+}
+
+/// 所有已创建队列的状态（按队列名排序）
+pub fn queue_stats() -> Vec<JobQueueStats> {
+    let mut stats: Vec<JobQueueStats> = QUEUES.iter().map(|queue| queue.stats()).collect();
+    stats.sort_by(|a, b| a.name.cmp(&b.name));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_queue(overflow: QueueOverflow) -> Arc<JobQueue> {
+        Arc::new(JobQueue::new(
+            "test",
+            1,
+            2,
+            overflow,
+            Duration::from_millis(50),
+        ))
+    }
+
+    #[actix_web::test]
+    async fn test_reject_when_queue_full() {
+        let queue = test_queue(QueueOverflow::Reject);
+        let gate = Arc::new(Notify::new());
+
+        for _ in 0..2 {
+            let gate = gate.clone();
+            queue
+                .submit(async move { gate.notified().await })
+                .await
+                .unwrap();
+        }
+        assert!(queue.submit(async {}).await.is_err());
+
+        let stats = queue.stats();
+        assert_eq!(stats.depth, 2);
+        assert_eq!(stats.submitted, 2);
+        assert_eq!(stats.rejected, 1);
+    }
+
+    #[actix_web::test]
+    async fn test_delay_waits_for_free_slot() {
+        let queue = test_queue(QueueOverflow::Delay);
+
+        for _ in 0..2 {
+            queue
+                .submit(tokio::time::sleep(Duration::from_millis(10)))
+                .await
+                .unwrap();
+        }
+        // 前面的任务在等待时间内完成，新任务被接受
+        queue.submit(async {}).await.unwrap();
+        assert_eq!(queue.stats().rejected, 0);
+    }
+}
@@ -1,8 +1,16 @@
+use crate::cache::invalidation::spawn_pg_invalidation_listener;
 use crate::cache::{ObjectCache, register::get_object_cache_plugin};
 use crate::config::AppConfig;
 use crate::models::users::entities::UserRole;
 use crate::models::users::requests::CreateUserRequest;
-use crate::services::system::DynamicConfig;
+use crate::services::classes::{spawn_class_stats_snapshot_job, spawn_scheduled_export_job};
+use crate::services::files::{spawn_file_retention_job, spawn_file_tiering_job};
+use crate::services::grades::{spawn_grade_draft_cleanup_job, spawn_grading_sla_job};
+use crate::services::homeworks::{spawn_missing_grade_job, spawn_solution_reveal_job};
+use crate::services::im_delivery::spawn_im_delivery_worker;
+use crate::services::outbox::spawn_outbox_relay;
+use crate::services::system::{DynamicConfig, propagation};
+use crate::services::usage::spawn_usage_report_job;
 use crate::storage::Storage;
 use crate::utils::password::hash_password;
 use std::sync::Arc;
@@ -31,7 +39,7 @@ async fn create_cache() -> Result<Arc<dyn ObjectCache>, Box<dyn std::error::Erro
                 warn!("Failed to create {} cache: {}", cache_type, e);
 
                 // 如果配置的缓存失败，尝试回退策略
-                if cache_type == "redis" {
+                if cache_type == "redis" || cache_type == "tiered" {
                     warn!("Falling back to memory cache");
                     if let Some(fallback_constructor) = get_object_cache_plugin("moka") {
                         match fallback_constructor().await {
@@ -154,6 +162,7 @@ async fn seed_admin(storage: &Arc<dyn Storage>) {
         role: UserRole::Admin,
         display_name: Some("Administrator".to_string()),
         avatar_url: None,
+        org_id: None,
     };
 
     match storage.create_user(admin_request).await {
@@ -186,15 +195,52 @@ pub async fn prepare_server_startup() -> StartupContext {
         .expect("Failed to create storage backend");
     warn!("Storage backend initialized and migrations completed");
 
-    // 初始化动态配置缓存
+    // 初始化动态配置缓存（先注册设置监听器，使子系统在加载时即收到数据库中的设置）
+    propagation::register_setting_listeners();
     init_dynamic_config(&storage).await;
 
     // 初始化默认管理员账号（如果需要）
     seed_admin(&storage).await;
 
+    // 启动每日用量报表任务
+    spawn_usage_report_job(storage.clone());
+
+    // 启动每日班级统计快照任务
+    spawn_class_stats_snapshot_job(storage.clone());
+
+    // 启动参考答案定时公开任务
+    spawn_solution_reveal_job(storage.clone());
+
+    // 启动缺交零分任务
+    spawn_missing_grade_job(storage.clone());
+
+    // 启动批改时限提醒任务
+    spawn_grading_sla_job(storage.clone());
+
+    // 启动评分草稿清理任务
+    spawn_grade_draft_cleanup_job(storage.clone());
+
+    // 启动文件保留清理任务
+    spawn_file_retention_job(storage.clone());
+
+    // 启动附件冷存储迁移任务
+    spawn_file_tiering_job(storage.clone());
+
+    // 启动班级 IM 消息投递任务
+    spawn_im_delivery_worker(storage.clone());
+
+    // 启动班级定时导出任务
+    spawn_scheduled_export_job(storage.clone());
+
+    // 启动事务发件箱转发任务
+    spawn_outbox_relay(storage.clone());
+
     // 创建缓存实例
     let cache = create_cache().await.expect("Failed to create cache");
     warn!("Cache backend initialized");
 
+    // 启动 PostgreSQL 跨节点缓存失效监听（未启用时不做任何事）
+    spawn_pg_invalidation_listener(storage.clone(), cache.clone());
+
     StartupContext { storage, cache }
 }
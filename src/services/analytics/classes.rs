@@ -0,0 +1,87 @@
+//! 班级对比分析
+//!
+//! 管理员按时间区间对比各班级的提交率、平均得分率、批改耗时与迟交率，
+//! 结果按租户范围与日期区间缓存，避免反复执行聚合查询。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::Utc;
+
+use super::{AnalyticsRange, AnalyticsService, scope_key};
+use crate::cache::traits::TypedObjectCache;
+use crate::cache::{CacheResult, ObjectCache};
+use crate::middlewares::TenantGuard;
+use crate::models::analytics::requests::AnalyticsRangeParams;
+use crate::models::analytics::responses::ClassComparisonResponse;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 结果缓存时间（秒）
+const COMPARISON_CACHE_TTL: u64 = 300;
+
+pub async fn compare_classes(
+    service: &AnalyticsService,
+    request: &HttpRequest,
+    query: AnalyticsRangeParams,
+) -> ActixResult<HttpResponse> {
+    let range = match AnalyticsRange::resolve(&query) {
+        Ok(range) => range,
+        Err(resp) => return Ok(resp),
+    };
+
+    let scope = TenantGuard::scope(request);
+    let key = format!(
+        "analytics:classes:{}:{}:{}",
+        scope_key(scope),
+        range.from,
+        range.to
+    );
+    let cache = request
+        .app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>()
+        .map(|c| c.get_ref().clone());
+
+    if let Some(cache) = &cache
+        && let CacheResult::Found(response) = cache.get::<ClassComparisonResponse>(&key).await
+    {
+        return Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")));
+    }
+
+    let storage = service.get_storage(request);
+    let mut items = match storage
+        .get_class_comparison(scope, range.from_ts, range.to_ts)
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计班级数据失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 提交率最低的班级排在前面，没有作业的班级排在最后
+    items.sort_by(|a, b| match (a.submission_rate, b.submission_rate) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.class_id.cmp(&b.class_id),
+    });
+
+    let response = ClassComparisonResponse {
+        from: range.from.to_string(),
+        to: range.to.to_string(),
+        items,
+        generated_at: Utc::now(),
+    };
+
+    if let Some(cache) = &cache {
+        cache
+            .insert(key, response.clone(), COMPARISON_CACHE_TTL)
+            .await;
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+}
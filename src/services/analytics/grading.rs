@@ -0,0 +1,72 @@
+//! 教师批改时效统计
+//!
+//! 管理员按时间区间查看各教师的评分数与平均批改耗时（提交到评分），
+//! 并附带当前超过批改时限（系统设置 `grading.sla_days`）仍未批改的提交数。
+//! 超时数反映实时状态，因此结果不做缓存。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::Utc;
+
+use super::{AnalyticsRange, AnalyticsService};
+use crate::middlewares::TenantGuard;
+use crate::models::analytics::requests::AnalyticsRangeParams;
+use crate::models::analytics::responses::GradingLatencyResponse;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::system::DynamicConfig;
+
+pub async fn grading_latency(
+    service: &AnalyticsService,
+    request: &HttpRequest,
+    query: AnalyticsRangeParams,
+) -> ActixResult<HttpResponse> {
+    let range = match AnalyticsRange::resolve(&query) {
+        Ok(range) => range,
+        Err(resp) => return Ok(resp),
+    };
+
+    let sla_days = DynamicConfig::grading_sla_days().await;
+    let cutoff = Utc::now().timestamp() - sla_days * 86400;
+
+    let storage = service.get_storage(request);
+    let mut items = match storage
+        .get_teacher_grading_latency(
+            TenantGuard::scope(request),
+            range.from_ts,
+            range.to_ts,
+            cutoff,
+        )
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计批改时效失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 超时未批改最多的教师排在前面，其次按平均批改耗时降序
+    items.sort_by(|a, b| {
+        b.overdue_count
+            .cmp(&a.overdue_count)
+            .then_with(|| {
+                let a_hours = a.average_grading_hours.unwrap_or(f64::NEG_INFINITY);
+                let b_hours = b.average_grading_hours.unwrap_or(f64::NEG_INFINITY);
+                b_hours.total_cmp(&a_hours)
+            })
+            .then_with(|| a.teacher_id.cmp(&b.teacher_id))
+    });
+
+    let response = GradingLatencyResponse {
+        from: range.from.to_string(),
+        to: range.to.to_string(),
+        sla_days,
+        items,
+        generated_at: Utc::now(),
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+}
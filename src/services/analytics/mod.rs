@@ -0,0 +1,124 @@
+pub mod classes;
+pub mod grading;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::{Duration, NaiveDate, Utc};
+use std::sync::Arc;
+
+use crate::models::analytics::requests::AnalyticsRangeParams;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+/// 默认统计天数
+const DEFAULT_RANGE_DAYS: i64 = 30;
+/// 最大统计天数
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// 解析后的统计区间（日期含首尾）
+pub(crate) struct AnalyticsRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// 起始日期 00:00:00 的时间戳
+    pub from_ts: i64,
+    /// 结束日期 23:59:59 的时间戳
+    pub to_ts: i64,
+}
+
+impl AnalyticsRange {
+    /// 解析查询参数，默认统计截至今天的最近 30 天
+    pub(crate) fn resolve(query: &AnalyticsRangeParams) -> Result<Self, HttpResponse> {
+        let to = parse_date(query.to.as_deref())?.unwrap_or_else(|| Utc::now().date_naive());
+        let from = parse_date(query.from.as_deref())?
+            .unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+        if from > to {
+            return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::BadRequest,
+                "起始日期不能晚于结束日期",
+            )));
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::BadRequest,
+                format!("统计区间不能超过 {MAX_RANGE_DAYS} 天"),
+            )));
+        }
+
+        Ok(Self {
+            from,
+            to,
+            from_ts: from
+                .and_hms_opt(0, 0, 0)
+                .unwrap_or_default()
+                .and_utc()
+                .timestamp(),
+            to_ts: to
+                .and_hms_opt(23, 59, 59)
+                .unwrap_or_default()
+                .and_utc()
+                .timestamp(),
+        })
+    }
+}
+
+fn parse_date(value: Option<&str>) -> Result<Option<NaiveDate>, HttpResponse> {
+    value
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::BadRequest,
+                    format!("日期格式无效: {date}，应为 YYYY-MM-DD"),
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// 租户范围在缓存键中的表示
+pub(crate) fn scope_key(scope: TenantScope) -> String {
+    match scope {
+        TenantScope::All => "all".to_string(),
+        TenantScope::Org(Some(org_id)) => org_id.to_string(),
+        TenantScope::Org(None) => "default".to_string(),
+    }
+}
+
+pub struct AnalyticsService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl AnalyticsService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    // 班级对比分析
+    pub async fn compare_classes(
+        &self,
+        request: &HttpRequest,
+        query: AnalyticsRangeParams,
+    ) -> ActixResult<HttpResponse> {
+        classes::compare_classes(self, request, query).await
+    }
+
+    // 教师批改时效统计
+    pub async fn grading_latency(
+        &self,
+        request: &HttpRequest,
+        query: AnalyticsRangeParams,
+    ) -> ActixResult<HttpResponse> {
+        grading::grading_latency(self, request, query).await
+    }
+}
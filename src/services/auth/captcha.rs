@@ -0,0 +1,185 @@
+//! 人机验证
+//!
+//! 服务商与密钥存储在系统设置（`captcha.*`）中；注册由 `registration.captcha_required`
+//! 控制，登录按 `captcha.login_mode` 在连续失败后逐步要求验证。
+//! 服务间调用可携带 `X-Captcha-Bypass` 头（见配置 `captcha.bypass_token`）跳过验证。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use serde_json::Value;
+
+use crate::cache::traits::TypedObjectCache;
+use crate::cache::{CacheResult, ObjectCache};
+use crate::config::AppConfig;
+use crate::models::system::entities::{CaptchaProvider, LoginCaptchaMode};
+use crate::models::system::responses::CaptchaConfigResponse;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::outbound::CAPTCHA_CLIENT;
+use crate::services::system::DynamicConfig;
+use crate::utils::ClientInfo;
+use crate::utils::password::constant_time_eq;
+
+/// 服务间调用免验证请求头
+const BYPASS_HEADER: &str = "X-Captcha-Bypass";
+
+/// 登录失败计数的保留时间（秒），期间无新失败则自动清零
+const LOGIN_FAILURE_TTL: u64 = 15 * 60;
+
+/// 获取人机验证配置（公开）
+pub async fn get_captcha_config(_req: HttpRequest) -> ActixResult<HttpResponse> {
+    let response = CaptchaConfigResponse {
+        provider: DynamicConfig::captcha_provider().await,
+        site_key: DynamicConfig::captcha_site_key().await,
+        login_mode: DynamicConfig::captcha_login_mode().await,
+        login_failure_threshold: DynamicConfig::captcha_login_failure_threshold().await,
+        register_required: DynamicConfig::registration_captcha_required().await,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        response,
+        "Captcha config retrieved successfully",
+    )))
+}
+
+/// 校验人机验证令牌，未启用服务商或服务间调用时直接通过
+pub async fn verify_captcha(
+    request: &HttpRequest,
+    token: Option<&str>,
+) -> Result<(), HttpResponse> {
+    let provider = DynamicConfig::captcha_provider().await;
+    let Some(verify_url) = provider.verify_url() else {
+        return Ok(());
+    };
+    if is_service_bypass(request) {
+        return Ok(());
+    }
+
+    let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+        return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::CaptchaRequired,
+            "请完成人机验证",
+        )));
+    };
+
+    let unavailable = |message: String| {
+        HttpResponse::ServiceUnavailable().json(ApiResponse::error_empty(
+            ErrorCode::CaptchaUnavailable,
+            message,
+        ))
+    };
+
+    let Some(secret) = DynamicConfig::captcha_secret_key().await else {
+        tracing::error!("人机验证已启用（{provider}）但未配置 captcha.secret_key");
+        return Err(unavailable("人机验证服务未正确配置".to_string()));
+    };
+
+    let mut form = vec![("secret", secret), ("response", token.to_string())];
+    if let Some(ip) = ClientInfo::from_request(request).ip_string() {
+        form.push(("remoteip", ip));
+    }
+
+    let payload: Value = match CAPTCHA_CLIENT
+        .send(|client| client.post(verify_url).form(&form))
+        .await
+    {
+        Ok(response) => response.json().await.unwrap_or(Value::Null),
+        Err(e) => {
+            tracing::warn!("调用 {provider} 校验接口失败: {e}");
+            return Err(unavailable(
+                "人机验证服务暂时不可用，请稍后重试".to_string(),
+            ));
+        }
+    };
+
+    if payload["success"].as_bool() == Some(true) {
+        Ok(())
+    } else {
+        tracing::debug!("人机验证未通过: {}", payload["error-codes"]);
+        Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::CaptchaInvalid,
+            "人机验证未通过，请重试",
+        )))
+    }
+}
+
+/// 本次登录是否需要人机验证
+pub async fn login_requires_captcha(request: &HttpRequest, username: &str) -> bool {
+    if DynamicConfig::captcha_provider().await == CaptchaProvider::None {
+        return false;
+    }
+    match DynamicConfig::captcha_login_mode().await {
+        LoginCaptchaMode::Off => false,
+        LoginCaptchaMode::Always => true,
+        LoginCaptchaMode::AfterFailures => {
+            let Some(cache) = cache(request) else {
+                return false;
+            };
+            let threshold = DynamicConfig::captcha_login_failure_threshold().await;
+            for key in failure_keys(request, username) {
+                if failure_count(&cache, &key).await >= threshold {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// 记录一次登录失败（按客户端 IP 与账号分别计数）
+pub async fn record_login_failure(request: &HttpRequest, username: &str) {
+    let Some(cache) = cache(request) else {
+        return;
+    };
+    for key in failure_keys(request, username) {
+        let count = failure_count(&cache, &key).await + 1;
+        cache.insert(key, count, LOGIN_FAILURE_TTL).await;
+    }
+}
+
+/// 登录成功后清除失败计数
+pub async fn clear_login_failures(request: &HttpRequest, username: &str) {
+    let Some(cache) = cache(request) else {
+        return;
+    };
+    for key in failure_keys(request, username) {
+        cache.remove(&key).await;
+    }
+}
+
+/// 服务间调用携带正确的免验证令牌
+fn is_service_bypass(request: &HttpRequest) -> bool {
+    let expected = &AppConfig::get().captcha.bypass_token;
+    if expected.is_empty() {
+        return false;
+    }
+    request
+        .headers()
+        .get(BYPASS_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| constant_time_eq(v.as_bytes(), expected.as_bytes()))
+}
+
+fn cache(request: &HttpRequest) -> Option<Arc<dyn ObjectCache>> {
+    request
+        .app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>()
+        .map(|c| c.get_ref().clone())
+}
+
+fn failure_keys(request: &HttpRequest, username: &str) -> Vec<String> {
+    let mut keys = vec![format!(
+        "captcha:login_failures:user:{}",
+        username.trim().to_lowercase()
+    )];
+    if let Some(ip) = ClientInfo::from_request(request).ip_string() {
+        keys.push(format!("captcha:login_failures:ip:{ip}"));
+    }
+    keys
+}
+
+async fn failure_count(cache: &Arc<dyn ObjectCache>, key: &str) -> i64 {
+    match cache.get::<i64>(key).await {
+        CacheResult::Found(count) => count,
+        _ => 0,
+    }
+}
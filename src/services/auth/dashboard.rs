@@ -0,0 +1,82 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use super::AuthService;
+use crate::cache::traits::TypedObjectCache;
+use crate::cache::{CacheResult, ObjectCache};
+use crate::middlewares::RequireJWT;
+use crate::models::auth::responses::StudentDashboard;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::users::stats::project_goal;
+
+/// 即将截止作业的时间窗口（天）
+const UPCOMING_DAYS: i64 = 7;
+/// 最近评分条数
+const RECENT_GRADE_LIMIT: u64 = 5;
+/// 仪表盘缓存时间（秒），短时间内重复刷新首页时直接返回
+const DASHBOARD_CACHE_TTL: u64 = 30;
+
+pub(crate) fn cache_key(user_id: i64) -> String {
+    format!("dashboard:{user_id}")
+}
+
+pub async fn handle_get_dashboard(
+    service: &AuthService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let cache = request
+        .app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>()
+        .map(|c| c.get_ref().clone());
+
+    if let Some(cache) = &cache
+        && let CacheResult::Found(dashboard) =
+            cache.get::<StudentDashboard>(&cache_key(user_id)).await
+    {
+        return Ok(HttpResponse::Ok().json(ApiResponse::success(dashboard, "查询成功")));
+    }
+
+    let storage = service.get_storage(request);
+    let upcoming_until = (Utc::now() + Duration::days(UPCOMING_DAYS)).timestamp();
+    let mut dashboard = match storage
+        .get_student_dashboard(user_id, upcoming_until, RECENT_GRADE_LIMIT)
+        .await
+    {
+        Ok(dashboard) => dashboard,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("获取仪表盘数据失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    dashboard.goals = match storage.list_goal_standings(user_id).await {
+        Ok(standings) => standings.into_iter().map(project_goal).collect(),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("获取学习目标进度失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if let Some(cache) = &cache {
+        cache
+            .insert(cache_key(user_id), dashboard.clone(), DASHBOARD_CACHE_TTL)
+            .await;
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(dashboard, "查询成功")))
+}
@@ -0,0 +1,187 @@
+//! 本人上传的文件
+//!
+//! 列出当前用户上传的全部文件及其被引用的位置（作业、提交、头像等），汇总存储用量；
+//! 未被任何位置引用的文件可由本人删除以释放空间。
+
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::AuthService;
+use crate::middlewares::RequireJWT;
+use crate::models::common::{PaginationInfo, PaginationPolicy, page_size_exceeded};
+use crate::models::files::entities::{File, FileReference, FileReferenceKind};
+use crate::models::files::requests::MyFileListQuery;
+use crate::models::files::responses::{
+    FileUsageBreakdown, FileUsageSummary, MyFileItem, MyFileListResponse,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::files::retention_job::remove_stored_files;
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+        ErrorCode::Unauthorized,
+        "无法获取用户信息",
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 汇总存储用量，同一文件被多类位置引用时在每一类中都计入
+fn usage_summary(files: &[File], refs: &HashMap<i64, Vec<FileReference>>) -> FileUsageSummary {
+    let mut summary = FileUsageSummary {
+        file_count: files.len() as i64,
+        total_size: 0,
+        unreferenced_count: 0,
+        unreferenced_size: 0,
+        breakdown: Vec::new(),
+    };
+    for file in files {
+        summary.total_size += file.file_size;
+        let Some(file_refs) = refs.get(&file.id) else {
+            summary.unreferenced_count += 1;
+            summary.unreferenced_size += file.file_size;
+            continue;
+        };
+        let mut kinds: Vec<FileReferenceKind> = Vec::new();
+        for kind in file_refs.iter().map(|r| r.kind) {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        for kind in kinds {
+            match summary.breakdown.iter_mut().find(|b| b.kind == kind) {
+                Some(entry) => {
+                    entry.file_count += 1;
+                    entry.total_size += file.file_size;
+                }
+                None => summary.breakdown.push(FileUsageBreakdown {
+                    kind,
+                    file_count: 1,
+                    total_size: file.file_size,
+                }),
+            }
+        }
+    }
+    summary
+        .breakdown
+        .sort_by(|a, b| b.total_size.cmp(&a.total_size));
+    summary
+}
+
+/// 列出本人上传的文件
+/// GET /auth/me/files
+pub async fn handle_list_my_files(
+    service: &AuthService,
+    query: MyFileListQuery,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let policy = PaginationPolicy::for_endpoint("my_files");
+    if let Err(limit) = policy.check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    let storage = service.get_storage(request);
+    let files = match storage.list_user_files(user_id).await {
+        Ok(files) => files,
+        Err(e) => return Ok(internal_error(format!("查询文件失败: {e}"))),
+    };
+    let mut refs = match storage.list_file_references(&files).await {
+        Ok(refs) => refs,
+        Err(e) => return Ok(internal_error(format!("查询文件引用失败: {e}"))),
+    };
+    let usage = usage_summary(&files, &refs);
+
+    let files: Vec<File> = match query.unreferenced {
+        Some(unreferenced) => files
+            .into_iter()
+            .filter(|f| refs.contains_key(&f.id) != unreferenced)
+            .collect(),
+        None => files,
+    };
+
+    let (page, size) = policy.resolve(query.page, query.size);
+    let total = files.len() as i64;
+    let items = files
+        .into_iter()
+        .skip(((page - 1) * size) as usize)
+        .take(size as usize)
+        .map(|file| MyFileItem {
+            references: refs.remove(&file.id).unwrap_or_default(),
+            download_token: file.download_token,
+            original_name: file.original_name,
+            file_size: file.file_size,
+            file_type: file.file_type,
+            created_at: file.created_at,
+        })
+        .collect();
+
+    let response = MyFileListResponse {
+        items,
+        pagination: PaginationInfo {
+            page: page as i64,
+            page_size: size as i64,
+            total,
+            total_pages: (total + size as i64 - 1) / size as i64,
+        },
+        usage,
+    };
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+}
+
+/// 删除本人上传且未被引用的文件
+/// DELETE /auth/me/files/{file_token}
+pub async fn handle_delete_my_file(
+    service: &AuthService,
+    file_token: String,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    let storage = service.get_storage(request);
+    // 他人的文件同样返回 404，避免暴露文件是否存在
+    let file = match storage.get_file_by_token(&file_token).await {
+        Ok(Some(file)) if file.user_id == Some(user_id) => file,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::FileNotFound,
+                "文件不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询文件失败: {e}"))),
+    };
+
+    let refs = match storage
+        .list_file_references(std::slice::from_ref(&file))
+        .await
+    {
+        Ok(refs) => refs,
+        Err(e) => return Ok(internal_error(format!("查询文件引用失败: {e}"))),
+    };
+    if let Some(file_refs) = refs.get(&file.id) {
+        return Ok(HttpResponse::Conflict().json(ApiResponse::error(
+            ErrorCode::FileInUse,
+            file_refs,
+            "文件仍被引用，不能删除",
+        )));
+    }
+
+    match storage.purge_files(&[file.id]).await {
+        Ok(purged) => {
+            remove_stored_files(&purged).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("文件已删除")))
+        }
+        Err(e) => Ok(internal_error(format!("删除文件失败: {e}"))),
+    }
+}
@@ -8,6 +8,10 @@ use crate::utils::jwt;
 use crate::utils::password::verify_password;
 
 use super::AuthService;
+use super::captcha::{
+    clear_login_failures, login_requires_captcha, record_login_failure, verify_captcha,
+};
+use super::login_history::record_login;
 
 pub async fn handle_login(
     service: &AuthService,
@@ -15,7 +19,13 @@ pub async fn handle_login(
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
-    let config = service.get_config();
+
+    // 连续登录失败后要求人机验证
+    if login_requires_captcha(request, &login_request.username).await
+        && let Err(response) = verify_captcha(request, login_request.captcha_token.as_deref()).await
+    {
+        return Ok(response);
+    }
 
     // 1. 根据用户名或邮箱获取用户信息
     match storage
@@ -27,21 +37,33 @@ pub async fn handle_login(
             if verify_password(&login_request.password, &user.password_hash) {
                 // 3. 更新最后登录时间
                 let _ = storage.update_last_login(user.id).await;
+                clear_login_failures(request, &login_request.username).await;
+                record_login(&storage, request, user.id, true).await;
 
                 // 4. 生成令牌对
-                match user
-                    .generate_token_pair(login_request.remember_me.then(|| {
-                        chrono::Duration::days(config.jwt.refresh_token_remember_me_expiry)
-                    }))
-                    .await
-                {
+                let session_expiry = if login_request.remember_me {
+                    chrono::Duration::days(jwt::JwtUtils::refresh_token_remember_me_expiry())
+                } else {
+                    chrono::Duration::days(jwt::JwtUtils::refresh_token_expiry())
+                };
+                match user.generate_token_pair(Some(session_expiry)).await {
                     Ok(token_pair) => {
                         // 生成 Access Token 和 Refresh Token 成功
                         tracing::info!("User {} logged in successfully", user.username);
 
+                        // 5. Cookie 认证模式下同时下发访问令牌与 CSRF 令牌 Cookie
+                        let auth_cookies = if jwt::JwtUtils::cookie_auth_enabled() {
+                            jwt::JwtUtils::create_auth_cookies(
+                                &token_pair.access_token,
+                                session_expiry,
+                            )
+                        } else {
+                            Vec::new()
+                        };
+
                         let response = LoginResponse {
                             access_token: token_pair.access_token,
-                            expires_in: config.jwt.access_token_expiry * 60, // 转换为秒
+                            expires_in: jwt::JwtUtils::access_token_expiry() * 60, // 转换为秒
                             user,
                             created_at: chrono::Utc::now(),
                         };
@@ -50,9 +72,12 @@ pub async fn handle_login(
                         let refresh_cookie =
                             jwt::JwtUtils::create_refresh_token_cookie(&token_pair.refresh_token);
 
-                        Ok(HttpResponse::Ok()
-                            .cookie(refresh_cookie)
-                            .json(ApiResponse::success(response, "Login successful")))
+                        let mut builder = HttpResponse::Ok();
+                        builder.cookie(refresh_cookie);
+                        for cookie in auth_cookies {
+                            builder.cookie(cookie);
+                        }
+                        Ok(builder.json(ApiResponse::success(response, "Login successful")))
                     }
                     Err(e) => {
                         tracing::error!("Failed to generate JWT token: {}", e);
@@ -65,16 +90,21 @@ pub async fn handle_login(
                     }
                 }
             } else {
+                record_login_failure(request, &login_request.username).await;
+                record_login(&storage, request, user.id, false).await;
                 Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
                     ErrorCode::AuthFailed,
                     "Username or password is incorrect",
                 )))
             }
         }
-        Ok(None) => Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
-            ErrorCode::AuthFailed,
-            "Username or password is incorrect",
-        ))),
+        Ok(None) => {
+            record_login_failure(request, &login_request.username).await;
+            Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::AuthFailed,
+                "Username or password is incorrect",
+            )))
+        }
         Err(e) => Ok(
             HttpResponse::InternalServerError().json(ApiResponse::error_empty(
                 ErrorCode::InternalServerError,
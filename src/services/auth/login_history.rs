@@ -0,0 +1,124 @@
+//! 登录历史
+//!
+//! 每次登录尝试（成功或密码错误）都写入登录历史，User-Agent 解析为浏览器、系统与设备类型；
+//! 成功登录被识别为新设备时向用户发送安全提醒。
+
+use std::sync::Arc;
+
+use actix_web::http::header::USER_AGENT;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::warn;
+
+use super::AuthService;
+use crate::middlewares::RequireJWT;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::notifications::entities::NotificationType;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::users::requests::{CreateLoginHistory, LoginHistoryQuery};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::storage::Storage;
+use crate::utils::{ClientInfo, UserAgentInfo};
+
+/// User-Agent 最大保存长度（字符）
+const MAX_USER_AGENT_LENGTH: usize = 255;
+
+/// 记录登录尝试，失败只记录警告，不影响登录结果
+pub(super) async fn record_login(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    user_id: i64,
+    success: bool,
+) {
+    let user_agent: Option<String> = request
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|ua| ua.chars().take(MAX_USER_AGENT_LENGTH).collect());
+    let device = UserAgentInfo::parse(user_agent.as_deref());
+    let client = ClientInfo::from_request(request);
+
+    let entry = CreateLoginHistory {
+        user_id,
+        success,
+        ip_address: client.ip_string(),
+        country: client.country,
+        user_agent,
+        browser: device.browser.to_string(),
+        os: device.os.to_string(),
+        device_type: device.device_type.to_string(),
+    };
+
+    let record = match storage.record_login_history(entry).await {
+        Ok(record) => record,
+        Err(e) => {
+            warn!("记录登录历史失败 (user={user_id}): {e}");
+            return;
+        }
+    };
+
+    if record.new_device {
+        let storage = storage.clone();
+        let vars = vec![
+            ("device", device.label()),
+            (
+                "ip_address",
+                record.ip_address.unwrap_or_else(|| "未知".to_string()),
+            ),
+            (
+                "login_time",
+                record.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            ),
+        ];
+        executor::spawn(NOTIFICATIONS_QUEUE, async move {
+            send_templated_notification(
+                storage,
+                user_id,
+                NotificationType::NewDeviceLogin,
+                vars,
+                None,
+                None,
+            )
+            .await;
+        });
+    }
+}
+
+/// 查询本人的登录历史
+pub async fn handle_get_my_login_history(
+    service: &AuthService,
+    query: LoginHistoryQuery,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("login_history").check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let storage = service.get_storage(request);
+    let query = LoginHistoryQuery {
+        user_id: Some(user_id),
+        tenant: TenantScope::All,
+        ..query
+    };
+
+    match storage.list_login_history(query).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            response,
+            "Login history retrieved successfully",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("Failed to retrieve login history: {e}"),
+            )),
+        ),
+    }
+}
@@ -4,12 +4,16 @@ use crate::models::ApiResponse;
 use crate::utils::jwt::JwtUtils;
 
 /// 处理用户登出
-/// 通过设置空的 refresh_token cookie 来清除客户端的登录状态
+/// 通过设置空的 refresh_token cookie（及 Cookie 认证的访问令牌、CSRF 令牌）来清除客户端的登录状态
 pub async fn handle_logout() -> ActixResult<HttpResponse> {
     // 创建空的 refresh_token cookie（max_age=0 会让浏览器删除该 cookie）
     let empty_cookie = JwtUtils::create_empty_refresh_token_cookie();
 
-    Ok(HttpResponse::Ok()
-        .cookie(empty_cookie)
-        .json(ApiResponse::<()>::success_empty("登出成功")))
+    let mut builder = HttpResponse::Ok();
+    builder.cookie(empty_cookie);
+    for cookie in JwtUtils::create_empty_auth_cookies() {
+        builder.cookie(cookie);
+    }
+
+    Ok(builder.json(ApiResponse::<()>::success_empty("登出成功")))
 }
@@ -1,13 +1,17 @@
+pub mod captcha;
+pub mod dashboard;
+pub mod files;
 pub mod login;
+pub mod login_history;
 pub mod logout;
 pub mod profile;
 pub mod register;
 pub mod token;
+pub mod transcript;
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
-use crate::config::AppConfig;
 use crate::storage::Storage;
 
 pub struct AuthService {
@@ -31,10 +35,6 @@ impl AuthService {
         }
     }
 
-    pub(crate) fn get_config(&self) -> &AppConfig {
-        AppConfig::get()
-    }
-
     // 登录验证
     pub async fn login(
         &self,
@@ -47,10 +47,10 @@ impl AuthService {
     // 用户注册
     pub async fn register(
         &self,
-        create_request: crate::models::users::requests::CreateUserRequest,
+        register_request: crate::models::auth::requests::RegisterRequest,
         request: &HttpRequest,
     ) -> ActixResult<HttpResponse> {
-        register::handle_register(self, create_request, request).await
+        register::handle_register(self, register_request, request).await
     }
 
     // 刷新令牌
@@ -79,6 +79,47 @@ impl AuthService {
         profile::handle_update_profile(self, update_request, request).await
     }
 
+    // 导出本人在某班级的成绩单（PDF）
+    pub async fn export_transcript(
+        &self,
+        params: crate::models::auth::requests::TranscriptParams,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        transcript::handle_export_transcript(self, params, request).await
+    }
+
+    // 获取学生首页仪表盘
+    pub async fn get_dashboard(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        dashboard::handle_get_dashboard(self, request).await
+    }
+
+    // 获取本人的登录历史
+    pub async fn get_my_login_history(
+        &self,
+        query: crate::models::users::requests::LoginHistoryQuery,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        login_history::handle_get_my_login_history(self, query, request).await
+    }
+
+    // 列出本人上传的文件及其引用位置
+    pub async fn list_my_files(
+        &self,
+        query: crate::models::files::requests::MyFileListQuery,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        files::handle_list_my_files(self, query, request).await
+    }
+
+    // 删除本人上传且未被引用的文件
+    pub async fn delete_my_file(
+        &self,
+        file_token: String,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        files::handle_delete_my_file(self, file_token, request).await
+    }
+
     // 用户登出
     pub async fn logout(&self) -> ActixResult<HttpResponse> {
         logout::handle_logout().await
@@ -75,10 +75,13 @@ pub async fn handle_update_profile(
     };
 
     match storage.update_user(current_user.id, storage_update).await {
-        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
-            UserResponse { user },
-            "用户信息更新成功",
-        ))),
+        Ok(Some(user)) => {
+            RequireJWT::invalidate_user(request, user.id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                UserResponse { user },
+                "用户信息更新成功",
+            )))
+        }
         Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::UserNotFound,
             "用户不存在",
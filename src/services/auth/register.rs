@@ -1,19 +1,84 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use argon2::Argon2;
 use argon2::password_hash::{PasswordHasher, SaltString, rand_core::OsRng};
+use chrono::Utc;
+use tracing::warn;
 
+use crate::errors::HWSystemError;
+use crate::middlewares::RequireClassRole;
+use crate::models::auth::requests::RegisterRequest;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::Class;
+use crate::models::system::entities::RegistrationMode;
+use crate::models::users::entities::{User, UserRole, UserStatus};
+use crate::models::viewers::entities::{ViewerGrant, ViewerGrantStatus};
 use crate::models::{ApiResponse, ErrorCode, users::requests::CreateUserRequest};
-use crate::utils::validate::{validate_email, validate_password, validate_username};
+use crate::services::system::DynamicConfig;
+use crate::services::system::registration::email_domain_allowed;
+use crate::utils::validate::{
+    validate_email, validate_password, validate_profile_name, validate_username,
+};
 
 use super::AuthService;
+use super::captcha::verify_captcha;
 
 pub async fn handle_register(
     service: &AuthService,
-    mut create_request: CreateUserRequest,
+    register_request: RegisterRequest,
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
 
+    // 家长/导师凭学生的查看邀请注册，不受注册开关、班级邀请码与邮箱域名限制
+    let viewer_invitation = match register_request.viewer_invitation.as_deref() {
+        Some(token) => match find_viewer_invitation(&storage, token).await {
+            Ok(invitation) => Some(invitation),
+            Err(response) => return Ok(response),
+        },
+        None => None,
+    };
+
+    // 注册策略：关闭、人机验证、邀请码
+    let mode = DynamicConfig::registration_mode().await;
+    if mode == RegistrationMode::Closed && viewer_invitation.is_none() {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::RegistrationClosed,
+            "系统已关闭自助注册，请联系管理员创建账号",
+        )));
+    }
+
+    if DynamicConfig::registration_captcha_required().await
+        && let Err(response) =
+            verify_captcha(request, register_request.captcha_token.as_deref()).await
+    {
+        return Ok(response);
+    }
+
+    let invite_class = if mode == RegistrationMode::InviteOnly && viewer_invitation.is_none() {
+        match find_invite_class(&storage, register_request.invite_code.as_deref()).await {
+            Ok(class) => Some(class),
+            Err(response) => return Ok(response),
+        }
+    } else {
+        None
+    };
+
+    // 自助注册的用户归属默认租户，组织用户由组织管理员创建或导入；角色由注册策略决定。
+    // 家长/导师账号与邀请的学生归属同一组织
+    let (role, org_id) = match &viewer_invitation {
+        Some((_, student)) => (UserRole::Viewer, student.org_id),
+        None => (DynamicConfig::registration_default_role().await, None),
+    };
+    let mut create_request = CreateUserRequest {
+        username: register_request.username,
+        email: register_request.email,
+        password: register_request.password,
+        role,
+        display_name: register_request.display_name,
+        avatar_url: register_request.avatar_url,
+        org_id,
+    };
+
     // 1. 检查用户名是否已存在
     if let Err(response) = check_username_exists(&storage, &create_request.username).await {
         return Ok(response);
@@ -36,6 +101,15 @@ pub async fn handle_register(
             .json(ApiResponse::error_empty(ErrorCode::UserEmailInvalid, msg)));
     }
 
+    // 验证邮箱域名
+    let email_domains = DynamicConfig::registration_email_domains().await;
+    if viewer_invitation.is_none() && !email_domain_allowed(&create_request.email, &email_domains) {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::RegistrationEmailDomainNotAllowed,
+            format!("仅允许使用以下域名的邮箱注册: {}", email_domains.join(", ")),
+        )));
+    }
+
     // 验证密码策略
     let password_result = validate_password(&create_request.password);
     if !password_result.is_valid {
@@ -54,6 +128,48 @@ pub async fn handle_register(
             // 4. 创建用户
             match storage.create_user(create_request).await {
                 Ok(user) => {
+                    if let Some((grant, _)) = &viewer_invitation {
+                        match storage
+                            .accept_viewer_invitation(&grant.token, user.id)
+                            .await
+                        {
+                            Ok(Some(_)) => {}
+                            Ok(None) => warn!("查看邀请 {} 在注册期间失效", grant.id),
+                            Err(e) => {
+                                warn!("注册用户 {} 接受查看邀请 {} 失败: {e}", user.id, grant.id)
+                            }
+                        }
+                    }
+                    // 邀请制注册：自动以学生身份加入邀请码对应的班级；
+                    // 要求实名的班级以显示名作为班级内姓名，不符合要求时由学生稍后自行加入
+                    if let Some(class) = invite_class {
+                        let profile_name = if class.require_real_name {
+                            user.display_name.as_deref().and_then(|name| {
+                                validate_profile_name(name, class.profile_name_pattern.as_deref())
+                                    .ok()
+                            })
+                        } else {
+                            None
+                        };
+                        if class.require_real_name && profile_name.is_none() {
+                            warn!(
+                                "注册用户 {} 的显示名不满足班级 {} 的实名要求，跳过自动加入",
+                                user.id, class.id
+                            );
+                        } else {
+                            match storage
+                                .join_class(user.id, class.id, ClassUserRole::Student, profile_name)
+                                .await
+                            {
+                                Ok(_) => {
+                                    RequireClassRole::invalidate(request, class.id, user.id).await
+                                }
+                                Err(e) => {
+                                    warn!("注册用户 {} 加入班级 {} 失败: {e}", user.id, class.id)
+                                }
+                            }
+                        }
+                    }
                     Ok(HttpResponse::Created().json(ApiResponse::success(user, "注册成功")))
                 }
                 Err(e) => Ok(
@@ -73,6 +189,64 @@ pub async fn handle_register(
     }
 }
 
+/// 邀请制注册：邀请码必须对应默认租户下的班级
+async fn find_invite_class(
+    storage: &std::sync::Arc<dyn crate::storage::Storage>,
+    invite_code: Option<&str>,
+) -> Result<Class, HttpResponse> {
+    let invalid = || {
+        HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::RegistrationInviteRequired,
+            "当前仅允许凭班级邀请码注册，请填写有效的邀请码",
+        ))
+    };
+
+    let Some(code) = invite_code.map(str::trim).filter(|c| !c.is_empty()) else {
+        return Err(invalid());
+    };
+
+    match storage.get_class_by_code(code).await {
+        Ok(Some(class)) if class.org_id.is_none() => Ok(class),
+        Ok(_) => Err(invalid()),
+        Err(e) => Err(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::RegisterFailed,
+                format!("Register failed: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 查看邀请注册：邀请必须待接受且邀请的学生账号仍有效
+async fn find_viewer_invitation(
+    storage: &std::sync::Arc<dyn crate::storage::Storage>,
+    token: &str,
+) -> Result<(ViewerGrant, User), HttpResponse> {
+    let invalid = || {
+        HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ViewerInvitationInvalid,
+            "查看邀请无效、已被使用或已过期",
+        ))
+    };
+    let internal_error = |e: HWSystemError| {
+        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+            ErrorCode::RegisterFailed,
+            format!("Register failed: {e}"),
+        ))
+    };
+
+    let grant = match storage.get_viewer_grant_by_token(token.trim()).await {
+        Ok(Some(grant)) if grant.status(Utc::now()) == ViewerGrantStatus::Pending => grant,
+        Ok(_) => return Err(invalid()),
+        Err(e) => return Err(internal_error(e)),
+    };
+    match storage.get_user_by_id(grant.student_id).await {
+        Ok(Some(student)) if student.status == UserStatus::Active => Ok((grant, student)),
+        Ok(_) => Err(invalid()),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
 async fn check_username_exists(
     storage: &std::sync::Arc<dyn crate::storage::Storage>,
     username: &str,
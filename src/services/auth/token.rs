@@ -13,42 +13,105 @@ pub async fn handle_refresh_token(
     service: &AuthService,
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
-    let config = service.get_config();
     // 从 cookie 中提取 refresh token
-    match jwt::JwtUtils::extract_refresh_token_from_cookie(request) {
-        Some(refresh_token) => {
-            // 验证 refresh token 并生成新的 access token
-            match jwt::JwtUtils::refresh_access_token(&refresh_token) {
-                Ok(new_access_token) => {
-                    let response = RefreshTokenResponse {
-                        access_token: new_access_token,
-                        expires_in: config.jwt.access_token_expiry,
-                    };
-                    Ok(HttpResponse::Ok().json(ApiResponse::success(
-                        response,
-                        "Token refreshed successfully",
-                    )))
-                }
-                Err(e) => {
-                    tracing::error!("Refresh token failed: {}", e);
+    let Some(refresh_token) = jwt::JwtUtils::extract_refresh_token_from_cookie(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "Unauthorized access, please login",
+        )));
+    };
+
+    if jwt::JwtUtils::cookie_auth_enabled() {
+        return refresh_with_cookies(request, &refresh_token);
+    }
+
+    // 验证 refresh token 并生成新的 access token
+    match jwt::JwtUtils::refresh_access_token(&refresh_token) {
+        Ok(new_access_token) => {
+            let response = RefreshTokenResponse {
+                access_token: new_access_token,
+                expires_in: jwt::JwtUtils::access_token_expiry(),
+            };
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                response,
+                "Token refreshed successfully",
+            )))
+        }
+        Err(e) => {
+            tracing::error!("Refresh token failed: {}", e);
+            Ok(refresh_failed())
+        }
+    }
+}
+
+/// Cookie 认证模式：校验 CSRF 令牌后轮换 Refresh Token、访问令牌与 CSRF 令牌 Cookie
+fn refresh_with_cookies(request: &HttpRequest, refresh_token: &str) -> ActixResult<HttpResponse> {
+    // 浏览器会话持有 CSRF Cookie 时必须回传，防止跨站触发刷新
+    if let Some(csrf_cookie) = request.cookie(jwt::CSRF_COOKIE) {
+        let header = request
+            .headers()
+            .get(jwt::CSRF_HEADER)
+            .and_then(|h| h.to_str().ok());
+        if !jwt::JwtUtils::csrf_token_matches(header, Some(csrf_cookie.value())) {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::CsrfTokenInvalid,
+                "CSRF token missing or mismatched",
+            )));
+        }
+    }
 
-                    // 清除无效的 refresh token cookie
-                    let empty_cookie = jwt::JwtUtils::create_empty_refresh_token_cookie();
+    let rotated = jwt::JwtUtils::verify_refresh_token(refresh_token).and_then(|claims| {
+        let user_id = claims
+            .sub
+            .parse::<i64>()
+            .map_err(|_| jsonwebtoken::errors::ErrorKind::InvalidToken)?;
+        let access_token = jwt::JwtUtils::generate_access_token(user_id, &claims.role)?;
+        let (refresh_token, remaining) = jwt::JwtUtils::rotate_refresh_token(&claims)?;
+        Ok((access_token, refresh_token, remaining))
+    });
 
-                    Ok(HttpResponse::Unauthorized().cookie(empty_cookie).json(
-                        ApiResponse::error_empty(
-                            ErrorCode::Unauthorized,
-                            "Login expired or invalid, please login again",
-                        ),
-                    ))
-                }
+    match rotated {
+        Ok((access_token, refresh_token, remaining)) => {
+            let mut refresh_cookie = jwt::JwtUtils::create_refresh_token_cookie(&refresh_token);
+            refresh_cookie.set_max_age(actix_web::cookie::time::Duration::seconds(
+                remaining.num_seconds(),
+            ));
+
+            let mut builder = HttpResponse::Ok();
+            builder.cookie(refresh_cookie);
+            for cookie in jwt::JwtUtils::create_auth_cookies(&access_token, remaining) {
+                builder.cookie(cookie);
             }
+
+            let response = RefreshTokenResponse {
+                access_token,
+                expires_in: jwt::JwtUtils::access_token_expiry(),
+            };
+            Ok(builder.json(ApiResponse::success(
+                response,
+                "Token refreshed successfully",
+            )))
+        }
+        Err(e) => {
+            tracing::error!("Refresh token failed: {}", e);
+            Ok(refresh_failed())
+        }
+    }
+}
+
+/// 刷新失败：清除无效的 refresh token 及认证 Cookie
+fn refresh_failed() -> HttpResponse {
+    let mut builder = HttpResponse::Unauthorized();
+    builder.cookie(jwt::JwtUtils::create_empty_refresh_token_cookie());
+    if jwt::JwtUtils::cookie_auth_enabled() {
+        for cookie in jwt::JwtUtils::create_empty_auth_cookies() {
+            builder.cookie(cookie);
         }
-        None => Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
-            ErrorCode::Unauthorized,
-            "Unauthorized access, please login",
-        ))),
     }
+    builder.json(ApiResponse::error_empty(
+        ErrorCode::Unauthorized,
+        "Login expired or invalid, please login again",
+    ))
 }
 
 pub async fn handle_verify_token(
@@ -0,0 +1,237 @@
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use super::AuthService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::auth::requests::TranscriptParams;
+use crate::models::classes::entities::Class;
+use crate::models::homeworks::entities::Homework;
+use crate::models::homeworks::requests::HomeworkListQuery;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::users::entities::User;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::jobs::{self, JobOutput};
+use crate::storage::Storage;
+use crate::utils::pdf::PdfDocument;
+
+/// 作业数不超过该值时直接返回 PDF，否则转为后台任务生成
+const SYNC_HOMEWORK_LIMIT: i64 = 30;
+/// 分页拉取作业时的每页数量
+const FETCH_PAGE_SIZE: i64 = 100;
+
+const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+pub async fn handle_export_transcript(
+    service: &AuthService,
+    params: TranscriptParams,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user) = RequireJWT::extract_user_claims(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let class = match storage.get_class_by_id(params.class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    match RequireClassRole::class_user(request, &storage, user.id, class.id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "您不是该班级成员",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级成员失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    let homework_count = match storage
+        .list_homeworks_with_pagination(homework_query(class.id, 1, 1), Some(user.id))
+        .await
+    {
+        Ok(resp) => resp.pagination.total,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 历史较长时交给后台任务，避免请求长时间阻塞
+    if homework_count > SYNC_HOMEWORK_LIMIT {
+        let owner_id = user.id;
+        let job = match jobs::submit_job(owner_id, "transcript", async move {
+            build_transcript(&storage, &user, &class).await
+        })
+        .await
+        {
+            Ok(job) => job,
+            Err(e) => return Ok(jobs::queue_full_response(e)),
+        };
+
+        return Ok(HttpResponse::Accepted().json(ApiResponse::success(
+            job,
+            "成绩单生成中，请稍后通过任务接口下载",
+        )));
+    }
+
+    match build_transcript(&storage, &user, &class).await {
+        Ok(output) => Ok(HttpResponse::Ok()
+            .insert_header((header::CONTENT_TYPE, output.content_type))
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", output.file_name),
+            ))
+            .body(output.data)),
+        Err(e) => Ok(HttpResponse::InternalServerError()
+            .json(ApiResponse::error_empty(ErrorCode::InternalServerError, e))),
+    }
+}
+
+fn homework_query(class_id: i64, page: i64, size: i64) -> HomeworkListQuery {
+    HomeworkListQuery {
+        page: Some(page),
+        size: Some(size),
+        class_id: Some(class_id),
+        created_by: None,
+        search: None,
+        include_stats: None,
+        tenant: TenantScope::All,
+    }
+}
+
+/// 拉取班级全部作业（按创建时间升序）
+async fn fetch_class_homeworks(
+    storage: &Arc<dyn Storage>,
+    class_id: i64,
+    user_id: i64,
+) -> Result<Vec<Homework>, String> {
+    let mut homeworks = Vec::new();
+    let mut page = 1;
+    loop {
+        let resp = storage
+            .list_homeworks_with_pagination(
+                homework_query(class_id, page, FETCH_PAGE_SIZE),
+                Some(user_id),
+            )
+            .await
+            .map_err(|e| format!("查询作业失败: {e}"))?;
+        homeworks.extend(resp.items.into_iter().map(|item| item.homework));
+        if page >= resp.pagination.total_pages {
+            break;
+        }
+        page += 1;
+    }
+    homeworks.sort_by_key(|hw| (hw.created_at, hw.id));
+    Ok(homeworks)
+}
+
+/// 生成成绩单 PDF
+async fn build_transcript(
+    storage: &Arc<dyn Storage>,
+    user: &User,
+    class: &Class,
+) -> Result<JobOutput, String> {
+    let homeworks = fetch_class_homeworks(storage, class.id, user.id).await?;
+
+    let student_name = user.display_name.as_deref().unwrap_or(&user.username);
+    let mut doc = PdfDocument::new(&format!("{} - 成绩单", class.name));
+    doc.heading(&format!("{} 成绩单", class.name));
+    doc.text(&format!("学生：{student_name}（{}）", user.username));
+    doc.text(&format!(
+        "生成时间：{}",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+    doc.spacer();
+
+    if homeworks.is_empty() {
+        doc.text("该班级暂无作业");
+    }
+
+    for homework in &homeworks {
+        let submissions = storage
+            .list_user_submissions(homework.id, user.id)
+            .await
+            .map_err(|e| format!("查询提交记录失败: {e}"))?;
+
+        doc.subheading(&homework.title);
+        let deadline = homework
+            .deadline
+            .map(|d| d.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| "无".to_string());
+        doc.text(&format!(
+            "截止时间：{deadline}    满分：{}",
+            homework.max_score
+        ));
+
+        if submissions.is_empty() {
+            doc.indented("未提交");
+        }
+
+        // 按版本升序列出全部提交
+        for submission in submissions.iter().rev() {
+            let submitted_at = chrono::DateTime::parse_from_rfc3339(&submission.submitted_at)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|_| submission.submitted_at.clone());
+            let late = if submission.is_late {
+                "（迟交）"
+            } else {
+                ""
+            };
+            let score = submission
+                .grade
+                .as_ref()
+                .map(|g| format!("{} / {}", g.score, homework.max_score))
+                .unwrap_or_else(|| "未评分".to_string());
+            doc.indented(&format!(
+                "第 {} 次提交  {submitted_at}{late}  得分：{score}",
+                submission.version
+            ));
+            if let Some(comment) = submission
+                .grade
+                .as_ref()
+                .and_then(|g| g.comment.as_deref())
+                .filter(|c| !c.trim().is_empty())
+            {
+                doc.indented(&format!("教师评语：{comment}"));
+            }
+        }
+        doc.spacer();
+    }
+
+    Ok(JobOutput {
+        file_name: format!("transcript-{}-{}.pdf", class.id, user.id),
+        content_type: PDF_CONTENT_TYPE,
+        data: doc.finish(),
+    })
+}
@@ -0,0 +1,85 @@
+//! 结业证书颁发
+
+use std::sync::Arc;
+
+use tracing::error;
+
+use crate::errors::Result;
+use crate::models::certificates::entities::Certificate;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::runtime::executor::{self, CERTIFICATES_QUEUE};
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::storage::Storage;
+use crate::utils::random_code::generate_random_code;
+
+/// 验证码长度
+const VERIFICATION_CODE_LENGTH: usize = 16;
+
+/// 满足条件时为学生颁发结业证书
+///
+/// 班级未启用证书或尚未满足条件时返回 None；已颁发过的直接返回原证书。
+/// 首次颁发时通知学生。
+pub async fn issue_if_eligible(
+    storage: &Arc<dyn Storage>,
+    class_id: i64,
+    user_id: i64,
+) -> Result<Option<Certificate>> {
+    let Some(settings) = storage.get_certificate_settings(class_id).await? else {
+        return Ok(None);
+    };
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    if let Some(existing) = storage.get_certificate(class_id, user_id).await? {
+        return Ok(Some(existing));
+    }
+
+    let progress = storage
+        .get_certificate_progress(class_id, user_id, settings.min_score_percent)
+        .await?;
+    if !progress.is_eligible() {
+        return Ok(None);
+    }
+
+    let code = generate_random_code(VERIFICATION_CODE_LENGTH).to_uppercase();
+    let (certificate, created) = storage
+        .issue_certificate(
+            class_id,
+            user_id,
+            &code,
+            progress.average_percent,
+            progress.required_count as i32,
+        )
+        .await?;
+
+    if created {
+        let class_name = storage
+            .get_class_by_id(class_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|c| c.name)
+            .unwrap_or_default();
+        send_templated_notification(
+            storage.clone(),
+            user_id,
+            NotificationType::CertificateIssued,
+            vec![("class_name", class_name)],
+            Some(ReferenceType::Class),
+            Some(class_id),
+        )
+        .await;
+    }
+
+    Ok(Some(certificate))
+}
+
+/// 评分变更后检查是否可以颁发证书（后台执行，失败仅记录日志）
+pub fn spawn_issue_check(storage: Arc<dyn Storage>, class_id: i64, user_id: i64) {
+    executor::spawn(CERTIFICATES_QUEUE, async move {
+        if let Err(e) = issue_if_eligible(&storage, class_id, user_id).await {
+            error!("检查结业证书颁发条件失败 (class={class_id}, user={user_id}): {e}");
+        }
+    });
+}
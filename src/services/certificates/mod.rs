@@ -0,0 +1,84 @@
+//! 结业证书服务
+//!
+//! 教师为班级开启结业证书后，学生完成全部（未豁免的）作业且每项得分率达到门槛时自动颁发证书。
+//! 证书记录只保存颁发结果与验证码，PDF 在下载时按当前模板与签名图片生成。
+
+pub mod issue;
+pub mod my;
+pub mod render;
+pub mod settings;
+pub mod verify;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::models::certificates::requests::UpdateCertificateSettingsRequest;
+use crate::storage::Storage;
+
+pub struct CertificateService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl CertificateService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    /// 获取班级结业证书设置
+    pub async fn get_settings(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        settings::get_settings(self, request, class_id).await
+    }
+
+    /// 更新班级结业证书设置
+    pub async fn update_settings(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        req: UpdateCertificateSettingsRequest,
+    ) -> ActixResult<HttpResponse> {
+        settings::update_settings(self, request, class_id, req).await
+    }
+
+    /// 获取当前学生的证书状态
+    pub async fn get_my_status(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        my::get_my_status(self, request, class_id).await
+    }
+
+    /// 下载当前学生的证书 PDF
+    pub async fn download_my_certificate(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        my::download_my_certificate(self, request, class_id).await
+    }
+
+    /// 通过验证码验证证书（公开）
+    pub async fn verify_certificate(
+        &self,
+        request: &HttpRequest,
+        code: &str,
+    ) -> ActixResult<HttpResponse> {
+        verify::verify_certificate(self, request, code).await
+    }
+}
@@ -0,0 +1,181 @@
+//! 学生查看与下载自己的结业证书
+
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{CertificateService, issue, render};
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::certificates::responses::CertificateStatusResponse;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::Class;
+use crate::models::{ApiResponse, ErrorCode};
+
+const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验当前用户是否为班级学生（含课代表），返回用户 ID 与班级
+async fn check_class_student(
+    service: &CertificateService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<(i64, Class), HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role != ClassUserRole::Teacher => Ok((user_id, class)),
+        Ok(Some(_)) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::Forbidden,
+            "结业证书仅颁发给班级学生",
+        ))),
+        Ok(None) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "您不是该班级成员",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+pub async fn get_my_status(
+    service: &CertificateService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, _) = match check_class_student(service, request, class_id).await {
+        Ok(result) => result,
+        Err(resp) => return Ok(resp),
+    };
+
+    let settings = match storage.get_certificate_settings(class_id).await {
+        Ok(settings) => settings,
+        Err(e) => return Ok(internal_error(format!("查询证书设置失败: {e}"))),
+    };
+    let (enabled, min_score_percent) = settings
+        .as_ref()
+        .map(|s| (s.enabled, s.min_score_percent))
+        .unwrap_or((false, 0.0));
+
+    // 设置开启前已满足条件的学生在此补发
+    let certificate = match issue::issue_if_eligible(&storage, class_id, user_id).await {
+        Ok(Some(certificate)) => Some(certificate),
+        // 关闭证书后，已颁发的证书仍然有效
+        Ok(None) => match storage.get_certificate(class_id, user_id).await {
+            Ok(certificate) => certificate,
+            Err(e) => return Ok(internal_error(format!("查询证书失败: {e}"))),
+        },
+        Err(e) => return Ok(internal_error(format!("颁发证书失败: {e}"))),
+    };
+
+    let progress = match storage
+        .get_certificate_progress(class_id, user_id, min_score_percent)
+        .await
+    {
+        Ok(progress) => progress,
+        Err(e) => return Ok(internal_error(format!("查询作业完成进度失败: {e}"))),
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        CertificateStatusResponse {
+            enabled,
+            min_score_percent,
+            progress,
+            certificate,
+        },
+        "查询成功",
+    )))
+}
+
+pub async fn download_my_certificate(
+    service: &CertificateService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, class) = match check_class_student(service, request, class_id).await {
+        Ok(result) => result,
+        Err(resp) => return Ok(resp),
+    };
+
+    let settings = match storage.get_certificate_settings(class_id).await {
+        Ok(Some(settings)) => settings,
+        Ok(None) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::CertificateNotEnabled,
+                "该班级未启用结业证书",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询证书设置失败: {e}"))),
+    };
+
+    let certificate = match issue::issue_if_eligible(&storage, class_id, user_id).await {
+        Ok(Some(certificate)) => certificate,
+        Ok(None) => match storage.get_certificate(class_id, user_id).await {
+            Ok(Some(certificate)) => certificate,
+            Ok(None) if !settings.enabled => {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::CertificateNotEnabled,
+                    "该班级未启用结业证书",
+                )));
+            }
+            Ok(None) => {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::CertificateNotEarned,
+                    "尚未完成全部作业或得分未达到要求",
+                )));
+            }
+            Err(e) => return Ok(internal_error(format!("查询证书失败: {e}"))),
+        },
+        Err(e) => return Ok(internal_error(format!("颁发证书失败: {e}"))),
+    };
+
+    let student = match storage.get_user_by_id(user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::UserNotFound,
+                "用户不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询用户失败: {e}"))),
+    };
+
+    let data =
+        render::render_certificate(&storage, &settings, &certificate, &student, &class).await;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, PDF_CONTENT_TYPE))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"certificate-{}-{}.pdf\"",
+                class.id, user_id
+            ),
+        ))
+        .body(data))
+}
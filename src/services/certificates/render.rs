@@ -0,0 +1,114 @@
+//! 结业证书 PDF 生成
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::models::certificates::entities::{Certificate, CertificateSettings};
+use crate::models::classes::entities::Class;
+use crate::models::users::entities::User;
+use crate::services::files::tiering::stored_path;
+use crate::storage::Storage;
+use crate::utils::pdf::{Align, BODY_SIZE, PdfDocument, PdfImage};
+
+/// 证书标题字号
+const TITLE_SIZE: f32 = 28.0;
+/// 证书正文字号
+const CONTENT_SIZE: f32 = 14.0;
+/// 签名图片显示宽度（pt）
+const SIGNATURE_WIDTH: f32 = 120.0;
+
+/// 按模板生成证书正文
+pub fn render_body(
+    template: &str,
+    certificate: &Certificate,
+    student_name: &str,
+    class_name: &str,
+) -> String {
+    template
+        .replace("{student_name}", student_name)
+        .replace("{class_name}", class_name)
+        .replace("{homework_count}", &certificate.homework_count.to_string())
+        .replace("{average}", &format!("{:.1}", certificate.average_percent))
+        .replace(
+            "{issued_date}",
+            &certificate.issued_at.format("%Y-%m-%d").to_string(),
+        )
+}
+
+/// 读取签名图片，文件缺失或格式不受支持时返回 None
+pub async fn load_signature(storage: &Arc<dyn Storage>, file_id: i64) -> Option<PdfImage> {
+    let file = match storage.get_file_by_id(file_id).await {
+        Ok(Some(file)) => file,
+        Ok(None) => return None,
+        Err(e) => {
+            warn!("查询证书签名图片失败: {e}");
+            return None;
+        }
+    };
+
+    let path = stored_path(&file);
+    match std::fs::read(&path) {
+        Ok(data) => PdfImage::from_bytes(&data),
+        Err(e) => {
+            warn!("读取证书签名图片失败: {e}");
+            None
+        }
+    }
+}
+
+/// 生成证书 PDF
+pub async fn render_certificate(
+    storage: &Arc<dyn Storage>,
+    settings: &CertificateSettings,
+    certificate: &Certificate,
+    student: &User,
+    class: &Class,
+) -> Vec<u8> {
+    let student_name = student.display_name.as_deref().unwrap_or(&student.username);
+
+    let mut doc = PdfDocument::new(&format!("{} - {}", class.name, settings.title));
+    for _ in 0..4 {
+        doc.spacer();
+    }
+    doc.aligned(&settings.title, TITLE_SIZE, Align::Center);
+    doc.spacer();
+    doc.spacer();
+    doc.aligned(
+        &render_body(
+            &settings.body_template,
+            certificate,
+            student_name,
+            &class.name,
+        ),
+        CONTENT_SIZE,
+        Align::Center,
+    );
+    for _ in 0..4 {
+        doc.spacer();
+    }
+
+    if let Some(file_id) = settings.signature_file_id
+        && let Some(signature) = load_signature(storage, file_id).await
+    {
+        doc.image(signature, SIGNATURE_WIDTH, Align::Right);
+    }
+    if let Some(signer) = &settings.signer_name {
+        doc.aligned(&format!("签发人：{signer}"), BODY_SIZE, Align::Right);
+    }
+    doc.aligned(
+        &format!("颁发日期：{}", certificate.issued_at.format("%Y-%m-%d")),
+        BODY_SIZE,
+        Align::Right,
+    );
+
+    doc.spacer();
+    doc.spacer();
+    doc.text(&format!("证书编号：{}", certificate.verification_code));
+    doc.text(&format!(
+        "验证方式：GET /api/v1/certificates/verify/{}",
+        certificate.verification_code
+    ));
+
+    doc.finish()
+}
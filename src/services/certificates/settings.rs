@@ -0,0 +1,224 @@
+//! 班级结业证书设置
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::CertificateService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::certificates::entities::{
+    CertificateSettings, DEFAULT_CERTIFICATE_TEMPLATE, DEFAULT_CERTIFICATE_TITLE,
+};
+use crate::models::certificates::requests::{
+    CertificateSettingsInput, UpdateCertificateSettingsRequest,
+};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::files::tiering::stored_path;
+use crate::storage::Storage;
+use crate::utils::pdf::PdfImage;
+
+/// 标题与签发人最大长度（字符）
+const MAX_NAME_LENGTH: usize = 64;
+/// 正文模板最大长度（字符）
+const MAX_TEMPLATE_LENGTH: usize = 2000;
+
+fn bad_request(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(ErrorCode::BadRequest, message))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验当前用户是否为班级教师或管理员，返回用户 ID
+async fn check_class_teacher(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<i64, HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    }
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok(user_id);
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => Ok(user_id),
+        Ok(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "只有班级教师可以管理结业证书",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+/// 未配置时的默认设置
+fn default_settings(class_id: i64) -> CertificateSettings {
+    CertificateSettings {
+        class_id,
+        enabled: false,
+        min_score_percent: 60.0,
+        title: DEFAULT_CERTIFICATE_TITLE.to_string(),
+        body_template: DEFAULT_CERTIFICATE_TEMPLATE.to_string(),
+        signer_name: None,
+        signature_file_id: None,
+        signature: None,
+        updated_by: 0,
+        updated_at: chrono::DateTime::<chrono::Utc>::default(),
+    }
+}
+
+pub async fn get_settings(
+    service: &CertificateService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id).await {
+        return Ok(resp);
+    }
+
+    match storage.get_certificate_settings(class_id).await {
+        Ok(settings) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            settings.unwrap_or_else(|| default_settings(class_id)),
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询证书设置失败: {e}"))),
+    }
+}
+
+pub async fn update_settings(
+    service: &CertificateService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: UpdateCertificateSettingsRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match check_class_teacher(&storage, request, class_id).await {
+        Ok(user_id) => user_id,
+        Err(resp) => return Ok(resp),
+    };
+
+    if !(0.0..=100.0).contains(&req.min_score_percent) {
+        return Ok(bad_request("得分率门槛必须在 0 到 100 之间"));
+    }
+
+    let title = non_empty(req.title).unwrap_or_else(|| DEFAULT_CERTIFICATE_TITLE.to_string());
+    if title.chars().count() > MAX_NAME_LENGTH {
+        return Ok(bad_request(format!(
+            "证书标题不能超过 {MAX_NAME_LENGTH} 个字符"
+        )));
+    }
+    let body_template =
+        non_empty(req.body_template).unwrap_or_else(|| DEFAULT_CERTIFICATE_TEMPLATE.to_string());
+    if body_template.chars().count() > MAX_TEMPLATE_LENGTH {
+        return Ok(bad_request(format!(
+            "证书正文模板不能超过 {MAX_TEMPLATE_LENGTH} 个字符"
+        )));
+    }
+    let signer_name = non_empty(req.signer_name);
+    if signer_name
+        .as_ref()
+        .is_some_and(|s| s.chars().count() > MAX_NAME_LENGTH)
+    {
+        return Ok(bad_request(format!(
+            "签发人不能超过 {MAX_NAME_LENGTH} 个字符"
+        )));
+    }
+
+    let previous = match storage.get_certificate_settings(class_id).await {
+        Ok(settings) => settings,
+        Err(e) => return Ok(internal_error(format!("查询证书设置失败: {e}"))),
+    };
+    let previous_file_id = previous.and_then(|s| s.signature_file_id);
+
+    // 签名图片必须是本人上传、可嵌入 PDF 的图片
+    let signature_file_id = match non_empty(req.signature) {
+        None => None,
+        Some(token) => {
+            let file = match storage.get_file_by_token(&token).await {
+                Ok(Some(file)) if file.user_id == Some(user_id) => file,
+                Ok(_) => {
+                    return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                        ErrorCode::FileNotFound,
+                        "签名图片不存在或不属于当前用户",
+                    )));
+                }
+                Err(e) => return Ok(internal_error(format!("查询签名图片失败: {e}"))),
+            };
+
+            let path = stored_path(&file);
+            let supported = std::fs::read(&path)
+                .ok()
+                .and_then(|data| PdfImage::from_bytes(&data))
+                .is_some();
+            if !supported {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::CertificateSignatureInvalid,
+                    "签名图片仅支持 JPEG 或不含透明通道的 PNG",
+                )));
+            }
+            Some(file.id)
+        }
+    };
+
+    let input = CertificateSettingsInput {
+        enabled: req.enabled,
+        min_score_percent: req.min_score_percent,
+        title,
+        body_template,
+        signer_name,
+        signature_file_id,
+    };
+
+    match storage
+        .upsert_certificate_settings(class_id, input, user_id)
+        .await
+    {
+        Ok(settings) => {
+            if previous_file_id != signature_file_id {
+                if let Some(file_id) = signature_file_id
+                    && let Err(e) = storage.increment_file_citation(file_id).await
+                {
+                    tracing::warn!("Failed to increment citation for file {file_id}: {e}");
+                }
+                if let Some(file_id) = previous_file_id
+                    && let Err(e) = storage.decrement_file_citation(file_id).await
+                {
+                    tracing::warn!("Failed to decrement citation for file {file_id}: {e}");
+                }
+            }
+            Ok(HttpResponse::Ok().json(ApiResponse::success(settings, "证书设置已保存")))
+        }
+        Err(e) => Ok(internal_error(format!("保存证书设置失败: {e}"))),
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
@@ -0,0 +1,68 @@
+//! 结业证书公开验证
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::CertificateService;
+use crate::models::certificates::entities::DEFAULT_CERTIFICATE_TITLE;
+use crate::models::certificates::responses::CertificateVerifyResponse;
+use crate::models::{ApiResponse, ErrorCode};
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+pub async fn verify_certificate(
+    service: &CertificateService,
+    request: &HttpRequest,
+    code: &str,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let not_found = || {
+        HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::CertificateNotFound,
+            "证书不存在",
+        ))
+    };
+
+    let certificate = match storage
+        .get_certificate_by_code(&code.trim().to_uppercase())
+        .await
+    {
+        Ok(Some(certificate)) => certificate,
+        Ok(None) => return Ok(not_found()),
+        Err(e) => return Ok(internal_error(format!("查询证书失败: {e}"))),
+    };
+
+    let student = match storage.get_user_by_id(certificate.user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return Ok(not_found()),
+        Err(e) => return Ok(internal_error(format!("查询用户失败: {e}"))),
+    };
+    let class = match storage.get_class_by_id(certificate.class_id).await {
+        Ok(Some(class)) => class,
+        Ok(None) => return Ok(not_found()),
+        Err(e) => return Ok(internal_error(format!("查询班级失败: {e}"))),
+    };
+    let settings = match storage.get_certificate_settings(certificate.class_id).await {
+        Ok(settings) => settings,
+        Err(e) => return Ok(internal_error(format!("查询证书设置失败: {e}"))),
+    };
+
+    let response = CertificateVerifyResponse {
+        verification_code: certificate.verification_code,
+        title: settings
+            .map(|s| s.title)
+            .unwrap_or_else(|| DEFAULT_CERTIFICATE_TITLE.to_string()),
+        student_name: student.display_name.unwrap_or(student.username),
+        class_name: class.name,
+        average_percent: certificate.average_percent,
+        homework_count: certificate.homework_count,
+        issued_at: certificate.issued_at.to_rfc3339(),
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "证书有效")))
+}
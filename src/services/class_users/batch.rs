@@ -0,0 +1,164 @@
+//! 班级成员批量操作
+//!
+//! 按用户 ID 列表批量修改角色或移出班级，在同一事务中执行并逐个返回结果。整批只写一条审计日志，
+//! 角色变化的成员通过一次批量通知告知。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::update::check_update_class_user_permissions;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserBatchAction;
+use crate::models::class_users::requests::BatchClassUsersRequest;
+use crate::models::class_users::responses::{BatchClassUsersResponse, ClassUserBatchItem};
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::ClassUserService;
+use crate::services::notifications::trigger::send_templated_notifications;
+use crate::utils::ClientInfo;
+
+fn invalid(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::ClassUserBatchInvalid,
+        message.into(),
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+pub async fn batch_class_users(
+    service: &ClassUserService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: BatchClassUsersRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user) = RequireJWT::extract_user_claims(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if let Err(resp) = check_update_class_user_permissions(&user, &class) {
+        return Ok(resp);
+    }
+
+    if req.user_ids.is_empty() {
+        return Ok(invalid("用户列表不能为空"));
+    }
+    if req.user_ids.len() > BatchClassUsersRequest::MAX_USERS {
+        return Ok(invalid(format!(
+            "每次最多操作 {} 个用户",
+            BatchClassUsersRequest::MAX_USERS
+        )));
+    }
+    let role = match req.action {
+        ClassUserBatchAction::SetRole => match req.role {
+            Some(role) => Some(role),
+            None => return Ok(invalid("批量修改角色时必须指定 role")),
+        },
+        ClassUserBatchAction::Remove => None,
+    };
+
+    // 去重后逐个预检：班级教师与操作者本人不参与批量操作
+    let mut seen = std::collections::HashSet::new();
+    let mut items = Vec::new();
+    let mut targets = Vec::new();
+    for user_id in req.user_ids {
+        if !seen.insert(user_id) {
+            continue;
+        }
+        let error = if user_id == class.teacher_id {
+            Some("不能修改或移出班级教师")
+        } else if user_id == user.id {
+            Some("不能对自己执行批量操作")
+        } else {
+            targets.push(user_id);
+            None
+        };
+        items.push(ClassUserBatchItem {
+            user_id,
+            success: error.is_none(),
+            error: error.map(str::to_string),
+        });
+    }
+
+    let ip_address = ClientInfo::from_request(request).ip_string();
+    let outcome = match storage
+        .batch_update_class_users(
+            class_id,
+            req.action,
+            role.clone(),
+            &targets,
+            user.id,
+            ip_address,
+        )
+        .await
+    {
+        Ok(outcome) => outcome,
+        Err(e) => return Ok(internal_error(format!("批量操作班级成员失败: {e}"))),
+    };
+
+    for item in items
+        .iter_mut()
+        .filter(|item| outcome.missing.contains(&item.user_id))
+    {
+        item.success = false;
+        item.error = Some("不是班级成员".to_string());
+    }
+    for &user_id in &outcome.changed {
+        RequireClassRole::invalidate(request, class_id, user_id).await;
+    }
+
+    // 角色变化的成员收到一次批量通知，角色名称使用班级自定义名称
+    if let Some(role) = role
+        && !outcome.changed.is_empty()
+    {
+        let role_name = match storage.get_class_role_labels(class_id).await {
+            Ok(labels) => labels.unwrap_or_default().display_name(&role, None),
+            Err(_) => role.display_name().to_string(),
+        };
+        let storage = storage.clone();
+        let user_ids = outcome.changed.clone();
+        let class_name = class.name.clone();
+        executor::spawn(NOTIFICATIONS_QUEUE, async move {
+            send_templated_notifications(
+                storage,
+                user_ids,
+                NotificationType::ClassRoleChanged,
+                vec![("class_name", class_name), ("role_name", role_name)],
+                Some(ReferenceType::Class),
+                Some(class_id),
+            )
+            .await;
+        });
+    }
+
+    let succeeded = items.iter().filter(|item| item.success).count();
+    let response = BatchClassUsersResponse {
+        class_id,
+        action: req.action,
+        succeeded,
+        failed: items.len() - succeeded,
+        items,
+    };
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "批量操作完成")))
+}
@@ -1,5 +1,5 @@
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireClassRole, RequireJWT},
     models::{
         ApiResponse, ErrorCode, class_users::entities::ClassUser, classes::entities::Class,
         users::entities::UserRole,
@@ -88,9 +88,12 @@ pub async fn delete_class_user(
         .leave_class(target_class_user.user_id, class_id)
         .await
     {
-        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty(
-            "Class user deleted successfully",
-        ))),
+        Ok(true) => {
+            RequireClassRole::invalidate(req, class_id, target_class_user.user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty(
+                "Class user deleted successfully",
+            )))
+        }
         Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::ClassUserNotFound,
             "Class user not found",
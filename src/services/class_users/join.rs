@@ -1,15 +1,21 @@
+use std::sync::Arc;
+
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use tracing::error;
 
 use super::ClassUserService;
 use crate::models::notifications::entities::{NotificationType, ReferenceType};
-use crate::services::notifications::trigger::send_notification;
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::send_templated_notification;
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireClassRole, RequireJWT, TenantGuard},
     models::{
         ApiResponse, ErrorCode,
         class_users::{entities::ClassUserRole, requests::JoinClassRequest},
+        classes::entities::Class,
     },
+    storage::Storage,
+    utils::validate::validate_profile_name,
 };
 
 pub async fn join_class(
@@ -47,13 +53,20 @@ pub async fn join_class(
         }
     };
 
-    match (class, class_user) {
+    let class = match (class, class_user) {
+        // 其他组织的班级按邀请码无效处理
         (None, _) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::ClassInviteCodeInvalid,
                 "Class not found or invite code is invalid",
             )));
         }
+        (Some(c), _) if !TenantGuard::can_access(request, c.org_id) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassInviteCodeInvalid,
+                "Class not found or invite code is invalid",
+            )));
+        }
         (Some(c), Some(_)) => {
             return Ok(HttpResponse::Conflict().json(ApiResponse::error(
                 ErrorCode::ClassAlreadyJoined,
@@ -61,27 +74,62 @@ pub async fn join_class(
                 "User has already joined the class",
             )));
         }
-        (Some(_), None) => {
-            // 继续执行加入逻辑
+        // 继续执行加入逻辑
+        (Some(c), None) => c,
+    };
+
+    join_as_student(&storage, request, user_id, &class, join_data.profile_name).await
+}
+
+/// 以学生身份加入班级并发送通知（调用方已完成凭证与重复加入校验）
+///
+/// 班级要求实名时必须提供符合格式的班级内姓名。
+pub(crate) async fn join_as_student(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    user_id: i64,
+    class: &Class,
+    profile_name: Option<String>,
+) -> ActixResult<HttpResponse> {
+    let class_id = class.id;
+    let profile_name = match profile_name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => {
+            match validate_profile_name(name, class.profile_name_pattern.as_deref()) {
+                Ok(name) => Some(name),
+                Err(msg) => {
+                    return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                        ErrorCode::ClassProfileNameInvalid,
+                        msg,
+                    )));
+                }
+            }
+        }
+        _ if class.require_real_name => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::ClassProfileNameRequired,
+                "This class requires a profile name to join",
+            )));
         }
-    }
+        _ => None,
+    };
 
     match storage
-        .join_class(user_id, class_id, ClassUserRole::Student)
+        .join_class(user_id, class_id, ClassUserRole::Student, profile_name)
         .await
     {
         Ok(class_user) => {
+            RequireClassRole::invalidate(request, class_id, user_id).await;
+
             // 异步发送通知
             let storage_clone = storage.clone();
 
-            tokio::spawn(async move {
+            executor::spawn(NOTIFICATIONS_QUEUE, async move {
                 if let Ok(Some(class)) = storage_clone.get_class_by_id(class_id).await {
-                    send_notification(
+                    send_templated_notification(
                         storage_clone,
                         user_id,
                         NotificationType::ClassJoined,
-                        format!("成功加入班级：{}", class.name),
-                        Some(format!("您已成功加入班级「{}」", class.name)),
+                        vec![("class_name", class.name)],
                         Some(ReferenceType::Class),
                         Some(class_id),
                     )
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use tracing::error;
 
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::{
     models::{
         ApiResponse, ErrorCode,
@@ -20,11 +21,15 @@ pub async fn list_class_users_with_pagination(
     class_id: i64,
     query: ClassUserListParams,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("class_users").check(query.pagination.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
 
     let list_query = ClassUserQuery {
         page: Some(query.pagination.page),
-        size: Some(query.pagination.size),
+        size: query.pagination.size,
         search: query.search,
         role: query.role,
     };
@@ -53,12 +58,22 @@ pub async fn list_class_users_with_pagination(
                 }
             }
 
+            // 角色显示名称按班级角色名称配置解析，查询失败时使用内置名称
+            let role_labels = storage
+                .get_class_role_labels(class_id)
+                .await
+                .unwrap_or_else(|e| {
+                    error!("Failed to get class role labels: {}", e);
+                    None
+                })
+                .unwrap_or_default();
+
             // 组装详情列表
             let items: Vec<ClassUserDetail> = response
                 .items
                 .into_iter()
                 .map(|cu| {
-                    let user = user_map
+                    let mut user = user_map
                         .get(&cu.user_id)
                         .cloned()
                         .unwrap_or_else(|| UserInfo {
@@ -67,12 +82,19 @@ pub async fn list_class_users_with_pagination(
                             display_name: None,
                             avatar_url: None,
                         });
+                    // 班级内姓名代替全局显示名
+                    if cu.profile_name.is_some() {
+                        user.display_name = cu.profile_name.clone();
+                    }
 
                     ClassUserDetail {
                         id: cu.id,
                         class_id: cu.class_id,
                         user_id: cu.user_id,
+                        role_name: role_labels.display_name(&cu.role, cu.role_label.as_deref()),
                         role: cu.role,
+                        profile_name: cu.profile_name,
+                        role_label: cu.role_label,
                         joined_at: cu.joined_at,
                         user,
                     }
@@ -1,14 +1,17 @@
+pub mod batch;
 pub mod delete;
 pub mod get;
 pub mod join;
 pub mod list;
+pub mod role_labels;
 pub mod update;
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
 use crate::models::class_users::requests::{
-    ClassUserListParams, JoinClassRequest, UpdateClassUserRequest,
+    BatchClassUsersRequest, ClassUserListParams, JoinClassRequest, UpdateClassRoleLabelsRequest,
+    UpdateClassUserRequest,
 };
 use crate::storage::Storage;
 
@@ -83,4 +86,33 @@ impl ClassUserService {
     ) -> ActixResult<HttpResponse> {
         delete::delete_class_user(self, req, class_id, user_id).await
     }
+
+    // 批量修改成员角色或移出班级
+    pub async fn batch_class_users(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        batch_data: BatchClassUsersRequest,
+    ) -> ActixResult<HttpResponse> {
+        batch::batch_class_users(self, req, class_id, batch_data).await
+    }
+
+    // 获取班级角色名称配置
+    pub async fn get_class_role_labels(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        role_labels::get_class_role_labels(self, req, class_id).await
+    }
+
+    // 更新班级角色名称配置
+    pub async fn update_class_role_labels(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        update_data: UpdateClassRoleLabelsRequest,
+    ) -> ActixResult<HttpResponse> {
+        role_labels::update_class_role_labels(self, req, class_id, update_data).await
+    }
 }
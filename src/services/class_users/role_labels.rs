@@ -0,0 +1,150 @@
+//! 班级角色名称
+//!
+//! 班级可以把内置角色改称为符合本校习惯的名称（如把「课代表」称为「助教」），并可为同一角色定义
+//! 多个名称供成员单独指定。名称只影响显示，权限始终按名称对应的内置角色在服务端校验。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::{ClassRoleLabel, ClassRoleLabels, ClassUserRole};
+use crate::models::class_users::requests::UpdateClassRoleLabelsRequest;
+use crate::models::class_users::responses::{ClassRoleLabelsResponse, ClassRoleNameItem};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::ClassUserService;
+use crate::storage::Storage;
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验班级存在且当前用户可访问，返回（用户 ID, 班级角色）；管理员的班级角色为 None
+async fn check_class_member(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<(i64, Option<ClassUserRole>), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    }
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok((user_id, None));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) => Ok((user_id, Some(cu.role))),
+        Ok(None) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "您不是该班级成员",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+fn build_response(class_id: i64, labels: ClassRoleLabels) -> ClassRoleLabelsResponse {
+    let role_names = ClassUserRole::all_roles()
+        .iter()
+        .map(|&role| ClassRoleNameItem {
+            role: role.clone(),
+            name: labels.display_name(role, None),
+        })
+        .collect();
+
+    ClassRoleLabelsResponse {
+        class_id,
+        customized: !labels.is_default(),
+        labels: labels.labels,
+        role_names,
+    }
+}
+
+/// 查看班级角色名称配置（班级成员）
+pub async fn get_class_role_labels(
+    service: &ClassUserService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_member(&storage, request, class_id).await {
+        return Ok(resp);
+    }
+
+    match storage.get_class_role_labels(class_id).await {
+        Ok(labels) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            build_response(class_id, labels.unwrap_or_default()),
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询角色名称配置失败: {e}"))),
+    }
+}
+
+/// 更新班级角色名称配置（班级教师、管理员）
+pub async fn update_class_role_labels(
+    service: &ClassUserService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: UpdateClassRoleLabelsRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match check_class_member(&storage, request, class_id).await {
+        Ok((user_id, None | Some(ClassUserRole::Teacher))) => user_id,
+        Ok(_) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只有班级教师可以设置角色名称",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+
+    let labels = ClassRoleLabels {
+        labels: req
+            .labels
+            .into_iter()
+            .map(|l| ClassRoleLabel {
+                key: l.key.trim().to_string(),
+                label: l.label.trim().to_string(),
+                role: l.role,
+            })
+            .collect(),
+    };
+    if let Err(message) = labels.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ClassRoleLabelInvalid,
+            message,
+        )));
+    }
+
+    match storage
+        .save_class_role_labels(class_id, labels.clone(), user_id)
+        .await
+    {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            build_response(class_id, labels),
+            "角色名称已保存",
+        ))),
+        Err(e) => Ok(internal_error(format!("保存角色名称配置失败: {e}"))),
+    }
+}
@@ -1,10 +1,11 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
-use crate::models::class_users::entities::ClassUserRole;
+use crate::models::class_users::entities::ClassRoleLabels;
 use crate::models::notifications::entities::{NotificationType, ReferenceType};
-use crate::services::notifications::trigger::send_notification;
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::send_templated_notification;
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireClassRole, RequireJWT},
     models::{
         ApiResponse, ErrorCode,
         class_users::requests::UpdateClassUserRequest,
@@ -12,6 +13,7 @@ use crate::{
         users::entities::{User, UserRole},
     },
     services::ClassUserService,
+    utils::validate::validate_profile_name,
 };
 
 pub async fn update_class_user(
@@ -19,7 +21,7 @@ pub async fn update_class_user(
     request: &HttpRequest,
     class_id: i64,
     user_id: i64,
-    update_data: UpdateClassUserRequest,
+    mut update_data: UpdateClassUserRequest,
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
 
@@ -57,6 +59,60 @@ pub async fn update_class_user(
         return Ok(resp);
     }
 
+    // 教师修改班级内姓名时只校验长度，不受班级格式要求限制
+    if let Some(name) = update_data.profile_name.as_mut()
+        && !name.is_empty()
+    {
+        match validate_profile_name(name, None) {
+            Ok(trimmed) => *name = trimmed,
+            Err(msg) => {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::ClassProfileNameInvalid,
+                    msg,
+                )));
+            }
+        }
+    }
+
+    // 班级角色名称配置：指定名称时角色随之变为名称对应的角色，通知中显示班级自定义名称
+    let role_labels = if update_data.role.is_some() || update_data.role_label.is_some() {
+        match storage.get_class_role_labels(class_id).await {
+            Ok(labels) => labels.unwrap_or_default(),
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("Failed to get class role labels: {e}"),
+                    )),
+                );
+            }
+        }
+    } else {
+        ClassRoleLabels::default()
+    };
+    if let Some(key) = update_data.role_label.as_mut() {
+        *key = key.trim().to_string();
+        if !key.is_empty() {
+            let Some(label) = role_labels.find(key) else {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::ClassRoleLabelInvalid,
+                    format!("角色名称 '{key}' 不存在"),
+                )));
+            };
+            if update_data
+                .role
+                .as_ref()
+                .is_some_and(|role| role != &label.role)
+            {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::ClassRoleLabelInvalid,
+                    format!("角色名称 '{key}' 与指定的角色不一致"),
+                )));
+            }
+            update_data.role = Some(label.role.clone());
+        }
+    }
+
     // 获取原角色用于比较
     let old_role = match storage
         .get_class_user_by_user_id_and_class_id(user_id, class_id)
@@ -71,28 +127,23 @@ pub async fn update_class_user(
         .await
     {
         Ok(Some(class_user)) => {
+            RequireClassRole::invalidate(request, class_id, user_id).await;
+
             // 检查角色是否变化，如果变化则发送通知
             if let Some(new_role) = &update_data.role
                 && old_role.as_ref() != Some(new_role)
             {
                 let storage_clone = storage.clone();
                 let class_name = class.name.clone();
-                let role_name = match new_role {
-                    ClassUserRole::Student => "学生",
-                    ClassUserRole::ClassRepresentative => "课代表",
-                    ClassUserRole::Teacher => "教师",
-                };
+                let role_name =
+                    role_labels.display_name(new_role, class_user.role_label.as_deref());
 
-                tokio::spawn(async move {
-                    send_notification(
+                executor::spawn(NOTIFICATIONS_QUEUE, async move {
+                    send_templated_notification(
                         storage_clone,
                         user_id,
                         NotificationType::ClassRoleChanged,
-                        format!("班级角色变更：{}", class_name),
-                        Some(format!(
-                            "您在班级「{}」的角色已变更为：{}",
-                            class_name, role_name
-                        )),
+                        vec![("class_name", class_name), ("role_name", role_name)],
                         Some(ReferenceType::Class),
                         Some(class_id),
                     )
@@ -118,7 +169,10 @@ pub async fn update_class_user(
     }
 }
 
-fn check_update_class_user_permissions(user: &User, class: &Class) -> Result<(), HttpResponse> {
+pub(super) fn check_update_class_user_permissions(
+    user: &User,
+    class: &Class,
+) -> Result<(), HttpResponse> {
     match user.role {
         UserRole::Admin => Ok(()),
         UserRole::Teacher if class.teacher_id == user.id => Ok(()),
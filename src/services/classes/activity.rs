@@ -0,0 +1,156 @@
+//! 班级活跃度报告服务
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::{Duration, Utc};
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::requests::ClassActivityReportParams;
+use crate::models::classes::responses::{ClassActivityReport, StudentActivity};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::export::{ExportFormat, ExportTable};
+
+/// 默认统计天数
+const DEFAULT_DAYS: i64 = 14;
+/// 最大统计天数
+const MAX_DAYS: i64 = 365;
+
+pub async fn get_activity_report(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    params: ClassActivityReportParams,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let days = params.days.unwrap_or(DEFAULT_DAYS);
+    if !(1..=MAX_DAYS).contains(&days) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("days 必须在 1 到 {MAX_DAYS} 之间"),
+        )));
+    }
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 仅班级教师和管理员可查看（包含登录等个人活动信息）
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以查看活跃度报告",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    let now = Utc::now();
+    let since = now - Duration::days(days);
+    let items = match storage
+        .get_class_activity(class_id, since.timestamp())
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计班级活跃度失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if let Some(format) = params.format.as_deref() {
+        let table = build_table(&items, days);
+        return Ok(table.into_response(
+            ExportFormat::parse(format),
+            &format!("class_{class_id}_activity"),
+        ));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ClassActivityReport {
+            class_id,
+            days,
+            since,
+            generated_at: now,
+            items,
+        },
+        "查询成功",
+    )))
+}
+
+fn build_table(items: &[StudentActivity], days: i64) -> ExportTable {
+    let recent_header = format!("近{days}天提交数");
+    let mut table = ExportTable::new(
+        "活跃度",
+        &[
+            "用户ID",
+            "用户名",
+            "显示名称",
+            "班级角色",
+            "最后登录",
+            &recent_header,
+            "已提交作业",
+            "迟交作业",
+            "平均迟交(小时)",
+            "未读通知",
+        ],
+    );
+    for item in items {
+        table.push_row(vec![
+            item.user_id.into(),
+            item.username.as_str().into(),
+            item.display_name.clone().into(),
+            item.role.to_string().into(),
+            item.last_login.map(|t| t.to_rfc3339()).into(),
+            item.recent_submissions.into(),
+            item.submitted_homeworks.into(),
+            item.late_homeworks.into(),
+            item.avg_lateness_hours
+                .map(|h| (h * 10.0).round() / 10.0)
+                .into(),
+            item.unread_notifications.into(),
+        ]);
+    }
+    table
+}
@@ -0,0 +1,171 @@
+//! 班级 API 令牌管理
+//!
+//! 令牌只在创建时返回一次，数据库只保存摘要。令牌的访问范围由 [`ClassTokenGuard`] 限制。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ClassService;
+use super::im_channels::check_class_teacher;
+use crate::middlewares::ClassTokenGuard;
+use crate::middlewares::class_token::{CLASS_TOKEN_PREFIX, hash_class_token};
+use crate::models::classes::entities::NewClassApiToken;
+use crate::models::classes::requests::CreateClassApiTokenRequest;
+use crate::models::classes::responses::{
+    ClassApiTokenListResponse, ClassApiTokenLogListResponse, CreatedClassApiToken,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::random_code::generate_random_code;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理 API 令牌";
+
+/// 令牌名称最大长度
+const MAX_NAME_LENGTH: usize = 64;
+
+/// 令牌随机部分长度
+const TOKEN_LENGTH: usize = 40;
+
+/// 列表中展示的令牌前缀长度（含 `hwc_`）
+const TOKEN_PREFIX_LENGTH: usize = 12;
+
+/// 调用记录最多返回条数
+const MAX_LOGS: u64 = 100;
+
+fn token_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::ClassApiTokenNotFound,
+        "API 令牌不存在",
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+pub async fn list_api_tokens(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.list_class_api_tokens(class_id).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ClassApiTokenListResponse { items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询 API 令牌失败: {e}"))),
+    }
+}
+
+pub async fn create_api_token(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: CreateClassApiTokenRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    // 令牌不能再签发令牌（ClassTokenGuard 已拦截，这里再次确认）
+    if ClassTokenGuard::scope(request).is_some() {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassApiTokenForbidden,
+            "API 令牌不能签发新的令牌",
+        )));
+    }
+
+    let (user_id, _) = match check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await
+    {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    let name = req.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("令牌名称不能为空且不超过 {MAX_NAME_LENGTH} 个字符"),
+        )));
+    }
+    if req
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "过期时间必须晚于当前时间",
+        )));
+    }
+
+    let token = format!("{CLASS_TOKEN_PREFIX}{}", generate_random_code(TOKEN_LENGTH));
+    let new_token = NewClassApiToken {
+        class_id,
+        name: name.to_string(),
+        token_hash: hash_class_token(&token),
+        token_prefix: token.chars().take(TOKEN_PREFIX_LENGTH).collect(),
+        read_only: req.read_only.unwrap_or(true),
+        created_by: user_id,
+        expires_at: req.expires_at.map(|t| t.timestamp()),
+    };
+
+    match storage.create_class_api_token(new_token).await {
+        Ok(info) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            CreatedClassApiToken { info, token },
+            "API 令牌已创建，请妥善保存，令牌只显示一次",
+        ))),
+        Err(e) => Ok(internal_error(format!("创建 API 令牌失败: {e}"))),
+    }
+}
+
+pub async fn revoke_api_token(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    token_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.revoke_class_api_token(class_id, token_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("API 令牌已撤销"))),
+        Ok(false) => Ok(token_not_found()),
+        Err(e) => Ok(internal_error(format!("撤销 API 令牌失败: {e}"))),
+    }
+}
+
+/// 令牌最近的写请求记录
+pub async fn list_api_token_logs(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    token_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.get_class_api_token(class_id, token_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Ok(token_not_found()),
+        Err(e) => return Ok(internal_error(format!("查询 API 令牌失败: {e}"))),
+    }
+
+    match storage.list_class_api_token_logs(token_id, MAX_LOGS).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ClassApiTokenLogListResponse { items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询令牌调用记录失败: {e}"))),
+    }
+}
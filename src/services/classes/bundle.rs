@@ -0,0 +1,856 @@
+//! 班级内容导出包
+//!
+//! 将班级的作业（含附件、分题与参考答案）、公告以及可选的学生提交与评分打包为 zip，
+//! 用于在部署之间迁移或长期归档。zip 根目录下：
+//! - `bundle.json`：带版本号的 [`ClassBundle`] 清单；
+//! - `files/{id}/{原始文件名}`：清单中引用的附件。
+//!
+//! 导入时总是创建新班级；学生提交按用户名关联目标部署中同一组织的用户，
+//! 找不到对应用户的提交会被跳过。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::sync::Arc;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::Utc;
+use tracing::{error, info, warn};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::ClassService;
+use super::create::{check_class_create_permission, handle_class_create_error};
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::{
+    ActivityEventType, CLASS_BUNDLE_VERSION, ClassBundle, ClassBundleAnnouncement,
+    ClassBundleClass, ClassBundleFile, ClassBundleGrade, ClassBundleHomework, ClassBundlePart,
+    ClassBundleSolution, ClassBundleSubmission, NewActivityEvent,
+};
+use crate::models::classes::requests::{
+    ClassBundleExportParams, ClassBundleImportParams, CreateClassRequest,
+};
+use crate::models::classes::responses::ClassBundleImportResponse;
+use crate::models::files::entities::File;
+use crate::models::homeworks::requests::{
+    CreateHomeworkRequest, HomeworkAttachmentInput, HomeworkListQuery, HomeworkPartInput,
+};
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::entities::{SubmissionStatus, SubmissionWorkflow};
+use crate::models::submissions::requests::{ArchivedSubmissionInput, SubmissionListQuery};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::files::tiering::stored_path;
+use crate::services::homeworks::import::{
+    normalize_path, read_entry, read_upload, store_attachment,
+};
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+
+/// zip 包中的清单文件名
+const BUNDLE_MANIFEST: &str = "bundle.json";
+/// 导入包最大字节数
+const MAX_CLASS_BUNDLE_SIZE: usize = 200 * 1024 * 1024;
+/// 清单最大字节数
+const MAX_MANIFEST_SIZE: u64 = 32 * 1024 * 1024;
+/// 分页遍历时的每页条数
+const PAGE_SIZE: i64 = 100;
+
+/// 导出包写入器，同一文件只写入一次
+struct BundleWriter {
+    zip: ZipWriter<Cursor<Vec<u8>>>,
+    written: HashMap<i64, ClassBundleFile>,
+}
+
+impl BundleWriter {
+    fn new() -> Self {
+        Self {
+            zip: ZipWriter::new(Cursor::new(Vec::new())),
+            written: HashMap::new(),
+        }
+    }
+
+    /// 写入附件，磁盘上缺失的文件记录日志后跳过
+    fn add_file(&mut self, file: &File) -> Result<Option<ClassBundleFile>, String> {
+        if let Some(entry) = self.written.get(&file.id) {
+            return Ok(Some(entry.clone()));
+        }
+        let data = match fs::read(stored_path(file)) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("导出班级时跳过缺失的文件 {}: {}", file.id, e);
+                return Ok(None);
+            }
+        };
+
+        // 原始文件名只保留最后一段，避免在 zip 中产生多级目录
+        let name = file
+            .original_name
+            .rsplit(['/', '\\'])
+            .next()
+            .filter(|name| !name.is_empty() && *name != "..")
+            .unwrap_or("file");
+        let path = format!("files/{}/{name}", file.id);
+        self.zip
+            .start_file(path.as_str(), SimpleFileOptions::default())
+            .map_err(|e| format!("写入文件 {path} 失败: {e}"))?;
+        self.zip
+            .write_all(&data)
+            .map_err(|e| format!("写入文件 {path} 失败: {e}"))?;
+
+        let entry = ClassBundleFile {
+            path,
+            original_name: file.original_name.clone(),
+            file_size: data.len() as i64,
+            kind: None,
+        };
+        self.written.insert(file.id, entry.clone());
+        Ok(Some(entry))
+    }
+
+    /// 写入清单并完成 zip 包
+    fn finish(mut self, bundle: &ClassBundle) -> Result<Vec<u8>, String> {
+        let manifest =
+            serde_json::to_vec_pretty(bundle).map_err(|e| format!("序列化清单失败: {e}"))?;
+        self.zip
+            .start_file(BUNDLE_MANIFEST, SimpleFileOptions::default())
+            .and_then(|_| self.zip.write_all(&manifest).map_err(Into::into))
+            .map_err(|e| format!("写入清单失败: {e}"))?;
+        self.zip
+            .finish()
+            .map(Cursor::into_inner)
+            .map_err(|e| format!("生成 zip 包失败: {e}"))
+    }
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 收集班级内容并写入导出包
+async fn build_bundle(
+    storage: &Arc<dyn Storage>,
+    writer: &mut BundleWriter,
+    class_id: i64,
+    include_submissions: bool,
+) -> Result<Vec<ClassBundleHomework>, String> {
+    let mut homeworks = Vec::new();
+    let mut page = 1;
+    loop {
+        let response = storage
+            .list_homeworks_with_pagination(
+                HomeworkListQuery {
+                    page: Some(page),
+                    size: Some(PAGE_SIZE),
+                    class_id: Some(class_id),
+                    created_by: None,
+                    search: None,
+                    include_stats: None,
+                    tenant: TenantScope::All,
+                },
+                None,
+            )
+            .await
+            .map_err(|e| format!("查询作业失败: {e}"))?;
+        homeworks.extend(response.items.into_iter().map(|item| item.homework));
+        if page >= response.pagination.total_pages {
+            break;
+        }
+        page += 1;
+    }
+    homeworks.sort_by_key(|hw| hw.id);
+
+    let mut result = Vec::with_capacity(homeworks.len());
+    for homework in homeworks {
+        let mut attachments = Vec::new();
+        let attachment_ids = storage
+            .get_homework_attachments(homework.id)
+            .await
+            .map_err(|e| format!("查询作业附件失败: {e}"))?;
+        for (file_id, kind) in attachment_ids {
+            let Some(file) = storage
+                .get_file_by_id(file_id)
+                .await
+                .map_err(|e| format!("查询文件失败: {e}"))?
+            else {
+                continue;
+            };
+            if let Some(mut entry) = writer.add_file(&file)? {
+                entry.kind = Some(kind);
+                attachments.push(entry);
+            }
+        }
+
+        let parts = storage
+            .list_homework_parts(homework.id)
+            .await
+            .map_err(|e| format!("查询分题失败: {e}"))?;
+        let part_positions: HashMap<i64, i32> =
+            parts.iter().map(|part| (part.id, part.position)).collect();
+
+        let solution = storage
+            .get_homework_solution(homework.id)
+            .await
+            .map_err(|e| format!("查询参考答案失败: {e}"))?
+            .map(|solution| ClassBundleSolution {
+                content: solution.content,
+                reveal_policy: solution.reveal_policy,
+            });
+
+        let submissions = if include_submissions {
+            collect_submissions(storage, writer, homework.id, &part_positions).await?
+        } else {
+            vec![]
+        };
+
+        result.push(ClassBundleHomework {
+            title: homework.title,
+            description: homework.description,
+            max_score: homework.max_score,
+            deadline: homework.deadline,
+            allow_late: homework.allow_late,
+            submission_mode: homework.submission_mode,
+            max_content_length: homework.max_content_length,
+            exam_mode: homework.exam_mode,
+            require_self_assessment: homework.require_self_assessment,
+            show_grade_context: homework.show_grade_context,
+            estimated_minutes: homework.estimated_minutes,
+            difficulty: homework.difficulty,
+            grace_minutes: homework.grace_minutes,
+            attachments,
+            parts: parts
+                .into_iter()
+                .map(|part| ClassBundlePart {
+                    title: part.title,
+                    description: part.description,
+                    max_score: part.max_score,
+                    deadline: part.deadline,
+                })
+                .collect(),
+            solution,
+            submissions,
+        });
+    }
+    Ok(result)
+}
+
+/// 收集作业的全部提交版本及评分
+async fn collect_submissions(
+    storage: &Arc<dyn Storage>,
+    writer: &mut BundleWriter,
+    homework_id: i64,
+    part_positions: &HashMap<i64, i32>,
+) -> Result<Vec<ClassBundleSubmission>, String> {
+    let mut items = Vec::new();
+    let mut page = 1;
+    loop {
+        let response = storage
+            .list_submissions_with_pagination(SubmissionListQuery {
+                page: Some(page),
+                size: Some(PAGE_SIZE),
+                homework_id: Some(homework_id),
+                creator_id: None,
+                status: None,
+                tenant: TenantScope::All,
+            })
+            .await
+            .map_err(|e| format!("查询提交失败: {e}"))?;
+        items.extend(response.items);
+        if page >= response.pagination.total_pages {
+            break;
+        }
+        page += 1;
+    }
+    items.sort_by_key(|item| item.id);
+
+    let mut submissions = Vec::with_capacity(items.len());
+    for item in items {
+        let Some(submission) = storage
+            .get_submission_by_id(item.id)
+            .await
+            .map_err(|e| format!("查询提交失败: {e}"))?
+        else {
+            continue;
+        };
+
+        let mut attachments = Vec::new();
+        let file_ids = storage
+            .get_submission_file_ids(submission.id)
+            .await
+            .map_err(|e| format!("查询提交附件失败: {e}"))?;
+        for file_id in file_ids {
+            if let Some(file) = storage
+                .get_file_by_id(file_id)
+                .await
+                .map_err(|e| format!("查询文件失败: {e}"))?
+                && let Some(entry) = writer.add_file(&file)?
+            {
+                attachments.push(entry);
+            }
+        }
+
+        let grade = storage
+            .get_grade_by_submission_id(submission.id)
+            .await
+            .map_err(|e| format!("查询评分失败: {e}"))?
+            .map(|grade| ClassBundleGrade {
+                score: grade.score,
+                comment: grade.comment,
+                graded_at: grade.graded_at,
+            });
+
+        submissions.push(ClassBundleSubmission {
+            username: item.creator.username,
+            part_position: submission
+                .part_id
+                .and_then(|id| part_positions.get(&id).copied()),
+            version: submission.version,
+            content: submission.content.unwrap_or_default(),
+            status: submission.status.to_string(),
+            is_late: submission.is_late,
+            submitted_at: submission.submitted_at,
+            attachments,
+            grade,
+        });
+    }
+    Ok(submissions)
+}
+
+/// 收集班级公告（按发布时间正序）
+async fn collect_announcements(
+    storage: &Arc<dyn Storage>,
+    class_id: i64,
+) -> Result<Vec<ClassBundleAnnouncement>, String> {
+    let mut announcements = Vec::new();
+    let mut before_id = None;
+    loop {
+        let (events, has_more) = storage
+            .list_class_activity_events(
+                class_id,
+                Some(ActivityEventType::Announcement),
+                before_id,
+                PAGE_SIZE as u64,
+            )
+            .await
+            .map_err(|e| format!("查询班级公告失败: {e}"))?;
+        before_id = events.last().map(|event| event.id);
+        announcements.extend(events.into_iter().map(|event| ClassBundleAnnouncement {
+            title: event.title,
+            created_at: event.created_at,
+        }));
+        if !has_more || before_id.is_none() {
+            break;
+        }
+    }
+    announcements.reverse();
+    Ok(announcements)
+}
+
+/// 导出班级内容
+///
+/// 仅班级教师或管理员可以导出；`include_submissions` 为 true 时包含学生提交与评分。
+pub async fn export_class_bundle(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    params: ClassBundleExportParams,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => c,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以导出班级内容",
+                )));
+            }
+            Err(e) => return Ok(internal_error(format!("查询班级成员失败: {e}"))),
+        }
+    }
+
+    let include_submissions = params.include_submissions.unwrap_or(false);
+    let mut writer = BundleWriter::new();
+    let homeworks = match build_bundle(&storage, &mut writer, class_id, include_submissions).await {
+        Ok(homeworks) => homeworks,
+        Err(e) => {
+            error!("导出班级 {} 失败: {}", class_id, e);
+            return Ok(internal_error(e));
+        }
+    };
+    let announcements = match collect_announcements(&storage, class_id).await {
+        Ok(announcements) => announcements,
+        Err(e) => return Ok(internal_error(e)),
+    };
+
+    let bundle = ClassBundle {
+        version: CLASS_BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        class: ClassBundleClass {
+            name: class.name,
+            description: class.description,
+        },
+        homeworks,
+        announcements,
+        include_submissions,
+    };
+
+    match writer.finish(&bundle) {
+        Ok(buffer) => {
+            info!("Class {} exported by {}", class_id, user_id);
+            let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
+            let filename = format!("class_{class_id}_bundle_{timestamp}.zip");
+            Ok(HttpResponse::Ok()
+                .content_type("application/zip")
+                .insert_header((
+                    "Content-Disposition",
+                    format!("attachment; filename=\"{filename}\""),
+                ))
+                .body(buffer))
+        }
+        Err(e) => {
+            error!("生成班级导出包失败: {}", e);
+            Ok(internal_error(e))
+        }
+    }
+}
+
+/// 导入过程的状态
+struct BundleImporter {
+    storage: Arc<dyn Storage>,
+    archive: ZipArchive<Cursor<Vec<u8>>>,
+    class_id: i64,
+    class_org_id: Option<i64>,
+    teacher_id: i64,
+    /// 已保存的附件：(zip 内路径, 所有者) -> (download_token, 文件 ID)
+    stored: HashMap<(String, i64), (String, i64)>,
+    /// 用户名 -> 目标部署中的用户 ID（None 表示不存在或不在本组织）
+    users: HashMap<String, Option<i64>>,
+    members: HashSet<i64>,
+    submissions: usize,
+    skipped_submissions: usize,
+    warnings: Vec<String>,
+}
+
+impl BundleImporter {
+    /// 保存导入包中的附件，同一所有者的同一文件只保存一次
+    async fn store(&mut self, path: &str, owner: i64) -> Result<(String, i64), String> {
+        let key = normalize_path(path).ok_or_else(|| format!("文件路径无效: {path}"))?;
+        if let Some(stored) = self.stored.get(&(key.clone(), owner)) {
+            return Ok(stored.clone());
+        }
+        let max_size = DynamicConfig::upload_max_size().await as u64;
+        let data = read_entry(&mut self.archive, &key, max_size)?;
+        let token = store_attachment(&self.storage, owner, &key, &data).await?;
+        let file_id = match self.storage.get_file_by_token(&token).await {
+            Ok(Some(file)) => file.id,
+            _ => return Err(format!("保存附件 {path} 失败")),
+        };
+        self.stored.insert((key, owner), (token.clone(), file_id));
+        Ok((token, file_id))
+    }
+
+    /// 创建作业及其分题、参考答案，返回 (作业 ID, 分题序号 -> 分题 ID)
+    async fn import_homework(
+        &mut self,
+        homework: &ClassBundleHomework,
+    ) -> Option<(i64, HashMap<i32, i64>)> {
+        let mut attachments = Vec::with_capacity(homework.attachments.len());
+        for file in &homework.attachments {
+            match self.store(&file.path, self.teacher_id).await {
+                Ok((token, _)) => attachments.push(HomeworkAttachmentInput::WithKind {
+                    token,
+                    kind: file.kind.unwrap_or_default(),
+                }),
+                Err(e) => self
+                    .warnings
+                    .push(format!("作业「{}」: {e}", homework.title)),
+            }
+        }
+
+        let req = CreateHomeworkRequest {
+            class_id: self.class_id,
+            title: homework.title.clone(),
+            description: homework.description.clone(),
+            max_score: Some(homework.max_score),
+            deadline: homework.deadline,
+            allow_late: Some(homework.allow_late),
+            submission_mode: Some(homework.submission_mode),
+            max_content_length: homework.max_content_length,
+            exam_mode: Some(homework.exam_mode),
+            require_self_assessment: Some(homework.require_self_assessment),
+            show_grade_context: Some(homework.show_grade_context),
+            estimated_minutes: homework.estimated_minutes,
+            difficulty: homework.difficulty,
+            grace_minutes: homework.grace_minutes,
+            attachments: (!attachments.is_empty()).then_some(attachments),
+        };
+        let created = match self
+            .storage
+            .create_homework(self.teacher_id, req, None)
+            .await
+        {
+            Ok(created) => created,
+            Err(e) => {
+                self.warnings
+                    .push(format!("作业「{}」创建失败: {e}", homework.title));
+                return None;
+            }
+        };
+
+        let mut part_ids = HashMap::new();
+        if !homework.parts.is_empty() {
+            let inputs = homework
+                .parts
+                .iter()
+                .map(|part| HomeworkPartInput {
+                    id: None,
+                    title: part.title.clone(),
+                    description: part.description.clone(),
+                    max_score: part.max_score,
+                    deadline: part.deadline,
+                })
+                .collect();
+            match self
+                .storage
+                .replace_homework_parts(created.id, inputs)
+                .await
+            {
+                Ok(parts) => {
+                    part_ids = parts.iter().map(|part| (part.position, part.id)).collect();
+                }
+                Err(e) => self
+                    .warnings
+                    .push(format!("作业「{}」分题导入失败: {e}", homework.title)),
+            }
+        }
+
+        if let Some(solution) = &homework.solution
+            && let Err(e) = self
+                .storage
+                .upsert_homework_solution(
+                    created.id,
+                    &solution.content,
+                    solution.reveal_policy,
+                    self.teacher_id,
+                )
+                .await
+        {
+            self.warnings
+                .push(format!("作业「{}」参考答案导入失败: {e}", homework.title));
+        }
+
+        Some((created.id, part_ids))
+    }
+
+    /// 按用户名查找目标部署中同一组织的用户
+    async fn resolve_user(&mut self, username: &str) -> Option<i64> {
+        if let Some(user_id) = self.users.get(username) {
+            return *user_id;
+        }
+        let user_id = match self.storage.get_user_by_username(username).await {
+            Ok(Some(user)) if user.org_id == self.class_org_id => Some(user.id),
+            Ok(_) => None,
+            Err(e) => {
+                error!("导入班级时查询用户 {} 失败: {}", username, e);
+                None
+            }
+        };
+        self.users.insert(username.to_string(), user_id);
+        user_id
+    }
+
+    /// 恢复一条提交；提交者不在班级中时以学生身份加入
+    async fn import_submission(
+        &mut self,
+        homework_id: i64,
+        part_ids: &HashMap<i32, i64>,
+        submission: &ClassBundleSubmission,
+    ) {
+        let Some(user_id) = self.resolve_user(&submission.username).await else {
+            self.skipped_submissions += 1;
+            return;
+        };
+
+        let part_id = match submission.part_position {
+            Some(position) => match part_ids.get(&position) {
+                Some(part_id) => Some(*part_id),
+                None => {
+                    self.warnings.push(format!(
+                        "{} 的提交引用了不存在的分题 {position}",
+                        submission.username
+                    ));
+                    self.skipped_submissions += 1;
+                    return;
+                }
+            },
+            None => None,
+        };
+
+        if !self.members.contains(&user_id) {
+            if let Err(e) = self
+                .storage
+                .join_class(user_id, self.class_id, ClassUserRole::Student, None)
+                .await
+            {
+                self.warnings
+                    .push(format!("{} 加入班级失败: {e}", submission.username));
+                self.skipped_submissions += 1;
+                return;
+            }
+            self.members.insert(user_id);
+        }
+
+        let mut file_ids = Vec::with_capacity(submission.attachments.len());
+        for file in &submission.attachments {
+            match self.store(&file.path, user_id).await {
+                Ok((_, file_id)) => file_ids.push(file_id),
+                Err(e) => self
+                    .warnings
+                    .push(format!("{} 的提交: {e}", submission.username)),
+            }
+        }
+
+        // 自定义工作流状态不随班级迁移，按评分情况回落到内置状态
+        let status = if SubmissionWorkflow::BUILTIN_STATUSES.contains(&submission.status.as_str()) {
+            submission.status.clone()
+        } else if submission.grade.is_some() {
+            SubmissionStatus::GRADED.to_string()
+        } else {
+            SubmissionStatus::PENDING.to_string()
+        };
+
+        let input = ArchivedSubmissionInput {
+            homework_id,
+            creator_id: user_id,
+            part_id,
+            version: submission.version,
+            content: submission.content.clone(),
+            status,
+            is_late: submission.is_late,
+            submitted_at: submission.submitted_at.timestamp(),
+            file_ids,
+            grade: submission.grade.as_ref().map(|grade| {
+                (
+                    self.teacher_id,
+                    grade.score,
+                    grade.comment.clone(),
+                    grade.graded_at.timestamp(),
+                )
+            }),
+        };
+        match self.storage.restore_archived_submission(input).await {
+            Ok(_) => self.submissions += 1,
+            Err(e) => {
+                self.warnings
+                    .push(format!("{} 的提交导入失败: {e}", submission.username));
+                self.skipped_submissions += 1;
+            }
+        }
+    }
+}
+
+/// 从导出包创建新班级
+///
+/// 权限规则与创建班级相同；请求体为 multipart，`file` 字段为导出的 zip 包。
+pub async fn import_class_bundle(
+    service: &ClassService,
+    request: &HttpRequest,
+    params: ClassBundleImportParams,
+    mut payload: Multipart,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let uid = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let (data, _) = match read_upload(&mut payload, MAX_CLASS_BUNDLE_SIZE).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::FileUploadFailed,
+                format!("文件读取失败: {e}"),
+            )));
+        }
+    };
+
+    let mut archive = match ZipArchive::new(Cursor::new(data)) {
+        Ok(archive) => archive,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::ImportFileParseFailed,
+                format!("zip 包解析失败: {e}"),
+            )));
+        }
+    };
+    let bundle: ClassBundle = match read_entry(&mut archive, BUNDLE_MANIFEST, MAX_MANIFEST_SIZE)
+        .and_then(|data| serde_json::from_slice(&data).map_err(|e| format!("清单解析失败: {e}")))
+    {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::ImportFileParseFailed,
+                e,
+            )));
+        }
+    };
+    if bundle.version > CLASS_BUNDLE_VERSION {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ImportFileDataInvalid,
+            format!(
+                "不支持的导出包版本 {}（当前最高支持 {CLASS_BUNDLE_VERSION}）",
+                bundle.version
+            ),
+        )));
+    }
+
+    let name = params
+        .name
+        .unwrap_or_else(|| bundle.class.name.clone())
+        .trim()
+        .to_string();
+    if name.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ImportFileDataInvalid,
+            "班级名称不能为空",
+        )));
+    }
+    let class_data = CreateClassRequest {
+        teacher_id: params.teacher_id,
+        name,
+        description: bundle.class.description.clone(),
+    };
+
+    let teacher_id = match check_class_create_permission(
+        RequireJWT::extract_user_role(request),
+        uid,
+        &class_data,
+        &storage,
+        TenantGuard::scope(request),
+    )
+    .await
+    {
+        Ok(tid) => tid,
+        Err(resp) => return Ok(resp),
+    };
+
+    let class = match storage.create_class(class_data).await {
+        Ok(class) => class,
+        Err(e) => return Ok(handle_class_create_error(&e.to_string())),
+    };
+    if let Err(e) = storage
+        .join_class(teacher_id, class.id, ClassUserRole::Teacher, None)
+        .await
+    {
+        error!(
+            "Failed to add teacher {} to class_users for class {}: {}",
+            teacher_id, class.id, e
+        );
+    }
+
+    let mut importer = BundleImporter {
+        storage: storage.clone(),
+        archive,
+        class_id: class.id,
+        class_org_id: class.org_id,
+        teacher_id,
+        stored: HashMap::new(),
+        users: HashMap::new(),
+        members: HashSet::from([teacher_id]),
+        submissions: 0,
+        skipped_submissions: 0,
+        warnings: Vec::new(),
+    };
+
+    // 先创建全部作业，再恢复提交，避免提交者入班后收到作业发布通知
+    let mut created = Vec::with_capacity(bundle.homeworks.len());
+    for homework in &bundle.homeworks {
+        if let Some(result) = importer.import_homework(homework).await {
+            created.push((homework, result));
+        }
+    }
+
+    let mut announcements = 0;
+    for announcement in &bundle.announcements {
+        match storage
+            .create_activity_event(NewActivityEvent {
+                class_id: class.id,
+                event_type: ActivityEventType::Announcement,
+                actor_id: Some(uid),
+                reference_id: None,
+                title: announcement.title.clone(),
+            })
+            .await
+        {
+            Ok(_) => announcements += 1,
+            Err(e) => importer
+                .warnings
+                .push(format!("公告「{}」导入失败: {e}", announcement.title)),
+        }
+    }
+
+    for (homework, (homework_id, part_ids)) in &created {
+        for submission in &homework.submissions {
+            importer
+                .import_submission(*homework_id, part_ids, submission)
+                .await;
+        }
+    }
+
+    // 新班级可能复用已删除班级的 ID，清除其成员缓存
+    for &member in &importer.members {
+        RequireClassRole::invalidate(request, class.id, member).await;
+    }
+
+    info!(
+        "Class {} imported from bundle by {}: {} homeworks, {} submissions",
+        class.id,
+        uid,
+        created.len(),
+        importer.submissions
+    );
+
+    let response = ClassBundleImportResponse {
+        class,
+        homeworks: created.len(),
+        announcements,
+        submissions: importer.submissions,
+        skipped_submissions: importer.skipped_submissions,
+        warnings: importer.warnings,
+    };
+    Ok(HttpResponse::Created().json(ApiResponse::success(response, "导入完成")))
+}
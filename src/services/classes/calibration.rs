@@ -0,0 +1,121 @@
+//! 班级作业用时与难度校准
+//!
+//! 对比教师为每个作业设置的预计用时、难度与学生评分后反馈的实际用时、体感难度，
+//! 帮助教师调整后续作业量；只返回聚合数据，不暴露单个学生的反馈。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::HomeworkCalibration;
+use crate::models::classes::responses::{
+    ClassWorkloadCalibrationResponse, WorkloadCalibrationSummary,
+};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 用时比高于该值视为预估偏少
+const UNDERESTIMATE_RATIO: f64 = 1.25;
+/// 用时比低于该值视为预估偏多
+const OVERESTIMATE_RATIO: f64 = 0.8;
+
+pub async fn get_workload_calibration(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以查看校准报告",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    let items = match storage.get_class_workload_calibration(class_id).await {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计作业反馈失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ClassWorkloadCalibrationResponse {
+            class_id,
+            summary: summarize(&items),
+            items,
+        },
+        "查询成功",
+    )))
+}
+
+/// 汇总各作业的用时比与难度差（未设置预估或没有反馈的作业不参与平均）
+fn summarize(items: &[HomeworkCalibration]) -> WorkloadCalibrationSummary {
+    let average = |values: Vec<f64>| {
+        (!values.is_empty())
+            .then(|| (values.iter().sum::<f64>() / values.len() as f64 * 100.0).round() / 100.0)
+    };
+
+    let ratios: Vec<f64> = items.iter().filter_map(|i| i.time_ratio).collect();
+    let deltas: Vec<f64> = items.iter().filter_map(|i| i.difficulty_delta).collect();
+
+    WorkloadCalibrationSummary {
+        homework_count: items
+            .iter()
+            .filter(|i| i.time_ratio.is_some() || i.difficulty_delta.is_some())
+            .count() as i64,
+        feedback_count: items.iter().map(|i| i.feedback_count).sum(),
+        underestimated_count: ratios.iter().filter(|r| **r > UNDERESTIMATE_RATIO).count() as i64,
+        overestimated_count: ratios.iter().filter(|r| **r < OVERESTIMATE_RATIO).count() as i64,
+        average_time_ratio: average(ratios),
+        average_difficulty_delta: average(deltas),
+    }
+}
@@ -4,9 +4,10 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use tracing::{error, info};
 
 use super::ClassService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::classes::requests::CreateClassRequest;
+use crate::models::organizations::entities::TenantScope;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
 use crate::storage::Storage;
@@ -17,7 +18,7 @@ use crate::storage::Storage;
 /// - **教师**：可以创建班级，自动成为该班级的负责教师
 ///   - 如果请求中包含 teacher_id，必须等于当前用户 ID
 ///   - 如果请求中不包含 teacher_id，自动使用当前用户 ID
-/// - **管理员**：可以为任意教师创建班级
+/// - **管理员**：可以为本组织内任意教师创建班级
 ///   - 必须在请求中指定 teacher_id
 ///   - 指定的用户必须存在且角色为 Teacher
 ///
@@ -43,7 +44,15 @@ pub async fn create_class(
     };
 
     // 权限校验并确定最终的 teacher_id
-    let teacher_id = match check_class_create_permission(role, uid, &class_data, &storage).await {
+    let teacher_id = match check_class_create_permission(
+        role,
+        uid,
+        &class_data,
+        &storage,
+        TenantGuard::scope(request),
+    )
+    .await
+    {
         Ok(tid) => tid,
         Err(resp) => return Ok(resp),
     };
@@ -56,7 +65,7 @@ pub async fn create_class(
         Ok(class) => {
             // 将创建者（教师）加入 class_users 表
             if let Err(e) = storage
-                .join_class(teacher_id, class.id, ClassUserRole::Teacher)
+                .join_class(teacher_id, class.id, ClassUserRole::Teacher, None)
                 .await
             {
                 error!(
@@ -80,20 +89,22 @@ pub async fn create_class(
 /// - `uid`: 当前登录用户的 ID
 /// - `class_data`: 创建班级的请求数据
 /// - `storage`: 存储层接口
+/// - `tenant`: 当前用户的租户范围
 ///
 /// # 返回
 /// - `Ok(teacher_id)`: 成功时返回最终确定的 teacher_id
 /// - `Err(HttpResponse)`: 失败时返回错误响应
 ///
 /// # 逻辑
-/// - **Admin**: 必须指定 teacher_id，且该用户必须是教师角色
+/// - **Admin**: 必须指定 teacher_id，且该用户必须是本组织的教师
 /// - **Teacher**: 如果指定了 teacher_id，必须是自己的 ID；否则自动使用自己的 ID
 /// - **其他角色**: 无权限创建班级
-async fn check_class_create_permission(
+pub(super) async fn check_class_create_permission(
     role: Option<UserRole>,
     uid: i64,
     class_data: &CreateClassRequest,
     storage: &Arc<dyn Storage>,
+    tenant: TenantScope,
 ) -> Result<i64, HttpResponse> {
     match role {
         Some(UserRole::Admin) => {
@@ -109,7 +120,8 @@ async fn check_class_create_permission(
             };
 
             match storage.get_user_by_id(teacher_id).await {
-                Ok(Some(user)) => {
+                // 其他组织的教师按不存在处理
+                Ok(Some(user)) if tenant.allows(user.org_id) => {
                     if user.role != UserRole::Teacher {
                         return Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
                             ErrorCode::ClassPermissionDenied,
@@ -118,7 +130,7 @@ async fn check_class_create_permission(
                     }
                     Ok(teacher_id)
                 }
-                Ok(None) => Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                Ok(_) => Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
                     ErrorCode::UserNotFound,
                     "The specified teacher does not exist",
                 ))),
@@ -153,7 +165,7 @@ async fn check_class_create_permission(
 }
 
 /// 错误响应辅助函数
-fn handle_class_create_error(e: &str) -> HttpResponse {
+pub(super) fn handle_class_create_error(e: &str) -> HttpResponse {
     let msg = format!("Class creation failed: {e}");
     error!("{}", msg);
     if msg.contains("UNIQUE constraint failed") {
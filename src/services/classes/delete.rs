@@ -2,8 +2,12 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::ClassService;
 use crate::{
-    middlewares::RequireJWT,
-    models::{ApiResponse, ErrorCode, classes::entities::Class, users::entities::UserRole},
+    middlewares::{RequireJWT, RequirePermission, TenantGuard},
+    models::{
+        ApiResponse, ErrorCode,
+        classes::entities::Class,
+        users::entities::{AdminPermission, UserRole},
+    },
 };
 
 pub async fn delete_class(
@@ -11,8 +15,14 @@ pub async fn delete_class(
     request: &HttpRequest,
     class_id: i64,
 ) -> ActixResult<HttpResponse> {
-    let role = RequireJWT::extract_user_role(request);
     let storage = service.get_storage(request);
+    // 被授予班级管理权限的用户按管理员处理
+    let role = match RequireJWT::extract_user_claims(request) {
+        Some(user) => Some(
+            RequirePermission::effective_role(&storage, &user, AdminPermission::ClassManage).await,
+        ),
+        None => None,
+    };
 
     let uid = match RequireJWT::extract_user_id(request) {
         Some(id) => id,
@@ -26,8 +36,9 @@ pub async fn delete_class(
 
     // 查询班级信息
     let class = match storage.get_class_by_id(class_id).await {
-        Ok(Some(class)) => class,
-        Ok(None) => {
+        // 跨组织的班级按不存在处理
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::ClassNotFound,
                 "Class not found",
@@ -0,0 +1,292 @@
+//! 班级内学生数据擦除
+//!
+//! 应学生请求删除其在某个班级内的提交、评分（含评语）、私信等数据；账号本身及其在其他班级的
+//! 数据不受影响（注销账号见用户删除）。先预览受影响的行数并获得确认令牌，再凭令牌执行。
+//! 令牌绑定班级、学生与预览时的统计，数据在预览后发生变化时需重新预览。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+
+use super::ClassService;
+use super::im_channels::check_class_teacher;
+use super::leaderboard;
+use crate::config::AppConfig;
+use crate::middlewares::RequireClassRole;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::ClassErasureCounts;
+use crate::models::classes::requests::ClassErasureRequest;
+use crate::models::classes::responses::{ClassErasurePreview, ClassErasureResult};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+use crate::utils::ClientInfo;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以擦除学生数据";
+
+/// 确认令牌有效期（分钟）
+const CONFIRM_TTL_MINUTES: i64 = 15;
+
+/// 擦除原因最大长度
+const MAX_REASON_LENGTH: usize = 500;
+
+/// 签名用途前缀，避免与其他使用同一密钥的签名混用
+const SIGNING_CONTEXT: &str = "hwsystem-class-erasure";
+
+fn mac_for(
+    class_id: i64,
+    user_id: i64,
+    counts: &ClassErasureCounts,
+    expires_at: i64,
+    secret: &str,
+) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    let counts = serde_json::to_string(counts).unwrap_or_default();
+    mac.update(
+        format!("{SIGNING_CONTEXT}\n{class_id}\n{user_id}\n{expires_at}\n{counts}").as_bytes(),
+    );
+    mac
+}
+
+/// 签发确认令牌：`<过期时间戳>.<签名>`
+fn sign_with(
+    class_id: i64,
+    user_id: i64,
+    counts: &ClassErasureCounts,
+    expires_at: i64,
+    secret: &str,
+) -> String {
+    let signature = mac_for(class_id, user_id, counts, expires_at, secret)
+        .finalize()
+        .into_bytes();
+    format!(
+        "{expires_at}.{}",
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature)
+    )
+}
+
+/// 校验确认令牌未过期，且签发时的统计与当前统计一致
+fn verify_with(
+    token: &str,
+    class_id: i64,
+    user_id: i64,
+    counts: &ClassErasureCounts,
+    now: i64,
+    secret: &str,
+) -> bool {
+    let Some((expires_at, signature)) = token.split_once('.') else {
+        return false;
+    };
+    let Ok(expires_at) = expires_at.parse::<i64>() else {
+        return false;
+    };
+    let Ok(signature) = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(signature) else {
+        return false;
+    };
+    expires_at >= now
+        && mac_for(class_id, user_id, counts, expires_at, secret)
+            .verify_slice(&signature)
+            .is_ok()
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn invalid_target(message: &str) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::ClassErasureInvalid,
+        message,
+    ))
+}
+
+fn confirm_invalid(message: &str) -> HttpResponse {
+    HttpResponse::Conflict().json(ApiResponse::error_empty(
+        ErrorCode::ClassErasureConfirmInvalid,
+        message,
+    ))
+}
+
+/// 校验操作者为班级教师（或管理员），且擦除对象是存在的非教师用户，返回操作者 ID
+///
+/// 已退出班级的学生同样可以擦除其遗留数据。
+async fn check_erasure(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+    user_id: i64,
+) -> Result<i64, HttpResponse> {
+    let (operator_id, _) = check_class_teacher(storage, request, class_id, DENIED_MESSAGE).await?;
+
+    if user_id == operator_id {
+        return Err(invalid_target("不能擦除自己的数据"));
+    }
+    match storage.get_user_by_id(user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::UserNotFound,
+                "用户不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询用户失败: {e}"))),
+    }
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {
+            Err(invalid_target("只能擦除学生的数据"))
+        }
+        Ok(_) => Ok(operator_id),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+pub async fn preview_erasure(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    user_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_erasure(&storage, request, class_id, user_id).await {
+        return Ok(resp);
+    }
+
+    let counts = match storage.preview_class_erasure(class_id, user_id).await {
+        Ok(counts) => counts,
+        Err(e) => return Ok(internal_error(format!("统计待擦除数据失败: {e}"))),
+    };
+
+    let expires_at = chrono::Utc::now() + chrono::Duration::minutes(CONFIRM_TTL_MINUTES);
+    let confirm_token = sign_with(
+        class_id,
+        user_id,
+        &counts,
+        expires_at.timestamp(),
+        &AppConfig::get().jwt.secret,
+    );
+    let preview = ClassErasurePreview {
+        class_id,
+        user_id,
+        total: counts.total(),
+        counts,
+        confirm_token,
+        expires_at,
+    };
+    Ok(HttpResponse::Ok().json(ApiResponse::success(preview, "查询成功")))
+}
+
+pub async fn erase_student_data(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    user_id: i64,
+    req: ClassErasureRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let operator_id = match check_erasure(&storage, request, class_id, user_id).await {
+        Ok(operator_id) => operator_id,
+        Err(resp) => return Ok(resp),
+    };
+
+    let reason = req
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if reason
+        .as_ref()
+        .is_some_and(|reason| reason.chars().count() > MAX_REASON_LENGTH)
+    {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("擦除原因不能超过 {MAX_REASON_LENGTH} 个字符"),
+        )));
+    }
+
+    // 令牌按当前统计校验：过期、伪造或预览后数据有变化都需要重新预览
+    let expected = match storage.preview_class_erasure(class_id, user_id).await {
+        Ok(counts) => counts,
+        Err(e) => return Ok(internal_error(format!("统计待擦除数据失败: {e}"))),
+    };
+    if !verify_with(
+        &req.confirm_token,
+        class_id,
+        user_id,
+        &expected,
+        chrono::Utc::now().timestamp(),
+        &AppConfig::get().jwt.secret,
+    ) {
+        return Ok(confirm_invalid(
+            "确认令牌无效、已过期或数据已变化，请重新预览",
+        ));
+    }
+
+    let ip_address = ClientInfo::from_request(request).ip_string();
+    let counts = match storage
+        .erase_class_student_data(
+            class_id,
+            user_id,
+            &expected,
+            operator_id,
+            ip_address,
+            reason,
+        )
+        .await
+    {
+        Ok(Some(counts)) => counts,
+        Ok(None) => return Ok(confirm_invalid("数据已变化，请重新预览")),
+        Err(e) => return Ok(internal_error(format!("擦除学生数据失败: {e}"))),
+    };
+
+    // 排行榜缓存中可能仍有该学生的成绩，成员缓存中可能仍有擦除前的成员信息
+    leaderboard::invalidate(request, class_id).await;
+    RequireClassRole::invalidate(request, class_id, user_id).await;
+
+    let result = ClassErasureResult {
+        class_id,
+        user_id,
+        total: counts.total(),
+        counts,
+    };
+    Ok(HttpResponse::Ok().json(ApiResponse::success(result, "擦除完成")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_token() {
+        let counts = ClassErasureCounts {
+            submissions: 2,
+            grades: 1,
+            ..Default::default()
+        };
+        let token = sign_with(1, 2, &counts, 1_700_000_000, "secret");
+        assert!(verify_with(&token, 1, 2, &counts, 1_699_999_999, "secret"));
+
+        // 过期、对象不同、数据变化或密钥不同均校验失败
+        assert!(!verify_with(&token, 1, 2, &counts, 1_700_000_001, "secret"));
+        assert!(!verify_with(&token, 1, 3, &counts, 1_699_999_999, "secret"));
+        let changed = ClassErasureCounts {
+            submissions: 3,
+            ..counts.clone()
+        };
+        assert!(!verify_with(
+            &token,
+            1,
+            2,
+            &changed,
+            1_699_999_999,
+            "secret"
+        ));
+        assert!(!verify_with(&token, 1, 2, &counts, 1_699_999_999, "other"));
+        assert!(!verify_with("not-a-token", 1, 2, &counts, 0, "secret"));
+    }
+}
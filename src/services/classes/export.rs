@@ -4,26 +4,35 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use chrono::Utc;
 use rust_xlsxwriter::{Format, Workbook, Worksheet};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::error;
 
 use super::ClassService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::class_users::requests::ClassUserQuery;
+use crate::models::classes::entities::Class;
 use crate::models::homeworks::requests::HomeworkListQuery;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::entities::{SubmissionStatus, SubmissionWorkflow};
 use crate::models::submissions::requests::SubmissionListQuery;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+/// XLSX 文件的 MIME 类型
+pub(crate) const XLSX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
 
 /// 学生作业状态
 #[derive(Debug, Clone)]
 enum StudentHomeworkStatus {
     /// 未提交
     NotSubmitted,
-    /// 已提交待批改
-    Submitted,
-    /// 已评分
-    Graded(f64),
+    /// 已提交待批改（附自定义状态名称）
+    Submitted(Option<String>),
+    /// 已评分（附自定义状态名称）
+    Graded(f64, Option<String>),
 }
 
 /// 作业汇总数据
@@ -67,8 +76,8 @@ pub async fn export_class_report(
 
     // 获取班级信息
     let class = match storage.get_class_by_id(class_id).await {
-        Ok(Some(c)) => c,
-        Ok(None) => {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => c,
+        Ok(_) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::ClassNotFound,
                 "班级不存在",
@@ -90,26 +99,24 @@ pub async fn export_class_report(
 
     // Admin 直接放行
     if user_role != Some(UserRole::Admin) {
-        let class_user = match storage
-            .get_class_user_by_user_id_and_class_id(user_id, class_id)
-            .await
-        {
-            Ok(Some(cu)) => cu,
-            Ok(None) => {
-                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
-                    ErrorCode::ClassPermissionDenied,
-                    "您不是该班级成员",
-                )));
-            }
-            Err(e) => {
-                return Ok(
-                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                        ErrorCode::InternalServerError,
-                        format!("查询班级成员失败: {e}"),
-                    )),
-                );
-            }
-        };
+        let class_user =
+            match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+                Ok(Some(cu)) => cu,
+                Ok(None) => {
+                    return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                        ErrorCode::ClassPermissionDenied,
+                        "您不是该班级成员",
+                    )));
+                }
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询班级成员失败: {e}"),
+                        )),
+                    );
+                }
+            };
 
         // 验证是教师或课代表
         if class_user.role != ClassUserRole::Teacher
@@ -127,6 +134,41 @@ pub async fn export_class_report(
         }
     }
 
+    match build_class_report(&storage, &class, show_scores).await {
+        Ok(buffer) => Ok(HttpResponse::Ok()
+            .content_type(XLSX_CONTENT_TYPE)
+            .insert_header((
+                "Content-Disposition",
+                format!(
+                    "attachment; filename=\"{}\"",
+                    class_report_filename(class_id)
+                ),
+            ))
+            .body(buffer)),
+        Err(e) => {
+            error!("导出班级 {} 报表失败: {}", class_id, e);
+            Ok(HttpResponse::InternalServerError()
+                .json(ApiResponse::error_empty(ErrorCode::InternalServerError, e)))
+        }
+    }
+}
+
+/// 报表文件名
+pub(crate) fn class_report_filename(class_id: i64) -> String {
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    format!("class_{class_id}_report_{timestamp}.xlsx")
+}
+
+/// 生成班级报表（XLSX），手动导出与定时导出共用
+///
+/// `show_scores` 为 false 时分数显示为 `***`（课代表导出）。
+pub(crate) async fn build_class_report(
+    storage: &Arc<dyn Storage>,
+    class: &Class,
+    show_scores: bool,
+) -> Result<Vec<u8>, String> {
+    let class_id = class.id;
+
     // 获取班级所有成员
     let class_users_query = ClassUserQuery {
         page: Some(1),
@@ -135,20 +177,10 @@ pub async fn export_class_report(
         role: None,
     };
 
-    let class_users_response = match storage
+    let class_users_response = storage
         .list_class_users_with_pagination(class_id, class_users_query)
         .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                    ErrorCode::InternalServerError,
-                    format!("查询班级成员失败: {e}"),
-                )),
-            );
-        }
-    };
+        .map_err(|e| format!("查询班级成员失败: {e}"))?;
 
     // 统计需要提交作业的成员（排除教师）
     let students: Vec<_> = class_users_response
@@ -167,26 +199,30 @@ pub async fn export_class_report(
         created_by: None,
         search: None,
         include_stats: None,
+        tenant: TenantScope::All,
     };
 
-    let homeworks_response = match storage
+    let homeworks_response = storage
         .list_homeworks_with_pagination(homework_query, None)
         .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                    ErrorCode::InternalServerError,
-                    format!("查询作业失败: {e}"),
-                )),
-            );
-        }
-    };
+        .map_err(|e| format!("查询作业失败: {e}"))?;
 
     let homeworks = &homeworks_response.items;
     let total_homeworks = homeworks.len() as i64;
 
+    // 班级自定义的提交状态在报表中显示其名称
+    let workflow = match storage.get_class_submission_workflow(class_id).await {
+        Ok(workflow) => workflow.unwrap_or_default(),
+        Err(e) => {
+            error!("查询班级 {} 的提交工作流失败: {}", class_id, e);
+            SubmissionWorkflow::default()
+        }
+    };
+    let custom_label = |status: &str| {
+        (!SubmissionWorkflow::BUILTIN_STATUSES.contains(&status))
+            .then(|| workflow.label(status).unwrap_or(status).to_string())
+    };
+
     // 收集所有作业的提交和评分数据
     // homework_id -> (user_id -> StudentHomeworkStatus)
     let mut homework_submissions: HashMap<i64, HashMap<i64, StudentHomeworkStatus>> =
@@ -203,6 +239,7 @@ pub async fn export_class_report(
             size: Some(10000),
             status: None,
             creator_id: None,
+            tenant: TenantScope::All,
         };
 
         let submissions_response = match storage
@@ -232,6 +269,8 @@ pub async fn export_class_report(
                 *entry = submission;
             }
         }
+        // 最新提交被退回待重交的视为未提交
+        latest_submissions.retain(|_, s| s.status != SubmissionStatus::RETURNED);
 
         // 获取评分信息并构建状态映射
         let mut user_statuses: HashMap<i64, StudentHomeworkStatus> = HashMap::new();
@@ -239,12 +278,13 @@ pub async fn export_class_report(
         let mut scores: Vec<f64> = Vec::new();
 
         for (&user_id, submission) in &latest_submissions {
+            let label = custom_label(&submission.status);
             if let Ok(Some(grade)) = storage.get_grade_by_submission_id(submission.id).await {
-                user_statuses.insert(user_id, StudentHomeworkStatus::Graded(grade.score));
+                user_statuses.insert(user_id, StudentHomeworkStatus::Graded(grade.score, label));
                 graded_count += 1;
                 scores.push(grade.score);
             } else {
-                user_statuses.insert(user_id, StudentHomeworkStatus::Submitted);
+                user_statuses.insert(user_id, StudentHomeworkStatus::Submitted(label));
             }
         }
 
@@ -285,10 +325,10 @@ pub async fn export_class_report(
 
                 match &status {
                     StudentHomeworkStatus::NotSubmitted => {}
-                    StudentHomeworkStatus::Submitted => {
+                    StudentHomeworkStatus::Submitted(_) => {
                         total_submitted += 1;
                     }
-                    StudentHomeworkStatus::Graded(score) => {
+                    StudentHomeworkStatus::Graded(score, _) => {
                         total_submitted += 1;
                         score_sum += score;
                         graded_count += 1;
@@ -304,7 +344,11 @@ pub async fn export_class_report(
             };
 
             student_details.push(StudentDetail {
-                display_name: user.display_name.unwrap_or_else(|| user.username.clone()),
+                display_name: student
+                    .profile_name
+                    .clone()
+                    .or(user.display_name)
+                    .unwrap_or_else(|| user.username.clone()),
                 username: user.username,
                 homework_statuses: statuses,
                 total_submitted,
@@ -327,7 +371,7 @@ pub async fn export_class_report(
     // 生成 XLSX
     let homework_titles: Vec<String> = homeworks.iter().map(|h| h.homework.title.clone()).collect();
 
-    let xlsx_result = generate_xlsx(
+    generate_xlsx(
         &class.name,
         total_students,
         total_homeworks,
@@ -336,31 +380,8 @@ pub async fn export_class_report(
         &student_details,
         &homework_titles,
         show_scores,
-    );
-
-    match xlsx_result {
-        Ok(buffer) => {
-            let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
-            let filename = format!("class_{class_id}_report_{timestamp}.xlsx");
-
-            Ok(HttpResponse::Ok()
-                .content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
-                .insert_header((
-                    "Content-Disposition",
-                    format!("attachment; filename=\"{filename}\""),
-                ))
-                .body(buffer))
-        }
-        Err(e) => {
-            error!("生成 XLSX 失败: {}", e);
-            Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                    ErrorCode::InternalServerError,
-                    format!("生成报表失败: {e}"),
-                )),
-            )
-        }
-    }
+    )
+    .map_err(|e| format!("生成报表失败: {e}"))
 }
 
 /// 生成 XLSX 文件
@@ -604,14 +625,15 @@ fn write_student_details_sheet(
         for status in &student.homework_statuses {
             let cell_value = match status {
                 StudentHomeworkStatus::NotSubmitted => "-".to_string(),
-                StudentHomeworkStatus::Submitted => "✓".to_string(),
-                StudentHomeworkStatus::Graded(score) => {
-                    if show_scores {
-                        format!("{score}")
-                    } else {
-                        "✓".to_string()
-                    }
+                StudentHomeworkStatus::Submitted(label) => {
+                    label.clone().unwrap_or_else(|| "✓".to_string())
                 }
+                StudentHomeworkStatus::Graded(score, label) => match (show_scores, label) {
+                    (true, Some(label)) => format!("{score}（{label}）"),
+                    (true, None) => format!("{score}"),
+                    (false, Some(label)) => label.clone(),
+                    (false, None) => "✓".to_string(),
+                },
             };
             sheet.write_string(row, col, &cell_value).ok();
             col += 1;
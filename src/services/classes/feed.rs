@@ -0,0 +1,120 @@
+//! 班级动态流
+//!
+//! 汇总 `activity_events` 中的发布作业、班级公告、发布评分与成员加入事件，按 ID 倒序游标分页，
+//! 班级成员与管理员可查看。班级公告附带表情回应汇总。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::classes::entities::ActivityEventType;
+use crate::models::classes::requests::ClassFeedQuery;
+use crate::models::classes::responses::ClassFeedResponse;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::reactions::entities::ReactionTargetType;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::reactions::load_reaction_summaries;
+
+pub async fn get_feed(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    query: ClassFeedQuery,
+) -> ActixResult<HttpResponse> {
+    let policy = PaginationPolicy::for_endpoint("class_feed");
+    if let Err(limit) = policy.check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级成员可以查看班级动态",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    // 游标分页不使用页码
+    let (_, size) = policy.resolve(None, query.size);
+    match storage
+        .list_class_activity_events(class_id, query.event_type, query.before_id, size)
+        .await
+    {
+        Ok((mut items, has_more)) => {
+            let announcement_ids: Vec<i64> = items
+                .iter()
+                .filter(|item| item.event_type == ActivityEventType::Announcement)
+                .map(|item| item.id)
+                .collect();
+            let mut summaries = load_reaction_summaries(
+                &storage,
+                ReactionTargetType::Announcement,
+                &announcement_ids,
+                user_id,
+            )
+            .await;
+            for item in &mut items {
+                if let Some(summary) = summaries.remove(&item.id) {
+                    item.reactions = summary;
+                }
+            }
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                ClassFeedResponse {
+                    class_id,
+                    items,
+                    has_more,
+                },
+                "查询成功",
+            )))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询班级动态失败: {e}"),
+            )),
+        ),
+    }
+}
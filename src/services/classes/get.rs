@@ -4,7 +4,7 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::ClassService;
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireClassRole, RequireJWT, RequirePermission, TenantGuard},
     models::{
         ApiResponse, ErrorCode,
         class_users::entities::ClassUserRole,
@@ -12,7 +12,7 @@ use crate::{
             entities::Class,
             responses::{ClassDetail, TeacherInfo},
         },
-        users::entities::UserRole,
+        users::entities::{AdminPermission, UserRole},
     },
     storage::Storage,
 };
@@ -22,8 +22,14 @@ pub async fn get_class(
     request: &HttpRequest,
     class_id: i64,
 ) -> ActixResult<HttpResponse> {
-    let role = RequireJWT::extract_user_role(request);
     let storage = service.get_storage(request);
+    // 被授予班级管理权限的用户按管理员处理
+    let role = match RequireJWT::extract_user_claims(request) {
+        Some(user) => Some(
+            RequirePermission::effective_role(&storage, &user, AdminPermission::ClassManage).await,
+        ),
+        None => None,
+    };
 
     let uid = match RequireJWT::extract_user_id(request) {
         Some(id) => id,
@@ -36,7 +42,8 @@ pub async fn get_class(
     };
 
     match storage.get_class_by_id(class_id).await {
-        Ok(Some(class)) => {
+        // 跨组织的班级按不存在处理
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {
             // 权限校验
             if let Err(resp) = check_class_access_permission(&storage, &role, uid, &class).await {
                 return Ok(resp);
@@ -63,10 +70,10 @@ pub async fn get_class(
             let my_role: Option<ClassUserRole> = match role {
                 Some(UserRole::Admin) => Some(ClassUserRole::Teacher), // 管理员视为教师权限
                 Some(UserRole::Teacher) if class.teacher_id == uid => Some(ClassUserRole::Teacher),
+                Some(UserRole::Viewer) => None,
                 Some(UserRole::Teacher) | Some(UserRole::User) | None => {
                     // 查询用户在班级中的角色
-                    storage
-                        .get_class_user_by_user_id_and_class_id(uid, class_id)
+                    RequireClassRole::class_user(request, &storage, uid, class_id)
                         .await
                         .ok()
                         .flatten()
@@ -86,7 +93,7 @@ pub async fn get_class(
                 "Class information retrieved successfully",
             )))
         }
-        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+        Ok(_) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::ClassNotFound,
             "Class not found",
         ))),
@@ -145,11 +152,12 @@ pub async fn get_class_by_code(
     let storage = service.get_storage(request);
 
     match storage.get_class_by_code(&code).await {
-        Ok(Some(class)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
-            class,
-            "Class information retrieved successfully",
-        ))),
-        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => Ok(HttpResponse::Ok()
+            .json(ApiResponse::success(
+                class,
+                "Class information retrieved successfully",
+            ))),
+        Ok(_) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::ClassNotFound,
             "Class not found",
         ))),
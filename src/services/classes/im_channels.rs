@@ -0,0 +1,289 @@
+//! 班级 IM 通知渠道管理
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::{Class, ImEvent, ImProvider};
+use crate::models::classes::requests::{CreateClassImChannelRequest, UpdateClassImChannelRequest};
+use crate::models::classes::responses::{ClassImChannelItem, ClassImChannelListResponse};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::im_delivery::{providers, templates};
+use crate::services::outbound::IM_CLIENT;
+use crate::storage::Storage;
+
+/// 渠道名称最大长度
+const MAX_NAME_LENGTH: usize = 64;
+
+/// 允许的 Webhook 地址前缀，避免服务端被用来请求任意地址
+fn webhook_prefix(provider: ImProvider) -> Option<&'static str> {
+    match provider {
+        ImProvider::Wecom => Some("https://qyapi.weixin.qq.com/"),
+        ImProvider::Dingtalk => Some("https://oapi.dingtalk.com/"),
+        ImProvider::Telegram => None,
+    }
+}
+
+fn bad_request(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(ErrorCode::BadRequest, message))
+}
+
+fn channel_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::ClassImChannelNotFound,
+        "IM 通知渠道不存在",
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理 IM 通知渠道";
+
+/// 校验当前用户是否为班级教师或管理员
+pub(super) async fn check_class_teacher(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+    denied_message: &str,
+) -> Result<(i64, Class), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok((user_id, class));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => Ok((user_id, class)),
+        Ok(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            denied_message,
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), HttpResponse> {
+    let len = name.trim().chars().count();
+    if len == 0 || len > MAX_NAME_LENGTH {
+        return Err(bad_request(format!(
+            "渠道名称不能为空且不超过 {MAX_NAME_LENGTH} 个字符"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_target(provider: ImProvider, target: &str) -> Result<(), HttpResponse> {
+    match webhook_prefix(provider) {
+        Some(prefix) if !target.starts_with(prefix) => Err(bad_request(format!(
+            "{provider} 机器人的 Webhook 地址必须以 {prefix} 开头"
+        ))),
+        None if target.trim().is_empty() => Err(bad_request("请填写 Telegram chat_id")),
+        _ => Ok(()),
+    }
+}
+
+fn validate_events(events: &[ImEvent]) -> Result<(), HttpResponse> {
+    if events.is_empty() {
+        return Err(bad_request("请至少选择一个推送事件"));
+    }
+    Ok(())
+}
+
+fn validate_templates(templates: &HashMap<ImEvent, String>) -> Result<(), HttpResponse> {
+    for (event, template) in templates {
+        let len = template.trim().chars().count();
+        if len == 0 || len > templates::MAX_TEMPLATE_LENGTH {
+            return Err(bad_request(format!(
+                "事件 {event} 的消息模板不能为空且不超过 {} 个字符",
+                templates::MAX_TEMPLATE_LENGTH
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub async fn list_im_channels(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.list_class_im_channels(class_id).await {
+        Ok(channels) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ClassImChannelListResponse {
+                items: channels.into_iter().map(ClassImChannelItem::from).collect(),
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询 IM 通知渠道失败: {e}"))),
+    }
+}
+
+pub async fn create_im_channel(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: CreateClassImChannelRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, _) = match check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await
+    {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    let validation = validate_name(&req.name)
+        .and_then(|_| validate_target(req.provider, &req.target))
+        .and_then(|_| validate_events(&req.events))
+        .and_then(|_| validate_templates(&req.templates));
+    if let Err(resp) = validation {
+        return Ok(resp);
+    }
+    if req.provider.requires_secret() && req.secret.as_deref().is_none_or(str::is_empty) {
+        return Ok(bad_request("Telegram 渠道需要填写 Bot Token"));
+    }
+
+    match storage
+        .create_class_im_channel(class_id, req, user_id)
+        .await
+    {
+        Ok(channel) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            ClassImChannelItem::from(channel),
+            "IM 通知渠道已创建",
+        ))),
+        Err(e) => Ok(internal_error(format!("创建 IM 通知渠道失败: {e}"))),
+    }
+}
+
+pub async fn update_im_channel(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    channel_id: i64,
+    req: UpdateClassImChannelRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    let channel = match storage.get_class_im_channel(class_id, channel_id).await {
+        Ok(Some(channel)) => channel,
+        Ok(None) => return Ok(channel_not_found()),
+        Err(e) => return Ok(internal_error(format!("查询 IM 通知渠道失败: {e}"))),
+    };
+
+    let mut validation = Ok(());
+    if let Some(name) = &req.name {
+        validation = validation.and_then(|_| validate_name(name));
+    }
+    if let Some(target) = &req.target {
+        validation = validation.and_then(|_| validate_target(channel.provider, target));
+    }
+    if let Some(events) = &req.events {
+        validation = validation.and_then(|_| validate_events(events));
+    }
+    if let Some(templates) = &req.templates {
+        validation = validation.and_then(|_| validate_templates(templates));
+    }
+    if let Err(resp) = validation {
+        return Ok(resp);
+    }
+    if channel.provider.requires_secret() && req.secret.as_deref() == Some("") {
+        return Ok(bad_request("Telegram 渠道需要填写 Bot Token"));
+    }
+
+    match storage
+        .update_class_im_channel(class_id, channel_id, req)
+        .await
+    {
+        Ok(Some(channel)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ClassImChannelItem::from(channel),
+            "IM 通知渠道已更新",
+        ))),
+        Ok(None) => Ok(channel_not_found()),
+        Err(e) => Ok(internal_error(format!("更新 IM 通知渠道失败: {e}"))),
+    }
+}
+
+pub async fn delete_im_channel(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    channel_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.delete_class_im_channel(class_id, channel_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("IM 通知渠道已删除"))),
+        Ok(false) => Ok(channel_not_found()),
+        Err(e) => Ok(internal_error(format!("删除 IM 通知渠道失败: {e}"))),
+    }
+}
+
+/// 立即发送一条测试消息（不经过投递队列，不重试）
+pub async fn test_im_channel(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    channel_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (_, class) = match check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    let channel = match storage.get_class_im_channel(class_id, channel_id).await {
+        Ok(Some(channel)) => channel,
+        Ok(None) => return Ok(channel_not_found()),
+        Err(e) => return Ok(internal_error(format!("查询 IM 通知渠道失败: {e}"))),
+    };
+
+    let message = format!("【{}】IM 通知渠道「{}」测试消息", class.name, channel.name);
+    match providers::send_message(&IM_CLIENT, &channel, &message).await {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("测试消息已发送"))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ClassImDeliveryFailed,
+            format!("测试消息发送失败: {error}"),
+        ))),
+    }
+}
@@ -0,0 +1,369 @@
+//! 班级排行榜
+//!
+//! 教师可为班级开启排行榜，按已公布成绩（已截止作业的正式评分）的平均得分率或总分排名。
+//! 开启后学生与课代表只能看到前 `top_n` 名与本人名次，未选择署名的同学匿名显示；
+//! 未开启时只有班级教师与管理员可以预览，学生与课代表一律返回 403，不返回任何成绩。
+//! 汇总结果按班级缓存，设置或署名变更时清除，新评分最迟在缓存过期后体现。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ClassService;
+use crate::cache::traits::TypedObjectCache;
+use crate::cache::{CacheResult, ObjectCache};
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::{
+    ClassLeaderboardSettings, LeaderboardMetric, LeaderboardStanding,
+};
+use crate::models::classes::requests::{LeaderboardOptInRequest, UpdateClassLeaderboardRequest};
+use crate::models::classes::responses::{ClassLeaderboardResponse, LeaderboardEntry};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+/// 成绩汇总缓存时间（秒）
+const LEADERBOARD_CACHE_TTL: u64 = 60;
+/// 默认展示名次数
+const DEFAULT_TOP_N: i32 = 10;
+/// 最多展示名次数
+const MAX_TOP_N: i32 = 100;
+
+fn cache_key(class_id: i64) -> String {
+    format!("leaderboard:{class_id}")
+}
+
+fn cache(request: &HttpRequest) -> Option<Arc<dyn ObjectCache>> {
+    request
+        .app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>()
+        .map(|c| c.get_ref().clone())
+}
+
+pub(super) async fn invalidate(request: &HttpRequest, class_id: i64) {
+    if let Some(cache) = cache(request) {
+        cache.remove(&cache_key(class_id)).await;
+    }
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 当前用户查看排行榜的身份
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Audience {
+    /// 班级教师或管理员：看到全部学生的实名
+    Teacher,
+    /// 学生或课代表：只看到前 N 名，未署名者匿名
+    Student,
+}
+
+/// 校验班级存在且当前用户为班级成员或管理员，返回用户 ID 与查看身份
+async fn check_member(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<(i64, Audience), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    }
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok((user_id, Audience::Teacher));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => Ok((user_id, Audience::Teacher)),
+        Ok(Some(_)) => Ok((user_id, Audience::Student)),
+        Ok(None) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "您不是该班级成员",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+/// 未配置时的默认设置
+fn default_settings(class_id: i64) -> ClassLeaderboardSettings {
+    ClassLeaderboardSettings {
+        class_id,
+        enabled: false,
+        top_n: DEFAULT_TOP_N,
+        metric: LeaderboardMetric::AveragePercent,
+        updated_by: 0,
+        updated_at: chrono::DateTime::<chrono::Utc>::default(),
+    }
+}
+
+fn metric_score(standing: &LeaderboardStanding, metric: LeaderboardMetric) -> f64 {
+    match metric {
+        LeaderboardMetric::AveragePercent => standing.average_percent,
+        LeaderboardMetric::TotalScore => standing.total_score,
+    }
+}
+
+/// 按指标降序排名，同分同名次（1, 1, 3）；同分时按计入作业数、用户 ID 排序保证顺序稳定
+fn rank_standings(
+    mut standings: Vec<LeaderboardStanding>,
+    metric: LeaderboardMetric,
+) -> Vec<(i64, LeaderboardStanding)> {
+    standings.sort_by(|a, b| {
+        metric_score(b, metric)
+            .total_cmp(&metric_score(a, metric))
+            .then_with(|| b.homework_count.cmp(&a.homework_count))
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    });
+
+    let mut ranked = Vec::with_capacity(standings.len());
+    let mut previous: Option<(f64, i64)> = None;
+    for (index, standing) in standings.into_iter().enumerate() {
+        let score = metric_score(&standing, metric);
+        let rank = match previous {
+            Some((previous_score, rank)) if previous_score == score => rank,
+            _ => index as i64 + 1,
+        };
+        previous = Some((score, rank));
+        ranked.push((rank, standing));
+    }
+    ranked
+}
+
+pub async fn get_leaderboard(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, audience) = match check_member(&storage, request, class_id).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    let settings = match storage.get_leaderboard_settings(class_id).await {
+        Ok(settings) => settings.unwrap_or_else(|| default_settings(class_id)),
+        Err(e) => return Ok(internal_error(format!("查询排行榜设置失败: {e}"))),
+    };
+    // 未开启时学生与课代表看不到任何成绩（包括本人名次）
+    if audience == Audience::Student && !settings.enabled {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassLeaderboardDisabled,
+            "班级未开启排行榜",
+        )));
+    }
+
+    let cache = cache(request);
+    let cached = match &cache {
+        Some(cache) => {
+            cache
+                .get::<Vec<LeaderboardStanding>>(&cache_key(class_id))
+                .await
+        }
+        None => CacheResult::NotFound,
+    };
+    let standings = match cached {
+        CacheResult::Found(standings) => standings,
+        _ => {
+            let standings = match storage
+                .list_leaderboard_standings(class_id, chrono::Utc::now().timestamp())
+                .await
+            {
+                Ok(standings) => standings,
+                Err(e) => return Ok(internal_error(format!("统计排行榜失败: {e}"))),
+            };
+            if let Some(cache) = &cache {
+                cache
+                    .insert(
+                        cache_key(class_id),
+                        standings.clone(),
+                        LEADERBOARD_CACHE_TTL,
+                    )
+                    .await;
+            }
+            standings
+        }
+    };
+
+    let participant_count = standings.len() as i64;
+    let ranked = rank_standings(standings, settings.metric);
+    let to_entry = |rank: i64, standing: &LeaderboardStanding| {
+        let is_me = standing.user_id == user_id;
+        let show_name = audience == Audience::Teacher || standing.opted_in || is_me;
+        LeaderboardEntry {
+            rank,
+            display_name: show_name.then(|| standing.name.clone()),
+            user_id: (audience == Audience::Teacher).then_some(standing.user_id),
+            score: metric_score(standing, settings.metric),
+            homework_count: standing.homework_count,
+            is_me,
+        }
+    };
+
+    let (entries, me, opted_in) = match audience {
+        Audience::Teacher => (
+            ranked.iter().map(|(rank, s)| to_entry(*rank, s)).collect(),
+            None,
+            false,
+        ),
+        Audience::Student => {
+            let opted_in = match storage.get_leaderboard_opt_in(class_id, user_id).await {
+                Ok(opted_in) => opted_in,
+                Err(e) => return Ok(internal_error(format!("查询排行榜署名失败: {e}"))),
+            };
+            (
+                ranked
+                    .iter()
+                    .take(settings.top_n.max(0) as usize)
+                    .map(|(rank, s)| to_entry(*rank, s))
+                    .collect(),
+                ranked
+                    .iter()
+                    .find(|(_, s)| s.user_id == user_id)
+                    .map(|(rank, s)| to_entry(*rank, s)),
+                opted_in,
+            )
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ClassLeaderboardResponse {
+            class_id,
+            enabled: settings.enabled,
+            metric: settings.metric,
+            top_n: settings.top_n,
+            participant_count,
+            entries,
+            me,
+            opted_in,
+            generated_at: chrono::Utc::now(),
+        },
+        "查询成功",
+    )))
+}
+
+pub async fn update_leaderboard_settings(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: UpdateClassLeaderboardRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match check_member(&storage, request, class_id).await {
+        Ok((user_id, Audience::Teacher)) => user_id,
+        Ok((_, Audience::Student)) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只有班级教师可以设置排行榜",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+
+    let top_n = req.top_n.unwrap_or(DEFAULT_TOP_N);
+    if !(1..=MAX_TOP_N).contains(&top_n) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("展示名次数必须在 1 到 {MAX_TOP_N} 之间"),
+        )));
+    }
+    let metric = req.metric.unwrap_or(LeaderboardMetric::AveragePercent);
+
+    match storage
+        .upsert_leaderboard_settings(class_id, req.enabled, top_n, metric, user_id)
+        .await
+    {
+        Ok(settings) => {
+            invalidate(request, class_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success(settings, "排行榜设置已保存")))
+        }
+        Err(e) => Ok(internal_error(format!("保存排行榜设置失败: {e}"))),
+    }
+}
+
+pub async fn set_leaderboard_opt_in(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: LeaderboardOptInRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match check_member(&storage, request, class_id).await {
+        Ok((user_id, Audience::Student)) => user_id,
+        Ok((_, Audience::Teacher)) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只有班级学生可以设置排行榜署名",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage
+        .set_leaderboard_opt_in(class_id, user_id, req.opt_in)
+        .await
+    {
+        Ok(()) => {
+            invalidate(request, class_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("排行榜署名设置已保存")))
+        }
+        Err(e) => Ok(internal_error(format!("保存排行榜署名失败: {e}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standing(user_id: i64, average_percent: f64, total_score: f64) -> LeaderboardStanding {
+        LeaderboardStanding {
+            user_id,
+            name: format!("student{user_id}"),
+            opted_in: false,
+            homework_count: 1,
+            total_score,
+            average_percent,
+        }
+    }
+
+    #[test]
+    fn test_rank_standings_ties() {
+        let ranked = rank_standings(
+            vec![
+                standing(1, 80.0, 160.0),
+                standing(2, 95.0, 95.0),
+                standing(3, 80.0, 240.0),
+                standing(4, 60.0, 60.0),
+            ],
+            LeaderboardMetric::AveragePercent,
+        );
+        let ranks: Vec<(i64, i64)> = ranked.iter().map(|(r, s)| (*r, s.user_id)).collect();
+        assert_eq!(ranks, vec![(1, 2), (2, 1), (2, 3), (4, 4)]);
+
+        let ranked = rank_standings(
+            vec![standing(1, 80.0, 160.0), standing(3, 80.0, 240.0)],
+            LeaderboardMetric::TotalScore,
+        );
+        assert_eq!(ranked[0].1.user_id, 3);
+        assert_eq!(ranked[1].0, 2);
+    }
+}
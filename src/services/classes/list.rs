@@ -4,15 +4,16 @@ use std::sync::Arc;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::ClassService;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireJWT, RequirePermission, TenantGuard},
     models::{
         ApiResponse, ErrorCode,
         classes::{
             requests::{ClassListQuery, ClassQueryParams},
             responses::{ClassDetail, ClassDetailListResponse, ClassListResponse, TeacherInfo},
         },
-        users::entities::UserRole,
+        users::entities::{AdminPermission, UserRole},
     },
     storage::Storage,
 };
@@ -22,8 +23,18 @@ pub async fn list_classes(
     request: &HttpRequest,
     query: ClassQueryParams,
 ) -> ActixResult<HttpResponse> {
-    let role = RequireJWT::extract_user_role(request);
+    if let Err(limit) = PaginationPolicy::for_endpoint("classes").check(query.pagination.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
+    // 被授予班级管理权限的用户按管理员处理
+    let role = match RequireJWT::extract_user_claims(request) {
+        Some(user) => Some(
+            RequirePermission::effective_role(&storage, &user, AdminPermission::ClassManage).await,
+        ),
+        None => None,
+    };
 
     let uid = match RequireJWT::extract_user_id(request) {
         Some(id) => id,
@@ -37,9 +48,10 @@ pub async fn list_classes(
 
     let mut list_query = ClassListQuery {
         page: Some(query.pagination.page),
-        size: Some(query.pagination.size),
+        size: query.pagination.size,
         teacher_id: None,
         search: query.search,
+        tenant: TenantGuard::scope(request),
     };
 
     // 权限校验 - 学生走特殊路径
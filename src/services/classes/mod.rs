@@ -1,16 +1,42 @@
+pub mod activity;
+pub mod api_tokens;
+pub mod bundle;
+pub mod calibration;
 pub mod create;
 pub mod delete;
+pub mod erasure;
 pub mod export;
+pub mod feed;
 pub mod get;
+pub mod im_channels;
+pub mod leaderboard;
 pub mod list;
+pub mod roster;
+pub mod scheduled_export_job;
+pub mod scheduled_exports;
+pub mod short_code;
+pub mod stats_history;
+pub mod stats_job;
 pub mod update;
+pub mod workload;
 
+use actix_multipart::Multipart;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
-use crate::models::classes::requests::{ClassQueryParams, CreateClassRequest, UpdateClassRequest};
+use crate::models::classes::requests::{
+    ClassActivityReportParams, ClassBundleExportParams, ClassBundleImportParams,
+    ClassErasureRequest, ClassFeedQuery, ClassQueryParams, ClassRosterExportParams,
+    ClassStatsHistoryParams, ClassWorkloadParams, CreateClassApiTokenRequest,
+    CreateClassImChannelRequest, CreateClassRequest, CreateClassScheduledExportRequest,
+    JoinClassByShortCodeRequest, LeaderboardOptInRequest, UpdateClassImChannelRequest,
+    UpdateClassLeaderboardRequest, UpdateClassRequest, UpdateClassScheduledExportRequest,
+};
 use crate::storage::Storage;
 
+pub use scheduled_export_job::spawn_scheduled_export_job;
+pub use stats_job::spawn_class_stats_snapshot_job;
+
 pub struct ClassService {
     storage: Option<Arc<dyn Storage>>,
 }
@@ -63,6 +89,24 @@ impl ClassService {
         get::get_class_by_code(self, req, code).await
     }
 
+    // 获取（必要时生成）班级短码
+    pub async fn get_short_code(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        short_code::get_short_code(self, req, class_id).await
+    }
+
+    // 使用班级短码加入班级
+    pub async fn join_by_short_code(
+        &self,
+        req: &HttpRequest,
+        join_data: JoinClassByShortCodeRequest,
+    ) -> ActixResult<HttpResponse> {
+        short_code::join_by_short_code(self, req, join_data).await
+    }
+
     // 更新班级信息
     pub async fn update_class(
         &self,
@@ -90,4 +134,252 @@ impl ClassService {
     ) -> ActixResult<HttpResponse> {
         export::export_class_report(self, req, class_id).await
     }
+
+    // 导出班级内容包
+    pub async fn export_class_bundle(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        params: ClassBundleExportParams,
+    ) -> ActixResult<HttpResponse> {
+        bundle::export_class_bundle(self, req, class_id, params).await
+    }
+
+    // 从班级内容包导入新班级
+    pub async fn import_class_bundle(
+        &self,
+        req: &HttpRequest,
+        params: ClassBundleImportParams,
+        payload: Multipart,
+    ) -> ActixResult<HttpResponse> {
+        bundle::import_class_bundle(self, req, params, payload).await
+    }
+
+    // 班级活跃度报告
+    pub async fn get_activity_report(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        params: ClassActivityReportParams,
+    ) -> ActixResult<HttpResponse> {
+        activity::get_activity_report(self, req, class_id, params).await
+    }
+
+    // 班级统计趋势
+    pub async fn get_stats_history(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        params: ClassStatsHistoryParams,
+    ) -> ActixResult<HttpResponse> {
+        stats_history::get_stats_history(self, req, class_id, params).await
+    }
+
+    // 导出班级名单
+    pub async fn export_roster(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        params: ClassRosterExportParams,
+    ) -> ActixResult<HttpResponse> {
+        roster::export_roster(self, req, class_id, params).await
+    }
+
+    // 班级学生作业负荷
+    pub async fn get_workload(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        params: ClassWorkloadParams,
+    ) -> ActixResult<HttpResponse> {
+        workload::get_workload(self, req, class_id, params).await
+    }
+
+    // 班级作业用时与难度校准报告
+    pub async fn get_workload_calibration(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        calibration::get_workload_calibration(self, req, class_id).await
+    }
+
+    // 班级排行榜
+    pub async fn get_leaderboard(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        leaderboard::get_leaderboard(self, req, class_id).await
+    }
+
+    // 更新班级排行榜设置
+    pub async fn update_leaderboard_settings(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        update: UpdateClassLeaderboardRequest,
+    ) -> ActixResult<HttpResponse> {
+        leaderboard::update_leaderboard_settings(self, req, class_id, update).await
+    }
+
+    // 设置本人是否在排行榜中署名
+    pub async fn set_leaderboard_opt_in(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        opt_in: LeaderboardOptInRequest,
+    ) -> ActixResult<HttpResponse> {
+        leaderboard::set_leaderboard_opt_in(self, req, class_id, opt_in).await
+    }
+
+    // 预览学生在班级内可擦除的数据
+    pub async fn preview_erasure(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        user_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        erasure::preview_erasure(self, req, class_id, user_id).await
+    }
+
+    // 凭确认令牌擦除学生在班级内的数据
+    pub async fn erase_student_data(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        user_id: i64,
+        erasure: ClassErasureRequest,
+    ) -> ActixResult<HttpResponse> {
+        erasure::erase_student_data(self, req, class_id, user_id, erasure).await
+    }
+
+    // 班级动态流
+    pub async fn get_feed(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        query: ClassFeedQuery,
+    ) -> ActixResult<HttpResponse> {
+        feed::get_feed(self, req, class_id, query).await
+    }
+
+    // 班级 IM 通知渠道
+    pub async fn list_im_channels(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        im_channels::list_im_channels(self, req, class_id).await
+    }
+
+    pub async fn create_im_channel(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        data: CreateClassImChannelRequest,
+    ) -> ActixResult<HttpResponse> {
+        im_channels::create_im_channel(self, req, class_id, data).await
+    }
+
+    pub async fn update_im_channel(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        channel_id: i64,
+        data: UpdateClassImChannelRequest,
+    ) -> ActixResult<HttpResponse> {
+        im_channels::update_im_channel(self, req, class_id, channel_id, data).await
+    }
+
+    pub async fn delete_im_channel(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        channel_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        im_channels::delete_im_channel(self, req, class_id, channel_id).await
+    }
+
+    pub async fn test_im_channel(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        channel_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        im_channels::test_im_channel(self, req, class_id, channel_id).await
+    }
+
+    // 班级定时导出
+    pub async fn list_scheduled_exports(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        scheduled_exports::list_scheduled_exports(self, req, class_id).await
+    }
+
+    pub async fn create_scheduled_export(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        data: CreateClassScheduledExportRequest,
+    ) -> ActixResult<HttpResponse> {
+        scheduled_exports::create_scheduled_export(self, req, class_id, data).await
+    }
+
+    pub async fn update_scheduled_export(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        export_id: i64,
+        data: UpdateClassScheduledExportRequest,
+    ) -> ActixResult<HttpResponse> {
+        scheduled_exports::update_scheduled_export(self, req, class_id, export_id, data).await
+    }
+
+    pub async fn delete_scheduled_export(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        export_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        scheduled_exports::delete_scheduled_export(self, req, class_id, export_id).await
+    }
+
+    // 班级 API 令牌
+    pub async fn list_api_tokens(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        api_tokens::list_api_tokens(self, req, class_id).await
+    }
+
+    pub async fn create_api_token(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        data: CreateClassApiTokenRequest,
+    ) -> ActixResult<HttpResponse> {
+        api_tokens::create_api_token(self, req, class_id, data).await
+    }
+
+    pub async fn revoke_api_token(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        token_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        api_tokens::revoke_api_token(self, req, class_id, token_id).await
+    }
+
+    pub async fn list_api_token_logs(
+        &self,
+        req: &HttpRequest,
+        class_id: i64,
+        token_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        api_tokens::list_api_token_logs(self, req, class_id, token_id).await
+    }
 }
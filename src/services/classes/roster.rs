@@ -0,0 +1,352 @@
+//! 班级名单导出
+//!
+//! 生成可打印的班级名单（PDF）或表格（XLSX），用于线下点名、签到。固定列为班级内姓名、
+//! 用户名、班级角色与加入日期，教师可通过 `columns` 追加邮箱、签到栏等列。
+
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::class_users::requests::ClassUserQuery;
+use crate::models::classes::entities::Class;
+use crate::models::classes::requests::ClassRosterExportParams;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::jobs::{self, JobOutput};
+use crate::storage::Storage;
+use crate::utils::export::{ExportCell, ExportFormat, ExportTable};
+
+/// 成员数不超过该值时直接返回文件，否则转为后台任务生成
+const SYNC_MEMBER_LIMIT: i64 = 300;
+
+const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RosterFormat {
+    Pdf,
+    Xlsx,
+}
+
+impl RosterFormat {
+    fn parse(format: Option<&str>) -> Option<Self> {
+        match format.map(|f| f.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("pdf") => Some(Self::Pdf),
+            Some("xlsx") => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// 可选附加列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RosterColumn {
+    Email,
+    DisplayName,
+    LastLogin,
+    /// 空白签到栏
+    Signature,
+    /// 空白备注栏
+    Remark,
+}
+
+impl RosterColumn {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "email" => Some(Self::Email),
+            "display_name" => Some(Self::DisplayName),
+            "last_login" => Some(Self::LastLogin),
+            "signature" => Some(Self::Signature),
+            "remark" => Some(Self::Remark),
+            _ => None,
+        }
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            Self::Email => "邮箱",
+            Self::DisplayName => "账号昵称",
+            Self::LastLogin => "最后登录",
+            Self::Signature => "签到",
+            Self::Remark => "备注",
+        }
+    }
+}
+
+/// 解析附加列（按请求中的顺序，忽略重复项）
+fn parse_columns(columns: Option<&str>) -> Result<Vec<RosterColumn>, String> {
+    let mut parsed = Vec::new();
+    for name in columns
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let column = RosterColumn::parse(name).ok_or_else(|| format!("不支持的列: {name}"))?;
+        if !parsed.contains(&column) {
+            parsed.push(column);
+        }
+    }
+    Ok(parsed)
+}
+
+fn role_label(role: &ClassUserRole) -> &'static str {
+    match role {
+        ClassUserRole::Teacher => "教师",
+        ClassUserRole::ClassRepresentative => "课代表",
+        ClassUserRole::Student => "学生",
+    }
+}
+
+pub async fn export_roster(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    params: ClassRosterExportParams,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let Some(format) = RosterFormat::parse(params.format.as_deref()) else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "format 仅支持 pdf 或 xlsx",
+        )));
+    };
+    let columns = match parse_columns(params.columns.as_deref()) {
+        Ok(columns) => columns,
+        Err(message) => {
+            return Ok(HttpResponse::BadRequest()
+                .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+        }
+    };
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => c,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以导出名单",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    let member_count = match storage
+        .list_class_users_with_pagination(class_id, member_query(1, 1, params.role.clone()))
+        .await
+    {
+        Ok(resp) => resp.pagination.total,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级成员失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 大班级交给后台任务，避免请求长时间阻塞
+    if member_count > SYNC_MEMBER_LIMIT {
+        let role = params.role;
+        let job = match jobs::submit_job(user_id, "class_roster", async move {
+            build_roster(&storage, &class, role, &columns, format).await
+        })
+        .await
+        {
+            Ok(job) => job,
+            Err(e) => return Ok(jobs::queue_full_response(e)),
+        };
+
+        return Ok(HttpResponse::Accepted().json(ApiResponse::success(
+            job,
+            "名单生成中，请稍后通过任务接口下载",
+        )));
+    }
+
+    match build_roster(&storage, &class, params.role, &columns, format).await {
+        Ok(output) => Ok(HttpResponse::Ok()
+            .insert_header((header::CONTENT_TYPE, output.content_type))
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", output.file_name),
+            ))
+            .body(output.data)),
+        Err(e) => Ok(HttpResponse::InternalServerError()
+            .json(ApiResponse::error_empty(ErrorCode::ExportFailed, e))),
+    }
+}
+
+fn member_query(page: i64, size: i64, role: Option<ClassUserRole>) -> ClassUserQuery {
+    ClassUserQuery {
+        page: Some(page),
+        size: Some(size),
+        search: None,
+        role,
+    }
+}
+
+/// 生成名单文件
+async fn build_roster(
+    storage: &Arc<dyn Storage>,
+    class: &Class,
+    role: Option<ClassUserRole>,
+    columns: &[RosterColumn],
+    format: RosterFormat,
+) -> Result<JobOutput, String> {
+    let mut members = Vec::new();
+    let mut page = 1;
+    loop {
+        let resp = storage
+            .list_class_users_with_pagination(class.id, member_query(page, 500, role.clone()))
+            .await
+            .map_err(|e| format!("查询班级成员失败: {e}"))?;
+        members.extend(resp.items);
+        if page >= resp.pagination.total_pages {
+            break;
+        }
+        page += 1;
+    }
+
+    let user_ids: Vec<i64> = members.iter().map(|m| m.user_id).collect();
+    let mut users: HashMap<i64, _> = storage
+        .list_users_by_ids(&user_ids)
+        .await
+        .map_err(|e| format!("查询用户失败: {e}"))?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let mut rows: Vec<_> = members
+        .into_iter()
+        .filter_map(|member| {
+            let user = users.remove(&member.user_id)?;
+            let name = member
+                .profile_name
+                .clone()
+                .or_else(|| user.display_name.clone())
+                .unwrap_or_else(|| user.username.clone());
+            Some((member, user, name))
+        })
+        .collect();
+    // 教师、课代表在前，其余按姓名排序
+    rows.sort_by(|a, b| {
+        b.0.role
+            .rank()
+            .cmp(&a.0.role.rank())
+            .then_with(|| a.2.cmp(&b.2))
+            .then_with(|| a.1.username.cmp(&b.1.username))
+    });
+
+    let mut headers = vec!["序号", "姓名", "用户名", "角色", "加入日期"];
+    headers.extend(columns.iter().map(RosterColumn::header));
+    let mut table = ExportTable::new("名单", &headers);
+    for (index, (member, user, name)) in rows.into_iter().enumerate() {
+        let mut row: Vec<ExportCell> = vec![
+            (index as i64 + 1).into(),
+            name.into(),
+            user.username.into(),
+            role_label(&member.role).into(),
+            member.joined_at.format("%Y-%m-%d").to_string().into(),
+        ];
+        row.extend(columns.iter().map(|column| {
+            match column {
+                RosterColumn::Email => user.email.clone().into(),
+                RosterColumn::DisplayName => user.display_name.clone().into(),
+                RosterColumn::LastLogin => user
+                    .last_login
+                    .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+                    .into(),
+                RosterColumn::Signature | RosterColumn::Remark => ExportCell::Empty,
+            }
+        }));
+        table.push_row(row);
+    }
+
+    let title = format!("{} 名单", class.name);
+    let output = match format {
+        RosterFormat::Pdf => JobOutput {
+            file_name: format!("roster-{}.pdf", class.id),
+            content_type: PDF_CONTENT_TYPE,
+            data: table.to_pdf(
+                &title,
+                &[
+                    format!("人数：{}", table.len()),
+                    format!(
+                        "导出时间：{}",
+                        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+                    ),
+                ],
+            ),
+        },
+        RosterFormat::Xlsx => JobOutput {
+            file_name: format!("roster-{}.xlsx", class.id),
+            content_type: ExportFormat::Xlsx.content_type(),
+            data: table.to_xlsx()?,
+        },
+    };
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(parse_columns(None), Ok(vec![]));
+        assert_eq!(
+            parse_columns(Some("signature, email,signature")),
+            Ok(vec![RosterColumn::Signature, RosterColumn::Email])
+        );
+        assert!(parse_columns(Some("password_hash")).is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(RosterFormat::parse(None), Some(RosterFormat::Pdf));
+        assert_eq!(RosterFormat::parse(Some("XLSX")), Some(RosterFormat::Xlsx));
+        assert_eq!(RosterFormat::parse(Some("csv")), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! 定时导出执行任务
+//!
+//! 定期领取到期的定时导出，生成班级报表后按投递方式发送：邮件经配置的 HTTP 邮件中继以附件发送，
+//! Webhook 以 multipart 表单上传。失败时通知创建者，连续失败达到 `scheduled_exports.max_failures`
+//! 后自动停用。创建者已不再是班级教师（且不是管理员）时不再生成报表。
+
+use std::sync::Arc;
+
+use base64::Engine;
+use once_cell::sync::Lazy;
+use serde_json::json;
+
+use super::export::{XLSX_CONTENT_TYPE, build_class_report, class_report_filename};
+use crate::config::AppConfig;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::{
+    Class, ClassScheduledExport, ScheduledExportDelivery, ScheduledExportKind,
+};
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::users::entities::UserRole;
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::services::outbound::public::check_public_url;
+use crate::services::outbound::{EXPORT_WEBHOOK_CLIENT, MAIL_RELAY_CLIENT};
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 检查间隔，默认 300 秒，可通过系统设置 `jobs.scheduled_export_interval` 调整
+pub static CHECK_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(300));
+
+/// 每次检查最多执行的定时导出数，其余留到下次检查
+const BATCH_SIZE: u64 = 20;
+
+/// 启动定时导出执行任务
+pub fn spawn_scheduled_export_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        CHECK_INTERVAL.run(|| run_scheduled_exports(&storage)).await;
+    });
+}
+
+async fn run_scheduled_exports(storage: &Arc<dyn Storage>) {
+    run_due_scheduled_exports(storage, chrono::Utc::now().timestamp()).await;
+}
+
+/// 执行计划时间不晚于 `now` 的定时导出，返回投递成功的数量
+pub async fn run_due_scheduled_exports(storage: &Arc<dyn Storage>, now: i64) -> usize {
+    let exports = match storage.claim_due_scheduled_exports(now, BATCH_SIZE).await {
+        Ok(exports) => exports,
+        Err(e) => {
+            tracing::warn!("Failed to claim due scheduled exports: {e}");
+            return 0;
+        }
+    };
+
+    let max_failures = AppConfig::get().scheduled_exports.max_failures;
+    let mut delivered = 0;
+    for export in exports {
+        let error = run_export(storage, &export).await.err();
+        if error.is_none() {
+            delivered += 1;
+        }
+
+        let updated = match storage
+            .finish_scheduled_export_run(export.id, error.clone(), max_failures)
+            .await
+        {
+            Ok(updated) => updated,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to record scheduled export {} result: {e}",
+                    export.id
+                );
+                None
+            }
+        };
+
+        if let Some(error) = error {
+            tracing::warn!("Scheduled export {} failed: {error}", export.id);
+            notify_failure(storage, updated.as_ref().unwrap_or(&export), error).await;
+        }
+    }
+    delivered
+}
+
+/// 生成并投递一次导出
+async fn run_export(
+    storage: &Arc<dyn Storage>,
+    export: &ClassScheduledExport,
+) -> Result<(), String> {
+    let class = storage
+        .get_class_by_id(export.class_id)
+        .await
+        .map_err(|e| format!("查询班级失败: {e}"))?
+        .ok_or_else(|| "班级不存在".to_string())?;
+    check_creator(storage, export).await?;
+
+    let (filename, data) = match export.kind {
+        ScheduledExportKind::ClassReport => (
+            class_report_filename(class.id),
+            build_class_report(storage, &class, true).await?,
+        ),
+    };
+
+    match export.delivery {
+        ScheduledExportDelivery::Email => deliver_email(export, &class, &filename, &data).await,
+        ScheduledExportDelivery::Webhook => deliver_webhook(export, &filename, data).await,
+    }
+}
+
+/// 创建者须仍为班级教师或管理员
+async fn check_creator(
+    storage: &Arc<dyn Storage>,
+    export: &ClassScheduledExport,
+) -> Result<(), String> {
+    let is_admin = storage
+        .get_user_by_id(export.created_by)
+        .await
+        .map_err(|e| format!("查询创建者失败: {e}"))?
+        .is_some_and(|user| user.role == UserRole::Admin);
+    if is_admin {
+        return Ok(());
+    }
+
+    let class_user = storage
+        .get_class_user_by_user_id_and_class_id(export.created_by, export.class_id)
+        .await
+        .map_err(|e| format!("查询班级成员失败: {e}"))?;
+    match class_user {
+        Some(cu) if cu.role == ClassUserRole::Teacher => Ok(()),
+        _ => Err("创建者已不再是班级教师".to_string()),
+    }
+}
+
+/// 通过邮件中继以附件发送
+async fn deliver_email(
+    export: &ClassScheduledExport,
+    class: &Class,
+    filename: &str,
+    data: &[u8],
+) -> Result<(), String> {
+    let config = &AppConfig::get().scheduled_exports;
+    if config.mail_relay_url.is_empty() {
+        return Err("服务器未配置邮件中继".to_string());
+    }
+
+    let body = json!({
+        "from": config.mail_from,
+        "to": export.target,
+        "subject": format!("班级报表：{}", class.name),
+        "text": format!("附件为班级「{}」的定时导出报表。", class.name),
+        "attachments": [{
+            "filename": filename,
+            "content_type": XLSX_CONTENT_TYPE,
+            "content": base64::engine::general_purpose::STANDARD.encode(data),
+        }],
+    });
+
+    let response = MAIL_RELAY_CLIENT
+        .send(|client| {
+            let request = client.post(&config.mail_relay_url).json(&body);
+            if config.mail_relay_token.is_empty() {
+                request
+            } else {
+                request.bearer_auth(&config.mail_relay_token)
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("邮件中继返回 HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// 以 multipart 表单上传到 Webhook
+async fn deliver_webhook(
+    export: &ClassScheduledExport,
+    filename: &str,
+    data: Vec<u8>,
+) -> Result<(), String> {
+    // 保存后 DNS 记录可能已改指向内网，投递前重新校验
+    check_public_url(&export.target)
+        .await
+        .map_err(|e| format!("Webhook 地址不可用：{e}"))?;
+    let generated_at = chrono::Utc::now().to_rfc3339();
+
+    let response = EXPORT_WEBHOOK_CLIENT
+        .send(|client| {
+            let part =
+                reqwest::multipart::Part::bytes(data.clone()).file_name(filename.to_string());
+            let form = reqwest::multipart::Form::new()
+                .text("class_id", export.class_id.to_string())
+                .text("export_id", export.id.to_string())
+                .text("generated_at", generated_at.clone())
+                .part("file", part);
+            client.post(&export.target).multipart(form)
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook 返回 HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// 通知创建者本次导出失败
+async fn notify_failure(storage: &Arc<dyn Storage>, export: &ClassScheduledExport, error: String) {
+    let class_name = match storage.get_class_by_id(export.class_id).await {
+        Ok(Some(class)) => class.name,
+        _ => format!("#{}", export.class_id),
+    };
+    let status = if export.enabled {
+        format!(
+            "将在下次计划时间（{}）重试",
+            export.next_run_at.format("%Y-%m-%d %H:%M UTC")
+        )
+    } else {
+        format!(
+            "已连续失败 {} 次，定时导出已停用，请检查配置后重新启用",
+            export.consecutive_failures
+        )
+    };
+
+    send_templated_notification(
+        storage.clone(),
+        export.created_by,
+        NotificationType::ScheduledExportFailed,
+        vec![
+            ("class_name", class_name),
+            ("error", error),
+            ("status", status),
+        ],
+        Some(ReferenceType::Class),
+        Some(export.class_id),
+    )
+    .await;
+}
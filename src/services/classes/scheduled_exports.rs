@@ -0,0 +1,219 @@
+//! 班级定时导出管理
+//!
+//! 定时导出按计划生成班级报表，由 [`super::scheduled_export_job`] 执行并投递。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ClassService;
+use super::im_channels::check_class_teacher;
+use crate::config::AppConfig;
+use crate::models::classes::entities::{ScheduledExportDelivery, ScheduledExportFrequency};
+use crate::models::classes::requests::{
+    CreateClassScheduledExportRequest, UpdateClassScheduledExportRequest,
+};
+use crate::models::classes::responses::ClassScheduledExportListResponse;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::outbound::public::{PublicUrlError, check_public_url};
+use crate::utils::validate::validate_email;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理定时导出";
+
+/// 投递地址最大长度
+const MAX_TARGET_LENGTH: usize = 512;
+
+fn invalid(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::ClassScheduledExportInvalid,
+        message,
+    ))
+}
+
+fn export_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::ClassScheduledExportNotFound,
+        "定时导出不存在",
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验执行计划
+fn validate_schedule(
+    frequency: ScheduledExportFrequency,
+    weekday: Option<i32>,
+    hour: i32,
+) -> Result<(), HttpResponse> {
+    if !(0..=23).contains(&hour) {
+        return Err(invalid("执行时间 hour 必须为 0-23 的整点（UTC）"));
+    }
+    if frequency == ScheduledExportFrequency::Weekly
+        && !weekday.is_some_and(|d| (0..=6).contains(&d))
+    {
+        return Err(invalid("每周执行须指定 weekday（0 为周一，6 为周日）"));
+    }
+    Ok(())
+}
+
+/// 校验投递地址
+///
+/// Webhook 地址不能指向内网；主机名暂时无法解析时允许保存，投递前会再次校验。
+async fn validate_target(
+    delivery: ScheduledExportDelivery,
+    target: &str,
+) -> Result<(), HttpResponse> {
+    if target.len() > MAX_TARGET_LENGTH {
+        return Err(invalid(format!(
+            "投递地址不能超过 {MAX_TARGET_LENGTH} 个字符"
+        )));
+    }
+    match delivery {
+        ScheduledExportDelivery::Email => {
+            if AppConfig::get().scheduled_exports.mail_relay_url.is_empty() {
+                return Err(invalid("服务器未配置邮件中继，无法使用邮件投递"));
+            }
+            validate_email(target).map_err(|_| invalid("邮箱地址格式无效"))
+        }
+        ScheduledExportDelivery::Webhook => {
+            if !target.starts_with("https://") {
+                return Err(invalid("Webhook 地址必须以 https:// 开头"));
+            }
+            match check_public_url(target).await {
+                Ok(()) | Err(PublicUrlError::Unresolved) => Ok(()),
+                Err(PublicUrlError::Internal) => {
+                    Err(invalid("Webhook 地址不能指向内网、回环或链路本地地址"))
+                }
+                Err(PublicUrlError::Invalid) => Err(invalid("Webhook 地址格式无效")),
+            }
+        }
+    }
+}
+
+pub async fn list_scheduled_exports(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.list_class_scheduled_exports(class_id).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ClassScheduledExportListResponse { items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询定时导出失败: {e}"))),
+    }
+}
+
+pub async fn create_scheduled_export(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: CreateClassScheduledExportRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, _) = match check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await
+    {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    if let Err(resp) = validate_schedule(req.frequency, req.weekday, req.hour) {
+        return Ok(resp);
+    }
+    if let Err(resp) = validate_target(req.delivery, &req.target).await {
+        return Ok(resp);
+    }
+
+    match storage
+        .create_class_scheduled_export(class_id, req, user_id)
+        .await
+    {
+        Ok(export) => {
+            Ok(HttpResponse::Created().json(ApiResponse::success(export, "定时导出已创建")))
+        }
+        Err(e) => Ok(internal_error(format!("创建定时导出失败: {e}"))),
+    }
+}
+
+pub async fn update_scheduled_export(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    export_id: i64,
+    req: UpdateClassScheduledExportRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    let export = match storage
+        .get_class_scheduled_export(class_id, export_id)
+        .await
+    {
+        Ok(Some(export)) => export,
+        Ok(None) => return Ok(export_not_found()),
+        Err(e) => return Ok(internal_error(format!("查询定时导出失败: {e}"))),
+    };
+
+    // 按更新后的完整配置校验（例如改为每周执行时须同时指定星期）
+    if let Err(resp) = validate_schedule(
+        req.frequency.unwrap_or(export.frequency),
+        req.weekday.or(export.weekday),
+        req.hour.unwrap_or(export.hour),
+    ) {
+        return Ok(resp);
+    }
+    if let Err(resp) = validate_target(
+        req.delivery.unwrap_or(export.delivery),
+        req.target.as_deref().unwrap_or(&export.target),
+    )
+    .await
+    {
+        return Ok(resp);
+    }
+
+    match storage
+        .update_class_scheduled_export(class_id, export_id, req)
+        .await
+    {
+        Ok(Some(export)) => {
+            Ok(HttpResponse::Ok().json(ApiResponse::success(export, "定时导出已更新")))
+        }
+        Ok(None) => Ok(export_not_found()),
+        Err(e) => Ok(internal_error(format!("更新定时导出失败: {e}"))),
+    }
+}
+
+pub async fn delete_scheduled_export(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    export_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage
+        .delete_class_scheduled_export(class_id, export_id)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("定时导出已删除"))),
+        Ok(false) => Ok(export_not_found()),
+        Err(e) => Ok(internal_error(format!("删除定时导出失败: {e}"))),
+    }
+}
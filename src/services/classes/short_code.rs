@@ -0,0 +1,277 @@
+//! 班级短码
+//!
+//! 与永久邀请码不同，短码为 6 位数字，便于在课堂上投屏或口头告知，
+//! 按系统设置 `class.short_code_rotation` 定期轮换。短码只保存在对象缓存中：
+//! `class_short_code:code:{code}` 用于加入时查找班级，`class_short_code:class:{id}`
+//! 用于在有效期内重复返回同一个短码。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::ClassService;
+use crate::cache::traits::TypedObjectCache;
+use crate::cache::{CacheResult, ObjectCache};
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::requests::JoinClassByShortCodeRequest;
+use crate::models::classes::responses::ClassShortCodeResponse;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::class_users::join::join_as_student;
+use crate::services::system::DynamicConfig;
+
+/// 短码位数
+const CODE_LENGTH: usize = 6;
+/// 生成不冲突短码的最大尝试次数
+const MAX_GENERATE_ATTEMPTS: usize = 10;
+
+/// 缓存中的短码记录（部分缓存实现忽略 TTL，因此自行记录过期时间）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ShortCodeEntry {
+    class_id: i64,
+    code: String,
+    expires_at: i64,
+}
+
+fn code_key(code: &str) -> String {
+    format!("class_short_code:code:{code}")
+}
+
+fn class_key(class_id: i64) -> String {
+    format!("class_short_code:class:{class_id}")
+}
+
+fn cache(request: &HttpRequest) -> Option<Arc<dyn ObjectCache>> {
+    request
+        .app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>()
+        .map(|c| c.get_ref().clone())
+}
+
+/// 读取未过期的短码记录
+async fn active_entry(cache: &Arc<dyn ObjectCache>, key: &str) -> Option<ShortCodeEntry> {
+    match cache.get::<ShortCodeEntry>(key).await {
+        CacheResult::Found(entry) if entry.expires_at > Utc::now().timestamp() => Some(entry),
+        _ => None,
+    }
+}
+
+fn is_valid_code(code: &str) -> bool {
+    code.len() == CODE_LENGTH && code.chars().all(|c| c.is_ascii_digit())
+}
+
+fn invalid_code() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::ClassShortCodeInvalid,
+        "短码无效或已过期",
+    ))
+}
+
+fn cache_unavailable() -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        "缓存服务不可用，无法使用班级短码",
+    ))
+}
+
+fn to_response(entry: ShortCodeEntry, rotation_minutes: i64) -> ClassShortCodeResponse {
+    ClassShortCodeResponse {
+        class_id: entry.class_id,
+        code: entry.code,
+        expires_at: DateTime::<Utc>::from_timestamp(entry.expires_at, 0).unwrap_or_default(),
+        rotation_minutes,
+    }
+}
+
+/// 获取班级当前短码，不存在或已过期时生成新短码
+pub async fn get_short_code(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    // 仅班级教师与管理员可以生成短码
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以生成短码",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    let Some(cache) = cache(request) else {
+        return Ok(cache_unavailable());
+    };
+
+    let rotation_minutes = DynamicConfig::class_short_code_rotation().await;
+
+    // 有效期内重复请求返回同一个短码，便于多个设备同时展示
+    if let Some(entry) = active_entry(&cache, &class_key(class_id)).await {
+        return Ok(HttpResponse::Ok().json(ApiResponse::success(
+            to_response(entry, rotation_minutes),
+            "获取短码成功",
+        )));
+    }
+
+    let mut code = None;
+    for _ in 0..MAX_GENERATE_ATTEMPTS {
+        let candidate = format!("{:06}", rand::rng().random_range(0..1_000_000u32));
+        if active_entry(&cache, &code_key(&candidate)).await.is_none() {
+            code = Some(candidate);
+            break;
+        }
+    }
+    let Some(code) = code else {
+        return Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                "生成短码失败，请稍后重试",
+            )),
+        );
+    };
+
+    let ttl = (rotation_minutes * 60) as u64;
+    let entry = ShortCodeEntry {
+        class_id,
+        code: code.clone(),
+        expires_at: Utc::now().timestamp() + ttl as i64,
+    };
+    cache.insert(code_key(&code), entry.clone(), ttl).await;
+    cache.insert(class_key(class_id), entry.clone(), ttl).await;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        to_response(entry, rotation_minutes),
+        "生成短码成功",
+    )))
+}
+
+/// 使用短码加入班级
+pub async fn join_by_short_code(
+    service: &ClassService,
+    request: &HttpRequest,
+    req: JoinClassByShortCodeRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let code = req.code.trim();
+    if !is_valid_code(code) {
+        return Ok(invalid_code());
+    }
+
+    let Some(cache) = cache(request) else {
+        return Ok(cache_unavailable());
+    };
+    let Some(entry) = active_entry(&cache, &code_key(code)).await else {
+        return Ok(invalid_code());
+    };
+
+    // 已被轮换掉的旧短码立即失效
+    match active_entry(&cache, &class_key(entry.class_id)).await {
+        Some(current) if current.code == entry.code => {}
+        _ => return Ok(invalid_code()),
+    }
+
+    // 其他组织的班级按短码无效处理
+    let class = match storage.get_class_by_id(entry.class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => return Ok(invalid_code()),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::ClassJoinFailed,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    match storage
+        .get_class_user_by_user_id_and_class_id(user_id, class.id)
+        .await
+    {
+        Ok(Some(_)) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error(
+                ErrorCode::ClassAlreadyJoined,
+                class,
+                "User has already joined the class",
+            )));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::ClassJoinFailed,
+                    format!("查询班级成员失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    join_as_student(&storage, request, user_id, &class, req.profile_name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_code() {
+        assert!(is_valid_code("012345"));
+        assert!(!is_valid_code("12345"));
+        assert!(!is_valid_code("12345a"));
+    }
+}
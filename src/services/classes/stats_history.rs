@@ -0,0 +1,116 @@
+//! 班级统计趋势
+//!
+//! 读取每日快照任务写入的 `class_stats_daily`，供教师仪表盘绘制成员数、累计提交与平均得分率的趋势图，
+//! 避免按需回溯计算历史数据。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::{Days, Utc};
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::requests::ClassStatsHistoryParams;
+use crate::models::classes::responses::ClassStatsHistoryResponse;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 默认返回天数
+const DEFAULT_DAYS: i64 = 30;
+/// 最大返回天数
+const MAX_DAYS: i64 = 365;
+
+pub async fn get_stats_history(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    params: ClassStatsHistoryParams,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let days = params.days.unwrap_or(DEFAULT_DAYS);
+    if !(1..=MAX_DAYS).contains(&days) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("days 必须在 1 到 {MAX_DAYS} 之间"),
+        )));
+    }
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以查看统计趋势",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    // 截至今天的最近 N 天；快照任务在次日零点才生成前一天的数据
+    let to = Utc::now().date_naive();
+    let from = to - Days::new(days as u64 - 1);
+    let items = match storage
+        .list_class_stats_daily(
+            class_id,
+            &from.format("%Y-%m-%d").to_string(),
+            &to.format("%Y-%m-%d").to_string(),
+        )
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级统计快照失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ClassStatsHistoryResponse {
+            class_id,
+            days,
+            items,
+        },
+        "查询成功",
+    )))
+}
@@ -0,0 +1,36 @@
+//! 班级统计快照定时任务
+//!
+//! 每天 UTC 零点为各班级记录前一天结束时的累计指标，写入 `class_stats_daily` 表。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::storage::Storage;
+
+/// 启动每日班级统计快照任务
+pub fn spawn_class_stats_snapshot_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        loop {
+            let now = chrono::Utc::now();
+            let next_midnight = (now.date_naive() + chrono::Days::new(1))
+                .and_hms_opt(0, 0, 0)
+                .map(|dt| dt.and_utc())
+                .unwrap_or(now + chrono::Duration::days(1));
+            let wait = (next_midnight - now)
+                .to_std()
+                .unwrap_or(Duration::from_secs(60));
+            tokio::time::sleep(wait).await;
+
+            // 零点之后为刚结束的一天生成快照
+            let stat_date = chrono::Utc::now().date_naive() - chrono::Days::new(1);
+            match storage.generate_class_stats_snapshots(stat_date).await {
+                Ok(count) => {
+                    tracing::info!("Generated {count} class stats snapshot(s) for {stat_date}");
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to generate class stats snapshots for {stat_date}: {e}");
+                }
+            }
+        }
+    });
+}
@@ -3,12 +3,13 @@ use tracing::error;
 
 use super::ClassService;
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireJWT, RequirePermission, TenantGuard},
     models::{
         ApiResponse, ErrorCode,
         classes::{entities::Class, requests::UpdateClassRequest},
-        users::entities::UserRole,
+        users::entities::{AdminPermission, UserRole},
     },
+    utils::validate::validate_profile_name_pattern,
 };
 
 pub async fn update_class(
@@ -17,8 +18,14 @@ pub async fn update_class(
     class_id: i64,
     update_data: UpdateClassRequest,
 ) -> ActixResult<HttpResponse> {
-    let role = RequireJWT::extract_user_role(request);
     let storage = service.get_storage(request);
+    // 被授予班级管理权限的用户按管理员处理
+    let role = match RequireJWT::extract_user_claims(request) {
+        Some(user) => Some(
+            RequirePermission::effective_role(&storage, &user, AdminPermission::ClassManage).await,
+        ),
+        None => None,
+    };
 
     let uid = match RequireJWT::extract_user_id(request) {
         Some(id) => id,
@@ -43,9 +50,10 @@ pub async fn update_class(
         }
     };
 
+    // 跨组织的班级按不存在处理
     let class = match class_opt {
-        Some(class) => class,
-        None => {
+        Some(class) if TenantGuard::can_access(request, class.org_id) => class,
+        _ => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::ClassNotFound,
                 "Class not found",
@@ -58,6 +66,15 @@ pub async fn update_class(
         return Ok(resp);
     }
 
+    if let Some(pattern) = update_data.profile_name_pattern.as_deref()
+        && !pattern.is_empty()
+        && let Err(msg) = validate_profile_name_pattern(pattern)
+    {
+        return Ok(
+            HttpResponse::BadRequest().json(ApiResponse::error_empty(ErrorCode::BadRequest, msg))
+        );
+    }
+
     match storage.update_class(class_id, update_data).await {
         Ok(Some(class)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
             class,
@@ -0,0 +1,109 @@
+//! 班级学生作业负荷
+//!
+//! 按天汇总本班学生在所有班级中截止的作业数，帮助教师选择负荷较低的截止日期；
+//! 只返回聚合计数，不暴露学生或其他班级的信息。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::Utc;
+
+use super::ClassService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::requests::ClassWorkloadParams;
+use crate::models::classes::responses::ClassWorkloadResponse;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 默认统计周数
+const DEFAULT_WEEKS: i64 = 4;
+/// 最大统计周数
+const MAX_WEEKS: i64 = 12;
+
+pub async fn get_workload(
+    service: &ClassService,
+    request: &HttpRequest,
+    class_id: i64,
+    params: ClassWorkloadParams,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let weeks = params.weeks.unwrap_or(DEFAULT_WEEKS);
+    if !(1..=MAX_WEEKS).contains(&weeks) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("weeks 必须在 1 到 {MAX_WEEKS} 之间"),
+        )));
+    }
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(c)) if TenantGuard::can_access(request, c.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以查看作业负荷",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级成员失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    let items = match storage
+        .get_class_workload(class_id, Utc::now().date_naive(), weeks * 7)
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计作业负荷失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ClassWorkloadResponse {
+            class_id,
+            weeks,
+            items,
+        },
+        "查询成功",
+    )))
+}
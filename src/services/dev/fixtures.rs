@@ -0,0 +1,314 @@
+//! 示例数据生成
+//!
+//! 每个示例直接构造真实接口使用的响应模型并序列化，模型字段变化时示例随之变化。
+//! 数据是固定的（ID、时间不随请求变化），便于前端做快照对比。
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde_json::Value;
+
+use crate::models::ApiResponse;
+use crate::models::PaginationInfo;
+use crate::models::auth::responses::UserInfoResponse;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::Class;
+use crate::models::classes::responses::{ClassDetail, ClassDetailListResponse, TeacherInfo};
+use crate::models::homeworks::entities::{Homework, SubmissionMode};
+use crate::models::homeworks::responses::{
+    HomeworkCreator, HomeworkListItem, HomeworkListResponse, HomeworkStatsSummary,
+    MyHomeworkStatsResponse, MySubmissionSummary, TeacherHomeworkStatsResponse,
+};
+use crate::models::submissions::entities::SubmissionStatus;
+use crate::models::submissions::responses::{
+    SubmissionCreator, SubmissionListItem, SubmissionListResponse,
+};
+use crate::models::users::entities::{User, UserRole, UserStatus};
+
+/// 示例接口定义
+pub struct Fixture {
+    pub name: &'static str,
+    pub method: &'static str,
+    pub path: &'static str,
+    pub roles: fn() -> &'static [&'static UserRole],
+    pub description: &'static str,
+    /// 生成指定角色视角下的完整响应（含 `ApiResponse` 外层）
+    pub build: fn(&UserRole) -> Value,
+}
+
+/// 全部示例接口
+pub static FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "auth-me",
+        method: "GET",
+        path: "/auth/me",
+        roles: UserRole::all_roles,
+        description: "当前登录用户",
+        build: auth_me,
+    },
+    Fixture {
+        name: "classes",
+        method: "GET",
+        path: "/classes",
+        roles: UserRole::all_roles,
+        description: "班级列表（学生为自己加入的班级）",
+        build: classes,
+    },
+    Fixture {
+        name: "homeworks",
+        method: "GET",
+        path: "/homeworks?class_id=1",
+        roles: UserRole::all_roles,
+        description: "班级作业列表（学生附带自己的提交，教师附带统计摘要）",
+        build: homeworks,
+    },
+    Fixture {
+        name: "homeworks-my-stats",
+        method: "GET",
+        path: "/homeworks/my/stats",
+        roles: UserRole::all_roles,
+        description: "学生作业统计",
+        build: homeworks_my_stats,
+    },
+    Fixture {
+        name: "homeworks-teacher-stats",
+        method: "GET",
+        path: "/homeworks/teacher/stats",
+        roles: UserRole::teacher_roles,
+        description: "教师作业统计",
+        build: homeworks_teacher_stats,
+    },
+    Fixture {
+        name: "submissions",
+        method: "GET",
+        path: "/submissions?homework_id=1",
+        roles: UserRole::all_roles,
+        description: "作业提交列表（学生只能看到自己的提交）",
+        build: submissions,
+    },
+];
+
+/// 按名称查找示例接口
+pub fn find(name: &str) -> Option<&'static Fixture> {
+    FIXTURES.iter().find(|fixture| fixture.name == name)
+}
+
+/// 示例数据的基准时间
+fn base_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap()
+}
+
+fn envelope<T: serde::Serialize + ts_rs::TS>(data: T, message: &str) -> Value {
+    let mut value = serde_json::to_value(ApiResponse::success(data, message)).unwrap_or_default();
+    // 时间戳固定，保证同一角色多次请求得到相同的示例
+    value["timestamp"] = Value::String(base_time().to_rfc3339());
+    value
+}
+
+fn pagination(total: i64) -> PaginationInfo {
+    PaginationInfo {
+        page: 1,
+        page_size: 20,
+        total,
+        total_pages: 1,
+    }
+}
+
+fn user(role: &UserRole) -> User {
+    let (id, username, display_name) = match role {
+        UserRole::Admin => (1, "admin", "系统管理员"),
+        UserRole::Teacher => (2, "teacher_zhang", "张老师"),
+        UserRole::User => (3, "student_li", "李同学"),
+        UserRole::Viewer => (4, "parent_li", "李同学家长"),
+    };
+    User {
+        id,
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password_hash: String::new(),
+        role: role.clone(),
+        status: UserStatus::Active,
+        display_name: Some(display_name.to_string()),
+        avatar_url: None,
+        org_id: None,
+        last_login: Some(base_time() + Duration::days(3)),
+        created_at: base_time() - Duration::days(30),
+        updated_at: base_time(),
+    }
+}
+
+fn class() -> Class {
+    Class {
+        id: 1,
+        name: "高一(1)班".to_string(),
+        description: Some("2026 春季学期".to_string()),
+        teacher_id: 2,
+        invite_code: "K7Q2M9XA".to_string(),
+        org_id: None,
+        archived_at: None,
+        allow_student_messages: false,
+        require_real_name: false,
+        profile_name_pattern: None,
+        created_at: base_time() - Duration::days(30),
+        updated_at: base_time(),
+    }
+}
+
+fn homework(id: i64, title: &str, due_in_days: i64) -> Homework {
+    Homework {
+        id,
+        class_id: 1,
+        title: title.to_string(),
+        description: Some("完成课本习题并上传解答过程".to_string()),
+        max_score: 100.0,
+        deadline: Some(base_time() + Duration::days(due_in_days)),
+        allow_late: true,
+        submission_mode: SubmissionMode::Both,
+        max_content_length: None,
+        exam_mode: false,
+        require_self_assessment: false,
+        show_grade_context: false,
+        estimated_minutes: Some(60),
+        difficulty: Some(3),
+        grace_minutes: None,
+        created_by: 2,
+        created_at: base_time() - Duration::days(7),
+        updated_at: base_time() - Duration::days(7),
+    }
+}
+
+fn auth_me(role: &UserRole) -> Value {
+    envelope(
+        UserInfoResponse { user: user(role) },
+        "User information retrieved successfully",
+    )
+}
+
+fn classes(role: &UserRole) -> Value {
+    let teacher = user(&UserRole::Teacher);
+    let my_role = match role {
+        UserRole::Admin | UserRole::Viewer => None,
+        UserRole::Teacher => Some(ClassUserRole::Teacher),
+        UserRole::User => Some(ClassUserRole::Student),
+    };
+    let response = ClassDetailListResponse {
+        pagination: pagination(1),
+        items: vec![ClassDetail {
+            class: class(),
+            teacher: TeacherInfo {
+                id: teacher.id,
+                username: teacher.username,
+                display_name: teacher.display_name,
+            },
+            member_count: 32,
+            my_role,
+        }],
+    };
+    let message = match role {
+        UserRole::User => "User class list retrieved successfully",
+        _ => "Class list retrieved successfully",
+    };
+    envelope(response, message)
+}
+
+fn homeworks(role: &UserRole) -> Value {
+    let teacher = user(&UserRole::Teacher);
+    let is_student = *role == UserRole::User;
+    let items = [(1, "第一章 集合与函数", -2), (2, "第二章 三角函数", 5)]
+        .into_iter()
+        .map(|(id, title, due_in_days)| HomeworkListItem {
+            homework: homework(id, title, due_in_days),
+            creator: Some(HomeworkCreator {
+                id: teacher.id,
+                username: teacher.username.clone(),
+                display_name: teacher.display_name.clone(),
+                avatar_url: None,
+            }),
+            // 学生只在已过截止的第一份作业上有提交
+            my_submission: (is_student && id == 1).then(|| MySubmissionSummary {
+                id: 1,
+                version: 1,
+                status: SubmissionStatus::GRADED.to_string(),
+                is_late: false,
+                score: Some(92.0),
+            }),
+            stats_summary: (!is_student).then_some(HomeworkStatsSummary {
+                total_students: 30,
+                submitted_count: if id == 1 { 28 } else { 6 },
+                graded_count: if id == 1 { 25 } else { 0 },
+            }),
+            is_exempted: false,
+            is_locked: false,
+        })
+        .collect::<Vec<_>>();
+    let response = HomeworkListResponse {
+        pagination: pagination(items.len() as i64),
+        items,
+    };
+    envelope(response, "获取作业列表成功")
+}
+
+fn homeworks_my_stats(_role: &UserRole) -> Value {
+    envelope(
+        MyHomeworkStatsResponse {
+            pending: 1,
+            submitted: 0,
+            graded: 1,
+            total: 2,
+        },
+        "获取作业统计成功",
+    )
+}
+
+fn homeworks_teacher_stats(_role: &UserRole) -> Value {
+    envelope(
+        TeacherHomeworkStatsResponse {
+            total_homeworks: 2,
+            pending_review: 9,
+            total_submissions: 34,
+            graded_submissions: 25,
+        },
+        "获取教师统计成功",
+    )
+}
+
+fn submissions(role: &UserRole) -> Value {
+    let students = [(3, "student_li", "李同学"), (4, "student_wang", "王同学")];
+    let items = students
+        .into_iter()
+        .enumerate()
+        // 学生只能看到自己的提交
+        .filter(|(_, (id, _, _))| *role != UserRole::User || *id == 3)
+        .map(|(index, (creator_id, username, display_name))| {
+            let graded = index == 0;
+            SubmissionListItem {
+                id: index as i64 + 1,
+                homework_id: 1,
+                creator_id,
+                creator: SubmissionCreator {
+                    id: creator_id,
+                    username: username.to_string(),
+                    display_name: Some(display_name.to_string()),
+                    avatar_url: None,
+                },
+                part_id: None,
+                version: 1,
+                content: Some("解答见附件".to_string()),
+                status: if graded {
+                    SubmissionStatus::GRADED
+                } else {
+                    SubmissionStatus::LATE
+                }
+                .to_string(),
+                is_late: !graded,
+                submitted_at: (base_time() - Duration::days(3 - index as i64)).to_rfc3339(),
+                word_count: 5,
+                char_count: 5,
+                attachment_count: 1,
+            }
+        })
+        .collect::<Vec<_>>();
+    let response = SubmissionListResponse {
+        pagination: pagination(items.len() as i64),
+        items,
+    };
+    envelope(response, "查询成功")
+}
@@ -0,0 +1,64 @@
+//! 开发模式示例数据服务
+//!
+//! 仅在 `app.environment = "development"` 时挂载。按角色返回各接口的示例响应，
+//! 供前端在后端接口未就绪或本地无数据时对齐数据结构。
+
+pub mod fixtures;
+
+use actix_web::{HttpResponse, Result as ActixResult};
+
+use crate::models::dev::requests::DevFixtureQuery;
+use crate::models::dev::responses::{DevFixtureInfo, DevFixtureListResponse};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+pub struct DevService;
+
+impl DevService {
+    pub fn new_lazy() -> Self {
+        Self
+    }
+
+    /// 列出可用的示例接口
+    pub async fn list_fixtures(&self) -> ActixResult<HttpResponse> {
+        let items = fixtures::FIXTURES
+            .iter()
+            .map(|fixture| DevFixtureInfo {
+                name: fixture.name.to_string(),
+                method: fixture.method.to_string(),
+                path: fixture.path.to_string(),
+                roles: (fixture.roles)().iter().map(|&role| role.clone()).collect(),
+                description: fixture.description.to_string(),
+            })
+            .collect();
+        Ok(HttpResponse::Ok().json(ApiResponse::success(
+            DevFixtureListResponse { items },
+            "查询成功",
+        )))
+    }
+
+    /// 返回指定角色视角下的示例响应
+    ///
+    /// 角色无权访问真实接口时，返回真实接口的 403 响应。
+    pub async fn get_fixture(
+        &self,
+        endpoint: &str,
+        query: DevFixtureQuery,
+    ) -> ActixResult<HttpResponse> {
+        let Some(fixture) = fixtures::find(endpoint) else {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::NotFound,
+                format!("示例接口不存在: {endpoint}"),
+            )));
+        };
+
+        let role = query.role.unwrap_or(UserRole::User);
+        if !(fixture.roles)().contains(&&role) {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::Forbidden,
+                "Access denied.",
+            )));
+        }
+        Ok(HttpResponse::Ok().json((fixture.build)(&role)))
+    }
+}
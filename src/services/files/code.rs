@@ -0,0 +1,90 @@
+//! 代码文件查看
+//!
+//! 返回文本文件的内容与上传时识别的代码语言，前端据此做语法高亮，无需再按文件名猜测。
+//! 迁移前上传的文件没有记录语言，查看时按文件名即时识别。
+//! 超过 `preview.code_max_size` 的文件与非 UTF-8 文本的文件不返回内容。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::FileService;
+use super::download::load_accessible_file;
+use super::tiering::stored_path;
+use crate::config::AppConfig;
+use crate::models::files::responses::CodeFileResponse;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::code_language::looks_like_text;
+use crate::utils::detect_code_language;
+
+pub async fn get_code_file(
+    service: &FileService,
+    request: &HttpRequest,
+    file_token: String,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let db_file = match load_accessible_file(&storage, request, &file_token).await {
+        Ok(f) => f,
+        Err(resp) => return Ok(resp),
+    };
+
+    let max_size = AppConfig::get().preview.code_max_size;
+    if db_file.file_size > max_size as i64 {
+        return Ok(
+            HttpResponse::PayloadTooLarge().json(ApiResponse::error_empty(
+                ErrorCode::FileSizeExceeded,
+                format!("文件超过 {max_size} 字节，无法在线查看"),
+            )),
+        );
+    }
+
+    let path = stored_path(&db_file);
+    let data = match tokio::fs::read(&path).await {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::FileNotFound,
+                "文件不存在",
+            )));
+        }
+        Err(e) => {
+            tracing::error!("Failed to read {}: {e}", path.display());
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    "File read failed",
+                )),
+            );
+        }
+    };
+
+    let content = match String::from_utf8(data) {
+        Ok(content) if looks_like_text(content.as_bytes()) => content,
+        _ => {
+            return Ok(
+                HttpResponse::UnsupportedMediaType().json(ApiResponse::error_empty(
+                    ErrorCode::FilePreviewUnsupported,
+                    "该文件不是 UTF-8 文本，无法在线查看",
+                )),
+            );
+        }
+    };
+    let content = content
+        .strip_prefix('\u{feff}')
+        .map(str::to_string)
+        .unwrap_or(content);
+
+    let language = db_file.code_language.or_else(|| {
+        detect_code_language(&db_file.original_name, content.as_bytes()).map(str::to_string)
+    });
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        CodeFileResponse {
+            file_name: db_file.original_name,
+            language,
+            size: db_file.file_size,
+            line_count: content.lines().count(),
+            content,
+        },
+        "获取文件内容成功",
+    )))
+}
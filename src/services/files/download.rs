@@ -1,17 +1,19 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, http::header};
-use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::sync::Arc;
 
 use super::FileService;
-use crate::config::AppConfig;
-use crate::errors::HWSystemError;
+use super::tiering::{read_error_response, read_stored_file};
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::files::entities::File;
+use crate::models::homeworks::entities::AttachmentKind;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::services::homeworks::attachments::can_view_solution;
+use crate::storage::Storage;
 
 // TODO: 实现更细粒度的文件访问权限检查
 // 目前 download_token 已经提供了一定程度的保护（需要知道 token 才能下载）
 // 后续可考虑：
-// 1. 作业附件：验证用户是否是该班级成员
+// 1. 作业附件：验证用户是否是该班级成员（参考答案已校验开放状态，见 check_solution_access）
 // 2. 提交附件：验证用户是否是提交者或班级教师
 // 3. 添加 token 过期机制
 
@@ -22,16 +24,44 @@ pub async fn handle_download(
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
 
-    let db_file = match storage.get_file_by_token(&file_token).await {
-        Ok(Some(f)) => f,
-        Ok(None) => {
-            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+    let db_file = match load_accessible_file(&storage, request, &file_token).await {
+        Ok(f) => f,
+        Err(resp) => return Ok(resp),
+    };
+
+    // 冷存储中的文件读取较慢，按层级使用不同的超时
+    let buf = match read_stored_file(&db_file).await {
+        Ok(data) => data,
+        Err(e) => return Ok(read_error_response(&db_file, e)),
+    };
+
+    // 使用数据库中的原始文件名
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, db_file.file_type.as_str()))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", db_file.original_name),
+        ))
+        .body(buf))
+}
+
+/// 按下载令牌查询文件并校验当前用户可以访问（下载与预览共用）
+pub(super) async fn load_accessible_file(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    file_token: &str,
+) -> Result<File, HttpResponse> {
+    let db_file = match storage.get_file_by_token(file_token).await {
+        // 跨组织的文件按不存在处理
+        Ok(Some(f)) if TenantGuard::can_access(request, f.org_id) => f,
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::FileNotFound,
                 "File not found",
             )));
         }
         Err(e) => {
-            return Ok(
+            return Err(
                 HttpResponse::InternalServerError().json(ApiResponse::error_empty(
                     ErrorCode::InternalServerError,
                     format!("File query failed: {e}"),
@@ -40,47 +70,56 @@ pub async fn handle_download(
         }
     };
 
-    let config = AppConfig::get();
-    let upload_dir = &config.upload.dir;
-    let file_path = format!("{}/{}", upload_dir, db_file.stored_name);
+    // 作业参考答案需在开放后才能下载
+    check_solution_access(storage, request, db_file.id).await?;
+
+    Ok(db_file)
+}
+
+/// 文件若是某作业的参考答案，要求当前用户已可查看该作业的答案
+async fn check_solution_access(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    file_id: i64,
+) -> Result<(), HttpResponse> {
+    let internal_error = |e: String| {
+        HttpResponse::InternalServerError()
+            .json(ApiResponse::error_empty(ErrorCode::InternalServerError, e))
+    };
 
-    if !Path::new(&file_path).exists() {
-        return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
-            ErrorCode::FileNotFound,
-            "文件不存在",
-        )));
+    let homework_ids: Vec<i64> = match storage.list_homework_attachments_by_file(file_id).await {
+        Ok(refs) => refs
+            .into_iter()
+            .filter(|(_, kind)| *kind == AttachmentKind::Solution)
+            .map(|(homework_id, _)| homework_id)
+            .collect(),
+        Err(e) => return Err(internal_error(format!("File query failed: {e}"))),
+    };
+    if homework_ids.is_empty() {
+        return Ok(());
     }
 
-    let mut file = match File::open(&file_path) {
-        Ok(f) => f,
-        Err(e) => {
-            tracing::error!("{:?}", HWSystemError::file_operation(format!("{e:?}")));
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                    ErrorCode::InternalServerError,
-                    "File open failed",
-                )),
-            );
-        }
+    let Some(user) = RequireJWT::extract_user_claims(request) else {
+        return Err(HttpResponse::Unauthorized()
+            .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录")));
     };
 
-    let mut buf = Vec::new();
-    if file.read_to_end(&mut buf).is_err() {
-        tracing::error!("{:?}", HWSystemError::file_operation("File read failed"));
-        return Ok(
-            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                ErrorCode::InternalServerError,
-                "File read failed",
-            )),
-        );
+    // 同一文件可能被多个作业引用，任一作业已开放即可下载
+    for homework_id in homework_ids {
+        let homework = match storage.get_homework_by_id(homework_id).await {
+            Ok(Some(homework)) => homework,
+            Ok(None) => continue,
+            Err(e) => return Err(internal_error(format!("查询作业失败: {e}"))),
+        };
+        match can_view_solution(storage, &user, &homework).await {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(e) => return Err(internal_error(format!("查询作业失败: {e}"))),
+        }
     }
 
-    // 使用数据库中的原始文件名
-    Ok(HttpResponse::Ok()
-        .insert_header((header::CONTENT_TYPE, db_file.file_type.as_str()))
-        .insert_header((
-            header::CONTENT_DISPOSITION,
-            format!("attachment; filename=\"{}\"", db_file.original_name),
-        ))
-        .body(buf))
+    Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+        ErrorCode::HomeworkSolutionLocked,
+        "参考答案尚未公开",
+    )))
 }
@@ -1,12 +1,25 @@
+pub mod code;
 pub mod download;
+pub mod preview;
+pub mod quarantine;
+pub mod retention;
+pub mod retention_job;
+pub mod tiering;
+pub mod tiering_job;
 pub mod upload;
 
 use actix_multipart::Multipart;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
+use crate::models::files::requests::{
+    FilePreviewQuery, RetentionReportQuery, UpdateClassRetentionRequest,
+};
 use crate::storage::Storage;
 
+pub use retention_job::spawn_file_retention_job;
+pub use tiering_job::spawn_file_tiering_job;
+
 pub struct FileService {
     storage: Option<Arc<dyn Storage>>,
 }
@@ -45,4 +58,84 @@ impl FileService {
     ) -> ActixResult<HttpResponse> {
         download::handle_download(self, request, file_token).await
     }
+
+    // 文档预览
+    pub async fn handle_preview(
+        &self,
+        request: &HttpRequest,
+        file_token: String,
+        query: FilePreviewQuery,
+    ) -> ActixResult<HttpResponse> {
+        preview::handle_preview(self, request, file_token, query).await
+    }
+
+    // 代码文件内容与语言
+    pub async fn get_code_file(
+        &self,
+        request: &HttpRequest,
+        file_token: String,
+    ) -> ActixResult<HttpResponse> {
+        code::get_code_file(self, request, file_token).await
+    }
+
+    // 获取班级文件保留策略
+    pub async fn get_class_retention(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        retention::get_class_retention(self, request, class_id).await
+    }
+
+    // 更新班级文件保留策略
+    pub async fn update_class_retention(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        req: UpdateClassRetentionRequest,
+    ) -> ActixResult<HttpResponse> {
+        retention::update_class_retention(self, request, class_id, req).await
+    }
+
+    // 班级即将删除的附件报告
+    pub async fn get_class_retention_report(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        query: RetentionReportQuery,
+    ) -> ActixResult<HttpResponse> {
+        retention::get_class_retention_report(self, request, class_id, query).await
+    }
+
+    // 全站即将删除的文件报告
+    pub async fn get_retention_report(
+        &self,
+        request: &HttpRequest,
+        query: RetentionReportQuery,
+    ) -> ActixResult<HttpResponse> {
+        retention::get_retention_report(self, request, query).await
+    }
+
+    // 列出待审核的隔离文件
+    pub async fn list_quarantined_files(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        quarantine::list_quarantined_files(self, request).await
+    }
+
+    // 放行隔离文件
+    pub async fn approve_quarantined_file(
+        &self,
+        request: &HttpRequest,
+        id: i64,
+    ) -> ActixResult<HttpResponse> {
+        quarantine::approve_quarantined_file(self, request, id).await
+    }
+
+    // 删除隔离文件
+    pub async fn delete_quarantined_file(
+        &self,
+        request: &HttpRequest,
+        id: i64,
+    ) -> ActixResult<HttpResponse> {
+        quarantine::delete_quarantined_file(self, request, id).await
+    }
 }
@@ -0,0 +1,311 @@
+//! 文档预览
+//!
+//! Office 文档经外部转换服务（见配置 `preview.*`）转换为 PDF 或 HTML 后缓存在
+//! `preview.cache_dir` 中。源文件上传后不再修改，缓存按存储文件名区分，无需失效；
+//! 文件被保留策略清理时一并删除缓存。PDF 源文件直接内联返回，不经过转换。
+//!
+//! 预览与下载使用相同的访问控制。转换结果禁止内容嗅探，HTML 预览附带
+//! `Content-Security-Policy: sandbox`，文档中夹带的脚本不会在本站域名下执行。
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, http::header};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+use super::FileService;
+use super::download::load_accessible_file;
+use super::tiering::stored_path;
+use crate::config::{AppConfig, PreviewProvider};
+use crate::models::files::entities::{File, PreviewFormat};
+use crate::models::files::requests::FilePreviewQuery;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::outbound::PREVIEW_CLIENT;
+
+/// 支持转换预览的文档扩展名
+const CONVERTIBLE_EXTENSIONS: &[&str] = &[
+    "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp", "rtf",
+];
+
+/// 预览响应的浏览器缓存时间（秒）
+const PREVIEW_MAX_AGE: u64 = 3600;
+
+/// HTML 预览的内容安全策略：禁止脚本、表单与外部资源
+const HTML_PREVIEW_CSP: &str =
+    "sandbox; default-src 'none'; img-src data:; style-src 'unsafe-inline'";
+
+/// 缓存路径 -> 转换锁，避免同一文件被并发重复转换
+static CONVERSION_LOCKS: Lazy<DashMap<PathBuf, Arc<Mutex<()>>>> = Lazy::new(DashMap::new);
+
+fn extension(name: &str) -> Option<String> {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+}
+
+/// 文件是否为可转换预览的 Office 文档
+pub fn is_convertible(file: &File) -> bool {
+    extension(&file.original_name).is_some_and(|ext| CONVERTIBLE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+fn is_pdf(file: &File) -> bool {
+    file.file_type == "application/pdf" || extension(&file.original_name).as_deref() == Some("pdf")
+}
+
+/// 转换结果的缓存路径
+fn cache_path(stored_name: &str, format: PreviewFormat) -> PathBuf {
+    Path::new(&AppConfig::get().preview.cache_dir)
+        .join(format!("{stored_name}.{}", format.extension()))
+}
+
+/// 删除文件的全部预览缓存（文件被清理时调用）
+pub async fn remove_cached_previews(stored_name: &str) {
+    for format in [PreviewFormat::Pdf, PreviewFormat::Html] {
+        let path = cache_path(stored_name, format);
+        if let Err(e) = tokio::fs::remove_file(&path).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove preview {}: {e}", path.display());
+        }
+    }
+}
+
+pub async fn handle_preview(
+    service: &FileService,
+    request: &HttpRequest,
+    file_token: String,
+    query: FilePreviewQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let db_file = match load_accessible_file(&storage, request, &file_token).await {
+        Ok(f) => f,
+        Err(resp) => return Ok(resp),
+    };
+    let format = query.format;
+    let source = stored_path(&db_file);
+
+    // PDF 无需转换
+    if format == PreviewFormat::Pdf && is_pdf(&db_file) {
+        return Ok(match read_source(&source).await {
+            Ok(data) => preview_response(data, format, &db_file.original_name),
+            Err(resp) => resp,
+        });
+    }
+
+    if !is_convertible(&db_file) {
+        return Ok(
+            HttpResponse::UnsupportedMediaType().json(ApiResponse::error_empty(
+                ErrorCode::FilePreviewUnsupported,
+                "该文件类型不支持预览",
+            )),
+        );
+    }
+
+    let config = &AppConfig::get().preview;
+    if !config.enabled || config.url.is_empty() {
+        return Ok(
+            HttpResponse::ServiceUnavailable().json(ApiResponse::error_empty(
+                ErrorCode::FilePreviewUnavailable,
+                "未启用文档预览服务",
+            )),
+        );
+    }
+    if format == PreviewFormat::Html && config.provider == PreviewProvider::Gotenberg {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::FilePreviewUnsupported,
+            "当前预览服务仅支持 PDF 格式",
+        )));
+    }
+    if db_file.file_size > config.max_size as i64 {
+        return Ok(
+            HttpResponse::PayloadTooLarge().json(ApiResponse::error_empty(
+                ErrorCode::FileSizeExceeded,
+                "文件过大，无法预览",
+            )),
+        );
+    }
+
+    match load_or_convert(&db_file, &source, format).await {
+        Ok(data) => Ok(preview_response(data, format, &db_file.original_name)),
+        Err(resp) => Ok(resp),
+    }
+}
+
+/// 读取缓存的转换结果，不存在时调用转换服务并写入缓存
+async fn load_or_convert(
+    file: &File,
+    source: &Path,
+    format: PreviewFormat,
+) -> Result<Vec<u8>, HttpResponse> {
+    let target = cache_path(&file.stored_name, format);
+    if let Ok(data) = tokio::fs::read(&target).await {
+        return Ok(data);
+    }
+
+    let lock = CONVERSION_LOCKS.entry(target.clone()).or_default().clone();
+    let _guard = lock.lock().await;
+
+    // 等待期间其他请求可能已完成转换
+    if let Ok(data) = tokio::fs::read(&target).await {
+        return Ok(data);
+    }
+
+    let result = convert_and_cache(file, source, &target, format).await;
+    CONVERSION_LOCKS.remove(&target);
+    result
+}
+
+async fn convert_and_cache(
+    file: &File,
+    source: &Path,
+    target: &Path,
+    format: PreviewFormat,
+) -> Result<Vec<u8>, HttpResponse> {
+    let data = read_source(source).await?;
+
+    let converted = convert(&file.original_name, data, format)
+        .await
+        .map_err(|e| {
+            tracing::warn!("Failed to convert {} to {format}: {e}", file.stored_name);
+            HttpResponse::BadGateway().json(ApiResponse::error_empty(
+                ErrorCode::FilePreviewUnavailable,
+                "文档转换失败，请稍后重试",
+            ))
+        })?;
+
+    // 先写临时文件再重命名，避免并发读取到不完整的缓存；写入失败不影响本次预览
+    let temp = target.with_extension(format!("{}.tmp", format.extension()));
+    let cached = async {
+        if let Some(dir) = target.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(&temp, &converted).await?;
+        tokio::fs::rename(&temp, target).await
+    }
+    .await;
+    if let Err(e) = cached {
+        tracing::warn!("Failed to cache preview {}: {e}", target.display());
+    }
+
+    Ok(converted)
+}
+
+/// 调用外部转换服务
+async fn convert(file_name: &str, data: Vec<u8>, format: PreviewFormat) -> Result<Vec<u8>, String> {
+    let config = &AppConfig::get().preview;
+    let base = config.url.trim_end_matches('/');
+    let (url, field) = match config.provider {
+        PreviewProvider::Generic => (format!("{base}?format={format}"), "file"),
+        PreviewProvider::Gotenberg => (format!("{base}/forms/libreoffice/convert"), "files"),
+        PreviewProvider::Collabora => (format!("{base}/cool/convert-to/{format}"), "data"),
+    };
+
+    let response = PREVIEW_CLIENT
+        .send(|client| {
+            // 转换服务按文件扩展名识别文档类型
+            let part =
+                reqwest::multipart::Part::bytes(data.clone()).file_name(file_name.to_string());
+            let request = client
+                .post(&url)
+                .multipart(reqwest::multipart::Form::new().part(field, part));
+            if config.api_key.is_empty() {
+                request
+            } else {
+                request.bearer_auth(&config.api_key)
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    if body.is_empty() {
+        return Err("empty response".to_string());
+    }
+    Ok(body.to_vec())
+}
+
+async fn read_source(path: &Path) -> Result<Vec<u8>, HttpResponse> {
+    tokio::fs::read(path).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::FileNotFound,
+                "文件不存在",
+            ))
+        } else {
+            tracing::error!("Failed to read {}: {e}", path.display());
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                "File read failed",
+            ))
+        }
+    })
+}
+
+fn preview_response(body: Vec<u8>, format: PreviewFormat, original_name: &str) -> HttpResponse {
+    let stem = Path::new(original_name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("preview");
+
+    let mut builder = HttpResponse::Ok();
+    builder
+        .insert_header((header::CONTENT_TYPE, format.content_type()))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("inline; filename=\"{stem}.{}\"", format.extension()),
+        ))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("private, max-age={PREVIEW_MAX_AGE}"),
+        ));
+    if format == PreviewFormat::Html {
+        builder.insert_header((header::CONTENT_SECURITY_POLICY, HTML_PREVIEW_CSP));
+    }
+    builder.body(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(original_name: &str, file_type: &str) -> File {
+        File {
+            id: 1,
+            user_id: None,
+            original_name: original_name.to_string(),
+            stored_name: "stored".to_string(),
+            file_type: file_type.to_string(),
+            file_size: 0,
+            file_path: String::new(),
+            download_token: String::new(),
+            citation_count: 0,
+            org_id: None,
+            code_language: None,
+            storage_tier: Default::default(),
+            tiered_at: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_preview_file_kinds() {
+        assert!(is_convertible(&file(
+            "报告.DOCX",
+            "application/octet-stream"
+        )));
+        assert!(is_convertible(&file("slides.pptx", "")));
+        assert!(!is_convertible(&file("scan.pdf", "application/pdf")));
+        assert!(!is_convertible(&file("README", "text/plain")));
+
+        assert!(is_pdf(&file("scan.pdf", "application/octet-stream")));
+        assert!(is_pdf(&file("scan", "application/pdf")));
+        assert!(!is_pdf(&file("报告.docx", "")));
+    }
+}
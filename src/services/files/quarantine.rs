@@ -0,0 +1,185 @@
+//! 上传文件隔离审核
+//!
+//! 未通过魔术字节校验的上传不直接拒绝，而是移入上传目录下的 `quarantine` 子目录并记录原因，
+//! 由管理员通过或删除；审核结果通知上传者。
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::FileService;
+use crate::config::AppConfig;
+use crate::middlewares::TenantGuard;
+use crate::models::files::entities::QuarantinedFile;
+use crate::models::files::responses::{FileUploadResponse, QuarantinedFileListResponse};
+use crate::models::notifications::entities::NotificationType;
+use crate::models::usage::entities::UsageMetric;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::services::usage::record_usage;
+use crate::storage::Storage;
+
+/// 隔离目录（位于上传目录下）
+pub fn quarantine_dir() -> PathBuf {
+    Path::new(&AppConfig::get().upload.dir).join("quarantine")
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::QuarantinedFileNotFound,
+        "隔离文件不存在",
+    ))
+}
+
+/// 查询隔离文件并校验租户范围
+async fn load_quarantined_file(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    id: i64,
+) -> Result<QuarantinedFile, HttpResponse> {
+    match storage.get_quarantined_file(id).await {
+        Ok(Some(file)) if TenantGuard::can_access(request, file.org_id) => Ok(file),
+        Ok(_) => Err(not_found()),
+        Err(e) => Err(internal_error(format!("查询隔离文件失败: {e}"))),
+    }
+}
+
+/// 通知上传者审核结果
+fn notify_uploader(storage: Arc<dyn Storage>, file: &QuarantinedFile, result: &str) {
+    let user_id = file.user_id;
+    let vars = vec![
+        ("file_name", file.original_name.clone()),
+        ("result", result.to_string()),
+    ];
+    executor::spawn(NOTIFICATIONS_QUEUE, async move {
+        send_templated_notification(
+            storage,
+            user_id,
+            NotificationType::FileQuarantineReviewed,
+            vars,
+            None,
+            None,
+        )
+        .await;
+    });
+}
+
+/// 列出待审核的隔离文件
+/// GET /files/quarantine
+pub async fn list_quarantined_files(
+    service: &FileService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    match storage.list_quarantined_files().await {
+        Ok(items) => {
+            let items = items
+                .into_iter()
+                .filter(|f| TenantGuard::can_access(request, f.org_id))
+                .collect();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                QuarantinedFileListResponse { items },
+                "查询成功",
+            )))
+        }
+        Err(e) => Ok(internal_error(format!("查询隔离文件失败: {e}"))),
+    }
+}
+
+/// 审核通过：文件移回上传目录并生成下载令牌
+/// POST /files/quarantine/{id}/approve
+pub async fn approve_quarantined_file(
+    service: &FileService,
+    request: &HttpRequest,
+    id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let quarantined = match load_quarantined_file(&storage, request, id).await {
+        Ok(file) => file,
+        Err(resp) => return Ok(resp),
+    };
+
+    let source = quarantine_dir().join(&quarantined.stored_name);
+    let target = Path::new(&AppConfig::get().upload.dir).join(&quarantined.stored_name);
+    if let Err(e) = std::fs::rename(&source, &target) {
+        return Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::FileUploadFailed,
+                format!("移动隔离文件失败: {e}"),
+            )),
+        );
+    }
+
+    match storage.release_quarantined_file(id).await {
+        Ok(Some(file)) => {
+            record_usage(
+                storage.clone(),
+                file.org_id,
+                UsageMetric::UploadedBytes,
+                file.file_size,
+            );
+            notify_uploader(storage.clone(), &quarantined, "已通过");
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                FileUploadResponse {
+                    download_token: file.download_token,
+                    file_name: file.original_name,
+                    size: file.file_size,
+                    content_type: file.file_type,
+                    code_language: file.code_language,
+                    sha256: None,
+                    created_at: file.created_at,
+                },
+                "文件已放行",
+            )))
+        }
+        Ok(None) => {
+            // 并发审核时记录已被处理，文件留在上传目录由另一方的结果决定
+            Ok(not_found())
+        }
+        Err(e) => {
+            // 记录未变更，将文件放回隔离目录
+            let _ = std::fs::rename(&target, &source);
+            Ok(internal_error(format!("放行隔离文件失败: {e}")))
+        }
+    }
+}
+
+/// 删除隔离文件及其记录
+/// DELETE /files/quarantine/{id}
+pub async fn delete_quarantined_file(
+    service: &FileService,
+    request: &HttpRequest,
+    id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = load_quarantined_file(&storage, request, id).await {
+        return Ok(resp);
+    }
+
+    match storage.delete_quarantined_file(id).await {
+        Ok(Some(file)) => {
+            let path = quarantine_dir().join(&file.stored_name);
+            if let Err(e) = std::fs::remove_file(&path)
+                && e.kind() != std::io::ErrorKind::NotFound
+            {
+                tracing::warn!("删除隔离文件 {} 失败: {e}", path.display());
+            }
+            notify_uploader(storage.clone(), &file, "已删除");
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("隔离文件已删除")))
+        }
+        Ok(None) => Ok(not_found()),
+        Err(e) => Ok(internal_error(format!("删除隔离文件失败: {e}"))),
+    }
+}
@@ -0,0 +1,231 @@
+//! 文件保留策略
+//!
+//! 系统默认值来自系统设置 `retention.*`，班级可覆盖提交附件与作业附件的保留天数。
+//! 到期文件由 [`super::retention_job`] 定期清理，教师可提前查看即将删除的文件并导出。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::FileService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::Class;
+use crate::models::files::entities::{ClassRetentionOverride, FileRetentionPolicy};
+use crate::models::files::requests::{RetentionReportQuery, UpdateClassRetentionRequest};
+use crate::models::files::responses::{ClassRetentionResponse, RetentionReportResponse};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+
+/// 保留天数上限（与系统设置约束一致）
+const MAX_RETENTION_DAYS: i32 = 3650;
+/// 报告默认范围（天）
+const DEFAULT_REPORT_DAYS: i64 = 30;
+/// 报告最大范围（天）
+const MAX_REPORT_DAYS: i64 = 365;
+
+/// 读取当前系统级保留策略
+pub async fn current_policy() -> FileRetentionPolicy {
+    FileRetentionPolicy {
+        submission_files_days: DynamicConfig::retention_submission_files_days().await,
+        homework_files_days: DynamicConfig::retention_homework_files_days().await,
+        orphan_files_days: DynamicConfig::retention_orphan_files_days().await,
+    }
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验当前用户是否为班级教师或管理员，返回（用户 ID, 班级）
+async fn check_class_teacher(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<(i64, Class), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok((user_id, class));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => Ok((user_id, class)),
+        Ok(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "只有班级教师可以管理文件保留策略",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+fn build_response(
+    class: Class,
+    overrides: Option<ClassRetentionOverride>,
+    policy: FileRetentionPolicy,
+) -> ClassRetentionResponse {
+    let submission_files_days = overrides
+        .as_ref()
+        .and_then(|o| o.submission_files_days)
+        .map_or(policy.submission_files_days, i64::from);
+    let homework_files_days = overrides
+        .as_ref()
+        .and_then(|o| o.homework_files_days)
+        .map_or(policy.homework_files_days, i64::from);
+
+    ClassRetentionResponse {
+        class_id: class.id,
+        archived_at: class.archived_at,
+        overrides,
+        submission_files_days,
+        homework_files_days,
+    }
+}
+
+fn report_days(query: &RetentionReportQuery) -> i64 {
+    query
+        .days
+        .unwrap_or(DEFAULT_REPORT_DAYS)
+        .clamp(0, MAX_REPORT_DAYS)
+}
+
+pub async fn get_class_retention(
+    service: &FileService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let class = match check_class_teacher(&storage, request, class_id).await {
+        Ok((_, class)) => class,
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage.get_class_retention_override(class_id).await {
+        Ok(overrides) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            build_response(class, overrides, current_policy().await),
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询保留策略失败: {e}"))),
+    }
+}
+
+pub async fn update_class_retention(
+    service: &FileService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: UpdateClassRetentionRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, class) = match check_class_teacher(&storage, request, class_id).await {
+        Ok(checked) => checked,
+        Err(resp) => return Ok(resp),
+    };
+
+    let out_of_range = [req.submission_files_days, req.homework_files_days]
+        .into_iter()
+        .flatten()
+        .any(|days| !(0..=MAX_RETENTION_DAYS).contains(&days));
+    if out_of_range {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("保留天数必须在 0 到 {MAX_RETENTION_DAYS} 之间（0 表示永久保留）"),
+        )));
+    }
+
+    match storage
+        .upsert_class_retention_override(class_id, req, user_id)
+        .await
+    {
+        Ok(overrides) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            build_response(class, Some(overrides), current_policy().await),
+            "保留策略已保存",
+        ))),
+        Err(e) => Ok(internal_error(format!("保存保留策略失败: {e}"))),
+    }
+}
+
+/// 班级即将删除的附件报告
+pub async fn get_class_retention_report(
+    service: &FileService,
+    request: &HttpRequest,
+    class_id: i64,
+    query: RetentionReportQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id).await {
+        return Ok(resp);
+    }
+
+    let days = report_days(&query);
+    let horizon = chrono::Utc::now().timestamp() + days * 86400;
+    match storage
+        .find_retention_candidates(current_policy().await, horizon, Some(class_id))
+        .await
+    {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            RetentionReportResponse {
+                days,
+                total_size: items.iter().map(|i| i.file_size).sum(),
+                items,
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询即将删除的文件失败: {e}"))),
+    }
+}
+
+/// 全站即将删除的文件报告（含孤立上传），按当前管理员可访问的组织过滤
+pub async fn get_retention_report(
+    service: &FileService,
+    request: &HttpRequest,
+    query: RetentionReportQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let days = report_days(&query);
+    let horizon = chrono::Utc::now().timestamp() + days * 86400;
+    match storage
+        .find_retention_candidates(current_policy().await, horizon, None)
+        .await
+    {
+        Ok(items) => {
+            let items: Vec<_> = items
+                .into_iter()
+                .filter(|i| TenantGuard::can_access(request, i.org_id))
+                .collect();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                RetentionReportResponse {
+                    days,
+                    total_size: items.iter().map(|i| i.file_size).sum(),
+                    items,
+                },
+                "查询成功",
+            )))
+        }
+        Err(e) => Ok(internal_error(format!("查询即将删除的文件失败: {e}"))),
+    }
+}
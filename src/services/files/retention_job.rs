@@ -0,0 +1,63 @@
+//! 文件保留清理任务
+//!
+//! 定期按保留策略（见 [`super::retention`]）查找已到期的文件，
+//! 删除数据库记录与附件关联后再删除磁盘文件。
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use super::preview;
+use super::retention::current_policy;
+use super::tiering::{remove_if_exists, stored_path};
+use crate::models::files::entities::File;
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 检查间隔，默认 3600 秒，可通过系统设置 `jobs.file_retention_interval` 调整
+pub static CHECK_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(3600));
+
+/// 启动文件保留清理任务
+pub fn spawn_file_retention_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        CHECK_INTERVAL.run(|| purge_expired_files(&storage)).await;
+    });
+}
+
+/// 删除已到期的文件
+async fn purge_expired_files(storage: &Arc<dyn Storage>) {
+    let now = chrono::Utc::now().timestamp();
+    let candidates = match storage
+        .find_retention_candidates(current_policy().await, now, None)
+        .await
+    {
+        Ok(candidates) => candidates,
+        Err(e) => {
+            tracing::warn!("Failed to find expired files: {e}");
+            return;
+        }
+    };
+    if candidates.is_empty() {
+        return;
+    }
+
+    let file_ids: Vec<i64> = candidates.iter().map(|c| c.file_id).collect();
+    let purged = match storage.purge_files(&file_ids).await {
+        Ok(purged) => purged,
+        Err(e) => {
+            tracing::warn!("Failed to purge expired files: {e}");
+            return;
+        }
+    };
+
+    remove_stored_files(&purged).await;
+    tracing::info!("Purged {} expired files", purged.len());
+}
+
+/// 删除已清除记录的磁盘文件及其预览缓存，失败只记录警告
+pub(crate) async fn remove_stored_files(files: &[File]) {
+    for file in files {
+        remove_if_exists(&stored_path(file)).await;
+        preview::remove_cached_previews(&file.stored_name).await;
+    }
+}
@@ -0,0 +1,84 @@
+//! 附件存储分层
+//!
+//! 文件按 `storage_tier` 存放在上传目录（hot）或冷存储目录（cold），所有读取磁盘文件的地方
+//! 都应通过 [`stored_path`] 解析路径。冷存储通常是低速磁盘或挂载的对象存储，
+//! 下载时使用 `tiering.cold_read_timeout` 作为读取超时。
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use actix_web::HttpResponse;
+
+use crate::config::AppConfig;
+use crate::models::files::entities::{File, StorageTier};
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 文件所在层级的目录
+pub fn tier_dir(tier: StorageTier) -> PathBuf {
+    let config = AppConfig::get();
+    match tier {
+        StorageTier::Hot => PathBuf::from(&config.upload.dir),
+        StorageTier::Cold => PathBuf::from(&config.tiering.cold_dir),
+    }
+}
+
+/// 文件在磁盘上的实际路径
+pub fn stored_path(file: &File) -> PathBuf {
+    tier_dir(file.storage_tier).join(&file.stored_name)
+}
+
+/// 按文件所在层级的超时读取文件内容
+pub async fn read_stored_file(file: &File) -> std::io::Result<Vec<u8>> {
+    let config = &AppConfig::get().tiering;
+    let timeout = match file.storage_tier {
+        StorageTier::Hot => config.hot_read_timeout,
+        StorageTier::Cold => config.cold_read_timeout,
+    };
+    let path = stored_path(file);
+    tokio::time::timeout(Duration::from_secs(timeout), tokio::fs::read(&path))
+        .await
+        .map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("读取 {} 超时", path.display()),
+            )
+        })?
+}
+
+/// 读取失败时的响应：文件缺失 404，超时 504，其余 500
+pub fn read_error_response(file: &File, e: std::io::Error) -> HttpResponse {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::FileNotFound,
+            "文件不存在",
+        )),
+        std::io::ErrorKind::TimedOut => HttpResponse::GatewayTimeout().json(
+            ApiResponse::error_empty(ErrorCode::FileReadTimeout, "读取文件超时，请稍后重试"),
+        ),
+        _ => {
+            tracing::error!("Failed to read file {}: {e}", file.id);
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                "File read failed",
+            ))
+        }
+    }
+}
+
+/// 将文件复制到目标层级的目录，目标目录不存在时自动创建
+pub(super) async fn copy_to_tier(file: &File, tier: StorageTier) -> std::io::Result<PathBuf> {
+    let dir = tier_dir(tier);
+    tokio::fs::create_dir_all(&dir).await?;
+    let target = dir.join(&file.stored_name);
+    tokio::fs::copy(stored_path(file), &target).await?;
+    Ok(target)
+}
+
+/// 删除文件，文件已不存在时忽略
+pub(super) async fn remove_if_exists(path: &Path) {
+    if let Err(e) = tokio::fs::remove_file(path).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove file {}: {e}", path.display());
+    }
+}
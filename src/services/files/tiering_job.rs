@@ -0,0 +1,78 @@
+//! 冷存储迁移任务
+//!
+//! 定期将已归档班级的附件从上传目录迁移到冷存储目录（见 [`super::tiering`]）：
+//! 先复制到冷存储，更新文件的 `storage_tier` 后再删除上传目录中的原文件，
+//! 任一步失败都保留原文件，下次检查时重试。
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use super::tiering::{copy_to_tier, remove_if_exists, stored_path};
+use crate::config::AppConfig;
+use crate::models::files::entities::StorageTier;
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 检查间隔，默认 3600 秒，可通过系统设置 `jobs.file_tiering_interval` 调整
+pub static CHECK_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(3600));
+
+/// 启动冷存储迁移任务（配置 `tiering.enabled` 为 false 时不启动）
+pub fn spawn_file_tiering_job(storage: Arc<dyn Storage>) {
+    if !AppConfig::get().tiering.enabled {
+        return;
+    }
+    tokio::spawn(async move {
+        CHECK_INTERVAL.run(|| tier_archived_files(&storage)).await;
+    });
+}
+
+/// 按系统设置 `tiering.cold_after_days` 迁移到期的附件
+async fn tier_archived_files(storage: &Arc<dyn Storage>) {
+    let cold_after_days = DynamicConfig::tiering_cold_after_days().await;
+    let archived_before = chrono::Utc::now().timestamp() - cold_after_days * 86400;
+    move_archived_files(storage, archived_before).await;
+}
+
+/// 迁移班级归档时间不晚于 `archived_before` 的附件，返回成功迁移的文件数
+pub async fn move_archived_files(storage: &Arc<dyn Storage>, archived_before: i64) -> usize {
+    let candidates = match storage.find_tiering_candidates(archived_before).await {
+        Ok(candidates) => candidates,
+        Err(e) => {
+            tracing::warn!("Failed to find files for cold storage: {e}");
+            return 0;
+        }
+    };
+
+    let mut moved = 0;
+    for file in candidates {
+        let source = stored_path(&file);
+        let target = match copy_to_tier(&file, StorageTier::Cold).await {
+            Ok(target) => target,
+            Err(e) => {
+                tracing::warn!("Failed to copy {} to cold storage: {e}", source.display());
+                continue;
+            }
+        };
+        match storage
+            .set_file_storage_tier(file.id, StorageTier::Cold)
+            .await
+        {
+            Ok(true) => {
+                remove_if_exists(&source).await;
+                moved += 1;
+            }
+            // 文件已被删除或更新失败，丢弃冷存储副本
+            Ok(false) => remove_if_exists(&target).await,
+            Err(e) => {
+                tracing::warn!("Failed to update storage tier of file {}: {e}", file.id);
+                remove_if_exists(&target).await;
+            }
+        }
+    }
+    if moved > 0 {
+        tracing::info!("Moved {moved} archived files to cold storage");
+    }
+    moved
+}
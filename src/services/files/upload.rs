@@ -1,32 +1,124 @@
 use actix_multipart::Multipart;
+use actix_web::http::header::CONTENT_LENGTH;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use futures_util::TryStreamExt;
 use futures_util::stream::StreamExt;
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::{fs::File, path::Path};
 use uuid::Uuid;
 
 use super::FileService;
+use super::quarantine::quarantine_dir;
 use crate::config::AppConfig;
 use crate::errors::HWSystemError;
 use crate::middlewares::RequireJWT;
 use crate::models::ErrorCode;
+use crate::models::files::entities::QuarantineReason;
+use crate::models::files::requests::QuarantinedFileInput;
+use crate::models::files::responses::FileQuarantinedResponse;
+use crate::models::usage::entities::UsageMetric;
 use crate::models::{ApiResponse, files::responses::FileUploadResponse};
 use crate::services::system::DynamicConfig;
-use crate::utils::validate_magic_bytes;
+use crate::services::usage::record_usage;
+use crate::storage::Storage;
+use crate::utils::{detect_code_language, validate_magic_bytes};
+
+/// 上传目录中尚未入库的文件，未调用 [`PendingFile::keep`] 时在释放时删除
+///
+/// 接收过程中任何提前返回（超限、非法字段、请求体错误）都不会留下半截文件。
+struct PendingFile {
+    path: PathBuf,
+    stored_name: String,
+    kept: bool,
+}
+
+impl PendingFile {
+    fn create(upload_dir: &str) -> std::io::Result<(Self, File)> {
+        let stored_name = format!("{}-{}.bin", chrono::Utc::now().timestamp(), Uuid::new_v4());
+        let path = Path::new(upload_dir).join(&stored_name);
+        let file = File::create(&path)?;
+        Ok((
+            Self {
+                path,
+                stored_name,
+                kept: false,
+            },
+            file,
+        ))
+    }
+
+    /// 保留文件，返回存储文件名
+    fn keep(mut self) -> String {
+        self.kept = true;
+        std::mem::take(&mut self.stored_name)
+    }
+}
+
+impl Drop for PendingFile {
+    fn drop(&mut self) {
+        if !self.kept {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// 已完整接收的上传文件
+struct ReceivedFile {
+    pending: PendingFile,
+    original_name: String,
+    file_type: String,
+    size: i64,
+    sha256: String,
+    // 魔术字节不匹配时记录声明的扩展名
+    magic_mismatch: Option<String>,
+    code_language: Option<&'static str>,
+}
+
+fn bad_request(code: ErrorCode, message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::<()>::error_empty(code, message))
+}
 
 pub async fn handle_upload(
     service: &FileService,
     req: &HttpRequest,
     mut payload: Multipart,
 ) -> ActixResult<HttpResponse> {
+    let user_id = match RequireJWT::extract_user_id(req) {
+        Some(id) => id,
+        None => {
+            return Ok(
+                HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
+                    ErrorCode::Unauthorized,
+                    "用户未登录",
+                )),
+            );
+        }
+    };
+
     // 获取配置（静态配置从 AppConfig，动态配置从 DynamicConfig）
     let config = AppConfig::get();
     let upload_dir = &config.upload.dir;
     let max_size = DynamicConfig::upload_max_size().await;
+    let max_request_size = DynamicConfig::upload_max_request_size().await;
     let allowed_types = DynamicConfig::upload_allowed_types().await;
 
+    // 声明的请求长度已超限时不读取请求体
+    let declared_length = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_length.is_some_and(|len| len > max_request_size) {
+        return Ok(bad_request(
+            ErrorCode::FileSizeExceeded,
+            "Request size exceeds the limit",
+        ));
+    }
+
     // 确保上传目录存在
     if !Path::new(upload_dir).exists()
         && let Err(e) = fs::create_dir_all(upload_dir)
@@ -40,137 +132,183 @@ pub async fn handle_upload(
         );
     }
 
-    // 文件相关信息
-    let mut original_name = String::new();
-    let mut file_size: i64 = 0;
-    let mut file_uploaded = false;
-    let mut file_type = String::new();
-    let mut stored_name = String::new();
+    let mut received: Option<ReceivedFile> = None;
+    // 整个请求已接收的字段内容字节数
+    let mut request_size: usize = 0;
 
-    while let Ok(Some(mut field)) = payload.try_next().await {
+    loop {
+        let mut field = match payload.try_next().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => {
+                return Ok(bad_request(
+                    ErrorCode::FileUploadFailed,
+                    format!("Invalid multipart payload: {e}"),
+                ));
+            }
+        };
         let content_disposition = field.content_disposition();
         let name = content_disposition
             .and_then(|cd| cd.get_name())
             .unwrap_or_default()
             .to_string();
 
-        if name == "file" {
-            if file_uploaded {
-                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
-                    ErrorCode::MultifileUploadNotAllowed,
-                    "Only one file can be uploaded at a time",
-                )));
-            }
-            file_uploaded = true;
-
-            // 先获取原始文件名
-            original_name = content_disposition
-                .and_then(|cd| cd.get_filename())
-                .map(|s| s.to_string())
-                .unwrap_or_default();
-
-            // 提取扩展名并校验
-            let extension = Path::new(&original_name)
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| format!(".{}", ext.to_lowercase()))
-                .unwrap_or_default();
-
-            if !allowed_types.iter().any(|t| t.to_lowercase() == extension) {
-                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
-                    ErrorCode::FileTypeNotAllowed,
-                    "File type not allowed",
-                )));
+        // 只接受 file 字段，其余字段在读取内容前拒绝
+        if name != "file" {
+            return Ok(bad_request(
+                ErrorCode::UploadFieldNotAllowed,
+                format!("Unexpected field in upload payload: {name:?}"),
+            ));
+        }
+        if received.is_some() {
+            return Ok(bad_request(
+                ErrorCode::MultifileUploadNotAllowed,
+                "Only one file can be uploaded at a time",
+            ));
+        }
+
+        // 先获取原始文件名
+        let original_name = content_disposition
+            .and_then(|cd| cd.get_filename())
+            .map(|s| s.to_string())
+            .unwrap_or_default();
+
+        // 提取扩展名并校验
+        let extension = Path::new(&original_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| format!(".{}", ext.to_lowercase()))
+            .unwrap_or_default();
+
+        if !allowed_types.iter().any(|t| t.to_lowercase() == extension) {
+            return Ok(bad_request(
+                ErrorCode::FileTypeNotAllowed,
+                "File type not allowed",
+            ));
+        }
+
+        // 获取 MIME 类型（用于存储记录，不用于校验）
+        let file_type = field
+            .content_type()
+            .map(|ct| ct.to_string())
+            .unwrap_or_default();
+
+        let (pending, mut f) = match PendingFile::create(upload_dir) {
+            Ok(created) => created,
+            Err(e) => {
+                tracing::error!("{}", HWSystemError::file_operation(format!("{e}")));
+                return Ok(HttpResponse::InternalServerError().json(
+                    ApiResponse::<()>::error_empty(ErrorCode::FileUploadFailed, "文件创建失败"),
+                ));
             }
+        };
 
-            // 获取 MIME 类型（用于存储记录，不用于校验）
-            file_type = field
-                .content_type()
-                .map(|ct| ct.to_string())
-                .unwrap_or_default();
-
-            stored_name = format!("{}-{}.bin", chrono::Utc::now().timestamp(), Uuid::new_v4());
-            let file_path = format!("{upload_dir}/{stored_name}");
-            let mut f = match File::create(&file_path) {
-                Ok(file) => file,
-                Err(e) => {
-                    tracing::error!("{}", HWSystemError::file_operation(format!("{e}")));
-                    return Ok(HttpResponse::InternalServerError().json(
-                        ApiResponse::<()>::error_empty(ErrorCode::FileUploadFailed, "文件创建失败"),
-                    ));
-                }
-            };
-
-            let mut total_size: usize = 0;
-            let mut first_chunk = true;
-            while let Some(chunk) = field.next().await {
-                let data = chunk?;
-
-                // 第一个 chunk 时验证魔术字节
-                if first_chunk {
-                    first_chunk = false;
-                    if !validate_magic_bytes(&data, &extension) {
-                        let _ = fs::remove_file(&file_path);
-                        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
-                            ErrorCode::FileTypeNotAllowed,
-                            "文件内容与扩展名不匹配",
-                        )));
-                    }
-                }
+        // 边接收边写入磁盘并计算摘要，不在内存中缓存整个文件
+        let mut hasher = Sha256::new();
+        let mut file_size: usize = 0;
+        let mut magic_mismatch = None;
+        let mut code_language = None;
+        let mut first_chunk = true;
+        while let Some(chunk) = field.next().await {
+            let data = chunk?;
 
-                total_size += data.len();
-                // 校验大小
-                if total_size > max_size {
-                    let _ = fs::remove_file(&file_path);
-                    return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
-                        ErrorCode::FileSizeExceeded,
-                        "File size exceeds the limit",
-                    )));
+            // 第一个 chunk 时验证魔术字节，不匹配时继续接收并转入隔离审核
+            if first_chunk {
+                first_chunk = false;
+                if !validate_magic_bytes(&data, &extension) {
+                    magic_mismatch = Some(extension.clone());
                 }
-                f.write_all(&data)?;
+                code_language = detect_code_language(&original_name, &data);
+            }
+
+            file_size += data.len();
+            request_size += data.len();
+            // 超过单文件或单次请求上限时立即中止，已写入的部分随 PendingFile 删除
+            if file_size > max_size {
+                return Ok(bad_request(
+                    ErrorCode::FileSizeExceeded,
+                    "File size exceeds the limit",
+                ));
             }
-            file_size = total_size as i64;
+            if request_size > max_request_size {
+                return Ok(bad_request(
+                    ErrorCode::FileSizeExceeded,
+                    "Request size exceeds the limit",
+                ));
+            }
+            hasher.update(&data);
+            f.write_all(&data)?;
         }
+
+        received = Some(ReceivedFile {
+            pending,
+            original_name,
+            file_type,
+            size: file_size as i64,
+            sha256: hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            magic_mismatch,
+            code_language,
+        });
     }
 
-    if !file_uploaded {
-        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+    let Some(received) = received else {
+        return Ok(bad_request(
             ErrorCode::FileNotFound,
             "No file found in upload payload",
-        )));
-    }
+        ));
+    };
 
     let storage = service.get_storage(req);
 
-    let user_id = match RequireJWT::extract_user_id(req) {
-        Some(id) => id,
-        None => {
-            return Ok(
-                HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::Unauthorized,
-                    "用户未登录",
-                )),
-            );
-        }
-    };
+    if let Some(extension) = received.magic_mismatch {
+        return quarantine_upload(
+            storage,
+            QuarantinedFileInput {
+                user_id,
+                original_name: received.original_name,
+                stored_name: received.pending.keep(),
+                file_type: received.file_type,
+                file_size: received.size,
+                reason: QuarantineReason::MagicMismatch,
+                detail: Some(format!("文件内容与扩展名 {extension} 不匹配")),
+            },
+        )
+        .await;
+    }
 
     let db_file = match storage
         .upload_file(
-            &original_name,
-            &stored_name,
-            &file_size,
-            &file_type,
+            &received.original_name,
+            &received.pending.stored_name,
+            &received.size,
+            &received.file_type,
+            received.code_language,
             user_id,
         )
         .await
     {
-        Ok(file) => FileUploadResponse {
-            download_token: file.download_token,
-            file_name: file.original_name,
-            size: file.file_size,
-            content_type: file.file_type,
-            created_at: file.created_at,
-        },
+        Ok(file) => {
+            received.pending.keep();
+            record_usage(
+                storage.clone(),
+                file.org_id,
+                UsageMetric::UploadedBytes,
+                file.file_size,
+            );
+            FileUploadResponse {
+                download_token: file.download_token,
+                file_name: file.original_name,
+                size: file.file_size,
+                content_type: file.file_type,
+                code_language: file.code_language,
+                sha256: Some(received.sha256),
+                created_at: file.created_at,
+            }
+        }
         Err(e) => {
             return Ok(
                 HttpResponse::InternalServerError().json(ApiResponse::error_empty(
@@ -183,3 +321,45 @@ pub async fn handle_upload(
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(db_file, "File uploaded successfully")))
 }
+
+/// 将未通过校验的上传移入隔离目录并记录，等待管理员审核
+async fn quarantine_upload(
+    storage: Arc<dyn Storage>,
+    input: QuarantinedFileInput,
+) -> ActixResult<HttpResponse> {
+    let upload_path = Path::new(&AppConfig::get().upload.dir).join(&input.stored_name);
+    let dir = quarantine_dir();
+    if let Err(e) = fs::create_dir_all(&dir)
+        .and_then(|_| fs::rename(&upload_path, dir.join(&input.stored_name)))
+    {
+        tracing::error!("{}", HWSystemError::file_operation(format!("{e}")));
+        let _ = fs::remove_file(&upload_path);
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::FileTypeNotAllowed,
+            "文件内容与扩展名不匹配",
+        )));
+    }
+
+    let stored_name = input.stored_name.clone();
+    match storage.create_quarantined_file(input).await {
+        Ok(file) => Ok(HttpResponse::Accepted().json(ApiResponse::error(
+            ErrorCode::FileQuarantined,
+            FileQuarantinedResponse {
+                quarantine_id: file.id,
+                file_name: file.original_name,
+                size: file.file_size,
+                reason: file.reason,
+            },
+            "文件内容与扩展名不匹配，已提交管理员审核",
+        ))),
+        Err(e) => {
+            let _ = fs::remove_file(dir.join(&stored_name));
+            Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::FileUploadFailed,
+                    format!("记录隔离文件失败: {e}"),
+                )),
+            )
+        }
+    }
+}
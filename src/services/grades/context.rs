@@ -0,0 +1,55 @@
+//! 学生查看成绩时附带的班级相对位置
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::models::grades::entities::{Grade, GradeClassContext};
+use crate::storage::Storage;
+
+/// 提交者本人查看、且作业开启了 `show_grade_context` 时填充 `class_context`
+///
+/// 查询失败只记录警告，不影响成绩本身的返回。
+pub async fn attach_class_context(storage: &Arc<dyn Storage>, grade: &mut Grade, viewer_id: i64) {
+    let submission = match storage.get_submission_by_id(grade.submission_id).await {
+        Ok(Some(submission)) if submission.creator_id == viewer_id => submission,
+        Ok(_) => return,
+        Err(e) => {
+            warn!(
+                "查询提交 {} 失败，跳过成绩相对位置: {e}",
+                grade.submission_id
+            );
+            return;
+        }
+    };
+    match storage.get_homework_by_id(submission.homework_id).await {
+        Ok(Some(homework)) if homework.show_grade_context => {}
+        Ok(_) => return,
+        Err(e) => {
+            warn!(
+                "查询作业 {} 失败，跳过成绩相对位置: {e}",
+                submission.homework_id
+            );
+            return;
+        }
+    }
+
+    let homework_id = submission.homework_id;
+    let part_id = submission.part_id;
+    let statistics = storage.get_grade_statistics(homework_id, part_id).await;
+    let percentile = storage
+        .get_grade_percentile(homework_id, part_id, grade.score)
+        .await;
+    match (statistics, percentile) {
+        (Ok(Some(statistics)), Ok(Some(percentile))) => {
+            grade.class_context = Some(GradeClassContext {
+                percentile,
+                statistics,
+            });
+        }
+        (Ok(_), Ok(_)) => {}
+        (Err(e), _) | (_, Err(e)) => {
+            warn!("统计作业 {homework_id} 成绩分布失败，跳过成绩相对位置: {e}");
+        }
+    }
+}
@@ -1,12 +1,47 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::GradeService;
-use crate::middlewares::RequireJWT;
+use super::mentions::{notify_mentioned, resolve_mentions, to_grade_mentions};
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::grades::requests::CreateGradeRequest;
-use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::moderation::entities::ModerationContentType;
+use crate::models::submissions::entities::Submission;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
-use crate::services::notifications::trigger::send_notification;
+use crate::services::certificates::issue::spawn_issue_check;
+use crate::services::moderation::{moderate_text, record_flag};
+use crate::storage::Storage;
+use std::sync::Arc;
+use tracing::error;
+
+/// 分题提交的分数不能超出分题满分
+pub(super) async fn check_part_score(
+    storage: &Arc<dyn Storage>,
+    submission: &Submission,
+    score: f64,
+) -> Result<(), HttpResponse> {
+    let Some(part_id) = submission.part_id else {
+        return Ok(());
+    };
+    match storage.get_homework_part(part_id).await {
+        Ok(Some(part)) if !(0.0..=part.max_score).contains(&score) => {
+            Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::GradeScoreOutOfRange,
+                format!(
+                    "分数应在 0 到 {} 之间（分题「{}」）",
+                    part.max_score, part.title
+                ),
+            )))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => Err(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询作业分题失败: {e}"),
+            )),
+        ),
+    }
+}
 
 pub async fn create_grade(
     service: &GradeService,
@@ -55,10 +90,10 @@ pub async fn create_grade(
         }
     };
 
-    // 获取班级信息
+    // 获取班级信息（跨组织的班级视为不存在）
     let class = match storage.get_class_by_id(homework.class_id).await {
-        Ok(Some(cls)) => cls,
-        Ok(None) => {
+        Ok(Some(cls)) if TenantGuard::can_access(request, cls.org_id) => cls,
+        Ok(_) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::ClassNotFound,
                 "班级不存在",
@@ -76,7 +111,7 @@ pub async fn create_grade(
 
     // 权限检查：只有该班级的教师或管理员才能评分
     match user_role {
-        Some(UserRole::Admin) => {} // 管理员可以评任何提交
+        Some(UserRole::Admin) => {} // 管理员可以评本组织的任何提交
         Some(UserRole::Teacher) => {
             if class.teacher_id != grader_id {
                 return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
@@ -112,27 +147,68 @@ pub async fn create_grade(
         _ => {}
     }
 
+    if let Err(resp) = check_part_score(&storage, &submission, req.score).await {
+        return Ok(resp);
+    }
+
+    // 解析评语中的 @提及
+    let mentioned = match req.comment.as_deref() {
+        Some(comment) => match resolve_mentions(&storage, &class, comment).await {
+            Ok(users) => users,
+            Err(resp) => return Ok(resp),
+        },
+        None => Vec::new(),
+    };
+
+    // 评语内容审核
+    let pending_flag = match req.comment.as_deref() {
+        Some(comment) => match moderate_text(
+            &storage,
+            class.id,
+            grader_id,
+            ModerationContentType::GradeComment,
+            comment,
+        )
+        .await
+        {
+            Ok(flag) => flag,
+            Err(resp) => return Ok(resp),
+        },
+        None => None,
+    };
+
     match storage.create_grade(grader_id, req).await {
-        Ok(grade) => {
-            // 异步通知学生
-            let storage_clone = storage.clone();
-            let grade_id = grade.id;
+        Ok(mut grade) => {
+            if let Some(mut flag) = pending_flag {
+                flag.content_id = Some(grade.id);
+                record_flag(&storage, flag).await;
+            }
+
+            if !mentioned.is_empty() {
+                let user_ids: Vec<i64> = mentioned.iter().map(|u| u.id).collect();
+                match storage
+                    .replace_grade_mentions(grade.id, grader_id, &user_ids)
+                    .await
+                {
+                    Ok(added) => {
+                        grade.mentions = to_grade_mentions(&mentioned);
+                        notify_mentioned(
+                            storage.clone(),
+                            grade.id,
+                            grader_id,
+                            homework.title.clone(),
+                            added,
+                        );
+                    }
+                    Err(e) => error!("保存评语提及失败: {}", e),
+                }
+            }
+
+            // 学生通知已随评分写入发件箱，由转发任务投递
             let student_id = submission.creator_id;
-            let score = grade.score;
-            let hw_title = homework.title.clone();
-
-            tokio::spawn(async move {
-                send_notification(
-                    storage_clone,
-                    student_id,
-                    NotificationType::GradeReceived,
-                    format!("作业已评分：{}", hw_title),
-                    Some(format!("您的作业「{}」已评分，得分：{}", hw_title, score)),
-                    Some(ReferenceType::Grade),
-                    Some(grade_id),
-                )
-                .await;
-            });
+
+            // 评分后检查是否满足结业证书颁发条件
+            spawn_issue_check(storage.clone(), class.id, student_id);
 
             Ok(HttpResponse::Created().json(ApiResponse::success(grade, "评分成功")))
         }
@@ -1,24 +1,22 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
-use super::GradeService;
-use crate::middlewares::RequireJWT;
+use super::{GradeService, attach_class_context};
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
+use crate::models::reactions::entities::ReactionTargetType;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::services::reactions::load_reaction_summaries;
 use crate::storage::Storage;
 
 /// 检查用户是否有权限访问某个提交的评分
 async fn check_grade_access_permission(
     storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
     current_user: &crate::models::users::entities::User,
     submission_id: i64,
 ) -> Result<(), HttpResponse> {
-    // Admin 直接放行
-    if current_user.role == UserRole::Admin {
-        return Ok(());
-    }
-
     // 获取提交信息
     let submission = match storage.get_submission_by_id(submission_id).await {
         Ok(Some(sub)) => sub,
@@ -38,6 +36,17 @@ async fn check_grade_access_permission(
         }
     };
 
+    // Admin 可以查看本组织的任何评分
+    if current_user.role == UserRole::Admin {
+        if !TenantGuard::can_access_homework(request, storage, submission.homework_id).await {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::GradeNotFound,
+                "评分不存在",
+            )));
+        }
+        return Ok(());
+    }
+
     // 如果是提交者本人，允许查看自己的成绩
     if submission.creator_id == current_user.id {
         return Ok(());
@@ -113,7 +122,7 @@ pub async fn get_grade(
     };
 
     // 获取评分
-    let grade = match storage.get_grade_by_id(grade_id).await {
+    let mut grade = match storage.get_grade_by_id(grade_id).await {
         Ok(Some(g)) => g,
         Ok(None) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
@@ -133,10 +142,23 @@ pub async fn get_grade(
 
     // 权限验证
     if let Err(resp) =
-        check_grade_access_permission(&storage, &current_user, grade.submission_id).await
+        check_grade_access_permission(&storage, request, &current_user, grade.submission_id).await
     {
         return Ok(resp);
     }
 
+    attach_class_context(&storage, &mut grade, current_user.id).await;
+    if let Some(summary) = load_reaction_summaries(
+        &storage,
+        ReactionTargetType::GradeComment,
+        &[grade.id],
+        current_user.id,
+    )
+    .await
+    .remove(&grade.id)
+    {
+        grade.reactions = summary;
+    }
+
     Ok(HttpResponse::Ok().json(ApiResponse::success(grade, "查询成功")))
 }
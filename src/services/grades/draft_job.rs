@@ -0,0 +1,30 @@
+//! 评分草稿清理任务
+//!
+//! 定期删除超过保留期未再保存的评分草稿。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::storage::Storage;
+
+/// 草稿最后一次保存后的保留天数
+const DRAFT_TTL_DAYS: i64 = 30;
+
+/// 检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+/// 启动评分草稿清理任务
+pub fn spawn_grade_draft_cleanup_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let cutoff = chrono::Utc::now().timestamp() - DRAFT_TTL_DAYS * 86400;
+            match storage.purge_expired_grade_drafts(cutoff).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {count} expired grade draft(s)"),
+                Err(e) => tracing::warn!("Failed to purge expired grade drafts: {e}"),
+            }
+        }
+    });
+}
@@ -1,17 +1,25 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::GradeService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::models::grades::requests::GradeListQuery;
+use crate::models::reactions::entities::ReactionTargetType;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::services::reactions::load_reaction_summaries;
 
 pub async fn list_grades(
     service: &GradeService,
     request: &HttpRequest,
-    query: GradeListQuery,
+    mut query: GradeListQuery,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("grades").check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
+    query.tenant = TenantGuard::scope(request);
 
     // 获取当前用户信息
     let current_user = match RequireJWT::extract_user_claims(request) {
@@ -25,7 +33,7 @@ pub async fn list_grades(
     // 权限过滤
     match current_user.role {
         UserRole::Admin => {
-            // Admin 可查看所有评分，不需要过滤
+            // Admin 可查看本组织的所有评分
         }
         UserRole::Teacher => {
             // 教师必须指定 homework_id，并验证是否有权限
@@ -80,7 +88,22 @@ pub async fn list_grades(
     }
 
     match storage.list_grades_with_pagination(query).await {
-        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功"))),
+        Ok(mut response) => {
+            let grade_ids: Vec<i64> = response.items.iter().map(|g| g.id).collect();
+            let mut summaries = load_reaction_summaries(
+                &storage,
+                ReactionTargetType::GradeComment,
+                &grade_ids,
+                current_user.id,
+            )
+            .await;
+            for grade in &mut response.items {
+                if let Some(summary) = summaries.remove(&grade.id) {
+                    grade.reactions = summary;
+                }
+            }
+            Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+        }
         Err(e) => Ok(
             HttpResponse::InternalServerError().json(ApiResponse::error_empty(
                 ErrorCode::InternalServerError,
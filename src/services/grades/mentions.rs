@@ -0,0 +1,178 @@
+//! 评语 @提及 解析与通知
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+
+use crate::models::classes::entities::Class;
+use crate::models::grades::entities::GradeMention;
+use crate::models::grades::responses::GradeMentionError;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::users::entities::User;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::send_templated_notifications;
+use crate::storage::Storage;
+
+/// 单条评语最多提及的用户数
+const MAX_MENTIONS: usize = 20;
+
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// 从评语中解析 `@username`，按出现顺序去重
+///
+/// `@` 前紧跟用户名字符时（如邮箱 `a@b.com`）不视为提及。
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        if c == '@' && !prev.is_some_and(is_username_char) {
+            let start = idx + 1;
+            let mut end = start;
+            while let Some(&(i, next)) = chars.peek() {
+                if !is_username_char(next) {
+                    break;
+                }
+                end = i + next.len_utf8();
+                prev = Some(next);
+                chars.next();
+            }
+            let name = &text[start..end];
+            if !name.is_empty() && !result.iter().any(|n| n == name) {
+                result.push(name.to_string());
+            }
+            if end > start {
+                continue;
+            }
+        }
+        prev = Some(c);
+    }
+
+    result
+}
+
+/// 解析并校验评语中的提及，被提及者必须是该班级成员（含班级教师）
+///
+/// 校验失败时返回可直接响应的错误。
+pub async fn resolve_mentions(
+    storage: &Arc<dyn Storage>,
+    class: &Class,
+    comment: &str,
+) -> Result<Vec<User>, HttpResponse> {
+    let names = parse_mentions(comment);
+    if names.len() > MAX_MENTIONS {
+        return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::GradeMentionInvalid,
+            format!("评语中最多提及 {MAX_MENTIONS} 位用户"),
+        )));
+    }
+
+    let mut users = Vec::with_capacity(names.len());
+    let mut invalid_usernames = Vec::new();
+    for name in names {
+        let user = match storage.get_user_by_username(&name).await {
+            Ok(user) => user,
+            Err(e) => return Err(internal_error(e)),
+        };
+        let Some(user) = user else {
+            invalid_usernames.push(name);
+            continue;
+        };
+
+        let is_member = user.id == class.teacher_id
+            || match storage
+                .get_class_user_by_user_id_and_class_id(user.id, class.id)
+                .await
+            {
+                Ok(class_user) => class_user.is_some(),
+                Err(e) => return Err(internal_error(e)),
+            };
+        if is_member {
+            users.push(user);
+        } else {
+            invalid_usernames.push(name);
+        }
+    }
+
+    if !invalid_usernames.is_empty() {
+        let message = format!(
+            "以下用户不存在或不是班级成员: {}",
+            invalid_usernames.join(", ")
+        );
+        return Err(HttpResponse::BadRequest().json(ApiResponse::error(
+            ErrorCode::GradeMentionInvalid,
+            GradeMentionError { invalid_usernames },
+            message,
+        )));
+    }
+
+    Ok(users)
+}
+
+/// 转换为响应中的提及信息
+pub fn to_grade_mentions(users: &[User]) -> Vec<GradeMention> {
+    users
+        .iter()
+        .map(|u| GradeMention {
+            user_id: u.id,
+            username: u.username.clone(),
+            display_name: u.display_name.clone(),
+        })
+        .collect()
+}
+
+/// 通知新被提及的用户（跳过评分者本人）
+pub fn notify_mentioned(
+    storage: Arc<dyn Storage>,
+    grade_id: i64,
+    grader_id: i64,
+    homework_title: String,
+    user_ids: Vec<i64>,
+) {
+    let targets: Vec<i64> = user_ids.into_iter().filter(|id| *id != grader_id).collect();
+    if targets.is_empty() {
+        return;
+    }
+
+    executor::spawn(NOTIFICATIONS_QUEUE, async move {
+        send_templated_notifications(
+            storage,
+            targets,
+            NotificationType::Mentioned,
+            vec![("homework_title", homework_title)],
+            Some(ReferenceType::Grade),
+            Some(grade_id),
+        )
+        .await;
+    });
+}
+
+fn internal_error(e: impl std::fmt::Display) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        format!("解析评语提及失败: {e}"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mentions() {
+        assert_eq!(
+            parse_mentions("@alice 请看一下，@bob_2 也是。@alice"),
+            vec!["alice", "bob_2"]
+        );
+        assert_eq!(
+            parse_mentions("联系 teacher@example.com"),
+            Vec::<String>::new()
+        );
+        assert_eq!(parse_mentions("(@carol)、@ 空"), vec!["carol"]);
+        assert_eq!(parse_mentions("无提及"), Vec::<String>::new());
+    }
+}
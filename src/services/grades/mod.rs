@@ -1,6 +1,10 @@
+mod context;
 pub mod create;
 pub mod detail;
+pub mod draft_job;
 pub mod list;
+mod mentions;
+pub mod sla_job;
 pub mod update;
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
@@ -9,6 +13,10 @@ use std::sync::Arc;
 use crate::models::grades::requests::{CreateGradeRequest, GradeListQuery, UpdateGradeRequest};
 use crate::storage::Storage;
 
+pub use context::attach_class_context;
+pub use draft_job::spawn_grade_draft_cleanup_job;
+pub use sla_job::spawn_grading_sla_job;
+
 pub struct GradeService {
     storage: Option<Arc<dyn Storage>>,
 }
@@ -0,0 +1,66 @@
+//! 批改时限提醒任务
+//!
+//! 定期查找超过系统设置 `grading.sla_days` 天仍未评分的最新提交，
+//! 按作业汇总后通知作业创建者。每份提交只提醒一次。
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 检查间隔，默认 3600 秒，可通过系统设置 `jobs.grading_sla_interval` 调整
+pub static CHECK_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(3600));
+
+/// 启动批改时限提醒任务
+pub fn spawn_grading_sla_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        CHECK_INTERVAL
+            .run(|| notify_overdue_grading(&storage))
+            .await;
+    });
+}
+
+/// 通知教师超过批改时限的提交
+async fn notify_overdue_grading(storage: &Arc<dyn Storage>) {
+    let sla_days = DynamicConfig::grading_sla_days().await;
+    let cutoff = chrono::Utc::now().timestamp() - sla_days * 86400;
+
+    let breaches = match storage.claim_grading_sla_breaches(cutoff).await {
+        Ok(breaches) => breaches,
+        Err(e) => {
+            tracing::warn!("Failed to claim grading SLA breaches: {e}");
+            return;
+        }
+    };
+
+    // 同一教师同一作业只发一条通知
+    let mut grouped: BTreeMap<(i64, i64), (String, usize)> = BTreeMap::new();
+    for breach in breaches {
+        grouped
+            .entry((breach.teacher_id, breach.homework_id))
+            .or_insert((breach.homework_title, 0))
+            .1 += 1;
+    }
+
+    for ((teacher_id, homework_id), (homework_title, count)) in grouped {
+        send_templated_notification(
+            storage.clone(),
+            teacher_id,
+            NotificationType::GradingOverdue,
+            vec![
+                ("homework_title", homework_title),
+                ("count", count.to_string()),
+                ("sla_days", sla_days.to_string()),
+            ],
+            Some(ReferenceType::Homework),
+            Some(homework_id),
+        )
+        .await;
+    }
+}
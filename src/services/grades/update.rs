@@ -1,12 +1,22 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
 
 use super::GradeService;
-use crate::middlewares::RequireJWT;
+use super::create::check_part_score;
+use super::mentions::{notify_mentioned, resolve_mentions, to_grade_mentions};
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::classes::entities::Class;
 use crate::models::grades::requests::UpdateGradeRequest;
+use crate::models::moderation::entities::ModerationContentType;
 use crate::models::notifications::entities::{NotificationType, ReferenceType};
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
-use crate::services::notifications::trigger::send_notification;
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::certificates::issue::spawn_issue_check;
+use crate::services::moderation::{moderate_text, record_flag};
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::storage::Storage;
+use tracing::error;
 
 pub async fn update_grade(
     service: &GradeService,
@@ -39,7 +49,22 @@ pub async fn update_grade(
 
     // 权限检查：只有原评分者或管理员才能更新
     match user_role {
-        Some(UserRole::Admin) => {} // 管理员可以更新任何评分
+        Some(UserRole::Admin) => {
+            // 管理员可以更新本组织的任何评分
+            let accessible = match storage.get_submission_by_id(grade.submission_id).await {
+                Ok(Some(submission)) => {
+                    TenantGuard::can_access_homework(request, &storage, submission.homework_id)
+                        .await
+                }
+                _ => false,
+            };
+            if !accessible {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::GradeNotFound,
+                    "评分不存在",
+                )));
+            }
+        }
         Some(UserRole::Teacher) => {
             if grade.grader_id != user_id {
                 return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
@@ -56,15 +81,90 @@ pub async fn update_grade(
         }
     }
 
+    // 修改分数时校验分题满分
+    if let Some(score) = req.score {
+        match storage.get_submission_by_id(grade.submission_id).await {
+            Ok(Some(submission)) => {
+                if let Err(resp) = check_part_score(&storage, &submission, score).await {
+                    return Ok(resp);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询提交失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    // 修改评语时重新解析 @提及并审核内容
+    let mut pending_flag = None;
+    let mentioned = match req.comment.as_deref() {
+        Some(comment) => {
+            let context = match load_grade_context(&storage, grade.submission_id).await {
+                Ok(context) => context,
+                Err(resp) => return Ok(resp),
+            };
+            match moderate_text(
+                &storage,
+                context.1.id,
+                user_id,
+                ModerationContentType::GradeComment,
+                comment,
+            )
+            .await
+            {
+                Ok(flag) => pending_flag = flag,
+                Err(resp) => return Ok(resp),
+            }
+            match resolve_mentions(&storage, &context.1, comment).await {
+                Ok(users) => Some((users, context.0)),
+                Err(resp) => return Ok(resp),
+            }
+        }
+        None => None,
+    };
+
     match storage.update_grade(grade_id, req).await {
-        Ok(Some(updated_grade)) => {
+        Ok(Some(mut updated_grade)) => {
+            if let Some(mut flag) = pending_flag {
+                flag.content_id = Some(grade_id);
+                record_flag(&storage, flag).await;
+            }
+
+            if let Some((users, homework_title)) = mentioned {
+                let user_ids: Vec<i64> = users.iter().map(|u| u.id).collect();
+                match storage
+                    .replace_grade_mentions(grade_id, user_id, &user_ids)
+                    .await
+                {
+                    Ok(added) => {
+                        updated_grade.mentions = to_grade_mentions(&users);
+                        notify_mentioned(storage.clone(), grade_id, user_id, homework_title, added);
+                    }
+                    Err(e) => error!("保存评语提及失败: {}", e),
+                }
+            }
+
+            // 修改已保存，丢弃该评分人对此提交的草稿
+            if let Err(e) = storage
+                .delete_grade_draft(grade.submission_id, user_id)
+                .await
+            {
+                error!("删除评分草稿失败: {}", e);
+            }
+
             // 异步通知学生
             let storage_clone = storage.clone();
             let g_id = updated_grade.id;
             let new_score = updated_grade.score;
             let submission_id = grade.submission_id;
 
-            tokio::spawn(async move {
+            executor::spawn(NOTIFICATIONS_QUEUE, async move {
                 // 获取提交和作业信息
                 if let Ok(Some(submission)) =
                     storage_clone.get_submission_by_id(submission_id).await
@@ -72,15 +172,21 @@ pub async fn update_grade(
                         .get_homework_by_id(submission.homework_id)
                         .await
                 {
-                    send_notification(
+                    // 分数变化后重新检查结业证书颁发条件
+                    spawn_issue_check(
+                        storage_clone.clone(),
+                        homework.class_id,
+                        submission.creator_id,
+                    );
+
+                    send_templated_notification(
                         storage_clone,
                         submission.creator_id,
                         NotificationType::GradeUpdated,
-                        format!("评分已更新：{}", homework.title),
-                        Some(format!(
-                            "您的作业「{}」评分已更新，新得分：{}",
-                            homework.title, new_score
-                        )),
+                        vec![
+                            ("homework_title", homework.title),
+                            ("score", new_score.to_string()),
+                        ],
                         Some(ReferenceType::Grade),
                         Some(g_id),
                     )
@@ -102,3 +208,43 @@ pub async fn update_grade(
         ),
     }
 }
+
+/// 获取评分所属作业标题及班级
+async fn load_grade_context(
+    storage: &Arc<dyn Storage>,
+    submission_id: i64,
+) -> Result<(String, Class), HttpResponse> {
+    let internal_error = |e: String| {
+        HttpResponse::InternalServerError()
+            .json(ApiResponse::error_empty(ErrorCode::InternalServerError, e))
+    };
+
+    let submission = match storage.get_submission_by_id(submission_id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionNotFound,
+                "提交不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询提交失败: {e}"))),
+    };
+    let homework = match storage.get_homework_by_id(submission.homework_id).await {
+        Ok(Some(homework)) => homework,
+        Ok(None) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询作业失败: {e}"))),
+    };
+    match storage.get_class_by_id(homework.class_id).await {
+        Ok(Some(class)) => Ok((homework.title, class)),
+        Ok(None) => Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::ClassNotFound,
+            "班级不存在",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级失败: {e}"))),
+    }
+}
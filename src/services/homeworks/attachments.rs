@@ -0,0 +1,44 @@
+//! 作业附件访问控制
+
+use std::sync::Arc;
+
+use super::solution::reveal_solution_if_due;
+use crate::errors::Result;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::homeworks::entities::Homework;
+use crate::models::submissions::entities::SubmissionStatus;
+use crate::models::users::entities::{User, UserRole};
+use crate::storage::Storage;
+
+/// 判断用户当前能否查看作业的参考答案
+///
+/// 管理员、作业创建者和班级教师始终可见。设置了参考答案时按其公开条件判断，
+/// 否则学生在截止后或本人最新提交已评分后可见。
+pub async fn can_view_solution(
+    storage: &Arc<dyn Storage>,
+    user: &User,
+    homework: &Homework,
+) -> Result<bool> {
+    if user.role == UserRole::Admin || homework.created_by == user.id {
+        return Ok(true);
+    }
+
+    if let Some(class_user) = storage
+        .get_class_user_by_user_id_and_class_id(user.id, homework.class_id)
+        .await?
+        && class_user.role == ClassUserRole::Teacher
+    {
+        return Ok(true);
+    }
+
+    if let Some(solution) = storage.get_homework_solution(homework.id).await? {
+        return reveal_solution_if_due(storage, homework, &solution).await;
+    }
+
+    let graded = storage
+        .get_latest_submission(homework.id, user.id)
+        .await?
+        .is_some_and(|submission| submission.status == SubmissionStatus::Graded);
+
+    Ok(homework.solution_unlocked(chrono::Utc::now(), graded))
+}
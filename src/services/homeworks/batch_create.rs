@@ -0,0 +1,137 @@
+//! 跨班级批量创建作业
+//!
+//! 同一份作业发布到多个班级：逐个校验班级与权限，通过校验的班级在一个事务中创建，
+//! 附件只增加引用计数，不重新上传。
+
+use std::collections::HashSet;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::homeworks::requests::{
+    BatchCreateHomeworkRequest, validate_grace_minutes, validate_workload_estimate,
+};
+use crate::models::homeworks::responses::{
+    BatchCreateHomeworkItemResult, BatchCreateHomeworkResponse,
+};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 单次最多发布的班级数
+const MAX_BATCH_CLASSES: usize = 50;
+
+fn rejected(class_id: i64, msg: &str) -> BatchCreateHomeworkItemResult {
+    BatchCreateHomeworkItemResult {
+        class_id,
+        success: false,
+        homework: None,
+        message: Some(msg.to_string()),
+    }
+}
+
+pub async fn batch_create_homeworks(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    created_by: i64,
+    req: BatchCreateHomeworkRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+    let user_role = RequireJWT::extract_user_role(request);
+
+    let mut seen = HashSet::new();
+    let class_ids: Vec<i64> = req
+        .class_ids
+        .iter()
+        .copied()
+        .filter(|id| seen.insert(*id))
+        .collect();
+    if class_ids.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "class_ids 不能为空",
+        )));
+    }
+    if class_ids.len() > MAX_BATCH_CLASSES {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("单次最多发布到 {MAX_BATCH_CLASSES} 个班级"),
+        )));
+    }
+    if req.max_content_length.is_some_and(|len| len < 0) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "内容长度限制不能为负数",
+        )));
+    }
+    if let Err(message) = validate_workload_estimate(req.estimated_minutes, req.difficulty) {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
+    if let Err(message) = validate_grace_minutes(req.grace_minutes) {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
+
+    // 逐个校验班级，权限规则与单个创建一致
+    let mut results = Vec::with_capacity(class_ids.len());
+    let mut targets = Vec::new();
+    for class_id in class_ids {
+        match storage.get_class_by_id(class_id).await {
+            Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {
+                if user_role == Some(UserRole::Admin) || class.teacher_id == created_by {
+                    targets.push(class_id);
+                } else {
+                    results.push(rejected(class_id, "只能在自己教授的班级创建作业"));
+                }
+            }
+            Ok(_) => results.push(rejected(class_id, "班级不存在")),
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    if !targets.is_empty() {
+        let requests = targets.iter().map(|id| req.for_class(*id)).collect();
+        match storage.batch_create_homeworks(created_by, requests).await {
+            Ok(homeworks) => {
+                for homework in homeworks {
+                    results.push(BatchCreateHomeworkItemResult {
+                        class_id: homework.class_id,
+                        success: true,
+                        homework: Some(homework),
+                        message: None,
+                    });
+                }
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::HomeworkCreateFailed,
+                        format!("批量创建作业失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    // 按请求中的班级顺序返回
+    let order: Vec<i64> = req.class_ids;
+    results.sort_by_key(|r| order.iter().position(|id| *id == r.class_id));
+
+    let success = results.iter().filter(|r| r.success).count();
+    let response = BatchCreateHomeworkResponse {
+        total: results.len(),
+        success,
+        failed: results.len() - success,
+        results,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "批量创建完成")))
+}
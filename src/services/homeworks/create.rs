@@ -1,12 +1,14 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::HomeworkService;
-use crate::middlewares::RequireJWT;
-use crate::models::homeworks::requests::CreateHomeworkRequest;
-use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::middlewares::{ClassTokenGuard, RequireJWT, TenantGuard};
+use crate::models::homeworks::requests::{
+    CreateHomeworkRequest, validate_grace_minutes, validate_workload_estimate,
+};
+use crate::models::homeworks::responses::CreateHomeworkResponse;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
-use crate::services::notifications::trigger::{get_class_student_ids, send_notifications};
+use crate::services::jobs;
 
 pub async fn create_homework(
     service: &HomeworkService,
@@ -17,10 +19,18 @@ pub async fn create_homework(
     let storage = service.get_storage(request);
     let user_role = RequireJWT::extract_user_role(request);
 
-    // 检查班级是否存在
+    // 班级 API 令牌只能在所属班级创建作业
+    if !ClassTokenGuard::can_access_class(request, req.class_id) {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassApiTokenForbidden,
+            "API 令牌无权访问该班级",
+        )));
+    }
+
+    // 检查班级是否存在（其他组织的班级按不存在处理）
     let class = match storage.get_class_by_id(req.class_id).await {
-        Ok(Some(class)) => class,
-        Ok(None) => {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::ClassNotFound,
                 "班级不存在",
@@ -55,35 +65,43 @@ pub async fn create_homework(
         }
     }
 
-    match storage.create_homework(created_by, req).await {
-        Ok(homework) => {
-            // 异步发送通知给班级学生
-            let storage_clone = storage.clone();
-            let homework_id = homework.id;
-            let class_id = homework.class_id;
-            let title = homework.title.clone();
+    if req.max_content_length.is_some_and(|len| len < 0) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "内容长度限制不能为负数",
+        )));
+    }
+    if let Err(message) = validate_workload_estimate(req.estimated_minutes, req.difficulty) {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
+    if let Err(message) = validate_grace_minutes(req.grace_minutes) {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
 
-            tokio::spawn(async move {
-                let student_ids = get_class_student_ids(&storage_clone, class_id).await;
-                send_notifications(
-                    storage_clone,
-                    student_ids,
-                    NotificationType::HomeworkCreated,
-                    format!("新作业发布：{}", title),
-                    Some(format!("作业「{}」已发布，请及时查看", title)),
-                    Some(ReferenceType::Homework),
-                    Some(homework_id),
-                )
-                .await;
-            });
+    // 学生通知由发件箱转发任务在后台分批投递，这里只登记任务供客户端查询进度
+    let job = jobs::register_job(created_by, "homework_notification").await;
 
-            Ok(HttpResponse::Created().json(ApiResponse::success(homework, "创建成功")))
+    match storage
+        .create_homework(created_by, req, Some(&job.id))
+        .await
+    {
+        Ok(homework) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            CreateHomeworkResponse {
+                homework,
+                notification_job: job,
+            },
+            "创建成功",
+        ))),
+        Err(e) => {
+            jobs::discard_job(&job.id).await;
+            Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("创建作业失败: {e}"),
+                )),
+            )
         }
-        Err(e) => Ok(
-            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                ErrorCode::InternalServerError,
-                format!("创建作业失败: {e}"),
-            )),
-        ),
     }
 }
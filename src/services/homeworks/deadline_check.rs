@@ -0,0 +1,94 @@
+//! 作业截止时间冲突检查
+//!
+//! 教师设置截止时间前查询前后窗口内到期的其他作业：本班作业，以及本班学生同时所在的
+//! 其他班级的作业，便于错开截止时间、平衡学生负担。只做提示，不阻止保存。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::homeworks::requests::CheckDeadlineRequest;
+use crate::models::homeworks::responses::DeadlineConflictResponse;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::system::DynamicConfig;
+
+pub async fn check_deadline(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    user_id: i64,
+    class_id: i64,
+    req: CheckDeadlineRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    // 权限规则与创建作业一致：班级教师或管理员
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {
+            if RequireJWT::extract_user_role(request) != Some(UserRole::Admin)
+                && class.teacher_id != user_id
+            {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::Forbidden,
+                    "只能检查自己教授班级的作业截止时间",
+                )));
+            }
+        }
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    let window_hours = match req.window_hours {
+        Some(hours) if !(1..=CheckDeadlineRequest::MAX_WINDOW_HOURS).contains(&hours) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::BadRequest,
+                format!(
+                    "冲突窗口应在 1 到 {} 小时之间",
+                    CheckDeadlineRequest::MAX_WINDOW_HOURS
+                ),
+            )));
+        }
+        Some(hours) => hours,
+        None => DynamicConfig::homework_deadline_conflict_window_hours().await,
+    };
+
+    let deadline = req.deadline.timestamp();
+    let window = window_hours * 3600;
+    match storage
+        .find_deadline_conflicts(
+            class_id,
+            deadline - window,
+            deadline + window,
+            req.exclude_homework_id,
+        )
+        .await
+    {
+        Ok(conflicts) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            DeadlineConflictResponse {
+                class_id,
+                deadline: req.deadline,
+                window_hours,
+                conflicts,
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询截止时间冲突失败: {e}"),
+            )),
+        ),
+    }
+}
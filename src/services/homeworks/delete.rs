@@ -1,7 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::HomeworkService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
 
@@ -35,7 +35,15 @@ pub async fn delete_homework(
 
     // 权限检查：只有作业创建者或管理员才能删除
     match user_role {
-        Some(UserRole::Admin) => {} // 管理员可以删除任何作业
+        // 管理员可以删除本组织的任何作业
+        Some(UserRole::Admin) => {
+            if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::HomeworkNotFound,
+                    "作业不存在",
+                )));
+            }
+        }
         Some(UserRole::Teacher) => {
             if homework.created_by != user_id {
                 return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
@@ -1,11 +1,19 @@
+use std::collections::HashMap;
+
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::HomeworkService;
-use crate::middlewares::RequireJWT;
+use super::attachments::can_view_solution;
+use super::exam_access::record_exam_access;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
 use crate::models::files::responses::FileInfo;
-use crate::models::homeworks::responses::HomeworkCreator;
+use crate::models::homeworks::entities::{AttachmentKind, ExamAccessEvent};
+use crate::models::homeworks::responses::HomeworkAttachment;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode, homeworks::responses::HomeworkDetail};
+use crate::services::presenters::{load_files, load_user};
+use crate::services::system::DynamicConfig;
 
 pub async fn get_homework(
     service: &HomeworkService,
@@ -25,14 +33,30 @@ pub async fn get_homework(
 
     match storage.get_homework_by_id(homework_id).await {
         Ok(Some(homework)) => {
-            // 权限验证：管理员直接放行，否则验证班级成员资格
+            // 权限验证：管理员只校验组织，否则验证班级成员资格
             if current_user.role != UserRole::Admin {
-                match storage
-                    .get_class_user_by_user_id_and_class_id(current_user.id, homework.class_id)
-                    .await
+                match RequireClassRole::class_user(
+                    request,
+                    &storage,
+                    current_user.id,
+                    homework.class_id,
+                )
+                .await
                 {
-                    Ok(Some(_)) => {
-                        // 用户是班级成员，允许访问
+                    Ok(Some(cu)) => {
+                        // 用户是班级成员，允许访问；考试模式下记录学生查看事件
+                        if homework.exam_mode && cu.role != ClassUserRole::Teacher {
+                            record_exam_access(
+                                &storage,
+                                request,
+                                homework.id,
+                                current_user.id,
+                                ExamAccessEvent::View,
+                                None,
+                                None,
+                            )
+                            .await;
+                        }
                     }
                     Ok(None) => {
                         return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
@@ -49,39 +73,99 @@ pub async fn get_homework(
                         ));
                     }
                 }
+            } else if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+                // 组织管理员只能访问本组织的班级
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::HomeworkNotFound,
+                    "作业不存在",
+                )));
             }
 
-            // 获取附件完整信息
-            let file_ids = storage
-                .get_homework_file_ids(homework_id)
+            // 获取附件完整信息，未开放的参考答案只计数不返回
+            let file_refs = storage
+                .get_homework_attachments(homework_id)
                 .await
                 .unwrap_or_default();
-            let mut attachments = Vec::new();
-            for file_id in file_ids {
-                if let Ok(Some(file)) = storage.get_file_by_id(file_id).await {
-                    attachments.push(FileInfo {
-                        download_token: file.download_token,
-                        original_name: file.original_name,
-                        file_size: file.file_size,
-                        file_type: file.file_type,
-                    });
+            // 参考答案文本与 solution 类型附件共用同一公开条件
+            let solution = match storage.get_homework_solution(homework_id).await {
+                Ok(solution) => solution,
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询参考答案失败: {e}"),
+                        )),
+                    );
                 }
-            }
+            };
+            let solution_visible = if solution.is_some()
+                || file_refs
+                    .iter()
+                    .any(|(_, kind)| *kind == AttachmentKind::Solution)
+            {
+                match can_view_solution(&storage, &current_user, &homework).await {
+                    Ok(visible) => visible,
+                    Err(e) => {
+                        return Ok(HttpResponse::InternalServerError().json(
+                            ApiResponse::error_empty(
+                                ErrorCode::InternalServerError,
+                                format!("查询作业附件失败: {e}"),
+                            ),
+                        ));
+                    }
+                }
+            } else {
+                true
+            };
+
+            let locked_solutions = file_refs
+                .iter()
+                .filter(|(_, kind)| *kind == AttachmentKind::Solution && !solution_visible)
+                .count() as i64;
+            let visible_refs: Vec<(i64, AttachmentKind)> = file_refs
+                .into_iter()
+                .filter(|(_, kind)| *kind != AttachmentKind::Solution || solution_visible)
+                .collect();
+            let files: HashMap<i64, FileInfo> =
+                load_files(&storage, visible_refs.iter().map(|(file_id, _)| *file_id))
+                    .await
+                    .unwrap_or_default();
+            let attachments = visible_refs
+                .into_iter()
+                .filter_map(|(file_id, kind)| {
+                    Some(HomeworkAttachment {
+                        file: files.get(&file_id)?.clone(),
+                        kind,
+                    })
+                })
+                .collect();
 
             // 获取创建者信息
-            let creator = match storage.get_user_by_id(homework.created_by).await {
-                Ok(Some(user)) => Some(HomeworkCreator {
-                    id: user.id,
-                    username: user.username,
-                    display_name: user.display_name,
-                    avatar_url: user.avatar_url,
-                }),
-                _ => None,
-            };
+            let creator = load_user(&storage, homework.created_by).await;
+
+            let parts = storage
+                .list_homework_parts(homework.id)
+                .await
+                .unwrap_or_default();
+            let links = storage
+                .list_homework_links(homework.id)
+                .await
+                .unwrap_or_default();
+
+            // 实际生效的迟交策略（分题截止时间另行叠加同一宽限期）
+            let late_policy =
+                homework.late_policy(DynamicConfig::homework_default_grace_minutes().await);
 
+            let solution_locked = solution.is_some() && !solution_visible;
             let detail = HomeworkDetail {
                 homework,
+                late_policy,
                 attachments,
+                locked_solutions,
+                solution: solution.filter(|_| solution_visible),
+                solution_locked,
+                parts,
+                links,
                 creator,
             };
             Ok(HttpResponse::Ok().json(ApiResponse::success(detail, "查询成功")))
@@ -0,0 +1,278 @@
+//! 考试模式访问日志
+//!
+//! 考试模式作业中，学生每次查看作业详情与提交尝试都会记录 IP 与 User-Agent，
+//! 教师按学生查看访问记录；多 IP 访问、查看后极短时间内提交、客户端时钟异常等在服务端标记。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::http::header::USER_AGENT;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::warn;
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use crate::models::homeworks::entities::{ExamAccessEvent, ExamAccessLog, ExamAnomaly};
+use crate::models::homeworks::requests::ExamAccessLogInput;
+use crate::models::homeworks::responses::{
+    ExamAccessDetailResponse, ExamAccessSummary, ExamAccessSummaryListResponse, HomeworkCreator,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::presenters::{load_user, load_users};
+use crate::storage::Storage;
+use crate::utils::ClientInfo;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以查看考试访问记录";
+/// 首次查看到首次成功提交的间隔低于该值（秒）时标记为异常
+const FAST_SUBMISSION_SECONDS: i64 = 60;
+/// User-Agent 最大保存长度（字符）
+const MAX_USER_AGENT_LENGTH: usize = 255;
+
+/// 记录考试访问事件，失败只记录警告，不影响正常请求
+pub async fn record_exam_access(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    homework_id: i64,
+    user_id: i64,
+    event: ExamAccessEvent,
+    submission_id: Option<i64>,
+    detail: Option<String>,
+) {
+    let user_agent = request
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|ua| ua.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+    let input = ExamAccessLogInput {
+        homework_id,
+        user_id,
+        event,
+        ip_address: ClientInfo::from_request(request).ip_string(),
+        user_agent,
+        submission_id,
+        detail: detail.map(|d| d.chars().take(MAX_USER_AGENT_LENGTH).collect()),
+    };
+
+    if let Err(e) = storage.record_exam_access(input).await {
+        warn!("记录考试访问日志失败 (homework={homework_id}, user={user_id}): {e}");
+    }
+}
+
+/// 汇总单个学生的访问记录并计算异常标记
+pub fn summarize(user_id: i64, logs: &[ExamAccessLog]) -> ExamAccessSummary {
+    let mut ip_addresses: Vec<String> = Vec::new();
+    let mut view_count = 0;
+    let mut submit_attempts = 0;
+    let mut first_view_at = None;
+    let mut first_submit_at = None;
+    let mut clock_skew = false;
+
+    for log in logs {
+        if let Some(ip) = &log.ip_address
+            && !ip_addresses.contains(ip)
+        {
+            ip_addresses.push(ip.clone());
+        }
+        match log.event {
+            ExamAccessEvent::View => {
+                view_count += 1;
+                first_view_at.get_or_insert(log.created_at);
+            }
+            ExamAccessEvent::Submit => {
+                submit_attempts += 1;
+                if log.submission_id.is_some() {
+                    first_submit_at.get_or_insert(log.created_at);
+                }
+            }
+            ExamAccessEvent::IpChange => {}
+            ExamAccessEvent::ClockSkew => clock_skew = true,
+        }
+    }
+
+    // 首次成功提交之前没有查看记录时不计算间隔
+    let seconds_to_submit = match (first_view_at, first_submit_at) {
+        (Some(view), Some(submit)) if view <= submit => Some((submit - view).num_seconds()),
+        _ => None,
+    };
+
+    let mut anomalies = Vec::new();
+    if ip_addresses.len() > 1 {
+        anomalies.push(ExamAnomaly::MultipleIps);
+    }
+    if seconds_to_submit.is_some_and(|secs| secs < FAST_SUBMISSION_SECONDS) {
+        anomalies.push(ExamAnomaly::FastSubmission);
+    }
+    if first_submit_at.is_some() && seconds_to_submit.is_none() {
+        anomalies.push(ExamAnomaly::SubmitWithoutView);
+    }
+    if clock_skew {
+        anomalies.push(ExamAnomaly::ClockSkew);
+    }
+
+    ExamAccessSummary {
+        user_id,
+        user: None,
+        view_count,
+        submit_attempts,
+        first_view_at,
+        first_submit_at,
+        seconds_to_submit,
+        ip_addresses,
+        anomalies,
+    }
+}
+
+pub async fn list_exam_access(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    let logs = match storage.list_exam_access_logs(homework_id, None).await {
+        Ok(logs) => logs,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询考试访问记录失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let mut by_user: HashMap<i64, Vec<ExamAccessLog>> = HashMap::new();
+    for log in logs {
+        by_user.entry(log.user_id).or_default().push(log);
+    }
+    let mut user_ids: Vec<i64> = by_user.keys().copied().collect();
+    user_ids.sort_unstable();
+
+    let mut users: HashMap<i64, HomeworkCreator> =
+        match load_users(&storage, user_ids.iter().copied()).await {
+            Ok(users) => users,
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询用户失败: {e}"),
+                    )),
+                );
+            }
+        };
+    let mut items: Vec<ExamAccessSummary> = user_ids
+        .into_iter()
+        .map(|user_id| {
+            let mut summary = summarize(user_id, &by_user[&user_id]);
+            summary.user = users.remove(&user_id);
+            summary
+        })
+        .collect();
+    // 异常多的学生排在前面，便于教师优先排查
+    items.sort_by(|a, b| b.anomalies.len().cmp(&a.anomalies.len()));
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ExamAccessSummaryListResponse { homework_id, items },
+        "查询成功",
+    )))
+}
+
+pub async fn get_student_exam_access(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    user_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    let logs = match storage
+        .list_exam_access_logs(homework_id, Some(user_id))
+        .await
+    {
+        Ok(logs) => logs,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询考试访问记录失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let mut summary = summarize(user_id, &logs);
+    summary.user = load_user(&storage, user_id).await;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ExamAccessDetailResponse { summary, logs },
+        "查询成功",
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn log(
+        event: ExamAccessEvent,
+        ip: &str,
+        offset: i64,
+        submission_id: Option<i64>,
+    ) -> ExamAccessLog {
+        ExamAccessLog {
+            id: 0,
+            homework_id: 1,
+            user_id: 1,
+            event,
+            ip_address: Some(ip.to_string()),
+            user_agent: None,
+            submission_id,
+            detail: None,
+            created_at: Utc::now() + Duration::seconds(offset),
+        }
+    }
+
+    #[test]
+    fn test_summarize_anomalies() {
+        let summary = summarize(
+            1,
+            &[
+                log(ExamAccessEvent::View, "10.0.0.1", 0, None),
+                log(ExamAccessEvent::Submit, "10.0.0.1", 600, Some(1)),
+            ],
+        );
+        assert_eq!(summary.seconds_to_submit, Some(600));
+        assert!(summary.anomalies.is_empty());
+
+        let summary = summarize(
+            1,
+            &[
+                log(ExamAccessEvent::View, "10.0.0.1", 0, None),
+                log(ExamAccessEvent::IpChange, "10.0.0.2", 10, None),
+                log(ExamAccessEvent::Submit, "10.0.0.2", 10, None),
+                log(ExamAccessEvent::Submit, "10.0.0.2", 20, Some(1)),
+            ],
+        );
+        assert_eq!(summary.submit_attempts, 2);
+        assert_eq!(summary.ip_addresses, vec!["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(
+            summary.anomalies,
+            vec![ExamAnomaly::MultipleIps, ExamAnomaly::FastSubmission]
+        );
+
+        let summary = summarize(1, &[log(ExamAccessEvent::Submit, "10.0.0.1", 0, Some(1))]);
+        assert_eq!(summary.anomalies, vec![ExamAnomaly::SubmitWithoutView]);
+    }
+}
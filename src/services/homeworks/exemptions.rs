@@ -0,0 +1,235 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::homeworks::entities::Homework;
+use crate::models::homeworks::requests::CreateHomeworkExemptionRequest;
+use crate::models::homeworks::responses::{
+    HomeworkCreator, HomeworkExemptionItem, HomeworkExemptionListResponse,
+};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::presenters::load_users;
+use crate::storage::Storage;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理作业豁免";
+
+/// 校验当前用户是否可以管理作业（管理员或班级教师）
+pub(super) async fn check_manage_permission(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    homework_id: i64,
+    denied_message: &'static str,
+) -> Result<(i64, Homework), HttpResponse> {
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let homework = match storage.get_homework_by_id(homework_id).await {
+        Ok(Some(hw)) => hw,
+        Ok(None) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+        Err(e) => {
+            return Err(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 本组织的 Admin 直接放行
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        if !TenantGuard::can_access_class(request, storage, homework.class_id).await {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+        return Ok((user_id, homework));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, homework.class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => Ok((user_id, homework)),
+        Ok(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            denied_message,
+        ))),
+        Err(e) => Err(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询班级成员失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn list_homework_exemptions(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    let exemptions = match storage.list_homework_exemptions(homework_id).await {
+        Ok(list) => list,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业豁免失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let mut users: HashMap<i64, HomeworkCreator> =
+        match load_users(&storage, exemptions.iter().map(|e| e.user_id)).await {
+            Ok(users) => users,
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询用户失败: {e}"),
+                    )),
+                );
+            }
+        };
+    let items = exemptions
+        .into_iter()
+        .map(|exemption| HomeworkExemptionItem {
+            user: users.remove(&exemption.user_id),
+            exemption,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        HomeworkExemptionListResponse { items },
+        "查询成功",
+    )))
+}
+
+pub async fn create_homework_exemption(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: CreateHomeworkExemptionRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, homework) =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok(v) => v,
+            Err(resp) => return Ok(resp),
+        };
+
+    // 被豁免者必须是该班级的学生
+    match RequireClassRole::class_user(request, &storage, req.user_id, homework.class_id).await {
+        Ok(Some(cu)) if cu.role != ClassUserRole::Teacher => {}
+        Ok(_) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::BadRequest,
+                "该用户不是班级学生",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级成员失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    match storage
+        .get_homework_exemption(homework_id, req.user_id)
+        .await
+    {
+        Ok(Some(_)) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::Conflict,
+                "该学生已被豁免",
+            )));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业豁免失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    let reason = req
+        .reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+
+    match storage
+        .create_homework_exemption(homework_id, req.user_id, reason, user_id)
+        .await
+    {
+        Ok(exemption) => {
+            Ok(HttpResponse::Created().json(ApiResponse::success(exemption, "豁免成功")))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("创建作业豁免失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn delete_homework_exemption(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    target_user_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage
+        .delete_homework_exemption(homework_id, target_user_id)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("已取消豁免"))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkExemptionNotFound,
+            "豁免记录不存在",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("删除作业豁免失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -0,0 +1,139 @@
+//! 作业用时与难度反馈
+//!
+//! 学生在本人最新提交评分后填写实际用时与体感难度，可多次修改；
+//! 教师通过班级校准报告查看聚合结果，不展示单个学生的反馈。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::parts::check_view_permission;
+use crate::models::homeworks::requests::HomeworkFeedbackRequest;
+use crate::models::homeworks::responses::HomeworkFeedbackResponse;
+use crate::models::submissions::entities::SubmissionStatus;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 校验调用者是班级学生，返回 (用户 ID, 最新提交是否已评分)
+async fn check_student(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> Result<(i64, bool), HttpResponse> {
+    let (user_id, is_teacher) = check_view_permission(service, request, homework_id).await?;
+    if is_teacher {
+        return Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkFeedbackNotAllowed,
+            "只有班级学生可以填写用时与难度反馈",
+        )));
+    }
+
+    match service
+        .get_storage(request)
+        .get_latest_submission(homework_id, user_id)
+        .await
+    {
+        Ok(submission) => Ok((
+            user_id,
+            submission.is_some_and(|s| s.status == SubmissionStatus::Graded),
+        )),
+        Err(e) => Err(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询提交失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn get_homework_feedback(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, graded) = match check_student(service, request, homework_id).await {
+        Ok(result) => result,
+        Err(resp) => return Ok(resp),
+    };
+
+    let homework = match storage.get_homework_by_id(homework_id).await {
+        Ok(Some(homework)) => homework,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    match storage.get_homework_feedback(homework_id, user_id).await {
+        Ok(feedback) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            HomeworkFeedbackResponse {
+                homework_id,
+                estimated_minutes: homework.estimated_minutes,
+                difficulty: homework.difficulty,
+                can_submit: graded,
+                feedback,
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询作业反馈失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn submit_homework_feedback(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: HomeworkFeedbackRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(message) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkFeedbackInvalid,
+            message,
+        )));
+    }
+
+    let (user_id, graded) = match check_student(service, request, homework_id).await {
+        Ok(result) => result,
+        Err(resp) => return Ok(resp),
+    };
+    if !graded {
+        return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkFeedbackNotAllowed,
+            "作业评分后才能填写用时与难度反馈",
+        )));
+    }
+
+    match service
+        .get_storage(request)
+        .upsert_homework_feedback(
+            homework_id,
+            user_id,
+            req.actual_minutes,
+            req.perceived_difficulty,
+        )
+        .await
+    {
+        Ok(feedback) => Ok(HttpResponse::Ok().json(ApiResponse::success(feedback, "反馈已保存"))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("保存作业反馈失败: {e}"),
+            )),
+        ),
+    }
+}
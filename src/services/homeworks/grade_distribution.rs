@@ -0,0 +1,136 @@
+//! 作业成绩分布（教师视角）
+//!
+//! 均值、中位数、标准差由存储层在数据库中计算；直方图按满分等分区间统计人数。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use crate::models::grades::requests::GradeDistributionQuery;
+use crate::models::grades::responses::{GradeDistributionResponse, GradeHistogramBin};
+use crate::models::{ApiResponse, ErrorCode};
+
+const DENIED_MESSAGE: &str = "只有班级教师可以查看成绩分布";
+/// 默认直方图分组数
+const DEFAULT_BINS: u32 = 10;
+/// 直方图分组数上限
+const MAX_BINS: u32 = 50;
+
+pub async fn get_grade_distribution(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    query: GradeDistributionQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let homework =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok((_, homework)) => homework,
+            Err(resp) => return Ok(resp),
+        };
+
+    // 指定分题时以分题满分为准
+    let max_score = match query.part_id {
+        Some(part_id) => match storage.get_homework_part(part_id).await {
+            Ok(Some(part)) if part.homework_id == homework_id => part.max_score,
+            Ok(_) => {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::HomeworkPartNotFound,
+                    "分题不存在或不属于该作业",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询作业分题失败: {e}"),
+                    )),
+                );
+            }
+        },
+        None => homework.max_score,
+    };
+
+    let statistics = match storage
+        .get_grade_statistics(homework_id, query.part_id)
+        .await
+    {
+        Ok(statistics) => statistics,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计成绩分布失败: {e}"),
+                )),
+            );
+        }
+    };
+    let scores = match storage
+        .list_latest_grade_scores(homework_id, query.part_id)
+        .await
+    {
+        Ok(scores) => scores,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询成绩列表失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let bins = query.bins.unwrap_or(DEFAULT_BINS).clamp(1, MAX_BINS);
+    let response = GradeDistributionResponse {
+        homework_id,
+        part_id: query.part_id,
+        max_score,
+        statistics,
+        histogram: build_histogram(&scores, max_score, bins),
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+}
+
+/// 将 [0, max_score] 等分为 `bins` 组统计人数；超出满分（加分）的计入最后一组，负分计入第一组
+fn build_histogram(scores: &[f64], max_score: f64, bins: u32) -> Vec<GradeHistogramBin> {
+    if max_score <= 0.0 || bins == 0 {
+        return vec![];
+    }
+
+    let width = max_score / bins as f64;
+    let mut histogram: Vec<GradeHistogramBin> = (0..bins)
+        .map(|i| GradeHistogramBin {
+            lower: (width * i as f64 * 100.0).round() / 100.0,
+            upper: (width * (i + 1) as f64 * 100.0).round() / 100.0,
+            count: 0,
+        })
+        .collect();
+
+    for &score in scores {
+        let index = ((score / width).floor().max(0.0) as usize).min(bins as usize - 1);
+        histogram[index].count += 1;
+    }
+
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_histogram_edges() {
+        let histogram = build_histogram(&[0.0, 9.99, 10.0, 55.0, 100.0, 105.0], 100.0, 10);
+        let counts: Vec<i64> = histogram.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![2, 1, 0, 0, 0, 1, 0, 0, 0, 2]);
+        assert_eq!(histogram[9].lower, 90.0);
+        assert_eq!(histogram[9].upper, 100.0);
+    }
+
+    #[test]
+    fn test_build_histogram_without_max_score() {
+        assert!(build_histogram(&[1.0], 0.0, 10).is_empty());
+    }
+}
@@ -0,0 +1,484 @@
+//! 从 Markdown/YAML 导入包批量创建作业
+//!
+//! 便于在 git 中维护课程材料后一次性推送到班级。上传内容可以是：
+//! - 单个 YAML 清单（`.yaml` / `.yml`），只能包含文本说明；
+//! - zip 包（`.zip`），根目录（或唯一的顶层目录）下包含 `homeworks.yaml` 清单，
+//!   说明文件与附件使用相对于清单的路径引用。
+//!
+//! 每个作业独立创建，失败的条目不影响其他条目，返回逐条结果。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use futures_util::StreamExt;
+use uuid::Uuid;
+use zip::ZipArchive;
+
+use super::HomeworkService;
+use crate::config::AppConfig;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::homeworks::requests::{
+    CreateHomeworkRequest, HomeworkAttachmentInput, HomeworkBundleItem, HomeworkBundleManifest,
+    validate_grace_minutes, validate_workload_estimate,
+};
+use crate::models::homeworks::responses::{HomeworkImportItemResult, HomeworkImportResponse};
+use crate::models::usage::entities::UsageMetric;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::system::DynamicConfig;
+use crate::services::usage::record_usage;
+use crate::storage::Storage;
+use crate::utils::{detect_code_language, validate_magic_bytes};
+
+/// 导入包最大字节数
+const MAX_BUNDLE_SIZE: usize = 50 * 1024 * 1024;
+/// 单次最多导入的作业数
+const MAX_IMPORT_ITEMS: usize = 100;
+/// 说明文件最大字节数
+const MAX_DESCRIPTION_SIZE: u64 = 1024 * 1024;
+/// zip 包中的清单文件名
+const MANIFEST_NAMES: [&str; 2] = ["homeworks.yaml", "homeworks.yml"];
+
+/// 解析后的导入包
+struct Bundle {
+    manifest: HomeworkBundleManifest,
+    archive: Option<ZipArchive<Cursor<Vec<u8>>>>,
+    /// 清单所在目录（zip 内路径前缀，根目录为空）
+    base_dir: String,
+}
+
+impl Bundle {
+    /// 按文件名解析上传内容
+    fn parse(file_name: &str, data: Vec<u8>) -> Result<Self, String> {
+        let lower = file_name.to_lowercase();
+        if lower.ends_with(".yaml") || lower.ends_with(".yml") {
+            return Ok(Self {
+                manifest: parse_manifest(&data)?,
+                archive: None,
+                base_dir: String::new(),
+            });
+        }
+        if !lower.ends_with(".zip") {
+            return Err("仅支持 .zip、.yaml、.yml 文件".to_string());
+        }
+
+        let mut archive =
+            ZipArchive::new(Cursor::new(data)).map_err(|e| format!("zip 包解析失败: {e}"))?;
+        // 取层级最浅的清单，兼容 git 导出时带一层顶层目录的 zip 包
+        let manifest_path = archive
+            .file_names()
+            .filter(|name| {
+                let file = name.rsplit('/').next().unwrap_or(name);
+                MANIFEST_NAMES.contains(&file) && name.matches('/').count() <= 1
+            })
+            .min_by_key(|name| name.matches('/').count())
+            .map(str::to_string)
+            .ok_or_else(|| "zip 包中缺少 homeworks.yaml 清单".to_string())?;
+        let base_dir = manifest_path
+            .rsplit_once('/')
+            .map(|(dir, _)| format!("{dir}/"))
+            .unwrap_or_default();
+
+        let manifest_data = read_entry(&mut archive, &manifest_path, MAX_DESCRIPTION_SIZE)?;
+        Ok(Self {
+            manifest: parse_manifest(&manifest_data)?,
+            archive: Some(archive),
+            base_dir,
+        })
+    }
+
+    /// 读取清单引用的文件
+    fn read(&mut self, path: &str, limit: u64) -> Result<Vec<u8>, String> {
+        let relative = normalize_path(path).ok_or_else(|| format!("文件路径无效: {path}"))?;
+        let archive = self
+            .archive
+            .as_mut()
+            .ok_or_else(|| format!("引用了文件 {path}，请将清单与文件打包为 zip 上传"))?;
+        read_entry(archive, &format!("{}{relative}", self.base_dir), limit)
+    }
+}
+
+fn parse_manifest(data: &[u8]) -> Result<HomeworkBundleManifest, String> {
+    serde_yaml::from_slice(data).map_err(|e| format!("清单解析失败: {e}"))
+}
+
+/// 规范化清单中的相对路径，拒绝绝对路径与 `..`
+pub(crate) fn normalize_path(path: &str) -> Option<String> {
+    let path = path.trim().replace('\\', "/");
+    if path.starts_with('/') {
+        return None;
+    }
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != ".")
+        .collect();
+    if segments.is_empty() || segments.contains(&"..") {
+        return None;
+    }
+    Some(segments.join("/"))
+}
+
+/// 读取 zip 条目，超过 `limit` 字节视为错误（防止压缩炸弹）
+pub(crate) fn read_entry(
+    archive: &mut ZipArchive<Cursor<Vec<u8>>>,
+    name: &str,
+    limit: u64,
+) -> Result<Vec<u8>, String> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|_| format!("zip 包中找不到文件: {name}"))?;
+    let mut data = Vec::new();
+    entry
+        .take(limit + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("读取文件 {name} 失败: {e}"))?;
+    if data.len() as u64 > limit {
+        return Err(format!("文件 {name} 超出大小限制"));
+    }
+    Ok(data)
+}
+
+/// 将导入包中的附件保存为上传文件，返回 download_token
+pub(crate) async fn store_attachment(
+    storage: &Arc<dyn Storage>,
+    user_id: i64,
+    path: &str,
+    data: &[u8],
+) -> Result<String, String> {
+    let original_name = path.rsplit('/').next().unwrap_or(path);
+    let extension = Path::new(original_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{}", ext.to_lowercase()))
+        .unwrap_or_default();
+    let allowed_types = DynamicConfig::upload_allowed_types().await;
+    if !allowed_types.iter().any(|t| t.to_lowercase() == extension) {
+        return Err(format!("附件 {path} 的文件类型不被允许"));
+    }
+    if !validate_magic_bytes(data, &extension) {
+        return Err(format!("附件 {path} 的内容与扩展名不匹配"));
+    }
+
+    let upload_dir = &AppConfig::get().upload.dir;
+    fs::create_dir_all(upload_dir).map_err(|e| format!("创建上传目录失败: {e}"))?;
+    let stored_name = format!("{}-{}.bin", chrono::Utc::now().timestamp(), Uuid::new_v4());
+    let file_path = format!("{upload_dir}/{stored_name}");
+    fs::write(&file_path, data).map_err(|e| format!("保存附件 {path} 失败: {e}"))?;
+
+    match storage
+        .upload_file(
+            original_name,
+            &stored_name,
+            &(data.len() as i64),
+            "application/octet-stream",
+            detect_code_language(original_name, data),
+            user_id,
+        )
+        .await
+    {
+        Ok(file) => {
+            record_usage(
+                storage.clone(),
+                file.org_id,
+                UsageMetric::UploadedBytes,
+                file.file_size,
+            );
+            Ok(file.download_token)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&file_path);
+            Err(format!("保存附件 {path} 失败: {e}"))
+        }
+    }
+}
+
+/// 将清单条目转换为创建请求；同一附件在多个作业中只保存一次
+async fn build_request(
+    storage: &Arc<dyn Storage>,
+    bundle: &mut Bundle,
+    stored: &mut HashMap<String, String>,
+    user_id: i64,
+    class_id: i64,
+    item: HomeworkBundleItem,
+) -> Result<CreateHomeworkRequest, String> {
+    if item.title.trim().is_empty() {
+        return Err("作业标题不能为空".to_string());
+    }
+    if item.max_content_length.is_some_and(|len| len < 0) {
+        return Err("内容长度限制不能为负数".to_string());
+    }
+    validate_workload_estimate(item.estimated_minutes, item.difficulty)?;
+    validate_grace_minutes(item.grace_minutes)?;
+
+    let description = match (item.description, &item.description_file) {
+        (Some(_), Some(_)) => return Err("description 与 description_file 只能填写一个".into()),
+        (Some(text), None) => Some(text),
+        (None, Some(path)) => {
+            let data = bundle.read(path, MAX_DESCRIPTION_SIZE)?;
+            Some(String::from_utf8(data).map_err(|_| format!("说明文件 {path} 不是 UTF-8 文本"))?)
+        }
+        (None, None) => None,
+    };
+
+    let max_size = DynamicConfig::upload_max_size().await as u64;
+    let mut attachments = Vec::with_capacity(item.attachments.len());
+    for attachment in &item.attachments {
+        let (path, kind) = attachment.parts();
+        let key = normalize_path(path).ok_or_else(|| format!("文件路径无效: {path}"))?;
+        let token = match stored.get(&key) {
+            Some(token) => token.clone(),
+            None => {
+                let data = bundle.read(path, max_size)?;
+                let token = store_attachment(storage, user_id, &key, &data).await?;
+                stored.insert(key, token.clone());
+                token
+            }
+        };
+        attachments.push(HomeworkAttachmentInput::WithKind { token, kind });
+    }
+
+    Ok(CreateHomeworkRequest {
+        class_id,
+        title: item.title,
+        description,
+        max_score: item.max_score,
+        deadline: item.deadline,
+        allow_late: item.allow_late,
+        submission_mode: item.submission_mode,
+        max_content_length: item.max_content_length,
+        exam_mode: item.exam_mode,
+        require_self_assessment: item.require_self_assessment,
+        show_grade_context: item.show_grade_context,
+        estimated_minutes: item.estimated_minutes,
+        difficulty: item.difficulty,
+        grace_minutes: item.grace_minutes,
+        attachments: (!attachments.is_empty()).then_some(attachments),
+    })
+}
+
+/// 读取 multipart 中的 `file` 字段，超过 `max_size` 字节视为错误
+pub(crate) async fn read_upload(
+    payload: &mut Multipart,
+    max_size: usize,
+) -> Result<(Vec<u8>, String), String> {
+    let mut file_bytes = Vec::new();
+    let mut file_name = String::new();
+
+    while let Some(item) = payload.next().await {
+        let mut field = item.map_err(|e| format!("读取字段失败: {e}"))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+        file_name = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .unwrap_or_default()
+            .to_string();
+        while let Some(chunk) = field.next().await {
+            let data = chunk.map_err(|e| format!("读取数据失败: {e}"))?;
+            if file_bytes.len() + data.len() > max_size {
+                return Err(format!("导入包不能超过 {} MB", max_size / 1024 / 1024));
+            }
+            file_bytes.extend_from_slice(&data);
+        }
+    }
+
+    if file_bytes.is_empty() {
+        return Err("未找到文件字段".to_string());
+    }
+    Ok((file_bytes, file_name))
+}
+
+pub async fn import_homeworks(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    created_by: i64,
+    class_id: i64,
+    mut payload: Multipart,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    // 权限规则与单个创建一致：班级教师或管理员
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {
+            if RequireJWT::extract_user_role(request) != Some(UserRole::Admin)
+                && class.teacher_id != created_by
+            {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::Forbidden,
+                    "只能在自己教授的班级创建作业",
+                )));
+            }
+        }
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    let (data, file_name) = match read_upload(&mut payload, MAX_BUNDLE_SIZE).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::FileUploadFailed,
+                format!("文件读取失败: {e}"),
+            )));
+        }
+    };
+
+    let mut bundle = match Bundle::parse(&file_name, data) {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::ImportFileParseFailed,
+                e,
+            )));
+        }
+    };
+
+    let items = std::mem::take(&mut bundle.manifest.homeworks);
+    if items.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ImportFileDataInvalid,
+            "清单中没有作业",
+        )));
+    }
+    if items.len() > MAX_IMPORT_ITEMS {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ImportFileDataInvalid,
+            format!("单次最多导入 {MAX_IMPORT_ITEMS} 个作业"),
+        )));
+    }
+
+    let mut stored = HashMap::new();
+    let mut results = Vec::with_capacity(items.len());
+    for (index, item) in items.into_iter().enumerate() {
+        let title = item.title.clone();
+        let outcome = match build_request(
+            &storage,
+            &mut bundle,
+            &mut stored,
+            created_by,
+            class_id,
+            item,
+        )
+        .await
+        {
+            Ok(req) => storage
+                .create_homework(created_by, req, None)
+                .await
+                .map_err(|e| format!("创建作业失败: {e}")),
+            Err(e) => Err(e),
+        };
+
+        results.push(match outcome {
+            Ok(homework) => HomeworkImportItemResult {
+                index,
+                title,
+                success: true,
+                homework: Some(homework),
+                message: None,
+            },
+            Err(message) => HomeworkImportItemResult {
+                index,
+                title,
+                success: false,
+                homework: None,
+                message: Some(message),
+            },
+        });
+    }
+
+    let success = results.iter().filter(|r| r.success).count();
+    let response = HomeworkImportResponse {
+        total: results.len(),
+        success,
+        failed: results.len() - success,
+        results,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "导入完成")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    const MANIFEST: &str = r##"
+homeworks:
+  - title: 第一章练习
+    description_file: ch1/README.md
+    max_score: 50
+    deadline: 2026-03-01T12:00:00Z
+    attachments:
+      - ch1/data.txt
+      - path: ch1/template.txt
+        kind: template
+  - title: 第二章练习
+    description: "# 第二章"
+"##;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./ch1//a.md"), Some("ch1/a.md".to_string()));
+        assert_eq!(normalize_path("ch1\\a.md"), Some("ch1/a.md".to_string()));
+        assert_eq!(normalize_path("../secret"), None);
+        assert_eq!(normalize_path("/etc/passwd"), None);
+        assert_eq!(normalize_path(""), None);
+    }
+
+    #[test]
+    fn test_parse_yaml_bundle() {
+        let mut bundle = Bundle::parse("course.yml", MANIFEST.as_bytes().to_vec()).unwrap();
+        assert_eq!(bundle.manifest.homeworks.len(), 2);
+        let first = &bundle.manifest.homeworks[0];
+        assert_eq!(first.max_score, Some(50.0));
+        assert!(first.deadline.is_some());
+        assert_eq!(first.attachments.len(), 2);
+        // 单独的 YAML 清单无法引用文件
+        assert!(bundle.read("ch1/README.md", 1024).is_err());
+    }
+
+    #[test]
+    fn test_parse_zip_bundle_with_top_level_dir() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        writer
+            .start_file("course-main/homeworks.yaml", options)
+            .unwrap();
+        writer.write_all(MANIFEST.as_bytes()).unwrap();
+        writer
+            .start_file("course-main/ch1/README.md", options)
+            .unwrap();
+        writer.write_all("# 第一章".as_bytes()).unwrap();
+        let data = writer.finish().unwrap().into_inner();
+
+        let mut bundle = Bundle::parse("course.zip", data).unwrap();
+        assert_eq!(bundle.base_dir, "course-main/");
+        assert_eq!(
+            bundle.read("./ch1/README.md", 1024).unwrap(),
+            "# 第一章".as_bytes()
+        );
+        assert!(bundle.read("ch1/README.md", 4).is_err());
+        assert!(bundle.read("ch1/missing.txt", 1024).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! 作业外部资源（讲解视频、外部文档链接）
+//!
+//! 教师可为作业添加外部链接，与上传的附件并列展示。学生通过 `open` 接口打开链接，
+//! 系统记录每个学生的打开次数与时间，教师据此查看学生是否打开了学习材料。
+
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use super::parts::check_view_permission;
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::class_users::requests::ClassUserQuery;
+use crate::models::homeworks::requests::{HomeworkLinkInput, ReplaceHomeworkLinksRequest};
+use crate::models::homeworks::responses::{
+    HomeworkLinkListResponse, HomeworkLinkOpenResponse, HomeworkLinkOpenStat,
+    HomeworkLinkStatsResponse, HomeworkLinkStudentOpens,
+};
+use crate::models::{ApiResponse, ErrorCode};
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理作业外部资源";
+
+/// 单个作业最多外部资源数
+const MAX_LINKS: usize = 30;
+
+/// 链接标题最大长度（字符）
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// 链接最大长度（字节）
+const MAX_URL_LENGTH: usize = 2048;
+
+/// 允许的链接协议，避免 `javascript:`、`data:` 等在前端被直接执行
+const ALLOWED_SCHEMES: &[&str] = &["http", "https"];
+
+/// 校验链接：协议须在允许列表内，且包含主机名、不含空白与控制字符
+pub(crate) fn validate_url(url: &str) -> Result<(), &'static str> {
+    if url.len() > MAX_URL_LENGTH {
+        return Err("链接过长");
+    }
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("链接不能包含空白字符");
+    }
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err("链接须以 http:// 或 https:// 开头");
+    };
+    if !ALLOWED_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) {
+        return Err("链接仅支持 http 和 https 协议");
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    if host.is_empty() || host.starts_with(':') {
+        return Err("链接缺少主机名");
+    }
+    Ok(())
+}
+
+/// 校验外部资源定义，`existing` 为作业当前的链接 ID
+fn validate_links(links: &[HomeworkLinkInput], existing: &HashSet<i64>) -> Result<(), String> {
+    if links.len() > MAX_LINKS {
+        return Err(format!("外部资源数量不能超过 {MAX_LINKS} 个"));
+    }
+    let mut seen = HashSet::new();
+    for (index, link) in links.iter().enumerate() {
+        let n = index + 1;
+        let title_len = link.title.trim().chars().count();
+        if title_len == 0 || title_len > MAX_TITLE_LENGTH {
+            return Err(format!(
+                "第 {n} 个链接标题不能为空且不超过 {MAX_TITLE_LENGTH} 个字符"
+            ));
+        }
+        if let Err(message) = validate_url(link.url.trim()) {
+            return Err(format!("第 {n} 个链接无效：{message}"));
+        }
+        if let Some(id) = link.id
+            && (!existing.contains(&id) || !seen.insert(id))
+        {
+            return Err(format!("第 {n} 个链接 ID {id} 无效"));
+        }
+    }
+    Ok(())
+}
+
+pub async fn list_homework_links(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_view_permission(service, request, homework_id).await {
+        return Ok(resp);
+    }
+
+    match storage.list_homework_links(homework_id).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            HomeworkLinkListResponse { homework_id, items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询作业外部资源失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn replace_homework_links(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: ReplaceHomeworkLinksRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok((user_id, _)) => user_id,
+            Err(resp) => return Ok(resp),
+        };
+
+    let existing_ids: HashSet<i64> = match storage.list_homework_links(homework_id).await {
+        Ok(links) => links.iter().map(|l| l.id).collect(),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业外部资源失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if let Err(message) = validate_links(&req.links, &existing_ids) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkLinkInvalid,
+            message,
+        )));
+    }
+
+    let links = req
+        .links
+        .into_iter()
+        .map(|l| HomeworkLinkInput {
+            title: l.title.trim().to_string(),
+            url: l.url.trim().to_string(),
+            ..l
+        })
+        .collect();
+
+    match storage
+        .replace_homework_links(homework_id, links, user_id)
+        .await
+    {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            HomeworkLinkListResponse { homework_id, items },
+            "外部资源已更新",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkUpdateFailed,
+                format!("保存作业外部资源失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 打开外部资源：记录学生的打开情况并返回链接（教师和管理员打开不计入统计）
+pub async fn open_homework_link(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    link_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, is_teacher) = match check_view_permission(service, request, homework_id).await {
+        Ok(result) => result,
+        Err(resp) => return Ok(resp),
+    };
+
+    let link = match storage.get_homework_link(link_id).await {
+        Ok(Some(link)) if link.homework_id == homework_id => link,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkLinkNotFound,
+                "外部资源不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业外部资源失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if !is_teacher && let Err(e) = storage.record_homework_link_open(link.id, user_id).await {
+        tracing::warn!("Failed to record homework link open: {e}");
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        HomeworkLinkOpenResponse {
+            link_id: link.id,
+            url: link.url,
+        },
+        "查询成功",
+    )))
+}
+
+/// 外部资源打开统计（教师查看哪些学生打开了学习材料）
+pub async fn get_homework_link_stats(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let homework =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok((_, homework)) => homework,
+            Err(resp) => return Ok(resp),
+        };
+
+    let links = match storage.list_homework_links(homework_id).await {
+        Ok(links) => links,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业外部资源失败: {e}"),
+                )),
+            );
+        }
+    };
+    let opens = match storage.list_homework_link_opens(homework_id).await {
+        Ok(opens) => opens,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询资源打开记录失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let class_users = match storage
+        .list_class_users_with_pagination(
+            homework.class_id,
+            ClassUserQuery {
+                page: Some(1),
+                size: Some(10000),
+                search: None,
+                role: None,
+            },
+        )
+        .await
+    {
+        Ok(resp) => resp.items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级成员失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 与作业统计一致，不计入教师和被豁免学生
+    let exempted_ids: HashSet<i64> = storage
+        .get_exempted_user_ids(homework_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let students: Vec<_> = class_users
+        .into_iter()
+        .filter(|cu| cu.role != ClassUserRole::Teacher && !exempted_ids.contains(&cu.user_id))
+        .collect();
+    let student_ids: HashSet<i64> = students.iter().map(|cu| cu.user_id).collect();
+    let total_students = students.len() as i64;
+
+    let opens: Vec<_> = opens
+        .into_iter()
+        .filter(|open| student_ids.contains(&open.user_id))
+        .collect();
+
+    let links = links
+        .into_iter()
+        .map(|link| {
+            let link_opens: Vec<_> = opens.iter().filter(|o| o.link_id == link.id).collect();
+            let opened_students = link_opens.len() as i64;
+            let open_rate = if total_students > 0 {
+                (opened_students as f64 / total_students as f64 * 100.0 * 100.0).round() / 100.0
+            } else {
+                0.0
+            };
+            HomeworkLinkOpenStat {
+                opened_students,
+                total_opens: link_opens.iter().map(|o| o.open_count as i64).sum(),
+                open_rate,
+                link,
+            }
+        })
+        .collect();
+
+    let mut by_student: HashMap<i64, Vec<_>> = HashMap::new();
+    for open in &opens {
+        by_student.entry(open.user_id).or_default().push(open);
+    }
+
+    let mut student_opens = Vec::with_capacity(students.len());
+    for student in students {
+        let Ok(Some(user)) = storage.get_user_by_id(student.user_id).await else {
+            continue;
+        };
+        let records = by_student.remove(&student.user_id).unwrap_or_default();
+        student_opens.push(HomeworkLinkStudentOpens {
+            user_id: user.id,
+            username: user.username,
+            display_name: student.profile_name.or(user.display_name),
+            opened_link_ids: records.iter().map(|o| o.link_id).collect(),
+            last_opened_at: records.iter().map(|o| o.last_opened_at).max(),
+        });
+    }
+    // 未打开任何材料的学生排在前面，便于教师提醒
+    student_opens.sort_by(|a, b| {
+        a.opened_link_ids
+            .len()
+            .cmp(&b.opened_link_ids.len())
+            .then_with(|| a.username.cmp(&b.username))
+    });
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        HomeworkLinkStatsResponse {
+            homework_id,
+            total_students,
+            links,
+            students: student_opens,
+        },
+        "查询成功",
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_url() {
+        assert!(validate_url("https://www.bilibili.com/video/BV1xx411c7mD").is_ok());
+        assert!(validate_url("HTTP://example.com:8080/doc?id=1#p2").is_ok());
+        assert!(validate_url("https://user@example.com").is_ok());
+        assert!(validate_url("javascript:alert(1)").is_err());
+        assert!(validate_url("data://text/html,hi").is_err());
+        assert!(validate_url("ftp://example.com/a.pdf").is_err());
+        assert!(validate_url("https:///path").is_err());
+        assert!(validate_url("https://example.com/a b").is_err());
+    }
+}
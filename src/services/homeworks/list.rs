@@ -1,15 +1,23 @@
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
 use crate::models::users::entities::UserRole;
-use crate::models::{ApiResponse, ErrorCode, homeworks::requests::{HomeworkListParams, HomeworkListQuery}};
+use crate::models::{
+    ApiResponse, ErrorCode,
+    homeworks::requests::{HomeworkListParams, HomeworkListQuery},
+};
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::HomeworkService;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 
 pub async fn list_homeworks(
     service: &HomeworkService,
     request: &HttpRequest,
     query: HomeworkListParams,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("homeworks").check(query.pagination.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
 
     // 获取当前用户信息
@@ -24,23 +32,23 @@ pub async fn list_homeworks(
     // 权限验证逻辑
     let mut filtered_query = HomeworkListQuery {
         page: Some(query.pagination.page),
-        size: Some(query.pagination.size),
+        size: query.pagination.size,
         class_id: query.class_id,
         created_by: query.created_by,
         search: query.search.clone(),
         include_stats: query.include_stats,
+        tenant: TenantGuard::scope(request),
     };
 
     match current_user.role {
         UserRole::Admin => {
-            // 管理员可以查看所有作业
+            // 管理员可以查看本组织的所有作业
         }
         UserRole::Teacher => {
             // 教师可以查看自己创建的作业，或者指定班级的作业（需要验证班级权限）
             if let Some(class_id) = query.class_id {
                 // 验证教师是否有该班级的权限
-                match storage
-                    .get_class_user_by_user_id_and_class_id(current_user.id, class_id)
+                match RequireClassRole::class_user(request, &storage, current_user.id, class_id)
                     .await
                 {
                     Ok(Some(_)) => {
@@ -80,8 +88,7 @@ pub async fn list_homeworks(
             // 普通用户（学生）必须指定班级，且必须是班级成员
             if let Some(class_id) = query.class_id {
                 // 验证用户是否为该班级成员
-                match storage
-                    .get_class_user_by_user_id_and_class_id(current_user.id, class_id)
+                match RequireClassRole::class_user(request, &storage, current_user.id, class_id)
                     .await
                 {
                     Ok(Some(_)) => {
@@ -110,6 +117,13 @@ pub async fn list_homeworks(
                 )));
             }
         }
+        UserRole::Viewer => {
+            // 家长/导师只能通过 /viewer 接口查看授权学生的汇总
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ViewerAccessDenied,
+                "只读查看账号无权访问作业列表",
+            )));
+        }
     }
 
     // 确定是否需要查询当前用户的提交状态
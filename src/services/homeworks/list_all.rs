@@ -3,6 +3,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use crate::middlewares::RequireJWT;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::models::homeworks::requests::{AllHomeworksParams, AllHomeworksQuery};
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
@@ -13,6 +14,10 @@ pub async fn list_all_homeworks(
     request: &HttpRequest,
     query: AllHomeworksParams,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("homeworks").check(query.pagination.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
 
     // 获取当前用户
@@ -32,7 +37,7 @@ pub async fn list_all_homeworks(
     // 转换为存储层查询参数
     let storage_query = AllHomeworksQuery {
         page: Some(query.pagination.page),
-        size: Some(query.pagination.size),
+        size: query.pagination.size,
         status: query.status,
         deadline_filter: query.deadline_filter,
         search: query.search,
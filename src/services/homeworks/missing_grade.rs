@@ -0,0 +1,175 @@
+//! 缺交零分策略
+//!
+//! 教师为作业设置最终截止时间后，到期时为班级中没有任何提交、未被豁免的学生写入占位提交和
+//! `auto_assigned` 零分，并通知学生。零分与普通评分一样计入作业统计与成绩汇总；
+//! 教师可以逐个撤销，或直接修改分数（修改后不再标记为自动记录）。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use crate::errors::Result;
+use crate::models::homeworks::requests::UpsertMissingGradePolicyRequest;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理缺交零分策略";
+
+/// 执行到期的缺交零分策略，返回被记零分的学生数（未到期或已执行时为 0）
+pub async fn apply_missing_grade_policy_if_due(
+    storage: &Arc<dyn Storage>,
+    homework_id: i64,
+) -> Result<usize> {
+    let student_ids = storage.apply_missing_grade_policy(homework_id).await?;
+    if !student_ids.is_empty() {
+        tracing::info!(
+            "Assigned zero grades to {} students without submissions for homework {homework_id}",
+            student_ids.len()
+        );
+    }
+    Ok(student_ids.len())
+}
+
+fn policy_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::MissingGradePolicyNotFound,
+        "该作业未设置缺交零分策略",
+    ))
+}
+
+pub async fn get_missing_grade_policy(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage.get_missing_grade_policy(homework_id).await {
+        Ok(Some(policy)) => Ok(HttpResponse::Ok().json(ApiResponse::success(policy, "查询成功"))),
+        Ok(None) => Ok(policy_not_found()),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询缺交零分策略失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn upsert_missing_grade_policy(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: UpsertMissingGradePolicyRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, homework) =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok(v) => v,
+            Err(resp) => return Ok(resp),
+        };
+
+    if homework
+        .deadline
+        .is_some_and(|deadline| req.cutoff_at < deadline)
+    {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "最终截止时间不能早于作业截止时间",
+        )));
+    }
+
+    if let Err(e) = storage
+        .upsert_missing_grade_policy(homework_id, req.cutoff_at.timestamp(), user_id)
+        .await
+    {
+        return Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("保存缺交零分策略失败: {e}"),
+            )),
+        );
+    }
+
+    // 最终截止时间已过的策略立即执行，无需等待定时任务
+    if let Err(e) = apply_missing_grade_policy_if_due(&storage, homework_id).await {
+        tracing::warn!("Failed to apply missing grade policy for homework {homework_id}: {e}");
+    }
+
+    match storage.get_missing_grade_policy(homework_id).await {
+        Ok(Some(policy)) => {
+            Ok(HttpResponse::Ok().json(ApiResponse::success(policy, "缺交零分策略已保存")))
+        }
+        Ok(None) => Ok(policy_not_found()),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询缺交零分策略失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn delete_missing_grade_policy(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage.delete_missing_grade_policy(homework_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("缺交零分策略已删除"))),
+        Ok(false) => Ok(policy_not_found()),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("删除缺交零分策略失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 撤销学生的自动零分，学生恢复为未提交
+pub async fn revert_auto_assigned_grade(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    target_user_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage
+        .revert_auto_assigned_grade(homework_id, target_user_id)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("自动零分已撤销"))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::AutoGradeNotFound,
+            "该学生没有自动记录的零分",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("撤销自动零分失败: {e}"),
+            )),
+        ),
+    }
+}
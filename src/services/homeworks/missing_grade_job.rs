@@ -0,0 +1,43 @@
+//! 缺交零分定时任务
+//!
+//! 定期执行已到最终截止时间的缺交零分策略。教师在截止时间之后才设置的策略保存时即执行，
+//! 此任务保证无人操作时也能按时记分并通知学生。
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use super::missing_grade::apply_missing_grade_policy_if_due;
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 检查间隔，默认 300 秒，可通过系统设置 `jobs.missing_grade_interval` 调整
+pub static CHECK_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(300));
+
+/// 启动缺交零分定时任务
+pub fn spawn_missing_grade_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        CHECK_INTERVAL.run(|| apply_due_policies(&storage)).await;
+    });
+}
+
+/// 执行所有到期的策略
+async fn apply_due_policies(storage: &Arc<dyn Storage>) {
+    let now = chrono::Utc::now().timestamp();
+    let policies = match storage.list_due_missing_grade_policies(now).await {
+        Ok(policies) => policies,
+        Err(e) => {
+            tracing::warn!("Failed to list due missing grade policies: {e}");
+            return;
+        }
+    };
+
+    for policy in policies {
+        if let Err(e) = apply_missing_grade_policy_if_due(storage, policy.homework_id).await {
+            tracing::warn!(
+                "Failed to apply missing grade policy for homework {}: {e}",
+                policy.homework_id
+            );
+        }
+    }
+}
@@ -1,22 +1,50 @@
+pub mod attachments;
+pub mod batch_create;
 pub mod create;
+pub mod deadline_check;
 pub mod delete;
 pub mod detail;
+pub mod exam_access;
+pub mod exemptions;
+pub mod feedback;
+pub mod grade_distribution;
+pub mod import;
+pub mod links;
 pub mod list;
 pub mod list_all;
+pub mod missing_grade;
+pub mod missing_grade_job;
 pub mod my_stats;
+pub mod parts;
+pub mod prerequisites;
+pub mod rubric;
+pub mod share_links;
+pub mod shared;
+pub mod solution;
+pub mod solution_job;
 pub mod stats;
 pub mod stats_export;
 pub mod teacher_stats;
 pub mod update;
 
+use actix_multipart::Multipart;
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
+use crate::models::grades::requests::GradeDistributionQuery;
 use crate::models::homeworks::requests::{
-    AllHomeworksParams, CreateHomeworkRequest, HomeworkListParams, UpdateHomeworkRequest,
+    AllHomeworksParams, BatchCreateHomeworkRequest, CheckDeadlineRequest,
+    CreateHomeworkExemptionRequest, CreateHomeworkRequest, CreateHomeworkShareLinkRequest,
+    HomeworkFeedbackRequest, HomeworkListParams, HomeworkPartScoresQuery,
+    ReplaceHomeworkLinksRequest, ReplaceHomeworkPartsRequest, ReplaceHomeworkPrerequisitesRequest,
+    UpdateHomeworkRequest, UpsertHomeworkSolutionRequest, UpsertMissingGradePolicyRequest,
 };
+use crate::models::rubrics::requests::SetHomeworkRubricRequest;
 use crate::storage::Storage;
 
+pub use missing_grade_job::spawn_missing_grade_job;
+pub use solution_job::spawn_solution_reveal_job;
+
 pub struct HomeworkService {
     storage: Option<Arc<dyn Storage>>,
 }
@@ -55,6 +83,35 @@ impl HomeworkService {
         create::create_homework(self, request, created_by, req).await
     }
 
+    pub async fn batch_create_homeworks(
+        &self,
+        request: &HttpRequest,
+        created_by: i64,
+        req: BatchCreateHomeworkRequest,
+    ) -> ActixResult<HttpResponse> {
+        batch_create::batch_create_homeworks(self, request, created_by, req).await
+    }
+
+    pub async fn import_homeworks(
+        &self,
+        request: &HttpRequest,
+        created_by: i64,
+        class_id: i64,
+        payload: Multipart,
+    ) -> ActixResult<HttpResponse> {
+        import::import_homeworks(self, request, created_by, class_id, payload).await
+    }
+
+    pub async fn check_deadline(
+        &self,
+        request: &HttpRequest,
+        user_id: i64,
+        class_id: i64,
+        req: CheckDeadlineRequest,
+    ) -> ActixResult<HttpResponse> {
+        deadline_check::check_deadline(self, request, user_id, class_id, req).await
+    }
+
     pub async fn get_homework(
         &self,
         request: &HttpRequest,
@@ -116,4 +173,285 @@ impl HomeworkService {
     ) -> ActixResult<HttpResponse> {
         list_all::list_all_homeworks(self, request, query).await
     }
+
+    pub async fn list_homework_exemptions(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        exemptions::list_homework_exemptions(self, request, homework_id).await
+    }
+
+    pub async fn create_homework_exemption(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: CreateHomeworkExemptionRequest,
+    ) -> ActixResult<HttpResponse> {
+        exemptions::create_homework_exemption(self, request, homework_id, req).await
+    }
+
+    pub async fn delete_homework_exemption(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        user_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        exemptions::delete_homework_exemption(self, request, homework_id, user_id).await
+    }
+
+    pub async fn list_exam_access(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        exam_access::list_exam_access(self, request, homework_id).await
+    }
+
+    pub async fn get_student_exam_access(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        user_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        exam_access::get_student_exam_access(self, request, homework_id, user_id).await
+    }
+
+    pub async fn list_homework_share_links(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        share_links::list_homework_share_links(self, request, homework_id).await
+    }
+
+    pub async fn create_homework_share_link(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: CreateHomeworkShareLinkRequest,
+    ) -> ActixResult<HttpResponse> {
+        share_links::create_homework_share_link(self, request, homework_id, req).await
+    }
+
+    pub async fn revoke_homework_share_link(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        link_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        share_links::revoke_homework_share_link(self, request, homework_id, link_id).await
+    }
+
+    pub async fn get_homework_solution(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        solution::get_homework_solution(self, request, homework_id).await
+    }
+
+    pub async fn upsert_homework_solution(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: UpsertHomeworkSolutionRequest,
+    ) -> ActixResult<HttpResponse> {
+        solution::upsert_homework_solution(self, request, homework_id, req).await
+    }
+
+    pub async fn delete_homework_solution(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        solution::delete_homework_solution(self, request, homework_id).await
+    }
+
+    pub async fn publish_homework_solution(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        solution::publish_homework_solution(self, request, homework_id).await
+    }
+
+    pub async fn get_missing_grade_policy(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        missing_grade::get_missing_grade_policy(self, request, homework_id).await
+    }
+
+    pub async fn upsert_missing_grade_policy(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: UpsertMissingGradePolicyRequest,
+    ) -> ActixResult<HttpResponse> {
+        missing_grade::upsert_missing_grade_policy(self, request, homework_id, req).await
+    }
+
+    pub async fn delete_missing_grade_policy(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        missing_grade::delete_missing_grade_policy(self, request, homework_id).await
+    }
+
+    pub async fn revert_auto_assigned_grade(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        user_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        missing_grade::revert_auto_assigned_grade(self, request, homework_id, user_id).await
+    }
+
+    pub async fn list_homework_parts(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        parts::list_homework_parts(self, request, homework_id).await
+    }
+
+    pub async fn replace_homework_parts(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: ReplaceHomeworkPartsRequest,
+    ) -> ActixResult<HttpResponse> {
+        parts::replace_homework_parts(self, request, homework_id, req).await
+    }
+
+    pub async fn get_homework_rubric(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        rubric::get_homework_rubric(self, request, homework_id).await
+    }
+
+    pub async fn set_homework_rubric(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: SetHomeworkRubricRequest,
+    ) -> ActixResult<HttpResponse> {
+        rubric::set_homework_rubric(self, request, homework_id, req).await
+    }
+
+    pub async fn delete_homework_rubric(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        rubric::delete_homework_rubric(self, request, homework_id).await
+    }
+
+    pub async fn list_homework_links(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        links::list_homework_links(self, request, homework_id).await
+    }
+
+    pub async fn replace_homework_links(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: ReplaceHomeworkLinksRequest,
+    ) -> ActixResult<HttpResponse> {
+        links::replace_homework_links(self, request, homework_id, req).await
+    }
+
+    pub async fn open_homework_link(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        link_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        links::open_homework_link(self, request, homework_id, link_id).await
+    }
+
+    pub async fn get_homework_link_stats(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        links::get_homework_link_stats(self, request, homework_id).await
+    }
+
+    pub async fn get_homework_feedback(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        feedback::get_homework_feedback(self, request, homework_id).await
+    }
+
+    pub async fn submit_homework_feedback(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: HomeworkFeedbackRequest,
+    ) -> ActixResult<HttpResponse> {
+        feedback::submit_homework_feedback(self, request, homework_id, req).await
+    }
+
+    pub async fn list_homework_prerequisites(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        prerequisites::list_homework_prerequisites(self, request, homework_id).await
+    }
+
+    pub async fn replace_homework_prerequisites(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        req: ReplaceHomeworkPrerequisitesRequest,
+    ) -> ActixResult<HttpResponse> {
+        prerequisites::replace_homework_prerequisites(self, request, homework_id, req).await
+    }
+
+    pub async fn get_grade_distribution(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        query: GradeDistributionQuery,
+    ) -> ActixResult<HttpResponse> {
+        grade_distribution::get_grade_distribution(self, request, homework_id, query).await
+    }
+
+    pub async fn get_homework_part_scores(
+        &self,
+        request: &HttpRequest,
+        homework_id: i64,
+        query: HomeworkPartScoresQuery,
+    ) -> ActixResult<HttpResponse> {
+        parts::get_homework_part_scores(self, request, homework_id, query).await
+    }
+
+    pub async fn get_shared_homework(
+        &self,
+        request: &HttpRequest,
+        token: &str,
+    ) -> ActixResult<HttpResponse> {
+        shared::get_shared_homework(self, request, token).await
+    }
+
+    pub async fn download_shared_file(
+        &self,
+        request: &HttpRequest,
+        token: &str,
+        file_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        shared::download_shared_file(self, request, token, file_id).await
+    }
 }
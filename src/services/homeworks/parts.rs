@@ -0,0 +1,402 @@
+//! 多部分作业（分题）
+//!
+//! 作业可拆分为若干分题，各自拥有说明、满分与可选截止时间。学生按分题提交，
+//! 每个分题取最新提交的评分，汇总为作业总分；作业满分同步为各分题满分之和。
+
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::homeworks::entities::HomeworkPart;
+use crate::models::homeworks::requests::{
+    HomeworkPartInput, HomeworkPartScoresQuery, ReplaceHomeworkPartsRequest,
+};
+use crate::models::homeworks::responses::{
+    HomeworkPartListResponse, HomeworkPartScore, HomeworkPartScoresResponse,
+};
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::requests::SubmissionListQuery;
+use crate::models::submissions::responses::SubmissionListItem;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理作业分题";
+
+/// 单个作业最多分题数
+const MAX_PARTS: usize = 50;
+
+/// 分题标题最大长度（字符）
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// 按（学生, 分题）保留最新版本的提交；未拆分作业的分题键为 `None`
+pub(super) fn latest_by_part(
+    items: &[SubmissionListItem],
+) -> HashMap<(i64, Option<i64>), &SubmissionListItem> {
+    let mut latest: HashMap<(i64, Option<i64>), &SubmissionListItem> = HashMap::new();
+    for item in items {
+        let entry = latest
+            .entry((item.creator_id, item.part_id))
+            .or_insert(item);
+        if item.version > entry.version {
+            *entry = item;
+        }
+    }
+    latest
+}
+
+/// 校验分题定义，`existing` 为作业当前的分题 ID
+fn validate_parts(parts: &[HomeworkPartInput], existing: &HashSet<i64>) -> Result<(), String> {
+    if parts.len() > MAX_PARTS {
+        return Err(format!("分题数量不能超过 {MAX_PARTS} 个"));
+    }
+    let mut seen = HashSet::new();
+    for (index, part) in parts.iter().enumerate() {
+        let n = index + 1;
+        let title_len = part.title.trim().chars().count();
+        if title_len == 0 || title_len > MAX_TITLE_LENGTH {
+            return Err(format!(
+                "第 {n} 个分题标题不能为空且不超过 {MAX_TITLE_LENGTH} 个字符"
+            ));
+        }
+        if !part.max_score.is_finite() || part.max_score <= 0.0 {
+            return Err(format!("第 {n} 个分题满分必须大于 0"));
+        }
+        if let Some(id) = part.id
+            && (!existing.contains(&id) || !seen.insert(id))
+        {
+            return Err(format!("第 {n} 个分题 ID {id} 无效"));
+        }
+    }
+    Ok(())
+}
+
+pub async fn list_homework_parts(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_view_permission(service, request, homework_id).await {
+        return Ok(resp);
+    }
+
+    match storage.list_homework_parts(homework_id).await {
+        Ok(items) => {
+            let total_max_score = items.iter().map(|p| p.max_score).sum();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                HomeworkPartListResponse {
+                    homework_id,
+                    items,
+                    total_max_score,
+                },
+                "查询成功",
+            )))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询作业分题失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn replace_homework_parts(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: ReplaceHomeworkPartsRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    let existing = match storage.list_homework_parts(homework_id).await {
+        Ok(parts) => parts,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业分题失败: {e}"),
+                )),
+            );
+        }
+    };
+    let submitted = match storage.list_submitted_homework_part_ids(homework_id).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询分题提交失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let existing_ids: HashSet<i64> = existing.iter().map(|p| p.id).collect();
+    if let Err(message) = validate_parts(&req.parts, &existing_ids) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkPartInvalid,
+            message,
+        )));
+    }
+
+    // 已有提交的分题不能删除，避免提交失去归属
+    let kept: HashSet<i64> = req.parts.iter().filter_map(|p| p.id).collect();
+    if let Some(part) = existing
+        .iter()
+        .find(|p| !kept.contains(&p.id) && submitted.contains(&p.id))
+    {
+        return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkPartInUse,
+            format!("分题「{}」已有提交，不能删除", part.title),
+        )));
+    }
+
+    let parts = req
+        .parts
+        .into_iter()
+        .map(|p| HomeworkPartInput {
+            title: p.title.trim().to_string(),
+            description: p.description.filter(|d| !d.trim().is_empty()),
+            ..p
+        })
+        .collect();
+
+    match storage.replace_homework_parts(homework_id, parts).await {
+        Ok(items) => {
+            let total_max_score = items.iter().map(|p| p.max_score).sum();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                HomeworkPartListResponse {
+                    homework_id,
+                    items,
+                    total_max_score,
+                },
+                "分题已更新",
+            )))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkUpdateFailed,
+                format!("保存作业分题失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 学生的分题得分汇总（学生查看自己，教师可通过 `user_id` 指定学生）
+pub async fn get_homework_part_scores(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    query: HomeworkPartScoresQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, can_manage) = match check_view_permission(service, request, homework_id).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+    let target_id = match query.user_id {
+        Some(id) if id != user_id && !can_manage => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只能查看自己的分题得分",
+            )));
+        }
+        Some(id) => id,
+        None => user_id,
+    };
+
+    let parts = match storage.list_homework_parts(homework_id).await {
+        Ok(parts) => parts,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业分题失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let submissions = match storage
+        .list_submissions_with_pagination(SubmissionListQuery {
+            page: Some(1),
+            size: Some(10000),
+            homework_id: Some(homework_id),
+            creator_id: Some(target_id),
+            status: None,
+            tenant: TenantScope::All,
+        })
+        .await
+    {
+        Ok(resp) => resp.items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询提交失败: {e}"),
+                )),
+            );
+        }
+    };
+    let latest = latest_by_part(&submissions);
+
+    let mut scores = Vec::with_capacity(parts.len());
+    for part in &parts {
+        let submission = latest.get(&(target_id, Some(part.id)));
+        let score = match submission {
+            Some(s) => storage
+                .get_grade_by_submission_id(s.id)
+                .await
+                .ok()
+                .flatten()
+                .map(|g| g.score),
+            None => None,
+        };
+        scores.push(part_score(part, submission.copied(), score));
+    }
+
+    let graded: Vec<f64> = scores.iter().filter_map(|s| s.score).collect();
+    let response = HomeworkPartScoresResponse {
+        homework_id,
+        user_id: target_id,
+        total_score: (!graded.is_empty()).then(|| graded.iter().sum()),
+        max_score: parts.iter().map(|p| p.max_score).sum(),
+        fully_graded: !scores.is_empty() && graded.len() == scores.len(),
+        parts: scores,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+}
+
+fn part_score(
+    part: &HomeworkPart,
+    submission: Option<&SubmissionListItem>,
+    score: Option<f64>,
+) -> HomeworkPartScore {
+    HomeworkPartScore {
+        part_id: part.id,
+        title: part.title.clone(),
+        max_score: part.max_score,
+        deadline: part.deadline,
+        submission_id: submission.map(|s| s.id),
+        is_late: submission.is_some_and(|s| s.is_late),
+        score,
+    }
+}
+
+/// 校验当前用户是否为作业所属班级成员，返回（用户 ID, 是否可管理）
+pub(super) async fn check_view_permission(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> Result<(i64, bool), HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let homework = match storage.get_homework_by_id(homework_id).await {
+        Ok(Some(hw)) => hw,
+        Ok(None) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+        Err(e) => {
+            return Err(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        // 组织管理员只能访问本组织班级的作业
+        if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+        return Ok((user_id, true));
+    }
+
+    match RequireClassRole::class_user(request, &storage, user_id, homework.class_id).await {
+        Ok(Some(cu)) => Ok((user_id, cu.role == ClassUserRole::Teacher)),
+        Ok(None) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "您不是该班级成员",
+        ))),
+        Err(e) => Err(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询班级成员失败: {e}"),
+            )),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(id: Option<i64>, title: &str, max_score: f64) -> HomeworkPartInput {
+        HomeworkPartInput {
+            id,
+            title: title.to_string(),
+            description: None,
+            max_score,
+            deadline: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_parts() {
+        let existing: HashSet<i64> = [1, 2].into_iter().collect();
+
+        assert!(validate_parts(&[], &existing).is_ok());
+        assert!(
+            validate_parts(
+                &[input(Some(1), "第一题", 40.0), input(None, "第二题", 60.0)],
+                &existing
+            )
+            .is_ok()
+        );
+
+        assert!(validate_parts(&[input(None, "  ", 10.0)], &existing).is_err());
+        assert!(validate_parts(&[input(None, "题", 0.0)], &existing).is_err());
+        assert!(validate_parts(&[input(None, "题", f64::NAN)], &existing).is_err());
+        // 不属于该作业的 ID
+        assert!(validate_parts(&[input(Some(9), "题", 10.0)], &existing).is_err());
+        // 重复 ID
+        assert!(
+            validate_parts(
+                &[input(Some(1), "甲", 10.0), input(Some(1), "乙", 10.0)],
+                &existing
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! 作业前置条件
+//!
+//! 作业可声明同班级的前置作业及完成要求（已提交 / 已及格）。学生未满足全部前置条件时，
+//! 作业在列表中标记为锁定且不能提交。保存时校验依赖关系，拒绝形成循环的配置。
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use super::parts::check_view_permission;
+use crate::models::homeworks::entities::{Homework, HomeworkPrerequisite, PrerequisiteRequirement};
+use crate::models::homeworks::requests::{
+    HomeworkPrerequisiteInput, ReplaceHomeworkPrerequisitesRequest,
+};
+use crate::models::homeworks::responses::{
+    HomeworkPrerequisiteItem, HomeworkPrerequisiteListResponse,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以设置作业前置条件";
+
+/// 单个作业最多前置作业数
+const MAX_PREREQUISITES: usize = 20;
+
+/// 校验前置条件定义（不含跨作业检查）
+fn validate_prerequisites(
+    homework_id: i64,
+    prerequisites: &[HomeworkPrerequisiteInput],
+) -> Result<(), String> {
+    if prerequisites.len() > MAX_PREREQUISITES {
+        return Err(format!("前置作业不能超过 {MAX_PREREQUISITES} 个"));
+    }
+    let mut seen = HashSet::new();
+    for item in prerequisites {
+        if item.homework_id == homework_id {
+            return Err("作业不能以自身为前置作业".to_string());
+        }
+        if !seen.insert(item.homework_id) {
+            return Err(format!("前置作业 {} 重复", item.homework_id));
+        }
+        if let Some(score) = item.min_score
+            && (!score.is_finite() || score < 0.0)
+        {
+            return Err(format!("前置作业 {} 的及格分数无效", item.homework_id));
+        }
+    }
+    Ok(())
+}
+
+/// 以 `prerequisites` 作为 `homework_id` 的新前置作业后，依赖图中是否出现循环
+///
+/// `edges` 为班级现有的 (作业, 前置作业) 关系；其中 `homework_id` 自身的旧关系会被忽略。
+/// 现有依赖图保持无环，因此只需检查能否从新的前置作业沿依赖回到 `homework_id`。
+fn creates_cycle(homework_id: i64, prerequisites: &[i64], edges: &[(i64, i64)]) -> bool {
+    let mut graph: HashMap<i64, Vec<i64>> = HashMap::new();
+    for &(from, to) in edges {
+        if from != homework_id {
+            graph.entry(from).or_default().push(to);
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut queue: VecDeque<i64> = prerequisites.iter().copied().collect();
+    while let Some(current) = queue.pop_front() {
+        if current == homework_id {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(next) = graph.get(&current) {
+            queue.extend(next.iter().copied());
+        }
+    }
+    false
+}
+
+pub async fn list_homework_prerequisites(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, can_manage) = match check_view_permission(service, request, homework_id).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    let prerequisites = match storage.list_homework_prerequisites(homework_id).await {
+        Ok(items) => items,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业前置条件失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 学生视角附带各前置条件的完成情况
+    let unmet: Option<HashSet<i64>> = if can_manage {
+        None
+    } else {
+        match storage
+            .list_unmet_prerequisites(user_id, &[homework_id])
+            .await
+        {
+            Ok(mut map) => Some(
+                map.remove(&homework_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect(),
+            ),
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询前置作业完成情况失败: {e}"),
+                    )),
+                );
+            }
+        }
+    };
+
+    let mut items = Vec::with_capacity(prerequisites.len());
+    for prerequisite in prerequisites {
+        let homework = match storage
+            .get_homework_by_id(prerequisite.prerequisite_id)
+            .await
+        {
+            Ok(Some(hw)) => hw,
+            Ok(None) => continue,
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询前置作业失败: {e}"),
+                    )),
+                );
+            }
+        };
+        let satisfied = unmet
+            .as_ref()
+            .map(|ids| !ids.contains(&prerequisite.prerequisite_id));
+        items.push(prerequisite_item(prerequisite, &homework, satisfied));
+    }
+
+    let locked = items.iter().any(|item| item.satisfied == Some(false));
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        HomeworkPrerequisiteListResponse {
+            homework_id,
+            items,
+            locked,
+        },
+        "查询成功",
+    )))
+}
+
+pub async fn replace_homework_prerequisites(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: ReplaceHomeworkPrerequisitesRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let homework =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok((_, homework)) => homework,
+            Err(resp) => return Ok(resp),
+        };
+
+    if let Err(message) = validate_prerequisites(homework_id, &req.prerequisites) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkPrerequisiteInvalid,
+            message,
+        )));
+    }
+
+    // 前置作业必须存在且属于同一班级
+    let mut targets = HashMap::new();
+    for item in &req.prerequisites {
+        match load_prerequisite_homework(&storage, &homework, item).await {
+            Ok(target) => {
+                targets.insert(target.id, target);
+            }
+            Err(resp) => return Ok(resp),
+        }
+    }
+
+    let edges = match storage
+        .list_class_prerequisite_edges(homework.class_id)
+        .await
+    {
+        Ok(edges) => edges,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询班级前置关系失败: {e}"),
+                )),
+            );
+        }
+    };
+    let new_ids: Vec<i64> = req.prerequisites.iter().map(|p| p.homework_id).collect();
+    if creates_cycle(homework_id, &new_ids, &edges) {
+        return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkPrerequisiteCycle,
+            "前置条件形成循环依赖",
+        )));
+    }
+
+    // 及格分数仅对 passed 生效
+    let prerequisites = req
+        .prerequisites
+        .into_iter()
+        .map(|p| HomeworkPrerequisiteInput {
+            min_score: p
+                .min_score
+                .filter(|_| p.requirement == PrerequisiteRequirement::Passed),
+            ..p
+        })
+        .collect();
+
+    match storage
+        .replace_homework_prerequisites(homework_id, prerequisites)
+        .await
+    {
+        Ok(saved) => {
+            let items = saved
+                .into_iter()
+                .filter_map(|p| {
+                    let target = targets.get(&p.prerequisite_id)?;
+                    Some(prerequisite_item(p, target, None))
+                })
+                .collect();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                HomeworkPrerequisiteListResponse {
+                    homework_id,
+                    items,
+                    locked: false,
+                },
+                "前置条件已更新",
+            )))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkUpdateFailed,
+                format!("保存作业前置条件失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 查询并校验前置作业：须存在、同班级，且及格分数不超过其满分
+async fn load_prerequisite_homework(
+    storage: &Arc<dyn Storage>,
+    homework: &Homework,
+    item: &HomeworkPrerequisiteInput,
+) -> Result<Homework, HttpResponse> {
+    let target = match storage.get_homework_by_id(item.homework_id).await {
+        Ok(Some(hw)) if hw.class_id == homework.class_id => hw,
+        Ok(_) => {
+            return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkPrerequisiteInvalid,
+                format!("前置作业 {} 不存在或不属于同一班级", item.homework_id),
+            )));
+        }
+        Err(e) => {
+            return Err(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询前置作业失败: {e}"),
+                )),
+            );
+        }
+    };
+    if item.requirement == PrerequisiteRequirement::Passed
+        && item.min_score.is_some_and(|score| score > target.max_score)
+    {
+        return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkPrerequisiteInvalid,
+            format!("前置作业「{}」的及格分数超过其满分", target.title),
+        )));
+    }
+    Ok(target)
+}
+
+fn prerequisite_item(
+    prerequisite: HomeworkPrerequisite,
+    homework: &Homework,
+    satisfied: Option<bool>,
+) -> HomeworkPrerequisiteItem {
+    let pass_score = (prerequisite.requirement == PrerequisiteRequirement::Passed)
+        .then(|| prerequisite.pass_score(homework.max_score));
+    HomeworkPrerequisiteItem {
+        prerequisite,
+        title: homework.title.clone(),
+        pass_score,
+        satisfied,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(homework_id: i64, min_score: Option<f64>) -> HomeworkPrerequisiteInput {
+        HomeworkPrerequisiteInput {
+            homework_id,
+            requirement: PrerequisiteRequirement::Passed,
+            min_score,
+        }
+    }
+
+    #[test]
+    fn test_validate_prerequisites() {
+        assert!(validate_prerequisites(1, &[]).is_ok());
+        assert!(validate_prerequisites(1, &[input(2, None), input(3, Some(80.0))]).is_ok());
+
+        // 以自身为前置
+        assert!(validate_prerequisites(1, &[input(1, None)]).is_err());
+        // 重复
+        assert!(validate_prerequisites(1, &[input(2, None), input(2, None)]).is_err());
+        assert!(validate_prerequisites(1, &[input(2, Some(-1.0))]).is_err());
+        assert!(validate_prerequisites(1, &[input(2, Some(f64::NAN))]).is_err());
+    }
+
+    #[test]
+    fn test_creates_cycle() {
+        // 3 -> 2 -> 1（箭头指向前置作业）
+        let edges = [(3, 2), (2, 1)];
+
+        assert!(!creates_cycle(4, &[3], &edges));
+        assert!(!creates_cycle(3, &[1], &edges));
+        assert!(creates_cycle(1, &[3], &edges));
+        assert!(creates_cycle(1, &[2], &edges));
+
+        // 作业自身的旧关系在替换时被忽略
+        assert!(!creates_cycle(2, &[], &edges));
+        assert!(!creates_cycle(3, &[1], &[(3, 2), (2, 1), (3, 1)]));
+        assert!(creates_cycle(2, &[3], &edges));
+    }
+}
@@ -0,0 +1,117 @@
+//! 作业评分量规
+//!
+//! 班级教师从自己可使用的量规中选择一个版本设置到作业上，作业保存该版本的快照；
+//! 之后修改或删除量规库中的量规都不会改变已设置的作业量规。班级成员均可查看。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use super::parts::check_view_permission;
+use crate::models::rubrics::requests::SetHomeworkRubricRequest;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::rubrics::{can_use_rubric, rubric_not_found};
+
+const DENIED_MESSAGE: &str = "只有班级教师可以设置作业量规";
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn homework_rubric_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::HomeworkRubricNotFound,
+        "该作业未设置评分量规",
+    ))
+}
+
+pub async fn get_homework_rubric(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_view_permission(service, request, homework_id).await {
+        return Ok(resp);
+    }
+
+    match storage.get_homework_rubric(homework_id).await {
+        Ok(Some(rubric)) => Ok(HttpResponse::Ok().json(ApiResponse::success(rubric, "查询成功"))),
+        Ok(None) => Ok(homework_rubric_not_found()),
+        Err(e) => Ok(internal_error(format!("查询作业量规失败: {e}"))),
+    }
+}
+
+pub async fn set_homework_rubric(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: SetHomeworkRubricRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, _) =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok(v) => v,
+            Err(resp) => return Ok(resp),
+        };
+
+    let rubric = match storage.get_rubric(req.rubric_id).await {
+        Ok(Some(rubric)) if can_use_rubric(request, user_id, &rubric) => rubric,
+        Ok(_) => return Ok(rubric_not_found()),
+        Err(e) => return Ok(internal_error(format!("查询量规失败: {e}"))),
+    };
+
+    // 指定历史版本时使用该版本的评分维度，标题取量规当前标题
+    let (version, criteria) = match req.version {
+        None => (rubric.version, rubric.criteria),
+        Some(version) => match storage.get_rubric_version(rubric.id, version).await {
+            Ok(Some(v)) => (v.version, v.criteria),
+            Ok(None) => {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::RubricNotFound,
+                    format!("量规版本 {version} 不存在"),
+                )));
+            }
+            Err(e) => return Ok(internal_error(format!("查询量规版本失败: {e}"))),
+        },
+    };
+
+    match storage
+        .set_homework_rubric(
+            homework_id,
+            rubric.id,
+            version,
+            rubric.title,
+            &criteria,
+            user_id,
+        )
+        .await
+    {
+        Ok(snapshot) => Ok(HttpResponse::Ok().json(ApiResponse::success(snapshot, "设置成功"))),
+        Err(e) => Ok(internal_error(format!("设置作业量规失败: {e}"))),
+    }
+}
+
+pub async fn delete_homework_rubric(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage.delete_homework_rubric(homework_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("已移除作业量规"))),
+        Ok(false) => Ok(homework_rubric_not_found()),
+        Err(e) => Ok(internal_error(format!("移除作业量规失败: {e}"))),
+    }
+}
@@ -0,0 +1,140 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use crate::models::homeworks::requests::CreateHomeworkShareLinkRequest;
+use crate::models::homeworks::responses::{HomeworkShareLinkItem, HomeworkShareLinkListResponse};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::random_code::generate_random_code;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理作业分享链接";
+
+/// 分享令牌长度
+const SHARE_TOKEN_LENGTH: usize = 32;
+
+/// 默认有效期（天）
+const DEFAULT_EXPIRY_DAYS: i64 = 7;
+
+/// 最长有效期（天）
+const MAX_EXPIRY_DAYS: i64 = 90;
+
+pub async fn list_homework_share_links(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage.list_homework_share_links(homework_id).await {
+        Ok(links) => {
+            let now = chrono::Utc::now();
+            let items = links
+                .into_iter()
+                .map(|link| HomeworkShareLinkItem {
+                    is_active: link.is_active(now),
+                    link,
+                })
+                .collect();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                HomeworkShareLinkListResponse { items },
+                "查询成功",
+            )))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询作业分享链接失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn create_homework_share_link(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: CreateHomeworkShareLinkRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, _) =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok(v) => v,
+            Err(resp) => return Ok(resp),
+        };
+
+    let now = chrono::Utc::now();
+    let expires_at = req
+        .expires_at
+        .unwrap_or(now + chrono::Duration::days(DEFAULT_EXPIRY_DAYS));
+
+    if expires_at <= now {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "过期时间必须晚于当前时间",
+        )));
+    }
+    if expires_at > now + chrono::Duration::days(MAX_EXPIRY_DAYS) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("分享链接有效期不能超过 {MAX_EXPIRY_DAYS} 天"),
+        )));
+    }
+
+    let token = generate_random_code(SHARE_TOKEN_LENGTH);
+
+    match storage
+        .create_homework_share_link(homework_id, &token, user_id, expires_at.timestamp())
+        .await
+    {
+        Ok(link) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            HomeworkShareLinkItem {
+                is_active: link.is_active(now),
+                link,
+            },
+            "分享链接已创建",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("创建作业分享链接失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn revoke_homework_share_link(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    link_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage
+        .revoke_homework_share_link(homework_id, link_id)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("分享链接已撤销"))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkShareLinkNotFound,
+            "分享链接不存在或已撤销",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("撤销作业分享链接失败: {e}"),
+            )),
+        ),
+    }
+}
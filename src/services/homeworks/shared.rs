@@ -0,0 +1,180 @@
+//! 通过公开分享链接只读访问作业
+//!
+//! 该入口无需登录，仅返回作业描述与附件，不包含任何提交、成绩或成员信息。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, http::header};
+use std::sync::Arc;
+
+use super::HomeworkService;
+use crate::middlewares::TenantGuard;
+use crate::models::homeworks::entities::{AttachmentKind, Homework, HomeworkShareLink};
+use crate::models::homeworks::responses::{SharedHomeworkAttachment, SharedHomeworkResponse};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::files::tiering::{read_error_response, read_stored_file};
+use crate::storage::Storage;
+
+fn link_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::HomeworkShareLinkNotFound,
+        "分享链接不存在或已失效",
+    ))
+}
+
+/// 解析分享令牌，返回有效的链接及其作业
+///
+/// 链接不存在、已撤销、已过期、作业已删除或所属组织已停用时统一返回 404，
+/// 避免泄露令牌是否曾经有效。
+async fn resolve_share_link(
+    storage: &Arc<dyn Storage>,
+    token: &str,
+) -> Result<(HomeworkShareLink, Homework, Option<String>), HttpResponse> {
+    let link = match storage.get_homework_share_link_by_token(token).await {
+        Ok(Some(link)) if link.is_active(chrono::Utc::now()) => link,
+        Ok(_) => return Err(link_not_found()),
+        Err(e) => {
+            return Err(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询分享链接失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let homework = match storage.get_homework_by_id(link.homework_id).await {
+        Ok(Some(hw)) => hw,
+        Ok(None) => return Err(link_not_found()),
+        Err(e) => {
+            return Err(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let class = storage
+        .get_class_by_id(homework.class_id)
+        .await
+        .ok()
+        .flatten();
+
+    if let Some(ref class) = class
+        && TenantGuard::ensure_org_active(storage, class.org_id)
+            .await
+            .is_err()
+    {
+        return Err(link_not_found());
+    }
+
+    Ok((link, homework, class.map(|c| c.name)))
+}
+
+/// 分享链接可见的附件：参考答案仅在已公开（未设置公开条件时为截止后）时可见
+async fn shared_file_ids(storage: &Arc<dyn Storage>, homework: &Homework) -> Vec<i64> {
+    let solution_unlocked = match storage.get_homework_solution(homework.id).await {
+        Ok(Some(solution)) => solution.revealed_at.is_some(),
+        Ok(None) => homework.solution_unlocked(chrono::Utc::now(), false),
+        Err(_) => false,
+    };
+    storage
+        .get_homework_attachments(homework.id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, kind)| *kind != AttachmentKind::Solution || solution_unlocked)
+        .map(|(file_id, _)| file_id)
+        .collect()
+}
+
+pub async fn get_shared_homework(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    token: &str,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (link, homework, class_name) = match resolve_share_link(&storage, token).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    let base_path = request.path().trim_end_matches('/');
+    let file_ids = shared_file_ids(&storage, &homework).await;
+    let mut attachments = Vec::new();
+    for file_id in file_ids {
+        if let Ok(Some(file)) = storage.get_file_by_id(file_id).await {
+            attachments.push(SharedHomeworkAttachment {
+                id: file.id,
+                original_name: file.original_name,
+                file_size: file.file_size,
+                file_type: file.file_type,
+                download_url: format!("{base_path}/files/{}", file.id),
+            });
+        }
+    }
+
+    let response = SharedHomeworkResponse {
+        title: homework.title,
+        description: homework.description,
+        max_score: homework.max_score,
+        deadline: homework.deadline,
+        allow_late: homework.allow_late,
+        class_name,
+        attachments,
+        expires_at: link.expires_at,
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(ApiResponse::success(response, "查询成功")))
+}
+
+pub async fn download_shared_file(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    token: &str,
+    file_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (_, homework, _) = match resolve_share_link(&storage, token).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    // 只允许下载该作业对外可见的附件
+    let file_ids = shared_file_ids(&storage, &homework).await;
+    let file = match storage.get_file_by_id(file_id).await {
+        Ok(Some(f)) if file_ids.contains(&f.id) => f,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::FileNotFound,
+                "文件不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询文件失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let data = match read_stored_file(&file).await {
+        Ok(data) => data,
+        Err(e) => return Ok(read_error_response(&file, e)),
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, file.file_type.as_str()))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file.original_name),
+        ))
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .body(data))
+}
@@ -0,0 +1,219 @@
+//! 作业参考答案
+//!
+//! 参考答案文本存放在 `homework_solutions` 表，文件通过 `solution` 类型的作业附件关联，
+//! 两者共用同一公开条件。首次满足公开条件时记录 `revealed_at` 并通知班级学生。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::HomeworkService;
+use super::exemptions::check_manage_permission;
+use crate::errors::Result;
+use crate::models::homeworks::entities::{Homework, HomeworkSolution, SolutionRevealPolicy};
+use crate::models::homeworks::requests::UpsertHomeworkSolutionRequest;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::{
+    get_class_student_ids, send_templated_notifications,
+};
+use crate::storage::Storage;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以管理参考答案";
+
+/// 参考答案文本最大长度（字符）
+const MAX_SOLUTION_LENGTH: usize = 100_000;
+
+/// 检查参考答案是否满足公开条件
+///
+/// 首次满足时标记为已公开并异步通知班级学生，返回当前是否对学生可见。
+pub async fn reveal_solution_if_due(
+    storage: &Arc<dyn Storage>,
+    homework: &Homework,
+    solution: &HomeworkSolution,
+) -> Result<bool> {
+    let all_graded = solution.revealed_at.is_none()
+        && solution.reveal_policy == SolutionRevealPolicy::AllGraded
+        && storage.is_homework_fully_graded(homework.id).await?;
+
+    if !solution.is_due(homework, chrono::Utc::now(), all_graded) {
+        return Ok(false);
+    }
+
+    if solution.revealed_at.is_none()
+        && storage.mark_homework_solution_revealed(homework.id).await?
+    {
+        notify_solution_published(storage.clone(), homework);
+    }
+
+    Ok(true)
+}
+
+/// 异步通知班级学生参考答案已公开
+fn notify_solution_published(storage: Arc<dyn Storage>, homework: &Homework) {
+    let homework_id = homework.id;
+    let class_id = homework.class_id;
+    let title = homework.title.clone();
+
+    executor::spawn(NOTIFICATIONS_QUEUE, async move {
+        let student_ids = get_class_student_ids(&storage, class_id).await;
+        send_templated_notifications(
+            storage,
+            student_ids,
+            NotificationType::SolutionPublished,
+            vec![("homework_title", title)],
+            Some(ReferenceType::Homework),
+            Some(homework_id),
+        )
+        .await;
+    });
+}
+
+/// 标记手动发布并立即公开，返回最新的参考答案
+async fn publish_and_reveal(
+    storage: &Arc<dyn Storage>,
+    homework: &Homework,
+) -> Result<Option<HomeworkSolution>> {
+    storage.publish_homework_solution(homework.id).await?;
+    let Some(solution) = storage.get_homework_solution(homework.id).await? else {
+        return Ok(None);
+    };
+    reveal_solution_if_due(storage, homework, &solution).await?;
+    storage.get_homework_solution(homework.id).await
+}
+
+fn solution_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::HomeworkSolutionNotFound,
+        "该作业未设置参考答案",
+    ))
+}
+
+pub async fn get_homework_solution(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage.get_homework_solution(homework_id).await {
+        Ok(Some(solution)) => {
+            Ok(HttpResponse::Ok().json(ApiResponse::success(solution, "查询成功")))
+        }
+        Ok(None) => Ok(solution_not_found()),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询参考答案失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn upsert_homework_solution(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+    req: UpsertHomeworkSolutionRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (user_id, homework) =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok(v) => v,
+            Err(resp) => return Ok(resp),
+        };
+
+    if req.content.trim().is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "参考答案内容不能为空",
+        )));
+    }
+    if req.content.chars().count() > MAX_SOLUTION_LENGTH {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("参考答案不能超过 {MAX_SOLUTION_LENGTH} 个字符"),
+        )));
+    }
+
+    let solution = match storage
+        .upsert_homework_solution(homework_id, &req.content, req.reveal_policy, user_id)
+        .await
+    {
+        Ok(solution) => solution,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("保存参考答案失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 截止后才设置的答案立即按条件公开，无需等待定时任务
+    if let Err(e) = reveal_solution_if_due(&storage, &homework, &solution).await {
+        tracing::warn!("Failed to reveal solution for homework {homework_id}: {e}");
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(solution, "参考答案已保存")))
+}
+
+pub async fn delete_homework_solution(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await
+    {
+        return Ok(resp);
+    }
+
+    match storage.delete_homework_solution(homework_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("参考答案已删除"))),
+        Ok(false) => Ok(solution_not_found()),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("删除参考答案失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 手动发布参考答案，无论公开条件如何立即对学生可见
+pub async fn publish_homework_solution(
+    service: &HomeworkService,
+    request: &HttpRequest,
+    homework_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (_, homework) =
+        match check_manage_permission(&storage, request, homework_id, DENIED_MESSAGE).await {
+            Ok(v) => v,
+            Err(resp) => return Ok(resp),
+        };
+
+    match publish_and_reveal(&storage, &homework).await {
+        Ok(Some(solution)) => {
+            Ok(HttpResponse::Ok().json(ApiResponse::success(solution, "参考答案已公开")))
+        }
+        Ok(None) => Ok(solution_not_found()),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("发布参考答案失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -0,0 +1,50 @@
+//! 参考答案定时公开任务
+//!
+//! 定期检查尚未公开的参考答案，满足公开条件时标记公开并通知学生。
+//! 学生查看作业详情时也会即时检查，此任务保证无人访问时通知也能按时发出。
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use super::solution::reveal_solution_if_due;
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 检查间隔，默认 300 秒，可通过系统设置 `jobs.solution_reveal_interval` 调整
+pub static CHECK_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(300));
+
+/// 启动参考答案定时公开任务
+pub fn spawn_solution_reveal_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        CHECK_INTERVAL.run(|| reveal_due_solutions(&storage)).await;
+    });
+}
+
+/// 公开所有已满足条件的参考答案
+async fn reveal_due_solutions(storage: &Arc<dyn Storage>) {
+    let solutions = match storage.list_pending_homework_solutions().await {
+        Ok(solutions) => solutions,
+        Err(e) => {
+            tracing::warn!("Failed to list pending homework solutions: {e}");
+            return;
+        }
+    };
+
+    for solution in solutions {
+        let homework = match storage.get_homework_by_id(solution.homework_id).await {
+            Ok(Some(homework)) => homework,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!("Failed to load homework {}: {e}", solution.homework_id);
+                continue;
+            }
+        };
+        if let Err(e) = reveal_solution_if_due(storage, &homework, &solution).await {
+            tracing::warn!(
+                "Failed to reveal solution for homework {}: {e}",
+                homework.id
+            );
+        }
+    }
+}
@@ -2,13 +2,18 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::collections::{HashMap, HashSet};
 
 use super::HomeworkService;
-use crate::middlewares::RequireJWT;
+use super::parts::latest_by_part;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::class_users::requests::ClassUserQuery;
 use crate::models::homeworks::stats_responses::{
-    HomeworkStatsResponse, ScoreRange, ScoreStats, UnsubmittedStudent,
+    ContentStats, HomeworkPartStats, HomeworkStatsResponse, LengthStats, ScoreRange, ScoreStats,
+    SelfAssessmentStats, UnsubmittedStudent,
 };
-use crate::models::submissions::requests::SubmissionListQuery;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::entities::{SubmissionSelfAssessment, SubmissionStatus};
+use crate::models::submissions::requests::{SelfAssessmentInput, SubmissionListQuery};
+use crate::models::submissions::responses::SubmissionListItem;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
 
@@ -57,26 +62,24 @@ pub async fn get_homework_stats(
     // Admin 直接放行，跳过班级成员检查
     if user_role != Some(UserRole::Admin) {
         // 非 Admin 用户需要验证班级成员资格
-        let class_user = match storage
-            .get_class_user_by_user_id_and_class_id(user_id, class_id)
-            .await
-        {
-            Ok(Some(cu)) => cu,
-            Ok(None) => {
-                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
-                    ErrorCode::ClassPermissionDenied,
-                    "您不是该班级成员",
-                )));
-            }
-            Err(e) => {
-                return Ok(
-                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                        ErrorCode::InternalServerError,
-                        format!("查询班级成员失败: {e}"),
-                    )),
-                );
-            }
-        };
+        let class_user =
+            match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+                Ok(Some(cu)) => cu,
+                Ok(None) => {
+                    return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                        ErrorCode::ClassPermissionDenied,
+                        "您不是该班级成员",
+                    )));
+                }
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询班级成员失败: {e}"),
+                        )),
+                    );
+                }
+            };
 
         // 验证是教师或课代表
         if class_user.role != ClassUserRole::Teacher
@@ -87,6 +90,12 @@ pub async fn get_homework_stats(
                 "只有教师或课代表可以查看统计",
             )));
         }
+    } else if !TenantGuard::can_access_class(request, &storage, class_id).await {
+        // 组织管理员只能访问本组织的班级
+        return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkNotFound,
+            "作业不存在",
+        )));
     }
 
     // 获取班级所有成员（不分页，获取全部）
@@ -112,11 +121,19 @@ pub async fn get_homework_stats(
         }
     };
 
-    // 统计需要提交作业的成员（排除教师）
+    // 被豁免的学生不计入统计
+    let exempted_ids: HashSet<i64> = storage
+        .get_exempted_user_ids(homework_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+
+    // 统计需要提交作业的成员（排除教师和被豁免学生）
     let students: Vec<_> = class_users_response
         .items
         .iter()
-        .filter(|cu| cu.role != ClassUserRole::Teacher)
+        .filter(|cu| cu.role != ClassUserRole::Teacher && !exempted_ids.contains(&cu.user_id))
         .collect();
     let total_students = students.len() as i64;
     let student_ids: HashSet<i64> = students.iter().map(|cu| cu.user_id).collect();
@@ -128,6 +145,7 @@ pub async fn get_homework_stats(
         size: Some(10000),
         status: None,
         creator_id: None,
+        tenant: TenantScope::All,
     };
 
     let submissions_response = match storage
@@ -145,43 +163,127 @@ pub async fn get_homework_stats(
         }
     };
 
-    // 只统计学生的提交，并为每个学生只保留最新版本
-    let mut latest_submissions: HashMap<
-        i64,
-        &crate::models::submissions::responses::SubmissionListItem,
-    > = HashMap::new();
-    for submission in &submissions_response.items {
-        if !student_ids.contains(&submission.creator_id) {
-            continue;
+    // 只统计学生的提交，并为每个学生（多部分作业按分题）只保留最新版本；
+    // 最新提交被退回待重交的视为未提交
+    let latest_submissions: HashMap<(i64, Option<i64>), &SubmissionListItem> =
+        latest_by_part(&submissions_response.items)
+            .into_iter()
+            .filter(|((creator_id, _), s)| {
+                student_ids.contains(creator_id) && s.status != SubmissionStatus::RETURNED
+            })
+            .collect();
+
+    // 获取所有最新提交的评分
+    let mut grades: HashMap<i64, f64> = HashMap::new();
+    let mut auto_assigned: HashSet<i64> = HashSet::new();
+    for submission in latest_submissions.values() {
+        if let Ok(Some(grade)) = storage.get_grade_by_submission_id(submission.id).await {
+            grades.insert(submission.id, grade.score);
+            if grade.auto_assigned {
+                auto_assigned.insert(submission.id);
+            }
         }
-        let entry = latest_submissions
+    }
+
+    // 按学生汇总：任一分题迟交即计为迟交，全部已提交分题均评分后计入成绩（分题得分之和）
+    let mut by_student: HashMap<i64, Vec<&SubmissionListItem>> = HashMap::new();
+    for submission in latest_submissions.values().copied() {
+        by_student
             .entry(submission.creator_id)
-            .or_insert(submission);
-        if submission.version > entry.version {
-            *entry = submission;
-        }
+            .or_default()
+            .push(submission);
     }
 
-    let student_submissions: Vec<_> = latest_submissions.values().collect();
+    // 缺交零分策略记录的零分计入成绩，但学生仍视为未提交
+    let auto_zero_students: HashSet<i64> = by_student
+        .iter()
+        .filter(|(_, items)| items.iter().all(|s| auto_assigned.contains(&s.id)))
+        .map(|(user_id, _)| *user_id)
+        .collect();
 
-    let submitted_count = student_submissions.len() as i64;
-    let late_count = student_submissions.iter().filter(|s| s.is_late).count() as i64;
+    let submitted_count = (by_student.len() - auto_zero_students.len()) as i64;
+    let late_count = by_student
+        .values()
+        .filter(|items| items.iter().any(|s| s.is_late))
+        .count() as i64;
 
     // 收集已提交学生的 ID
-    let submitted_student_ids: HashSet<i64> =
-        student_submissions.iter().map(|s| s.creator_id).collect();
+    let submitted_student_ids: HashSet<i64> = by_student
+        .keys()
+        .filter(|user_id| !auto_zero_students.contains(user_id))
+        .copied()
+        .collect();
 
-    // 获取所有提交的评分
     let mut graded_count = 0i64;
     let mut scores: Vec<f64> = Vec::new();
-
-    for submission in &student_submissions {
-        if let Ok(Some(grade)) = storage.get_grade_by_submission_id(submission.id).await {
-            graded_count += 1;
-            scores.push(grade.score);
+    for (user_id, items) in &by_student {
+        if items.iter().all(|s| grades.contains_key(&s.id)) {
+            if !auto_zero_students.contains(user_id) {
+                graded_count += 1;
+            }
+            scores.push(items.iter().map(|s| grades[&s.id]).sum());
         }
     }
 
+    // 分题统计
+    let parts = storage
+        .list_homework_parts(homework_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|part| {
+            let items: Vec<_> = latest_submissions
+                .values()
+                .filter(|s| s.part_id == Some(part.id))
+                .collect();
+            let part_scores: Vec<f64> = items
+                .iter()
+                .filter_map(|s| grades.get(&s.id).copied())
+                .collect();
+            HomeworkPartStats {
+                part_id: part.id,
+                title: part.title,
+                max_score: part.max_score,
+                submitted_count: items.len() as i64,
+                graded_count: part_scores.len() as i64,
+                late_count: items.iter().filter(|s| s.is_late).count() as i64,
+                average_score: (!part_scores.is_empty()).then(|| {
+                    let average = part_scores.iter().sum::<f64>() / part_scores.len() as f64;
+                    (average * 100.0).round() / 100.0
+                }),
+            }
+        })
+        .collect();
+
+    // 自评汇总：只取学生最新提交的自评
+    let latest_ids: HashSet<i64> = latest_submissions.values().map(|s| s.id).collect();
+    let assessments: Vec<SubmissionSelfAssessment> = storage
+        .list_homework_self_assessments(homework_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|a| latest_ids.contains(&a.submission_id))
+        .collect();
+    let self_assessment = summarize_self_assessments(&assessments);
+
+    // 正文长度汇总：每位学生取各分题最新提交之和
+    let mut word_counts = Vec::new();
+    let mut char_counts = Vec::new();
+    let mut attachment_counts = Vec::new();
+    for (_, items) in by_student
+        .iter()
+        .filter(|(user_id, _)| !auto_zero_students.contains(user_id))
+    {
+        word_counts.push(items.iter().map(|s| s.word_count as i64).sum());
+        char_counts.push(items.iter().map(|s| s.char_count as i64).sum());
+        attachment_counts.push(items.iter().map(|s| s.attachment_count as i64).sum());
+    }
+    let content_stats = (!word_counts.is_empty()).then(|| ContentStats {
+        word_count: length_stats(&word_counts),
+        char_count: length_stats(&char_counts),
+        attachment_count: length_stats(&attachment_counts),
+    });
+
     // 计算分数统计
     let score_stats = if !scores.is_empty() {
         let sum: f64 = scores.iter().sum();
@@ -217,7 +319,7 @@ pub async fn get_homework_stats(
             unsubmitted_students.push(UnsubmittedStudent {
                 id: user.id,
                 username: user.username,
-                display_name: user.display_name,
+                display_name: student.profile_name.clone().or(user.display_name),
                 avatar_url: user.avatar_url,
             });
         }
@@ -233,11 +335,55 @@ pub async fn get_homework_stats(
         score_stats,
         score_distribution,
         unsubmitted_students,
+        parts,
+        self_assessment,
+        content_stats,
     };
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
 }
 
+/// 汇总自评：平均把握程度、平均用时与把握程度分布
+fn summarize_self_assessments(
+    assessments: &[SubmissionSelfAssessment],
+) -> Option<SelfAssessmentStats> {
+    if assessments.is_empty() {
+        return None;
+    }
+
+    let count = assessments.len() as f64;
+    let average_confidence = assessments.iter().map(|a| a.confidence as f64).sum::<f64>() / count;
+    let average_time_spent_minutes = assessments
+        .iter()
+        .map(|a| a.time_spent_minutes as f64)
+        .sum::<f64>()
+        / count;
+
+    let mut confidence_distribution = vec![0i64; SelfAssessmentInput::MAX_CONFIDENCE as usize];
+    for assessment in assessments {
+        if let Some(slot) = confidence_distribution.get_mut((assessment.confidence - 1) as usize) {
+            *slot += 1;
+        }
+    }
+
+    Some(SelfAssessmentStats {
+        response_count: assessments.len() as i64,
+        average_confidence: (average_confidence * 100.0).round() / 100.0,
+        average_time_spent_minutes: (average_time_spent_minutes * 100.0).round() / 100.0,
+        confidence_distribution,
+    })
+}
+
+/// 计算平均（保留两位小数）、最大与最小值，调用方保证非空
+fn length_stats(values: &[i64]) -> LengthStats {
+    let average = values.iter().sum::<i64>() as f64 / values.len() as f64;
+    LengthStats {
+        average: (average * 100.0).round() / 100.0,
+        max: values.iter().copied().max().unwrap_or(0),
+        min: values.iter().copied().min().unwrap_or(0),
+    }
+}
+
 /// 计算分数分布
 fn calculate_score_distribution(scores: &[f64], max_score: f64) -> Vec<ScoreRange> {
     if max_score <= 0.0 {
@@ -7,9 +7,11 @@ use std::collections::{HashMap, HashSet};
 use tracing::error;
 
 use super::HomeworkService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::class_users::requests::ClassUserQuery;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::entities::SubmissionStatus;
 use crate::models::submissions::requests::SubmissionListQuery;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
@@ -68,29 +70,27 @@ pub async fn export_homework_stats(
     let user_role = RequireJWT::extract_user_role(request);
     let mut show_scores = true; // 默认显示分数
 
-    // Admin 直接放行，跳过班级成员检查
+    // Admin 只校验组织，跳过班级成员检查
     if user_role != Some(UserRole::Admin) {
         // 非 Admin 用户需要验证班级成员资格
-        let class_user = match storage
-            .get_class_user_by_user_id_and_class_id(user_id, class_id)
-            .await
-        {
-            Ok(Some(cu)) => cu,
-            Ok(None) => {
-                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
-                    ErrorCode::ClassPermissionDenied,
-                    "您不是该班级成员",
-                )));
-            }
-            Err(e) => {
-                return Ok(
-                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                        ErrorCode::InternalServerError,
-                        format!("查询班级成员失败: {e}"),
-                    )),
-                );
-            }
-        };
+        let class_user =
+            match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+                Ok(Some(cu)) => cu,
+                Ok(None) => {
+                    return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                        ErrorCode::ClassPermissionDenied,
+                        "您不是该班级成员",
+                    )));
+                }
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询班级成员失败: {e}"),
+                        )),
+                    );
+                }
+            };
 
         // 验证是教师或课代表
         if class_user.role != ClassUserRole::Teacher
@@ -106,6 +106,12 @@ pub async fn export_homework_stats(
         if class_user.role == ClassUserRole::ClassRepresentative {
             show_scores = false;
         }
+    } else if !TenantGuard::can_access_class(request, &storage, class_id).await {
+        // 组织管理员只能访问本组织的班级
+        return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::HomeworkNotFound,
+            "作业不存在",
+        )));
     }
 
     // 获取班级所有成员（不分页，获取全部）
@@ -131,11 +137,19 @@ pub async fn export_homework_stats(
         }
     };
 
-    // 统计需要提交作业的成员（排除教师）
+    // 被豁免的学生不计入统计
+    let exempted_ids: HashSet<i64> = storage
+        .get_exempted_user_ids(homework_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+
+    // 统计需要提交作业的成员（排除教师和被豁免学生）
     let students: Vec<_> = class_users_response
         .items
         .iter()
-        .filter(|cu| cu.role != ClassUserRole::Teacher)
+        .filter(|cu| cu.role != ClassUserRole::Teacher && !exempted_ids.contains(&cu.user_id))
         .collect();
     let total_students = students.len() as i64;
     let student_ids: HashSet<i64> = students.iter().map(|cu| cu.user_id).collect();
@@ -147,6 +161,7 @@ pub async fn export_homework_stats(
         size: Some(10000),
         status: None,
         creator_id: None,
+        tenant: TenantScope::All,
     };
 
     let submissions_response = match storage
@@ -181,6 +196,9 @@ pub async fn export_homework_stats(
         }
     }
 
+    // 最新提交被退回待重交的视为未提交
+    latest_submissions.retain(|_, s| s.status != SubmissionStatus::RETURNED);
+
     let student_submissions: Vec<_> = latest_submissions.values().collect();
     let submitted_count = student_submissions.len() as i64;
     let late_count = student_submissions.iter().filter(|s| s.is_late).count() as i64;
@@ -237,7 +255,11 @@ pub async fn export_homework_stats(
             };
 
             student_details.push(StudentDetail {
-                display_name: user.display_name.unwrap_or_else(|| user.username.clone()),
+                display_name: student
+                    .profile_name
+                    .clone()
+                    .or(user.display_name)
+                    .unwrap_or_else(|| user.username.clone()),
                 username: user.username,
                 submitted,
                 score,
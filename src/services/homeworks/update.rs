@@ -1,12 +1,17 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::HomeworkService;
-use crate::middlewares::RequireJWT;
-use crate::models::homeworks::requests::UpdateHomeworkRequest;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::homeworks::requests::{
+    UpdateHomeworkRequest, validate_grace_minutes, validate_workload_estimate,
+};
 use crate::models::notifications::entities::{NotificationType, ReferenceType};
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
-use crate::services::notifications::trigger::{get_class_student_ids, send_notifications};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::{
+    get_class_student_ids, send_templated_notifications,
+};
 
 pub async fn update_homework(
     service: &HomeworkService,
@@ -39,7 +44,15 @@ pub async fn update_homework(
 
     // 权限检查：只有作业创建者或管理员才能更新
     match user_role {
-        Some(UserRole::Admin) => {} // 管理员可以更新任何作业
+        // 管理员可以更新本组织的任何作业
+        Some(UserRole::Admin) => {
+            if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::HomeworkNotFound,
+                    "作业不存在",
+                )));
+            }
+        }
         Some(UserRole::Teacher) => {
             if homework.created_by != user_id {
                 return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
@@ -56,6 +69,21 @@ pub async fn update_homework(
         }
     }
 
+    if req.max_content_length.is_some_and(|len| len < 0) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "内容长度限制不能为负数",
+        )));
+    }
+    if let Err(message) = validate_workload_estimate(req.estimated_minutes, req.difficulty) {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
+    if let Err(message) = validate_grace_minutes(req.grace_minutes) {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
+
     match storage.update_homework(homework_id, req, user_id).await {
         Ok(Some(updated_homework)) => {
             // 异步发送通知给班级学生
@@ -64,14 +92,22 @@ pub async fn update_homework(
             let class_id = homework.class_id;
             let title = updated_homework.title.clone();
 
-            tokio::spawn(async move {
-                let student_ids = get_class_student_ids(&storage_clone, class_id).await;
-                send_notifications(
+            executor::spawn(NOTIFICATIONS_QUEUE, async move {
+                // 被豁免的学生不接收作业更新通知
+                let exempted_ids = storage_clone
+                    .get_exempted_user_ids(hw_id)
+                    .await
+                    .unwrap_or_default();
+                let student_ids: Vec<i64> = get_class_student_ids(&storage_clone, class_id)
+                    .await
+                    .into_iter()
+                    .filter(|id| !exempted_ids.contains(id))
+                    .collect();
+                send_templated_notifications(
                     storage_clone,
                     student_ids,
                     NotificationType::HomeworkUpdated,
-                    format!("作业更新：{}", title),
-                    Some(format!("作业「{}」已更新，请查看最新内容", title)),
+                    vec![("homework_title", title)],
                     Some(ReferenceType::Homework),
                     Some(hw_id),
                 )
@@ -0,0 +1,49 @@
+//! 班级 IM 机器人消息投递
+//!
+//! 班级事件先按渠道模板渲染后写入 `im_deliveries` 队列，由后台任务统一投递，
+//! 失败时按指数退避重试，超过 `im.max_attempts` 后标记为失败。
+
+pub mod providers;
+pub mod templates;
+pub mod worker;
+
+use std::sync::Arc;
+
+use crate::errors::Result;
+use crate::models::classes::entities::{ImEvent, NewImDelivery};
+use crate::models::homeworks::entities::Homework;
+use crate::storage::Storage;
+
+pub use worker::spawn_im_delivery_worker;
+
+/// 将作业事件写入订阅该事件的所有渠道的投递队列，返回新增的消息数
+pub async fn enqueue_homework_event(
+    storage: &Arc<dyn Storage>,
+    event: ImEvent,
+    homework: &Homework,
+) -> Result<u64> {
+    let channels = storage
+        .list_im_channels_for_event(homework.class_id, event)
+        .await?;
+    if channels.is_empty() {
+        return Ok(0);
+    }
+
+    let class_name = storage
+        .get_class_by_id(homework.class_id)
+        .await?
+        .map(|class| class.name)
+        .unwrap_or_default();
+
+    let deliveries = channels
+        .iter()
+        .map(|channel| NewImDelivery {
+            channel_id: channel.id,
+            event,
+            reference_id: homework.id,
+            message: templates::render_homework_event(channel, event, &class_name, homework),
+        })
+        .collect();
+
+    storage.enqueue_im_deliveries(deliveries).await
+}
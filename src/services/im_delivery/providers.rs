@@ -0,0 +1,133 @@
+//! IM 机器人接口调用
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha2::Sha256;
+
+use crate::models::classes::entities::{ClassImChannel, ImProvider};
+use crate::services::outbound::OutboundClient;
+
+const TELEGRAM_API: &str = "https://api.telegram.org";
+
+/// 发送一条文本消息，失败时返回错误描述
+pub async fn send_message(
+    client: &OutboundClient,
+    channel: &ClassImChannel,
+    message: &str,
+) -> Result<(), String> {
+    let (url, body) = match channel.provider {
+        ImProvider::Wecom => (
+            channel.target.clone(),
+            json!({ "msgtype": "text", "text": { "content": message } }),
+        ),
+        ImProvider::Dingtalk => (
+            dingtalk_url(&channel.target, channel.secret.as_deref()),
+            json!({ "msgtype": "text", "text": { "content": message } }),
+        ),
+        ImProvider::Telegram => {
+            let token = channel.secret.as_deref().ok_or("缺少 Telegram Bot Token")?;
+            (
+                format!("{TELEGRAM_API}/bot{token}/sendMessage"),
+                json!({ "chat_id": channel.target, "text": message }),
+            )
+        }
+    };
+
+    let response = client
+        .send(|client| client.post(&url).json(&body))
+        .await
+        .map_err(|e| format!("请求失败: {e}"))?;
+    let status = response.status();
+    let payload: Value = response.json().await.unwrap_or(Value::Null);
+
+    check_response(channel.provider, status.as_u16(), &payload)
+}
+
+/// 钉钉加签：在 Webhook 地址后附加 timestamp 和 sign 参数
+fn dingtalk_url(webhook: &str, secret: Option<&str>) -> String {
+    let Some(secret) = secret else {
+        return webhook.to_string();
+    };
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let sign = dingtalk_sign(timestamp, secret);
+    let separator = if webhook.contains('?') { '&' } else { '?' };
+    format!("{webhook}{separator}timestamp={timestamp}&sign={sign}")
+}
+
+fn dingtalk_sign(timestamp: i64, secret: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{timestamp}\n{secret}").as_bytes());
+    let signature = base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+    // base64 中的 + / = 需要 URL 编码
+    signature
+        .replace('+', "%2B")
+        .replace('/', "%2F")
+        .replace('=', "%3D")
+}
+
+/// 企业微信/钉钉通过 errcode 表示结果，Telegram 通过 ok 字段
+fn check_response(provider: ImProvider, status: u16, payload: &Value) -> Result<(), String> {
+    match provider {
+        ImProvider::Wecom | ImProvider::Dingtalk => match payload["errcode"].as_i64() {
+            Some(0) => Ok(()),
+            Some(code) => Err(format!(
+                "errcode {code}: {}",
+                payload["errmsg"].as_str().unwrap_or_default()
+            )),
+            None => Err(format!("HTTP {status}")),
+        },
+        ImProvider::Telegram => {
+            if payload["ok"].as_bool() == Some(true) {
+                Ok(())
+            } else {
+                Err(payload["description"]
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("HTTP {status}")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_response() {
+        assert!(check_response(ImProvider::Wecom, 200, &json!({"errcode": 0})).is_ok());
+        assert_eq!(
+            check_response(
+                ImProvider::Dingtalk,
+                200,
+                &json!({"errcode": 310000, "errmsg": "sign not match"})
+            ),
+            Err("errcode 310000: sign not match".to_string())
+        );
+        assert_eq!(
+            check_response(ImProvider::Wecom, 502, &Value::Null),
+            Err("HTTP 502".to_string())
+        );
+        assert!(check_response(ImProvider::Telegram, 200, &json!({"ok": true})).is_ok());
+        assert_eq!(
+            check_response(
+                ImProvider::Telegram,
+                400,
+                &json!({"ok": false, "description": "Bad Request: chat not found"})
+            ),
+            Err("Bad Request: chat not found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dingtalk_url() {
+        let webhook = "https://oapi.dingtalk.com/robot/send?access_token=abc";
+        assert_eq!(dingtalk_url(webhook, None), webhook);
+        let signed = dingtalk_url(webhook, Some("SECxyz"));
+        assert!(signed.starts_with(&format!("{webhook}&timestamp=")));
+        assert!(signed.contains("&sign="));
+        assert!(!dingtalk_sign(1_700_000_000_000, "SECxyz").contains(['+', '/', '=']));
+    }
+}
@@ -0,0 +1,96 @@
+//! IM 消息模板
+//!
+//! 模板中的 `{name}` 占位符会被替换，未知占位符原样保留。
+
+use crate::models::classes::entities::{ClassImChannel, ImEvent};
+use crate::models::homeworks::entities::Homework;
+
+/// 可用的占位符
+pub const PLACEHOLDERS: &[&str] = &[
+    "class_name",
+    "title",
+    "deadline",
+    "max_score",
+    "description",
+];
+
+/// 模板最大长度（字符）
+pub const MAX_TEMPLATE_LENGTH: usize = 2000;
+
+/// 事件的默认模板
+pub fn default_template(event: ImEvent) -> &'static str {
+    match event {
+        ImEvent::HomeworkCreated => "【{class_name}】新作业发布：{title}\n截止时间：{deadline}",
+        ImEvent::HomeworkDeadline => {
+            "【{class_name}】作业即将截止：{title}\n截止时间：{deadline}，请尚未提交的同学抓紧完成"
+        }
+    }
+}
+
+/// 替换模板中的占位符
+pub fn render(template: &str, vars: &[(&str, String)]) -> String {
+    let mut output = template.to_string();
+    for (name, value) in vars {
+        output = output.replace(&format!("{{{name}}}"), value);
+    }
+    output
+}
+
+/// 按渠道模板（未设置时使用默认模板）渲染作业事件消息
+pub fn render_homework_event(
+    channel: &ClassImChannel,
+    event: ImEvent,
+    class_name: &str,
+    homework: &Homework,
+) -> String {
+    let template = channel
+        .templates
+        .get(&event)
+        .map(String::as_str)
+        .unwrap_or_else(|| default_template(event));
+
+    let deadline = homework
+        .deadline
+        .map(|deadline| deadline.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| "无".to_string());
+
+    render(
+        template,
+        &[
+            ("class_name", class_name.to_string()),
+            ("title", homework.title.clone()),
+            ("deadline", deadline),
+            ("max_score", homework.max_score.to_string()),
+            (
+                "description",
+                homework.description.clone().unwrap_or_default(),
+            ),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let vars = [
+            ("title", "链表".to_string()),
+            ("class_name", "一班".to_string()),
+        ];
+        assert_eq!(render("【{class_name}】{title}", &vars), "【一班】链表");
+        assert_eq!(render("{unknown} {title}", &vars), "{unknown} 链表");
+    }
+
+    #[test]
+    fn test_default_templates_use_known_placeholders() {
+        for event in [ImEvent::HomeworkCreated, ImEvent::HomeworkDeadline] {
+            let vars: Vec<(&str, String)> = PLACEHOLDERS
+                .iter()
+                .map(|name| (*name, String::new()))
+                .collect();
+            assert!(!render(default_template(event), &vars).contains('{'));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! IM 消息投递与截止提醒后台任务
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+use super::{enqueue_homework_event, providers};
+use crate::config::AppConfig;
+use crate::models::classes::entities::{ImDelivery, ImEvent};
+use crate::services::outbound::{IM_CLIENT, OutboundTarget, is_circuit_open};
+use crate::storage::Storage;
+use crate::utils::LiveInterval;
+
+/// 投递队列轮询间隔
+const DELIVERY_INTERVAL: Duration = Duration::from_secs(10);
+/// 每轮最多投递的消息数
+const DELIVERY_BATCH_SIZE: u64 = 50;
+/// 截止提醒扫描间隔，默认 300 秒，可通过系统设置 `jobs.im_reminder_interval` 调整
+pub static REMINDER_INTERVAL: Lazy<LiveInterval> = Lazy::new(|| LiveInterval::new(300));
+/// 首次重试等待时间（秒），之后每次翻倍
+const RETRY_BASE_SECS: i64 = 60;
+/// 最长重试等待时间（秒）
+const RETRY_MAX_SECS: i64 = 3600;
+
+/// 启动 IM 消息投递任务和截止提醒扫描任务
+pub fn spawn_im_delivery_worker(storage: Arc<dyn Storage>) {
+    let delivery_storage = storage.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DELIVERY_INTERVAL);
+        loop {
+            interval.tick().await;
+            deliver_due_messages(&delivery_storage).await;
+        }
+    });
+
+    tokio::spawn(async move {
+        REMINDER_INTERVAL
+            .run(|| enqueue_deadline_reminders(&storage))
+            .await;
+    });
+}
+
+/// 第 `attempts` 次失败后的重试等待时间
+fn retry_delay_secs(attempts: i32) -> i64 {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    (RETRY_BASE_SECS << exponent).min(RETRY_MAX_SECS)
+}
+
+async fn deliver_due_messages(storage: &Arc<dyn Storage>) {
+    // 熔断期间不消耗投递次数，等冷却结束后再投递
+    if is_circuit_open(OutboundTarget::Im) {
+        return;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let deliveries = match storage
+        .list_due_im_deliveries(now, DELIVERY_BATCH_SIZE)
+        .await
+    {
+        Ok(deliveries) => deliveries,
+        Err(e) => {
+            tracing::warn!("Failed to list due IM deliveries: {e}");
+            return;
+        }
+    };
+
+    for delivery in deliveries {
+        deliver(storage, &delivery).await;
+    }
+}
+
+async fn deliver(storage: &Arc<dyn Storage>, delivery: &ImDelivery) {
+    let channel = match storage.get_im_channel_by_id(delivery.channel_id).await {
+        Ok(Some(channel)) => channel,
+        // 渠道已删除时投递记录随之级联删除
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to load IM channel {}: {e}", delivery.channel_id);
+            return;
+        }
+    };
+
+    let result = if channel.enabled {
+        providers::send_message(&IM_CLIENT, &channel, &delivery.message).await
+    } else {
+        Err("渠道已停用".to_string())
+    };
+
+    let update = match result {
+        Ok(()) => storage.mark_im_delivery_delivered(delivery).await,
+        Err(error) => {
+            let attempts = delivery.attempts + 1;
+            let retry_at = (channel.enabled && attempts < AppConfig::get().im.max_attempts)
+                .then(|| chrono::Utc::now().timestamp() + retry_delay_secs(attempts));
+            tracing::warn!(
+                "IM delivery {} to channel {} failed (attempt {attempts}): {error}",
+                delivery.id,
+                channel.id
+            );
+            storage
+                .mark_im_delivery_failed(delivery, &error, retry_at)
+                .await
+        }
+    };
+    if let Err(e) = update {
+        tracing::warn!("Failed to update IM delivery {}: {e}", delivery.id);
+    }
+}
+
+/// 为即将截止的作业写入提醒，队列唯一约束保证每个渠道只提醒一次
+async fn enqueue_deadline_reminders(storage: &Arc<dyn Storage>) {
+    let now = chrono::Utc::now();
+    let until = now + chrono::Duration::hours(AppConfig::get().im.deadline_reminder_hours);
+    let homeworks = match storage
+        .list_homeworks_due_between(now.timestamp(), until.timestamp())
+        .await
+    {
+        Ok(homeworks) => homeworks,
+        Err(e) => {
+            tracing::warn!("Failed to list homeworks due soon: {e}");
+            return;
+        }
+    };
+
+    for homework in homeworks {
+        if let Err(e) = enqueue_homework_event(storage, ImEvent::HomeworkDeadline, &homework).await
+        {
+            tracing::warn!(
+                "Failed to enqueue deadline reminder for homework {}: {e}",
+                homework.id
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay_secs(1), 60);
+        assert_eq!(retry_delay_secs(2), 120);
+        assert_eq!(retry_delay_secs(3), 240);
+        assert_eq!(retry_delay_secs(20), RETRY_MAX_SECS);
+    }
+}
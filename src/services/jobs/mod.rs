@@ -0,0 +1,215 @@
+//! 后台任务队列
+//!
+//! 用于耗时较长的导出类任务（如成绩单 PDF）。任务在进程内的 `exports` 队列中执行，
+//! 并发数与积压上限见 [`crate::runtime::executor`]；任务状态与结果保存在内存缓存中，过期后自动清理。
+//! 服务重启后未完成的任务会丢失，客户端需重新发起请求。
+//!
+//! 由其他后台流程执行的工作（如发件箱投递的通知扩散）可通过 [`register_job`] 登记，
+//! 只跟踪状态、不产出文件。
+
+pub mod status;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::jobs::entities::{JobInfo, JobStatus};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, EXPORTS_QUEUE, QueueFull};
+
+/// 任务状态与结果的保留时间
+const JOB_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 任务产出的文件
+pub struct JobOutput {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+struct JobRecord {
+    owner_id: i64,
+    info: JobInfo,
+    output: Option<Arc<JobOutput>>,
+}
+
+/// 任务记录缓存
+/// 键: 任务ID
+static JOBS: Lazy<Cache<String, JobRecord>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(JOB_TTL)
+        .max_capacity(10_000)
+        .build()
+});
+
+/// 提交后台任务，立即返回任务信息；导出队列繁忙时返回 [`QueueFull`]
+pub async fn submit_job<F>(owner_id: i64, kind: &str, task: F) -> Result<JobInfo, QueueFull>
+where
+    F: Future<Output = Result<JobOutput, String>> + Send + 'static,
+{
+    let info = new_job_info(kind);
+    let job_id = info.id.clone();
+    JOBS.insert(
+        job_id.clone(),
+        JobRecord {
+            owner_id,
+            info: info.clone(),
+            output: None,
+        },
+    )
+    .await;
+
+    let submitted = executor::queue(EXPORTS_QUEUE)
+        .submit({
+            let job_id = job_id.clone();
+            async move {
+                update_job(&job_id, |record| record.info.status = JobStatus::Running).await;
+
+                let result = task.await;
+                update_job(&job_id, move |record| {
+                    record.info.finished_at = Some(chrono::Utc::now());
+                    match result {
+                        Ok(output) => {
+                            record.info.status = JobStatus::Completed;
+                            record.output = Some(Arc::new(output));
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                "Job {} ({}) failed: {e}",
+                                record.info.id,
+                                record.info.kind
+                            );
+                            record.info.status = JobStatus::Failed;
+                            record.info.error = Some(e);
+                        }
+                    }
+                })
+                .await;
+            }
+        })
+        .await;
+    if let Err(e) = submitted {
+        JOBS.invalidate(&job_id).await;
+        return Err(e);
+    }
+
+    Ok(info)
+}
+
+fn new_job_info(kind: &str) -> JobInfo {
+    JobInfo {
+        id: Uuid::new_v4().simple().to_string(),
+        kind: kind.to_string(),
+        status: JobStatus::Pending,
+        error: None,
+        download_url: None,
+        created_at: chrono::Utc::now(),
+        finished_at: None,
+    }
+}
+
+/// 登记由其他后台流程执行的任务，执行方通过 [`mark_job_running`] 与 [`finish_job`] 更新状态
+pub async fn register_job(owner_id: i64, kind: &str) -> JobInfo {
+    let info = new_job_info(kind);
+    JOBS.insert(
+        info.id.clone(),
+        JobRecord {
+            owner_id,
+            info: info.clone(),
+            output: None,
+        },
+    )
+    .await;
+    info
+}
+
+/// 标记登记的任务开始执行（已结束的任务不受影响）
+pub async fn mark_job_running(job_id: &str) {
+    update_job(job_id, |record| {
+        if !record.info.status.is_finished() {
+            record.info.status = JobStatus::Running;
+        }
+    })
+    .await;
+}
+
+/// 结束登记的任务
+pub async fn finish_job(job_id: &str, result: Result<(), String>) {
+    update_job(job_id, move |record| {
+        record.info.finished_at = Some(chrono::Utc::now());
+        match result {
+            Ok(()) => record.info.status = JobStatus::Completed,
+            Err(e) => {
+                record.info.status = JobStatus::Failed;
+                record.info.error = Some(e);
+            }
+        }
+    })
+    .await;
+}
+
+/// 丢弃登记的任务（发起方的业务写入失败时调用）
+pub async fn discard_job(job_id: &str) {
+    JOBS.invalidate(job_id).await;
+}
+
+async fn update_job(job_id: &str, f: impl FnOnce(&mut JobRecord)) {
+    if let Some(mut record) = JOBS.get(job_id).await {
+        f(&mut record);
+        JOBS.insert(job_id.to_string(), record).await;
+    }
+}
+
+/// 导出队列繁忙时的响应
+pub fn queue_full_response(e: QueueFull) -> HttpResponse {
+    HttpResponse::ServiceUnavailable()
+        .insert_header((actix_web::http::header::RETRY_AFTER, "30"))
+        .json(ApiResponse::error_empty(
+            ErrorCode::JobQueueFull,
+            e.to_string(),
+        ))
+}
+
+/// 获取属于 owner_id 的任务（不属于该用户时视为不存在）
+async fn get_owned_job(job_id: &str, owner_id: i64) -> Option<JobRecord> {
+    JOBS.get(job_id)
+        .await
+        .filter(|record| record.owner_id == owner_id)
+}
+
+/// 填充任务结果的下载地址
+///
+/// `api_prefix` 为当前请求的 API 版本前缀（如 `/api/v1`）。
+pub fn with_download_url(mut info: JobInfo, api_prefix: &str) -> JobInfo {
+    if info.status == JobStatus::Completed {
+        info.download_url = Some(format!("{api_prefix}/jobs/{}/download", info.id));
+    }
+    info
+}
+
+pub struct JobService;
+
+impl JobService {
+    pub fn new_lazy() -> Self {
+        Self
+    }
+
+    // 查询任务状态
+    pub async fn get_job(&self, request: &HttpRequest, job_id: &str) -> ActixResult<HttpResponse> {
+        status::get_job(request, job_id).await
+    }
+
+    // 下载任务结果
+    pub async fn download_job(
+        &self,
+        request: &HttpRequest,
+        job_id: &str,
+    ) -> ActixResult<HttpResponse> {
+        status::download_job(request, job_id).await
+    }
+}
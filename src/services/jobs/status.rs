@@ -0,0 +1,84 @@
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{get_owned_job, with_download_url};
+use crate::middlewares::RequireJWT;
+use crate::models::jobs::entities::JobStatus;
+use crate::models::{ApiResponse, ErrorCode};
+
+fn job_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::JobNotFound,
+        "任务不存在或已过期",
+    ))
+}
+
+pub async fn get_job(request: &HttpRequest, job_id: &str) -> ActixResult<HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let Some(record) = get_owned_job(job_id, user_id).await else {
+        return Ok(job_not_found());
+    };
+
+    let api_prefix = request
+        .path()
+        .strip_suffix(&format!("/jobs/{job_id}"))
+        .unwrap_or_default();
+
+    // 登记的状态跟踪任务没有可下载的结果
+    let info = if record.output.is_some() {
+        with_download_url(record.info, api_prefix)
+    } else {
+        record.info
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(info, "查询成功")))
+}
+
+pub async fn download_job(request: &HttpRequest, job_id: &str) -> ActixResult<HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let Some(record) = get_owned_job(job_id, user_id).await else {
+        return Ok(job_not_found());
+    };
+
+    let output = match (record.info.status, record.output) {
+        (JobStatus::Completed, Some(output)) => output,
+        (JobStatus::Completed, None) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::JobNotReady,
+                "该任务没有可下载的文件",
+            )));
+        }
+        (JobStatus::Failed, _) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::JobNotReady,
+                format!("任务执行失败: {}", record.info.error.unwrap_or_default()),
+            )));
+        }
+        _ => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::JobNotReady,
+                "任务尚未完成",
+            )));
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, output.content_type))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", output.file_name),
+        ))
+        .body(output.data.clone()))
+}
@@ -0,0 +1,162 @@
+//! 私信会话查询与已读标记
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::MessageService;
+use super::send::{load_class, member_role};
+use crate::middlewares::RequireJWT;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::messages::requests::{ConversationListQuery, ConversationMessagesQuery};
+use crate::models::messages::responses::{
+    ConversationMessagesResponse, MarkConversationReadResponse, MessagePeer,
+    UnreadMessageCountResponse,
+};
+use crate::models::{ApiResponse, ErrorCode};
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+        ErrorCode::Unauthorized,
+        "无法获取用户信息",
+    ))
+}
+
+/// 校验当前用户是班级成员，并查询会话对方信息
+async fn check_conversation(
+    service: &MessageService,
+    request: &HttpRequest,
+    class_id: i64,
+    peer_id: i64,
+) -> Result<(i64, MessagePeer), HttpResponse> {
+    let storage = service.get_storage(request);
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(unauthorized)?;
+
+    let class = load_class(&storage, request, class_id).await?;
+    if member_role(&storage, request, &class, user_id)
+        .await?
+        .is_none()
+    {
+        return Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "您不是该班级成员",
+        )));
+    }
+
+    match storage.get_user_by_id(peer_id).await {
+        Ok(Some(user)) => Ok((
+            user_id,
+            MessagePeer {
+                id: user.id,
+                username: user.username,
+                display_name: user.display_name,
+                avatar_url: user.avatar_url,
+            },
+        )),
+        Ok(None) => Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::UserNotFound,
+            "用户不存在",
+        ))),
+        Err(e) => Err(internal_error(format!("查询用户失败: {e}"))),
+    }
+}
+
+pub async fn list_conversations(
+    service: &MessageService,
+    request: &HttpRequest,
+    query: ConversationListQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    match storage.list_conversations(user_id, query.class_id).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功"))),
+        Err(e) => Ok(internal_error(format!("查询会话失败: {e}"))),
+    }
+}
+
+pub async fn list_conversation_messages(
+    service: &MessageService,
+    request: &HttpRequest,
+    class_id: i64,
+    peer_id: i64,
+    query: ConversationMessagesQuery,
+) -> ActixResult<HttpResponse> {
+    let policy = PaginationPolicy::for_endpoint("conversation_messages");
+    if let Err(limit) = policy.check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let storage = service.get_storage(request);
+    let (user_id, peer) = match check_conversation(service, request, class_id, peer_id).await {
+        Ok(v) => v,
+        Err(resp) => return Ok(resp),
+    };
+
+    // 游标分页不使用页码
+    let (_, size) = policy.resolve(None, query.size);
+    match storage
+        .list_conversation_messages(class_id, user_id, peer_id, query.before_id, size)
+        .await
+    {
+        Ok((items, has_more)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ConversationMessagesResponse {
+                class_id,
+                peer,
+                items,
+                has_more,
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询会话消息失败: {e}"))),
+    }
+}
+
+pub async fn mark_conversation_read(
+    service: &MessageService,
+    request: &HttpRequest,
+    class_id: i64,
+    peer_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+    let user_id = match check_conversation(service, request, class_id, peer_id).await {
+        Ok((user_id, _)) => user_id,
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage
+        .mark_conversation_read(class_id, user_id, peer_id)
+        .await
+    {
+        Ok(marked) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            MarkConversationReadResponse { marked },
+            "已标记为已读",
+        ))),
+        Err(e) => Ok(internal_error(format!("标记私信已读失败: {e}"))),
+    }
+}
+
+pub async fn get_unread_count(
+    service: &MessageService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    match storage.count_unread_messages(user_id).await {
+        Ok(unread_count) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            UnreadMessageCountResponse { unread_count },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("统计未读私信失败: {e}"))),
+    }
+}
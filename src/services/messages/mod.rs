@@ -0,0 +1,82 @@
+//! 班级私信服务
+//!
+//! 私信限定在班级内：教师可以与班级任意成员互发私信，学生之间默认不能私信，
+//! 需班级教师开启 `allow_student_messages`。发送前经过内容审核；接收方在线时通过
+//! WebSocket 推送 `direct_message`，离线时改为发送 `message_received` 通知。
+
+pub mod conversations;
+pub mod send;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::models::messages::requests::{
+    ConversationListQuery, ConversationMessagesQuery, SendMessageRequest,
+};
+use crate::storage::Storage;
+
+pub struct MessageService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl MessageService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    /// 发送私信
+    pub async fn send_message(
+        &self,
+        request: &HttpRequest,
+        req: SendMessageRequest,
+    ) -> ActixResult<HttpResponse> {
+        send::send_message(self, request, req).await
+    }
+
+    /// 列出当前用户的会话
+    pub async fn list_conversations(
+        &self,
+        request: &HttpRequest,
+        query: ConversationListQuery,
+    ) -> ActixResult<HttpResponse> {
+        conversations::list_conversations(self, request, query).await
+    }
+
+    /// 查询会话消息
+    pub async fn list_conversation_messages(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        peer_id: i64,
+        query: ConversationMessagesQuery,
+    ) -> ActixResult<HttpResponse> {
+        conversations::list_conversation_messages(self, request, class_id, peer_id, query).await
+    }
+
+    /// 将会话标记为已读
+    pub async fn mark_conversation_read(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        peer_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        conversations::mark_conversation_read(self, request, class_id, peer_id).await
+    }
+
+    /// 获取未读私信数
+    pub async fn get_unread_count(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        conversations::get_unread_count(self, request).await
+    }
+}
@@ -0,0 +1,199 @@
+//! 发送私信
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::MessageService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::Class;
+use crate::models::messages::requests::SendMessageRequest;
+use crate::models::moderation::entities::ModerationContentType;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::moderation::{moderate_text, record_flag};
+use crate::services::notifications::trigger::send_templated_notification;
+use crate::services::websocket::push_message_to_user;
+use crate::storage::Storage;
+
+/// 私信内容最大长度（字符）
+pub const MAX_MESSAGE_LENGTH: usize = 2000;
+/// 离线通知中的内容摘录长度（字符）
+const PREVIEW_LENGTH: usize = 50;
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 双方在班级中的角色是否允许私信：教师可与任何成员私信，学生之间取决于班级设置
+fn can_message(sender: &ClassUserRole, recipient: &ClassUserRole, allow_students: bool) -> bool {
+    *sender == ClassUserRole::Teacher || *recipient == ClassUserRole::Teacher || allow_students
+}
+
+/// 查询班级并校验租户范围
+pub(super) async fn load_class(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<Class, HttpResponse> {
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => Ok(class),
+        Ok(_) => Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::ClassNotFound,
+            "班级不存在",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级失败: {e}"))),
+    }
+}
+
+/// 用户在班级中的角色，班级负责教师视为教师；不是成员时返回 None
+pub(super) async fn member_role(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class: &Class,
+    user_id: i64,
+) -> Result<Option<ClassUserRole>, HttpResponse> {
+    if class.teacher_id == user_id {
+        return Ok(Some(ClassUserRole::Teacher));
+    }
+    match RequireClassRole::class_user(request, storage, user_id, class.id).await {
+        Ok(class_user) => Ok(class_user.map(|cu| cu.role)),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+pub async fn send_message(
+    service: &MessageService,
+    request: &HttpRequest,
+    req: SendMessageRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(sender) = RequireJWT::extract_user_claims(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let content = req.content.trim();
+    if content.is_empty() || content.chars().count() > MAX_MESSAGE_LENGTH {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::MessageContentInvalid,
+            format!("私信内容不能为空且不能超过 {MAX_MESSAGE_LENGTH} 个字符"),
+        )));
+    }
+    if req.recipient_id == sender.id {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::MessageNotAllowed,
+            "不能给自己发送私信",
+        )));
+    }
+
+    let class = match load_class(&storage, request, req.class_id).await {
+        Ok(class) => class,
+        Err(resp) => return Ok(resp),
+    };
+
+    let sender_role = match member_role(&storage, request, &class, sender.id).await {
+        Ok(Some(role)) => role,
+        Ok(None) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "您不是该班级成员",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+    let recipient_role = match member_role(&storage, request, &class, req.recipient_id).await {
+        Ok(Some(role)) => role,
+        Ok(None) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::MessageNotAllowed,
+                "对方不是该班级成员",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+    if !can_message(&sender_role, &recipient_role, class.allow_student_messages) {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::MessageNotAllowed,
+            "该班级未开启学生之间的私信",
+        )));
+    }
+
+    let flag = match moderate_text(
+        &storage,
+        class.id,
+        sender.id,
+        ModerationContentType::Message,
+        content,
+    )
+    .await
+    {
+        Ok(flag) => flag,
+        Err(resp) => return Ok(resp),
+    };
+
+    let message = match storage
+        .create_message(class.id, sender.id, req.recipient_id, content)
+        .await
+    {
+        Ok(message) => message,
+        Err(e) => return Ok(internal_error(format!("发送私信失败: {e}"))),
+    };
+
+    if let Some(mut flag) = flag {
+        flag.content_id = Some(message.id);
+        record_flag(&storage, flag).await;
+    }
+
+    // 接收方在线时直接推送，否则发送通知
+    if !push_message_to_user(message.recipient_id, message.clone()) {
+        let storage = storage.clone();
+        let (class_id, recipient_id) = (class.id, message.recipient_id);
+        let vars = vec![
+            (
+                "sender_name",
+                sender.display_name.unwrap_or(sender.username),
+            ),
+            ("class_name", class.name),
+            ("preview", content.chars().take(PREVIEW_LENGTH).collect()),
+        ];
+        executor::spawn(NOTIFICATIONS_QUEUE, async move {
+            send_templated_notification(
+                storage,
+                recipient_id,
+                NotificationType::MessageReceived,
+                vars,
+                Some(ReferenceType::Class),
+                Some(class_id),
+            )
+            .await;
+        });
+    }
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(message, "发送成功")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_message() {
+        use ClassUserRole::*;
+
+        assert!(can_message(&Teacher, &Student, false));
+        assert!(can_message(&Student, &Teacher, false));
+        assert!(can_message(&ClassRepresentative, &Teacher, false));
+        assert!(!can_message(&Student, &Student, false));
+        assert!(!can_message(&Student, &ClassRepresentative, false));
+        assert!(can_message(&Student, &Student, true));
+    }
+}
@@ -1,26 +1,52 @@
+pub mod analytics;
 pub mod auth;
+pub mod certificates;
 pub mod class_users;
 pub mod classes;
+pub mod dev;
 pub mod files;
 pub mod grades;
 pub mod homeworks;
+pub mod im_delivery;
+pub mod jobs;
+pub mod messages;
+pub mod moderation;
 pub mod notifications;
+pub mod organizations;
+pub mod outbound;
+pub mod outbox;
+pub mod presenters;
+pub mod reactions;
+pub mod rubrics;
 pub mod submissions;
 pub mod system;
+pub mod usage;
 pub mod users;
+pub mod viewers;
 pub mod websocket;
 
+pub use analytics::AnalyticsService;
 pub use auth::AuthService;
+pub use certificates::CertificateService;
 pub use class_users::ClassUserService;
 pub use classes::ClassService;
+pub use dev::DevService;
 pub use files::FileService;
 pub use grades::GradeService;
 pub use homeworks::HomeworkService;
+pub use jobs::JobService;
+pub use messages::MessageService;
+pub use moderation::ModerationService;
 pub use notifications::NotificationService;
+pub use organizations::OrganizationService;
+pub use reactions::ReactionService;
+pub use rubrics::RubricService;
 pub use submissions::SubmissionService;
 pub use system::SystemService;
+pub use usage::UsageService;
 pub use users::UserService;
+pub use viewers::ViewerService;
 pub use websocket::{
-    WebSocketService, get_online_count, is_user_online, push_notification_to_user,
-    push_notification_to_users,
+    WebSocketService, disconnect_user, get_online_count, is_user_online, push_message_to_user,
+    push_notification_to_user, push_notification_to_users, push_reaction_update,
 };
@@ -0,0 +1,173 @@
+//! 文本内容审核
+//!
+//! 处理方式与违禁词列表存储在系统设置（`moderation.*`）中。违禁词不区分大小写按子串匹配；
+//! 配置了 `moderation.api_url` 时再调用外部审核接口。`block` 模式下命中的内容被拒绝发布，
+//! `flag` 模式下正常发布；两种情况都写入审核标记供班级教师复核。
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use serde_json::{Value, json};
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::models::moderation::entities::{
+    ModerationContentType, ModerationMode, ModerationSource,
+};
+use crate::models::moderation::requests::ModerationFlagInput;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::outbound::MODERATION_CLIENT;
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+
+/// 标记中保存的内容摘录最大长度（字符）
+pub(super) const MAX_EXCERPT_LENGTH: usize = 500;
+/// 命中原因最大长度（字符）
+const MAX_REASON_LENGTH: usize = 255;
+
+/// 审核待发布的文本
+///
+/// - 未启用审核或未命中：返回 `Ok(None)`
+/// - `flag` 模式命中：返回待写入的标记，调用方在内容保存后补上 `content_id` 并调用 [`record_flag`]
+/// - `block` 模式命中：写入拦截标记并返回 400 响应
+pub async fn moderate_text(
+    storage: &Arc<dyn Storage>,
+    class_id: i64,
+    user_id: i64,
+    content_type: ModerationContentType,
+    text: &str,
+) -> Result<Option<ModerationFlagInput>, HttpResponse> {
+    let mode = DynamicConfig::moderation_mode().await;
+    if mode == ModerationMode::Off || text.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let Some((source, reason)) = check_text(content_type, text).await else {
+        return Ok(None);
+    };
+
+    let mut input = ModerationFlagInput {
+        class_id,
+        user_id,
+        content_type,
+        content_id: None,
+        source,
+        reason: truncate(&reason, MAX_REASON_LENGTH),
+        excerpt: truncate(text, MAX_EXCERPT_LENGTH),
+        blocked: false,
+    };
+
+    if mode == ModerationMode::Block {
+        input.blocked = true;
+        record_flag(storage, input).await;
+        return Err(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ContentModerationBlocked,
+            "内容包含不允许发布的词语，请修改后重试",
+        )));
+    }
+
+    Ok(Some(input))
+}
+
+/// 写入审核标记，失败只记录警告，不影响正常请求
+pub async fn record_flag(storage: &Arc<dyn Storage>, input: ModerationFlagInput) {
+    let (class_id, user_id) = (input.class_id, input.user_id);
+    if let Err(e) = storage.create_moderation_flag(input).await {
+        warn!("记录审核标记失败 (class={class_id}, user={user_id}): {e}");
+    }
+}
+
+/// 依次按违禁词列表与外部接口检查文本，返回命中来源与原因
+async fn check_text(
+    content_type: ModerationContentType,
+    text: &str,
+) -> Option<(ModerationSource, String)> {
+    let words = DynamicConfig::moderation_banned_words().await;
+    let matched = match_banned_words(text, &words);
+    if !matched.is_empty() {
+        return Some((ModerationSource::WordList, matched.join(", ")));
+    }
+
+    let url = DynamicConfig::moderation_api_url().await?;
+    match call_external(&url, content_type, text).await {
+        Ok(reason) => reason.map(|r| (ModerationSource::External, r)),
+        Err(e) => {
+            warn!("调用外部审核接口失败: {e}");
+            (!AppConfig::get().moderation.fail_open)
+                .then(|| (ModerationSource::External, "外部审核接口不可用".to_string()))
+        }
+    }
+}
+
+/// 调用外部审核接口
+///
+/// 请求体为 `{"content_type", "text"}`，配置了 `moderation.api_key` 时携带 Bearer 令牌；
+/// 响应 `{"flagged": true, "reason": "..."}` 表示命中。
+async fn call_external(
+    url: &str,
+    content_type: ModerationContentType,
+    text: &str,
+) -> Result<Option<String>, String> {
+    let body = json!({
+        "content_type": content_type,
+        "text": text,
+    });
+    let api_key = DynamicConfig::moderation_api_key().await;
+
+    let response = MODERATION_CLIENT
+        .send(|client| {
+            let request = client.post(url).json(&body);
+            match &api_key {
+                Some(key) => request.bearer_auth(key),
+                None => request,
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let payload: Value = response.json().await.map_err(|e| e.to_string())?;
+
+    if payload["flagged"].as_bool() == Some(true) {
+        let reason = payload["reason"]
+            .as_str()
+            .filter(|r| !r.is_empty())
+            .unwrap_or("外部审核接口标记");
+        Ok(Some(reason.to_string()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// 按违禁词列表匹配文本（不区分大小写），返回命中的词（按列表顺序，大小写不同的重复项只保留一个）
+pub fn match_banned_words(text: &str, words: &[String]) -> Vec<String> {
+    let text = text.to_lowercase();
+    let mut matched: Vec<String> = Vec::new();
+    for word in words {
+        let lower = word.to_lowercase();
+        if text.contains(&lower) && !matched.iter().any(|m| m.to_lowercase() == lower) {
+            matched.push(word.clone());
+        }
+    }
+    matched
+}
+
+pub(super) fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_banned_words() {
+        let words = vec!["Spam".to_string(), "作弊".to_string(), "spam".to_string()];
+        assert_eq!(
+            match_banned_words("This is SPAM, 不是作弊", &words),
+            vec!["Spam", "作弊"]
+        );
+        assert!(match_banned_words("正常的答案", &words).is_empty());
+    }
+}
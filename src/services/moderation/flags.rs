@@ -0,0 +1,129 @@
+//! 班级审核标记复核
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ModerationService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::Class;
+use crate::models::moderation::entities::ModerationFlagStatus;
+use crate::models::moderation::requests::{ModerationFlagListQuery, ReviewModerationFlagRequest};
+use crate::models::moderation::responses::ModerationFlagListResponse;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以复核审核标记";
+
+pub(super) fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验当前用户是否为班级教师或管理员，返回用户 ID 与班级
+pub(super) async fn check_class_teacher(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+    denied_message: &str,
+) -> Result<(i64, Class), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok((user_id, class));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => Ok((user_id, class)),
+        Ok(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            denied_message,
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+pub async fn list_flags(
+    service: &ModerationService,
+    request: &HttpRequest,
+    class_id: i64,
+    query: ModerationFlagListQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.list_moderation_flags(class_id, query.status).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ModerationFlagListResponse { class_id, items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询审核标记失败: {e}"))),
+    }
+}
+
+pub async fn review_flag(
+    service: &ModerationService,
+    request: &HttpRequest,
+    class_id: i64,
+    flag_id: i64,
+    req: ReviewModerationFlagRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let reviewer_id = match check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        Ok((user_id, _)) => user_id,
+        Err(resp) => return Ok(resp),
+    };
+
+    if req.status == ModerationFlagStatus::Pending {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "复核结果只能为 dismissed 或 confirmed",
+        )));
+    }
+
+    let not_found = || {
+        HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::ModerationFlagNotFound,
+            "审核标记不存在",
+        ))
+    };
+
+    match storage.get_moderation_flag(flag_id).await {
+        Ok(Some(flag)) if flag.class_id == class_id => {}
+        Ok(_) => return Ok(not_found()),
+        Err(e) => return Ok(internal_error(format!("查询审核标记失败: {e}"))),
+    }
+
+    match storage
+        .review_moderation_flag(flag_id, req.status, reviewer_id)
+        .await
+    {
+        Ok(Some(flag)) => Ok(HttpResponse::Ok().json(ApiResponse::success(flag, "复核成功"))),
+        Ok(None) => Ok(not_found()),
+        Err(e) => Ok(internal_error(format!("更新审核标记失败: {e}"))),
+    }
+}
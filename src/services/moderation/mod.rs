@@ -0,0 +1,94 @@
+//! 内容审核服务
+//!
+//! 提交内容与评分评语发布前经过 [`filter::moderate_text`] 审核，命中的内容按系统设置
+//! `moderation.mode` 拦截或标记；班级教师在标记列表中复核。
+//! 班级成员也可以主动举报内容（见 [`reports`]），由班级教师在举报队列中处理。
+
+pub mod filter;
+pub mod flags;
+pub mod reports;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::models::moderation::requests::{
+    ContentReportListQuery, CreateContentReportRequest, ModerationFlagListQuery,
+    ResolveContentReportRequest, ReviewModerationFlagRequest,
+};
+use crate::storage::Storage;
+
+pub use filter::{moderate_text, record_flag};
+
+pub struct ModerationService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl ModerationService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    /// 列出班级审核标记
+    pub async fn list_flags(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        query: ModerationFlagListQuery,
+    ) -> ActixResult<HttpResponse> {
+        flags::list_flags(self, request, class_id, query).await
+    }
+
+    /// 复核审核标记
+    pub async fn review_flag(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        flag_id: i64,
+        req: ReviewModerationFlagRequest,
+    ) -> ActixResult<HttpResponse> {
+        flags::review_flag(self, request, class_id, flag_id, req).await
+    }
+
+    /// 举报班级内容
+    pub async fn create_report(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        req: CreateContentReportRequest,
+    ) -> ActixResult<HttpResponse> {
+        reports::create_report(self, request, class_id, req).await
+    }
+
+    /// 列出班级举报
+    pub async fn list_reports(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        query: ContentReportListQuery,
+    ) -> ActixResult<HttpResponse> {
+        reports::list_reports(self, request, class_id, query).await
+    }
+
+    /// 处理举报
+    pub async fn resolve_report(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        report_id: i64,
+        req: ResolveContentReportRequest,
+    ) -> ActixResult<HttpResponse> {
+        reports::resolve_report(self, request, class_id, report_id, req).await
+    }
+}
@@ -0,0 +1,303 @@
+//! 内容举报
+//!
+//! 班级成员可以举报能看到的评语、班级公告与收到的私信，班级教师收到 `content_reported` 通知。
+//! 教师在举报队列中驳回、删除内容或警告作者（作者收到 `content_warning` 通知），
+//! 同一内容的其他待处理举报一并处理。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::error;
+
+use super::ModerationService;
+use super::filter::{MAX_EXCERPT_LENGTH, truncate};
+use super::flags::{check_class_teacher, internal_error};
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::moderation::entities::{ContentReportAction, ReportContentType};
+use crate::models::moderation::requests::{
+    ContentReportInput, ContentReportListQuery, CreateContentReportRequest,
+    ResolveContentReportRequest,
+};
+use crate::models::moderation::responses::ContentReportListResponse;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::notifications::requests::NotificationRecipientFilter;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::{
+    send_templated_notification, send_templated_notifications,
+};
+use crate::storage::Storage;
+
+const DENIED_MESSAGE: &str = "只有班级教师可以处理举报";
+
+fn content_label(content_type: ReportContentType) -> &'static str {
+    match content_type {
+        ReportContentType::GradeComment => "评语",
+        ReportContentType::Announcement => "公告",
+        ReportContentType::Message => "私信",
+    }
+}
+
+fn invalid(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::ContentReportInvalid,
+        message.into(),
+    ))
+}
+
+fn target_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::ContentReportTargetNotFound,
+        "被举报的内容不存在",
+    ))
+}
+
+/// 通知班级教师（举报人除外）有新的举报
+fn notify_teachers(
+    storage: Arc<dyn Storage>,
+    class_id: i64,
+    class_name: String,
+    reporter_id: i64,
+    content_type: ReportContentType,
+    reason: String,
+) {
+    executor::spawn(NOTIFICATIONS_QUEUE, async move {
+        let filter = NotificationRecipientFilter {
+            role: Some(ClassUserRole::Teacher),
+            ..Default::default()
+        };
+        let teacher_ids: Vec<i64> = match storage
+            .resolve_notification_recipients(class_id, &filter)
+            .await
+        {
+            Ok(ids) => ids.into_iter().filter(|&id| id != reporter_id).collect(),
+            Err(e) => {
+                error!("查询班级教师失败 (class={class_id}): {e}");
+                return;
+            }
+        };
+        send_templated_notifications(
+            storage,
+            teacher_ids,
+            NotificationType::ContentReported,
+            vec![
+                ("class_name", class_name),
+                ("content_type", content_label(content_type).to_string()),
+                ("reason", reason),
+            ],
+            Some(ReferenceType::Class),
+            Some(class_id),
+        )
+        .await;
+    });
+}
+
+pub async fn create_report(
+    service: &ModerationService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: CreateContentReportRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    if let Err(message) = req.validate() {
+        return Ok(invalid(message));
+    }
+    let reason = req.reason.trim().to_string();
+
+    let class = match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级成员可以举报班级内容",
+                )));
+            }
+            Err(e) => return Ok(internal_error(format!("查询班级成员失败: {e}"))),
+        }
+    }
+
+    // 只能举报本班级内自己能看到的内容：评语限提交者，私信限接收方，公告全体成员可见
+    let content = match storage
+        .get_reported_content(req.content_type, req.content_id)
+        .await
+    {
+        Ok(Some(content))
+            if content.class_id == class_id
+                && content.viewer_id.is_none_or(|viewer| viewer == user_id) =>
+        {
+            content
+        }
+        Ok(_) => return Ok(target_not_found()),
+        Err(e) => return Ok(internal_error(format!("查询被举报内容失败: {e}"))),
+    };
+    if content.author_id == user_id {
+        return Ok(invalid("不能举报自己发布的内容"));
+    }
+
+    match storage
+        .has_pending_content_report(user_id, req.content_type, req.content_id)
+        .await
+    {
+        Ok(false) => {}
+        Ok(true) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::ContentReportDuplicate,
+                "已举报过该内容，请等待教师处理",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询举报失败: {e}"))),
+    }
+
+    let report = match storage
+        .create_content_report(ContentReportInput {
+            class_id,
+            reporter_id: user_id,
+            author_id: content.author_id,
+            content_type: req.content_type,
+            content_id: req.content_id,
+            reason: reason.clone(),
+            excerpt: truncate(&content.text, MAX_EXCERPT_LENGTH),
+        })
+        .await
+    {
+        Ok(report) => report,
+        Err(e) => return Ok(internal_error(format!("创建举报失败: {e}"))),
+    };
+
+    notify_teachers(
+        storage,
+        class_id,
+        class.name,
+        user_id,
+        req.content_type,
+        reason,
+    );
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(report, "举报已提交")))
+}
+
+pub async fn list_reports(
+    service: &ModerationService,
+    request: &HttpRequest,
+    class_id: i64,
+    query: ContentReportListQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+        return Ok(resp);
+    }
+
+    match storage.list_content_reports(class_id, query.status).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            ContentReportListResponse { class_id, items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询举报失败: {e}"))),
+    }
+}
+
+pub async fn resolve_report(
+    service: &ModerationService,
+    request: &HttpRequest,
+    class_id: i64,
+    report_id: i64,
+    req: ResolveContentReportRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let (resolver_id, class) =
+        match check_class_teacher(&storage, request, class_id, DENIED_MESSAGE).await {
+            Ok(result) => result,
+            Err(resp) => return Ok(resp),
+        };
+
+    let note = req
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|note| note.chars().count() > ResolveContentReportRequest::MAX_NOTE_CHARS)
+    {
+        return Ok(invalid(format!(
+            "处理备注不能超过 {} 个字符",
+            ResolveContentReportRequest::MAX_NOTE_CHARS
+        )));
+    }
+
+    let not_found = || {
+        HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::ContentReportNotFound,
+            "举报不存在",
+        ))
+    };
+    let already_resolved = || {
+        HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::ContentReportInvalid,
+            "举报已处理",
+        ))
+    };
+
+    match storage.get_content_report(report_id).await {
+        Ok(Some(report)) if report.class_id == class_id => {}
+        Ok(_) => return Ok(not_found()),
+        Err(e) => return Ok(internal_error(format!("查询举报失败: {e}"))),
+    }
+
+    let report = match storage
+        .resolve_content_report(report_id, req.action, note.clone(), resolver_id)
+        .await
+    {
+        Ok(Some(report)) => report,
+        Ok(None) => return Ok(already_resolved()),
+        Err(e) => return Ok(internal_error(format!("处理举报失败: {e}"))),
+    };
+
+    if req.action == ContentReportAction::WarnUser {
+        let author_id = report.author_id;
+        let vars = vec![
+            ("class_name", class.name),
+            (
+                "content_type",
+                content_label(report.content_type).to_string(),
+            ),
+            ("note", note.unwrap_or_default()),
+        ];
+        executor::spawn(NOTIFICATIONS_QUEUE, async move {
+            send_templated_notification(
+                storage,
+                author_id,
+                NotificationType::ContentWarning,
+                vars,
+                Some(ReferenceType::Class),
+                Some(class_id),
+            )
+            .await;
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(report, "处理成功")))
+}
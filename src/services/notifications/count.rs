@@ -1,28 +1,78 @@
+//! 未读通知数量
+//!
+//! 供无法保持 WebSocket 的客户端（如小程序）高频轮询：数量缓存在进程内，
+//! 本节点的通知写入（创建、已读、删除）立即失效；其他节点的写入依靠较短的 TTL 兜底。
+//! 响应带 ETag，数量未变化时返回 304。
+
+use actix_web::http::header::{CACHE_CONTROL, ETAG};
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::time::Duration;
 
 use super::NotificationService;
 use crate::models::notifications::responses::UnreadCountResponse;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::etag::if_none_match;
+
+/// 未读数量缓存
+/// 键: user_id，值: 未读数量
+static UNREAD_COUNT_CACHE: Lazy<Cache<i64, i64>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(Duration::from_secs(10))
+        .max_capacity(100_000)
+        .build()
+});
+
+/// 使用户的未读数量缓存失效（通知写入后调用）
+pub async fn invalidate_unread_count(user_id: i64) {
+    UNREAD_COUNT_CACHE.invalidate(&user_id).await;
+}
 
 pub async fn get_unread_count(
     service: &NotificationService,
     request: &HttpRequest,
     user_id: i64,
 ) -> ActixResult<HttpResponse> {
-    let storage = service.get_storage(request);
+    let count = match UNREAD_COUNT_CACHE.get(&user_id).await {
+        Some(count) => count,
+        None => {
+            let storage = service.get_storage(request);
+            match storage.get_unread_notification_count(user_id).await {
+                Ok(count) => {
+                    UNREAD_COUNT_CACHE.insert(user_id, count).await;
+                    count
+                }
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询未读通知数量失败: {e}"),
+                        )),
+                    );
+                }
+            }
+        }
+    };
 
-    match storage.get_unread_notification_count(user_id).await {
-        Ok(count) => Ok(HttpResponse::Ok().json(ApiResponse::success(
-            UnreadCountResponse {
-                unread_count: count,
-            },
-            "查询成功",
-        ))),
-        Err(e) => Ok(
-            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
-                ErrorCode::InternalServerError,
-                format!("查询未读通知数量失败: {e}"),
-            )),
-        ),
+    let etag = format!("\"unread-{count}\"");
+    let not_modified = if_none_match(request, &etag);
+    let mut response = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header((CACHE_CONTROL, "private, no-cache"))
+        .insert_header((ETAG, etag));
+    if not_modified {
+        return Ok(response.finish());
     }
+
+    Ok(response.json(ApiResponse::success(
+        UnreadCountResponse {
+            unread_count: count,
+        },
+        "查询成功",
+    )))
 }
@@ -1,6 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::NotificationService;
+use super::count::invalidate_unread_count;
 use crate::middlewares::RequireJWT;
 use crate::models::{ApiResponse, ErrorCode};
 
@@ -50,7 +51,10 @@ pub async fn delete_notification(
     }
 
     match storage.delete_notification(notification_id).await {
-        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("通知已删除"))),
+        Ok(true) => {
+            invalidate_unread_count(current_user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("通知已删除")))
+        }
         Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::NotificationNotFound,
             "通知不存在",
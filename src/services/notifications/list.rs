@@ -1,6 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::NotificationService;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::models::notifications::requests::NotificationListQuery;
 use crate::models::{ApiResponse, ErrorCode};
 
@@ -10,6 +11,11 @@ pub async fn list_notifications(
     user_id: i64,
     query: NotificationListQuery,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("notifications").check(query.pagination.size)
+    {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
 
     match storage
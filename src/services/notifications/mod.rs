@@ -2,12 +2,16 @@ pub mod count;
 pub mod delete;
 pub mod list;
 pub mod read;
+pub mod send;
+pub mod templates;
 pub mod trigger;
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
-use crate::models::notifications::requests::NotificationListQuery;
+use crate::models::notifications::requests::{
+    NotificationListQuery, SendNotificationRequest, UpsertNotificationTemplateRequest,
+};
 use crate::storage::Storage;
 
 pub struct NotificationService {
@@ -76,4 +80,39 @@ impl NotificationService {
     ) -> ActixResult<HttpResponse> {
         delete::delete_notification(self, request, notification_id).await
     }
+
+    /// 群发班级通知
+    pub async fn send_notification(
+        &self,
+        request: &HttpRequest,
+        req: SendNotificationRequest,
+    ) -> ActixResult<HttpResponse> {
+        send::send_notification(self, request, req).await
+    }
+
+    /// 列出通知模板
+    pub async fn list_templates(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        templates::list_templates(self, request).await
+    }
+
+    /// 创建或更新通知模板
+    pub async fn upsert_template(
+        &self,
+        request: &HttpRequest,
+        notification_type: &str,
+        locale: &str,
+        req: UpsertNotificationTemplateRequest,
+    ) -> ActixResult<HttpResponse> {
+        templates::upsert_template(self, request, notification_type, locale, req).await
+    }
+
+    /// 删除通知模板
+    pub async fn delete_template(
+        &self,
+        request: &HttpRequest,
+        notification_type: &str,
+        locale: &str,
+    ) -> ActixResult<HttpResponse> {
+        templates::delete_template(self, request, notification_type, locale).await
+    }
 }
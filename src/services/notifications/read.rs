@@ -1,6 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::NotificationService;
+use super::count::invalidate_unread_count;
 use crate::middlewares::RequireJWT;
 use crate::models::notifications::responses::MarkAllReadResponse;
 use crate::models::{ApiResponse, ErrorCode};
@@ -51,7 +52,10 @@ pub async fn mark_as_read(
     }
 
     match storage.mark_notification_as_read(notification_id).await {
-        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("通知已标记为已读"))),
+        Ok(true) => {
+            invalidate_unread_count(current_user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("通知已标记为已读")))
+        }
         Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::NotificationNotFound,
             "通知不存在",
@@ -73,12 +77,15 @@ pub async fn mark_all_as_read(
     let storage = service.get_storage(request);
 
     match storage.mark_all_notifications_as_read(user_id).await {
-        Ok(count) => Ok(HttpResponse::Ok().json(ApiResponse::success(
-            MarkAllReadResponse {
-                marked_count: count,
-            },
-            "操作成功",
-        ))),
+        Ok(count) => {
+            invalidate_unread_count(user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                MarkAllReadResponse {
+                    marked_count: count,
+                },
+                "操作成功",
+            )))
+        }
         Err(e) => Ok(
             HttpResponse::InternalServerError().json(ApiResponse::error_empty(
                 ErrorCode::InternalServerError,
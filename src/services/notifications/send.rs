@@ -0,0 +1,149 @@
+//! 群发班级通知
+//!
+//! 班级教师或管理员按筛选条件（指定用户、班级角色、未提交某作业）选择接收人，
+//! 服务端通过存储查询解析为用户 ID 后批量创建通知并推送，并写入班级公告动态。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::warn;
+
+use super::NotificationService;
+use super::templates::render_notification;
+use super::trigger::deliver_notifications;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::{ActivityEventType, NewActivityEvent};
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::notifications::requests::SendNotificationRequest;
+use crate::models::notifications::responses::SendNotificationResponse;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 群发班级通知（班级教师、管理员）
+pub async fn send_notification(
+    service: &NotificationService,
+    request: &HttpRequest,
+    req: SendNotificationRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match RequireJWT::extract_user_id(request) {
+        Some(id) => id,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "无法获取用户信息",
+            )));
+        }
+    };
+
+    let class = match storage.get_class_by_id(req.class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => class,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询班级失败: {e}"))),
+    };
+
+    if RequireJWT::extract_user_role(request) != Some(UserRole::Admin) {
+        match RequireClassRole::class_user(request, &storage, user_id, class.id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "只有班级教师可以群发通知",
+                )));
+            }
+            Err(e) => return Ok(internal_error(format!("查询班级成员失败: {e}"))),
+        }
+    }
+
+    if let Err(message) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::NotificationRequestInvalid,
+            message,
+        )));
+    }
+
+    // 未提交筛选的作业必须属于该班级
+    if let Some(homework_id) = req.filter.not_submitted_homework_id {
+        match storage.get_homework_by_id(homework_id).await {
+            Ok(Some(homework)) if homework.class_id == class.id => {}
+            Ok(_) => {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::NotificationRequestInvalid,
+                    "筛选的作业不属于该班级",
+                )));
+            }
+            Err(e) => return Ok(internal_error(format!("查询作业失败: {e}"))),
+        }
+    }
+
+    let mut recipients = match storage
+        .resolve_notification_recipients(class.id, &req.filter)
+        .await
+    {
+        Ok(ids) => ids,
+        Err(e) => return Ok(internal_error(format!("解析通知接收人失败: {e}"))),
+    };
+    recipients.retain(|&id| id != user_id);
+
+    let sender_name = match storage.get_user_by_id(user_id).await {
+        Ok(Some(user)) => user.display_name.unwrap_or(user.username),
+        Ok(None) => String::new(),
+        Err(e) => return Ok(internal_error(format!("查询用户失败: {e}"))),
+    };
+
+    let notification_type = NotificationType::ClassAnnouncement;
+    let announcement_title = req.title.trim().to_string();
+    let vars = [
+        ("class_name", class.name),
+        ("sender_name", sender_name),
+        ("title", announcement_title.clone()),
+        (
+            "content",
+            req.content.unwrap_or_default().trim().to_string(),
+        ),
+    ];
+    let (title, content) = render_notification(&storage, &notification_type, &vars).await;
+
+    match deliver_notifications(
+        &storage,
+        &recipients,
+        &notification_type,
+        title,
+        content,
+        Some(&ReferenceType::Class),
+        Some(class.id),
+    )
+    .await
+    {
+        Ok(recipient_count) => {
+            // 班级动态写入失败不影响已送达的通知
+            let event = NewActivityEvent {
+                class_id: class.id,
+                event_type: ActivityEventType::Announcement,
+                actor_id: Some(user_id),
+                reference_id: None,
+                title: announcement_title,
+            };
+            if let Err(e) = storage.create_activity_event(event).await {
+                warn!("写入班级公告动态失败 (class={}): {e}", class.id);
+            }
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                SendNotificationResponse { recipient_count },
+                "通知已发送",
+            )))
+        }
+        Err(e) => Ok(internal_error(format!("发送通知失败: {e}"))),
+    }
+}
@@ -0,0 +1,424 @@
+//! 通知模板
+//!
+//! 每种通知类型都有内置的中文文案；管理员可按语言保存自定义模板覆盖内置文案，
+//! 发送时使用系统设置 `notification.locale` 对应语言的模板。
+//! 模板中的 `{name}` 占位符会被替换为通知变量。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::warn;
+
+use super::NotificationService;
+use crate::middlewares::RequireJWT;
+use crate::models::notifications::entities::NotificationType;
+use crate::models::notifications::requests::UpsertNotificationTemplateRequest;
+use crate::models::notifications::responses::{
+    NotificationTemplateInfo, NotificationTemplateListResponse,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::im_delivery::templates::render;
+use crate::services::system::DynamicConfig;
+use crate::storage::Storage;
+
+/// 标题模板最大长度（字符）
+pub const MAX_TITLE_LENGTH: usize = 200;
+/// 内容模板最大长度（字符）
+pub const MAX_CONTENT_LENGTH: usize = 2000;
+/// 语言标识最大长度
+const MAX_LOCALE_LENGTH: usize = 16;
+
+/// 内置通知模板
+pub struct BuiltinTemplate {
+    /// 可用的变量
+    pub variables: &'static [&'static str],
+    pub title: &'static str,
+    pub content: Option<&'static str>,
+}
+
+/// 通知类型的内置模板
+pub fn builtin_template(notification_type: &NotificationType) -> BuiltinTemplate {
+    let (variables, title, content): (&'static [&'static str], _, _) = match notification_type {
+        NotificationType::HomeworkCreated => (
+            &["homework_title"],
+            "新作业发布：{homework_title}",
+            "作业「{homework_title}」已发布，请及时查看",
+        ),
+        NotificationType::HomeworkUpdated => (
+            &["homework_title"],
+            "作业更新：{homework_title}",
+            "作业「{homework_title}」已更新，请查看最新内容",
+        ),
+        NotificationType::HomeworkDeadline => (
+            &["homework_title", "deadline"],
+            "作业即将截止：{homework_title}",
+            "作业「{homework_title}」将于 {deadline} 截止，请及时提交",
+        ),
+        NotificationType::SolutionPublished => (
+            &["homework_title"],
+            "参考答案已公开：{homework_title}",
+            "作业「{homework_title}」的参考答案已公开，请及时查看",
+        ),
+        NotificationType::SubmissionReceived => (
+            &["homework_title", "student_name"],
+            "收到新提交：{homework_title}",
+            "{student_name} 提交了作业「{homework_title}」",
+        ),
+        NotificationType::GradingOverdue => (
+            &["homework_title", "count", "sla_days"],
+            "作业批改超时：{homework_title}",
+            "作业「{homework_title}」有 {count} 份提交已超过 {sla_days} 天未批改",
+        ),
+        NotificationType::ResubmissionRequested => (
+            &["homework_title", "comment", "reopen_until"],
+            "作业被退回重交：{homework_title}",
+            "您的作业「{homework_title}」已被退回，请在 {reopen_until} 前重新提交。教师意见：{comment}",
+        ),
+        NotificationType::GradeReceived => (
+            &["homework_title", "score"],
+            "作业已评分：{homework_title}",
+            "您的作业「{homework_title}」已评分，得分：{score}",
+        ),
+        NotificationType::GradeUpdated => (
+            &["homework_title", "score"],
+            "评分已更新：{homework_title}",
+            "您的作业「{homework_title}」评分已更新，新得分：{score}",
+        ),
+        NotificationType::MissingGradeAssigned => (
+            &["homework_title"],
+            "缺交作业已记零分：{homework_title}",
+            "您在最终截止时间前未提交作业「{homework_title}」，已自动记为零分，如有疑问请联系教师",
+        ),
+        NotificationType::ClassJoined => (
+            &["class_name"],
+            "成功加入班级：{class_name}",
+            "您已成功加入班级「{class_name}」",
+        ),
+        NotificationType::ClassRoleChanged => (
+            &["class_name", "role_name"],
+            "班级角色变更：{class_name}",
+            "您在班级「{class_name}」的角色已变更为：{role_name}",
+        ),
+        NotificationType::ClassAnnouncement => (
+            &["class_name", "sender_name", "title", "content"],
+            "{class_name}：{title}",
+            "{content}",
+        ),
+        NotificationType::Mentioned => (
+            &["homework_title"],
+            "有人在评语中提到了您：{homework_title}",
+            "作业「{homework_title}」的评语中提到了您",
+        ),
+        NotificationType::RoleRequestSubmitted => (
+            &["applicant", "reason"],
+            "新的教师身份申请：{applicant}",
+            "{reason}",
+        ),
+        NotificationType::RoleRequestReviewed => (
+            &["result", "comment", "message"],
+            "教师身份申请{result}",
+            "{message}",
+        ),
+        NotificationType::CertificateIssued => (
+            &["class_name"],
+            "获得结业证书：{class_name}",
+            "您已完成「{class_name}」的全部作业，结业证书已生成，可在班级页面下载",
+        ),
+        NotificationType::FileQuarantineReviewed => (
+            &["file_name", "result"],
+            "上传文件审核{result}：{file_name}",
+            "您上传的文件「{file_name}」因内容与扩展名不匹配被暂扣，管理员审核结果：{result}",
+        ),
+        NotificationType::MessageReceived => (
+            &["sender_name", "class_name", "preview"],
+            "收到私信：{sender_name}",
+            "{sender_name} 在班级「{class_name}」给您发送了私信：{preview}",
+        ),
+        NotificationType::ContentReported => (
+            &["class_name", "content_type", "reason"],
+            "新的内容举报：{class_name}",
+            "班级「{class_name}」中有{content_type}被举报，理由：{reason}",
+        ),
+        NotificationType::ContentWarning => (
+            &["class_name", "content_type", "note"],
+            "内容警告：{class_name}",
+            "您在班级「{class_name}」发布的{content_type}被举报，经教师核实给予警告。{note}",
+        ),
+        NotificationType::ScheduledExportFailed => (
+            &["class_name", "error", "status"],
+            "定时导出失败：{class_name}",
+            "班级「{class_name}」的定时导出未能送达：{error}。{status}",
+        ),
+        NotificationType::NewDeviceLogin => (
+            &["device", "ip_address", "login_time"],
+            "新设备登录提醒",
+            "您的账号于 {login_time} 在新设备（{device}）上登录，IP：{ip_address}。如非本人操作，请立即修改密码",
+        ),
+    };
+
+    BuiltinTemplate {
+        variables,
+        title,
+        content: Some(content),
+    }
+}
+
+/// 渲染通知标题与内容（优先使用当前语言的自定义模板，查询失败时回退到内置模板）
+pub async fn render_notification(
+    storage: &Arc<dyn Storage>,
+    notification_type: &NotificationType,
+    vars: &[(&str, String)],
+) -> (String, Option<String>) {
+    let locale = DynamicConfig::notification_locale().await;
+    let custom = match storage
+        .get_notification_template(notification_type, &locale)
+        .await
+    {
+        Ok(template) => template,
+        Err(e) => {
+            warn!("查询通知模板 {notification_type}/{locale} 失败，使用内置模板: {e}");
+            None
+        }
+    };
+
+    let (title, content) = match custom {
+        Some(template) => (template.title, template.content),
+        None => {
+            let builtin = builtin_template(notification_type);
+            (
+                builtin.title.to_string(),
+                builtin.content.map(str::to_string),
+            )
+        }
+    };
+
+    let title = render(&title, vars);
+    let content = content
+        .map(|content| render(&content, vars))
+        .filter(|content| !content.trim().is_empty());
+    (title, content)
+}
+
+/// 提取模板中的占位符名称
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            names.push(name);
+            rest = &rest[end + 1..];
+        }
+    }
+    names
+}
+
+fn validate_locale(locale: &str) -> Result<(), String> {
+    if locale.is_empty()
+        || locale.len() > MAX_LOCALE_LENGTH
+        || !locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "语言标识无效，应为不超过 {MAX_LOCALE_LENGTH} 个字符的字母、数字、- 或 _（如 zh-CN、en）"
+        ));
+    }
+    Ok(())
+}
+
+fn validate_template(
+    notification_type: &NotificationType,
+    req: &UpsertNotificationTemplateRequest,
+) -> Result<(), String> {
+    let title_len = req.title.trim().chars().count();
+    if title_len == 0 || title_len > MAX_TITLE_LENGTH {
+        return Err(format!(
+            "标题模板不能为空且不超过 {MAX_TITLE_LENGTH} 个字符"
+        ));
+    }
+    if let Some(content) = &req.content
+        && content.chars().count() > MAX_CONTENT_LENGTH
+    {
+        return Err(format!("内容模板不能超过 {MAX_CONTENT_LENGTH} 个字符"));
+    }
+
+    let variables = builtin_template(notification_type).variables;
+    let templates = std::iter::once(req.title.as_str()).chain(req.content.as_deref());
+    for template in templates {
+        if let Some(unknown) = placeholders(template)
+            .into_iter()
+            .find(|name| !variables.contains(name))
+        {
+            return Err(format!(
+                "未知的模板变量 {{{unknown}}}，{notification_type} 可用变量：{}",
+                variables.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn invalid(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::NotificationTemplateInvalid,
+        message.into(),
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn parse_target(notification_type: &str, locale: &str) -> Result<NotificationType, HttpResponse> {
+    let notification_type = notification_type
+        .parse::<NotificationType>()
+        .map_err(|_| invalid(format!("未知的通知类型: {notification_type}")))?;
+    validate_locale(locale).map_err(invalid)?;
+    Ok(notification_type)
+}
+
+/// 列出所有通知类型的内置模板与自定义模板
+pub async fn list_templates(
+    service: &NotificationService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let overrides = match storage.list_notification_templates().await {
+        Ok(templates) => templates,
+        Err(e) => return Ok(internal_error(format!("查询通知模板失败: {e}"))),
+    };
+
+    let items = NotificationType::all()
+        .into_iter()
+        .map(|notification_type| {
+            let builtin = builtin_template(&notification_type);
+            NotificationTemplateInfo {
+                variables: builtin.variables.iter().map(|v| v.to_string()).collect(),
+                default_title: builtin.title.to_string(),
+                default_content: builtin.content.map(str::to_string),
+                overrides: overrides
+                    .iter()
+                    .filter(|t| t.notification_type == notification_type)
+                    .cloned()
+                    .collect(),
+                notification_type,
+            }
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        NotificationTemplateListResponse {
+            active_locale: DynamicConfig::notification_locale().await,
+            items,
+        },
+        "查询成功",
+    )))
+}
+
+/// 创建或更新通知模板
+pub async fn upsert_template(
+    service: &NotificationService,
+    request: &HttpRequest,
+    notification_type: &str,
+    locale: &str,
+    req: UpsertNotificationTemplateRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let notification_type = match parse_target(notification_type, locale) {
+        Ok(t) => t,
+        Err(resp) => return Ok(resp),
+    };
+
+    let req = UpsertNotificationTemplateRequest {
+        title: req.title.trim().to_string(),
+        content: req.content.filter(|c| !c.trim().is_empty()),
+    };
+    if let Err(message) = validate_template(&notification_type, &req) {
+        return Ok(invalid(message));
+    }
+
+    match storage
+        .upsert_notification_template(&notification_type, locale, req, user_id)
+        .await
+    {
+        Ok(template) => Ok(HttpResponse::Ok().json(ApiResponse::success(template, "保存成功"))),
+        Err(e) => Ok(internal_error(format!("保存通知模板失败: {e}"))),
+    }
+}
+
+/// 删除通知模板，恢复内置文案
+pub async fn delete_template(
+    service: &NotificationService,
+    request: &HttpRequest,
+    notification_type: &str,
+    locale: &str,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let notification_type = match parse_target(notification_type, locale) {
+        Ok(t) => t,
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage
+        .delete_notification_template(&notification_type, locale)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("删除成功"))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::NotificationTemplateNotFound,
+            "通知模板不存在",
+        ))),
+        Err(e) => Ok(internal_error(format!("删除通知模板失败: {e}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("{homework_title}：{score} 分 {} {中文} {a"),
+            vec!["homework_title", "score"]
+        );
+    }
+
+    #[test]
+    fn test_builtin_templates_use_declared_variables() {
+        for notification_type in NotificationType::all() {
+            let builtin = builtin_template(&notification_type);
+            let req = UpsertNotificationTemplateRequest {
+                title: builtin.title.to_string(),
+                content: builtin.content.map(str::to_string),
+            };
+            assert!(validate_template(&notification_type, &req).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_locale() {
+        assert!(validate_locale("zh-CN").is_ok());
+        assert!(validate_locale("en").is_ok());
+        assert!(validate_locale("").is_err());
+        assert!(validate_locale("zh/CN").is_err());
+    }
+}
@@ -1,18 +1,81 @@
 //! 通知触发辅助模块
 //!
 //! 提供异步发送通知的函数，不阻塞主业务流程。
+//! 业务代码通过 `send_templated_*` 传入模板变量，标题与内容由通知模板渲染。
 
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{error, info};
 
+use crate::errors::Result;
 use crate::models::class_users::requests::ClassUserQuery;
 use crate::models::notifications::{
     entities::{NotificationType, ReferenceType},
     requests::CreateNotificationRequest,
 };
-use crate::services::websocket::push_notification_to_users;
+use crate::services::notifications::count::invalidate_unread_count;
+use crate::services::notifications::templates::render_notification;
+use crate::services::websocket::push_notification_to_user;
 use crate::storage::Storage;
 
+/// 每批写入并推送的通知数
+const DELIVERY_BATCH_SIZE: usize = 200;
+/// 相邻两批推送之间的间隔，避免大班级的通知在同一时刻涌向 WebSocket 连接
+const DELIVERY_BATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 批量创建通知并通过 WebSocket 推送，返回创建的通知数
+///
+/// 接收人较多时按 [`DELIVERY_BATCH_SIZE`] 分批写入与推送，批次之间稍作停顿。
+/// 与 [`send_notifications`] 不同，错误会返回给调用方（发件箱转发任务据此重试，
+/// 已写入的批次会随重试重复投递，与发件箱的至少一次语义一致）。
+pub async fn deliver_notifications(
+    storage: &Arc<dyn Storage>,
+    user_ids: &[i64],
+    notification_type: &NotificationType,
+    title: String,
+    content: Option<String>,
+    reference_type: Option<&ReferenceType>,
+    reference_id: Option<i64>,
+) -> Result<usize> {
+    if user_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let mut count = 0;
+    for (index, batch) in user_ids.chunks(DELIVERY_BATCH_SIZE).enumerate() {
+        if index > 0 {
+            tokio::time::sleep(DELIVERY_BATCH_INTERVAL).await;
+        }
+
+        let requests: Vec<CreateNotificationRequest> = batch
+            .iter()
+            .map(|&user_id| CreateNotificationRequest {
+                user_id,
+                notification_type: notification_type.to_string(),
+                title: title.clone(),
+                content: content.clone(),
+                reference_type: reference_type.map(|r| r.to_string()),
+                reference_id,
+            })
+            .collect();
+
+        let notifications = storage.create_notifications_batch(requests).await?;
+        count += notifications.len();
+
+        // WebSocket 推送（每个用户推送自己的通知记录，便于按 ID 标记送达）
+        for notification in notifications {
+            invalidate_unread_count(notification.user_id).await;
+            push_notification_to_user(notification.user_id, notification);
+        }
+    }
+    info!(
+        "Created {} notifications of type {}",
+        count, notification_type
+    );
+
+    Ok(count)
+}
+
 /// 批量发送通知（异步，不阻塞）
 ///
 /// 1. 批量创建通知到数据库
@@ -27,38 +90,18 @@ pub async fn send_notifications(
     reference_type: Option<ReferenceType>,
     reference_id: Option<i64>,
 ) {
-    if user_ids.is_empty() {
-        return;
-    }
-
-    let requests: Vec<CreateNotificationRequest> = user_ids
-        .iter()
-        .map(|&user_id| CreateNotificationRequest {
-            user_id,
-            notification_type: notification_type.to_string(),
-            title: title.clone(),
-            content: content.clone(),
-            reference_type: reference_type.as_ref().map(|r| r.to_string()),
-            reference_id,
-        })
-        .collect();
-
-    match storage.create_notifications_batch(requests).await {
-        Ok(notifications) => {
-            info!(
-                "Created {} notifications of type {}",
-                notifications.len(),
-                notification_type
-            );
-
-            // WebSocket 推送
-            if let Some(first) = notifications.into_iter().next() {
-                push_notification_to_users(&user_ids, first);
-            }
-        }
-        Err(e) => {
-            error!("Failed to create notifications: {}", e);
-        }
+    if let Err(e) = deliver_notifications(
+        &storage,
+        &user_ids,
+        &notification_type,
+        title,
+        content,
+        reference_type.as_ref(),
+        reference_id,
+    )
+    .await
+    {
+        error!("Failed to create notifications: {}", e);
     }
 }
 
@@ -84,8 +127,68 @@ pub async fn send_notification(
     .await;
 }
 
+/// 按通知模板渲染后批量发送通知
+pub async fn send_templated_notifications(
+    storage: Arc<dyn Storage>,
+    user_ids: Vec<i64>,
+    notification_type: NotificationType,
+    vars: Vec<(&'static str, String)>,
+    reference_type: Option<ReferenceType>,
+    reference_id: Option<i64>,
+) {
+    if user_ids.is_empty() {
+        return;
+    }
+
+    let (title, content) = render_notification(&storage, &notification_type, &vars).await;
+    send_notifications(
+        storage,
+        user_ids,
+        notification_type,
+        title,
+        content,
+        reference_type,
+        reference_id,
+    )
+    .await;
+}
+
+/// 按通知模板渲染后发送单个通知
+pub async fn send_templated_notification(
+    storage: Arc<dyn Storage>,
+    user_id: i64,
+    notification_type: NotificationType,
+    vars: Vec<(&'static str, String)>,
+    reference_type: Option<ReferenceType>,
+    reference_id: Option<i64>,
+) {
+    send_templated_notifications(
+        storage,
+        vec![user_id],
+        notification_type,
+        vars,
+        reference_type,
+        reference_id,
+    )
+    .await;
+}
+
 /// 获取班级所有学生的 user_id 列表（排除教师角色）
 pub async fn get_class_student_ids(storage: &Arc<dyn Storage>, class_id: i64) -> Vec<i64> {
+    match fetch_class_student_ids(storage, class_id).await {
+        Ok(student_ids) => student_ids,
+        Err(e) => {
+            error!("Failed to get class students for class {}: {}", class_id, e);
+            vec![]
+        }
+    }
+}
+
+/// 获取班级所有学生的 user_id 列表，查询失败时返回错误
+pub async fn fetch_class_student_ids(
+    storage: &Arc<dyn Storage>,
+    class_id: i64,
+) -> Result<Vec<i64>> {
     use crate::models::class_users::entities::ClassUserRole;
 
     let query = ClassUserQuery {
@@ -95,19 +198,13 @@ pub async fn get_class_student_ids(storage: &Arc<dyn Storage>, class_id: i64) ->
         role: None,
     };
 
-    match storage
+    let response = storage
         .list_class_users_with_pagination(class_id, query)
-        .await
-    {
-        Ok(response) => response
-            .items
-            .into_iter()
-            .filter(|cu| cu.role != ClassUserRole::Teacher)
-            .map(|cu| cu.user_id)
-            .collect(),
-        Err(e) => {
-            error!("Failed to get class students for class {}: {}", class_id, e);
-            vec![]
-        }
-    }
+        .await?;
+    Ok(response
+        .items
+        .into_iter()
+        .filter(|cu| cu.role != ClassUserRole::Teacher)
+        .map(|cu| cu.user_id)
+        .collect())
 }
@@ -0,0 +1,71 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::{error, info};
+
+use super::{OrganizationService, require_platform_admin};
+use crate::models::{ApiResponse, ErrorCode, organizations::requests::CreateOrganizationRequest};
+use crate::utils::validate::validate_org_slug;
+
+pub async fn create_organization(
+    service: &OrganizationService,
+    mut req: CreateOrganizationRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let current_user = match require_platform_admin(request) {
+        Ok(user) => user,
+        Err(resp) => return Ok(resp),
+    };
+
+    req.name = req.name.trim().to_string();
+    if req.name.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "组织名称不能为空",
+        )));
+    }
+
+    if let Err(msg) = validate_org_slug(&req.slug) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::OrganizationSlugInvalid,
+            msg,
+        )));
+    }
+
+    let storage = service.get_storage(request);
+
+    match storage.get_organization_by_slug(&req.slug).await {
+        Ok(Some(_)) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::OrganizationSlugExists,
+                "组织标识已存在",
+            )));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询组织失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    match storage.create_organization(req).await {
+        Ok(org) => {
+            info!(
+                "Organization {} ({}) created by {}",
+                org.slug, org.id, current_user.id
+            );
+            Ok(HttpResponse::Created().json(ApiResponse::success(org, "组织创建成功")))
+        }
+        Err(e) => {
+            error!("创建组织失败: {}", e);
+            Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("创建组织失败: {e}"),
+                )),
+            )
+        }
+    }
+}
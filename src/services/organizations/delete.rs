@@ -0,0 +1,53 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{OrganizationService, require_platform_admin};
+use crate::middlewares::TenantGuard;
+use crate::models::{ApiResponse, ErrorCode};
+
+pub async fn delete_organization(
+    service: &OrganizationService,
+    org_id: i64,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(resp) = require_platform_admin(request) {
+        return Ok(resp);
+    }
+
+    let storage = service.get_storage(request);
+
+    // 组织下仍有用户时禁止删除，避免数据落入默认租户
+    match storage.count_organization_users(org_id).await {
+        Ok(0) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::OrganizationNotEmpty,
+                "组织下仍有用户，无法删除",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("统计组织用户失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    match storage.delete_organization(org_id).await {
+        Ok(true) => {
+            TenantGuard::invalidate(org_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("组织删除成功")))
+        }
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::OrganizationNotFound,
+            "组织不存在",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("删除组织失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -0,0 +1,71 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::OrganizationService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::{ApiResponse, ErrorCode, organizations::responses::OrganizationResponse};
+
+pub async fn get_organization(
+    service: &OrganizationService,
+    org_id: i64,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    // 非平台管理员只能查看自己所属的组织
+    if !TenantGuard::can_access(request, Some(org_id)) {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::OrganizationNotFound,
+            "组织不存在",
+        )));
+    }
+
+    let storage = service.get_storage(request);
+
+    let organization = match storage.get_organization_by_id(org_id).await {
+        Ok(Some(org)) => org,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::OrganizationNotFound,
+                "组织不存在",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询组织失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let user_count = storage.count_organization_users(org_id).await.unwrap_or(0);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        OrganizationResponse {
+            organization,
+            user_count,
+        },
+        "查询成功",
+    )))
+}
+
+pub async fn get_my_organization(
+    service: &OrganizationService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let org_id = match RequireJWT::extract_user_claims(request) {
+        Some(user) => user.org_id,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录")));
+        }
+    };
+
+    // 默认租户的用户不属于任何组织
+    match org_id {
+        Some(org_id) => get_organization(service, org_id, request).await,
+        None => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::OrganizationNotFound,
+            "当前用户不属于任何组织",
+        ))),
+    }
+}
@@ -0,0 +1,41 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{OrganizationService, require_platform_admin};
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::{
+    ApiResponse, ErrorCode,
+    organizations::requests::{OrganizationListParams, OrganizationListQuery},
+};
+
+pub async fn list_organizations(
+    service: &OrganizationService,
+    query: OrganizationListParams,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(resp) = require_platform_admin(request) {
+        return Ok(resp);
+    }
+
+    if let Err(limit) = PaginationPolicy::for_endpoint("organizations").check(query.pagination.size)
+    {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let storage = service.get_storage(request);
+
+    let list_query = OrganizationListQuery {
+        page: Some(query.pagination.page),
+        size: query.pagination.size,
+        search: query.search,
+    };
+
+    match storage.list_organizations_with_pagination(list_query).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功"))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询组织列表失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -0,0 +1,102 @@
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod list;
+pub mod update;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::middlewares::RequireJWT;
+use crate::models::organizations::requests::{
+    CreateOrganizationRequest, OrganizationListParams, UpdateOrganizationRequest,
+};
+use crate::models::users::entities::User;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+pub struct OrganizationService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl OrganizationService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    // 获取组织列表
+    pub async fn list_organizations(
+        &self,
+        query: OrganizationListParams,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        list::list_organizations(self, query, request).await
+    }
+
+    // 创建组织
+    pub async fn create_organization(
+        &self,
+        req: CreateOrganizationRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        create::create_organization(self, req, request).await
+    }
+
+    // 获取组织详情
+    pub async fn get_organization(
+        &self,
+        org_id: i64,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        get::get_organization(self, org_id, request).await
+    }
+
+    // 获取当前用户所属组织
+    pub async fn get_my_organization(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        get::get_my_organization(self, request).await
+    }
+
+    // 更新组织
+    pub async fn update_organization(
+        &self,
+        org_id: i64,
+        req: UpdateOrganizationRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        update::update_organization(self, org_id, req, request).await
+    }
+
+    // 删除组织
+    pub async fn delete_organization(
+        &self,
+        org_id: i64,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        delete::delete_organization(self, org_id, request).await
+    }
+}
+
+/// 校验当前用户为平台管理员（不属于任何组织的系统管理员）
+fn require_platform_admin(request: &HttpRequest) -> Result<User, HttpResponse> {
+    match RequireJWT::extract_user_claims(request) {
+        Some(user) if user.is_platform_admin() => Ok(user),
+        Some(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::PermissionDenied,
+            "仅平台管理员可执行此操作",
+        ))),
+        None => Err(HttpResponse::Unauthorized()
+            .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录"))),
+    }
+}
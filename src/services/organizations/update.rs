@@ -0,0 +1,67 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::OrganizationService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::{
+    ApiResponse, ErrorCode, organizations::requests::UpdateOrganizationRequest,
+    users::entities::UserRole,
+};
+
+pub async fn update_organization(
+    service: &OrganizationService,
+    org_id: i64,
+    req: UpdateOrganizationRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let current_user = match RequireJWT::extract_user_claims(request) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录")));
+        }
+    };
+
+    // 组织管理员只能管理本组织
+    if current_user.role != UserRole::Admin || !TenantGuard::can_access(request, Some(org_id)) {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::PermissionDenied,
+            "无权修改该组织",
+        )));
+    }
+
+    // 启用/停用组织仅平台管理员可操作
+    if req.is_active.is_some() && !current_user.is_platform_admin() {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::PermissionDenied,
+            "仅平台管理员可启用或停用组织",
+        )));
+    }
+
+    if let Some(ref name) = req.name
+        && name.trim().is_empty()
+    {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "组织名称不能为空",
+        )));
+    }
+
+    let storage = service.get_storage(request);
+
+    match storage.update_organization(org_id, req).await {
+        Ok(Some(org)) => {
+            TenantGuard::invalidate(org_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success(org, "组织更新成功")))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::OrganizationNotFound,
+            "组织不存在",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("更新组织失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -0,0 +1,75 @@
+//! 外部调用统计的 Prometheus 文本格式导出
+//!
+//! 随 WebSocket 指标一起由 `/ws/metrics` 导出，按 `target` 标签区分调用目标。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{OutboundTarget, is_circuit_open};
+use crate::services::websocket::metrics::write_metric;
+
+fn samples(counter: impl Fn(OutboundTarget) -> u64) -> Vec<(String, u64)> {
+    OutboundTarget::ALL
+        .iter()
+        .map(|target| (format!("target=\"{}\"", target.as_str()), counter(*target)))
+        .collect()
+}
+
+fn load(counter: &AtomicU64) -> u64 {
+    counter.load(Ordering::Relaxed)
+}
+
+/// 以 Prometheus 文本格式导出各目标的调用与熔断统计
+pub fn render_prometheus() -> String {
+    let mut out = String::new();
+
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_requests_total",
+        "counter",
+        "Outbound HTTP attempts, including retries.",
+        &samples(|t| load(&t.state().requests)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_failures_total",
+        "counter",
+        "Outbound attempts that failed (error, timeout, 5xx or 429).",
+        &samples(|t| load(&t.state().failures)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_timeouts_total",
+        "counter",
+        "Outbound attempts that timed out.",
+        &samples(|t| load(&t.state().timeouts)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_retries_total",
+        "counter",
+        "Outbound retries after a failed attempt.",
+        &samples(|t| load(&t.state().retries)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_rejected_total",
+        "counter",
+        "Outbound calls rejected without a request while the circuit was open.",
+        &samples(|t| load(&t.state().rejected)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_circuit_opened_total",
+        "counter",
+        "Times the circuit breaker opened.",
+        &samples(|t| load(&t.state().circuit_opened)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_outbound_circuit_open",
+        "gauge",
+        "Whether the circuit breaker is currently open (1) or closed (0).",
+        &samples(|t| is_circuit_open(t) as u64),
+    );
+    out
+}
@@ -0,0 +1,420 @@
+//! 外部 HTTP 调用
+//!
+//! 人机验证、外部审核、文档预览、IM 机器人、Git 元数据抓取与定时导出投递共用这里的出站客户端：每个目标使用各自配置节中的
+//! 超时，按 `outbound.retries` 对超时、连接失败与 5xx/429 响应退避重试，并各自维护一个熔断器。
+//! 连续失败达到 `outbound.failure_threshold` 后熔断 `outbound.open_duration` 秒，期间调用直接失败，
+//! 不再让请求处理或后台任务等待超时；冷却结束后放行一次试探请求，成功则恢复，失败则继续熔断。
+
+pub mod metrics;
+pub mod public;
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use reqwest::StatusCode;
+
+use crate::config::AppConfig;
+
+/// 外部调用目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundTarget {
+    /// 人机验证服务商校验接口
+    Captcha,
+    /// 外部内容审核接口
+    Moderation,
+    /// 文档预览转换服务
+    Preview,
+    /// IM 机器人接口
+    Im,
+    /// 代码托管平台接口（提交外部引用的元数据）
+    Git,
+    /// 定时导出投递（邮件中继或 Webhook）
+    Export,
+}
+
+impl OutboundTarget {
+    pub const ALL: [Self; 6] = [
+        Self::Captcha,
+        Self::Moderation,
+        Self::Preview,
+        Self::Im,
+        Self::Git,
+        Self::Export,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Captcha => "captcha",
+            Self::Moderation => "moderation",
+            Self::Preview => "preview",
+            Self::Im => "im",
+            Self::Git => "git",
+            Self::Export => "export",
+        }
+    }
+
+    /// 目标的单次请求超时（秒），沿用各自配置节
+    fn timeout(&self) -> u64 {
+        let config = AppConfig::get();
+        match self {
+            Self::Captcha => config.captcha.verify_timeout,
+            Self::Moderation => config.moderation.api_timeout,
+            Self::Preview => config.preview.timeout,
+            Self::Im => config.im.request_timeout,
+            Self::Git => config.submission_references.api_timeout,
+            Self::Export => config.scheduled_exports.request_timeout,
+        }
+    }
+
+    /// 未在 `outbound.retries` 中配置时的重试次数
+    ///
+    /// 验证令牌只能校验一次，IM 消息由投递队列自行退避重试，文档转换耗时较长，
+    /// Git 元数据只是可选快照、不应拖慢提交，定时导出失败后由下一次执行重新投递，五者默认不重试。
+    fn default_retries(&self) -> u32 {
+        match self {
+            Self::Moderation => 1,
+            Self::Captcha | Self::Preview | Self::Im | Self::Git | Self::Export => 0,
+        }
+    }
+
+    fn retries(&self) -> u32 {
+        AppConfig::get()
+            .outbound
+            .retries
+            .get(self.as_str())
+            .copied()
+            .unwrap_or_else(|| self.default_retries())
+    }
+
+    fn state(&self) -> &'static TargetState {
+        &STATES[*self as usize]
+    }
+}
+
+impl fmt::Display for OutboundTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 外部调用失败
+#[derive(Debug)]
+pub enum OutboundError {
+    /// 熔断中，未发出请求
+    CircuitOpen(OutboundTarget),
+    /// 请求超时
+    Timeout,
+    /// 其他请求错误（已去除 URL，避免泄露地址中的令牌）
+    Request(String),
+}
+
+impl fmt::Display for OutboundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CircuitOpen(target) => write!(f, "{target} 外部服务连续失败，已暂停调用"),
+            Self::Timeout => f.write_str("请求超时"),
+            Self::Request(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<reqwest::Error> for OutboundError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Request(e.without_url().to_string())
+        }
+    }
+}
+
+/// 熔断器状态
+#[derive(Debug)]
+struct Breaker {
+    consecutive_failures: u32,
+    /// 熔断截止时间
+    open_until: Option<Instant>,
+    /// 冷却结束后已放行试探请求
+    probing: bool,
+}
+
+impl Breaker {
+    const fn new() -> Self {
+        Self {
+            consecutive_failures: 0,
+            open_until: None,
+            probing: false,
+        }
+    }
+
+    /// 是否允许发出请求
+    ///
+    /// 放行试探请求时同时顺延熔断截止时间：试探请求被取消而未回报结果时，
+    /// 下一个冷却期结束后再放行新的试探，不会一直停留在熔断状态。
+    fn allow(&mut self, now: Instant, open_for: Duration) -> bool {
+        match self.open_until {
+            None => true,
+            Some(until) if now < until => false,
+            Some(_) => {
+                self.probing = true;
+                self.open_until = Some(now + open_for);
+                true
+            }
+        }
+    }
+
+    fn record_success(&mut self) {
+        *self = Self::new();
+    }
+
+    /// 记录一次失败，返回是否因此进入熔断
+    fn record_failure(&mut self, now: Instant, threshold: u32, open_for: Duration) -> bool {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        let trip = self.probing
+            || (threshold > 0
+                && self.open_until.is_none()
+                && self.consecutive_failures >= threshold);
+        if trip {
+            self.open_until = Some(now + open_for);
+            self.probing = false;
+        }
+        trip
+    }
+
+    /// 当前是否拒绝调用
+    fn is_open(&self, now: Instant) -> bool {
+        self.open_until.is_some_and(|until| now < until)
+    }
+}
+
+/// 单个目标的熔断器与统计
+struct TargetState {
+    breaker: Mutex<Breaker>,
+    requests: AtomicU64,
+    failures: AtomicU64,
+    timeouts: AtomicU64,
+    retries: AtomicU64,
+    rejected: AtomicU64,
+    circuit_opened: AtomicU64,
+}
+
+impl TargetState {
+    const fn new() -> Self {
+        Self {
+            breaker: Mutex::new(Breaker::new()),
+            requests: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            circuit_opened: AtomicU64::new(0),
+        }
+    }
+
+    fn breaker(&self) -> std::sync::MutexGuard<'_, Breaker> {
+        self.breaker.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn allow(&self) -> bool {
+        let open_for = Duration::from_secs(AppConfig::get().outbound.open_duration);
+        let allowed = self.breaker().allow(Instant::now(), open_for);
+        if !allowed {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+        }
+        allowed
+    }
+
+    fn record_success(&self) {
+        self.breaker().record_success();
+    }
+
+    fn record_failure(&self, target: OutboundTarget) {
+        let config = &AppConfig::get().outbound;
+        let tripped = self.breaker().record_failure(
+            Instant::now(),
+            config.failure_threshold,
+            Duration::from_secs(config.open_duration),
+        );
+        if tripped {
+            self.circuit_opened.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                "外部服务 {target} 连续调用失败，暂停调用 {} 秒",
+                config.open_duration
+            );
+        }
+    }
+}
+
+static STATES: [TargetState; OutboundTarget::ALL.len()] =
+    [const { TargetState::new() }; OutboundTarget::ALL.len()];
+
+/// 出站 HTTP 客户端
+pub struct OutboundClient {
+    target: OutboundTarget,
+    client: reqwest::Client,
+}
+
+impl OutboundClient {
+    pub fn new(target: OutboundTarget) -> Self {
+        Self::build(target, |builder| builder)
+    }
+
+    /// 请求用户提供地址的客户端：只连接公网地址，不跟随重定向
+    pub fn public_only(target: OutboundTarget) -> Self {
+        Self::build(target, |builder| {
+            builder
+                .redirect(reqwest::redirect::Policy::none())
+                .dns_resolver(Arc::new(public::PublicResolver))
+        })
+    }
+
+    fn build(
+        target: OutboundTarget,
+        configure: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder,
+    ) -> Self {
+        let client =
+            configure(reqwest::Client::builder().timeout(Duration::from_secs(target.timeout())))
+                .build()
+                .expect("Failed to build outbound HTTP client");
+        Self { target, client }
+    }
+
+    /// 发送请求
+    ///
+    /// `build` 每次尝试都会调用一次，以便重新构造请求体（multipart 等请求体不能复用）。
+    /// 重试用尽后仍为 5xx/429 时返回该响应，由调用方按状态码处理。
+    pub async fn send<F>(&self, build: F) -> Result<reqwest::Response, OutboundError>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    {
+        let state = self.target.state();
+        if !state.allow() {
+            return Err(OutboundError::CircuitOpen(self.target));
+        }
+
+        let retries = self.target.retries();
+        let mut backoff = Duration::from_millis(AppConfig::get().outbound.retry_backoff_ms);
+        let mut attempt = 0;
+        loop {
+            state.requests.fetch_add(1, Ordering::Relaxed);
+            let result = build(&self.client).send().await;
+            let retryable = match &result {
+                Ok(response) => is_retryable_status(response.status()),
+                Err(e) => {
+                    if e.is_timeout() {
+                        state.timeouts.fetch_add(1, Ordering::Relaxed);
+                    }
+                    e.is_timeout() || e.is_connect()
+                }
+            };
+            let failed = retryable || result.is_err();
+            if !failed {
+                state.record_success();
+                return result.map_err(OutboundError::from);
+            }
+
+            state.failures.fetch_add(1, Ordering::Relaxed);
+            if !retryable || attempt >= retries {
+                state.record_failure(self.target);
+                return result.map_err(OutboundError::from);
+            }
+            attempt += 1;
+            state.retries.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// 目标当前是否处于熔断状态（冷却结束、等待试探时返回 `false`）
+pub fn is_circuit_open(target: OutboundTarget) -> bool {
+    target.state().breaker().is_open(Instant::now())
+}
+
+/// 各目标共用的客户端实例
+pub static CAPTCHA_CLIENT: Lazy<OutboundClient> =
+    Lazy::new(|| OutboundClient::new(OutboundTarget::Captcha));
+pub static MODERATION_CLIENT: Lazy<OutboundClient> =
+    Lazy::new(|| OutboundClient::new(OutboundTarget::Moderation));
+pub static PREVIEW_CLIENT: Lazy<OutboundClient> =
+    Lazy::new(|| OutboundClient::new(OutboundTarget::Preview));
+pub static IM_CLIENT: Lazy<OutboundClient> = Lazy::new(|| OutboundClient::new(OutboundTarget::Im));
+pub static GIT_CLIENT: Lazy<OutboundClient> =
+    Lazy::new(|| OutboundClient::new(OutboundTarget::Git));
+/// 定时导出的邮件中继由管理员配置，可以位于内网
+pub static MAIL_RELAY_CLIENT: Lazy<OutboundClient> =
+    Lazy::new(|| OutboundClient::new(OutboundTarget::Export));
+/// 定时导出的 Webhook 地址由教师填写，只允许公网地址
+pub static EXPORT_WEBHOOK_CLIENT: Lazy<OutboundClient> =
+    Lazy::new(|| OutboundClient::public_only(OutboundTarget::Export));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_after_threshold() {
+        let now = Instant::now();
+        let open_for = Duration::from_secs(30);
+        let mut breaker = Breaker::new();
+
+        assert!(!breaker.record_failure(now, 3, open_for));
+        assert!(!breaker.record_failure(now, 3, open_for));
+        breaker.record_success();
+        assert!(!breaker.record_failure(now, 3, open_for));
+        assert!(!breaker.record_failure(now, 3, open_for));
+        assert!(breaker.record_failure(now, 3, open_for));
+        assert!(breaker.is_open(now));
+        assert!(!breaker.allow(now + Duration::from_secs(10), open_for));
+        assert!(!breaker.is_open(now + open_for));
+    }
+
+    #[test]
+    fn test_breaker_half_open_probe() {
+        let now = Instant::now();
+        let open_for = Duration::from_secs(30);
+        let mut breaker = Breaker::new();
+        assert!(breaker.record_failure(now, 1, open_for));
+
+        // 冷却结束后只放行一个试探请求
+        let later = now + open_for;
+        assert!(breaker.allow(later, open_for));
+        assert!(!breaker.allow(later, open_for));
+
+        // 试探失败继续熔断
+        assert!(breaker.record_failure(later, 1, open_for));
+        assert!(!breaker.allow(later + Duration::from_secs(1), open_for));
+
+        // 试探成功恢复
+        let recovered = later + open_for;
+        assert!(breaker.allow(recovered, open_for));
+        breaker.record_success();
+        assert!(!breaker.is_open(recovered));
+        assert!(breaker.allow(recovered, open_for));
+    }
+
+    #[test]
+    fn test_breaker_disabled() {
+        let now = Instant::now();
+        let mut breaker = Breaker::new();
+        for _ in 0..100 {
+            assert!(!breaker.record_failure(now, 0, Duration::from_secs(30)));
+        }
+        assert!(breaker.allow(now, Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::OK));
+    }
+}
@@ -0,0 +1,161 @@
+//! 用户提供的出站地址校验
+//!
+//! 定时导出 Webhook 等地址由用户填写，服务器会主动向其发送数据。为避免被用来探测内网服务，
+//! 这类地址只允许指向公网：保存时与每次投递前解析主机名并拒绝内网地址，连接时再由
+//! [`PublicResolver`] 过滤解析结果，防止校验后 DNS 记录被改指向内网（DNS 重绑定）。
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+use reqwest::Url;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+/// 地址校验失败原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicUrlError {
+    /// 地址格式无效或缺少主机名
+    Invalid,
+    /// 主机名无法解析
+    Unresolved,
+    /// 指向回环、私有、链路本地等内网地址
+    Internal,
+}
+
+impl fmt::Display for PublicUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublicUrlError::Invalid => write!(f, "地址格式无效"),
+            PublicUrlError::Unresolved => write!(f, "无法解析主机名"),
+            PublicUrlError::Internal => write!(f, "不允许访问内网地址"),
+        }
+    }
+}
+
+/// 是否为公网地址
+///
+/// 拒绝未指定、回环、私有、运营商级 NAT、链路本地、唯一本地、组播与广播地址；
+/// IPv4 映射的 IPv6 地址按 IPv4 判断。
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public_ipv4(v4),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_multicast()
+        || ip.is_broadcast()
+        // 0.0.0.0/8
+        || a == 0
+        // 100.64.0.0/10 运营商级 NAT
+        || (a == 100 && (b & 0xc0) == 64))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 唯一本地地址
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10 链路本地地址
+        || (first & 0xffc0) == 0xfe80)
+}
+
+/// 解析主机名（阻塞的系统解析放到线程池执行）
+async fn lookup(host: String, port: u16) -> std::io::Result<Vec<SocketAddr>> {
+    tokio::task::spawn_blocking(move || {
+        (host.as_str(), port)
+            .to_socket_addrs()
+            .map(|addrs| addrs.collect())
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// 校验地址的主机只指向公网
+///
+/// IP 字面量直接判断；主机名解析到的任一地址为内网地址时拒绝。
+pub async fn check_public_url(url: &str) -> Result<(), PublicUrlError> {
+    let url = Url::parse(url).map_err(|_| PublicUrlError::Invalid)?;
+    let host = url.host_str().ok_or(PublicUrlError::Invalid)?;
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    // IPv6 字面量带方括号
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = literal.parse::<IpAddr>() {
+        return if is_public_ip(ip) {
+            Ok(())
+        } else {
+            Err(PublicUrlError::Internal)
+        };
+    }
+
+    let addrs = lookup(host.to_string(), port)
+        .await
+        .map_err(|_| PublicUrlError::Unresolved)?;
+    if addrs.is_empty() {
+        return Err(PublicUrlError::Unresolved);
+    }
+    if addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+        return Err(PublicUrlError::Internal);
+    }
+    Ok(())
+}
+
+/// 只返回公网地址的 DNS 解析器
+///
+/// 主机名解析结果全部为内网地址时连接失败。
+pub(super) struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = lookup(host.clone(), 0)
+                .await?
+                .into_iter()
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{host} 未解析到公网地址").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_internal_addresses_rejected() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["93.184.216.34", "2606:2800:220:1::1"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+}
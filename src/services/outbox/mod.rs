@@ -0,0 +1,9 @@
+//! 事务发件箱
+//!
+//! 通知、WebSocket 推送与 IM 群消息不再在业务写入后直接发送，而是以发件箱事件形式
+//! 与业务变更在同一事务中写入 `outbox_events`，再由 [`relay`] 后台任务投递并标记完成。
+//! 进程在提交后崩溃时事件仍保留在表中，重启后继续投递（至少一次语义）。
+
+pub mod relay;
+
+pub use relay::{relay_due_events, spawn_outbox_relay};
@@ -0,0 +1,207 @@
+//! 发件箱转发任务
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::outbox::entities::{OutboxEvent, OutboxMessage};
+use crate::services::im_delivery::enqueue_homework_event;
+use crate::services::jobs;
+use crate::services::notifications::templates::render_notification;
+use crate::services::notifications::trigger::{deliver_notifications, fetch_class_student_ids};
+use crate::storage::Storage;
+
+/// 发件箱轮询间隔
+const RELAY_INTERVAL: Duration = Duration::from_secs(2);
+/// 每轮最多投递的事件数
+const RELAY_BATCH_SIZE: u64 = 100;
+/// 最多尝试次数，超过后标记为失败
+const MAX_ATTEMPTS: i32 = 10;
+/// 首次重试等待时间（秒），之后每次翻倍
+const RETRY_BASE_SECS: i64 = 10;
+/// 最长重试等待时间（秒）
+const RETRY_MAX_SECS: i64 = 1800;
+
+/// 启动发件箱转发任务
+pub fn spawn_outbox_relay(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RELAY_INTERVAL);
+        loop {
+            interval.tick().await;
+            relay_due_events(&storage).await;
+        }
+    });
+}
+
+/// 第 `attempts` 次失败后的重试等待时间
+fn retry_delay_secs(attempts: i32) -> i64 {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    (RETRY_BASE_SECS << exponent).min(RETRY_MAX_SECS)
+}
+
+/// 投递一批到期事件，返回成功投递的数量
+pub async fn relay_due_events(storage: &Arc<dyn Storage>) -> usize {
+    let now = chrono::Utc::now().timestamp();
+    let messages = match storage.list_due_outbox_events(now, RELAY_BATCH_SIZE).await {
+        Ok(messages) => messages,
+        Err(e) => {
+            tracing::warn!("Failed to list due outbox events: {e}");
+            return 0;
+        }
+    };
+
+    let mut delivered = 0;
+    for message in messages {
+        if relay(storage, &message).await {
+            delivered += 1;
+        }
+    }
+    delivered
+}
+
+async fn relay(storage: &Arc<dyn Storage>, message: &OutboxMessage) -> bool {
+    let job_id = message.event.as_ref().ok().and_then(OutboxEvent::job_id);
+    if let Some(job_id) = job_id {
+        jobs::mark_job_running(job_id).await;
+    }
+
+    let result = match &message.event {
+        Ok(event) => dispatch(storage, event).await,
+        Err(error) => Err(error.clone()),
+    };
+
+    let (update, delivered) = match result {
+        Ok(()) => {
+            if let Some(job_id) = job_id {
+                jobs::finish_job(job_id, Ok(())).await;
+            }
+            (storage.mark_outbox_event_delivered(message).await, true)
+        }
+        Err(error) => {
+            let attempts = message.attempts + 1;
+            // 负载无法解析时重试没有意义
+            let retry_at = (message.event.is_ok() && attempts < MAX_ATTEMPTS)
+                .then(|| chrono::Utc::now().timestamp() + retry_delay_secs(attempts));
+            // 等待重试期间任务保持执行中，重试次数耗尽后才标记失败
+            if retry_at.is_none()
+                && let Some(job_id) = job_id
+            {
+                jobs::finish_job(job_id, Err(error.clone())).await;
+            }
+            tracing::warn!(
+                "Outbox event {} ({}) failed (attempt {attempts}): {error}",
+                message.id,
+                message.event_type
+            );
+            (
+                storage
+                    .mark_outbox_event_failed(message, &error, retry_at)
+                    .await,
+                false,
+            )
+        }
+    };
+    if let Err(e) = update {
+        tracing::warn!("Failed to update outbox event {}: {e}", message.id);
+    }
+    delivered
+}
+
+async fn dispatch(storage: &Arc<dyn Storage>, event: &OutboxEvent) -> Result<(), String> {
+    match event {
+        OutboxEvent::Notification {
+            user_ids,
+            notification_type,
+            vars,
+            reference_type,
+            reference_id,
+        } => {
+            notify(
+                storage,
+                user_ids,
+                notification_type,
+                vars,
+                reference_type.as_ref(),
+                *reference_id,
+            )
+            .await
+        }
+        OutboxEvent::ClassNotification {
+            class_id,
+            notification_type,
+            vars,
+            reference_type,
+            reference_id,
+            ..
+        } => {
+            let student_ids = fetch_class_student_ids(storage, *class_id)
+                .await
+                .map_err(|e| format!("查询班级学生失败: {e}"))?;
+            notify(
+                storage,
+                &student_ids,
+                notification_type,
+                vars,
+                reference_type.as_ref(),
+                *reference_id,
+            )
+            .await
+        }
+        OutboxEvent::HomeworkImEvent { homework_id, event } => {
+            let homework = match storage.get_homework_by_id(*homework_id).await {
+                Ok(Some(homework)) => homework,
+                // 作业已删除，无需再转发
+                Ok(None) => return Ok(()),
+                Err(e) => return Err(format!("查询作业失败: {e}")),
+            };
+            enqueue_homework_event(storage, *event, &homework)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("写入 IM 投递队列失败: {e}"))
+        }
+    }
+}
+
+async fn notify(
+    storage: &Arc<dyn Storage>,
+    user_ids: &[i64],
+    notification_type: &NotificationType,
+    vars: &[(String, String)],
+    reference_type: Option<&ReferenceType>,
+    reference_id: Option<i64>,
+) -> Result<(), String> {
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let vars: Vec<(&str, String)> = vars
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.clone()))
+        .collect();
+    let (title, content) = render_notification(storage, notification_type, &vars).await;
+    deliver_notifications(
+        storage,
+        user_ids,
+        notification_type,
+        title,
+        content,
+        reference_type,
+        reference_id,
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| format!("创建通知失败: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay_secs(1), 10);
+        assert_eq!(retry_delay_secs(2), 20);
+        assert_eq!(retry_delay_secs(5), 160);
+        assert_eq!(retry_delay_secs(20), RETRY_MAX_SECS);
+    }
+}
@@ -0,0 +1,57 @@
+//! 响应组装
+//!
+//! 列表与详情响应中附带的用户、附件等关联信息统一在这里按 ID 批量加载，再经响应类型的
+//! `From` 实现（如 `HomeworkCreator: From<User>`、`FileInfo: From<File>`）转换。处理器先收集
+//! 所需 ID、一次查询得到映射后再组装条目，避免逐条查询关联数据（N+1）。
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::errors::Result;
+use crate::models::files::entities::File;
+use crate::models::users::entities::User;
+use crate::storage::Storage;
+
+/// 批量加载用户并转换为响应类型，按用户 ID 索引（不存在的用户不在结果中）
+pub async fn load_users<T: From<User>>(
+    storage: &Arc<dyn Storage>,
+    user_ids: impl IntoIterator<Item = i64>,
+) -> Result<HashMap<i64, T>> {
+    let ids: Vec<i64> = user_ids
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    Ok(storage
+        .list_users_by_ids(&ids)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, T::from(user)))
+        .collect())
+}
+
+/// 加载单个用户并转换为响应类型，查询失败或用户不存在时返回 `None`
+pub async fn load_user<T: From<User>>(storage: &Arc<dyn Storage>, user_id: i64) -> Option<T> {
+    load_users(storage, [user_id])
+        .await
+        .ok()
+        .and_then(|mut users| users.remove(&user_id))
+}
+
+/// 批量加载文件并转换为响应类型，按文件 ID 索引（不存在的文件不在结果中）
+pub async fn load_files<T: From<File>>(
+    storage: &Arc<dyn Storage>,
+    file_ids: impl IntoIterator<Item = i64>,
+) -> Result<HashMap<i64, T>> {
+    let ids: Vec<i64> = file_ids
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    Ok(storage
+        .list_files_by_ids(&ids)
+        .await?
+        .into_iter()
+        .map(|file| (file.id, T::from(file)))
+        .collect())
+}
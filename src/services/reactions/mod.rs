@@ -0,0 +1,55 @@
+//! 表情回应服务
+//!
+//! 班级成员可以对班级公告、能查看评语的用户可以对评语添加表情回应。回应变化后通过
+//! WebSocket 向能看到该内容的在线用户推送 `reaction_updated`；公告与评分的响应中附带回应汇总。
+
+pub mod react;
+mod summaries;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::models::reactions::requests::ReactionRequest;
+use crate::storage::Storage;
+
+pub use summaries::load_reaction_summaries;
+
+pub struct ReactionService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl ReactionService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    /// 添加表情回应
+    pub async fn add_reaction(
+        &self,
+        request: &HttpRequest,
+        req: ReactionRequest,
+    ) -> ActixResult<HttpResponse> {
+        react::update_reaction(self, request, req, true).await
+    }
+
+    /// 取消表情回应
+    pub async fn remove_reaction(
+        &self,
+        request: &HttpRequest,
+        req: ReactionRequest,
+    ) -> ActixResult<HttpResponse> {
+        react::update_reaction(self, request, req, false).await
+    }
+}
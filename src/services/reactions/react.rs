@@ -0,0 +1,201 @@
+//! 添加与取消表情回应
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::ReactionService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::ActivityEventType;
+use crate::models::notifications::requests::NotificationRecipientFilter;
+use crate::models::reactions::entities::ReactionTargetType;
+use crate::models::reactions::requests::ReactionRequest;
+use crate::models::reactions::responses::ReactionUpdate;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::websocket::push_reaction_update;
+use crate::storage::Storage;
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn target_not_found(message: &str) -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::ReactionTargetNotFound,
+        message,
+    ))
+}
+
+/// 校验用户能否回应目标，返回应收到回应变化推送的用户
+async fn resolve_audience(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    user_id: i64,
+    target_type: ReactionTargetType,
+    target_id: i64,
+) -> Result<Vec<i64>, HttpResponse> {
+    let is_admin = RequireJWT::extract_user_role(request) == Some(UserRole::Admin);
+
+    match target_type {
+        // 班级成员与管理员可以回应班级公告，推送给全体班级成员
+        ReactionTargetType::Announcement => {
+            let event = match storage.get_activity_event_by_id(target_id).await {
+                Ok(Some(event)) if event.event_type == ActivityEventType::Announcement => event,
+                Ok(_) => return Err(target_not_found("公告不存在")),
+                Err(e) => return Err(internal_error(format!("查询班级动态失败: {e}"))),
+            };
+            match storage.get_class_by_id(event.class_id).await {
+                Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+                Ok(_) => return Err(target_not_found("公告不存在")),
+                Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+            }
+            if !is_admin {
+                match RequireClassRole::class_user(request, storage, user_id, event.class_id).await
+                {
+                    Ok(Some(_)) => {}
+                    Ok(None) => {
+                        return Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                            ErrorCode::ClassPermissionDenied,
+                            "只有班级成员可以回应班级公告",
+                        )));
+                    }
+                    Err(e) => return Err(internal_error(format!("查询班级成员失败: {e}"))),
+                }
+            }
+            storage
+                .resolve_notification_recipients(
+                    event.class_id,
+                    &NotificationRecipientFilter::default(),
+                )
+                .await
+                .map_err(|e| internal_error(format!("查询班级成员失败: {e}")))
+        }
+        // 能查看评语的用户（提交者、评分人、班级教师、管理员）可以回应，推送给提交者与评分人
+        ReactionTargetType::GradeComment => {
+            let grade = match storage.get_grade_by_id(target_id).await {
+                Ok(Some(grade))
+                    if grade
+                        .comment
+                        .as_deref()
+                        .is_some_and(|c| !c.trim().is_empty()) =>
+                {
+                    grade
+                }
+                Ok(_) => return Err(target_not_found("评语不存在")),
+                Err(e) => return Err(internal_error(format!("查询评分失败: {e}"))),
+            };
+            let submission = match storage.get_submission_by_id(grade.submission_id).await {
+                Ok(Some(submission)) => submission,
+                Ok(None) => return Err(target_not_found("评语不存在")),
+                Err(e) => return Err(internal_error(format!("查询提交失败: {e}"))),
+            };
+            let class_id = match storage.get_homework_by_id(submission.homework_id).await {
+                Ok(Some(homework)) => homework.class_id,
+                Ok(None) => return Err(target_not_found("评语不存在")),
+                Err(e) => return Err(internal_error(format!("查询作业失败: {e}"))),
+            };
+            match storage.get_class_by_id(class_id).await {
+                Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+                Ok(_) => return Err(target_not_found("评语不存在")),
+                Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+            }
+            if !is_admin && user_id != submission.creator_id && user_id != grade.grader_id {
+                match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+                    Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+                    Ok(_) => {
+                        return Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                            ErrorCode::ClassPermissionDenied,
+                            "没有查看该评语的权限",
+                        )));
+                    }
+                    Err(e) => return Err(internal_error(format!("查询班级成员失败: {e}"))),
+                }
+            }
+            let mut audience = vec![submission.creator_id, grade.grader_id];
+            audience.dedup();
+            Ok(audience)
+        }
+    }
+}
+
+/// 添加（`add = true`）或取消表情回应，返回目标最新的回应汇总
+pub async fn update_reaction(
+    service: &ReactionService,
+    request: &HttpRequest,
+    req: ReactionRequest,
+    add: bool,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    if let Err(message) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ReactionEmojiInvalid,
+            message,
+        )));
+    }
+    let emoji = req.emoji.trim();
+
+    let audience =
+        match resolve_audience(&storage, request, user_id, req.target_type, req.target_id).await {
+            Ok(audience) => audience,
+            Err(resp) => return Ok(resp),
+        };
+
+    let changed = if add {
+        storage
+            .add_reaction(user_id, req.target_type, req.target_id, emoji)
+            .await
+    } else {
+        storage
+            .remove_reaction(user_id, req.target_type, req.target_id, emoji)
+            .await
+    };
+    let changed = match changed {
+        Ok(changed) => changed,
+        Err(e) => return Ok(internal_error(format!("更新表情回应失败: {e}"))),
+    };
+
+    let summary = match storage
+        .get_reaction_summaries(req.target_type, &[req.target_id], user_id)
+        .await
+    {
+        Ok(mut summaries) => summaries.remove(&req.target_id).unwrap_or_default(),
+        Err(e) => return Ok(internal_error(format!("查询表情回应失败: {e}"))),
+    };
+
+    // 重复添加或取消不存在的回应时计数没有变化，不推送
+    if changed {
+        push_reaction_update(
+            &audience,
+            ReactionUpdate {
+                target_type: req.target_type,
+                target_id: req.target_id,
+                user_id,
+                emoji: emoji.to_string(),
+                added: add,
+                counts: summary.counts.clone(),
+            },
+        );
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        summary,
+        if add {
+            "已添加回应"
+        } else {
+            "已取消回应"
+        },
+    )))
+}
@@ -0,0 +1,28 @@
+//! 响应中附带的表情回应汇总
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::models::reactions::entities::{ReactionSummary, ReactionTargetType};
+use crate::storage::Storage;
+
+/// 批量查询表情回应汇总，查询失败只记录警告并返回空结果，不影响内容本身的返回
+pub async fn load_reaction_summaries(
+    storage: &Arc<dyn Storage>,
+    target_type: ReactionTargetType,
+    target_ids: &[i64],
+    viewer_id: i64,
+) -> HashMap<i64, ReactionSummary> {
+    match storage
+        .get_reaction_summaries(target_type, target_ids, viewer_id)
+        .await
+    {
+        Ok(summaries) => summaries,
+        Err(e) => {
+            warn!("查询表情回应失败 ({target_type}): {e}");
+            HashMap::new()
+        }
+    }
+}
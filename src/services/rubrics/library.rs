@@ -0,0 +1,220 @@
+//! 量规库的增删改查与版本历史
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{
+    RubricService, can_use_rubric, internal_error, invalid, present_rubric, rubric_not_found,
+    validate_criteria, validate_title,
+};
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::rubrics::entities::Rubric;
+use crate::models::rubrics::requests::{
+    CreateRubricRequest, RubricInput, RubricListQuery, RubricUpdate, UpdateRubricRequest,
+};
+use crate::models::rubrics::responses::{RubricListResponse, RubricVersionListResponse};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+        ErrorCode::Unauthorized,
+        "无法获取用户信息",
+    ))
+}
+
+/// 加载当前用户可使用的量规；`owner_only` 时只允许所有者（修改、删除、分享）
+pub(super) async fn load_rubric(
+    storage: &dyn Storage,
+    request: &HttpRequest,
+    user_id: i64,
+    rubric_id: i64,
+    owner_only: bool,
+) -> Result<Rubric, HttpResponse> {
+    let rubric = match storage.get_rubric(rubric_id).await {
+        Ok(Some(rubric)) if can_use_rubric(request, user_id, &rubric) => rubric,
+        Ok(_) => return Err(rubric_not_found()),
+        Err(e) => return Err(internal_error(format!("查询量规失败: {e}"))),
+    };
+    if owner_only && rubric.owner_id != user_id {
+        return Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::Forbidden,
+            "只能管理自己的量规",
+        )));
+    }
+    Ok(rubric)
+}
+
+/// 规范化可选说明：去除首尾空白，空字符串视为未填写
+fn normalize_description(description: String) -> Option<String> {
+    let description = description.trim();
+    (!description.is_empty()).then(|| description.to_string())
+}
+
+pub async fn list_rubrics(
+    service: &RubricService,
+    request: &HttpRequest,
+    query: RubricListQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    match storage
+        .list_rubrics(user_id, TenantGuard::scope(request), query.scope)
+        .await
+    {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            RubricListResponse {
+                items: items
+                    .into_iter()
+                    .map(|rubric| present_rubric(rubric, user_id))
+                    .collect(),
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询量规失败: {e}"))),
+    }
+}
+
+pub async fn create_rubric(
+    service: &RubricService,
+    request: &HttpRequest,
+    req: CreateRubricRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user) = RequireJWT::extract_user_claims(request) else {
+        return Ok(unauthorized());
+    };
+
+    let title = match validate_title(&req.title) {
+        Ok(title) => title,
+        Err(message) => return Ok(invalid(message)),
+    };
+    if let Err(message) = validate_criteria(&req.criteria) {
+        return Ok(invalid(message));
+    }
+
+    match storage
+        .create_rubric(RubricInput {
+            owner_id: user.id,
+            org_id: user.org_id,
+            title,
+            description: req.description.and_then(normalize_description),
+            criteria: req.criteria,
+            org_visible: req.org_visible,
+            source_rubric_id: None,
+        })
+        .await
+    {
+        Ok(rubric) => Ok(HttpResponse::Created().json(ApiResponse::success(rubric, "创建成功"))),
+        Err(e) => Ok(internal_error(format!("创建量规失败: {e}"))),
+    }
+}
+
+pub async fn get_rubric(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    match load_rubric(storage.as_ref(), request, user_id, rubric_id, false).await {
+        Ok(rubric) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            present_rubric(rubric, user_id),
+            "查询成功",
+        ))),
+        Err(resp) => Ok(resp),
+    }
+}
+
+pub async fn update_rubric(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+    req: UpdateRubricRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    if let Err(resp) = load_rubric(storage.as_ref(), request, user_id, rubric_id, true).await {
+        return Ok(resp);
+    }
+
+    let title = match req.title.as_deref().map(validate_title).transpose() {
+        Ok(title) => title,
+        Err(message) => return Ok(invalid(message)),
+    };
+    if let Some(criteria) = &req.criteria
+        && let Err(message) = validate_criteria(criteria)
+    {
+        return Ok(invalid(message));
+    }
+
+    let update = RubricUpdate {
+        title,
+        description: req.description.map(normalize_description),
+        criteria: req.criteria,
+        org_visible: req.org_visible,
+    };
+    match storage.update_rubric(rubric_id, update, user_id).await {
+        Ok(Some(rubric)) => Ok(HttpResponse::Ok().json(ApiResponse::success(rubric, "更新成功"))),
+        Ok(None) => Ok(rubric_not_found()),
+        Err(e) => Ok(internal_error(format!("更新量规失败: {e}"))),
+    }
+}
+
+pub async fn delete_rubric(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    if let Err(resp) = load_rubric(storage.as_ref(), request, user_id, rubric_id, true).await {
+        return Ok(resp);
+    }
+
+    match storage.delete_rubric(rubric_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("删除成功"))),
+        Ok(false) => Ok(rubric_not_found()),
+        Err(e) => Ok(internal_error(format!("删除量规失败: {e}"))),
+    }
+}
+
+pub async fn list_rubric_versions(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(unauthorized());
+    };
+
+    if let Err(resp) = load_rubric(storage.as_ref(), request, user_id, rubric_id, false).await {
+        return Ok(resp);
+    }
+
+    match storage.list_rubric_versions(rubric_id).await {
+        Ok(items) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            RubricVersionListResponse { rubric_id, items },
+            "查询成功",
+        ))),
+        Err(e) => Ok(internal_error(format!("查询量规版本失败: {e}"))),
+    }
+}
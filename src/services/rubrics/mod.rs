@@ -0,0 +1,252 @@
+//! 评分量规库服务
+//!
+//! 教师把常用的评分量规保存在个人量规库中，可设为同组织可见，或生成分享码发给同事；
+//! 同事通过分享码或组织可见的量规 ID 导入一份属于自己的副本（见 [`sharing`]）。
+//! 修改评分维度会生成新版本，作业设置量规时复制指定版本的快照，之后量规库中的修改不影响作业。
+
+pub mod library;
+pub mod sharing;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::middlewares::TenantGuard;
+use crate::models::rubrics::entities::{Rubric, RubricCriterion};
+use crate::models::rubrics::requests::{
+    CreateRubricRequest, ImportRubricRequest, RubricListQuery, UpdateRubricRequest,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+/// 量规标题最大长度（字符）
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// 单个量规最多评分维度数
+const MAX_CRITERIA: usize = 50;
+
+/// 单个评分维度最多等级数
+const MAX_LEVELS: usize = 10;
+
+/// 维度标题与等级名称最大长度（字符）
+const MAX_LABEL_LENGTH: usize = 100;
+
+/// 用户能否查看与使用该量规：自己的量规，或同租户其他教师设为组织可见的量规
+pub(crate) fn can_use_rubric(request: &HttpRequest, user_id: i64, rubric: &Rubric) -> bool {
+    rubric.owner_id == user_id
+        || (rubric.org_visible && TenantGuard::can_access(request, rubric.org_id))
+}
+
+/// 非所有者看不到分享码
+pub(crate) fn present_rubric(mut rubric: Rubric, user_id: i64) -> Rubric {
+    if rubric.owner_id != user_id {
+        rubric.share_code = None;
+    }
+    rubric
+}
+
+pub(crate) fn rubric_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::RubricNotFound,
+        "量规不存在",
+    ))
+}
+
+fn invalid(message: impl Into<String>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::RubricInvalid,
+        message.into(),
+    ))
+}
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 校验量规标题，返回去除首尾空白后的标题
+fn validate_title(title: &str) -> Result<String, String> {
+    let title = title.trim();
+    let len = title.chars().count();
+    if len == 0 || len > MAX_TITLE_LENGTH {
+        return Err(format!(
+            "量规标题不能为空且不超过 {MAX_TITLE_LENGTH} 个字符"
+        ));
+    }
+    Ok(title.to_string())
+}
+
+/// 校验评分维度：至少一个维度，满分大于 0，等级分值在 0 到维度满分之间
+fn validate_criteria(criteria: &[RubricCriterion]) -> Result<(), String> {
+    if criteria.is_empty() {
+        return Err("量规至少需要一个评分维度".to_string());
+    }
+    if criteria.len() > MAX_CRITERIA {
+        return Err(format!("评分维度不能超过 {MAX_CRITERIA} 个"));
+    }
+    for (index, criterion) in criteria.iter().enumerate() {
+        let n = index + 1;
+        let title_len = criterion.title.trim().chars().count();
+        if title_len == 0 || title_len > MAX_LABEL_LENGTH {
+            return Err(format!(
+                "第 {n} 个评分维度标题不能为空且不超过 {MAX_LABEL_LENGTH} 个字符"
+            ));
+        }
+        if !criterion.points.is_finite() || criterion.points <= 0.0 {
+            return Err(format!("第 {n} 个评分维度满分必须大于 0"));
+        }
+        if criterion.levels.len() > MAX_LEVELS {
+            return Err(format!("第 {n} 个评分维度的等级不能超过 {MAX_LEVELS} 个"));
+        }
+        for level in &criterion.levels {
+            let label_len = level.label.trim().chars().count();
+            if label_len == 0 || label_len > MAX_LABEL_LENGTH {
+                return Err(format!(
+                    "第 {n} 个评分维度的等级名称不能为空且不超过 {MAX_LABEL_LENGTH} 个字符"
+                ));
+            }
+            if !level.points.is_finite() || level.points < 0.0 || level.points > criterion.points {
+                return Err(format!(
+                    "第 {n} 个评分维度的等级分值必须在 0 到该维度满分之间"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub struct RubricService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl RubricService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    /// 列出量规库
+    pub async fn list_rubrics(
+        &self,
+        request: &HttpRequest,
+        query: RubricListQuery,
+    ) -> ActixResult<HttpResponse> {
+        library::list_rubrics(self, request, query).await
+    }
+
+    /// 创建量规
+    pub async fn create_rubric(
+        &self,
+        request: &HttpRequest,
+        req: CreateRubricRequest,
+    ) -> ActixResult<HttpResponse> {
+        library::create_rubric(self, request, req).await
+    }
+
+    /// 获取量规
+    pub async fn get_rubric(
+        &self,
+        request: &HttpRequest,
+        rubric_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        library::get_rubric(self, request, rubric_id).await
+    }
+
+    /// 更新量规
+    pub async fn update_rubric(
+        &self,
+        request: &HttpRequest,
+        rubric_id: i64,
+        req: UpdateRubricRequest,
+    ) -> ActixResult<HttpResponse> {
+        library::update_rubric(self, request, rubric_id, req).await
+    }
+
+    /// 删除量规
+    pub async fn delete_rubric(
+        &self,
+        request: &HttpRequest,
+        rubric_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        library::delete_rubric(self, request, rubric_id).await
+    }
+
+    /// 列出量规版本
+    pub async fn list_rubric_versions(
+        &self,
+        request: &HttpRequest,
+        rubric_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        library::list_rubric_versions(self, request, rubric_id).await
+    }
+
+    /// 生成（或重新生成）分享码
+    pub async fn create_share_code(
+        &self,
+        request: &HttpRequest,
+        rubric_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        sharing::create_share_code(self, request, rubric_id).await
+    }
+
+    /// 停用分享码
+    pub async fn revoke_share_code(
+        &self,
+        request: &HttpRequest,
+        rubric_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        sharing::revoke_share_code(self, request, rubric_id).await
+    }
+
+    /// 导入同事分享的量规
+    pub async fn import_rubric(
+        &self,
+        request: &HttpRequest,
+        req: ImportRubricRequest,
+    ) -> ActixResult<HttpResponse> {
+        sharing::import_rubric(self, request, req).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::rubrics::entities::RubricLevel;
+
+    fn criterion(points: f64, level_points: &[f64]) -> RubricCriterion {
+        RubricCriterion {
+            title: "论证".to_string(),
+            description: None,
+            points,
+            levels: level_points
+                .iter()
+                .map(|&points| RubricLevel {
+                    label: "等级".to_string(),
+                    points,
+                    description: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_criteria() {
+        assert!(validate_criteria(&[criterion(10.0, &[10.0, 6.0, 0.0])]).is_ok());
+        assert!(validate_criteria(&[]).is_err());
+        assert!(validate_criteria(&[criterion(0.0, &[])]).is_err());
+        assert!(validate_criteria(&[criterion(10.0, &[12.0])]).is_err());
+        assert!(validate_criteria(&[criterion(f64::NAN, &[])]).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! 量规分享与导入
+//!
+//! 所有者可生成分享码发给同事，同事凭分享码导入；组织可见的量规也可直接按 ID 导入。
+//! 导入得到一份属于自己的副本（从版本 1 开始，默认不公开），之后与原量规互不影响。
+//! 分享码不跨租户生效。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::library::load_rubric;
+use super::{RubricService, internal_error, invalid, rubric_not_found};
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::rubrics::requests::{ImportRubricRequest, RubricInput};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::random_code::generate_random_code;
+
+/// 分享码长度
+const SHARE_CODE_LENGTH: usize = 10;
+
+async fn set_share_code(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+    share_code: Option<String>,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    if let Err(resp) = load_rubric(storage.as_ref(), request, user_id, rubric_id, true).await {
+        return Ok(resp);
+    }
+
+    let message = if share_code.is_some() {
+        "分享码已生成"
+    } else {
+        "分享码已停用"
+    };
+    match storage.set_rubric_share_code(rubric_id, share_code).await {
+        Ok(Some(rubric)) => Ok(HttpResponse::Ok().json(ApiResponse::success(rubric, message))),
+        Ok(None) => Ok(rubric_not_found()),
+        Err(e) => Ok(internal_error(format!("更新量规分享码失败: {e}"))),
+    }
+}
+
+/// 生成分享码，已有分享码时替换（旧分享码随即失效）
+pub async fn create_share_code(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+) -> ActixResult<HttpResponse> {
+    let code = generate_random_code(SHARE_CODE_LENGTH).to_uppercase();
+    set_share_code(service, request, rubric_id, Some(code)).await
+}
+
+pub async fn revoke_share_code(
+    service: &RubricService,
+    request: &HttpRequest,
+    rubric_id: i64,
+) -> ActixResult<HttpResponse> {
+    set_share_code(service, request, rubric_id, None).await
+}
+
+pub async fn import_rubric(
+    service: &RubricService,
+    request: &HttpRequest,
+    req: ImportRubricRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user) = RequireJWT::extract_user_claims(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let source = match (req.share_code, req.rubric_id) {
+        (Some(code), None) => {
+            let code = code.trim().to_uppercase();
+            match storage.get_rubric_by_share_code(&code).await {
+                Ok(Some(rubric)) if TenantGuard::can_access(request, rubric.org_id) => rubric,
+                Ok(_) => {
+                    return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                        ErrorCode::RubricShareCodeInvalid,
+                        "分享码无效或已停用",
+                    )));
+                }
+                Err(e) => return Ok(internal_error(format!("查询量规失败: {e}"))),
+            }
+        }
+        (None, Some(rubric_id)) => {
+            match load_rubric(storage.as_ref(), request, user.id, rubric_id, false).await {
+                Ok(rubric) => rubric,
+                Err(resp) => return Ok(resp),
+            }
+        }
+        _ => return Ok(invalid("请提供分享码或量规 ID 其中之一")),
+    };
+
+    match storage
+        .create_rubric(RubricInput {
+            owner_id: user.id,
+            org_id: user.org_id,
+            title: source.title,
+            description: source.description,
+            criteria: source.criteria,
+            org_visible: false,
+            source_rubric_id: Some(source.id),
+        })
+        .await
+    {
+        Ok(rubric) => Ok(HttpResponse::Created().json(ApiResponse::success(rubric, "导入成功"))),
+        Err(e) => Ok(internal_error(format!("导入量规失败: {e}"))),
+    }
+}
@@ -1,18 +1,30 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::SubmissionService;
-use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::homeworks::entities::ExamAccessEvent;
+use crate::models::moderation::entities::ModerationContentType;
 use crate::models::submissions::requests::CreateSubmissionRequest;
+use crate::models::usage::entities::UsageMetric;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
-use crate::services::notifications::trigger::send_notification;
+use crate::services::homeworks::exam_access::record_exam_access;
+use crate::services::moderation::{moderate_text, record_flag};
+use crate::services::submissions::references::{snapshot_references, validate_references};
+use crate::services::system::DynamicConfig;
+use crate::services::usage::record_usage;
+use crate::utils::signed_time::verify_timestamp;
+
+/// 客户端时钟允许的最大偏差（毫秒）
+const CLOCK_SKEW_TOLERANCE_MS: i64 = 30_000;
 
 pub async fn create_submission(
     service: &SubmissionService,
     request: &HttpRequest,
     creator_id: i64,
     creator_role: UserRole,
-    req: CreateSubmissionRequest,
+    mut req: CreateSubmissionRequest,
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
 
@@ -35,14 +47,21 @@ pub async fn create_submission(
         }
     };
 
-    // 验证用户是否为该作业所属班级的成员（管理员除外）
-    if creator_role != UserRole::Admin {
-        match storage
-            .get_class_user_by_user_id_and_class_id(creator_id, homework.class_id)
-            .await
-        {
-            Ok(Some(_)) => {
+    // 验证用户是否为该作业所属班级的成员（管理员只校验组织）
+    // 考试模式与前置条件仅针对学生（非教师）
+    let mut is_student = false;
+    if creator_role == UserRole::Admin {
+        if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "作业不存在",
+            )));
+        }
+    } else {
+        match RequireClassRole::class_user(request, &storage, creator_id, homework.class_id).await {
+            Ok(Some(cu)) => {
                 // 用户是班级成员，允许提交
+                is_student = cu.role != ClassUserRole::Teacher;
             }
             Ok(None) => {
                 return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
@@ -60,34 +79,168 @@ pub async fn create_submission(
             }
         }
     }
+    let track_exam = homework.exam_mode && is_student;
 
-    match storage.create_submission(creator_id, req).await {
+    // 前置作业未完成时不能提交
+    if is_student {
+        match storage
+            .list_unmet_prerequisites(creator_id, &[homework.id])
+            .await
+        {
+            Ok(unmet) if unmet.contains_key(&homework.id) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::SubmissionPrerequisitesNotMet,
+                    "请先完成该作业的前置作业",
+                )));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询作业前置条件失败: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
+    // 考试模式下核对客户端时钟，仅记录异常不拒绝提交
+    if track_exam && let Some(detail) = check_client_clock(&req) {
+        record_exam_access(
+            &storage,
+            request,
+            homework.id,
+            creator_id,
+            ExamAccessEvent::ClockSkew,
+            None,
+            Some(detail),
+        )
+        .await;
+    }
+
+    // 多部分作业必须指定所属分题，未拆分的作业不接受分题
+    let parts = match storage.list_homework_parts(homework.id).await {
+        Ok(parts) => parts,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询作业分题失败: {e}"),
+                )),
+            );
+        }
+    };
+    match req.part_id {
+        None if !parts.is_empty() => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionPartRequired,
+                "该作业包含多个分题，请指定提交的分题",
+            )));
+        }
+        Some(part_id) if !parts.iter().any(|p| p.id == part_id) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkPartNotFound,
+                "分题不存在或不属于该作业",
+            )));
+        }
+        _ => {}
+    }
+
+    // 外部引用须格式有效
+    let references = req.references.take().unwrap_or_default();
+    if let Err(message) = validate_references(&references) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::SubmissionReferenceInvalid,
+            message,
+        )));
+    }
+
+    // 校验提交方式与内容长度（外部引用与附件同样计数）
+    let attachment_count = req.attachments.as_ref().map_or(0, |a| a.len()) + references.len();
+    if let Err((code, message)) = homework.validate_submission(&req.content, attachment_count) {
+        if track_exam {
+            record_exam_access(
+                &storage,
+                request,
+                homework.id,
+                creator_id,
+                ExamAccessEvent::Submit,
+                None,
+                Some(message.clone()),
+            )
+            .await;
+        }
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(code, message)));
+    }
+
+    // 作业要求自评时必须填写，填写了则校验取值范围
+    match &req.self_assessment {
+        None if homework.require_self_assessment => {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::SelfAssessmentRequired,
+                "该作业提交时须填写自评",
+            )));
+        }
+        Some(assessment) => {
+            if let Err(message) = assessment.validate() {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::SelfAssessmentInvalid,
+                    message,
+                )));
+            }
+        }
+        None => {}
+    }
+
+    // 内容审核：拦截时直接返回，标记时在提交保存后记录
+    let pending_flag = match moderate_text(
+        &storage,
+        homework.class_id,
+        creator_id,
+        ModerationContentType::Submission,
+        &req.content,
+    )
+    .await
+    {
+        Ok(flag) => flag,
+        Err(resp) => return Ok(resp),
+    };
+
+    // 内容审核通过后再抓取 Git 提交元数据，避免被拦截的提交产生外部调用
+    let references = snapshot_references(references).await;
+
+    // 迟交判定叠加宽限期，作业未单独设置时沿用系统默认
+    let default_grace_minutes = DynamicConfig::homework_default_grace_minutes().await;
+
+    match storage
+        .create_submission(creator_id, req, references, default_grace_minutes)
+        .await
+    {
         Ok(submission) => {
-            // 异步通知教师（作业创建者）
-            let storage_clone = storage.clone();
-            let submission_id = submission.id;
-            let teacher_id = homework.created_by;
-            let hw_title = homework.title.clone();
-
-            tokio::spawn(async move {
-                // 获取提交者名称
-                let student_name = match storage_clone.get_user_by_id(creator_id).await {
-                    Ok(Some(user)) => user.display_name.unwrap_or(user.username),
-                    _ => "学生".to_string(),
-                };
-
-                send_notification(
-                    storage_clone,
-                    teacher_id,
-                    NotificationType::SubmissionReceived,
-                    format!("收到新提交：{}", hw_title),
-                    Some(format!("{} 提交了作业「{}」", student_name, hw_title)),
-                    Some(ReferenceType::Submission),
-                    Some(submission_id),
+            if let Some(mut flag) = pending_flag {
+                flag.content_id = Some(submission.id);
+                record_flag(&storage, flag).await;
+            }
+
+            if track_exam {
+                record_exam_access(
+                    &storage,
+                    request,
+                    homework.id,
+                    creator_id,
+                    ExamAccessEvent::Submit,
+                    Some(submission.id),
+                    None,
                 )
                 .await;
-            });
+            }
 
+            // 记录组织用量
+            let org_id = RequireJWT::extract_user_claims(request).and_then(|user| user.org_id);
+            record_usage(storage.clone(), org_id, UsageMetric::Submissions, 1);
+
+            // 教师通知已随提交写入发件箱，由转发任务投递
             Ok(HttpResponse::Created().json(ApiResponse::success(submission, "提交成功")))
         }
         Err(e) => Ok(
@@ -98,3 +251,16 @@ pub async fn create_submission(
         ),
     }
 }
+
+/// 校验提交携带的签名时间戳与客户端时间，返回异常说明；未携带时不校验
+fn check_client_clock(req: &CreateSubmissionRequest) -> Option<String> {
+    let token = req.signed_timestamp.as_deref()?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let Some(server_time) =
+        verify_timestamp(token).filter(|ts| *ts <= now + CLOCK_SKEW_TOLERANCE_MS)
+    else {
+        return Some("签名时间戳无效".to_string());
+    };
+    let skew = req.client_time? - server_time;
+    (skew.abs() > CLOCK_SKEW_TOLERANCE_MS).then(|| format!("客户端时钟偏差 {skew} 毫秒"))
+}
@@ -1,7 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::SubmissionService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
 
@@ -36,7 +36,13 @@ pub async fn delete_submission(
     // 权限检查：只有提交者本人或管理员才能删除
     match user_role {
         Some(UserRole::Admin) => {
-            // 管理员可以删除任何提交
+            // 管理员可以删除本组织的任何提交
+            if !TenantGuard::can_access_homework(request, &storage, submission.homework_id).await {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::SubmissionNotFound,
+                    "提交不存在",
+                )));
+            }
         }
         _ => {
             // 其他用户只能删除自己的提交
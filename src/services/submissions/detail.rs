@@ -1,7 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::SubmissionService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
@@ -49,7 +49,13 @@ pub async fn get_submission(
     let include_grades;
 
     if user_role == Some(UserRole::Admin) {
-        // 管理员可以查看任何提交和成绩
+        // 管理员可以查看本组织的任何提交和成绩
+        if !TenantGuard::can_access_homework(request, &storage, submission.homework_id).await {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionNotFound,
+                "提交不存在",
+            )));
+        }
         include_grades = true;
     } else {
         // 获取作业信息以确定班级
@@ -72,9 +78,8 @@ pub async fn get_submission(
         };
 
         // 检查用户在班级中的角色
-        let class_user = storage
-            .get_class_user_by_user_id_and_class_id(user_id, homework.class_id)
-            .await;
+        let class_user =
+            RequireClassRole::class_user(request, &storage, user_id, homework.class_id).await;
 
         match class_user {
             Ok(Some(cu)) => {
@@ -114,9 +119,10 @@ pub async fn get_submission(
         }
     }
 
-    // 如果不能查看成绩，将 grade 字段设为 None
+    // 如果不能查看成绩，将 grade 与自评字段设为 None
     if !include_grades {
         submission.grade = None;
+        submission.self_assessment = None;
     }
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(submission, "查询成功")))
@@ -0,0 +1,203 @@
+//! 提交版本对比
+//!
+//! 对同一学生同一作业的两个提交版本的文本内容做按行 diff，
+//! 以结构化的差异块返回，便于教师快速查看重新提交时改动了什么。
+
+use std::time::Duration;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use similar::{Algorithm, ChangeTag, TextDiff};
+
+use super::SubmissionService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::submissions::entities::Submission;
+use crate::models::submissions::requests::SubmissionDiffQuery;
+use crate::models::submissions::responses::{
+    SubmissionDiffHunk, SubmissionDiffLine, SubmissionDiffLineKind, SubmissionDiffResponse,
+    SubmissionDiffVersion,
+};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 默认上下文行数
+const DEFAULT_CONTEXT_LINES: usize = 3;
+/// 上下文行数上限
+const MAX_CONTEXT_LINES: usize = 20;
+/// diff 计算超时，超时后退化为较粗粒度的结果
+const DIFF_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub async fn diff_submissions(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    submission_id: i64,
+    query: SubmissionDiffQuery,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let target = match storage.get_submission_by_id(submission_id).await {
+        Ok(Some(s)) => s,
+        Ok(None) => return submission_not_found(),
+        Err(e) => return internal_error(format!("查询提交失败: {e}")),
+    };
+    let base = match storage.get_submission_by_id(query.against).await {
+        Ok(Some(s)) => s,
+        Ok(None) => return submission_not_found(),
+        Err(e) => return internal_error(format!("查询提交失败: {e}")),
+    };
+
+    if base.homework_id != target.homework_id || base.creator_id != target.creator_id {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::SubmissionDiffMismatch,
+            "只能对比同一学生同一作业的提交版本",
+        )));
+    }
+
+    // 权限检查：本组织管理员、班级教师或提交者本人
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        if !TenantGuard::can_access_homework(request, &storage, target.homework_id).await {
+            return submission_not_found();
+        }
+    } else if target.creator_id != user_id {
+        let homework = match storage.get_homework_by_id(target.homework_id).await {
+            Ok(Some(hw)) => hw,
+            Ok(None) => {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::HomeworkNotFound,
+                    "关联作业不存在",
+                )));
+            }
+            Err(e) => return internal_error(format!("查询作业失败: {e}")),
+        };
+
+        match RequireClassRole::class_user(request, &storage, user_id, homework.class_id).await {
+            Ok(Some(cu)) if cu.role == ClassUserRole::Teacher => {}
+            Ok(Some(_)) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::Forbidden,
+                    "只有班级教师可以对比学生的提交版本",
+                )));
+            }
+            Ok(None) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::ClassPermissionDenied,
+                    "您不是该班级成员",
+                )));
+            }
+            Err(e) => return internal_error(format!("查询班级成员失败: {e}")),
+        }
+    }
+
+    let context = query
+        .context
+        .unwrap_or(DEFAULT_CONTEXT_LINES)
+        .min(MAX_CONTEXT_LINES);
+    let (hunks, added_lines, removed_lines) = compute_hunks(
+        base.content.as_deref().unwrap_or_default(),
+        target.content.as_deref().unwrap_or_default(),
+        context,
+    );
+
+    let response = SubmissionDiffResponse {
+        homework_id: target.homework_id,
+        creator_id: target.creator_id,
+        base: diff_version(&base),
+        target: diff_version(&target),
+        added_lines,
+        removed_lines,
+        hunks,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功")))
+}
+
+fn diff_version(submission: &Submission) -> SubmissionDiffVersion {
+    SubmissionDiffVersion {
+        id: submission.id,
+        version: submission.version,
+        submitted_at: submission.submitted_at.to_rfc3339(),
+    }
+}
+
+/// 按行计算差异，返回 (差异块, 新增行数, 删除行数)
+fn compute_hunks(old: &str, new: &str, context: usize) -> (Vec<SubmissionDiffHunk>, usize, usize) {
+    let diff = TextDiff::configure()
+        .algorithm(Algorithm::Myers)
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(old, new);
+
+    let mut added = 0;
+    let mut removed = 0;
+    let mut hunks = Vec::new();
+
+    for group in diff.grouped_ops(context) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_start = first.old_range().start;
+        let new_start = first.new_range().start;
+        let old_lines = last.old_range().end - old_start;
+        let new_lines = last.new_range().end - new_start;
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => SubmissionDiffLineKind::Equal,
+                    ChangeTag::Insert => {
+                        added += 1;
+                        SubmissionDiffLineKind::Insert
+                    }
+                    ChangeTag::Delete => {
+                        removed += 1;
+                        SubmissionDiffLineKind::Delete
+                    }
+                };
+                lines.push(SubmissionDiffLine {
+                    kind,
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                    content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                });
+            }
+        }
+
+        hunks.push(SubmissionDiffHunk {
+            old_start: hunk_start(old_start, old_lines),
+            old_lines,
+            new_start: hunk_start(new_start, new_lines),
+            new_lines,
+            lines,
+        });
+    }
+
+    (hunks, added, removed)
+}
+
+/// 转换为从 1 开始的行号；与 unified diff 一致，空范围取其前一行
+fn hunk_start(start: usize, len: usize) -> usize {
+    if len == 0 { start } else { start + 1 }
+}
+
+fn submission_not_found() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::SubmissionNotFound,
+        "提交不存在",
+    )))
+}
+
+fn internal_error(msg: String) -> ActixResult<HttpResponse> {
+    Ok(
+        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+            ErrorCode::InternalServerError,
+            msg,
+        )),
+    )
+}
@@ -2,23 +2,22 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
 use super::SubmissionService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
+use crate::models::reactions::entities::ReactionTargetType;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::services::grades::attach_class_context;
+use crate::services::reactions::load_reaction_summaries;
 use crate::storage::Storage;
 
 /// 检查用户是否有权限访问某个提交的评分
 async fn check_grade_access_permission(
     storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
     current_user: &crate::models::users::entities::User,
     submission_id: i64,
 ) -> Result<(), HttpResponse> {
-    // Admin 直接放行
-    if current_user.role == UserRole::Admin {
-        return Ok(());
-    }
-
     // 获取提交信息
     let submission = match storage.get_submission_by_id(submission_id).await {
         Ok(Some(sub)) => sub,
@@ -38,6 +37,17 @@ async fn check_grade_access_permission(
         }
     };
 
+    // Admin 可以查看本组织的任何评分
+    if current_user.role == UserRole::Admin {
+        if !TenantGuard::can_access_homework(request, storage, submission.homework_id).await {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionNotFound,
+                "提交不存在",
+            )));
+        }
+        return Ok(());
+    }
+
     // 如果是提交者本人，允许查看自己的成绩
     if submission.creator_id == current_user.id {
         return Ok(());
@@ -115,13 +125,29 @@ pub async fn get_submission_grade(
     };
 
     // 权限验证
-    if let Err(resp) = check_grade_access_permission(&storage, &current_user, submission_id).await {
+    if let Err(resp) =
+        check_grade_access_permission(&storage, request, &current_user, submission_id).await
+    {
         return Ok(resp);
     }
 
     // 获取评分
     match storage.get_grade_by_submission_id(submission_id).await {
-        Ok(Some(grade)) => Ok(HttpResponse::Ok().json(ApiResponse::success(grade, "查询成功"))),
+        Ok(Some(mut grade)) => {
+            attach_class_context(&storage, &mut grade, current_user.id).await;
+            if let Some(summary) = load_reaction_summaries(
+                &storage,
+                ReactionTargetType::GradeComment,
+                &[grade.id],
+                current_user.id,
+            )
+            .await
+            .remove(&grade.id)
+            {
+                grade.reactions = summary;
+            }
+            Ok(HttpResponse::Ok().json(ApiResponse::success(grade, "查询成功")))
+        }
         Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::GradeNotFound,
             "该提交尚未评分",
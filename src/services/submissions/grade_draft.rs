@@ -0,0 +1,169 @@
+//! 评分草稿：教师批改过程中自动保存分数与评语，正式评分后删除
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use super::SubmissionService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::grades::requests::SaveGradeDraftRequest;
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+/// 检查用户能否为该提交评分（班级教师或管理员），返回用户 ID
+async fn check_grader_permission(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    submission_id: i64,
+) -> Result<i64, HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return Err(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let submission = match storage.get_submission_by_id(submission_id).await {
+        Ok(Some(sub)) => sub,
+        Ok(None) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionNotFound,
+                "提交不存在",
+            )));
+        }
+        Err(e) => {
+            return Err(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询提交失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    match RequireJWT::extract_user_role(request) {
+        Some(UserRole::Admin) => {
+            if TenantGuard::can_access_homework(request, storage, submission.homework_id).await {
+                Ok(user_id)
+            } else {
+                Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::SubmissionNotFound,
+                    "提交不存在",
+                )))
+            }
+        }
+        Some(UserRole::Teacher) => {
+            let class = match storage.get_homework_by_id(submission.homework_id).await {
+                Ok(Some(hw)) => storage.get_class_by_id(hw.class_id).await,
+                Ok(None) => Ok(None),
+                Err(e) => Err(e),
+            };
+            match class {
+                Ok(Some(cls)) if cls.teacher_id == user_id => Ok(user_id),
+                Ok(_) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                    ErrorCode::Forbidden,
+                    "只能对自己班级的提交进行评分",
+                ))),
+                Err(e) => Err(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("查询班级失败: {e}"),
+                    )),
+                ),
+            }
+        }
+        _ => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::Forbidden,
+            "没有评分权限",
+        ))),
+    }
+}
+
+/// 获取当前用户对提交的评分草稿
+/// GET /submissions/{id}/grade-draft
+pub async fn get_grade_draft(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    submission_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+    let grader_id = match check_grader_permission(&storage, request, submission_id).await {
+        Ok(id) => id,
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage.get_grade_draft(submission_id, grader_id).await {
+        Ok(Some(draft)) => Ok(HttpResponse::Ok().json(ApiResponse::success(draft, "查询成功"))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::GradeDraftNotFound,
+            "评分草稿不存在",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询评分草稿失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 保存评分草稿（自动保存，整体覆盖）
+/// PUT /submissions/{id}/grade-draft
+pub async fn save_grade_draft(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    submission_id: i64,
+    req: SaveGradeDraftRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(message) = req.validate() {
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, message)));
+    }
+
+    let storage = service.get_storage(request);
+    let grader_id = match check_grader_permission(&storage, request, submission_id).await {
+        Ok(id) => id,
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage
+        .upsert_grade_draft(submission_id, grader_id, req)
+        .await
+    {
+        Ok(draft) => Ok(HttpResponse::Ok().json(ApiResponse::success(draft, "草稿已保存"))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("保存评分草稿失败: {e}"),
+            )),
+        ),
+    }
+}
+
+/// 丢弃评分草稿
+/// DELETE /submissions/{id}/grade-draft
+pub async fn delete_grade_draft(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    submission_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+    let grader_id = match check_grader_permission(&storage, request, submission_id).await {
+        Ok(id) => id,
+        Err(resp) => return Ok(resp),
+    };
+
+    match storage.delete_grade_draft(submission_id, grader_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("草稿已删除"))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::GradeDraftNotFound,
+            "评分草稿不存在",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("删除评分草稿失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -1,7 +1,8 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::SubmissionService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::models::submissions::requests::SubmissionListQuery;
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
@@ -11,14 +12,19 @@ pub async fn list_submissions(
     request: &HttpRequest,
     mut query: SubmissionListQuery,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("submissions").check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
     let user_role = RequireJWT::extract_user_role(request);
     let user_id = RequireJWT::extract_user_id(request);
+    query.tenant = TenantGuard::scope(request);
 
     // 权限检查：学生只能看自己的提交，教师可以看班级所有提交
     match user_role {
         Some(UserRole::Admin) => {
-            // 管理员可以查看所有提交，不需要过滤
+            // 管理员可以查看本组织的所有提交
         }
         Some(UserRole::Teacher) => {
             // 教师可以通过 homework_id 查看班级内的提交
@@ -46,7 +52,7 @@ pub async fn list_submissions(
                 )));
             }
         }
-        Some(UserRole::User) | None => {
+        Some(UserRole::User) | Some(UserRole::Viewer) | None => {
             // 学生只能查看自己的提交
             if let Some(uid) = user_id {
                 query.creator_id = Some(uid);
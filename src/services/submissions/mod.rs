@@ -1,15 +1,24 @@
 pub mod create;
 pub mod delete;
 pub mod detail;
+pub mod diff;
 pub mod grade;
+pub mod grade_draft;
 pub mod history;
 pub mod list;
+pub mod references;
+pub mod resubmission;
 pub mod summary;
+pub mod workflow;
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
-use crate::models::submissions::requests::{CreateSubmissionRequest, SubmissionListQuery};
+use crate::models::grades::requests::SaveGradeDraftRequest;
+use crate::models::submissions::requests::{
+    CreateSubmissionRequest, RequestResubmissionRequest, SubmissionDiffQuery, SubmissionListQuery,
+    SubmissionSummaryQuery, TransitionSubmissionStatusRequest, UpdateSubmissionWorkflowRequest,
+};
 use crate::models::users::entities::UserRole;
 use crate::storage::Storage;
 
@@ -54,6 +63,16 @@ impl SubmissionService {
         detail::get_submission(self, request, submission_id).await
     }
 
+    /// 对比两个提交版本的文本内容
+    pub async fn diff_submissions(
+        &self,
+        request: &HttpRequest,
+        submission_id: i64,
+        query: SubmissionDiffQuery,
+    ) -> ActixResult<HttpResponse> {
+        diff::diff_submissions(self, request, submission_id, query).await
+    }
+
     /// 获取最新提交
     pub async fn get_latest_submission(
         &self,
@@ -98,11 +117,9 @@ impl SubmissionService {
         &self,
         request: &HttpRequest,
         homework_id: i64,
-        page: Option<i64>,
-        size: Option<i64>,
-        graded: Option<bool>,
+        query: SubmissionSummaryQuery,
     ) -> ActixResult<HttpResponse> {
-        summary::get_submission_summary(self, request, homework_id, page, size, graded).await
+        summary::get_submission_summary(self, request, homework_id, query).await
     }
 
     /// 获取某学生某作业的所有版本（教师视角）
@@ -123,4 +140,71 @@ impl SubmissionService {
     ) -> ActixResult<HttpResponse> {
         grade::get_submission_grade(self, request, submission_id).await
     }
+
+    /// 获取当前用户的评分草稿
+    pub async fn get_grade_draft(
+        &self,
+        request: &HttpRequest,
+        submission_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        grade_draft::get_grade_draft(self, request, submission_id).await
+    }
+
+    /// 保存评分草稿
+    pub async fn save_grade_draft(
+        &self,
+        request: &HttpRequest,
+        submission_id: i64,
+        req: SaveGradeDraftRequest,
+    ) -> ActixResult<HttpResponse> {
+        grade_draft::save_grade_draft(self, request, submission_id, req).await
+    }
+
+    /// 丢弃评分草稿
+    pub async fn delete_grade_draft(
+        &self,
+        request: &HttpRequest,
+        submission_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        grade_draft::delete_grade_draft(self, request, submission_id).await
+    }
+
+    /// 按班级工作流转换提交状态
+    pub async fn transition_submission_status(
+        &self,
+        request: &HttpRequest,
+        submission_id: i64,
+        req: TransitionSubmissionStatusRequest,
+    ) -> ActixResult<HttpResponse> {
+        workflow::transition_submission_status(self, request, submission_id, req).await
+    }
+
+    /// 退回提交，要求学生重新提交
+    pub async fn request_resubmission(
+        &self,
+        request: &HttpRequest,
+        submission_id: i64,
+        req: RequestResubmissionRequest,
+    ) -> ActixResult<HttpResponse> {
+        resubmission::request_resubmission(self, request, submission_id, req).await
+    }
+
+    /// 获取班级提交状态工作流
+    pub async fn get_class_submission_workflow(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        workflow::get_class_submission_workflow(self, request, class_id).await
+    }
+
+    /// 更新班级提交状态工作流
+    pub async fn update_class_submission_workflow(
+        &self,
+        request: &HttpRequest,
+        class_id: i64,
+        req: UpdateSubmissionWorkflowRequest,
+    ) -> ActixResult<HttpResponse> {
+        workflow::update_class_submission_workflow(self, request, class_id, req).await
+    }
 }
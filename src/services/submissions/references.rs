@@ -0,0 +1,317 @@
+//! 提交外部引用（Git 仓库 / 在线文档）
+//!
+//! 学生可以用 Git 仓库地址 + 提交哈希或在线文档链接代替或补充附件。开启
+//! `submission_references.fetch_metadata` 时，GitHub 与 GitLab（gitlab.com）仓库的引用在提交时
+//! 抓取提交说明与提交时间作为快照，供教师批改时对照；抓取失败不影响提交，只是不保存快照。
+
+use futures_util::future::join_all;
+use serde_json::Value;
+
+use crate::config::AppConfig;
+use crate::models::submissions::entities::SubmissionReferenceKind;
+use crate::models::submissions::requests::{SubmissionReferenceInput, SubmissionReferenceSnapshot};
+use crate::services::homeworks::links::validate_url;
+use crate::services::outbound::GIT_CLIENT;
+
+/// 单次提交最多外部引用数
+const MAX_REFERENCES: usize = 5;
+
+/// 提交哈希长度范围（缩写哈希至 SHA-256 完整哈希）
+const MIN_COMMIT_LENGTH: usize = 7;
+const MAX_COMMIT_LENGTH: usize = 64;
+
+/// 提交说明快照最大长度（字符）
+const MAX_COMMIT_MESSAGE_LENGTH: usize = 2000;
+
+/// 校验外部引用：链接须为 http(s)，Git 引用须附带十六进制提交哈希，文档链接不能附带哈希
+pub fn validate_references(references: &[SubmissionReferenceInput]) -> Result<(), String> {
+    if references.len() > MAX_REFERENCES {
+        return Err(format!("外部引用数量不能超过 {MAX_REFERENCES} 个"));
+    }
+    for (index, reference) in references.iter().enumerate() {
+        let n = index + 1;
+        if let Err(message) = validate_url(reference.url.trim()) {
+            return Err(format!("第 {n} 个外部引用无效：{message}"));
+        }
+        let commit = reference.commit.as_deref().map(str::trim);
+        match (reference.kind, commit) {
+            (SubmissionReferenceKind::Git, Some(commit)) if is_commit_hash(commit) => {}
+            (SubmissionReferenceKind::Git, _) => {
+                return Err(format!(
+                    "第 {n} 个外部引用须填写 {MIN_COMMIT_LENGTH}-{MAX_COMMIT_LENGTH} 位十六进制提交哈希"
+                ));
+            }
+            (SubmissionReferenceKind::Document, Some(commit)) if !commit.is_empty() => {
+                return Err(format!("第 {n} 个外部引用为文档链接，不能填写提交哈希"));
+            }
+            (SubmissionReferenceKind::Document, _) => {}
+        }
+    }
+    Ok(())
+}
+
+fn is_commit_hash(commit: &str) -> bool {
+    (MIN_COMMIT_LENGTH..=MAX_COMMIT_LENGTH).contains(&commit.len())
+        && commit.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 整理已校验的外部引用，按配置抓取 Git 提交元数据
+pub async fn snapshot_references(
+    references: Vec<SubmissionReferenceInput>,
+) -> Vec<SubmissionReferenceSnapshot> {
+    let fetch = AppConfig::get().submission_references.fetch_metadata;
+    join_all(references.into_iter().map(|reference| async move {
+        let url = reference.url.trim().to_string();
+        let commit_hash = match reference.kind {
+            SubmissionReferenceKind::Git => reference
+                .commit
+                .map(|commit| commit.trim().to_ascii_lowercase()),
+            SubmissionReferenceKind::Document => None,
+        };
+        let mut snapshot = SubmissionReferenceSnapshot {
+            kind: reference.kind,
+            url,
+            commit_hash,
+            commit_message: None,
+            committed_at: None,
+            metadata_fetched_at: None,
+        };
+        if fetch
+            && let Some(commit) = snapshot.commit_hash.clone()
+            && let Some(metadata) = fetch_commit_metadata(&snapshot.url, &commit).await
+        {
+            // 缩写哈希以平台返回的完整哈希为准
+            if let Some(full_hash) = metadata.hash.filter(|h| h.starts_with(&commit)) {
+                snapshot.commit_hash = Some(full_hash);
+            }
+            snapshot.commit_message = metadata.message;
+            snapshot.committed_at = metadata.committed_at;
+            snapshot.metadata_fetched_at = Some(chrono::Utc::now().timestamp());
+        }
+        snapshot
+    }))
+    .await
+}
+
+/// 代码托管平台返回的提交元数据
+#[derive(Debug, Default, PartialEq)]
+struct CommitMetadata {
+    hash: Option<String>,
+    message: Option<String>,
+    committed_at: Option<i64>,
+}
+
+/// 支持抓取元数据的代码托管平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitProvider {
+    GitHub,
+    GitLab,
+}
+
+/// 由仓库地址得到平台与查询提交的接口地址，不支持的平台或地址格式返回 `None`
+fn commit_api_url(repo_url: &str, commit: &str) -> Option<(GitProvider, String)> {
+    let (_, rest) = repo_url.split_once("://")?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    // 去掉 GitLab 的 `/-/tree/main` 等页面路径与 `.git` 后缀
+    let path = path.split("/-/").next().unwrap_or_default();
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').collect();
+    let valid_segment = |s: &&str| {
+        !s.is_empty()
+            && !s.starts_with('.')
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    match host {
+        "github.com" => {
+            let [owner, repo, ..] = segments.as_slice() else {
+                return None;
+            };
+            if !valid_segment(owner) || !valid_segment(repo) {
+                return None;
+            }
+            Some((
+                GitProvider::GitHub,
+                format!("https://api.github.com/repos/{owner}/{repo}/commits/{commit}"),
+            ))
+        }
+        "gitlab.com" => {
+            if segments.len() < 2 || !segments.iter().all(valid_segment) {
+                return None;
+            }
+            Some((
+                GitProvider::GitLab,
+                format!(
+                    "https://gitlab.com/api/v4/projects/{}/repository/commits/{commit}",
+                    segments.join("%2F")
+                ),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// 解析平台接口返回的提交信息
+fn parse_commit_metadata(provider: GitProvider, payload: &Value) -> CommitMetadata {
+    let (hash, message, date) = match provider {
+        GitProvider::GitHub => (
+            &payload["sha"],
+            &payload["commit"]["message"],
+            &payload["commit"]["committer"]["date"],
+        ),
+        GitProvider::GitLab => (
+            &payload["id"],
+            &payload["message"],
+            &payload["committed_date"],
+        ),
+    };
+    CommitMetadata {
+        hash: hash.as_str().map(str::to_ascii_lowercase),
+        message: message
+            .as_str()
+            .map(|m| m.trim().chars().take(MAX_COMMIT_MESSAGE_LENGTH).collect()),
+        committed_at: date
+            .as_str()
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.timestamp()),
+    }
+}
+
+/// 抓取提交元数据，失败时记录日志并返回 `None`
+async fn fetch_commit_metadata(repo_url: &str, commit: &str) -> Option<CommitMetadata> {
+    let (provider, api_url) = commit_api_url(repo_url, commit)?;
+    let config = &AppConfig::get().submission_references;
+    let token = match provider {
+        GitProvider::GitHub => &config.github_token,
+        GitProvider::GitLab => &config.gitlab_token,
+    };
+
+    let result = GIT_CLIENT
+        .send(|client| {
+            let request = client
+                .get(&api_url)
+                .header(reqwest::header::USER_AGENT, "hwsystem");
+            match (provider, token.is_empty()) {
+                (_, true) => request,
+                (GitProvider::GitHub, false) => request.bearer_auth(token),
+                (GitProvider::GitLab, false) => request.header("PRIVATE-TOKEN", token),
+            }
+        })
+        .await;
+    let response = match result {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            tracing::warn!(
+                "抓取提交元数据失败: {repo_url}@{commit}: HTTP {}",
+                response.status()
+            );
+            return None;
+        }
+        Err(e) => {
+            tracing::warn!("抓取提交元数据失败: {repo_url}@{commit}: {e}");
+            return None;
+        }
+    };
+    match response.json::<Value>().await {
+        Ok(payload) => Some(parse_commit_metadata(provider, &payload)),
+        Err(e) => {
+            tracing::warn!("解析提交元数据失败: {repo_url}@{commit}: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn git(url: &str, commit: Option<&str>) -> SubmissionReferenceInput {
+        SubmissionReferenceInput {
+            kind: SubmissionReferenceKind::Git,
+            url: url.to_string(),
+            commit: commit.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_validate_references() {
+        assert!(validate_references(&[git("https://github.com/a/b", Some("a1b2c3d"))]).is_ok());
+        assert!(validate_references(&[git("https://github.com/a/b", None)]).is_err());
+        assert!(validate_references(&[git("https://github.com/a/b", Some("a1b2"))]).is_err());
+        assert!(validate_references(&[git("https://github.com/a/b", Some("main"))]).is_err());
+        assert!(validate_references(&[git("javascript:alert(1)", Some("a1b2c3d"))]).is_err());
+
+        let document = SubmissionReferenceInput {
+            kind: SubmissionReferenceKind::Document,
+            url: "https://docs.qq.com/doc/abc".to_string(),
+            commit: Some("a1b2c3d".to_string()),
+        };
+        assert!(validate_references(&[document]).is_err());
+
+        let many = vec![git("https://github.com/a/b", Some("a1b2c3d")); MAX_REFERENCES + 1];
+        assert!(validate_references(&many).is_err());
+    }
+
+    #[test]
+    fn test_commit_api_url() {
+        assert_eq!(
+            commit_api_url("https://github.com/rust-lang/rust.git", "abc1234"),
+            Some((
+                GitProvider::GitHub,
+                "https://api.github.com/repos/rust-lang/rust/commits/abc1234".to_string()
+            ))
+        );
+        assert_eq!(
+            commit_api_url("https://www.github.com/a/b/tree/main?tab=1", "abc1234")
+                .map(|(_, url)| url),
+            Some("https://api.github.com/repos/a/b/commits/abc1234".to_string())
+        );
+        assert_eq!(
+            commit_api_url("https://gitlab.com/group/sub/project/-/tree/main", "abc1234")
+                .map(|(_, url)| url),
+            Some(
+                "https://gitlab.com/api/v4/projects/group%2Fsub%2Fproject/repository/commits/abc1234"
+                    .to_string()
+            )
+        );
+        assert!(commit_api_url("https://github.com/only-owner", "abc1234").is_none());
+        assert!(commit_api_url("https://github.com/a/..", "abc1234").is_none());
+        assert!(commit_api_url("https://git.example.com/a/b", "abc1234").is_none());
+    }
+
+    #[test]
+    fn test_parse_commit_metadata() {
+        let github = json!({
+            "sha": "ABC1234DEF",
+            "commit": {
+                "message": "Fix bug\n",
+                "committer": { "date": "2025-03-01T08:00:00Z" }
+            }
+        });
+        assert_eq!(
+            parse_commit_metadata(GitProvider::GitHub, &github),
+            CommitMetadata {
+                hash: Some("abc1234def".to_string()),
+                message: Some("Fix bug".to_string()),
+                committed_at: Some(1_740_816_000),
+            }
+        );
+
+        let gitlab = json!({
+            "id": "abc1234def",
+            "message": "Init",
+            "committed_date": "2025-03-01T16:00:00+08:00"
+        });
+        assert_eq!(
+            parse_commit_metadata(GitProvider::GitLab, &gitlab).committed_at,
+            Some(1_740_816_000)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! 退回重交
+//!
+//! 教师将提交退回并附上意见：提交状态变为 returned，学生收到通知。退回后作业对该学生
+//! 重新开放至教师设定的期限，期限内重新提交即使已过截止时间也不计迟交。
+//! 最新提交处于退回状态的学生在作业统计中计为未提交。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::SubmissionService;
+use super::workflow::{check_class_member, internal_error};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::submissions::requests::RequestResubmissionRequest;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// 退回提交（班级教师、管理员）
+pub async fn request_resubmission(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    submission_id: i64,
+    req: RequestResubmissionRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let submission = match storage.get_submission_by_id(submission_id).await {
+        Ok(Some(sub)) => sub,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionNotFound,
+                "提交不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询提交失败: {e}"))),
+    };
+    let homework = match storage.get_homework_by_id(submission.homework_id).await {
+        Ok(Some(hw)) => hw,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "关联作业不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询作业失败: {e}"))),
+    };
+
+    let user_id = match check_class_member(&storage, request, homework.class_id).await {
+        Ok((user_id, None | Some(ClassUserRole::Teacher))) => user_id,
+        Ok(_) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只有班级教师可以退回提交",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+
+    if let Err(message) = req.validate(chrono::Utc::now()) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::ResubmissionRequestInvalid,
+            message,
+        )));
+    }
+
+    match storage
+        .request_resubmission(
+            submission_id,
+            user_id,
+            req.comment.trim().to_string(),
+            req.reopen_until.timestamp(),
+        )
+        .await
+    {
+        Ok(Some(resubmission)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            resubmission,
+            "已退回，等待学生重新提交",
+        ))),
+        Ok(None) => Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::SubmissionStatusTransitionInvalid,
+            "只能退回学生最新的提交，且该提交尚未被退回",
+        ))),
+        Err(e) => Ok(internal_error(format!("退回提交失败: {e}"))),
+    }
+}
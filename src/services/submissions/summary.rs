@@ -2,11 +2,14 @@
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
 use crate::models::class_users::entities::ClassUserRole;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::submissions::requests::{SubmissionSummaryFilter, SubmissionSummaryQuery};
 use crate::models::users::entities::UserRole;
 use crate::models::{ApiResponse, ErrorCode};
 use crate::services::submissions::SubmissionService;
+use crate::services::system::DynamicConfig;
 
 /// 获取作业提交概览（按学生聚合）
 ///
@@ -16,14 +19,18 @@ use crate::services::submissions::SubmissionService;
 ///
 /// 参数：
 /// - `graded`: 筛选是否已批改，true=已批改，false=待批改，None=全部
+/// - `overdue`: 筛选是否超过批改时限（系统设置 `grading.sla_days`）仍未评分
 pub async fn get_submission_summary(
     service: &SubmissionService,
     request: &HttpRequest,
     homework_id: i64,
-    page: Option<i64>,
-    size: Option<i64>,
-    graded: Option<bool>,
+    query: SubmissionSummaryQuery,
 ) -> ActixResult<HttpResponse> {
+    let policy = PaginationPolicy::for_endpoint("submissions");
+    if let Err(limit) = policy.check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
 
     // 获取当前用户信息
@@ -56,15 +63,27 @@ pub async fn get_submission_summary(
         }
     };
 
-    // 权限检查：Admin 直接放行，其他用户检查班级角色
+    // 权限检查：Admin 只校验组织，其他用户检查班级角色
     // 同时确定是否可以查看成绩（教师和管理员可以，课代表不可以）
     let include_grades = if current_user.role == UserRole::Admin {
+        if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+            return Ok(
+                HttpResponse::NotFound().json(ApiResponse::<()>::error_empty(
+                    ErrorCode::HomeworkNotFound,
+                    "作业不存在",
+                )),
+            );
+        }
         true // Admin 可以查看成绩
     } else {
         // 非 Admin 用户需要验证班级成员资格
-        let class_user = match storage
-            .get_class_user_by_user_id_and_class_id(current_user.id, homework.class_id)
-            .await
+        let class_user = match RequireClassRole::class_user(
+            request,
+            &storage,
+            current_user.id,
+            homework.class_id,
+        )
+        .await
         {
             Ok(Some(cu)) => cu,
             Ok(None) => {
@@ -102,11 +121,18 @@ pub async fn get_submission_summary(
     };
 
     // 获取提交概览
-    let page = page.unwrap_or(1);
-    let size = size.unwrap_or(20);
+    let page = query.page.unwrap_or(1);
+    let size = query.size.unwrap_or(policy.default_size);
+    let sla_days = DynamicConfig::grading_sla_days().await;
+    let filter = SubmissionSummaryFilter {
+        graded: query.graded,
+        overdue: query.overdue,
+        overdue_before: chrono::Utc::now().timestamp() - sla_days * 86400,
+        draft_grader_id: include_grades.then_some(current_user.id),
+    };
 
     let summary = match storage
-        .get_submission_summary(homework_id, page, size, include_grades, graded)
+        .get_submission_summary(homework_id, page, size, include_grades, filter)
         .await
     {
         Ok(s) => s,
@@ -166,15 +192,27 @@ pub async fn list_user_submissions_for_teacher(
         }
     };
 
-    // 权限检查：Admin 直接放行，其他用户检查班级角色
+    // 权限检查：Admin 只校验组织，其他用户检查班级角色
     // 同时确定是否可以查看成绩（教师和管理员可以，课代表不可以）
     let include_grades = if current_user.role == UserRole::Admin {
+        if !TenantGuard::can_access_class(request, &storage, homework.class_id).await {
+            return Ok(
+                HttpResponse::NotFound().json(ApiResponse::<()>::error_empty(
+                    ErrorCode::HomeworkNotFound,
+                    "作业不存在",
+                )),
+            );
+        }
         true // Admin 可以查看成绩
     } else {
         // 非 Admin 用户需要验证班级成员资格
-        let class_user = match storage
-            .get_class_user_by_user_id_and_class_id(current_user.id, homework.class_id)
-            .await
+        let class_user = match RequireClassRole::class_user(
+            request,
+            &storage,
+            current_user.id,
+            homework.class_id,
+        )
+        .await
         {
             Ok(Some(cu)) => cu,
             Ok(None) => {
@@ -0,0 +1,348 @@
+//! 提交状态工作流
+//!
+//! 内置状态 pending / late / graded / returned 由提交、评分与退回自动维护。班级可额外定义
+//! 自定义状态（如「需要修改」）及允许的转换，转换按班级角色授权；评分会清除自定义状态。
+//! 未自定义的班级沿用默认工作流，不支持手动转换。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::SubmissionService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::submissions::entities::{SubmissionWorkflow, SubmissionWorkflowStatus};
+use crate::models::submissions::requests::{
+    TransitionSubmissionStatusRequest, UpdateSubmissionWorkflowRequest,
+};
+use crate::models::submissions::responses::{
+    SubmissionStatusTransitionResponse, SubmissionWorkflowResponse, SubmissionWorkflowStatusItem,
+};
+use crate::models::users::entities::UserRole;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+pub(super) fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+/// 读取班级当前生效的工作流
+async fn load_class_workflow(
+    storage: &Arc<dyn Storage>,
+    class_id: i64,
+) -> Result<SubmissionWorkflow, HttpResponse> {
+    storage
+        .get_class_submission_workflow(class_id)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| internal_error(format!("查询提交工作流失败: {e}")))
+}
+
+/// 校验班级存在且当前用户可访问，返回（用户 ID, 班级角色）；管理员的班级角色为 None
+pub(super) async fn check_class_member(
+    storage: &Arc<dyn Storage>,
+    request: &HttpRequest,
+    class_id: i64,
+) -> Result<(i64, Option<ClassUserRole>), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        ))
+    })?;
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return Err(internal_error(format!("查询班级失败: {e}"))),
+    }
+
+    if RequireJWT::extract_user_role(request) == Some(UserRole::Admin) {
+        return Ok((user_id, None));
+    }
+
+    match RequireClassRole::class_user(request, storage, user_id, class_id).await {
+        Ok(Some(cu)) => Ok((user_id, Some(cu.role))),
+        Ok(None) => Err(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ClassPermissionDenied,
+            "您不是该班级成员",
+        ))),
+        Err(e) => Err(internal_error(format!("查询班级成员失败: {e}"))),
+    }
+}
+
+fn build_response(class_id: i64, workflow: SubmissionWorkflow) -> SubmissionWorkflowResponse {
+    let builtin =
+        SubmissionWorkflow::BUILTIN_STATUSES
+            .iter()
+            .map(|&key| SubmissionWorkflowStatusItem {
+                key: key.to_string(),
+                label: workflow.label(key).unwrap_or(key).to_string(),
+                builtin: true,
+            });
+    let custom = workflow
+        .statuses
+        .iter()
+        .map(|status| SubmissionWorkflowStatusItem {
+            key: status.key.clone(),
+            label: status.label.clone(),
+            builtin: false,
+        });
+
+    SubmissionWorkflowResponse {
+        class_id,
+        customized: !workflow.is_default(),
+        statuses: builtin.chain(custom).collect(),
+        transitions: workflow.transitions,
+    }
+}
+
+/// 查看班级提交状态工作流（班级成员）
+pub async fn get_class_submission_workflow(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    class_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    if let Err(resp) = check_class_member(&storage, request, class_id).await {
+        return Ok(resp);
+    }
+
+    match load_class_workflow(&storage, class_id).await {
+        Ok(workflow) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            build_response(class_id, workflow),
+            "查询成功",
+        ))),
+        Err(resp) => Ok(resp),
+    }
+}
+
+/// 更新班级提交状态工作流（班级教师、管理员）
+pub async fn update_class_submission_workflow(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    class_id: i64,
+    req: UpdateSubmissionWorkflowRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user_id = match check_class_member(&storage, request, class_id).await {
+        Ok((user_id, None | Some(ClassUserRole::Teacher))) => user_id,
+        Ok(_) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只有班级教师可以设置提交状态工作流",
+            )));
+        }
+        Err(resp) => return Ok(resp),
+    };
+
+    let workflow = SubmissionWorkflow {
+        statuses: req
+            .statuses
+            .into_iter()
+            .map(|s| SubmissionWorkflowStatus {
+                key: s.key.trim().to_string(),
+                label: s.label.trim().to_string(),
+            })
+            .collect(),
+        transitions: req.transitions,
+    };
+    if let Err(message) = workflow.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::SubmissionWorkflowInvalid,
+            message,
+        )));
+    }
+
+    match storage
+        .save_class_submission_workflow(class_id, workflow.clone(), user_id)
+        .await
+    {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            build_response(class_id, workflow),
+            "提交状态工作流已保存",
+        ))),
+        Err(e) => Ok(internal_error(format!("保存提交工作流失败: {e}"))),
+    }
+}
+
+/// 按班级工作流转换提交状态
+///
+/// 权限：转换声明的班级角色；学生只能转换自己的提交。管理员不受角色限制。
+pub async fn transition_submission_status(
+    service: &SubmissionService,
+    request: &HttpRequest,
+    submission_id: i64,
+    req: TransitionSubmissionStatusRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let submission = match storage.get_submission_by_id(submission_id).await {
+        Ok(Some(sub)) => sub,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::SubmissionNotFound,
+                "提交不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询提交失败: {e}"))),
+    };
+    let homework = match storage.get_homework_by_id(submission.homework_id).await {
+        Ok(Some(hw)) => hw,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::HomeworkNotFound,
+                "关联作业不存在",
+            )));
+        }
+        Err(e) => return Ok(internal_error(format!("查询作业失败: {e}"))),
+    };
+
+    let (user_id, class_role) = match check_class_member(&storage, request, homework.class_id).await
+    {
+        Ok(checked) => checked,
+        Err(resp) => return Ok(resp),
+    };
+    let workflow = match load_class_workflow(&storage, homework.class_id).await {
+        Ok(workflow) => workflow,
+        Err(resp) => return Ok(resp),
+    };
+
+    let from = submission.effective_status();
+    let to = req.status.trim();
+    let Some(transition) = workflow.transition(&from, to) else {
+        return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::SubmissionStatusTransitionInvalid,
+            format!(
+                "不允许从「{}」转换为「{to}」",
+                workflow.label(&from).unwrap_or(&from)
+            ),
+        )));
+    };
+
+    if let Some(role) = class_role {
+        if !transition.roles.contains(&role) {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "您的班级角色不能执行该状态转换",
+            )));
+        }
+        if role == ClassUserRole::Student && submission.creator_id != user_id {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::Forbidden,
+                "只能转换自己提交的状态",
+            )));
+        }
+    }
+
+    match storage
+        .transition_submission_status(submission_id, &from, to)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            SubmissionStatusTransitionResponse {
+                submission_id,
+                label: workflow.label(to).unwrap_or(to).to_string(),
+                from,
+                to: to.to_string(),
+            },
+            "提交状态已更新",
+        ))),
+        // 期间状态已被其他请求修改
+        Ok(false) => Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+            ErrorCode::SubmissionStatusTransitionInvalid,
+            "提交状态已变化，请刷新后重试",
+        ))),
+        Err(e) => Ok(internal_error(format!("更新提交状态失败: {e}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::submissions::entities::SubmissionWorkflowTransition;
+
+    fn status(key: &str) -> SubmissionWorkflowStatus {
+        SubmissionWorkflowStatus {
+            key: key.to_string(),
+            label: key.to_string(),
+        }
+    }
+
+    fn transition(from: &str, to: &str) -> SubmissionWorkflowTransition {
+        SubmissionWorkflowTransition {
+            from: from.to_string(),
+            to: to.to_string(),
+            roles: vec![ClassUserRole::Teacher],
+        }
+    }
+
+    #[test]
+    fn test_validate_workflow() {
+        assert!(SubmissionWorkflow::default().validate().is_ok());
+
+        let workflow = SubmissionWorkflow {
+            statuses: vec![status("needs_revision"), status("resubmitted")],
+            transitions: vec![
+                transition("pending", "needs_revision"),
+                transition("graded", "needs_revision"),
+                transition("needs_revision", "resubmitted"),
+            ],
+        };
+        assert!(workflow.validate().is_ok());
+
+        // 与内置状态重名、标识非法、重复
+        for key in ["graded", "Returned", "", "needs-review"] {
+            let invalid = SubmissionWorkflow {
+                statuses: vec![status(key)],
+                transitions: vec![],
+            };
+            assert!(invalid.validate().is_err(), "{key}");
+        }
+        let duplicated = SubmissionWorkflow {
+            statuses: vec![status("needs_revision"), status("needs_revision")],
+            transitions: vec![],
+        };
+        assert!(duplicated.validate().is_err());
+
+        // 目标必须是自定义状态，起始状态必须已定义
+        for (from, to) in [
+            ("needs_revision", "graded"),
+            ("unknown", "needs_revision"),
+            ("needs_revision", "needs_revision"),
+        ] {
+            let invalid = SubmissionWorkflow {
+                statuses: vec![status("needs_revision")],
+                transitions: vec![transition(from, to)],
+            };
+            assert!(invalid.validate().is_err(), "{from} -> {to}");
+        }
+    }
+
+    #[test]
+    fn test_build_response() {
+        let response = build_response(1, SubmissionWorkflow::default());
+        assert!(!response.customized);
+        assert_eq!(response.statuses.len(), 4);
+        assert!(response.statuses.iter().all(|s| s.builtin));
+
+        let workflow = SubmissionWorkflow {
+            statuses: vec![status("needs_revision")],
+            transitions: vec![transition("pending", "needs_revision")],
+        };
+        let response = build_response(1, workflow);
+        assert!(response.customized);
+        assert_eq!(response.statuses[4].key, "needs_revision");
+        assert!(!response.statuses[4].builtin);
+    }
+}
@@ -0,0 +1,127 @@
+//! 品牌设置
+//!
+//! 品牌配置存储在系统设置（`branding.*`）中，通过 DynamicConfig 缓存读取；
+//! 公开接口供前端在登录前获取系统名称、Logo、主题色等信息。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, http::header, web};
+
+use super::DynamicConfig;
+use crate::models::{ApiResponse, ErrorCode, system::responses::BrandingResponse};
+use crate::services::files::tiering::stored_path;
+use crate::storage::Storage;
+use crate::utils::validate::validate_hex_color;
+
+/// 公开品牌接口的浏览器缓存时间（秒）
+const BRANDING_CACHE_MAX_AGE: u32 = 300;
+
+/// Logo 允许的图片类型
+const LOGO_EXTENSIONS: [&str; 5] = [".png", ".jpg", ".jpeg", ".gif", ".webp"];
+
+/// Logo 公开访问路径（相对于 API 版本前缀）
+const LOGO_PATH: &str = "/system/branding/logo";
+
+/// 校验品牌设置的值（长度等通用约束见 `KnownSettingKey::constraints`），非品牌设置直接通过
+pub async fn validate_branding_setting(
+    storage: &Arc<dyn Storage>,
+    key: &str,
+    value: &str,
+) -> Result<(), String> {
+    match key {
+        "branding.logo_token" => {
+            if value.is_empty() {
+                return Ok(());
+            }
+            let file = storage
+                .get_file_by_token(value)
+                .await
+                .map_err(|e| format!("查询 Logo 文件失败: {e}"))?
+                .ok_or_else(|| "Logo 文件不存在".to_string())?;
+            let name = file.original_name.to_lowercase();
+            if !LOGO_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
+                return Err(format!(
+                    "Logo 必须为图片文件（{}）",
+                    LOGO_EXTENSIONS.join(", ")
+                ));
+            }
+            Ok(())
+        }
+        "branding.primary_color" => validate_hex_color(value).map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// 获取品牌设置（公开）
+pub async fn get_branding(req: HttpRequest) -> ActixResult<HttpResponse> {
+    let logo_url = DynamicConfig::branding_logo_token().await.map(|_| {
+        // 保持与当前请求相同的 API 版本前缀
+        let prefix = req
+            .path()
+            .strip_suffix("/system/branding")
+            .unwrap_or_default();
+        format!("{prefix}{LOGO_PATH}")
+    });
+
+    let response = BrandingResponse {
+        system_name: DynamicConfig::system_name().await,
+        logo_url,
+        primary_color: DynamicConfig::branding_primary_color().await,
+        login_message: DynamicConfig::branding_login_message().await,
+        support_contact: DynamicConfig::branding_support_contact().await,
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("public, max-age={BRANDING_CACHE_MAX_AGE}"),
+        ))
+        .json(ApiResponse::success(
+            response,
+            "Branding retrieved successfully",
+        )))
+}
+
+/// 获取品牌 Logo（公开）
+pub async fn get_branding_logo(
+    _req: HttpRequest,
+    storage: web::Data<Arc<dyn Storage>>,
+) -> ActixResult<HttpResponse> {
+    let not_found = || {
+        HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::FileNotFound,
+            "Logo not configured",
+        ))
+    };
+
+    let Some(token) = DynamicConfig::branding_logo_token().await else {
+        return Ok(not_found());
+    };
+
+    let file = match storage.get_file_by_token(&token).await {
+        Ok(Some(f)) => f,
+        Ok(None) => return Ok(not_found()),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("File query failed: {e}"),
+                )),
+            );
+        }
+    };
+
+    let file_path = stored_path(&file);
+    let data = match std::fs::read(&file_path) {
+        Ok(data) => data,
+        Err(_) => return Ok(not_found()),
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, file.file_type.as_str()))
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("public, max-age={BRANDING_CACHE_MAX_AGE}"),
+        ))
+        .body(data))
+}
@@ -0,0 +1,102 @@
+//! SQLite 数据库维护
+//!
+//! 供未部署独立数据库运维的小规模站点使用：查看数据库与 WAL 文件大小、手动触发 WAL 检查点、
+//! 在不停机的情况下生成备份。备份写入配置的 `backup.dir` 目录，文件名带时间戳。
+
+use std::path::Path;
+use std::sync::Arc;
+
+use actix_web::{HttpResponse, Result as ActixResult, web};
+
+use crate::config::AppConfig;
+use crate::models::system::requests::WalCheckpointQuery;
+use crate::models::system::responses::DatabaseBackupResponse;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+fn internal_error(message: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+        ErrorCode::InternalServerError,
+        message,
+    ))
+}
+
+fn unsupported() -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::DatabaseMaintenanceUnsupported,
+        "仅 SQLite 数据库支持该操作",
+    ))
+}
+
+/// 查询数据库文件状态
+pub async fn get_database_status(
+    storage: web::Data<Arc<dyn Storage>>,
+) -> ActixResult<HttpResponse> {
+    match storage.get_database_status().await {
+        Ok(status) => Ok(HttpResponse::Ok().json(ApiResponse::success(status, "查询成功"))),
+        Err(e) => Ok(internal_error(format!("查询数据库状态失败: {e}"))),
+    }
+}
+
+/// 执行 WAL 检查点
+pub async fn checkpoint_wal(
+    storage: web::Data<Arc<dyn Storage>>,
+    query: web::Query<WalCheckpointQuery>,
+) -> ActixResult<HttpResponse> {
+    let mode = query.into_inner().mode;
+
+    match storage.checkpoint_wal(mode).await {
+        Ok(Some(result)) => {
+            tracing::info!(
+                "WAL checkpoint ({mode}): busy={}, log={}, checkpointed={}",
+                result.busy,
+                result.log_frames,
+                result.checkpointed_frames
+            );
+            Ok(HttpResponse::Ok().json(ApiResponse::success(result, "检查点已完成")))
+        }
+        Ok(None) => Ok(unsupported()),
+        Err(e) => Ok(internal_error(format!("执行 WAL 检查点失败: {e}"))),
+    }
+}
+
+/// 在线备份数据库
+pub async fn backup_database(storage: web::Data<Arc<dyn Storage>>) -> ActixResult<HttpResponse> {
+    let dir = Path::new(&AppConfig::get().backup.dir);
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        return Ok(internal_error(format!("创建备份目录失败: {e}")));
+    }
+
+    let created_at = chrono::Utc::now();
+    let file_name = format!(
+        "hwsystem-{}-{}.db",
+        created_at.format("%Y%m%d%H%M%S"),
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let target = dir.join(&file_name);
+
+    let started = std::time::Instant::now();
+    match storage.backup_database(&target).await {
+        Ok(true) => {
+            let size = std::fs::metadata(&target)
+                .map(|m| m.len() as i64)
+                .unwrap_or(0);
+            tracing::info!("Database backed up to {} ({size} bytes)", target.display());
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                DatabaseBackupResponse {
+                    file_name,
+                    size,
+                    created_at,
+                    elapsed_ms: started.elapsed().as_millis() as i64,
+                },
+                "备份完成",
+            )))
+        }
+        Ok(false) => Ok(unsupported()),
+        Err(e) => {
+            // 失败时可能留下不完整的文件
+            let _ = std::fs::remove_file(&target);
+            Ok(internal_error(format!("备份数据库失败: {e}")))
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! 压测数据生成（仅开发环境）
+//!
+//! 通过存储层批量写入生成 N 个班级 × M 名学生 × K 份作业及其提交、评分，
+//! 用于在接近真实的数据量下测量统计、列表类接口的性能。生成在后台任务中执行。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+
+use crate::config::AppConfig;
+use crate::middlewares::RequireJWT;
+use crate::models::system::requests::GenerateDevDataRequest;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::jobs::{self, JobOutput};
+use crate::storage::Storage;
+use crate::utils::password::hash_password;
+
+/// 生成用户的统一登录密码
+pub const DEV_DATA_PASSWORD: &str = "loadtest123";
+
+const MAX_CLASSES: u32 = 200;
+const MAX_STUDENTS_PER_CLASS: u32 = 500;
+const MAX_HOMEWORKS_PER_CLASS: u32 = 100;
+/// 单次生成的最大潜在提交数（班级 × 学生 × 作业）
+const MAX_POTENTIAL_SUBMISSIONS: u64 = 1_000_000;
+
+/// 提交压测数据生成任务
+pub async fn generate_dev_data(
+    req: HttpRequest,
+    storage: web::Data<Arc<dyn Storage>>,
+    body: web::Json<GenerateDevDataRequest>,
+) -> ActixResult<HttpResponse> {
+    if !AppConfig::get().is_development() {
+        return Ok(HttpResponse::NotFound()
+            .json(ApiResponse::error_empty(ErrorCode::NotFound, "接口不存在")));
+    }
+
+    let Some(user_id) = RequireJWT::extract_user_id(&req) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let body = body.into_inner();
+    if let Err(msg) = validate_request(&body) {
+        return Ok(
+            HttpResponse::BadRequest().json(ApiResponse::error_empty(ErrorCode::BadRequest, msg))
+        );
+    }
+
+    let storage = storage.get_ref().clone();
+    let submitted = jobs::submit_job(user_id, "dev_data", async move {
+        let started = std::time::Instant::now();
+        let password_hash = hash_password(DEV_DATA_PASSWORD).map_err(|e| e.to_string())?;
+        let mut summary = storage
+            .generate_dev_data(&password_hash, &body)
+            .await
+            .map_err(|e| e.to_string())?;
+        summary.elapsed_ms = started.elapsed().as_millis() as i64;
+
+        tracing::info!(
+            "Generated dev data batch {}: {} classes, {} students, {} homeworks, {} submissions in {} ms",
+            summary.batch,
+            summary.classes,
+            summary.students,
+            summary.homeworks,
+            summary.submissions,
+            summary.elapsed_ms
+        );
+
+        Ok(JobOutput {
+            file_name: format!("dev-data-{}.json", summary.batch),
+            content_type: "application/json",
+            data: serde_json::to_vec_pretty(&summary).map_err(|e| e.to_string())?,
+        })
+    })
+    .await;
+    let job = match submitted {
+        Ok(job) => job,
+        Err(e) => return Ok(jobs::queue_full_response(e)),
+    };
+
+    Ok(HttpResponse::Accepted().json(ApiResponse::success(
+        job,
+        "压测数据生成中，完成后可通过任务接口下载生成结果",
+    )))
+}
+
+/// 校验生成规模与比例参数
+fn validate_request(req: &GenerateDevDataRequest) -> Result<(), String> {
+    if req.classes == 0 || req.classes > MAX_CLASSES {
+        return Err(format!("班级数量需在 1~{MAX_CLASSES} 之间"));
+    }
+    if req.students_per_class > MAX_STUDENTS_PER_CLASS {
+        return Err(format!("每班学生数不能超过 {MAX_STUDENTS_PER_CLASS}"));
+    }
+    if req.homeworks_per_class > MAX_HOMEWORKS_PER_CLASS {
+        return Err(format!("每班作业数不能超过 {MAX_HOMEWORKS_PER_CLASS}"));
+    }
+    let potential =
+        req.classes as u64 * req.students_per_class as u64 * req.homeworks_per_class as u64;
+    if potential > MAX_POTENTIAL_SUBMISSIONS {
+        return Err(format!(
+            "班级数 × 每班学生数 × 每班作业数不能超过 {MAX_POTENTIAL_SUBMISSIONS}"
+        ));
+    }
+    for (name, rate) in [
+        ("submission_rate", req.submission_rate),
+        ("graded_rate", req.graded_rate),
+    ] {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(format!("{name} 需在 0~1 之间"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(classes: u32, students: u32, homeworks: u32) -> GenerateDevDataRequest {
+        GenerateDevDataRequest {
+            classes,
+            students_per_class: students,
+            homeworks_per_class: homeworks,
+            submission_rate: 0.8,
+            graded_rate: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_validate_request_bounds() {
+        assert!(validate_request(&request(10, 50, 20)).is_ok());
+        assert!(validate_request(&request(0, 50, 20)).is_err());
+        assert!(validate_request(&request(MAX_CLASSES + 1, 1, 1)).is_err());
+        assert!(validate_request(&request(200, 500, 100)).is_err());
+    }
+
+    #[test]
+    fn test_validate_request_rates() {
+        let mut req = request(1, 1, 1);
+        req.submission_rate = 1.5;
+        assert!(validate_request(&req).is_err());
+        req.submission_rate = 1.0;
+        req.graded_rate = f64::NAN;
+        assert!(validate_request(&req).is_err());
+    }
+}
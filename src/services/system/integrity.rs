@@ -0,0 +1,74 @@
+//! 数据一致性检查
+//!
+//! 管理员触发后在后台任务中检查孤立的班级成员、孤立提交、文件引用计数偏差、孤立评分与作业计数偏差，
+//! 结果以 JSON 报告形式通过任务接口下载。开启 `repair` 时同一事务内修复其中可安全修复的问题。
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
+
+use crate::middlewares::RequireJWT;
+use crate::models::system::requests::IntegrityCheckRequest;
+use crate::models::system::responses::IntegrityReport;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::jobs::{self, JobOutput};
+use crate::storage::Storage;
+
+/// 提交数据一致性检查任务
+pub async fn run_integrity_check(
+    req: HttpRequest,
+    storage: web::Data<Arc<dyn Storage>>,
+    body: Option<web::Json<IntegrityCheckRequest>>,
+) -> ActixResult<HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(&req) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let repair = body.map(|b| b.into_inner()).unwrap_or_default().repair;
+    let storage = storage.get_ref().clone();
+    let submitted = jobs::submit_job(user_id, "integrity_check", async move {
+        let started_at = chrono::Utc::now();
+        let started = std::time::Instant::now();
+        let checks = storage
+            .run_integrity_checks(repair)
+            .await
+            .map_err(|e| e.to_string())?;
+        let report = IntegrityReport {
+            repair,
+            total_found: checks.iter().map(|c| c.found).sum(),
+            total_repaired: checks.iter().map(|c| c.repaired).sum(),
+            checks,
+            started_at,
+            elapsed_ms: started.elapsed().as_millis() as i64,
+        };
+
+        tracing::info!(
+            "Integrity check by user {} finished: {} issues found, {} repaired",
+            user_id,
+            report.total_found,
+            report.total_repaired
+        );
+
+        Ok(JobOutput {
+            file_name: format!(
+                "integrity-report-{}.json",
+                started_at.format("%Y%m%d%H%M%S")
+            ),
+            content_type: "application/json",
+            data: serde_json::to_vec_pretty(&report).map_err(|e| e.to_string())?,
+        })
+    })
+    .await;
+    let job = match submitted {
+        Ok(job) => job,
+        Err(e) => return Ok(jobs::queue_full_response(e)),
+    };
+
+    Ok(HttpResponse::Accepted().json(ApiResponse::success(
+        job,
+        "数据一致性检查中，完成后可通过任务接口下载报告",
+    )))
+}
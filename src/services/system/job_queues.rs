@@ -0,0 +1,13 @@
+//! 后台任务队列监控
+//!
+//! 返回各命名队列的并发上限、当前积压与累计计数，用于观察后台任务是否堆积。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use crate::models::ApiResponse;
+use crate::runtime::executor;
+
+/// 获取后台任务队列状态（仅管理员）
+pub async fn get_job_queues(_req: HttpRequest) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ApiResponse::success(executor::queue_stats(), "查询成功")))
+}
@@ -1,5 +1,13 @@
+pub mod branding;
+pub mod database;
+pub mod dev_data;
+pub mod integrity;
+pub mod job_queues;
+pub mod propagation;
+pub mod registration;
 pub mod settings;
 pub mod settings_cache;
+pub mod time;
 
 pub use settings_cache::DynamicConfig;
 
@@ -0,0 +1,73 @@
+//! 系统设置变更传播
+//!
+//! 将设置变更推送给运行中的子系统，使其无需重启即可生效：
+//! - `jwt.*`：令牌有效期
+//! - `cors.*`：跨域来源、预检缓存时间
+//! - `rate_limit.<前缀>`：对应端点的请求上限
+//! - `jobs.*`：后台定时任务间隔
+//!
+//! 上传限制（`upload.*`）在每次上传时直接读取 [`DynamicConfig`]，无需订阅。
+
+use super::DynamicConfig;
+use crate::middlewares::{cors, rate_limit};
+use crate::services::classes::scheduled_export_job;
+use crate::services::files::{retention_job, tiering_job};
+use crate::services::grades::sla_job;
+use crate::services::homeworks::{missing_grade_job, solution_job};
+use crate::services::im_delivery::worker;
+use crate::utils::jwt::JwtUtils;
+
+/// 注册各子系统的设置监听器，需在动态配置初始化之前调用
+pub fn register_setting_listeners() {
+    DynamicConfig::subscribe("jwt.", |key, value| {
+        let Ok(value) = value.parse::<i64>() else {
+            return;
+        };
+        match key {
+            "jwt.access_token_expiry" => JwtUtils::set_access_token_expiry(value),
+            "jwt.refresh_token_expiry" => JwtUtils::set_refresh_token_expiry(value),
+            "jwt.refresh_token_remember_me_expiry" => {
+                JwtUtils::set_refresh_token_remember_me_expiry(value)
+            }
+            _ => {}
+        }
+    });
+
+    DynamicConfig::subscribe("cors.", |key, value| match key {
+        "cors.allowed_origins" => {
+            if let Ok(origins) = serde_json::from_str(value) {
+                cors::set_allowed_origins(origins);
+            }
+        }
+        "cors.max_age" => {
+            if let Ok(secs) = value.parse() {
+                cors::set_max_age(secs);
+            }
+        }
+        _ => {}
+    });
+
+    DynamicConfig::subscribe("rate_limit.", |key, value| {
+        if let Some(prefix) = key.strip_prefix("rate_limit.")
+            && let Ok(max_requests) = value.parse()
+        {
+            rate_limit::set_limit_override(prefix, max_requests);
+        }
+    });
+
+    DynamicConfig::subscribe("jobs.", |key, value| {
+        let Ok(secs) = value.parse() else {
+            return;
+        };
+        match key {
+            "jobs.solution_reveal_interval" => solution_job::CHECK_INTERVAL.set(secs),
+            "jobs.grading_sla_interval" => sla_job::CHECK_INTERVAL.set(secs),
+            "jobs.file_retention_interval" => retention_job::CHECK_INTERVAL.set(secs),
+            "jobs.file_tiering_interval" => tiering_job::CHECK_INTERVAL.set(secs),
+            "jobs.missing_grade_interval" => missing_grade_job::CHECK_INTERVAL.set(secs),
+            "jobs.im_reminder_interval" => worker::REMINDER_INTERVAL.set(secs),
+            "jobs.scheduled_export_interval" => scheduled_export_job::CHECK_INTERVAL.set(secs),
+            _ => {}
+        }
+    });
+}
@@ -0,0 +1,53 @@
+//! 注册策略
+//!
+//! 注册策略存储在系统设置（`registration.*`）中，由注册接口强制执行；
+//! 公开接口供前端在注册前调整表单（邀请码、邮箱提示、人机验证）。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::DynamicConfig;
+use crate::models::{ApiResponse, system::responses::RegistrationPolicyResponse};
+
+/// 获取注册策略（公开）
+pub async fn get_registration_policy(_req: HttpRequest) -> ActixResult<HttpResponse> {
+    let response = RegistrationPolicyResponse {
+        mode: DynamicConfig::registration_mode().await,
+        email_domains: DynamicConfig::registration_email_domains().await,
+        default_role: DynamicConfig::registration_default_role().await,
+        captcha_required: DynamicConfig::registration_captcha_required().await,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        response,
+        "Registration policy retrieved successfully",
+    )))
+}
+
+/// 邮箱是否属于允许的域名（含子域名），未配置域名时全部允许
+pub fn email_domain_allowed(email: &str, domains: &[String]) -> bool {
+    if domains.is_empty() {
+        return true;
+    }
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    let domain = domain.to_lowercase();
+    domains
+        .iter()
+        .any(|allowed| domain == *allowed || domain.ends_with(&format!(".{allowed}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_domain_allowed() {
+        let domains = vec!["school.edu".to_string()];
+        assert!(email_domain_allowed("a@school.edu", &domains));
+        assert!(email_domain_allowed("a@Mail.School.EDU", &domains));
+        assert!(!email_domain_allowed("a@evilschool.edu", &domains));
+        assert!(!email_domain_allowed("a@school.edu.cn", &domains));
+        assert!(email_domain_allowed("a@gmail.com", &[]));
+    }
+}
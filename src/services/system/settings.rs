@@ -1,18 +1,28 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, web};
 
-use super::{DynamicConfig, SystemService};
+use super::{DynamicConfig, SystemService, branding::validate_branding_setting};
 use crate::middlewares::RequireJWT;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::models::{
     ApiResponse, ErrorCode,
     system::{
-        requests::{SettingAuditQuery, UpdateSettingRequest},
-        responses::{AdminSettingsListResponse, SettingResponse, SystemSettingsResponse},
+        entities::{
+            KnownSettingKey, SettingChange, SettingValueType, SettingsUpdateOutcome, SystemSetting,
+        },
+        requests::{
+            BatchUpdateSettingsRequest, SettingAuditQuery, UpdateSettingItem, UpdateSettingRequest,
+        },
+        responses::{
+            AdminSettingsListResponse, BatchUpdateSettingsResponse, SettingDefinition,
+            SettingResponse, SettingSchemaResponse, SystemSettingsResponse,
+        },
     },
 };
 use crate::storage::Storage;
-use crate::utils::SafeSettingKey;
+use crate::utils::{ClientInfo, SafeSettingKey};
 
 /// 获取公开系统设置（只读）
 pub async fn get_settings(
@@ -37,6 +47,12 @@ pub async fn get_settings(
     )))
 }
 
+/// 自定义配置项键名最大长度
+const CUSTOM_KEY_MAX_LEN: usize = 100;
+
+/// 自定义配置项值最大长度（字符）
+const CUSTOM_VALUE_MAX_LEN: usize = 2000;
+
 /// 获取所有管理员配置
 pub async fn get_admin_settings(
     _req: HttpRequest,
@@ -54,7 +70,19 @@ pub async fn get_admin_settings(
         }
     };
 
-    let response = AdminSettingsListResponse { settings };
+    let version = match storage.get_settings_version().await {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("获取配置版本失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let response = AdminSettingsListResponse { settings, version };
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(
         response,
@@ -62,6 +90,23 @@ pub async fn get_admin_settings(
     )))
 }
 
+/// 获取已登记配置项的类型与约束
+pub async fn get_setting_schema(_req: HttpRequest) -> ActixResult<HttpResponse> {
+    let settings = KnownSettingKey::all()
+        .into_iter()
+        .map(|key| SettingDefinition {
+            key: key.as_str().to_string(),
+            value_type: key.value_type(),
+            constraints: key.constraints(),
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        SettingSchemaResponse { settings },
+        "Setting schema retrieved successfully",
+    )))
+}
+
 /// 更新单个配置
 pub async fn update_setting(
     req: HttpRequest,
@@ -69,45 +114,46 @@ pub async fn update_setting(
     body: web::Json<UpdateSettingRequest>,
     storage: web::Data<Arc<dyn Storage>>,
 ) -> ActixResult<HttpResponse> {
-    let key = path.0;
+    let body = body.into_inner();
+    let item = UpdateSettingItem {
+        key: path.0,
+        value: body.value,
+        custom: body.custom,
+    };
 
-    // 获取当前用户 ID
-    let user_id = match RequireJWT::extract_user_id(&req) {
-        Some(id) => id,
-        None => {
-            return Ok(
-                HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::Unauthorized,
-                    "用户未登录",
-                )),
-            );
-        }
+    let changes = match validate_items(storage.get_ref(), vec![item.clone()]).await {
+        Ok(changes) => changes,
+        Err(resp) => return Ok(resp),
     };
 
-    // 获取客户端 IP
-    let ip_address = req
-        .connection_info()
-        .realip_remote_addr()
-        .map(|s| s.to_string());
-
-    // 更新配置
-    let setting = match storage
-        .update_setting(&key, &body.value, user_id, ip_address)
-        .await
-    {
-        Ok(s) => s,
-        Err(e) => {
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::InternalServerError,
-                    format!("更新配置失败: {e}"),
-                )),
-            );
-        }
+    let outcome = match apply_changes(&req, storage.get_ref(), changes, None).await {
+        Ok(outcome) => outcome,
+        Err(resp) => return Ok(resp),
     };
 
-    // 更新缓存
-    DynamicConfig::update(&key, &body.value).await;
+    // 值未变化时返回当前配置
+    let setting = match outcome {
+        (mut changed, _, _) if !changed.is_empty() => changed.remove(0),
+        _ => match storage.get_setting_by_key(&item.key).await {
+            Ok(Some(setting)) => setting,
+            Ok(None) => {
+                return Ok(
+                    HttpResponse::NotFound().json(ApiResponse::<()>::error_empty(
+                        ErrorCode::NotFound,
+                        format!("配置项不存在: {}", item.key),
+                    )),
+                );
+            }
+            Err(e) => {
+                return Ok(HttpResponse::InternalServerError().json(
+                    ApiResponse::<()>::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("获取配置失败: {e}"),
+                    ),
+                ));
+            }
+        },
+    };
 
     let response = SettingResponse { setting };
 
@@ -117,12 +163,192 @@ pub async fn update_setting(
     )))
 }
 
+/// 批量更新配置
+///
+/// 所有配置项通过校验后才在同一事务中写入；值未变化的配置项跳过，重复提交不会产生新的审计记录。
+pub async fn batch_update_settings(
+    req: HttpRequest,
+    body: web::Json<BatchUpdateSettingsRequest>,
+    storage: web::Data<Arc<dyn Storage>>,
+) -> ActixResult<HttpResponse> {
+    let body = body.into_inner();
+
+    if body.settings.is_empty() {
+        return Ok(
+            HttpResponse::BadRequest().json(ApiResponse::<()>::error_empty(
+                ErrorCode::BadRequest,
+                "至少需要提供一个配置项",
+            )),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    if let Some(dup) = body.settings.iter().find(|i| !seen.insert(i.key.as_str())) {
+        return Ok(
+            HttpResponse::BadRequest().json(ApiResponse::<()>::error_empty(
+                ErrorCode::BadRequest,
+                format!("配置项重复: {}", dup.key),
+            )),
+        );
+    }
+
+    let changes = match validate_items(storage.get_ref(), body.settings).await {
+        Ok(changes) => changes,
+        Err(resp) => return Ok(resp),
+    };
+
+    let (updated, unchanged, version) =
+        match apply_changes(&req, storage.get_ref(), changes, body.expected_version).await {
+            Ok(outcome) => outcome,
+            Err(resp) => return Ok(resp),
+        };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        BatchUpdateSettingsResponse {
+            updated,
+            unchanged,
+            version,
+        },
+        "Settings updated successfully",
+    )))
+}
+
+/// 校验全部配置项，任一失败则整体拒绝
+async fn validate_items(
+    storage: &Arc<dyn Storage>,
+    items: Vec<UpdateSettingItem>,
+) -> Result<Vec<SettingChange>, HttpResponse> {
+    let mut changes = Vec::with_capacity(items.len());
+    let mut errors = Vec::new();
+
+    for item in items {
+        let value_type = match item.key.parse::<KnownSettingKey>() {
+            Ok(known) => match known.validate(&item.value) {
+                Ok(()) => known.value_type(),
+                Err(msg) => {
+                    errors.push(msg);
+                    continue;
+                }
+            },
+            Err(_) if item.custom => match validate_custom_setting(&item.key, &item.value) {
+                Ok(()) => SettingValueType::String,
+                Err(msg) => {
+                    errors.push(msg);
+                    continue;
+                }
+            },
+            Err(_) => {
+                return Err(
+                    HttpResponse::BadRequest().json(ApiResponse::<()>::error_empty(
+                        ErrorCode::SettingKeyUnknown,
+                        format!(
+                            "未知的配置项: {}（自定义配置项需指定 custom: true）",
+                            item.key
+                        ),
+                    )),
+                );
+            }
+        };
+
+        if let Err(msg) = validate_branding_setting(storage, &item.key, &item.value).await {
+            errors.push(msg);
+            continue;
+        }
+
+        changes.push(SettingChange {
+            key: item.key,
+            value: item.value,
+            value_type,
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(
+            HttpResponse::BadRequest().json(ApiResponse::<()>::error_empty(
+                ErrorCode::SettingValueInvalid,
+                errors.join("；"),
+            )),
+        );
+    }
+
+    Ok(changes)
+}
+
+/// 校验自定义配置项的键名与值
+fn validate_custom_setting(key: &str, value: &str) -> Result<(), String> {
+    let valid_key = !key.is_empty()
+        && key.len() <= CUSTOM_KEY_MAX_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.');
+    if !valid_key {
+        return Err(format!(
+            "自定义配置项键名只能包含小写字母、数字、下划线和点，且不超过 {CUSTOM_KEY_MAX_LEN} 个字符: {key}"
+        ));
+    }
+    if value.chars().count() > CUSTOM_VALUE_MAX_LEN {
+        return Err(format!("{key} 不能超过 {CUSTOM_VALUE_MAX_LEN} 个字符"));
+    }
+    Ok(())
+}
+
+/// 写入配置变更并刷新缓存，返回（已变更、未变化、当前版本）
+async fn apply_changes(
+    req: &HttpRequest,
+    storage: &Arc<dyn Storage>,
+    changes: Vec<SettingChange>,
+    expected_version: Option<i64>,
+) -> Result<(Vec<SystemSetting>, Vec<String>, i64), HttpResponse> {
+    let user_id = RequireJWT::extract_user_id(req).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ApiResponse::<()>::error_empty(
+            ErrorCode::Unauthorized,
+            "用户未登录",
+        ))
+    })?;
+
+    // 获取客户端 IP
+    let ip_address = ClientInfo::from_request(req).ip_string();
+
+    let outcome = storage
+        .batch_update_settings(changes, expected_version, user_id, ip_address)
+        .await
+        .map_err(|e| {
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
+                ErrorCode::InternalServerError,
+                format!("更新配置失败: {e}"),
+            ))
+        })?;
+
+    match outcome {
+        SettingsUpdateOutcome::Applied {
+            changed,
+            unchanged,
+            version,
+        } => {
+            // 更新缓存
+            for setting in &changed {
+                DynamicConfig::update(&setting.key, &setting.value).await;
+            }
+            Ok((changed, unchanged, version))
+        }
+        SettingsUpdateOutcome::VersionConflict { current_version } => Err(HttpResponse::Conflict()
+            .json(ApiResponse::<()>::error_empty(
+                ErrorCode::SettingVersionConflict,
+                format!("配置已被修改（当前版本 {current_version}），请刷新后重试"),
+            ))),
+    }
+}
+
 /// 获取审计日志
 pub async fn get_setting_audits(
     _req: HttpRequest,
     query: web::Query<SettingAuditQuery>,
     storage: web::Data<Arc<dyn Storage>>,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("setting_audits").check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let audits = match storage.list_setting_audits(query.into_inner()).await {
         Ok(a) => a,
         Err(e) => {
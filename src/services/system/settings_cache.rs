@@ -1,13 +1,23 @@
 //! 动态配置缓存
 //!
 //! 提供从数据库加载的动态配置的全局缓存访问。
-//! 使用 RwLock 保护，支持热更新。
+//! 使用 RwLock 保护，支持热更新；运行中的子系统可通过 [`DynamicConfig::subscribe`]
+//! 订阅配置变更，在管理员修改设置后即时生效。
 
 use std::collections::HashMap;
 use std::sync::OnceLock;
 use tokio::sync::RwLock;
 
 use crate::config::AppConfig;
+use crate::models::moderation::entities::ModerationMode;
+use crate::models::system::entities::{CaptchaProvider, LoginCaptchaMode, RegistrationMode};
+use crate::models::users::entities::UserRole;
+
+/// 默认主题色
+const DEFAULT_PRIMARY_COLOR: &str = "#1677ff";
+
+/// 未配置上传请求上限时在单文件上限之外预留的字节数
+const UPLOAD_REQUEST_OVERHEAD: usize = 64 * 1024;
 
 /// 动态配置缓存
 static DYNAMIC_CONFIG: OnceLock<RwLock<DynamicConfigCache>> = OnceLock::new();
@@ -19,6 +29,13 @@ struct DynamicConfigCache {
     initialized: bool,
 }
 
+/// 配置变更监听器，参数为（配置键，新值）
+type SettingListener = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+/// 已注册的监听器及其订阅的配置键前缀
+static LISTENERS: std::sync::RwLock<Vec<(&'static str, SettingListener)>> =
+    std::sync::RwLock::new(Vec::new());
+
 /// 动态配置访问接口
 pub struct DynamicConfig;
 
@@ -30,8 +47,8 @@ impl DynamicConfig {
 
         let mut guard = cache.write().await;
         guard.settings.clear();
-        for (key, value) in settings {
-            guard.settings.insert(key, value);
+        for (key, value) in &settings {
+            guard.settings.insert(key.clone(), value.clone());
         }
         guard.initialized = true;
 
@@ -39,6 +56,11 @@ impl DynamicConfig {
             "动态配置缓存初始化完成，加载了 {} 个配置项",
             guard.settings.len()
         );
+        drop(guard);
+
+        for (key, value) in &settings {
+            Self::notify(key, value);
+        }
     }
 
     /// 更新单个配置项
@@ -48,6 +70,31 @@ impl DynamicConfig {
             guard.settings.insert(key.to_string(), value.to_string());
             tracing::debug!("动态配置更新: {} = {}", key, value);
         }
+        Self::notify(key, value);
+    }
+
+    /// 订阅配置变更
+    ///
+    /// 缓存初始化时对已有配置、之后每次更新时，键以 `prefix` 开头的配置项都会同步回调
+    /// `listener(key, value)`。需在 [`DynamicConfig::init`] 之前注册才能收到初始值。
+    pub fn subscribe<F>(prefix: &'static str, listener: F)
+    where
+        F: Fn(&str, &str) + Send + Sync + 'static,
+    {
+        LISTENERS
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((prefix, Box::new(listener)));
+    }
+
+    /// 通知订阅了该配置键的监听器
+    fn notify(key: &str, value: &str) {
+        let listeners = LISTENERS.read().unwrap_or_else(|e| e.into_inner());
+        for (prefix, listener) in listeners.iter() {
+            if key.starts_with(prefix) {
+                listener(key, value);
+            }
+        }
     }
 
     /// 获取字符串配置
@@ -64,6 +111,11 @@ impl DynamicConfig {
         Self::get_string(key).await.and_then(|v| v.parse().ok())
     }
 
+    /// 获取布尔配置
+    async fn get_bool(key: &str) -> Option<bool> {
+        Self::get_string(key).await.and_then(|v| v.parse().ok())
+    }
+
     /// 获取 JSON 数组配置
     async fn get_json_array(key: &str) -> Option<Vec<String>> {
         Self::get_string(key)
@@ -111,6 +163,19 @@ impl DynamicConfig {
             .unwrap_or_else(|| AppConfig::get().upload.max_size)
     }
 
+    /// 获取单次上传请求的大小限制（字节）
+    ///
+    /// 未配置时为单文件上限加上 multipart 边界与字段头的余量。
+    pub async fn upload_max_request_size() -> usize {
+        match Self::get_i64("upload.max_request_size").await {
+            Some(v) => v as usize,
+            None => match AppConfig::get().upload.max_request_size {
+                0 => Self::upload_max_size().await + UPLOAD_REQUEST_OVERHEAD,
+                v => v,
+            },
+        }
+    }
+
     /// 获取允许上传的文件类型
     pub async fn upload_allowed_types() -> Vec<String> {
         Self::get_json_array("upload.allowed_types")
@@ -133,6 +198,215 @@ impl DynamicConfig {
             .unwrap_or_else(|| AppConfig::get().cors.max_age)
     }
 
+    /// 获取品牌 Logo 文件下载令牌（为空表示使用默认 Logo）
+    pub async fn branding_logo_token() -> Option<String> {
+        Self::get_string("branding.logo_token")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 获取品牌主题色
+    pub async fn branding_primary_color() -> String {
+        Self::get_string("branding.primary_color")
+            .await
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_PRIMARY_COLOR.to_string())
+    }
+
+    /// 获取登录页提示信息
+    pub async fn branding_login_message() -> Option<String> {
+        Self::get_string("branding.login_message")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 获取技术支持联系方式
+    pub async fn branding_support_contact() -> Option<String> {
+        Self::get_string("branding.support_contact")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 获取注册模式（默认开放注册）
+    pub async fn registration_mode() -> RegistrationMode {
+        Self::get_string("registration.mode")
+            .await
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(RegistrationMode::Open)
+    }
+
+    /// 获取允许注册的邮箱域名（小写、不含 `@`，为空表示不限制）
+    pub async fn registration_email_domains() -> Vec<String> {
+        Self::get_json_array("registration.email_domains")
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|d| d.trim().trim_start_matches('@').to_lowercase())
+            .filter(|d| !d.is_empty())
+            .collect()
+    }
+
+    /// 获取自助注册用户的角色（不会是管理员）
+    pub async fn registration_default_role() -> UserRole {
+        match Self::get_string("registration.default_role")
+            .await
+            .as_deref()
+        {
+            Some(UserRole::TEACHER) => UserRole::Teacher,
+            _ => UserRole::User,
+        }
+    }
+
+    /// 注册时是否需要人机验证（未配置验证服务商时不生效）
+    pub async fn registration_captcha_required() -> bool {
+        Self::get_bool("registration.captcha_required")
+            .await
+            .unwrap_or(false)
+            && Self::captcha_provider().await != CaptchaProvider::None
+    }
+
+    /// 获取人机验证服务商（默认不启用）
+    pub async fn captcha_provider() -> CaptchaProvider {
+        Self::get_string("captcha.provider")
+            .await
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(CaptchaProvider::None)
+    }
+
+    /// 获取人机验证站点密钥（前端组件使用，可公开）
+    pub async fn captcha_site_key() -> Option<String> {
+        Self::get_string("captcha.site_key")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 获取人机验证服务端密钥
+    pub async fn captcha_secret_key() -> Option<String> {
+        Self::get_string("captcha.secret_key")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 获取登录人机验证策略（默认失败后才需要验证）
+    pub async fn captcha_login_mode() -> LoginCaptchaMode {
+        Self::get_string("captcha.login_mode")
+            .await
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(LoginCaptchaMode::AfterFailures)
+    }
+
+    /// 获取触发登录人机验证的连续失败次数
+    pub async fn captcha_login_failure_threshold() -> i64 {
+        Self::get_i64("captcha.login_failure_threshold")
+            .await
+            .unwrap_or(3)
+    }
+
+    /// 获取通知模板使用的语言
+    pub async fn notification_locale() -> String {
+        Self::get_string("notification.locale")
+            .await
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "zh-CN".to_string())
+    }
+
+    /// 获取班级短码轮换间隔（分钟）
+    pub async fn class_short_code_rotation() -> i64 {
+        Self::get_i64("class.short_code_rotation")
+            .await
+            .filter(|v| *v > 0)
+            .unwrap_or(10)
+    }
+
+    /// 获取批改时限（天），超过时限未批改的提交视为批改超时
+    pub async fn grading_sla_days() -> i64 {
+        Self::get_i64("grading.sla_days")
+            .await
+            .filter(|v| *v > 0)
+            .unwrap_or(7)
+    }
+
+    /// 获取作业截止时间冲突检查窗口（小时，前后各算）
+    pub async fn homework_deadline_conflict_window_hours() -> i64 {
+        Self::get_i64("homework.deadline_conflict_window_hours")
+            .await
+            .filter(|v| *v > 0)
+            .unwrap_or(48)
+    }
+
+    /// 获取作业截止后的默认迟交宽限期（分钟，作业可单独覆盖）
+    pub async fn homework_default_grace_minutes() -> i64 {
+        Self::get_i64("homework.default_grace_minutes")
+            .await
+            .filter(|v| *v >= 0)
+            .unwrap_or(0)
+    }
+
+    /// 获取提交附件在班级归档后的保留天数（0 表示永久保留）
+    pub async fn retention_submission_files_days() -> i64 {
+        Self::get_i64("retention.submission_files_days")
+            .await
+            .filter(|v| *v >= 0)
+            .unwrap_or(730)
+    }
+
+    /// 获取作业附件在班级归档后的保留天数（0 表示永久保留）
+    pub async fn retention_homework_files_days() -> i64 {
+        Self::get_i64("retention.homework_files_days")
+            .await
+            .filter(|v| *v >= 0)
+            .unwrap_or(730)
+    }
+
+    /// 获取孤立上传的保留天数
+    pub async fn retention_orphan_files_days() -> i64 {
+        Self::get_i64("retention.orphan_files_days")
+            .await
+            .filter(|v| *v > 0)
+            .unwrap_or(7)
+    }
+
+    /// 获取附件在班级归档后迁移到冷存储的天数（0 表示归档后即迁移）
+    pub async fn tiering_cold_after_days() -> i64 {
+        Self::get_i64("tiering.cold_after_days")
+            .await
+            .filter(|v| *v >= 0)
+            .unwrap_or(180)
+    }
+
+    /// 获取内容审核处理方式（默认不审核）
+    pub async fn moderation_mode() -> ModerationMode {
+        Self::get_string("moderation.mode")
+            .await
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(ModerationMode::Off)
+    }
+
+    /// 获取违禁词列表（忽略空白项）
+    pub async fn moderation_banned_words() -> Vec<String> {
+        Self::get_json_array("moderation.banned_words")
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty())
+            .collect()
+    }
+
+    /// 获取外部审核接口地址
+    pub async fn moderation_api_url() -> Option<String> {
+        Self::get_string("moderation.api_url")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 获取外部审核接口密钥
+    pub async fn moderation_api_key() -> Option<String> {
+        Self::get_string("moderation.api_key")
+            .await
+            .filter(|v| !v.is_empty())
+    }
+
     /// 检查缓存是否已初始化
     pub async fn is_initialized() -> bool {
         if let Some(cache) = DYNAMIC_CONFIG.get() {
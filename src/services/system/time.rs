@@ -0,0 +1,23 @@
+//! 服务器时间同步
+//!
+//! 客户端据此校准倒计时，并在考试模式提交时回传签名时间戳，用于发现篡改本机时钟的情况。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult, http::header};
+
+use crate::models::{ApiResponse, system::responses::ServerTimeResponse};
+use crate::utils::signed_time::sign_timestamp;
+
+/// 获取服务器时间（公开，不查询数据库）
+pub async fn get_server_time(_req: HttpRequest) -> ActixResult<HttpResponse> {
+    let server_time = chrono::Utc::now().timestamp_millis();
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(ApiResponse::success(
+            ServerTimeResponse {
+                server_time,
+                signed_timestamp: sign_timestamp(server_time),
+            },
+            "查询成功",
+        )))
+}
@@ -0,0 +1,42 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{UsageService, resolve_org_id};
+use crate::models::{
+    ApiResponse, ErrorCode,
+    usage::{
+        entities::{DEFAULT_TENANT_ORG_ID, usage_period},
+        requests::CurrentUsageParams,
+        responses::CurrentUsageResponse,
+    },
+};
+
+pub async fn get_current_usage(
+    service: &UsageService,
+    query: CurrentUsageParams,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let org_id = match resolve_org_id(request, query.org_id) {
+        Ok(org_id) => org_id.unwrap_or(DEFAULT_TENANT_ORG_ID),
+        Err(resp) => return Ok(resp),
+    };
+
+    let storage = service.get_storage(request);
+    let period = usage_period(chrono::Utc::now());
+
+    match storage.list_usage_counters(org_id, &period).await {
+        Ok(counters) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            CurrentUsageResponse {
+                org_id,
+                period,
+                counters,
+            },
+            "查询成功",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询当前用量失败: {e}"),
+            )),
+        ),
+    }
+}
@@ -0,0 +1,105 @@
+pub mod current;
+pub mod report_job;
+pub mod reports;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::middlewares::RequireJWT;
+use crate::models::usage::entities::{UsageMetric, usage_org_id, usage_period};
+use crate::models::usage::requests::{CurrentUsageParams, UsageReportListParams};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::storage::Storage;
+
+pub use report_job::spawn_usage_report_job;
+
+pub struct UsageService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl UsageService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    // 查询用量报表历史
+    pub async fn list_reports(
+        &self,
+        query: UsageReportListParams,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        reports::list_reports(self, query, request).await
+    }
+
+    // 手动生成当天用量报表
+    pub async fn generate_reports(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        reports::generate_reports(self, request).await
+    }
+
+    // 查询当月实时用量
+    pub async fn get_current_usage(
+        &self,
+        query: CurrentUsageParams,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        current::get_current_usage(self, query, request).await
+    }
+}
+
+/// 解析本次查询可访问的组织
+///
+/// 平台管理员可指定任意组织（`None` 表示全部）；组织管理员只能查询本组织。
+fn resolve_org_id(
+    request: &HttpRequest,
+    requested: Option<i64>,
+) -> Result<Option<i64>, HttpResponse> {
+    match RequireJWT::extract_user_claims(request) {
+        Some(user) if user.is_platform_admin() => Ok(requested),
+        Some(user) => {
+            let own = usage_org_id(user.org_id);
+            match requested {
+                Some(org_id) if org_id != own => Err(HttpResponse::Forbidden().json(
+                    ApiResponse::error_empty(ErrorCode::PermissionDenied, "只能查询本组织的用量"),
+                )),
+                _ => Ok(Some(own)),
+            }
+        }
+        None => Err(HttpResponse::Unauthorized()
+            .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录"))),
+    }
+}
+
+/// 记录一次用量（异步执行，失败只记录日志，不影响业务请求）
+pub fn record_usage(
+    storage: Arc<dyn Storage>,
+    org_id: Option<i64>,
+    metric: UsageMetric,
+    delta: i64,
+) {
+    if delta <= 0 {
+        return;
+    }
+    let org_id = usage_org_id(org_id);
+    let period = usage_period(chrono::Utc::now());
+
+    tokio::spawn(async move {
+        if let Err(e) = storage
+            .increment_usage_counter(org_id, &period, metric, delta)
+            .await
+        {
+            tracing::warn!("Failed to record usage {metric} for org {org_id}: {e}");
+        }
+    });
+}
@@ -0,0 +1,36 @@
+//! 用量报表定时任务
+//!
+//! 每天 UTC 零点汇总前一天的用量，写入 `usage_reports` 表。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::storage::Storage;
+
+/// 启动每日用量报表任务
+pub fn spawn_usage_report_job(storage: Arc<dyn Storage>) {
+    tokio::spawn(async move {
+        loop {
+            let now = chrono::Utc::now();
+            let next_midnight = (now.date_naive() + chrono::Days::new(1))
+                .and_hms_opt(0, 0, 0)
+                .map(|dt| dt.and_utc())
+                .unwrap_or(now + chrono::Duration::days(1));
+            let wait = (next_midnight - now)
+                .to_std()
+                .unwrap_or(Duration::from_secs(60));
+            tokio::time::sleep(wait).await;
+
+            // 零点之后汇总刚结束的一天
+            let report_date = chrono::Utc::now().date_naive() - chrono::Days::new(1);
+            match storage.generate_usage_reports(report_date).await {
+                Ok(count) => {
+                    tracing::info!("Generated {count} usage report(s) for {report_date}");
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to generate usage reports for {report_date}: {e}");
+                }
+            }
+        }
+    });
+}
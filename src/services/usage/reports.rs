@@ -0,0 +1,96 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::{UsageService, resolve_org_id};
+use crate::middlewares::RequireJWT;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::{
+    ApiResponse, ErrorCode,
+    usage::{
+        requests::{UsageReportListParams, UsageReportListQuery},
+        responses::GenerateUsageReportsResponse,
+    },
+};
+
+pub async fn list_reports(
+    service: &UsageService,
+    query: UsageReportListParams,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let org_id = match resolve_org_id(request, query.org_id) {
+        Ok(org_id) => org_id,
+        Err(resp) => return Ok(resp),
+    };
+
+    for date in [&query.from, &query.to].into_iter().flatten() {
+        if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                ErrorCode::BadRequest,
+                format!("日期格式无效: {date}，应为 YYYY-MM-DD"),
+            )));
+        }
+    }
+
+    if let Err(limit) = PaginationPolicy::for_endpoint("usage_reports").check(query.pagination.size)
+    {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let storage = service.get_storage(request);
+
+    let list_query = UsageReportListQuery {
+        page: Some(query.pagination.page),
+        size: query.pagination.size,
+        org_id,
+        from: query.from,
+        to: query.to,
+    };
+
+    match storage.list_usage_reports(list_query).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功"))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询用量报表失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn generate_reports(
+    service: &UsageService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    // 报表覆盖所有组织，仅平台管理员可手动触发
+    match RequireJWT::extract_user_claims(request) {
+        Some(user) if user.is_platform_admin() => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::PermissionDenied,
+                "仅平台管理员可执行此操作",
+            )));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录")));
+        }
+    }
+
+    let storage = service.get_storage(request);
+    let today = chrono::Utc::now().date_naive();
+
+    match storage.generate_usage_reports(today).await {
+        Ok(generated) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            GenerateUsageReportsResponse {
+                report_date: today.format("%Y-%m-%d").to_string(),
+                generated,
+            },
+            "用量报表已生成",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("生成用量报表失败: {e}"),
+            )),
+        ),
+    }
+}
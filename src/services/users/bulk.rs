@@ -0,0 +1,199 @@
+//! 批量用户操作服务
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::collections::HashSet;
+use tracing::error;
+
+use super::UserService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::users::entities::{BulkUserAction, User, UserRole};
+use crate::models::users::requests::BulkUserRequest;
+use crate::models::users::responses::{BulkUserItemResult, BulkUserResponse};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::disconnect_user;
+use crate::utils::ClientInfo;
+
+/// 单次批量操作最多涉及的用户数
+const MAX_BULK_USERS: usize = 1000;
+
+fn bad_request(msg: impl Into<String>) -> ActixResult<HttpResponse> {
+    Ok(
+        HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, msg.into())),
+    )
+}
+
+fn rejected(user_id: i64, msg: &str) -> BulkUserItemResult {
+    BulkUserItemResult {
+        user_id,
+        success: false,
+        message: Some(msg.to_string()),
+    }
+}
+
+pub async fn bulk_users(
+    service: &UserService,
+    req: BulkUserRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let current_user = match RequireJWT::extract_user_claims(request) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录")));
+        }
+    };
+
+    match (req.action, &req.role) {
+        (BulkUserAction::AssignRole, None) => {
+            return bad_request("assign_role 操作必须指定目标角色");
+        }
+        // 禁止批量提升为管理员（防止权限提升）
+        (BulkUserAction::AssignRole, Some(UserRole::Admin))
+            if current_user.role != UserRole::Admin =>
+        {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::PermissionDenied,
+                "无权将用户提升为管理员",
+            )));
+        }
+        _ => {}
+    }
+
+    let mut results = Vec::new();
+
+    // 解析目标用户：user_ids 与 filter 二选一
+    let candidates: Vec<User> = match (req.user_ids, req.filter) {
+        (Some(ids), None) => {
+            let mut seen = HashSet::new();
+            let ids: Vec<i64> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+            if ids.is_empty() {
+                return bad_request("user_ids 不能为空");
+            }
+            if ids.len() > MAX_BULK_USERS {
+                return bad_request(format!("单次最多操作 {MAX_BULK_USERS} 个用户"));
+            }
+
+            let users = match storage.list_users_by_ids(&ids).await {
+                Ok(users) => users,
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询用户失败: {e}"),
+                        )),
+                    );
+                }
+            };
+
+            // 不存在或跨组织的用户按不存在处理
+            let accessible: Vec<User> = users
+                .into_iter()
+                .filter(|u| TenantGuard::can_access(request, u.org_id))
+                .collect();
+            let found: HashSet<i64> = accessible.iter().map(|u| u.id).collect();
+            results.extend(
+                ids.iter()
+                    .filter(|id| !found.contains(id))
+                    .map(|id| rejected(*id, "用户不存在")),
+            );
+            accessible
+        }
+        (None, Some(filter)) => {
+            match storage
+                .list_users_for_bulk(
+                    filter,
+                    TenantGuard::scope(request),
+                    MAX_BULK_USERS as u64 + 1,
+                )
+                .await
+            {
+                Ok(users) if users.len() > MAX_BULK_USERS => {
+                    return bad_request(format!(
+                        "筛选结果超过 {MAX_BULK_USERS} 个用户，请缩小筛选范围"
+                    ));
+                }
+                Ok(users) => users,
+                Err(e) => {
+                    return Ok(
+                        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                            ErrorCode::InternalServerError,
+                            format!("查询用户失败: {e}"),
+                        )),
+                    );
+                }
+            }
+        }
+        _ => return bad_request("必须且只能指定 user_ids 或 filter 之一"),
+    };
+
+    // 逐个校验权限，与单个用户的修改/删除规则保持一致
+    let mut targets = Vec::with_capacity(candidates.len());
+    for user in &candidates {
+        if user.id == current_user.id && req.action != BulkUserAction::Enable {
+            results.push(rejected(user.id, "不能对当前登录用户执行该操作"));
+        } else if user.role == UserRole::Admin && req.action == BulkUserAction::Delete {
+            results.push(rejected(user.id, "无法删除管理员用户"));
+        } else if user.role == UserRole::Admin && user.id != current_user.id {
+            results.push(rejected(user.id, "无法修改其他管理员用户"));
+        } else {
+            targets.push(user.id);
+        }
+    }
+
+    let ip_address = ClientInfo::from_request(request).ip_string();
+
+    let applied = match storage
+        .bulk_apply_user_action(req.action, req.role, &targets, current_user.id, ip_address)
+        .await
+    {
+        Ok(applied) => applied,
+        Err(e) => {
+            error!("批量用户操作失败: {}", e);
+            let code = if req.action == BulkUserAction::Delete {
+                ErrorCode::UserDeleteFailed
+            } else {
+                ErrorCode::UserUpdateFailed
+            };
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    code,
+                    format!("批量操作失败，已全部回滚: {e}"),
+                )),
+            );
+        }
+    };
+
+    for item in applied.iter().filter(|r| r.success) {
+        RequireJWT::invalidate_user(request, item.user_id).await;
+    }
+
+    // 停用或删除的账号断开实时连接
+    let reason = match req.action {
+        BulkUserAction::Disable => Some("账号已停用"),
+        BulkUserAction::Delete => Some("账号已删除"),
+        _ => None,
+    };
+    if let Some(reason) = reason {
+        for item in applied.iter().filter(|r| r.success) {
+            disconnect_user(item.user_id, reason);
+        }
+    }
+
+    results.extend(applied);
+    results.sort_by_key(|r| r.user_id);
+
+    let success = results.iter().filter(|r| r.success).count();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        BulkUserResponse {
+            action: req.action,
+            total: results.len(),
+            success,
+            failed: results.len() - success,
+            results,
+        },
+        "批量操作完成",
+    )))
+}
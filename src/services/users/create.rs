@@ -2,9 +2,10 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use tracing::error;
 
 use super::UserService;
+use crate::middlewares::RequireJWT;
 use crate::models::{
     ApiResponse, ErrorCode,
-    users::{requests::CreateUserRequest, responses::UserResponse},
+    users::{entities::UserRole, requests::CreateUserRequest, responses::UserResponse},
 };
 use crate::utils::password::hash_password;
 use crate::utils::validate::{validate_email, validate_password_simple, validate_username};
@@ -14,6 +15,46 @@ pub async fn create_user(
     mut user_data: CreateUserRequest,
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
+    // 只有系统管理员可以创建管理员（被委派用户管理权限的用户不可提权）
+    if user_data.role == UserRole::Admin
+        && RequireJWT::extract_user_role(request) != Some(UserRole::Admin)
+    {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::PermissionDenied,
+            "Only admins can create admin users",
+        )));
+    }
+
+    // 租户归属：平台管理员可指定组织，其他操作者创建的用户归属其所在组织
+    let current_user = RequireJWT::extract_user_claims(request);
+    match current_user {
+        Some(ref user) if user.is_platform_admin() => {}
+        Some(ref user) => user_data.org_id = user.org_id,
+        None => user_data.org_id = None,
+    }
+
+    let storage = service.get_storage(request);
+
+    if let Some(org_id) = user_data.org_id {
+        match storage.get_organization_by_id(org_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                    ErrorCode::OrganizationNotFound,
+                    "Organization not found",
+                )));
+            }
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("Failed to get organization: {e}"),
+                    )),
+                );
+            }
+        }
+    }
+
     // 验证用户名
     if let Err(msg) = validate_username(&user_data.username) {
         return Ok(HttpResponse::BadRequest()
@@ -46,8 +87,6 @@ pub async fn create_user(
         }
     };
 
-    let storage = service.get_storage(request);
-
     match storage.create_user(user_data).await {
         Ok(user) => Ok(HttpResponse::Created()
             .json(ApiResponse::success(UserResponse { user }, "用户创建成功"))),
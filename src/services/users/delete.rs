@@ -2,8 +2,9 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::UserService;
 use crate::{
-    middlewares::RequireJWT,
+    middlewares::{RequireJWT, TenantGuard},
     models::{ApiResponse, ErrorCode, users::entities::UserRole},
+    services::disconnect_user,
 };
 
 pub async fn delete_user(
@@ -32,8 +33,9 @@ pub async fn delete_user(
 
     // 获取目标用户信息
     let target_user = match storage.get_user_by_id(user_id).await {
-        Ok(Some(user)) => user,
-        Ok(None) => {
+        // 跨组织的用户按不存在处理
+        Ok(Some(user)) if TenantGuard::can_access(request, user.org_id) => user,
+        Ok(_) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::UserNotFound,
                 "用户不存在",
@@ -58,7 +60,11 @@ pub async fn delete_user(
     }
 
     match storage.delete_user(user_id).await {
-        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("用户删除成功"))),
+        Ok(true) => {
+            RequireJWT::invalidate_user(request, user_id).await;
+            disconnect_user(user_id, "账号已删除");
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("用户删除成功")))
+        }
         Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::UserNotFound,
             "用户不存在",
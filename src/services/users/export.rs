@@ -1,12 +1,47 @@
 //! 用户导出服务
 
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
-use rust_xlsxwriter::{Format, Workbook};
 use tracing::error;
 
 use super::UserService;
+use crate::middlewares::TenantGuard;
 use crate::models::users::requests::UserExportParams;
 use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::export::{ExportFormat, ExportTable};
+
+/// 单次最多导出的用户数
+const MAX_EXPORT_USERS: u64 = 10000;
+
+/// CSV 表头（与导入模板字段名风格一致）
+const CSV_HEADERS: [&str; 10] = [
+    "id",
+    "username",
+    "email",
+    "role",
+    "status",
+    "display_name",
+    "org_id",
+    "class_count",
+    "last_login",
+    "created_at",
+];
+
+/// XLSX 表头
+const XLSX_HEADERS: [&str; 10] = [
+    "ID",
+    "用户名",
+    "邮箱",
+    "角色",
+    "状态",
+    "显示名称",
+    "组织ID",
+    "加入班级数",
+    "最后登录",
+    "创建时间",
+];
+
+/// 导入模板表头
+const TEMPLATE_HEADERS: [&str; 5] = ["username", "email", "password", "role", "display_name"];
 
 /// 导出用户列表
 pub async fn export_users(
@@ -15,10 +50,16 @@ pub async fn export_users(
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
+    let format = ExportFormat::parse(&params.format);
 
-    // 最多导出 10000 条
     let users = match storage
-        .list_users_for_export_filtered(10000, params.role, params.status, params.search.as_deref())
+        .list_users_for_export_filtered(
+            MAX_EXPORT_USERS,
+            params.role,
+            params.status,
+            params.search.as_deref(),
+            TenantGuard::scope(request),
+        )
         .await
     {
         Ok(users) => users,
@@ -33,247 +74,54 @@ pub async fn export_users(
         }
     };
 
-    match params.format.as_str() {
-        "xlsx" => export_xlsx(&users),
-        _ => export_csv(&users),
-    }
-}
-
-/// 下载导入模板
-pub async fn download_template(format: &str) -> ActixResult<HttpResponse> {
-    match format {
-        "xlsx" => generate_template_xlsx(),
-        _ => generate_template_csv(),
-    }
-}
-
-fn export_csv(users: &[crate::models::users::entities::User]) -> ActixResult<HttpResponse> {
-    let mut wtr = csv::Writer::from_writer(vec![]);
-
-    // 写入表头
-    if let Err(e) = wtr.write_record([
-        "id",
-        "username",
-        "email",
-        "role",
-        "status",
-        "display_name",
-        "created_at",
-    ]) {
-        error!("CSV 写入失败: {}", e);
-        return Ok(
-            HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                ErrorCode::ExportFailed,
-                format!("CSV 写入失败: {e}"),
-            )),
-        );
-    }
-
-    // 写入数据
-    for user in users {
-        if let Err(e) = wtr.write_record([
-            user.id.to_string(),
-            user.username.clone(),
-            user.email.clone(),
-            user.role.to_string(),
-            user.status.to_string(),
-            user.display_name.clone().unwrap_or_default(),
-            user.created_at.to_rfc3339(),
-        ]) {
-            error!("CSV 写入失败: {}", e);
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("CSV 写入失败: {e}"),
-                )),
-            );
-        }
-    }
-
-    let data = match wtr.into_inner() {
-        Ok(d) => d,
+    let user_ids: Vec<i64> = users.iter().map(|u| u.id).collect();
+    let class_counts = match storage.count_classes_by_user_ids(&user_ids).await {
+        Ok(counts) => counts,
         Err(e) => {
-            error!("CSV 生成失败: {}", e);
+            error!("统计用户班级数量失败: {}", e);
             return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("CSV 生成失败: {e}"),
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("导出用户失败: {e}"),
                 )),
             );
         }
     };
 
-    Ok(HttpResponse::Ok()
-        .content_type("text/csv; charset=utf-8")
-        .insert_header(("Content-Disposition", "attachment; filename=\"users.csv\""))
-        .body(data))
-}
-
-fn export_xlsx(users: &[crate::models::users::entities::User]) -> ActixResult<HttpResponse> {
-    let mut workbook = Workbook::new();
-    let worksheet = workbook.add_worksheet();
-
-    // 表头格式
-    let header_format = Format::new().set_bold();
-
-    // 写入表头
-    let headers = [
-        "ID",
-        "用户名",
-        "邮箱",
-        "角色",
-        "状态",
-        "显示名称",
-        "创建时间",
-    ];
-    for (col, header) in headers.iter().enumerate() {
-        if let Err(e) = worksheet.write_string_with_format(0, col as u16, *header, &header_format) {
-            error!("XLSX 写入失败: {}", e);
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("XLSX 写入失败: {e}"),
-                )),
-            );
-        }
-    }
-
-    // 写入数据
-    for (row, user) in users.iter().enumerate() {
-        let row = (row + 1) as u32;
-        worksheet.write_number(row, 0, user.id as f64).ok();
-        worksheet.write_string(row, 1, &user.username).ok();
-        worksheet.write_string(row, 2, &user.email).ok();
-        worksheet.write_string(row, 3, user.role.to_string()).ok();
-        worksheet.write_string(row, 4, user.status.to_string()).ok();
-        worksheet
-            .write_string(row, 5, user.display_name.as_deref().unwrap_or(""))
-            .ok();
-        worksheet
-            .write_string(row, 6, user.created_at.to_rfc3339())
-            .ok();
-    }
-
-    // 生成二进制数据
-    let buffer = match workbook.save_to_buffer() {
-        Ok(b) => b,
-        Err(e) => {
-            error!("XLSX 生成失败: {}", e);
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("XLSX 生成失败: {e}"),
-                )),
-            );
-        }
+    let headers = match format {
+        ExportFormat::Csv => &CSV_HEADERS,
+        ExportFormat::Xlsx => &XLSX_HEADERS,
     };
-
-    Ok(HttpResponse::Ok()
-        .content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
-        .insert_header(("Content-Disposition", "attachment; filename=\"users.xlsx\""))
-        .body(buffer))
-}
-
-fn generate_template_csv() -> ActixResult<HttpResponse> {
-    let mut wtr = csv::Writer::from_writer(vec![]);
-
-    // 写入表头
-    if let Err(e) = wtr.write_record(["username", "email", "password", "role", "display_name"]) {
-        error!("CSV 写入失败: {}", e);
-        return Ok(
-            HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                ErrorCode::ExportFailed,
-                format!("CSV 写入失败: {e}"),
-            )),
-        );
-    }
-
-    // 写入示例行
-    if let Err(e) = wtr.write_record([
-        "example_user",
-        "user@example.com",
-        "password123",
-        "user",
-        "示例用户",
-    ]) {
-        error!("CSV 写入失败: {}", e);
-        return Ok(
-            HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                ErrorCode::ExportFailed,
-                format!("CSV 写入失败: {e}"),
-            )),
-        );
+    let mut table = ExportTable::new("用户", headers);
+    for user in users {
+        table.push_row(vec![
+            user.id.into(),
+            user.username.into(),
+            user.email.into(),
+            user.role.to_string().into(),
+            user.status.to_string().into(),
+            user.display_name.into(),
+            user.org_id.into(),
+            class_counts.get(&user.id).copied().unwrap_or(0).into(),
+            user.last_login.map(|t| t.to_rfc3339()).into(),
+            user.created_at.to_rfc3339().into(),
+        ]);
     }
 
-    let data = match wtr.into_inner() {
-        Ok(d) => d,
-        Err(e) => {
-            error!("CSV 生成失败: {}", e);
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("CSV 生成失败: {e}"),
-                )),
-            );
-        }
-    };
-
-    Ok(HttpResponse::Ok()
-        .content_type("text/csv; charset=utf-8")
-        .insert_header((
-            "Content-Disposition",
-            "attachment; filename=\"user_import_template.csv\"",
-        ))
-        .body(data))
+    Ok(table.into_response(format, "users"))
 }
 
-fn generate_template_xlsx() -> ActixResult<HttpResponse> {
-    let mut workbook = Workbook::new();
-    let worksheet = workbook.add_worksheet();
-
-    // 表头格式
-    let header_format = Format::new().set_bold();
-
-    // 写入表头
-    let headers = ["username", "email", "password", "role", "display_name"];
-    for (col, header) in headers.iter().enumerate() {
-        if let Err(e) = worksheet.write_string_with_format(0, col as u16, *header, &header_format) {
-            error!("XLSX 写入失败: {}", e);
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("XLSX 写入失败: {e}"),
-                )),
-            );
-        }
-    }
-
-    // 写入示例行
-    worksheet.write_string(1, 0, "example_user").ok();
-    worksheet.write_string(1, 1, "user@example.com").ok();
-    worksheet.write_string(1, 2, "password123").ok();
-    worksheet.write_string(1, 3, "user").ok();
-    worksheet.write_string(1, 4, "示例用户").ok();
-
-    // 生成二进制数据
-    let buffer = match workbook.save_to_buffer() {
-        Ok(b) => b,
-        Err(e) => {
-            error!("XLSX 生成失败: {}", e);
-            return Ok(
-                HttpResponse::InternalServerError().json(ApiResponse::<()>::error_empty(
-                    ErrorCode::ExportFailed,
-                    format!("XLSX 生成失败: {e}"),
-                )),
-            );
-        }
-    };
-
-    Ok(HttpResponse::Ok()
-        .content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
-        .insert_header((
-            "Content-Disposition",
-            "attachment; filename=\"user_import_template.xlsx\"",
-        ))
-        .body(buffer))
+/// 下载导入模板
+pub async fn download_template(format: &str) -> ActixResult<HttpResponse> {
+    let mut table = ExportTable::new("导入模板", &TEMPLATE_HEADERS);
+    // 示例行
+    table.push_row(vec![
+        "example_user".into(),
+        "user@example.com".into(),
+        "password123".into(),
+        "user".into(),
+        "示例用户".into(),
+    ]);
+
+    Ok(table.into_response(ExportFormat::parse(format), "user_import_template"))
 }
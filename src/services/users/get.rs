@@ -1,6 +1,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::UserService;
+use crate::middlewares::TenantGuard;
 use crate::models::users::responses::UserResponse;
 use crate::models::{ApiResponse, ErrorCode};
 
@@ -12,11 +13,13 @@ pub async fn get_user(
     let storage = service.get_storage(request);
 
     match storage.get_user_by_id(user_id).await {
-        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
-            UserResponse { user },
-            "User information retrieved successfully",
-        ))),
-        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+        // 跨组织的用户按不存在处理
+        Ok(Some(user)) if TenantGuard::can_access(request, user.org_id) => Ok(HttpResponse::Ok()
+            .json(ApiResponse::success(
+                UserResponse { user },
+                "User information retrieved successfully",
+            ))),
+        Ok(_) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::UserNotFound,
             "User not found",
         ))),
@@ -0,0 +1,137 @@
+//! 学习目标服务
+//!
+//! 学生为所在班级设定目标平均得分率，进度按已评分作业与剩余作业的满分加权预测。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use super::UserService;
+use super::stats::project_goal;
+use crate::cache::ObjectCache;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::users::requests::SetStudentGoalRequest;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::auth::dashboard;
+
+fn unauthorized() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+        ErrorCode::Unauthorized,
+        "无法获取用户信息",
+    )))
+}
+
+fn internal_error(msg: String) -> ActixResult<HttpResponse> {
+    Ok(
+        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+            ErrorCode::InternalServerError,
+            msg,
+        )),
+    )
+}
+
+/// 目标变更后清除仪表盘缓存，使首页立即反映新目标
+async fn invalidate_dashboard(request: &HttpRequest, user_id: i64) {
+    if let Some(cache) = request.app_data::<actix_web::web::Data<Arc<dyn ObjectCache>>>() {
+        cache.remove(&dashboard::cache_key(user_id)).await;
+    }
+}
+
+// 列出我的学习目标及进度
+pub async fn list_my_goals(
+    service: &UserService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return unauthorized();
+    };
+
+    match storage.list_goal_standings(user_id).await {
+        Ok(standings) => {
+            let goals: Vec<_> = standings.into_iter().map(project_goal).collect();
+            Ok(HttpResponse::Ok().json(ApiResponse::success(goals, "查询成功")))
+        }
+        Err(e) => internal_error(format!("查询学习目标失败: {e}")),
+    }
+}
+
+// 设置班级学习目标（仅班级学生）
+pub async fn set_my_goal(
+    service: &UserService,
+    class_id: i64,
+    req: SetStudentGoalRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return unauthorized();
+    };
+
+    if !(req.target_percent > 0.0 && req.target_percent <= 100.0) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "目标得分率必须大于 0 且不超过 100",
+        )));
+    }
+
+    match storage.get_class_by_id(class_id).await {
+        Ok(Some(class)) if TenantGuard::can_access(request, class.org_id) => {}
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::ClassNotFound,
+                "班级不存在",
+            )));
+        }
+        Err(e) => return internal_error(format!("查询班级失败: {e}")),
+    }
+
+    match RequireClassRole::class_user(request, &storage, user_id, class_id).await {
+        Ok(Some(cu)) if cu.role != ClassUserRole::Teacher => {}
+        Ok(_) => {
+            return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+                ErrorCode::ClassPermissionDenied,
+                "只有班级学生可以设置学习目标",
+            )));
+        }
+        Err(e) => return internal_error(format!("查询班级成员失败: {e}")),
+    }
+
+    match storage
+        .upsert_student_goal(user_id, class_id, req.target_percent)
+        .await
+    {
+        Ok(goal) => {
+            invalidate_dashboard(request, user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success(goal, "学习目标已保存")))
+        }
+        Err(e) => internal_error(format!("保存学习目标失败: {e}")),
+    }
+}
+
+// 删除班级学习目标
+pub async fn delete_my_goal(
+    service: &UserService,
+    class_id: i64,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return unauthorized();
+    };
+
+    match storage.delete_student_goal(user_id, class_id).await {
+        Ok(true) => {
+            invalidate_dashboard(request, user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success_empty("学习目标已删除")))
+        }
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::StudentGoalNotFound,
+            "学习目标不存在",
+        ))),
+        Err(e) => internal_error(format!("删除学习目标失败: {e}")),
+    }
+}
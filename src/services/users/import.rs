@@ -6,9 +6,10 @@ use calamine::{Reader, Xlsx};
 use futures_util::StreamExt;
 use std::collections::HashSet;
 use std::io::Cursor;
-use tracing::error;
+use tracing::{error, warn};
 
 use super::UserService;
+use crate::middlewares::RequireJWT;
 use crate::models::users::entities::UserRole;
 use crate::models::users::requests::CreateUserRequest;
 use crate::models::users::responses::{ImportRowError, UserImportResponse};
@@ -59,6 +60,9 @@ pub async fn import_users(
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
     let storage = service.get_storage(request);
+    let is_admin = RequireJWT::extract_user_role(request) == Some(UserRole::Admin);
+    // 导入的用户归属操作者所在的组织
+    let org_id = RequireJWT::extract_user_claims(request).and_then(|user| user.org_id);
 
     // 读取文件内容
     let (file_bytes, file_name) = match read_file_from_multipart(&mut payload).await {
@@ -144,8 +148,26 @@ pub async fn import_users(
     let mut skipped = 0;
     let mut to_create: Vec<ImportRow> = Vec::new();
 
+    // 文件内重复的用户名/邮箱只保留首次出现的行，避免批量写入因唯一约束整体失败
+    let mut seen_usernames = HashSet::new();
+    let mut seen_emails = HashSet::new();
+
     for row in valid_rows {
-        if existing_usernames_set.contains(&row.username) {
+        if !seen_usernames.insert(row.username.clone()) {
+            skipped += 1;
+            errors.push(ImportRowError {
+                row: row.row_num,
+                field: "username".to_string(),
+                message: "文件中用户名重复".to_string(),
+            });
+        } else if !seen_emails.insert(row.email.clone()) {
+            skipped += 1;
+            errors.push(ImportRowError {
+                row: row.row_num,
+                field: "email".to_string(),
+                message: "文件中邮箱重复".to_string(),
+            });
+        } else if existing_usernames_set.contains(&row.username) {
             skipped += 1;
             errors.push(ImportRowError {
                 row: row.row_num,
@@ -164,9 +186,10 @@ pub async fn import_users(
         }
     }
 
-    // 批量创建用户
+    // 准备创建请求（哈希密码、校验角色）
     let mut success = 0;
     let mut failed = 0;
+    let mut prepared: Vec<(usize, CreateUserRequest)> = Vec::with_capacity(to_create.len());
 
     for row in to_create {
         // 哈希密码（使用 spawn_blocking 避免阻塞）
@@ -195,6 +218,16 @@ pub async fn import_users(
         };
 
         let role = match row.role.parse::<UserRole>() {
+            // 只有系统管理员可以导入管理员账号
+            Ok(UserRole::Admin) if !is_admin => {
+                failed += 1;
+                errors.push(ImportRowError {
+                    row: row.row_num,
+                    field: "role".to_string(),
+                    message: "无权导入管理员账号".to_string(),
+                });
+                continue;
+            }
             Ok(r) => r,
             Err(_) => {
                 failed += 1;
@@ -214,18 +247,31 @@ pub async fn import_users(
             role,
             display_name: row.display_name,
             avatar_url: None,
+            org_id,
         };
 
-        match storage.create_user(create_req).await {
-            Ok(_) => success += 1,
-            Err(e) => {
-                failed += 1;
-                error!("创建用户失败: {}", e);
-                errors.push(ImportRowError {
-                    row: row.row_num,
-                    field: "".to_string(),
-                    message: format!("创建失败: {e}"),
-                });
+        prepared.push((row.row_num, create_req));
+    }
+
+    // 批量写入；失败时（如导入期间被并发占用了用户名）逐行重试以定位失败行
+    let batch: Vec<CreateUserRequest> = prepared.iter().map(|(_, req)| req.clone()).collect();
+    match storage.create_users_batch(batch).await {
+        Ok(created) => success += created,
+        Err(e) => {
+            warn!("批量创建用户失败，改为逐行创建: {}", e);
+            for (row_num, create_req) in prepared {
+                match storage.create_user(create_req).await {
+                    Ok(_) => success += 1,
+                    Err(e) => {
+                        failed += 1;
+                        error!("创建用户失败: {}", e);
+                        errors.push(ImportRowError {
+                            row: row_num,
+                            field: "".to_string(),
+                            message: format!("创建失败: {e}"),
+                        });
+                    }
+                }
             }
         }
     }
@@ -1,6 +1,8 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::UserService;
+use crate::middlewares::TenantGuard;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
 use crate::models::{
     ApiResponse, ErrorCode,
     users::requests::{UserListParams, UserListQuery},
@@ -11,14 +13,19 @@ pub async fn list_users(
     query: UserListParams,
     request: &HttpRequest,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("users").check(query.pagination.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
     let storage = service.get_storage(request);
 
     let list_query = UserListQuery {
         page: Some(query.pagination.page),
-        size: Some(query.pagination.size),
+        size: query.pagination.size,
         role: query.role,
         status: query.status,
         search: query.search,
+        tenant: TenantGuard::scope(request),
     };
 
     match storage.list_users_with_pagination(list_query).await {
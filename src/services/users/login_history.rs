@@ -0,0 +1,36 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::UserService;
+use crate::middlewares::TenantGuard;
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::{ApiResponse, ErrorCode, users::requests::LoginHistoryQuery};
+
+// 管理员查看登录历史（可按用户、成功与否、可疑标记筛选，限当前租户的用户）
+pub async fn list_login_history(
+    service: &UserService,
+    query: LoginHistoryQuery,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("login_history").check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let storage = service.get_storage(request);
+    let query = LoginHistoryQuery {
+        tenant: TenantGuard::scope(request),
+        ..query
+    };
+
+    match storage.list_login_history(query).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            response,
+            "Login history retrieved successfully",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("Failed to retrieve login history: {e}"),
+            )),
+        ),
+    }
+}
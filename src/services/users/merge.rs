@@ -0,0 +1,108 @@
+//! 重复账号合并服务
+//!
+//! 同一学生误注册了多个账号时，管理员可将源账号的班级成员关系、提交、评分、文件与通知
+//! 全部转移到目标账号。合并在单个事务中完成，源账号随后被停用并保留以便追溯。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use tracing::error;
+
+use super::UserService;
+use crate::middlewares::{RequireClassRole, RequireJWT, TenantGuard};
+use crate::models::users::entities::{User, UserRole};
+use crate::models::users::requests::MergeUsersRequest;
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::disconnect_user;
+use crate::utils::ClientInfo;
+
+fn invalid(msg: &str) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(ErrorCode::UserMergeInvalid, msg)))
+}
+
+/// 查询合并涉及的账号，不存在或跨组织时返回 404
+async fn load_user(
+    service: &UserService,
+    request: &HttpRequest,
+    user_id: i64,
+) -> Result<User, HttpResponse> {
+    match service.get_storage(request).get_user_by_id(user_id).await {
+        Ok(Some(user)) if TenantGuard::can_access(request, user.org_id) => Ok(user),
+        Ok(_) => Err(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::UserNotFound,
+            format!("用户 {user_id} 不存在"),
+        ))),
+        Err(e) => Err(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("查询用户失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn merge_users(
+    service: &UserService,
+    req: MergeUsersRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let current_user = match RequireJWT::extract_user_claims(request) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .json(ApiResponse::error_empty(ErrorCode::Unauthorized, "未登录")));
+        }
+    };
+
+    if req.source_user_id == req.target_user_id {
+        return invalid("源账号与目标账号不能相同");
+    }
+    if req.source_user_id == current_user.id {
+        return invalid("不能合并当前登录用户");
+    }
+
+    let source = match load_user(service, request, req.source_user_id).await {
+        Ok(user) => user,
+        Err(resp) => return Ok(resp),
+    };
+    let target = match load_user(service, request, req.target_user_id).await {
+        Ok(user) => user,
+        Err(resp) => return Ok(resp),
+    };
+
+    if source.role == UserRole::Admin {
+        return invalid("管理员账号不能作为源账号合并");
+    }
+
+    let ip_address = ClientInfo::from_request(request).ip_string();
+
+    let result = match storage
+        .merge_users(source.id, target.id, current_user.id, ip_address)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            error!(
+                "Failed to merge user {} into {}: {}",
+                source.id, target.id, e
+            );
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::UserMergeFailed,
+                    format!("账号合并失败，已全部回滚: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 源账号已停用，目标账号的班级与权限发生变化
+    RequireJWT::invalidate_user(request, source.id).await;
+    RequireJWT::invalidate_user(request, target.id).await;
+    for &class_id in &result.class_ids {
+        RequireClassRole::invalidate(request, class_id, source.id).await;
+        RequireClassRole::invalidate(request, class_id, target.id).await;
+    }
+    disconnect_user(source.id, "账号已合并");
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(result, "账号合并完成")))
+}
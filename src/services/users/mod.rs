@@ -1,9 +1,15 @@
+pub mod bulk;
 pub mod create;
 pub mod delete;
 pub mod export;
 pub mod get;
+pub mod goals;
 pub mod import;
 pub mod list;
+pub mod login_history;
+pub mod merge;
+pub mod permissions;
+pub mod role_requests;
 pub mod stats;
 pub mod update;
 
@@ -12,7 +18,9 @@ use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 use std::sync::Arc;
 
 use crate::models::users::requests::{
-    CreateUserRequest, UpdateUserRequest, UserExportParams, UserListParams,
+    BulkUserRequest, CreateRoleRequestRequest, CreateUserRequest, LoginHistoryQuery,
+    MergeUsersRequest, ReviewRoleRequestRequest, RoleRequestListParams, SetStudentGoalRequest,
+    UpdateAdminPermissionsRequest, UpdateUserRequest, UserExportParams, UserListParams,
 };
 use crate::storage::Storage;
 
@@ -79,6 +87,24 @@ impl UserService {
         delete::delete_user(self, user_id, request).await
     }
 
+    // 批量用户操作
+    pub async fn bulk_users(
+        &self,
+        req: BulkUserRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        bulk::bulk_users(self, req, request).await
+    }
+
+    // 合并重复账号
+    pub async fn merge_users(
+        &self,
+        req: MergeUsersRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        merge::merge_users(self, req, request).await
+    }
+
     // 导出用户
     pub async fn export_users(
         &self,
@@ -106,4 +132,98 @@ impl UserService {
     pub async fn get_my_stats(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
         stats::get_my_stats(self, request).await
     }
+
+    // 获取用户管理权限
+    pub async fn get_user_permissions(
+        &self,
+        user_id: i64,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        permissions::get_user_permissions(self, user_id, request).await
+    }
+
+    // 设置用户管理权限
+    pub async fn update_user_permissions(
+        &self,
+        user_id: i64,
+        req: UpdateAdminPermissionsRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        permissions::update_user_permissions(self, user_id, req, request).await
+    }
+
+    // 获取当前用户管理权限
+    pub async fn get_my_permissions(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        permissions::get_my_permissions(self, request).await
+    }
+
+    // 提交角色申请
+    pub async fn create_role_request(
+        &self,
+        req: CreateRoleRequestRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        role_requests::create_role_request(self, req, request).await
+    }
+
+    // 获取当前用户的角色申请
+    pub async fn list_my_role_requests(
+        &self,
+        params: RoleRequestListParams,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        role_requests::list_my_role_requests(self, params, request).await
+    }
+
+    // 获取角色申请审核队列
+    pub async fn list_role_requests(
+        &self,
+        params: RoleRequestListParams,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        role_requests::list_role_requests(self, params, request).await
+    }
+
+    // 管理员查看登录历史
+    pub async fn list_login_history(
+        &self,
+        query: LoginHistoryQuery,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        login_history::list_login_history(self, query, request).await
+    }
+
+    // 审核角色申请
+    pub async fn review_role_request(
+        &self,
+        id: i64,
+        req: ReviewRoleRequestRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        role_requests::review_role_request(self, id, req, request).await
+    }
+
+    // 获取当前用户的学习目标
+    pub async fn list_my_goals(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        goals::list_my_goals(self, request).await
+    }
+
+    // 设置班级学习目标
+    pub async fn set_my_goal(
+        &self,
+        class_id: i64,
+        req: SetStudentGoalRequest,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        goals::set_my_goal(self, class_id, req, request).await
+    }
+
+    // 删除班级学习目标
+    pub async fn delete_my_goal(
+        &self,
+        class_id: i64,
+        request: &HttpRequest,
+    ) -> ActixResult<HttpResponse> {
+        goals::delete_my_goal(self, class_id, request).await
+    }
 }
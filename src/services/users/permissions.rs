@@ -0,0 +1,158 @@
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+
+use super::UserService;
+use crate::middlewares::{RequireJWT, RequirePermission, TenantGuard};
+use crate::models::users::entities::{AdminPermission, UserRole};
+use crate::models::users::requests::UpdateAdminPermissionsRequest;
+use crate::models::users::responses::AdminPermissionsResponse;
+use crate::models::{ApiResponse, ErrorCode};
+
+// 获取指定用户的管理权限
+pub async fn get_user_permissions(
+    service: &UserService,
+    user_id: i64,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let user = match storage.get_user_by_id(user_id).await {
+        Ok(Some(user)) if TenantGuard::can_access(request, user.org_id) => user,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::UserNotFound,
+                "User not found",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("Failed to get user information: {e}"),
+                )),
+            );
+        }
+    };
+
+    let is_super_admin = user.is_platform_admin();
+    let permissions = if is_super_admin {
+        AdminPermission::all().to_vec()
+    } else {
+        match storage.get_user_admin_permissions(user_id).await {
+            // 组织管理员默认拥有本组织范围的权限
+            Ok(granted) if user.role == UserRole::Admin => AdminPermission::all()
+                .iter()
+                .copied()
+                .filter(|p| p.is_tenant_scoped() || granted.contains(p))
+                .collect(),
+            Ok(permissions) => permissions,
+            Err(e) => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                        ErrorCode::InternalServerError,
+                        format!("Failed to get user permissions: {e}"),
+                    )),
+                );
+            }
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        AdminPermissionsResponse {
+            user_id,
+            is_super_admin,
+            permissions,
+        },
+        "User permissions retrieved successfully",
+    )))
+}
+
+// 设置指定用户的管理权限（仅系统管理员）
+pub async fn update_user_permissions(
+    service: &UserService,
+    user_id: i64,
+    req: UpdateAdminPermissionsRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let current_user = match RequireJWT::extract_user_claims(request) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+                ErrorCode::Unauthorized,
+                "Unauthorized: missing user id",
+            )));
+        }
+    };
+
+    let user = match storage.get_user_by_id(user_id).await {
+        Ok(Some(user)) if TenantGuard::can_access(request, user.org_id) => user,
+        Ok(_) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+                ErrorCode::UserNotFound,
+                "User not found",
+            )));
+        }
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("Failed to get user information: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 平台管理员已拥有全部权限，无需单独授权
+    if user.is_platform_admin() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            "Admin users already have all permissions",
+        )));
+    }
+
+    // 全平台共享的权限只能由平台管理员授予
+    if !current_user.is_platform_admin() && req.permissions.iter().any(|p| !p.is_tenant_scoped()) {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::Forbidden,
+            "Only platform admins can grant platform-wide permissions",
+        )));
+    }
+
+    match storage
+        .set_user_admin_permissions(user_id, &req.permissions, current_user.id)
+        .await
+    {
+        Ok(permissions) => {
+            RequirePermission::invalidate(user_id).await;
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                AdminPermissionsResponse {
+                    user_id,
+                    is_super_admin: false,
+                    permissions,
+                },
+                "User permissions updated successfully",
+            )))
+        }
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("Failed to update user permissions: {e}"),
+            )),
+        ),
+    }
+}
+
+// 获取当前用户的管理权限
+pub async fn get_my_permissions(
+    service: &UserService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    match RequireJWT::extract_user_id(request) {
+        Some(user_id) => get_user_permissions(service, user_id, request).await,
+        None => Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "Unauthorized: missing user id",
+        ))),
+    }
+}
@@ -0,0 +1,344 @@
+//! 角色申请服务
+//!
+//! 普通用户提交成为教师的申请（说明 + 证明材料附件），管理员在审核队列中通过或拒绝。
+//! 通过后用户角色在同一事务中变更为教师并写入审计日志，申请人与管理员均会收到通知。
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use super::UserService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::common::{PaginationPolicy, page_size_exceeded};
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::organizations::entities::TenantScope;
+use crate::models::users::entities::{RoleRequest, RoleRequestStatus, UserRole, UserStatus};
+use crate::models::users::requests::{
+    BulkUserFilter, CreateRoleRequestRequest, ReviewRoleRequestRequest, RoleRequestListParams,
+    RoleRequestListQuery,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::runtime::executor::{self, NOTIFICATIONS_QUEUE};
+use crate::services::notifications::trigger::{
+    send_templated_notification, send_templated_notifications,
+};
+use crate::storage::Storage;
+use crate::utils::ClientInfo;
+
+/// 申请说明最大长度（字符）
+const MAX_REASON_LENGTH: usize = 2000;
+/// 审核意见最大长度（字符）
+const MAX_COMMENT_LENGTH: usize = 500;
+/// 新申请通知的管理员数量上限
+const MAX_NOTIFIED_REVIEWERS: u64 = 1000;
+
+fn unauthorized() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+        ErrorCode::Unauthorized,
+        "无法获取用户信息",
+    )))
+}
+
+fn bad_request(msg: impl Into<String>) -> ActixResult<HttpResponse> {
+    Ok(
+        HttpResponse::BadRequest()
+            .json(ApiResponse::error_empty(ErrorCode::BadRequest, msg.into())),
+    )
+}
+
+fn internal_error(msg: String) -> ActixResult<HttpResponse> {
+    Ok(
+        HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+            ErrorCode::InternalServerError,
+            msg,
+        )),
+    )
+}
+
+fn not_found() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+        ErrorCode::RoleRequestNotFound,
+        "角色申请不存在",
+    )))
+}
+
+// 提交角色申请（普通用户申请成为教师）
+pub async fn create_role_request(
+    service: &UserService,
+    req: CreateRoleRequestRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(user) = RequireJWT::extract_user_claims(request) else {
+        return unauthorized();
+    };
+
+    if user.role != UserRole::User {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::RoleRequestNotAllowed,
+            "当前角色无需申请成为教师",
+        )));
+    }
+
+    let reason = req.reason.trim().to_string();
+    if reason.is_empty() {
+        return bad_request("申请说明不能为空");
+    }
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return bad_request(format!("申请说明不能超过 {MAX_REASON_LENGTH} 个字符"));
+    }
+
+    match storage.get_pending_role_request(user.id).await {
+        Ok(Some(_)) => {
+            return Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+                ErrorCode::RoleRequestPending,
+                "已有待审核的角色申请，请等待管理员处理",
+            )));
+        }
+        Ok(None) => {}
+        Err(e) => return internal_error(format!("查询角色申请失败: {e}")),
+    }
+
+    // 证明材料必须是申请人自己上传的文件
+    let attachment_file_id = match req.attachment.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(token) => match storage.get_file_by_token(token).await {
+            Ok(Some(file)) if file.user_id == Some(user.id) => Some(file.id),
+            Ok(_) => {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+                    ErrorCode::FileNotFound,
+                    "附件不存在或不属于当前用户",
+                )));
+            }
+            Err(e) => return internal_error(format!("查询附件失败: {e}")),
+        },
+    };
+
+    let role_request = match storage
+        .create_role_request(
+            user.id,
+            user.org_id,
+            UserRole::Teacher,
+            reason,
+            attachment_file_id,
+        )
+        .await
+    {
+        Ok(role_request) => role_request,
+        Err(e) => return internal_error(format!("提交角色申请失败: {e}")),
+    };
+
+    if let Some(file_id) = attachment_file_id
+        && let Err(e) = storage.increment_file_citation(file_id).await
+    {
+        tracing::warn!("Failed to increment citation for file {file_id}: {e}");
+    }
+
+    let storage_clone = storage.clone();
+    let notify_request = role_request.clone();
+    executor::spawn(NOTIFICATIONS_QUEUE, async move {
+        notify_reviewers(storage_clone, &notify_request).await;
+    });
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(
+        role_request,
+        "角色申请已提交，请等待管理员审核",
+    )))
+}
+
+// 获取当前用户的角色申请记录
+pub async fn list_my_role_requests(
+    service: &UserService,
+    params: RoleRequestListParams,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let Some(user_id) = RequireJWT::extract_user_id(request) else {
+        return unauthorized();
+    };
+
+    let query = RoleRequestListQuery {
+        page: Some(params.pagination.page),
+        size: params.pagination.size,
+        status: params.status,
+        user_id: Some(user_id),
+        tenant: TenantScope::All,
+    };
+    list(service, query, request).await
+}
+
+// 获取角色申请审核队列（管理员）
+pub async fn list_role_requests(
+    service: &UserService,
+    params: RoleRequestListParams,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let query = RoleRequestListQuery {
+        page: Some(params.pagination.page),
+        size: params.pagination.size,
+        status: params.status,
+        user_id: None,
+        tenant: TenantGuard::scope(request),
+    };
+    list(service, query, request).await
+}
+
+async fn list(
+    service: &UserService,
+    query: RoleRequestListQuery,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    if let Err(limit) = PaginationPolicy::for_endpoint("role_requests").check(query.size) {
+        return Ok(page_size_exceeded(limit));
+    }
+
+    let storage = service.get_storage(request);
+    match storage.list_role_requests(query).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(ApiResponse::success(response, "查询成功"))),
+        Err(e) => internal_error(format!("查询角色申请失败: {e}")),
+    }
+}
+
+// 审核角色申请（管理员）
+pub async fn review_role_request(
+    service: &UserService,
+    id: i64,
+    req: ReviewRoleRequestRequest,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(reviewer) = RequireJWT::extract_user_claims(request) else {
+        return unauthorized();
+    };
+
+    let comment = req
+        .comment
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+    if let Some(comment) = &comment
+        && comment.chars().count() > MAX_COMMENT_LENGTH
+    {
+        return bad_request(format!("审核意见不能超过 {MAX_COMMENT_LENGTH} 个字符"));
+    }
+
+    let role_request = match storage.get_role_request_by_id(id).await {
+        // 跨组织的申请按不存在处理
+        Ok(Some(r)) if TenantGuard::can_access(request, r.org_id) => r,
+        Ok(_) => return not_found(),
+        Err(e) => return internal_error(format!("查询角色申请失败: {e}")),
+    };
+
+    if role_request.user_id == reviewer.id {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::PermissionDenied,
+            "不能审核自己的角色申请",
+        )));
+    }
+    if role_request.status != RoleRequestStatus::Pending {
+        return already_reviewed();
+    }
+
+    let ip_address = ClientInfo::from_request(request).ip_string();
+    let reviewed = match storage
+        .review_role_request(id, req.approve, comment, reviewer.id, ip_address)
+        .await
+    {
+        Ok(Some(reviewed)) => reviewed,
+        // 并发审核时另一方已先完成
+        Ok(None) => return already_reviewed(),
+        Err(e) => return internal_error(format!("审核角色申请失败: {e}")),
+    };
+    if req.approve {
+        RequireJWT::invalidate_user(request, reviewed.user_id).await;
+    }
+
+    let comment = reviewed.review_comment.clone().unwrap_or_default();
+    let (result, message) = if req.approve {
+        (
+            "已通过",
+            "您的教师身份申请已通过审核，重新登录后即可使用教师功能".to_string(),
+        )
+    } else {
+        (
+            "未通过",
+            match &reviewed.review_comment {
+                Some(comment) => format!("您的教师身份申请未通过审核：{comment}"),
+                None => "您的教师身份申请未通过审核".to_string(),
+            },
+        )
+    };
+    let vars = vec![
+        ("result", result.to_string()),
+        ("comment", comment),
+        ("message", message),
+    ];
+    let storage_clone = storage.clone();
+    let applicant_id = reviewed.user_id;
+    executor::spawn(NOTIFICATIONS_QUEUE, async move {
+        send_templated_notification(
+            storage_clone,
+            applicant_id,
+            NotificationType::RoleRequestReviewed,
+            vars,
+            Some(ReferenceType::RoleRequest),
+            Some(id),
+        )
+        .await;
+    });
+
+    let message = if req.approve {
+        "角色申请已通过"
+    } else {
+        "角色申请已拒绝"
+    };
+    Ok(HttpResponse::Ok().json(ApiResponse::success(reviewed, message)))
+}
+
+fn already_reviewed() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Conflict().json(ApiResponse::error_empty(
+        ErrorCode::RoleRequestAlreadyReviewed,
+        "该角色申请已被审核",
+    )))
+}
+
+/// 通知可审核该申请的管理员（同组织管理员与平台管理员）
+async fn notify_reviewers(storage: Arc<dyn Storage>, role_request: &RoleRequest) {
+    let filter = BulkUserFilter {
+        role: Some(UserRole::Admin),
+        status: Some(UserStatus::Active),
+        ..Default::default()
+    };
+
+    let mut scopes = vec![TenantScope::Org(None)];
+    if role_request.org_id.is_some() {
+        scopes.push(TenantScope::Org(role_request.org_id));
+    }
+
+    let mut reviewer_ids = Vec::new();
+    for scope in scopes {
+        match storage
+            .list_users_for_bulk(filter.clone(), scope, MAX_NOTIFIED_REVIEWERS)
+            .await
+        {
+            Ok(users) => reviewer_ids.extend(users.into_iter().map(|u| u.id)),
+            Err(e) => tracing::error!("Failed to list role request reviewers: {e}"),
+        }
+    }
+
+    let applicant = role_request
+        .display_name
+        .clone()
+        .unwrap_or_else(|| role_request.username.clone());
+    send_templated_notifications(
+        storage,
+        reviewer_ids,
+        NotificationType::RoleRequestSubmitted,
+        vec![
+            ("applicant", applicant),
+            ("reason", role_request.reason.chars().take(100).collect()),
+        ],
+        Some(ReferenceType::RoleRequest),
+        Some(role_request.id),
+    )
+    .await;
+}
@@ -3,6 +3,7 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use crate::middlewares::RequireJWT;
+use crate::models::users::entities::{GoalProgress, GoalStanding, GoalStatus};
 use crate::models::{ApiResponse, ErrorCode};
 use crate::services::users::UserService;
 
@@ -40,3 +41,96 @@ pub async fn get_my_stats(
         }
     }
 }
+
+/// 浮点比较容差，避免 59.999999 之类的误差影响目标判断
+const GOAL_EPSILON: f64 = 1e-9;
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// 根据班级成绩汇总预测学习目标进度
+///
+/// 最终得分率 = (已得分 + 剩余作业得分) / (已评分满分 + 剩余满分)，
+/// 据此计算剩余作业需要达到的平均得分率。
+pub fn project_goal(standing: GoalStanding) -> GoalProgress {
+    let total_max = standing.graded_max_score + standing.remaining_max_score;
+    let target_points = standing.target_percent / 100.0 * total_max;
+    let needed_points = target_points - standing.earned_score;
+
+    let current_percent = (standing.graded_max_score > 0.0)
+        .then(|| round2(standing.earned_score / standing.graded_max_score * 100.0));
+    let best_possible_percent = (total_max > 0.0).then(|| {
+        round2((standing.earned_score + standing.remaining_max_score) / total_max * 100.0)
+    });
+    let required_percent = (standing.remaining_max_score > 0.0 && needed_points > GOAL_EPSILON)
+        .then(|| round2(needed_points / standing.remaining_max_score * 100.0));
+
+    let status = if standing.graded_count == 0 {
+        GoalStatus::NotStarted
+    } else if needed_points <= GOAL_EPSILON {
+        GoalStatus::Achieved
+    } else if needed_points > standing.remaining_max_score + GOAL_EPSILON {
+        GoalStatus::Unreachable
+    } else if current_percent.is_some_and(|p| p + GOAL_EPSILON >= standing.target_percent) {
+        GoalStatus::OnTrack
+    } else {
+        GoalStatus::Behind
+    };
+
+    GoalProgress {
+        class_id: standing.class_id,
+        class_name: standing.class_name,
+        target_percent: standing.target_percent,
+        current_percent,
+        graded_count: standing.graded_count,
+        remaining_count: standing.remaining_count,
+        remaining_max_score: standing.remaining_max_score,
+        required_percent,
+        best_possible_percent,
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standing(earned: f64, graded_max: f64, remaining_max: f64) -> GoalStanding {
+        GoalStanding {
+            class_id: 1,
+            class_name: "班级".to_string(),
+            target_percent: 80.0,
+            earned_score: earned,
+            graded_max_score: graded_max,
+            graded_count: if graded_max > 0.0 { 1 } else { 0 },
+            remaining_max_score: remaining_max,
+            remaining_count: if remaining_max > 0.0 { 1 } else { 0 },
+        }
+    }
+
+    #[test]
+    fn test_project_goal() {
+        // 70/100，剩余 100 分需要 90% 才能达到 80%
+        let progress = project_goal(standing(70.0, 100.0, 100.0));
+        assert_eq!(progress.current_percent, Some(70.0));
+        assert_eq!(progress.required_percent, Some(90.0));
+        assert_eq!(progress.best_possible_percent, Some(85.0));
+        assert_eq!(progress.status, GoalStatus::Behind);
+
+        let progress = project_goal(standing(90.0, 100.0, 100.0));
+        assert_eq!(progress.status, GoalStatus::OnTrack);
+
+        let progress = project_goal(standing(170.0, 200.0, 10.0));
+        assert_eq!(progress.required_percent, None);
+        assert_eq!(progress.status, GoalStatus::Achieved);
+
+        let progress = project_goal(standing(20.0, 100.0, 100.0));
+        assert_eq!(progress.status, GoalStatus::Unreachable);
+
+        let progress = project_goal(standing(0.0, 0.0, 100.0));
+        assert_eq!(progress.current_percent, None);
+        assert_eq!(progress.required_percent, Some(80.0));
+        assert_eq!(progress.status, GoalStatus::NotStarted);
+    }
+}
@@ -1,11 +1,16 @@
 use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
 
 use super::UserService;
-use crate::middlewares::RequireJWT;
+use crate::middlewares::{RequireJWT, TenantGuard};
 use crate::models::{
     ApiResponse, ErrorCode,
-    users::{entities::UserRole, requests::UpdateUserRequest, responses::UserResponse},
+    users::{
+        entities::{UserRole, UserStatus},
+        requests::UpdateUserRequest,
+        responses::UserResponse,
+    },
 };
+use crate::services::disconnect_user;
 use crate::utils::validate::validate_password_simple;
 
 pub async fn update_user(
@@ -27,8 +32,9 @@ pub async fn update_user(
 
     // 获取目标用户信息
     let target_user = match storage.get_user_by_id(user_id).await {
-        Ok(Some(user)) => user,
-        Ok(None) => {
+        // 跨组织的用户按不存在处理
+        Ok(Some(user)) if TenantGuard::can_access(request, user.org_id) => user,
+        Ok(_) => {
             return Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
                 ErrorCode::UserNotFound,
                 "用户不存在",
@@ -86,10 +92,17 @@ pub async fn update_user(
     }
 
     match storage.update_user(user_id, update_data).await {
-        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
-            UserResponse { user },
-            "用户信息更新成功",
-        ))),
+        Ok(Some(user)) => {
+            RequireJWT::invalidate_user(request, user.id).await;
+            // 账号被停用时断开其实时连接
+            if user.status != UserStatus::Active {
+                disconnect_user(user.id, "账号已停用");
+            }
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                UserResponse { user },
+                "用户信息更新成功",
+            )))
+        }
         Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
             ErrorCode::UserNotFound,
             "用户不存在",
@@ -0,0 +1,168 @@
+//! 学生管理家长/导师查看授权
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::collections::HashMap;
+
+use super::ViewerService;
+use crate::middlewares::RequireJWT;
+use crate::models::viewers::requests::CreateViewerInvitationRequest;
+use crate::models::viewers::responses::{ViewerGrantItem, ViewerGrantListResponse, ViewerUserInfo};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::presenters::load_users;
+use crate::utils::random_code::generate_random_code;
+
+/// 邀请令牌长度
+const INVITATION_TOKEN_LENGTH: usize = 32;
+
+/// 默认有效期（天）
+const DEFAULT_EXPIRY_DAYS: i64 = 7;
+
+/// 最长有效期（天）
+const MAX_EXPIRY_DAYS: i64 = 30;
+
+/// 备注最大字符数
+const MAX_NOTE_CHARS: usize = 100;
+
+pub async fn create_invitation(
+    service: &ViewerService,
+    request: &HttpRequest,
+    req: CreateViewerInvitationRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(student_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let expiry_days = req.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+    if !(1..=MAX_EXPIRY_DAYS).contains(&expiry_days) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("邀请有效期必须在 1 到 {MAX_EXPIRY_DAYS} 天之间"),
+        )));
+    }
+
+    let note = req
+        .note
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+            ErrorCode::BadRequest,
+            format!("备注不能超过 {MAX_NOTE_CHARS} 个字符"),
+        )));
+    }
+
+    let now = chrono::Utc::now();
+    let expires_at = now + chrono::Duration::days(expiry_days);
+    let token = generate_random_code(INVITATION_TOKEN_LENGTH);
+
+    match storage
+        .create_viewer_invitation(student_id, &token, note, expires_at.timestamp())
+        .await
+    {
+        Ok(grant) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            ViewerGrantItem {
+                status: grant.status(now),
+                grant,
+                viewer: None,
+            },
+            "查看邀请已创建",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("创建查看邀请失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn list_grants(
+    service: &ViewerService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(student_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let grants = match storage.list_viewer_grants_by_student(student_id).await {
+        Ok(grants) => grants,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询查看授权失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let viewer_ids = grants.iter().filter_map(|g| g.viewer_id);
+    let viewers: HashMap<i64, ViewerUserInfo> = match load_users(&storage, viewer_ids).await {
+        Ok(users) => users,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询用户失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let items = grants
+        .into_iter()
+        .map(|grant| ViewerGrantItem {
+            status: grant.status(now),
+            viewer: grant.viewer_id.and_then(|id| viewers.get(&id).cloned()),
+            grant,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ViewerGrantListResponse { items },
+        "查询成功",
+    )))
+}
+
+pub async fn revoke_grant(
+    service: &ViewerService,
+    request: &HttpRequest,
+    grant_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(student_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    match storage.revoke_viewer_grant(student_id, grant_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::success_empty("查看授权已撤销"))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::error_empty(
+            ErrorCode::ViewerGrantNotFound,
+            "查看授权不存在或已撤销",
+        ))),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("撤销查看授权失败: {e}"),
+            )),
+        ),
+    }
+}
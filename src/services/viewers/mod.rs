@@ -0,0 +1,84 @@
+//! 家长/导师只读查看服务
+//!
+//! 学生生成查看邀请，家长/导师凭邀请令牌注册 `viewer` 角色账号（或用已有的查看账号接受），
+//! 之后只能通过 `/viewer` 接口查看授权学生的每周汇总：近期评分、即将截止的作业与各班进度。
+//! `viewer` 账号访问其他受保护接口时由 [`RequireJWT`](crate::middlewares::RequireJWT) 拒绝。
+
+pub mod grants;
+pub mod students;
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use std::sync::Arc;
+
+use crate::models::viewers::requests::{
+    AcceptViewerInvitationRequest, CreateViewerInvitationRequest,
+};
+use crate::storage::Storage;
+
+pub struct ViewerService {
+    storage: Option<Arc<dyn Storage>>,
+}
+
+impl ViewerService {
+    pub fn new_lazy() -> Self {
+        Self { storage: None }
+    }
+
+    pub(crate) fn get_storage(&self, request: &HttpRequest) -> Arc<dyn Storage> {
+        if let Some(storage) = &self.storage {
+            storage.clone()
+        } else {
+            request
+                .app_data::<actix_web::web::Data<Arc<dyn Storage>>>()
+                .expect("Storage not found in app data")
+                .get_ref()
+                .clone()
+        }
+    }
+
+    /// 学生创建查看邀请
+    pub async fn create_invitation(
+        &self,
+        request: &HttpRequest,
+        req: CreateViewerInvitationRequest,
+    ) -> ActixResult<HttpResponse> {
+        grants::create_invitation(self, request, req).await
+    }
+
+    /// 学生列出自己发出的查看授权
+    pub async fn list_grants(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        grants::list_grants(self, request).await
+    }
+
+    /// 学生撤销查看授权
+    pub async fn revoke_grant(
+        &self,
+        request: &HttpRequest,
+        grant_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        grants::revoke_grant(self, request, grant_id).await
+    }
+
+    /// 家长/导师接受查看邀请
+    pub async fn accept_invitation(
+        &self,
+        request: &HttpRequest,
+        req: AcceptViewerInvitationRequest,
+    ) -> ActixResult<HttpResponse> {
+        students::accept_invitation(self, request, req).await
+    }
+
+    /// 家长/导师列出可查看的学生
+    pub async fn list_students(&self, request: &HttpRequest) -> ActixResult<HttpResponse> {
+        students::list_students(self, request).await
+    }
+
+    /// 家长/导师查看学生每周汇总
+    pub async fn get_student_summary(
+        &self,
+        request: &HttpRequest,
+        student_id: i64,
+    ) -> ActixResult<HttpResponse> {
+        students::get_student_summary(self, request, student_id).await
+    }
+}
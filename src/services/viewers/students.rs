@@ -0,0 +1,228 @@
+//! 家长/导师查看授权学生
+
+use actix_web::{HttpRequest, HttpResponse, Result as ActixResult};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use super::ViewerService;
+use crate::middlewares::{RequireJWT, TenantGuard};
+use crate::models::users::entities::UserStatus;
+use crate::models::viewers::entities::ViewerGrantStatus;
+use crate::models::viewers::requests::AcceptViewerInvitationRequest;
+use crate::models::viewers::responses::{
+    ViewerStudentItem, ViewerStudentListResponse, ViewerStudentSummary, ViewerUserInfo,
+};
+use crate::models::{ApiResponse, ErrorCode};
+use crate::services::presenters::load_users;
+
+/// 汇总的时间窗口（天）：过去 7 天的评分、未来 7 天的截止日期
+const SUMMARY_DAYS: i64 = 7;
+/// 汇总中最多返回的评分条数
+const SUMMARY_GRADE_LIMIT: u64 = 50;
+
+fn invalid_invitation(msg: &str) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::BadRequest().json(ApiResponse::error_empty(
+        ErrorCode::ViewerInvitationInvalid,
+        msg,
+    )))
+}
+
+pub async fn accept_invitation(
+    service: &ViewerService,
+    request: &HttpRequest,
+    req: AcceptViewerInvitationRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(viewer_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let grant = match storage.get_viewer_grant_by_token(req.token.trim()).await {
+        Ok(Some(grant)) if grant.status(Utc::now()) == ViewerGrantStatus::Pending => grant,
+        Ok(_) => return invalid_invitation("邀请无效、已被使用或已过期"),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询查看邀请失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    match storage.has_viewer_grant(viewer_id, grant.student_id).await {
+        Ok(false) => {}
+        Ok(true) => return invalid_invitation("已拥有该学生的查看权限"),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询查看授权失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    match storage
+        .accept_viewer_invitation(&grant.token, viewer_id)
+        .await
+    {
+        Ok(Some(grant)) => Ok(HttpResponse::Ok().json(ApiResponse::success(grant, "已接受邀请"))),
+        // 并发接受时另一方已抢先使用
+        Ok(None) => invalid_invitation("邀请无效、已被使用或已过期"),
+        Err(e) => Ok(
+            HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                ErrorCode::InternalServerError,
+                format!("接受查看邀请失败: {e}"),
+            )),
+        ),
+    }
+}
+
+pub async fn list_students(
+    service: &ViewerService,
+    request: &HttpRequest,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(viewer_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    let grants = match storage.list_active_viewer_grants(viewer_id).await {
+        Ok(grants) => grants,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询查看授权失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let student_ids = grants.iter().map(|g| g.student_id);
+    let students: HashMap<i64, ViewerUserInfo> = match load_users(&storage, student_ids).await {
+        Ok(users) => users,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询用户失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let items = grants
+        .into_iter()
+        .filter_map(|grant| {
+            Some(ViewerStudentItem {
+                grant_id: grant.id,
+                student: students.get(&grant.student_id).cloned()?,
+                granted_at: grant.accepted_at,
+            })
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        ViewerStudentListResponse { items },
+        "查询成功",
+    )))
+}
+
+pub async fn get_student_summary(
+    service: &ViewerService,
+    request: &HttpRequest,
+    student_id: i64,
+) -> ActixResult<HttpResponse> {
+    let storage = service.get_storage(request);
+
+    let Some(viewer_id) = RequireJWT::extract_user_id(request) else {
+        return Ok(HttpResponse::Unauthorized().json(ApiResponse::error_empty(
+            ErrorCode::Unauthorized,
+            "无法获取用户信息",
+        )));
+    };
+
+    // 未授权与学生不存在返回相同的响应，避免探测学生 ID
+    let denied = || {
+        HttpResponse::Forbidden().json(ApiResponse::error_empty(
+            ErrorCode::ViewerAccessDenied,
+            "没有查看该学生的权限",
+        ))
+    };
+
+    match storage.has_viewer_grant(viewer_id, student_id).await {
+        Ok(true) => {}
+        Ok(false) => return Ok(denied()),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询查看授权失败: {e}"),
+                )),
+            );
+        }
+    }
+
+    let student = match storage.get_user_by_id(student_id).await {
+        Ok(Some(user))
+            if user.status == UserStatus::Active
+                && TenantGuard::can_access(request, user.org_id) =>
+        {
+            user
+        }
+        Ok(_) => return Ok(denied()),
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("查询用户失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    let now = Utc::now();
+    let period_start = now - Duration::days(SUMMARY_DAYS);
+    let period_end = now + Duration::days(SUMMARY_DAYS);
+    let dashboard = match storage
+        .get_student_dashboard(student.id, period_end.timestamp(), SUMMARY_GRADE_LIMIT)
+        .await
+    {
+        Ok(dashboard) => dashboard,
+        Err(e) => {
+            return Ok(
+                HttpResponse::InternalServerError().json(ApiResponse::error_empty(
+                    ErrorCode::InternalServerError,
+                    format!("获取学生汇总失败: {e}"),
+                )),
+            );
+        }
+    };
+
+    // 只读视图不包含通知与学习目标，评分只保留本周期内的
+    let summary = ViewerStudentSummary {
+        student: student.into(),
+        period_start,
+        period_end,
+        upcoming_deadlines: dashboard.upcoming_deadlines,
+        recent_grades: dashboard
+            .recent_grades
+            .into_iter()
+            .filter(|g| g.graded_at >= period_start)
+            .collect(),
+        class_progress: dashboard.class_progress,
+        generated_at: now,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(summary, "查询成功")))
+}
@@ -0,0 +1,52 @@
+//! WebSocket 认证
+//!
+//! 连接建立后由客户端发送 `auth` 消息完成认证，长连接期间通过 `token_refresh`
+//! 消息续期，避免访问令牌出现在 URL（进而进入访问日志）中。
+
+use std::sync::Arc;
+
+use crate::middlewares::TenantGuard;
+use crate::models::users::entities::{User, UserStatus};
+use crate::storage::Storage;
+use crate::utils::jwt::JwtUtils;
+
+/// 认证结果
+pub struct WsAuth {
+    pub user: User,
+    /// 访问令牌过期时间（Unix 时间戳）
+    pub expires_at: i64,
+}
+
+/// 校验访问令牌并加载用户
+pub async fn authenticate(storage: &Arc<dyn Storage>, token: &str) -> Result<WsAuth, String> {
+    let claims = JwtUtils::verify_access_token(token).map_err(|_| "Invalid token".to_string())?;
+
+    let user_id = claims
+        .sub
+        .parse::<i64>()
+        .map_err(|_| "Invalid user ID".to_string())?;
+
+    let user = load_active_user(storage, user_id).await?;
+
+    Ok(WsAuth {
+        user,
+        expires_at: claims.exp as i64,
+    })
+}
+
+/// 加载用户并确认账号及所属组织处于启用状态
+pub async fn load_active_user(storage: &Arc<dyn Storage>, user_id: i64) -> Result<User, String> {
+    let user = storage
+        .get_user_by_id(user_id)
+        .await
+        .map_err(|_| "Failed to get user".to_string())?
+        .ok_or_else(|| "User not found".to_string())?;
+
+    if user.status != UserStatus::Active {
+        return Err("User is not active".to_string());
+    }
+
+    TenantGuard::ensure_org_active(storage, user.org_id).await?;
+
+    Ok(user)
+}
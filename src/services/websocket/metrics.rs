@@ -0,0 +1,306 @@
+//! WebSocket 连接生命周期统计与 Prometheus 文本格式导出
+//!
+//! 计数器只增不减，连接与断开速率、广播落后情况由 Prometheus 的 `rate()` 计算；
+//! 断开按原因分别计数，便于区分客户端断开、空闲超时、令牌过期等情况。
+//! 广播落后（`RecvError::Lagged`）说明单个用户的推送积压超过了通道容量，是扇出问题的主要信号。
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{ConnectionLimit, ConnectionManager, ws_metrics};
+use crate::config::AppConfig;
+use crate::models::system::responses::WsConnectionMetrics;
+
+/// 全局连接统计
+static METRICS: ConnectionMetrics = ConnectionMetrics::new();
+
+/// 连接断开原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// 客户端关闭或连接中断
+    Client,
+    /// 客户端长时间未发送消息
+    IdleTimeout,
+    /// 访问令牌过期且未续期
+    TokenExpired,
+    /// 账号被停用或删除
+    SessionRevoked,
+    /// 发送失败或读取出错
+    Error,
+}
+
+impl DisconnectReason {
+    const ALL: [Self; 5] = [
+        Self::Client,
+        Self::IdleTimeout,
+        Self::TokenExpired,
+        Self::SessionRevoked,
+        Self::Error,
+    ];
+
+    /// Prometheus 标签值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::IdleTimeout => "idle_timeout",
+            Self::TokenExpired => "token_expired",
+            Self::SessionRevoked => "session_revoked",
+            Self::Error => "error",
+        }
+    }
+}
+
+struct ConnectionMetrics {
+    opened: AtomicU64,
+    closed: [AtomicU64; DisconnectReason::ALL.len()],
+    rejected_global: AtomicU64,
+    rejected_user: AtomicU64,
+    lag_events: AtomicU64,
+    lagged_messages: AtomicU64,
+}
+
+impl ConnectionMetrics {
+    const fn new() -> Self {
+        Self {
+            opened: AtomicU64::new(0),
+            closed: [const { AtomicU64::new(0) }; DisconnectReason::ALL.len()],
+            rejected_global: AtomicU64::new(0),
+            rejected_user: AtomicU64::new(0),
+            lag_events: AtomicU64::new(0),
+            lagged_messages: AtomicU64::new(0),
+        }
+    }
+
+    fn closed(&self, reason: DisconnectReason) -> u64 {
+        self.closed[reason as usize].load(Ordering::Relaxed)
+    }
+}
+
+/// 记录一次连接建立
+pub fn record_connected() {
+    METRICS.opened.fetch_add(1, Ordering::Relaxed);
+}
+
+/// 记录一次连接断开
+pub fn record_disconnected(reason: DisconnectReason) {
+    METRICS.closed[reason as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+/// 记录一次因连接上限被拒绝的连接
+pub fn record_rejected(limit: ConnectionLimit) {
+    let counter = match limit {
+        ConnectionLimit::Global => &METRICS.rejected_global,
+        ConnectionLimit::PerUser => &METRICS.rejected_user,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// 记录一次广播接收落后，`skipped` 为被丢弃的消息数
+pub fn record_lagged(skipped: u64) {
+    METRICS.lag_events.fetch_add(1, Ordering::Relaxed);
+    METRICS
+        .lagged_messages
+        .fetch_add(skipped, Ordering::Relaxed);
+}
+
+/// 获取连接统计
+pub fn connection_metrics() -> WsConnectionMetrics {
+    WsConnectionMetrics {
+        active_connections: ConnectionManager::get().connection_count(),
+        connections_opened: METRICS.opened.load(Ordering::Relaxed),
+        connections_closed: DisconnectReason::ALL
+            .iter()
+            .map(|reason| METRICS.closed(*reason))
+            .sum(),
+        idle_timeouts: METRICS.closed(DisconnectReason::IdleTimeout),
+        rejected_global_limit: METRICS.rejected_global.load(Ordering::Relaxed),
+        rejected_user_limit: METRICS.rejected_user.load(Ordering::Relaxed),
+        lag_events: METRICS.lag_events.load(Ordering::Relaxed),
+        lagged_messages: METRICS.lagged_messages.load(Ordering::Relaxed),
+    }
+}
+
+/// 写入一个指标（`samples` 为标签与取值，标签为空表示无标签）
+pub(crate) fn write_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: &[(String, u64)],
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{name} {value}");
+        } else {
+            let _ = writeln!(out, "{name}{{{labels}}} {value}");
+        }
+    }
+}
+
+pub(crate) fn single(value: u64) -> [(String, u64); 1] {
+    [(String::new(), value)]
+}
+
+/// 以 Prometheus 文本格式导出连接与发送统计
+pub fn render_prometheus() -> String {
+    let config = &AppConfig::get().websocket;
+    let manager = ConnectionManager::get();
+    let sent = ws_metrics();
+    let mut out = String::new();
+
+    write_metric(
+        &mut out,
+        "hwsystem_ws_connections",
+        "gauge",
+        "Current WebSocket connections.",
+        &single(manager.connection_count() as u64),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_online_users",
+        "gauge",
+        "Users with at least one WebSocket connection.",
+        &single(manager.online_count() as u64),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_max_connections",
+        "gauge",
+        "Configured global connection limit (0 = unlimited).",
+        &single(config.max_connections as u64),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_max_connections_per_user",
+        "gauge",
+        "Configured per-user connection limit (0 = unlimited).",
+        &single(config.max_connections_per_user as u64),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_connections_opened_total",
+        "counter",
+        "WebSocket connections accepted after authentication.",
+        &single(METRICS.opened.load(Ordering::Relaxed)),
+    );
+    let closed: Vec<_> = DisconnectReason::ALL
+        .iter()
+        .map(|reason| {
+            (
+                format!("reason=\"{}\"", reason.as_str()),
+                METRICS.closed(*reason),
+            )
+        })
+        .collect();
+    write_metric(
+        &mut out,
+        "hwsystem_ws_connections_closed_total",
+        "counter",
+        "WebSocket connections closed, by reason.",
+        &closed,
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_connections_rejected_total",
+        "counter",
+        "WebSocket connections rejected by a connection limit.",
+        &[
+            (
+                "limit=\"global\"".to_string(),
+                METRICS.rejected_global.load(Ordering::Relaxed),
+            ),
+            (
+                "limit=\"user\"".to_string(),
+                METRICS.rejected_user.load(Ordering::Relaxed),
+            ),
+        ],
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_broadcast_lag_events_total",
+        "counter",
+        "Times a connection fell behind its broadcast channel.",
+        &single(METRICS.lag_events.load(Ordering::Relaxed)),
+    );
+    write_metric(
+        &mut out,
+        "hwsystem_ws_broadcast_lagged_messages_total",
+        "counter",
+        "Messages dropped because a connection fell behind.",
+        &single(METRICS.lagged_messages.load(Ordering::Relaxed)),
+    );
+    for (name, help, value) in [
+        (
+            "hwsystem_ws_frames_sent_total",
+            "Frames sent to clients.",
+            sent.frames_sent,
+        ),
+        (
+            "hwsystem_ws_bytes_sent_total",
+            "Bytes sent to clients after compression.",
+            sent.bytes_sent,
+        ),
+        (
+            "hwsystem_ws_bytes_uncompressed_total",
+            "Serialized bytes before compression.",
+            sent.bytes_uncompressed,
+        ),
+        (
+            "hwsystem_ws_compressed_frames_total",
+            "Frames sent compressed.",
+            sent.compressed_frames,
+        ),
+        (
+            "hwsystem_ws_batched_frames_total",
+            "Batched notification frames sent.",
+            sent.batched_frames,
+        ),
+        (
+            "hwsystem_ws_batched_notifications_total",
+            "Notifications delivered in batched frames.",
+            sent.batched_notifications,
+        ),
+    ] {
+        write_metric(&mut out, name, "counter", help, &single(value));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_metric_format() {
+        let mut out = String::new();
+        write_metric(
+            &mut out,
+            "hwsystem_ws_connections_closed_total",
+            "counter",
+            "Closed connections.",
+            &[
+                ("reason=\"client\"".to_string(), 3),
+                ("reason=\"error\"".to_string(), 0),
+            ],
+        );
+        write_metric(
+            &mut out,
+            "hwsystem_ws_connections",
+            "gauge",
+            "Now.",
+            &single(2),
+        );
+        assert_eq!(
+            out,
+            "# HELP hwsystem_ws_connections_closed_total Closed connections.\n\
+             # TYPE hwsystem_ws_connections_closed_total counter\n\
+             hwsystem_ws_connections_closed_total{reason=\"client\"} 3\n\
+             hwsystem_ws_connections_closed_total{reason=\"error\"} 0\n\
+             # HELP hwsystem_ws_connections Now.\n\
+             # TYPE hwsystem_ws_connections gauge\n\
+             hwsystem_ws_connections 2\n"
+        );
+    }
+}
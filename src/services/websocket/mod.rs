@@ -5,11 +5,16 @@
  *
  * ## 使用方法
  *
- * 客户端通过以下 URL 连接：
+ * 客户端连接后在 10 秒内发送认证消息：
  * ```text
- * ws://host/api/v1/ws?token=<access_token>
+ * ws://host/api/v1/ws
+ * ```
+ * ```json
+ * {"type": "auth", "token": "<access_token>"}
  * ```
  *
+ * 旧版的 `ws://host/api/v1/ws?token=<access_token>` 仍然可用，但令牌会出现在访问日志中，不再推荐。
+ *
  * ## 消息格式
  *
  * ### 服务端推送
@@ -33,17 +38,88 @@
  * {"type": "ping"}
  * {"type": "pong"}
  * ```
+ *
+ * ### 令牌续期
+ * 访问令牌过期前发送新令牌即可继续使用当前连接，过期未续期的连接会被关闭：
+ * ```json
+ * {"type": "token_refresh", "token": "<new_access_token>"}
+ * {"type": "token_refreshed", "expires_at": 1769241600}
+ * ```
+ *
+ * ### 离线通知补发
+ * 连接 URL 携带 `since`（Unix 时间戳）时，认证成功后按时间顺序补发该时间之后尚未送达的通知；
+ * 也可以随时发送 `replay` 消息（`since` 可省略）。补发结束后返回 `replay_complete`：
+ * ```json
+ * {"type": "replay", "since": 1769241600}
+ * {"type": "replay_complete", "count": 3, "has_more": false}
+ * ```
+ *
+ * ### 批量推送与压缩
+ * 连接 URL 携带 `batch=true` 时，短时间窗口内的多条通知合并为一条 `notifications` 消息：
+ * ```json
+ * {"type": "notifications", "payloads": [{ ... }, { ... }]}
+ * ```
+ * 携带 `compression=deflate` 时，较大的消息以二进制帧发送原始 DEFLATE 数据（见 [`outbound`]）。
+ *
+ * ### 私信
+ * 收到班级私信时推送完整消息，接收方离线时改为发送 `message_received` 通知：
+ * ```json
+ * {"type": "direct_message", "payload": {"id": 1, "class_id": 1, "sender_id": 2, "recipient_id": 3, "content": "老师好", "read_at": null, "created_at": "2026-01-24T12:00:00Z"}}
+ * ```
+ *
+ * ### 表情回应
+ * 班级公告或评语的表情回应变化时，向能看到该内容的在线用户推送变化后的计数：
+ * ```json
+ * {"type": "reaction_updated", "payload": {"target_type": "announcement", "target_id": 12, "user_id": 3, "emoji": "👍", "added": true, "counts": [{"emoji": "👍", "count": 5}]}}
+ * ```
+ *
+ * ### 会话终止
+ * 账号被停用或删除时服务端推送后关闭连接：
+ * ```json
+ * {"type": "session_revoked", "reason": "账号已停用"}
+ * ```
+ *
+ * ### 连接上限与空闲超时
+ * 认证后按 `websocket.max_connections`（全局）与 `websocket.max_connections_per_user` 检查并发连接数，
+ * 超过时推送 `error` 后关闭（全局上限关闭码 1013，单用户上限 1008）。
+ * 配置了 `websocket.idle_timeout` 时，客户端在该时间内未发送任何消息（如 `ping`）则以关闭码 1001 关闭；
+ * 服务端每 30 秒发送的心跳帧只用于保活，其回应不计入。连接统计见 [`metrics`]。
  */
 
-use actix_ws::Message;
+pub mod auth;
+pub mod metrics;
+pub mod outbound;
+
+use actix_ws::{CloseCode, CloseReason, Message};
 use dashmap::DashMap;
 use futures_util::StreamExt;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 use tokio::sync::broadcast;
+use tokio::time::Instant;
 use tracing::{debug, info, warn};
 
+use crate::config::AppConfig;
+use crate::models::messages::entities::Message as DirectMessage;
 use crate::models::notifications::entities::Notification;
+use crate::models::reactions::responses::ReactionUpdate;
+use crate::storage::Storage;
+
+pub use auth::WsAuth;
+pub use metrics::{DisconnectReason, connection_metrics};
+pub use outbound::{WsCompression, WsSender, ws_metrics};
+
+/// 连接后等待认证消息的超时时间
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+/// 心跳间隔
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+/// 复查用户状态的间隔（兜底处理其他节点上的停用操作）
+const STATUS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// 单次补发的最大通知数
+const REPLAY_LIMIT: u64 = 200;
 
 /// 全局连接管理器
 static CONNECTION_MANAGER: Lazy<ConnectionManager> = Lazy::new(ConnectionManager::new);
@@ -62,6 +138,35 @@ pub enum WsMessage {
     Connected { user_id: i64 },
     /// 错误消息
     Error { message: String },
+    /// 认证请求（客户端发送）
+    Auth { token: String },
+    /// 令牌续期请求（客户端发送）
+    TokenRefresh { token: String },
+    /// 令牌续期成功
+    TokenRefreshed { expires_at: i64 },
+    /// 会话被终止（账号停用/删除），随后关闭连接
+    SessionRevoked { reason: String },
+    /// 补发未送达通知请求（客户端发送）
+    Replay { since: Option<i64> },
+    /// 补发完成
+    ReplayComplete { count: usize, has_more: bool },
+    /// 批量通知（客户端声明支持批量时，短时间内的多条通知合并发送）
+    Notifications { payloads: Vec<NotificationPayload> },
+    /// 班级私信
+    DirectMessage { payload: DirectMessage },
+    /// 表情回应变化
+    ReactionUpdated { payload: ReactionUpdate },
+}
+
+/// 连接选项（由连接 URL 参数协商）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WsOptions {
+    /// 认证后补发该时间之后未送达的通知
+    pub replay_since: Option<i64>,
+    /// 客户端支持 `notifications` 批量消息
+    pub batch: bool,
+    /// 下行消息压缩方式
+    pub compression: WsCompression,
 }
 
 /// 通知载荷
@@ -90,16 +195,41 @@ impl From<Notification> for NotificationPayload {
     }
 }
 
+/// 触发的连接上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionLimit {
+    /// 全局并发连接上限
+    Global,
+    /// 单个用户的并发连接上限
+    PerUser,
+}
+
+impl ConnectionLimit {
+    fn close_reason(&self) -> CloseReason {
+        match self {
+            // 1013：服务端过载，客户端稍后重试
+            ConnectionLimit::Global => CloseReason {
+                code: CloseCode::Again,
+                description: Some("server connection limit reached".to_string()),
+            },
+            ConnectionLimit::PerUser => policy_close("too many connections"),
+        }
+    }
+}
+
 /// 连接管理器
 pub struct ConnectionManager {
     /// 用户 ID -> 广播发送器
     connections: DashMap<i64, broadcast::Sender<WsMessage>>,
+    /// 当前连接总数
+    total: AtomicUsize,
 }
 
 impl ConnectionManager {
     fn new() -> Self {
         Self {
             connections: DashMap::new(),
+            total: AtomicUsize::new(0),
         }
     }
 
@@ -108,23 +238,58 @@ impl ConnectionManager {
         &CONNECTION_MANAGER
     }
 
-    /// 注册用户连接
+    /// 注册用户连接（不检查连接上限）
     pub fn register(&self, user_id: i64) -> broadcast::Receiver<WsMessage> {
+        match self.try_register(user_id, 0, 0) {
+            Ok(rx) => rx,
+            Err(_) => unreachable!("unlimited registration cannot be rejected"),
+        }
+    }
+
+    /// 在连接上限内注册用户连接，上限为 0 表示不限制
+    pub fn try_register(
+        &self,
+        user_id: i64,
+        max_total: usize,
+        max_per_user: usize,
+    ) -> Result<broadcast::Receiver<WsMessage>, ConnectionLimit> {
+        if self
+            .total
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (max_total == 0 || n < max_total).then_some(n + 1)
+            })
+            .is_err()
+        {
+            return Err(ConnectionLimit::Global);
+        }
+
+        // 持有条目锁期间检查并订阅，同一用户的并发连接不会超过上限
         let entry = self.connections.entry(user_id).or_insert_with(|| {
             let (tx, _) = broadcast::channel(100);
             tx
         });
-        entry.subscribe()
+        if max_per_user > 0 && entry.receiver_count() >= max_per_user {
+            drop(entry);
+            self.total.fetch_sub(1, Ordering::AcqRel);
+            return Err(ConnectionLimit::PerUser);
+        }
+        Ok(entry.subscribe())
     }
 
     /// 移除用户连接
-    pub fn unregister(&self, user_id: i64) {
+    pub fn unregister(&self, user_id: i64, rx: broadcast::Receiver<WsMessage>) {
+        drop(rx);
+        let _ = self
+            .total
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1));
         // 只有当没有订阅者时才移除
-        if let Some(entry) = self.connections.get(&user_id)
-            && entry.receiver_count() == 0
-        {
-            self.connections.remove(&user_id);
-        }
+        self.connections
+            .remove_if(&user_id, |_, sender| sender.receiver_count() == 0);
+    }
+
+    /// 当前连接总数
+    pub fn connection_count(&self) -> usize {
+        self.total.load(Ordering::Acquire)
     }
 
     /// 向指定用户发送通知
@@ -165,6 +330,16 @@ impl ConnectionManager {
             .get(&user_id)
             .is_some_and(|s| s.receiver_count() > 0)
     }
+
+    /// 终止用户的全部连接
+    pub fn disconnect_user(&self, user_id: i64, reason: &str) {
+        self.send_to_user(
+            user_id,
+            WsMessage::SessionRevoked {
+                reason: reason.to_string(),
+            },
+        );
+    }
 }
 
 /// WebSocket 服务
@@ -172,41 +347,145 @@ pub struct WebSocketService;
 
 impl WebSocketService {
     /// 处理 WebSocket 连接
+    ///
+    /// `auth` 为空时（连接 URL 未携带令牌）先等待客户端发送 `auth` 消息；
+    /// `options.replay_since` 有值时认证后立即补发该时间之后未送达的通知。
     pub async fn handle_connection(
-        user_id: i64,
-        mut session: actix_ws::Session,
+        storage: Arc<dyn Storage>,
+        auth: Option<WsAuth>,
+        options: WsOptions,
+        session: actix_ws::Session,
         mut stream: actix_ws::MessageStream,
     ) {
-        info!("WebSocket connected for user: {}", user_id);
+        let config = &AppConfig::get().websocket;
+        let mut sender = WsSender::new(session, options.compression, config.compression_threshold);
+        // 批量窗口为 0 或客户端未声明支持时逐条发送
+        let batch_window = Duration::from_millis(config.batch_window_ms);
+        let batch_max = if options.batch && !batch_window.is_zero() {
+            config.batch_max_size.max(1)
+        } else {
+            1
+        };
+
+        let auth = match auth {
+            Some(auth) => auth,
+            None => match Self::await_auth(&storage, &mut sender.session, &mut stream).await {
+                Ok(auth) => auth,
+                Err(message) => {
+                    sender.send(&WsMessage::Error { message }).await;
+                    let _ = sender
+                        .session
+                        .close(Some(policy_close("unauthorized")))
+                        .await;
+                    return;
+                }
+            },
+        };
+
+        let user_id = auth.user.id;
 
         // 注册连接
-        let mut rx = ConnectionManager::get().register(user_id);
+        let mut rx = match ConnectionManager::get().try_register(
+            user_id,
+            config.max_connections,
+            config.max_connections_per_user,
+        ) {
+            Ok(rx) => rx,
+            Err(limit) => {
+                warn!(
+                    "WebSocket connection for user {} rejected: {:?} limit reached",
+                    user_id, limit
+                );
+                metrics::record_rejected(limit);
+                sender
+                    .send(&WsMessage::Error {
+                        message: "Too many connections".to_string(),
+                    })
+                    .await;
+                let _ = sender.session.close(Some(limit.close_reason())).await;
+                return;
+            }
+        };
+        metrics::record_connected();
+        info!("WebSocket connected for user: {}", user_id);
 
         // 发送连接成功消息
-        let connected_msg = WsMessage::Connected { user_id };
-        if let Ok(json) = serde_json::to_string(&connected_msg) {
-            let _ = session.text(json).await;
+        sender.send(&WsMessage::Connected { user_id }).await;
+
+        if options.replay_since.is_some()
+            && !replay_notifications(
+                &storage,
+                &mut sender,
+                user_id,
+                options.replay_since,
+                batch_max,
+            )
+            .await
+        {
+            ConnectionManager::get().unregister(user_id, rx);
+            metrics::record_disconnected(DisconnectReason::Error);
+            return;
         }
 
-        // 心跳间隔
-        let heartbeat_interval = std::time::Duration::from_secs(30);
-        let mut heartbeat = tokio::time::interval(heartbeat_interval);
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        let mut status_check = tokio::time::interval(STATUS_CHECK_INTERVAL);
+        status_check.reset();
+        let token_expiry = tokio::time::sleep_until(expiry_instant(auth.expires_at));
+        tokio::pin!(token_expiry);
+        // 等待合并发送的通知，窗口到期或数量达到上限时发送
+        let mut pending: Vec<NotificationPayload> = Vec::new();
+        let batch_flush = tokio::time::sleep(batch_window);
+        tokio::pin!(batch_flush);
+        // 空闲超时只由客户端上行消息重置，服务端心跳的回应不计入
+        let idle_timeout = Duration::from_secs(config.idle_timeout);
+        let idle = tokio::time::sleep(idle_timeout);
+        tokio::pin!(idle);
 
-        loop {
+        let reason = loop {
             tokio::select! {
                 // 处理来自客户端的消息
                 msg = stream.next() => {
+                    if matches!(msg, Some(Ok(Message::Text(_) | Message::Binary(_)))) {
+                        idle.as_mut().reset(Instant::now() + idle_timeout);
+                    }
                     match msg {
                         Some(Ok(Message::Text(text))) => {
                             if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
                                 match ws_msg {
                                     WsMessage::Ping => {
-                                        let pong = serde_json::to_string(&WsMessage::Pong)
-                                            .unwrap_or_else(|_| r#"{"type":"pong"}"#.to_string());
-                                        if session.text(pong).await.is_err() {
-                                            break;
+                                        if !sender.send(&WsMessage::Pong).await {
+                                            break DisconnectReason::Error;
+                                        }
+                                    }
+                                    WsMessage::TokenRefresh { token } => {
+                                        let reply = match auth::authenticate(&storage, &token).await {
+                                            Ok(renewed) if renewed.user.id == user_id => {
+                                                token_expiry
+                                                    .as_mut()
+                                                    .reset(expiry_instant(renewed.expires_at));
+                                                WsMessage::TokenRefreshed {
+                                                    expires_at: renewed.expires_at,
+                                                }
+                                            }
+                                            Ok(_) => WsMessage::Error {
+                                                message: "Token belongs to another user".to_string(),
+                                            },
+                                            Err(message) => WsMessage::Error { message },
+                                        };
+                                        if !sender.send(&reply).await {
+                                            break DisconnectReason::Error;
+                                        }
+                                    }
+                                    WsMessage::Replay { since } => {
+                                        if !flush_notifications(&storage, &mut sender, user_id, &mut pending).await
+                                            || !replay_notifications(&storage, &mut sender, user_id, since, batch_max).await
+                                        {
+                                            break DisconnectReason::Error;
                                         }
                                     }
+                                    WsMessage::Auth { .. } => {
+                                        debug!("Ignoring repeated auth message from user {}", user_id);
+                                    }
                                     _ => {
                                         debug!("Received message from user {}: {:?}", user_id, ws_msg);
                                     }
@@ -214,17 +493,17 @@ impl WebSocketService {
                             }
                         }
                         Some(Ok(Message::Ping(data))) => {
-                            if session.pong(&data).await.is_err() {
-                                break;
+                            if sender.session.pong(&data).await.is_err() {
+                                break DisconnectReason::Error;
                             }
                         }
                         Some(Ok(Message::Close(_))) | None => {
                             info!("WebSocket closed for user: {}", user_id);
-                            break;
+                            break DisconnectReason::Client;
                         }
                         Some(Err(e)) => {
                             warn!("WebSocket error for user {}: {:?}", user_id, e);
-                            break;
+                            break DisconnectReason::Error;
                         }
                         _ => {}
                     }
@@ -233,33 +512,240 @@ impl WebSocketService {
                 // 处理来自服务器的推送消息
                 msg = rx.recv() => {
                     match msg {
+                        Ok(WsMessage::Notification { payload }) => {
+                            if pending.is_empty() {
+                                batch_flush.as_mut().reset(Instant::now() + batch_window);
+                            }
+                            pending.push(payload);
+                            if pending.len() >= batch_max
+                                && !flush_notifications(&storage, &mut sender, user_id, &mut pending).await
+                            {
+                                break DisconnectReason::Error;
+                            }
+                        }
+                        Ok(ws_msg @ WsMessage::SessionRevoked { .. }) => {
+                            flush_notifications(&storage, &mut sender, user_id, &mut pending).await;
+                            sender.send(&ws_msg).await;
+                            break DisconnectReason::SessionRevoked;
+                        }
                         Ok(ws_msg) => {
-                            if let Ok(json) = serde_json::to_string(&ws_msg)
-                                && session.text(json).await.is_err() {
-                                    break;
-                                }
+                            if !flush_notifications(&storage, &mut sender, user_id, &mut pending).await
+                                || !sender.send(&ws_msg).await
+                            {
+                                break DisconnectReason::Error;
+                            }
                         }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
                             warn!("WebSocket for user {} lagged by {} messages", user_id, n);
+                            metrics::record_lagged(n);
                         }
                         Err(broadcast::error::RecvError::Closed) => {
-                            break;
+                            break DisconnectReason::Error;
                         }
                     }
                 }
 
+                // 合并窗口到期
+                _ = &mut batch_flush, if !pending.is_empty() => {
+                    if !flush_notifications(&storage, &mut sender, user_id, &mut pending).await {
+                        break DisconnectReason::Error;
+                    }
+                }
+
+                // 访问令牌过期且未续期
+                _ = &mut token_expiry => {
+                    sender.send(&WsMessage::Error {
+                        message: "Token expired".to_string(),
+                    })
+                    .await;
+                    break DisconnectReason::TokenExpired;
+                }
+
+                // 客户端长时间未发送消息
+                _ = &mut idle, if !idle_timeout.is_zero() => {
+                    sender.send(&WsMessage::Error {
+                        message: "Idle timeout".to_string(),
+                    })
+                    .await;
+                    break DisconnectReason::IdleTimeout;
+                }
+
+                // 定期复查账号状态
+                _ = status_check.tick() => {
+                    if let Err(reason) = auth::load_active_user(&storage, user_id).await {
+                        sender.send(&WsMessage::SessionRevoked { reason }).await;
+                        break DisconnectReason::SessionRevoked;
+                    }
+                }
+
                 // 心跳
                 _ = heartbeat.tick() => {
-                    if session.ping(b"").await.is_err() {
-                        break;
+                    if sender.session.ping(b"").await.is_err() {
+                        break DisconnectReason::Error;
                     }
                 }
             }
+        };
+
+        if let Some(close) = close_reason(reason) {
+            let _ = sender.session.close(Some(close)).await;
         }
 
         // 清理连接
-        ConnectionManager::get().unregister(user_id);
-        info!("WebSocket disconnected for user: {}", user_id);
+        ConnectionManager::get().unregister(user_id, rx);
+        metrics::record_disconnected(reason);
+        info!(
+            "WebSocket disconnected for user: {} ({})",
+            user_id,
+            reason.as_str()
+        );
+    }
+
+    /// 等待客户端发送 `auth` 消息并完成认证
+    async fn await_auth(
+        storage: &Arc<dyn Storage>,
+        session: &mut actix_ws::Session,
+        stream: &mut actix_ws::MessageStream,
+    ) -> Result<WsAuth, String> {
+        let wait = async {
+            while let Some(msg) = stream.next().await {
+                match msg {
+                    Ok(Message::Text(text)) => {
+                        return match serde_json::from_str::<WsMessage>(&text) {
+                            Ok(WsMessage::Auth { token }) => {
+                                auth::authenticate(storage, &token).await
+                            }
+                            _ => Err("Authentication required".to_string()),
+                        };
+                    }
+                    Ok(Message::Ping(data)) => {
+                        let _ = session.pong(&data).await;
+                    }
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    _ => {}
+                }
+            }
+            Err("Connection closed before authentication".to_string())
+        };
+
+        tokio::time::timeout(AUTH_TIMEOUT, wait)
+            .await
+            .unwrap_or_else(|_| Err("Authentication timeout".to_string()))
+    }
+}
+
+/// 按时间顺序补发未送达的通知（支持批量时按 `batch_max` 分组发送），返回连接是否仍可用
+async fn replay_notifications(
+    storage: &Arc<dyn Storage>,
+    sender: &mut WsSender,
+    user_id: i64,
+    since: Option<i64>,
+    batch_max: usize,
+) -> bool {
+    let notifications = match storage
+        .list_undelivered_notifications(user_id, since, REPLAY_LIMIT + 1)
+        .await
+    {
+        Ok(list) => list,
+        Err(e) => {
+            warn!(
+                "Failed to load undelivered notifications for user {}: {}",
+                user_id, e
+            );
+            return sender
+                .send(&WsMessage::Error {
+                    message: "Failed to replay notifications".to_string(),
+                })
+                .await;
+        }
+    };
+
+    let has_more = notifications.len() as u64 > REPLAY_LIMIT;
+    let payloads: Vec<NotificationPayload> = notifications
+        .into_iter()
+        .take(REPLAY_LIMIT as usize)
+        .map(NotificationPayload::from)
+        .collect();
+    let mut count = 0;
+    for chunk in payloads.chunks(batch_max) {
+        let mut batch = chunk.to_vec();
+        if !flush_notifications(storage, sender, user_id, &mut batch).await {
+            return false;
+        }
+        count += chunk.len();
+    }
+
+    sender
+        .send(&WsMessage::ReplayComplete { count, has_more })
+        .await
+}
+
+/// 发送待推送的通知（单条用 `notification`，多条合并为 `notifications`），
+/// 发送成功后标记已送达并清空，返回连接是否仍可用
+async fn flush_notifications(
+    storage: &Arc<dyn Storage>,
+    sender: &mut WsSender,
+    user_id: i64,
+    pending: &mut Vec<NotificationPayload>,
+) -> bool {
+    let ids: Vec<i64> = pending.iter().map(|p| p.id).collect();
+    let message = match pending.len() {
+        0 => return true,
+        1 => WsMessage::Notification {
+            payload: pending.remove(0),
+        },
+        _ => WsMessage::Notifications {
+            payloads: std::mem::take(pending),
+        },
+    };
+    if !sender.send(&message).await {
+        return false;
+    }
+    mark_delivered(storage.clone(), user_id, ids);
+    true
+}
+
+/// 异步标记通知已送达（失败只记录日志）
+fn mark_delivered(storage: Arc<dyn Storage>, user_id: i64, notification_ids: Vec<i64>) {
+    if notification_ids.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(e) = storage
+            .mark_notifications_delivered(user_id, &notification_ids)
+            .await
+        {
+            warn!(
+                "Failed to mark notifications delivered for user {}: {}",
+                user_id, e
+            );
+        }
+    });
+}
+
+/// 将令牌过期时间戳转换为定时器时刻
+fn expiry_instant(expires_at: i64) -> Instant {
+    let remaining = expires_at - chrono::Utc::now().timestamp();
+    Instant::now() + Duration::from_secs(remaining.max(0) as u64)
+}
+
+/// 服务端主动断开时发送的关闭帧，客户端断开或连接出错时不发送
+fn close_reason(reason: DisconnectReason) -> Option<CloseReason> {
+    match reason {
+        DisconnectReason::IdleTimeout => Some(CloseReason {
+            code: CloseCode::Away,
+            description: Some("idle timeout".to_string()),
+        }),
+        DisconnectReason::TokenExpired => Some(policy_close("token expired")),
+        DisconnectReason::SessionRevoked => Some(policy_close("session revoked")),
+        DisconnectReason::Client | DisconnectReason::Error => None,
+    }
+}
+
+fn policy_close(description: &str) -> CloseReason {
+    CloseReason {
+        code: CloseCode::Policy,
+        description: Some(description.to_string()),
     }
 }
 
@@ -277,6 +763,24 @@ pub fn push_notification_to_users(user_ids: &[i64], notification: Notification)
     manager.send_to_users(user_ids, message);
 }
 
+/// 辅助函数：向在线用户推送私信，用户不在线时返回 false
+pub fn push_message_to_user(user_id: i64, message: DirectMessage) -> bool {
+    let manager = ConnectionManager::get();
+    manager.is_online(user_id)
+        && manager.send_to_user(user_id, WsMessage::DirectMessage { payload: message })
+}
+
+/// 辅助函数：向多个用户推送表情回应变化
+pub fn push_reaction_update(user_ids: &[i64], update: ReactionUpdate) {
+    ConnectionManager::get()
+        .send_to_users(user_ids, WsMessage::ReactionUpdated { payload: update });
+}
+
+/// 辅助函数：终止用户的全部连接（账号停用或删除时调用）
+pub fn disconnect_user(user_id: i64, reason: &str) {
+    ConnectionManager::get().disconnect_user(user_id, reason);
+}
+
 /// 辅助函数：检查用户是否在线
 pub fn is_user_online(user_id: i64) -> bool {
     ConnectionManager::get().is_online(user_id)
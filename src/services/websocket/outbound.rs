@@ -0,0 +1,209 @@
+//! WebSocket 下行发送：可选压缩与发送量统计
+//!
+//! actix-ws 的帧编码不支持 RSV1 标志位，无法实现协议层的 permessage-deflate，
+//! 因此压缩在应用层协商：连接 URL 携带 `compression=deflate` 时，
+//! 序列化后超过阈值的消息以二进制帧发送原始 DEFLATE 数据（与 permessage-deflate 同一算法），
+//! 浏览器可用 `DecompressionStream("deflate-raw")` 解压；未超过阈值的消息仍以文本帧发送。
+
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::WsMessage;
+use crate::models::system::responses::WsMetricsSnapshot;
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+
+/// 全局发送统计
+static METRICS: WsMetrics = WsMetrics::new();
+
+/// 下行消息压缩方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WsCompression {
+    /// 不压缩
+    #[default]
+    None,
+    /// 原始 DEFLATE（二进制帧）
+    Deflate,
+}
+
+impl WsCompression {
+    /// 解析客户端声明的压缩方式，无法识别时不压缩
+    pub fn negotiate(requested: Option<&str>) -> Self {
+        match requested {
+            Some(value) if value.eq_ignore_ascii_case("deflate") => WsCompression::Deflate,
+            _ => WsCompression::None,
+        }
+    }
+}
+
+struct WsMetrics {
+    frames_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_uncompressed: AtomicU64,
+    compressed_frames: AtomicU64,
+    batched_frames: AtomicU64,
+    batched_notifications: AtomicU64,
+}
+
+impl WsMetrics {
+    const fn new() -> Self {
+        Self {
+            frames_sent: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            bytes_uncompressed: AtomicU64::new(0),
+            compressed_frames: AtomicU64::new(0),
+            batched_frames: AtomicU64::new(0),
+            batched_notifications: AtomicU64::new(0),
+        }
+    }
+}
+
+/// 获取发送统计
+pub fn ws_metrics() -> WsMetricsSnapshot {
+    WsMetricsSnapshot {
+        frames_sent: METRICS.frames_sent.load(Ordering::Relaxed),
+        bytes_sent: METRICS.bytes_sent.load(Ordering::Relaxed),
+        bytes_uncompressed: METRICS.bytes_uncompressed.load(Ordering::Relaxed),
+        compressed_frames: METRICS.compressed_frames.load(Ordering::Relaxed),
+        batched_frames: METRICS.batched_frames.load(Ordering::Relaxed),
+        batched_notifications: METRICS.batched_notifications.load(Ordering::Relaxed),
+    }
+}
+
+/// 编码后的一帧
+#[derive(Debug)]
+pub enum EncodedFrame {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl EncodedFrame {
+    fn len(&self) -> usize {
+        match self {
+            EncodedFrame::Text(text) => text.len(),
+            EncodedFrame::Binary(bytes) => bytes.len(),
+        }
+    }
+}
+
+/// 序列化消息，超过阈值且协商了压缩时压缩为二进制帧；同时返回压缩前的字节数
+pub fn encode_message(
+    message: &WsMessage,
+    compression: WsCompression,
+    threshold: usize,
+) -> Option<(EncodedFrame, usize)> {
+    let json = serde_json::to_string(message).ok()?;
+    let raw_len = json.len();
+    if compression == WsCompression::Deflate
+        && raw_len >= threshold
+        && let Some(compressed) = deflate(json.as_bytes())
+        && compressed.len() < raw_len
+    {
+        return Some((EncodedFrame::Binary(compressed), raw_len));
+    }
+    Some((EncodedFrame::Text(json), raw_len))
+}
+
+fn deflate(data: &[u8]) -> Option<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(data).ok()?;
+    encoder.finish().ok()
+}
+
+/// 带压缩协商与发送统计的会话
+pub struct WsSender {
+    pub session: actix_ws::Session,
+    compression: WsCompression,
+    threshold: usize,
+}
+
+impl WsSender {
+    pub fn new(session: actix_ws::Session, compression: WsCompression, threshold: usize) -> Self {
+        Self {
+            session,
+            compression,
+            threshold,
+        }
+    }
+
+    /// 发送消息，返回是否发送成功（序列化失败视为成功以保持连接）
+    pub async fn send(&mut self, message: &WsMessage) -> bool {
+        let Some((frame, raw_len)) = encode_message(message, self.compression, self.threshold)
+        else {
+            return true;
+        };
+
+        let wire_len = frame.len();
+        let compressed = matches!(frame, EncodedFrame::Binary(_));
+        let sent = match frame {
+            EncodedFrame::Text(text) => self.session.text(text).await.is_ok(),
+            EncodedFrame::Binary(bytes) => self.session.binary(bytes).await.is_ok(),
+        };
+        if !sent {
+            return false;
+        }
+
+        METRICS.frames_sent.fetch_add(1, Ordering::Relaxed);
+        METRICS
+            .bytes_sent
+            .fetch_add(wire_len as u64, Ordering::Relaxed);
+        METRICS
+            .bytes_uncompressed
+            .fetch_add(raw_len as u64, Ordering::Relaxed);
+        if compressed {
+            METRICS.compressed_frames.fetch_add(1, Ordering::Relaxed);
+        }
+        if let WsMessage::Notifications { payloads } = message {
+            METRICS.batched_frames.fetch_add(1, Ordering::Relaxed);
+            METRICS
+                .batched_notifications
+                .fetch_add(payloads.len() as u64, Ordering::Relaxed);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::DeflateDecoder;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_message_compresses_large_payloads() {
+        let message = WsMessage::Error {
+            message: "作业已发布".repeat(200),
+        };
+        let json = serde_json::to_string(&message).unwrap();
+
+        let (frame, raw_len) = encode_message(&message, WsCompression::Deflate, 1024).unwrap();
+        assert_eq!(raw_len, json.len());
+        let EncodedFrame::Binary(bytes) = frame else {
+            panic!("expected compressed frame");
+        };
+        assert!(bytes.len() < json.len());
+        let mut decoded = String::new();
+        DeflateDecoder::new(bytes.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, json);
+
+        // 未协商压缩或低于阈值时保持文本帧
+        let (frame, _) = encode_message(&message, WsCompression::None, 1024).unwrap();
+        assert!(matches!(frame, EncodedFrame::Text(_)));
+        let (frame, _) = encode_message(&WsMessage::Pong, WsCompression::Deflate, 1024).unwrap();
+        assert!(matches!(frame, EncodedFrame::Text(_)));
+    }
+
+    #[test]
+    fn test_negotiate_compression() {
+        assert_eq!(
+            WsCompression::negotiate(Some("deflate")),
+            WsCompression::Deflate
+        );
+        assert_eq!(WsCompression::negotiate(Some("gzip")), WsCompression::None);
+        assert_eq!(WsCompression::negotiate(None), WsCompression::None);
+    }
+}
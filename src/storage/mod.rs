@@ -1,50 +1,129 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::models::{
+    analytics::responses::{ClassComparisonItem, TeacherGradingLatencyItem},
+    auth::responses::StudentDashboard,
+    certificates::{
+        entities::{Certificate, CertificateProgress, CertificateSettings},
+        requests::CertificateSettingsInput,
+    },
     class_users::{
-        entities::{ClassUser, ClassUserRole},
+        entities::{
+            ClassRoleLabels, ClassUser, ClassUserBatchAction, ClassUserBatchOutcome, ClassUserRole,
+        },
         requests::{ClassUserQuery, UpdateClassUserRequest},
         responses::ClassUserListResponse,
     },
     classes::{
-        entities::Class,
-        requests::{ClassListQuery, CreateClassRequest, UpdateClassRequest},
-        responses::ClassListResponse,
+        entities::{
+            ActivityEvent, ActivityEventType, Class, ClassApiToken, ClassApiTokenLog,
+            ClassErasureCounts, ClassImChannel, ClassLeaderboardSettings, ClassScheduledExport,
+            ClassStatsDaily, ClassWorkloadDay, HomeworkCalibration, ImDelivery, ImEvent,
+            LeaderboardMetric, LeaderboardStanding, NewActivityEvent, NewClassApiToken,
+            NewImDelivery,
+        },
+        requests::{
+            ClassListQuery, CreateClassImChannelRequest, CreateClassRequest,
+            CreateClassScheduledExportRequest, UpdateClassImChannelRequest, UpdateClassRequest,
+            UpdateClassScheduledExportRequest,
+        },
+        responses::{ClassListResponse, StudentActivity},
+    },
+    files::{
+        entities::{
+            ClassRetentionOverride, File, FileReference, FileRetentionPolicy, QuarantinedFile,
+            RetentionCandidate, StorageTier,
+        },
+        requests::{QuarantinedFileInput, UpdateClassRetentionRequest},
     },
-    files::entities::File,
     grades::{
-        entities::Grade,
-        requests::{CreateGradeRequest, GradeListQuery, UpdateGradeRequest},
+        entities::{Grade, GradeDraft, GradeStatistics},
+        requests::{CreateGradeRequest, GradeListQuery, SaveGradeDraftRequest, UpdateGradeRequest},
         responses::GradeListResponse,
     },
     homeworks::{
-        entities::Homework,
+        entities::{
+            AttachmentKind, ExamAccessLog, Homework, HomeworkExemption, HomeworkFeedback,
+            HomeworkLink, HomeworkLinkOpen, HomeworkPart, HomeworkPrerequisite, HomeworkShareLink,
+            HomeworkSolution, MissingGradePolicy, SolutionRevealPolicy,
+        },
         requests::{
-            AllHomeworksQuery, CreateHomeworkRequest, HomeworkListQuery, UpdateHomeworkRequest,
+            AllHomeworksQuery, CreateHomeworkRequest, ExamAccessLogInput, HomeworkAttachmentInput,
+            HomeworkLinkInput, HomeworkListQuery, HomeworkPartInput, HomeworkPrerequisiteInput,
+            UpdateHomeworkRequest,
+        },
+        responses::{AllHomeworksResponse, DeadlineConflictItem, HomeworkListResponse},
+    },
+    messages::{entities::Message, responses::ConversationListResponse},
+    moderation::{
+        entities::{
+            ContentReport, ContentReportAction, ContentReportStatus, ModerationFlag,
+            ModerationFlagStatus, ReportContentType, ReportedContent,
         },
-        responses::{AllHomeworksResponse, HomeworkListResponse},
+        requests::{ContentReportInput, ModerationFlagInput},
     },
     notifications::{
-        entities::Notification,
-        requests::{CreateNotificationRequest, NotificationListQuery},
+        entities::{Notification, NotificationTemplate, NotificationType},
+        requests::{
+            CreateNotificationRequest, NotificationListQuery, NotificationRecipientFilter,
+            UpsertNotificationTemplateRequest,
+        },
         responses::NotificationListResponse,
     },
+    organizations::{
+        entities::{Organization, TenantScope},
+        requests::{CreateOrganizationRequest, OrganizationListQuery, UpdateOrganizationRequest},
+        responses::OrganizationListResponse,
+    },
+    outbox::entities::OutboxMessage,
+    reactions::entities::{ReactionSummary, ReactionTargetType},
+    rubrics::{
+        entities::{HomeworkRubric, Rubric, RubricCriterion, RubricVersion},
+        requests::{RubricInput, RubricScope, RubricUpdate},
+    },
     submissions::{
-        entities::Submission,
-        requests::{CreateSubmissionRequest, SubmissionListQuery},
+        entities::{
+            GradingSlaBreach, ResubmissionRequest, Submission, SubmissionSelfAssessment,
+            SubmissionWorkflow,
+        },
+        requests::{
+            ArchivedSubmissionInput, CreateSubmissionRequest, SubmissionListQuery,
+            SubmissionReferenceSnapshot, SubmissionSummaryFilter,
+        },
         responses::{
             SubmissionListResponse, SubmissionResponse, SubmissionSummaryResponse,
             UserSubmissionHistoryItem,
         },
     },
     system::{
-        entities::SystemSetting, requests::SettingAuditQuery, responses::SettingAuditListResponse,
+        entities::{SettingChange, SettingsUpdateOutcome, SystemSetting, WalCheckpointMode},
+        requests::{GenerateDevDataRequest, SettingAuditQuery},
+        responses::{
+            DatabaseStatus, DevDataSummary, IntegrityCheckResult, SettingAuditListResponse,
+            WalCheckpointResponse,
+        },
+    },
+    usage::{
+        entities::{UsageCounter, UsageMetric},
+        requests::UsageReportListQuery,
+        responses::UsageReportListResponse,
     },
     users::{
-        entities::{User, UserRole, UserStatus},
-        requests::{CreateUserRequest, UpdateUserRequest, UserListQuery},
-        responses::{UserListResponse, UserStatsResponse},
+        entities::{
+            AdminPermission, BulkUserAction, GoalStanding, LoginHistory, RoleRequest, StudentGoal,
+            User, UserRole, UserStatus,
+        },
+        requests::{
+            BulkUserFilter, CreateLoginHistory, CreateUserRequest, LoginHistoryQuery,
+            RoleRequestListQuery, UpdateUserRequest, UserListQuery,
+        },
+        responses::{
+            BulkUserItemResult, LoginHistoryListResponse, MergeUsersResponse,
+            RoleRequestListResponse, UserListResponse, UserStatsResponse,
+        },
     },
+    viewers::entities::ViewerGrant,
 };
 
 use crate::errors::Result;
@@ -59,6 +138,8 @@ pub trait Storage: Send + Sync {
 
     /// 创建用户
     async fn create_user(&self, user: CreateUserRequest) -> Result<User>;
+    /// 批量创建用户（全部成功或全部回滚），返回创建数量
+    async fn create_users_batch(&self, users: Vec<CreateUserRequest>) -> Result<usize>;
     /// 通过ID获取用户信息
     async fn get_user_by_id(&self, id: i64) -> Result<Option<User>>;
     /// 通过用户名获取用户信息
@@ -75,6 +156,13 @@ pub trait Storage: Send + Sync {
     async fn delete_user(&self, id: i64) -> Result<bool>;
     /// 更新用户最后登录时间
     async fn update_last_login(&self, id: i64) -> Result<bool>;
+    /// 记录登录历史（成功登录时与近期成功登录比较，计算新设备与可疑标记）
+    async fn record_login_history(&self, entry: CreateLoginHistory) -> Result<LoginHistory>;
+    /// 分页查询登录历史（按时间倒序）
+    async fn list_login_history(
+        &self,
+        query: LoginHistoryQuery,
+    ) -> Result<LoginHistoryListResponse>;
     /// 统计用户数量
     async fn count_users(&self) -> Result<u64>;
     /// 批量检查用户名是否已存在
@@ -90,31 +178,199 @@ pub trait Storage: Send + Sync {
         role: Option<UserRole>,
         status: Option<UserStatus>,
         search: Option<&str>,
+        tenant: TenantScope,
     ) -> Result<Vec<User>>;
     /// 获取用户综合统计（合并学生和教师视角）
     async fn get_user_stats(&self, user_id: i64, role: UserRole) -> Result<UserStatsResponse>;
+    /// 获取学生首页仪表盘数据（即将截止作业、最近评分、未读通知数、各班完成进度）
+    async fn get_student_dashboard(
+        &self,
+        user_id: i64,
+        upcoming_until: i64,
+        recent_grade_limit: u64,
+    ) -> Result<StudentDashboard>;
+    /// 获取用户被授予的管理权限
+    async fn get_user_admin_permissions(&self, user_id: i64) -> Result<Vec<AdminPermission>>;
+    /// 设置用户的管理权限（整体替换），返回去重后的权限列表
+    async fn set_user_admin_permissions(
+        &self,
+        user_id: i64,
+        permissions: &[AdminPermission],
+        granted_by: i64,
+    ) -> Result<Vec<AdminPermission>>;
+    /// 按 ID 批量获取用户
+    async fn list_users_by_ids(&self, ids: &[i64]) -> Result<Vec<User>>;
+    /// 按条件筛选批量操作的目标用户
+    async fn list_users_for_bulk(
+        &self,
+        filter: BulkUserFilter,
+        tenant: TenantScope,
+        limit: u64,
+    ) -> Result<Vec<User>>;
+    /// 在同一事务中对多个用户执行批量操作并写入审计日志，返回逐项结果
+    async fn bulk_apply_user_action(
+        &self,
+        action: BulkUserAction,
+        role: Option<UserRole>,
+        user_ids: &[i64],
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<Vec<BulkUserItemResult>>;
+    /// 在同一事务中把源账号的班级成员关系、提交、评分、文件和通知移到目标账号，
+    /// 停用源账号并为两个账号写入审计日志
+    async fn merge_users(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<MergeUsersResponse>;
+
+    // ============================================
+    // 角色申请方法
+    // ============================================
+
+    /// 创建角色申请
+    async fn create_role_request(
+        &self,
+        user_id: i64,
+        org_id: Option<i64>,
+        requested_role: UserRole,
+        reason: String,
+        attachment_file_id: Option<i64>,
+    ) -> Result<RoleRequest>;
+    /// 通过 ID 获取角色申请
+    async fn get_role_request_by_id(&self, id: i64) -> Result<Option<RoleRequest>>;
+    /// 获取用户待审核的角色申请
+    async fn get_pending_role_request(&self, user_id: i64) -> Result<Option<RoleRequest>>;
+    /// 分页列出角色申请
+    async fn list_role_requests(
+        &self,
+        query: RoleRequestListQuery,
+    ) -> Result<RoleRequestListResponse>;
+    /// 审核角色申请：在同一事务中更新申请状态、用户角色（通过时）并写入审计日志，
+    /// 申请不存在或已被审核时返回 None
+    async fn review_role_request(
+        &self,
+        id: i64,
+        approve: bool,
+        comment: Option<String>,
+        reviewer_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<Option<RoleRequest>>;
+
+    // ============================================
+    // 学习目标方法
+    // ============================================
+
+    /// 设置学习目标（已存在时更新目标值）
+    async fn upsert_student_goal(
+        &self,
+        user_id: i64,
+        class_id: i64,
+        target_percent: f64,
+    ) -> Result<StudentGoal>;
+    /// 删除学习目标，目标不存在时返回 false
+    async fn delete_student_goal(&self, user_id: i64, class_id: i64) -> Result<bool>;
+    /// 汇总用户各学习目标所在班级的成绩（按班级 ID 升序）
+    async fn list_goal_standings(&self, user_id: i64) -> Result<Vec<GoalStanding>>;
+
+    // ============================================
+    // 家长/导师查看授权方法
+    // ============================================
+
+    /// 创建查看邀请
+    async fn create_viewer_invitation(
+        &self,
+        student_id: i64,
+        token: &str,
+        note: Option<String>,
+        expires_at: i64,
+    ) -> Result<ViewerGrant>;
+    /// 通过令牌获取查看授权
+    async fn get_viewer_grant_by_token(&self, token: &str) -> Result<Option<ViewerGrant>>;
+    /// 接受查看邀请，邀请已被接受、已撤销或已过期时返回 None
+    async fn accept_viewer_invitation(
+        &self,
+        token: &str,
+        viewer_id: i64,
+    ) -> Result<Option<ViewerGrant>>;
+    /// 列出学生发出的全部查看授权（按创建时间倒序）
+    async fn list_viewer_grants_by_student(&self, student_id: i64) -> Result<Vec<ViewerGrant>>;
+    /// 列出家长/导师生效中的查看授权
+    async fn list_active_viewer_grants(&self, viewer_id: i64) -> Result<Vec<ViewerGrant>>;
+    /// 家长/导师是否有查看该学生的有效授权
+    async fn has_viewer_grant(&self, viewer_id: i64, student_id: i64) -> Result<bool>;
+    /// 撤销查看授权，不存在或已撤销时返回 false
+    async fn revoke_viewer_grant(&self, student_id: i64, grant_id: i64) -> Result<bool>;
 
     // ============================================
     // 文件管理方法
     // ============================================
 
-    /// 上传文件
+    /// 上传文件（`code_language` 为上传时识别的代码语言）
     async fn upload_file(
         &self,
         original_name: &str,
         stored_name: &str,
         file_size: &i64,
         file_type: &str,
+        code_language: Option<&str>,
         user_id: i64,
     ) -> Result<File>;
     /// 通过唯一 token 获取文件信息
     async fn get_file_by_token(&self, token: &str) -> Result<Option<File>>;
     /// 通过 ID 获取文件信息
     async fn get_file_by_id(&self, id: i64) -> Result<Option<File>>;
+    /// 按 ID 批量获取文件信息（不存在的 ID 忽略）
+    async fn list_files_by_ids(&self, ids: &[i64]) -> Result<Vec<File>>;
     /// 增加文件引用计数
     async fn increment_file_citation(&self, file_id: i64) -> Result<bool>;
     /// 减少文件引用计数
     async fn decrement_file_citation(&self, file_id: i64) -> Result<bool>;
+    /// 获取班级文件保留策略覆盖
+    async fn get_class_retention_override(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<ClassRetentionOverride>>;
+    /// 创建或更新班级文件保留策略覆盖
+    async fn upsert_class_retention_override(
+        &self,
+        class_id: i64,
+        req: UpdateClassRetentionRequest,
+        updated_by: i64,
+    ) -> Result<ClassRetentionOverride>;
+    /// 查找在 `horizon` 时间戳前按保留策略到期的文件，可限定为某班级的附件
+    async fn find_retention_candidates(
+        &self,
+        policy: FileRetentionPolicy,
+        horizon: i64,
+        class_id: Option<i64>,
+    ) -> Result<Vec<RetentionCandidate>>;
+    /// 删除文件记录及其附件关联，返回被删除的文件
+    async fn purge_files(&self, file_ids: &[i64]) -> Result<Vec<File>>;
+    /// 查找可迁移到冷存储的附件（仅被归档时间不晚于 `archived_before` 的班级引用）
+    async fn find_tiering_candidates(&self, archived_before: i64) -> Result<Vec<File>>;
+    /// 更新文件存储层级，文件不存在时返回 false
+    async fn set_file_storage_tier(&self, file_id: i64, tier: StorageTier) -> Result<bool>;
+    /// 列出用户上传的全部文件（最新的在前）
+    async fn list_user_files(&self, user_id: i64) -> Result<Vec<File>>;
+    /// 反查文件被引用的位置（作业、提交、头像等），按文件 ID 分组
+    async fn list_file_references(
+        &self,
+        files: &[File],
+    ) -> Result<HashMap<i64, Vec<FileReference>>>;
+    /// 记录未通过内容校验的隔离文件
+    async fn create_quarantined_file(&self, input: QuarantinedFileInput)
+    -> Result<QuarantinedFile>;
+    /// 列出待审核的隔离文件（最新的在前）
+    async fn list_quarantined_files(&self) -> Result<Vec<QuarantinedFile>>;
+    /// 获取隔离文件
+    async fn get_quarantined_file(&self, id: i64) -> Result<Option<QuarantinedFile>>;
+    /// 审核通过：创建正式文件记录并移除隔离记录，不存在时返回 None
+    async fn release_quarantined_file(&self, id: i64) -> Result<Option<File>>;
+    /// 删除隔离记录，返回被删除的记录
+    async fn delete_quarantined_file(&self, id: i64) -> Result<Option<QuarantinedFile>>;
 
     // ============================================
     // 班级管理方法
@@ -139,6 +395,279 @@ pub trait Storage: Send + Sync {
     ) -> Result<Option<Class>>;
     /// 删除班级
     async fn delete_class(&self, class_id: i64) -> Result<bool>;
+    /// 统计班级学生活跃度（最后登录、近期提交、迟交、未读通知），`since` 为近期提交的起始时间戳
+    async fn get_class_activity(&self, class_id: i64, since: i64) -> Result<Vec<StudentActivity>>;
+    /// 生成指定日期的班级统计快照，返回生成条数
+    async fn generate_class_stats_snapshots(&self, stat_date: chrono::NaiveDate) -> Result<i64>;
+    /// 查询班级在 [from, to] 日期区间（YYYY-MM-DD）内的统计快照，按日期升序
+    async fn list_class_stats_daily(
+        &self,
+        class_id: i64,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<ClassStatsDaily>>;
+    /// 统计班级学生从 `from` 起 `days` 天内每天截止的作业数（跨学生所在的全部班级聚合）
+    async fn get_class_workload(
+        &self,
+        class_id: i64,
+        from: chrono::NaiveDate,
+        days: i64,
+    ) -> Result<Vec<ClassWorkloadDay>>;
+    /// 汇总班级每个作业的预计用时、难度与学生反馈（按截止时间升序）
+    async fn get_class_workload_calibration(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<HomeworkCalibration>>;
+    /// 获取班级排行榜设置
+    async fn get_leaderboard_settings(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<ClassLeaderboardSettings>>;
+    /// 创建或更新班级排行榜设置
+    async fn upsert_leaderboard_settings(
+        &self,
+        class_id: i64,
+        enabled: bool,
+        top_n: i32,
+        metric: LeaderboardMetric,
+        user_id: i64,
+    ) -> Result<ClassLeaderboardSettings>;
+    /// 查询学生是否选择在排行榜中署名
+    async fn get_leaderboard_opt_in(&self, class_id: i64, user_id: i64) -> Result<bool>;
+    /// 设置学生是否在排行榜中署名
+    async fn set_leaderboard_opt_in(&self, class_id: i64, user_id: i64, opt_in: bool)
+    -> Result<()>;
+    /// 汇总班级学生的已公布成绩（截至 `now` 已截止的作业），用于计算排行榜
+    async fn list_leaderboard_standings(
+        &self,
+        class_id: i64,
+        now: i64,
+    ) -> Result<Vec<LeaderboardStanding>>;
+    /// 统计学生在班级内可擦除的数据行数（预览，不修改数据）
+    async fn preview_class_erasure(
+        &self,
+        class_id: i64,
+        user_id: i64,
+    ) -> Result<ClassErasureCounts>;
+    /// 擦除学生在班级内的提交、评分与私信等数据并写入审计日志，
+    /// 数据与预览时的 `expected` 不一致时不做修改并返回 `None`
+    async fn erase_class_student_data(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        expected: &ClassErasureCounts,
+        operator_id: i64,
+        ip_address: Option<String>,
+        reason: Option<String>,
+    ) -> Result<Option<ClassErasureCounts>>;
+    /// 写入班级动态
+    async fn create_activity_event(&self, event: NewActivityEvent) -> Result<ActivityEvent>;
+    /// 通过 ID 获取班级动态
+    async fn get_activity_event_by_id(&self, event_id: i64) -> Result<Option<ActivityEvent>>;
+    /// 查询班级动态（按 ID 倒序，`before_id` 为游标），返回 (动态, 是否还有更早的动态)
+    async fn list_class_activity_events(
+        &self,
+        class_id: i64,
+        event_type: Option<ActivityEventType>,
+        before_id: Option<i64>,
+        limit: u64,
+    ) -> Result<(Vec<ActivityEvent>, bool)>;
+    /// 班级对比分析：统计范围内各班级在 [from, to] 时间戳区间布置的作业的提交率、迟交率、平均得分率与批改耗时
+    async fn get_class_comparison(
+        &self,
+        tenant: TenantScope,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ClassComparisonItem>>;
+    /// 教师批改耗时统计：[from, to] 区间内的评分数与平均批改耗时，以及 `cutoff` 之前提交的超时未批改数
+    async fn get_teacher_grading_latency(
+        &self,
+        tenant: TenantScope,
+        from: i64,
+        to: i64,
+        cutoff: i64,
+    ) -> Result<Vec<TeacherGradingLatencyItem>>;
+
+    // ============================================
+    // 班级 IM 通知渠道管理方法
+    // ============================================
+
+    /// 创建班级 IM 通知渠道
+    async fn create_class_im_channel(
+        &self,
+        class_id: i64,
+        req: CreateClassImChannelRequest,
+        created_by: i64,
+    ) -> Result<ClassImChannel>;
+    /// 列出班级的 IM 通知渠道
+    async fn list_class_im_channels(&self, class_id: i64) -> Result<Vec<ClassImChannel>>;
+    /// 获取班级的某个 IM 通知渠道
+    async fn get_class_im_channel(
+        &self,
+        class_id: i64,
+        channel_id: i64,
+    ) -> Result<Option<ClassImChannel>>;
+    /// 更新 IM 通知渠道
+    async fn update_class_im_channel(
+        &self,
+        class_id: i64,
+        channel_id: i64,
+        req: UpdateClassImChannelRequest,
+    ) -> Result<Option<ClassImChannel>>;
+    /// 删除 IM 通知渠道
+    async fn delete_class_im_channel(&self, class_id: i64, channel_id: i64) -> Result<bool>;
+    /// 获取 IM 通知渠道（不校验班级）
+    async fn get_im_channel_by_id(&self, channel_id: i64) -> Result<Option<ClassImChannel>>;
+    /// 列出班级中订阅了指定事件的已启用渠道
+    async fn list_im_channels_for_event(
+        &self,
+        class_id: i64,
+        event: ImEvent,
+    ) -> Result<Vec<ClassImChannel>>;
+    /// 写入 IM 投递队列（重复的渠道+事件+关联实体忽略），返回新增数量
+    async fn enqueue_im_deliveries(&self, deliveries: Vec<NewImDelivery>) -> Result<u64>;
+    /// 列出到期待投递的消息
+    async fn list_due_im_deliveries(&self, now: i64, limit: u64) -> Result<Vec<ImDelivery>>;
+    /// 记录投递成功
+    async fn mark_im_delivery_delivered(&self, delivery: &ImDelivery) -> Result<()>;
+    /// 记录投递失败，`retry_at` 为空表示不再重试
+    async fn mark_im_delivery_failed(
+        &self,
+        delivery: &ImDelivery,
+        error: &str,
+        retry_at: Option<i64>,
+    ) -> Result<()>;
+
+    // ============================================
+    // 班级定时导出方法
+    // ============================================
+
+    /// 创建班级定时导出
+    async fn create_class_scheduled_export(
+        &self,
+        class_id: i64,
+        req: CreateClassScheduledExportRequest,
+        created_by: i64,
+    ) -> Result<ClassScheduledExport>;
+    /// 列出班级的定时导出
+    async fn list_class_scheduled_exports(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<ClassScheduledExport>>;
+    /// 获取班级的某个定时导出
+    async fn get_class_scheduled_export(
+        &self,
+        class_id: i64,
+        export_id: i64,
+    ) -> Result<Option<ClassScheduledExport>>;
+    /// 更新定时导出（修改执行计划或重新启用时重新计算下一次执行时间）
+    async fn update_class_scheduled_export(
+        &self,
+        class_id: i64,
+        export_id: i64,
+        req: UpdateClassScheduledExportRequest,
+    ) -> Result<Option<ClassScheduledExport>>;
+    /// 删除定时导出
+    async fn delete_class_scheduled_export(&self, class_id: i64, export_id: i64) -> Result<bool>;
+    /// 领取到期的定时导出，并把下一次执行时间推进到 `now` 之后
+    async fn claim_due_scheduled_exports(
+        &self,
+        now: i64,
+        limit: u64,
+    ) -> Result<Vec<ClassScheduledExport>>;
+    /// 记录一次执行结果，连续失败达到 `max_failures` 后停用
+    async fn finish_scheduled_export_run(
+        &self,
+        export_id: i64,
+        error: Option<String>,
+        max_failures: i32,
+    ) -> Result<Option<ClassScheduledExport>>;
+
+    // ============================================
+    // 班级 API 令牌方法
+    // ============================================
+
+    /// 创建班级 API 令牌
+    async fn create_class_api_token(&self, token: NewClassApiToken) -> Result<ClassApiToken>;
+    /// 列出班级的 API 令牌（含已撤销）
+    async fn list_class_api_tokens(&self, class_id: i64) -> Result<Vec<ClassApiToken>>;
+    /// 获取班级的某个 API 令牌
+    async fn get_class_api_token(
+        &self,
+        class_id: i64,
+        token_id: i64,
+    ) -> Result<Option<ClassApiToken>>;
+    /// 通过令牌摘要查找 API 令牌
+    async fn get_class_api_token_by_hash(&self, token_hash: &str) -> Result<Option<ClassApiToken>>;
+    /// 撤销 API 令牌（不存在或已撤销返回 false）
+    async fn revoke_class_api_token(&self, class_id: i64, token_id: i64) -> Result<bool>;
+    /// 更新令牌最近使用时间
+    async fn touch_class_api_token(&self, token_id: i64, now: i64) -> Result<()>;
+    /// 记录令牌发起的写请求
+    async fn create_class_api_token_log(
+        &self,
+        token_id: i64,
+        method: &str,
+        path: &str,
+        status: i32,
+        ip_address: Option<String>,
+    ) -> Result<()>;
+    /// 列出令牌最近的写请求记录
+    async fn list_class_api_token_logs(
+        &self,
+        token_id: i64,
+        limit: u64,
+    ) -> Result<Vec<ClassApiTokenLog>>;
+
+    // ============================================
+    // 事务发件箱方法
+    // ============================================
+
+    /// 列出到期待投递的发件箱事件
+    async fn list_due_outbox_events(&self, now: i64, limit: u64) -> Result<Vec<OutboxMessage>>;
+    /// 记录发件箱事件投递成功
+    async fn mark_outbox_event_delivered(&self, message: &OutboxMessage) -> Result<()>;
+    /// 记录发件箱事件投递失败，`retry_at` 为空表示不再重试
+    async fn mark_outbox_event_failed(
+        &self,
+        message: &OutboxMessage,
+        error: &str,
+        retry_at: Option<i64>,
+    ) -> Result<()>;
+
+    // ============================================
+    // 结业证书管理方法
+    // ============================================
+
+    /// 获取班级结业证书设置
+    async fn get_certificate_settings(&self, class_id: i64) -> Result<Option<CertificateSettings>>;
+    /// 创建或更新班级结业证书设置
+    async fn upsert_certificate_settings(
+        &self,
+        class_id: i64,
+        input: CertificateSettingsInput,
+        user_id: i64,
+    ) -> Result<CertificateSettings>;
+    /// 计算学生在班级中的作业完成进度
+    async fn get_certificate_progress(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        min_score_percent: f64,
+    ) -> Result<CertificateProgress>;
+    /// 颁发结业证书（已存在时返回原证书），第二个返回值表示是否为新颁发
+    async fn issue_certificate(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        verification_code: &str,
+        average_percent: f64,
+        homework_count: i32,
+    ) -> Result<(Certificate, bool)>;
+    /// 获取学生在班级中的证书
+    async fn get_certificate(&self, class_id: i64, user_id: i64) -> Result<Option<Certificate>>;
+    /// 通过验证码获取证书
+    async fn get_certificate_by_code(&self, code: &str) -> Result<Option<Certificate>>;
 
     // ============================================
     // 班级成员管理方法
@@ -146,12 +675,15 @@ pub trait Storage: Send + Sync {
 
     /// 获取班级成员数量
     async fn count_class_members(&self, class_id: i64) -> Result<i64>;
-    /// 学生加入班级
+    /// 批量统计用户加入的班级数量（未加入任何班级的用户不在结果中）
+    async fn count_classes_by_user_ids(&self, user_ids: &[i64]) -> Result<HashMap<i64, i64>>;
+    /// 学生加入班级，`profile_name` 为班级内姓名
     async fn join_class(
         &self,
         user_id: i64,
         class_id: i64,
         role: ClassUserRole,
+        profile_name: Option<String>,
     ) -> Result<ClassUser>;
     /// 学生离开/踢出班级
     async fn leave_class(&self, user_id: i64, class_id: i64) -> Result<bool>;
@@ -162,6 +694,16 @@ pub trait Storage: Send + Sync {
         user_id: i64,
         update_data: UpdateClassUserRequest,
     ) -> Result<Option<ClassUser>>;
+    /// 在同一事务中批量修改成员角色或移出班级，写入一条汇总审计日志
+    async fn batch_update_class_users(
+        &self,
+        class_id: i64,
+        action: ClassUserBatchAction,
+        role: Option<ClassUserRole>,
+        user_ids: &[i64],
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<ClassUserBatchOutcome>;
     /// 列出班级用户
     async fn list_class_users_with_pagination(
         &self,
@@ -174,6 +716,15 @@ pub trait Storage: Send + Sync {
         user_id: i64,
         query: ClassListQuery,
     ) -> Result<ClassListResponse>;
+    /// 获取班级角色名称配置，未配置时返回 None
+    async fn get_class_role_labels(&self, class_id: i64) -> Result<Option<ClassRoleLabels>>;
+    /// 保存班级角色名称配置（空配置即恢复内置名称），并清除成员已失效的角色名称
+    async fn save_class_role_labels(
+        &self,
+        class_id: i64,
+        labels: ClassRoleLabels,
+        updated_by: i64,
+    ) -> Result<()>;
     /// 获取用户在班级中的信息
     async fn get_class_user_by_user_id_and_class_id(
         &self,
@@ -194,14 +745,23 @@ pub trait Storage: Send + Sync {
     // 作业管理方法
     // ============================================
 
-    /// 创建作业
+    /// 创建作业（`notification_job_id` 为跟踪班级学生通知投递的后台任务）
     async fn create_homework(
         &self,
         created_by: i64,
         req: CreateHomeworkRequest,
+        notification_job_id: Option<&str>,
     ) -> Result<Homework>;
+    /// 在一个事务中批量创建作业（任一失败则全部回滚）
+    async fn batch_create_homeworks(
+        &self,
+        created_by: i64,
+        requests: Vec<CreateHomeworkRequest>,
+    ) -> Result<Vec<Homework>>;
     /// 通过 ID 获取作业
     async fn get_homework_by_id(&self, homework_id: i64) -> Result<Option<Homework>>;
+    /// 列出截止时间落在 `(from, to]` 区间内的作业
+    async fn list_homeworks_due_between(&self, from: i64, to: i64) -> Result<Vec<Homework>>;
     /// 列出作业
     /// - current_user_id: 当前用户 ID，如果提供则查询该用户对这些作业的提交状态
     async fn list_homeworks_with_pagination(
@@ -220,11 +780,21 @@ pub trait Storage: Send + Sync {
     async fn delete_homework(&self, homework_id: i64) -> Result<bool>;
     /// 获取作业附件 ID 列表
     async fn get_homework_file_ids(&self, homework_id: i64) -> Result<Vec<i64>>;
+    /// 获取作业附件及其用途
+    async fn get_homework_attachments(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<(i64, AttachmentKind)>>;
+    /// 查询文件作为作业附件的关联（作业 ID 及用途）
+    async fn list_homework_attachments_by_file(
+        &self,
+        file_id: i64,
+    ) -> Result<Vec<(i64, AttachmentKind)>>;
     /// 设置作业附件（通过 download_token，带所有权校验）
     async fn set_homework_files(
         &self,
         homework_id: i64,
-        tokens: Vec<String>,
+        attachments: Vec<HomeworkAttachmentInput>,
         user_id: i64,
     ) -> Result<()>;
     /// 获取学生作业统计（跨所有加入的班级）
@@ -241,15 +811,219 @@ pub trait Storage: Send + Sync {
         query: AllHomeworksQuery,
     ) -> Result<AllHomeworksResponse>;
 
+    // ============================================
+    // 作业豁免管理方法
+    // ============================================
+
+    /// 创建作业豁免
+    async fn create_homework_exemption(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+        reason: Option<String>,
+        created_by: i64,
+    ) -> Result<HomeworkExemption>;
+    /// 获取某学生某作业的豁免记录
+    async fn get_homework_exemption(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<Option<HomeworkExemption>>;
+    /// 删除作业豁免
+    async fn delete_homework_exemption(&self, homework_id: i64, user_id: i64) -> Result<bool>;
+    /// 列出作业的所有豁免记录
+    async fn list_homework_exemptions(&self, homework_id: i64) -> Result<Vec<HomeworkExemption>>;
+    /// 获取作业被豁免的学生 ID 列表
+    async fn get_exempted_user_ids(&self, homework_id: i64) -> Result<Vec<i64>>;
+
+    // ============================================
+    // 考试访问日志方法
+    // ============================================
+
+    /// 记录考试访问事件（IP 变化时自动追加 ip_change 事件）
+    async fn record_exam_access(&self, input: ExamAccessLogInput) -> Result<()>;
+    /// 列出作业的考试访问日志，可限定学生
+    async fn list_exam_access_logs(
+        &self,
+        homework_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Vec<ExamAccessLog>>;
+
+    // ============================================
+    // 作业分享链接管理方法
+    // ============================================
+
+    /// 创建作业分享链接
+    async fn create_homework_share_link(
+        &self,
+        homework_id: i64,
+        token: &str,
+        created_by: i64,
+        expires_at: i64,
+    ) -> Result<HomeworkShareLink>;
+    /// 列出作业的所有分享链接
+    async fn list_homework_share_links(&self, homework_id: i64) -> Result<Vec<HomeworkShareLink>>;
+    /// 通过令牌获取分享链接
+    async fn get_homework_share_link_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<HomeworkShareLink>>;
+    /// 撤销分享链接
+    async fn revoke_homework_share_link(&self, homework_id: i64, link_id: i64) -> Result<bool>;
+
+    // ============================================
+    // 作业参考答案管理方法
+    // ============================================
+
+    /// 获取作业参考答案
+    async fn get_homework_solution(&self, homework_id: i64) -> Result<Option<HomeworkSolution>>;
+    /// 创建或更新作业参考答案
+    async fn upsert_homework_solution(
+        &self,
+        homework_id: i64,
+        content: &str,
+        reveal_policy: SolutionRevealPolicy,
+        user_id: i64,
+    ) -> Result<HomeworkSolution>;
+    /// 删除作业参考答案
+    async fn delete_homework_solution(&self, homework_id: i64) -> Result<bool>;
+    /// 手动发布参考答案
+    async fn publish_homework_solution(&self, homework_id: i64) -> Result<bool>;
+    /// 标记参考答案已公开（仅首次返回 true）
+    async fn mark_homework_solution_revealed(&self, homework_id: i64) -> Result<bool>;
+    /// 列出尚未公开、等待自动公开的参考答案
+    async fn list_pending_homework_solutions(&self) -> Result<Vec<HomeworkSolution>>;
+
+    // ============================================
+    // 缺交零分策略管理方法
+    // ============================================
+
+    /// 获取作业的缺交零分策略
+    async fn get_missing_grade_policy(
+        &self,
+        homework_id: i64,
+    ) -> Result<Option<MissingGradePolicy>>;
+    /// 创建或更新缺交零分策略
+    async fn upsert_missing_grade_policy(
+        &self,
+        homework_id: i64,
+        cutoff_at: i64,
+        user_id: i64,
+    ) -> Result<MissingGradePolicy>;
+    /// 删除缺交零分策略
+    async fn delete_missing_grade_policy(&self, homework_id: i64) -> Result<bool>;
+    /// 列出已到最终截止时间、尚未执行的策略
+    async fn list_due_missing_grade_policies(&self, now: i64) -> Result<Vec<MissingGradePolicy>>;
+    /// 执行缺交零分策略，返回被记零分的学生 ID（已执行时为空）
+    async fn apply_missing_grade_policy(&self, homework_id: i64) -> Result<Vec<i64>>;
+    /// 撤销学生的自动零分
+    async fn revert_auto_assigned_grade(&self, homework_id: i64, user_id: i64) -> Result<bool>;
+
+    // ============================================
+    // 作业分题管理方法
+    // ============================================
+
+    /// 列出作业的分题（按顺序）
+    async fn list_homework_parts(&self, homework_id: i64) -> Result<Vec<HomeworkPart>>;
+    /// 通过 ID 获取分题
+    async fn get_homework_part(&self, part_id: i64) -> Result<Option<HomeworkPart>>;
+    /// 列出已有提交的分题 ID
+    async fn list_submitted_homework_part_ids(&self, homework_id: i64) -> Result<Vec<i64>>;
+    /// 整体替换作业分题（同步作业满分）
+    async fn replace_homework_parts(
+        &self,
+        homework_id: i64,
+        parts: Vec<HomeworkPartInput>,
+    ) -> Result<Vec<HomeworkPart>>;
+
+    // ============================================
+    // 作业外部资源方法
+    // ============================================
+
+    /// 列出作业的外部资源（按顺序）
+    async fn list_homework_links(&self, homework_id: i64) -> Result<Vec<HomeworkLink>>;
+    /// 通过 ID 获取外部资源
+    async fn get_homework_link(&self, link_id: i64) -> Result<Option<HomeworkLink>>;
+    /// 整体替换作业的外部资源（删除的链接连同打开记录一起删除）
+    async fn replace_homework_links(
+        &self,
+        homework_id: i64,
+        links: Vec<HomeworkLinkInput>,
+        created_by: i64,
+    ) -> Result<Vec<HomeworkLink>>;
+    /// 记录学生打开外部资源（同一学生重复打开时累加次数）
+    async fn record_homework_link_open(&self, link_id: i64, user_id: i64) -> Result<()>;
+    /// 列出作业全部外部资源的打开记录
+    async fn list_homework_link_opens(&self, homework_id: i64) -> Result<Vec<HomeworkLinkOpen>>;
+
+    // ============================================
+    // 作业用时与难度反馈方法
+    // ============================================
+
+    /// 获取学生对作业的用时与难度反馈
+    async fn get_homework_feedback(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<Option<HomeworkFeedback>>;
+    /// 写入学生的用时与难度反馈（已有反馈时覆盖）
+    async fn upsert_homework_feedback(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+        actual_minutes: i32,
+        perceived_difficulty: i32,
+    ) -> Result<HomeworkFeedback>;
+
+    // ============================================
+    // 作业前置条件方法
+    // ============================================
+
+    /// 列出作业的前置条件
+    async fn list_homework_prerequisites(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<HomeworkPrerequisite>>;
+    /// 列出班级内全部前置关系 (homework_id, prerequisite_id)
+    async fn list_class_prerequisite_edges(&self, class_id: i64) -> Result<Vec<(i64, i64)>>;
+    /// 整体替换作业的前置条件
+    async fn replace_homework_prerequisites(
+        &self,
+        homework_id: i64,
+        prerequisites: Vec<HomeworkPrerequisiteInput>,
+    ) -> Result<Vec<HomeworkPrerequisite>>;
+    /// 查询学生在给定作业中尚未满足的前置作业（homework_id -> 前置作业 ID 列表）
+    async fn list_unmet_prerequisites(
+        &self,
+        user_id: i64,
+        homework_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<i64>>>;
+    /// 查询截止时间在 `[from, to]` 内、与班级学生相关的作业（本班及学生所在的其他班级）
+    async fn find_deadline_conflicts(
+        &self,
+        class_id: i64,
+        from: i64,
+        to: i64,
+        exclude_homework_id: Option<i64>,
+    ) -> Result<Vec<DeadlineConflictItem>>;
+
     // ============================================
     // 提交管理方法
     // ============================================
 
-    /// 创建提交（自动计算版本号）
+    /// 创建提交（自动计算版本号），`references` 为外部引用及其元数据快照，
+    /// 作业未单独设置宽限期时按 `default_grace_minutes` 判定迟交
     async fn create_submission(
         &self,
         creator_id: i64,
         req: CreateSubmissionRequest,
+        references: Vec<SubmissionReferenceSnapshot>,
+        default_grace_minutes: i64,
+    ) -> Result<Submission>;
+    /// 写入从班级导出包恢复的提交（保留原版本号、状态与提交时间，可附带评分）
+    async fn restore_archived_submission(
+        &self,
+        input: ArchivedSubmissionInput,
     ) -> Result<Submission>;
     /// 通过 ID 获取提交
     async fn get_submission_by_id(&self, submission_id: i64) -> Result<Option<Submission>>;
@@ -264,6 +1038,8 @@ pub trait Storage: Send + Sync {
         homework_id: i64,
         creator_id: i64,
     ) -> Result<Option<Submission>>;
+    /// 作业是否已全部批改（每位学生的最新提交都已评分）
+    async fn is_homework_fully_graded(&self, homework_id: i64) -> Result<bool>;
     /// 获取学生某作业的提交历史（包含评分和附件）
     async fn list_user_submissions(
         &self,
@@ -279,6 +1055,33 @@ pub trait Storage: Send + Sync {
     async fn delete_submission(&self, submission_id: i64) -> Result<bool>;
     /// 更新提交状态
     async fn update_submission_status(&self, submission_id: i64, status: &str) -> Result<bool>;
+    /// 将提交从 `from` 状态转换为自定义状态 `to`（当前状态已变化时返回 false）
+    async fn transition_submission_status(
+        &self,
+        submission_id: i64,
+        from: &str,
+        to: &str,
+    ) -> Result<bool>;
+    /// 获取班级自定义的提交状态工作流，未自定义时返回 None
+    async fn get_class_submission_workflow(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<SubmissionWorkflow>>;
+    /// 保存班级提交状态工作流（默认工作流即恢复默认），并清除已删除的自定义状态
+    async fn save_class_submission_workflow(
+        &self,
+        class_id: i64,
+        workflow: SubmissionWorkflow,
+        updated_by: i64,
+    ) -> Result<()>;
+    /// 退回提交并通知学生（提交不是最新版本或已被退回时返回 None）
+    async fn request_resubmission(
+        &self,
+        submission_id: i64,
+        requested_by: i64,
+        comment: String,
+        reopen_until: i64,
+    ) -> Result<Option<ResubmissionRequest>>;
     /// 获取提交附件 ID 列表
     async fn get_submission_file_ids(&self, submission_id: i64) -> Result<Vec<i64>>;
     /// 设置提交附件（通过 download_token，带所有权校验）
@@ -290,15 +1093,23 @@ pub trait Storage: Send + Sync {
     ) -> Result<()>;
     /// 获取作业提交概览（按学生聚合）
     /// - `include_grades`: 是否包含成绩信息（课代表不可见成绩）
-    /// - `graded`: 筛选是否已批改，true=已批改，false=待批改，None=全部
+    /// - `filter`: 按是否已批改、是否超过批改时限筛选
     async fn get_submission_summary(
         &self,
         homework_id: i64,
         page: i64,
         size: i64,
         include_grades: bool,
-        graded: Option<bool>,
+        filter: SubmissionSummaryFilter,
     ) -> Result<SubmissionSummaryResponse>;
+    /// 领取 `cutoff` 之前提交、仍未批改且尚未提醒的最新提交，并标记为已提醒
+    async fn claim_grading_sla_breaches(&self, cutoff: i64) -> Result<Vec<GradingSlaBreach>>;
+    /// 按作业创建者统计 `cutoff` 之前提交且仍未批改的最新提交数
+    async fn count_grading_overdue_by_teacher(
+        &self,
+        tenant: TenantScope,
+        cutoff: i64,
+    ) -> Result<HashMap<i64, i64>>;
     /// 获取某学生某作业的所有提交版本（教师视角，包含评分和附件）
     /// - `include_grades`: 是否包含成绩信息（课代表不可见成绩）
     async fn list_user_submissions_for_teacher(
@@ -307,6 +1118,11 @@ pub trait Storage: Send + Sync {
         user_id: i64,
         include_grades: bool,
     ) -> Result<Vec<UserSubmissionHistoryItem>>;
+    /// 列出作业各提交的自评（按提交 ID 升序）
+    async fn list_homework_self_assessments(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<SubmissionSelfAssessment>>;
 
     // ============================================
     // 评分管理方法
@@ -327,6 +1143,49 @@ pub trait Storage: Send + Sync {
     /// 列出评分（分页）
     async fn list_grades_with_pagination(&self, query: GradeListQuery)
     -> Result<GradeListResponse>;
+    /// 替换评分评语中的 @提及，返回本次新增的被提及用户 ID
+    async fn replace_grade_mentions(
+        &self,
+        grade_id: i64,
+        mentioned_by: i64,
+        user_ids: &[i64],
+    ) -> Result<Vec<i64>>;
+    /// 作业（或分题）成绩分布统计，每位学生取最新提交的评分；尚无评分时返回 None
+    async fn get_grade_statistics(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+    ) -> Result<Option<GradeStatistics>>;
+    /// 某分数在作业（或分题）成绩中的百分位；尚无评分时返回 None
+    async fn get_grade_percentile(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+        score: f64,
+    ) -> Result<Option<f64>>;
+    /// 列出作业（或分题）中每位学生最新提交的得分
+    async fn list_latest_grade_scores(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+    ) -> Result<Vec<f64>>;
+    /// 保存评分人对提交的评分草稿（已存在时整体覆盖）
+    async fn upsert_grade_draft(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+        req: SaveGradeDraftRequest,
+    ) -> Result<GradeDraft>;
+    /// 获取评分人对提交的评分草稿
+    async fn get_grade_draft(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+    ) -> Result<Option<GradeDraft>>;
+    /// 删除评分草稿，返回是否存在
+    async fn delete_grade_draft(&self, submission_id: i64, grader_id: i64) -> Result<bool>;
+    /// 删除在给定时间之前最后保存的评分草稿，返回删除数量
+    async fn purge_expired_grade_drafts(&self, updated_before: i64) -> Result<u64>;
 
     // ============================================
     // 通知管理方法
@@ -355,6 +1214,80 @@ pub trait Storage: Send + Sync {
     async fn mark_all_notifications_as_read(&self, user_id: i64) -> Result<i64>;
     /// 删除通知
     async fn delete_notification(&self, notification_id: i64) -> Result<bool>;
+    /// 列出用户尚未通过 WebSocket 送达的通知（按创建时间升序）
+    async fn list_undelivered_notifications(
+        &self,
+        user_id: i64,
+        since: Option<i64>,
+        limit: u64,
+    ) -> Result<Vec<Notification>>;
+    /// 标记通知已通过 WebSocket 送达
+    async fn mark_notifications_delivered(
+        &self,
+        user_id: i64,
+        notification_ids: &[i64],
+    ) -> Result<i64>;
+    /// 按筛选条件解析群发通知的接收人（班级成员 user_id）
+    async fn resolve_notification_recipients(
+        &self,
+        class_id: i64,
+        filter: &NotificationRecipientFilter,
+    ) -> Result<Vec<i64>>;
+
+    // ============================================
+    // 表情回应方法
+    // ============================================
+
+    /// 添加表情回应，已回应过同一表情时返回 false
+    async fn add_reaction(
+        &self,
+        user_id: i64,
+        target_type: ReactionTargetType,
+        target_id: i64,
+        emoji: &str,
+    ) -> Result<bool>;
+    /// 取消表情回应，未回应过时返回 false
+    async fn remove_reaction(
+        &self,
+        user_id: i64,
+        target_type: ReactionTargetType,
+        target_id: i64,
+        emoji: &str,
+    ) -> Result<bool>;
+    /// 批量汇总目标的表情回应（`mine` 为 `viewer_id` 回应过的表情），没有回应的目标不在结果中
+    async fn get_reaction_summaries(
+        &self,
+        target_type: ReactionTargetType,
+        target_ids: &[i64],
+        viewer_id: i64,
+    ) -> Result<HashMap<i64, ReactionSummary>>;
+
+    // ============================================
+    // 通知模板管理方法
+    // ============================================
+
+    /// 列出所有自定义通知模板
+    async fn list_notification_templates(&self) -> Result<Vec<NotificationTemplate>>;
+    /// 获取指定类型与语言的通知模板
+    async fn get_notification_template(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+    ) -> Result<Option<NotificationTemplate>>;
+    /// 创建或更新通知模板
+    async fn upsert_notification_template(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+        req: UpsertNotificationTemplateRequest,
+        user_id: i64,
+    ) -> Result<NotificationTemplate>;
+    /// 删除通知模板（恢复内置文案）
+    async fn delete_notification_template(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+    ) -> Result<bool>;
 
     // ============================================
     // 系统设置管理方法
@@ -372,18 +1305,247 @@ pub trait Storage: Send + Sync {
         user_id: i64,
         ip_address: Option<String>,
     ) -> Result<SystemSetting>;
-    /// 批量更新设置
+    /// 批量更新设置（事务内执行，可指定期望版本）
     async fn batch_update_settings(
         &self,
-        updates: Vec<(String, String)>,
+        changes: Vec<SettingChange>,
+        expected_version: Option<i64>,
         user_id: i64,
         ip_address: Option<String>,
-    ) -> Result<Vec<SystemSetting>>;
+    ) -> Result<SettingsUpdateOutcome>;
+    /// 获取当前配置版本
+    async fn get_settings_version(&self) -> Result<i64>;
     /// 获取审计日志
     async fn list_setting_audits(
         &self,
         query: SettingAuditQuery,
     ) -> Result<SettingAuditListResponse>;
+
+    // ============================================
+    // 组织（租户）管理方法
+    // ============================================
+
+    /// 创建组织
+    async fn create_organization(&self, req: CreateOrganizationRequest) -> Result<Organization>;
+    /// 通过ID获取组织
+    async fn get_organization_by_id(&self, id: i64) -> Result<Option<Organization>>;
+    /// 通过标识获取组织
+    async fn get_organization_by_slug(&self, slug: &str) -> Result<Option<Organization>>;
+    /// 列出组织
+    async fn list_organizations_with_pagination(
+        &self,
+        query: OrganizationListQuery,
+    ) -> Result<OrganizationListResponse>;
+    /// 更新组织
+    async fn update_organization(
+        &self,
+        id: i64,
+        update: UpdateOrganizationRequest,
+    ) -> Result<Option<Organization>>;
+    /// 删除组织
+    async fn delete_organization(&self, id: i64) -> Result<bool>;
+    /// 统计组织内用户数量
+    async fn count_organization_users(&self, org_id: i64) -> Result<i64>;
+
+    // ============================================
+    // 用量统计方法
+    // ============================================
+
+    /// 累加组织在某周期的用量计数
+    async fn increment_usage_counter(
+        &self,
+        org_id: i64,
+        period: &str,
+        metric: UsageMetric,
+        delta: i64,
+    ) -> Result<()>;
+    /// 获取组织在某周期的用量计数
+    async fn list_usage_counters(&self, org_id: i64, period: &str) -> Result<Vec<UsageCounter>>;
+    /// 生成指定日期的用量报表，返回生成条数
+    async fn generate_usage_reports(&self, report_date: chrono::NaiveDate) -> Result<i64>;
+    /// 列出用量报表
+    async fn list_usage_reports(
+        &self,
+        query: UsageReportListQuery,
+    ) -> Result<UsageReportListResponse>;
+
+    // ============================================
+    // 内容审核方法
+    // ============================================
+
+    /// 记录内容审核标记
+    async fn create_moderation_flag(&self, input: ModerationFlagInput) -> Result<ModerationFlag>;
+    /// 列出班级的审核标记（新记录在前），可按复核状态筛选
+    async fn list_moderation_flags(
+        &self,
+        class_id: i64,
+        status: Option<ModerationFlagStatus>,
+    ) -> Result<Vec<ModerationFlag>>;
+    /// 获取审核标记
+    async fn get_moderation_flag(&self, flag_id: i64) -> Result<Option<ModerationFlag>>;
+    /// 更新审核标记的复核结果
+    async fn review_moderation_flag(
+        &self,
+        flag_id: i64,
+        status: ModerationFlagStatus,
+        reviewer_id: i64,
+    ) -> Result<Option<ModerationFlag>>;
+    /// 查询被举报内容的班级、作者与可见范围，内容不存在时返回 None
+    async fn get_reported_content(
+        &self,
+        content_type: ReportContentType,
+        content_id: i64,
+    ) -> Result<Option<ReportedContent>>;
+    /// 用户是否已举报过该内容且尚未处理
+    async fn has_pending_content_report(
+        &self,
+        reporter_id: i64,
+        content_type: ReportContentType,
+        content_id: i64,
+    ) -> Result<bool>;
+    /// 创建举报
+    async fn create_content_report(&self, input: ContentReportInput) -> Result<ContentReport>;
+    /// 列出班级的举报（新记录在前），可按处理状态筛选
+    async fn list_content_reports(
+        &self,
+        class_id: i64,
+        status: Option<ContentReportStatus>,
+    ) -> Result<Vec<ContentReport>>;
+    /// 获取举报
+    async fn get_content_report(&self, report_id: i64) -> Result<Option<ContentReport>>;
+    /// 处理举报（同一内容的待处理举报一并处理，`delete_content` 时删除内容），
+    /// 举报不存在或已处理时返回 None
+    async fn resolve_content_report(
+        &self,
+        report_id: i64,
+        action: ContentReportAction,
+        note: Option<String>,
+        resolver_id: i64,
+    ) -> Result<Option<ContentReport>>;
+
+    // ============================================
+    // 评分量规方法
+    // ============================================
+
+    /// 创建量规（同时写入版本 1）
+    async fn create_rubric(&self, input: RubricInput) -> Result<Rubric>;
+    /// 列出用户自己的量规与租户范围内其他教师公开的量规
+    async fn list_rubrics(
+        &self,
+        user_id: i64,
+        tenant: TenantScope,
+        scope: Option<RubricScope>,
+    ) -> Result<Vec<Rubric>>;
+    /// 获取量规
+    async fn get_rubric(&self, rubric_id: i64) -> Result<Option<Rubric>>;
+    /// 通过分享码获取量规
+    async fn get_rubric_by_share_code(&self, share_code: &str) -> Result<Option<Rubric>>;
+    /// 更新量规，评分维度变化时生成新版本；量规不存在时返回 None
+    async fn update_rubric(
+        &self,
+        rubric_id: i64,
+        update: RubricUpdate,
+        editor_id: i64,
+    ) -> Result<Option<Rubric>>;
+    /// 设置或清除量规分享码
+    async fn set_rubric_share_code(
+        &self,
+        rubric_id: i64,
+        share_code: Option<String>,
+    ) -> Result<Option<Rubric>>;
+    /// 删除量规及其版本，作业上的量规快照保留
+    async fn delete_rubric(&self, rubric_id: i64) -> Result<bool>;
+    /// 列出量规的全部版本（新版本在前）
+    async fn list_rubric_versions(&self, rubric_id: i64) -> Result<Vec<RubricVersion>>;
+    /// 获取量规的指定版本
+    async fn get_rubric_version(
+        &self,
+        rubric_id: i64,
+        version: i32,
+    ) -> Result<Option<RubricVersion>>;
+    /// 获取作业的量规快照
+    async fn get_homework_rubric(&self, homework_id: i64) -> Result<Option<HomeworkRubric>>;
+    /// 保存作业的量规快照（覆盖已有快照）
+    async fn set_homework_rubric(
+        &self,
+        homework_id: i64,
+        rubric_id: i64,
+        rubric_version: i32,
+        title: String,
+        criteria: &[RubricCriterion],
+        attached_by: i64,
+    ) -> Result<HomeworkRubric>;
+    /// 移除作业的量规快照
+    async fn delete_homework_rubric(&self, homework_id: i64) -> Result<bool>;
+
+    // ============================================
+    // 私信方法
+    // ============================================
+
+    /// 保存私信
+    async fn create_message(
+        &self,
+        class_id: i64,
+        sender_id: i64,
+        recipient_id: i64,
+        content: &str,
+    ) -> Result<Message>;
+    /// 列出用户的会话（最近有消息的在前），可按班级筛选
+    async fn list_conversations(
+        &self,
+        user_id: i64,
+        class_id: Option<i64>,
+    ) -> Result<ConversationListResponse>;
+    /// 查询会话消息（新消息在前），返回消息与是否还有更早的消息
+    async fn list_conversation_messages(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        peer_id: i64,
+        before_id: Option<i64>,
+        limit: u64,
+    ) -> Result<(Vec<Message>, bool)>;
+    /// 将对方在会话中发来的未读消息标记为已读，返回标记条数
+    async fn mark_conversation_read(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        peer_id: i64,
+    ) -> Result<i64>;
+    /// 统计用户未读私信数
+    async fn count_unread_messages(&self, user_id: i64) -> Result<i64>;
+
+    // ============================================
+    // 数据一致性检查方法
+    // ============================================
+
+    /// 执行数据一致性检查，`repair` 为真时在同一事务中修复可安全修复的问题
+    async fn run_integrity_checks(&self, repair: bool) -> Result<Vec<IntegrityCheckResult>>;
+
+    // ============================================
+    // 数据库维护方法（SQLite）
+    // ============================================
+
+    /// 查询数据库类型与文件大小（文件相关字段仅 SQLite 提供）
+    async fn get_database_status(&self) -> Result<DatabaseStatus>;
+    /// 执行 WAL 检查点，非 SQLite 数据库返回 None
+    async fn checkpoint_wal(
+        &self,
+        mode: WalCheckpointMode,
+    ) -> Result<Option<WalCheckpointResponse>>;
+    /// 在线备份数据库到 `target`（VACUUM INTO，目标文件须不存在），非 SQLite 数据库返回 false
+    async fn backup_database(&self, target: &std::path::Path) -> Result<bool>;
+
+    // ============================================
+    // 开发工具方法
+    // ============================================
+
+    /// 批量生成压测数据（班级 × 学生 × 作业，含提交与评分），仅供开发环境使用
+    async fn generate_dev_data(
+        &self,
+        password_hash: &str,
+        req: &GenerateDevDataRequest,
+    ) -> Result<DevDataSummary>;
 }
 
 pub async fn create_storage() -> Result<Arc<dyn Storage>> {
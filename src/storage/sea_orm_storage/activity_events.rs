@@ -0,0 +1,116 @@
+//! 班级动态存储操作
+//!
+//! 发布作业、发布评分与成员加入通过 [`insert_activity_event`] 在业务事务中写入，
+//! 班级公告由服务层在发送成功后写入。
+
+use super::SeaOrmStorage;
+use crate::entity::activity_events::{ActiveModel, Column, Entity as ActivityEvents};
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::entities::{ActivityEvent, ActivityEventType, NewActivityEvent};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+
+/// 动态标题最大长度（字符）
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// 同一作业的评分动态合并窗口（秒）：逐份批改时窗口内只保留第一条
+const GRADE_RELEASE_WINDOW_SECS: i64 = 3600;
+
+/// 在给定连接（通常为业务事务）中写入班级动态
+pub(super) async fn insert_activity_event<C: ConnectionTrait>(
+    conn: &C,
+    event: NewActivityEvent,
+) -> Result<ActivityEvent> {
+    let model = ActiveModel {
+        class_id: Set(event.class_id),
+        event_type: Set(event.event_type.to_string()),
+        actor_id: Set(event.actor_id),
+        reference_id: Set(event.reference_id),
+        title: Set(event.title.chars().take(MAX_TITLE_LENGTH).collect()),
+        created_at: Set(chrono::Utc::now().timestamp()),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await
+    .map_err(|e| HWSystemError::database_operation(format!("写入班级动态失败: {e}")))?;
+
+    Ok(model.into_activity_event())
+}
+
+/// 写入作业评分动态；同一作业在合并窗口内已有评分动态时跳过
+pub(super) async fn insert_grade_released_event<C: ConnectionTrait>(
+    conn: &C,
+    event: NewActivityEvent,
+) -> Result<()> {
+    let since = chrono::Utc::now().timestamp() - GRADE_RELEASE_WINDOW_SECS;
+    let recent = ActivityEvents::find()
+        .filter(Column::EventType.eq(ActivityEventType::GradeReleased.to_string()))
+        .filter(Column::ReferenceId.eq(event.reference_id))
+        .filter(Column::CreatedAt.gte(since))
+        .count(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询评分动态失败: {e}")))?;
+    if recent == 0 {
+        insert_activity_event(conn, event).await?;
+    }
+    Ok(())
+}
+
+impl SeaOrmStorage {
+    /// 写入班级动态
+    pub async fn create_activity_event_impl(
+        &self,
+        event: NewActivityEvent,
+    ) -> Result<ActivityEvent> {
+        insert_activity_event(&self.db, event).await
+    }
+
+    /// 通过 ID 获取班级动态
+    pub async fn get_activity_event_by_id_impl(
+        &self,
+        event_id: i64,
+    ) -> Result<Option<ActivityEvent>> {
+        let result = ActivityEvents::find_by_id(event_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级动态失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_activity_event()))
+    }
+
+    /// 查询班级动态，新动态在前；多取一条用于判断是否还有更早的动态
+    pub async fn list_class_activity_events_impl(
+        &self,
+        class_id: i64,
+        event_type: Option<ActivityEventType>,
+        before_id: Option<i64>,
+        limit: u64,
+    ) -> Result<(Vec<ActivityEvent>, bool)> {
+        let mut select = ActivityEvents::find().filter(Column::ClassId.eq(class_id));
+        if let Some(event_type) = event_type {
+            select = select.filter(Column::EventType.eq(event_type.to_string()));
+        }
+        if let Some(before_id) = before_id {
+            select = select.filter(Column::Id.lt(before_id));
+        }
+
+        let mut models = select
+            .order_by_desc(Column::Id)
+            .limit(limit + 1)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级动态失败: {e}")))?;
+
+        let has_more = models.len() as u64 > limit;
+        models.truncate(limit as usize);
+        Ok((
+            models
+                .into_iter()
+                .map(|m| m.into_activity_event())
+                .collect(),
+            has_more,
+        ))
+    }
+}
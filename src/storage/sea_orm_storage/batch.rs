@@ -0,0 +1,54 @@
+//! 批量写入工具
+//!
+//! 多行 INSERT 按绑定参数上限分块执行，避免逐行往返数据库。
+
+/// 单条语句的绑定参数上限（SQLite 3.32+ 为 32766，PostgreSQL/MySQL 为 65535，取最小值）
+const MAX_BIND_PARAMS: usize = 32766;
+
+/// 单条语句的最大行数，避免生成过长的 SQL
+const MAX_ROWS_PER_INSERT: usize = 1000;
+
+/// 按列数计算每条 INSERT 语句可容纳的行数
+pub(super) fn chunk_rows(columns: usize) -> usize {
+    (MAX_BIND_PARAMS / columns.max(1)).clamp(1, MAX_ROWS_PER_INSERT)
+}
+
+/// 分块执行多行 INSERT
+///
+/// 用法：`insert_chunked!(Entity, models, &conn, "描述")`，`models` 为 ActiveModel 集合，
+/// 失败时以 `批量写入{描述}失败` 返回数据库操作错误。
+macro_rules! insert_chunked {
+    ($entity:ty, $models:expr, $conn:expr, $what:expr) => {{
+        let columns =
+            <<$entity as sea_orm::EntityTrait>::Column as sea_orm::Iterable>::iter().count();
+        let rows_per_insert = super::batch::chunk_rows(columns);
+        let mut rows = $models.into_iter().peekable();
+        while rows.peek().is_some() {
+            let chunk: Vec<_> = rows.by_ref().take(rows_per_insert).collect();
+            <$entity as sea_orm::EntityTrait>::insert_many(chunk)
+                .exec($conn)
+                .await
+                .map_err(|e| {
+                    $crate::errors::HWSystemError::database_operation(format!(
+                        "批量写入{}失败: {e}",
+                        $what
+                    ))
+                })?;
+        }
+    }};
+}
+
+pub(super) use insert_chunked;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_rows() {
+        assert_eq!(chunk_rows(8), 1000);
+        assert_eq!(chunk_rows(50), 655);
+        assert_eq!(chunk_rows(0), 1000);
+        assert_eq!(chunk_rows(usize::MAX), 1);
+    }
+}
@@ -0,0 +1,256 @@
+//! 结业证书存储操作
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::certificates::{
+    ActiveModel as CertificateActiveModel, Column as CertificateColumn, Entity as Certificates,
+};
+use crate::entity::class_certificate_settings::{ActiveModel, Entity as ClassCertificateSettings};
+use crate::entity::files::Entity as Files;
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_exemptions::{Column as ExemptionColumn, Entity as HomeworkExemptions};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::certificates::entities::{
+    Certificate, CertificateProgress, CertificateSettings,
+};
+use crate::models::certificates::requests::CertificateSettingsInput;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+impl SeaOrmStorage {
+    /// 获取班级结业证书设置
+    pub async fn get_certificate_settings_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<CertificateSettings>> {
+        let Some(model) = ClassCertificateSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询证书设置失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let file = match model.signature_file_id {
+            Some(file_id) => Files::find_by_id(file_id)
+                .one(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询签名图片失败: {e}")))?,
+            None => None,
+        };
+
+        Ok(Some(model.into_certificate_settings(file.as_ref())))
+    }
+
+    /// 创建或更新班级结业证书设置
+    pub async fn upsert_certificate_settings_impl(
+        &self,
+        class_id: i64,
+        input: CertificateSettingsInput,
+        user_id: i64,
+    ) -> Result<CertificateSettings> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = ClassCertificateSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询证书设置失败: {e}")))?;
+
+        match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.enabled = Set(input.enabled);
+                active.min_score_percent = Set(input.min_score_percent);
+                active.title = Set(input.title);
+                active.body_template = Set(input.body_template);
+                active.signer_name = Set(input.signer_name);
+                active.signature_file_id = Set(input.signature_file_id);
+                active.updated_by = Set(user_id);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    class_id: Set(class_id),
+                    enabled: Set(input.enabled),
+                    min_score_percent: Set(input.min_score_percent),
+                    title: Set(input.title),
+                    body_template: Set(input.body_template),
+                    signer_name: Set(input.signer_name),
+                    signature_file_id: Set(input.signature_file_id),
+                    updated_by: Set(user_id),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存证书设置失败: {e}")))?;
+
+        self.get_certificate_settings_impl(class_id)
+            .await?
+            .ok_or_else(|| HWSystemError::not_found("证书设置不存在"))
+    }
+
+    /// 计算学生在班级中的作业完成进度
+    ///
+    /// 每项作业取最新一次已评分的提交，得分率 = 得分 / 满分 × 100；被豁免的作业不计入。
+    pub async fn get_certificate_progress_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        min_score_percent: f64,
+    ) -> Result<CertificateProgress> {
+        let homeworks = Homeworks::find()
+            .filter(HomeworkColumn::ClassId.eq(class_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?;
+        let homework_ids: Vec<i64> = homeworks.iter().map(|h| h.id).collect();
+
+        let mut progress = CertificateProgress {
+            required_count: 0,
+            graded_count: 0,
+            passed_count: 0,
+            average_percent: 0.0,
+        };
+        if homework_ids.is_empty() {
+            return Ok(progress);
+        }
+
+        let exempted: HashSet<i64> = HomeworkExemptions::find()
+            .filter(ExemptionColumn::UserId.eq(user_id))
+            .filter(ExemptionColumn::HomeworkId.is_in(homework_ids.clone()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?
+            .into_iter()
+            .map(|e| e.homework_id)
+            .collect();
+
+        // 按版本倒序，便于取每项作业最新的已评分提交
+        let submissions = Submissions::find()
+            .filter(SubmissionColumn::CreatorId.eq(user_id))
+            .filter(SubmissionColumn::HomeworkId.is_in(homework_ids))
+            .order_by_desc(SubmissionColumn::Version)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交记录失败: {e}")))?;
+
+        let submission_ids: Vec<i64> = submissions.iter().map(|s| s.id).collect();
+        let grades: HashMap<i64, f64> = if submission_ids.is_empty() {
+            HashMap::new()
+        } else {
+            Grades::find()
+                .filter(GradeColumn::SubmissionId.is_in(submission_ids))
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+                .into_iter()
+                .map(|g| (g.submission_id, g.score))
+                .collect()
+        };
+
+        let mut latest_scores: HashMap<i64, f64> = HashMap::new();
+        for submission in &submissions {
+            if let Some(score) = grades.get(&submission.id) {
+                latest_scores
+                    .entry(submission.homework_id)
+                    .or_insert(*score);
+            }
+        }
+
+        let mut percent_sum = 0.0;
+        for homework in homeworks.iter().filter(|h| !exempted.contains(&h.id)) {
+            progress.required_count += 1;
+            let Some(score) = latest_scores.get(&homework.id) else {
+                continue;
+            };
+            let percent = if homework.max_score > 0.0 {
+                score / homework.max_score * 100.0
+            } else {
+                100.0
+            };
+            progress.graded_count += 1;
+            percent_sum += percent;
+            if percent >= min_score_percent {
+                progress.passed_count += 1;
+            }
+        }
+        if progress.graded_count > 0 {
+            progress.average_percent =
+                (percent_sum / progress.graded_count as f64 * 100.0).round() / 100.0;
+        }
+
+        Ok(progress)
+    }
+
+    /// 颁发结业证书
+    ///
+    /// 同一学生在同一班级只颁发一次，已存在时返回原证书；第二个返回值表示是否为新颁发。
+    pub async fn issue_certificate_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        verification_code: &str,
+        average_percent: f64,
+        homework_count: i32,
+    ) -> Result<(Certificate, bool)> {
+        if let Some(existing) = self.get_certificate_impl(class_id, user_id).await? {
+            return Ok((existing, false));
+        }
+
+        let inserted = CertificateActiveModel {
+            class_id: Set(class_id),
+            user_id: Set(user_id),
+            verification_code: Set(verification_code.to_string()),
+            average_percent: Set(average_percent),
+            homework_count: Set(homework_count),
+            issued_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await;
+
+        match inserted {
+            Ok(model) => Ok((model.into_certificate(), true)),
+            // 并发颁发时唯一索引冲突，返回先写入的证书
+            Err(e) => match self.get_certificate_impl(class_id, user_id).await? {
+                Some(existing) => Ok((existing, false)),
+                None => Err(HWSystemError::database_operation(format!(
+                    "颁发证书失败: {e}"
+                ))),
+            },
+        }
+    }
+
+    /// 获取学生在班级中的证书
+    pub async fn get_certificate_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+    ) -> Result<Option<Certificate>> {
+        let result = Certificates::find()
+            .filter(CertificateColumn::ClassId.eq(class_id))
+            .filter(CertificateColumn::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询证书失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_certificate()))
+    }
+
+    /// 通过验证码获取证书
+    pub async fn get_certificate_by_code_impl(&self, code: &str) -> Result<Option<Certificate>> {
+        let result = Certificates::find()
+            .filter(CertificateColumn::VerificationCode.eq(code))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询证书失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_certificate()))
+    }
+}
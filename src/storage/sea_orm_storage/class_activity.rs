@@ -0,0 +1,190 @@
+//! 班级活跃度统计存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::notifications::{Column as NotificationColumn, Entity as Notifications};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    class_users::entities::ClassUserRole, classes::responses::StudentActivity,
+    notifications::entities::ReferenceType,
+};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect};
+
+/// 单个学生的累计数据
+#[derive(Default)]
+struct ActivityAccumulator {
+    recent_submissions: i64,
+    submitted_homeworks: i64,
+    late_homeworks: i64,
+    total_lateness_secs: i64,
+    unread_notifications: i64,
+}
+
+impl SeaOrmStorage {
+    /// 统计班级学生活跃度
+    ///
+    /// 在应用层汇总，避免不同数据库聚合函数返回类型不一致。
+    pub async fn get_class_activity_impl(
+        &self,
+        class_id: i64,
+        since: i64,
+    ) -> Result<Vec<StudentActivity>> {
+        // 1. 班级学生（含课代表，不含教师）
+        let members: Vec<(i64, String, Option<String>)> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::UserId)
+            .column(ClassUserColumn::Role)
+            .column(ClassUserColumn::ProfileName)
+            .filter(ClassUserColumn::ClassId.eq(class_id))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+
+        if members.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let member_ids: Vec<i64> = members.iter().map(|(id, _, _)| *id).collect();
+        let mut stats: HashMap<i64, ActivityAccumulator> = member_ids
+            .iter()
+            .map(|id| (*id, ActivityAccumulator::default()))
+            .collect();
+
+        let users = Users::find()
+            .filter(UserColumn::Id.is_in(member_ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?;
+        let users: HashMap<i64, _> = users.into_iter().map(|u| (u.id, u)).collect();
+
+        // 2. 班级作业及截止时间
+        let homeworks: Vec<(i64, Option<i64>)> = Homeworks::find()
+            .select_only()
+            .column(HomeworkColumn::Id)
+            .column(HomeworkColumn::Deadline)
+            .filter(HomeworkColumn::ClassId.eq(class_id))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?;
+        let deadlines: HashMap<i64, Option<i64>> = homeworks.iter().copied().collect();
+        let homework_ids: Vec<i64> = homeworks.iter().map(|(id, _)| *id).collect();
+
+        // 3. 提交记录：统计近期提交数，并按 (作业, 学生) 取最新版本计算迟交时长
+        if !homework_ids.is_empty() {
+            let submissions: Vec<(i64, i64, i32, i64)> = Submissions::find()
+                .select_only()
+                .column(SubmissionColumn::HomeworkId)
+                .column(SubmissionColumn::CreatorId)
+                .column(SubmissionColumn::Version)
+                .column(SubmissionColumn::SubmittedAt)
+                .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.iter().copied()))
+                .filter(SubmissionColumn::CreatorId.is_in(member_ids.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询提交记录失败: {e}")))?;
+
+            let mut latest: HashMap<(i64, i64), (i32, i64)> = HashMap::new();
+            for (homework_id, creator_id, version, submitted_at) in submissions {
+                if submitted_at >= since
+                    && let Some(acc) = stats.get_mut(&creator_id)
+                {
+                    acc.recent_submissions += 1;
+                }
+                let entry = latest
+                    .entry((homework_id, creator_id))
+                    .or_insert((version, submitted_at));
+                if version > entry.0 {
+                    *entry = (version, submitted_at);
+                }
+            }
+
+            for ((homework_id, creator_id), (_, submitted_at)) in latest {
+                let Some(Some(deadline)) = deadlines.get(&homework_id) else {
+                    continue;
+                };
+                let Some(acc) = stats.get_mut(&creator_id) else {
+                    continue;
+                };
+                acc.submitted_homeworks += 1;
+                let lateness = submitted_at - deadline;
+                if lateness > 0 {
+                    acc.late_homeworks += 1;
+                    acc.total_lateness_secs += lateness;
+                }
+            }
+        }
+
+        // 4. 与本班相关（班级本身或班级作业）的未读通知
+        let mut related = Condition::any().add(
+            Condition::all()
+                .add(NotificationColumn::ReferenceType.eq(ReferenceType::Class.to_string()))
+                .add(NotificationColumn::ReferenceId.eq(class_id)),
+        );
+        if !homework_ids.is_empty() {
+            related = related.add(
+                Condition::all()
+                    .add(NotificationColumn::ReferenceType.eq(ReferenceType::Homework.to_string()))
+                    .add(NotificationColumn::ReferenceId.is_in(homework_ids.iter().copied())),
+            );
+        }
+        let unread: Vec<i64> = Notifications::find()
+            .select_only()
+            .column(NotificationColumn::UserId)
+            .filter(NotificationColumn::UserId.is_in(member_ids.iter().copied()))
+            .filter(NotificationColumn::IsRead.eq(false))
+            .filter(related)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询未读通知失败: {e}")))?;
+        for user_id in unread {
+            if let Some(acc) = stats.get_mut(&user_id) {
+                acc.unread_notifications += 1;
+            }
+        }
+
+        let mut items: Vec<StudentActivity> = members
+            .into_iter()
+            .filter_map(|(user_id, role, profile_name)| {
+                let user = users.get(&user_id)?;
+                let acc = stats.remove(&user_id).unwrap_or_default();
+                Some(StudentActivity {
+                    user_id,
+                    username: user.username.clone(),
+                    // 班级内姓名代替全局显示名
+                    display_name: profile_name.or_else(|| user.display_name.clone()),
+                    role: role.parse().unwrap_or(ClassUserRole::Student),
+                    last_login: user
+                        .last_login
+                        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+                    recent_submissions: acc.recent_submissions,
+                    submitted_homeworks: acc.submitted_homeworks,
+                    late_homeworks: acc.late_homeworks,
+                    avg_lateness_hours: (acc.submitted_homeworks > 0).then(|| {
+                        acc.total_lateness_secs as f64 / 3600.0 / acc.submitted_homeworks as f64
+                    }),
+                    unread_notifications: acc.unread_notifications,
+                })
+            })
+            .collect();
+
+        // 近期提交最少、最久未登录的学生排在前面
+        items.sort_by(|a, b| {
+            a.recent_submissions
+                .cmp(&b.recent_submissions)
+                .then(a.last_login.cmp(&b.last_login))
+                .then(a.user_id.cmp(&b.user_id))
+        });
+
+        Ok(items)
+    }
+}
@@ -0,0 +1,224 @@
+//! 班级对比分析存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use super::organizations::tenant_condition;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    analytics::responses::ClassComparisonItem, class_users::entities::ClassUserRole,
+    organizations::entities::TenantScope,
+};
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{
+    ColumnTrait, EntityTrait, ExprTrait, JoinType, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait,
+};
+
+/// 计算比率，分母为 0 时返回 None
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+impl SeaOrmStorage {
+    /// 统计范围内各班级的提交率、迟交率、平均得分率与批改耗时
+    ///
+    /// 每类指标一次 GROUP BY 查询；求和项统一乘以浮点参数，
+    /// 保证不同数据库的 SUM 结果均为浮点类型。
+    pub async fn get_class_comparison_impl(
+        &self,
+        tenant: TenantScope,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ClassComparisonItem>> {
+        let classes: Vec<(i64, String, i64)> = Classes::find()
+            .select_only()
+            .column(ClassColumn::Id)
+            .column(ClassColumn::Name)
+            .column(ClassColumn::TeacherId)
+            .filter(tenant_condition(ClassColumn::OrgId, tenant))
+            .order_by_asc(ClassColumn::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?;
+        if classes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // 1. 学生人数
+        let student_counts: HashMap<i64, i64> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::ClassId)
+            .column_as(
+                Func::count(Expr::col((ClassUsers, ClassUserColumn::Id))),
+                "count",
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::class_users::Relation::Class.def(),
+            )
+            .filter(tenant_condition(ClassColumn::OrgId, tenant))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .group_by(ClassUserColumn::ClassId)
+            .into_tuple::<(i64, i64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级人数失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        // 2. 区间内布置的作业数
+        let homework_counts: HashMap<i64, i64> = Homeworks::find()
+            .select_only()
+            .column(HomeworkColumn::ClassId)
+            .column_as(
+                Func::count(Expr::col((Homeworks, HomeworkColumn::Id))),
+                "count",
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::homeworks::Relation::Class.def(),
+            )
+            .filter(tenant_condition(ClassColumn::OrgId, tenant))
+            .filter(HomeworkColumn::CreatedAt.between(from, to))
+            .group_by(HomeworkColumn::ClassId)
+            .into_tuple::<(i64, i64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级作业失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        // 3. 已提交与迟交数（每个学生每份作业只有一条 version = 1 的提交）
+        let submission_query = |late_only: bool| {
+            let mut select = Submissions::find()
+                .select_only()
+                .column(HomeworkColumn::ClassId)
+                .column_as(
+                    Func::count(Expr::col((Submissions, SubmissionColumn::Id))),
+                    "count",
+                )
+                .join(
+                    JoinType::InnerJoin,
+                    crate::entity::submissions::Relation::Homework.def(),
+                )
+                .join(
+                    JoinType::InnerJoin,
+                    crate::entity::homeworks::Relation::Class.def(),
+                )
+                .filter(tenant_condition(ClassColumn::OrgId, tenant))
+                .filter(HomeworkColumn::CreatedAt.between(from, to))
+                .filter(SubmissionColumn::Version.eq(1));
+            if late_only {
+                select = select.filter(SubmissionColumn::IsLate.eq(true));
+            }
+            select.group_by(HomeworkColumn::ClassId)
+        };
+        let submitted_counts: HashMap<i64, i64> = submission_query(false)
+            .into_tuple::<(i64, i64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级提交失败: {e}")))?
+            .into_iter()
+            .collect();
+        let late_counts: HashMap<i64, i64> = submission_query(true)
+            .into_tuple::<(i64, i64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级迟交失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        // 4. 评分数、得分率之和与批改耗时之和（秒）
+        let score_percent = Expr::col((Grades, GradeColumn::Score))
+            .mul(100.0_f64)
+            .div(Expr::col((Homeworks, HomeworkColumn::MaxScore)));
+        let grading_secs = Expr::col((Grades, GradeColumn::GradedAt))
+            .sub(Expr::col((Submissions, SubmissionColumn::SubmittedAt)))
+            .mul(1.0_f64);
+        let grade_rows: Vec<(i64, i64, Option<f64>, Option<f64>)> = Grades::find()
+            .select_only()
+            .column(HomeworkColumn::ClassId)
+            .column_as(Func::count(Expr::col((Grades, GradeColumn::Id))), "count")
+            .column_as(Func::sum(score_percent), "score_percent_sum")
+            .column_as(Func::sum(grading_secs), "grading_secs_sum")
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::grades::Relation::Submission.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::submissions::Relation::Homework.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::homeworks::Relation::Class.def(),
+            )
+            .filter(tenant_condition(ClassColumn::OrgId, tenant))
+            .filter(HomeworkColumn::CreatedAt.between(from, to))
+            .filter(HomeworkColumn::MaxScore.gt(0.0))
+            .group_by(HomeworkColumn::ClassId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级评分失败: {e}")))?;
+        let grade_stats: HashMap<i64, (i64, f64, f64)> = grade_rows
+            .into_iter()
+            .map(|(class_id, count, percent, secs)| {
+                (
+                    class_id,
+                    (count, percent.unwrap_or(0.0), secs.unwrap_or(0.0)),
+                )
+            })
+            .collect();
+
+        let items = classes
+            .into_iter()
+            .map(|(class_id, class_name, teacher_id)| {
+                let student_count = student_counts.get(&class_id).copied().unwrap_or(0);
+                let homework_count = homework_counts.get(&class_id).copied().unwrap_or(0);
+                let submitted_count = submitted_counts.get(&class_id).copied().unwrap_or(0);
+                let late_count = late_counts.get(&class_id).copied().unwrap_or(0);
+                let (graded_count, percent_sum, secs_sum) =
+                    grade_stats.get(&class_id).copied().unwrap_or_default();
+
+                ClassComparisonItem {
+                    class_id,
+                    class_name,
+                    teacher_id,
+                    student_count,
+                    homework_count,
+                    submitted_count,
+                    submission_rate: ratio(
+                        submitted_count as f64,
+                        (student_count * homework_count) as f64,
+                    ),
+                    late_count,
+                    late_rate: ratio(late_count as f64, submitted_count as f64),
+                    graded_count,
+                    average_score_percent: ratio(percent_sum, graded_count as f64),
+                    average_grading_hours: ratio(secs_sum / 3600.0, graded_count as f64),
+                }
+            })
+            .collect();
+
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio() {
+        assert_eq!(ratio(1.0, 4.0), Some(0.25));
+        assert_eq!(ratio(3.0, 0.0), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! 班级 API 令牌存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::class_api_token_logs::{
+    ActiveModel as LogActiveModel, Column as LogColumn, Entity as ClassApiTokenLogs,
+};
+use crate::entity::class_api_tokens::{ActiveModel, Column, Entity as ClassApiTokens};
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::entities::{ClassApiToken, ClassApiTokenLog, NewClassApiToken};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::Expr,
+};
+
+impl SeaOrmStorage {
+    /// 创建班级 API 令牌
+    pub async fn create_class_api_token_impl(
+        &self,
+        token: NewClassApiToken,
+    ) -> Result<ClassApiToken> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            class_id: Set(token.class_id),
+            name: Set(token.name),
+            token_hash: Set(token.token_hash),
+            token_prefix: Set(token.token_prefix),
+            read_only: Set(token.read_only),
+            created_by: Set(token.created_by),
+            expires_at: Set(token.expires_at),
+            last_used_at: Set(None),
+            revoked_at: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model.insert(&self.db).await.map_err(|e| {
+            HWSystemError::database_operation(format!("创建班级 API 令牌失败: {e}"))
+        })?;
+
+        Ok(result.into_class_api_token())
+    }
+
+    /// 列出班级的 API 令牌（含已撤销）
+    pub async fn list_class_api_tokens_impl(&self, class_id: i64) -> Result<Vec<ClassApiToken>> {
+        let results = ClassApiTokens::find()
+            .filter(Column::ClassId.eq(class_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("查询班级 API 令牌失败: {e}"))
+            })?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_class_api_token())
+            .collect())
+    }
+
+    /// 获取班级的某个 API 令牌
+    pub async fn get_class_api_token_impl(
+        &self,
+        class_id: i64,
+        token_id: i64,
+    ) -> Result<Option<ClassApiToken>> {
+        let result = ClassApiTokens::find_by_id(token_id)
+            .filter(Column::ClassId.eq(class_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("查询班级 API 令牌失败: {e}"))
+            })?;
+
+        Ok(result.map(|m| m.into_class_api_token()))
+    }
+
+    /// 通过令牌摘要查找 API 令牌
+    pub async fn get_class_api_token_by_hash_impl(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<ClassApiToken>> {
+        let result = ClassApiTokens::find()
+            .filter(Column::TokenHash.eq(token_hash))
+            .one(&self.db)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("查询班级 API 令牌失败: {e}"))
+            })?;
+
+        Ok(result.map(|m| m.into_class_api_token()))
+    }
+
+    /// 撤销 API 令牌（不存在或已撤销返回 false）
+    pub async fn revoke_class_api_token_impl(&self, class_id: i64, token_id: i64) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = ClassApiTokens::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(now))
+            .filter(Column::Id.eq(token_id))
+            .filter(Column::ClassId.eq(class_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("撤销班级 API 令牌失败: {e}"))
+            })?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 更新令牌最近使用时间
+    pub async fn touch_class_api_token_impl(&self, token_id: i64, now: i64) -> Result<()> {
+        ClassApiTokens::update_many()
+            .col_expr(Column::LastUsedAt, Expr::value(now))
+            .filter(Column::Id.eq(token_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新令牌使用时间失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 记录令牌发起的写请求
+    pub async fn create_class_api_token_log_impl(
+        &self,
+        token_id: i64,
+        method: &str,
+        path: &str,
+        status: i32,
+        ip_address: Option<String>,
+    ) -> Result<()> {
+        LogActiveModel {
+            token_id: Set(token_id),
+            method: Set(method.to_string()),
+            path: Set(path.chars().take(255).collect()),
+            status: Set(status),
+            ip_address: Set(ip_address),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("记录令牌调用失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 列出令牌最近的写请求记录
+    pub async fn list_class_api_token_logs_impl(
+        &self,
+        token_id: i64,
+        limit: u64,
+    ) -> Result<Vec<ClassApiTokenLog>> {
+        let results = ClassApiTokenLogs::find()
+            .filter(LogColumn::TokenId.eq(token_id))
+            .order_by_desc(LogColumn::CreatedAt)
+            .order_by_desc(LogColumn::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询令牌调用记录失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_class_api_token_log())
+            .collect())
+    }
+}
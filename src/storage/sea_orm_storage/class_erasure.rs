@@ -0,0 +1,322 @@
+//! 班级内学生数据擦除存储操作
+
+use super::SeaOrmStorage;
+use super::homework_counters::refresh_homework_counters;
+use crate::entity::activity_events::{Column as ActivityEventColumn, Entity as ActivityEvents};
+use crate::entity::class_leaderboard_opt_ins::{
+    Column as LeaderboardOptInColumn, Entity as LeaderboardOptIns,
+};
+use crate::entity::content_reports::{Column as ContentReportColumn, Entity as ContentReports};
+use crate::entity::exam_access_logs::{Column as ExamAccessLogColumn, Entity as ExamAccessLogs};
+use crate::entity::grade_drafts::{Column as GradeDraftColumn, Entity as GradeDrafts};
+use crate::entity::grade_mentions::{Column as GradeMentionColumn, Entity as GradeMentions};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_feedback::{
+    Column as HomeworkFeedbackColumn, Entity as HomeworkFeedback,
+};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::messages::{Column as MessageColumn, Entity as Messages};
+use crate::entity::moderation_flags::{Column as ModerationFlagColumn, Entity as ModerationFlags};
+use crate::entity::prelude::UserAuditLogActiveModel;
+use crate::entity::reactions::{Column as ReactionColumn, Entity as Reactions};
+use crate::entity::resubmission_requests::{
+    Column as ResubmissionColumn, Entity as ResubmissionRequests,
+};
+use crate::entity::student_goals::{Column as StudentGoalColumn, Entity as StudentGoals};
+use crate::entity::submission_files::{Column as SubmissionFileColumn, Entity as SubmissionFiles};
+use crate::entity::submission_references::{
+    Column as SubmissionReferenceColumn, Entity as SubmissionReferences,
+};
+use crate::entity::submission_self_assessments::{
+    Column as SelfAssessmentColumn, Entity as SelfAssessments,
+};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::entities::ClassErasureCounts;
+use crate::models::reactions::entities::ReactionTargetType;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+
+/// 统计或删除满足条件的行，返回行数
+///
+/// 用法：`erase_rows!(Entity, 条件, &conn, dry_run, "描述")`，`dry_run` 时只计数不删除。
+macro_rules! erase_rows {
+    ($entity:ty, $condition:expr, $conn:expr, $dry_run:expr, $what:expr) => {{
+        let condition = $condition;
+        if $dry_run {
+            <$entity as EntityTrait>::find()
+                .filter(condition)
+                .count($conn)
+                .await
+        } else {
+            <$entity as EntityTrait>::delete_many()
+                .filter(condition)
+                .exec($conn)
+                .await
+                .map(|result| result.rows_affected)
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("擦除{}失败: {e}", $what)))?
+    }};
+}
+
+/// 统计（`dry_run`）或删除学生在班级内的数据
+///
+/// 预览与执行共用同一套条件，保证确认时看到的行数就是实际删除的范围。
+/// 子表先于提交删除；评语的 @提及与表情回应随评分一并删除，不单独计数。
+async fn erase_student_rows<C: ConnectionTrait>(
+    conn: &C,
+    class_id: i64,
+    user_id: i64,
+    dry_run: bool,
+) -> Result<ClassErasureCounts> {
+    let homework_ids: Vec<i64> = Homeworks::find()
+        .select_only()
+        .column(HomeworkColumn::Id)
+        .filter(HomeworkColumn::ClassId.eq(class_id))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?;
+    let submission_ids: Vec<i64> = Submissions::find()
+        .select_only()
+        .column(SubmissionColumn::Id)
+        .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.clone()))
+        .filter(SubmissionColumn::CreatorId.eq(user_id))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询学生提交失败: {e}")))?;
+
+    if !dry_run {
+        let grade_ids: Vec<i64> = Grades::find()
+            .select_only()
+            .column(GradeColumn::Id)
+            .filter(GradeColumn::SubmissionId.is_in(submission_ids.clone()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
+        erase_rows!(
+            GradeMentions,
+            GradeMentionColumn::GradeId.is_in(grade_ids.clone()),
+            conn,
+            false,
+            "评语提及"
+        );
+        erase_rows!(
+            Reactions,
+            Condition::all()
+                .add(ReactionColumn::TargetType.eq(ReactionTargetType::GradeComment.to_string()))
+                .add(ReactionColumn::TargetId.is_in(grade_ids)),
+            conn,
+            false,
+            "评语回应"
+        );
+    }
+
+    let counts = ClassErasureCounts {
+        grades: erase_rows!(
+            Grades,
+            GradeColumn::SubmissionId.is_in(submission_ids.clone()),
+            conn,
+            dry_run,
+            "评分"
+        ),
+        grade_drafts: erase_rows!(
+            GradeDrafts,
+            GradeDraftColumn::SubmissionId.is_in(submission_ids.clone()),
+            conn,
+            dry_run,
+            "评分草稿"
+        ),
+        submission_files: erase_rows!(
+            SubmissionFiles,
+            SubmissionFileColumn::SubmissionId.is_in(submission_ids.clone()),
+            conn,
+            dry_run,
+            "提交附件关联"
+        ),
+        submission_references: erase_rows!(
+            SubmissionReferences,
+            SubmissionReferenceColumn::SubmissionId.is_in(submission_ids.clone()),
+            conn,
+            dry_run,
+            "提交外部引用"
+        ),
+        self_assessments: erase_rows!(
+            SelfAssessments,
+            SelfAssessmentColumn::SubmissionId.is_in(submission_ids.clone()),
+            conn,
+            dry_run,
+            "提交自评"
+        ),
+        resubmission_requests: erase_rows!(
+            ResubmissionRequests,
+            Condition::all()
+                .add(ResubmissionColumn::StudentId.eq(user_id))
+                .add(ResubmissionColumn::HomeworkId.is_in(homework_ids.clone())),
+            conn,
+            dry_run,
+            "退回重交请求"
+        ),
+        exam_access_logs: erase_rows!(
+            ExamAccessLogs,
+            Condition::all()
+                .add(ExamAccessLogColumn::UserId.eq(user_id))
+                .add(ExamAccessLogColumn::HomeworkId.is_in(homework_ids.clone())),
+            conn,
+            dry_run,
+            "考试访问日志"
+        ),
+        homework_feedback: erase_rows!(
+            HomeworkFeedback,
+            Condition::all()
+                .add(HomeworkFeedbackColumn::UserId.eq(user_id))
+                .add(HomeworkFeedbackColumn::HomeworkId.is_in(homework_ids.clone())),
+            conn,
+            dry_run,
+            "作业用时反馈"
+        ),
+        messages: erase_rows!(
+            Messages,
+            Condition::all()
+                .add(MessageColumn::ClassId.eq(class_id))
+                .add(
+                    Condition::any()
+                        .add(MessageColumn::SenderId.eq(user_id))
+                        .add(MessageColumn::RecipientId.eq(user_id))
+                ),
+            conn,
+            dry_run,
+            "私信"
+        ),
+        moderation_flags: erase_rows!(
+            ModerationFlags,
+            Condition::all()
+                .add(ModerationFlagColumn::ClassId.eq(class_id))
+                .add(ModerationFlagColumn::UserId.eq(user_id)),
+            conn,
+            dry_run,
+            "内容审核标记"
+        ),
+        content_reports: erase_rows!(
+            ContentReports,
+            Condition::all()
+                .add(ContentReportColumn::ClassId.eq(class_id))
+                .add(
+                    Condition::any()
+                        .add(ContentReportColumn::ReporterId.eq(user_id))
+                        .add(ContentReportColumn::AuthorId.eq(user_id))
+                ),
+            conn,
+            dry_run,
+            "内容举报"
+        ),
+        activity_events: erase_rows!(
+            ActivityEvents,
+            Condition::all()
+                .add(ActivityEventColumn::ClassId.eq(class_id))
+                .add(ActivityEventColumn::ActorId.eq(user_id)),
+            conn,
+            dry_run,
+            "班级动态"
+        ),
+        student_goals: erase_rows!(
+            StudentGoals,
+            Condition::all()
+                .add(StudentGoalColumn::ClassId.eq(class_id))
+                .add(StudentGoalColumn::UserId.eq(user_id)),
+            conn,
+            dry_run,
+            "学习目标"
+        ),
+        leaderboard_opt_ins: erase_rows!(
+            LeaderboardOptIns,
+            Condition::all()
+                .add(LeaderboardOptInColumn::ClassId.eq(class_id))
+                .add(LeaderboardOptInColumn::UserId.eq(user_id)),
+            conn,
+            dry_run,
+            "排行榜署名"
+        ),
+        // 提交最后删除
+        submissions: erase_rows!(
+            Submissions,
+            SubmissionColumn::Id.is_in(submission_ids),
+            conn,
+            dry_run,
+            "提交"
+        ),
+    };
+
+    if !dry_run && counts.submissions > 0 {
+        refresh_homework_counters(conn, &homework_ids).await?;
+    }
+    Ok(counts)
+}
+
+impl SeaOrmStorage {
+    /// 统计学生在班级内可擦除的数据行数，不修改数据
+    pub async fn preview_class_erasure_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+    ) -> Result<ClassErasureCounts> {
+        erase_student_rows(&self.db, class_id, user_id, true).await
+    }
+
+    /// 擦除学生在班级内的数据并写入审计日志
+    ///
+    /// 事务内先重新统计，与预览时的 `expected` 不一致说明数据已变化，返回 `None` 且不做任何修改。
+    pub async fn erase_class_student_data_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        expected: &ClassErasureCounts,
+        operator_id: i64,
+        ip_address: Option<String>,
+        reason: Option<String>,
+    ) -> Result<Option<ClassErasureCounts>> {
+        let now = chrono::Utc::now().timestamp();
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        if erase_student_rows(&txn, class_id, user_id, true).await? != *expected {
+            return Ok(None);
+        }
+        let counts = erase_student_rows(&txn, class_id, user_id, false).await?;
+
+        let mut detail = format!(
+            "class_id={class_id},total={},counts={}",
+            counts.total(),
+            serde_json::to_string(&counts).unwrap_or_default(),
+        );
+        if let Some(reason) = reason {
+            detail.push_str(&format!(",reason={reason}"));
+        }
+        UserAuditLogActiveModel {
+            user_id: Set(user_id),
+            action: Set("class_erasure".to_string()),
+            detail: Set(Some(detail)),
+            operator_id: Set(operator_id),
+            ip_address: Set(ip_address),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(Some(counts))
+    }
+}
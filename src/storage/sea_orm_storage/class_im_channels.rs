@@ -0,0 +1,329 @@
+//! 班级 IM 通知渠道存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::class_im_channels::{
+    ActiveModel, Column, Entity as ClassImChannels, Model as ClassImChannelModel,
+};
+use crate::entity::im_deliveries::{
+    ActiveModel as ImDeliveryActiveModel, Column as ImDeliveryColumn, Entity as ImDeliveries,
+};
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::{
+    entities::{ClassImChannel, ImDelivery, ImDeliveryStatus, ImEvent, NewImDelivery},
+    requests::{CreateClassImChannelRequest, UpdateClassImChannelRequest},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::{Expr, OnConflict},
+};
+use std::collections::HashMap;
+
+fn join_events(events: &[ImEvent]) -> String {
+    events
+        .iter()
+        .map(|event| event.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn templates_json(templates: &HashMap<ImEvent, String>) -> Option<String> {
+    if templates.is_empty() {
+        None
+    } else {
+        serde_json::to_string(templates).ok()
+    }
+}
+
+impl SeaOrmStorage {
+    /// 创建班级 IM 通知渠道
+    pub async fn create_class_im_channel_impl(
+        &self,
+        class_id: i64,
+        req: CreateClassImChannelRequest,
+        created_by: i64,
+    ) -> Result<ClassImChannel> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            class_id: Set(class_id),
+            name: Set(req.name),
+            provider: Set(req.provider.to_string()),
+            target: Set(req.target),
+            secret: Set(req.secret),
+            events: Set(join_events(&req.events)),
+            templates: Set(templates_json(&req.templates)),
+            enabled: Set(req.enabled.unwrap_or(true)),
+            last_error: Set(None),
+            last_delivered_at: Set(None),
+            created_by: Set(created_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建 IM 通知渠道失败: {e}")))?;
+
+        Ok(result.into_im_channel())
+    }
+
+    /// 列出班级的 IM 通知渠道
+    pub async fn list_class_im_channels_impl(&self, class_id: i64) -> Result<Vec<ClassImChannel>> {
+        let results = ClassImChannels::find()
+            .filter(Column::ClassId.eq(class_id))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询 IM 通知渠道失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_im_channel()).collect())
+    }
+
+    async fn find_class_im_channel(
+        &self,
+        class_id: i64,
+        channel_id: i64,
+    ) -> Result<Option<ClassImChannelModel>> {
+        ClassImChannels::find_by_id(channel_id)
+            .filter(Column::ClassId.eq(class_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询 IM 通知渠道失败: {e}")))
+    }
+
+    /// 获取班级的某个 IM 通知渠道
+    pub async fn get_class_im_channel_impl(
+        &self,
+        class_id: i64,
+        channel_id: i64,
+    ) -> Result<Option<ClassImChannel>> {
+        Ok(self
+            .find_class_im_channel(class_id, channel_id)
+            .await?
+            .map(|m| m.into_im_channel()))
+    }
+
+    /// 更新 IM 通知渠道
+    pub async fn update_class_im_channel_impl(
+        &self,
+        class_id: i64,
+        channel_id: i64,
+        req: UpdateClassImChannelRequest,
+    ) -> Result<Option<ClassImChannel>> {
+        let Some(model) = self.find_class_im_channel(class_id, channel_id).await? else {
+            return Ok(None);
+        };
+
+        let mut active: ActiveModel = model.into();
+        if let Some(name) = req.name {
+            active.name = Set(name);
+        }
+        if let Some(target) = req.target {
+            active.target = Set(target);
+        }
+        if let Some(secret) = req.secret {
+            // 空字符串表示清除密钥
+            active.secret = Set(Some(secret).filter(|s| !s.is_empty()));
+        }
+        if let Some(events) = req.events {
+            active.events = Set(join_events(&events));
+        }
+        if let Some(templates) = req.templates {
+            active.templates = Set(templates_json(&templates));
+        }
+        if let Some(enabled) = req.enabled {
+            active.enabled = Set(enabled);
+        }
+        active.updated_at = Set(chrono::Utc::now().timestamp());
+
+        let result = active
+            .update(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新 IM 通知渠道失败: {e}")))?;
+
+        Ok(Some(result.into_im_channel()))
+    }
+
+    /// 删除 IM 通知渠道（待投递的消息随之删除）
+    pub async fn delete_class_im_channel_impl(
+        &self,
+        class_id: i64,
+        channel_id: i64,
+    ) -> Result<bool> {
+        let result = ClassImChannels::delete_many()
+            .filter(Column::Id.eq(channel_id))
+            .filter(Column::ClassId.eq(class_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除 IM 通知渠道失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 获取 IM 通知渠道（投递任务使用，不校验班级）
+    pub async fn get_im_channel_by_id_impl(
+        &self,
+        channel_id: i64,
+    ) -> Result<Option<ClassImChannel>> {
+        let result = ClassImChannels::find_by_id(channel_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询 IM 通知渠道失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_im_channel()))
+    }
+
+    /// 列出班级中订阅了指定事件的已启用渠道
+    pub async fn list_im_channels_for_event_impl(
+        &self,
+        class_id: i64,
+        event: ImEvent,
+    ) -> Result<Vec<ClassImChannel>> {
+        let results = ClassImChannels::find()
+            .filter(Column::ClassId.eq(class_id))
+            .filter(Column::Enabled.eq(true))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询 IM 通知渠道失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_im_channel())
+            .filter(|channel| channel.events.contains(&event))
+            .collect())
+    }
+
+    /// 写入投递队列，同一渠道、事件和关联实体已存在时忽略，返回新增数量
+    pub async fn enqueue_im_deliveries_impl(&self, deliveries: Vec<NewImDelivery>) -> Result<u64> {
+        let now = chrono::Utc::now().timestamp();
+        let mut inserted = 0;
+
+        for delivery in deliveries {
+            let model = ImDeliveryActiveModel {
+                channel_id: Set(delivery.channel_id),
+                event: Set(delivery.event.to_string()),
+                reference_id: Set(delivery.reference_id),
+                message: Set(delivery.message),
+                status: Set(ImDeliveryStatus::Pending.to_string()),
+                attempts: Set(0),
+                next_attempt_at: Set(now),
+                last_error: Set(None),
+                created_at: Set(now),
+                delivered_at: Set(None),
+                ..Default::default()
+            };
+
+            let result = ImDeliveries::insert(model)
+                .on_conflict(
+                    OnConflict::columns([
+                        ImDeliveryColumn::ChannelId,
+                        ImDeliveryColumn::Event,
+                        ImDeliveryColumn::ReferenceId,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("写入 IM 投递队列失败: {e}"))
+                })?;
+            inserted += result;
+        }
+
+        Ok(inserted)
+    }
+
+    /// 列出到期待投递的消息
+    pub async fn list_due_im_deliveries_impl(
+        &self,
+        now: i64,
+        limit: u64,
+    ) -> Result<Vec<ImDelivery>> {
+        let results = ImDeliveries::find()
+            .filter(ImDeliveryColumn::Status.eq(ImDeliveryStatus::Pending.to_string()))
+            .filter(ImDeliveryColumn::NextAttemptAt.lte(now))
+            .order_by_asc(ImDeliveryColumn::NextAttemptAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询 IM 投递队列失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_im_delivery()).collect())
+    }
+
+    /// 记录投递成功
+    pub async fn mark_im_delivery_delivered_impl(&self, delivery: &ImDelivery) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        ImDeliveries::update_many()
+            .col_expr(
+                ImDeliveryColumn::Status,
+                Expr::value(ImDeliveryStatus::Delivered.to_string()),
+            )
+            .col_expr(
+                ImDeliveryColumn::Attempts,
+                Expr::value(delivery.attempts + 1),
+            )
+            .col_expr(
+                ImDeliveryColumn::LastError,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(ImDeliveryColumn::DeliveredAt, Expr::value(now))
+            .filter(ImDeliveryColumn::Id.eq(delivery.id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新 IM 投递状态失败: {e}")))?;
+
+        ClassImChannels::update_many()
+            .col_expr(Column::LastDeliveredAt, Expr::value(now))
+            .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+            .filter(Column::Id.eq(delivery.channel_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新 IM 通知渠道失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 记录投递失败；`retry_at` 为空表示不再重试
+    pub async fn mark_im_delivery_failed_impl(
+        &self,
+        delivery: &ImDelivery,
+        error: &str,
+        retry_at: Option<i64>,
+    ) -> Result<()> {
+        let status = if retry_at.is_some() {
+            ImDeliveryStatus::Pending
+        } else {
+            ImDeliveryStatus::Failed
+        };
+
+        let mut update = ImDeliveries::update_many()
+            .col_expr(ImDeliveryColumn::Status, Expr::value(status.to_string()))
+            .col_expr(
+                ImDeliveryColumn::Attempts,
+                Expr::value(delivery.attempts + 1),
+            )
+            .col_expr(ImDeliveryColumn::LastError, Expr::value(error));
+        if let Some(retry_at) = retry_at {
+            update = update.col_expr(ImDeliveryColumn::NextAttemptAt, Expr::value(retry_at));
+        }
+        update
+            .filter(ImDeliveryColumn::Id.eq(delivery.id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新 IM 投递状态失败: {e}")))?;
+
+        ClassImChannels::update_many()
+            .col_expr(Column::LastError, Expr::value(error))
+            .filter(Column::Id.eq(delivery.channel_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新 IM 通知渠道失败: {e}")))?;
+
+        Ok(())
+    }
+}
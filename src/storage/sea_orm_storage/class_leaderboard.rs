@@ -0,0 +1,278 @@
+//! 班级排行榜存储操作
+//!
+//! 成绩只统计已截止（或未设置截止时间）的作业，每项作业取学生最新一次已评分的整体提交；
+//! 评分草稿不属于正式评分，不参与统计。
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::class_leaderboard_opt_ins::{
+    ActiveModel as OptInActiveModel, Column as OptInColumn, Entity as ClassLeaderboardOptIns,
+};
+use crate::entity::class_leaderboard_settings::{ActiveModel, Entity as ClassLeaderboardSettings};
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::{
+    ClassLeaderboardSettings as LeaderboardSettings, LeaderboardMetric, LeaderboardStanding,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    Set,
+};
+
+impl SeaOrmStorage {
+    /// 获取班级排行榜设置
+    pub async fn get_leaderboard_settings_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<LeaderboardSettings>> {
+        let result = ClassLeaderboardSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询排行榜设置失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_leaderboard_settings()))
+    }
+
+    /// 创建或更新班级排行榜设置
+    pub async fn upsert_leaderboard_settings_impl(
+        &self,
+        class_id: i64,
+        enabled: bool,
+        top_n: i32,
+        metric: LeaderboardMetric,
+        user_id: i64,
+    ) -> Result<LeaderboardSettings> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = ClassLeaderboardSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询排行榜设置失败: {e}")))?;
+
+        let result = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.enabled = Set(enabled);
+                active.top_n = Set(top_n);
+                active.metric = Set(metric.to_string());
+                active.updated_by = Set(user_id);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    class_id: Set(class_id),
+                    enabled: Set(enabled),
+                    top_n: Set(top_n),
+                    metric: Set(metric.to_string()),
+                    updated_by: Set(user_id),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存排行榜设置失败: {e}")))?;
+
+        Ok(result.into_leaderboard_settings())
+    }
+
+    /// 查询学生是否选择在排行榜中署名
+    pub async fn get_leaderboard_opt_in_impl(&self, class_id: i64, user_id: i64) -> Result<bool> {
+        let existing = ClassLeaderboardOptIns::find()
+            .filter(OptInColumn::ClassId.eq(class_id))
+            .filter(OptInColumn::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询排行榜署名失败: {e}")))?;
+
+        Ok(existing.is_some())
+    }
+
+    /// 设置学生是否在排行榜中署名
+    pub async fn set_leaderboard_opt_in_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        opt_in: bool,
+    ) -> Result<()> {
+        if !opt_in {
+            ClassLeaderboardOptIns::delete_many()
+                .filter(OptInColumn::ClassId.eq(class_id))
+                .filter(OptInColumn::UserId.eq(user_id))
+                .exec(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("取消排行榜署名失败: {e}"))
+                })?;
+            return Ok(());
+        }
+
+        if self.get_leaderboard_opt_in_impl(class_id, user_id).await? {
+            return Ok(());
+        }
+        OptInActiveModel {
+            class_id: Set(class_id),
+            user_id: Set(user_id),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("保存排行榜署名失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 汇总班级学生（含课代表）的已公布成绩，没有计入作业的学生不返回
+    pub async fn list_leaderboard_standings_impl(
+        &self,
+        class_id: i64,
+        now: i64,
+    ) -> Result<Vec<LeaderboardStanding>> {
+        // 1. 班级学生及班级内姓名
+        let members: Vec<(i64, Option<String>)> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::UserId)
+            .column(ClassUserColumn::ProfileName)
+            .filter(ClassUserColumn::ClassId.eq(class_id))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+        if members.is_empty() {
+            return Ok(vec![]);
+        }
+        let student_ids: Vec<i64> = members.iter().map(|(id, _)| *id).collect();
+
+        // 2. 已截止或未设置截止时间的作业
+        let homeworks: HashMap<i64, f64> = Homeworks::find()
+            .select_only()
+            .column(HomeworkColumn::Id)
+            .column(HomeworkColumn::MaxScore)
+            .filter(HomeworkColumn::ClassId.eq(class_id))
+            .filter(
+                Condition::any()
+                    .add(HomeworkColumn::Deadline.is_null())
+                    .add(HomeworkColumn::Deadline.lte(now)),
+            )
+            .into_tuple::<(i64, f64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?
+            .into_iter()
+            .collect();
+        if homeworks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // 3. 整体提交，按版本倒序，便于取每项作业最新的已评分提交
+        let submissions: Vec<(i64, i64, i64)> = Submissions::find()
+            .select_only()
+            .column(SubmissionColumn::Id)
+            .column(SubmissionColumn::HomeworkId)
+            .column(SubmissionColumn::CreatorId)
+            .filter(SubmissionColumn::HomeworkId.is_in(homeworks.keys().copied()))
+            .filter(SubmissionColumn::CreatorId.is_in(student_ids.clone()))
+            .filter(SubmissionColumn::PartId.is_null())
+            .order_by_desc(SubmissionColumn::Version)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交记录失败: {e}")))?;
+        if submissions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let grades: HashMap<i64, f64> = Grades::find()
+            .select_only()
+            .column(GradeColumn::SubmissionId)
+            .column(GradeColumn::Score)
+            .filter(GradeColumn::SubmissionId.is_in(submissions.iter().map(|s| s.0)))
+            .into_tuple::<(i64, f64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        let mut latest: HashMap<(i64, i64), f64> = HashMap::new();
+        for (submission_id, homework_id, creator_id) in submissions {
+            if let Some(score) = grades.get(&submission_id) {
+                latest.entry((creator_id, homework_id)).or_insert(*score);
+            }
+        }
+
+        // 4. 按学生汇总
+        let mut totals: HashMap<i64, (i64, f64, f64)> = HashMap::new();
+        for ((user_id, homework_id), score) in latest {
+            let max_score = homeworks[&homework_id];
+            let percent = if max_score > 0.0 {
+                score / max_score * 100.0
+            } else {
+                100.0
+            };
+            let entry = totals.entry(user_id).or_default();
+            entry.0 += 1;
+            entry.1 += score;
+            entry.2 += percent;
+        }
+        if totals.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // 5. 显示名称与署名选择
+        let users: HashMap<i64, (String, Option<String>)> = Users::find()
+            .select_only()
+            .column(UserColumn::Id)
+            .column(UserColumn::Username)
+            .column(UserColumn::DisplayName)
+            .filter(UserColumn::Id.is_in(totals.keys().copied()))
+            .into_tuple::<(i64, String, Option<String>)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?
+            .into_iter()
+            .map(|(id, username, display_name)| (id, (username, display_name)))
+            .collect();
+        let opted_in: HashSet<i64> = ClassLeaderboardOptIns::find()
+            .select_only()
+            .column(OptInColumn::UserId)
+            .filter(OptInColumn::ClassId.eq(class_id))
+            .into_tuple::<i64>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询排行榜署名失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        Ok(members
+            .into_iter()
+            .filter_map(|(user_id, profile_name)| {
+                let (homework_count, total_score, percent_sum) = *totals.get(&user_id)?;
+                let (username, display_name) = users.get(&user_id)?.clone();
+                Some(LeaderboardStanding {
+                    user_id,
+                    name: profile_name.or(display_name).unwrap_or(username),
+                    opted_in: opted_in.contains(&user_id),
+                    homework_count,
+                    total_score: round2(total_score),
+                    average_percent: round2(percent_sum / homework_count as f64),
+                })
+            })
+            .collect())
+    }
+}
+
+/// 保留两位小数
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,117 @@
+//! 班级角色名称配置存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::class_role_labels::{ActiveModel, Entity as ClassRoleLabelSettings};
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassRoleLabels;
+use sea_orm::sea_query::{Condition, Expr};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+
+impl SeaOrmStorage {
+    /// 获取班级角色名称配置，未配置时返回 None
+    pub async fn get_class_role_labels_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<ClassRoleLabels>> {
+        let model = ClassRoleLabelSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色名称配置失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_class_role_labels()))
+    }
+
+    /// 保存班级角色名称配置（空配置删除记录）
+    ///
+    /// 成员的角色名称已被删除或已改为对应其他角色时清除，成员回到所属角色的默认名称。
+    pub async fn save_class_role_labels_impl(
+        &self,
+        class_id: i64,
+        labels: ClassRoleLabels,
+        updated_by: i64,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let existing = ClassRoleLabelSettings::find_by_id(class_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色名称配置失败: {e}")))?;
+
+        if labels.is_default() {
+            if existing.is_some() {
+                ClassRoleLabelSettings::delete_by_id(class_id)
+                    .exec(&txn)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("删除角色名称配置失败: {e}"))
+                    })?;
+            }
+        } else {
+            let json = serde_json::to_string(&labels.labels)
+                .map_err(|e| HWSystemError::serialization(format!("序列化角色名称失败: {e}")))?;
+
+            match existing {
+                Some(model) => {
+                    let mut active: ActiveModel = model.into();
+                    active.labels = Set(json);
+                    active.updated_by = Set(updated_by);
+                    active.updated_at = Set(now);
+                    active.update(&txn).await
+                }
+                None => {
+                    ActiveModel {
+                        class_id: Set(class_id),
+                        labels: Set(json),
+                        updated_by: Set(updated_by),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                    }
+                    .insert(&txn)
+                    .await
+                }
+            }
+            .map_err(|e| HWSystemError::database_operation(format!("保存角色名称配置失败: {e}")))?;
+        }
+
+        // 清除失效的成员角色名称：标识已删除，或名称对应的角色与成员角色不一致
+        let keys: Vec<String> = labels.labels.iter().map(|l| l.key.clone()).collect();
+        let mut stale = Condition::any().add(if keys.is_empty() {
+            ClassUserColumn::RoleLabel.is_not_null()
+        } else {
+            ClassUserColumn::RoleLabel.is_not_in(keys)
+        });
+        for label in &labels.labels {
+            stale = stale.add(
+                Condition::all()
+                    .add(ClassUserColumn::RoleLabel.eq(label.key.clone()))
+                    .add(ClassUserColumn::Role.ne(label.role.to_string())),
+            );
+        }
+        ClassUsers::update_many()
+            .col_expr(
+                ClassUserColumn::RoleLabel,
+                Expr::value(Option::<String>::None),
+            )
+            .filter(ClassUserColumn::ClassId.eq(class_id))
+            .filter(ClassUserColumn::RoleLabel.is_not_null())
+            .filter(stale)
+            .exec(&txn)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("清除失效的角色名称失败: {e}"))
+            })?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(())
+    }
+}
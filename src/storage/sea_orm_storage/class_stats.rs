@@ -0,0 +1,208 @@
+//! 班级每日统计快照存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use super::batch::insert_chunked;
+use crate::entity::class_stats_daily::{
+    ActiveModel as ClassStatsActiveModel, Column as ClassStatsColumn, Entity as ClassStatsDaily,
+};
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::ClassStatsDaily as ClassStatsDailyItem;
+use sea_orm::sea_query::{Condition, Expr, Func};
+use sea_orm::{
+    ColumnTrait, EntityTrait, ExprTrait, JoinType, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait, Set, TransactionTrait,
+};
+
+/// 单个班级的快照汇总
+#[derive(Default)]
+struct SnapshotRow {
+    member_count: i64,
+    student_count: i64,
+    submission_count: i64,
+    graded_count: i64,
+    score_percent_sum: f64,
+}
+
+impl SeaOrmStorage {
+    /// 生成指定日期的班级统计快照（每个班级一条，重复生成会覆盖）
+    ///
+    /// 指标均为截至当天结束（UTC）的累计值；当天结束前已归档的班级不再生成快照。
+    pub async fn generate_class_stats_snapshots_impl(
+        &self,
+        stat_date: chrono::NaiveDate,
+    ) -> Result<i64> {
+        let date = stat_date.format("%Y-%m-%d").to_string();
+        let day_end = stat_date
+            .succ_opt()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc().timestamp())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+        let class_ids: Vec<i64> = Classes::find()
+            .select_only()
+            .column(ClassColumn::Id)
+            .filter(ClassColumn::CreatedAt.lt(day_end))
+            .filter(
+                Condition::any()
+                    .add(ClassColumn::ArchivedAt.is_null())
+                    .add(ClassColumn::ArchivedAt.gte(day_end)),
+            )
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?;
+        let mut rows: HashMap<i64, SnapshotRow> = class_ids
+            .into_iter()
+            .map(|class_id| (class_id, SnapshotRow::default()))
+            .collect();
+
+        // 1. 成员数与学生数
+        let members: Vec<(i64, String, i64)> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::ClassId)
+            .column(ClassUserColumn::Role)
+            .column_as(
+                Func::count(Expr::col((ClassUsers, ClassUserColumn::Id))),
+                "count",
+            )
+            .filter(ClassUserColumn::JoinedAt.lt(day_end))
+            .group_by(ClassUserColumn::ClassId)
+            .group_by(ClassUserColumn::Role)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级人数失败: {e}")))?;
+        for (class_id, role, count) in members {
+            if let Some(row) = rows.get_mut(&class_id) {
+                row.member_count += count;
+                if role != ClassUserRole::Teacher.to_string() {
+                    row.student_count += count;
+                }
+            }
+        }
+
+        // 2. 累计提交数（含重交版本）
+        let submissions: Vec<(i64, i64)> = Submissions::find()
+            .select_only()
+            .column(HomeworkColumn::ClassId)
+            .column_as(
+                Func::count(Expr::col((Submissions, SubmissionColumn::Id))),
+                "count",
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::submissions::Relation::Homework.def(),
+            )
+            .filter(SubmissionColumn::SubmittedAt.lt(day_end))
+            .group_by(HomeworkColumn::ClassId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级提交失败: {e}")))?;
+        for (class_id, count) in submissions {
+            if let Some(row) = rows.get_mut(&class_id) {
+                row.submission_count = count;
+            }
+        }
+
+        // 3. 累计评分数与得分率之和（乘以浮点参数，保证不同数据库的 SUM 结果均为浮点类型）
+        let score_percent = Expr::col((Grades, GradeColumn::Score))
+            .mul(100.0_f64)
+            .div(Expr::col((Homeworks, HomeworkColumn::MaxScore)));
+        let grades: Vec<(i64, i64, Option<f64>)> = Grades::find()
+            .select_only()
+            .column(HomeworkColumn::ClassId)
+            .column_as(Func::count(Expr::col((Grades, GradeColumn::Id))), "count")
+            .column_as(Func::sum(score_percent), "score_percent_sum")
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::grades::Relation::Submission.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::submissions::Relation::Homework.def(),
+            )
+            .filter(GradeColumn::GradedAt.lt(day_end))
+            .filter(HomeworkColumn::MaxScore.gt(0.0))
+            .group_by(HomeworkColumn::ClassId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计班级评分失败: {e}")))?;
+        for (class_id, count, percent_sum) in grades {
+            if let Some(row) = rows.get_mut(&class_id) {
+                row.graded_count = count;
+                row.score_percent_sum = percent_sum.unwrap_or(0.0);
+            }
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        // 覆盖同日期的旧快照
+        ClassStatsDaily::delete_many()
+            .filter(ClassStatsColumn::StatDate.eq(&date))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除旧班级快照失败: {e}")))?;
+
+        let now = chrono::Utc::now().timestamp();
+        let generated = rows.len() as i64;
+        let models = rows.into_iter().map(|(class_id, row)| {
+            let average_score_percent =
+                (row.graded_count > 0).then(|| row.score_percent_sum / row.graded_count as f64);
+            ClassStatsActiveModel {
+                class_id: Set(class_id),
+                stat_date: Set(date.clone()),
+                member_count: Set(row.member_count),
+                student_count: Set(row.student_count),
+                submission_count: Set(row.submission_count),
+                graded_count: Set(row.graded_count),
+                average_score_percent: Set(average_score_percent),
+                created_at: Set(now),
+                ..Default::default()
+            }
+        });
+        insert_chunked!(ClassStatsDaily, models, &txn, "班级快照");
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(generated)
+    }
+
+    /// 查询班级在日期区间（YYYY-MM-DD，含两端）内的统计快照，按日期升序
+    pub async fn list_class_stats_daily_impl(
+        &self,
+        class_id: i64,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<ClassStatsDailyItem>> {
+        // 日期为 YYYY-MM-DD 格式，可直接按字符串比较
+        let results = ClassStatsDaily::find()
+            .filter(ClassStatsColumn::ClassId.eq(class_id))
+            .filter(ClassStatsColumn::StatDate.gte(from))
+            .filter(ClassStatsColumn::StatDate.lte(to))
+            .order_by_asc(ClassStatsColumn::StatDate)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级快照失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_class_stats_daily())
+            .collect())
+    }
+}
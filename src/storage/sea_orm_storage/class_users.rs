@@ -1,24 +1,36 @@
 //! 班级用户关联存储操作
 
 use super::SeaOrmStorage;
+use super::activity_events::insert_activity_event;
 use crate::entity::class_users::{ActiveModel, Column, Entity as ClassUsers};
 use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::prelude::UserAuditLogActiveModel;
 use crate::entity::users::{self, Entity as Users};
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     PaginationInfo,
     class_users::{
-        entities::{ClassUser, ClassUserRole},
+        entities::{ClassUser, ClassUserBatchAction, ClassUserBatchOutcome, ClassUserRole},
         requests::{ClassUserQuery, UpdateClassUserRequest},
         responses::ClassUserListResponse,
     },
-    classes::{entities::Class, requests::ClassListQuery, responses::ClassListResponse},
+    classes::{
+        entities::{ActivityEventType, Class, NewActivityEvent},
+        requests::ClassListQuery,
+        responses::ClassListResponse,
+    },
+    common::PaginationPolicy,
 };
 use crate::utils::escape_like_pattern;
+use sea_orm::sea_query::Expr;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    Set,
+    QuerySelect, Set, TransactionTrait,
 };
+use std::collections::HashMap;
+
+/// 批量统计时每次查询的用户数
+const COUNT_CHUNK_SIZE: usize = 500;
 
 impl SeaOrmStorage {
     /// 获取班级成员数量
@@ -32,28 +44,88 @@ impl SeaOrmStorage {
         Ok(count as i64)
     }
 
-    /// 加入班级
+    /// 批量统计用户加入的班级数量
+    pub async fn count_classes_by_user_ids_impl(
+        &self,
+        user_ids: &[i64],
+    ) -> Result<HashMap<i64, i64>> {
+        let mut counts = HashMap::new();
+
+        // 分批查询，避免 IN 参数过多
+        for chunk in user_ids.chunks(COUNT_CHUNK_SIZE) {
+            let rows: Vec<i64> = ClassUsers::find()
+                .select_only()
+                .column(Column::UserId)
+                .filter(Column::UserId.is_in(chunk.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("统计用户班级数量失败: {e}"))
+                })?;
+            for user_id in rows {
+                *counts.entry(user_id).or_insert(0) += 1;
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// 加入班级（学生与课代表加入时同时写入成员加入动态）
     pub async fn join_class_impl(
         &self,
         user_id: i64,
         class_id: i64,
         role: ClassUserRole,
+        profile_name: Option<String>,
     ) -> Result<ClassUser> {
         let now = chrono::Utc::now().timestamp();
 
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
         let model = ActiveModel {
             class_id: Set(class_id),
             user_id: Set(user_id),
             role: Set(role.to_string()),
+            profile_name: Set(profile_name),
             joined_at: Set(now),
             ..Default::default()
         };
 
         let result = model
-            .insert(&self.db)
+            .insert(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("加入班级失败: {e}")))?;
 
+        // 教师随创建班级加入，不记录成员加入动态
+        if role != ClassUserRole::Teacher {
+            let user = Users::find_by_id(user_id)
+                .one(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?;
+            if let Some(user) = user {
+                insert_activity_event(
+                    &txn,
+                    NewActivityEvent {
+                        class_id,
+                        event_type: ActivityEventType::MemberJoined,
+                        actor_id: Some(user_id),
+                        reference_id: None,
+                        title: user.display_name.unwrap_or(user.username),
+                    },
+                )
+                .await?;
+            }
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
         Ok(result.into_class_user())
     }
 
@@ -72,6 +144,110 @@ impl SeaOrmStorage {
         Ok(result.rows_affected > 0)
     }
 
+    /// 批量修改成员角色或移出班级，并写入一条汇总审计日志
+    ///
+    /// 在同一事务中执行；不是班级成员的用户记入 `missing`，不做修改。修改角色时清除成员的角色名称。
+    pub async fn batch_update_class_users_impl(
+        &self,
+        class_id: i64,
+        action: ClassUserBatchAction,
+        role: Option<ClassUserRole>,
+        user_ids: &[i64],
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<ClassUserBatchOutcome> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let members: HashMap<i64, String> = ClassUsers::find()
+            .select_only()
+            .column(Column::UserId)
+            .column(Column::Role)
+            .filter(Column::ClassId.eq(class_id))
+            .filter(Column::UserId.is_in(user_ids.iter().copied()))
+            .into_tuple::<(i64, String)>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级用户失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        let mut outcome = ClassUserBatchOutcome::default();
+        for &user_id in user_ids {
+            match members.get(&user_id) {
+                None => outcome.missing.push(user_id),
+                Some(current) => {
+                    let changed = match &role {
+                        Some(role) if action == ClassUserBatchAction::SetRole => {
+                            *current != role.to_string()
+                        }
+                        _ => true,
+                    };
+                    if changed {
+                        outcome.changed.push(user_id);
+                    }
+                }
+            }
+        }
+
+        if !outcome.changed.is_empty() {
+            let filter = Condition::all()
+                .add(Column::ClassId.eq(class_id))
+                .add(Column::UserId.is_in(outcome.changed.clone()));
+            match (action, &role) {
+                (ClassUserBatchAction::SetRole, Some(role)) => {
+                    ClassUsers::update_many()
+                        .col_expr(Column::Role, Expr::value(role.to_string()))
+                        .col_expr(Column::RoleLabel, Expr::value(Option::<String>::None))
+                        .filter(filter)
+                        .exec(&txn)
+                        .await
+                        .map_err(|e| {
+                            HWSystemError::database_operation(format!("批量修改角色失败: {e}"))
+                        })?;
+                }
+                _ => {
+                    ClassUsers::delete_many()
+                        .filter(filter)
+                        .exec(&txn)
+                        .await
+                        .map_err(|e| {
+                            HWSystemError::database_operation(format!("批量移出班级失败: {e}"))
+                        })?;
+                }
+            }
+        }
+
+        let mut detail = format!(
+            "class_id={class_id},action={action},changed={:?},missing={:?}",
+            outcome.changed, outcome.missing
+        );
+        if let Some(role) = &role {
+            detail.push_str(&format!(",role={role}"));
+        }
+        UserAuditLogActiveModel {
+            user_id: Set(operator_id),
+            action: Set("class_users_batch".to_string()),
+            detail: Set(Some(detail)),
+            operator_id: Set(operator_id),
+            ip_address: Set(ip_address),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(outcome)
+    }
+
     /// 更新班级用户信息（通过 user_id 和 class_id）
     pub async fn update_class_user_impl(
         &self,
@@ -98,9 +274,21 @@ impl SeaOrmStorage {
         };
 
         if let Some(role) = update.role {
+            // 角色变化时原角色名称失效
+            if update.role_label.is_none() && role.to_string() != class_user.role {
+                model.role_label = Set(None);
+            }
             model.role = Set(role.to_string());
         }
 
+        if let Some(role_label) = update.role_label {
+            model.role_label = Set(Some(role_label).filter(|l| !l.is_empty()));
+        }
+
+        if let Some(profile_name) = update.profile_name {
+            model.profile_name = Set(Some(profile_name).filter(|n| !n.is_empty()));
+        }
+
         let result = model
             .update(&self.db)
             .await
@@ -109,14 +297,38 @@ impl SeaOrmStorage {
         Ok(Some(result.into_class_user()))
     }
 
+    /// 批量查询班级成员的班级内姓名（未设置的成员不在结果中）
+    pub(super) async fn list_profile_names_impl(
+        &self,
+        class_id: i64,
+        user_ids: &[i64],
+    ) -> Result<HashMap<i64, String>> {
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let rows: Vec<(i64, String)> = ClassUsers::find()
+            .select_only()
+            .column(Column::UserId)
+            .column(Column::ProfileName)
+            .filter(Column::ClassId.eq(class_id))
+            .filter(Column::UserId.is_in(user_ids.iter().copied()))
+            .filter(Column::ProfileName.is_not_null())
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级内姓名失败: {e}")))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
     /// 分页列出班级用户
     pub async fn list_class_users_with_pagination_impl(
         &self,
         class_id: i64,
         query: ClassUserQuery,
     ) -> Result<ClassUserListResponse> {
-        let page = query.page.unwrap_or(1).max(1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("class_users").resolve(query.page, query.size);
 
         let mut select = ClassUsers::find()
             .filter(Column::ClassId.eq(class_id))
@@ -127,7 +339,7 @@ impl SeaOrmStorage {
             select = select.filter(Column::Role.eq(role.to_string()));
         }
 
-        // search 过滤 - 查 users 表的 username/display_name
+        // search 过滤 - 查 users 表的 username/display_name 及班级内姓名
         if let Some(ref search) = query.search
             && !search.trim().is_empty()
         {
@@ -145,7 +357,11 @@ impl SeaOrmStorage {
                 .map(|u| u.id)
                 .collect();
 
-            select = select.filter(Column::UserId.is_in(matching_user_ids));
+            select = select.filter(
+                Condition::any()
+                    .add(Column::UserId.is_in(matching_user_ids))
+                    .add(Column::ProfileName.contains(&escaped)),
+            );
         }
 
         // 分页查询
@@ -182,8 +398,8 @@ impl SeaOrmStorage {
         user_id: i64,
         query: ClassListQuery,
     ) -> Result<ClassListResponse> {
-        let page = query.page.unwrap_or(1).max(1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("classes").resolve(query.page, query.size);
 
         // 查询用户加入的班级 ID
         let class_user_records = ClassUsers::find()
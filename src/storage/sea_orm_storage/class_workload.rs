@@ -0,0 +1,126 @@
+//! 班级学生作业负荷统计
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::classes::entities::ClassWorkloadDay;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+impl SeaOrmStorage {
+    /// 统计班级学生从 `from` 起 `days` 天内每天（UTC）截止的作业数
+    ///
+    /// 覆盖本班学生（含课代表）所在的全部未归档班级；只返回聚合人数，不暴露具体学生与班级。
+    pub async fn get_class_workload_impl(
+        &self,
+        class_id: i64,
+        from: chrono::NaiveDate,
+        days: i64,
+    ) -> Result<Vec<ClassWorkloadDay>> {
+        let from_ts = from.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+        let to_ts = from_ts + days * SECONDS_PER_DAY;
+
+        // 1. 本班学生
+        let student_ids: Vec<i64> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::UserId)
+            .filter(ClassUserColumn::ClassId.eq(class_id))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+
+        // 2. 这些学生以学生身份加入的全部班级（含本班）
+        let memberships: Vec<(i64, i64)> = if student_ids.is_empty() {
+            vec![]
+        } else {
+            ClassUsers::find()
+                .select_only()
+                .column(ClassUserColumn::ClassId)
+                .column(ClassUserColumn::UserId)
+                .filter(ClassUserColumn::UserId.is_in(student_ids.iter().copied()))
+                .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("查询学生所在班级失败: {e}"))
+                })?
+        };
+        let mut class_students: HashMap<i64, Vec<i64>> = HashMap::new();
+        for (member_class_id, user_id) in memberships {
+            class_students
+                .entry(member_class_id)
+                .or_default()
+                .push(user_id);
+        }
+
+        // 3. 排除已归档的班级
+        let active_class_ids: Vec<i64> = if class_students.is_empty() {
+            vec![]
+        } else {
+            Classes::find()
+                .select_only()
+                .column(ClassColumn::Id)
+                .filter(ClassColumn::Id.is_in(class_students.keys().copied()))
+                .filter(ClassColumn::ArchivedAt.is_null())
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?
+        };
+
+        // 4. 统计区间内截止的作业
+        let homeworks: Vec<(i64, i64, Option<i64>)> = if active_class_ids.is_empty() {
+            vec![]
+        } else {
+            Homeworks::find()
+                .select_only()
+                .column(HomeworkColumn::Id)
+                .column(HomeworkColumn::ClassId)
+                .column(HomeworkColumn::Deadline)
+                .filter(HomeworkColumn::ClassId.is_in(active_class_ids))
+                .filter(HomeworkColumn::Deadline.gte(from_ts))
+                .filter(HomeworkColumn::Deadline.lt(to_ts))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?
+        };
+
+        // 5. 按天汇总：作业数、有作业截止的学生数、单个学生最多的截止数
+        let mut homework_ids: Vec<HashSet<i64>> = vec![HashSet::new(); days as usize];
+        let mut per_student: Vec<HashMap<i64, i64>> = vec![HashMap::new(); days as usize];
+        for (homework_id, hw_class_id, deadline) in homeworks {
+            let Some(deadline) = deadline else {
+                continue;
+            };
+            let day = ((deadline - from_ts) / SECONDS_PER_DAY) as usize;
+            homework_ids[day].insert(homework_id);
+            for user_id in class_students.get(&hw_class_id).into_iter().flatten() {
+                *per_student[day].entry(*user_id).or_default() += 1;
+            }
+        }
+
+        Ok(homework_ids
+            .into_iter()
+            .zip(per_student)
+            .enumerate()
+            .map(|(offset, (ids, counts))| ClassWorkloadDay {
+                date: (from + chrono::Days::new(offset as u64))
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                homework_count: ids.len() as i64,
+                students_with_due: counts.len() as i64,
+                max_per_student: counts.values().copied().max().unwrap_or(0),
+            })
+            .collect())
+    }
+}
@@ -1,6 +1,7 @@
 //! 班级存储操作
 
 use super::SeaOrmStorage;
+use super::organizations::tenant_condition;
 use crate::entity::classes::{ActiveModel, Column, Entity as Classes};
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
@@ -10,6 +11,7 @@ use crate::models::{
         requests::{ClassListQuery, CreateClassRequest, UpdateClassRequest},
         responses::ClassListResponse,
     },
+    common::PaginationPolicy,
 };
 use crate::utils::{escape_like_pattern, random_code::generate_random_code};
 use sea_orm::{
@@ -27,8 +29,15 @@ impl SeaOrmStorage {
             HWSystemError::database_operation("teacher_id must be set before calling create_class")
         })?;
 
+        // 班级归属负责教师所在的组织
+        let org_id = self
+            .get_user_by_id_impl(teacher_id)
+            .await?
+            .and_then(|teacher| teacher.org_id);
+
         let model = ActiveModel {
             teacher_id: Set(teacher_id),
+            org_id: Set(org_id),
             name: Set(req.name),
             description: Set(req.description),
             invite_code: Set(invite_code),
@@ -71,10 +80,10 @@ impl SeaOrmStorage {
         &self,
         query: ClassListQuery,
     ) -> Result<ClassListResponse> {
-        let page = query.page.unwrap_or(1).max(1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("classes").resolve(query.page, query.size);
 
-        let mut select = Classes::find();
+        let mut select = Classes::find().filter(tenant_condition(Column::OrgId, query.tenant));
 
         // 教师筛选
         if let Some(teacher_id) = query.teacher_id {
@@ -148,6 +157,27 @@ impl SeaOrmStorage {
             model.description = Set(Some(description));
         }
 
+        if let Some(allow) = update.allow_student_messages {
+            model.allow_student_messages = Set(allow);
+        }
+
+        if let Some(require) = update.require_real_name {
+            model.require_real_name = Set(require);
+        }
+
+        if let Some(pattern) = update.profile_name_pattern {
+            model.profile_name_pattern = Set(Some(pattern).filter(|p| !p.is_empty()));
+        }
+
+        // 重复归档保留首次归档时间
+        match update.archived {
+            Some(true) if existing.as_ref().is_some_and(|c| c.archived_at.is_none()) => {
+                model.archived_at = Set(Some(now));
+            }
+            Some(false) => model.archived_at = Set(None),
+            _ => {}
+        }
+
         model
             .update(&self.db)
             .await
@@ -0,0 +1,299 @@
+//! 内容举报存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::activity_events::Entity as ActivityEvents;
+use crate::entity::content_reports::{ActiveModel, Column, Entity as ContentReports};
+use crate::entity::grade_mentions::{Column as GradeMentionColumn, Entity as GradeMentions};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homeworks::Entity as Homeworks;
+use crate::entity::messages::Entity as Messages;
+use crate::entity::reactions::{Column as ReactionColumn, Entity as Reactions};
+use crate::entity::submissions::Entity as Submissions;
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::entities::ActivityEventType;
+use crate::models::moderation::entities::{
+    ContentReport, ContentReportAction, ContentReportStatus, ReportContentType, ReportedContent,
+};
+use crate::models::moderation::requests::ContentReportInput;
+use crate::models::reactions::entities::ReactionTargetType;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+
+/// 删除被举报的内容：评语只清空评语文本（保留分数）并移除其 @提及与表情回应，
+/// 公告连同表情回应一并删除，私信直接删除
+async fn delete_reported_content<C: ConnectionTrait>(
+    conn: &C,
+    content_type: ReportContentType,
+    content_id: i64,
+) -> Result<()> {
+    let reaction_target = match content_type {
+        ReportContentType::GradeComment => Some(ReactionTargetType::GradeComment),
+        ReportContentType::Announcement => Some(ReactionTargetType::Announcement),
+        ReportContentType::Message => None,
+    };
+    if let Some(target_type) = reaction_target {
+        Reactions::delete_many()
+            .filter(ReactionColumn::TargetType.eq(target_type.to_string()))
+            .filter(ReactionColumn::TargetId.eq(content_id))
+            .exec(conn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除表情回应失败: {e}")))?;
+    }
+
+    match content_type {
+        ReportContentType::GradeComment => {
+            GradeMentions::delete_many()
+                .filter(GradeMentionColumn::GradeId.eq(content_id))
+                .exec(conn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("删除评语提及失败: {e}")))?;
+            Grades::update_many()
+                .col_expr(GradeColumn::Comment, Expr::value(Option::<String>::None))
+                .col_expr(
+                    GradeColumn::UpdatedAt,
+                    Expr::value(chrono::Utc::now().timestamp()),
+                )
+                .filter(GradeColumn::Id.eq(content_id))
+                .exec(conn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("清空评语失败: {e}")))?;
+        }
+        ReportContentType::Announcement => {
+            ActivityEvents::delete_by_id(content_id)
+                .exec(conn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("删除公告失败: {e}")))?;
+        }
+        ReportContentType::Message => {
+            Messages::delete_by_id(content_id)
+                .exec(conn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("删除私信失败: {e}")))?;
+        }
+    }
+    Ok(())
+}
+
+impl SeaOrmStorage {
+    /// 查询被举报内容的班级、作者与可见范围，内容不存在（或评语为空）时返回 None
+    pub async fn get_reported_content_impl(
+        &self,
+        content_type: ReportContentType,
+        content_id: i64,
+    ) -> Result<Option<ReportedContent>> {
+        match content_type {
+            ReportContentType::GradeComment => {
+                let Some(grade) = Grades::find_by_id(content_id)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+                else {
+                    return Ok(None);
+                };
+                let Some(comment) = grade.comment.filter(|c| !c.trim().is_empty()) else {
+                    return Ok(None);
+                };
+                let Some(submission) = Submissions::find_by_id(grade.submission_id)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?
+                else {
+                    return Ok(None);
+                };
+                let homework = Homeworks::find_by_id(submission.homework_id)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
+                Ok(homework.map(|homework| ReportedContent {
+                    class_id: homework.class_id,
+                    author_id: grade.grader_id,
+                    viewer_id: Some(submission.creator_id),
+                    text: comment,
+                }))
+            }
+            ReportContentType::Announcement => {
+                let event = ActivityEvents::find_by_id(content_id)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("查询班级动态失败: {e}"))
+                    })?;
+                Ok(event.and_then(|event| {
+                    if event.event_type != ActivityEventType::Announcement.to_string() {
+                        return None;
+                    }
+                    event.actor_id.map(|author_id| ReportedContent {
+                        class_id: event.class_id,
+                        author_id,
+                        viewer_id: None,
+                        text: event.title,
+                    })
+                }))
+            }
+            ReportContentType::Message => {
+                let message = Messages::find_by_id(content_id)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| HWSystemError::database_operation(format!("查询私信失败: {e}")))?;
+                Ok(message.map(|message| ReportedContent {
+                    class_id: message.class_id,
+                    author_id: message.sender_id,
+                    viewer_id: Some(message.recipient_id),
+                    text: message.content,
+                }))
+            }
+        }
+    }
+
+    /// 用户是否已举报过该内容且尚未处理
+    pub async fn has_pending_content_report_impl(
+        &self,
+        reporter_id: i64,
+        content_type: ReportContentType,
+        content_id: i64,
+    ) -> Result<bool> {
+        let count = ContentReports::find()
+            .filter(Column::ReporterId.eq(reporter_id))
+            .filter(Column::ContentType.eq(content_type.to_string()))
+            .filter(Column::ContentId.eq(content_id))
+            .filter(Column::Status.eq(ContentReportStatus::Pending.to_string()))
+            .count(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询举报失败: {e}")))?;
+
+        Ok(count > 0)
+    }
+
+    /// 创建举报
+    pub async fn create_content_report_impl(
+        &self,
+        input: ContentReportInput,
+    ) -> Result<ContentReport> {
+        let model = ActiveModel {
+            class_id: Set(input.class_id),
+            reporter_id: Set(input.reporter_id),
+            author_id: Set(input.author_id),
+            content_type: Set(input.content_type.to_string()),
+            content_id: Set(input.content_id),
+            reason: Set(input.reason),
+            excerpt: Set(input.excerpt),
+            status: Set(ContentReportStatus::Pending.to_string()),
+            resolution: Set(None),
+            resolution_note: Set(None),
+            resolved_by: Set(None),
+            resolved_at: Set(None),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建举报失败: {e}")))?;
+
+        Ok(model.into_content_report())
+    }
+
+    /// 列出班级的举报，新记录在前
+    pub async fn list_content_reports_impl(
+        &self,
+        class_id: i64,
+        status: Option<ContentReportStatus>,
+    ) -> Result<Vec<ContentReport>> {
+        let mut select = ContentReports::find().filter(Column::ClassId.eq(class_id));
+        if let Some(status) = status {
+            select = select.filter(Column::Status.eq(status.to_string()));
+        }
+
+        let reports = select
+            .order_by_desc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询举报失败: {e}")))?;
+
+        Ok(reports
+            .into_iter()
+            .map(|m| m.into_content_report())
+            .collect())
+    }
+
+    /// 获取举报
+    pub async fn get_content_report_impl(&self, report_id: i64) -> Result<Option<ContentReport>> {
+        let report = ContentReports::find_by_id(report_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询举报失败: {e}")))?;
+
+        Ok(report.map(|m| m.into_content_report()))
+    }
+
+    /// 处理举报
+    ///
+    /// 同一内容的全部待处理举报按相同方式一并处理；`delete_content` 时在同一事务中删除内容。
+    /// 举报不存在或已被处理时返回 None 且不做任何修改。
+    pub async fn resolve_content_report_impl(
+        &self,
+        report_id: i64,
+        action: ContentReportAction,
+        note: Option<String>,
+        resolver_id: i64,
+    ) -> Result<Option<ContentReport>> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let Some(report) = ContentReports::find_by_id(report_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询举报失败: {e}")))?
+            .filter(|r| r.status == ContentReportStatus::Pending.to_string())
+        else {
+            return Ok(None);
+        };
+
+        // 按状态条件更新，并发处理同一举报时只有一方生效
+        let result = ContentReports::update_many()
+            .col_expr(
+                Column::Status,
+                Expr::value(ContentReportStatus::Resolved.to_string()),
+            )
+            .col_expr(Column::Resolution, Expr::value(Some(action.to_string())))
+            .col_expr(Column::ResolutionNote, Expr::value(note))
+            .col_expr(Column::ResolvedBy, Expr::value(Some(resolver_id)))
+            .col_expr(
+                Column::ResolvedAt,
+                Expr::value(Some(chrono::Utc::now().timestamp())),
+            )
+            .filter(Column::ContentType.eq(report.content_type.clone()))
+            .filter(Column::ContentId.eq(report.content_id))
+            .filter(Column::Status.eq(ContentReportStatus::Pending.to_string()))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新举报失败: {e}")))?;
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+
+        if action == ContentReportAction::DeleteContent {
+            let content_type = report
+                .content_type
+                .parse()
+                .map_err(HWSystemError::database_operation)?;
+            delete_reported_content(&txn, content_type, report.content_id).await?;
+        }
+
+        let report = ContentReports::find_by_id(report_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询举报失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(report.map(|m| m.into_content_report()))
+    }
+}
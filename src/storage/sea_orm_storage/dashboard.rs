@@ -0,0 +1,219 @@
+//! 学生仪表盘存储操作
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_exemptions::{Column as ExemptionColumn, Entity as HomeworkExemptions};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    auth::responses::{
+        DashboardClassProgress, DashboardDeadline, DashboardGrade, StudentDashboard,
+    },
+    class_users::entities::ClassUserRole,
+    submissions::entities::SubmissionStatus,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+
+impl SeaOrmStorage {
+    /// 获取学生首页仪表盘数据
+    ///
+    /// 每类数据一次批量查询，避免按班级/作业逐个查询。
+    pub async fn get_student_dashboard_impl(
+        &self,
+        user_id: i64,
+        upcoming_until: i64,
+        recent_grade_limit: u64,
+    ) -> Result<StudentDashboard> {
+        let now = Utc::now();
+        let now_ts = now.timestamp();
+
+        let unread_notifications = self.get_unread_notification_count_impl(user_id).await?;
+
+        // 1. 以学生身份加入的班级
+        let class_ids: Vec<i64> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::ClassId)
+            .filter(ClassUserColumn::UserId.eq(user_id))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户班级失败: {e}")))?;
+
+        if class_ids.is_empty() {
+            return Ok(StudentDashboard {
+                upcoming_deadlines: vec![],
+                recent_grades: vec![],
+                unread_notifications,
+                class_progress: vec![],
+                goals: vec![],
+                generated_at: now,
+            });
+        }
+
+        let classes: Vec<(i64, String)> = Classes::find()
+            .select_only()
+            .column(ClassColumn::Id)
+            .column(ClassColumn::Name)
+            .filter(ClassColumn::Id.is_in(class_ids.iter().copied()))
+            .order_by_asc(ClassColumn::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?;
+        let class_names: HashMap<i64, String> = classes.iter().cloned().collect();
+
+        // 2. 这些班级的全部作业
+        let homeworks = Homeworks::find()
+            .filter(HomeworkColumn::ClassId.is_in(class_ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?;
+        let homework_ids: Vec<i64> = homeworks.iter().map(|hw| hw.id).collect();
+
+        // 3. 本人的提交与豁免
+        let submissions: Vec<(i64, i64, i32, String)> = if homework_ids.is_empty() {
+            vec![]
+        } else {
+            Submissions::find()
+                .select_only()
+                .column(SubmissionColumn::Id)
+                .column(SubmissionColumn::HomeworkId)
+                .column(SubmissionColumn::Version)
+                .column(SubmissionColumn::Status)
+                .filter(SubmissionColumn::CreatorId.eq(user_id))
+                .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询提交记录失败: {e}")))?
+        };
+        // 最新提交被退回待重交的作业视为未提交
+        let mut latest: HashMap<i64, (i32, &str)> = HashMap::new();
+        for (_, hw_id, version, status) in &submissions {
+            let entry = latest.entry(*hw_id).or_insert((*version, status.as_str()));
+            if *version > entry.0 {
+                *entry = (*version, status.as_str());
+            }
+        }
+        let submitted: HashSet<i64> = latest
+            .into_iter()
+            .filter(|(_, (_, status))| *status != SubmissionStatus::RETURNED)
+            .map(|(hw_id, _)| hw_id)
+            .collect();
+        let submission_homework: HashMap<i64, i64> = submissions
+            .iter()
+            .map(|(submission_id, hw_id, _, _)| (*submission_id, *hw_id))
+            .collect();
+
+        let exempted: HashSet<i64> = HomeworkExemptions::find()
+            .select_only()
+            .column(ExemptionColumn::HomeworkId)
+            .filter(ExemptionColumn::UserId.eq(user_id))
+            .into_tuple::<i64>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        // 4. 即将截止的作业
+        let mut upcoming_deadlines: Vec<DashboardDeadline> = homeworks
+            .iter()
+            .filter(|hw| !exempted.contains(&hw.id))
+            .filter_map(|hw| {
+                let deadline = hw.deadline?;
+                if deadline < now_ts || deadline > upcoming_until {
+                    return None;
+                }
+                Some(DashboardDeadline {
+                    homework_id: hw.id,
+                    class_id: hw.class_id,
+                    class_name: class_names.get(&hw.class_id).cloned().unwrap_or_default(),
+                    title: hw.title.clone(),
+                    deadline: DateTime::<Utc>::from_timestamp(deadline, 0).unwrap_or_default(),
+                    submitted: submitted.contains(&hw.id),
+                })
+            })
+            .collect();
+        upcoming_deadlines.sort_by_key(|d| (d.deadline, d.homework_id));
+
+        // 5. 最近评分
+        let homework_map: HashMap<i64, _> = homeworks.iter().map(|hw| (hw.id, hw)).collect();
+        let grades = if submissions.is_empty() || recent_grade_limit == 0 {
+            vec![]
+        } else {
+            Grades::find()
+                .filter(
+                    GradeColumn::SubmissionId
+                        .is_in(submissions.iter().map(|(submission_id, ..)| *submission_id)),
+                )
+                .order_by_desc(GradeColumn::GradedAt)
+                .limit(recent_grade_limit)
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+        };
+        let recent_grades = grades
+            .into_iter()
+            .filter_map(|grade| {
+                let homework = submission_homework
+                    .get(&grade.submission_id)
+                    .and_then(|hw_id| homework_map.get(hw_id))?;
+                Some(DashboardGrade {
+                    homework_id: homework.id,
+                    homework_title: homework.title.clone(),
+                    submission_id: grade.submission_id,
+                    score: grade.score,
+                    max_score: homework.max_score,
+                    graded_at: DateTime::<Utc>::from_timestamp(grade.graded_at, 0)
+                        .unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        // 6. 各班完成进度
+        let mut totals: HashMap<i64, (i64, i64)> = HashMap::new();
+        for hw in homeworks.iter().filter(|hw| !exempted.contains(&hw.id)) {
+            let entry = totals.entry(hw.class_id).or_default();
+            entry.0 += 1;
+            if submitted.contains(&hw.id) {
+                entry.1 += 1;
+            }
+        }
+        let class_progress = classes
+            .into_iter()
+            .map(|(class_id, class_name)| {
+                let (total, done) = totals.get(&class_id).copied().unwrap_or_default();
+                let percent = if total == 0 {
+                    100.0
+                } else {
+                    (done as f64 * 1000.0 / total as f64).round() / 10.0
+                };
+                DashboardClassProgress {
+                    class_id,
+                    class_name,
+                    total_homeworks: total,
+                    submitted_homeworks: done,
+                    completion_percent: percent,
+                }
+            })
+            .collect();
+
+        Ok(StudentDashboard {
+            upcoming_deadlines,
+            recent_grades,
+            unread_notifications,
+            class_progress,
+            // 学习目标进度由服务层计算填充
+            goals: vec![],
+            generated_at: now,
+        })
+    }
+}
@@ -0,0 +1,108 @@
+//! 作业截止时间冲突查询
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::homeworks::responses::DeadlineConflictItem;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+
+impl SeaOrmStorage {
+    /// 查询截止时间落在 `[from, to]` 内、与班级学生相关的其他作业
+    ///
+    /// 包括本班作业，以及本班学生（含课代表）同时加入的其他未归档班级的作业；
+    /// 在应用层统计每个班级与本班重叠的学生数。
+    pub async fn find_deadline_conflicts_impl(
+        &self,
+        class_id: i64,
+        from: i64,
+        to: i64,
+        exclude_homework_id: Option<i64>,
+    ) -> Result<Vec<DeadlineConflictItem>> {
+        // 1. 本班学生
+        let student_ids: Vec<i64> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::UserId)
+            .filter(ClassUserColumn::ClassId.eq(class_id))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+
+        // 2. 这些学生以学生身份加入的其他班级，按班级统计重叠人数
+        let mut shared_counts: HashMap<i64, i64> = HashMap::new();
+        if !student_ids.is_empty() {
+            let memberships: Vec<i64> = ClassUsers::find()
+                .select_only()
+                .column(ClassUserColumn::ClassId)
+                .filter(ClassUserColumn::UserId.is_in(student_ids.iter().copied()))
+                .filter(ClassUserColumn::ClassId.ne(class_id))
+                .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("查询学生所在班级失败: {e}"))
+                })?;
+            for other_class_id in memberships {
+                *shared_counts.entry(other_class_id).or_default() += 1;
+            }
+        }
+
+        // 3. 排除已归档的班级
+        let mut class_ids: Vec<i64> = shared_counts.keys().copied().collect();
+        class_ids.push(class_id);
+        let class_names: HashMap<i64, String> = Classes::find()
+            .filter(ClassColumn::Id.is_in(class_ids))
+            .filter(ClassColumn::ArchivedAt.is_null())
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?
+            .into_iter()
+            .map(|c| (c.id, c.name))
+            .collect();
+        if class_names.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // 4. 窗口内的作业
+        let mut select = Homeworks::find()
+            .filter(HomeworkColumn::ClassId.is_in(class_names.keys().copied()))
+            .filter(HomeworkColumn::Deadline.between(from, to));
+        if let Some(homework_id) = exclude_homework_id {
+            select = select.filter(HomeworkColumn::Id.ne(homework_id));
+        }
+        let homeworks = select
+            .order_by_asc(HomeworkColumn::Deadline)
+            .order_by_asc(HomeworkColumn::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
+
+        Ok(homeworks
+            .into_iter()
+            .filter_map(|hw| {
+                let deadline = chrono::DateTime::from_timestamp(hw.deadline?, 0)?;
+                let same_class = hw.class_id == class_id;
+                Some(DeadlineConflictItem {
+                    homework_id: hw.id,
+                    title: hw.title,
+                    class_id: hw.class_id,
+                    class_name: class_names.get(&hw.class_id).cloned().unwrap_or_default(),
+                    deadline,
+                    same_class,
+                    shared_student_count: if same_class {
+                        student_ids.len() as i64
+                    } else {
+                        shared_counts.get(&hw.class_id).copied().unwrap_or(0)
+                    },
+                })
+            })
+            .collect())
+    }
+}
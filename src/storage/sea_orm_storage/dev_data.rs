@@ -0,0 +1,371 @@
+//! 压测数据生成（仅开发环境使用）
+//!
+//! 所有数据在同一事务中通过多行 INSERT 批量写入；自增 ID 无法从批量插入中取回，
+//! 因此每一层写入后按本批次特征回查 ID，再生成下一层数据。
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use super::SeaOrmStorage;
+use super::batch::insert_chunked;
+use crate::entity::class_users::{ActiveModel as ClassUserActiveModel, Entity as ClassUsers};
+use crate::entity::classes::{
+    ActiveModel as ClassActiveModel, Column as ClassColumn, Entity as Classes,
+};
+use crate::entity::grades::{ActiveModel as GradeActiveModel, Entity as Grades};
+use crate::entity::homeworks::{
+    ActiveModel as HomeworkActiveModel, Column as HomeworkColumn, Entity as Homeworks,
+};
+use crate::entity::submissions::{
+    ActiveModel as SubmissionActiveModel, Column as SubmissionColumn, Entity as Submissions,
+};
+use crate::entity::users::{ActiveModel as UserActiveModel, Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    class_users::entities::ClassUserRole,
+    homeworks::entities::SubmissionMode,
+    submissions::entities::{SubmissionContentMetrics, SubmissionStatus},
+    system::{requests::GenerateDevDataRequest, responses::DevDataSummary},
+    users::entities::{UserRole, UserStatus},
+};
+use crate::utils::random_code::generate_random_code;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect, Set, TransactionTrait};
+
+/// 生成的作业满分
+const MAX_SCORE: f64 = 100.0;
+
+impl SeaOrmStorage {
+    /// 生成一批压测数据
+    ///
+    /// 每个班级包含一名教师、`students_per_class` 名学生和 `homeworks_per_class` 份作业，
+    /// 学生按比例随机提交并被评分。生成的用户统一使用 `password_hash` 作为密码。
+    pub async fn generate_dev_data_impl(
+        &self,
+        password_hash: &str,
+        req: &GenerateDevDataRequest,
+    ) -> Result<DevDataSummary> {
+        let batch = generate_random_code(6).to_lowercase();
+        let user_prefix = format!("lt_{batch}_");
+        let now = chrono::Utc::now().timestamp();
+
+        let classes = req.classes as usize;
+        let students_per_class = req.students_per_class as usize;
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        // 1. 用户：先教师后学生
+        let mut users = Vec::with_capacity(classes * (students_per_class + 1));
+        for c in 0..classes {
+            users.push(new_user(
+                &user_prefix,
+                &format!("t{c}"),
+                UserRole::Teacher,
+                password_hash,
+                now,
+            ));
+        }
+        for c in 0..classes {
+            for s in 0..students_per_class {
+                users.push(new_user(
+                    &user_prefix,
+                    &format!("c{c}_s{s}"),
+                    UserRole::User,
+                    password_hash,
+                    now,
+                ));
+            }
+        }
+        insert_chunked!(Users, users, &txn, "用户");
+
+        let user_ids: HashMap<String, i64> = Users::find()
+            .select_only()
+            .column(UserColumn::Username)
+            .column(UserColumn::Id)
+            .filter(UserColumn::Username.starts_with(&user_prefix))
+            .into_tuple::<(String, i64)>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("回查用户失败: {e}")))?
+            .into_iter()
+            .collect();
+        let user_id = |suffix: &str| -> Result<i64> {
+            user_ids
+                .get(&format!("{user_prefix}{suffix}"))
+                .copied()
+                .ok_or_else(|| HWSystemError::database_operation("回查用户 ID 失败"))
+        };
+        let teacher_ids = (0..classes)
+            .map(|c| user_id(&format!("t{c}")))
+            .collect::<Result<Vec<_>>>()?;
+
+        // 2. 班级：每名教师一个班级
+        let class_models: Vec<_> = teacher_ids
+            .iter()
+            .enumerate()
+            .map(|(c, &teacher_id)| ClassActiveModel {
+                name: Set(format!("压测班级 {batch}-{c}")),
+                description: Set(Some(format!("压测数据批次 {batch}"))),
+                teacher_id: Set(teacher_id),
+                invite_code: Set(generate_random_code(8)),
+                org_id: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .collect();
+        insert_chunked!(Classes, class_models, &txn, "班级");
+
+        let class_by_teacher: HashMap<i64, i64> = Classes::find()
+            .select_only()
+            .column(ClassColumn::TeacherId)
+            .column(ClassColumn::Id)
+            .filter(ClassColumn::TeacherId.is_in(teacher_ids.clone()))
+            .into_tuple::<(i64, i64)>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("回查班级失败: {e}")))?
+            .into_iter()
+            .collect();
+        let class_ids = teacher_ids
+            .iter()
+            .map(|tid| {
+                class_by_teacher
+                    .get(tid)
+                    .copied()
+                    .ok_or_else(|| HWSystemError::database_operation("回查班级 ID 失败"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // 3. 班级成员
+        let mut members = Vec::with_capacity(classes * (students_per_class + 1));
+        let mut students_by_class: HashMap<i64, Vec<i64>> = HashMap::new();
+        for (c, (&class_id, &teacher_id)) in class_ids.iter().zip(&teacher_ids).enumerate() {
+            members.push(new_member(
+                class_id,
+                teacher_id,
+                ClassUserRole::Teacher,
+                now,
+            ));
+            let students = (0..students_per_class)
+                .map(|s| user_id(&format!("c{c}_s{s}")))
+                .collect::<Result<Vec<_>>>()?;
+            for &student_id in &students {
+                members.push(new_member(
+                    class_id,
+                    student_id,
+                    ClassUserRole::Student,
+                    now,
+                ));
+            }
+            students_by_class.insert(class_id, students);
+        }
+        let member_count = members.len() as i64;
+        insert_chunked!(ClassUsers, members, &txn, "班级成员");
+
+        // 4. 作业：截止时间分布在过去与未来，便于覆盖逾期统计
+        let mut homework_models = Vec::new();
+        for (&class_id, &teacher_id) in class_ids.iter().zip(&teacher_ids) {
+            for h in 0..req.homeworks_per_class as i64 {
+                homework_models.push(HomeworkActiveModel {
+                    class_id: Set(class_id),
+                    title: Set(format!("压测作业 {batch}-{h}")),
+                    description: Set(Some("自动生成的压测作业".to_string())),
+                    max_score: Set(MAX_SCORE),
+                    deadline: Set(Some(now + (h - req.homeworks_per_class as i64 / 2) * 86400)),
+                    allow_late: Set(true),
+                    submission_mode: Set(SubmissionMode::Both.to_string()),
+                    max_content_length: Set(None),
+                    exam_mode: Set(false),
+                    require_self_assessment: Set(false),
+                    show_grade_context: Set(false),
+                    estimated_minutes: Set(None),
+                    difficulty: Set(None),
+                    grace_minutes: Set(None),
+                    created_by: Set(teacher_id),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    ..Default::default()
+                });
+            }
+        }
+        insert_chunked!(Homeworks, homework_models, &txn, "作业");
+
+        let homeworks: Vec<(i64, i64)> = Homeworks::find()
+            .select_only()
+            .column(HomeworkColumn::Id)
+            .column(HomeworkColumn::ClassId)
+            .filter(HomeworkColumn::ClassId.is_in(class_ids.clone()))
+            .into_tuple::<(i64, i64)>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("回查作业失败: {e}")))?;
+        let homework_count = homeworks.len() as i64;
+
+        // 5. 提交：被选中评分的提交直接写为已批改状态
+        let submission_models = build_submissions(
+            &homeworks,
+            &students_by_class,
+            req.submission_rate,
+            req.graded_rate,
+            now,
+        );
+        let submission_count = submission_models.len() as i64;
+        insert_chunked!(Submissions, submission_models, &txn, "提交");
+
+        // 6. 评分：按作业 ID 区间回查已批改的提交，避免超长 IN 列表
+        let mut grade_models = Vec::new();
+        if let (Some(min_hw), Some(max_hw)) = (
+            homeworks.iter().map(|(id, _)| *id).min(),
+            homeworks.iter().map(|(id, _)| *id).max(),
+        ) {
+            let teacher_by_class: HashMap<i64, i64> = class_ids
+                .iter()
+                .copied()
+                .zip(teacher_ids.iter().copied())
+                .collect();
+            let grader_by_homework: HashMap<i64, i64> = homeworks
+                .iter()
+                .filter_map(|(hw_id, class_id)| {
+                    teacher_by_class.get(class_id).map(|tid| (*hw_id, *tid))
+                })
+                .collect();
+
+            let graded: Vec<(i64, i64)> = Submissions::find()
+                .select_only()
+                .column(SubmissionColumn::Id)
+                .column(SubmissionColumn::HomeworkId)
+                .filter(SubmissionColumn::HomeworkId.between(min_hw, max_hw))
+                .filter(SubmissionColumn::Status.eq(SubmissionStatus::GRADED))
+                .into_tuple::<(i64, i64)>()
+                .all(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("回查提交失败: {e}")))?;
+
+            grade_models = build_grades(&graded, &grader_by_homework, now);
+        }
+        let grade_count = grade_models.len() as i64;
+        insert_chunked!(Grades, grade_models, &txn, "评分");
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(DevDataSummary {
+            batch,
+            teachers: classes as i64,
+            students: (classes * students_per_class) as i64,
+            classes: classes as i64,
+            class_members: member_count,
+            homeworks: homework_count,
+            submissions: submission_count,
+            grades: grade_count,
+            elapsed_ms: 0,
+        })
+    }
+}
+
+fn new_user(
+    prefix: &str,
+    suffix: &str,
+    role: UserRole,
+    password_hash: &str,
+    now: i64,
+) -> UserActiveModel {
+    let username = format!("{prefix}{suffix}");
+    UserActiveModel {
+        email: Set(format!("{username}@loadtest.local")),
+        display_name: Set(Some(username.clone())),
+        username: Set(username),
+        password_hash: Set(password_hash.to_string()),
+        role: Set(role.to_string()),
+        status: Set(UserStatus::Active.to_string()),
+        avatar_url: Set(None),
+        last_login: Set(None),
+        org_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+}
+
+fn new_member(class_id: i64, user_id: i64, role: ClassUserRole, now: i64) -> ClassUserActiveModel {
+    ClassUserActiveModel {
+        class_id: Set(class_id),
+        user_id: Set(user_id),
+        role: Set(role.to_string()),
+        joined_at: Set(now),
+        ..Default::default()
+    }
+}
+
+/// 按比例随机生成提交（同步函数：随机数生成器不能跨越 await）
+fn build_submissions(
+    homeworks: &[(i64, i64)],
+    students_by_class: &HashMap<i64, Vec<i64>>,
+    submission_rate: f64,
+    graded_rate: f64,
+    now: i64,
+) -> Vec<SubmissionActiveModel> {
+    let mut rng = rand::rng();
+    let mut models = Vec::new();
+    for (homework_id, class_id) in homeworks {
+        let Some(students) = students_by_class.get(class_id) else {
+            continue;
+        };
+        for &student_id in students {
+            if !rng.random_bool(submission_rate) {
+                continue;
+            }
+            let status = if rng.random_bool(graded_rate) {
+                SubmissionStatus::GRADED
+            } else {
+                SubmissionStatus::PENDING
+            };
+            let content = format!("压测提交内容 #{student_id}");
+            let metrics = SubmissionContentMetrics::from_content(&content);
+            models.push(SubmissionActiveModel {
+                homework_id: Set(*homework_id),
+                creator_id: Set(student_id),
+                version: Set(1),
+                content: Set(Some(content)),
+                word_count: Set(metrics.word_count),
+                char_count: Set(metrics.char_count),
+                status: Set(status.to_string()),
+                is_late: Set(false),
+                submitted_at: Set(now - rng.random_range(0..7 * 86400)),
+                ..Default::default()
+            });
+        }
+    }
+    models
+}
+
+/// 为已批改的提交生成随机分数（集中在 60~100 分）
+fn build_grades(
+    graded: &[(i64, i64)],
+    grader_by_homework: &HashMap<i64, i64>,
+    now: i64,
+) -> Vec<GradeActiveModel> {
+    let mut rng = rand::rng();
+    graded
+        .iter()
+        .filter_map(|(submission_id, homework_id)| {
+            let grader_id = *grader_by_homework.get(homework_id)?;
+            let score = (rng.random_range(60.0..=MAX_SCORE) * 2.0_f64).round() / 2.0;
+            Some(GradeActiveModel {
+                submission_id: Set(*submission_id),
+                grader_id: Set(grader_id),
+                score: Set(score),
+                comment: Set(None),
+                graded_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
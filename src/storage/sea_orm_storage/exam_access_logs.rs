@@ -0,0 +1,83 @@
+//! 考试访问日志存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::exam_access_logs::{ActiveModel, Column, Entity as ExamAccessLogs};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::entities::{ExamAccessEvent, ExamAccessLog};
+use crate::models::homeworks::requests::ExamAccessLogInput;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+impl SeaOrmStorage {
+    /// 记录考试访问事件
+    ///
+    /// IP 与该学生在本作业上一次记录的 IP 不同时，先追加一条 `ip_change` 事件。
+    pub async fn record_exam_access_impl(&self, input: ExamAccessLogInput) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        let previous_ip = ExamAccessLogs::find()
+            .filter(Column::HomeworkId.eq(input.homework_id))
+            .filter(Column::UserId.eq(input.user_id))
+            .filter(Column::IpAddress.is_not_null())
+            .order_by_desc(Column::Id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询考试访问日志失败: {e}")))?
+            .and_then(|log| log.ip_address);
+
+        if let (Some(previous), Some(current)) = (&previous_ip, &input.ip_address)
+            && previous != current
+        {
+            ActiveModel {
+                homework_id: Set(input.homework_id),
+                user_id: Set(input.user_id),
+                event: Set(ExamAccessEvent::IpChange.to_string()),
+                ip_address: Set(Some(current.clone())),
+                user_agent: Set(input.user_agent.clone()),
+                submission_id: Set(None),
+                detail: Set(Some(format!("{previous} -> {current}"))),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("记录考试访问日志失败: {e}")))?;
+        }
+
+        ActiveModel {
+            homework_id: Set(input.homework_id),
+            user_id: Set(input.user_id),
+            event: Set(input.event.to_string()),
+            ip_address: Set(input.ip_address),
+            user_agent: Set(input.user_agent),
+            submission_id: Set(input.submission_id),
+            detail: Set(input.detail),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("记录考试访问日志失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 列出作业的考试访问日志（可限定学生），按记录顺序升序
+    pub async fn list_exam_access_logs_impl(
+        &self,
+        homework_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Vec<ExamAccessLog>> {
+        let mut select = ExamAccessLogs::find().filter(Column::HomeworkId.eq(homework_id));
+        if let Some(user_id) = user_id {
+            select = select.filter(Column::UserId.eq(user_id));
+        }
+
+        let logs = select
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询考试访问日志失败: {e}")))?;
+
+        Ok(logs.into_iter().map(|m| m.into_exam_access_log()).collect())
+    }
+}
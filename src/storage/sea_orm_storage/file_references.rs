@@ -0,0 +1,199 @@
+//! 文件引用反查存储操作
+//!
+//! 由文件反查其被引用的位置：作业附件、提交附件、结业证书签名、角色申请附件与用户头像。
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use super::file_retention::avatar_token;
+use crate::entity::class_certificate_settings::{
+    Column as CertificateSettingColumn, Entity as ClassCertificateSettings,
+};
+use crate::entity::classes::Column as ClassColumn;
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::homework_files::{Column as HomeworkFileColumn, Entity as HomeworkFiles};
+use crate::entity::homeworks::Column as HomeworkColumn;
+use crate::entity::role_requests::{Column as RoleRequestColumn, Entity as RoleRequests};
+use crate::entity::submission_files::{Column as SubmissionFileColumn, Entity as SubmissionFiles};
+use crate::entity::submissions::Column as SubmissionColumn;
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::files::entities::{File, FileReference, FileReferenceKind};
+use sea_orm::{
+    ColumnTrait, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+
+/// 每批查询的文件数
+const CHUNK_SIZE: usize = 500;
+
+impl SeaOrmStorage {
+    /// 列出用户上传的全部文件（最新的在前）
+    pub async fn list_user_files_impl(&self, user_id: i64) -> Result<Vec<File>> {
+        let files = Files::find()
+            .filter(FileColumn::UserId.eq(user_id))
+            .order_by_desc(FileColumn::CreatedAt)
+            .order_by_desc(FileColumn::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+
+        Ok(files.into_iter().map(|m| m.into_file()).collect())
+    }
+
+    /// 反查文件被引用的位置，未被引用的文件不出现在结果中
+    pub async fn list_file_references_impl(
+        &self,
+        files: &[File],
+    ) -> Result<HashMap<i64, Vec<FileReference>>> {
+        let mut refs: HashMap<i64, Vec<FileReference>> = HashMap::new();
+        if files.is_empty() {
+            return Ok(refs);
+        }
+
+        let file_ids: Vec<i64> = files.iter().map(|f| f.id).collect();
+        for chunk in file_ids.chunks(CHUNK_SIZE) {
+            // 作业附件：(文件 ID, 作业 ID, 班级 ID, 作业标题)
+            let homework_refs: Vec<(i64, i64, i64, String)> = HomeworkFiles::find()
+                .select_only()
+                .column(HomeworkFileColumn::FileId)
+                .column(HomeworkColumn::Id)
+                .column(HomeworkColumn::ClassId)
+                .column(HomeworkColumn::Title)
+                .join(
+                    JoinType::InnerJoin,
+                    crate::entity::homework_files::Relation::Homework.def(),
+                )
+                .filter(HomeworkFileColumn::FileId.is_in(chunk.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("查询作业附件引用失败: {e}"))
+                })?;
+            for (file_id, homework_id, class_id, title) in homework_refs {
+                refs.entry(file_id).or_default().push(FileReference {
+                    kind: FileReferenceKind::Homework,
+                    target_id: homework_id,
+                    homework_id: Some(homework_id),
+                    class_id: Some(class_id),
+                    title: Some(title),
+                });
+            }
+
+            // 提交附件：(文件 ID, 提交 ID, 作业 ID, 班级 ID, 作业标题)
+            let submission_refs: Vec<(i64, i64, i64, i64, String)> = SubmissionFiles::find()
+                .select_only()
+                .column(SubmissionFileColumn::FileId)
+                .column(SubmissionColumn::Id)
+                .column(HomeworkColumn::Id)
+                .column(HomeworkColumn::ClassId)
+                .column(HomeworkColumn::Title)
+                .join(
+                    JoinType::InnerJoin,
+                    crate::entity::submission_files::Relation::Submission.def(),
+                )
+                .join(
+                    JoinType::InnerJoin,
+                    crate::entity::submissions::Relation::Homework.def(),
+                )
+                .filter(SubmissionFileColumn::FileId.is_in(chunk.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("查询提交附件引用失败: {e}"))
+                })?;
+            for (file_id, submission_id, homework_id, class_id, title) in submission_refs {
+                refs.entry(file_id).or_default().push(FileReference {
+                    kind: FileReferenceKind::Submission,
+                    target_id: submission_id,
+                    homework_id: Some(homework_id),
+                    class_id: Some(class_id),
+                    title: Some(title),
+                });
+            }
+
+            // 结业证书签名：(文件 ID, 班级 ID, 班级名称)
+            let signature_refs: Vec<(Option<i64>, i64, String)> = ClassCertificateSettings::find()
+                .select_only()
+                .column(CertificateSettingColumn::SignatureFileId)
+                .column(ClassColumn::Id)
+                .column(ClassColumn::Name)
+                .join(
+                    JoinType::InnerJoin,
+                    crate::entity::class_certificate_settings::Relation::Class.def(),
+                )
+                .filter(CertificateSettingColumn::SignatureFileId.is_in(chunk.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("查询证书签名图片失败: {e}"))
+                })?;
+            for (file_id, class_id, name) in signature_refs {
+                let Some(file_id) = file_id else { continue };
+                refs.entry(file_id).or_default().push(FileReference {
+                    kind: FileReferenceKind::CertificateSignature,
+                    target_id: class_id,
+                    homework_id: None,
+                    class_id: Some(class_id),
+                    title: Some(name),
+                });
+            }
+
+            // 角色申请附件：(文件 ID, 申请 ID)
+            let request_refs: Vec<(Option<i64>, i64)> = RoleRequests::find()
+                .select_only()
+                .column(RoleRequestColumn::AttachmentFileId)
+                .column(RoleRequestColumn::Id)
+                .filter(RoleRequestColumn::AttachmentFileId.is_in(chunk.iter().copied()))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("查询角色申请附件失败: {e}"))
+                })?;
+            for (file_id, request_id) in request_refs {
+                let Some(file_id) = file_id else { continue };
+                refs.entry(file_id).or_default().push(FileReference {
+                    kind: FileReferenceKind::RoleRequest,
+                    target_id: request_id,
+                    homework_id: None,
+                    class_id: None,
+                    title: None,
+                });
+            }
+        }
+
+        // 头像地址为自由字符串，与文件保留任务一致按末段路径匹配下载令牌
+        let by_token: HashMap<&str, i64> = files
+            .iter()
+            .map(|f| (f.download_token.as_str(), f.id))
+            .collect();
+        let avatars: Vec<(i64, Option<String>)> = Users::find()
+            .select_only()
+            .column(UserColumn::Id)
+            .column(UserColumn::AvatarUrl)
+            .filter(UserColumn::AvatarUrl.is_not_null())
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户头像失败: {e}")))?;
+        for (user_id, url) in avatars {
+            let Some(token) = url.as_deref().and_then(avatar_token) else {
+                continue;
+            };
+            if let Some(&file_id) = by_token.get(token.as_str()) {
+                refs.entry(file_id).or_default().push(FileReference {
+                    kind: FileReferenceKind::Avatar,
+                    target_id: user_id,
+                    homework_id: None,
+                    class_id: None,
+                    title: None,
+                });
+            }
+        }
+
+        Ok(refs)
+    }
+}
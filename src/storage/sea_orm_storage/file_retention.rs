@@ -0,0 +1,525 @@
+//! 文件保留策略存储操作
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::class_certificate_settings::{
+    Column as CertificateSettingColumn, Entity as ClassCertificateSettings,
+};
+use crate::entity::class_retention_settings::{ActiveModel, Entity as ClassRetentionSettings};
+use crate::entity::classes::Column as ClassColumn;
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::homework_files::{Column as HomeworkFileColumn, Entity as HomeworkFiles};
+use crate::entity::role_requests::{Column as RoleRequestColumn, Entity as RoleRequests};
+use crate::entity::submission_files::{Column as SubmissionFileColumn, Entity as SubmissionFiles};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::files::entities::{
+    ClassRetentionOverride, File, FileRetentionPolicy, RetentionCandidate, RetentionCategory,
+    StorageTier,
+};
+use crate::models::files::requests::UpdateClassRetentionRequest;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, JoinType, QueryFilter,
+    QuerySelect, RelationTrait, Set, TransactionTrait,
+};
+
+/// 附件引用：(文件 ID, 班级 ID, 班级归档时间)
+type AttachmentRef = (i64, i64, Option<i64>);
+
+/// 每批查询/删除的文件数
+const CHUNK_SIZE: usize = 500;
+
+/// 查询提交附件引用，`archived` 指定只查已归档或未归档班级
+async fn submission_refs<C: ConnectionTrait>(
+    conn: &C,
+    archived: bool,
+    file_ids: Option<&[i64]>,
+) -> Result<Vec<AttachmentRef>> {
+    let mut select = SubmissionFiles::find()
+        .select_only()
+        .column(SubmissionFileColumn::FileId)
+        .column(ClassColumn::Id)
+        .column(ClassColumn::ArchivedAt)
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::submission_files::Relation::Submission.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::submissions::Relation::Homework.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::homeworks::Relation::Class.def(),
+        );
+    select = if archived {
+        select.filter(ClassColumn::ArchivedAt.is_not_null())
+    } else {
+        select.filter(ClassColumn::ArchivedAt.is_null())
+    };
+    if let Some(ids) = file_ids {
+        select = select.filter(SubmissionFileColumn::FileId.is_in(ids.iter().copied()));
+    }
+    select
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询提交附件引用失败: {e}")))
+}
+
+/// 查询作业附件引用，`archived` 指定只查已归档或未归档班级
+async fn homework_refs<C: ConnectionTrait>(
+    conn: &C,
+    archived: bool,
+    file_ids: Option<&[i64]>,
+) -> Result<Vec<AttachmentRef>> {
+    let mut select = HomeworkFiles::find()
+        .select_only()
+        .column(HomeworkFileColumn::FileId)
+        .column(ClassColumn::Id)
+        .column(ClassColumn::ArchivedAt)
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::homework_files::Relation::Homework.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::homeworks::Relation::Class.def(),
+        );
+    select = if archived {
+        select.filter(ClassColumn::ArchivedAt.is_not_null())
+    } else {
+        select.filter(ClassColumn::ArchivedAt.is_null())
+    };
+    if let Some(ids) = file_ids {
+        select = select.filter(HomeworkFileColumn::FileId.is_in(ids.iter().copied()));
+    }
+    select
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询作业附件引用失败: {e}")))
+}
+
+/// 查询始终保留的文件：结业证书签名、角色申请附件与用户当前头像
+///
+/// 返回（文件 ID 集合, 头像下载令牌集合）。头像地址为自由字符串，按末段路径匹配下载令牌。
+async fn pinned_files<C: ConnectionTrait>(conn: &C) -> Result<(HashSet<i64>, HashSet<String>)> {
+    let signatures: Vec<Option<i64>> = ClassCertificateSettings::find()
+        .select_only()
+        .column(CertificateSettingColumn::SignatureFileId)
+        .filter(CertificateSettingColumn::SignatureFileId.is_not_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询证书签名图片失败: {e}")))?;
+    let attachments: Vec<Option<i64>> = RoleRequests::find()
+        .select_only()
+        .column(RoleRequestColumn::AttachmentFileId)
+        .filter(RoleRequestColumn::AttachmentFileId.is_not_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询角色申请附件失败: {e}")))?;
+    let avatars: Vec<Option<String>> = Users::find()
+        .select_only()
+        .column(UserColumn::AvatarUrl)
+        .filter(UserColumn::AvatarUrl.is_not_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询用户头像失败: {e}")))?;
+
+    let ids = signatures
+        .into_iter()
+        .chain(attachments)
+        .flatten()
+        .collect();
+    let tokens = avatars
+        .into_iter()
+        .flatten()
+        .filter_map(|url| avatar_token(&url))
+        .collect();
+    Ok((ids, tokens))
+}
+
+/// 从头像地址中提取下载令牌（忽略查询参数）
+pub(super) fn avatar_token(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?.trim_end_matches('/');
+    path.rsplit('/')
+        .next()
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+}
+
+/// 按分类计算保留天数，班级覆盖优先于系统默认值
+fn retention_days(
+    policy: &FileRetentionPolicy,
+    overrides: &HashMap<i64, ClassRetentionOverride>,
+    class_id: i64,
+    category: RetentionCategory,
+) -> i64 {
+    let class = overrides.get(&class_id);
+    match category {
+        RetentionCategory::SubmissionAttachment => class
+            .and_then(|o| o.submission_files_days)
+            .map_or(policy.submission_files_days, i64::from),
+        RetentionCategory::HomeworkAttachment => class
+            .and_then(|o| o.homework_files_days)
+            .map_or(policy.homework_files_days, i64::from),
+        RetentionCategory::Orphan => policy.orphan_files_days,
+    }
+}
+
+impl SeaOrmStorage {
+    /// 获取班级文件保留策略覆盖
+    pub async fn get_class_retention_override_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<ClassRetentionOverride>> {
+        let model = ClassRetentionSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询保留策略失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_class_retention_override()))
+    }
+
+    /// 创建或更新班级文件保留策略覆盖
+    pub async fn upsert_class_retention_override_impl(
+        &self,
+        class_id: i64,
+        req: UpdateClassRetentionRequest,
+        user_id: i64,
+    ) -> Result<ClassRetentionOverride> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = ClassRetentionSettings::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询保留策略失败: {e}")))?;
+
+        let model = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.submission_files_days = Set(req.submission_files_days);
+                active.homework_files_days = Set(req.homework_files_days);
+                active.updated_by = Set(user_id);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    class_id: Set(class_id),
+                    submission_files_days: Set(req.submission_files_days),
+                    homework_files_days: Set(req.homework_files_days),
+                    updated_by: Set(user_id),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存保留策略失败: {e}")))?;
+
+        Ok(model.into_class_retention_override())
+    }
+
+    /// 查找在 `horizon` 时间戳前按保留策略到期的文件
+    ///
+    /// - 附件仅被已归档班级引用时，到期时间取各引用中最晚的「归档时间 + 保留天数」，
+    ///   任一引用永久保留（0 天）或仍被未归档班级引用时不删除；
+    /// - 未被任何附件关联的文件按孤立文件处理，自上传起计算；
+    /// - 结业证书签名、角色申请附件与用户当前头像始终保留。
+    ///
+    /// 指定 `class_id` 时只返回由该班级决定到期时间的附件，不包含孤立文件。
+    pub async fn find_retention_candidates_impl(
+        &self,
+        policy: FileRetentionPolicy,
+        horizon: i64,
+        class_id: Option<i64>,
+    ) -> Result<Vec<RetentionCandidate>> {
+        let (pinned_ids, pinned_tokens) = pinned_files(&self.db).await?;
+
+        // 1. 已归档班级中的附件引用，按文件汇总
+        let mut refs: HashMap<i64, Vec<(i64, i64, RetentionCategory)>> = HashMap::new();
+        for (file_id, ref_class_id, archived_at) in submission_refs(&self.db, true, None).await? {
+            refs.entry(file_id).or_default().push((
+                ref_class_id,
+                archived_at.unwrap_or_default(),
+                RetentionCategory::SubmissionAttachment,
+            ));
+        }
+        for (file_id, ref_class_id, archived_at) in homework_refs(&self.db, true, None).await? {
+            refs.entry(file_id).or_default().push((
+                ref_class_id,
+                archived_at.unwrap_or_default(),
+                RetentionCategory::HomeworkAttachment,
+            ));
+        }
+        if let Some(class_id) = class_id {
+            refs.retain(|_, class_refs| class_refs.iter().any(|r| r.0 == class_id));
+        }
+
+        // 2. 排除仍被未归档班级引用的文件
+        let file_ids: Vec<i64> = refs.keys().copied().collect();
+        for chunk in file_ids.chunks(CHUNK_SIZE) {
+            let active = submission_refs(&self.db, false, Some(chunk))
+                .await?
+                .into_iter()
+                .chain(homework_refs(&self.db, false, Some(chunk)).await?);
+            for (file_id, ..) in active {
+                refs.remove(&file_id);
+            }
+        }
+
+        // 3. 计算每个文件的到期时间
+        let class_ids: HashSet<i64> = refs.values().flatten().map(|r| r.0).collect();
+        let overrides: HashMap<i64, ClassRetentionOverride> = if class_ids.is_empty() {
+            HashMap::new()
+        } else {
+            ClassRetentionSettings::find()
+                .filter(crate::entity::class_retention_settings::Column::ClassId.is_in(class_ids))
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询保留策略失败: {e}")))?
+                .into_iter()
+                .map(|m| (m.class_id, m.into_class_retention_override()))
+                .collect()
+        };
+
+        let mut due: HashMap<i64, (RetentionCategory, Option<i64>, i64)> = HashMap::new();
+        'files: for (file_id, class_refs) in refs {
+            if pinned_ids.contains(&file_id) {
+                continue;
+            }
+            let mut latest: Option<(RetentionCategory, i64, i64)> = None;
+            for (ref_class_id, archived_at, category) in class_refs {
+                let days = retention_days(&policy, &overrides, ref_class_id, category);
+                if days <= 0 {
+                    continue 'files;
+                }
+                let expires_at = archived_at + days * 86400;
+                if latest.is_none_or(|(.., at)| expires_at > at) {
+                    latest = Some((category, ref_class_id, expires_at));
+                }
+            }
+            if let Some((category, ref_class_id, expires_at)) = latest
+                && expires_at <= horizon
+                && class_id.is_none_or(|id| id == ref_class_id)
+            {
+                due.insert(file_id, (category, Some(ref_class_id), expires_at));
+            }
+        }
+
+        // 4. 加载到期附件的文件信息
+        let mut candidates = Vec::with_capacity(due.len());
+        let due_ids: Vec<i64> = due.keys().copied().collect();
+        for chunk in due_ids.chunks(CHUNK_SIZE) {
+            let files = Files::find()
+                .filter(FileColumn::Id.is_in(chunk.iter().copied()))
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+            for file in files {
+                if pinned_tokens.contains(&file.download_token) {
+                    continue;
+                }
+                let (category, ref_class_id, expires_at) = due[&file.id];
+                candidates.push(candidate(file, category, ref_class_id, expires_at));
+            }
+        }
+
+        // 5. 孤立文件：未关联任何作业或提交
+        if class_id.is_none() && policy.orphan_files_days > 0 {
+            let orphan_ttl = policy.orphan_files_days * 86400;
+            let orphans = Files::find()
+                .filter(FileColumn::CreatedAt.lte(horizon - orphan_ttl))
+                .filter(
+                    FileColumn::Id.not_in_subquery(
+                        Query::select()
+                            .column(SubmissionFileColumn::FileId)
+                            .from(SubmissionFiles)
+                            .to_owned(),
+                    ),
+                )
+                .filter(
+                    FileColumn::Id.not_in_subquery(
+                        Query::select()
+                            .column(HomeworkFileColumn::FileId)
+                            .from(HomeworkFiles)
+                            .to_owned(),
+                    ),
+                )
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询孤立文件失败: {e}")))?;
+            for file in orphans {
+                if pinned_ids.contains(&file.id) || pinned_tokens.contains(&file.download_token) {
+                    continue;
+                }
+                let expires_at = file.created_at + orphan_ttl;
+                candidates.push(candidate(file, RetentionCategory::Orphan, None, expires_at));
+            }
+        }
+
+        candidates.sort_by_key(|c| (c.expires_at, c.file_id));
+        Ok(candidates)
+    }
+
+    /// 删除文件记录及其附件关联，返回被删除的文件（磁盘文件由调用方清理）
+    pub async fn purge_files_impl(&self, file_ids: &[i64]) -> Result<Vec<File>> {
+        if file_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let mut purged = Vec::with_capacity(file_ids.len());
+        for chunk in file_ids.chunks(CHUNK_SIZE) {
+            let files = Files::find()
+                .filter(FileColumn::Id.is_in(chunk.iter().copied()))
+                .all(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+
+            SubmissionFiles::delete_many()
+                .filter(SubmissionFileColumn::FileId.is_in(chunk.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("删除提交附件关联失败: {e}"))
+                })?;
+            HomeworkFiles::delete_many()
+                .filter(HomeworkFileColumn::FileId.is_in(chunk.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("删除作业附件关联失败: {e}"))
+                })?;
+            Files::delete_many()
+                .filter(FileColumn::Id.is_in(chunk.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("删除文件记录失败: {e}")))?;
+
+            purged.extend(files.into_iter().map(|f| f.into_file()));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(purged)
+    }
+
+    /// 查找可迁移到冷存储的附件：仅被已归档班级引用、最晚归档时间不晚于 `archived_before`
+    /// 且仍在热存储中的文件。头像、证书签名与角色申请附件始终留在热存储。
+    pub async fn find_tiering_candidates_impl(&self, archived_before: i64) -> Result<Vec<File>> {
+        let (pinned_ids, pinned_tokens) = pinned_files(&self.db).await?;
+
+        let mut latest_archive: HashMap<i64, i64> = HashMap::new();
+        let archived = submission_refs(&self.db, true, None)
+            .await?
+            .into_iter()
+            .chain(homework_refs(&self.db, true, None).await?);
+        for (file_id, _, archived_at) in archived {
+            let at = latest_archive.entry(file_id).or_default();
+            *at = (*at).max(archived_at.unwrap_or_default());
+        }
+        latest_archive
+            .retain(|file_id, at| *at <= archived_before && !pinned_ids.contains(file_id));
+
+        let mut files = Vec::new();
+        let file_ids: Vec<i64> = latest_archive.into_keys().collect();
+        for chunk in file_ids.chunks(CHUNK_SIZE) {
+            let active: HashSet<i64> = submission_refs(&self.db, false, Some(chunk))
+                .await?
+                .into_iter()
+                .chain(homework_refs(&self.db, false, Some(chunk)).await?)
+                .map(|(file_id, ..)| file_id)
+                .collect();
+            let ids = chunk.iter().copied().filter(|id| !active.contains(id));
+            let models = Files::find()
+                .filter(FileColumn::Id.is_in(ids))
+                .filter(FileColumn::StorageTier.eq(StorageTier::Hot.to_string()))
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+            files.extend(
+                models
+                    .into_iter()
+                    .filter(|f| !pinned_tokens.contains(&f.download_token))
+                    .map(|f| f.into_file()),
+            );
+        }
+
+        files.sort_by_key(|f| f.id);
+        Ok(files)
+    }
+
+    /// 更新文件存储层级，文件不存在时返回 false
+    pub async fn set_file_storage_tier_impl(
+        &self,
+        file_id: i64,
+        tier: StorageTier,
+    ) -> Result<bool> {
+        let tiered_at = match tier {
+            StorageTier::Hot => None,
+            StorageTier::Cold => Some(chrono::Utc::now().timestamp()),
+        };
+        let result = Files::update_many()
+            .col_expr(FileColumn::StorageTier, Expr::value(tier.to_string()))
+            .col_expr(FileColumn::TieredAt, Expr::value(tiered_at))
+            .filter(FileColumn::Id.eq(file_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新文件存储层级失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
+
+fn candidate(
+    file: crate::entity::files::Model,
+    category: RetentionCategory,
+    class_id: Option<i64>,
+    expires_at: i64,
+) -> RetentionCandidate {
+    RetentionCandidate {
+        file_id: file.id,
+        download_token: file.download_token,
+        original_name: file.original_name,
+        file_size: file.file_size,
+        category,
+        class_id,
+        expires_at: chrono::DateTime::<chrono::Utc>::from_timestamp(expires_at, 0)
+            .unwrap_or_default(),
+        org_id: file.org_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::avatar_token;
+
+    #[test]
+    fn test_avatar_token_from_download_url() {
+        assert_eq!(
+            avatar_token("/api/v1/files/download/abc-123?size=64").as_deref(),
+            Some("abc-123")
+        );
+        assert_eq!(
+            avatar_token("https://cdn.example.com/avatars/abc/").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(avatar_token(""), None);
+    }
+}
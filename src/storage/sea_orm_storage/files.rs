@@ -1,10 +1,12 @@
 //! 文件存储操作
 
+use std::collections::HashMap;
+
 use super::SeaOrmStorage;
 use crate::config::AppConfig;
 use crate::entity::files::{ActiveModel, Column, Entity as Files};
 use crate::errors::{HWSystemError, Result};
-use crate::models::files::entities::File;
+use crate::models::files::entities::{File, StorageTier};
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, ExprTrait, QueryFilter, Set};
 use uuid::Uuid;
 
@@ -16,6 +18,7 @@ impl SeaOrmStorage {
         stored_name: &str,
         file_size: &i64,
         file_type: &str,
+        code_language: Option<&str>,
         user_id: i64,
     ) -> Result<File> {
         let now = chrono::Utc::now().timestamp();
@@ -24,6 +27,12 @@ impl SeaOrmStorage {
         let file_path = format!("{}/{}", upload_dir, stored_name);
         let download_token = Uuid::new_v4().to_string();
 
+        // 文件归属上传者所在的组织
+        let org_id = self
+            .get_user_by_id_impl(user_id)
+            .await?
+            .and_then(|user| user.org_id);
+
         let model = ActiveModel {
             original_name: Set(original_name.to_string()),
             stored_name: Set(stored_name.to_string()),
@@ -33,6 +42,9 @@ impl SeaOrmStorage {
             download_token: Set(download_token),
             citation_count: Set(0),
             user_id: Set(Some(user_id)),
+            org_id: Set(org_id),
+            code_language: Set(code_language.map(str::to_string)),
+            storage_tier: Set(StorageTier::Hot.to_string()),
             created_at: Set(now),
             ..Default::default()
         };
@@ -66,6 +78,21 @@ impl SeaOrmStorage {
         Ok(result.map(|m| m.into_file()))
     }
 
+    /// 按 ID 批量获取文件信息（不存在的 ID 忽略）
+    pub async fn list_files_by_ids_impl(&self, ids: &[i64]) -> Result<Vec<File>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let files = Files::find()
+            .filter(Column::Id.is_in(ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+
+        Ok(files.into_iter().map(|m| m.into_file()).collect())
+    }
+
     /// 增加文件引用计数
     pub async fn increment_file_citation_impl(&self, file_id: i64) -> Result<bool> {
         use sea_orm::sea_query::Expr;
@@ -83,6 +110,67 @@ impl SeaOrmStorage {
         Ok(result.rows_affected > 0)
     }
 
+    /// 批量增加文件引用计数（每个文件 +1）
+    pub(super) async fn increment_file_citations_impl(&self, file_ids: &[i64]) -> Result<u64> {
+        use sea_orm::sea_query::Expr;
+
+        if file_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = Files::update_many()
+            .col_expr(
+                Column::CitationCount,
+                Expr::col(Column::CitationCount).add(1),
+            )
+            .filter(Column::Id.is_in(file_ids.iter().copied()))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("增加文件引用计数失败: {e}")))?;
+
+        Ok(result.rows_affected)
+    }
+
+    /// 通过下载令牌批量解析文件 ID，并校验文件均属于 `user_id`
+    ///
+    /// 返回令牌到文件 ID 的映射；任一令牌不存在或无权使用时整体失败。
+    pub(super) async fn resolve_owned_files_impl(
+        &self,
+        tokens: &[String],
+        user_id: i64,
+    ) -> Result<HashMap<String, i64>> {
+        if tokens.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let files = Files::find()
+            .filter(Column::DownloadToken.is_in(tokens.iter().cloned()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+        let by_token: HashMap<String, (i64, Option<i64>)> = files
+            .into_iter()
+            .map(|f| (f.download_token, (f.id, f.user_id)))
+            .collect();
+
+        let mut resolved = HashMap::with_capacity(tokens.len());
+        for token in tokens {
+            let &(file_id, owner) = by_token
+                .get(token)
+                .ok_or_else(|| HWSystemError::not_found(format!("文件不存在: {token}")))?;
+
+            // 校验文件所有权
+            if owner != Some(user_id) {
+                return Err(HWSystemError::authorization(format!(
+                    "无权使用此文件: {token}"
+                )));
+            }
+            resolved.insert(token.clone(), file_id);
+        }
+
+        Ok(resolved)
+    }
+
     /// 减少文件引用计数
     pub async fn decrement_file_citation_impl(&self, file_id: i64) -> Result<bool> {
         use sea_orm::sea_query::Expr;
@@ -0,0 +1,104 @@
+//! 评分草稿存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::grade_drafts::{ActiveModel, Column, Entity as GradeDrafts};
+use crate::errors::{HWSystemError, Result};
+use crate::models::grades::{entities::GradeDraft, requests::SaveGradeDraftRequest};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set};
+
+/// 在给定连接（通常为评分事务）中删除评分人对该提交的草稿
+pub(super) async fn delete_grade_draft_in<C: ConnectionTrait>(
+    conn: &C,
+    submission_id: i64,
+    grader_id: i64,
+) -> Result<bool> {
+    let result = GradeDrafts::delete_many()
+        .filter(Column::SubmissionId.eq(submission_id))
+        .filter(Column::GraderId.eq(grader_id))
+        .exec(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("删除评分草稿失败: {e}")))?;
+
+    Ok(result.rows_affected > 0)
+}
+
+impl SeaOrmStorage {
+    /// 保存评分草稿（已存在时整体覆盖）
+    pub async fn upsert_grade_draft_impl(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+        req: SaveGradeDraftRequest,
+    ) -> Result<GradeDraft> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = GradeDrafts::find()
+            .filter(Column::SubmissionId.eq(submission_id))
+            .filter(Column::GraderId.eq(grader_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分草稿失败: {e}")))?;
+
+        let model = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.score = Set(req.score);
+                active.comment = Set(req.comment);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    submission_id: Set(submission_id),
+                    grader_id: Set(grader_id),
+                    score: Set(req.score),
+                    comment: Set(req.comment),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    ..Default::default()
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存评分草稿失败: {e}")))?;
+
+        Ok(model.into_grade_draft())
+    }
+
+    /// 获取评分人对该提交的草稿
+    pub async fn get_grade_draft_impl(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+    ) -> Result<Option<GradeDraft>> {
+        let result = GradeDrafts::find()
+            .filter(Column::SubmissionId.eq(submission_id))
+            .filter(Column::GraderId.eq(grader_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分草稿失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_grade_draft()))
+    }
+
+    /// 删除评分草稿
+    pub async fn delete_grade_draft_impl(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+    ) -> Result<bool> {
+        delete_grade_draft_in(&self.db, submission_id, grader_id).await
+    }
+
+    /// 删除在给定时间之前最后保存的草稿，返回删除数量
+    pub async fn purge_expired_grade_drafts_impl(&self, updated_before: i64) -> Result<u64> {
+        let result = GradeDrafts::delete_many()
+            .filter(Column::UpdatedAt.lt(updated_before))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("清理过期评分草稿失败: {e}")))?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,159 @@
+//! 成绩分布统计存储操作
+//!
+//! 统计样本为作业（或分题）中每位学生最新提交的评分，最新提交尚未评分的学生不计入。
+//! 均值与标准差由 AVG(score) 与 AVG(score * score) 在数据库中计算，中位数按分数排序后取中间值。
+
+use super::SeaOrmStorage;
+use crate::entity::grades::{Column, Entity as Grades};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::grades::entities::GradeStatistics;
+use sea_orm::sea_query::{Expr, Func, Query};
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, ExprTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select,
+};
+
+/// 参与统计的评分：每位学生在作业（或分题）中最新一次提交的评分
+fn latest_grades(homework_id: i64, part_id: Option<i64>) -> Select<Grades> {
+    let part_condition = match part_id {
+        Some(part_id) => SubmissionColumn::PartId.eq(part_id),
+        None => SubmissionColumn::PartId.is_null(),
+    };
+    Grades::find().filter(
+        Column::SubmissionId.in_subquery(
+            Query::select()
+                .expr(Func::max(Expr::col((Submissions, SubmissionColumn::Id))))
+                .from(Submissions)
+                .and_where(SubmissionColumn::HomeworkId.eq(homework_id))
+                .and_where(part_condition)
+                .group_by_col(SubmissionColumn::CreatorId)
+                .to_owned(),
+        ),
+    )
+}
+
+impl SeaOrmStorage {
+    /// 作业（或分题）成绩分布统计
+    pub async fn get_grade_statistics_impl(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+    ) -> Result<Option<GradeStatistics>> {
+        let row: Option<(i64, Option<f64>, Option<f64>, Option<f64>, Option<f64>)> =
+            latest_grades(homework_id, part_id)
+                .select_only()
+                .column_as(Func::count(Expr::col((Grades, Column::Id))), "count")
+                .column_as(Func::avg(Expr::col((Grades, Column::Score))), "mean")
+                .column_as(
+                    Func::avg(
+                        Expr::col((Grades, Column::Score)).mul(Expr::col((Grades, Column::Score))),
+                    ),
+                    "mean_square",
+                )
+                .column_as(Func::min(Expr::col((Grades, Column::Score))), "min")
+                .column_as(Func::max(Expr::col((Grades, Column::Score))), "max")
+                .into_tuple()
+                .one(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("统计成绩分布失败: {e}")))?;
+
+        let Some((count, Some(mean), Some(mean_square), Some(min), Some(max))) = row else {
+            return Ok(None);
+        };
+        if count == 0 {
+            return Ok(None);
+        }
+
+        // 中位数：样本数为偶数时取中间两个分数的平均值
+        let middle: Vec<f64> = latest_grades(homework_id, part_id)
+            .select_only()
+            .column(Column::Score)
+            .order_by_asc(Column::Score)
+            .offset(((count - 1) / 2) as u64)
+            .limit(if count % 2 == 0 { 2 } else { 1 })
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询成绩中位数失败: {e}")))?;
+        let median = middle.iter().sum::<f64>() / middle.len().max(1) as f64;
+
+        // 浮点误差可能使方差略小于 0
+        let stddev = (mean_square - mean * mean).max(0.0).sqrt();
+
+        Ok(Some(GradeStatistics {
+            count,
+            mean: round2(mean),
+            median: round2(median),
+            stddev: round2(stddev),
+            min,
+            max,
+        }))
+    }
+
+    /// 某分数的百分位：低于该分数的人数加同分人数的一半，占总人数的百分比
+    pub async fn get_grade_percentile_impl(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+        score: f64,
+    ) -> Result<Option<f64>> {
+        let total = self
+            .count_latest_grades(homework_id, part_id, Condition::all())
+            .await?;
+        if total == 0 {
+            return Ok(None);
+        }
+        let below = self
+            .count_latest_grades(
+                homework_id,
+                part_id,
+                Condition::all().add(Column::Score.lt(score)),
+            )
+            .await?;
+        let equal = self
+            .count_latest_grades(
+                homework_id,
+                part_id,
+                Condition::all().add(Column::Score.eq(score)),
+            )
+            .await?;
+
+        let percentile = (below as f64 + equal as f64 / 2.0) / total as f64 * 100.0;
+        Ok(Some(round2(percentile)))
+    }
+
+    async fn count_latest_grades(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+        condition: Condition,
+    ) -> Result<u64> {
+        latest_grades(homework_id, part_id)
+            .filter(condition)
+            .count(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计成绩百分位失败: {e}")))
+    }
+
+    /// 列出每位学生最新提交的得分（升序）
+    pub async fn list_latest_grade_scores_impl(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+    ) -> Result<Vec<f64>> {
+        latest_grades(homework_id, part_id)
+            .select_only()
+            .column(Column::Score)
+            .order_by_asc(Column::Score)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询成绩列表失败: {e}")))
+    }
+}
+
+/// 保留两位小数
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -1,25 +1,46 @@
 //! 评分存储操作
 
+use std::collections::{HashMap, HashSet};
+
 use super::SeaOrmStorage;
+use super::activity_events::insert_grade_released_event;
+use super::grade_drafts::delete_grade_draft_in;
+use super::homework_counters::{submitter_state, track_submitter_change};
+use super::organizations::tenant_homework_ids;
+use super::outbox::insert_outbox_events;
+use crate::entity::grade_mentions::{
+    ActiveModel as MentionActiveModel, Column as MentionColumn, Entity as GradeMentions,
+};
 use crate::entity::grades::{ActiveModel, Column, Entity as Grades};
-use crate::entity::submissions::Column as SubmissionColumn;
+use crate::entity::homeworks::Entity as Homeworks;
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::entity::users::Column as UserColumn;
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     PaginationInfo,
+    classes::entities::{ActivityEventType, NewActivityEvent},
+    common::PaginationPolicy,
     grades::{
-        entities::Grade,
+        entities::{Grade, GradeMention},
         requests::{CreateGradeRequest, GradeListQuery, UpdateGradeRequest},
         responses::GradeListResponse,
     },
+    notifications::entities::{NotificationType, ReferenceType},
+    organizations::entities::TenantScope,
+    outbox::entities::OutboxEvent,
     submissions::entities::SubmissionStatus,
 };
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, RelationTrait, Set,
+    QuerySelect, RelationTrait, Set, TransactionTrait,
+    sea_query::{Expr, Query},
 };
 
 impl SeaOrmStorage {
     /// 创建评分
+    ///
+    /// 评分、提交状态、学生通知的发件箱事件与班级评分动态在同一事务中写入，
+    /// 评分人对该提交的草稿随之删除
     pub async fn create_grade_impl(
         &self,
         grader_id: i64,
@@ -27,6 +48,28 @@ impl SeaOrmStorage {
     ) -> Result<Grade> {
         let now = chrono::Utc::now().timestamp();
 
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        // 记录评分前学生的计数状态，用于增量更新作业计数
+        let submitter: Option<(i64, i64)> = Submissions::find_by_id(req.submission_id)
+            .select_only()
+            .column(SubmissionColumn::HomeworkId)
+            .column(SubmissionColumn::CreatorId)
+            .into_tuple()
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
+        let before = match submitter {
+            Some((homework_id, creator_id)) => {
+                Some(submitter_state(&txn, homework_id, creator_id).await?)
+            }
+            None => None,
+        };
+
         let model = ActiveModel {
             submission_id: Set(req.submission_id),
             grader_id: Set(grader_id),
@@ -38,13 +81,69 @@ impl SeaOrmStorage {
         };
 
         let result = model
-            .insert(&self.db)
+            .insert(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("创建评分失败: {e}")))?;
 
-        // 更新提交状态为已评分
-        self.update_submission_status_impl(req.submission_id, SubmissionStatus::GRADED)
+        // 更新提交状态为已评分，并清除工作流中的自定义状态
+        Submissions::update_many()
+            .col_expr(
+                SubmissionColumn::Status,
+                Expr::value(SubmissionStatus::GRADED.to_string()),
+            )
+            .col_expr(
+                SubmissionColumn::WorkflowStatus,
+                Expr::value(Option::<String>::None),
+            )
+            .filter(SubmissionColumn::Id.eq(req.submission_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新提交状态失败: {e}")))?;
+
+        delete_grade_draft_in(&txn, req.submission_id, grader_id).await?;
+
+        if let (Some((homework_id, creator_id)), Some(before)) = (submitter, before) {
+            track_submitter_change(&txn, homework_id, creator_id, before).await?;
+        }
+
+        // 通知学生
+        let target = Submissions::find_by_id(req.submission_id)
+            .find_also_related(Homeworks)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
+        if let Some((submission, Some(homework))) = target {
+            // 班级动态只记录作业有新评分，不包含学生与分数
+            insert_grade_released_event(
+                &txn,
+                NewActivityEvent {
+                    class_id: homework.class_id,
+                    event_type: ActivityEventType::GradeReleased,
+                    actor_id: Some(grader_id),
+                    reference_id: Some(homework.id),
+                    title: homework.title.clone(),
+                },
+            )
+            .await?;
+            insert_outbox_events(
+                &txn,
+                vec![OutboxEvent::Notification {
+                    user_ids: vec![submission.creator_id],
+                    notification_type: NotificationType::GradeReceived,
+                    vars: vec![
+                        ("homework_title".to_string(), homework.title),
+                        ("score".to_string(), result.score.to_string()),
+                    ],
+                    reference_type: Some(ReferenceType::Grade),
+                    reference_id: Some(result.id),
+                }],
+            )
             .await?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
 
         Ok(result.into_grade())
     }
@@ -56,7 +155,7 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
 
-        Ok(result.map(|m| m.into_grade()))
+        self.with_mentions(result.map(|m| m.into_grade())).await
     }
 
     /// 通过提交 ID 获取评分
@@ -70,7 +169,7 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
 
-        Ok(result.map(|m| m.into_grade()))
+        self.with_mentions(result.map(|m| m.into_grade())).await
     }
 
     /// 更新评分
@@ -87,9 +186,11 @@ impl SeaOrmStorage {
 
         let now = chrono::Utc::now().timestamp();
 
+        // 教师修改后不再视为自动记录的零分
         let mut model = ActiveModel {
             id: Set(grade_id),
             updated_at: Set(now),
+            auto_assigned: Set(false),
             ..Default::default()
         };
 
@@ -114,11 +215,26 @@ impl SeaOrmStorage {
         &self,
         query: GradeListQuery,
     ) -> Result<GradeListResponse> {
-        let page = query.page.unwrap_or(1).max(1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) = PaginationPolicy::for_endpoint("grades").resolve(query.page, query.size);
 
         let mut select = Grades::find();
 
+        // 租户筛选（按提交所属作业的班级组织）
+        if query.tenant != TenantScope::All {
+            select = select.filter(
+                Column::SubmissionId.in_subquery(
+                    Query::select()
+                        .column(SubmissionColumn::Id)
+                        .from(Submissions)
+                        .cond_where(
+                            SubmissionColumn::HomeworkId
+                                .in_subquery(tenant_homework_ids(query.tenant)),
+                        )
+                        .to_owned(),
+                ),
+            );
+        }
+
         // 如果指定了 homework_id，需要 join submissions 表
         if let Some(homework_id) = query.homework_id {
             select = select
@@ -159,8 +275,11 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询评分列表失败: {e}")))?;
 
+        let mut items: Vec<Grade> = grades.into_iter().map(|m| m.into_grade()).collect();
+        self.attach_grade_mentions(&mut items).await?;
+
         Ok(GradeListResponse {
-            items: grades.into_iter().map(|m| m.into_grade()).collect(),
+            items,
             pagination: PaginationInfo {
                 page: page as i64,
                 page_size: size as i64,
@@ -169,4 +288,113 @@ impl SeaOrmStorage {
             },
         })
     }
+
+    /// 替换评分评语中的 @提及，返回本次新增的被提及用户
+    pub async fn replace_grade_mentions_impl(
+        &self,
+        grade_id: i64,
+        mentioned_by: i64,
+        user_ids: &[i64],
+    ) -> Result<Vec<i64>> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let existing: HashSet<i64> = GradeMentions::find()
+            .select_only()
+            .column(MentionColumn::UserId)
+            .filter(MentionColumn::GradeId.eq(grade_id))
+            .into_tuple::<i64>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评语提及失败: {e}")))?
+            .into_iter()
+            .collect();
+        let wanted: HashSet<i64> = user_ids.iter().copied().collect();
+
+        let removed: Vec<i64> = existing.difference(&wanted).copied().collect();
+        if !removed.is_empty() {
+            GradeMentions::delete_many()
+                .filter(MentionColumn::GradeId.eq(grade_id))
+                .filter(MentionColumn::UserId.is_in(removed))
+                .exec(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("删除评语提及失败: {e}")))?;
+        }
+
+        // 保持评语中的出现顺序
+        let now = chrono::Utc::now().timestamp();
+        let mut added = Vec::new();
+        for &user_id in user_ids {
+            if existing.contains(&user_id) || added.contains(&user_id) {
+                continue;
+            }
+            MentionActiveModel {
+                grade_id: Set(grade_id),
+                user_id: Set(user_id),
+                mentioned_by: Set(mentioned_by),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("保存评语提及失败: {e}")))?;
+            added.push(user_id);
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(added)
+    }
+
+    async fn with_mentions(&self, grade: Option<Grade>) -> Result<Option<Grade>> {
+        let Some(grade) = grade else {
+            return Ok(None);
+        };
+        let mut grades = [grade];
+        self.attach_grade_mentions(&mut grades).await?;
+        let [grade] = grades;
+        Ok(Some(grade))
+    }
+
+    /// 批量填充评分的 @提及 用户
+    async fn attach_grade_mentions(&self, grades: &mut [Grade]) -> Result<()> {
+        if grades.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<(i64, i64, String, Option<String>)> = GradeMentions::find()
+            .select_only()
+            .column(MentionColumn::GradeId)
+            .column(MentionColumn::UserId)
+            .column(UserColumn::Username)
+            .column(UserColumn::DisplayName)
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::grade_mentions::Relation::User.def(),
+            )
+            .filter(MentionColumn::GradeId.is_in(grades.iter().map(|g| g.id)))
+            .order_by_asc(MentionColumn::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评语提及失败: {e}")))?;
+
+        let mut by_grade: HashMap<i64, Vec<GradeMention>> = HashMap::new();
+        for (grade_id, user_id, username, display_name) in rows {
+            by_grade.entry(grade_id).or_default().push(GradeMention {
+                user_id,
+                username,
+                display_name,
+            });
+        }
+        for grade in grades.iter_mut() {
+            grade.mentions = by_grade.remove(&grade.id).unwrap_or_default();
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,246 @@
+//! 批改时限存储操作
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use super::organizations::tenant_condition;
+use crate::entity::classes::Column as ClassColumn;
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homeworks::Column as HomeworkColumn;
+use crate::entity::submissions::{Column, Entity as Submissions};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::analytics::responses::TeacherGradingLatencyItem;
+use crate::models::organizations::entities::TenantScope;
+use crate::models::submissions::entities::{GradingSlaBreach, SubmissionStatus};
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, JoinType, QueryFilter, QuerySelect,
+    RelationTrait, TransactionTrait,
+};
+
+/// 候选提交：(提交 ID, 作业 ID, 提交者, 分题, 版本, 作业标题, 作业创建者)
+type CandidateRow = (i64, i64, i64, Option<i64>, i32, String, i64);
+
+/// 每批标记的提交数
+const MARK_CHUNK_SIZE: usize = 500;
+
+/// 查询超时未批改的提交
+///
+/// 返回（仍为最新版本的超时提交, 全部候选提交 ID）。已被新版本取代的旧版本不再提醒，
+/// 但仍需返回其 ID 以便标记为已处理。
+async fn find_overdue<C: ConnectionTrait>(
+    conn: &C,
+    tenant: TenantScope,
+    cutoff: i64,
+    only_unnotified: bool,
+) -> Result<(Vec<GradingSlaBreach>, Vec<i64>)> {
+    let mut select = Submissions::find()
+        .select_only()
+        .column(Column::Id)
+        .column(Column::HomeworkId)
+        .column(Column::CreatorId)
+        .column(Column::PartId)
+        .column(Column::Version)
+        .column(HomeworkColumn::Title)
+        .column(HomeworkColumn::CreatedBy)
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::submissions::Relation::Homework.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            crate::entity::homeworks::Relation::Class.def(),
+        )
+        .filter(tenant_condition(ClassColumn::OrgId, tenant))
+        // 已退回的提交等待学生重交，不计入批改时限
+        .filter(Column::Status.is_not_in([SubmissionStatus::GRADED, SubmissionStatus::RETURNED]))
+        .filter(Column::SubmittedAt.lt(cutoff));
+    if only_unnotified {
+        select = select.filter(Column::GradingSlaNotifiedAt.is_null());
+    }
+    let candidates: Vec<CandidateRow> =
+        select.into_tuple().all(conn).await.map_err(|e| {
+            HWSystemError::database_operation(format!("查询超时未批改提交失败: {e}"))
+        })?;
+    if candidates.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+
+    // 同一学生同一分题的最新版本号
+    let homework_ids: HashSet<i64> = candidates.iter().map(|c| c.1).collect();
+    let creator_ids: HashSet<i64> = candidates.iter().map(|c| c.2).collect();
+    let versions: Vec<(i64, i64, Option<i64>, i32)> = Submissions::find()
+        .select_only()
+        .column(Column::HomeworkId)
+        .column(Column::CreatorId)
+        .column(Column::PartId)
+        .column(Column::Version)
+        .filter(Column::HomeworkId.is_in(homework_ids))
+        .filter(Column::CreatorId.is_in(creator_ids))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询提交版本失败: {e}")))?;
+    let mut latest: HashMap<(i64, i64, Option<i64>), i32> = HashMap::new();
+    for (homework_id, creator_id, part_id, version) in versions {
+        let entry = latest
+            .entry((homework_id, creator_id, part_id))
+            .or_insert(0);
+        *entry = (*entry).max(version);
+    }
+
+    let all_ids = candidates.iter().map(|c| c.0).collect();
+    let breaches = candidates
+        .into_iter()
+        .filter(|(_, homework_id, creator_id, part_id, version, ..)| {
+            latest
+                .get(&(*homework_id, *creator_id, *part_id))
+                .is_none_or(|max| version >= max)
+        })
+        .map(
+            |(submission_id, homework_id, _, _, _, homework_title, teacher_id)| GradingSlaBreach {
+                submission_id,
+                homework_id,
+                homework_title,
+                teacher_id,
+            },
+        )
+        .collect();
+
+    Ok((breaches, all_ids))
+}
+
+impl SeaOrmStorage {
+    /// 领取待提醒的超时未批改提交，并标记为已提醒
+    pub async fn claim_grading_sla_breaches_impl(
+        &self,
+        cutoff: i64,
+    ) -> Result<Vec<GradingSlaBreach>> {
+        let now = chrono::Utc::now().timestamp();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let (breaches, all_ids) = find_overdue(&txn, TenantScope::All, cutoff, true).await?;
+        for chunk in all_ids.chunks(MARK_CHUNK_SIZE) {
+            Submissions::update_many()
+                .col_expr(Column::GradingSlaNotifiedAt, Expr::value(now))
+                .filter(Column::Id.is_in(chunk.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("标记批改超时提醒失败: {e}"))
+                })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+        Ok(breaches)
+    }
+
+    /// 按批改教师统计超时未批改的提交数
+    pub async fn count_grading_overdue_by_teacher_impl(
+        &self,
+        tenant: TenantScope,
+        cutoff: i64,
+    ) -> Result<HashMap<i64, i64>> {
+        let (breaches, _) = find_overdue(&self.db, tenant, cutoff, false).await?;
+        let mut counts = HashMap::new();
+        for breach in breaches {
+            *counts.entry(breach.teacher_id).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    /// 按评分教师统计区间内的评分数与平均批改耗时，并附带当前超时未批改数
+    pub async fn get_teacher_grading_latency_impl(
+        &self,
+        tenant: TenantScope,
+        from: i64,
+        to: i64,
+        cutoff: i64,
+    ) -> Result<Vec<TeacherGradingLatencyItem>> {
+        // 求和项乘以浮点参数，保证不同数据库的 SUM 结果均为浮点类型
+        let grading_secs = Expr::col((Grades, GradeColumn::GradedAt))
+            .sub(Expr::col((Submissions, Column::SubmittedAt)))
+            .mul(1.0_f64);
+        let rows: Vec<(i64, i64, Option<f64>)> = Grades::find()
+            .select_only()
+            .column(GradeColumn::GraderId)
+            .column_as(Func::count(Expr::col((Grades, GradeColumn::Id))), "count")
+            .column_as(Func::sum(grading_secs), "grading_secs_sum")
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::grades::Relation::Submission.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::submissions::Relation::Homework.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::homeworks::Relation::Class.def(),
+            )
+            .filter(tenant_condition(ClassColumn::OrgId, tenant))
+            .filter(GradeColumn::GradedAt.between(from, to))
+            .group_by(GradeColumn::GraderId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计批改耗时失败: {e}")))?;
+
+        let overdue = self
+            .count_grading_overdue_by_teacher_impl(tenant, cutoff)
+            .await?;
+
+        let mut stats: HashMap<i64, (i64, f64)> = rows
+            .into_iter()
+            .map(|(grader_id, count, secs)| (grader_id, (count, secs.unwrap_or(0.0))))
+            .collect();
+        for teacher_id in overdue.keys() {
+            stats.entry(*teacher_id).or_insert((0, 0.0));
+        }
+        if stats.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let users: HashMap<i64, (String, Option<String>)> = Users::find()
+            .select_only()
+            .column(UserColumn::Id)
+            .column(UserColumn::Username)
+            .column(UserColumn::DisplayName)
+            .filter(UserColumn::Id.is_in(stats.keys().copied()))
+            .into_tuple::<(i64, String, Option<String>)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询教师信息失败: {e}")))?
+            .into_iter()
+            .map(|(id, username, display_name)| (id, (username, display_name)))
+            .collect();
+
+        let items = stats
+            .into_iter()
+            .map(|(teacher_id, (graded_count, secs_sum))| {
+                let (username, display_name) = users
+                    .get(&teacher_id)
+                    .cloned()
+                    .unwrap_or_else(|| ("未知用户".to_string(), None));
+                TeacherGradingLatencyItem {
+                    teacher_id,
+                    username,
+                    display_name,
+                    graded_count,
+                    average_grading_hours: (graded_count > 0)
+                        .then(|| secs_sum / 3600.0 / graded_count as f64),
+                    overdue_count: overdue.get(&teacher_id).copied().unwrap_or(0),
+                }
+            })
+            .collect();
+
+        Ok(items)
+    }
+}
@@ -0,0 +1,298 @@
+//! 作业统计计数存储操作
+//!
+//! 作业列表的提交/评分人数读取 `homework_counters`，不再每次加载全部提交与评分。
+//! 单个学生的提交、评分与退回在写入事务中比较前后状态增量更新；批量写入（自动零分、数据擦除、
+//! 账号合并等）直接重算受影响的作业。没有计数记录的作业在读取时全量计算后补写。
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_counters::{ActiveModel, Column, Entity as HomeworkCounters};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::submissions::entities::SubmissionStatus;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, PaginatorTrait, QueryFilter, QuerySelect,
+};
+
+/// 全量计算时每批处理的作业数
+const COMPUTE_CHUNK_SIZE: usize = 200;
+
+/// 作业的已提交与已评分人数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct HomeworkCounts {
+    pub submitted: i64,
+    pub graded: i64,
+}
+
+/// 学生在作业中的计数状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct SubmitterState {
+    // 计为已提交：有提交且最新提交未被退回
+    submitted: bool,
+    // 计为已评分：已提交且任一版本有评分
+    graded: bool,
+}
+
+/// 查询学生在作业中的计数状态
+pub(super) async fn submitter_state<C: ConnectionTrait>(
+    conn: &C,
+    homework_id: i64,
+    creator_id: i64,
+) -> Result<SubmitterState> {
+    let submissions: Vec<(i64, i32, String)> = Submissions::find()
+        .select_only()
+        .column(SubmissionColumn::Id)
+        .column(SubmissionColumn::Version)
+        .column(SubmissionColumn::Status)
+        .filter(SubmissionColumn::HomeworkId.eq(homework_id))
+        .filter(SubmissionColumn::CreatorId.eq(creator_id))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询学生提交失败: {e}")))?;
+
+    let Some((_, _, status)) = submissions.iter().max_by_key(|(_, version, _)| *version) else {
+        return Ok(SubmitterState::default());
+    };
+    if status == SubmissionStatus::RETURNED {
+        return Ok(SubmitterState::default());
+    }
+
+    let graded = Grades::find()
+        .filter(GradeColumn::SubmissionId.is_in(submissions.iter().map(|(id, _, _)| *id)))
+        .count(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+        > 0;
+
+    Ok(SubmitterState {
+        submitted: true,
+        graded,
+    })
+}
+
+/// 按学生写入前的状态增量更新作业计数
+///
+/// 在写入事务内调用；作业尚无计数记录时不做处理，读取时会全量计算。
+pub(super) async fn track_submitter_change<C: ConnectionTrait>(
+    conn: &C,
+    homework_id: i64,
+    creator_id: i64,
+    before: SubmitterState,
+) -> Result<()> {
+    let after = submitter_state(conn, homework_id, creator_id).await?;
+    let submitted_delta = after.submitted as i64 - before.submitted as i64;
+    let graded_delta = after.graded as i64 - before.graded as i64;
+    if submitted_delta == 0 && graded_delta == 0 {
+        return Ok(());
+    }
+
+    HomeworkCounters::update_many()
+        .col_expr(
+            Column::SubmittedCount,
+            Expr::col(Column::SubmittedCount).add(submitted_delta),
+        )
+        .col_expr(
+            Column::GradedCount,
+            Expr::col(Column::GradedCount).add(graded_delta),
+        )
+        .col_expr(
+            Column::UpdatedAt,
+            Expr::value(chrono::Utc::now().timestamp()),
+        )
+        .filter(Column::HomeworkId.eq(homework_id))
+        .exec(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("更新作业计数失败: {e}")))?;
+
+    Ok(())
+}
+
+/// 全量计算作业的提交与评分人数
+pub(super) async fn compute_homework_counts<C: ConnectionTrait>(
+    conn: &C,
+    homework_ids: &[i64],
+) -> Result<HashMap<i64, HomeworkCounts>> {
+    let mut counts: HashMap<i64, HomeworkCounts> = homework_ids
+        .iter()
+        .map(|&id| (id, HomeworkCounts::default()))
+        .collect();
+    if homework_ids.is_empty() {
+        return Ok(counts);
+    }
+
+    let submissions: Vec<(i64, i64, i64, i32, String)> = Submissions::find()
+        .select_only()
+        .column(SubmissionColumn::Id)
+        .column(SubmissionColumn::HomeworkId)
+        .column(SubmissionColumn::CreatorId)
+        .column(SubmissionColumn::Version)
+        .column(SubmissionColumn::Status)
+        .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.iter().copied()))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询作业提交失败: {e}")))?;
+
+    // 每个学生的最新提交，最新提交被退回的学生视为未提交
+    let mut latest: HashMap<(i64, i64), (i32, &str)> = HashMap::new();
+    for (_, homework_id, creator_id, version, status) in &submissions {
+        let entry = latest
+            .entry((*homework_id, *creator_id))
+            .or_insert((*version, status.as_str()));
+        if *version > entry.0 {
+            *entry = (*version, status.as_str());
+        }
+    }
+    let submitters: HashSet<(i64, i64)> = latest
+        .into_iter()
+        .filter(|(_, (_, status))| *status != SubmissionStatus::RETURNED)
+        .map(|(key, _)| key)
+        .collect();
+
+    let sub_to_key: HashMap<i64, (i64, i64)> = submissions
+        .iter()
+        .filter(|(_, homework_id, creator_id, _, _)| {
+            submitters.contains(&(*homework_id, *creator_id))
+        })
+        .map(|(id, homework_id, creator_id, _, _)| (*id, (*homework_id, *creator_id)))
+        .collect();
+    let graded_submission_ids: Vec<i64> = if sub_to_key.is_empty() {
+        Vec::new()
+    } else {
+        Grades::find()
+            .select_only()
+            .column(GradeColumn::SubmissionId)
+            .filter(GradeColumn::SubmissionId.is_in(sub_to_key.keys().copied()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+    };
+    let graded: HashSet<(i64, i64)> = graded_submission_ids
+        .iter()
+        .filter_map(|id| sub_to_key.get(id).copied())
+        .collect();
+
+    for (homework_id, _) in &submitters {
+        if let Some(entry) = counts.get_mut(homework_id) {
+            entry.submitted += 1;
+        }
+    }
+    for (homework_id, _) in &graded {
+        if let Some(entry) = counts.get_mut(homework_id) {
+            entry.graded += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// 全量重算并写入作业计数（批量写入后调用）
+pub(super) async fn refresh_homework_counters<C: ConnectionTrait>(
+    conn: &C,
+    homework_ids: &[i64],
+) -> Result<()> {
+    let counts = compute_homework_counts(conn, homework_ids).await?;
+    save_homework_counters(conn, counts).await
+}
+
+/// 计数记录与全量计算结果不一致的作业 ID
+pub(super) async fn homework_counter_mismatches<C: ConnectionTrait>(conn: &C) -> Result<Vec<i64>> {
+    let stored: Vec<(i64, i64, i64)> = HomeworkCounters::find()
+        .select_only()
+        .column(Column::HomeworkId)
+        .column(Column::SubmittedCount)
+        .column(Column::GradedCount)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询作业计数失败: {e}")))?;
+    let mut mismatches = Vec::new();
+    for chunk in stored.chunks(COMPUTE_CHUNK_SIZE) {
+        let homework_ids: Vec<i64> = chunk.iter().map(|(id, _, _)| *id).collect();
+        let expected = compute_homework_counts(conn, &homework_ids).await?;
+        mismatches.extend(chunk.iter().filter_map(|&(id, submitted, graded)| {
+            let actual = expected.get(&id)?;
+            (actual.submitted != submitted || actual.graded != graded).then_some(id)
+        }));
+    }
+    Ok(mismatches)
+}
+
+/// 写入作业计数（已有记录时覆盖）
+async fn save_homework_counters<C: ConnectionTrait>(
+    conn: &C,
+    counts: HashMap<i64, HomeworkCounts>,
+) -> Result<()> {
+    if counts.is_empty() {
+        return Ok(());
+    }
+    let now = chrono::Utc::now().timestamp();
+    let models = counts.into_iter().map(|(homework_id, counts)| ActiveModel {
+        homework_id: sea_orm::Set(homework_id),
+        submitted_count: sea_orm::Set(counts.submitted),
+        graded_count: sea_orm::Set(counts.graded),
+        updated_at: sea_orm::Set(now),
+    });
+
+    HomeworkCounters::insert_many(models)
+        .on_conflict(
+            OnConflict::column(Column::HomeworkId)
+                .update_columns([
+                    Column::SubmittedCount,
+                    Column::GradedCount,
+                    Column::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("保存作业计数失败: {e}")))?;
+
+    Ok(())
+}
+
+impl SeaOrmStorage {
+    /// 读取作业的提交与评分人数，缺少计数记录的作业全量计算后补写
+    pub(super) async fn load_homework_counts_impl(
+        &self,
+        homework_ids: &[i64],
+    ) -> Result<HashMap<i64, HomeworkCounts>> {
+        if homework_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut counts: HashMap<i64, HomeworkCounts> = HomeworkCounters::find()
+            .filter(Column::HomeworkId.is_in(homework_ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业计数失败: {e}")))?
+            .into_iter()
+            .map(|m| {
+                (
+                    m.homework_id,
+                    HomeworkCounts {
+                        submitted: m.submitted_count,
+                        graded: m.graded_count,
+                    },
+                )
+            })
+            .collect();
+
+        let missing: Vec<i64> = homework_ids
+            .iter()
+            .copied()
+            .filter(|id| !counts.contains_key(id))
+            .collect();
+        if !missing.is_empty() {
+            let computed = compute_homework_counts(&self.db, &missing).await?;
+            save_homework_counters(&self.db, computed.clone()).await?;
+            counts.extend(computed);
+        }
+
+        Ok(counts)
+    }
+}
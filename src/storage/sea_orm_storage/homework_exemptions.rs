@@ -0,0 +1,137 @@
+//! 作业豁免存储操作
+
+use std::collections::HashSet;
+
+use super::SeaOrmStorage;
+use crate::entity::homework_exemptions::{ActiveModel, Column, Entity as HomeworkExemptions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::entities::HomeworkExemption;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+impl SeaOrmStorage {
+    /// 创建作业豁免
+    pub async fn create_homework_exemption_impl(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+        reason: Option<String>,
+        created_by: i64,
+    ) -> Result<HomeworkExemption> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            homework_id: Set(homework_id),
+            user_id: Set(user_id),
+            reason: Set(reason),
+            created_by: Set(created_by),
+            created_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建作业豁免失败: {e}")))?;
+
+        Ok(result.into_exemption())
+    }
+
+    /// 获取某学生某作业的豁免记录
+    pub async fn get_homework_exemption_impl(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<Option<HomeworkExemption>> {
+        let result = HomeworkExemptions::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_exemption()))
+    }
+
+    /// 删除作业豁免
+    pub async fn delete_homework_exemption_impl(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<bool> {
+        let result = HomeworkExemptions::delete_many()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除作业豁免失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 列出作业的所有豁免记录
+    pub async fn list_homework_exemptions_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<HomeworkExemption>> {
+        let results = HomeworkExemptions::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免列表失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_exemption()).collect())
+    }
+
+    /// 获取作业被豁免的学生 ID 列表
+    pub async fn get_exempted_user_ids_impl(&self, homework_id: i64) -> Result<Vec<i64>> {
+        let results = HomeworkExemptions::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.user_id).collect())
+    }
+
+    /// 批量查询多个作业的豁免关系，返回 (homework_id, user_id) 集合
+    pub(crate) async fn get_exempted_pairs_impl(
+        &self,
+        homework_ids: &[i64],
+    ) -> Result<HashSet<(i64, i64)>> {
+        if homework_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let results = HomeworkExemptions::find()
+            .filter(Column::HomeworkId.is_in(homework_ids.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| (m.homework_id, m.user_id))
+            .collect())
+    }
+
+    /// 查询某学生在给定作业中被豁免的作业 ID 集合
+    pub(crate) async fn get_user_exempted_homework_ids_impl(
+        &self,
+        user_id: i64,
+        homework_ids: &[i64],
+    ) -> Result<HashSet<i64>> {
+        if homework_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let results = HomeworkExemptions::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::HomeworkId.is_in(homework_ids.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.homework_id).collect())
+    }
+}
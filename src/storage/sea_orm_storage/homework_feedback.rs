@@ -0,0 +1,157 @@
+//! 作业用时与难度反馈存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::homework_feedback::{ActiveModel, Column, Entity as HomeworkFeedbacks};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::entities::HomeworkCalibration;
+use crate::models::homeworks::entities::HomeworkFeedback;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+/// 保留两位小数
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+impl SeaOrmStorage {
+    /// 获取学生对作业的用时与难度反馈
+    pub async fn get_homework_feedback_impl(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<Option<HomeworkFeedback>> {
+        let model = HomeworkFeedbacks::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业反馈失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_homework_feedback()))
+    }
+
+    /// 写入学生的用时与难度反馈，已有反馈时覆盖并更新修改时间
+    pub async fn upsert_homework_feedback_impl(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+        actual_minutes: i32,
+        perceived_difficulty: i32,
+    ) -> Result<HomeworkFeedback> {
+        let now = chrono::Utc::now().timestamp();
+        let model = ActiveModel {
+            homework_id: Set(homework_id),
+            user_id: Set(user_id),
+            actual_minutes: Set(actual_minutes),
+            perceived_difficulty: Set(perceived_difficulty),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        HomeworkFeedbacks::insert(model)
+            .on_conflict(
+                OnConflict::columns([Column::HomeworkId, Column::UserId])
+                    .update_columns([
+                        Column::ActualMinutes,
+                        Column::PerceivedDifficulty,
+                        Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("保存作业反馈失败: {e}")))?;
+
+        self.get_homework_feedback_impl(homework_id, user_id)
+            .await?
+            .ok_or_else(|| HWSystemError::database_operation("保存作业反馈失败: 记录不存在"))
+    }
+
+    /// 汇总班级每个作业的预计用时、难度与学生反馈
+    ///
+    /// 作业按截止时间升序（未设置截止时间的排在最后），没有反馈的作业聚合字段为空。
+    pub async fn get_class_workload_calibration_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<HomeworkCalibration>> {
+        let homeworks = Homeworks::find()
+            .filter(HomeworkColumn::ClassId.eq(class_id))
+            .order_by_asc(HomeworkColumn::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
+        if homeworks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(i64, i32, i32)> = HomeworkFeedbacks::find()
+            .select_only()
+            .column(Column::HomeworkId)
+            .column(Column::ActualMinutes)
+            .column(Column::PerceivedDifficulty)
+            .filter(Column::HomeworkId.is_in(homeworks.iter().map(|h| h.id)))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业反馈失败: {e}")))?;
+        let mut feedback: HashMap<i64, Vec<(i32, i32)>> = HashMap::new();
+        for (homework_id, minutes, difficulty) in rows {
+            feedback
+                .entry(homework_id)
+                .or_default()
+                .push((minutes, difficulty));
+        }
+
+        let mut items: Vec<HomeworkCalibration> = homeworks
+            .into_iter()
+            .map(|homework| {
+                let mut entries = feedback.remove(&homework.id).unwrap_or_default();
+                let count = entries.len();
+                let homework = homework.into_homework();
+
+                let (average_minutes, median_minutes, average_difficulty) = if count == 0 {
+                    (None, None, None)
+                } else {
+                    entries.sort_unstable();
+                    let total: i64 = entries.iter().map(|(m, _)| *m as i64).sum();
+                    let median = if count % 2 == 1 {
+                        entries[count / 2].0 as f64
+                    } else {
+                        (entries[count / 2 - 1].0 + entries[count / 2].0) as f64 / 2.0
+                    };
+                    let difficulty: i64 = entries.iter().map(|(_, d)| *d as i64).sum();
+                    (
+                        Some(total as f64 / count as f64),
+                        Some(median),
+                        Some(difficulty as f64 / count as f64),
+                    )
+                };
+
+                HomeworkCalibration {
+                    homework_id: homework.id,
+                    title: homework.title,
+                    deadline: homework.deadline,
+                    estimated_minutes: homework.estimated_minutes,
+                    difficulty: homework.difficulty,
+                    feedback_count: count as i64,
+                    average_actual_minutes: average_minutes.map(round2),
+                    median_actual_minutes: median_minutes,
+                    average_perceived_difficulty: average_difficulty.map(round2),
+                    time_ratio: average_minutes
+                        .zip(homework.estimated_minutes)
+                        .map(|(actual, estimated)| round2(actual / estimated as f64)),
+                    difficulty_delta: average_difficulty
+                        .zip(homework.difficulty)
+                        .map(|(perceived, preset)| round2(perceived - preset as f64)),
+                }
+            })
+            .collect();
+
+        items.sort_by_key(|item| (item.deadline.is_none(), item.deadline));
+        Ok(items)
+    }
+}
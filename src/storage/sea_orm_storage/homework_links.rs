@@ -0,0 +1,170 @@
+//! 作业外部资源存储操作
+
+use std::collections::HashSet;
+
+use super::SeaOrmStorage;
+use crate::entity::homework_link_opens::{
+    ActiveModel as OpenActiveModel, Column as OpenColumn, Entity as HomeworkLinkOpens,
+};
+use crate::entity::homework_links::{ActiveModel, Column, Entity as HomeworkLinks};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::{
+    entities::{HomeworkLink, HomeworkLinkOpen},
+    requests::HomeworkLinkInput,
+};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+
+impl SeaOrmStorage {
+    /// 列出作业的外部资源（按顺序）
+    pub async fn list_homework_links_impl(&self, homework_id: i64) -> Result<Vec<HomeworkLink>> {
+        let models = HomeworkLinks::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .order_by_asc(Column::Position)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业外部资源失败: {e}")))?;
+
+        Ok(models.into_iter().map(|m| m.into_homework_link()).collect())
+    }
+
+    /// 通过 ID 获取外部资源
+    pub async fn get_homework_link_impl(&self, link_id: i64) -> Result<Option<HomeworkLink>> {
+        let result = HomeworkLinks::find_by_id(link_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业外部资源失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_homework_link()))
+    }
+
+    /// 整体替换作业的外部资源
+    ///
+    /// 带 `id` 的条目更新对应链接（保留打开记录），其余新建；未出现的链接被删除，打开记录级联删除。
+    pub async fn replace_homework_links_impl(
+        &self,
+        homework_id: i64,
+        links: Vec<HomeworkLinkInput>,
+        created_by: i64,
+    ) -> Result<Vec<HomeworkLink>> {
+        let now = chrono::Utc::now().timestamp();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let keep: HashSet<i64> = links.iter().filter_map(|l| l.id).collect();
+        let existing = HomeworkLinks::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业外部资源失败: {e}")))?;
+
+        let removed: Vec<i64> = existing
+            .iter()
+            .map(|m| m.id)
+            .filter(|id| !keep.contains(id))
+            .collect();
+        if !removed.is_empty() {
+            HomeworkLinks::delete_many()
+                .filter(Column::Id.is_in(removed))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("删除作业外部资源失败: {e}"))
+                })?;
+        }
+
+        for (index, link) in links.into_iter().enumerate() {
+            let current = link
+                .id
+                .and_then(|id| existing.iter().find(|m| m.id == id).cloned());
+            let mut active: ActiveModel = match current {
+                Some(model) => model.into(),
+                None => ActiveModel {
+                    homework_id: Set(homework_id),
+                    created_by: Set(created_by),
+                    created_at: Set(now),
+                    ..Default::default()
+                },
+            };
+            active.position = Set(index as i32 + 1);
+            active.link_type = Set(link.link_type.to_string());
+            active.title = Set(link.title);
+            active.url = Set(link.url);
+            active.updated_at = Set(now);
+            active.save(&txn).await.map_err(|e| {
+                HWSystemError::database_operation(format!("保存作业外部资源失败: {e}"))
+            })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        self.list_homework_links_impl(homework_id).await
+    }
+
+    /// 记录学生打开外部资源，重复打开时累加次数并更新最近打开时间
+    pub async fn record_homework_link_open_impl(&self, link_id: i64, user_id: i64) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let model = OpenActiveModel {
+            link_id: Set(link_id),
+            user_id: Set(user_id),
+            open_count: Set(1),
+            first_opened_at: Set(now),
+            last_opened_at: Set(now),
+            ..Default::default()
+        };
+
+        HomeworkLinkOpens::insert(model)
+            .on_conflict(
+                OnConflict::columns([OpenColumn::LinkId, OpenColumn::UserId])
+                    .value(
+                        OpenColumn::OpenCount,
+                        Expr::col((HomeworkLinkOpens, OpenColumn::OpenCount)).add(1),
+                    )
+                    .update_column(OpenColumn::LastOpenedAt)
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("记录资源打开失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 列出作业全部外部资源的打开记录
+    pub async fn list_homework_link_opens_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<HomeworkLinkOpen>> {
+        let link_ids: Vec<i64> = HomeworkLinks::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::HomeworkId.eq(homework_id))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业外部资源失败: {e}")))?;
+        if link_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let models = HomeworkLinkOpens::find()
+            .filter(OpenColumn::LinkId.is_in(link_ids))
+            .order_by_asc(OpenColumn::FirstOpenedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询资源打开记录失败: {e}")))?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| m.into_homework_link_open())
+            .collect())
+    }
+}
@@ -0,0 +1,140 @@
+//! 作业分题存储操作
+
+use std::collections::HashSet;
+
+use super::SeaOrmStorage;
+use crate::entity::homework_parts::{ActiveModel, Column, Entity as HomeworkParts};
+use crate::entity::homeworks::{ActiveModel as HomeworkActiveModel, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::{entities::HomeworkPart, requests::HomeworkPartInput};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+
+impl SeaOrmStorage {
+    /// 列出作业的分题（按顺序）
+    pub async fn list_homework_parts_impl(&self, homework_id: i64) -> Result<Vec<HomeworkPart>> {
+        let models = HomeworkParts::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .order_by_asc(Column::Position)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业分题失败: {e}")))?;
+
+        Ok(models.into_iter().map(|m| m.into_homework_part()).collect())
+    }
+
+    /// 通过 ID 获取分题
+    pub async fn get_homework_part_impl(&self, part_id: i64) -> Result<Option<HomeworkPart>> {
+        let result = HomeworkParts::find_by_id(part_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业分题失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_homework_part()))
+    }
+
+    /// 列出已有提交的分题 ID
+    pub async fn list_submitted_homework_part_ids_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<i64>> {
+        let ids = Submissions::find()
+            .select_only()
+            .column(SubmissionColumn::PartId)
+            .filter(SubmissionColumn::HomeworkId.eq(homework_id))
+            .filter(SubmissionColumn::PartId.is_not_null())
+            .distinct()
+            .into_tuple::<Option<i64>>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询分题提交失败: {e}")))?;
+
+        Ok(ids.into_iter().flatten().collect())
+    }
+
+    /// 整体替换作业分题，并将作业满分同步为各分题满分之和
+    ///
+    /// 带 `id` 的条目更新对应分题，其余新建；未出现的分题被删除。
+    pub async fn replace_homework_parts_impl(
+        &self,
+        homework_id: i64,
+        parts: Vec<HomeworkPartInput>,
+    ) -> Result<Vec<HomeworkPart>> {
+        let now = chrono::Utc::now().timestamp();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let keep: HashSet<i64> = parts.iter().filter_map(|p| p.id).collect();
+        let existing = HomeworkParts::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业分题失败: {e}")))?;
+
+        let removed: Vec<i64> = existing
+            .iter()
+            .map(|m| m.id)
+            .filter(|id| !keep.contains(id))
+            .collect();
+        if !removed.is_empty() {
+            HomeworkParts::delete_many()
+                .filter(Column::Id.is_in(removed))
+                .exec(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("删除作业分题失败: {e}")))?;
+        }
+
+        let total_max_score: f64 = parts.iter().map(|p| p.max_score).sum();
+        for (index, part) in parts.into_iter().enumerate() {
+            let current = part
+                .id
+                .and_then(|id| existing.iter().find(|m| m.id == id).cloned());
+            let mut active: ActiveModel = match current {
+                Some(model) => model.into(),
+                None => ActiveModel {
+                    homework_id: Set(homework_id),
+                    created_at: Set(now),
+                    ..Default::default()
+                },
+            };
+            active.position = Set(index as i32 + 1);
+            active.title = Set(part.title);
+            active.description = Set(part.description);
+            active.max_score = Set(part.max_score);
+            active.deadline = Set(part.deadline.map(|d| d.timestamp()));
+            active.updated_at = Set(now);
+            active
+                .save(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("保存作业分题失败: {e}")))?;
+        }
+
+        // 拆分后作业满分由分题决定；清空分题时保留原满分
+        if total_max_score > 0.0
+            && let Some(homework) = Homeworks::find_by_id(homework_id)
+                .one(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?
+        {
+            let mut active: HomeworkActiveModel = homework.into();
+            active.max_score = Set(total_max_score);
+            active.updated_at = Set(now);
+            active
+                .update(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("更新作业满分失败: {e}")))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        self.list_homework_parts_impl(homework_id).await
+    }
+}
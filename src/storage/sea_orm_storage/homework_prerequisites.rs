@@ -0,0 +1,222 @@
+//! 作业前置条件存储操作
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_prerequisites::{ActiveModel, Column, Entity as HomeworkPrerequisites};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::{
+    entities::HomeworkPrerequisite, requests::HomeworkPrerequisiteInput,
+};
+use crate::models::submissions::entities::SubmissionStatus;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+
+impl SeaOrmStorage {
+    /// 列出作业的前置条件
+    pub async fn list_homework_prerequisites_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<HomeworkPrerequisite>> {
+        let models = HomeworkPrerequisites::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业前置条件失败: {e}")))?;
+
+        Ok(models.into_iter().map(|m| m.into_prerequisite()).collect())
+    }
+
+    /// 列出班级内全部前置关系 (homework_id, prerequisite_id)
+    pub async fn list_class_prerequisite_edges_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<(i64, i64)>> {
+        let homework_ids: Vec<i64> = Homeworks::find()
+            .select_only()
+            .column(HomeworkColumn::Id)
+            .filter(HomeworkColumn::ClassId.eq(class_id))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?;
+        if homework_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        HomeworkPrerequisites::find()
+            .select_only()
+            .column(Column::HomeworkId)
+            .column(Column::PrerequisiteId)
+            .filter(Column::HomeworkId.is_in(homework_ids))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业前置条件失败: {e}")))
+    }
+
+    /// 整体替换作业的前置条件
+    pub async fn replace_homework_prerequisites_impl(
+        &self,
+        homework_id: i64,
+        prerequisites: Vec<HomeworkPrerequisiteInput>,
+    ) -> Result<Vec<HomeworkPrerequisite>> {
+        let now = chrono::Utc::now().timestamp();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        HomeworkPrerequisites::delete_many()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除作业前置条件失败: {e}")))?;
+
+        let mut items = Vec::with_capacity(prerequisites.len());
+        for input in prerequisites {
+            let model = ActiveModel {
+                homework_id: Set(homework_id),
+                prerequisite_id: Set(input.homework_id),
+                requirement: Set(input.requirement.to_string()),
+                min_score: Set(input.min_score),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("保存作业前置条件失败: {e}")))?;
+            items.push(model.into_prerequisite());
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(items)
+    }
+
+    /// 查询学生在给定作业中尚未满足的前置作业
+    ///
+    /// 返回 homework_id -> 未满足的前置作业 ID 列表；全部满足或没有前置条件的作业不在其中。
+    /// 前置作业按各分题最新提交计分，被豁免的前置作业视为已满足。
+    pub async fn list_unmet_prerequisites_impl(
+        &self,
+        user_id: i64,
+        homework_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<i64>>> {
+        if homework_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let prerequisites: Vec<HomeworkPrerequisite> = HomeworkPrerequisites::find()
+            .filter(Column::HomeworkId.is_in(homework_ids.iter().copied()))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业前置条件失败: {e}")))?
+            .into_iter()
+            .map(|m| m.into_prerequisite())
+            .collect();
+        if prerequisites.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let required_ids: Vec<i64> = prerequisites
+            .iter()
+            .map(|p| p.prerequisite_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let max_scores: HashMap<i64, f64> = Homeworks::find()
+            .select_only()
+            .column(HomeworkColumn::Id)
+            .column(HomeworkColumn::MaxScore)
+            .filter(HomeworkColumn::Id.is_in(required_ids.clone()))
+            .into_tuple::<(i64, f64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询前置作业失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        // 每个（作业, 分题）取最新提交
+        let submissions = Submissions::find()
+            .filter(SubmissionColumn::CreatorId.eq(user_id))
+            .filter(SubmissionColumn::HomeworkId.is_in(required_ids.clone()))
+            .order_by_desc(SubmissionColumn::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户提交失败: {e}")))?;
+        let mut latest: HashMap<(i64, Option<i64>), i64> = HashMap::new();
+        // 最新提交被退回待重交的前置作业视为未提交
+        let mut returned: HashSet<i64> = HashSet::new();
+        for sub in &submissions {
+            if let Entry::Vacant(entry) = latest.entry((sub.homework_id, sub.part_id)) {
+                entry.insert(sub.id);
+                if sub.status == SubmissionStatus::RETURNED {
+                    returned.insert(sub.homework_id);
+                }
+            }
+        }
+        latest.retain(|(homework_id, _), _| !returned.contains(homework_id));
+
+        let latest_ids: Vec<i64> = latest.values().copied().collect();
+        let grade_map: HashMap<i64, f64> = if latest_ids.is_empty() {
+            HashMap::new()
+        } else {
+            Grades::find()
+                .filter(GradeColumn::SubmissionId.is_in(latest_ids))
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+                .into_iter()
+                .map(|g| (g.submission_id, g.score))
+                .collect()
+        };
+
+        // 前置作业得分：各分题最新提交得分之和，存在未评分分题时视为未评分
+        let mut scores: HashMap<i64, Option<f64>> = HashMap::new();
+        for ((homework_id, _), submission_id) in &latest {
+            let grade = grade_map.get(submission_id).copied();
+            let entry = scores.entry(*homework_id).or_insert(Some(0.0));
+            *entry = match (*entry, grade) {
+                (Some(total), Some(score)) => Some(total + score),
+                _ => None,
+            };
+        }
+
+        let exempted = self
+            .get_user_exempted_homework_ids_impl(user_id, &required_ids)
+            .await?;
+
+        let mut unmet: HashMap<i64, Vec<i64>> = HashMap::new();
+        for prerequisite in &prerequisites {
+            if exempted.contains(&prerequisite.prerequisite_id) {
+                continue;
+            }
+            let max_score = max_scores
+                .get(&prerequisite.prerequisite_id)
+                .copied()
+                .unwrap_or_default();
+            let score = scores.get(&prerequisite.prerequisite_id).copied();
+            if !prerequisite.is_met(max_score, score.is_some(), score.flatten()) {
+                unmet
+                    .entry(prerequisite.homework_id)
+                    .or_default()
+                    .push(prerequisite.prerequisite_id);
+            }
+        }
+
+        Ok(unmet)
+    }
+}
@@ -0,0 +1,82 @@
+//! 作业量规快照存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::homework_rubrics::{ActiveModel, Entity as HomeworkRubrics};
+use crate::errors::{HWSystemError, Result};
+use crate::models::rubrics::entities::{HomeworkRubric, RubricCriterion};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+impl SeaOrmStorage {
+    /// 获取作业的量规快照
+    pub async fn get_homework_rubric_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Option<HomeworkRubric>> {
+        let model = HomeworkRubrics::find_by_id(homework_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业量规失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_homework_rubric()))
+    }
+
+    /// 保存作业的量规快照（覆盖已有快照）
+    pub async fn set_homework_rubric_impl(
+        &self,
+        homework_id: i64,
+        rubric_id: i64,
+        rubric_version: i32,
+        title: String,
+        criteria: &[RubricCriterion],
+        attached_by: i64,
+    ) -> Result<HomeworkRubric> {
+        let now = chrono::Utc::now().timestamp();
+        let criteria = serde_json::to_string(criteria)
+            .map_err(|e| HWSystemError::serialization(format!("序列化评分维度失败: {e}")))?;
+
+        let existing = HomeworkRubrics::find_by_id(homework_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业量规失败: {e}")))?;
+
+        let model = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.rubric_id = Set(Some(rubric_id));
+                active.rubric_version = Set(rubric_version);
+                active.title = Set(title);
+                active.criteria = Set(criteria);
+                active.attached_by = Set(attached_by);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    homework_id: Set(homework_id),
+                    rubric_id: Set(Some(rubric_id)),
+                    rubric_version: Set(rubric_version),
+                    title: Set(title),
+                    criteria: Set(criteria),
+                    attached_by: Set(attached_by),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存作业量规失败: {e}")))?;
+
+        Ok(model.into_homework_rubric())
+    }
+
+    /// 移除作业的量规快照
+    pub async fn delete_homework_rubric_impl(&self, homework_id: i64) -> Result<bool> {
+        let result = HomeworkRubrics::delete_by_id(homework_id)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除作业量规失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,88 @@
+//! 作业分享链接存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::homework_share_links::{ActiveModel, Column, Entity as HomeworkShareLinks};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::entities::HomeworkShareLink;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set, sea_query::Expr,
+};
+
+impl SeaOrmStorage {
+    /// 创建作业分享链接
+    pub async fn create_homework_share_link_impl(
+        &self,
+        homework_id: i64,
+        token: &str,
+        created_by: i64,
+        expires_at: i64,
+    ) -> Result<HomeworkShareLink> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            homework_id: Set(homework_id),
+            token: Set(token.to_string()),
+            created_by: Set(created_by),
+            expires_at: Set(expires_at),
+            revoked_at: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建作业分享链接失败: {e}")))?;
+
+        Ok(result.into_share_link())
+    }
+
+    /// 列出作业的所有分享链接
+    pub async fn list_homework_share_links_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<HomeworkShareLink>> {
+        let results = HomeworkShareLinks::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业分享链接失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_share_link()).collect())
+    }
+
+    /// 通过令牌获取分享链接
+    pub async fn get_homework_share_link_by_token_impl(
+        &self,
+        token: &str,
+    ) -> Result<Option<HomeworkShareLink>> {
+        let result = HomeworkShareLinks::find()
+            .filter(Column::Token.eq(token))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业分享链接失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_share_link()))
+    }
+
+    /// 撤销分享链接（已撤销的链接返回 false）
+    pub async fn revoke_homework_share_link_impl(
+        &self,
+        homework_id: i64,
+        link_id: i64,
+    ) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = HomeworkShareLinks::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(now))
+            .filter(Column::Id.eq(link_id))
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("撤销作业分享链接失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
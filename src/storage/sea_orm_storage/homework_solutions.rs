@@ -0,0 +1,130 @@
+//! 作业参考答案存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::homework_solutions::{ActiveModel, Column, Entity as HomeworkSolutions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::homeworks::entities::{HomeworkSolution, SolutionRevealPolicy};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, Set, sea_query::Expr,
+};
+
+impl SeaOrmStorage {
+    /// 获取作业参考答案
+    pub async fn get_homework_solution_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Option<HomeworkSolution>> {
+        let result = HomeworkSolutions::find_by_id(homework_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业参考答案失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_homework_solution()))
+    }
+
+    /// 创建或更新作业参考答案（不影响已公开状态）
+    pub async fn upsert_homework_solution_impl(
+        &self,
+        homework_id: i64,
+        content: &str,
+        reveal_policy: SolutionRevealPolicy,
+        user_id: i64,
+    ) -> Result<HomeworkSolution> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = HomeworkSolutions::find_by_id(homework_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业参考答案失败: {e}")))?;
+
+        let result = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.content = Set(content.to_string());
+                active.reveal_policy = Set(reveal_policy.to_string());
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    homework_id: Set(homework_id),
+                    content: Set(content.to_string()),
+                    reveal_policy: Set(reveal_policy.to_string()),
+                    published_at: Set(None),
+                    revealed_at: Set(None),
+                    created_by: Set(user_id),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存作业参考答案失败: {e}")))?;
+
+        Ok(result.into_homework_solution())
+    }
+
+    /// 删除作业参考答案
+    pub async fn delete_homework_solution_impl(&self, homework_id: i64) -> Result<bool> {
+        let result = HomeworkSolutions::delete_by_id(homework_id)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除作业参考答案失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 手动发布参考答案（已发布的返回 false）
+    pub async fn publish_homework_solution_impl(&self, homework_id: i64) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = HomeworkSolutions::update_many()
+            .col_expr(Column::PublishedAt, Expr::value(now))
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::PublishedAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("发布作业参考答案失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 标记参考答案已公开
+    ///
+    /// 仅在首次标记时返回 true，调用方据此保证公开通知只发送一次。
+    pub async fn mark_homework_solution_revealed_impl(&self, homework_id: i64) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = HomeworkSolutions::update_many()
+            .col_expr(Column::RevealedAt, Expr::value(now))
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::RevealedAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("更新参考答案公开状态失败: {e}"))
+            })?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 列出尚未公开、可能自动公开的参考答案（手动模式仅包含已发布的）
+    pub async fn list_pending_homework_solutions_impl(&self) -> Result<Vec<HomeworkSolution>> {
+        let results = HomeworkSolutions::find()
+            .filter(Column::RevealedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(Column::RevealPolicy.ne(SolutionRevealPolicy::Manual.to_string()))
+                    .add(Column::PublishedAt.is_not_null()),
+            )
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业参考答案失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_homework_solution())
+            .collect())
+    }
+}
@@ -3,6 +3,10 @@
 use std::collections::HashMap;
 
 use super::SeaOrmStorage;
+use super::activity_events::insert_activity_event;
+use super::batch::insert_chunked;
+use super::organizations::tenant_class_ids;
+use super::outbox::insert_outbox_events;
 use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
 use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
 use crate::entity::homework_files::{
@@ -13,16 +17,23 @@ use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissio
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     PaginationInfo,
+    classes::entities::{ActivityEventType, ImEvent, NewActivityEvent},
+    common::PaginationPolicy,
     homeworks::{
-        entities::{DeadlineFilter, Homework, HomeworkUserStatus},
+        entities::{AttachmentKind, DeadlineFilter, Homework, HomeworkUserStatus},
         requests::{
-            AllHomeworksQuery, CreateHomeworkRequest, HomeworkListQuery, UpdateHomeworkRequest,
+            AllHomeworksQuery, CreateHomeworkRequest, HomeworkAttachmentInput, HomeworkListQuery,
+            UpdateHomeworkRequest,
         },
         responses::{
             AllHomeworksResponse, HomeworkCreator, HomeworkListItem, HomeworkListResponse,
             HomeworkStatsSummary, MySubmissionSummary,
         },
     },
+    notifications::entities::{NotificationType, ReferenceType},
+    organizations::entities::TenantScope,
+    outbox::entities::OutboxEvent,
+    submissions::entities::SubmissionStatus,
 };
 use crate::utils::escape_like_pattern;
 use sea_orm::{
@@ -30,40 +41,175 @@ use sea_orm::{
     Set,
 };
 
+/// 最新提交已被退回、尚未重交的（作业 ID, 学生 ID），统计时视为未提交
+pub(super) fn returned_submitters(
+    submissions: &[crate::entity::submissions::Model],
+) -> std::collections::HashSet<(i64, i64)> {
+    let mut latest: HashMap<(i64, i64), &crate::entity::submissions::Model> = HashMap::new();
+    for sub in submissions {
+        let entry = latest
+            .entry((sub.homework_id, sub.creator_id))
+            .or_insert(sub);
+        if sub.version > entry.version {
+            *entry = sub;
+        }
+    }
+    latest
+        .into_iter()
+        .filter(|(_, sub)| sub.status == SubmissionStatus::RETURNED)
+        .map(|(key, _)| key)
+        .collect()
+}
+
 impl SeaOrmStorage {
-    /// 创建作业
+    /// 创建作业，`notification_job_id` 为跟踪学生通知投递的后台任务
     pub async fn create_homework_impl(
         &self,
         created_by: i64,
         req: CreateHomeworkRequest,
+        notification_job_id: Option<&str>,
     ) -> Result<Homework> {
+        self.batch_create_homeworks_impl(created_by, vec![req], notification_job_id)
+            .await?
+            .pop()
+            .ok_or_else(|| HWSystemError::database_operation("创建作业失败: 未返回作业"))
+    }
+
+    /// 在一个事务中批量创建作业（附件共享同一文件，每份作业各增加一次引用计数）
+    ///
+    /// 班级学生通知与 IM 群消息以发件箱事件形式随作业一起写入，同时写入班级动态
+    pub async fn batch_create_homeworks_impl(
+        &self,
+        created_by: i64,
+        requests: Vec<CreateHomeworkRequest>,
+        notification_job_id: Option<&str>,
+    ) -> Result<Vec<Homework>> {
+        use crate::entity::files::{Column as FileColumn, Entity as Files};
+        use sea_orm::TransactionTrait;
+        use sea_orm::sea_query::Expr;
+
         let now = chrono::Utc::now().timestamp();
 
-        let model = ActiveModel {
-            class_id: Set(req.class_id),
-            title: Set(req.title),
-            description: Set(req.description),
-            max_score: Set(req.max_score.unwrap_or(100.0)),
-            deadline: Set(req.deadline.map(|dt| dt.timestamp())),
-            allow_late: Set(req.allow_late.unwrap_or(false)),
-            created_by: Set(created_by),
-            created_at: Set(now),
-            updated_at: Set(now),
-            ..Default::default()
-        };
+        // 事务外先解析全部附件令牌并校验所有权
+        let requests: Vec<(CreateHomeworkRequest, Vec<(String, AttachmentKind)>)> = requests
+            .into_iter()
+            .map(|mut req| {
+                let mut seen = std::collections::HashSet::new();
+                let attachments = req
+                    .attachments
+                    .take()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(HomeworkAttachmentInput::into_parts)
+                    .filter(|(token, _)| seen.insert(token.clone()))
+                    .collect();
+                (req, attachments)
+            })
+            .collect();
+        let tokens: Vec<String> = requests
+            .iter()
+            .flat_map(|(_, attachments)| attachments.iter().map(|(t, _)| t.clone()))
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        let file_ids = self.resolve_owned_files_impl(&tokens, created_by).await?;
 
-        let result = model
-            .insert(&self.db)
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let mut homeworks = Vec::with_capacity(requests.len());
+        let mut citations: HashMap<i64, i32> = HashMap::new();
+        for (req, attachments) in requests {
+            let result = ActiveModel {
+                class_id: Set(req.class_id),
+                title: Set(req.title),
+                description: Set(req.description),
+                max_score: Set(req.max_score.unwrap_or(100.0)),
+                deadline: Set(req.deadline.map(|dt| dt.timestamp())),
+                allow_late: Set(req.allow_late.unwrap_or(false)),
+                submission_mode: Set(req.submission_mode.unwrap_or_default().to_string()),
+                max_content_length: Set(req.max_content_length.filter(|len| *len > 0)),
+                exam_mode: Set(req.exam_mode.unwrap_or(false)),
+                require_self_assessment: Set(req.require_self_assessment.unwrap_or(false)),
+                show_grade_context: Set(req.show_grade_context.unwrap_or(false)),
+                estimated_minutes: Set(req.estimated_minutes.filter(|m| *m > 0)),
+                difficulty: Set(req.difficulty.filter(|d| *d > 0)),
+                grace_minutes: Set(req.grace_minutes.filter(|m| *m >= 0)),
+                created_by: Set(created_by),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("创建作业失败: {e}")))?;
 
-        // 处理附件
-        if let Some(tokens) = req.attachments {
-            self.set_homework_files_impl(result.id, tokens, created_by)
-                .await?;
+            let models = attachments.iter().map(|(token, kind)| {
+                *citations.entry(file_ids[token]).or_default() += 1;
+                HomeworkFileActiveModel {
+                    homework_id: Set(result.id),
+                    file_id: Set(file_ids[token]),
+                    attachment_kind: Set(kind.to_string()),
+                }
+            });
+            insert_chunked!(HomeworkFiles, models, &txn, "附件关联");
+
+            insert_outbox_events(
+                &txn,
+                vec![
+                    OutboxEvent::ClassNotification {
+                        class_id: result.class_id,
+                        notification_type: NotificationType::HomeworkCreated,
+                        vars: vec![("homework_title".to_string(), result.title.clone())],
+                        reference_type: Some(ReferenceType::Homework),
+                        reference_id: Some(result.id),
+                        job_id: notification_job_id.map(str::to_string),
+                    },
+                    OutboxEvent::HomeworkImEvent {
+                        homework_id: result.id,
+                        event: ImEvent::HomeworkCreated,
+                    },
+                ],
+            )
+            .await?;
+            insert_activity_event(
+                &txn,
+                NewActivityEvent {
+                    class_id: result.class_id,
+                    event_type: ActivityEventType::HomeworkCreated,
+                    actor_id: Some(created_by),
+                    reference_id: Some(result.id),
+                    title: result.title.clone(),
+                },
+            )
+            .await?;
+
+            homeworks.push(result.into_homework());
         }
 
-        Ok(result.into_homework())
+        // 附件不重复上传，只按引用次数增加计数
+        for (file_id, count) in citations {
+            Files::update_many()
+                .col_expr(
+                    FileColumn::CitationCount,
+                    Expr::col(FileColumn::CitationCount).add(count),
+                )
+                .filter(FileColumn::Id.eq(file_id))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("增加文件引用计数失败: {e}"))
+                })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(homeworks)
     }
 
     /// 通过 ID 获取作业
@@ -76,17 +222,38 @@ impl SeaOrmStorage {
         Ok(result.map(|m| m.into_homework()))
     }
 
+    /// 列出截止时间落在 `(from, to]` 区间内的作业
+    pub async fn list_homeworks_due_between_impl(
+        &self,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<Homework>> {
+        let results = Homeworks::find()
+            .filter(Column::Deadline.gt(from))
+            .filter(Column::Deadline.lte(to))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_homework()).collect())
+    }
+
     /// 分页列出作业
     pub async fn list_homeworks_with_pagination_impl(
         &self,
         query: HomeworkListQuery,
         current_user_id: Option<i64>,
     ) -> Result<HomeworkListResponse> {
-        let page = Ord::max(query.page.unwrap_or(1), 1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("homeworks").resolve(query.page, query.size);
 
         let mut select = Homeworks::find();
 
+        // 租户筛选（按班级所属组织）
+        if query.tenant != TenantScope::All {
+            select = select.filter(Column::ClassId.in_subquery(tenant_class_ids(query.tenant)));
+        }
+
         // 班级筛选
         if let Some(class_id) = query.class_id {
             select = select.filter(Column::ClassId.eq(class_id));
@@ -137,20 +304,12 @@ impl SeaOrmStorage {
             .collect();
 
         // 查询创建者信息
-        let mut creator_map: HashMap<i64, HomeworkCreator> = HashMap::new();
-        for creator_id in creator_ids {
-            if let Ok(Some(user)) = self.get_user_by_id_impl(creator_id).await {
-                creator_map.insert(
-                    creator_id,
-                    HomeworkCreator {
-                        id: user.id,
-                        username: user.username,
-                        display_name: user.display_name,
-                        avatar_url: user.avatar_url,
-                    },
-                );
-            }
-        }
+        let creator_map: HashMap<i64, HomeworkCreator> = self
+            .list_users_by_ids_impl(&creator_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user.into()))
+            .collect();
 
         // 查询当前用户的提交状态（如果提供了 current_user_id）
         let mut my_submission_map: HashMap<i64, MySubmissionSummary> = HashMap::new();
@@ -210,14 +369,35 @@ impl SeaOrmStorage {
             }
         }
 
+        // 查询当前用户被豁免的作业
+        let my_exempted_ids = match current_user_id {
+            Some(user_id) => {
+                let homework_ids: Vec<i64> = homeworks.iter().map(|h| h.id).collect();
+                self.get_user_exempted_homework_ids_impl(user_id, &homework_ids)
+                    .await?
+            }
+            None => std::collections::HashSet::new(),
+        };
+
+        // 查询当前用户尚未满足前置条件的作业
+        let my_locked = match current_user_id {
+            Some(user_id) => {
+                let homework_ids: Vec<i64> = homeworks.iter().map(|h| h.id).collect();
+                self.list_unmet_prerequisites_impl(user_id, &homework_ids)
+                    .await?
+            }
+            None => HashMap::new(),
+        };
+
         // 查询统计信息（如果 include_stats=true）
         let mut stats_map: HashMap<i64, HomeworkStatsSummary> = HashMap::new();
         if query.include_stats.unwrap_or(false) && !homeworks.is_empty() {
             let homework_ids: Vec<i64> = homeworks.iter().map(|h| h.id).collect();
+            let exempted_pairs = self.get_exempted_pairs_impl(&homework_ids).await?;
 
-            // 获取每个作业所属班级的需要提交作业的人数（学生和课代表，排除教师）
+            // 获取每个作业所属班级的需要提交作业的人数（学生和课代表，排除教师和被豁免学生）
             for hw in &homeworks {
-                let total_students = ClassUsers::find()
+                let class_students = ClassUsers::find()
                     .filter(ClassUserColumn::ClassId.eq(hw.class_id))
                     .filter(ClassUserColumn::Role.is_in(["student", "class_representative"]))
                     .count(&self.db)
@@ -225,6 +405,11 @@ impl SeaOrmStorage {
                     .map_err(|e| {
                         HWSystemError::database_operation(format!("查询班级学生数失败: {e}"))
                     })? as i64;
+                let exempted_count = exempted_pairs
+                    .iter()
+                    .filter(|(hw_id, _)| *hw_id == hw.id)
+                    .count() as i64;
+                let total_students = (class_students - exempted_count).max(0);
 
                 stats_map.insert(
                     hw.id,
@@ -236,61 +421,11 @@ impl SeaOrmStorage {
                 );
             }
 
-            // 查询每个作业的提交人数（按 creator_id 去重）
-            let submissions = Submissions::find()
-                .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.clone()))
-                .all(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("查询作业提交失败: {e}")))?;
-
-            // 按 homework_id 聚合，统计唯一提交者
-            let mut hw_submitters: HashMap<i64, std::collections::HashSet<i64>> = HashMap::new();
-            let mut submission_ids: Vec<i64> = Vec::new();
-            for sub in &submissions {
-                hw_submitters
-                    .entry(sub.homework_id)
-                    .or_default()
-                    .insert(sub.creator_id);
-                submission_ids.push(sub.id);
-            }
-
-            for (hw_id, submitters) in hw_submitters {
+            // 提交与评分人数读取作业计数
+            for (hw_id, counts) in self.load_homework_counts_impl(&homework_ids).await? {
                 if let Some(stats) = stats_map.get_mut(&hw_id) {
-                    stats.submitted_count = submitters.len() as i64;
-                }
-            }
-
-            // 查询已评分的提交数
-            if !submission_ids.is_empty() {
-                let grades = Grades::find()
-                    .filter(GradeColumn::SubmissionId.is_in(submission_ids))
-                    .all(&self.db)
-                    .await
-                    .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
-
-                // 建立 submission_id -> homework_id 的映射
-                let sub_to_hw: HashMap<i64, i64> =
-                    submissions.iter().map(|s| (s.id, s.homework_id)).collect();
-
-                // 按 homework 聚合已评分的唯一用户
-                let mut hw_graded_users: HashMap<i64, std::collections::HashSet<i64>> =
-                    HashMap::new();
-                let sub_to_creator: HashMap<i64, i64> =
-                    submissions.iter().map(|s| (s.id, s.creator_id)).collect();
-
-                for grade in grades {
-                    if let (Some(&hw_id), Some(&creator_id)) = (
-                        sub_to_hw.get(&grade.submission_id),
-                        sub_to_creator.get(&grade.submission_id),
-                    ) {
-                        hw_graded_users.entry(hw_id).or_default().insert(creator_id);
-                    }
-                }
-
-                for (hw_id, graded_users) in hw_graded_users {
-                    if let Some(stats) = stats_map.get_mut(&hw_id) {
-                        stats.graded_count = graded_users.len() as i64;
-                    }
+                    stats.submitted_count = counts.submitted;
+                    stats.graded_count = counts.graded;
                 }
             }
         }
@@ -302,11 +437,15 @@ impl SeaOrmStorage {
                 let creator = creator_map.get(&homework.created_by).cloned();
                 let my_submission = my_submission_map.get(&homework.id).cloned();
                 let stats_summary = stats_map.get(&homework.id).cloned();
+                let is_exempted = my_exempted_ids.contains(&homework.id);
+                let is_locked = my_locked.contains_key(&homework.id);
                 HomeworkListItem {
                     homework,
                     creator,
                     my_submission,
                     stats_summary,
+                    is_exempted,
+                    is_locked,
                 }
             })
             .collect();
@@ -363,14 +502,49 @@ impl SeaOrmStorage {
             model.allow_late = Set(allow_late);
         }
 
+        if let Some(submission_mode) = update.submission_mode {
+            model.submission_mode = Set(submission_mode.to_string());
+        }
+
+        // 0 表示取消长度限制
+        if let Some(max_content_length) = update.max_content_length {
+            model.max_content_length = Set(Some(max_content_length).filter(|len| *len > 0));
+        }
+
+        if let Some(exam_mode) = update.exam_mode {
+            model.exam_mode = Set(exam_mode);
+        }
+
+        if let Some(require_self_assessment) = update.require_self_assessment {
+            model.require_self_assessment = Set(require_self_assessment);
+        }
+
+        if let Some(show_grade_context) = update.show_grade_context {
+            model.show_grade_context = Set(show_grade_context);
+        }
+
+        // 0 表示清除预计用时 / 难度
+        if let Some(estimated_minutes) = update.estimated_minutes {
+            model.estimated_minutes = Set(Some(estimated_minutes).filter(|m| *m > 0));
+        }
+
+        if let Some(difficulty) = update.difficulty {
+            model.difficulty = Set(Some(difficulty).filter(|d| *d > 0));
+        }
+
+        // -1 表示恢复沿用系统默认宽限期
+        if let Some(grace_minutes) = update.grace_minutes {
+            model.grace_minutes = Set(Some(grace_minutes).filter(|m| *m >= 0));
+        }
+
         model
             .update(&self.db)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("更新作业失败: {e}")))?;
 
         // 处理附件
-        if let Some(tokens) = update.attachments {
-            self.set_homework_files_impl(homework_id, tokens, user_id)
+        if let Some(attachments) = update.attachments {
+            self.set_homework_files_impl(homework_id, attachments, user_id)
                 .await?;
         }
 
@@ -405,11 +579,45 @@ impl SeaOrmStorage {
         Ok(results.into_iter().map(|m| m.file_id).collect())
     }
 
+    /// 获取作业附件及其用途
+    pub async fn get_homework_attachments_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<(i64, AttachmentKind)>> {
+        let results = HomeworkFiles::find()
+            .filter(HomeworkFileColumn::HomeworkId.eq(homework_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业附件失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| (m.file_id, m.attachment_kind.parse().unwrap_or_default()))
+            .collect())
+    }
+
+    /// 查询文件作为作业附件的关联（作业 ID 及用途）
+    pub async fn list_homework_attachments_by_file_impl(
+        &self,
+        file_id: i64,
+    ) -> Result<Vec<(i64, AttachmentKind)>> {
+        let results = HomeworkFiles::find()
+            .filter(HomeworkFileColumn::FileId.eq(file_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业附件失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| (m.homework_id, m.attachment_kind.parse().unwrap_or_default()))
+            .collect())
+    }
+
     /// 设置作业附件（通过 download_token，带所有权校验）
     pub async fn set_homework_files_impl(
         &self,
         homework_id: i64,
-        tokens: Vec<String>,
+        attachments: Vec<HomeworkAttachmentInput>,
         user_id: i64,
     ) -> Result<()> {
         // 先删除旧的关联
@@ -419,33 +627,33 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("删除旧附件关联失败: {e}")))?;
 
-        // 通过 token 查找文件并校验所有权
-        for token in tokens {
-            let file = self
-                .get_file_by_token_impl(&token)
-                .await?
-                .ok_or_else(|| HWSystemError::not_found(format!("文件不存在: {token}")))?;
+        // 同一文件只关联一次（保留首次出现的用途）
+        let mut seen = std::collections::HashSet::new();
+        let attachments: Vec<(String, AttachmentKind)> = attachments
+            .into_iter()
+            .map(HomeworkAttachmentInput::into_parts)
+            .filter(|(token, _)| seen.insert(token.clone()))
+            .collect();
+        if attachments.is_empty() {
+            return Ok(());
+        }
 
-            // 校验文件所有权
-            if file.user_id != Some(user_id) {
-                return Err(HWSystemError::authorization(format!(
-                    "无权使用此文件: {token}"
-                )));
-            }
+        // 通过 token 批量查找文件并校验所有权
+        let tokens: Vec<String> = attachments.iter().map(|(t, _)| t.clone()).collect();
+        let file_ids = self.resolve_owned_files_impl(&tokens, user_id).await?;
 
-            let model = HomeworkFileActiveModel {
+        let models = attachments
+            .iter()
+            .map(|(token, kind)| HomeworkFileActiveModel {
                 homework_id: Set(homework_id),
-                file_id: Set(file.id),
-            };
-
-            model
-                .insert(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("创建附件关联失败: {e}")))?;
+                file_id: Set(file_ids[token]),
+                attachment_kind: Set(kind.to_string()),
+            });
+        insert_chunked!(HomeworkFiles, models, &self.db, "附件关联");
 
-            // 增加文件引用计数
-            self.increment_file_citation_impl(file.id).await?;
-        }
+        // 增加文件引用计数
+        let ids: Vec<i64> = file_ids.into_values().collect();
+        self.increment_file_citations_impl(&ids).await?;
 
         Ok(())
     }
@@ -472,13 +680,21 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
 
-        let total = homeworks.len() as i64;
+        // 排除被豁免的作业
+        let all_ids: Vec<i64> = homeworks.iter().map(|h| h.id).collect();
+        let exempted_ids = self
+            .get_user_exempted_homework_ids_impl(user_id, &all_ids)
+            .await?;
+        let homework_ids: Vec<i64> = all_ids
+            .into_iter()
+            .filter(|id| !exempted_ids.contains(id))
+            .collect();
+
+        let total = homework_ids.len() as i64;
         if total == 0 {
             return Ok((0, 0, 0, 0));
         }
 
-        let homework_ids: Vec<i64> = homeworks.iter().map(|h| h.id).collect();
-
         // 3. 获取用户对这些作业的提交（取每个作业的最新版本）
         let submissions = Submissions::find()
             .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.clone()))
@@ -488,11 +704,13 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
 
-        // 按 homework_id 聚合，取最新版本
+        // 按 homework_id 聚合，取最新版本（被退回待重交的不计为已提交）
         let mut latest_submissions: HashMap<i64, i64> = HashMap::new(); // homework_id -> submission_id
         for sub in &submissions {
             latest_submissions.entry(sub.homework_id).or_insert(sub.id);
         }
+        let returned = returned_submitters(&submissions);
+        latest_submissions.retain(|homework_id, _| !returned.contains(&(*homework_id, user_id)));
 
         let submitted_homework_ids: std::collections::HashSet<i64> =
             latest_submissions.keys().cloned().collect();
@@ -574,13 +792,15 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
 
-        // 按 (homework_id, creator_id) 聚合，取最新版本
+        // 按 (homework_id, creator_id) 聚合，取最新版本（被退回待重交的不计入）
         let mut latest_submissions: HashMap<(i64, i64), i64> = HashMap::new();
         for sub in &submissions {
             latest_submissions
                 .entry((sub.homework_id, sub.creator_id))
                 .or_insert(sub.id);
         }
+        let returned = returned_submitters(&submissions);
+        latest_submissions.retain(|key, _| !returned.contains(key));
 
         let total_submissions = latest_submissions.len() as i64;
 
@@ -615,8 +835,8 @@ impl SeaOrmStorage {
         is_teacher: bool,
         query: AllHomeworksQuery,
     ) -> Result<AllHomeworksResponse> {
-        let page = Ord::max(query.page.unwrap_or(1), 1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("homeworks").resolve(query.page, query.size);
         let now = chrono::Utc::now();
         let now_ts = now.timestamp();
 
@@ -766,17 +986,36 @@ impl SeaOrmStorage {
             }
         }
 
+        // 查询当前用户被豁免的作业
+        let my_exempted_ids = self
+            .get_user_exempted_homework_ids_impl(user_id, &homework_ids)
+            .await?;
+
+        // 查询当前用户尚未满足前置条件的作业（仅学生视角）
+        let my_locked = if is_teacher {
+            HashMap::new()
+        } else {
+            self.list_unmet_prerequisites_impl(user_id, &homework_ids)
+                .await?
+        };
+
         // 5. 根据状态过滤作业
         let filtered_homework_ids: Vec<i64> = if let Some(status) = query.status {
             all_homeworks
                 .iter()
                 .filter(|hw| {
-                    let hw_status = if let Some((sub_id, _, _, _)) = my_submission_map.get(&hw.id) {
+                    // 被退回待重交的作业仍为待完成
+                    let hw_status = if let Some((sub_id, _, sub_status, _)) =
+                        my_submission_map.get(&hw.id)
+                        && sub_status != SubmissionStatus::RETURNED
+                    {
                         if grade_map.contains_key(sub_id) {
                             HomeworkUserStatus::Graded
                         } else {
                             HomeworkUserStatus::Submitted
                         }
+                    } else if my_exempted_ids.contains(&hw.id) {
+                        HomeworkUserStatus::Exempted
                     } else {
                         HomeworkUserStatus::Pending
                     };
@@ -821,26 +1060,21 @@ impl SeaOrmStorage {
             .into_iter()
             .collect();
 
-        let mut creator_map: HashMap<i64, HomeworkCreator> = HashMap::new();
-        for creator_id in creator_ids {
-            if let Ok(Some(user)) = self.get_user_by_id_impl(creator_id).await {
-                creator_map.insert(
-                    creator_id,
-                    HomeworkCreator {
-                        id: user.id,
-                        username: user.username,
-                        display_name: user.display_name,
-                        avatar_url: user.avatar_url,
-                    },
-                );
-            }
-        }
+        let creator_map: HashMap<i64, HomeworkCreator> = self
+            .list_users_by_ids_impl(&creator_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user.into()))
+            .collect();
 
         // 9. 查询统计信息（如果 include_stats=true）
         let mut stats_map: HashMap<i64, HomeworkStatsSummary> = HashMap::new();
         if query.include_stats.unwrap_or(false) && !ordered_homeworks.is_empty() {
+            let paged_hw_ids: Vec<i64> = ordered_homeworks.iter().map(|h| h.id).collect();
+            let exempted_pairs = self.get_exempted_pairs_impl(&paged_hw_ids).await?;
+
             for hw in &ordered_homeworks {
-                let total_students = ClassUsers::find()
+                let class_students = ClassUsers::find()
                     .filter(ClassUserColumn::ClassId.eq(hw.class_id))
                     .filter(ClassUserColumn::Role.is_in(["student", "class_representative"]))
                     .count(&self.db)
@@ -848,6 +1082,11 @@ impl SeaOrmStorage {
                     .map_err(|e| {
                         HWSystemError::database_operation(format!("查询班级学生数失败: {e}"))
                     })? as i64;
+                let exempted_count = exempted_pairs
+                    .iter()
+                    .filter(|(hw_id, _)| *hw_id == hw.id)
+                    .count() as i64;
+                let total_students = (class_students - exempted_count).max(0);
 
                 stats_map.insert(
                     hw.id,
@@ -859,62 +1098,11 @@ impl SeaOrmStorage {
                 );
             }
 
-            // 查询提交统计
-            let hw_ids: Vec<i64> = ordered_homeworks.iter().map(|h| h.id).collect();
-            let all_submissions = Submissions::find()
-                .filter(SubmissionColumn::HomeworkId.is_in(hw_ids.clone()))
-                .all(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("查询作业提交失败: {e}")))?;
-
-            let mut hw_submitters: HashMap<i64, std::collections::HashSet<i64>> = HashMap::new();
-            let mut all_sub_ids: Vec<i64> = Vec::new();
-            for sub in &all_submissions {
-                hw_submitters
-                    .entry(sub.homework_id)
-                    .or_default()
-                    .insert(sub.creator_id);
-                all_sub_ids.push(sub.id);
-            }
-
-            for (hw_id, submitters) in &hw_submitters {
-                if let Some(stats) = stats_map.get_mut(hw_id) {
-                    stats.submitted_count = submitters.len() as i64;
-                }
-            }
-
-            // 查询评分统计
-            if !all_sub_ids.is_empty() {
-                let all_grades = Grades::find()
-                    .filter(GradeColumn::SubmissionId.is_in(all_sub_ids))
-                    .all(&self.db)
-                    .await
-                    .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
-
-                let sub_to_hw: HashMap<i64, i64> = all_submissions
-                    .iter()
-                    .map(|s| (s.id, s.homework_id))
-                    .collect();
-                let sub_to_creator: HashMap<i64, i64> = all_submissions
-                    .iter()
-                    .map(|s| (s.id, s.creator_id))
-                    .collect();
-
-                let mut hw_graded_users: HashMap<i64, std::collections::HashSet<i64>> =
-                    HashMap::new();
-                for grade in all_grades {
-                    if let (Some(&hw_id), Some(&creator_id)) = (
-                        sub_to_hw.get(&grade.submission_id),
-                        sub_to_creator.get(&grade.submission_id),
-                    ) {
-                        hw_graded_users.entry(hw_id).or_default().insert(creator_id);
-                    }
-                }
-
-                for (hw_id, graded_users) in hw_graded_users {
-                    if let Some(stats) = stats_map.get_mut(&hw_id) {
-                        stats.graded_count = graded_users.len() as i64;
-                    }
+            // 提交与评分人数读取作业计数
+            for (hw_id, counts) in self.load_homework_counts_impl(&paged_hw_ids).await? {
+                if let Some(stats) = stats_map.get_mut(&hw_id) {
+                    stats.submitted_count = counts.submitted;
+                    stats.graded_count = counts.graded;
                 }
             }
         }
@@ -943,11 +1131,15 @@ impl SeaOrmStorage {
                             }
                         });
                 let stats_summary = stats_map.get(&homework.id).cloned();
+                let is_exempted = my_exempted_ids.contains(&homework.id);
+                let is_locked = my_locked.contains_key(&homework.id);
                 HomeworkListItem {
                     homework,
                     creator,
                     my_submission,
                     stats_summary,
+                    is_exempted,
+                    is_locked,
                 }
             })
             .collect();
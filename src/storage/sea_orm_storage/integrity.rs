@@ -0,0 +1,283 @@
+//! 数据一致性检查存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use super::homework_counters::{homework_counter_mismatches, refresh_homework_counters};
+use crate::entity::class_certificate_settings::{
+    Column as CertificateSettingColumn, Entity as ClassCertificateSettings,
+};
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_files::{Column as HomeworkFileColumn, Entity as HomeworkFiles};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::role_requests::{Column as RoleRequestColumn, Entity as RoleRequests};
+use crate::entity::submission_files::{Column as SubmissionFileColumn, Entity as SubmissionFiles};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::system::responses::{IntegrityCheckKind, IntegrityCheckResult};
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect,
+    TransactionTrait,
+};
+
+/// 报告中每项检查最多列出的问题记录 ID 数
+const SAMPLE_LIMIT: usize = 100;
+/// 每批删除/更新的记录数
+const CHUNK_SIZE: usize = 500;
+
+fn check_result(
+    check: IntegrityCheckKind,
+    ids: &[i64],
+    repairable: bool,
+    repaired: i64,
+) -> IntegrityCheckResult {
+    IntegrityCheckResult {
+        check,
+        found: ids.len() as i64,
+        repairable,
+        repaired,
+        sample_ids: ids.iter().take(SAMPLE_LIMIT).copied().collect(),
+    }
+}
+
+/// 班级或用户已不存在的班级成员记录 ID
+async fn orphaned_class_users<C: ConnectionTrait>(conn: &C) -> Result<Vec<i64>> {
+    ClassUsers::find()
+        .select_only()
+        .column(ClassUserColumn::Id)
+        .filter(
+            Condition::any()
+                .add(
+                    ClassUserColumn::ClassId.not_in_subquery(
+                        Query::select()
+                            .column(ClassColumn::Id)
+                            .from(Classes)
+                            .to_owned(),
+                    ),
+                )
+                .add(
+                    ClassUserColumn::UserId.not_in_subquery(
+                        Query::select()
+                            .column(UserColumn::Id)
+                            .from(Users)
+                            .to_owned(),
+                    ),
+                ),
+        )
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询孤立班级成员失败: {e}")))
+}
+
+/// 所属作业已不存在的提交 ID
+async fn submissions_without_homework<C: ConnectionTrait>(conn: &C) -> Result<Vec<i64>> {
+    Submissions::find()
+        .select_only()
+        .column(SubmissionColumn::Id)
+        .filter(
+            SubmissionColumn::HomeworkId.not_in_subquery(
+                Query::select()
+                    .column(HomeworkColumn::Id)
+                    .from(Homeworks)
+                    .to_owned(),
+            ),
+        )
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询孤立提交失败: {e}")))
+}
+
+/// 指向已删除提交的评分 ID
+async fn grades_without_submission<C: ConnectionTrait>(conn: &C) -> Result<Vec<i64>> {
+    Grades::find()
+        .select_only()
+        .column(GradeColumn::Id)
+        .filter(
+            GradeColumn::SubmissionId.not_in_subquery(
+                Query::select()
+                    .column(SubmissionColumn::Id)
+                    .from(Submissions)
+                    .to_owned(),
+            ),
+        )
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询孤立评分失败: {e}")))
+}
+
+/// 按实际引用计算每个文件应有的引用计数：提交附件、作业附件、证书签名与角色申请附件
+async fn expected_citations<C: ConnectionTrait>(conn: &C) -> Result<HashMap<i64, i32>> {
+    let submission_refs: Vec<i64> = SubmissionFiles::find()
+        .select_only()
+        .column(SubmissionFileColumn::FileId)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询提交附件引用失败: {e}")))?;
+    let homework_refs: Vec<i64> = HomeworkFiles::find()
+        .select_only()
+        .column(HomeworkFileColumn::FileId)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询作业附件引用失败: {e}")))?;
+    let signatures: Vec<Option<i64>> = ClassCertificateSettings::find()
+        .select_only()
+        .column(CertificateSettingColumn::SignatureFileId)
+        .filter(CertificateSettingColumn::SignatureFileId.is_not_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询证书签名图片失败: {e}")))?;
+    let attachments: Vec<Option<i64>> = RoleRequests::find()
+        .select_only()
+        .column(RoleRequestColumn::AttachmentFileId)
+        .filter(RoleRequestColumn::AttachmentFileId.is_not_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询角色申请附件失败: {e}")))?;
+
+    let mut counts: HashMap<i64, i32> = HashMap::new();
+    for file_id in submission_refs
+        .into_iter()
+        .chain(homework_refs)
+        .chain(signatures.into_iter().flatten())
+        .chain(attachments.into_iter().flatten())
+    {
+        *counts.entry(file_id).or_default() += 1;
+    }
+    Ok(counts)
+}
+
+/// 引用计数与实际引用数不一致的文件：(文件 ID, 应有引用计数)
+async fn citation_mismatches<C: ConnectionTrait>(conn: &C) -> Result<Vec<(i64, i32)>> {
+    let expected = expected_citations(conn).await?;
+    let files: Vec<(i64, i32)> = Files::find()
+        .select_only()
+        .column(FileColumn::Id)
+        .column(FileColumn::CitationCount)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询文件引用计数失败: {e}")))?;
+
+    Ok(files
+        .into_iter()
+        .filter_map(|(id, count)| {
+            let actual = expected.get(&id).copied().unwrap_or(0);
+            (count != actual).then_some((id, actual))
+        })
+        .collect())
+}
+
+impl SeaOrmStorage {
+    /// 执行数据一致性检查
+    ///
+    /// 修复范围：删除孤立的班级成员与评分、将文件引用计数重算为实际引用数、重算作业提交/评分计数。
+    /// 孤立提交包含学生作答内容，只报告不删除。
+    pub async fn run_integrity_checks_impl(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<IntegrityCheckResult>> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let class_user_ids = orphaned_class_users(&txn).await?;
+        let submission_ids = submissions_without_homework(&txn).await?;
+        let grade_ids = grades_without_submission(&txn).await?;
+        let mismatches = citation_mismatches(&txn).await?;
+        let counter_ids = homework_counter_mismatches(&txn).await?;
+
+        let mut repaired_class_users = 0;
+        let mut repaired_grades = 0;
+        let mut repaired_citations = 0;
+        let mut repaired_counters = 0;
+        if repair {
+            for chunk in class_user_ids.chunks(CHUNK_SIZE) {
+                let result = ClassUsers::delete_many()
+                    .filter(ClassUserColumn::Id.is_in(chunk.iter().copied()))
+                    .exec(&txn)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("删除孤立班级成员失败: {e}"))
+                    })?;
+                repaired_class_users += result.rows_affected as i64;
+            }
+            for chunk in grade_ids.chunks(CHUNK_SIZE) {
+                let result = Grades::delete_many()
+                    .filter(GradeColumn::Id.is_in(chunk.iter().copied()))
+                    .exec(&txn)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("删除孤立评分失败: {e}"))
+                    })?;
+                repaired_grades += result.rows_affected as i64;
+            }
+            for &(file_id, count) in &mismatches {
+                let result = Files::update_many()
+                    .col_expr(FileColumn::CitationCount, Expr::value(count))
+                    .filter(FileColumn::Id.eq(file_id))
+                    .exec(&txn)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("修正文件引用计数失败: {e}"))
+                    })?;
+                repaired_citations += result.rows_affected as i64;
+            }
+            for chunk in counter_ids.chunks(CHUNK_SIZE) {
+                refresh_homework_counters(&txn, chunk).await?;
+                repaired_counters += chunk.len() as i64;
+            }
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        let mismatch_ids: Vec<i64> = mismatches.iter().map(|(id, _)| *id).collect();
+        Ok(vec![
+            check_result(
+                IntegrityCheckKind::OrphanedClassUsers,
+                &class_user_ids,
+                true,
+                repaired_class_users,
+            ),
+            check_result(
+                IntegrityCheckKind::SubmissionsWithoutHomework,
+                &submission_ids,
+                false,
+                0,
+            ),
+            check_result(
+                IntegrityCheckKind::CitationCountMismatch,
+                &mismatch_ids,
+                true,
+                repaired_citations,
+            ),
+            check_result(
+                IntegrityCheckKind::GradesWithoutSubmission,
+                &grade_ids,
+                true,
+                repaired_grades,
+            ),
+            check_result(
+                IntegrityCheckKind::HomeworkCounterMismatch,
+                &counter_ids,
+                true,
+                repaired_counters,
+            ),
+        ])
+    }
+}
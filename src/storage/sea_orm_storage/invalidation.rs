@@ -0,0 +1,30 @@
+//! 发布跨节点缓存失效消息（PostgreSQL NOTIFY）
+
+use super::SeaOrmStorage;
+use crate::cache::invalidation::CacheInvalidation;
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tracing::error;
+
+impl SeaOrmStorage {
+    /// 在写操作成功后发布缓存失效消息
+    ///
+    /// 未启用时不做任何事；发布失败只记录日志，不影响已完成的写操作。
+    pub(super) async fn publish_invalidation(&self, event: CacheInvalidation) {
+        let Some(channel) = &self.invalidation_channel else {
+            return;
+        };
+
+        let payload = event.to_payload();
+        if let Err(e) = self
+            .db
+            .execute_raw(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "SELECT pg_notify($1, $2)",
+                [channel.as_str().into(), payload.as_str().into()],
+            ))
+            .await
+        {
+            error!("Failed to publish cache invalidation '{}': {}", payload, e);
+        }
+    }
+}
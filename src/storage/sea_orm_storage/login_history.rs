@@ -0,0 +1,178 @@
+//! 登录历史存储操作
+
+use std::net::IpAddr;
+
+use super::SeaOrmStorage;
+use super::organizations::tenant_condition;
+use crate::entity::login_history::{ActiveModel, Column, Entity as LoginHistories};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::organizations::entities::TenantScope;
+use crate::models::{
+    PaginationInfo,
+    common::PaginationPolicy,
+    users::{
+        entities::LoginHistory,
+        requests::{CreateLoginHistory, LoginHistoryQuery},
+        responses::LoginHistoryListResponse,
+    },
+};
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+/// 判断新设备、新网段时参与比较的近期成功登录数
+const RECENT_LOGIN_WINDOW: u64 = 50;
+
+/// IP 所属网段（IPv4 取 /24，IPv6 取 /48），同一网段内更换地址不视为新网络
+fn network_prefix(ip: &str) -> Option<String> {
+    match ip.parse::<IpAddr>().ok()? {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            Some(format!("{a}.{b}.{c}.0/24"))
+        }
+        IpAddr::V6(v6) => {
+            let s = v6.segments();
+            Some(format!("{:x}:{:x}:{:x}::/48", s[0], s[1], s[2]))
+        }
+    }
+}
+
+impl SeaOrmStorage {
+    /// 记录登录历史
+    ///
+    /// 成功登录时与最近的成功登录比较：未出现过的（浏览器, 系统, 设备类型）组合为新设备，
+    /// 未出现过的网段为新网络，有国家记录且国家未出现过为新国家。
+    /// 新国家，或新设备且来自新网络，标记为可疑。首次登录没有可比较的记录，不做标记。
+    pub async fn record_login_history_impl(
+        &self,
+        entry: CreateLoginHistory,
+    ) -> Result<LoginHistory> {
+        let (mut new_device, mut new_ip, mut new_country) = (false, false, false);
+
+        if entry.success {
+            let recent: Vec<(String, String, String, Option<String>, Option<String>)> =
+                LoginHistories::find()
+                    .select_only()
+                    .column(Column::Browser)
+                    .column(Column::Os)
+                    .column(Column::DeviceType)
+                    .column(Column::IpAddress)
+                    .column(Column::Country)
+                    .filter(Column::UserId.eq(entry.user_id))
+                    .filter(Column::Success.eq(true))
+                    .order_by_desc(Column::CreatedAt)
+                    .order_by_desc(Column::Id)
+                    .limit(RECENT_LOGIN_WINDOW)
+                    .into_tuple()
+                    .all(&self.db)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("查询近期登录记录失败: {e}"))
+                    })?;
+
+            if !recent.is_empty() {
+                new_device = !recent.iter().any(|(browser, os, device_type, _, _)| {
+                    *browser == entry.browser
+                        && *os == entry.os
+                        && *device_type == entry.device_type
+                });
+                if let Some(network) = entry.ip_address.as_deref().and_then(network_prefix) {
+                    new_ip = !recent.iter().any(|(_, _, _, ip, _)| {
+                        ip.as_deref().and_then(network_prefix).as_ref() == Some(&network)
+                    });
+                }
+                if let Some(country) = &entry.country {
+                    // 近期登录均无国家记录时无从比较
+                    let mut known = recent
+                        .iter()
+                        .filter_map(|(_, _, _, _, c)| c.as_deref())
+                        .peekable();
+                    new_country = known.peek().is_some() && !known.any(|c| c == country);
+                }
+            }
+        }
+
+        let model = ActiveModel {
+            user_id: Set(entry.user_id),
+            success: Set(entry.success),
+            ip_address: Set(entry.ip_address),
+            country: Set(entry.country),
+            user_agent: Set(entry.user_agent),
+            browser: Set(entry.browser),
+            os: Set(entry.os),
+            device_type: Set(entry.device_type),
+            new_device: Set(new_device),
+            new_ip: Set(new_ip),
+            new_country: Set(new_country),
+            suspicious: Set(new_country || (new_device && new_ip)),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("记录登录历史失败: {e}")))?;
+
+        Ok(model.into_login_history())
+    }
+
+    /// 分页查询登录历史（按时间倒序），非全局范围时只返回本租户用户的记录
+    pub async fn list_login_history_impl(
+        &self,
+        query: LoginHistoryQuery,
+    ) -> Result<LoginHistoryListResponse> {
+        let (page, size) =
+            PaginationPolicy::for_endpoint("login_history").resolve(query.page, query.size);
+
+        let mut select = LoginHistories::find();
+        if query.tenant != TenantScope::All {
+            select = select.filter(
+                Column::UserId.in_subquery(
+                    Query::select()
+                        .column(UserColumn::Id)
+                        .from(Users)
+                        .cond_where(tenant_condition(UserColumn::OrgId, query.tenant))
+                        .to_owned(),
+                ),
+            );
+        }
+        if let Some(user_id) = query.user_id {
+            select = select.filter(Column::UserId.eq(user_id));
+        }
+        if let Some(success) = query.success {
+            select = select.filter(Column::Success.eq(success));
+        }
+        if let Some(suspicious) = query.suspicious {
+            select = select.filter(Column::Suspicious.eq(suspicious));
+        }
+
+        let paginator = select
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .paginate(&self.db, size);
+        let total = paginator
+            .num_items()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询登录历史总数失败: {e}")))?;
+        let pages = paginator
+            .num_pages()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询登录历史页数失败: {e}")))?;
+        let models = paginator
+            .fetch_page(page - 1)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询登录历史失败: {e}")))?;
+
+        Ok(LoginHistoryListResponse {
+            items: models.into_iter().map(|m| m.into_login_history()).collect(),
+            pagination: PaginationInfo {
+                page: page as i64,
+                page_size: size as i64,
+                total: total as i64,
+                total_pages: pages as i64,
+            },
+        })
+    }
+}
@@ -0,0 +1,157 @@
+//! SQLite 数据库维护：文件状态、WAL 检查点与在线备份
+//!
+//! 仅在 SQLite 连接上执行，其他数据库由运维工具自行管理。
+
+use std::path::{Path, PathBuf};
+
+use super::SeaOrmStorage;
+use crate::errors::{HWSystemError, Result};
+use crate::models::system::entities::WalCheckpointMode;
+use crate::models::system::responses::{DatabaseStatus, WalCheckpointResponse};
+use sea_orm::{ConnectionTrait, DbBackend, QueryResult, Statement};
+
+/// 文件大小，文件不存在时为 0
+fn file_size(path: &Path) -> i64 {
+    std::fs::metadata(path).map(|m| m.len() as i64).unwrap_or(0)
+}
+
+/// WAL 文件与数据库文件同目录，文件名追加 `-wal`
+fn wal_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push("-wal");
+    PathBuf::from(name)
+}
+
+impl SeaOrmStorage {
+    fn is_sqlite(&self) -> bool {
+        self.db.get_database_backend() == DbBackend::Sqlite
+    }
+
+    /// 执行 PRAGMA 并返回第一行
+    async fn pragma_row(&self, pragma: &str) -> Result<QueryResult> {
+        self.db
+            .query_one_raw(Statement::from_string(
+                DbBackend::Sqlite,
+                format!("PRAGMA {pragma}"),
+            ))
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("执行 PRAGMA {pragma} 失败: {e}"))
+            })?
+            .ok_or_else(|| HWSystemError::database_operation(format!("PRAGMA {pragma} 无结果")))
+    }
+
+    async fn pragma_i64(&self, pragma: &str) -> Result<i64> {
+        self.pragma_row(pragma)
+            .await?
+            .try_get_by_index::<i64>(0)
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("读取 PRAGMA {pragma} 失败: {e}"))
+            })
+    }
+
+    /// 主数据库文件路径，内存数据库返回 None
+    async fn sqlite_file_path(&self) -> Result<Option<PathBuf>> {
+        let file: String = self
+            .pragma_row("database_list")
+            .await?
+            .try_get("", "file")
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("读取数据库文件路径失败: {e}"))
+            })?;
+
+        Ok((!file.is_empty()).then(|| PathBuf::from(file)))
+    }
+
+    /// 查询数据库类型与文件大小
+    pub async fn get_database_status_impl(&self) -> Result<DatabaseStatus> {
+        // sqlite / postgres / mysql
+        let backend = format!("{:?}", self.db.get_database_backend()).to_lowercase();
+        if !self.is_sqlite() {
+            return Ok(DatabaseStatus {
+                backend,
+                file_path: None,
+                journal_mode: None,
+                database_size: None,
+                wal_size: None,
+                page_size: None,
+                page_count: None,
+                freelist_count: None,
+            });
+        }
+
+        let file_path = self.sqlite_file_path().await?;
+        let journal_mode: String = self
+            .pragma_row("journal_mode")
+            .await?
+            .try_get_by_index(0)
+            .map_err(|e| HWSystemError::database_operation(format!("读取日志模式失败: {e}")))?;
+        let page_size = self.pragma_i64("page_size").await?;
+        let page_count = self.pragma_i64("page_count").await?;
+        let freelist_count = self.pragma_i64("freelist_count").await?;
+
+        Ok(DatabaseStatus {
+            backend,
+            wal_size: file_path.as_deref().map(|p| file_size(&wal_path(p))),
+            file_path: file_path.map(|p| p.display().to_string()),
+            journal_mode: Some(journal_mode),
+            database_size: Some(page_size * page_count),
+            page_size: Some(page_size),
+            page_count: Some(page_count),
+            freelist_count: Some(freelist_count),
+        })
+    }
+
+    /// 执行 WAL 检查点
+    pub async fn checkpoint_wal_impl(
+        &self,
+        mode: WalCheckpointMode,
+    ) -> Result<Option<WalCheckpointResponse>> {
+        if !self.is_sqlite() {
+            return Ok(None);
+        }
+
+        // 返回 (busy, log, checkpointed)
+        let row = self
+            .pragma_row(&format!("wal_checkpoint({})", mode.pragma_arg()))
+            .await?;
+        let column = |index: usize| {
+            row.try_get_by_index::<i64>(index)
+                .map_err(|e| HWSystemError::database_operation(format!("读取检查点结果失败: {e}")))
+        };
+        let busy = column(0)? != 0;
+        let log_frames = column(1)?;
+        let checkpointed_frames = column(2)?;
+
+        let wal_size = self
+            .sqlite_file_path()
+            .await?
+            .map(|p| file_size(&wal_path(&p)));
+
+        Ok(Some(WalCheckpointResponse {
+            mode,
+            busy,
+            log_frames,
+            checkpointed_frames,
+            wal_size,
+        }))
+    }
+
+    /// 在线备份：VACUUM INTO 在读事务中生成一致且紧凑的副本，不阻塞其他连接的写入
+    pub async fn backup_database_impl(&self, target: &Path) -> Result<bool> {
+        if !self.is_sqlite() {
+            return Ok(false);
+        }
+
+        self.db
+            .execute_raw(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                "VACUUM INTO ?",
+                [target.display().to_string().into()],
+            ))
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("备份数据库失败: {e}")))?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,255 @@
+//! 私信存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::messages::{ActiveModel, Column, Entity as Messages};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::messages::{
+    entities::Message,
+    responses::{ConversationListResponse, ConversationSummary, MessagePeer},
+};
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+/// 班级内两个用户之间的会话条件
+fn conversation_condition(class_id: i64, user_id: i64, peer_id: i64) -> Condition {
+    Condition::all().add(Column::ClassId.eq(class_id)).add(
+        Condition::any()
+            .add(
+                Condition::all()
+                    .add(Column::SenderId.eq(user_id))
+                    .add(Column::RecipientId.eq(peer_id)),
+            )
+            .add(
+                Condition::all()
+                    .add(Column::SenderId.eq(peer_id))
+                    .add(Column::RecipientId.eq(user_id)),
+            ),
+    )
+}
+
+impl SeaOrmStorage {
+    /// 保存私信
+    pub async fn create_message_impl(
+        &self,
+        class_id: i64,
+        sender_id: i64,
+        recipient_id: i64,
+        content: &str,
+    ) -> Result<Message> {
+        let model = ActiveModel {
+            class_id: Set(class_id),
+            sender_id: Set(sender_id),
+            recipient_id: Set(recipient_id),
+            content: Set(content.to_string()),
+            read_at: Set(None),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("保存私信失败: {e}")))?;
+
+        Ok(model.into_message())
+    }
+
+    /// 列出用户的会话，最近有消息的在前
+    ///
+    /// 按（班级, 发送方, 接收方）分组取最新消息 ID 后在内存中合并两个方向，
+    /// 未读数按（班级, 发送方）分组统计。
+    pub async fn list_conversations_impl(
+        &self,
+        user_id: i64,
+        class_id: Option<i64>,
+    ) -> Result<ConversationListResponse> {
+        let mut latest_query = Messages::find()
+            .select_only()
+            .column(Column::ClassId)
+            .column(Column::SenderId)
+            .column(Column::RecipientId)
+            .column_as(Func::max(Expr::col((Messages, Column::Id))), "last_id")
+            .filter(
+                Condition::any()
+                    .add(Column::SenderId.eq(user_id))
+                    .add(Column::RecipientId.eq(user_id)),
+            );
+        if let Some(class_id) = class_id {
+            latest_query = latest_query.filter(Column::ClassId.eq(class_id));
+        }
+        let rows: Vec<(i64, i64, i64, i64)> = latest_query
+            .group_by(Column::ClassId)
+            .group_by(Column::SenderId)
+            .group_by(Column::RecipientId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询会话失败: {e}")))?;
+
+        // (班级, 对方) -> 最新消息 ID
+        let mut latest: HashMap<(i64, i64), i64> = HashMap::new();
+        for (class_id, sender_id, recipient_id, last_id) in rows {
+            let peer_id = if sender_id == user_id {
+                recipient_id
+            } else {
+                sender_id
+            };
+            let entry = latest.entry((class_id, peer_id)).or_insert(last_id);
+            *entry = (*entry).max(last_id);
+        }
+        if latest.is_empty() {
+            return Ok(ConversationListResponse {
+                items: Vec::new(),
+                total_unread: 0,
+            });
+        }
+
+        let mut unread_query = Messages::find()
+            .select_only()
+            .column(Column::ClassId)
+            .column(Column::SenderId)
+            .column_as(Func::count(Expr::col((Messages, Column::Id))), "count")
+            .filter(Column::RecipientId.eq(user_id))
+            .filter(Column::ReadAt.is_null());
+        if let Some(class_id) = class_id {
+            unread_query = unread_query.filter(Column::ClassId.eq(class_id));
+        }
+        let unread: HashMap<(i64, i64), i64> = unread_query
+            .group_by(Column::ClassId)
+            .group_by(Column::SenderId)
+            .into_tuple::<(i64, i64, i64)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计未读私信失败: {e}")))?
+            .into_iter()
+            .map(|(class_id, sender_id, count)| ((class_id, sender_id), count))
+            .collect();
+
+        let messages: HashMap<i64, Message> = Messages::find()
+            .filter(Column::Id.is_in(latest.values().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询私信失败: {e}")))?
+            .into_iter()
+            .map(|m| (m.id, m.into_message()))
+            .collect();
+
+        let peers: HashMap<i64, MessagePeer> = Users::find()
+            .filter(UserColumn::Id.is_in(latest.keys().map(|(_, peer_id)| *peer_id)))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?
+            .into_iter()
+            .map(|u| {
+                (
+                    u.id,
+                    MessagePeer {
+                        id: u.id,
+                        username: u.username,
+                        display_name: u.display_name,
+                        avatar_url: u.avatar_url,
+                    },
+                )
+            })
+            .collect();
+
+        let class_names: HashMap<i64, String> = Classes::find()
+            .select_only()
+            .column(ClassColumn::Id)
+            .column(ClassColumn::Name)
+            .filter(ClassColumn::Id.is_in(latest.keys().map(|(class_id, _)| *class_id)))
+            .into_tuple::<(i64, String)>()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        let mut items: Vec<ConversationSummary> = latest
+            .into_iter()
+            .filter_map(|((class_id, peer_id), last_id)| {
+                Some(ConversationSummary {
+                    class_id,
+                    class_name: class_names.get(&class_id)?.clone(),
+                    peer: peers.get(&peer_id)?.clone(),
+                    last_message: messages.get(&last_id)?.clone(),
+                    unread_count: unread.get(&(class_id, peer_id)).copied().unwrap_or(0),
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| b.last_message.id.cmp(&a.last_message.id));
+
+        let total_unread = items.iter().map(|item| item.unread_count).sum();
+        Ok(ConversationListResponse {
+            items,
+            total_unread,
+        })
+    }
+
+    /// 查询会话消息，新消息在前；多取一条用于判断是否还有更早的消息
+    pub async fn list_conversation_messages_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        peer_id: i64,
+        before_id: Option<i64>,
+        limit: u64,
+    ) -> Result<(Vec<Message>, bool)> {
+        let mut select =
+            Messages::find().filter(conversation_condition(class_id, user_id, peer_id));
+        if let Some(before_id) = before_id {
+            select = select.filter(Column::Id.lt(before_id));
+        }
+
+        let mut models = select
+            .order_by_desc(Column::Id)
+            .limit(limit + 1)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询会话消息失败: {e}")))?;
+
+        let has_more = models.len() as u64 > limit;
+        models.truncate(limit as usize);
+        Ok((
+            models.into_iter().map(|m| m.into_message()).collect(),
+            has_more,
+        ))
+    }
+
+    /// 将对方在会话中发来的未读消息标记为已读，返回标记条数
+    pub async fn mark_conversation_read_impl(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        peer_id: i64,
+    ) -> Result<i64> {
+        let result = Messages::update_many()
+            .col_expr(Column::ReadAt, Expr::value(chrono::Utc::now().timestamp()))
+            .filter(Column::ClassId.eq(class_id))
+            .filter(Column::SenderId.eq(peer_id))
+            .filter(Column::RecipientId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("标记私信已读失败: {e}")))?;
+
+        Ok(result.rows_affected as i64)
+    }
+
+    /// 统计用户未读私信数
+    pub async fn count_unread_messages_impl(&self, user_id: i64) -> Result<i64> {
+        let count = Messages::find()
+            .filter(Column::RecipientId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .count(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计未读私信失败: {e}")))?;
+
+        Ok(count as i64)
+    }
+}
@@ -0,0 +1,287 @@
+//! 缺交零分策略存储操作
+
+use std::collections::HashSet;
+
+use super::SeaOrmStorage;
+use super::homework_counters::refresh_homework_counters;
+use super::outbox::insert_outbox_events;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::grades::{
+    ActiveModel as GradeActiveModel, Column as GradeColumn, Entity as Grades,
+};
+use crate::entity::homework_exemptions::{Column as ExemptionColumn, Entity as HomeworkExemptions};
+use crate::entity::homeworks::Entity as Homeworks;
+use crate::entity::missing_grade_policies::{ActiveModel, Column, Entity as MissingGradePolicies};
+use crate::entity::submissions::{
+    ActiveModel as SubmissionActiveModel, Column as SubmissionColumn, Entity as Submissions,
+};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::homeworks::entities::MissingGradePolicy;
+use crate::models::notifications::entities::{NotificationType, ReferenceType};
+use crate::models::outbox::entities::OutboxEvent;
+use crate::models::submissions::entities::SubmissionStatus;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait,
+    Set, TransactionTrait, sea_query::Expr,
+};
+
+/// 自动零分的评语
+const AUTO_GRADE_COMMENT: &str = "截止前未提交，系统自动记为零分";
+
+impl SeaOrmStorage {
+    /// 获取作业的缺交零分策略
+    pub async fn get_missing_grade_policy_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Option<MissingGradePolicy>> {
+        let result = MissingGradePolicies::find_by_id(homework_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询缺交零分策略失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_missing_grade_policy()))
+    }
+
+    /// 创建或更新缺交零分策略（不影响已执行状态）
+    pub async fn upsert_missing_grade_policy_impl(
+        &self,
+        homework_id: i64,
+        cutoff_at: i64,
+        user_id: i64,
+    ) -> Result<MissingGradePolicy> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = MissingGradePolicies::find_by_id(homework_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询缺交零分策略失败: {e}")))?;
+
+        let result = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.cutoff_at = Set(cutoff_at);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    homework_id: Set(homework_id),
+                    cutoff_at: Set(cutoff_at),
+                    applied_at: Set(None),
+                    created_by: Set(user_id),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存缺交零分策略失败: {e}")))?;
+
+        Ok(result.into_missing_grade_policy())
+    }
+
+    /// 删除缺交零分策略（已记录的零分保留）
+    pub async fn delete_missing_grade_policy_impl(&self, homework_id: i64) -> Result<bool> {
+        let result = MissingGradePolicies::delete_by_id(homework_id)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除缺交零分策略失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 列出已到最终截止时间、尚未执行的策略
+    pub async fn list_due_missing_grade_policies_impl(
+        &self,
+        now: i64,
+    ) -> Result<Vec<MissingGradePolicy>> {
+        let results = MissingGradePolicies::find()
+            .filter(Column::AppliedAt.is_null())
+            .filter(Column::CutoffAt.lte(now))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询缺交零分策略失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_missing_grade_policy())
+            .collect())
+    }
+
+    /// 执行缺交零分策略
+    ///
+    /// 为班级中没有任何提交、未被豁免的学生写入占位提交与 `auto_assigned` 零分，
+    /// 并在同一事务中写入通知学生的发件箱事件。策略只执行一次（并发执行时只有一个生效），
+    /// 返回被记零分的学生 ID；策略不存在、未到期或已执行时返回空列表。
+    pub async fn apply_missing_grade_policy_impl(&self, homework_id: i64) -> Result<Vec<i64>> {
+        let now = chrono::Utc::now().timestamp();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let claimed = MissingGradePolicies::update_many()
+            .col_expr(Column::AppliedAt, Expr::value(now))
+            .filter(Column::HomeworkId.eq(homework_id))
+            .filter(Column::AppliedAt.is_null())
+            .filter(Column::CutoffAt.lte(now))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("标记缺交零分策略失败: {e}")))?;
+        if claimed.rows_affected == 0 {
+            return Ok(vec![]);
+        }
+
+        let policy = MissingGradePolicies::find_by_id(homework_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询缺交零分策略失败: {e}")))?;
+        let homework = Homeworks::find_by_id(homework_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
+        let (Some(policy), Some(homework)) = (policy, homework) else {
+            return Ok(vec![]);
+        };
+
+        // 需要提交的学生：班级非教师成员，排除被豁免和已有提交（含被退回）的学生
+        let student_ids: Vec<i64> = ClassUsers::find()
+            .select_only()
+            .column(ClassUserColumn::UserId)
+            .filter(ClassUserColumn::ClassId.eq(homework.class_id))
+            .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+            .into_tuple()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+        let exempted: HashSet<i64> = HomeworkExemptions::find()
+            .select_only()
+            .column(ExemptionColumn::UserId)
+            .filter(ExemptionColumn::HomeworkId.eq(homework_id))
+            .into_tuple::<i64>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?
+            .into_iter()
+            .collect();
+        let submitted: HashSet<i64> = Submissions::find()
+            .select_only()
+            .column(SubmissionColumn::CreatorId)
+            .filter(SubmissionColumn::HomeworkId.eq(homework_id))
+            .distinct()
+            .into_tuple::<i64>()
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?
+            .into_iter()
+            .collect();
+        let missing: Vec<i64> = student_ids
+            .into_iter()
+            .filter(|id| !exempted.contains(id) && !submitted.contains(id))
+            .collect();
+
+        for student_id in &missing {
+            let submission = SubmissionActiveModel {
+                homework_id: Set(homework_id),
+                creator_id: Set(*student_id),
+                part_id: Set(None),
+                version: Set(1),
+                content: Set(None),
+                status: Set(SubmissionStatus::GRADED.to_string()),
+                is_late: Set(false),
+                submitted_at: Set(policy.cutoff_at),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建占位提交失败: {e}")))?;
+
+            GradeActiveModel {
+                submission_id: Set(submission.id),
+                grader_id: Set(policy.created_by),
+                score: Set(0.0),
+                comment: Set(Some(AUTO_GRADE_COMMENT.to_string())),
+                graded_at: Set(now),
+                updated_at: Set(now),
+                auto_assigned: Set(true),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建自动零分失败: {e}")))?;
+        }
+
+        if !missing.is_empty() {
+            refresh_homework_counters(&txn, &[homework_id]).await?;
+            insert_outbox_events(
+                &txn,
+                vec![OutboxEvent::Notification {
+                    user_ids: missing.clone(),
+                    notification_type: NotificationType::MissingGradeAssigned,
+                    vars: vec![("homework_title".to_string(), homework.title)],
+                    reference_type: Some(ReferenceType::Homework),
+                    reference_id: Some(homework_id),
+                }],
+            )
+            .await?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(missing)
+    }
+
+    /// 撤销学生的自动零分（连同占位提交一并删除），没有自动零分时返回 false
+    pub async fn revert_auto_assigned_grade_impl(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<bool> {
+        let submission_ids: Vec<i64> = Grades::find()
+            .select_only()
+            .column(GradeColumn::SubmissionId)
+            .join(
+                JoinType::InnerJoin,
+                crate::entity::grades::Relation::Submission.def(),
+            )
+            .filter(SubmissionColumn::HomeworkId.eq(homework_id))
+            .filter(SubmissionColumn::CreatorId.eq(user_id))
+            .filter(GradeColumn::AutoAssigned.eq(true))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询自动零分失败: {e}")))?;
+        if submission_ids.is_empty() {
+            return Ok(false);
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        Grades::delete_many()
+            .filter(GradeColumn::SubmissionId.is_in(submission_ids.iter().copied()))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除自动零分失败: {e}")))?;
+        Submissions::delete_many()
+            .filter(SubmissionColumn::Id.is_in(submission_ids))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除占位提交失败: {e}")))?;
+        refresh_homework_counters(&txn, &[homework_id]).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(true)
+    }
+}
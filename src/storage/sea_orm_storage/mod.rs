@@ -2,20 +2,75 @@
 //!
 //! 统一的数据库存储层，支持 SQLite、PostgreSQL 和 MySQL。
 
+mod activity_events;
+mod batch;
+mod certificates;
+mod class_activity;
+mod class_analytics;
+mod class_api_tokens;
+mod class_erasure;
+mod class_im_channels;
+mod class_leaderboard;
+mod class_role_labels;
+mod class_stats;
 mod class_users;
+mod class_workload;
 mod classes;
+mod content_reports;
+mod dashboard;
+mod deadline_conflicts;
+mod dev_data;
+mod exam_access_logs;
+mod file_references;
+mod file_retention;
 mod files;
+mod grade_drafts;
+mod grade_statistics;
 mod grades;
+mod grading_sla;
+mod homework_counters;
+mod homework_exemptions;
+mod homework_feedback;
+mod homework_links;
+mod homework_parts;
+mod homework_prerequisites;
+mod homework_rubrics;
+mod homework_share_links;
+mod homework_solutions;
 mod homeworks;
+mod integrity;
+mod invalidation;
+mod login_history;
+mod maintenance;
+mod messages;
+mod missing_grade_policies;
+mod moderation_flags;
+mod notification_templates;
 mod notifications;
+mod organizations;
+mod outbox;
+mod quarantined_files;
+mod reactions;
+mod resubmissions;
+mod role_requests;
+mod rubrics;
+mod scheduled_exports;
+mod self_assessments;
+mod student_goals;
+mod submission_references;
+mod submission_workflows;
 mod submissions;
 mod system_settings;
+mod usage;
+mod user_admin_permissions;
+mod user_merge;
 mod users;
+mod viewer_grants;
 
 use crate::config::AppConfig;
 use crate::errors::{HWSystemError, Result};
 use migration::{Migrator, MigratorTrait};
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend};
 use std::time::Duration;
 use tracing::info;
 
@@ -23,13 +78,22 @@ use tracing::info;
 #[derive(Clone)]
 pub struct SeaOrmStorage {
     pub(crate) db: DatabaseConnection,
+    /// 缓存失效的 NOTIFY 频道，仅 PostgreSQL 且启用 `cache.pg_notify` 时存在
+    invalidation_channel: Option<String>,
 }
 
 impl SeaOrmStorage {
     /// 创建新的 SeaORM 存储实例
     pub async fn new_async() -> Result<Self> {
+        Self::new_with_url(&AppConfig::get().database.url).await
+    }
+
+    /// 使用指定的数据库 URL 创建存储实例（连接池参数仍取自配置）
+    ///
+    /// 传入 `:memory:` 可得到独立的内存 SQLite 数据库，供集成测试使用。
+    pub async fn new_with_url(url: &str) -> Result<Self> {
         let config = AppConfig::get();
-        let db_url = Self::build_database_url(&config.database.url)?;
+        let db_url = Self::build_database_url(url)?;
 
         // 根据数据库类型选择连接方式
         let db = if db_url.starts_with("sqlite://") {
@@ -45,7 +109,14 @@ impl SeaOrmStorage {
 
         info!("SeaORM 存储初始化完成，数据库: {}", db_url);
 
-        Ok(Self { db })
+        let invalidation_channel = (config.cache.pg_notify.enabled
+            && db.get_database_backend() == DbBackend::Postgres)
+            .then(|| config.cache.pg_notify.channel.clone());
+
+        Ok(Self {
+            db,
+            invalidation_channel,
+        })
     }
 
     /// SQLite 专用连接（WAL + pragma 优化）
@@ -119,50 +190,124 @@ impl SeaOrmStorage {
 
 // Storage trait 实现
 use crate::models::{
+    analytics::responses::{ClassComparisonItem, TeacherGradingLatencyItem},
+    auth::responses::StudentDashboard,
+    certificates::{
+        entities::{Certificate, CertificateProgress, CertificateSettings},
+        requests::CertificateSettingsInput,
+    },
     class_users::{
-        entities::{ClassUser, ClassUserRole},
+        entities::{
+            ClassRoleLabels, ClassUser, ClassUserBatchAction, ClassUserBatchOutcome, ClassUserRole,
+        },
         requests::{ClassUserQuery, UpdateClassUserRequest},
         responses::ClassUserListResponse,
     },
     classes::{
-        entities::Class,
-        requests::{ClassListQuery, CreateClassRequest, UpdateClassRequest},
-        responses::ClassListResponse,
+        entities::{
+            ActivityEvent, ActivityEventType, Class, ClassApiToken, ClassApiTokenLog,
+            ClassErasureCounts, ClassImChannel, ClassLeaderboardSettings, ClassScheduledExport,
+            ClassStatsDaily, ClassWorkloadDay, HomeworkCalibration, ImDelivery, ImEvent,
+            LeaderboardMetric, LeaderboardStanding, NewActivityEvent, NewClassApiToken,
+            NewImDelivery,
+        },
+        requests::{
+            ClassListQuery, CreateClassImChannelRequest, CreateClassRequest,
+            CreateClassScheduledExportRequest, UpdateClassImChannelRequest, UpdateClassRequest,
+            UpdateClassScheduledExportRequest,
+        },
+        responses::{ClassListResponse, StudentActivity},
+    },
+    files::{
+        entities::{
+            ClassRetentionOverride, File, FileReference, FileRetentionPolicy, QuarantinedFile,
+            RetentionCandidate, StorageTier,
+        },
+        requests::{QuarantinedFileInput, UpdateClassRetentionRequest},
     },
-    files::entities::File,
     grades::{
-        entities::Grade,
-        requests::{CreateGradeRequest, GradeListQuery, UpdateGradeRequest},
+        entities::{Grade, GradeDraft, GradeStatistics},
+        requests::{CreateGradeRequest, GradeListQuery, SaveGradeDraftRequest, UpdateGradeRequest},
         responses::GradeListResponse,
     },
     homeworks::{
-        entities::Homework,
+        entities::{
+            AttachmentKind, ExamAccessLog, Homework, HomeworkExemption, HomeworkFeedback,
+            HomeworkLink, HomeworkLinkOpen, HomeworkPart, HomeworkPrerequisite, HomeworkShareLink,
+            HomeworkSolution, MissingGradePolicy, SolutionRevealPolicy,
+        },
         requests::{
-            AllHomeworksQuery, CreateHomeworkRequest, HomeworkListQuery, UpdateHomeworkRequest,
+            AllHomeworksQuery, CreateHomeworkRequest, ExamAccessLogInput, HomeworkAttachmentInput,
+            HomeworkLinkInput, HomeworkListQuery, HomeworkPartInput, HomeworkPrerequisiteInput,
+            UpdateHomeworkRequest,
+        },
+        responses::{AllHomeworksResponse, DeadlineConflictItem, HomeworkListResponse},
+    },
+    messages::{entities::Message, responses::ConversationListResponse},
+    moderation::{
+        entities::{
+            ContentReport, ContentReportAction, ContentReportStatus, ModerationFlag,
+            ModerationFlagStatus, ReportContentType, ReportedContent,
         },
-        responses::{AllHomeworksResponse, HomeworkListResponse},
+        requests::{ContentReportInput, ModerationFlagInput},
     },
     notifications::{
-        entities::Notification,
-        requests::{CreateNotificationRequest, NotificationListQuery},
+        entities::{Notification, NotificationTemplate, NotificationType},
+        requests::{
+            CreateNotificationRequest, NotificationListQuery, NotificationRecipientFilter,
+            UpsertNotificationTemplateRequest,
+        },
         responses::NotificationListResponse,
     },
+    organizations::{
+        entities::{Organization, TenantScope},
+        requests::{CreateOrganizationRequest, OrganizationListQuery, UpdateOrganizationRequest},
+        responses::OrganizationListResponse,
+    },
+    outbox::entities::OutboxMessage,
+    reactions::entities::{ReactionSummary, ReactionTargetType},
+    rubrics::{
+        entities::{HomeworkRubric, Rubric, RubricCriterion, RubricVersion},
+        requests::{RubricInput, RubricScope, RubricUpdate},
+    },
     submissions::{
-        entities::Submission,
-        requests::{CreateSubmissionRequest, SubmissionListQuery},
+        entities::{
+            GradingSlaBreach, ResubmissionRequest, Submission, SubmissionSelfAssessment,
+            SubmissionWorkflow,
+        },
+        requests::{
+            ArchivedSubmissionInput, CreateSubmissionRequest, SubmissionListQuery,
+            SubmissionReferenceSnapshot, SubmissionSummaryFilter,
+        },
         responses::{
             SubmissionListResponse, SubmissionResponse, SubmissionSummaryResponse,
             UserSubmissionHistoryItem,
         },
     },
+    usage::{
+        entities::{UsageCounter, UsageMetric},
+        requests::UsageReportListQuery,
+        responses::UsageReportListResponse,
+    },
     users::{
-        entities::{User, UserRole, UserStatus},
-        requests::{CreateUserRequest, UpdateUserRequest, UserListQuery},
-        responses::{UserListResponse, UserStatsResponse},
+        entities::{
+            AdminPermission, BulkUserAction, GoalStanding, LoginHistory, RoleRequest, StudentGoal,
+            User, UserRole, UserStatus,
+        },
+        requests::{
+            BulkUserFilter, CreateLoginHistory, CreateUserRequest, LoginHistoryQuery,
+            RoleRequestListQuery, UpdateUserRequest, UserListQuery,
+        },
+        responses::{
+            BulkUserItemResult, LoginHistoryListResponse, MergeUsersResponse,
+            RoleRequestListResponse, UserListResponse, UserStatsResponse,
+        },
     },
+    viewers::entities::ViewerGrant,
 };
 use crate::storage::Storage;
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 #[async_trait]
 impl Storage for SeaOrmStorage {
@@ -174,6 +319,10 @@ impl Storage for SeaOrmStorage {
         self.create_user_impl(user).await
     }
 
+    async fn create_users_batch(&self, users: Vec<CreateUserRequest>) -> Result<usize> {
+        self.create_users_batch_impl(users).await
+    }
+
     async fn get_user_by_id(&self, id: i64) -> Result<Option<User>> {
         self.get_user_by_id_impl(id).await
     }
@@ -198,6 +347,17 @@ impl Storage for SeaOrmStorage {
         self.update_last_login_impl(id).await
     }
 
+    async fn record_login_history(&self, entry: CreateLoginHistory) -> Result<LoginHistory> {
+        self.record_login_history_impl(entry).await
+    }
+
+    async fn list_login_history(
+        &self,
+        query: LoginHistoryQuery,
+    ) -> Result<LoginHistoryListResponse> {
+        self.list_login_history_impl(query).await
+    }
+
     async fn update_user(&self, id: i64, update: UpdateUserRequest) -> Result<Option<User>> {
         self.update_user_impl(id, update).await
     }
@@ -228,8 +388,9 @@ impl Storage for SeaOrmStorage {
         role: Option<UserRole>,
         status: Option<UserStatus>,
         search: Option<&str>,
+        tenant: TenantScope,
     ) -> Result<Vec<User>> {
-        self.list_users_for_export_filtered_impl(limit, role, status, search)
+        self.list_users_for_export_filtered_impl(limit, role, status, search, tenant)
             .await
     }
 
@@ -237,6 +398,174 @@ impl Storage for SeaOrmStorage {
         self.get_user_stats_impl(user_id, role).await
     }
 
+    async fn get_student_dashboard(
+        &self,
+        user_id: i64,
+        upcoming_until: i64,
+        recent_grade_limit: u64,
+    ) -> Result<StudentDashboard> {
+        self.get_student_dashboard_impl(user_id, upcoming_until, recent_grade_limit)
+            .await
+    }
+
+    async fn get_user_admin_permissions(&self, user_id: i64) -> Result<Vec<AdminPermission>> {
+        self.get_user_admin_permissions_impl(user_id).await
+    }
+
+    async fn set_user_admin_permissions(
+        &self,
+        user_id: i64,
+        permissions: &[AdminPermission],
+        granted_by: i64,
+    ) -> Result<Vec<AdminPermission>> {
+        self.set_user_admin_permissions_impl(user_id, permissions, granted_by)
+            .await
+    }
+
+    async fn list_users_by_ids(&self, ids: &[i64]) -> Result<Vec<User>> {
+        self.list_users_by_ids_impl(ids).await
+    }
+
+    async fn list_users_for_bulk(
+        &self,
+        filter: BulkUserFilter,
+        tenant: TenantScope,
+        limit: u64,
+    ) -> Result<Vec<User>> {
+        self.list_users_for_bulk_impl(filter, tenant, limit).await
+    }
+
+    async fn bulk_apply_user_action(
+        &self,
+        action: BulkUserAction,
+        role: Option<UserRole>,
+        user_ids: &[i64],
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<Vec<BulkUserItemResult>> {
+        self.bulk_apply_user_action_impl(action, role, user_ids, operator_id, ip_address)
+            .await
+    }
+
+    async fn merge_users(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<MergeUsersResponse> {
+        self.merge_users_impl(source_id, target_id, operator_id, ip_address)
+            .await
+    }
+
+    // ============================================
+    // 角色申请模块
+    // ============================================
+
+    async fn create_role_request(
+        &self,
+        user_id: i64,
+        org_id: Option<i64>,
+        requested_role: UserRole,
+        reason: String,
+        attachment_file_id: Option<i64>,
+    ) -> Result<RoleRequest> {
+        self.create_role_request_impl(user_id, org_id, requested_role, reason, attachment_file_id)
+            .await
+    }
+
+    async fn get_role_request_by_id(&self, id: i64) -> Result<Option<RoleRequest>> {
+        self.get_role_request_by_id_impl(id).await
+    }
+
+    async fn get_pending_role_request(&self, user_id: i64) -> Result<Option<RoleRequest>> {
+        self.get_pending_role_request_impl(user_id).await
+    }
+
+    async fn list_role_requests(
+        &self,
+        query: RoleRequestListQuery,
+    ) -> Result<RoleRequestListResponse> {
+        self.list_role_requests_impl(query).await
+    }
+
+    async fn review_role_request(
+        &self,
+        id: i64,
+        approve: bool,
+        comment: Option<String>,
+        reviewer_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<Option<RoleRequest>> {
+        self.review_role_request_impl(id, approve, comment, reviewer_id, ip_address)
+            .await
+    }
+
+    // ============================================
+    // 学习目标模块
+    // ============================================
+
+    async fn upsert_student_goal(
+        &self,
+        user_id: i64,
+        class_id: i64,
+        target_percent: f64,
+    ) -> Result<StudentGoal> {
+        self.upsert_student_goal_impl(user_id, class_id, target_percent)
+            .await
+    }
+
+    async fn delete_student_goal(&self, user_id: i64, class_id: i64) -> Result<bool> {
+        self.delete_student_goal_impl(user_id, class_id).await
+    }
+
+    async fn list_goal_standings(&self, user_id: i64) -> Result<Vec<GoalStanding>> {
+        self.list_goal_standings_impl(user_id).await
+    }
+
+    // ============================================
+    // 家长/导师查看授权模块
+    // ============================================
+
+    async fn create_viewer_invitation(
+        &self,
+        student_id: i64,
+        token: &str,
+        note: Option<String>,
+        expires_at: i64,
+    ) -> Result<ViewerGrant> {
+        self.create_viewer_invitation_impl(student_id, token, note, expires_at)
+            .await
+    }
+
+    async fn get_viewer_grant_by_token(&self, token: &str) -> Result<Option<ViewerGrant>> {
+        self.get_viewer_grant_by_token_impl(token).await
+    }
+
+    async fn accept_viewer_invitation(
+        &self,
+        token: &str,
+        viewer_id: i64,
+    ) -> Result<Option<ViewerGrant>> {
+        self.accept_viewer_invitation_impl(token, viewer_id).await
+    }
+
+    async fn list_viewer_grants_by_student(&self, student_id: i64) -> Result<Vec<ViewerGrant>> {
+        self.list_viewer_grants_by_student_impl(student_id).await
+    }
+
+    async fn list_active_viewer_grants(&self, viewer_id: i64) -> Result<Vec<ViewerGrant>> {
+        self.list_active_viewer_grants_impl(viewer_id).await
+    }
+
+    async fn has_viewer_grant(&self, viewer_id: i64, student_id: i64) -> Result<bool> {
+        self.has_viewer_grant_impl(viewer_id, student_id).await
+    }
+
+    async fn revoke_viewer_grant(&self, student_id: i64, grant_id: i64) -> Result<bool> {
+        self.revoke_viewer_grant_impl(student_id, grant_id).await
+    }
+
     // ============================================
     // 文件模块
     // ============================================
@@ -247,10 +576,18 @@ impl Storage for SeaOrmStorage {
         stored_name: &str,
         file_size: &i64,
         file_type: &str,
+        code_language: Option<&str>,
         user_id: i64,
     ) -> Result<File> {
-        self.upload_file_impl(original_name, stored_name, file_size, file_type, user_id)
-            .await
+        self.upload_file_impl(
+            original_name,
+            stored_name,
+            file_size,
+            file_type,
+            code_language,
+            user_id,
+        )
+        .await
     }
 
     async fn get_file_by_token(&self, token: &str) -> Result<Option<File>> {
@@ -261,6 +598,10 @@ impl Storage for SeaOrmStorage {
         self.get_file_by_id_impl(id).await
     }
 
+    async fn list_files_by_ids(&self, ids: &[i64]) -> Result<Vec<File>> {
+        self.list_files_by_ids_impl(ids).await
+    }
+
     async fn increment_file_citation(&self, file_id: i64) -> Result<bool> {
         self.increment_file_citation_impl(file_id).await
     }
@@ -269,6 +610,79 @@ impl Storage for SeaOrmStorage {
         self.decrement_file_citation_impl(file_id).await
     }
 
+    async fn get_class_retention_override(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<ClassRetentionOverride>> {
+        self.get_class_retention_override_impl(class_id).await
+    }
+
+    async fn upsert_class_retention_override(
+        &self,
+        class_id: i64,
+        req: UpdateClassRetentionRequest,
+        updated_by: i64,
+    ) -> Result<ClassRetentionOverride> {
+        self.upsert_class_retention_override_impl(class_id, req, updated_by)
+            .await
+    }
+
+    async fn find_retention_candidates(
+        &self,
+        policy: FileRetentionPolicy,
+        horizon: i64,
+        class_id: Option<i64>,
+    ) -> Result<Vec<RetentionCandidate>> {
+        self.find_retention_candidates_impl(policy, horizon, class_id)
+            .await
+    }
+
+    async fn purge_files(&self, file_ids: &[i64]) -> Result<Vec<File>> {
+        self.purge_files_impl(file_ids).await
+    }
+
+    async fn find_tiering_candidates(&self, archived_before: i64) -> Result<Vec<File>> {
+        self.find_tiering_candidates_impl(archived_before).await
+    }
+
+    async fn set_file_storage_tier(&self, file_id: i64, tier: StorageTier) -> Result<bool> {
+        self.set_file_storage_tier_impl(file_id, tier).await
+    }
+
+    async fn list_user_files(&self, user_id: i64) -> Result<Vec<File>> {
+        self.list_user_files_impl(user_id).await
+    }
+
+    async fn list_file_references(
+        &self,
+        files: &[File],
+    ) -> Result<HashMap<i64, Vec<FileReference>>> {
+        self.list_file_references_impl(files).await
+    }
+
+    async fn create_quarantined_file(
+        &self,
+        input: QuarantinedFileInput,
+    ) -> Result<QuarantinedFile> {
+        self.create_quarantined_file_impl(input).await
+    }
+
+    async fn list_quarantined_files(&self) -> Result<Vec<QuarantinedFile>> {
+        self.list_quarantined_files_impl().await
+    }
+
+    async fn get_quarantined_file(&self, id: i64) -> Result<Option<QuarantinedFile>> {
+        self.get_quarantined_file_impl(id).await
+    }
+
+    async fn release_quarantined_file(&self, id: i64) -> Result<Option<File>> {
+        self.release_quarantined_file_impl(id).await
+    }
+
+    async fn delete_quarantined_file(&self, id: i64) -> Result<Option<QuarantinedFile>> {
+        self.delete_quarantined_file_impl(id).await
+    }
+
     // ============================================
     // 班级模块
     // ============================================
@@ -304,133 +718,609 @@ impl Storage for SeaOrmStorage {
         self.delete_class_impl(class_id).await
     }
 
-    // ============================================
-    // 班级用户模块
-    // ============================================
+    async fn get_class_activity(&self, class_id: i64, since: i64) -> Result<Vec<StudentActivity>> {
+        self.get_class_activity_impl(class_id, since).await
+    }
 
-    async fn count_class_members(&self, class_id: i64) -> Result<i64> {
-        self.count_class_members_impl(class_id).await
+    async fn generate_class_stats_snapshots(&self, stat_date: chrono::NaiveDate) -> Result<i64> {
+        self.generate_class_stats_snapshots_impl(stat_date).await
     }
 
-    async fn join_class(
+    async fn list_class_stats_daily(
         &self,
-        user_id: i64,
         class_id: i64,
-        role: ClassUserRole,
-    ) -> Result<ClassUser> {
-        self.join_class_impl(user_id, class_id, role).await
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<ClassStatsDaily>> {
+        self.list_class_stats_daily_impl(class_id, from, to).await
     }
 
-    async fn leave_class(&self, user_id: i64, class_id: i64) -> Result<bool> {
-        self.leave_class_impl(user_id, class_id).await
+    async fn get_class_workload(
+        &self,
+        class_id: i64,
+        from: chrono::NaiveDate,
+        days: i64,
+    ) -> Result<Vec<ClassWorkloadDay>> {
+        self.get_class_workload_impl(class_id, from, days).await
     }
 
-    async fn update_class_user(
+    async fn get_class_workload_calibration(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<HomeworkCalibration>> {
+        self.get_class_workload_calibration_impl(class_id).await
+    }
+
+    async fn get_leaderboard_settings(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<ClassLeaderboardSettings>> {
+        self.get_leaderboard_settings_impl(class_id).await
+    }
+
+    async fn upsert_leaderboard_settings(
         &self,
         class_id: i64,
+        enabled: bool,
+        top_n: i32,
+        metric: LeaderboardMetric,
         user_id: i64,
-        update_data: UpdateClassUserRequest,
-    ) -> Result<Option<ClassUser>> {
-        self.update_class_user_impl(class_id, user_id, update_data)
+    ) -> Result<ClassLeaderboardSettings> {
+        self.upsert_leaderboard_settings_impl(class_id, enabled, top_n, metric, user_id)
             .await
     }
 
-    async fn list_class_users_with_pagination(
+    async fn get_leaderboard_opt_in(&self, class_id: i64, user_id: i64) -> Result<bool> {
+        self.get_leaderboard_opt_in_impl(class_id, user_id).await
+    }
+
+    async fn set_leaderboard_opt_in(
         &self,
         class_id: i64,
-        query: ClassUserQuery,
-    ) -> Result<ClassUserListResponse> {
-        self.list_class_users_with_pagination_impl(class_id, query)
+        user_id: i64,
+        opt_in: bool,
+    ) -> Result<()> {
+        self.set_leaderboard_opt_in_impl(class_id, user_id, opt_in)
             .await
     }
 
-    async fn list_user_classes_with_pagination(
+    async fn list_leaderboard_standings(
+        &self,
+        class_id: i64,
+        now: i64,
+    ) -> Result<Vec<LeaderboardStanding>> {
+        self.list_leaderboard_standings_impl(class_id, now).await
+    }
+
+    async fn preview_class_erasure(
         &self,
+        class_id: i64,
         user_id: i64,
-        query: ClassListQuery,
-    ) -> Result<ClassListResponse> {
-        self.list_user_classes_with_pagination_impl(user_id, query)
-            .await
+    ) -> Result<ClassErasureCounts> {
+        self.preview_class_erasure_impl(class_id, user_id).await
     }
 
-    async fn get_class_user_by_user_id_and_class_id(
+    async fn erase_class_student_data(
         &self,
+        class_id: i64,
         user_id: i64,
+        expected: &ClassErasureCounts,
+        operator_id: i64,
+        ip_address: Option<String>,
+        reason: Option<String>,
+    ) -> Result<Option<ClassErasureCounts>> {
+        self.erase_class_student_data_impl(
+            class_id,
+            user_id,
+            expected,
+            operator_id,
+            ip_address,
+            reason,
+        )
+        .await
+    }
+
+    async fn create_activity_event(&self, event: NewActivityEvent) -> Result<ActivityEvent> {
+        self.create_activity_event_impl(event).await
+    }
+
+    async fn get_activity_event_by_id(&self, event_id: i64) -> Result<Option<ActivityEvent>> {
+        self.get_activity_event_by_id_impl(event_id).await
+    }
+
+    async fn list_class_activity_events(
+        &self,
         class_id: i64,
-    ) -> Result<Option<ClassUser>> {
-        self.get_class_user_by_user_id_and_class_id_impl(user_id, class_id)
+        event_type: Option<ActivityEventType>,
+        before_id: Option<i64>,
+        limit: u64,
+    ) -> Result<(Vec<ActivityEvent>, bool)> {
+        self.list_class_activity_events_impl(class_id, event_type, before_id, limit)
             .await
     }
 
-    async fn get_class_user_by_id(&self, class_user_id: i64) -> Result<Option<ClassUser>> {
-        self.get_class_user_by_id_impl(class_user_id).await
+    async fn get_class_comparison(
+        &self,
+        tenant: TenantScope,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ClassComparisonItem>> {
+        self.get_class_comparison_impl(tenant, from, to).await
     }
 
-    async fn get_class_and_class_user_by_class_id_and_code(
+    async fn get_teacher_grading_latency(
         &self,
-        class_id: i64,
-        invite_code: &str,
-        user_id: i64,
-    ) -> Result<(Option<Class>, Option<ClassUser>)> {
-        self.get_class_and_class_user_by_class_id_and_code_impl(class_id, invite_code, user_id)
+        tenant: TenantScope,
+        from: i64,
+        to: i64,
+        cutoff: i64,
+    ) -> Result<Vec<TeacherGradingLatencyItem>> {
+        self.get_teacher_grading_latency_impl(tenant, from, to, cutoff)
             .await
     }
 
     // ============================================
-    // 作业模块
+    // 班级 IM 通知渠道模块
     // ============================================
 
-    async fn create_homework(
+    async fn create_class_im_channel(
         &self,
+        class_id: i64,
+        req: CreateClassImChannelRequest,
         created_by: i64,
-        req: CreateHomeworkRequest,
-    ) -> Result<Homework> {
-        self.create_homework_impl(created_by, req).await
+    ) -> Result<ClassImChannel> {
+        self.create_class_im_channel_impl(class_id, req, created_by)
+            .await
     }
 
-    async fn get_homework_by_id(&self, homework_id: i64) -> Result<Option<Homework>> {
-        self.get_homework_by_id_impl(homework_id).await
+    async fn list_class_im_channels(&self, class_id: i64) -> Result<Vec<ClassImChannel>> {
+        self.list_class_im_channels_impl(class_id).await
     }
 
-    async fn list_homeworks_with_pagination(
+    async fn get_class_im_channel(
         &self,
-        query: HomeworkListQuery,
-        current_user_id: Option<i64>,
-    ) -> Result<HomeworkListResponse> {
-        self.list_homeworks_with_pagination_impl(query, current_user_id)
-            .await
+        class_id: i64,
+        channel_id: i64,
+    ) -> Result<Option<ClassImChannel>> {
+        self.get_class_im_channel_impl(class_id, channel_id).await
     }
 
-    async fn update_homework(
+    async fn update_class_im_channel(
         &self,
-        homework_id: i64,
-        update: UpdateHomeworkRequest,
-        user_id: i64,
-    ) -> Result<Option<Homework>> {
-        self.update_homework_impl(homework_id, update, user_id)
+        class_id: i64,
+        channel_id: i64,
+        req: UpdateClassImChannelRequest,
+    ) -> Result<Option<ClassImChannel>> {
+        self.update_class_im_channel_impl(class_id, channel_id, req)
             .await
     }
 
-    async fn delete_homework(&self, homework_id: i64) -> Result<bool> {
-        self.delete_homework_impl(homework_id).await
+    async fn delete_class_im_channel(&self, class_id: i64, channel_id: i64) -> Result<bool> {
+        self.delete_class_im_channel_impl(class_id, channel_id)
+            .await
     }
 
-    async fn get_homework_file_ids(&self, homework_id: i64) -> Result<Vec<i64>> {
-        self.get_homework_file_ids_impl(homework_id).await
+    async fn get_im_channel_by_id(&self, channel_id: i64) -> Result<Option<ClassImChannel>> {
+        self.get_im_channel_by_id_impl(channel_id).await
     }
 
-    async fn set_homework_files(
+    async fn list_im_channels_for_event(
         &self,
-        homework_id: i64,
-        tokens: Vec<String>,
-        user_id: i64,
-    ) -> Result<()> {
-        self.set_homework_files_impl(homework_id, tokens, user_id)
-            .await
+        class_id: i64,
+        event: ImEvent,
+    ) -> Result<Vec<ClassImChannel>> {
+        self.list_im_channels_for_event_impl(class_id, event).await
     }
 
-    async fn get_my_homework_stats(&self, user_id: i64) -> Result<(i64, i64, i64, i64)> {
-        self.get_my_homework_stats_impl(user_id).await
+    async fn enqueue_im_deliveries(&self, deliveries: Vec<NewImDelivery>) -> Result<u64> {
+        self.enqueue_im_deliveries_impl(deliveries).await
+    }
+
+    async fn list_due_im_deliveries(&self, now: i64, limit: u64) -> Result<Vec<ImDelivery>> {
+        self.list_due_im_deliveries_impl(now, limit).await
+    }
+
+    async fn mark_im_delivery_delivered(&self, delivery: &ImDelivery) -> Result<()> {
+        self.mark_im_delivery_delivered_impl(delivery).await
+    }
+
+    async fn mark_im_delivery_failed(
+        &self,
+        delivery: &ImDelivery,
+        error: &str,
+        retry_at: Option<i64>,
+    ) -> Result<()> {
+        self.mark_im_delivery_failed_impl(delivery, error, retry_at)
+            .await
+    }
+
+    // ============================================
+    // 班级定时导出模块
+    // ============================================
+
+    async fn create_class_scheduled_export(
+        &self,
+        class_id: i64,
+        req: CreateClassScheduledExportRequest,
+        created_by: i64,
+    ) -> Result<ClassScheduledExport> {
+        self.create_class_scheduled_export_impl(class_id, req, created_by)
+            .await
+    }
+
+    async fn list_class_scheduled_exports(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<ClassScheduledExport>> {
+        self.list_class_scheduled_exports_impl(class_id).await
+    }
+
+    async fn get_class_scheduled_export(
+        &self,
+        class_id: i64,
+        export_id: i64,
+    ) -> Result<Option<ClassScheduledExport>> {
+        self.get_class_scheduled_export_impl(class_id, export_id)
+            .await
+    }
+
+    async fn update_class_scheduled_export(
+        &self,
+        class_id: i64,
+        export_id: i64,
+        req: UpdateClassScheduledExportRequest,
+    ) -> Result<Option<ClassScheduledExport>> {
+        self.update_class_scheduled_export_impl(class_id, export_id, req)
+            .await
+    }
+
+    async fn delete_class_scheduled_export(&self, class_id: i64, export_id: i64) -> Result<bool> {
+        self.delete_class_scheduled_export_impl(class_id, export_id)
+            .await
+    }
+
+    async fn claim_due_scheduled_exports(
+        &self,
+        now: i64,
+        limit: u64,
+    ) -> Result<Vec<ClassScheduledExport>> {
+        self.claim_due_scheduled_exports_impl(now, limit).await
+    }
+
+    async fn finish_scheduled_export_run(
+        &self,
+        export_id: i64,
+        error: Option<String>,
+        max_failures: i32,
+    ) -> Result<Option<ClassScheduledExport>> {
+        self.finish_scheduled_export_run_impl(export_id, error, max_failures)
+            .await
+    }
+
+    // ============================================
+    // 班级 API 令牌模块
+    // ============================================
+
+    async fn create_class_api_token(&self, token: NewClassApiToken) -> Result<ClassApiToken> {
+        self.create_class_api_token_impl(token).await
+    }
+
+    async fn list_class_api_tokens(&self, class_id: i64) -> Result<Vec<ClassApiToken>> {
+        self.list_class_api_tokens_impl(class_id).await
+    }
+
+    async fn get_class_api_token(
+        &self,
+        class_id: i64,
+        token_id: i64,
+    ) -> Result<Option<ClassApiToken>> {
+        self.get_class_api_token_impl(class_id, token_id).await
+    }
+
+    async fn get_class_api_token_by_hash(&self, token_hash: &str) -> Result<Option<ClassApiToken>> {
+        self.get_class_api_token_by_hash_impl(token_hash).await
+    }
+
+    async fn revoke_class_api_token(&self, class_id: i64, token_id: i64) -> Result<bool> {
+        self.revoke_class_api_token_impl(class_id, token_id).await
+    }
+
+    async fn touch_class_api_token(&self, token_id: i64, now: i64) -> Result<()> {
+        self.touch_class_api_token_impl(token_id, now).await
+    }
+
+    async fn create_class_api_token_log(
+        &self,
+        token_id: i64,
+        method: &str,
+        path: &str,
+        status: i32,
+        ip_address: Option<String>,
+    ) -> Result<()> {
+        self.create_class_api_token_log_impl(token_id, method, path, status, ip_address)
+            .await
+    }
+
+    async fn list_class_api_token_logs(
+        &self,
+        token_id: i64,
+        limit: u64,
+    ) -> Result<Vec<ClassApiTokenLog>> {
+        self.list_class_api_token_logs_impl(token_id, limit).await
+    }
+
+    // ============================================
+    // 事务发件箱模块
+    // ============================================
+
+    async fn list_due_outbox_events(&self, now: i64, limit: u64) -> Result<Vec<OutboxMessage>> {
+        self.list_due_outbox_events_impl(now, limit).await
+    }
+
+    async fn mark_outbox_event_delivered(&self, message: &OutboxMessage) -> Result<()> {
+        self.mark_outbox_event_delivered_impl(message).await
+    }
+
+    async fn mark_outbox_event_failed(
+        &self,
+        message: &OutboxMessage,
+        error: &str,
+        retry_at: Option<i64>,
+    ) -> Result<()> {
+        self.mark_outbox_event_failed_impl(message, error, retry_at)
+            .await
+    }
+
+    // ============================================
+    // 结业证书模块
+    // ============================================
+
+    async fn get_certificate_settings(&self, class_id: i64) -> Result<Option<CertificateSettings>> {
+        self.get_certificate_settings_impl(class_id).await
+    }
+
+    async fn upsert_certificate_settings(
+        &self,
+        class_id: i64,
+        input: CertificateSettingsInput,
+        user_id: i64,
+    ) -> Result<CertificateSettings> {
+        self.upsert_certificate_settings_impl(class_id, input, user_id)
+            .await
+    }
+
+    async fn get_certificate_progress(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        min_score_percent: f64,
+    ) -> Result<CertificateProgress> {
+        self.get_certificate_progress_impl(class_id, user_id, min_score_percent)
+            .await
+    }
+
+    async fn issue_certificate(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        verification_code: &str,
+        average_percent: f64,
+        homework_count: i32,
+    ) -> Result<(Certificate, bool)> {
+        self.issue_certificate_impl(
+            class_id,
+            user_id,
+            verification_code,
+            average_percent,
+            homework_count,
+        )
+        .await
+    }
+
+    async fn get_certificate(&self, class_id: i64, user_id: i64) -> Result<Option<Certificate>> {
+        self.get_certificate_impl(class_id, user_id).await
+    }
+
+    async fn get_certificate_by_code(&self, code: &str) -> Result<Option<Certificate>> {
+        self.get_certificate_by_code_impl(code).await
+    }
+
+    // ============================================
+    // 班级用户模块
+    // ============================================
+
+    async fn count_class_members(&self, class_id: i64) -> Result<i64> {
+        self.count_class_members_impl(class_id).await
+    }
+
+    async fn count_classes_by_user_ids(&self, user_ids: &[i64]) -> Result<HashMap<i64, i64>> {
+        self.count_classes_by_user_ids_impl(user_ids).await
+    }
+
+    async fn join_class(
+        &self,
+        user_id: i64,
+        class_id: i64,
+        role: ClassUserRole,
+        profile_name: Option<String>,
+    ) -> Result<ClassUser> {
+        self.join_class_impl(user_id, class_id, role, profile_name)
+            .await
+    }
+
+    async fn leave_class(&self, user_id: i64, class_id: i64) -> Result<bool> {
+        self.leave_class_impl(user_id, class_id).await
+    }
+
+    async fn update_class_user(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        update_data: UpdateClassUserRequest,
+    ) -> Result<Option<ClassUser>> {
+        self.update_class_user_impl(class_id, user_id, update_data)
+            .await
+    }
+
+    async fn batch_update_class_users(
+        &self,
+        class_id: i64,
+        action: ClassUserBatchAction,
+        role: Option<ClassUserRole>,
+        user_ids: &[i64],
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<ClassUserBatchOutcome> {
+        self.batch_update_class_users_impl(
+            class_id,
+            action,
+            role,
+            user_ids,
+            operator_id,
+            ip_address,
+        )
+        .await
+    }
+
+    async fn list_class_users_with_pagination(
+        &self,
+        class_id: i64,
+        query: ClassUserQuery,
+    ) -> Result<ClassUserListResponse> {
+        self.list_class_users_with_pagination_impl(class_id, query)
+            .await
+    }
+
+    async fn get_class_role_labels(&self, class_id: i64) -> Result<Option<ClassRoleLabels>> {
+        self.get_class_role_labels_impl(class_id).await
+    }
+
+    async fn save_class_role_labels(
+        &self,
+        class_id: i64,
+        labels: ClassRoleLabels,
+        updated_by: i64,
+    ) -> Result<()> {
+        self.save_class_role_labels_impl(class_id, labels, updated_by)
+            .await
+    }
+
+    async fn list_user_classes_with_pagination(
+        &self,
+        user_id: i64,
+        query: ClassListQuery,
+    ) -> Result<ClassListResponse> {
+        self.list_user_classes_with_pagination_impl(user_id, query)
+            .await
+    }
+
+    async fn get_class_user_by_user_id_and_class_id(
+        &self,
+        user_id: i64,
+        class_id: i64,
+    ) -> Result<Option<ClassUser>> {
+        self.get_class_user_by_user_id_and_class_id_impl(user_id, class_id)
+            .await
+    }
+
+    async fn get_class_user_by_id(&self, class_user_id: i64) -> Result<Option<ClassUser>> {
+        self.get_class_user_by_id_impl(class_user_id).await
+    }
+
+    async fn get_class_and_class_user_by_class_id_and_code(
+        &self,
+        class_id: i64,
+        invite_code: &str,
+        user_id: i64,
+    ) -> Result<(Option<Class>, Option<ClassUser>)> {
+        self.get_class_and_class_user_by_class_id_and_code_impl(class_id, invite_code, user_id)
+            .await
+    }
+
+    // ============================================
+    // 作业模块
+    // ============================================
+
+    async fn create_homework(
+        &self,
+        created_by: i64,
+        req: CreateHomeworkRequest,
+        notification_job_id: Option<&str>,
+    ) -> Result<Homework> {
+        self.create_homework_impl(created_by, req, notification_job_id)
+            .await
+    }
+
+    async fn batch_create_homeworks(
+        &self,
+        created_by: i64,
+        requests: Vec<CreateHomeworkRequest>,
+    ) -> Result<Vec<Homework>> {
+        self.batch_create_homeworks_impl(created_by, requests, None)
+            .await
+    }
+
+    async fn get_homework_by_id(&self, homework_id: i64) -> Result<Option<Homework>> {
+        self.get_homework_by_id_impl(homework_id).await
+    }
+
+    async fn list_homeworks_due_between(&self, from: i64, to: i64) -> Result<Vec<Homework>> {
+        self.list_homeworks_due_between_impl(from, to).await
+    }
+
+    async fn list_homeworks_with_pagination(
+        &self,
+        query: HomeworkListQuery,
+        current_user_id: Option<i64>,
+    ) -> Result<HomeworkListResponse> {
+        self.list_homeworks_with_pagination_impl(query, current_user_id)
+            .await
+    }
+
+    async fn update_homework(
+        &self,
+        homework_id: i64,
+        update: UpdateHomeworkRequest,
+        user_id: i64,
+    ) -> Result<Option<Homework>> {
+        self.update_homework_impl(homework_id, update, user_id)
+            .await
+    }
+
+    async fn delete_homework(&self, homework_id: i64) -> Result<bool> {
+        self.delete_homework_impl(homework_id).await
+    }
+
+    async fn get_homework_file_ids(&self, homework_id: i64) -> Result<Vec<i64>> {
+        self.get_homework_file_ids_impl(homework_id).await
+    }
+
+    async fn get_homework_attachments(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<(i64, AttachmentKind)>> {
+        self.get_homework_attachments_impl(homework_id).await
+    }
+
+    async fn list_homework_attachments_by_file(
+        &self,
+        file_id: i64,
+    ) -> Result<Vec<(i64, AttachmentKind)>> {
+        self.list_homework_attachments_by_file_impl(file_id).await
+    }
+
+    async fn set_homework_files(
+        &self,
+        homework_id: i64,
+        attachments: Vec<HomeworkAttachmentInput>,
+        user_id: i64,
+    ) -> Result<()> {
+        self.set_homework_files_impl(homework_id, attachments, user_id)
+            .await
+    }
+
+    async fn get_my_homework_stats(&self, user_id: i64) -> Result<(i64, i64, i64, i64)> {
+        self.get_my_homework_stats_impl(user_id).await
     }
 
     async fn get_teacher_homework_stats(&self, user_id: i64) -> Result<(i64, i64, i64, i64)> {
@@ -447,6 +1337,281 @@ impl Storage for SeaOrmStorage {
             .await
     }
 
+    // ============================================
+    // 作业豁免模块
+    // ============================================
+
+    async fn create_homework_exemption(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+        reason: Option<String>,
+        created_by: i64,
+    ) -> Result<HomeworkExemption> {
+        self.create_homework_exemption_impl(homework_id, user_id, reason, created_by)
+            .await
+    }
+
+    async fn get_homework_exemption(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<Option<HomeworkExemption>> {
+        self.get_homework_exemption_impl(homework_id, user_id).await
+    }
+
+    async fn delete_homework_exemption(&self, homework_id: i64, user_id: i64) -> Result<bool> {
+        self.delete_homework_exemption_impl(homework_id, user_id)
+            .await
+    }
+
+    async fn list_homework_exemptions(&self, homework_id: i64) -> Result<Vec<HomeworkExemption>> {
+        self.list_homework_exemptions_impl(homework_id).await
+    }
+
+    async fn get_exempted_user_ids(&self, homework_id: i64) -> Result<Vec<i64>> {
+        self.get_exempted_user_ids_impl(homework_id).await
+    }
+
+    // ============================================
+    // 考试访问日志模块
+    // ============================================
+
+    async fn record_exam_access(&self, input: ExamAccessLogInput) -> Result<()> {
+        self.record_exam_access_impl(input).await
+    }
+
+    async fn list_exam_access_logs(
+        &self,
+        homework_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Vec<ExamAccessLog>> {
+        self.list_exam_access_logs_impl(homework_id, user_id).await
+    }
+
+    // ============================================
+    // 作业分享链接模块
+    // ============================================
+
+    async fn create_homework_share_link(
+        &self,
+        homework_id: i64,
+        token: &str,
+        created_by: i64,
+        expires_at: i64,
+    ) -> Result<HomeworkShareLink> {
+        self.create_homework_share_link_impl(homework_id, token, created_by, expires_at)
+            .await
+    }
+
+    async fn list_homework_share_links(&self, homework_id: i64) -> Result<Vec<HomeworkShareLink>> {
+        self.list_homework_share_links_impl(homework_id).await
+    }
+
+    async fn get_homework_share_link_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<HomeworkShareLink>> {
+        self.get_homework_share_link_by_token_impl(token).await
+    }
+
+    async fn revoke_homework_share_link(&self, homework_id: i64, link_id: i64) -> Result<bool> {
+        self.revoke_homework_share_link_impl(homework_id, link_id)
+            .await
+    }
+
+    // ============================================
+    // 作业参考答案模块
+    // ============================================
+
+    async fn get_homework_solution(&self, homework_id: i64) -> Result<Option<HomeworkSolution>> {
+        self.get_homework_solution_impl(homework_id).await
+    }
+
+    async fn upsert_homework_solution(
+        &self,
+        homework_id: i64,
+        content: &str,
+        reveal_policy: SolutionRevealPolicy,
+        user_id: i64,
+    ) -> Result<HomeworkSolution> {
+        self.upsert_homework_solution_impl(homework_id, content, reveal_policy, user_id)
+            .await
+    }
+
+    async fn delete_homework_solution(&self, homework_id: i64) -> Result<bool> {
+        self.delete_homework_solution_impl(homework_id).await
+    }
+
+    async fn publish_homework_solution(&self, homework_id: i64) -> Result<bool> {
+        self.publish_homework_solution_impl(homework_id).await
+    }
+
+    async fn mark_homework_solution_revealed(&self, homework_id: i64) -> Result<bool> {
+        self.mark_homework_solution_revealed_impl(homework_id).await
+    }
+
+    async fn list_pending_homework_solutions(&self) -> Result<Vec<HomeworkSolution>> {
+        self.list_pending_homework_solutions_impl().await
+    }
+
+    // ============================================
+    // 缺交零分策略模块
+    // ============================================
+
+    async fn get_missing_grade_policy(
+        &self,
+        homework_id: i64,
+    ) -> Result<Option<MissingGradePolicy>> {
+        self.get_missing_grade_policy_impl(homework_id).await
+    }
+
+    async fn upsert_missing_grade_policy(
+        &self,
+        homework_id: i64,
+        cutoff_at: i64,
+        user_id: i64,
+    ) -> Result<MissingGradePolicy> {
+        self.upsert_missing_grade_policy_impl(homework_id, cutoff_at, user_id)
+            .await
+    }
+
+    async fn delete_missing_grade_policy(&self, homework_id: i64) -> Result<bool> {
+        self.delete_missing_grade_policy_impl(homework_id).await
+    }
+
+    async fn list_due_missing_grade_policies(&self, now: i64) -> Result<Vec<MissingGradePolicy>> {
+        self.list_due_missing_grade_policies_impl(now).await
+    }
+
+    async fn apply_missing_grade_policy(&self, homework_id: i64) -> Result<Vec<i64>> {
+        self.apply_missing_grade_policy_impl(homework_id).await
+    }
+
+    async fn revert_auto_assigned_grade(&self, homework_id: i64, user_id: i64) -> Result<bool> {
+        self.revert_auto_assigned_grade_impl(homework_id, user_id)
+            .await
+    }
+
+    // ============================================
+    // 作业分题模块
+    // ============================================
+
+    async fn list_homework_parts(&self, homework_id: i64) -> Result<Vec<HomeworkPart>> {
+        self.list_homework_parts_impl(homework_id).await
+    }
+
+    async fn get_homework_part(&self, part_id: i64) -> Result<Option<HomeworkPart>> {
+        self.get_homework_part_impl(part_id).await
+    }
+
+    async fn list_submitted_homework_part_ids(&self, homework_id: i64) -> Result<Vec<i64>> {
+        self.list_submitted_homework_part_ids_impl(homework_id)
+            .await
+    }
+
+    async fn replace_homework_parts(
+        &self,
+        homework_id: i64,
+        parts: Vec<HomeworkPartInput>,
+    ) -> Result<Vec<HomeworkPart>> {
+        self.replace_homework_parts_impl(homework_id, parts).await
+    }
+
+    // ============================================
+    // 作业外部资源模块
+    // ============================================
+
+    async fn list_homework_links(&self, homework_id: i64) -> Result<Vec<HomeworkLink>> {
+        self.list_homework_links_impl(homework_id).await
+    }
+
+    async fn get_homework_link(&self, link_id: i64) -> Result<Option<HomeworkLink>> {
+        self.get_homework_link_impl(link_id).await
+    }
+
+    async fn replace_homework_links(
+        &self,
+        homework_id: i64,
+        links: Vec<HomeworkLinkInput>,
+        created_by: i64,
+    ) -> Result<Vec<HomeworkLink>> {
+        self.replace_homework_links_impl(homework_id, links, created_by)
+            .await
+    }
+
+    async fn record_homework_link_open(&self, link_id: i64, user_id: i64) -> Result<()> {
+        self.record_homework_link_open_impl(link_id, user_id).await
+    }
+
+    async fn list_homework_link_opens(&self, homework_id: i64) -> Result<Vec<HomeworkLinkOpen>> {
+        self.list_homework_link_opens_impl(homework_id).await
+    }
+
+    async fn get_homework_feedback(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+    ) -> Result<Option<HomeworkFeedback>> {
+        self.get_homework_feedback_impl(homework_id, user_id).await
+    }
+
+    async fn upsert_homework_feedback(
+        &self,
+        homework_id: i64,
+        user_id: i64,
+        actual_minutes: i32,
+        perceived_difficulty: i32,
+    ) -> Result<HomeworkFeedback> {
+        self.upsert_homework_feedback_impl(
+            homework_id,
+            user_id,
+            actual_minutes,
+            perceived_difficulty,
+        )
+        .await
+    }
+
+    async fn list_homework_prerequisites(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<HomeworkPrerequisite>> {
+        self.list_homework_prerequisites_impl(homework_id).await
+    }
+
+    async fn list_class_prerequisite_edges(&self, class_id: i64) -> Result<Vec<(i64, i64)>> {
+        self.list_class_prerequisite_edges_impl(class_id).await
+    }
+
+    async fn replace_homework_prerequisites(
+        &self,
+        homework_id: i64,
+        prerequisites: Vec<HomeworkPrerequisiteInput>,
+    ) -> Result<Vec<HomeworkPrerequisite>> {
+        self.replace_homework_prerequisites_impl(homework_id, prerequisites)
+            .await
+    }
+
+    async fn list_unmet_prerequisites(
+        &self,
+        user_id: i64,
+        homework_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<i64>>> {
+        self.list_unmet_prerequisites_impl(user_id, homework_ids)
+            .await
+    }
+
+    async fn find_deadline_conflicts(
+        &self,
+        class_id: i64,
+        from: i64,
+        to: i64,
+        exclude_homework_id: Option<i64>,
+    ) -> Result<Vec<DeadlineConflictItem>> {
+        self.find_deadline_conflicts_impl(class_id, from, to, exclude_homework_id)
+            .await
+    }
+
     // ============================================
     // 提交模块
     // ============================================
@@ -455,8 +1620,18 @@ impl Storage for SeaOrmStorage {
         &self,
         creator_id: i64,
         req: CreateSubmissionRequest,
+        references: Vec<SubmissionReferenceSnapshot>,
+        default_grace_minutes: i64,
+    ) -> Result<Submission> {
+        self.create_submission_impl(creator_id, req, references, default_grace_minutes)
+            .await
+    }
+
+    async fn restore_archived_submission(
+        &self,
+        input: ArchivedSubmissionInput,
     ) -> Result<Submission> {
-        self.create_submission_impl(creator_id, req).await
+        self.restore_archived_submission_impl(input).await
     }
 
     async fn get_submission_by_id(&self, submission_id: i64) -> Result<Option<Submission>> {
@@ -479,6 +1654,10 @@ impl Storage for SeaOrmStorage {
             .await
     }
 
+    async fn is_homework_fully_graded(&self, homework_id: i64) -> Result<bool> {
+        self.is_homework_fully_graded_impl(homework_id).await
+    }
+
     async fn list_user_submissions(
         &self,
         homework_id: i64,
@@ -499,8 +1678,46 @@ impl Storage for SeaOrmStorage {
         self.delete_submission_impl(submission_id).await
     }
 
-    async fn update_submission_status(&self, submission_id: i64, status: &str) -> Result<bool> {
-        self.update_submission_status_impl(submission_id, status)
+    async fn update_submission_status(&self, submission_id: i64, status: &str) -> Result<bool> {
+        self.update_submission_status_impl(submission_id, status)
+            .await
+    }
+
+    async fn transition_submission_status(
+        &self,
+        submission_id: i64,
+        from: &str,
+        to: &str,
+    ) -> Result<bool> {
+        self.transition_submission_status_impl(submission_id, from, to)
+            .await
+    }
+
+    async fn get_class_submission_workflow(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<SubmissionWorkflow>> {
+        self.get_class_submission_workflow_impl(class_id).await
+    }
+
+    async fn save_class_submission_workflow(
+        &self,
+        class_id: i64,
+        workflow: SubmissionWorkflow,
+        updated_by: i64,
+    ) -> Result<()> {
+        self.save_class_submission_workflow_impl(class_id, workflow, updated_by)
+            .await
+    }
+
+    async fn request_resubmission(
+        &self,
+        submission_id: i64,
+        requested_by: i64,
+        comment: String,
+        reopen_until: i64,
+    ) -> Result<Option<ResubmissionRequest>> {
+        self.request_resubmission_impl(submission_id, requested_by, comment, reopen_until)
             .await
     }
 
@@ -524,9 +1741,22 @@ impl Storage for SeaOrmStorage {
         page: i64,
         size: i64,
         include_grades: bool,
-        graded: Option<bool>,
+        filter: SubmissionSummaryFilter,
     ) -> Result<SubmissionSummaryResponse> {
-        self.get_submission_summary_impl(homework_id, page, size, include_grades, graded)
+        self.get_submission_summary_impl(homework_id, page, size, include_grades, filter)
+            .await
+    }
+
+    async fn claim_grading_sla_breaches(&self, cutoff: i64) -> Result<Vec<GradingSlaBreach>> {
+        self.claim_grading_sla_breaches_impl(cutoff).await
+    }
+
+    async fn count_grading_overdue_by_teacher(
+        &self,
+        tenant: TenantScope,
+        cutoff: i64,
+    ) -> Result<HashMap<i64, i64>> {
+        self.count_grading_overdue_by_teacher_impl(tenant, cutoff)
             .await
     }
 
@@ -540,6 +1770,13 @@ impl Storage for SeaOrmStorage {
             .await
     }
 
+    async fn list_homework_self_assessments(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<SubmissionSelfAssessment>> {
+        self.list_homework_self_assessments_impl(homework_id).await
+    }
+
     // ============================================
     // 评分模块
     // ============================================
@@ -571,6 +1808,69 @@ impl Storage for SeaOrmStorage {
         self.list_grades_with_pagination_impl(query).await
     }
 
+    async fn replace_grade_mentions(
+        &self,
+        grade_id: i64,
+        mentioned_by: i64,
+        user_ids: &[i64],
+    ) -> Result<Vec<i64>> {
+        self.replace_grade_mentions_impl(grade_id, mentioned_by, user_ids)
+            .await
+    }
+
+    async fn get_grade_statistics(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+    ) -> Result<Option<GradeStatistics>> {
+        self.get_grade_statistics_impl(homework_id, part_id).await
+    }
+
+    async fn get_grade_percentile(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+        score: f64,
+    ) -> Result<Option<f64>> {
+        self.get_grade_percentile_impl(homework_id, part_id, score)
+            .await
+    }
+
+    async fn list_latest_grade_scores(
+        &self,
+        homework_id: i64,
+        part_id: Option<i64>,
+    ) -> Result<Vec<f64>> {
+        self.list_latest_grade_scores_impl(homework_id, part_id)
+            .await
+    }
+
+    async fn upsert_grade_draft(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+        req: SaveGradeDraftRequest,
+    ) -> Result<GradeDraft> {
+        self.upsert_grade_draft_impl(submission_id, grader_id, req)
+            .await
+    }
+
+    async fn get_grade_draft(
+        &self,
+        submission_id: i64,
+        grader_id: i64,
+    ) -> Result<Option<GradeDraft>> {
+        self.get_grade_draft_impl(submission_id, grader_id).await
+    }
+
+    async fn delete_grade_draft(&self, submission_id: i64, grader_id: i64) -> Result<bool> {
+        self.delete_grade_draft_impl(submission_id, grader_id).await
+    }
+
+    async fn purge_expired_grade_drafts(&self, updated_before: i64) -> Result<u64> {
+        self.purge_expired_grade_drafts_impl(updated_before).await
+    }
+
     // ============================================
     // 通知模块
     // ============================================
@@ -615,6 +1915,107 @@ impl Storage for SeaOrmStorage {
         self.delete_notification_impl(notification_id).await
     }
 
+    async fn list_undelivered_notifications(
+        &self,
+        user_id: i64,
+        since: Option<i64>,
+        limit: u64,
+    ) -> Result<Vec<Notification>> {
+        self.list_undelivered_notifications_impl(user_id, since, limit)
+            .await
+    }
+
+    async fn mark_notifications_delivered(
+        &self,
+        user_id: i64,
+        notification_ids: &[i64],
+    ) -> Result<i64> {
+        self.mark_notifications_delivered_impl(user_id, notification_ids)
+            .await
+    }
+
+    async fn resolve_notification_recipients(
+        &self,
+        class_id: i64,
+        filter: &NotificationRecipientFilter,
+    ) -> Result<Vec<i64>> {
+        self.resolve_notification_recipients_impl(class_id, filter)
+            .await
+    }
+
+    // ============================================
+    // 表情回应模块
+    // ============================================
+
+    async fn add_reaction(
+        &self,
+        user_id: i64,
+        target_type: ReactionTargetType,
+        target_id: i64,
+        emoji: &str,
+    ) -> Result<bool> {
+        self.add_reaction_impl(user_id, target_type, target_id, emoji)
+            .await
+    }
+
+    async fn remove_reaction(
+        &self,
+        user_id: i64,
+        target_type: ReactionTargetType,
+        target_id: i64,
+        emoji: &str,
+    ) -> Result<bool> {
+        self.remove_reaction_impl(user_id, target_type, target_id, emoji)
+            .await
+    }
+
+    async fn get_reaction_summaries(
+        &self,
+        target_type: ReactionTargetType,
+        target_ids: &[i64],
+        viewer_id: i64,
+    ) -> Result<HashMap<i64, ReactionSummary>> {
+        self.get_reaction_summaries_impl(target_type, target_ids, viewer_id)
+            .await
+    }
+
+    // ============================================
+    // 通知模板模块
+    // ============================================
+
+    async fn list_notification_templates(&self) -> Result<Vec<NotificationTemplate>> {
+        self.list_notification_templates_impl().await
+    }
+
+    async fn get_notification_template(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+    ) -> Result<Option<NotificationTemplate>> {
+        self.get_notification_template_impl(notification_type, locale)
+            .await
+    }
+
+    async fn upsert_notification_template(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+        req: UpsertNotificationTemplateRequest,
+        user_id: i64,
+    ) -> Result<NotificationTemplate> {
+        self.upsert_notification_template_impl(notification_type, locale, req, user_id)
+            .await
+    }
+
+    async fn delete_notification_template(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+    ) -> Result<bool> {
+        self.delete_notification_template_impl(notification_type, locale)
+            .await
+    }
+
     // ============================================
     // 系统设置模块
     // ============================================
@@ -645,18 +2046,347 @@ impl Storage for SeaOrmStorage {
 
     async fn batch_update_settings(
         &self,
-        updates: Vec<(String, String)>,
+        changes: Vec<crate::models::system::entities::SettingChange>,
+        expected_version: Option<i64>,
         user_id: i64,
         ip_address: Option<String>,
-    ) -> Result<Vec<crate::models::system::entities::SystemSetting>> {
-        self.batch_update_settings_impl(updates, user_id, ip_address)
+    ) -> Result<crate::models::system::entities::SettingsUpdateOutcome> {
+        self.batch_update_settings_impl(changes, expected_version, user_id, ip_address)
             .await
     }
 
+    async fn get_settings_version(&self) -> Result<i64> {
+        self.get_settings_version_impl().await
+    }
+
     async fn list_setting_audits(
         &self,
         query: crate::models::system::requests::SettingAuditQuery,
     ) -> Result<crate::models::system::responses::SettingAuditListResponse> {
         self.list_setting_audits_impl(query).await
     }
+
+    // ============================================
+    // 组织模块
+    // ============================================
+
+    async fn create_organization(&self, req: CreateOrganizationRequest) -> Result<Organization> {
+        self.create_organization_impl(req).await
+    }
+
+    async fn get_organization_by_id(&self, id: i64) -> Result<Option<Organization>> {
+        self.get_organization_by_id_impl(id).await
+    }
+
+    async fn get_organization_by_slug(&self, slug: &str) -> Result<Option<Organization>> {
+        self.get_organization_by_slug_impl(slug).await
+    }
+
+    async fn list_organizations_with_pagination(
+        &self,
+        query: OrganizationListQuery,
+    ) -> Result<OrganizationListResponse> {
+        self.list_organizations_with_pagination_impl(query).await
+    }
+
+    async fn update_organization(
+        &self,
+        id: i64,
+        update: UpdateOrganizationRequest,
+    ) -> Result<Option<Organization>> {
+        self.update_organization_impl(id, update).await
+    }
+
+    async fn delete_organization(&self, id: i64) -> Result<bool> {
+        self.delete_organization_impl(id).await
+    }
+
+    async fn count_organization_users(&self, org_id: i64) -> Result<i64> {
+        self.count_organization_users_impl(org_id).await
+    }
+
+    // ============================================
+    // 用量统计模块
+    // ============================================
+
+    async fn increment_usage_counter(
+        &self,
+        org_id: i64,
+        period: &str,
+        metric: UsageMetric,
+        delta: i64,
+    ) -> Result<()> {
+        self.increment_usage_counter_impl(org_id, period, metric, delta)
+            .await
+    }
+
+    async fn list_usage_counters(&self, org_id: i64, period: &str) -> Result<Vec<UsageCounter>> {
+        self.list_usage_counters_impl(org_id, period).await
+    }
+
+    async fn generate_usage_reports(&self, report_date: chrono::NaiveDate) -> Result<i64> {
+        self.generate_usage_reports_impl(report_date).await
+    }
+
+    async fn list_usage_reports(
+        &self,
+        query: UsageReportListQuery,
+    ) -> Result<UsageReportListResponse> {
+        self.list_usage_reports_impl(query).await
+    }
+
+    // ============================================
+    // 内容审核模块
+    // ============================================
+
+    async fn create_moderation_flag(&self, input: ModerationFlagInput) -> Result<ModerationFlag> {
+        self.create_moderation_flag_impl(input).await
+    }
+
+    async fn list_moderation_flags(
+        &self,
+        class_id: i64,
+        status: Option<ModerationFlagStatus>,
+    ) -> Result<Vec<ModerationFlag>> {
+        self.list_moderation_flags_impl(class_id, status).await
+    }
+
+    async fn get_moderation_flag(&self, flag_id: i64) -> Result<Option<ModerationFlag>> {
+        self.get_moderation_flag_impl(flag_id).await
+    }
+
+    async fn review_moderation_flag(
+        &self,
+        flag_id: i64,
+        status: ModerationFlagStatus,
+        reviewer_id: i64,
+    ) -> Result<Option<ModerationFlag>> {
+        self.review_moderation_flag_impl(flag_id, status, reviewer_id)
+            .await
+    }
+
+    async fn get_reported_content(
+        &self,
+        content_type: ReportContentType,
+        content_id: i64,
+    ) -> Result<Option<ReportedContent>> {
+        self.get_reported_content_impl(content_type, content_id)
+            .await
+    }
+
+    async fn has_pending_content_report(
+        &self,
+        reporter_id: i64,
+        content_type: ReportContentType,
+        content_id: i64,
+    ) -> Result<bool> {
+        self.has_pending_content_report_impl(reporter_id, content_type, content_id)
+            .await
+    }
+
+    async fn create_content_report(&self, input: ContentReportInput) -> Result<ContentReport> {
+        self.create_content_report_impl(input).await
+    }
+
+    async fn list_content_reports(
+        &self,
+        class_id: i64,
+        status: Option<ContentReportStatus>,
+    ) -> Result<Vec<ContentReport>> {
+        self.list_content_reports_impl(class_id, status).await
+    }
+
+    async fn get_content_report(&self, report_id: i64) -> Result<Option<ContentReport>> {
+        self.get_content_report_impl(report_id).await
+    }
+
+    async fn resolve_content_report(
+        &self,
+        report_id: i64,
+        action: ContentReportAction,
+        note: Option<String>,
+        resolver_id: i64,
+    ) -> Result<Option<ContentReport>> {
+        self.resolve_content_report_impl(report_id, action, note, resolver_id)
+            .await
+    }
+
+    // ============================================
+    // 评分量规模块
+    // ============================================
+
+    async fn create_rubric(&self, input: RubricInput) -> Result<Rubric> {
+        self.create_rubric_impl(input).await
+    }
+
+    async fn list_rubrics(
+        &self,
+        user_id: i64,
+        tenant: TenantScope,
+        scope: Option<RubricScope>,
+    ) -> Result<Vec<Rubric>> {
+        self.list_rubrics_impl(user_id, tenant, scope).await
+    }
+
+    async fn get_rubric(&self, rubric_id: i64) -> Result<Option<Rubric>> {
+        self.get_rubric_impl(rubric_id).await
+    }
+
+    async fn get_rubric_by_share_code(&self, share_code: &str) -> Result<Option<Rubric>> {
+        self.get_rubric_by_share_code_impl(share_code).await
+    }
+
+    async fn update_rubric(
+        &self,
+        rubric_id: i64,
+        update: RubricUpdate,
+        editor_id: i64,
+    ) -> Result<Option<Rubric>> {
+        self.update_rubric_impl(rubric_id, update, editor_id).await
+    }
+
+    async fn set_rubric_share_code(
+        &self,
+        rubric_id: i64,
+        share_code: Option<String>,
+    ) -> Result<Option<Rubric>> {
+        self.set_rubric_share_code_impl(rubric_id, share_code).await
+    }
+
+    async fn delete_rubric(&self, rubric_id: i64) -> Result<bool> {
+        self.delete_rubric_impl(rubric_id).await
+    }
+
+    async fn list_rubric_versions(&self, rubric_id: i64) -> Result<Vec<RubricVersion>> {
+        self.list_rubric_versions_impl(rubric_id).await
+    }
+
+    async fn get_rubric_version(
+        &self,
+        rubric_id: i64,
+        version: i32,
+    ) -> Result<Option<RubricVersion>> {
+        self.get_rubric_version_impl(rubric_id, version).await
+    }
+
+    async fn get_homework_rubric(&self, homework_id: i64) -> Result<Option<HomeworkRubric>> {
+        self.get_homework_rubric_impl(homework_id).await
+    }
+
+    async fn set_homework_rubric(
+        &self,
+        homework_id: i64,
+        rubric_id: i64,
+        rubric_version: i32,
+        title: String,
+        criteria: &[RubricCriterion],
+        attached_by: i64,
+    ) -> Result<HomeworkRubric> {
+        self.set_homework_rubric_impl(
+            homework_id,
+            rubric_id,
+            rubric_version,
+            title,
+            criteria,
+            attached_by,
+        )
+        .await
+    }
+
+    async fn delete_homework_rubric(&self, homework_id: i64) -> Result<bool> {
+        self.delete_homework_rubric_impl(homework_id).await
+    }
+
+    // ============================================
+    // 私信模块
+    // ============================================
+
+    async fn create_message(
+        &self,
+        class_id: i64,
+        sender_id: i64,
+        recipient_id: i64,
+        content: &str,
+    ) -> Result<Message> {
+        self.create_message_impl(class_id, sender_id, recipient_id, content)
+            .await
+    }
+
+    async fn list_conversations(
+        &self,
+        user_id: i64,
+        class_id: Option<i64>,
+    ) -> Result<ConversationListResponse> {
+        self.list_conversations_impl(user_id, class_id).await
+    }
+
+    async fn list_conversation_messages(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        peer_id: i64,
+        before_id: Option<i64>,
+        limit: u64,
+    ) -> Result<(Vec<Message>, bool)> {
+        self.list_conversation_messages_impl(class_id, user_id, peer_id, before_id, limit)
+            .await
+    }
+
+    async fn mark_conversation_read(
+        &self,
+        class_id: i64,
+        user_id: i64,
+        peer_id: i64,
+    ) -> Result<i64> {
+        self.mark_conversation_read_impl(class_id, user_id, peer_id)
+            .await
+    }
+
+    async fn count_unread_messages(&self, user_id: i64) -> Result<i64> {
+        self.count_unread_messages_impl(user_id).await
+    }
+
+    // ============================================
+    // 数据一致性检查模块
+    // ============================================
+
+    async fn run_integrity_checks(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<crate::models::system::responses::IntegrityCheckResult>> {
+        self.run_integrity_checks_impl(repair).await
+    }
+
+    // ============================================
+    // 数据库维护模块
+    // ============================================
+
+    async fn get_database_status(
+        &self,
+    ) -> Result<crate::models::system::responses::DatabaseStatus> {
+        self.get_database_status_impl().await
+    }
+
+    async fn checkpoint_wal(
+        &self,
+        mode: crate::models::system::entities::WalCheckpointMode,
+    ) -> Result<Option<crate::models::system::responses::WalCheckpointResponse>> {
+        self.checkpoint_wal_impl(mode).await
+    }
+
+    async fn backup_database(&self, target: &std::path::Path) -> Result<bool> {
+        self.backup_database_impl(target).await
+    }
+
+    // ============================================
+    // 开发工具模块
+    // ============================================
+
+    async fn generate_dev_data(
+        &self,
+        password_hash: &str,
+        req: &crate::models::system::requests::GenerateDevDataRequest,
+    ) -> Result<crate::models::system::responses::DevDataSummary> {
+        self.generate_dev_data_impl(password_hash, req).await
+    }
 }
@@ -0,0 +1,97 @@
+//! 内容审核标记存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::moderation_flags::{ActiveModel, Column, Entity as ModerationFlags};
+use crate::errors::{HWSystemError, Result};
+use crate::models::moderation::entities::{ModerationFlag, ModerationFlagStatus};
+use crate::models::moderation::requests::ModerationFlagInput;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+impl SeaOrmStorage {
+    /// 记录内容审核标记
+    pub async fn create_moderation_flag_impl(
+        &self,
+        input: ModerationFlagInput,
+    ) -> Result<ModerationFlag> {
+        let model = ActiveModel {
+            class_id: Set(input.class_id),
+            user_id: Set(input.user_id),
+            content_type: Set(input.content_type.to_string()),
+            content_id: Set(input.content_id),
+            source: Set(input.source.to_string()),
+            reason: Set(input.reason),
+            excerpt: Set(input.excerpt),
+            blocked: Set(input.blocked),
+            status: Set(ModerationFlagStatus::Pending.to_string()),
+            reviewed_by: Set(None),
+            reviewed_at: Set(None),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("记录审核标记失败: {e}")))?;
+
+        Ok(model.into_moderation_flag())
+    }
+
+    /// 列出班级的审核标记，新记录在前
+    pub async fn list_moderation_flags_impl(
+        &self,
+        class_id: i64,
+        status: Option<ModerationFlagStatus>,
+    ) -> Result<Vec<ModerationFlag>> {
+        let mut select = ModerationFlags::find().filter(Column::ClassId.eq(class_id));
+        if let Some(status) = status {
+            select = select.filter(Column::Status.eq(status.to_string()));
+        }
+
+        let flags = select
+            .order_by_desc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询审核标记失败: {e}")))?;
+
+        Ok(flags
+            .into_iter()
+            .map(|m| m.into_moderation_flag())
+            .collect())
+    }
+
+    /// 获取审核标记
+    pub async fn get_moderation_flag_impl(&self, flag_id: i64) -> Result<Option<ModerationFlag>> {
+        let flag = ModerationFlags::find_by_id(flag_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询审核标记失败: {e}")))?;
+
+        Ok(flag.map(|m| m.into_moderation_flag()))
+    }
+
+    /// 更新审核标记的复核结果
+    pub async fn review_moderation_flag_impl(
+        &self,
+        flag_id: i64,
+        status: ModerationFlagStatus,
+        reviewer_id: i64,
+    ) -> Result<Option<ModerationFlag>> {
+        let Some(model) = ModerationFlags::find_by_id(flag_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询审核标记失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let mut active: ActiveModel = model.into();
+        active.status = Set(status.to_string());
+        active.reviewed_by = Set(Some(reviewer_id));
+        active.reviewed_at = Set(Some(chrono::Utc::now().timestamp()));
+        let model = active
+            .update(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新审核标记失败: {e}")))?;
+
+        Ok(Some(model.into_moderation_flag()))
+    }
+}
@@ -0,0 +1,105 @@
+//! 通知模板存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::notification_templates::{ActiveModel, Column, Entity as NotificationTemplates};
+use crate::errors::{HWSystemError, Result};
+use crate::models::notifications::{
+    entities::{NotificationTemplate, NotificationType},
+    requests::UpsertNotificationTemplateRequest,
+};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+impl SeaOrmStorage {
+    /// 列出所有自定义通知模板
+    pub async fn list_notification_templates_impl(&self) -> Result<Vec<NotificationTemplate>> {
+        let models = NotificationTemplates::find()
+            .order_by_asc(Column::NotificationType)
+            .order_by_asc(Column::Locale)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询通知模板失败: {e}")))?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| m.into_notification_template())
+            .collect())
+    }
+
+    /// 获取指定类型与语言的通知模板
+    pub async fn get_notification_template_impl(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+    ) -> Result<Option<NotificationTemplate>> {
+        let model = NotificationTemplates::find()
+            .filter(Column::NotificationType.eq(notification_type.to_string()))
+            .filter(Column::Locale.eq(locale))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询通知模板失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_notification_template()))
+    }
+
+    /// 创建或更新通知模板
+    pub async fn upsert_notification_template_impl(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+        req: UpsertNotificationTemplateRequest,
+        user_id: i64,
+    ) -> Result<NotificationTemplate> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = NotificationTemplates::find()
+            .filter(Column::NotificationType.eq(notification_type.to_string()))
+            .filter(Column::Locale.eq(locale))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询通知模板失败: {e}")))?;
+
+        let model = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.title = Set(req.title);
+                active.content = Set(req.content);
+                active.updated_by = Set(Some(user_id));
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    notification_type: Set(notification_type.to_string()),
+                    locale: Set(locale.to_string()),
+                    title: Set(req.title),
+                    content: Set(req.content),
+                    updated_by: Set(Some(user_id)),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    ..Default::default()
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存通知模板失败: {e}")))?;
+
+        Ok(model.into_notification_template())
+    }
+
+    /// 删除通知模板
+    pub async fn delete_notification_template_impl(
+        &self,
+        notification_type: &NotificationType,
+        locale: &str,
+    ) -> Result<bool> {
+        let result = NotificationTemplates::delete_many()
+            .filter(Column::NotificationType.eq(notification_type.to_string()))
+            .filter(Column::Locale.eq(locale))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除通知模板失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -1,18 +1,29 @@
 //! 通知存储操作
 
+use std::collections::{HashMap, HashSet};
+
 use super::SeaOrmStorage;
+use super::batch::insert_chunked;
+use super::homeworks::returned_submitters;
+use crate::entity::class_users::{Column as ClassUserColumn, Entity as ClassUsers};
+use crate::entity::homework_exemptions::{Column as ExemptionColumn, Entity as HomeworkExemptions};
 use crate::entity::notifications::{ActiveModel, Column, Entity as Notifications};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     PaginationInfo,
+    class_users::entities::ClassUserRole,
+    common::PaginationPolicy,
     notifications::{
         entities::Notification,
-        requests::{CreateNotificationRequest, NotificationListQuery},
+        requests::{CreateNotificationRequest, NotificationListQuery, NotificationRecipientFilter},
         responses::NotificationListResponse,
     },
 };
+use sea_orm::sea_query::Query;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
 };
 
 impl SeaOrmStorage {
@@ -44,35 +55,93 @@ impl SeaOrmStorage {
     }
 
     /// 批量创建通知
+    ///
+    /// 在同一事务中分块执行多行 INSERT。批量插入无法取回自增 ID，
+    /// 因此写入后按「ID 大于写入前最大值且创建时间相同」回查，并逐条匹配本批请求，
+    /// 返回的记录带有真实 ID（用于 WebSocket 推送和送达标记）。
     pub async fn create_notifications_batch_impl(
         &self,
         reqs: Vec<CreateNotificationRequest>,
     ) -> Result<Vec<Notification>> {
+        if reqs.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let now = chrono::Utc::now().timestamp();
-        let mut notifications = Vec::new();
-
-        for req in reqs {
-            let model = ActiveModel {
-                user_id: Set(req.user_id),
-                notification_type: Set(req.notification_type),
-                title: Set(req.title),
-                content: Set(req.content),
-                reference_type: Set(req.reference_type),
-                reference_id: Set(req.reference_id),
-                is_read: Set(false),
-                created_at: Set(now),
-                ..Default::default()
-            };
-
-            let result = model
-                .insert(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("批量创建通知失败: {e}")))?;
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let last_id = Notifications::find()
+            .select_only()
+            .column_as(Column::Id.max(), "max_id")
+            .into_tuple::<Option<i64>>()
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询通知最大 ID 失败: {e}")))?
+            .flatten()
+            .unwrap_or(0);
 
-            notifications.push(result.into_notification());
+        // 本批请求的匹配计数，键为 (user_id, type, title, reference_id)
+        let mut expected: HashMap<(i64, String, String, Option<i64>), usize> = HashMap::new();
+        for req in &reqs {
+            *expected
+                .entry((
+                    req.user_id,
+                    req.notification_type.clone(),
+                    req.title.clone(),
+                    req.reference_id,
+                ))
+                .or_default() += 1;
         }
 
-        Ok(notifications)
+        let models = reqs.into_iter().map(|req| ActiveModel {
+            user_id: Set(req.user_id),
+            notification_type: Set(req.notification_type),
+            title: Set(req.title),
+            content: Set(req.content),
+            reference_type: Set(req.reference_type),
+            reference_id: Set(req.reference_id),
+            is_read: Set(false),
+            created_at: Set(now),
+            ..Default::default()
+        });
+        insert_chunked!(Notifications, models, &txn, "通知");
+
+        let inserted = Notifications::find()
+            .filter(Column::Id.gt(last_id))
+            .filter(Column::CreatedAt.eq(now))
+            .order_by_asc(Column::Id)
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("回查通知失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        // 跳过同一时刻由其他请求写入的通知
+        Ok(inserted
+            .into_iter()
+            .filter(|m| {
+                let key = (
+                    m.user_id,
+                    m.notification_type.clone(),
+                    m.title.clone(),
+                    m.reference_id,
+                );
+                match expected.get_mut(&key) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                }
+            })
+            .map(|m| m.into_notification())
+            .collect())
     }
 
     /// 通过 ID 获取通知
@@ -94,8 +163,8 @@ impl SeaOrmStorage {
         user_id: i64,
         query: NotificationListQuery,
     ) -> Result<NotificationListResponse> {
-        let page = query.pagination.page.max(1) as u64;
-        let size = query.pagination.size.clamp(1, 100) as u64;
+        let (page, size) = PaginationPolicy::for_endpoint("notifications")
+            .resolve(Some(query.pagination.page), query.pagination.size);
 
         let mut select = Notifications::find().filter(Column::UserId.eq(user_id));
 
@@ -184,4 +253,117 @@ impl SeaOrmStorage {
 
         Ok(result.rows_affected > 0)
     }
+
+    /// 列出用户尚未通过 WebSocket 送达的通知
+    pub async fn list_undelivered_notifications_impl(
+        &self,
+        user_id: i64,
+        since: Option<i64>,
+        limit: u64,
+    ) -> Result<Vec<Notification>> {
+        let mut select = Notifications::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::DeliveredAt.is_null());
+
+        if let Some(since) = since {
+            select = select.filter(Column::CreatedAt.gte(since));
+        }
+
+        let notifications = select
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询未送达通知失败: {e}")))?;
+
+        Ok(notifications
+            .into_iter()
+            .map(|m| m.into_notification())
+            .collect())
+    }
+
+    /// 标记通知已送达
+    pub async fn mark_notifications_delivered_impl(
+        &self,
+        user_id: i64,
+        notification_ids: &[i64],
+    ) -> Result<i64> {
+        if notification_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let result = Notifications::update_many()
+            .col_expr(Column::DeliveredAt, sea_orm::sea_query::Expr::value(now))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Id.is_in(notification_ids.iter().copied()))
+            .filter(Column::DeliveredAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("标记通知已送达失败: {e}")))?;
+
+        Ok(result.rows_affected as i64)
+    }
+
+    /// 按筛选条件解析群发通知的接收人（班级成员 user_id，升序）
+    ///
+    /// 指定未提交作业时只保留非教师成员，并排除豁免学生与最新提交有效（未被退回）的学生。
+    pub async fn resolve_notification_recipients_impl(
+        &self,
+        class_id: i64,
+        filter: &NotificationRecipientFilter,
+    ) -> Result<Vec<i64>> {
+        let mut select = ClassUsers::find().filter(ClassUserColumn::ClassId.eq(class_id));
+
+        if let Some(user_ids) = &filter.user_ids {
+            select = select.filter(ClassUserColumn::UserId.is_in(user_ids.iter().copied()));
+        }
+        if let Some(role) = &filter.role {
+            select = select.filter(ClassUserColumn::Role.eq(role.to_string()));
+        }
+        if let Some(homework_id) = filter.not_submitted_homework_id {
+            select = select
+                .filter(ClassUserColumn::Role.ne(ClassUserRole::Teacher.to_string()))
+                .filter(
+                    ClassUserColumn::UserId.not_in_subquery(
+                        Query::select()
+                            .column(ExemptionColumn::UserId)
+                            .from(HomeworkExemptions)
+                            .and_where(ExemptionColumn::HomeworkId.eq(homework_id))
+                            .to_owned(),
+                    ),
+                );
+        }
+
+        let mut user_ids: Vec<i64> = select
+            .select_only()
+            .column(ClassUserColumn::UserId)
+            .order_by_asc(ClassUserColumn::UserId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+
+        if let Some(homework_id) = filter.not_submitted_homework_id
+            && !user_ids.is_empty()
+        {
+            let submissions = Submissions::find()
+                .filter(SubmissionColumn::HomeworkId.eq(homework_id))
+                .filter(SubmissionColumn::CreatorId.is_in(user_ids.iter().copied()))
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
+
+            let returned = returned_submitters(&submissions);
+            let submitted: HashSet<i64> = submissions
+                .iter()
+                .filter(|sub| !returned.contains(&(sub.homework_id, sub.creator_id)))
+                .map(|sub| sub.creator_id)
+                .collect();
+            user_ids.retain(|user_id| !submitted.contains(user_id));
+        }
+
+        Ok(user_ids)
+    }
 }
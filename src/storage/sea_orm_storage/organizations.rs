@@ -0,0 +1,219 @@
+//! 组织（租户）存储操作
+
+use super::SeaOrmStorage;
+use crate::cache::invalidation::CacheInvalidation;
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::organizations::{ActiveModel, Column, Entity as Organizations};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    PaginationInfo,
+    common::PaginationPolicy,
+    organizations::{
+        entities::{Organization, TenantScope},
+        requests::{CreateOrganizationRequest, OrganizationListQuery, UpdateOrganizationRequest},
+        responses::OrganizationListResponse,
+    },
+};
+use crate::utils::escape_like_pattern;
+use sea_orm::sea_query::{Query, SelectStatement};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    Set,
+};
+
+/// 根据租户范围生成 org_id 过滤条件
+///
+/// `TenantScope::All` 不附加任何条件；默认租户匹配 `org_id IS NULL`。
+pub(super) fn tenant_condition<C: ColumnTrait>(column: C, scope: TenantScope) -> Condition {
+    match scope {
+        TenantScope::All => Condition::all(),
+        TenantScope::Org(Some(org_id)) => Condition::all().add(column.eq(org_id)),
+        TenantScope::Org(None) => Condition::all().add(column.is_null()),
+    }
+}
+
+/// 租户范围内的班级 ID 子查询
+pub(super) fn tenant_class_ids(scope: TenantScope) -> SelectStatement {
+    Query::select()
+        .column(ClassColumn::Id)
+        .from(Classes)
+        .cond_where(tenant_condition(ClassColumn::OrgId, scope))
+        .to_owned()
+}
+
+/// 租户范围内的作业 ID 子查询（按作业所属班级的组织）
+pub(super) fn tenant_homework_ids(scope: TenantScope) -> SelectStatement {
+    Query::select()
+        .column(HomeworkColumn::Id)
+        .from(Homeworks)
+        .cond_where(HomeworkColumn::ClassId.in_subquery(tenant_class_ids(scope)))
+        .to_owned()
+}
+
+impl SeaOrmStorage {
+    /// 创建组织
+    pub async fn create_organization_impl(
+        &self,
+        req: CreateOrganizationRequest,
+    ) -> Result<Organization> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            name: Set(req.name),
+            slug: Set(req.slug),
+            description: Set(req.description),
+            is_active: Set(true),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建组织失败: {e}")))?;
+
+        Ok(result.into_organization())
+    }
+
+    /// 通过 ID 获取组织
+    pub async fn get_organization_by_id_impl(&self, id: i64) -> Result<Option<Organization>> {
+        let result = Organizations::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询组织失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_organization()))
+    }
+
+    /// 通过标识获取组织
+    pub async fn get_organization_by_slug_impl(&self, slug: &str) -> Result<Option<Organization>> {
+        let result = Organizations::find()
+            .filter(Column::Slug.eq(slug))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询组织失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_organization()))
+    }
+
+    /// 分页列出组织
+    pub async fn list_organizations_with_pagination_impl(
+        &self,
+        query: OrganizationListQuery,
+    ) -> Result<OrganizationListResponse> {
+        let (page, size) =
+            PaginationPolicy::for_endpoint("organizations").resolve(query.page, query.size);
+
+        let mut select = Organizations::find();
+
+        if let Some(ref search) = query.search
+            && !search.trim().is_empty()
+        {
+            let escaped = escape_like_pattern(search.trim());
+            select = select.filter(
+                Condition::any()
+                    .add(Column::Name.contains(&escaped))
+                    .add(Column::Slug.contains(&escaped)),
+            );
+        }
+
+        let paginator = select.order_by_asc(Column::Id).paginate(&self.db, size);
+        let total = paginator
+            .num_items()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询组织总数失败: {e}")))?;
+
+        let pages = paginator
+            .num_pages()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询组织页数失败: {e}")))?;
+
+        let organizations = paginator
+            .fetch_page(page - 1)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询组织列表失败: {e}")))?;
+
+        Ok(OrganizationListResponse {
+            items: organizations
+                .into_iter()
+                .map(|m| m.into_organization())
+                .collect(),
+            pagination: PaginationInfo {
+                page: page as i64,
+                page_size: size as i64,
+                total: total as i64,
+                total_pages: pages as i64,
+            },
+        })
+    }
+
+    /// 更新组织
+    pub async fn update_organization_impl(
+        &self,
+        id: i64,
+        update: UpdateOrganizationRequest,
+    ) -> Result<Option<Organization>> {
+        // 先检查组织是否存在
+        let existing = self.get_organization_by_id_impl(id).await?;
+        if existing.is_none() {
+            return Ok(None);
+        }
+
+        let now = chrono::Utc::now().timestamp();
+
+        let mut model = ActiveModel {
+            id: Set(id),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        if let Some(name) = update.name {
+            model.name = Set(name);
+        }
+
+        if let Some(description) = update.description {
+            model.description = Set(Some(description));
+        }
+
+        if let Some(is_active) = update.is_active {
+            model.is_active = Set(is_active);
+        }
+
+        model
+            .update(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新组织失败: {e}")))?;
+        self.publish_invalidation(CacheInvalidation::Organization(id))
+            .await;
+
+        self.get_organization_by_id_impl(id).await
+    }
+
+    /// 删除组织
+    pub async fn delete_organization_impl(&self, id: i64) -> Result<bool> {
+        let result = Organizations::delete_by_id(id)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除组织失败: {e}")))?;
+        if result.rows_affected > 0 {
+            self.publish_invalidation(CacheInvalidation::Organization(id))
+                .await;
+        }
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 统计组织内用户数量
+    pub async fn count_organization_users_impl(&self, org_id: i64) -> Result<i64> {
+        let count = Users::find()
+            .filter(UserColumn::OrgId.eq(org_id))
+            .count(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计组织用户数量失败: {e}")))?;
+
+        Ok(count as i64)
+    }
+}
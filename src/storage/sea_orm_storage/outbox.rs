@@ -0,0 +1,120 @@
+//! 事务发件箱存储操作
+//!
+//! 业务写入通过 [`insert_outbox_events`] 在同一事务中追加事件，
+//! 转发任务轮询待投递记录并回写投递结果。
+
+use super::SeaOrmStorage;
+use crate::entity::outbox_events::{ActiveModel, Column, Entity as OutboxEvents};
+use crate::errors::{HWSystemError, Result};
+use crate::models::outbox::entities::{OutboxEvent, OutboxMessage, OutboxStatus};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::Expr,
+};
+
+/// 在给定连接（通常为业务事务）中写入发件箱事件，立即可被转发任务投递
+pub(super) async fn insert_outbox_events<C: ConnectionTrait>(
+    conn: &C,
+    events: Vec<OutboxEvent>,
+) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut models = Vec::with_capacity(events.len());
+    for event in events {
+        let payload = serde_json::to_string(&event)
+            .map_err(|e| HWSystemError::serialization(format!("序列化发件箱事件失败: {e}")))?;
+        models.push(ActiveModel {
+            event_type: Set(event.kind().to_string()),
+            payload: Set(payload),
+            status: Set(OutboxStatus::Pending.to_string()),
+            attempts: Set(0),
+            next_attempt_at: Set(now),
+            created_at: Set(now),
+            ..Default::default()
+        });
+    }
+
+    OutboxEvents::insert_many(models)
+        .exec(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("写入发件箱事件失败: {e}")))?;
+
+    Ok(())
+}
+
+impl SeaOrmStorage {
+    /// 查询到期的待投递事件
+    pub async fn list_due_outbox_events_impl(
+        &self,
+        now: i64,
+        limit: u64,
+    ) -> Result<Vec<OutboxMessage>> {
+        let results = OutboxEvents::find()
+            .filter(Column::Status.eq(OutboxStatus::Pending.to_string()))
+            .filter(Column::NextAttemptAt.lte(now))
+            .order_by_asc(Column::NextAttemptAt)
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询发件箱失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_outbox_message())
+            .collect())
+    }
+
+    /// 记录投递成功
+    pub async fn mark_outbox_event_delivered_impl(&self, message: &OutboxMessage) -> Result<()> {
+        OutboxEvents::update_many()
+            .col_expr(
+                Column::Status,
+                Expr::value(OutboxStatus::Delivered.to_string()),
+            )
+            .col_expr(Column::Attempts, Expr::value(message.attempts + 1))
+            .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+            .col_expr(
+                Column::DeliveredAt,
+                Expr::value(chrono::Utc::now().timestamp()),
+            )
+            .filter(Column::Id.eq(message.id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新发件箱状态失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 记录投递失败；`retry_at` 为空表示不再重试
+    pub async fn mark_outbox_event_failed_impl(
+        &self,
+        message: &OutboxMessage,
+        error: &str,
+        retry_at: Option<i64>,
+    ) -> Result<()> {
+        let status = if retry_at.is_some() {
+            OutboxStatus::Pending
+        } else {
+            OutboxStatus::Failed
+        };
+
+        let mut update = OutboxEvents::update_many()
+            .col_expr(Column::Status, Expr::value(status.to_string()))
+            .col_expr(Column::Attempts, Expr::value(message.attempts + 1))
+            .col_expr(Column::LastError, Expr::value(error));
+        if let Some(retry_at) = retry_at {
+            update = update.col_expr(Column::NextAttemptAt, Expr::value(retry_at));
+        }
+        update
+            .filter(Column::Id.eq(message.id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新发件箱状态失败: {e}")))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! 隔离文件存储操作
+
+use super::SeaOrmStorage;
+use crate::config::AppConfig;
+use crate::entity::files::ActiveModel as FileActiveModel;
+use crate::entity::quarantined_files::{ActiveModel, Column, Entity as QuarantinedFiles};
+use crate::errors::{HWSystemError, Result};
+use crate::models::files::entities::{File, QuarantinedFile, StorageTier};
+use crate::models::files::requests::QuarantinedFileInput;
+use crate::utils::detect_code_language;
+use sea_orm::{ActiveModelTrait, EntityTrait, ModelTrait, QueryOrder, Set, TransactionTrait};
+use uuid::Uuid;
+
+impl SeaOrmStorage {
+    /// 记录隔离文件
+    pub async fn create_quarantined_file_impl(
+        &self,
+        input: QuarantinedFileInput,
+    ) -> Result<QuarantinedFile> {
+        // 与正式文件一致，归属上传者所在的组织
+        let org_id = self
+            .get_user_by_id_impl(input.user_id)
+            .await?
+            .and_then(|user| user.org_id);
+
+        let model = ActiveModel {
+            user_id: Set(input.user_id),
+            original_name: Set(input.original_name),
+            stored_name: Set(input.stored_name),
+            file_type: Set(input.file_type),
+            file_size: Set(input.file_size),
+            reason: Set(input.reason.to_string()),
+            detail: Set(input.detail),
+            org_id: Set(org_id),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("记录隔离文件失败: {e}")))?;
+
+        Ok(model.into_quarantined_file())
+    }
+
+    /// 列出待审核的隔离文件（最新的在前）
+    pub async fn list_quarantined_files_impl(&self) -> Result<Vec<QuarantinedFile>> {
+        let models = QuarantinedFiles::find()
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询隔离文件失败: {e}")))?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| m.into_quarantined_file())
+            .collect())
+    }
+
+    /// 获取隔离文件
+    pub async fn get_quarantined_file_impl(&self, id: i64) -> Result<Option<QuarantinedFile>> {
+        let model = QuarantinedFiles::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询隔离文件失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_quarantined_file()))
+    }
+
+    /// 审核通过：在同一事务中创建正式文件记录并移除隔离记录
+    ///
+    /// 调用方需先将文件从隔离目录移回上传目录（存储文件名不变）。
+    pub async fn release_quarantined_file_impl(&self, id: i64) -> Result<Option<File>> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let Some(model) = QuarantinedFiles::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询隔离文件失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let upload_dir = &AppConfig::get().upload.dir;
+        let file = FileActiveModel {
+            original_name: Set(model.original_name.clone()),
+            stored_name: Set(model.stored_name.clone()),
+            file_size: Set(model.file_size),
+            file_type: Set(model.file_type.clone()),
+            file_path: Set(format!("{}/{}", upload_dir, model.stored_name)),
+            download_token: Set(Uuid::new_v4().to_string()),
+            citation_count: Set(0),
+            user_id: Set(Some(model.user_id)),
+            org_id: Set(model.org_id),
+            // 内容未通过魔术字节校验，只按文件名识别
+            code_language: Set(detect_code_language(&model.original_name, &[]).map(str::to_string)),
+            storage_tier: Set(StorageTier::Hot.to_string()),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建文件记录失败: {e}")))?;
+
+        model
+            .delete(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除隔离记录失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(Some(file.into_file()))
+    }
+
+    /// 删除隔离记录，返回被删除的记录
+    pub async fn delete_quarantined_file_impl(&self, id: i64) -> Result<Option<QuarantinedFile>> {
+        let Some(model) = QuarantinedFiles::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询隔离文件失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let result = QuarantinedFiles::delete_by_id(id)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除隔离记录失败: {e}")))?;
+
+        Ok((result.rows_affected > 0).then(|| model.into_quarantined_file()))
+    }
+}
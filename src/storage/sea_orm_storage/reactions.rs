@@ -0,0 +1,121 @@
+//! 表情回应存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::reactions::{ActiveModel, Column, Entity as Reactions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::reactions::entities::{ReactionCount, ReactionSummary, ReactionTargetType};
+use sea_orm::sea_query::{Expr, Func, OnConflict};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+impl SeaOrmStorage {
+    /// 添加表情回应，已回应过同一表情时不重复写入，返回是否新增
+    pub async fn add_reaction_impl(
+        &self,
+        user_id: i64,
+        target_type: ReactionTargetType,
+        target_id: i64,
+        emoji: &str,
+    ) -> Result<bool> {
+        let model = ActiveModel {
+            user_id: Set(user_id),
+            target_type: Set(target_type.to_string()),
+            target_id: Set(target_id),
+            emoji: Set(emoji.to_string()),
+            created_at: Set(chrono::Utc::now().timestamp()),
+            ..Default::default()
+        };
+
+        let inserted = Reactions::insert(model)
+            .on_conflict(
+                OnConflict::columns([
+                    Column::TargetType,
+                    Column::TargetId,
+                    Column::UserId,
+                    Column::Emoji,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("添加表情回应失败: {e}")))?;
+
+        Ok(inserted > 0)
+    }
+
+    /// 取消表情回应，返回是否删除了记录
+    pub async fn remove_reaction_impl(
+        &self,
+        user_id: i64,
+        target_type: ReactionTargetType,
+        target_id: i64,
+        emoji: &str,
+    ) -> Result<bool> {
+        let result = Reactions::delete_many()
+            .filter(Column::TargetType.eq(target_type.to_string()))
+            .filter(Column::TargetId.eq(target_id))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Emoji.eq(emoji))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("取消表情回应失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 批量汇总目标的表情回应，`mine` 为 `viewer_id` 回应过的表情；没有回应的目标不在结果中
+    pub async fn get_reaction_summaries_impl(
+        &self,
+        target_type: ReactionTargetType,
+        target_ids: &[i64],
+        viewer_id: i64,
+    ) -> Result<HashMap<i64, ReactionSummary>> {
+        let mut summaries: HashMap<i64, ReactionSummary> = HashMap::new();
+        if target_ids.is_empty() {
+            return Ok(summaries);
+        }
+
+        // 按表情分组计数，以最早一条回应的 ID 排序，保证表情顺序稳定
+        let counts: Vec<(i64, String, i64)> = Reactions::find()
+            .select_only()
+            .column(Column::TargetId)
+            .column(Column::Emoji)
+            .column_as(Func::count(Expr::col(Column::Id)), "count")
+            .filter(Column::TargetType.eq(target_type.to_string()))
+            .filter(Column::TargetId.is_in(target_ids.iter().copied()))
+            .group_by(Column::TargetId)
+            .group_by(Column::Emoji)
+            .order_by_asc(Func::min(Expr::col(Column::Id)))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("统计表情回应失败: {e}")))?;
+        for (target_id, emoji, count) in counts {
+            summaries
+                .entry(target_id)
+                .or_default()
+                .counts
+                .push(ReactionCount { emoji, count });
+        }
+
+        let mine: Vec<(i64, String)> = Reactions::find()
+            .select_only()
+            .column(Column::TargetId)
+            .column(Column::Emoji)
+            .filter(Column::TargetType.eq(target_type.to_string()))
+            .filter(Column::TargetId.is_in(target_ids.iter().copied()))
+            .filter(Column::UserId.eq(viewer_id))
+            .order_by_asc(Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询表情回应失败: {e}")))?;
+        for (target_id, emoji) in mine {
+            summaries.entry(target_id).or_default().mine.push(emoji);
+        }
+
+        Ok(summaries)
+    }
+}
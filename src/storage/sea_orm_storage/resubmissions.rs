@@ -0,0 +1,186 @@
+//! 退回重交存储操作
+
+use super::SeaOrmStorage;
+use super::homework_counters::{submitter_state, track_submitter_change};
+use super::outbox::insert_outbox_events;
+use crate::entity::homeworks::Entity as Homeworks;
+use crate::entity::resubmission_requests::{ActiveModel, Column, Entity as ResubmissionRequests};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    notifications::entities::{NotificationType, ReferenceType},
+    outbox::entities::OutboxEvent,
+    submissions::entities::{ResubmissionRequest, SubmissionStatus},
+};
+use sea_orm::sea_query::{Condition, Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    TransactionTrait,
+};
+
+/// 同一作业（同一分题）下学生尚未重交的退回请求
+fn unfulfilled(homework_id: i64, student_id: i64, part_id: Option<i64>) -> Condition {
+    let same_part = match part_id {
+        Some(part_id) => SubmissionColumn::PartId.eq(part_id),
+        None => SubmissionColumn::PartId.is_null(),
+    };
+    Condition::all()
+        .add(Column::HomeworkId.eq(homework_id))
+        .add(Column::StudentId.eq(student_id))
+        .add(Column::FulfilledAt.is_null())
+        .add(
+            Column::SubmissionId.in_subquery(
+                Query::select()
+                    .column(SubmissionColumn::Id)
+                    .from(Submissions)
+                    .and_where(same_part)
+                    .to_owned(),
+            ),
+        )
+}
+
+/// 学生在重新开放期限内是否可以重交（不计迟交）
+pub(super) async fn is_reopened<C: ConnectionTrait>(
+    conn: &C,
+    homework_id: i64,
+    student_id: i64,
+    part_id: Option<i64>,
+    now: i64,
+) -> Result<bool> {
+    let count = ResubmissionRequests::find()
+        .filter(unfulfilled(homework_id, student_id, part_id))
+        .filter(Column::ReopenUntil.gte(now))
+        .count(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询退回请求失败: {e}")))?;
+    Ok(count > 0)
+}
+
+/// 学生重新提交后，将其未完成的退回请求标记为已重交
+pub(super) async fn fulfill_resubmission_requests<C: ConnectionTrait>(
+    conn: &C,
+    homework_id: i64,
+    student_id: i64,
+    part_id: Option<i64>,
+    now: i64,
+) -> Result<()> {
+    ResubmissionRequests::update_many()
+        .col_expr(Column::FulfilledAt, Expr::value(Some(now)))
+        .filter(unfulfilled(homework_id, student_id, part_id))
+        .exec(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("更新退回请求失败: {e}")))?;
+    Ok(())
+}
+
+impl SeaOrmStorage {
+    /// 退回提交：状态置为 returned（清除工作流自定义状态）、记录退回请求并通知学生
+    ///
+    /// 提交已有更新版本或已处于退回状态时不做修改，返回 None。
+    pub async fn request_resubmission_impl(
+        &self,
+        submission_id: i64,
+        requested_by: i64,
+        comment: String,
+        reopen_until: i64,
+    ) -> Result<Option<ResubmissionRequest>> {
+        let now = chrono::Utc::now().timestamp();
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let Some((submission, homework)) = Submissions::find_by_id(submission_id)
+            .find_also_related(Homeworks)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        // 只能退回学生（同一分题）最新的提交
+        let same_part = match submission.part_id {
+            Some(part_id) => SubmissionColumn::PartId.eq(part_id),
+            None => SubmissionColumn::PartId.is_null(),
+        };
+        let newer = Submissions::find()
+            .filter(SubmissionColumn::HomeworkId.eq(submission.homework_id))
+            .filter(SubmissionColumn::CreatorId.eq(submission.creator_id))
+            .filter(same_part)
+            .filter(SubmissionColumn::Version.gt(submission.version))
+            .count(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交版本失败: {e}")))?;
+        if newer > 0 {
+            return Ok(None);
+        }
+
+        let before = submitter_state(&txn, submission.homework_id, submission.creator_id).await?;
+
+        // 仅当提交尚未被退回时更新，并发退回时后到的请求不生效
+        let updated = Submissions::update_many()
+            .col_expr(
+                SubmissionColumn::Status,
+                Expr::value(SubmissionStatus::RETURNED.to_string()),
+            )
+            .col_expr(
+                SubmissionColumn::WorkflowStatus,
+                Expr::value(Option::<String>::None),
+            )
+            .filter(SubmissionColumn::Id.eq(submission_id))
+            .filter(SubmissionColumn::Status.ne(SubmissionStatus::RETURNED))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新提交状态失败: {e}")))?;
+        if updated.rows_affected == 0 {
+            return Ok(None);
+        }
+        track_submitter_change(&txn, submission.homework_id, submission.creator_id, before).await?;
+
+        let model = ActiveModel {
+            submission_id: Set(submission_id),
+            homework_id: Set(submission.homework_id),
+            student_id: Set(submission.creator_id),
+            requested_by: Set(requested_by),
+            comment: Set(comment.clone()),
+            reopen_until: Set(reopen_until),
+            fulfilled_at: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建退回请求失败: {e}")))?;
+
+        // 通知学生
+        if let Some(homework) = homework {
+            let reopen_until = chrono::DateTime::from_timestamp(reopen_until, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_default();
+            insert_outbox_events(
+                &txn,
+                vec![OutboxEvent::Notification {
+                    user_ids: vec![submission.creator_id],
+                    notification_type: NotificationType::ResubmissionRequested,
+                    vars: vec![
+                        ("homework_title".to_string(), homework.title),
+                        ("comment".to_string(), comment),
+                        ("reopen_until".to_string(), reopen_until),
+                    ],
+                    reference_type: Some(ReferenceType::Submission),
+                    reference_id: Some(submission_id),
+                }],
+            )
+            .await?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(Some(model.into_resubmission_request()))
+    }
+}
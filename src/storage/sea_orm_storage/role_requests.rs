@@ -0,0 +1,265 @@
+//! 角色申请存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use super::organizations::tenant_condition;
+use crate::cache::invalidation::CacheInvalidation;
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::prelude::UserAuditLogActiveModel;
+use crate::entity::role_requests::{ActiveModel, Column, Entity as RoleRequests, Model};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    PaginationInfo,
+    common::PaginationPolicy,
+    users::{
+        entities::{RoleRequest, RoleRequestStatus, UserRole},
+        requests::RoleRequestListQuery,
+        responses::RoleRequestListResponse,
+    },
+};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+
+/// 批量查询申请人与附件信息并转换为业务模型
+async fn with_details<C: ConnectionTrait>(
+    conn: &C,
+    models: Vec<Model>,
+) -> Result<Vec<RoleRequest>> {
+    if models.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let user_ids: Vec<i64> = models.iter().map(|m| m.user_id).collect();
+    let users: HashMap<i64, _> = Users::find()
+        .filter(UserColumn::Id.is_in(user_ids))
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询申请人失败: {e}")))?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let file_ids: Vec<i64> = models.iter().filter_map(|m| m.attachment_file_id).collect();
+    let files: HashMap<i64, _> = if file_ids.is_empty() {
+        HashMap::new()
+    } else {
+        Files::find()
+            .filter(FileColumn::Id.is_in(file_ids))
+            .all(conn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询申请附件失败: {e}")))?
+            .into_iter()
+            .map(|f| (f.id, f))
+            .collect()
+    };
+
+    Ok(models
+        .into_iter()
+        .map(|m| {
+            let user = users.get(&m.user_id);
+            let file = m.attachment_file_id.and_then(|id| files.get(&id));
+            m.into_role_request(user, file)
+        })
+        .collect())
+}
+
+impl SeaOrmStorage {
+    /// 创建角色申请
+    pub async fn create_role_request_impl(
+        &self,
+        user_id: i64,
+        org_id: Option<i64>,
+        requested_role: UserRole,
+        reason: String,
+        attachment_file_id: Option<i64>,
+    ) -> Result<RoleRequest> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            user_id: Set(user_id),
+            org_id: Set(org_id),
+            requested_role: Set(requested_role.to_string()),
+            reason: Set(reason),
+            attachment_file_id: Set(attachment_file_id),
+            status: Set(RoleRequestStatus::Pending.to_string()),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建角色申请失败: {e}")))?;
+
+        Ok(with_details(&self.db, vec![model]).await?.remove(0))
+    }
+
+    /// 通过 ID 获取角色申请
+    pub async fn get_role_request_by_id_impl(&self, id: i64) -> Result<Option<RoleRequest>> {
+        let model = RoleRequests::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色申请失败: {e}")))?;
+
+        match model {
+            Some(model) => Ok(with_details(&self.db, vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    /// 获取用户待审核的角色申请
+    pub async fn get_pending_role_request_impl(&self, user_id: i64) -> Result<Option<RoleRequest>> {
+        let model = RoleRequests::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Status.eq(RoleRequestStatus::Pending.to_string()))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色申请失败: {e}")))?;
+
+        match model {
+            Some(model) => Ok(with_details(&self.db, vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    /// 分页列出角色申请（按提交时间倒序）
+    pub async fn list_role_requests_impl(
+        &self,
+        query: RoleRequestListQuery,
+    ) -> Result<RoleRequestListResponse> {
+        let (page, size) =
+            PaginationPolicy::for_endpoint("role_requests").resolve(query.page, query.size);
+
+        let mut select = RoleRequests::find().filter(tenant_condition(Column::OrgId, query.tenant));
+
+        if let Some(status) = query.status {
+            select = select.filter(Column::Status.eq(status.to_string()));
+        }
+        if let Some(user_id) = query.user_id {
+            select = select.filter(Column::UserId.eq(user_id));
+        }
+
+        let paginator = select
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .paginate(&self.db, size);
+        let total = paginator
+            .num_items()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色申请总数失败: {e}")))?;
+        let pages = paginator
+            .num_pages()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色申请页数失败: {e}")))?;
+        let models = paginator
+            .fetch_page(page - 1)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色申请列表失败: {e}")))?;
+
+        Ok(RoleRequestListResponse {
+            items: with_details(&self.db, models).await?,
+            pagination: PaginationInfo {
+                page: page as i64,
+                page_size: size as i64,
+                total: total as i64,
+                total_pages: pages as i64,
+            },
+        })
+    }
+
+    /// 审核角色申请
+    ///
+    /// 申请状态、用户角色（通过时）与审计日志在同一事务中更新；
+    /// 申请不存在或已被审核时返回 None。
+    pub async fn review_role_request_impl(
+        &self,
+        id: i64,
+        approve: bool,
+        comment: Option<String>,
+        reviewer_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<Option<RoleRequest>> {
+        let now = chrono::Utc::now().timestamp();
+        let status = if approve {
+            RoleRequestStatus::Approved
+        } else {
+            RoleRequestStatus::Rejected
+        };
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        // 仅更新待审核的申请，避免并发重复审核
+        let rows_affected = RoleRequests::update_many()
+            .col_expr(Column::Status, Expr::value(status.to_string()))
+            .col_expr(Column::ReviewerId, Expr::value(reviewer_id))
+            .col_expr(Column::ReviewComment, Expr::value(comment))
+            .col_expr(Column::ReviewedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(RoleRequestStatus::Pending.to_string()))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新角色申请失败: {e}")))?
+            .rows_affected;
+        if rows_affected == 0 {
+            return Ok(None);
+        }
+
+        let model = RoleRequests::find_by_id(id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询角色申请失败: {e}")))?
+            .ok_or_else(|| HWSystemError::not_found("角色申请不存在"))?;
+
+        if approve {
+            Users::update_many()
+                .col_expr(UserColumn::Role, Expr::value(model.requested_role.clone()))
+                .col_expr(UserColumn::UpdatedAt, Expr::value(now))
+                .filter(UserColumn::Id.eq(model.user_id))
+                .exec(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("更新用户角色失败: {e}")))?;
+        }
+
+        // 写入审计日志
+        let action = if approve {
+            "role_request_approve"
+        } else {
+            "role_request_reject"
+        };
+        UserAuditLogActiveModel {
+            user_id: Set(model.user_id),
+            action: Set(action.to_string()),
+            detail: Set(Some(format!(
+                "request_id={id},role={}",
+                model.requested_role
+            ))),
+            operator_id: Set(reviewer_id),
+            ip_address: Set(ip_address),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+
+        let request = with_details(&txn, vec![model]).await?.pop();
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        if approve && let Some(request) = &request {
+            self.publish_invalidation(CacheInvalidation::User(request.user_id))
+                .await;
+        }
+
+        Ok(request)
+    }
+}
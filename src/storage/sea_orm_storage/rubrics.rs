@@ -0,0 +1,299 @@
+//! 评分量规库存储操作
+
+use super::SeaOrmStorage;
+use super::organizations::tenant_condition;
+use crate::entity::homework_rubrics::{Column as HomeworkRubricColumn, Entity as HomeworkRubrics};
+use crate::entity::rubric_versions::{
+    ActiveModel as VersionActiveModel, Column as VersionColumn, Entity as RubricVersions,
+};
+use crate::entity::rubrics::{ActiveModel, Column, Entity as Rubrics};
+use crate::errors::{HWSystemError, Result};
+use crate::models::organizations::entities::TenantScope;
+use crate::models::rubrics::entities::{Rubric, RubricCriterion, RubricVersion};
+use crate::models::rubrics::requests::{RubricInput, RubricScope, RubricUpdate};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+
+fn serialize_criteria(criteria: &[RubricCriterion]) -> Result<String> {
+    serde_json::to_string(criteria)
+        .map_err(|e| HWSystemError::serialization(format!("序列化评分维度失败: {e}")))
+}
+
+/// 写入一个量规版本
+async fn insert_version<C: ConnectionTrait>(
+    conn: &C,
+    rubric_id: i64,
+    version: i32,
+    criteria: String,
+    created_by: i64,
+    now: i64,
+) -> Result<()> {
+    VersionActiveModel {
+        rubric_id: Set(rubric_id),
+        version: Set(version),
+        criteria: Set(criteria),
+        created_by: Set(created_by),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await
+    .map_err(|e| HWSystemError::database_operation(format!("创建量规版本失败: {e}")))?;
+    Ok(())
+}
+
+impl SeaOrmStorage {
+    /// 创建量规，同时写入版本 1
+    pub async fn create_rubric_impl(&self, input: RubricInput) -> Result<Rubric> {
+        let now = chrono::Utc::now().timestamp();
+        let criteria = serialize_criteria(&input.criteria)?;
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let model = ActiveModel {
+            owner_id: Set(input.owner_id),
+            org_id: Set(input.org_id),
+            title: Set(input.title),
+            description: Set(input.description),
+            criteria: Set(criteria.clone()),
+            version: Set(1),
+            org_visible: Set(input.org_visible),
+            share_code: Set(None),
+            source_rubric_id: Set(input.source_rubric_id),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建量规失败: {e}")))?;
+
+        insert_version(&txn, model.id, 1, criteria, input.owner_id, now).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(model.into_rubric())
+    }
+
+    /// 列出用户可见的量规：自己的量规，以及租户范围内其他教师公开的量规
+    pub async fn list_rubrics_impl(
+        &self,
+        user_id: i64,
+        tenant: TenantScope,
+        scope: Option<RubricScope>,
+    ) -> Result<Vec<Rubric>> {
+        let mine = Condition::all().add(Column::OwnerId.eq(user_id));
+        let org = Condition::all()
+            .add(Column::OwnerId.ne(user_id))
+            .add(Column::OrgVisible.eq(true))
+            .add(tenant_condition(Column::OrgId, tenant));
+        let condition = match scope {
+            Some(RubricScope::Mine) => mine,
+            Some(RubricScope::Org) => org,
+            None => Condition::any().add(mine).add(org),
+        };
+
+        let models = Rubrics::find()
+            .filter(condition)
+            .order_by_desc(Column::UpdatedAt)
+            .order_by_desc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规失败: {e}")))?;
+
+        Ok(models.into_iter().map(|m| m.into_rubric()).collect())
+    }
+
+    /// 获取量规
+    pub async fn get_rubric_impl(&self, rubric_id: i64) -> Result<Option<Rubric>> {
+        let model = Rubrics::find_by_id(rubric_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_rubric()))
+    }
+
+    /// 通过分享码获取量规
+    pub async fn get_rubric_by_share_code_impl(&self, share_code: &str) -> Result<Option<Rubric>> {
+        let model = Rubrics::find()
+            .filter(Column::ShareCode.eq(share_code))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_rubric()))
+    }
+
+    /// 更新量规
+    ///
+    /// 评分维度与当前版本不同时版本号加一并写入新版本；只改标题、说明或可见性不产生新版本。
+    pub async fn update_rubric_impl(
+        &self,
+        rubric_id: i64,
+        update: RubricUpdate,
+        editor_id: i64,
+    ) -> Result<Option<Rubric>> {
+        let now = chrono::Utc::now().timestamp();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let Some(model) = Rubrics::find_by_id(rubric_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let current: Vec<RubricCriterion> =
+            serde_json::from_str(&model.criteria).unwrap_or_default();
+        let new_criteria = update.criteria.filter(|criteria| *criteria != current);
+
+        let version = model.version;
+        let mut active: ActiveModel = model.into();
+        if let Some(title) = update.title {
+            active.title = Set(title);
+        }
+        if let Some(description) = update.description {
+            active.description = Set(description);
+        }
+        if let Some(org_visible) = update.org_visible {
+            active.org_visible = Set(org_visible);
+        }
+        if let Some(criteria) = new_criteria {
+            let criteria = serialize_criteria(&criteria)?;
+            insert_version(
+                &txn,
+                rubric_id,
+                version + 1,
+                criteria.clone(),
+                editor_id,
+                now,
+            )
+            .await?;
+            active.criteria = Set(criteria);
+            active.version = Set(version + 1);
+        }
+        active.updated_at = Set(now);
+        let model = active
+            .update(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新量规失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(Some(model.into_rubric()))
+    }
+
+    /// 设置或清除量规分享码
+    pub async fn set_rubric_share_code_impl(
+        &self,
+        rubric_id: i64,
+        share_code: Option<String>,
+    ) -> Result<Option<Rubric>> {
+        let Some(model) = Rubrics::find_by_id(rubric_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let mut active: ActiveModel = model.into();
+        active.share_code = Set(share_code);
+        let model = active
+            .update(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新量规分享码失败: {e}")))?;
+
+        Ok(Some(model.into_rubric()))
+    }
+
+    /// 删除量规及其版本
+    ///
+    /// 已设置到作业上的快照保留，只解除与来源量规的关联；导入的副本同样只清除来源。
+    pub async fn delete_rubric_impl(&self, rubric_id: i64) -> Result<bool> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        HomeworkRubrics::update_many()
+            .col_expr(
+                HomeworkRubricColumn::RubricId,
+                Expr::value(Option::<i64>::None),
+            )
+            .filter(HomeworkRubricColumn::RubricId.eq(rubric_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("解除作业量规关联失败: {e}")))?;
+        Rubrics::update_many()
+            .col_expr(Column::SourceRubricId, Expr::value(Option::<i64>::None))
+            .filter(Column::SourceRubricId.eq(rubric_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("解除量规来源失败: {e}")))?;
+        RubricVersions::delete_many()
+            .filter(VersionColumn::RubricId.eq(rubric_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除量规版本失败: {e}")))?;
+        let result = Rubrics::delete_by_id(rubric_id)
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除量规失败: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 列出量规的全部版本，新版本在前
+    pub async fn list_rubric_versions_impl(&self, rubric_id: i64) -> Result<Vec<RubricVersion>> {
+        let models = RubricVersions::find()
+            .filter(VersionColumn::RubricId.eq(rubric_id))
+            .order_by_desc(VersionColumn::Version)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规版本失败: {e}")))?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| m.into_rubric_version())
+            .collect())
+    }
+
+    /// 获取量规的指定版本
+    pub async fn get_rubric_version_impl(
+        &self,
+        rubric_id: i64,
+        version: i32,
+    ) -> Result<Option<RubricVersion>> {
+        let model = RubricVersions::find()
+            .filter(VersionColumn::RubricId.eq(rubric_id))
+            .filter(VersionColumn::Version.eq(version))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询量规版本失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_rubric_version()))
+    }
+}
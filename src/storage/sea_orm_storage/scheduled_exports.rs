@@ -0,0 +1,270 @@
+//! 班级定时导出存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::scheduled_exports::{
+    ActiveModel, Column, Entity as ScheduledExports, Model as ScheduledExportModel,
+};
+use crate::errors::{HWSystemError, Result};
+use crate::models::classes::{
+    entities::{ClassScheduledExport, ScheduledExportFrequency, ScheduledExportKind},
+    requests::{CreateClassScheduledExportRequest, UpdateClassScheduledExportRequest},
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::Expr,
+};
+
+/// 按执行计划计算 `now` 之后的下一次执行时间
+fn next_run_at(frequency: &str, weekday: Option<i32>, hour: i32, now: i64) -> i64 {
+    let frequency = frequency
+        .parse()
+        .unwrap_or(ScheduledExportFrequency::Weekly);
+    let now = DateTime::<Utc>::from_timestamp(now, 0).unwrap_or_default();
+    frequency.next_run(weekday, hour, now).timestamp()
+}
+
+impl SeaOrmStorage {
+    /// 创建班级定时导出
+    pub async fn create_class_scheduled_export_impl(
+        &self,
+        class_id: i64,
+        req: CreateClassScheduledExportRequest,
+        created_by: i64,
+    ) -> Result<ClassScheduledExport> {
+        let now = Utc::now().timestamp();
+        // 仅每周执行时保留星期
+        let weekday = req
+            .weekday
+            .filter(|_| req.frequency == ScheduledExportFrequency::Weekly);
+        let frequency = req.frequency.to_string();
+
+        let model = ActiveModel {
+            class_id: Set(class_id),
+            kind: Set(req
+                .kind
+                .unwrap_or(ScheduledExportKind::ClassReport)
+                .to_string()),
+            next_run_at: Set(next_run_at(&frequency, weekday, req.hour, now)),
+            frequency: Set(frequency),
+            weekday: Set(weekday),
+            hour: Set(req.hour),
+            delivery: Set(req.delivery.to_string()),
+            target: Set(req.target),
+            enabled: Set(req.enabled.unwrap_or(true)),
+            last_run_at: Set(None),
+            last_error: Set(None),
+            consecutive_failures: Set(0),
+            created_by: Set(created_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建定时导出失败: {e}")))?;
+
+        Ok(result.into_scheduled_export())
+    }
+
+    /// 列出班级的定时导出
+    pub async fn list_class_scheduled_exports_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Vec<ClassScheduledExport>> {
+        let results = ScheduledExports::find()
+            .filter(Column::ClassId.eq(class_id))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询定时导出失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .map(|m| m.into_scheduled_export())
+            .collect())
+    }
+
+    async fn find_class_scheduled_export(
+        &self,
+        class_id: i64,
+        export_id: i64,
+    ) -> Result<Option<ScheduledExportModel>> {
+        ScheduledExports::find_by_id(export_id)
+            .filter(Column::ClassId.eq(class_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询定时导出失败: {e}")))
+    }
+
+    /// 获取班级的某个定时导出
+    pub async fn get_class_scheduled_export_impl(
+        &self,
+        class_id: i64,
+        export_id: i64,
+    ) -> Result<Option<ClassScheduledExport>> {
+        Ok(self
+            .find_class_scheduled_export(class_id, export_id)
+            .await?
+            .map(|m| m.into_scheduled_export()))
+    }
+
+    /// 更新定时导出
+    ///
+    /// 修改执行计划或重新启用时重新计算下一次执行时间；重新启用时清零连续失败次数。
+    pub async fn update_class_scheduled_export_impl(
+        &self,
+        class_id: i64,
+        export_id: i64,
+        req: UpdateClassScheduledExportRequest,
+    ) -> Result<Option<ClassScheduledExport>> {
+        let Some(model) = self
+            .find_class_scheduled_export(class_id, export_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let now = Utc::now().timestamp();
+        let frequency = req
+            .frequency
+            .map(|f| f.to_string())
+            .unwrap_or_else(|| model.frequency.clone());
+        let weekday = req
+            .weekday
+            .or(model.weekday)
+            .filter(|_| frequency == ScheduledExportFrequency::Weekly.to_string());
+        let hour = req.hour.unwrap_or(model.hour);
+        let reenabled = req.enabled == Some(true) && !model.enabled;
+        let reschedule = reenabled
+            || frequency != model.frequency
+            || weekday != model.weekday
+            || hour != model.hour;
+
+        let mut active: ActiveModel = model.into();
+        if reschedule {
+            active.next_run_at = Set(next_run_at(&frequency, weekday, hour, now));
+        }
+        active.frequency = Set(frequency);
+        active.weekday = Set(weekday);
+        active.hour = Set(hour);
+        if let Some(delivery) = req.delivery {
+            active.delivery = Set(delivery.to_string());
+        }
+        if let Some(target) = req.target {
+            active.target = Set(target);
+        }
+        if let Some(enabled) = req.enabled {
+            active.enabled = Set(enabled);
+        }
+        if reenabled {
+            active.consecutive_failures = Set(0);
+        }
+        active.updated_at = Set(now);
+
+        let result = active
+            .update(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新定时导出失败: {e}")))?;
+
+        Ok(Some(result.into_scheduled_export()))
+    }
+
+    /// 删除定时导出
+    pub async fn delete_class_scheduled_export_impl(
+        &self,
+        class_id: i64,
+        export_id: i64,
+    ) -> Result<bool> {
+        let result = ScheduledExports::delete_many()
+            .filter(Column::Id.eq(export_id))
+            .filter(Column::ClassId.eq(class_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除定时导出失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 领取到期的定时导出
+    ///
+    /// 领取时即把下一次执行时间推进到 `now` 之后，条件更新保证多实例部署时同一次执行只被一个实例领取；
+    /// 停机期间错过的多次执行只补一次。
+    pub async fn claim_due_scheduled_exports_impl(
+        &self,
+        now: i64,
+        limit: u64,
+    ) -> Result<Vec<ClassScheduledExport>> {
+        let due = ScheduledExports::find()
+            .filter(Column::Enabled.eq(true))
+            .filter(Column::NextRunAt.lte(now))
+            .order_by_asc(Column::NextRunAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询到期定时导出失败: {e}")))?;
+
+        let mut claimed = Vec::with_capacity(due.len());
+        for mut model in due {
+            let next = next_run_at(&model.frequency, model.weekday, model.hour, now);
+            let result = ScheduledExports::update_many()
+                .col_expr(Column::NextRunAt, Expr::value(next))
+                .col_expr(Column::LastRunAt, Expr::value(now))
+                .filter(Column::Id.eq(model.id))
+                .filter(Column::NextRunAt.eq(model.next_run_at))
+                .exec(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("领取定时导出失败: {e}")))?;
+            if result.rows_affected == 1 {
+                model.next_run_at = next;
+                model.last_run_at = Some(now);
+                claimed.push(model.into_scheduled_export());
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    /// 记录一次执行结果
+    ///
+    /// 成功时清空错误与连续失败次数；失败时累加，达到 `max_failures`（大于 0）后停用。
+    /// 返回更新后的记录，已被删除时返回 None。
+    pub async fn finish_scheduled_export_run_impl(
+        &self,
+        export_id: i64,
+        error: Option<String>,
+        max_failures: i32,
+    ) -> Result<Option<ClassScheduledExport>> {
+        let Some(model) = ScheduledExports::find_by_id(export_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询定时导出失败: {e}")))?
+        else {
+            return Ok(None);
+        };
+
+        let failures = if error.is_some() {
+            model.consecutive_failures + 1
+        } else {
+            0
+        };
+        let disable = max_failures > 0 && failures >= max_failures;
+
+        let mut active: ActiveModel = model.into();
+        active.consecutive_failures = Set(failures);
+        active.last_error = Set(error);
+        if disable {
+            active.enabled = Set(false);
+        }
+        active.updated_at = Set(Utc::now().timestamp());
+
+        let result = active
+            .update(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("记录定时导出结果失败: {e}")))?;
+
+        Ok(Some(result.into_scheduled_export()))
+    }
+}
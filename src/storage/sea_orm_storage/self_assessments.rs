@@ -0,0 +1,50 @@
+//! 提交自评存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::submission_self_assessments::{Column, Entity as SubmissionSelfAssessments};
+use crate::errors::{HWSystemError, Result};
+use crate::models::submissions::entities::SubmissionSelfAssessment;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+impl SeaOrmStorage {
+    /// 批量查询提交的自评，按提交 ID 索引
+    pub async fn self_assessment_map_impl(
+        &self,
+        submission_ids: &[i64],
+    ) -> Result<HashMap<i64, SubmissionSelfAssessment>> {
+        if submission_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let models = SubmissionSelfAssessments::find()
+            .filter(Column::SubmissionId.is_in(submission_ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交自评失败: {e}")))?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| (m.submission_id, m.into_self_assessment()))
+            .collect())
+    }
+
+    /// 列出作业各提交的自评
+    pub async fn list_homework_self_assessments_impl(
+        &self,
+        homework_id: i64,
+    ) -> Result<Vec<SubmissionSelfAssessment>> {
+        let models = SubmissionSelfAssessments::find()
+            .filter(Column::HomeworkId.eq(homework_id))
+            .order_by_asc(Column::SubmissionId)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交自评失败: {e}")))?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| m.into_self_assessment())
+            .collect())
+    }
+}
@@ -0,0 +1,193 @@
+//! 学习目标存储操作
+
+use std::collections::{HashMap, HashSet};
+
+use super::SeaOrmStorage;
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::homework_exemptions::{Column as ExemptionColumn, Entity as HomeworkExemptions};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::student_goals::{ActiveModel, Column, Entity as StudentGoals};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::users::entities::{GoalStanding, StudentGoal};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+
+impl SeaOrmStorage {
+    /// 设置学习目标（已存在时更新目标值）
+    pub async fn upsert_student_goal_impl(
+        &self,
+        user_id: i64,
+        class_id: i64,
+        target_percent: f64,
+    ) -> Result<StudentGoal> {
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = StudentGoals::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ClassId.eq(class_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询学习目标失败: {e}")))?;
+
+        let model = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.target_percent = Set(target_percent);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ActiveModel {
+                    user_id: Set(user_id),
+                    class_id: Set(class_id),
+                    target_percent: Set(target_percent),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    ..Default::default()
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+        .map_err(|e| HWSystemError::database_operation(format!("保存学习目标失败: {e}")))?;
+
+        Ok(model.into_student_goal())
+    }
+
+    /// 删除学习目标
+    pub async fn delete_student_goal_impl(&self, user_id: i64, class_id: i64) -> Result<bool> {
+        let result = StudentGoals::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ClassId.eq(class_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除学习目标失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 汇总用户各学习目标所在班级的成绩
+    ///
+    /// 每项作业取最新一次已评分的提交，按作业满分加权；被豁免的作业不计入。
+    pub async fn list_goal_standings_impl(&self, user_id: i64) -> Result<Vec<GoalStanding>> {
+        let goals = StudentGoals::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::ClassId)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询学习目标失败: {e}")))?;
+        if goals.is_empty() {
+            return Ok(vec![]);
+        }
+        let class_ids: Vec<i64> = goals.iter().map(|g| g.class_id).collect();
+
+        let class_names: HashMap<i64, String> = Classes::find()
+            .select_only()
+            .column(ClassColumn::Id)
+            .column(ClassColumn::Name)
+            .filter(ClassColumn::Id.is_in(class_ids.iter().copied()))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级失败: {e}")))?
+            .into_iter()
+            .collect();
+
+        let homeworks = Homeworks::find()
+            .filter(HomeworkColumn::ClassId.is_in(class_ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级作业失败: {e}")))?;
+        let homework_ids: Vec<i64> = homeworks.iter().map(|hw| hw.id).collect();
+
+        let mut latest_scores: HashMap<i64, f64> = HashMap::new();
+        let mut exempted: HashSet<i64> = HashSet::new();
+        if !homework_ids.is_empty() {
+            exempted = HomeworkExemptions::find()
+                .select_only()
+                .column(ExemptionColumn::HomeworkId)
+                .filter(ExemptionColumn::UserId.eq(user_id))
+                .filter(ExemptionColumn::HomeworkId.is_in(homework_ids.iter().copied()))
+                .into_tuple::<i64>()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询作业豁免失败: {e}")))?
+                .into_iter()
+                .collect();
+
+            // 按版本倒序，便于取每项作业最新的已评分提交
+            let submissions: Vec<(i64, i64)> = Submissions::find()
+                .select_only()
+                .column(SubmissionColumn::Id)
+                .column(SubmissionColumn::HomeworkId)
+                .filter(SubmissionColumn::CreatorId.eq(user_id))
+                .filter(SubmissionColumn::HomeworkId.is_in(homework_ids.iter().copied()))
+                .order_by_desc(SubmissionColumn::Version)
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询提交记录失败: {e}")))?;
+
+            if !submissions.is_empty() {
+                let grades: HashMap<i64, f64> = Grades::find()
+                    .select_only()
+                    .column(GradeColumn::SubmissionId)
+                    .column(GradeColumn::Score)
+                    .filter(
+                        GradeColumn::SubmissionId
+                            .is_in(submissions.iter().map(|(submission_id, _)| *submission_id)),
+                    )
+                    .into_tuple()
+                    .all(&self.db)
+                    .await
+                    .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
+                    .into_iter()
+                    .collect();
+
+                for (submission_id, homework_id) in &submissions {
+                    if let Some(score) = grades.get(submission_id) {
+                        latest_scores.entry(*homework_id).or_insert(*score);
+                    }
+                }
+            }
+        }
+
+        let standings = goals
+            .into_iter()
+            .map(|goal| {
+                let mut standing = GoalStanding {
+                    class_id: goal.class_id,
+                    class_name: class_names.get(&goal.class_id).cloned().unwrap_or_default(),
+                    target_percent: goal.target_percent,
+                    earned_score: 0.0,
+                    graded_max_score: 0.0,
+                    graded_count: 0,
+                    remaining_max_score: 0.0,
+                    remaining_count: 0,
+                };
+                for hw in homeworks
+                    .iter()
+                    .filter(|hw| hw.class_id == goal.class_id && !exempted.contains(&hw.id))
+                {
+                    match latest_scores.get(&hw.id) {
+                        Some(score) => {
+                            standing.earned_score += score;
+                            standing.graded_max_score += hw.max_score;
+                            standing.graded_count += 1;
+                        }
+                        None => {
+                            standing.remaining_max_score += hw.max_score;
+                            standing.remaining_count += 1;
+                        }
+                    }
+                }
+                standing
+            })
+            .collect();
+
+        Ok(standings)
+    }
+}
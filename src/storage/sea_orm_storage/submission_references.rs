@@ -0,0 +1,37 @@
+//! 提交外部引用存储操作
+
+use std::collections::HashMap;
+
+use super::SeaOrmStorage;
+use crate::entity::submission_references::{Column, Entity as SubmissionReferences};
+use crate::errors::{HWSystemError, Result};
+use crate::models::submissions::entities::SubmissionReference;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+impl SeaOrmStorage {
+    /// 批量查询提交的外部引用，按提交 ID 索引（保持提交时的顺序）
+    pub async fn submission_reference_map_impl(
+        &self,
+        submission_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<SubmissionReference>>> {
+        if submission_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let models = SubmissionReferences::find()
+            .filter(Column::SubmissionId.is_in(submission_ids.iter().copied()))
+            .order_by_asc(Column::SubmissionId)
+            .order_by_asc(Column::Position)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交外部引用失败: {e}")))?;
+
+        let mut map: HashMap<i64, Vec<SubmissionReference>> = HashMap::new();
+        for model in models {
+            map.entry(model.submission_id)
+                .or_default()
+                .push(model.into_submission_reference());
+        }
+        Ok(map)
+    }
+}
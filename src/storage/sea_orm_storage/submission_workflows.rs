@@ -0,0 +1,152 @@
+//! 提交状态工作流存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::class_submission_workflows::{ActiveModel, Entity as ClassSubmissionWorkflows};
+use crate::entity::homeworks::{Column as HomeworkColumn, Entity as Homeworks};
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::submissions::entities::SubmissionWorkflow;
+use sea_orm::sea_query::{Condition, Expr, Query};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+
+impl SeaOrmStorage {
+    /// 获取班级自定义的提交状态工作流，未自定义时返回 None
+    pub async fn get_class_submission_workflow_impl(
+        &self,
+        class_id: i64,
+    ) -> Result<Option<SubmissionWorkflow>> {
+        let model = ClassSubmissionWorkflows::find_by_id(class_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交工作流失败: {e}")))?;
+
+        Ok(model.map(|m| m.into_submission_workflow()))
+    }
+
+    /// 保存班级提交状态工作流（默认工作流删除记录）
+    ///
+    /// 已不在新工作流中的自定义状态会被清除，对应提交回到内置状态。
+    pub async fn save_class_submission_workflow_impl(
+        &self,
+        class_id: i64,
+        workflow: SubmissionWorkflow,
+        updated_by: i64,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let keys: Vec<String> = workflow.statuses.iter().map(|s| s.key.clone()).collect();
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let existing = ClassSubmissionWorkflows::find_by_id(class_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交工作流失败: {e}")))?;
+
+        if workflow.is_default() {
+            if existing.is_some() {
+                ClassSubmissionWorkflows::delete_by_id(class_id)
+                    .exec(&txn)
+                    .await
+                    .map_err(|e| {
+                        HWSystemError::database_operation(format!("删除提交工作流失败: {e}"))
+                    })?;
+            }
+        } else {
+            let statuses = serde_json::to_string(&workflow.statuses)
+                .map_err(|e| HWSystemError::serialization(format!("序列化工作流状态失败: {e}")))?;
+            let transitions = serde_json::to_string(&workflow.transitions)
+                .map_err(|e| HWSystemError::serialization(format!("序列化工作流转换失败: {e}")))?;
+
+            match existing {
+                Some(model) => {
+                    let mut active: ActiveModel = model.into();
+                    active.statuses = Set(statuses);
+                    active.transitions = Set(transitions);
+                    active.updated_by = Set(updated_by);
+                    active.updated_at = Set(now);
+                    active.update(&txn).await
+                }
+                None => {
+                    ActiveModel {
+                        class_id: Set(class_id),
+                        statuses: Set(statuses),
+                        transitions: Set(transitions),
+                        updated_by: Set(updated_by),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                    }
+                    .insert(&txn)
+                    .await
+                }
+            }
+            .map_err(|e| HWSystemError::database_operation(format!("保存提交工作流失败: {e}")))?;
+        }
+
+        // 清除已删除的自定义状态
+        let mut stale = Condition::all().add(SubmissionColumn::WorkflowStatus.is_not_null());
+        if !keys.is_empty() {
+            stale = stale.add(SubmissionColumn::WorkflowStatus.is_not_in(keys));
+        }
+        Submissions::update_many()
+            .col_expr(
+                SubmissionColumn::WorkflowStatus,
+                Expr::value(Option::<String>::None),
+            )
+            .filter(stale)
+            .filter(
+                SubmissionColumn::HomeworkId.in_subquery(
+                    Query::select()
+                        .column(HomeworkColumn::Id)
+                        .from(Homeworks)
+                        .and_where(HomeworkColumn::ClassId.eq(class_id))
+                        .to_owned(),
+                ),
+            )
+            .exec(&txn)
+            .await
+            .map_err(|e| {
+                HWSystemError::database_operation(format!("清除失效的提交状态失败: {e}"))
+            })?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(())
+    }
+
+    /// 将提交从 `from` 状态转换为自定义状态 `to`
+    ///
+    /// 仅当提交当前状态仍为 `from` 时更新，并发转换时后到的请求返回 false。
+    pub async fn transition_submission_status_impl(
+        &self,
+        submission_id: i64,
+        from: &str,
+        to: &str,
+    ) -> Result<bool> {
+        let current = if SubmissionWorkflow::BUILTIN_STATUSES.contains(&from) {
+            Condition::all()
+                .add(SubmissionColumn::WorkflowStatus.is_null())
+                .add(SubmissionColumn::Status.eq(from))
+        } else {
+            Condition::all().add(SubmissionColumn::WorkflowStatus.eq(from))
+        };
+
+        let result = Submissions::update_many()
+            .col_expr(
+                SubmissionColumn::WorkflowStatus,
+                Expr::value(Some(to.to_string())),
+            )
+            .filter(SubmissionColumn::Id.eq(submission_id))
+            .filter(current)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新提交状态失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -1,23 +1,43 @@
 //! 提交存储操作
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::SeaOrmStorage;
-use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use super::batch::insert_chunked;
+use super::homework_counters::{submitter_state, track_submitter_change};
+use super::organizations::tenant_homework_ids;
+use super::outbox::insert_outbox_events;
+use super::resubmissions::{fulfill_resubmission_requests, is_reopened};
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::grade_drafts::{Column as GradeDraftColumn, Entity as GradeDrafts};
+use crate::entity::grades::{
+    ActiveModel as GradeActiveModel, Column as GradeColumn, Entity as Grades,
+};
 use crate::entity::homeworks::Entity as Homeworks;
 use crate::entity::submission_files::{
     ActiveModel as SubmissionFileActiveModel, Column as SubmissionFileColumn,
     Entity as SubmissionFiles,
 };
+use crate::entity::submission_references::{
+    ActiveModel as SubmissionReferenceActiveModel, Entity as SubmissionReferences,
+};
+use crate::entity::submission_self_assessments::ActiveModel as SelfAssessmentActiveModel;
 use crate::entity::submissions::{ActiveModel, Column, Entity as Submissions};
 use crate::entity::users::{Column as UserColumn, Entity as Users};
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     PaginationInfo,
+    common::PaginationPolicy,
     files::responses::FileInfo,
+    notifications::entities::{NotificationType, ReferenceType},
+    organizations::entities::TenantScope,
+    outbox::entities::OutboxEvent,
     submissions::{
-        entities::{Submission, SubmissionStatus},
-        requests::{CreateSubmissionRequest, SubmissionListQuery},
+        entities::{Submission, SubmissionContentMetrics, SubmissionStatus, SubmissionWorkflow},
+        requests::{
+            ArchivedSubmissionInput, CreateSubmissionRequest, SubmissionListQuery,
+            SubmissionReferenceSnapshot, SubmissionSummaryFilter,
+        },
         responses::{
             LatestSubmissionInfo, SubmissionCreator, SubmissionGradeInfo, SubmissionHomeworkInfo,
             SubmissionListItem, SubmissionListResponse, SubmissionResponse, SubmissionSummaryItem,
@@ -26,8 +46,8 @@ use crate::models::{
     },
 };
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, EntityTrait, ExprTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait, sea_query::Expr,
 };
 
 impl SeaOrmStorage {
@@ -36,6 +56,8 @@ impl SeaOrmStorage {
         &self,
         creator_id: i64,
         req: CreateSubmissionRequest,
+        references: Vec<SubmissionReferenceSnapshot>,
+        default_grace_minutes: i64,
     ) -> Result<Submission> {
         let now = chrono::Utc::now().timestamp();
 
@@ -53,18 +75,26 @@ impl SeaOrmStorage {
             .unwrap_or(0);
 
         let version = max_version + 1;
+        let self_assessment = req.self_assessment;
 
-        // 检查是否迟交
+        // 检查是否迟交（分题设置了截止时间时以分题为准）
         let homework = self.get_homework_by_id_impl(req.homework_id).await?;
-        let is_late = if let Some(hw) = homework {
-            if let Some(deadline) = hw.deadline {
-                chrono::Utc::now() > deadline
-            } else {
-                false
-            }
-        } else {
-            false
+        let part = match req.part_id {
+            Some(part_id) => self.get_homework_part_impl(part_id).await?,
+            None => None,
+        };
+        let deadline = match (&homework, &part) {
+            (Some(hw), Some(part)) => part.effective_deadline(hw),
+            (Some(hw), None) => hw.deadline,
+            _ => None,
         };
+        // 截止后的宽限期内不计迟交；教师退回后，在重新开放期限内重交不计迟交
+        let is_late = homework.as_ref().is_some_and(|hw| {
+            hw.late_policy(default_grace_minutes)
+                .with_deadline(deadline)
+                .is_late(chrono::Utc::now())
+        }) && !is_reopened(&self.db, req.homework_id, creator_id, req.part_id, now)
+            .await?;
 
         let status = if is_late {
             SubmissionStatus::Late.to_string()
@@ -72,28 +102,216 @@ impl SeaOrmStorage {
             SubmissionStatus::Pending.to_string()
         };
 
+        // 事务外先解析附件令牌并校验所有权（同一文件只关联一次）
+        let mut seen = std::collections::HashSet::new();
+        let tokens: Vec<String> = req
+            .attachments
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|token| seen.insert(token.clone()))
+            .collect();
+        let file_ids = self.resolve_owned_files_impl(&tokens, creator_id).await?;
+
+        let metrics = SubmissionContentMetrics::from_content(&req.content);
         let model = ActiveModel {
             homework_id: Set(req.homework_id),
             creator_id: Set(creator_id),
+            part_id: Set(req.part_id),
             version: Set(version),
             content: Set(Some(req.content)),
             status: Set(status),
             is_late: Set(is_late),
             submitted_at: Set(now),
+            word_count: Set(metrics.word_count),
+            char_count: Set(metrics.char_count),
+            attachment_count: Set(tokens.len() as i32),
             ..Default::default()
         };
 
+        let student_name = Users::find_by_id(creator_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?
+            .map(|user| user.display_name.unwrap_or(user.username))
+            .unwrap_or_else(|| "学生".to_string());
+
+        // 提交、附件关联与教师通知的发件箱事件在同一事务中写入
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let before = submitter_state(&txn, req.homework_id, creator_id).await?;
         let result = model
-            .insert(&self.db)
+            .insert(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("创建提交失败: {e}")))?;
 
-        // 处理附件
-        if let Some(attachments) = req.attachments {
-            self.set_submission_files_impl(result.id, attachments, creator_id)
-                .await?;
+        let models = tokens.iter().map(|token| SubmissionFileActiveModel {
+            submission_id: Set(result.id),
+            file_id: Set(file_ids[token]),
+        });
+        insert_chunked!(SubmissionFiles, models, &txn, "附件关联");
+
+        let models =
+            references
+                .into_iter()
+                .enumerate()
+                .map(|(index, r)| SubmissionReferenceActiveModel {
+                    submission_id: Set(result.id),
+                    position: Set(index as i32 + 1),
+                    kind: Set(r.kind.to_string()),
+                    url: Set(r.url),
+                    commit_hash: Set(r.commit_hash),
+                    commit_message: Set(r.commit_message),
+                    committed_at: Set(r.committed_at),
+                    metadata_fetched_at: Set(r.metadata_fetched_at),
+                    created_at: Set(now),
+                    ..Default::default()
+                });
+        insert_chunked!(SubmissionReferences, models, &txn, "提交外部引用");
+
+        if let Some(assessment) = self_assessment {
+            SelfAssessmentActiveModel {
+                submission_id: Set(result.id),
+                homework_id: Set(result.homework_id),
+                user_id: Set(creator_id),
+                confidence: Set(assessment.confidence),
+                time_spent_minutes: Set(assessment.time_spent_minutes),
+                difficulties: Set(assessment
+                    .difficulties
+                    .map(|d| d.trim().to_string())
+                    .filter(|d| !d.is_empty())),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("保存提交自评失败: {e}")))?;
+        }
+
+        if !file_ids.is_empty() {
+            Files::update_many()
+                .col_expr(
+                    FileColumn::CitationCount,
+                    Expr::col(FileColumn::CitationCount).add(1),
+                )
+                .filter(FileColumn::Id.is_in(file_ids.values().copied()))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("增加文件引用计数失败: {e}"))
+                })?;
+        }
+
+        fulfill_resubmission_requests(&txn, result.homework_id, creator_id, result.part_id, now)
+            .await?;
+        track_submitter_change(&txn, result.homework_id, creator_id, before).await?;
+
+        // 通知教师（作业创建者）
+        if let Some(homework) = homework {
+            insert_outbox_events(
+                &txn,
+                vec![OutboxEvent::Notification {
+                    user_ids: vec![homework.created_by],
+                    notification_type: NotificationType::SubmissionReceived,
+                    vars: vec![
+                        ("homework_title".to_string(), homework.title),
+                        ("student_name".to_string(), student_name),
+                    ],
+                    reference_type: Some(ReferenceType::Submission),
+                    reference_id: Some(result.id),
+                }],
+            )
+            .await?;
         }
 
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(result.into_submission())
+    }
+
+    /// 写入从班级导出包恢复的提交
+    ///
+    /// 保留原版本号、状态、迟交标记与提交时间，不计算迟交、不发送通知；
+    /// 附评分时一并写入，提交状态以归档数据为准。
+    pub async fn restore_archived_submission_impl(
+        &self,
+        input: ArchivedSubmissionInput,
+    ) -> Result<Submission> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let before = submitter_state(&txn, input.homework_id, input.creator_id).await?;
+        let metrics = SubmissionContentMetrics::from_content(&input.content);
+        let result = ActiveModel {
+            homework_id: Set(input.homework_id),
+            creator_id: Set(input.creator_id),
+            part_id: Set(input.part_id),
+            version: Set(input.version),
+            content: Set(Some(input.content)),
+            status: Set(input.status),
+            is_late: Set(input.is_late),
+            submitted_at: Set(input.submitted_at),
+            word_count: Set(metrics.word_count),
+            char_count: Set(metrics.char_count),
+            attachment_count: Set(input.file_ids.len() as i32),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("创建提交失败: {e}")))?;
+
+        let models = input
+            .file_ids
+            .iter()
+            .map(|file_id| SubmissionFileActiveModel {
+                submission_id: Set(result.id),
+                file_id: Set(*file_id),
+            });
+        insert_chunked!(SubmissionFiles, models, &txn, "附件关联");
+
+        if !input.file_ids.is_empty() {
+            Files::update_many()
+                .col_expr(
+                    FileColumn::CitationCount,
+                    Expr::col(FileColumn::CitationCount).add(1),
+                )
+                .filter(FileColumn::Id.is_in(input.file_ids.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("增加文件引用计数失败: {e}"))
+                })?;
+        }
+
+        if let Some((grader_id, score, comment, graded_at)) = input.grade {
+            GradeActiveModel {
+                submission_id: Set(result.id),
+                grader_id: Set(grader_id),
+                score: Set(score),
+                comment: Set(comment),
+                graded_at: Set(graded_at),
+                updated_at: Set(graded_at),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建评分失败: {e}")))?;
+        }
+
+        track_submitter_change(&txn, result.homework_id, result.creator_id, before).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
         Ok(result.into_submission())
     }
 
@@ -127,111 +345,50 @@ impl SeaOrmStorage {
         Ok(result.map(|m| m.into_submission()))
     }
 
-    /// 获取学生某作业的提交历史（包含评分和附件）
-    pub async fn list_user_submissions_impl(
-        &self,
-        homework_id: i64,
-        creator_id: i64,
-    ) -> Result<Vec<UserSubmissionHistoryItem>> {
-        let submissions = Submissions::find()
+    /// 作业是否已全部批改：至少有一份提交，且每位学生的最新提交都已评分（退回待重交的不算）
+    pub async fn is_homework_fully_graded_impl(&self, homework_id: i64) -> Result<bool> {
+        let rows: Vec<(i64, i64, String)> = Submissions::find()
+            .select_only()
+            .column(Column::Id)
+            .column(Column::CreatorId)
+            .column(Column::Status)
             .filter(Column::HomeworkId.eq(homework_id))
-            .filter(Column::CreatorId.eq(creator_id))
             .order_by_desc(Column::Version)
+            .into_tuple()
             .all(&self.db)
             .await
-            .map_err(|e| HWSystemError::database_operation(format!("查询提交历史失败: {e}")))?;
-
-        if submissions.is_empty() {
-            return Ok(vec![]);
-        }
-
-        // 批量查询评分
-        let submission_ids: Vec<i64> = submissions.iter().map(|s| s.id).collect();
-        let grades = Grades::find()
-            .filter(GradeColumn::SubmissionId.is_in(submission_ids.clone()))
-            .all(&self.db)
-            .await
-            .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
-        let grade_map: HashMap<i64, _> = grades.into_iter().map(|g| (g.submission_id, g)).collect();
-
-        // 批量查询附件关联
-        let sub_files = SubmissionFiles::find()
-            .filter(SubmissionFileColumn::SubmissionId.is_in(submission_ids))
-            .all(&self.db)
-            .await
-            .map_err(|e| HWSystemError::database_operation(format!("查询提交附件失败: {e}")))?;
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交列表失败: {e}")))?;
 
-        // 按 submission_id 分组
-        let mut file_ids_map: HashMap<i64, Vec<i64>> = HashMap::new();
-        for sf in &sub_files {
-            file_ids_map
-                .entry(sf.submission_id)
-                .or_default()
-                .push(sf.file_id);
+        let mut latest: HashMap<i64, (i64, String)> = HashMap::new();
+        for (submission_id, creator_id, status) in rows {
+            latest.entry(creator_id).or_insert((submission_id, status));
         }
-
-        // 批量查询所有文件信息
-        let all_file_ids: Vec<i64> = sub_files.iter().map(|sf| sf.file_id).collect();
-        let mut file_map: HashMap<i64, FileInfo> = HashMap::new();
-        if !all_file_ids.is_empty() {
-            use crate::entity::files::{Column as FileColumn, Entity as Files};
-            let files = Files::find()
-                .filter(FileColumn::Id.is_in(all_file_ids))
-                .all(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("查询文件信息失败: {e}")))?;
-            for f in files {
-                file_map.insert(
-                    f.id,
-                    FileInfo {
-                        download_token: f.download_token,
-                        original_name: f.original_name,
-                        file_size: f.file_size,
-                        file_type: f.file_type,
-                    },
-                );
-            }
+        if latest.is_empty()
+            || latest
+                .values()
+                .any(|(_, status)| status == SubmissionStatus::RETURNED)
+        {
+            return Ok(false);
         }
 
-        // 组装结果
-        let items = submissions
-            .into_iter()
-            .map(|s| {
-                let grade = grade_map.get(&s.id).map(|g| SubmissionGradeInfo {
-                    id: g.id,
-                    score: g.score,
-                    comment: g.comment.clone(),
-                    graded_at: chrono::DateTime::from_timestamp(g.graded_at, 0)
-                        .map(|dt| dt.to_rfc3339())
-                        .unwrap_or_default(),
-                });
+        let latest_ids: Vec<i64> = latest.into_values().map(|(id, _)| id).collect();
+        let graded = Grades::find()
+            .filter(GradeColumn::SubmissionId.is_in(latest_ids.clone()))
+            .count(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分信息失败: {e}")))?;
 
-                let attachments = file_ids_map
-                    .get(&s.id)
-                    .map(|fids| {
-                        fids.iter()
-                            .filter_map(|fid| file_map.get(fid).cloned())
-                            .collect()
-                    })
-                    .unwrap_or_default();
-
-                UserSubmissionHistoryItem {
-                    id: s.id,
-                    homework_id: s.homework_id,
-                    version: s.version,
-                    content: s.content,
-                    status: s.status,
-                    is_late: s.is_late,
-                    submitted_at: chrono::DateTime::from_timestamp(s.submitted_at, 0)
-                        .map(|dt| dt.to_rfc3339())
-                        .unwrap_or_default(),
-                    attachments,
-                    grade,
-                }
-            })
-            .collect();
+        Ok(graded as usize == latest_ids.len())
+    }
 
-        Ok(items)
+    /// 获取学生某作业的提交历史（包含评分和附件）
+    pub async fn list_user_submissions_impl(
+        &self,
+        homework_id: i64,
+        creator_id: i64,
+    ) -> Result<Vec<UserSubmissionHistoryItem>> {
+        self.list_user_submissions_for_teacher_impl(homework_id, creator_id, true)
+            .await
     }
 
     /// 列出提交（分页）
@@ -239,11 +396,17 @@ impl SeaOrmStorage {
         &self,
         query: SubmissionListQuery,
     ) -> Result<SubmissionListResponse> {
-        let page = query.page.unwrap_or(1).max(1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("submissions").resolve(query.page, query.size);
 
         let mut select = Submissions::find();
 
+        // 租户筛选（按作业所属班级的组织）
+        if query.tenant != TenantScope::All {
+            select =
+                select.filter(Column::HomeworkId.in_subquery(tenant_homework_ids(query.tenant)));
+        }
+
         // 作业筛选
         if let Some(homework_id) = query.homework_id {
             select = select.filter(Column::HomeworkId.eq(homework_id));
@@ -254,9 +417,15 @@ impl SeaOrmStorage {
             select = select.filter(Column::CreatorId.eq(creator_id));
         }
 
-        // 状态筛选
+        // 状态筛选：内置状态只匹配没有自定义状态的提交
         if let Some(ref status) = query.status {
-            select = select.filter(Column::Status.eq(status));
+            select = if SubmissionWorkflow::BUILTIN_STATUSES.contains(&status.as_str()) {
+                select
+                    .filter(Column::Status.eq(status))
+                    .filter(Column::WorkflowStatus.is_null())
+            } else {
+                select.filter(Column::WorkflowStatus.eq(status))
+            };
         }
 
         // 排序
@@ -286,40 +455,30 @@ impl SeaOrmStorage {
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
             .collect();
-
-        let users = Users::find()
-            .filter(UserColumn::Id.is_in(creator_ids))
-            .all(&self.db)
-            .await
-            .map_err(|e| HWSystemError::database_operation(format!("查询用户信息失败: {e}")))?;
-
-        let user_map: HashMap<i64, _> = users.into_iter().map(|u| (u.id, u)).collect();
+        let creator_map = self.submission_creator_map(&creator_ids).await?;
 
         // 组装 SubmissionListItem
         let items = submissions
             .into_iter()
-            .map(|s| {
-                let creator = user_map.get(&s.creator_id);
-                SubmissionListItem {
-                    id: s.id,
-                    homework_id: s.homework_id,
-                    creator_id: s.creator_id,
-                    creator: SubmissionCreator {
-                        id: creator.map(|u| u.id).unwrap_or(s.creator_id),
-                        username: creator
-                            .map(|u| u.username.clone())
-                            .unwrap_or_else(|| "未知用户".to_string()),
-                        display_name: creator.and_then(|u| u.display_name.clone()),
-                        avatar_url: creator.and_then(|u| u.avatar_url.clone()),
-                    },
-                    version: s.version,
-                    content: s.content,
-                    status: s.status,
-                    is_late: s.is_late,
-                    submitted_at: chrono::DateTime::from_timestamp(s.submitted_at, 0)
-                        .map(|dt| dt.to_rfc3339())
-                        .unwrap_or_default(),
-                }
+            .map(|s| SubmissionListItem {
+                id: s.id,
+                homework_id: s.homework_id,
+                creator_id: s.creator_id,
+                creator: creator_map
+                    .get(&s.creator_id)
+                    .cloned()
+                    .unwrap_or_else(|| SubmissionCreator::unknown(s.creator_id)),
+                part_id: s.part_id,
+                version: s.version,
+                content: s.content,
+                status: s.workflow_status.unwrap_or(s.status),
+                is_late: s.is_late,
+                submitted_at: chrono::DateTime::from_timestamp(s.submitted_at, 0)
+                    .map(|dt| dt.to_rfc3339())
+                    .unwrap_or_default(),
+                word_count: s.word_count,
+                char_count: s.char_count,
+                attachment_count: s.attachment_count,
             })
             .collect();
 
@@ -336,18 +495,39 @@ impl SeaOrmStorage {
 
     /// 删除提交（撤回）
     pub async fn delete_submission_impl(&self, submission_id: i64) -> Result<bool> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let Some(submission) = Submissions::find_by_id(submission_id)
+            .one(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?
+        else {
+            return Ok(false);
+        };
+        let before = submitter_state(&txn, submission.homework_id, submission.creator_id).await?;
+
         // 先删除附件关联
         SubmissionFiles::delete_many()
             .filter(SubmissionFileColumn::SubmissionId.eq(submission_id))
-            .exec(&self.db)
+            .exec(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("删除提交附件关联失败: {e}")))?;
 
         let result = Submissions::delete_by_id(submission_id)
-            .exec(&self.db)
+            .exec(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("删除提交失败: {e}")))?;
 
+        track_submitter_change(&txn, submission.homework_id, submission.creator_id, before).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
         Ok(result.rows_affected > 0)
     }
 
@@ -395,50 +575,51 @@ impl SeaOrmStorage {
             .await
             .map_err(|e| HWSystemError::database_operation(format!("删除旧附件关联失败: {e}")))?;
 
-        // 通过 token 查找文件并校验所有权
-        for token in tokens {
-            let file = self
-                .get_file_by_token_impl(&token)
-                .await?
-                .ok_or_else(|| HWSystemError::not_found(format!("文件不存在: {token}")))?;
-
-            // 校验文件所有权
-            if file.user_id != Some(user_id) {
-                return Err(HWSystemError::authorization(format!(
-                    "无权使用此文件: {token}"
-                )));
-            }
+        // 同一文件只关联一次
+        let mut seen = std::collections::HashSet::new();
+        let tokens: Vec<String> = tokens
+            .into_iter()
+            .filter(|token| seen.insert(token.clone()))
+            .collect();
+        Submissions::update_many()
+            .col_expr(Column::AttachmentCount, Expr::value(tokens.len() as i32))
+            .filter(Column::Id.eq(submission_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新附件数失败: {e}")))?;
+        if tokens.is_empty() {
+            return Ok(());
+        }
 
-            let model = SubmissionFileActiveModel {
-                submission_id: Set(submission_id),
-                file_id: Set(file.id),
-            };
+        // 通过 token 批量查找文件并校验所有权
+        let file_ids = self.resolve_owned_files_impl(&tokens, user_id).await?;
 
-            model
-                .insert(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("创建附件关联失败: {e}")))?;
+        let models = tokens.iter().map(|token| SubmissionFileActiveModel {
+            submission_id: Set(submission_id),
+            file_id: Set(file_ids[token]),
+        });
+        insert_chunked!(SubmissionFiles, models, &self.db, "附件关联");
 
-            // 增加文件引用计数
-            self.increment_file_citation_impl(file.id).await?;
-        }
+        // 增加文件引用计数
+        let ids: Vec<i64> = file_ids.into_values().collect();
+        self.increment_file_citations_impl(&ids).await?;
 
         Ok(())
     }
 
     /// 获取作业提交概览（按学生聚合）
     /// - `include_grades`: 是否包含成绩信息（课代表不可见成绩）
-    /// - `graded`: 筛选是否已批改，true=已批改，false=待批改，None=全部
+    /// - `filter`: 按是否已批改、是否超过批改时限筛选
     pub async fn get_submission_summary_impl(
         &self,
         homework_id: i64,
         page: i64,
         size: i64,
         include_grades: bool,
-        graded: Option<bool>,
+        filter: SubmissionSummaryFilter,
     ) -> Result<SubmissionSummaryResponse> {
-        let page = page.max(1) as u64;
-        let size = size.clamp(1, 100) as u64;
+        let (page, size) =
+            PaginationPolicy::for_endpoint("submissions").resolve(Some(page), Some(size));
 
         // 1. 查询该作业所有提交（按 creator_id 和 version 倒序）
         let all_submissions = Submissions::find()
@@ -482,14 +663,22 @@ impl SeaOrmStorage {
             .map(|g| (g.submission_id, g))
             .collect();
 
-        // 4. 根据 graded 参数筛选
+        // 4. 根据 graded / overdue 参数筛选
+        let is_overdue = |sub: &crate::entity::submissions::Model| {
+            !grade_map.contains_key(&sub.id)
+                && sub.status != SubmissionStatus::RETURNED
+                && sub.submitted_at < filter.overdue_before
+        };
         let mut user_data: Vec<_> = user_latest.into_iter().collect();
-        if let Some(is_graded) = graded {
+        if let Some(is_graded) = filter.graded {
             user_data.retain(|(_, (sub, _))| {
                 let has_grade = grade_map.contains_key(&sub.id);
                 has_grade == is_graded
             });
         }
+        if let Some(overdue) = filter.overdue {
+            user_data.retain(|(_, (sub, _))| is_overdue(sub) == overdue);
+        }
 
         // 5. 分页
         let total = user_data.len() as u64;
@@ -514,6 +703,38 @@ impl SeaOrmStorage {
             .map_err(|e| HWSystemError::database_operation(format!("查询用户信息失败: {e}")))?;
         let user_map: HashMap<i64, _> = users.into_iter().map(|u| (u.id, u)).collect();
 
+        // 班级内姓名代替全局显示名
+        let class_id: Option<i64> = Homeworks::find_by_id(homework_id)
+            .select_only()
+            .column(crate::entity::homeworks::Column::ClassId)
+            .into_tuple()
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询作业失败: {e}")))?;
+        let profile_names = match class_id {
+            Some(class_id) => self.list_profile_names_impl(class_id, &creator_ids).await?,
+            None => HashMap::new(),
+        };
+
+        // 查询当前评分人已保存草稿的提交
+        let drafted: HashSet<i64> = match filter.draft_grader_id {
+            Some(grader_id) => GradeDrafts::find()
+                .select_only()
+                .column(GradeDraftColumn::SubmissionId)
+                .filter(GradeDraftColumn::GraderId.eq(grader_id))
+                .filter(
+                    GradeDraftColumn::SubmissionId
+                        .is_in(paged_data.iter().map(|(_, (sub, _))| sub.id)),
+                )
+                .into_tuple::<i64>()
+                .all(&self.db)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("查询评分草稿失败: {e}")))?
+                .into_iter()
+                .collect(),
+            None => HashSet::new(),
+        };
+
         // 7. 组装结果（grade_map 已在步骤 3 中查询）
         let items = paged_data
             .into_iter()
@@ -521,14 +742,7 @@ impl SeaOrmStorage {
                 let user = user_map.get(&creator_id);
                 // 根据 include_grades 参数决定是否返回成绩
                 let grade = if include_grades {
-                    grade_map.get(&sub.id).map(|g| SubmissionGradeInfo {
-                        id: g.id,
-                        score: g.score,
-                        comment: g.comment.clone(),
-                        graded_at: chrono::DateTime::from_timestamp(g.graded_at, 0)
-                            .map(|dt| dt.to_rfc3339())
-                            .unwrap_or_default(),
-                    })
+                    grade_map.get(&sub.id).map(grade_info)
                 } else {
                     None
                 };
@@ -539,20 +753,31 @@ impl SeaOrmStorage {
                         username: user
                             .map(|u| u.username.clone())
                             .unwrap_or_else(|| "未知用户".to_string()),
-                        display_name: user.and_then(|u| u.display_name.clone()),
+                        display_name: profile_names
+                            .get(&creator_id)
+                            .cloned()
+                            .or_else(|| user.and_then(|u| u.display_name.clone())),
                         avatar_url: user.and_then(|u| u.avatar_url.clone()),
                     },
                     latest_submission: LatestSubmissionInfo {
                         id: sub.id,
                         version: sub.version,
-                        status: sub.status.clone(),
+                        status: sub
+                            .workflow_status
+                            .clone()
+                            .unwrap_or_else(|| sub.status.clone()),
                         is_late: sub.is_late,
                         submitted_at: chrono::DateTime::from_timestamp(sub.submitted_at, 0)
                             .map(|dt| dt.to_rfc3339())
                             .unwrap_or_default(),
+                        word_count: sub.word_count,
+                        char_count: sub.char_count,
+                        attachment_count: sub.attachment_count,
                     },
                     grade,
                     total_versions: version_count,
+                    grading_overdue: is_overdue(sub),
+                    has_draft: drafted.contains(&sub.id),
                 }
             })
             .collect();
@@ -588,103 +813,106 @@ impl SeaOrmStorage {
             return Ok(vec![]);
         }
 
-        // 批量查询评分（仅当 include_grades 为 true 时需要）
-        let grade_map: HashMap<i64, _> = if include_grades {
-            let submission_ids: Vec<i64> = submissions.iter().map(|s| s.id).collect();
-            let grades = Grades::find()
-                .filter(GradeColumn::SubmissionId.is_in(submission_ids))
-                .all(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
-            grades.into_iter().map(|g| (g.submission_id, g)).collect()
+        // 批量查询评分、自评、附件与外部引用（评分与自评仅当 include_grades 为 true 时需要）
+        let submission_ids: Vec<i64> = submissions.iter().map(|s| s.id).collect();
+        let (mut grade_map, mut assessment_map) = if include_grades {
+            (
+                self.submission_grade_map(&submission_ids).await?,
+                self.self_assessment_map_impl(&submission_ids).await?,
+            )
         } else {
-            HashMap::new()
+            (HashMap::new(), HashMap::new())
         };
+        let mut attachment_map = self.submission_attachment_map(&submission_ids).await?;
+        let mut reference_map = self.submission_reference_map_impl(&submission_ids).await?;
+
+        // 组装结果
+        let items = submissions
+            .into_iter()
+            .map(|s| UserSubmissionHistoryItem {
+                id: s.id,
+                homework_id: s.homework_id,
+                version: s.version,
+                content: s.content,
+                status: s.workflow_status.unwrap_or(s.status),
+                is_late: s.is_late,
+                submitted_at: chrono::DateTime::from_timestamp(s.submitted_at, 0)
+                    .map(|dt| dt.to_rfc3339())
+                    .unwrap_or_default(),
+                attachments: attachment_map.remove(&s.id).unwrap_or_default(),
+                references: reference_map.remove(&s.id).unwrap_or_default(),
+                grade: grade_map.remove(&s.id),
+                self_assessment: assessment_map.remove(&s.id),
+            })
+            .collect();
+
+        Ok(items)
+    }
+
+    /// 批量查询提交附件，按提交 ID 索引
+    async fn submission_attachment_map(
+        &self,
+        submission_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<FileInfo>>> {
+        if submission_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
 
-        // 批量查询附件关联
-        let submission_ids: Vec<i64> = submissions.iter().map(|s| s.id).collect();
         let sub_files = SubmissionFiles::find()
-            .filter(SubmissionFileColumn::SubmissionId.is_in(submission_ids))
+            .filter(SubmissionFileColumn::SubmissionId.is_in(submission_ids.iter().copied()))
             .all(&self.db)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("查询提交附件失败: {e}")))?;
 
-        // 按 submission_id 分组
-        let mut file_ids_map: HashMap<i64, Vec<i64>> = HashMap::new();
-        for sf in &sub_files {
-            file_ids_map
-                .entry(sf.submission_id)
-                .or_default()
-                .push(sf.file_id);
-        }
+        let file_ids: Vec<i64> = sub_files.iter().map(|sf| sf.file_id).collect();
+        let file_map: HashMap<i64, FileInfo> = self
+            .list_files_by_ids_impl(&file_ids)
+            .await?
+            .into_iter()
+            .map(|f| (f.id, f.into()))
+            .collect();
 
-        // 批量查询所有文件信息
-        let all_file_ids: Vec<i64> = sub_files.iter().map(|sf| sf.file_id).collect();
-        let mut file_map: HashMap<i64, FileInfo> = HashMap::new();
-        if !all_file_ids.is_empty() {
-            use crate::entity::files::{Column as FileColumn, Entity as Files};
-            let files = Files::find()
-                .filter(FileColumn::Id.is_in(all_file_ids))
-                .all(&self.db)
-                .await
-                .map_err(|e| HWSystemError::database_operation(format!("查询文件信息失败: {e}")))?;
-            for f in files {
-                file_map.insert(
-                    f.id,
-                    FileInfo {
-                        download_token: f.download_token,
-                        original_name: f.original_name,
-                        file_size: f.file_size,
-                        file_type: f.file_type,
-                    },
-                );
+        let mut map: HashMap<i64, Vec<FileInfo>> = HashMap::new();
+        for sf in sub_files {
+            if let Some(file) = file_map.get(&sf.file_id) {
+                map.entry(sf.submission_id).or_default().push(file.clone());
             }
         }
+        Ok(map)
+    }
 
-        // 组装结果
-        let items = submissions
-            .into_iter()
-            .map(|s| {
-                // 根据 include_grades 参数决定是否返回成绩
-                let grade = if include_grades {
-                    grade_map.get(&s.id).map(|g| SubmissionGradeInfo {
-                        id: g.id,
-                        score: g.score,
-                        comment: g.comment.clone(),
-                        graded_at: chrono::DateTime::from_timestamp(g.graded_at, 0)
-                            .map(|dt| dt.to_rfc3339())
-                            .unwrap_or_default(),
-                    })
-                } else {
-                    None
-                };
+    /// 批量查询提交评分，按提交 ID 索引
+    async fn submission_grade_map(
+        &self,
+        submission_ids: &[i64],
+    ) -> Result<HashMap<i64, SubmissionGradeInfo>> {
+        if submission_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
 
-                let attachments = file_ids_map
-                    .get(&s.id)
-                    .map(|fids| {
-                        fids.iter()
-                            .filter_map(|fid| file_map.get(fid).cloned())
-                            .collect()
-                    })
-                    .unwrap_or_default();
-
-                UserSubmissionHistoryItem {
-                    id: s.id,
-                    homework_id: s.homework_id,
-                    version: s.version,
-                    content: s.content,
-                    status: s.status,
-                    is_late: s.is_late,
-                    submitted_at: chrono::DateTime::from_timestamp(s.submitted_at, 0)
-                        .map(|dt| dt.to_rfc3339())
-                        .unwrap_or_default(),
-                    attachments,
-                    grade,
-                }
-            })
-            .collect();
+        let grades = Grades::find()
+            .filter(GradeColumn::SubmissionId.is_in(submission_ids.iter().copied()))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?;
 
-        Ok(items)
+        Ok(grades
+            .into_iter()
+            .map(|g| (g.submission_id, grade_info(&g)))
+            .collect())
+    }
+
+    /// 批量查询提交者，按用户 ID 索引
+    async fn submission_creator_map(
+        &self,
+        creator_ids: &[i64],
+    ) -> Result<HashMap<i64, SubmissionCreator>> {
+        Ok(self
+            .list_users_by_ids_impl(creator_ids)
+            .await?
+            .into_iter()
+            .map(|u| (u.id, u.into()))
+            .collect())
     }
 
     /// 获取提交详情（完整响应，包含 creator、attachments、grade）
@@ -703,49 +931,24 @@ impl SeaOrmStorage {
         };
 
         // 2. 查询用户信息
-        let user = Users::find_by_id(submission.creator_id)
-            .one(&self.db)
-            .await
-            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?;
-
-        let creator = SubmissionCreator {
-            id: submission.creator_id,
-            username: user
-                .as_ref()
-                .map(|u| u.username.clone())
-                .unwrap_or_else(|| "未知用户".to_string()),
-            display_name: user.as_ref().and_then(|u| u.display_name.clone()),
-            avatar_url: user.and_then(|u| u.avatar_url),
-        };
+        let creator = self
+            .submission_creator_map(&[submission.creator_id])
+            .await?
+            .remove(&submission.creator_id)
+            .unwrap_or_else(|| SubmissionCreator::unknown(submission.creator_id));
 
         // 3. 查询附件
-        let file_ids = self.get_submission_file_ids_impl(submission_id).await?;
-        let mut attachments = Vec::new();
-        for file_id in file_ids {
-            if let Some(file) = self.get_file_by_id_impl(file_id).await? {
-                attachments.push(FileInfo {
-                    download_token: file.download_token,
-                    original_name: file.original_name,
-                    file_size: file.file_size,
-                    file_type: file.file_type,
-                });
-            }
-        }
+        let attachments = self
+            .submission_attachment_map(&[submission_id])
+            .await?
+            .remove(&submission_id)
+            .unwrap_or_default();
 
         // 4. 查询评分
-        let grade = Grades::find()
-            .filter(GradeColumn::SubmissionId.eq(submission_id))
-            .one(&self.db)
-            .await
-            .map_err(|e| HWSystemError::database_operation(format!("查询评分失败: {e}")))?
-            .map(|g| SubmissionGradeInfo {
-                id: g.id,
-                score: g.score,
-                comment: g.comment,
-                graded_at: chrono::DateTime::from_timestamp(g.graded_at, 0)
-                    .map(|dt| dt.to_rfc3339())
-                    .unwrap_or_default(),
-            });
+        let grade = self
+            .submission_grade_map(&[submission_id])
+            .await?
+            .remove(&submission_id);
 
         // 5. 查询作业信息
         let homework = Homeworks::find_by_id(submission.homework_id)
@@ -761,14 +964,27 @@ impl SeaOrmStorage {
                 }),
             });
 
-        // 6. 组装响应
+        // 6. 查询自评
+        let self_assessment = self
+            .self_assessment_map_impl(&[submission_id])
+            .await?
+            .remove(&submission_id);
+
+        // 7. 查询外部引用
+        let references = self
+            .submission_reference_map_impl(&[submission_id])
+            .await?
+            .remove(&submission_id)
+            .unwrap_or_default();
+
+        // 8. 组装响应
         Ok(Some(SubmissionResponse {
             id: submission.id,
             homework_id: submission.homework_id,
             creator,
             content: submission.content.unwrap_or_default(),
             attachments,
-            status: submission.status,
+            status: submission.workflow_status.unwrap_or(submission.status),
             submitted_at: chrono::DateTime::from_timestamp(submission.submitted_at, 0)
                 .map(|dt| dt.to_rfc3339())
                 .unwrap_or_default(),
@@ -776,6 +992,20 @@ impl SeaOrmStorage {
             version: submission.version,
             is_late: submission.is_late,
             homework,
+            self_assessment,
+            references,
         }))
     }
 }
+
+/// 评分记录转换为提交中的评分信息
+fn grade_info(grade: &crate::entity::grades::Model) -> SubmissionGradeInfo {
+    SubmissionGradeInfo {
+        id: grade.id,
+        score: grade.score,
+        comment: grade.comment.clone(),
+        graded_at: chrono::DateTime::from_timestamp(grade.graded_at, 0)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_default(),
+    }
+}
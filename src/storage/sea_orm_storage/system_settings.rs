@@ -1,16 +1,21 @@
 //! 系统设置存储实现
 
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 
+use crate::cache::invalidation::CacheInvalidation;
 use crate::entity::prelude::{SystemSettings, SystemSettingsAudit};
+use crate::entity::{system_settings, system_settings_audit};
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     common::PaginationInfo,
+    common::PaginationPolicy,
     system::{
-        entities::SystemSetting, requests::SettingAuditQuery, responses::SettingAuditListResponse,
+        entities::{SettingChange, SettingsUpdateOutcome, SystemSetting},
+        requests::SettingAuditQuery,
+        responses::SettingAuditListResponse,
     },
 };
 
@@ -83,27 +88,113 @@ impl SeaOrmStorage {
             .insert(&self.db)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+        self.publish_invalidation(CacheInvalidation::Settings).await;
 
         Ok(updated.into_setting())
     }
 
+    /// 获取当前配置版本（最新一条审计日志的 ID，无变更记录时为 0）
+    pub(crate) async fn get_settings_version_impl(&self) -> Result<i64> {
+        settings_version(&self.db).await
+    }
+
     /// 批量更新设置
+    ///
+    /// 在同一事务中写入所有变更；值未变化的配置项不写入、不记审计日志。
     pub(crate) async fn batch_update_settings_impl(
         &self,
-        updates: Vec<(String, String)>,
+        changes: Vec<SettingChange>,
+        expected_version: Option<i64>,
         user_id: i64,
         ip_address: Option<String>,
-    ) -> Result<Vec<SystemSetting>> {
-        let mut results = Vec::new();
-
-        for (key, value) in updates {
-            let setting = self
-                .update_setting_impl(&key, &value, user_id, ip_address.clone())
-                .await?;
-            results.push(setting);
+    ) -> Result<SettingsUpdateOutcome> {
+        let now = chrono::Utc::now().timestamp();
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let current_version = settings_version(&txn).await?;
+        if let Some(expected) = expected_version
+            && expected != current_version
+        {
+            return Ok(SettingsUpdateOutcome::VersionConflict { current_version });
+        }
+
+        let mut changed = Vec::new();
+        let mut unchanged = Vec::new();
+
+        for change in changes {
+            let existing = SystemSettings::find_by_id(change.key.clone())
+                .one(&txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("获取设置失败: {e}")))?;
+
+            let (old_value, setting) = match existing {
+                Some(existing) if existing.value == change.value => {
+                    unchanged.push(change.key);
+                    continue;
+                }
+                Some(existing) => {
+                    let old_value = existing.value.clone();
+                    let mut active_model: system_settings::ActiveModel = existing.into();
+                    active_model.value = Set(change.value.clone());
+                    active_model.updated_at = Set(now);
+                    active_model.updated_by = Set(Some(user_id));
+                    let updated = active_model.update(&txn).await.map_err(|e| {
+                        HWSystemError::database_operation(format!("更新设置失败: {e}"))
+                    })?;
+                    (Some(old_value), updated)
+                }
+                None => {
+                    let created = system_settings::ActiveModel {
+                        key: Set(change.key.clone()),
+                        value: Set(change.value.clone()),
+                        value_type: Set(change.value_type.to_string()),
+                        description: Set(None),
+                        updated_at: Set(now),
+                        updated_by: Set(Some(user_id)),
+                    }
+                    .insert(&txn)
+                    .await
+                    .map_err(|e| HWSystemError::database_operation(format!("创建设置失败: {e}")))?;
+                    (None, created)
+                }
+            };
+
+            system_settings_audit::ActiveModel {
+                setting_key: Set(change.key),
+                old_value: Set(old_value),
+                new_value: Set(change.value),
+                changed_by: Set(user_id),
+                changed_at: Set(now),
+                ip_address: Set(ip_address.clone()),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+
+            changed.push(setting.into_setting());
         }
 
-        Ok(results)
+        let version = settings_version(&txn).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        if !changed.is_empty() {
+            self.publish_invalidation(CacheInvalidation::Settings).await;
+        }
+
+        Ok(SettingsUpdateOutcome::Applied {
+            changed,
+            unchanged,
+            version,
+        })
     }
 
     /// 获取审计日志
@@ -111,8 +202,9 @@ impl SeaOrmStorage {
         &self,
         query: SettingAuditQuery,
     ) -> Result<SettingAuditListResponse> {
-        let page = query.page.unwrap_or(1).max(1);
-        let size = query.size.unwrap_or(20).clamp(1, 100);
+        let (page, size) =
+            PaginationPolicy::for_endpoint("setting_audits").resolve(query.page, query.size);
+        let (page, size) = (page as i64, size as i64);
 
         let mut find = SystemSettingsAudit::find();
 
@@ -153,3 +245,15 @@ impl SeaOrmStorage {
         })
     }
 }
+
+/// 配置版本：最新一条审计日志的 ID
+async fn settings_version<C: ConnectionTrait>(db: &C) -> Result<i64> {
+    SystemSettingsAudit::find()
+        .select_only()
+        .column_as(system_settings_audit::Column::Id.max(), "max_id")
+        .into_tuple::<Option<i64>>()
+        .one(db)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询配置版本失败: {e}")))
+        .map(|v| v.flatten().unwrap_or(0))
+}
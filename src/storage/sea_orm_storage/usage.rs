@@ -0,0 +1,266 @@
+//! 用量统计存储操作
+
+use std::collections::BTreeMap;
+
+use super::SeaOrmStorage;
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::organizations::{Column as OrganizationColumn, Entity as Organizations};
+use crate::entity::usage_counters::{
+    ActiveModel as UsageCounterActiveModel, Column as UsageCounterColumn, Entity as UsageCounters,
+};
+use crate::entity::usage_reports::{
+    ActiveModel as UsageReportActiveModel, Column as UsageReportColumn, Entity as UsageReports,
+};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::{
+    PaginationInfo,
+    common::PaginationPolicy,
+    usage::{
+        entities::{DEFAULT_TENANT_ORG_ID, UsageCounter, UsageMetric, usage_org_id},
+        requests::UsageReportListQuery,
+        responses::UsageReportListResponse,
+    },
+};
+use sea_orm::{
+    ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::Expr,
+};
+
+/// 活跃用户统计窗口（天）
+const ACTIVE_USER_WINDOW_DAYS: i64 = 30;
+
+/// 单个组织的报表汇总
+#[derive(Default)]
+struct ReportRow {
+    total_users: i64,
+    active_users: i64,
+    storage_bytes: i64,
+    uploaded_bytes: i64,
+    submissions: i64,
+}
+
+impl SeaOrmStorage {
+    /// 累加用量计数器（不存在则创建）
+    pub async fn increment_usage_counter_impl(
+        &self,
+        org_id: i64,
+        period: &str,
+        metric: UsageMetric,
+        delta: i64,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        let update = || {
+            UsageCounters::update_many()
+                .col_expr(
+                    UsageCounterColumn::Value,
+                    Expr::col(UsageCounterColumn::Value).add(delta),
+                )
+                .col_expr(UsageCounterColumn::UpdatedAt, Expr::value(now))
+                .filter(UsageCounterColumn::OrgId.eq(org_id))
+                .filter(UsageCounterColumn::Period.eq(period))
+                .filter(UsageCounterColumn::Metric.eq(metric.to_string()))
+                .exec(&self.db)
+        };
+
+        let result = update()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("更新用量计数失败: {e}")))?;
+        if result.rows_affected > 0 {
+            return Ok(());
+        }
+
+        let model = UsageCounterActiveModel {
+            org_id: Set(org_id),
+            period: Set(period.to_string()),
+            metric: Set(metric.to_string()),
+            value: Set(delta),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        // 并发创建时唯一索引冲突，退回到累加
+        if UsageCounters::insert(model).exec(&self.db).await.is_err() {
+            update()
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("更新用量计数失败: {e}")))?;
+        }
+
+        Ok(())
+    }
+
+    /// 获取组织在某周期的用量计数
+    pub async fn list_usage_counters_impl(
+        &self,
+        org_id: i64,
+        period: &str,
+    ) -> Result<Vec<UsageCounter>> {
+        let results = UsageCounters::find()
+            .filter(UsageCounterColumn::OrgId.eq(org_id))
+            .filter(UsageCounterColumn::Period.eq(period))
+            .order_by_asc(UsageCounterColumn::Metric)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用量计数失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .filter_map(|m| m.into_usage_counter())
+            .collect())
+    }
+
+    /// 汇总生成指定日期的用量报表（每个组织一条，重复生成会覆盖）
+    pub async fn generate_usage_reports_impl(&self, report_date: chrono::NaiveDate) -> Result<i64> {
+        let date = report_date.format("%Y-%m-%d").to_string();
+        let period = report_date.format("%Y-%m").to_string();
+        let day_end = report_date
+            .succ_opt()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc().timestamp())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp());
+        let active_since = day_end - ACTIVE_USER_WINDOW_DAYS * 24 * 3600;
+
+        let mut rows: BTreeMap<i64, ReportRow> = BTreeMap::new();
+        rows.insert(DEFAULT_TENANT_ORG_ID, ReportRow::default());
+
+        // 所有组织（即使没有用量也生成报表）
+        let org_ids: Vec<i64> = Organizations::find()
+            .select_only()
+            .column(OrganizationColumn::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询组织失败: {e}")))?;
+        for org_id in org_ids {
+            rows.entry(org_id).or_default();
+        }
+
+        // 用户数与活跃用户数
+        let users: Vec<(Option<i64>, Option<i64>)> = Users::find()
+            .select_only()
+            .column(UserColumn::OrgId)
+            .column(UserColumn::LastLogin)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?;
+        for (org_id, last_login) in users {
+            let row = rows.entry(usage_org_id(org_id)).or_default();
+            row.total_users += 1;
+            if last_login.is_some_and(|ts| ts >= active_since && ts < day_end) {
+                row.active_users += 1;
+            }
+        }
+
+        // 已用存储空间（在应用层汇总，避免不同数据库 SUM 返回类型不一致）
+        let files: Vec<(Option<i64>, i64)> = Files::find()
+            .select_only()
+            .column(FileColumn::OrgId)
+            .column(FileColumn::FileSize)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询文件失败: {e}")))?;
+        for (org_id, file_size) in files {
+            rows.entry(usage_org_id(org_id)).or_default().storage_bytes += file_size;
+        }
+
+        // 当月累计计数
+        let counters = UsageCounters::find()
+            .filter(UsageCounterColumn::Period.eq(&period))
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用量计数失败: {e}")))?;
+        for counter in counters.into_iter().filter_map(|m| m.into_usage_counter()) {
+            let row = rows.entry(counter.org_id).or_default();
+            match counter.metric {
+                UsageMetric::UploadedBytes => row.uploaded_bytes = counter.value,
+                UsageMetric::Submissions => row.submissions = counter.value,
+            }
+        }
+
+        // 覆盖同日期的旧报表
+        UsageReports::delete_many()
+            .filter(UsageReportColumn::ReportDate.eq(&date))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除旧用量报表失败: {e}")))?;
+
+        let now = chrono::Utc::now().timestamp();
+        let generated = rows.len() as i64;
+        let models = rows
+            .into_iter()
+            .map(|(org_id, row)| UsageReportActiveModel {
+                org_id: Set(org_id),
+                report_date: Set(date.clone()),
+                total_users: Set(row.total_users),
+                active_users: Set(row.active_users),
+                storage_bytes: Set(row.storage_bytes),
+                uploaded_bytes: Set(row.uploaded_bytes),
+                submissions: Set(row.submissions),
+                created_at: Set(now),
+                ..Default::default()
+            });
+
+        UsageReports::insert_many(models)
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("写入用量报表失败: {e}")))?;
+
+        Ok(generated)
+    }
+
+    /// 分页查询用量报表
+    pub async fn list_usage_reports_impl(
+        &self,
+        query: UsageReportListQuery,
+    ) -> Result<UsageReportListResponse> {
+        let (page, size) =
+            PaginationPolicy::for_endpoint("usage_reports").resolve(query.page, query.size);
+
+        let mut select = UsageReports::find();
+
+        if let Some(org_id) = query.org_id {
+            select = select.filter(UsageReportColumn::OrgId.eq(org_id));
+        }
+
+        // 日期为 YYYY-MM-DD 格式，可直接按字符串比较
+        if let Some(ref from) = query.from {
+            select = select.filter(UsageReportColumn::ReportDate.gte(from.as_str()));
+        }
+        if let Some(ref to) = query.to {
+            select = select.filter(UsageReportColumn::ReportDate.lte(to.as_str()));
+        }
+
+        let paginator = select
+            .order_by_desc(UsageReportColumn::ReportDate)
+            .order_by_asc(UsageReportColumn::OrgId)
+            .paginate(&self.db, size);
+
+        let total = paginator
+            .num_items()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用量报表总数失败: {e}")))?;
+
+        let pages = paginator
+            .num_pages()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用量报表页数失败: {e}")))?;
+
+        let reports = paginator
+            .fetch_page(page - 1)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用量报表失败: {e}")))?;
+
+        Ok(UsageReportListResponse {
+            items: reports.into_iter().map(|m| m.into_usage_report()).collect(),
+            pagination: PaginationInfo {
+                page: page as i64,
+                page_size: size as i64,
+                total: total as i64,
+                total_pages: pages as i64,
+            },
+        })
+    }
+}
@@ -0,0 +1,73 @@
+//! 用户管理权限存储操作
+
+use super::SeaOrmStorage;
+use crate::cache::invalidation::CacheInvalidation;
+use crate::entity::user_admin_permissions::{ActiveModel, Column, Entity as UserAdminPermissions};
+use crate::errors::{HWSystemError, Result};
+use crate::models::users::entities::AdminPermission;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+impl SeaOrmStorage {
+    /// 获取用户被授予的管理权限
+    pub async fn get_user_admin_permissions_impl(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<AdminPermission>> {
+        let results = UserAdminPermissions::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户管理权限失败: {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .filter_map(|m| m.into_permission())
+            .collect())
+    }
+
+    /// 设置用户的管理权限（整体替换）
+    pub async fn set_user_admin_permissions_impl(
+        &self,
+        user_id: i64,
+        permissions: &[AdminPermission],
+        granted_by: i64,
+    ) -> Result<Vec<AdminPermission>> {
+        // 先删除旧的授权
+        UserAdminPermissions::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("删除用户管理权限失败: {e}")))?;
+
+        // 去重后批量写入
+        let mut unique: Vec<AdminPermission> = Vec::with_capacity(permissions.len());
+        for permission in permissions {
+            if !unique.contains(permission) {
+                unique.push(*permission);
+            }
+        }
+
+        if !unique.is_empty() {
+            let now = chrono::Utc::now().timestamp();
+            let models = unique.iter().map(|permission| ActiveModel {
+                user_id: Set(user_id),
+                permission: Set(permission.to_string()),
+                granted_by: Set(Some(granted_by)),
+                granted_at: Set(now),
+                ..Default::default()
+            });
+
+            UserAdminPermissions::insert_many(models)
+                .exec(&self.db)
+                .await
+                .map_err(|e| {
+                    HWSystemError::database_operation(format!("写入用户管理权限失败: {e}"))
+                })?;
+        }
+        self.publish_invalidation(CacheInvalidation::Permissions(user_id))
+            .await;
+
+        Ok(unique)
+    }
+}
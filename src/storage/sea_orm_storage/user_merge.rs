@@ -0,0 +1,258 @@
+//! 重复账号合并存储操作
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::SeaOrmStorage;
+use super::homework_counters::refresh_homework_counters;
+use crate::cache::invalidation::CacheInvalidation;
+use crate::entity::class_users::{
+    ActiveModel as ClassUserActiveModel, Column as ClassUserColumn, Entity as ClassUsers,
+};
+use crate::entity::classes::{Column as ClassColumn, Entity as Classes};
+use crate::entity::files::{Column as FileColumn, Entity as Files};
+use crate::entity::grades::{Column as GradeColumn, Entity as Grades};
+use crate::entity::notifications::{Column as NotificationColumn, Entity as Notifications};
+use crate::entity::prelude::UserAuditLogActiveModel;
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
+use crate::entity::users::{Column as UserColumn, Entity as Users};
+use crate::errors::{HWSystemError, Result};
+use crate::models::class_users::entities::ClassUserRole;
+use crate::models::users::entities::UserStatus;
+use crate::models::users::responses::MergeUsersResponse;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+
+/// 把两个账号在同一作业下的提交按提交时间（相同时按 ID）重新编号，全部归到目标账号
+///
+/// 版本号受 (homework_id, creator_id, version) 唯一约束，先写入负数临时版本再改为最终值。
+async fn renumber_submissions(
+    txn: &DatabaseTransaction,
+    homework_id: i64,
+    source_id: i64,
+    target_id: i64,
+) -> Result<u64> {
+    let submissions = Submissions::find()
+        .filter(SubmissionColumn::HomeworkId.eq(homework_id))
+        .filter(SubmissionColumn::CreatorId.is_in([source_id, target_id]))
+        .order_by_asc(SubmissionColumn::SubmittedAt)
+        .order_by_asc(SubmissionColumn::Id)
+        .all(txn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
+
+    let moved = submissions
+        .iter()
+        .filter(|s| s.creator_id == source_id)
+        .count() as u64;
+    for pass in [-1, 1] {
+        for (index, submission) in submissions.iter().enumerate() {
+            Submissions::update_many()
+                .col_expr(SubmissionColumn::CreatorId, Expr::value(target_id))
+                .col_expr(
+                    SubmissionColumn::Version,
+                    Expr::value(pass * (index as i32 + 1)),
+                )
+                .filter(SubmissionColumn::Id.eq(submission.id))
+                .exec(txn)
+                .await
+                .map_err(|e| HWSystemError::database_operation(format!("更新提交版本失败: {e}")))?;
+        }
+    }
+    Ok(moved)
+}
+
+impl SeaOrmStorage {
+    /// 合并重复账号
+    ///
+    /// 冲突按固定规则处理：同一班级的两条成员关系合并为一条，保留较高的班级角色、较早的加入时间，
+    /// 班级内姓名优先取目标账号；同一作业下两个账号都有提交时，按提交时间重新编号版本。
+    pub async fn merge_users_impl(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<MergeUsersResponse> {
+        let now = chrono::Utc::now().timestamp();
+        let mut result = MergeUsersResponse {
+            source_user_id: source_id,
+            target_user_id: target_id,
+            ..Default::default()
+        };
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        // 1. 班级成员关系
+        let memberships = ClassUsers::find()
+            .filter(ClassUserColumn::UserId.is_in([source_id, target_id]))
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询班级成员失败: {e}")))?;
+        let target_memberships: HashMap<i64, _> = memberships
+            .iter()
+            .filter(|m| m.user_id == target_id)
+            .map(|m| (m.class_id, m.clone()))
+            .collect();
+        for source in memberships.iter().filter(|m| m.user_id == source_id) {
+            result.class_ids.push(source.class_id);
+            match target_memberships.get(&source.class_id) {
+                Some(target) => {
+                    let rank = |role: &str| {
+                        role.parse::<ClassUserRole>()
+                            .map(|r| r.rank())
+                            .unwrap_or_default()
+                    };
+                    let mut active: ClassUserActiveModel = target.clone().into();
+                    if rank(&source.role) > rank(&target.role) {
+                        active.role = Set(source.role.clone());
+                    }
+                    active.joined_at = Set(target.joined_at.min(source.joined_at));
+                    active.profile_name =
+                        Set(target.profile_name.clone().or(source.profile_name.clone()));
+                    active.update(&txn).await.map_err(|e| {
+                        HWSystemError::database_operation(format!("合并班级成员失败: {e}"))
+                    })?;
+                    ClassUsers::delete_by_id(source.id)
+                        .exec(&txn)
+                        .await
+                        .map_err(|e| {
+                            HWSystemError::database_operation(format!("删除班级成员失败: {e}"))
+                        })?;
+                    result.merged_memberships += 1;
+                }
+                None => {
+                    ClassUsers::update_many()
+                        .col_expr(ClassUserColumn::UserId, Expr::value(target_id))
+                        .filter(ClassUserColumn::Id.eq(source.id))
+                        .exec(&txn)
+                        .await
+                        .map_err(|e| {
+                            HWSystemError::database_operation(format!("转移班级成员失败: {e}"))
+                        })?;
+                    result.class_memberships += 1;
+                }
+            }
+        }
+
+        result.owned_classes = Classes::update_many()
+            .col_expr(ClassColumn::TeacherId, Expr::value(target_id))
+            .filter(ClassColumn::TeacherId.eq(source_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("转移班级失败: {e}")))?
+            .rows_affected;
+
+        // 2. 提交：两个账号都提交过的作业重新编号，其余直接转移
+        let submissions = Submissions::find()
+            .filter(SubmissionColumn::CreatorId.is_in([source_id, target_id]))
+            .all(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询提交失败: {e}")))?;
+        let homeworks_of = |user_id: i64| -> BTreeSet<i64> {
+            submissions
+                .iter()
+                .filter(|s| s.creator_id == user_id)
+                .map(|s| s.homework_id)
+                .collect()
+        };
+        let shared: Vec<i64> = homeworks_of(source_id)
+            .intersection(&homeworks_of(target_id))
+            .copied()
+            .collect();
+        for &homework_id in &shared {
+            result.submissions +=
+                renumber_submissions(&txn, homework_id, source_id, target_id).await?;
+        }
+        result.renumbered_homeworks = shared.len() as u64;
+        result.submissions += Submissions::update_many()
+            .col_expr(SubmissionColumn::CreatorId, Expr::value(target_id))
+            .filter(SubmissionColumn::CreatorId.eq(source_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("转移提交失败: {e}")))?
+            .rows_affected;
+        // 两个账号都提交过的作业，合并后只计一名提交者
+        refresh_homework_counters(&txn, &shared).await?;
+
+        // 3. 评分、文件、通知
+        result.grades = Grades::update_many()
+            .col_expr(GradeColumn::GraderId, Expr::value(target_id))
+            .filter(GradeColumn::GraderId.eq(source_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("转移评分失败: {e}")))?
+            .rows_affected;
+        result.files = Files::update_many()
+            .col_expr(FileColumn::UserId, Expr::value(target_id))
+            .filter(FileColumn::UserId.eq(source_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("转移文件失败: {e}")))?
+            .rows_affected;
+        result.notifications = Notifications::update_many()
+            .col_expr(NotificationColumn::UserId, Expr::value(target_id))
+            .filter(NotificationColumn::UserId.eq(source_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("转移通知失败: {e}")))?
+            .rows_affected;
+
+        // 4. 停用源账号，保留记录以便追溯
+        Users::update_many()
+            .col_expr(
+                UserColumn::Status,
+                Expr::value(UserStatus::Suspended.to_string()),
+            )
+            .col_expr(UserColumn::UpdatedAt, Expr::value(now))
+            .filter(UserColumn::Id.eq(source_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("停用源账号失败: {e}")))?;
+
+        // 5. 两个账号各写一条审计日志
+        let detail = format!(
+            "source={source_id},target={target_id},class_memberships={},merged_memberships={},\
+             owned_classes={},submissions={},renumbered_homeworks={},grades={},files={},notifications={}",
+            result.class_memberships,
+            result.merged_memberships,
+            result.owned_classes,
+            result.submissions,
+            result.renumbered_homeworks,
+            result.grades,
+            result.files,
+            result.notifications,
+        );
+        for (user_id, action) in [(source_id, "merge_into"), (target_id, "merge_from")] {
+            UserAuditLogActiveModel {
+                user_id: Set(user_id),
+                action: Set(action.to_string()),
+                detail: Set(Some(detail.clone())),
+                operator_id: Set(operator_id),
+                ip_address: Set(ip_address.clone()),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        for user_id in [source_id, target_id] {
+            self.publish_invalidation(CacheInvalidation::User(user_id))
+                .await;
+        }
+
+        Ok(result)
+    }
+}
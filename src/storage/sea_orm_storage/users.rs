@@ -1,20 +1,45 @@
 use super::SeaOrmStorage;
+use super::batch::insert_chunked;
+use super::homework_counters::refresh_homework_counters;
+use super::organizations::tenant_condition;
+use crate::cache::invalidation::CacheInvalidation;
+use crate::entity::prelude::UserAuditLogActiveModel;
+use crate::entity::submissions::{Column as SubmissionColumn, Entity as Submissions};
 use crate::entity::users::{ActiveModel, Column, Entity as Users};
 use crate::errors::{HWSystemError, Result};
 use crate::models::{
     PaginationInfo,
+    common::PaginationPolicy,
+    organizations::entities::TenantScope,
     users::{
-        entities::{User, UserRole, UserStatus},
-        requests::{CreateUserRequest, UpdateUserRequest, UserListQuery},
-        responses::{UserListResponse, UserStatsResponse},
+        entities::{BulkUserAction, User, UserRole, UserStatus},
+        requests::{BulkUserFilter, CreateUserRequest, UpdateUserRequest, UserListQuery},
+        responses::{BulkUserItemResult, UserListResponse, UserStatsResponse},
     },
 };
 use crate::utils::escape_like_pattern;
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, IdenStatic,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 
+/// 用户提交过的作业 ID（删除用户前查询，提交会随用户级联删除）
+async fn submitted_homework_ids<C: ConnectionTrait>(
+    conn: &C,
+    user_ids: &[i64],
+) -> Result<Vec<i64>> {
+    Submissions::find()
+        .select_only()
+        .column(SubmissionColumn::HomeworkId)
+        .distinct()
+        .filter(SubmissionColumn::CreatorId.is_in(user_ids.iter().copied()))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(|e| HWSystemError::database_operation(format!("查询用户提交失败: {e}")))
+}
+
 impl SeaOrmStorage {
     /// 创建用户
     pub async fn create_user_impl(&self, req: CreateUserRequest) -> Result<User> {
@@ -28,6 +53,7 @@ impl SeaOrmStorage {
             status: Set(UserStatus::Active.to_string()),
             display_name: Set(req.display_name),
             avatar_url: Set(req.avatar_url),
+            org_id: Set(req.org_id),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -41,6 +67,43 @@ impl SeaOrmStorage {
         Ok(result.into_user())
     }
 
+    /// 批量创建用户（单事务，分块多行 INSERT），返回创建数量
+    ///
+    /// 任一行失败（如用户名冲突）时整体回滚。
+    pub async fn create_users_batch_impl(&self, reqs: Vec<CreateUserRequest>) -> Result<usize> {
+        if reqs.is_empty() {
+            return Ok(0);
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let count = reqs.len();
+        let models = reqs.into_iter().map(|req| ActiveModel {
+            username: Set(req.username),
+            email: Set(req.email),
+            password_hash: Set(req.password),
+            role: Set(req.role.to_string()),
+            status: Set(UserStatus::Active.to_string()),
+            display_name: Set(req.display_name),
+            avatar_url: Set(req.avatar_url),
+            org_id: Set(req.org_id),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        });
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+        insert_chunked!(Users, models, &txn, "用户");
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        Ok(count)
+    }
+
     /// 通过 ID 获取用户
     pub async fn get_user_by_id_impl(&self, id: i64) -> Result<Option<User>> {
         let result = Users::find_by_id(id)
@@ -96,10 +159,9 @@ impl SeaOrmStorage {
         &self,
         query: UserListQuery,
     ) -> Result<UserListResponse> {
-        let page = query.page.unwrap_or(1).max(1) as u64;
-        let size = query.size.unwrap_or(20).clamp(1, 100) as u64;
+        let (page, size) = PaginationPolicy::for_endpoint("users").resolve(query.page, query.size);
 
-        let mut select = Users::find();
+        let mut select = Users::find().filter(tenant_condition(Column::OrgId, query.tenant));
 
         // 搜索条件
         if let Some(ref search) = query.search
@@ -217,16 +279,35 @@ impl SeaOrmStorage {
             .update(&self.db)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("更新用户失败: {e}")))?;
+        self.publish_invalidation(CacheInvalidation::User(id)).await;
 
         self.get_user_by_id_impl(id).await
     }
 
     /// 删除用户
+    ///
+    /// 用户的提交随外键级联删除，同一事务内重算其提交过的作业的统计计数。
     pub async fn delete_user_impl(&self, id: i64) -> Result<bool> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let homework_ids = submitted_homework_ids(&txn, &[id]).await?;
+
         let result = Users::delete_by_id(id)
-            .exec(&self.db)
+            .exec(&txn)
             .await
             .map_err(|e| HWSystemError::database_operation(format!("删除用户失败: {e}")))?;
+        refresh_homework_counters(&txn, &homework_ids).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+        if result.rows_affected > 0 {
+            self.publish_invalidation(CacheInvalidation::User(id)).await;
+        }
 
         Ok(result.rows_affected > 0)
     }
@@ -291,8 +372,9 @@ impl SeaOrmStorage {
         role: Option<UserRole>,
         status: Option<UserStatus>,
         search: Option<&str>,
+        tenant: TenantScope,
     ) -> Result<Vec<User>> {
-        let mut select = Users::find();
+        let mut select = Users::find().filter(tenant_condition(Column::OrgId, tenant));
 
         // 搜索条件
         if let Some(search) = search
@@ -416,4 +498,184 @@ impl SeaOrmStorage {
             server_time: now.to_rfc3339(),
         })
     }
+
+    /// 按 ID 批量获取用户
+    pub async fn list_users_by_ids_impl(&self, ids: &[i64]) -> Result<Vec<User>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let users = Users::find()
+            .filter(Column::Id.is_in(ids.iter().copied()))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?;
+
+        Ok(users.into_iter().map(|m| m.into_user()).collect())
+    }
+
+    /// 按条件筛选批量操作的目标用户
+    pub async fn list_users_for_bulk_impl(
+        &self,
+        filter: BulkUserFilter,
+        tenant: TenantScope,
+        limit: u64,
+    ) -> Result<Vec<User>> {
+        let mut select = Users::find().filter(tenant_condition(Column::OrgId, tenant));
+
+        // 搜索条件
+        if let Some(ref search) = filter.search
+            && !search.trim().is_empty()
+        {
+            let escaped = escape_like_pattern(search.trim());
+            select = select.filter(
+                Condition::any()
+                    .add(Column::Username.contains(&escaped))
+                    .add(Column::Email.contains(&escaped))
+                    .add(Column::DisplayName.contains(&escaped)),
+            );
+        }
+
+        // 角色筛选
+        if let Some(ref role) = filter.role {
+            select = select.filter(Column::Role.eq(role.to_string()));
+        }
+
+        // 状态筛选
+        if let Some(ref status) = filter.status {
+            select = select.filter(Column::Status.eq(status.to_string()));
+        }
+
+        // 最后登录时间筛选：从未登录的用户按创建时间判断
+        if let Some(before) = filter.last_login_before {
+            let ts = before.timestamp();
+            select = select.filter(
+                Condition::any().add(Column::LastLogin.lt(ts)).add(
+                    Condition::all()
+                        .add(Column::LastLogin.is_null())
+                        .add(Column::CreatedAt.lt(ts)),
+                ),
+            );
+        }
+
+        let users = select
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询用户失败: {e}")))?;
+
+        Ok(users.into_iter().map(|m| m.into_user()).collect())
+    }
+
+    /// 批量执行用户操作
+    ///
+    /// 全部操作与审计日志在同一事务中完成，任一数据库错误都会整体回滚；
+    /// 目标用户不存在时仅在对应结果项中标记失败，不影响其他用户。
+    pub async fn bulk_apply_user_action_impl(
+        &self,
+        action: BulkUserAction,
+        role: Option<UserRole>,
+        user_ids: &[i64],
+        operator_id: i64,
+        ip_address: Option<String>,
+    ) -> Result<Vec<BulkUserItemResult>> {
+        let now = chrono::Utc::now().timestamp();
+
+        // 需要更新的列及新值；删除操作为 None
+        let update_target = match action {
+            BulkUserAction::Disable => Some((Column::Status, UserStatus::Suspended.to_string())),
+            BulkUserAction::Enable => Some((Column::Status, UserStatus::Active.to_string())),
+            BulkUserAction::AssignRole => {
+                let role = role
+                    .as_ref()
+                    .ok_or_else(|| HWSystemError::validation("assign_role 操作必须指定目标角色"))?;
+                Some((Column::Role, role.to_string()))
+            }
+            BulkUserAction::Delete => None,
+        };
+        let detail = update_target
+            .as_ref()
+            .map(|(column, value)| format!("{}={value}", column.as_str()));
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("开启事务失败: {e}")))?;
+
+        let affected_homework_ids = match action {
+            BulkUserAction::Delete => submitted_homework_ids(&txn, user_ids).await?,
+            _ => Vec::new(),
+        };
+
+        let mut results = Vec::with_capacity(user_ids.len());
+        for &user_id in user_ids {
+            let rows_affected = match &update_target {
+                Some((column, value)) => {
+                    Users::update_many()
+                        .col_expr(*column, Expr::value(value.clone()))
+                        .col_expr(Column::UpdatedAt, Expr::value(now))
+                        .filter(Column::Id.eq(user_id))
+                        .exec(&txn)
+                        .await
+                        .map_err(|e| {
+                            HWSystemError::database_operation(format!("更新用户失败: {e}"))
+                        })?
+                        .rows_affected
+                }
+                None => {
+                    Users::delete_by_id(user_id)
+                        .exec(&txn)
+                        .await
+                        .map_err(|e| {
+                            HWSystemError::database_operation(format!("删除用户失败: {e}"))
+                        })?
+                        .rows_affected
+                }
+            };
+
+            if rows_affected == 0 {
+                results.push(BulkUserItemResult {
+                    user_id,
+                    success: false,
+                    message: Some("用户不存在".to_string()),
+                });
+                continue;
+            }
+
+            // 写入审计日志
+            UserAuditLogActiveModel {
+                user_id: Set(user_id),
+                action: Set(action.to_string()),
+                detail: Set(detail.clone()),
+                operator_id: Set(operator_id),
+                ip_address: Set(ip_address.clone()),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建审计日志失败: {e}")))?;
+
+            results.push(BulkUserItemResult {
+                user_id,
+                success: true,
+                message: None,
+            });
+        }
+        refresh_homework_counters(&txn, &affected_homework_ids).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("提交事务失败: {e}")))?;
+
+        for result in results.iter().filter(|r| r.success) {
+            self.publish_invalidation(CacheInvalidation::User(result.user_id))
+                .await;
+        }
+
+        Ok(results)
+    }
 }
@@ -0,0 +1,139 @@
+//! 家长/导师查看授权存储操作
+
+use super::SeaOrmStorage;
+use crate::entity::viewer_grants::{ActiveModel, Column, Entity as ViewerGrants};
+use crate::errors::{HWSystemError, Result};
+use crate::models::viewers::entities::ViewerGrant;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    sea_query::Expr,
+};
+
+impl SeaOrmStorage {
+    /// 创建查看邀请
+    pub async fn create_viewer_invitation_impl(
+        &self,
+        student_id: i64,
+        token: &str,
+        note: Option<String>,
+        expires_at: i64,
+    ) -> Result<ViewerGrant> {
+        let now = chrono::Utc::now().timestamp();
+
+        let model = ActiveModel {
+            student_id: Set(student_id),
+            viewer_id: Set(None),
+            token: Set(token.to_string()),
+            note: Set(note),
+            expires_at: Set(expires_at),
+            accepted_at: Set(None),
+            revoked_at: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        };
+
+        let result = model
+            .insert(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("创建查看邀请失败: {e}")))?;
+
+        Ok(result.into_viewer_grant())
+    }
+
+    /// 通过令牌获取查看授权
+    pub async fn get_viewer_grant_by_token_impl(&self, token: &str) -> Result<Option<ViewerGrant>> {
+        let result = ViewerGrants::find()
+            .filter(Column::Token.eq(token))
+            .one(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询查看授权失败: {e}")))?;
+
+        Ok(result.map(|m| m.into_viewer_grant()))
+    }
+
+    /// 接受查看邀请
+    ///
+    /// 条件更新保证同一邀请只能被接受一次。
+    pub async fn accept_viewer_invitation_impl(
+        &self,
+        token: &str,
+        viewer_id: i64,
+    ) -> Result<Option<ViewerGrant>> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = ViewerGrants::update_many()
+            .col_expr(Column::ViewerId, Expr::value(viewer_id))
+            .col_expr(Column::AcceptedAt, Expr::value(now))
+            .filter(Column::Token.eq(token))
+            .filter(Column::ViewerId.is_null())
+            .filter(Column::RevokedAt.is_null())
+            .filter(Column::ExpiresAt.gt(now))
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("接受查看邀请失败: {e}")))?;
+
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+        self.get_viewer_grant_by_token_impl(token).await
+    }
+
+    /// 列出学生发出的全部查看授权
+    pub async fn list_viewer_grants_by_student_impl(
+        &self,
+        student_id: i64,
+    ) -> Result<Vec<ViewerGrant>> {
+        let results = ViewerGrants::find()
+            .filter(Column::StudentId.eq(student_id))
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询查看授权失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_viewer_grant()).collect())
+    }
+
+    /// 列出家长/导师生效中的查看授权
+    pub async fn list_active_viewer_grants_impl(&self, viewer_id: i64) -> Result<Vec<ViewerGrant>> {
+        let results = ViewerGrants::find()
+            .filter(Column::ViewerId.eq(viewer_id))
+            .filter(Column::RevokedAt.is_null())
+            .order_by_asc(Column::AcceptedAt)
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询查看授权失败: {e}")))?;
+
+        Ok(results.into_iter().map(|m| m.into_viewer_grant()).collect())
+    }
+
+    /// 家长/导师是否有查看该学生的有效授权
+    pub async fn has_viewer_grant_impl(&self, viewer_id: i64, student_id: i64) -> Result<bool> {
+        let count = ViewerGrants::find()
+            .filter(Column::ViewerId.eq(viewer_id))
+            .filter(Column::StudentId.eq(student_id))
+            .filter(Column::RevokedAt.is_null())
+            .count(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("查询查看授权失败: {e}")))?;
+
+        Ok(count > 0)
+    }
+
+    /// 撤销查看授权（已撤销的授权返回 false）
+    pub async fn revoke_viewer_grant_impl(&self, student_id: i64, grant_id: i64) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = ViewerGrants::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(now))
+            .filter(Column::Id.eq(grant_id))
+            .filter(Column::StudentId.eq(student_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(&self.db)
+            .await
+            .map_err(|e| HWSystemError::database_operation(format!("撤销查看授权失败: {e}")))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
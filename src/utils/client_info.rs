@@ -0,0 +1,282 @@
+//! 客户端连接信息
+//!
+//! 只有当直连对端属于 `server.trusted_proxies` 时才采信 X-Forwarded-* / X-Real-IP
+//! 等转发头，否则一律使用 TCP 对端地址和请求自身的 Host，防止客户端伪造 IP 绕过限流
+//! 或污染审计日志。国家/地区代码同理，只采信可信代理（如 Cloudflare）写入的 CF-IPCountry 头。
+
+use std::net::IpAddr;
+
+use actix_web::HttpRequest;
+use actix_web::http::header::HOST;
+use once_cell::sync::Lazy;
+use tracing::warn;
+
+use crate::config::AppConfig;
+
+/// 通过 Unix 套接字连接时的对端标识，写入 `trusted_proxies` 表示信任本机反向代理
+const UNIX_SOCKET_PEER: &str = "unix";
+
+/// 可信代理列表（启动后首次使用时从配置解析）
+static TRUSTED_PROXIES: Lazy<TrustedProxies> =
+    Lazy::new(|| TrustedProxies::parse(&AppConfig::get().server.trusted_proxies));
+
+/// 网段（CIDR）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNet {
+    /// 解析 `10.0.0.0/8`、`::1/128` 或单个 IP
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (addr, prefix) = match value.split_once('/') {
+            Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse().ok()?)),
+            None => (value.parse::<IpAddr>().ok()?, None),
+        };
+        let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max_prefix);
+        (prefix <= max_prefix).then_some(Self { addr, prefix })
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, normalize_ip(*ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// IPv4 映射的 IPv6 地址（::ffff:a.b.c.d）按 IPv4 处理
+fn normalize_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(v6)),
+        v4 => v4,
+    }
+}
+
+/// 可信代理集合
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    nets: Vec<IpNet>,
+    trust_unix_socket: bool,
+}
+
+impl TrustedProxies {
+    /// 解析配置，无效项记录警告后忽略
+    pub fn parse(entries: &[String]) -> Self {
+        let mut proxies = Self::default();
+        for entry in entries {
+            if entry.trim().eq_ignore_ascii_case(UNIX_SOCKET_PEER) {
+                proxies.trust_unix_socket = true;
+            } else if let Some(net) = IpNet::parse(entry) {
+                proxies.nets.push(net);
+            } else {
+                warn!("忽略无效的可信代理配置: {}", entry);
+            }
+        }
+        proxies
+    }
+
+    /// 对端是否为可信代理，`None` 表示 Unix 套接字连接
+    pub fn is_trusted(&self, peer: Option<IpAddr>) -> bool {
+        match peer {
+            Some(ip) => self.nets.iter().any(|net| net.contains(&ip)),
+            None => self.trust_unix_socket,
+        }
+    }
+
+    /// 从 X-Forwarded-For 中取真实客户端 IP
+    ///
+    /// 从右向左跳过可信代理，第一个不可信的地址即为客户端；全部可信时取最左侧地址。
+    fn client_from_forwarded_for(&self, value: &str) -> Option<IpAddr> {
+        let hops: Vec<IpAddr> = value
+            .split(',')
+            .map(|ip| ip.trim().parse::<IpAddr>().ok())
+            .collect::<Option<_>>()?;
+        hops.iter()
+            .rev()
+            .find(|ip| !self.is_trusted(Some(**ip)))
+            .or(hops.first())
+            .copied()
+    }
+}
+
+/// 解析后的客户端连接信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    /// 客户端 IP，Unix 套接字直连且无可信转发头时为 `None`
+    pub ip: Option<IpAddr>,
+    /// 协议（http / https）
+    pub scheme: String,
+    /// 主机名（可含端口）
+    pub host: String,
+    /// 国家/地区代码（ISO 3166-1 alpha-2，大写），仅由可信代理提供
+    pub country: Option<String>,
+}
+
+impl ClientInfo {
+    /// 从请求中解析连接信息（使用全局可信代理配置）
+    pub fn from_request(req: &HttpRequest) -> Self {
+        Self::resolve(req, &TRUSTED_PROXIES)
+    }
+
+    /// 客户端 IP 的字符串形式，用于审计日志
+    pub fn ip_string(&self) -> Option<String> {
+        self.ip.map(|ip| ip.to_string())
+    }
+
+    fn resolve(req: &HttpRequest, proxies: &TrustedProxies) -> Self {
+        let peer = req.peer_addr().map(|addr| normalize_ip(addr.ip()));
+        let direct_scheme = if req.app_config().secure() {
+            "https"
+        } else {
+            "http"
+        };
+        let direct_host =
+            header_value(req, HOST.as_str()).unwrap_or_else(|| req.app_config().host().to_string());
+
+        if !proxies.is_trusted(peer) {
+            return Self {
+                ip: peer,
+                scheme: direct_scheme.to_string(),
+                host: direct_host,
+                country: None,
+            };
+        }
+
+        let ip = header_value(req, "X-Forwarded-For")
+            .and_then(|value| proxies.client_from_forwarded_for(&value))
+            .or_else(|| header_value(req, "X-Real-IP").and_then(|value| value.trim().parse().ok()))
+            .map(normalize_ip)
+            .or(peer);
+        let scheme = header_value(req, "X-Forwarded-Proto")
+            .and_then(|value| first_token(&value))
+            .map(|value| value.to_ascii_lowercase())
+            .filter(|value| value == "http" || value == "https")
+            .unwrap_or_else(|| direct_scheme.to_string());
+        let host = header_value(req, "X-Forwarded-Host")
+            .and_then(|value| first_token(&value))
+            .unwrap_or(direct_host);
+        let country = header_value(req, "CF-IPCountry").and_then(|value| country_code(&value));
+
+        Self {
+            ip,
+            scheme,
+            host,
+            country,
+        }
+    }
+}
+
+fn header_value(req: &HttpRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+/// 多级代理时头部可能为逗号分隔列表，取第一个（最靠近客户端）
+fn first_token(value: &str) -> Option<String> {
+    value
+        .split(',')
+        .next()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+}
+
+/// 校验国家代码，`XX`（未知）与 `T1`（Tor 出口）不视为有效国家
+fn country_code(value: &str) -> Option<String> {
+    let value = value.trim().to_ascii_uppercase();
+    (value.len() == 2 && value.bytes().all(|b| b.is_ascii_uppercase()) && value != "XX")
+        .then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn proxies() -> TrustedProxies {
+        TrustedProxies::parse(&["10.0.0.0/8".to_string(), "::1".to_string()])
+    }
+
+    #[test]
+    fn test_ip_net_contains() {
+        let net = IpNet::parse("10.0.0.0/8").unwrap();
+        assert!(net.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(net.contains(&"::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!net.contains(&"11.0.0.1".parse().unwrap()));
+        assert!(
+            IpNet::parse("0.0.0.0/0")
+                .unwrap()
+                .contains(&"8.8.8.8".parse().unwrap())
+        );
+        assert!(IpNet::parse("10.0.0.0/33").is_none());
+        assert!(IpNet::parse("proxy").is_none());
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_forwarded_headers() {
+        let req = TestRequest::default()
+            .peer_addr("203.0.113.5:4000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "1.2.3.4"))
+            .insert_header(("X-Forwarded-Proto", "https"))
+            .insert_header(("X-Forwarded-Host", "evil.example.com"))
+            .insert_header(("CF-IPCountry", "US"))
+            .insert_header((HOST, "api.example.com"))
+            .to_http_request();
+
+        let info = ClientInfo::resolve(&req, &proxies());
+        assert_eq!(info.ip, Some("203.0.113.5".parse().unwrap()));
+        assert_eq!(info.scheme, "http");
+        assert_eq!(info.host, "api.example.com");
+        assert_eq!(info.country, None);
+    }
+
+    #[test]
+    fn test_trusted_peer_uses_forwarded_headers() {
+        let req = TestRequest::default()
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "1.2.3.4, 198.51.100.7, 10.0.0.9"))
+            .insert_header(("X-Forwarded-Proto", "https"))
+            .insert_header(("X-Forwarded-Host", "hw.example.com"))
+            .insert_header(("CF-IPCountry", "cn"))
+            .to_http_request();
+
+        let info = ClientInfo::resolve(&req, &proxies());
+        // 198.51.100.7 是最后一个不可信的跳，左侧地址可能由客户端伪造
+        assert_eq!(info.ip, Some("198.51.100.7".parse().unwrap()));
+        assert_eq!(info.scheme, "https");
+        assert_eq!(info.host, "hw.example.com");
+        assert_eq!(info.country.as_deref(), Some("CN"));
+        assert_eq!(country_code("XX"), None);
+        assert_eq!(country_code("T1"), None);
+    }
+
+    #[test]
+    fn test_unix_socket_peer() {
+        let req = TestRequest::default()
+            .insert_header(("X-Real-IP", "1.2.3.4"))
+            .to_http_request();
+
+        assert_eq!(ClientInfo::resolve(&req, &proxies()).ip, None);
+        let unix = TrustedProxies::parse(&["unix".to_string()]);
+        assert_eq!(
+            ClientInfo::resolve(&req, &unix).ip,
+            Some("1.2.3.4".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! 代码文件语言识别
+//!
+//! 上传时按扩展名（或 `Makefile` 等固定文件名）识别代码语言，无扩展名时再根据 shebang 行识别。
+//! 返回的标识与 highlight.js / Prism 的语言名一致，前端可直接用于语法高亮。
+
+use std::path::Path;
+
+/// 扩展名（小写，不含点号）-> 语言
+const EXTENSION_LANGUAGES: &[(&str, &str)] = &[
+    ("c", "c"),
+    ("h", "c"),
+    ("cc", "cpp"),
+    ("cpp", "cpp"),
+    ("cxx", "cpp"),
+    ("hpp", "cpp"),
+    ("hh", "cpp"),
+    ("cs", "csharp"),
+    ("java", "java"),
+    ("kt", "kotlin"),
+    ("kts", "kotlin"),
+    ("scala", "scala"),
+    ("go", "go"),
+    ("rs", "rust"),
+    ("swift", "swift"),
+    ("py", "python"),
+    ("pyw", "python"),
+    ("rb", "ruby"),
+    ("php", "php"),
+    ("pl", "perl"),
+    ("lua", "lua"),
+    ("r", "r"),
+    ("m", "matlab"),
+    ("jl", "julia"),
+    ("hs", "haskell"),
+    ("ml", "ocaml"),
+    ("ex", "elixir"),
+    ("exs", "elixir"),
+    ("erl", "erlang"),
+    ("clj", "clojure"),
+    ("dart", "dart"),
+    ("js", "javascript"),
+    ("mjs", "javascript"),
+    ("cjs", "javascript"),
+    ("jsx", "jsx"),
+    ("ts", "typescript"),
+    ("tsx", "tsx"),
+    ("vue", "vue"),
+    ("html", "html"),
+    ("htm", "html"),
+    ("css", "css"),
+    ("scss", "scss"),
+    ("less", "less"),
+    ("xml", "xml"),
+    ("json", "json"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("toml", "toml"),
+    ("ini", "ini"),
+    ("md", "markdown"),
+    ("sql", "sql"),
+    ("sh", "bash"),
+    ("bash", "bash"),
+    ("zsh", "bash"),
+    ("ps1", "powershell"),
+    ("bat", "dos"),
+    ("asm", "x86asm"),
+    ("s", "x86asm"),
+    ("v", "verilog"),
+    ("sv", "verilog"),
+    ("vhd", "vhdl"),
+    ("tex", "latex"),
+    ("ipynb", "json"),
+];
+
+/// 没有扩展名的固定文件名 -> 语言
+const FILE_NAME_LANGUAGES: &[(&str, &str)] = &[
+    ("makefile", "makefile"),
+    ("gnumakefile", "makefile"),
+    ("dockerfile", "dockerfile"),
+    ("cmakelists.txt", "cmake"),
+];
+
+/// shebang 解释器 -> 语言
+const INTERPRETER_LANGUAGES: &[(&str, &str)] = &[
+    ("python", "python"),
+    ("bash", "bash"),
+    ("sh", "bash"),
+    ("zsh", "bash"),
+    ("node", "javascript"),
+    ("ruby", "ruby"),
+    ("perl", "perl"),
+    ("php", "php"),
+    ("lua", "lua"),
+    ("Rscript", "r"),
+];
+
+/// 扩展名（不含点号，不区分大小写）对应的语言
+pub fn language_for_extension(extension: &str) -> Option<&'static str> {
+    let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+    EXTENSION_LANGUAGES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, language)| *language)
+}
+
+/// 内容开头是否像文本（不含 NUL 字节）
+pub fn looks_like_text(head: &[u8]) -> bool {
+    !head.contains(&0)
+}
+
+/// 识别代码文件的语言
+///
+/// `head` 为文件开头的若干字节，用于排除二进制内容并识别 shebang；不是代码文件时返回 None。
+pub fn detect_code_language(file_name: &str, head: &[u8]) -> Option<&'static str> {
+    if !looks_like_text(head) {
+        return None;
+    }
+
+    let lower_name = file_name.to_ascii_lowercase();
+    if let Some((_, language)) = FILE_NAME_LANGUAGES
+        .iter()
+        .find(|(name, _)| *name == lower_name)
+    {
+        return Some(language);
+    }
+
+    match Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some(extension) => language_for_extension(extension),
+        None => shebang_language(head),
+    }
+}
+
+/// 根据 `#!/usr/bin/env python3`、`#!/bin/bash` 等 shebang 行识别语言
+fn shebang_language(head: &[u8]) -> Option<&'static str> {
+    let first_line = head.strip_prefix(b"#!")?.split(|b| *b == b'\n').next()?;
+    let first_line = std::str::from_utf8(first_line).ok()?;
+    let mut parts = first_line.split_whitespace();
+    let mut program = parts.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = parts.find(|arg| !arg.starts_with('-'))?;
+    }
+    // python3、python3.11 等带版本号的解释器
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETER_LANGUAGES
+        .iter()
+        .find(|(name, _)| *name == program)
+        .map(|(_, language)| *language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_by_extension() {
+        assert_eq!(
+            detect_code_language("main.RS", b"fn main() {}"),
+            Some("rust")
+        );
+        assert_eq!(detect_code_language("实验一.cpp", b""), Some("cpp"));
+        assert_eq!(
+            detect_code_language("Makefile", b"all:\n"),
+            Some("makefile")
+        );
+        assert_eq!(detect_code_language("notes.txt", b"hello"), None);
+        assert_eq!(detect_code_language("report.pdf", b"%PDF-1.4"), None);
+        // 扩展名是代码但内容是二进制
+        assert_eq!(detect_code_language("a.py", b"\x00\x01"), None);
+    }
+
+    #[test]
+    fn test_detect_by_shebang() {
+        assert_eq!(
+            detect_code_language("run", b"#!/usr/bin/env python3\nprint(1)"),
+            Some("python")
+        );
+        assert_eq!(
+            detect_code_language("build", b"#!/bin/bash\nset -e"),
+            Some("bash")
+        );
+        assert_eq!(
+            detect_code_language("tool", b"#!/usr/bin/env -S node --no-warnings\n"),
+            Some("javascript")
+        );
+        assert_eq!(detect_code_language("README", b"hello"), None);
+    }
+}
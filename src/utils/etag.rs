@@ -0,0 +1,15 @@
+use actix_web::HttpRequest;
+use actix_web::http::header::IF_NONE_MATCH;
+
+/// 请求的 `If-None-Match` 是否命中当前 ETag（忽略弱校验前缀 `W/`）
+pub fn if_none_match(req: &HttpRequest, etag: &str) -> bool {
+    req.headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == etag || tag == "*")
+        })
+}
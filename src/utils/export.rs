@@ -0,0 +1,258 @@
+//! 表格导出（CSV / XLSX，以及用于打印的 PDF）
+//!
+//! 业务层只需组装表头和行数据，由本模块统一生成文件和下载响应。
+
+use actix_web::HttpResponse;
+use rust_xlsxwriter::{Format, Workbook};
+use tracing::error;
+
+use crate::models::{ApiResponse, ErrorCode};
+use crate::utils::pdf::PdfDocument;
+
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    /// 解析格式参数，无法识别时回退为 CSV
+    pub fn parse(format: &str) -> Self {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "xlsx" => Self::Xlsx,
+            _ => Self::Csv,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => CSV_CONTENT_TYPE,
+            Self::Xlsx => XLSX_CONTENT_TYPE,
+        }
+    }
+}
+
+/// 单元格
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl From<String> for ExportCell {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for ExportCell {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<i64> for ExportCell {
+    fn from(value: i64) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<f64> for ExportCell {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl<T: Into<ExportCell>> From<Option<T>> for ExportCell {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Self::Empty)
+    }
+}
+
+impl ExportCell {
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(s) => s.clone(),
+            Self::Number(n) => n.to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+}
+
+/// 导出表格
+#[derive(Debug, Clone)]
+pub struct ExportTable {
+    /// 工作表名称（仅 XLSX 使用）
+    sheet_name: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<ExportCell>>,
+}
+
+impl ExportTable {
+    pub fn new(sheet_name: &str, headers: &[&str]) -> Self {
+        Self {
+            sheet_name: sheet_name.to_string(),
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<ExportCell>) {
+        self.rows.push(row);
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// 生成 CSV 数据
+    pub fn to_csv(&self) -> Result<Vec<u8>, String> {
+        let mut wtr = csv::Writer::from_writer(vec![]);
+        wtr.write_record(&self.headers)
+            .map_err(|e| format!("CSV 写入失败: {e}"))?;
+        for row in &self.rows {
+            wtr.write_record(row.iter().map(ExportCell::to_text))
+                .map_err(|e| format!("CSV 写入失败: {e}"))?;
+        }
+        wtr.into_inner().map_err(|e| format!("CSV 生成失败: {e}"))
+    }
+
+    /// 生成 XLSX 数据
+    pub fn to_xlsx(&self) -> Result<Vec<u8>, String> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet
+            .set_name(&self.sheet_name)
+            .map_err(|e| format!("XLSX 写入失败: {e}"))?;
+
+        let header_format = Format::new().set_bold();
+        for (col, header) in self.headers.iter().enumerate() {
+            worksheet
+                .write_string_with_format(0, col as u16, header, &header_format)
+                .map_err(|e| format!("XLSX 写入失败: {e}"))?;
+        }
+
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            let row_num = (row_idx + 1) as u32;
+            for (col, cell) in row.iter().enumerate() {
+                let col = col as u16;
+                let result = match cell {
+                    ExportCell::Text(s) => worksheet.write_string(row_num, col, s).map(|_| ()),
+                    ExportCell::Number(n) => worksheet.write_number(row_num, col, *n).map(|_| ()),
+                    ExportCell::Empty => Ok(()),
+                };
+                result.map_err(|e| format!("XLSX 写入失败: {e}"))?;
+            }
+        }
+
+        workbook
+            .save_to_buffer()
+            .map_err(|e| format!("XLSX 生成失败: {e}"))
+    }
+
+    /// 生成可打印的 PDF 数据：标题与说明行之后是带边框的表格
+    pub fn to_pdf(&self, title: &str, notes: &[String]) -> Vec<u8> {
+        let mut doc = PdfDocument::new(title);
+        doc.heading(title);
+        for note in notes {
+            doc.text(note);
+        }
+        doc.spacer();
+
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(ExportCell::to_text).collect())
+            .collect();
+        doc.table(&self.headers, &rows);
+        doc.finish()
+    }
+
+    /// 按格式生成文件数据
+    pub fn render(&self, format: ExportFormat) -> Result<Vec<u8>, String> {
+        match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Xlsx => self.to_xlsx(),
+        }
+    }
+
+    /// 生成文件下载响应，`file_stem` 为不含扩展名的文件名
+    pub fn into_response(self, format: ExportFormat, file_stem: &str) -> HttpResponse {
+        match self.render(format) {
+            Ok(data) => HttpResponse::Ok()
+                .content_type(format.content_type())
+                .insert_header((
+                    "Content-Disposition",
+                    format!(
+                        "attachment; filename=\"{file_stem}.{}\"",
+                        format.extension()
+                    ),
+                ))
+                .body(data),
+            Err(e) => {
+                error!("导出失败: {}", e);
+                HttpResponse::InternalServerError()
+                    .json(ApiResponse::<()>::error_empty(ErrorCode::ExportFailed, e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(ExportFormat::parse("xlsx"), ExportFormat::Xlsx);
+        assert_eq!(ExportFormat::parse("XLSX"), ExportFormat::Xlsx);
+        assert_eq!(ExportFormat::parse("csv"), ExportFormat::Csv);
+        assert_eq!(ExportFormat::parse("pdf"), ExportFormat::Csv);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut table = ExportTable::new("用户", &["id", "name", "score"]);
+        table.push_row(vec![1i64.into(), "张三".into(), 95.5.into()]);
+        table.push_row(vec![2i64.into(), "a,b".into(), None::<f64>.into()]);
+
+        let csv = String::from_utf8(table.to_csv().unwrap()).unwrap();
+        assert_eq!(csv, "id,name,score\n1,张三,95.5\n2,\"a,b\",\n");
+    }
+
+    #[test]
+    fn test_to_xlsx() {
+        let mut table = ExportTable::new("用户", &["id"]);
+        table.push_row(vec![1i64.into()]);
+
+        let data = table.to_xlsx().unwrap();
+        // XLSX 为 zip 格式
+        assert!(data.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn test_to_pdf() {
+        let mut table = ExportTable::new("名单", &["姓名", "签到"]);
+        table.push_row(vec!["张三".into(), ExportCell::Empty]);
+
+        let data = table.to_pdf("名单", &["人数：1".to_string()]);
+        assert!(data.starts_with(b"%PDF"));
+    }
+}
@@ -74,6 +74,9 @@ define_safe_i64_extractor!(SafeGradeIdI64, "grade_id");
 define_safe_i64_extractor!(SafeSubmissionIdI64, "submission_id");
 define_safe_i64_extractor!(SafeNotificationIdI64, "notification_id");
 
+define_safe_string_extractor!(SafeCertificateCode, "code");
 define_safe_string_extractor!(SafeClassCode, "code");
 define_safe_string_extractor!(SafeFileToken, "file_token");
+define_safe_string_extractor!(SafeJobId, "job_id");
 define_safe_string_extractor!(SafeSettingKey, "key");
+define_safe_string_extractor!(SafeShareToken, "token");
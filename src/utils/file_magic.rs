@@ -1,3 +1,5 @@
+use super::code_language::{language_for_extension, looks_like_text};
+
 /// 验证文件内容的魔术字节是否与扩展名匹配
 ///
 /// # Arguments
@@ -46,6 +48,9 @@ pub fn validate_magic_bytes(data: &[u8], extension: &str) -> bool {
         // 文本格式 - 不检查魔术字节
         ".txt" | ".md" | ".json" | ".xml" | ".html" | ".css" | ".js" | ".ts" | ".csv" => true,
 
+        // 源代码 - 只要求是文本内容
+        ext if language_for_extension(ext).is_some() => looks_like_text(data),
+
         // 未知格式 - 默认拒绝
         _ => false,
     }
@@ -86,6 +91,16 @@ mod tests {
         assert!(validate_magic_bytes(text_content, ".json"));
     }
 
+    #[test]
+    fn test_source_files() {
+        assert!(validate_magic_bytes(b"#include <stdio.h>", ".c"));
+        assert!(validate_magic_bytes(b"print('hi')", ".PY"));
+        assert!(!validate_magic_bytes(
+            &[0x7F, 0x45, 0x4C, 0x46, 0x00],
+            ".py"
+        ));
+    }
+
     #[test]
     fn test_empty_data() {
         assert!(!validate_magic_bytes(&[], ".png"));
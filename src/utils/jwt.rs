@@ -1,7 +1,29 @@
 use crate::config::AppConfig;
 use actix_web::cookie::{Cookie, SameSite};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::utils::password::constant_time_eq;
+use crate::utils::random_code::generate_random_code;
+
+/// Cookie 认证模式下保存访问令牌的 Cookie（HttpOnly）
+pub const ACCESS_TOKEN_COOKIE: &str = "access_token";
+/// CSRF 双提交令牌 Cookie（前端可读，写请求时回传到 [`CSRF_HEADER`]）
+pub const CSRF_COOKIE: &str = "csrf_token";
+/// CSRF 令牌请求头
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+/// CSRF 令牌长度
+const CSRF_TOKEN_LENGTH: usize = 32;
+
+// 令牌有效期，默认取配置文件，系统设置 `jwt.*` 变更时实时更新
+static ACCESS_TOKEN_EXPIRY: Lazy<AtomicI64> =
+    Lazy::new(|| AtomicI64::new(AppConfig::get().jwt.access_token_expiry));
+static REFRESH_TOKEN_EXPIRY: Lazy<AtomicI64> =
+    Lazy::new(|| AtomicI64::new(AppConfig::get().jwt.refresh_token_expiry));
+static REFRESH_TOKEN_REMEMBER_ME_EXPIRY: Lazy<AtomicI64> =
+    Lazy::new(|| AtomicI64::new(AppConfig::get().jwt.refresh_token_remember_me_expiry));
 
 // JWT Claims 结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,17 +50,46 @@ impl JwtUtils {
         AppConfig::get().jwt.secret.clone()
     }
 
+    /// Access Token 有效期（分钟）
+    pub fn access_token_expiry() -> i64 {
+        ACCESS_TOKEN_EXPIRY.load(Ordering::Relaxed)
+    }
+
+    /// Refresh Token 有效期（天）
+    pub fn refresh_token_expiry() -> i64 {
+        REFRESH_TOKEN_EXPIRY.load(Ordering::Relaxed)
+    }
+
+    /// 记住我 Refresh Token 有效期（天）
+    pub fn refresh_token_remember_me_expiry() -> i64 {
+        REFRESH_TOKEN_REMEMBER_ME_EXPIRY.load(Ordering::Relaxed)
+    }
+
+    /// 更新 Access Token 有效期（分钟）
+    pub fn set_access_token_expiry(minutes: i64) {
+        ACCESS_TOKEN_EXPIRY.store(minutes, Ordering::Relaxed);
+    }
+
+    /// 更新 Refresh Token 有效期（天）
+    pub fn set_refresh_token_expiry(days: i64) {
+        REFRESH_TOKEN_EXPIRY.store(days, Ordering::Relaxed);
+    }
+
+    /// 更新记住我 Refresh Token 有效期（天）
+    pub fn set_refresh_token_remember_me_expiry(days: i64) {
+        REFRESH_TOKEN_REMEMBER_ME_EXPIRY.store(days, Ordering::Relaxed);
+    }
+
     // 生成 Access Token
     pub fn generate_access_token(
         user_id: i64,
         role: &str,
     ) -> Result<String, jsonwebtoken::errors::Error> {
-        let config = AppConfig::get();
         Self::generate_token_with_expiry(
             user_id,
             role,
             "access",
-            chrono::Duration::minutes(config.jwt.access_token_expiry),
+            chrono::Duration::minutes(Self::access_token_expiry()),
         )
     }
 
@@ -48,14 +99,13 @@ impl JwtUtils {
         role: &str,
         token_expiry: Option<chrono::Duration>,
     ) -> Result<String, jsonwebtoken::errors::Error> {
-        let config = AppConfig::get();
         match token_expiry {
             Some(expiry) => Self::generate_token_with_expiry(user_id, role, "refresh", expiry),
             None => Self::generate_token_with_expiry(
                 user_id,
                 role,
                 "refresh",
-                chrono::Duration::days(config.jwt.refresh_token_expiry),
+                chrono::Duration::days(Self::refresh_token_expiry()),
             ),
         }
     }
@@ -140,6 +190,20 @@ impl JwtUtils {
         decode::<Claims>(token, &decoding_key, &validation).map(|token_data| token_data.claims)
     }
 
+    /// 轮换 Refresh Token：签发新令牌并保留原过期时间，返回新令牌与剩余有效期
+    pub fn rotate_refresh_token(
+        claims: &Claims,
+    ) -> Result<(String, chrono::Duration), jsonwebtoken::errors::Error> {
+        let user_id = claims
+            .sub
+            .parse::<i64>()
+            .map_err(|_| jsonwebtoken::errors::ErrorKind::InvalidToken)?;
+        let remaining =
+            chrono::Duration::seconds(claims.exp as i64 - chrono::Utc::now().timestamp());
+        let token = Self::generate_token_with_expiry(user_id, &claims.role, "refresh", remaining)?;
+        Ok((token, remaining))
+    }
+
     // 使用 Refresh Token 生成新的 Access Token
     pub fn refresh_access_token(
         refresh_token: &str,
@@ -158,7 +222,7 @@ impl JwtUtils {
         Cookie::build("refresh_token", refresh_token.to_string())
             .path("/")
             .max_age(actix_web::cookie::time::Duration::days(
-                config.jwt.refresh_token_expiry,
+                Self::refresh_token_expiry(),
             ))
             .same_site(SameSite::Strict)
             .http_only(true)
@@ -183,4 +247,91 @@ impl JwtUtils {
         req.cookie("refresh_token")
             .map(|cookie| cookie.value().to_string())
     }
+
+    /// 是否启用 Cookie 认证（登录/刷新时下发访问令牌 Cookie）
+    pub fn cookie_auth_enabled() -> bool {
+        AppConfig::get().auth_cookie.enabled
+    }
+
+    /// 创建 Cookie 认证所需的 Cookie：访问令牌（HttpOnly）与新的 CSRF 令牌
+    ///
+    /// `session_max_age` 为登录会话（Refresh Token）的剩余有效期，CSRF 令牌与之同步过期。
+    pub fn create_auth_cookies(
+        access_token: &str,
+        session_max_age: chrono::Duration,
+    ) -> Vec<Cookie<'static>> {
+        vec![
+            Self::build_auth_cookie(
+                ACCESS_TOKEN_COOKIE,
+                access_token.to_string(),
+                true,
+                Self::access_token_expiry() * 60,
+            ),
+            Self::build_auth_cookie(
+                CSRF_COOKIE,
+                generate_random_code(CSRF_TOKEN_LENGTH),
+                false,
+                session_max_age.num_seconds(),
+            ),
+        ]
+    }
+
+    /// 创建清除 Cookie 认证状态的空 Cookie（用于注销）
+    pub fn create_empty_auth_cookies() -> Vec<Cookie<'static>> {
+        [ACCESS_TOKEN_COOKIE, CSRF_COOKIE]
+            .into_iter()
+            .map(|name| Self::build_auth_cookie(name, String::new(), name != CSRF_COOKIE, 0))
+            .collect()
+    }
+
+    fn build_auth_cookie(
+        name: &'static str,
+        value: String,
+        http_only: bool,
+        max_age_secs: i64,
+    ) -> Cookie<'static> {
+        let config = &AppConfig::get().auth_cookie;
+        let same_site = if config.same_site.eq_ignore_ascii_case("lax") {
+            SameSite::Lax
+        } else {
+            SameSite::Strict
+        };
+
+        let mut cookie = Cookie::build(name, value)
+            .path("/")
+            .max_age(actix_web::cookie::time::Duration::seconds(
+                max_age_secs.max(0),
+            ))
+            .same_site(same_site)
+            .http_only(http_only)
+            .secure(config.secure)
+            .finish();
+        if !config.domain.is_empty() {
+            cookie.set_domain(config.domain.clone());
+        }
+        cookie
+    }
+
+    /// 校验 CSRF 双提交令牌：请求头中的值必须与 Cookie 一致
+    pub fn csrf_token_matches(header: Option<&str>, cookie: Option<&str>) -> bool {
+        match (header, cookie) {
+            (Some(header), Some(cookie)) if !cookie.is_empty() => {
+                constant_time_eq(header.as_bytes(), cookie.as_bytes())
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csrf_token_matches() {
+        assert!(JwtUtils::csrf_token_matches(Some("abc"), Some("abc")));
+        assert!(!JwtUtils::csrf_token_matches(Some("abc"), Some("abd")));
+        assert!(!JwtUtils::csrf_token_matches(None, Some("abc")));
+        assert!(!JwtUtils::csrf_token_matches(Some(""), Some("")));
+    }
 }
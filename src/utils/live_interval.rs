@@ -0,0 +1,64 @@
+//! 可运行时调整的定时任务间隔
+//!
+//! 后台任务用 [`LiveInterval::run`] 循环执行；间隔被修改时立即结束当前等待，
+//! 执行一次后按新间隔继续，无需重启任务。
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+pub struct LiveInterval {
+    secs: watch::Sender<u64>,
+}
+
+impl LiveInterval {
+    pub fn new(secs: u64) -> Self {
+        Self {
+            secs: watch::Sender::new(secs),
+        }
+    }
+
+    /// 当前间隔（秒）
+    pub fn get(&self) -> u64 {
+        *self.secs.borrow()
+    }
+
+    /// 修改间隔（秒）
+    pub fn set(&self, secs: u64) {
+        self.secs.send_replace(secs);
+    }
+
+    /// 立即执行一次任务，之后每隔当前间隔执行一次
+    pub async fn run<F, Fut>(&self, mut task: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let mut changes = self.secs.subscribe();
+        loop {
+            task().await;
+
+            let secs = *changes.borrow_and_update();
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(secs)) => {}
+                _ = changes.changed() => {
+                    tracing::debug!("Job interval changed to {}s", self.get());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_interval() {
+        let interval = LiveInterval::new(300);
+        assert_eq!(interval.get(), 300);
+        interval.set(60);
+        assert_eq!(interval.get(), 60);
+    }
+}
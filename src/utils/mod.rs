@@ -1,17 +1,30 @@
+pub mod client_info;
+pub mod code_language;
+pub mod etag;
+pub mod export;
 pub mod extractor;
 pub mod file_magic;
 pub mod jwt;
+pub mod live_interval;
 pub mod parameter_error_handler;
 pub mod password;
+pub mod pdf;
 pub mod random_code;
+pub mod signed_time;
 pub mod sql;
+pub mod user_agent;
 pub mod validate;
 
+pub use client_info::ClientInfo;
+pub use code_language::detect_code_language;
 pub use extractor::{
-    SafeClassCode, SafeClassIdI64, SafeFileToken, SafeGradeIdI64, SafeHomeworkIdI64, SafeIDI64,
-    SafeNotificationIdI64, SafeSettingKey, SafeSubmissionIdI64,
+    SafeCertificateCode, SafeClassCode, SafeClassIdI64, SafeFileToken, SafeGradeIdI64,
+    SafeHomeworkIdI64, SafeIDI64, SafeJobId, SafeNotificationIdI64, SafeSettingKey, SafeShareToken,
+    SafeSubmissionIdI64,
 };
 pub use file_magic::validate_magic_bytes;
+pub use live_interval::LiveInterval;
 pub use parameter_error_handler::json_error_handler;
 pub use parameter_error_handler::query_error_handler;
 pub use sql::escape_like_pattern;
+pub use user_agent::UserAgentInfo;
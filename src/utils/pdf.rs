@@ -0,0 +1,601 @@
+//! 简易 PDF 生成
+//!
+//! 只支持按行排版的纯文本文档（标题、正文、空行、自动分页）、带边框的简单表格以及图片插入，
+//! 用于成绩单、名单、证书等导出。
+//! 字体使用 PDF 阅读器内置的 `STSong-Light`（Adobe-GB1 CID 字体，UniGB-UCS2-H 编码），
+//! 无需嵌入字体文件即可显示中文。
+
+use std::fmt::Write as _;
+
+/// A4 纸宽度（pt）
+const PAGE_WIDTH: f32 = 595.0;
+/// A4 纸高度（pt）
+const PAGE_HEIGHT: f32 = 842.0;
+/// 页边距（pt）
+const MARGIN: f32 = 50.0;
+/// 行距系数
+const LINE_SPACING: f32 = 1.5;
+/// 表格行高系数（相对字号，留出手写签到的空间）
+const TABLE_ROW_SPACING: f32 = 2.2;
+/// 表格单元格左右内边距（pt）
+const TABLE_CELL_PADDING: f32 = 4.0;
+
+/// 标题字号
+pub const HEADING_SIZE: f32 = 16.0;
+/// 小标题字号
+pub const SUBHEADING_SIZE: f32 = 13.0;
+/// 正文字号
+pub const BODY_SIZE: f32 = 10.5;
+
+/// 水平对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// 可嵌入 PDF 的图片
+///
+/// 支持 JPEG（灰度 / RGB）与 8 位、非隔行、不含透明通道的 PNG（灰度 / RGB）。
+/// 两者都无需解码：JPEG 直接使用 DCTDecode，PNG 的 IDAT 数据本身就是 zlib 流，
+/// 配合 PNG 预测器参数即可由 FlateDecode 还原。
+pub struct PdfImage {
+    width: u32,
+    height: u32,
+    color_space: &'static str,
+    filter: &'static str,
+    decode_parms: Option<String>,
+    data: Vec<u8>,
+}
+
+impl PdfImage {
+    /// 从图片文件内容解析，不支持的格式返回 None
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0xFF, 0xD8]) {
+            Self::from_jpeg(data)
+        } else if data.starts_with(PNG_SIGNATURE) {
+            Self::from_png(data)
+        } else {
+            None
+        }
+    }
+
+    fn from_jpeg(data: &[u8]) -> Option<Self> {
+        let mut pos = 2;
+        while pos + 4 <= data.len() {
+            if data[pos] != 0xFF {
+                return None;
+            }
+            let marker = data[pos + 1];
+            // 填充字节与无长度的独立标记
+            if marker == 0xFF {
+                pos += 1;
+                continue;
+            }
+            if marker == 0x01 || (0xD0..=0xD9).contains(&marker) {
+                pos += 2;
+                continue;
+            }
+            let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+            // SOF0 ~ SOF15（排除 DHT / JPG / DAC）
+            if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+                let sof = data.get(pos + 4..pos + 10)?;
+                let height = u16::from_be_bytes([sof[1], sof[2]]) as u32;
+                let width = u16::from_be_bytes([sof[3], sof[4]]) as u32;
+                let color_space = match sof[5] {
+                    1 => "DeviceGray",
+                    3 => "DeviceRGB",
+                    _ => return None,
+                };
+                if width == 0 || height == 0 {
+                    return None;
+                }
+                return Some(Self {
+                    width,
+                    height,
+                    color_space,
+                    filter: "DCTDecode",
+                    decode_parms: None,
+                    data: data.to_vec(),
+                });
+            }
+            pos += 2 + len;
+        }
+        None
+    }
+
+    fn from_png(data: &[u8]) -> Option<Self> {
+        let mut pos = PNG_SIGNATURE.len();
+        let mut header = None;
+        let mut idat = Vec::new();
+
+        while pos + 8 <= data.len() {
+            let len = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+            let kind = &data[pos + 4..pos + 8];
+            let body = data.get(pos + 8..pos + 8 + len)?;
+            match kind {
+                b"IHDR" if body.len() >= 13 => {
+                    let width = u32::from_be_bytes(body[0..4].try_into().ok()?);
+                    let height = u32::from_be_bytes(body[4..8].try_into().ok()?);
+                    // 位深、颜色类型、隔行方式
+                    header = Some((width, height, body[8], body[9], body[12]));
+                }
+                b"IDAT" => idat.extend_from_slice(body),
+                b"IEND" => break,
+                _ => {}
+            }
+            // 长度 + 类型 + 数据 + CRC
+            pos += 12 + len;
+        }
+
+        let (width, height, bit_depth, color_type, interlace) = header?;
+        let (color_space, colors) = match color_type {
+            0 => ("DeviceGray", 1),
+            2 => ("DeviceRGB", 3),
+            _ => return None,
+        };
+        if bit_depth != 8 || interlace != 0 || width == 0 || height == 0 || idat.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height,
+            color_space,
+            filter: "FlateDecode",
+            decode_parms: Some(format!(
+                "<< /Predictor 15 /Colors {colors} /BitsPerComponent 8 /Columns {width} >>"
+            )),
+            data: idat,
+        })
+    }
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+pub struct PdfDocument {
+    title: String,
+    pages: Vec<String>,
+    current: String,
+    cursor_y: f32,
+    images: Vec<PdfImage>,
+}
+
+impl PdfDocument {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            pages: Vec::new(),
+            current: String::new(),
+            cursor_y: PAGE_HEIGHT - MARGIN,
+            images: Vec::new(),
+        }
+    }
+
+    /// 添加标题
+    pub fn heading(&mut self, text: &str) {
+        self.write(text, HEADING_SIZE, 0.0);
+    }
+
+    /// 添加小标题
+    pub fn subheading(&mut self, text: &str) {
+        self.write(text, SUBHEADING_SIZE, 0.0);
+    }
+
+    /// 添加正文（超出页宽自动换行）
+    pub fn text(&mut self, text: &str) {
+        self.write(text, BODY_SIZE, 0.0);
+    }
+
+    /// 添加缩进正文
+    pub fn indented(&mut self, text: &str) {
+        self.write(text, BODY_SIZE, BODY_SIZE * 2.0);
+    }
+
+    /// 添加空行
+    pub fn spacer(&mut self) {
+        self.advance(BODY_SIZE * LINE_SPACING);
+    }
+
+    /// 添加指定字号与对齐方式的文本（超出页宽自动换行）
+    pub fn aligned(&mut self, text: &str, size: f32, align: Align) {
+        let max_width = PAGE_WIDTH - MARGIN * 2.0;
+        for paragraph in text.lines() {
+            for line in wrap_line(paragraph, size, max_width) {
+                let width = text_width(&line, size);
+                self.advance(size * LINE_SPACING);
+                self.draw_text(
+                    &line,
+                    size,
+                    MARGIN + align_offset(align, width, max_width),
+                    self.cursor_y,
+                );
+            }
+        }
+    }
+
+    /// 插入图片，`width` 为显示宽度（pt），高度按原始比例计算
+    pub fn image(&mut self, image: PdfImage, width: f32, align: Align) {
+        let max_width = PAGE_WIDTH - MARGIN * 2.0;
+        let width = width.min(max_width);
+        let height = width * image.height as f32 / image.width as f32;
+
+        self.advance(height);
+        self.images.push(image);
+        let _ = writeln!(
+            self.current,
+            "q {width:.1} 0 0 {height:.1} {:.1} {:.1} cm /Im{} Do Q",
+            MARGIN + align_offset(align, width, max_width),
+            self.cursor_y,
+            self.images.len()
+        );
+    }
+
+    /// 添加带边框的表格
+    ///
+    /// 列宽按各列最长内容估算后等比缩放到页宽，放不下的单元格内容截断并以省略号结尾；
+    /// 表格跨页时在新页重复表头。
+    pub fn table(&mut self, headers: &[String], rows: &[Vec<String>]) {
+        if headers.is_empty() {
+            return;
+        }
+        let size = BODY_SIZE;
+        let row_height = size * TABLE_ROW_SPACING;
+
+        let mut widths: Vec<f32> = headers.iter().map(|h| text_width(h, size)).collect();
+        for row in rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = width.max(text_width(cell, size));
+            }
+        }
+        let natural: f32 = widths.iter().map(|w| w + TABLE_CELL_PADDING * 2.0).sum();
+        let scale = (PAGE_WIDTH - MARGIN * 2.0) / natural;
+        let widths: Vec<f32> = widths
+            .iter()
+            .map(|w| (w + TABLE_CELL_PADDING * 2.0) * scale)
+            .collect();
+
+        // 表头之后至少还要放得下一行
+        if self.cursor_y - row_height * 2.0 < MARGIN {
+            self.new_page();
+        }
+        self.table_row(headers, &widths, row_height);
+        for row in rows {
+            if self.cursor_y - row_height < MARGIN {
+                self.new_page();
+                self.table_row(headers, &widths, row_height);
+            }
+            self.table_row(row, &widths, row_height);
+        }
+    }
+
+    fn table_row(&mut self, cells: &[String], widths: &[f32], height: f32) {
+        let size = BODY_SIZE;
+        self.advance(height);
+        let bottom = self.cursor_y;
+        // 基线位置使文字在单元格内垂直居中
+        let baseline = bottom + (height - size) / 2.0 + size * 0.15;
+
+        let mut x = MARGIN;
+        for (i, &width) in widths.iter().enumerate() {
+            let cell = cells.get(i).map(String::as_str).unwrap_or_default();
+            let text = truncate_line(cell, size, width - TABLE_CELL_PADDING * 2.0);
+            self.draw_text(&text, size, x + TABLE_CELL_PADDING, baseline);
+            let _ = writeln!(
+                self.current,
+                "0.5 w {x:.1} {bottom:.1} {width:.1} {height:.1} re S"
+            );
+            x += width;
+        }
+    }
+
+    fn write(&mut self, text: &str, size: f32, indent: f32) {
+        let max_width = PAGE_WIDTH - MARGIN * 2.0 - indent;
+        for paragraph in text.lines() {
+            for line in wrap_line(paragraph, size, max_width) {
+                self.advance(size * LINE_SPACING);
+                self.draw_text(&line, size, MARGIN + indent, self.cursor_y);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, line: &str, size: f32, x: f32, y: f32) {
+        let _ = writeln!(
+            self.current,
+            "BT /F1 {size:.1} Tf {x:.1} {y:.1} Td <{}> Tj ET",
+            encode_ucs2(line)
+        );
+    }
+
+    fn advance(&mut self, height: f32) {
+        if self.cursor_y - height < MARGIN {
+            self.new_page();
+        }
+        self.cursor_y -= height;
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.current));
+        self.cursor_y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// 生成 PDF 文件内容
+    pub fn finish(mut self) -> Vec<u8> {
+        if !self.current.is_empty() || self.pages.is_empty() {
+            self.pages.push(std::mem::take(&mut self.current));
+        }
+
+        // 对象编号：1 目录，2 页面树，3 字体，4 CID 字体，5 文档信息，之后每页两个对象（页面 + 内容流），
+        // 最后是图片对象
+        let page_count = self.pages.len();
+        let page_ids: Vec<usize> = (0..page_count).map(|i| 6 + i * 2).collect();
+        let first_image_id = 6 + page_count * 2;
+
+        let x_objects = if self.images.is_empty() {
+            String::new()
+        } else {
+            let refs = (0..self.images.len())
+                .map(|i| format!("/Im{} {} 0 R", i + 1, first_image_id + i))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(" /XObject << {refs} >>")
+        };
+
+        let mut objects: Vec<Vec<u8>> = Vec::with_capacity(5 + page_count * 2 + self.images.len());
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {page_count} >>",
+                page_ids
+                    .iter()
+                    .map(|id| format!("{id} 0 R"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+            .into_bytes(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type0 /BaseFont /STSong-Light /Encoding /UniGB-UCS2-H /DescendantFonts [4 0 R] >>"
+                .to_vec(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /CIDFontType0 /BaseFont /STSong-Light /CIDSystemInfo << /Registry (Adobe) /Ordering (GB1) /Supplement 2 >> /DW 1000 /W [1 95 500] >>"
+                .to_vec(),
+        );
+        objects.push(
+            format!(
+                "<< /Title <FEFF{}> /Producer (hwsystem) >>",
+                encode_ucs2(&self.title)
+            )
+            .into_bytes(),
+        );
+
+        for (i, content) in self.pages.iter().enumerate() {
+            let content_id = page_ids[i] + 1;
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] /Resources << /Font << /F1 3 0 R >>{x_objects} >> /Contents {content_id} 0 R >>"
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(content.as_bytes());
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        for image in &self.images {
+            let decode_parms = image
+                .decode_parms
+                .as_deref()
+                .map(|p| format!(" /DecodeParms {p}"))
+                .unwrap_or_default();
+            let mut stream = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /{} /BitsPerComponent 8 /Filter /{}{decode_parms} /Length {} >>\nstream\n",
+                image.width,
+                image.height,
+                image.color_space,
+                image.filter,
+                image.data.len()
+            )
+            .into_bytes();
+            stream.extend_from_slice(&image.data);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, body) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.extend_from_slice(xref.as_bytes());
+        out
+    }
+}
+
+/// 计算对齐后相对于左边距的水平偏移
+fn align_offset(align: Align, width: f32, max_width: f32) -> f32 {
+    match align {
+        Align::Left => 0.0,
+        Align::Center => ((max_width - width) / 2.0).max(0.0),
+        Align::Right => (max_width - width).max(0.0),
+    }
+}
+
+/// 估算字符宽度（ASCII 为半角，其余按全角计算）
+fn char_width(c: char, size: f32) -> f32 {
+    if c.is_ascii() { size * 0.5 } else { size }
+}
+
+/// 估算文本宽度
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().map(|c| char_width(c, size)).sum()
+}
+
+/// 截断到指定宽度，被截断时以省略号结尾
+fn truncate_line(text: &str, size: f32, max_width: f32) -> String {
+    if text_width(text, size) <= max_width {
+        return text.to_string();
+    }
+    let mut line = String::new();
+    let mut width = char_width('…', size);
+    for c in text.chars() {
+        let w = char_width(c, size);
+        if width + w > max_width {
+            break;
+        }
+        line.push(c);
+        width += w;
+    }
+    line.push('…');
+    line
+}
+
+/// 按宽度折行
+fn wrap_line(text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut width = 0.0;
+
+    for c in text.chars() {
+        let w = char_width(c, size);
+        if width + w > max_width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+            width = 0.0;
+        }
+        current.push(c);
+        width += w;
+    }
+    lines.push(current);
+    lines
+}
+
+/// 编码为 UCS-2 大端十六进制字符串（BMP 之外的字符替换为 ?）
+fn encode_ucs2(text: &str) -> String {
+    let mut hex = String::with_capacity(text.len() * 4);
+    for c in text.chars() {
+        let code = match c {
+            '\t' => ' ' as u32,
+            c if (c as u32) <= 0xFFFF && !c.is_control() => c as u32,
+            _ => '?' as u32,
+        };
+        let _ = write!(hex, "{code:04X}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_structure() {
+        let mut doc = PdfDocument::new("成绩单");
+        doc.heading("成绩单");
+        doc.text("作业 1：90 分");
+        let pdf = doc.finish();
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+    }
+
+    #[test]
+    fn test_pagination() {
+        let mut doc = PdfDocument::new("test");
+        for i in 0..200 {
+            doc.text(&format!("line {i}"));
+        }
+        let pdf = String::from_utf8_lossy(&doc.finish()).to_string();
+
+        assert!(!pdf.contains("/Count 1 "));
+        assert!(pdf.contains("/Count 5 "));
+    }
+
+    #[test]
+    fn test_png_image() {
+        // 1x1 RGB PNG（IDAT 为 zlib 压缩的单行扫描线）
+        let mut png = PNG_SIGNATURE.to_vec();
+        let chunk = |kind: &[u8], body: &[u8]| {
+            let mut c = (body.len() as u32).to_be_bytes().to_vec();
+            c.extend_from_slice(kind);
+            c.extend_from_slice(body);
+            c.extend_from_slice(&[0, 0, 0, 0]);
+            c
+        };
+        png.extend(chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 2, 0, 0, 0]));
+        png.extend(chunk(b"IDAT", &[0x78, 0x9C, 0x63, 0x60, 0x00, 0x00]));
+        png.extend(chunk(b"IEND", &[]));
+
+        let image = PdfImage::from_bytes(&png).unwrap();
+        assert_eq!((image.width, image.height), (1, 1));
+        assert_eq!(image.color_space, "DeviceRGB");
+
+        let mut doc = PdfDocument::new("证书");
+        doc.aligned("结业证书", HEADING_SIZE, Align::Center);
+        doc.image(image, 120.0, Align::Right);
+        let pdf = String::from_utf8_lossy(&doc.finish()).to_string();
+        assert!(pdf.contains("/XObject << /Im1 8 0 R >>"));
+        assert!(pdf.contains("/Subtype /Image /Width 1 /Height 1"));
+        assert!(pdf.contains("/Im1 Do"));
+
+        // 带透明通道的 PNG 不支持
+        let mut rgba = PNG_SIGNATURE.to_vec();
+        rgba.extend(chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]));
+        rgba.extend(chunk(b"IDAT", &[0x78, 0x9C]));
+        assert!(PdfImage::from_bytes(&rgba).is_none());
+        assert!(PdfImage::from_bytes(b"GIF89a").is_none());
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        let lines = wrap_line(&"中".repeat(100), 10.0, 495.0);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].chars().count(), 49);
+    }
+
+    #[test]
+    fn test_table_repeats_header_across_pages() {
+        let headers = vec!["序号".to_string(), "姓名".to_string(), "签到".to_string()];
+        let rows: Vec<Vec<String>> = (1..=60)
+            .map(|i| vec![i.to_string(), format!("学生{i}"), String::new()])
+            .collect();
+
+        let mut doc = PdfDocument::new("名单");
+        doc.heading("名单");
+        doc.table(&headers, &rows);
+        let pdf = String::from_utf8_lossy(&doc.finish()).to_string();
+
+        assert!(pdf.contains("/Count 2 "));
+        // 每页一个表头
+        assert_eq!(pdf.matches(&encode_ucs2("签到")).count(), 2);
+        assert_eq!(pdf.matches(" re S").count(), (60 + 2) * 3);
+    }
+
+    #[test]
+    fn test_truncate_line() {
+        assert_eq!(truncate_line("abc", 10.0, 100.0), "abc");
+        assert_eq!(truncate_line("中文姓名很长", 10.0, 40.0), "中文姓…");
+    }
+
+    #[test]
+    fn test_encode_ucs2() {
+        assert_eq!(encode_ucs2("A中"), "00414E2D");
+        assert_eq!(encode_ucs2("😀"), "003F");
+    }
+}
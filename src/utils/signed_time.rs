@@ -0,0 +1,69 @@
+//! 签名时间戳
+//!
+//! `GET /system/time` 返回带 HMAC 签名的服务器时间（`<毫秒时间戳>.<签名>`），
+//! 客户端在考试模式提交时回传，服务端据此确认时间戳确实由服务器签发，
+//! 并与客户端同时上报的本地时间比较，发现篡改本机时钟的情况。
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::AppConfig;
+
+/// 签名用途前缀，避免与其他使用同一密钥的签名混用
+const SIGNING_CONTEXT: &str = "hwsystem-time";
+
+fn mac_for(timestamp_ms: i64, secret: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{SIGNING_CONTEXT}\n{timestamp_ms}").as_bytes());
+    mac
+}
+
+fn sign_with(timestamp_ms: i64, secret: &str) -> String {
+    let signature = mac_for(timestamp_ms, secret).finalize().into_bytes();
+    format!(
+        "{timestamp_ms}.{}",
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature)
+    )
+}
+
+fn verify_with(token: &str, secret: &str) -> Option<i64> {
+    let (timestamp, signature) = token.split_once('.')?;
+    let timestamp_ms: i64 = timestamp.parse().ok()?;
+    let signature = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(signature)
+        .ok()?;
+    mac_for(timestamp_ms, secret)
+        .verify_slice(&signature)
+        .ok()
+        .map(|_| timestamp_ms)
+}
+
+/// 签名毫秒时间戳
+pub fn sign_timestamp(timestamp_ms: i64) -> String {
+    sign_with(timestamp_ms, &AppConfig::get().jwt.secret)
+}
+
+/// 校验签名时间戳，成功时返回其中的毫秒时间戳
+pub fn verify_timestamp(token: &str) -> Option<i64> {
+    verify_with(token, &AppConfig::get().jwt.secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let token = sign_with(1_700_000_000_123, "secret");
+        assert!(token.starts_with("1700000000123."));
+        assert_eq!(verify_with(&token, "secret"), Some(1_700_000_000_123));
+
+        // 篡改时间戳或使用其他密钥均校验失败
+        let forged = token.replacen("1700000000123", "1700000060123", 1);
+        assert_eq!(verify_with(&forged, "secret"), None);
+        assert_eq!(verify_with(&token, "other"), None);
+        assert_eq!(verify_with("not-a-token", "secret"), None);
+    }
+}
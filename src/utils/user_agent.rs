@@ -0,0 +1,172 @@
+//! User-Agent 解析
+//!
+//! 只识别主流浏览器与操作系统的名称，不保留版本号：登录历史按
+//! （浏览器, 操作系统, 设备类型）判断是否为新设备，浏览器自动升级不应触发新设备提醒。
+
+/// 未能识别时使用的名称
+pub const UNKNOWN: &str = "Unknown";
+
+/// User-Agent 解析结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserAgentInfo {
+    pub browser: &'static str,
+    pub os: &'static str,
+    /// desktop / mobile / tablet / bot / unknown
+    pub device_type: &'static str,
+}
+
+impl UserAgentInfo {
+    /// 解析 User-Agent，缺失时各字段均为未知
+    pub fn parse(user_agent: Option<&str>) -> Self {
+        let ua = user_agent.unwrap_or_default();
+        let lower = ua.to_ascii_lowercase();
+        if ["bot", "spider", "crawler"]
+            .iter()
+            .any(|keyword| lower.contains(keyword))
+        {
+            return Self {
+                browser: "Bot",
+                os: UNKNOWN,
+                device_type: "bot",
+            };
+        }
+
+        let os = parse_os(ua);
+        Self {
+            browser: parse_browser(ua),
+            os,
+            device_type: parse_device_type(ua, os),
+        }
+    }
+
+    /// 展示用的设备描述，例如 `Chrome / Windows`
+    pub fn label(&self) -> String {
+        format!("{} / {}", self.browser, self.os)
+    }
+}
+
+/// 浏览器（基于 Chromium 的浏览器会同时带有 Chrome 标识，需要先匹配）
+fn parse_browser(ua: &str) -> &'static str {
+    const RULES: &[(&[&str], &str)] = &[
+        (&["MicroMessenger/"], "WeChat"),
+        (&["Edg/", "EdgA/", "EdgiOS/"], "Edge"),
+        (&["OPR/", "Opera"], "Opera"),
+        (&["SamsungBrowser/"], "Samsung Internet"),
+        (&["Firefox/", "FxiOS/"], "Firefox"),
+        (&["CriOS/", "Chrome/", "Chromium/"], "Chrome"),
+        (&["MSIE ", "Trident/"], "Internet Explorer"),
+        (&["Safari/"], "Safari"),
+    ];
+    RULES
+        .iter()
+        .find(|(patterns, _)| patterns.iter().any(|p| ua.contains(p)))
+        .map(|(_, name)| *name)
+        .unwrap_or(UNKNOWN)
+}
+
+/// 操作系统（iOS 的 UA 含 `like Mac OS X`，鸿蒙的 UA 可能含 Android，均需先匹配）
+fn parse_os(ua: &str) -> &'static str {
+    const RULES: &[(&[&str], &str)] = &[
+        (&["Windows"], "Windows"),
+        (&["iPhone", "iPad", "iPod"], "iOS"),
+        (&["HarmonyOS", "OpenHarmony"], "HarmonyOS"),
+        (&["Android"], "Android"),
+        (&["CrOS"], "ChromeOS"),
+        (&["Macintosh", "Mac OS X"], "macOS"),
+        (&["Linux"], "Linux"),
+    ];
+    RULES
+        .iter()
+        .find(|(patterns, _)| patterns.iter().any(|p| ua.contains(p)))
+        .map(|(_, name)| *name)
+        .unwrap_or(UNKNOWN)
+}
+
+/// 设备类型（Android 平板的 UA 不含 `Mobile`）
+fn parse_device_type(ua: &str, os: &str) -> &'static str {
+    if ua.contains("iPad") || ua.contains("Tablet") || (os == "Android" && !ua.contains("Mobile")) {
+        "tablet"
+    } else if ua.contains("Mobi") || ua.contains("iPhone") || ua.contains("iPod") {
+        "mobile"
+    } else if matches!(os, "Windows" | "macOS" | "Linux" | "ChromeOS") {
+        "desktop"
+    } else {
+        "unknown"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_desktop_browsers() {
+        let chrome = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+        ));
+        assert_eq!(chrome.browser, "Chrome");
+        assert_eq!(chrome.os, "Windows");
+        assert_eq!(chrome.device_type, "desktop");
+        assert_eq!(chrome.label(), "Chrome / Windows");
+
+        let edge = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91",
+        ));
+        assert_eq!(edge.browser, "Edge");
+
+        let safari = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_2) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+        ));
+        assert_eq!(safari.browser, "Safari");
+        assert_eq!(safari.os, "macOS");
+
+        let firefox = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+        ));
+        assert_eq!(firefox.browser, "Firefox");
+        assert_eq!(firefox.os, "Linux");
+    }
+
+    #[test]
+    fn test_parse_mobile_devices() {
+        let iphone = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1",
+        ));
+        assert_eq!(iphone.browser, "Safari");
+        assert_eq!(iphone.os, "iOS");
+        assert_eq!(iphone.device_type, "mobile");
+
+        let android = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+        ));
+        assert_eq!(android.os, "Android");
+        assert_eq!(android.device_type, "mobile");
+
+        let tablet = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (Linux; Android 13; SM-X710) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+        ));
+        assert_eq!(tablet.device_type, "tablet");
+
+        let wechat = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (Linux; Android 14; V2243A) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/111.0 Mobile Safari/537.36 MicroMessenger/8.0.44",
+        ));
+        assert_eq!(wechat.browser, "WeChat");
+    }
+
+    #[test]
+    fn test_parse_unknown_and_bot() {
+        let missing = UserAgentInfo::parse(None);
+        assert_eq!(missing.browser, UNKNOWN);
+        assert_eq!(missing.os, UNKNOWN);
+        assert_eq!(missing.device_type, "unknown");
+
+        let curl = UserAgentInfo::parse(Some("curl/8.4.0"));
+        assert_eq!(curl.browser, UNKNOWN);
+
+        let bot = UserAgentInfo::parse(Some(
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        ));
+        assert_eq!(bot.browser, "Bot");
+        assert_eq!(bot.device_type, "bot");
+    }
+}
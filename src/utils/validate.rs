@@ -4,6 +4,12 @@ use regex::Regex;
 static USERNAME_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^[A-Za-z0-9_-]+$").expect("Invalid username regex"));
 
+static ORG_SLUG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").expect("Invalid org slug regex"));
+
+static HEX_COLOR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^#[0-9A-Fa-f]{6}$").expect("Invalid hex color regex"));
+
 static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}$").expect("Invalid email regex")
 });
@@ -28,6 +34,59 @@ pub fn validate_email(email: &str) -> Result<(), &'static str> {
     Ok(())
 }
 
+pub fn validate_org_slug(slug: &str) -> Result<(), &'static str> {
+    // 组织标识长度校验：2 <= x <= 64
+    if slug.len() < 2 || slug.len() > 64 {
+        return Err("Organization slug length must be between 2 and 64 characters");
+    }
+    // 组织标识格式校验：小写字母、数字，以连字符分隔
+    if !ORG_SLUG_RE.is_match(slug) {
+        return Err("Organization slug must contain only lowercase letters, numbers and hyphens");
+    }
+    Ok(())
+}
+
+pub fn validate_hex_color(color: &str) -> Result<(), &'static str> {
+    // 颜色格式校验：#RRGGBB
+    if !HEX_COLOR_RE.is_match(color) {
+        return Err("Color must be in #RRGGBB format");
+    }
+    Ok(())
+}
+
+/// 班级内姓名最大长度（字符数）
+pub const MAX_PROFILE_NAME_LENGTH: usize = 64;
+
+/// 校验班级内姓名，返回去除首尾空白后的姓名
+///
+/// `pattern` 为班级设置的格式正则，须完整匹配；正则本身在班级设置时已校验。
+pub fn validate_profile_name(name: &str, pattern: Option<&str>) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_PROFILE_NAME_LENGTH {
+        return Err(format!(
+            "Profile name length must be between 1 and {MAX_PROFILE_NAME_LENGTH} characters"
+        ));
+    }
+    if let Some(pattern) = pattern {
+        let re = Regex::new(&format!("^(?:{pattern})$"))
+            .map_err(|e| format!("Profile name pattern is invalid: {e}"))?;
+        if !re.is_match(name) {
+            return Err(format!("Profile name must match the pattern: {pattern}"));
+        }
+    }
+    Ok(name.to_string())
+}
+
+/// 校验班级内姓名格式正则
+pub fn validate_profile_name_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.len() > 255 {
+        return Err("Profile name pattern must not exceed 255 characters".to_string());
+    }
+    Regex::new(pattern)
+        .map(|_| ())
+        .map_err(|e| format!("Profile name pattern is invalid: {e}"))
+}
+
 /// 密码策略验证结果
 #[derive(Debug, Clone)]
 pub struct PasswordValidationResult {
@@ -170,4 +229,22 @@ mod tests {
                 .contains(&"Password is too common, please choose a stronger password")
         );
     }
+
+    #[test]
+    fn test_org_slug() {
+        assert!(validate_org_slug("school-1").is_ok());
+        assert!(validate_org_slug("a").is_err());
+        assert!(validate_org_slug("School").is_err());
+        assert!(validate_org_slug("-school").is_err());
+        assert!(validate_org_slug("school--1").is_err());
+    }
+
+    #[test]
+    fn test_hex_color() {
+        assert!(validate_hex_color("#1677ff").is_ok());
+        assert!(validate_hex_color("#1677FF").is_ok());
+        assert!(validate_hex_color("1677ff").is_err());
+        assert!(validate_hex_color("#167").is_err());
+        assert!(validate_hex_color("#1677fg").is_err());
+    }
 }
@@ -0,0 +1,75 @@
+//! 班级对比分析集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+
+#[actix_web::test]
+async fn test_class_comparison_analytics() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("analytics").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let idle_class = ctx.create_class(&s.teacher, "analytics 空班").await;
+    let submission = ctx
+        .create_submission(&s.student, &s.homework, "答案")
+        .await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 80.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 仅管理员可访问
+    let (status, _) = send(
+        &app,
+        get("/api/v1/admin/analytics/classes", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        get(
+            "/api/v1/admin/analytics/classes?from=2026-13-01",
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/admin/analytics/classes", Some(&s.admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+
+    let item = &items[0];
+    assert_eq!(item["class_id"], s.class.id);
+    assert_eq!(item["student_count"], 1);
+    assert_eq!(item["homework_count"], 1);
+    assert_eq!(item["submitted_count"], 1);
+    assert_eq!(item["submission_rate"], 1.0);
+    assert_eq!(item["late_rate"], 0.0);
+    assert_eq!(item["graded_count"], 1);
+    assert_eq!(item["average_score_percent"], 80.0);
+    assert!(item["average_grading_hours"].as_f64().unwrap() >= 0.0);
+
+    // 没有作业的班级比率为 null，排在最后
+    assert_eq!(items[1]["class_id"], idle_class.id);
+    assert!(items[1]["submission_rate"].is_null());
+}
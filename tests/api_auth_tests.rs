@@ -0,0 +1,152 @@
+//! 认证接口集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TEST_PASSWORD, TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_login_success_returns_usable_token() {
+    let ctx = TestContext::new().await;
+    ctx.create_user("alice", UserRole::User).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/login",
+            None,
+            json!({ "username": "alice", "password": TEST_PASSWORD }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["user"]["username"], "alice");
+
+    let token = body["data"]["access_token"].as_str().unwrap().to_string();
+    let (status, body) = send(&app, get("/api/v1/auth/me", Some(&token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["user"]["username"], "alice");
+}
+
+#[actix_web::test]
+async fn test_login_by_email() {
+    let ctx = TestContext::new().await;
+    ctx.create_user("bob", UserRole::Teacher).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/login",
+            None,
+            json!({ "username": "bob@example.com", "password": TEST_PASSWORD }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_login_wrong_password() {
+    let ctx = TestContext::new().await;
+    ctx.create_user("carol", UserRole::User).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/login",
+            None,
+            json!({ "username": "carol", "password": "wrong-password" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["code"], ErrorCode::AuthFailed as i32);
+}
+
+#[actix_web::test]
+async fn test_login_unknown_user() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/login",
+            None,
+            json!({ "username": "nobody", "password": TEST_PASSWORD }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["code"], ErrorCode::AuthFailed as i32);
+}
+
+#[actix_web::test]
+async fn test_protected_endpoint_requires_token() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, _) = send(&app, get("/api/v1/auth/me", None).to_request()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = send(
+        &app,
+        get("/api/v1/auth/me", Some("not-a-valid-token")).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_unknown_api_path_returns_json_404() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(&app, get("/api/v1/does-not-exist", None).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::NotFound as i32);
+}
+
+#[actix_web::test]
+async fn test_cached_user_invalidated_after_suspension() {
+    let ctx = TestContext::new().await;
+    let (_, admin_token) = ctx
+        .create_user_with_token("cache_admin", UserRole::Admin)
+        .await;
+    let (user, token) = ctx
+        .create_user_with_token("cache_user", UserRole::User)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 首次访问后用户信息进入认证缓存
+    let (status, _) = send(&app, get("/api/v1/auth/me", Some(&token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/users/{}", user.id),
+            Some(&admin_token),
+            json!({ "status": "suspended" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 停用立即生效，不再使用缓存中的旧用户信息
+    let (status, _) = send(&app, get("/api/v1/auth/me", Some(&token)).to_request()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
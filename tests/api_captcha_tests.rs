@@ -0,0 +1,68 @@
+//! 人机验证集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TEST_PASSWORD, TestContext, build_app, get, post_json, send, set_setting};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_login_captcha_step_up_after_failures() {
+    let ctx = TestContext::new().await;
+    ctx.init_dynamic_config().await;
+    let (_, admin_token) = ctx
+        .create_user_with_token("captcha_admin", UserRole::Admin)
+        .await;
+    ctx.create_user("captcha_user", UserRole::User).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    for (key, value) in [
+        ("captcha.provider", "turnstile"),
+        ("captcha.site_key", "site-key"),
+        ("captcha.login_failure_threshold", "2"),
+    ] {
+        let (status, _) = send(&app, set_setting(&admin_token, key, value).to_request()).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = send(&app, get("/api/v1/auth/captcha", None).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["provider"], "turnstile");
+    assert_eq!(body["data"]["site_key"], "site-key");
+    assert_eq!(body["data"]["login_mode"], "after_failures");
+
+    let login = |password: &str| {
+        post_json(
+            "/api/v1/auth/login",
+            None,
+            json!({ "username": "captcha_user", "password": password }),
+        )
+        .to_request()
+    };
+
+    // 未达到失败阈值前无需验证
+    for _ in 0..2 {
+        let (status, body) = send(&app, login("wrong-password")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], ErrorCode::AuthFailed as i32);
+    }
+
+    // 达到阈值后即使密码正确也需要人机验证
+    let (status, body) = send(&app, login(TEST_PASSWORD)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::CaptchaRequired as i32);
+
+    // 关闭登录验证后恢复正常登录
+    let (status, _) = send(
+        &app,
+        set_setting(&admin_token, "captcha.login_mode", "off").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, login(TEST_PASSWORD)).await;
+    assert_eq!(status, StatusCode::OK);
+}
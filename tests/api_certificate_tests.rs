@@ -0,0 +1,125 @@
+//! 结业证书集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send, send_raw};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_certificate_issue_download_and_verify() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("cert").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let base = format!("/api/v1/classes/{}/certificate", s.class.id);
+
+    // 学生不能修改证书设置
+    let settings = json!({ "enabled": true, "min_score_percent": 60.0, "signer_name": "王老师" });
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("{base}/settings"),
+            Some(&s.student_token),
+            settings.clone(),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 教师开启证书
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("{base}/settings"),
+            Some(&s.teacher_token),
+            settings,
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["enabled"], true);
+    assert_eq!(body["data"]["title"], "结业证书");
+
+    // 未完成作业时不能下载
+    let (status, body) = send(
+        &app,
+        get(&format!("{base}/download"), Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::CertificateNotEarned as i32);
+
+    // 学生提交，教师评分达到门槛
+    let submission = ctx
+        .create_submission(&s.student, &s.homework, "我的答案")
+        .await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 85.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send(
+        &app,
+        get(&format!("{base}/status"), Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["progress"]["required_count"], 1);
+    assert_eq!(body["data"]["progress"]["passed_count"], 1);
+    assert_eq!(body["data"]["certificate"]["average_percent"], 85.0);
+    let code = body["data"]["certificate"]["verification_code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (status, content_type, data) = send_raw(
+        &app,
+        get(&format!("{base}/download"), Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/pdf");
+    assert!(data.starts_with(b"%PDF-"));
+
+    // 班级外用户无法查看证书状态
+    let (status, _) = send(
+        &app,
+        get(&format!("{base}/status"), Some(&s.outsider_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 公开验证（验证码不区分大小写）
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/certificates/verify/{}", code.to_lowercase()),
+            None,
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["student_name"], s.student.username);
+    assert_eq!(body["data"]["class_name"], s.class.name);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/certificates/verify/NOTEXIST", None).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::CertificateNotFound as i32);
+}
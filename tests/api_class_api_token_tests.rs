@@ -0,0 +1,215 @@
+//! 班级 API 令牌集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_read_only_class_token_is_scoped_to_class() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("apitoken").await;
+    let other_class = ctx.create_class(&s.teacher, "apitoken 其他班级").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let tokens_url = format!("/api/v1/classes/{}/api-tokens", s.class.id);
+
+    // 学生不能签发令牌
+    let (status, _) = send(
+        &app,
+        post_json(
+            &tokens_url,
+            Some(&s.student_token),
+            json!({ "name": "bot" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &tokens_url,
+            Some(&s.teacher_token),
+            json!({ "name": "统计脚本" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["read_only"], true);
+    let token_id = body["data"]["id"].as_i64().unwrap();
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+    assert!(token.starts_with("hwc_"));
+    assert!(token.starts_with(body["data"]["token_prefix"].as_str().unwrap()));
+
+    // 列表不返回令牌本身
+    let (status, body) = send(&app, get(&tokens_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 1);
+    assert!(body["data"]["items"][0].get("token").is_none());
+
+    // 本班资源可读
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks?class_id={}", s.class.id),
+            Some(&token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["title"], "apitoken 作业");
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{}/stats", s.homework.id),
+            Some(&token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 其他班级与班级之外的接口不可访问
+    for url in [
+        format!("/api/v1/homeworks?class_id={}", other_class.id),
+        format!("/api/v1/classes/{}", other_class.id),
+        "/api/v1/classes".to_string(),
+        "/api/v1/notifications".to_string(),
+        tokens_url.clone(),
+    ] {
+        let (status, body) = send(&app, get(&url, Some(&token)).to_request()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{url}");
+        assert_eq!(body["code"], ErrorCode::ClassApiTokenForbidden as i32);
+    }
+
+    // 只读令牌不能写
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&token),
+            json!({ "class_id": s.class.id, "title": "第二周", "max_score": 100.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassApiTokenForbidden as i32);
+
+    // 撤销后令牌失效
+    let (status, _) = send(
+        &app,
+        delete(&format!("{tokens_url}/{token_id}"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks?class_id={}", s.class.id),
+            Some(&token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, body) = send(
+        &app,
+        delete(&format!("{tokens_url}/{token_id}"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::ClassApiTokenNotFound as i32);
+}
+
+#[actix_web::test]
+async fn test_write_class_token_creates_homework_with_audit_log() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("apitokenw").await;
+    let other_class = ctx.create_class(&s.teacher, "apitokenw 其他班级").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let tokens_url = format!("/api/v1/classes/{}/api-tokens", s.class.id);
+    let (status, body) = send(
+        &app,
+        post_json(
+            &tokens_url,
+            Some(&s.teacher_token),
+            json!({ "name": "作业发布", "read_only": false }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let token_id = body["data"]["id"].as_i64().unwrap();
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&token),
+            json!({ "class_id": s.class.id, "title": "第二周练习", "max_score": 100.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["created_by"], s.teacher.id);
+
+    // 请求体中的班级由业务层校验
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&token),
+            json!({ "class_id": other_class.id, "title": "越界", "max_score": 100.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassApiTokenForbidden as i32);
+
+    // 不能代学生提交
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&token),
+            json!({ "homework_id": s.homework.id, "content": "代交" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 写请求记录归属到令牌
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("{tokens_url}/{token_id}/logs"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["status"], 403);
+    assert_eq!(items[1]["method"], "POST");
+    assert_eq!(items[1]["path"], "/api/v1/homeworks");
+    assert_eq!(items[1]["status"], 201);
+
+    let (_, body) = send(&app, get(&tokens_url, Some(&s.teacher_token)).to_request()).await;
+    assert!(body["data"]["items"][0]["last_used_at"].is_string());
+}
@@ -0,0 +1,237 @@
+//! 班级内容导出包集成测试
+
+mod common;
+
+use std::io::{Cursor, Read, Write};
+
+use actix_web::http::StatusCode;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::test::{self, TestRequest};
+use serde_json::{Value, json};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use common::{TestContext, build_app, get, post_json, send, send_raw};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+const BOUNDARY: &str = "hwsystem-bundle-boundary";
+
+/// 构造只包含 `file` 字段的 multipart 上传请求
+fn upload(path: &str, token: &str, data: &[u8]) -> TestRequest {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"bundle.zip\"\r\n\
+         Content-Type: application/zip\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    TestRequest::post()
+        .uri(path)
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .insert_header((
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(body)
+}
+
+/// 读取导出包中的清单
+fn read_manifest(data: &[u8]) -> Value {
+    let mut archive = ZipArchive::new(Cursor::new(data)).expect("invalid zip");
+    let mut manifest = String::new();
+    archive
+        .by_name("bundle.json")
+        .expect("missing bundle.json")
+        .read_to_string(&mut manifest)
+        .unwrap();
+    serde_json::from_str(&manifest).unwrap()
+}
+
+/// 只包含清单的导出包
+fn zip_manifest(manifest: &Value) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("bundle.json", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(manifest.to_string().as_bytes()).unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+#[actix_web::test]
+async fn test_class_bundle_round_trip() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("bundle").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx
+        .create_submission(&s.student, &s.homework, "我的答案")
+        .await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 88.0, "comment": "不错" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 默认不包含提交
+    let url = format!("/api/v1/classes/{}/export-bundle", s.class.id);
+    let (status, content_type, data) =
+        send_raw(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/zip");
+    let manifest = read_manifest(&data);
+    assert_eq!(manifest["version"], 1);
+    assert_eq!(manifest["class"]["name"], "bundle 班级");
+    assert_eq!(manifest["homeworks"][0]["title"], "bundle 作业");
+    assert_eq!(manifest["homeworks"][0]["submissions"], json!([]));
+
+    let (status, _, data) = send_raw(
+        &app,
+        get(
+            &format!("{url}?include_submissions=true"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let manifest = read_manifest(&data);
+    let exported = &manifest["homeworks"][0]["submissions"][0];
+    assert_eq!(exported["username"], "bundle_student");
+    assert_eq!(exported["content"], "我的答案");
+    assert_eq!(exported["grade"]["score"], 88.0);
+
+    // 导入为新班级，提交按用户名关联
+    let (status, body) = send(
+        &app,
+        upload(
+            "/api/v1/classes/import-bundle?name=bundle%20%E5%BD%92%E6%A1%A3",
+            &s.teacher_token,
+            &data,
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let imported = &body["data"];
+    assert_eq!(imported["class"]["name"], "bundle 归档");
+    assert_eq!(imported["class"]["teacher_id"], s.teacher.id);
+    assert_eq!(imported["homeworks"], 1);
+    assert_eq!(imported["submissions"], 1);
+    assert_eq!(imported["skipped_submissions"], 0);
+
+    let class_id = imported["class"]["id"].as_i64().unwrap();
+    let (status, _, data) = send_raw(
+        &app,
+        get(
+            &format!("/api/v1/classes/{class_id}/export-bundle?include_submissions=true"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let restored = &read_manifest(&data)["homeworks"][0]["submissions"][0];
+    assert_eq!(restored["username"], "bundle_student");
+    assert_eq!(restored["submitted_at"], exported["submitted_at"]);
+    assert_eq!(restored["status"], exported["status"]);
+    assert_eq!(restored["grade"]["score"], 88.0);
+    assert_eq!(restored["grade"]["comment"], "不错");
+}
+
+#[actix_web::test]
+async fn test_class_bundle_permissions_and_version() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("bundleperm").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 学生与其他教师不能导出
+    let url = format!("/api/v1/classes/{}/export-bundle", s.class.id);
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, other_token) = ctx
+        .create_user_with_token("bundleperm_other", UserRole::Teacher)
+        .await;
+    let (status, _) = send(&app, get(&url, Some(&other_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let manifest = json!({
+        "version": 99,
+        "exported_at": "2030-01-01T00:00:00Z",
+        "class": { "name": "未来班级", "description": null }
+    });
+    let (status, _) = send(
+        &app,
+        upload(
+            "/api/v1/classes/import-bundle",
+            &s.student_token,
+            &zip_manifest(&manifest),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 拒绝更高版本的导出包
+    let (status, body) = send(
+        &app,
+        upload(
+            "/api/v1/classes/import-bundle",
+            &s.teacher_token,
+            &zip_manifest(&manifest),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ImportFileDataInvalid as i32);
+
+    // 找不到的用户的提交被跳过
+    let manifest = json!({
+        "version": 1,
+        "exported_at": "2030-01-01T00:00:00Z",
+        "class": { "name": "bundleperm 导入", "description": "说明" },
+        "homeworks": [{
+            "title": "第一章",
+            "description": null,
+            "max_score": 10.0,
+            "deadline": null,
+            "allow_late": false,
+            "submission_mode": "text",
+            "max_content_length": null,
+            "solution": null,
+            "submissions": [{
+                "username": "nobody_here",
+                "part_position": null,
+                "version": 1,
+                "content": "答案",
+                "status": "pending",
+                "is_late": false,
+                "submitted_at": "2029-12-01T00:00:00Z",
+                "grade": null
+            }]
+        }],
+        "announcements": [{ "title": "欢迎", "created_at": "2029-11-01T00:00:00Z" }],
+        "include_submissions": true
+    });
+    let (status, body) = send(
+        &app,
+        upload(
+            "/api/v1/classes/import-bundle",
+            &s.teacher_token,
+            &zip_manifest(&manifest),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["homeworks"], 1);
+    assert_eq!(body["data"]["announcements"], 1);
+    assert_eq!(body["data"]["submissions"], 0);
+    assert_eq!(body["data"]["skipped_submissions"], 1);
+}
@@ -0,0 +1,109 @@
+//! 班级内学生数据擦除集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_class_student_erasure() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("erase").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!(
+        "/api/v1/classes/{}/students/{}/erasure",
+        s.class.id, s.student.id
+    );
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 90.0, "comment": "不错" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 学生无权预览，也不能擦除教师的数据
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let teacher_url = format!(
+        "/api/v1/classes/{}/students/{}/erasure",
+        s.class.id, s.teacher.id
+    );
+    let (status, body) = send(&app, get(&teacher_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ClassErasureInvalid as i32);
+
+    // 预览只统计不删除
+    let (status, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["counts"]["submissions"], 1);
+    assert_eq!(body["data"]["counts"]["grades"], 1);
+    assert_eq!(body["data"]["total"], 2);
+    let token = body["data"]["confirm_token"].as_str().unwrap().to_string();
+
+    // 伪造的令牌被拒绝
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "confirm_token": "0.invalid" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::ClassErasureConfirmInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "confirm_token": token, "reason": "学生申请删除" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["total"], 2);
+
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{}", submission.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // 数据已变化，同一令牌不能重复使用
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "confirm_token": token }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::ClassErasureConfirmInvalid as i32);
+
+    let (status, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["total"], 0);
+}
@@ -0,0 +1,144 @@
+//! 班级动态流集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_class_feed_aggregates_events() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("feed").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/feed", s.class.id);
+
+    let late_student = ctx.create_user("feed_late", UserRole::User).await;
+    ctx.join_class(&late_student, &s.class, ClassUserRole::Student)
+        .await;
+    let submissions = [
+        ctx.create_submission(&s.student, &s.homework, "答案").await,
+        ctx.create_submission(&late_student, &s.homework, "答案")
+            .await,
+    ];
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/notifications",
+            Some(&s.teacher_token),
+            json!({ "class_id": s.class.id, "title": "明天停课" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 连续批改同一作业只产生一条评分动态
+    for submission in &submissions {
+        let (status, _) = send(
+            &app,
+            post_json(
+                "/api/v1/grades",
+                Some(&s.teacher_token),
+                json!({ "submission_id": submission.id, "score": 90.0 }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, body) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    let types: Vec<&str> = items
+        .iter()
+        .map(|item| item["event_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        types,
+        [
+            "grade_released",
+            "announcement",
+            "member_joined",
+            "homework_created",
+            "member_joined",
+        ]
+    );
+    assert_eq!(items[0]["reference_id"], s.homework.id);
+    assert_eq!(items[1]["title"], "明天停课");
+    assert_eq!(items[2]["actor_id"], late_student.id);
+    assert_eq!(items[3]["title"], "feed 作业");
+    assert_eq!(body["data"]["has_more"], false);
+
+    // 按类型筛选
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("{url}?event_type=member_joined"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 2);
+
+    // 非班级成员不能查看，管理员可以
+    let (status, _) = send(&app, get(&url, Some(&s.outsider_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(&app, get(&url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_class_feed_cursor_pagination() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("feedpage").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/feed", s.class.id);
+
+    for title in ["作业一", "作业二", "作业三"] {
+        ctx.create_homework(&s.teacher, &s.class, title).await;
+    }
+
+    // 共 5 条动态，每页 2 条
+    let mut ids = Vec::new();
+    let mut before_id: Option<i64> = None;
+    for expected_more in [true, true, false] {
+        let page_url = match before_id {
+            Some(id) => format!("{url}?size=2&before_id={id}"),
+            None => format!("{url}?size=2"),
+        };
+        let (status, body) = send(&app, get(&page_url, Some(&s.teacher_token)).to_request()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["has_more"], expected_more);
+        let page: Vec<i64> = body["data"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["id"].as_i64().unwrap())
+            .collect();
+        before_id = page.last().copied();
+        ids.extend(page);
+    }
+
+    assert_eq!(ids.len(), 5);
+    assert!(ids.windows(2).all(|w| w[0] > w[1]));
+
+    // 超出每页上限时报错，不再静默截断
+    let (status, body) = send(
+        &app,
+        get(&format!("{url}?size=1000"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::PageSizeExceeded as i32);
+    assert_eq!(body["data"]["page_size_limit"], 100);
+}
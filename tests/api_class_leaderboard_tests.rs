@@ -0,0 +1,166 @@
+//! 班级排行榜集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send, token_for};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_class_leaderboard() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("board").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/leaderboard", s.class.id);
+
+    let peer = ctx.create_user("board_peer", UserRole::User).await;
+    ctx.join_class(&peer, &s.class, ClassUserRole::Student)
+        .await;
+    let rep = ctx.create_user("board_rep", UserRole::User).await;
+    ctx.join_class(&rep, &s.class, ClassUserRole::ClassRepresentative)
+        .await;
+    let (peer_token, rep_token) = (token_for(&peer), token_for(&rep));
+
+    // 尚未截止的作业不计入排行榜
+    let open_homework = ctx
+        .create_homework(&s.teacher, &s.class, "未截止作业")
+        .await;
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{}", open_homework.id),
+            Some(&s.teacher_token),
+            json!({ "deadline": "2099-01-01T00:00:00Z" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    for (student, homework, score) in [
+        (&s.student, &s.homework, 80.0),
+        (&peer, &s.homework, 100.0),
+        (&rep, &s.homework, 60.0),
+        (&s.student, &open_homework, 0.0),
+    ] {
+        let submission = ctx.create_submission(student, homework, "答案").await;
+        let (status, _) = send(
+            &app,
+            post_json(
+                "/api/v1/grades",
+                Some(&s.teacher_token),
+                json!({ "submission_id": submission.id, "score": score }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // 未开启时学生与课代表看不到任何成绩，班级外用户无权访问
+    for token in [&s.student_token, &rep_token] {
+        let (status, body) = send(&app, get(&url, Some(token)).to_request()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], ErrorCode::ClassLeaderboardDisabled as i32);
+        assert!(body["data"].is_null());
+    }
+    let (status, body) = send(&app, get(&url, Some(&s.outsider_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassPermissionDenied as i32);
+
+    // 教师可以预览，看到全部学生的实名
+    let (status, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["enabled"], false);
+    assert_eq!(body["data"]["participant_count"], 3);
+    let entries = body["data"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0]["user_id"], peer.id);
+    assert_eq!(entries[1]["user_id"], s.student.id);
+    assert_eq!(entries[1]["score"], 80.0);
+    assert_eq!(entries[2]["display_name"], "board_rep");
+
+    // 只有班级教师可以设置
+    let (status, _) = send(
+        &app,
+        put_json(&url, Some(&rep_token), json!({ "enabled": true })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "enabled": true, "top_n": 0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "enabled": true, "top_n": 1 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["metric"], "average_percent");
+
+    // 学生只看到前 N 名与本人名次，未署名的同学匿名
+    let (status, body) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0]["display_name"].is_null());
+    assert!(entries[0].get("user_id").is_none());
+    assert_eq!(entries[0]["score"], 100.0);
+    assert_eq!(body["data"]["me"]["rank"], 2);
+    assert_eq!(body["data"]["me"]["is_me"], true);
+    assert_eq!(body["data"]["me"]["display_name"], "board_student");
+    assert_eq!(body["data"]["opted_in"], false);
+
+    // 署名后显示姓名，教师不能设置署名
+    let opt_in_url = format!("{url}/opt-in");
+    let (status, _) = send(
+        &app,
+        put_json(&opt_in_url, Some(&peer_token), json!({ "opt_in": true })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        put_json(
+            &opt_in_url,
+            Some(&s.teacher_token),
+            json!({ "opt_in": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["entries"][0]["display_name"], "board_peer");
+
+    // 重新关闭后课代表再次无法查看
+    let (status, _) = send(
+        &app,
+        put_json(&url, Some(&s.teacher_token), json!({ "enabled": false })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, get(&url, Some(&rep_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassLeaderboardDisabled as i32);
+}
@@ -0,0 +1,165 @@
+//! 班级实名要求集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_real_name_required_on_join() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("realname").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let class_url = format!("/api/v1/classes/{}", s.class.id);
+    let join_url = format!("/api/v1/classes/{}/students", s.class.id);
+
+    // 无效正则被拒绝
+    let (status, _) = send(
+        &app,
+        put_json(
+            &class_url,
+            Some(&s.teacher_token),
+            json!({ "require_real_name": true, "profile_name_pattern": "(" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 要求“8 位学号 + 姓名”
+    let (status, body) = send(
+        &app,
+        put_json(
+            &class_url,
+            Some(&s.teacher_token),
+            json!({ "require_real_name": true, "profile_name_pattern": r"\d{8}\p{Han}{2,4}" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["require_real_name"], true);
+
+    let (student, token) = ctx
+        .create_user_with_token("realname_new", UserRole::User)
+        .await;
+    let (status, body) = send(
+        &app,
+        post_json(
+            &join_url,
+            Some(&token),
+            json!({ "invite_code": s.class.invite_code }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ClassProfileNameRequired as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &join_url,
+            Some(&token),
+            json!({ "invite_code": s.class.invite_code, "profile_name": "张三" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ClassProfileNameInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &join_url,
+            Some(&token),
+            json!({ "invite_code": s.class.invite_code, "profile_name": " 20240001张三 " }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["profile_name"], "20240001张三");
+
+    // 成员列表以班级内姓名代替显示名，并支持按班级内姓名搜索
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("{join_url}?search=20240001"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["user_id"], student.id);
+    assert_eq!(items[0]["user"]["display_name"], "20240001张三");
+}
+
+#[actix_web::test]
+async fn test_teacher_edits_profile_name() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("realnameedit").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let member_url = format!("/api/v1/classes/{}/students/{}", s.class.id, s.student.id);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &member_url,
+            Some(&s.teacher_token),
+            json!({ "profile_name": "李四（旁听）" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["profile_name"], "李四（旁听）");
+
+    // 活跃度报告同样使用班级内姓名
+    let (_, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/classes/{}/activity-report", s.class.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["items"][0]["display_name"], "李四（旁听）");
+
+    // 空字符串清除
+    let (status, body) = send(
+        &app,
+        put_json(
+            &member_url,
+            Some(&s.teacher_token),
+            json!({ "profile_name": "" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["profile_name"], serde_json::Value::Null);
+
+    // 学生不能修改
+    let (status, _) = send(
+        &app,
+        put_json(
+            &member_url,
+            Some(&s.student_token),
+            json!({ "profile_name": "王五" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,120 @@
+//! 班级角色名称集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_class_role_labels() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("role_label").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/role-labels", s.class.id);
+    let member_url = format!("/api/v1/classes/{}/students/{}", s.class.id, s.student.id);
+    let list_url = format!("/api/v1/classes/{}/students", s.class.id);
+
+    // 未配置时使用内置名称
+    let (status, body) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["customized"], false);
+    assert_eq!(body["data"]["role_names"][1]["name"], "课代表");
+
+    // 学生不能修改；标识格式无效被拒绝
+    let labels = json!({ "labels": [
+        { "key": "ta", "label": "助教", "role": "class_representative" },
+        { "key": "lead", "label": "组长", "role": "class_representative" },
+    ] });
+    let (status, _) = send(
+        &app,
+        put_json(&url, Some(&s.student_token), labels.clone()).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "labels": [{ "key": "TA", "label": "助教", "role": "class_representative" }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ClassRoleLabelInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        put_json(&url, Some(&s.teacher_token), labels).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["role_names"][1]["name"], "助教");
+
+    // 指定名称后角色随之变为名称对应的角色；与指定角色冲突时拒绝
+    let (status, body) = send(
+        &app,
+        put_json(
+            &member_url,
+            Some(&s.teacher_token),
+            json!({ "role": "teacher", "role_label": "lead" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ClassRoleLabelInvalid as i32);
+    let (status, body) = send(
+        &app,
+        put_json(
+            &member_url,
+            Some(&s.teacher_token),
+            json!({ "role_label": "lead" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["role"], "class_representative");
+    assert_eq!(body["data"]["role_label"], "lead");
+
+    let (status, body) = send(&app, get(&list_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let student = body["data"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["user_id"] == s.student.id)
+        .unwrap()
+        .clone();
+    assert_eq!(student["role_name"], "组长");
+
+    // 删除名称后成员回到该角色的默认名称
+    let (status, _) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "labels": [{ "key": "ta", "label": "助教", "role": "class_representative" }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, get(&list_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let student = body["data"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["user_id"] == s.student.id)
+        .unwrap()
+        .clone();
+    assert!(student["role_label"].is_null());
+    assert_eq!(student["role_name"], "助教");
+}
@@ -0,0 +1,66 @@
+//! 班级名单导出集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+
+use common::{TestContext, build_app, get, send, send_raw};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_export_roster() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("roster").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/roster/export", s.class.id);
+
+    // 默认导出 PDF
+    let (status, content_type, body) =
+        send_raw(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/pdf");
+    assert!(body.starts_with(b"%PDF"));
+
+    let (status, content_type, body) = send_raw(
+        &app,
+        get(
+            &format!("{url}?format=xlsx&role=student&columns=email,signature"),
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.contains("spreadsheetml"));
+    assert!(body.starts_with(b"PK"));
+
+    // 学生与其他教师无权导出
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, other_token) = ctx
+        .create_user_with_token("roster_other_teacher", UserRole::Teacher)
+        .await;
+    let (status, body) = send(&app, get(&url, Some(&other_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassPermissionDenied as i32);
+}
+
+#[actix_web::test]
+async fn test_export_roster_invalid_params() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("rostercheck").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/roster/export", s.class.id);
+
+    for query in ["format=csv", "columns=email,password_hash"] {
+        let (status, body) = send(
+            &app,
+            get(&format!("{url}?{query}"), Some(&s.teacher_token)).to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+        assert_eq!(body["code"], ErrorCode::BadRequest as i32);
+    }
+}
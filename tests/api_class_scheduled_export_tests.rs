@@ -0,0 +1,206 @@
+//! 班级定时导出集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::services::classes::scheduled_export_job::run_due_scheduled_exports;
+
+#[actix_web::test]
+async fn test_scheduled_export_crud_and_validation() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("schedexport").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/scheduled-exports", s.class.id);
+
+    // 学生不能管理定时导出
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 每周执行须指定星期，Webhook 须为 https 且不能指向内网，未配置邮件中继时不能使用邮件投递
+    let hook = "https://example.com/hook";
+    let internal_hooks = [
+        "https://127.0.0.1/hook",
+        "https://localhost:8443/hook",
+        "https://10.0.0.8/hook",
+        "https://169.254.169.254/latest/meta-data",
+        "https://[::1]/hook",
+        "https://[fd00::1]/hook",
+    ];
+    let internal_bodies = internal_hooks.map(|target| {
+        json!({ "frequency": "daily", "hour": 8, "delivery": "webhook", "target": target })
+    });
+    for body in [
+        json!({ "frequency": "weekly", "hour": 8, "delivery": "webhook", "target": hook }),
+        json!({ "frequency": "daily", "hour": 24, "delivery": "webhook", "target": hook }),
+        json!({
+            "frequency": "daily",
+            "hour": 8,
+            "delivery": "webhook",
+            "target": "http://example.com/hook"
+        }),
+        json!({
+            "frequency": "daily",
+            "hour": 8,
+            "delivery": "email",
+            "target": "teacher@example.com"
+        }),
+    ]
+    .into_iter()
+    .chain(internal_bodies)
+    {
+        let (status, resp) = send(
+            &app,
+            post_json(&url, Some(&s.teacher_token), body).to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(resp["code"], ErrorCode::ClassScheduledExportInvalid as i32);
+    }
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({
+                "frequency": "weekly",
+                "weekday": 4,
+                "hour": 9,
+                "delivery": "webhook",
+                "target": hook
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["kind"], "class_report");
+    assert_eq!(body["data"]["enabled"], true);
+    let export_id = body["data"]["id"].as_i64().unwrap();
+
+    // 下一次执行在周五 09:00 UTC
+    let next_run: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(body["data"]["next_run_at"].clone()).unwrap();
+    assert_eq!(chrono::Datelike::weekday(&next_run), chrono::Weekday::Fri);
+    assert_eq!(chrono::Timelike::hour(&next_run), 9);
+    assert!(next_run > chrono::Utc::now());
+
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 1);
+
+    // 改为每天执行后不再保留星期
+    let item_url = format!("{url}/{export_id}");
+    let (status, body) = send(
+        &app,
+        put_json(
+            &item_url,
+            Some(&s.teacher_token),
+            json!({ "frequency": "daily", "hour": 6 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["frequency"], "daily");
+    assert!(body["data"]["weekday"].is_null());
+    let next_run: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(body["data"]["next_run_at"].clone()).unwrap();
+    assert_eq!(chrono::Timelike::hour(&next_run), 6);
+    assert!(next_run - chrono::Utc::now() <= chrono::Duration::days(1));
+
+    let (status, _) = send(&app, delete(&item_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, delete(&item_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::ClassScheduledExportNotFound as i32);
+}
+
+#[actix_web::test]
+async fn test_failed_scheduled_export_notifies_and_disables() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("schedfail").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/scheduled-exports", s.class.id);
+
+    // .invalid 域名不会被解析，保存时允许，投递时失败
+    let (_, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({
+                "frequency": "daily",
+                "hour": 0,
+                "delivery": "webhook",
+                "target": "https://hwsystem-export.invalid/hook"
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    let export_id = body["data"]["id"].as_i64().unwrap();
+
+    // 未到执行时间时不执行
+    let now = chrono::Utc::now().timestamp();
+    assert_eq!(run_due_scheduled_exports(&ctx.storage, now).await, 0);
+    let export = ctx
+        .storage
+        .get_class_scheduled_export(s.class.id, export_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(export.consecutive_failures, 0);
+    assert!(export.last_run_at.is_none());
+
+    // 连续失败达到上限（默认 3 次）后停用，每次失败都通知创建者
+    let mut at = now;
+    for attempt in 1..=3 {
+        at += 86400 * 2;
+        assert_eq!(run_due_scheduled_exports(&ctx.storage, at).await, 0);
+        let export = ctx
+            .storage
+            .get_class_scheduled_export(s.class.id, export_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(export.consecutive_failures, attempt);
+        assert!(export.last_error.is_some());
+        assert!(export.next_run_at.timestamp() > at);
+        assert_eq!(export.enabled, attempt < 3);
+    }
+
+    let (_, body) = send(
+        &app,
+        get("/api/v1/notifications", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    let items = body["data"]["items"].as_array().unwrap();
+    let failures: Vec<_> = items
+        .iter()
+        .filter(|n| n["notification_type"] == "scheduled_export_failed")
+        .collect();
+    assert_eq!(failures.len(), 3);
+
+    // 停用后不再执行；重新启用时清零失败次数
+    assert_eq!(
+        run_due_scheduled_exports(&ctx.storage, at + 86400 * 2).await,
+        0
+    );
+    let (_, body) = send(
+        &app,
+        put_json(
+            &format!("{url}/{export_id}"),
+            Some(&s.teacher_token),
+            json!({ "enabled": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["enabled"], true);
+    assert_eq!(body["data"]["consecutive_failures"], 0);
+}
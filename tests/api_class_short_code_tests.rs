@@ -0,0 +1,87 @@
+//! 班级短码集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_class_short_code_generate_and_join() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("shortcode").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/short-code", s.class.id);
+
+    // 学生不能生成短码
+    let (status, _) = send(
+        &app,
+        post_json(&url, Some(&s.student_token), json!({})).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        post_json(&url, Some(&s.teacher_token), json!({})).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let code = body["data"]["code"].as_str().unwrap().to_string();
+    assert_eq!(code.len(), 6);
+    assert!(code.chars().all(|c| c.is_ascii_digit()));
+    assert_eq!(body["data"]["rotation_minutes"], 10);
+
+    // 有效期内返回同一个短码
+    let (status, body) = send(
+        &app,
+        post_json(&url, Some(&s.admin_token), json!({})).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["code"], code.as_str());
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/classes/join/short-code",
+            Some(&s.outsider_token),
+            json!({ "code": "12ab" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::ClassShortCodeInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/classes/join/short-code",
+            Some(&s.outsider_token),
+            json!({ "code": code }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["class_id"], s.class.id);
+    assert_eq!(body["data"]["role"], "student");
+
+    // 重复加入
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/classes/join/short-code",
+            Some(&s.outsider_token),
+            json!({ "code": code }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::ClassAlreadyJoined as i32);
+}
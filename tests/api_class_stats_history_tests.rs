@@ -0,0 +1,80 @@
+//! 班级统计趋势集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+
+use common::{TestContext, build_app, get, send};
+use rust_hwsystem_next::models::grades::requests::CreateGradeRequest;
+
+#[actix_web::test]
+async fn test_class_stats_history_from_snapshots() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("stats_history").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/stats-history", s.class.id);
+    let today = chrono::Utc::now().date_naive();
+
+    // 快照生成前没有数据点
+    let (status, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["days"], 30);
+    assert!(body["data"]["items"].as_array().unwrap().is_empty());
+
+    // 昨天结束时班级尚未创建，不生成快照
+    let generated = ctx
+        .storage
+        .generate_class_stats_snapshots(today - chrono::Days::new(1))
+        .await
+        .unwrap();
+    assert_eq!(generated, 0);
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    ctx.storage
+        .create_grade(
+            s.teacher.id,
+            CreateGradeRequest {
+                submission_id: submission.id,
+                score: 80.0,
+                comment: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // 重复生成同一天的快照会覆盖
+    for _ in 0..2 {
+        let generated = ctx
+            .storage
+            .generate_class_stats_snapshots(today)
+            .await
+            .unwrap();
+        assert_eq!(generated, 1);
+    }
+
+    let (status, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["stat_date"], today.format("%Y-%m-%d").to_string());
+    assert_eq!(items[0]["member_count"], 2);
+    assert_eq!(items[0]["student_count"], 1);
+    assert_eq!(items[0]["submission_count"], 1);
+    assert_eq!(items[0]["graded_count"], 1);
+    assert_eq!(items[0]["average_score_percent"], 80.0);
+
+    // 管理员可以查看，学生不能
+    let (status, _) = send(&app, get(&url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 天数超出范围
+    let (status, _) = send(
+        &app,
+        get(&format!("{url}?days=0"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,193 @@
+//! 班级生命周期集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_class_lifecycle() {
+    let ctx = TestContext::new().await;
+    let (_, teacher_token) = ctx.create_user_with_token("t1", UserRole::Teacher).await;
+    let (_, student_token) = ctx.create_user_with_token("s1", UserRole::User).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 教师创建班级
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/classes",
+            Some(&teacher_token),
+            json!({ "name": "高一(1)班", "description": "测试班级" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let class_id = body["data"]["id"].as_i64().unwrap();
+    let invite_code = body["data"]["invite_code"].as_str().unwrap().to_string();
+
+    // 学生凭邀请码加入
+    let join_path = format!("/api/v1/classes/{class_id}/students");
+    let (status, _) = send(
+        &app,
+        post_json(
+            &join_path,
+            Some(&student_token),
+            json!({ "invite_code": invite_code }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 重复加入返回冲突
+    let (status, body) = send(
+        &app,
+        post_json(
+            &join_path,
+            Some(&student_token),
+            json!({ "invite_code": invite_code }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::ClassAlreadyJoined as i32);
+
+    // 学生查看班级详情，角色为 student
+    let class_path = format!("/api/v1/classes/{class_id}");
+    let (status, body) = send(&app, get(&class_path, Some(&student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["my_role"], "student");
+    assert_eq!(body["data"]["member_count"], 2);
+
+    // 教师查看学生列表
+    let (status, body) = send(&app, get(&join_path, Some(&teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 2);
+
+    // 教师更新班级
+    let (status, _) = send(
+        &app,
+        put_json(
+            &class_path,
+            Some(&teacher_token),
+            json!({ "name": "高一(2)班" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, get(&class_path, Some(&teacher_token)).to_request()).await;
+    assert_eq!(body["data"]["name"], "高一(2)班");
+
+    // 教师删除班级后不可再访问
+    let (status, _) = send(&app, delete(&class_path, Some(&teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, get(&class_path, Some(&teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_join_with_wrong_invite_code() {
+    let ctx = TestContext::new().await;
+    let scenario = ctx.class_scenario("wrong_code").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &format!("/api/v1/classes/{}/students", scenario.class.id),
+            Some(&scenario.outsider_token),
+            json!({ "invite_code": "NOT-A-CODE" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::ClassInviteCodeInvalid as i32);
+}
+
+#[actix_web::test]
+async fn test_class_list_only_contains_joined_classes() {
+    let ctx = TestContext::new().await;
+    let scenario = ctx.class_scenario("list").await;
+    let other_teacher = ctx.create_user("list_teacher2", UserRole::Teacher).await;
+    ctx.create_class(&other_teacher, "别人的班级").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/classes", Some(&scenario.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], scenario.class.id);
+
+    let (_, body) = send(
+        &app,
+        get("/api/v1/classes", Some(&scenario.outsider_token)).to_request(),
+    )
+    .await;
+    assert!(body["data"]["items"].as_array().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn test_class_membership_cache_invalidated_on_change() {
+    let ctx = TestContext::new().await;
+    let scenario = ctx.class_scenario("member_cache").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let students_path = format!("/api/v1/classes/{}/students", scenario.class.id);
+    let member_path = format!("{students_path}/{}", scenario.student.id);
+
+    // 学生角色无法查看成员列表（此时成员关系已被缓存）
+    let (status, _) = send(
+        &app,
+        get(&students_path, Some(&scenario.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 提升为课代表后立即生效
+    let (status, _) = send(
+        &app,
+        put_json(
+            &member_path,
+            Some(&scenario.teacher_token),
+            json!({ "role": "class_representative" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        get(&students_path, Some(&scenario.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 移出班级后立即失去访问权限
+    let (status, _) = send(
+        &app,
+        delete(&member_path, Some(&scenario.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        get(&member_path, Some(&scenario.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassPermissionDenied as i32);
+}
@@ -0,0 +1,108 @@
+//! 班级成员批量操作集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_batch_class_users() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("batch").await;
+    let classmate = ctx.create_user("batch_classmate", UserRole::User).await;
+    ctx.join_class(&classmate, &s.class, ClassUserRole::Student)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/users/batch", s.class.id);
+
+    // 学生无权批量操作；修改角色必须指定 role
+    let (status, _) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.student_token),
+            json!({ "action": "remove", "user_ids": [classmate.id] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "action": "set_role", "user_ids": [classmate.id] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ClassUserBatchInvalid as i32);
+
+    // 逐个返回结果：非成员与班级教师失败，其余成功
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({
+                "action": "set_role",
+                "role": "class_representative",
+                "user_ids": [s.student.id, classmate.id, s.outsider.id, s.teacher.id],
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["succeeded"], 2);
+    assert_eq!(body["data"]["failed"], 2);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items[2]["user_id"], s.outsider.id);
+    assert_eq!(items[2]["success"], false);
+    assert_eq!(items[3]["success"], false);
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/classes/{}/students/{}", s.class.id, classmate.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["role"], "class_representative");
+
+    // 批量移出
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "action": "remove", "user_ids": [s.student.id, classmate.id] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["succeeded"], 2);
+
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/classes/{}/students/{}", s.class.id, classmate.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
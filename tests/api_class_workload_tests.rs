@@ -0,0 +1,119 @@
+//! 班级作业负荷集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use chrono::{Duration, Utc};
+
+use common::{TestContext, build_app, get, send};
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_class_workload_aggregates_across_classes() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("workload").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let today_noon = Utc::now()
+        .date_naive()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc();
+
+    // 第二名学生只在本班；第一名学生还在物理班；化学班与本班没有共同学生
+    let classmate = ctx.create_user("workload_classmate", UserRole::User).await;
+    ctx.join_class(&classmate, &s.class, ClassUserRole::Student)
+        .await;
+    let other_teacher = ctx.create_user("workload_other", UserRole::Teacher).await;
+    let shared_class = ctx.create_class(&other_teacher, "物理班").await;
+    ctx.join_class(&s.student, &shared_class, ClassUserRole::Student)
+        .await;
+    let unrelated_class = ctx.create_class(&other_teacher, "化学班").await;
+    ctx.join_class(&s.outsider, &unrelated_class, ClassUserRole::Student)
+        .await;
+
+    let day2 = today_noon + Duration::days(2);
+    ctx.create_homework_due(&s.teacher, &s.class, "本班作业", day2)
+        .await;
+    ctx.create_homework_due(&other_teacher, &shared_class, "物理作业", day2)
+        .await;
+    ctx.create_homework_due(&other_teacher, &unrelated_class, "化学作业", day2)
+        .await;
+    ctx.create_homework_due(
+        &other_teacher,
+        &shared_class,
+        "物理实验",
+        today_noon + Duration::days(5),
+    )
+    .await;
+    ctx.create_homework_due(
+        &other_teacher,
+        &shared_class,
+        "期末报告",
+        today_noon + Duration::days(30),
+    )
+    .await;
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/classes/{}/workload?weeks=2", s.class.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 14);
+    assert_eq!(
+        items[0]["date"],
+        Utc::now().date_naive().format("%Y-%m-%d").to_string()
+    );
+    assert_eq!(items[0]["homework_count"], 0);
+
+    assert_eq!(items[2]["date"], day2.format("%Y-%m-%d").to_string());
+    assert_eq!(items[2]["homework_count"], 2);
+    assert_eq!(items[2]["students_with_due"], 2);
+    assert_eq!(items[2]["max_per_student"], 2);
+
+    assert_eq!(items[5]["homework_count"], 1);
+    assert_eq!(items[5]["students_with_due"], 1);
+    assert_eq!(items[5]["max_per_student"], 1);
+
+    let total: i64 = items
+        .iter()
+        .map(|item| item["homework_count"].as_i64().unwrap())
+        .sum();
+    assert_eq!(total, 3);
+}
+
+#[actix_web::test]
+async fn test_class_workload_permissions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("workloadperm").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/workload", s.class.id);
+
+    let (status, body) = send(&app, get(&url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["weeks"], 4);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 28);
+
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (_, other_token) = ctx
+        .create_user_with_token("workloadperm_other", UserRole::Teacher)
+        .await;
+    let (status, _) = send(&app, get(&url, Some(&other_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        get(&format!("{url}?weeks=13"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
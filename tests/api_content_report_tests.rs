@@ -0,0 +1,218 @@
+//! 内容举报集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send, token_for};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_content_reports() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("report").await;
+    let classmate = ctx.create_user("report_classmate", UserRole::User).await;
+    ctx.join_class(&classmate, &s.class, ClassUserRole::Student)
+        .await;
+    let classmate_token = token_for(&classmate);
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/reports", s.class.id);
+
+    // 开启学生互发私信后，同学给学生发私信；教师给学生的提交写评语
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/classes/{}", s.class.id),
+            Some(&s.teacher_token),
+            json!({ "allow_student_messages": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&classmate_token),
+            json!({ "class_id": s.class.id, "recipient_id": s.student.id, "content": "难听的话" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let message_id = body["data"]["id"].as_i64().unwrap();
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 60.0, "comment": "不当评语" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grade_id = body["data"]["id"].as_i64().unwrap();
+
+    let report = |content_type: &str, content_id: i64, reason: &str| json!({ "content_type": content_type, "content_id": content_id, "reason": reason });
+
+    // 理由不能为空；只有接收方能举报私信，作者不能举报自己的内容
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.student_token),
+            report("message", message_id, "  "),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ContentReportInvalid as i32);
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            report("message", message_id, "骚扰"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::ContentReportTargetNotFound as i32);
+    let (status, _) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.outsider_token),
+            report("message", message_id, "骚扰"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.student_token),
+            report("message", message_id, "骚扰"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["author_id"], classmate.id);
+    assert_eq!(body["data"]["excerpt"], "难听的话");
+    assert_eq!(body["data"]["status"], "pending");
+    let message_report_id = body["data"]["id"].as_i64().unwrap();
+
+    // 待处理期间不能重复举报
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.student_token),
+            report("message", message_id, "再次举报"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::ContentReportDuplicate as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.student_token),
+            report("grade_comment", grade_id, "评语带有侮辱"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let comment_report_id = body["data"]["id"].as_i64().unwrap();
+
+    // 只有班级教师可以查看举报队列
+    let (status, _) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(
+        &app,
+        get(&format!("{url}?status=pending"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 2);
+
+    // 警告作者：内容保留
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("{url}/{message_report_id}"),
+            Some(&s.teacher_token),
+            json!({ "action": "warn_user", "note": "请注意文明用语" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "resolved");
+    assert_eq!(body["data"]["resolution"], "warn_user");
+
+    // 删除内容：评语被清空，分数保留
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("{url}/{comment_report_id}"),
+            Some(&s.teacher_token),
+            json!({ "action": "delete_content" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/grades/{grade_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["comment"].is_null());
+    assert_eq!(body["data"]["score"], 60.0);
+
+    // 已处理的举报不能再次处理
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("{url}/{comment_report_id}"),
+            Some(&s.teacher_token),
+            json!({ "action": "dismiss" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::ContentReportInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        get(&format!("{url}?status=pending"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["items"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,93 @@
+//! Cookie 认证与 CSRF 双提交校验集成测试
+
+mod common;
+
+use actix_web::cookie::Cookie;
+use actix_web::http::StatusCode;
+use actix_web::test::{self, TestRequest};
+use serde_json::json;
+
+use common::{TestContext, build_app, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+use rust_hwsystem_next::utils::jwt::{ACCESS_TOKEN_COOKIE, CSRF_COOKIE, CSRF_HEADER};
+
+const CSRF_TOKEN: &str = "test-csrf-token";
+
+#[actix_web::test]
+async fn test_cookie_auth_requires_csrf_for_writes() {
+    let ctx = TestContext::new().await;
+    let (_, token) = ctx
+        .create_user_with_token("cookie_user", UserRole::User)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let with_cookies = |req: TestRequest| {
+        req.cookie(Cookie::new(ACCESS_TOKEN_COOKIE, token.clone()))
+            .cookie(Cookie::new(CSRF_COOKIE, CSRF_TOKEN))
+    };
+
+    // 读请求仅凭 Cookie 即可认证
+    let (status, body) = send(
+        &app,
+        with_cookies(TestRequest::get().uri("/api/v1/auth/me")).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["user"]["username"], "cookie_user");
+
+    // 写请求缺少 CSRF 头被拒绝
+    let update = json!({ "display_name": "Cookie 用户" });
+    let (status, body) = send(
+        &app,
+        with_cookies(put_json("/api/v1/auth/me", None, update.clone())).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::CsrfTokenInvalid as i32);
+
+    // CSRF 头与 Cookie 不一致
+    let (status, _) = send(
+        &app,
+        with_cookies(put_json("/api/v1/auth/me", None, update.clone()))
+            .insert_header((CSRF_HEADER, "forged"))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        with_cookies(put_json("/api/v1/auth/me", None, update.clone()))
+            .insert_header((CSRF_HEADER, CSRF_TOKEN))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["user"]["display_name"], "Cookie 用户");
+
+    // 使用 Authorization 头的请求不受 CSRF 校验影响
+    let (status, _) = send(
+        &app,
+        put_json("/api/v1/auth/me", Some(&token), update).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 登出同时清除访问令牌与 CSRF Cookie
+    let resp = test::call_service(
+        &app,
+        TestRequest::post().uri("/api/v1/auth/logout").to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let cleared: Vec<String> = resp
+        .response()
+        .cookies()
+        .filter(|c| c.value().is_empty())
+        .map(|c| c.name().to_string())
+        .collect();
+    for name in ["refresh_token", ACCESS_TOKEN_COOKIE, CSRF_COOKIE] {
+        assert!(cleared.iter().any(|c| c == name), "{name} not cleared");
+    }
+}
@@ -0,0 +1,136 @@
+//! SQLite 数据库维护集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send, token_for};
+use rust_hwsystem_next::config::AppConfig;
+use rust_hwsystem_next::models::organizations::requests::CreateOrganizationRequest;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_database_status_checkpoint_and_backup() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("dbmaint").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 测试使用内存数据库：没有数据库文件与 WAL
+    let (status, body) = send(
+        &app,
+        get("/api/v1/admin/database", Some(&s.admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["backend"], "sqlite");
+    assert_eq!(body["data"]["file_path"], serde_json::Value::Null);
+    assert!(body["data"]["page_count"].as_i64().unwrap() > 0);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/database/checkpoint?mode=passive",
+            Some(&s.admin_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["mode"], "passive");
+    assert_eq!(body["data"]["busy"], false);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/database/backup",
+            Some(&s.admin_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let file_name = body["data"]["file_name"].as_str().unwrap();
+    let path = std::path::Path::new(&AppConfig::get().backup.dir).join(file_name);
+    assert!(path.exists());
+    assert_eq!(
+        body["data"]["size"].as_i64().unwrap(),
+        std::fs::metadata(&path).unwrap().len() as i64
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[actix_web::test]
+async fn test_database_maintenance_requires_admin() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("dbmaintperm").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, _) = send(
+        &app,
+        get("/api/v1/admin/database", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/database/backup",
+            Some(&s.teacher_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 备份与检查点涉及全部组织的数据，组织管理员无权操作
+    let org = ctx
+        .storage
+        .create_organization(CreateOrganizationRequest {
+            name: "dbmaint".to_string(),
+            slug: "dbmaint".to_string(),
+            description: None,
+        })
+        .await
+        .expect("Failed to create organization");
+    let org_admin = token_for(
+        &ctx.create_org_user("dbmaint_org_admin", UserRole::Admin, Some(org.id))
+            .await,
+    );
+    let (status, _) = send(
+        &app,
+        get("/api/v1/admin/database", Some(&org_admin)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    for path in ["backup", "checkpoint?mode=passive"] {
+        let (status, _) = send(
+            &app,
+            post_json(
+                &format!("/api/v1/admin/database/{path}"),
+                Some(&org_admin),
+                json!({}),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{path}");
+    }
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/database/checkpoint?mode=eager",
+            Some(&s.admin_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
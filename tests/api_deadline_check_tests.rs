@@ -0,0 +1,145 @@
+//! 作业截止时间冲突检查集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+
+use common::{TestContext, build_app, post_json, send};
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_check_deadline_finds_conflicts_across_classes() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("deadline").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/homeworks/check-deadline", s.class.id);
+    let deadline = DateTime::parse_from_rfc3339("2030-03-01T12:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+
+    // 学生同时在另一位教师的班级中；第三个班级与本班没有共同学生
+    let other_teacher = ctx.create_user("deadline_other", UserRole::Teacher).await;
+    let shared_class = ctx.create_class(&other_teacher, "物理班").await;
+    ctx.join_class(&s.student, &shared_class, ClassUserRole::Student)
+        .await;
+    let unrelated_class = ctx.create_class(&other_teacher, "化学班").await;
+    ctx.join_class(&s.outsider, &unrelated_class, ClassUserRole::Student)
+        .await;
+
+    let same_class_hw = ctx
+        .create_homework_due(
+            &s.teacher,
+            &s.class,
+            "本班作业",
+            deadline - Duration::hours(5),
+        )
+        .await;
+    ctx.create_homework_due(
+        &other_teacher,
+        &shared_class,
+        "物理实验报告",
+        deadline + Duration::hours(10),
+    )
+    .await;
+    ctx.create_homework_due(&other_teacher, &unrelated_class, "化学作业", deadline)
+        .await;
+    ctx.create_homework_due(
+        &other_teacher,
+        &shared_class,
+        "下周的物理作业",
+        deadline + Duration::days(7),
+    )
+    .await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "deadline": "2030-03-01T12:00:00Z" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["window_hours"], 48);
+    let conflicts = body["data"]["conflicts"].as_array().unwrap();
+    let titles: Vec<&str> = conflicts
+        .iter()
+        .map(|c| c["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["本班作业", "物理实验报告"]);
+    assert_eq!(conflicts[0]["same_class"], true);
+    assert_eq!(conflicts[1]["same_class"], false);
+    assert_eq!(conflicts[1]["class_name"], "物理班");
+    assert_eq!(conflicts[1]["shared_student_count"], 1);
+
+    // 缩小窗口并排除正在修改的作业
+    let (status, body) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({
+                "deadline": "2030-03-01T12:00:00Z",
+                "window_hours": 6,
+                "exclude_homework_id": same_class_hw.id
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["conflicts"], json!([]));
+}
+
+#[actix_web::test]
+async fn test_check_deadline_permissions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("deadlineperm").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/classes/{}/homeworks/check-deadline", s.class.id);
+
+    let other_teacher = ctx
+        .create_user_with_token("deadlineperm_other", UserRole::Teacher)
+        .await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&other_teacher.1),
+            json!({ "deadline": "2030-03-01T12:00:00Z" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.student_token),
+            json!({ "deadline": "2030-03-01T12:00:00Z" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            &url,
+            Some(&s.teacher_token),
+            json!({ "deadline": "2030-03-01T12:00:00Z", "window_hours": 0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,70 @@
+//! 开发模式示例数据集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+
+use common::{TestContext, build_app, get, send};
+
+#[actix_web::test]
+async fn test_dev_fixtures_are_role_aware() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(&app, get("/api/v1/dev/fixtures", None).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = body["data"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"auth-me"));
+    assert!(names.contains(&"homeworks"));
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/dev/fixtures/auth-me?role=teacher", None).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], 0);
+    assert_eq!(body["data"]["user"]["role"], "teacher");
+    assert!(body["data"]["user"].get("password_hash").is_none());
+
+    // 学生视角附带自己的提交，教师视角附带统计摘要
+    let (_, student) = send(
+        &app,
+        get("/api/v1/dev/fixtures/homeworks", None).to_request(),
+    )
+    .await;
+    let item = &student["data"]["items"][0];
+    assert_eq!(item["my_submission"]["status"], "graded");
+    assert!(item["stats_summary"].is_null());
+    let (_, teacher) = send(
+        &app,
+        get("/api/v1/dev/fixtures/homeworks?role=teacher", None).to_request(),
+    )
+    .await;
+    let item = &teacher["data"]["items"][0];
+    assert!(item["my_submission"].is_null());
+    assert_eq!(item["stats_summary"]["total_students"], 30);
+
+    // 学生无权访问的接口返回真实接口的 403
+    let (status, _) = send(
+        &app,
+        get("/api/v1/dev/fixtures/homeworks-teacher-stats", None).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(&app, get("/api/v1/dev/fixtures/unknown", None).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        get("/api/v1/dev/fixtures/auth-me?role=root", None).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,203 @@
+//! 考试模式访问日志集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+
+#[actix_web::test]
+async fn test_exam_access_log_flags_anomalies() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("exam").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&s.teacher_token),
+            json!({
+                "class_id": s.class.id,
+                "title": "期中考试",
+                "max_score": 100.0,
+                "exam_mode": true
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["exam_mode"], true);
+    let homework_id = body["data"]["id"].as_i64().unwrap();
+
+    // 学生从一个 IP 查看，又从另一个 IP 立即提交
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.student_token),
+        )
+        .peer_addr("10.0.0.1:40000".parse().unwrap())
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "我的答案" }),
+        )
+        .peer_addr("10.0.0.2:40000".parse().unwrap())
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 教师查看不产生访问记录
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/exam-access"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    let summary = &items[0];
+    assert_eq!(summary["user_id"], s.student.id);
+    assert_eq!(summary["view_count"], 1);
+    assert_eq!(summary["submit_attempts"], 1);
+    assert_eq!(summary["ip_addresses"].as_array().unwrap().len(), 2);
+    let anomalies: Vec<&str> = summary["anomalies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v.as_str())
+        .collect();
+    assert!(anomalies.contains(&"multiple_ips"));
+    assert!(anomalies.contains(&"fast_submission"));
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!(
+                "/api/v1/homeworks/{homework_id}/exam-access/{}",
+                s.student.id
+            ),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let events: Vec<&str> = body["data"]["logs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|log| log["event"].as_str())
+        .collect();
+    assert_eq!(events, vec!["view", "ip_change", "submit"]);
+
+    // 学生无权查看访问记录
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/exam-access"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_exam_submission_flags_clock_skew() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("exam_clock").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 服务器时间接口无需登录
+    let (status, body) = send(&app, get("/api/v1/system/time", None).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let server_time = body["data"]["server_time"].as_i64().unwrap();
+    let signed = body["data"]["signed_timestamp"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(signed.starts_with(&format!("{server_time}.")));
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&s.teacher_token),
+            json!({
+                "class_id": s.class.id,
+                "title": "限时测验",
+                "max_score": 100.0,
+                "exam_mode": true
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let homework_id = body["data"]["id"].as_i64().unwrap();
+
+    // 客户端时钟比服务器慢 10 分钟：提交仍然成功，但被标记
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({
+                "homework_id": homework_id,
+                "content": "我的答案",
+                "signed_timestamp": signed,
+                "client_time": server_time - 600_000
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/exam-access"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let anomalies: Vec<&str> = body["data"]["items"][0]["anomalies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v.as_str())
+        .collect();
+    assert!(anomalies.contains(&"clock_skew"));
+}
@@ -0,0 +1,135 @@
+//! 代码文件查看集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+
+use common::{TestContext, build_app, get, send};
+use rust_hwsystem_next::config::AppConfig;
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::User;
+
+/// 在上传目录写入文件并创建记录，返回下载令牌
+async fn store(
+    ctx: &TestContext,
+    user: &User,
+    name: &str,
+    data: &[u8],
+    code_language: Option<&str>,
+) -> String {
+    let dir = &AppConfig::get().upload.dir;
+    std::fs::create_dir_all(dir).unwrap();
+    let stored_name = format!("code-view-{}.bin", uuid::Uuid::new_v4());
+    std::fs::write(format!("{dir}/{stored_name}"), data).unwrap();
+    ctx.storage
+        .upload_file(
+            name,
+            &stored_name,
+            &(data.len() as i64),
+            "text/plain",
+            code_language,
+            user.id,
+        )
+        .await
+        .expect("Failed to create file record")
+        .download_token
+}
+
+#[actix_web::test]
+async fn test_code_file_content_and_language() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("codeview").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let token = store(
+        &ctx,
+        &s.student,
+        "solution.py",
+        "\u{feff}def main():\n    print('你好')\n".as_bytes(),
+        Some("python"),
+    )
+    .await;
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/files/code/{token}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["file_name"], "solution.py");
+    assert_eq!(body["data"]["language"], "python");
+    assert_eq!(body["data"]["line_count"], 2);
+    assert_eq!(body["data"]["content"], "def main():\n    print('你好')\n");
+
+    // 未记录语言的旧文件按文件名识别，普通文本没有语言
+    let legacy = store(&ctx, &s.student, "main.rs", b"fn main() {}", None).await;
+    let (_, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/files/code/{legacy}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["language"], "rust");
+    let notes = store(&ctx, &s.student, "notes.txt", b"hello", None).await;
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/files/code/{notes}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["language"].is_null());
+}
+
+#[actix_web::test]
+async fn test_code_file_guards() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("codeguard").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 二进制内容
+    let binary = store(
+        &ctx,
+        &s.student,
+        "a.out",
+        &[0x7f, b'E', b'L', b'F', 0, 1],
+        None,
+    )
+    .await;
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/files/code/{binary}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["code"], ErrorCode::FilePreviewUnsupported as i32);
+
+    // 超过大小上限
+    let large = vec![b'x'; AppConfig::get().preview.code_max_size + 1];
+    let large = store(&ctx, &s.student, "big.c", &large, Some("c")).await;
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/files/code/{large}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["code"], ErrorCode::FileSizeExceeded as i32);
+}
@@ -0,0 +1,126 @@
+//! 上传文件隔离审核集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+
+use common::{TestContext, build_app, delete, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::files::entities::{QuarantineReason, QuarantinedFile};
+use rust_hwsystem_next::models::files::requests::QuarantinedFileInput;
+use rust_hwsystem_next::models::users::entities::User;
+use rust_hwsystem_next::services::files::quarantine::quarantine_dir;
+
+/// 模拟一次未通过魔术字节校验的上传
+async fn quarantine(ctx: &TestContext, user: &User, name: &str) -> QuarantinedFile {
+    let stored_name = format!(
+        "{}-{}.bin",
+        chrono::Utc::now().timestamp(),
+        uuid::Uuid::new_v4()
+    );
+    let dir = quarantine_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(&stored_name), b"MZ not really a pdf").unwrap();
+
+    ctx.storage
+        .create_quarantined_file(QuarantinedFileInput {
+            user_id: user.id,
+            original_name: name.to_string(),
+            stored_name,
+            file_type: "application/pdf".to_string(),
+            file_size: 19,
+            reason: QuarantineReason::MagicMismatch,
+            detail: Some("文件内容与扩展名 .pdf 不匹配".to_string()),
+        })
+        .await
+        .expect("Failed to quarantine file")
+}
+
+#[actix_web::test]
+async fn test_quarantine_approve_and_delete() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("quarantine").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let approved = quarantine(&ctx, &s.student, "实验报告.pdf").await;
+    let rejected = quarantine(&ctx, &s.student, "可疑文件.pdf").await;
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/files/quarantine", Some(&s.admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["reason"], "magic_mismatch");
+    assert!(items[0].get("stored_name").is_none());
+
+    // 放行后生成下载令牌，文件可正常下载
+    let (status, body) = send(
+        &app,
+        post_json(
+            &format!("/api/v1/files/quarantine/{}/approve", approved.id),
+            Some(&s.admin_token),
+            serde_json::json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["file_name"], "实验报告.pdf");
+    let token = body["data"]["download_token"].as_str().unwrap().to_string();
+    let req = get(
+        &format!("/api/v1/files/download/{token}"),
+        Some(&s.student_token),
+    );
+    let resp = test::call_service(&app, req.to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(!quarantine_dir().join(&approved.stored_name).exists());
+
+    // 删除后移除隔离文件
+    let url = format!("/api/v1/files/quarantine/{}", rejected.id);
+    let (status, _) = send(&app, delete(&url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!quarantine_dir().join(&rejected.stored_name).exists());
+    let (status, body) = send(&app, delete(&url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::QuarantinedFileNotFound as i32);
+
+    let (_, body) = send(
+        &app,
+        get("/api/v1/files/quarantine", Some(&s.admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["items"], serde_json::json!([]));
+}
+
+#[actix_web::test]
+async fn test_quarantine_requires_admin() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("quarantineperm").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let file = quarantine(&ctx, &s.student, "作业.pdf").await;
+
+    let (status, _) = send(
+        &app,
+        get("/api/v1/files/quarantine", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            &format!("/api/v1/files/quarantine/{}/approve", file.id),
+            Some(&s.student_token),
+            serde_json::json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(quarantine_dir().join(&file.stored_name).exists());
+}
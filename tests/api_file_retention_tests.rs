@@ -0,0 +1,177 @@
+//! 文件保留策略集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, put_json, send};
+use rust_hwsystem_next::models::submissions::requests::CreateSubmissionRequest;
+
+#[actix_web::test]
+async fn test_file_retention_policy_and_report() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("retention").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let attachment = ctx
+        .storage
+        .upload_file(
+            "报告.pdf",
+            "retention_attachment.pdf",
+            &2048,
+            "application/pdf",
+            None,
+            s.student.id,
+        )
+        .await
+        .unwrap();
+    ctx.storage
+        .create_submission(
+            s.student.id,
+            CreateSubmissionRequest {
+                homework_id: s.homework.id,
+                part_id: None,
+                content: "答案".to_string(),
+                attachments: Some(vec![attachment.download_token.clone()]),
+                signed_timestamp: None,
+                client_time: None,
+                self_assessment: None,
+            },
+        )
+        .await
+        .unwrap();
+    let orphan = ctx
+        .storage
+        .upload_file(
+            "草稿.pdf",
+            "retention_orphan.pdf",
+            &512,
+            "application/pdf",
+            None,
+            s.student.id,
+        )
+        .await
+        .unwrap();
+
+    let retention = format!("/api/v1/classes/{}/retention", s.class.id);
+    let upcoming = format!("{retention}/upcoming?days=2");
+
+    // 仅班级教师或管理员可管理
+    let (status, _) = send(&app, get(&retention, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(&app, get(&retention, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["submission_files_days"], 730);
+    assert!(body["data"]["archived_at"].is_null());
+    assert!(body["data"]["overrides"].is_null());
+
+    let (status, _) = send(
+        &app,
+        put_json(
+            &retention,
+            Some(&s.teacher_token),
+            json!({ "submission_files_days": 5000 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &retention,
+            Some(&s.teacher_token),
+            json!({ "submission_files_days": 1 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["submission_files_days"], 1);
+    assert_eq!(body["data"]["homework_files_days"], 730);
+
+    // 未归档班级的附件不会到期
+    let (status, body) = send(&app, get(&upcoming, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 0);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/classes/{}", s.class.id),
+            Some(&s.teacher_token),
+            json!({ "archived": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["archived_at"].is_string());
+
+    let (status, body) = send(&app, get(&upcoming, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["file_id"], attachment.id);
+    assert_eq!(items[0]["category"], "submission_attachment");
+    assert_eq!(body["data"]["total_size"], 2048);
+
+    // 全站报告仅管理员可查看，包含孤立上传
+    let (status, _) = send(
+        &app,
+        get(
+            "/api/v1/files/retention/upcoming?days=30",
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        get(
+            "/api/v1/files/retention/upcoming?days=30",
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert!(items.iter().any(|i| i["file_id"] == attachment.id));
+    assert!(
+        items
+            .iter()
+            .any(|i| i["file_id"] == orphan.id && i["category"] == "orphan")
+    );
+
+    // 0 天表示永久保留
+    let (status, _) = send(
+        &app,
+        put_json(
+            &retention,
+            Some(&s.teacher_token),
+            json!({ "submission_files_days": 0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, get(&upcoming, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 0);
+
+    let purged = ctx.storage.purge_files(&[orphan.id]).await.unwrap();
+    assert_eq!(purged.len(), 1);
+    assert!(
+        ctx.storage
+            .get_file_by_id(orphan.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,93 @@
+//! 附件冷存储分层集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, put_json, send, send_raw};
+use rust_hwsystem_next::config::AppConfig;
+use rust_hwsystem_next::models::files::entities::StorageTier;
+use rust_hwsystem_next::models::submissions::requests::CreateSubmissionRequest;
+use rust_hwsystem_next::services::files::tiering::stored_path;
+use rust_hwsystem_next::services::files::tiering_job::move_archived_files;
+
+#[actix_web::test]
+async fn test_archived_attachments_move_to_cold_storage() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("tiering").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let dir = &AppConfig::get().upload.dir;
+    std::fs::create_dir_all(dir).unwrap();
+    let stored_name = format!("tiering-{}.pdf", uuid::Uuid::new_v4());
+    std::fs::write(format!("{dir}/{stored_name}"), b"%PDF-1.4 report").unwrap();
+    let file = ctx
+        .storage
+        .upload_file(
+            "报告.pdf",
+            &stored_name,
+            &15,
+            "application/pdf",
+            None,
+            s.student.id,
+        )
+        .await
+        .unwrap();
+    ctx.storage
+        .create_submission(
+            s.student.id,
+            CreateSubmissionRequest {
+                homework_id: s.homework.id,
+                part_id: None,
+                content: "答案".to_string(),
+                attachments: Some(vec![file.download_token.clone()]),
+                signed_timestamp: None,
+                client_time: None,
+                self_assessment: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // 未归档班级的附件留在热存储
+    let now = chrono::Utc::now().timestamp();
+    assert_eq!(move_archived_files(&ctx.storage, now + 60).await, 0);
+
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/classes/{}", s.class.id),
+            Some(&s.teacher_token),
+            json!({ "archived": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 归档时间晚于阈值时不迁移
+    assert_eq!(move_archived_files(&ctx.storage, now - 86400).await, 0);
+    assert_eq!(move_archived_files(&ctx.storage, now + 60).await, 1);
+
+    let file = ctx.storage.get_file_by_id(file.id).await.unwrap().unwrap();
+    assert_eq!(file.storage_tier, StorageTier::Cold);
+    assert!(file.tiered_at.is_some());
+    assert!(stored_path(&file).exists());
+    assert!(!std::path::Path::new(dir).join(&stored_name).exists());
+    assert_eq!(move_archived_files(&ctx.storage, now + 60).await, 0);
+
+    // 下载时透明读取冷存储
+    let (status, _, body) = send_raw(
+        &app,
+        get(
+            &format!("/api/v1/files/download/{}", file.download_token),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"%PDF-1.4 report");
+}
@@ -0,0 +1,130 @@
+//! 文件上传集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::test::{self, TestRequest};
+
+use common::{TestContext, build_app, send, set_setting};
+use rust_hwsystem_next::config::AppConfig;
+use rust_hwsystem_next::models::ErrorCode;
+
+const BOUNDARY: &str = "hwsystem-upload-boundary";
+
+/// 按顺序拼接 multipart 字段，`filename` 为空时作为普通字段
+fn upload(token: &str, fields: &[(&str, Option<&str>, &str)]) -> TestRequest {
+    let mut body = String::new();
+    for (name, filename, content) in fields {
+        let disposition = match filename {
+            Some(filename) => format!("form-data; name=\"{name}\"; filename=\"{filename}\""),
+            None => format!("form-data; name=\"{name}\""),
+        };
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: {disposition}\r\n\
+             Content-Type: text/plain\r\n\r\n{content}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+    TestRequest::post()
+        .uri("/api/v1/files/upload")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .insert_header((
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(body)
+}
+
+/// 上传目录中的文件数
+fn stored_file_count() -> usize {
+    std::fs::read_dir(&AppConfig::get().upload.dir)
+        .map(|dir| dir.count())
+        .unwrap_or(0)
+}
+
+#[actix_web::test]
+async fn test_upload_limits_fields_and_hash() {
+    let ctx = TestContext::new().await;
+    ctx.init_dynamic_config().await;
+    let s = ctx.class_scenario("upload").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "upload.allowed_types", r#"[".txt"]"#).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "upload.max_size", "64").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 返回边接收边计算的 SHA-256
+    let (status, body) = send(
+        &app,
+        upload(
+            &s.student_token,
+            &[("file", Some("note.txt"), "hello world")],
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["size"], 11);
+    assert_eq!(
+        body["data"]["sha256"],
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+    );
+
+    // 文件前后出现其他字段都拒绝，且不留下已写入的文件
+    let before = stored_file_count();
+    for fields in [
+        [("comment", None, "hi"), ("file", Some("a.txt"), "data")],
+        [("file", Some("a.txt"), "data"), ("comment", None, "hi")],
+    ] {
+        let (status, body) = send(&app, upload(&s.student_token, &fields).to_request()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], ErrorCode::UploadFieldNotAllowed as i32);
+    }
+    assert_eq!(stored_file_count(), before);
+
+    // 超过单文件上限时中止接收并删除部分文件
+    let large = "x".repeat(100);
+    let (status, body) = send(
+        &app,
+        upload(&s.student_token, &[("file", Some("big.txt"), &large)]).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::FileSizeExceeded as i32);
+    assert_eq!(body["message"], "File size exceeds the limit");
+    assert_eq!(stored_file_count(), before);
+
+    // 请求总大小超限时不读取请求体
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "upload.max_size", "4096").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "upload.max_request_size", "512").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let large = "x".repeat(1000);
+    let (status, body) = send(
+        &app,
+        upload(&s.student_token, &[("file", Some("big.txt"), &large)]).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::FileSizeExceeded as i32);
+    assert_eq!(body["message"], "Request size exceeds the limit");
+    assert_eq!(stored_file_count(), before);
+}
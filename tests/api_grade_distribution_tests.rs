@@ -0,0 +1,117 @@
+//! 成绩分布与百分位集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_grade_context_and_distribution() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("gradedist").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+
+    let mut own_submission = 0;
+    for (i, score) in [60.0, 80.0, 100.0].into_iter().enumerate() {
+        let student = if score == 80.0 {
+            s.student.clone()
+        } else {
+            let student = ctx
+                .create_user(&format!("gradedist_peer{i}"), UserRole::User)
+                .await;
+            ctx.join_class(&student, &s.class, ClassUserRole::Student)
+                .await;
+            student
+        };
+        let submission = ctx.create_submission(&student, &s.homework, "答案").await;
+        if score == 80.0 {
+            own_submission = submission.id;
+        }
+        let (status, _) = send(
+            &app,
+            post_json(
+                "/api/v1/grades",
+                Some(&s.teacher_token),
+                json!({ "submission_id": submission.id, "score": score }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let grade_url = format!("/api/v1/submissions/{own_submission}/grade");
+
+    // 默认不返回相对位置
+    let (status, body) = send(&app, get(&grade_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].get("class_context").is_none());
+
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.teacher_token),
+            json!({ "show_grade_context": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, get(&grade_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let context = &body["data"]["class_context"];
+    assert_eq!(context["percentile"], 50.0);
+    assert_eq!(context["count"], 3);
+    assert_eq!(context["mean"], 80.0);
+    assert_eq!(context["median"], 80.0);
+    assert_eq!(context["stddev"], 16.33);
+
+    // 教师查看评分不附带相对位置
+    let (status, body) = send(&app, get(&grade_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].get("class_context").is_none());
+
+    let distribution_url = format!("/api/v1/homeworks/{homework_id}/grade-distribution?bins=5");
+    let (status, _) = send(
+        &app,
+        get(&distribution_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        get(&distribution_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["statistics"]["min"], 60.0);
+    assert_eq!(body["data"]["statistics"]["max"], 100.0);
+    let counts: Vec<i64> = body["data"]["histogram"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|bin| bin["count"].as_i64().unwrap())
+        .collect();
+    assert_eq!(counts, vec![0, 0, 0, 1, 2]);
+
+    // 重新提交后以最新提交为准，未评分的最新提交不计入
+    ctx.create_submission(&s.student, &s.homework, "修改后的答案")
+        .await;
+    let (status, body) = send(
+        &app,
+        get(&distribution_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["statistics"]["count"], 2);
+    assert_eq!(body["data"]["statistics"]["median"], 80.0);
+}
@@ -0,0 +1,131 @@
+//! 评分草稿集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_grade_draft_lifecycle() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("draft").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let draft_url = format!("/api/v1/submissions/{}/grade-draft", submission.id);
+    let summary_url = format!("/api/v1/homeworks/{}/submissions/summary", s.homework.id);
+
+    // 自动保存覆盖上一次的草稿
+    for (score, comment) in [(60.0, "第一题"), (85.0, "第一题正确，第二题缺少推导")]
+    {
+        let (status, _) = send(
+            &app,
+            put_json(
+                &draft_url,
+                Some(&s.teacher_token),
+                json!({ "score": score, "comment": comment }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, body) = send(&app, get(&draft_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["score"], 85.0);
+    assert_eq!(body["data"]["comment"], "第一题正确，第二题缺少推导");
+
+    // 提交概览标记当前评分人有草稿的提交
+    let (_, body) = send(&app, get(&summary_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(body["data"]["items"][0]["has_draft"], true);
+    let (_, body) = send(&app, get(&summary_url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(body["data"]["items"][0]["has_draft"], false);
+
+    // 正式评分后草稿被删除
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 85.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = send(&app, get(&draft_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::GradeDraftNotFound as i32);
+    let (_, body) = send(&app, get(&summary_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(body["data"]["items"][0]["has_draft"], false);
+}
+
+#[actix_web::test]
+async fn test_grade_draft_permissions_and_cleanup() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("draftperm").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let draft_url = format!("/api/v1/submissions/{}/grade-draft", submission.id);
+
+    // 学生不能保存草稿
+    let (status, _) = send(
+        &app,
+        put_json(
+            &draft_url,
+            Some(&s.student_token),
+            json!({ "score": 100.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        put_json(&draft_url, Some(&s.teacher_token), json!({ "score": -1.0 })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 草稿按评分人区分
+    let (status, _) = send(
+        &app,
+        put_json(
+            &draft_url,
+            Some(&s.admin_token),
+            json!({ "comment": "待复核" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, get(&draft_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, delete(&draft_url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, delete(&draft_url, Some(&s.admin_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // 过期草稿被清理
+    let (status, _) = send(
+        &app,
+        put_json(&draft_url, Some(&s.teacher_token), json!({ "score": 90.0 })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let purged = ctx
+        .storage
+        .purge_expired_grade_drafts(chrono::Utc::now().timestamp() + 1)
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    let (status, _) = send(&app, get(&draft_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,98 @@
+//! 批改时限集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::organizations::entities::TenantScope;
+
+#[actix_web::test]
+async fn test_grading_sla_overdue_tracking() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("sla").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let summary = format!("/api/v1/homeworks/{}/submissions/summary", s.homework.id);
+
+    // 刚提交的作业未超过默认 7 天时限
+    let (status, body) = send(
+        &app,
+        get(&format!("{summary}?overdue=true"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 0);
+
+    let (status, body) = send(
+        &app,
+        get(&format!("{summary}?overdue=false"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["grading_overdue"], false);
+
+    // 截止时间设在未来时，待批改提交即视为超时
+    let cutoff = chrono::Utc::now().timestamp() + 60;
+    let counts = ctx
+        .storage
+        .count_grading_overdue_by_teacher(TenantScope::All, cutoff)
+        .await
+        .unwrap();
+    assert_eq!(counts.get(&s.teacher.id), Some(&1));
+
+    // 每份提交只提醒一次
+    let breaches = ctx
+        .storage
+        .claim_grading_sla_breaches(cutoff)
+        .await
+        .unwrap();
+    assert_eq!(breaches.len(), 1);
+    assert_eq!(breaches[0].submission_id, submission.id);
+    assert_eq!(breaches[0].teacher_id, s.teacher.id);
+    let breaches = ctx
+        .storage
+        .claim_grading_sla_breaches(cutoff)
+        .await
+        .unwrap();
+    assert!(breaches.is_empty());
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 90.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 仅管理员可查看批改时效
+    let (status, _) = send(
+        &app,
+        get("/api/v1/admin/analytics/grading", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/admin/analytics/grading", Some(&s.admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["sla_days"], 7);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["teacher_id"], s.teacher.id);
+    assert_eq!(items[0]["graded_count"], 1);
+    assert_eq!(items[0]["overdue_count"], 0);
+    assert!(items[0]["average_grading_hours"].as_f64().unwrap() >= 0.0);
+}
@@ -0,0 +1,97 @@
+//! 跨班级批量创建作业集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, post_json, send};
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_batch_create_homeworks_across_classes() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("hwbatch").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let second_class = ctx.create_class(&s.teacher, "hwbatch 二班").await;
+    let other_teacher = ctx
+        .create_user("hwbatch_other_teacher", UserRole::Teacher)
+        .await;
+    let foreign_class = ctx.create_class(&other_teacher, "hwbatch 他人班级").await;
+
+    let file = ctx
+        .storage
+        .upload_file(
+            "要求.pdf",
+            "hwbatch_stored.pdf",
+            &1024,
+            "application/pdf",
+            None,
+            s.teacher.id,
+        )
+        .await
+        .unwrap();
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks/batch-create",
+            Some(&s.teacher_token),
+            json!({
+                "class_ids": [s.class.id, foreign_class.id, 999999, second_class.id, s.class.id],
+                "title": "第三章练习",
+                "max_score": 50.0,
+                "attachments": [file.download_token]
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let data = &body["data"];
+    assert_eq!(data["total"], 4);
+    assert_eq!(data["success"], 2);
+    assert_eq!(data["failed"], 2);
+
+    // 按请求顺序返回（重复的班级只处理一次）
+    let results = data["results"].as_array().unwrap();
+    let class_ids: Vec<i64> = results
+        .iter()
+        .map(|r| r["class_id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(
+        class_ids,
+        vec![s.class.id, foreign_class.id, 999999, second_class.id]
+    );
+    assert_eq!(results[0]["success"], true);
+    assert_eq!(results[0]["homework"]["title"], "第三章练习");
+    assert_eq!(results[1]["success"], false);
+    assert_eq!(results[2]["success"], false);
+    assert_eq!(results[3]["homework"]["class_id"], second_class.id);
+
+    // 附件不重复上传，引用计数随作业数增加
+    let file = ctx.storage.get_file_by_id(file.id).await.unwrap().unwrap();
+    assert_eq!(file.citation_count, 2);
+    let homework_id = results[3]["homework"]["id"].as_i64().unwrap();
+    let file_ids = ctx
+        .storage
+        .get_homework_file_ids(homework_id)
+        .await
+        .unwrap();
+    assert_eq!(file_ids, vec![file.id]);
+
+    // 学生无权批量创建
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks/batch-create",
+            Some(&s.student_token),
+            json!({ "class_ids": [s.class.id], "title": "x" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
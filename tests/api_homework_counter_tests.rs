@@ -0,0 +1,131 @@
+//! 作业统计计数集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::Value;
+
+use common::{TestContext, build_app, delete, get, send};
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::grades::requests::CreateGradeRequest;
+use rust_hwsystem_next::models::system::responses::IntegrityCheckKind;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_homework_counters_follow_submissions_and_grades() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("counter").await;
+    let classmate = ctx.create_user("counter_classmate", UserRole::User).await;
+    ctx.join_class(&classmate, &s.class, ClassUserRole::Student)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!(
+        "/api/v1/homeworks?class_id={}&include_stats=true",
+        s.class.id
+    );
+    let stats = |body: &Value| {
+        let summary = &body["data"]["items"][0]["stats_summary"];
+        (
+            summary["submitted_count"].as_i64().unwrap(),
+            summary["graded_count"].as_i64().unwrap(),
+        )
+    };
+
+    // 首次读取时补写计数
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (0, 0));
+    assert_eq!(
+        body["data"]["items"][0]["stats_summary"]["total_students"],
+        2
+    );
+
+    // 同一学生多次提交只计一次
+    let submission = ctx.create_submission(&s.student, &s.homework, "v1").await;
+    ctx.create_submission(&s.student, &s.homework, "v2").await;
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (1, 0));
+
+    // 评分任一版本即计为已评分
+    ctx.storage
+        .create_grade(
+            s.teacher.id,
+            CreateGradeRequest {
+                submission_id: submission.id,
+                score: 90.0,
+                comment: None,
+            },
+        )
+        .await
+        .unwrap();
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (1, 1));
+
+    // 最新提交被退回的学生不计入，重交后恢复
+    let returned = ctx
+        .create_submission(&classmate, &s.homework, "draft")
+        .await;
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (2, 1));
+    let reopen_until = chrono::Utc::now().timestamp() + 3600;
+    ctx.storage
+        .request_resubmission(
+            returned.id,
+            s.teacher.id,
+            "请补充".to_string(),
+            reopen_until,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (1, 1));
+    let resubmitted = ctx
+        .create_submission(&classmate, &s.homework, "final")
+        .await;
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (2, 1));
+
+    // 撤回重交后最新提交又是被退回的版本
+    assert!(ctx.storage.delete_submission(resubmitted.id).await.unwrap());
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (1, 1));
+
+    // 删除学生时其提交级联删除，计数随之重算
+    ctx.create_submission(&classmate, &s.homework, "final")
+        .await;
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (2, 1));
+    let (status, _) = send(
+        &app,
+        delete(
+            &format!("/api/v1/users/{}", classmate.id),
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (1, 1));
+    let (status, _) = send(
+        &app,
+        delete(
+            &format!("/api/v1/users/{}", s.student.id),
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(stats(&body), (0, 0));
+
+    // 增量维护的计数与全量计算一致
+    let checks = ctx.storage.run_integrity_checks(false).await.unwrap();
+    let counters = checks
+        .iter()
+        .find(|c| c.check == IntegrityCheckKind::HomeworkCounterMismatch)
+        .unwrap();
+    assert_eq!(counters.found, 0);
+}
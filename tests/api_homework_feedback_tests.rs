@@ -0,0 +1,113 @@
+//! 作业用时与难度反馈、班级校准报告集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_feedback_requires_graded_submission() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("feedback").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = format!("/api/v1/homeworks/{}/feedback", s.homework.id);
+
+    // 教师设置预计用时与难度
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{}", s.homework.id),
+            Some(&s.teacher_token),
+            json!({ "estimated_minutes": 60, "difficulty": 3 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["estimated_minutes"], 60);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{}", s.homework.id),
+            Some(&s.teacher_token),
+            json!({ "difficulty": 6 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    // 未评分前不能填写
+    let feedback = json!({ "actual_minutes": 90, "perceived_difficulty": 4 });
+    let (status, body) = send(
+        &app,
+        put_json(&url, Some(&s.student_token), feedback.clone()).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::HomeworkFeedbackNotAllowed as i32);
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 80.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&s.student_token),
+            json!({ "actual_minutes": 90, "perceived_difficulty": 9 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::HomeworkFeedbackInvalid as i32);
+
+    let (status, _) = send(
+        &app,
+        put_json(&url, Some(&s.student_token), feedback).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, get(&url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["can_submit"], true);
+    assert_eq!(body["data"]["estimated_minutes"], 60);
+    assert_eq!(body["data"]["feedback"]["actual_minutes"], 90);
+
+    // 教师与班级外用户不能填写
+    let (status, _) = send(&app, get(&url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(&app, get(&url, Some(&s.outsider_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 校准报告
+    let report_url = format!("/api/v1/classes/{}/workload-calibration", s.class.id);
+    let (status, body) = send(&app, get(&report_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let item = &body["data"]["items"][0];
+    assert_eq!(item["feedback_count"], 1);
+    assert_eq!(item["median_actual_minutes"], 90.0);
+    assert_eq!(item["time_ratio"], 1.5);
+    assert_eq!(item["difficulty_delta"], 1.0);
+    assert_eq!(body["data"]["summary"]["underestimated_count"], 1);
+
+    let (status, _) = send(&app, get(&report_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,236 @@
+//! 作业 → 提交 → 评分 全流程集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_homework_submission_grade_flow() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("flow").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 教师布置作业
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&s.teacher_token),
+            json!({
+                "class_id": s.class.id,
+                "title": "第一章练习",
+                "description": "完成课后习题",
+                "max_score": 100.0
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let homework_id = body["data"]["id"].as_i64().unwrap();
+
+    // 学生可以看到作业详情
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["title"], "第一章练习");
+
+    // 学生提交
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "我的答案" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let submission_id = body["data"]["id"].as_i64().unwrap();
+
+    // 教师评分
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission_id, "score": 92.5, "comment": "很好" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grade_id = body["data"]["id"].as_i64().unwrap();
+
+    // 重复评分返回冲突
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission_id, "score": 80.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::Conflict as i32);
+
+    // 学生查看自己的成绩
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/grades/{grade_id}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["score"], 92.5);
+}
+
+#[actix_web::test]
+async fn test_outsider_cannot_submit() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("outsider_submit").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.outsider_token),
+            json!({ "homework_id": s.homework.id, "content": "不该被接收" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ClassPermissionDenied as i32);
+}
+
+#[actix_web::test]
+async fn test_other_teacher_cannot_grade() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("other_grader").await;
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (_, other_token) = ctx
+        .create_user_with_token("other_grader_teacher2", UserRole::Teacher)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&other_token),
+            json!({ "submission_id": submission.id, "score": 60.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::Forbidden as i32);
+
+    // 平台管理员可以评分任意班级的提交
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.admin_token),
+            json!({ "submission_id": submission.id, "score": 60.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[actix_web::test]
+async fn test_submission_diff_between_versions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("diff").await;
+    let v1 = ctx
+        .create_submission(&s.student, &s.homework, "第一行\n第二行\n第三行\n")
+        .await;
+    let v2 = ctx
+        .create_submission(
+            &s.student,
+            &s.homework,
+            "第一行\n第二行（修改）\n第三行\n第四行\n",
+        )
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{}/diff?against={}", v2.id, v1.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let data = &body["data"];
+    assert_eq!(data["base"]["version"], 1);
+    assert_eq!(data["target"]["version"], 2);
+    assert_eq!(data["added_lines"], 2);
+    assert_eq!(data["removed_lines"], 1);
+    let hunks = data["hunks"].as_array().unwrap();
+    assert_eq!(hunks.len(), 1);
+    let lines = hunks[0]["lines"].as_array().unwrap();
+    assert!(
+        lines
+            .iter()
+            .any(|l| l["kind"] == "delete" && l["content"] == "第二行" && l["old_line"] == 2)
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|l| l["kind"] == "insert" && l["content"] == "第四行" && l["new_line"] == 4)
+    );
+
+    // 其他学生的提交不能参与对比
+    let other = ctx
+        .create_submission(&s.teacher, &s.homework, "其他内容")
+        .await;
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{}/diff?against={}", v2.id, other.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::SubmissionDiffMismatch as i32);
+
+    // 非班级成员无权查看
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{}/diff?against={}", v2.id, v1.id),
+            Some(&s.outsider_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
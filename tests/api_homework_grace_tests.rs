@@ -0,0 +1,120 @@
+//! 迟交宽限期集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send, set_setting};
+
+#[actix_web::test]
+async fn test_deadline_grace_period() {
+    let ctx = TestContext::new().await;
+    ctx.init_dynamic_config().await;
+    let s = ctx.class_scenario("grace").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_url = format!("/api/v1/homeworks/{}", s.homework.id);
+    let submit = || {
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": s.homework.id, "content": "答案" }),
+        )
+        .to_request()
+    };
+
+    // 截止时间两分钟前已过
+    let deadline = chrono::Utc::now() - chrono::Duration::minutes(2);
+    let (status, _) = send(
+        &app,
+        put_json(
+            &homework_url,
+            Some(&s.teacher_token),
+            json!({ "deadline": deadline }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 未设置宽限期时按截止时间判定
+    let (status, body) = send(&app, submit()).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["is_late"], true);
+
+    // 系统默认宽限 5 分钟，作业沿用
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "homework.default_grace_minutes", "5").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, submit()).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["is_late"], false);
+
+    let (status, body) = send(
+        &app,
+        get(&homework_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let policy = &body["data"]["late_policy"];
+    assert_eq!(policy["grace_minutes"], 5);
+    assert_eq!(policy["grace_inherited"], true);
+    assert!(policy["late_after"].is_string());
+
+    // 作业单独取消宽限期，优先于系统默认
+    let (status, _) = send(
+        &app,
+        put_json(
+            &homework_url,
+            Some(&s.teacher_token),
+            json!({ "grace_minutes": 2000 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = send(
+        &app,
+        put_json(
+            &homework_url,
+            Some(&s.teacher_token),
+            json!({ "grace_minutes": 0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["grace_minutes"], 0);
+
+    let (status, body) = send(&app, submit()).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["is_late"], true);
+
+    // -1 恢复沿用系统设置
+    let (status, body) = send(
+        &app,
+        put_json(
+            &homework_url,
+            Some(&s.teacher_token),
+            json!({ "grace_minutes": -1 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["grace_minutes"].is_null());
+
+    let (status, body) = send(
+        &app,
+        get(&homework_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["late_policy"]["grace_minutes"], 5);
+    assert_eq!(body["data"]["late_policy"]["grace_inherited"], true);
+}
@@ -0,0 +1,81 @@
+//! 作业导入集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::test::{self, TestRequest};
+
+use common::{TestContext, build_app, send};
+
+const BOUNDARY: &str = "hwsystem-import-boundary";
+
+/// 构造只包含 `file` 字段的 multipart 上传请求
+fn upload(path: &str, token: &str, file_name: &str, content: &str) -> TestRequest {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n{content}\r\n--{BOUNDARY}--\r\n"
+    );
+    TestRequest::post()
+        .uri(path)
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .insert_header((
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(body)
+}
+
+#[actix_web::test]
+async fn test_import_homeworks_from_yaml() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("import").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let path = format!("/api/v1/classes/{}/homeworks/import", s.class.id);
+
+    let manifest = r###"homeworks:
+  - title: 第一章练习
+    description: "## 题目\n完成课后习题"
+    max_score: 50
+    deadline: 2030-03-01T12:00:00Z
+  - title: 第二章练习
+    attachments:
+      - ch2/data.txt
+  - title: ""
+"###;
+
+    // 其他教师、学生不能导入
+    let (status, _) = send(
+        &app,
+        upload(&path, &s.student_token, "course.yaml", manifest).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        upload(&path, &s.teacher_token, "course.txt", manifest).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = send(
+        &app,
+        upload(&path, &s.teacher_token, "course.yaml", manifest).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let data = &body["data"];
+    assert_eq!(data["total"], 3);
+    assert_eq!(data["success"], 1);
+    assert_eq!(data["failed"], 2);
+
+    let results = data["results"].as_array().unwrap();
+    assert_eq!(results[0]["success"], true);
+    assert_eq!(results[0]["homework"]["class_id"], s.class.id);
+    assert_eq!(results[0]["homework"]["max_score"], 50.0);
+    // 单独的 YAML 清单无法携带附件
+    assert_eq!(results[1]["success"], false);
+    assert!(results[1]["message"].as_str().unwrap().contains("zip"));
+    assert_eq!(results[2]["success"], false);
+}
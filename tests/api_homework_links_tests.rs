@@ -0,0 +1,201 @@
+//! 作业外部资源集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_homework_links_open_tracking() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("hwlink").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let links_url = format!("/api/v1/homeworks/{}/links", s.homework.id);
+
+    // 学生不能设置外部资源
+    let (status, _) = send(
+        &app,
+        put_json(
+            &links_url,
+            Some(&s.student_token),
+            json!({ "links": [{ "title": "讲解", "url": "https://example.com/v" }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &links_url,
+            Some(&s.teacher_token),
+            json!({ "links": [
+                { "link_type": "video", "title": " 第一讲 讲解视频 ", "url": "https://video.example.com/1" },
+                { "link_type": "document", "title": "实验指导", "url": "https://docs.example.com/lab" }
+            ] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = &body["data"]["items"];
+    assert_eq!(items[0]["title"], "第一讲 讲解视频");
+    assert_eq!(items[0]["link_type"], "video");
+    assert_eq!(items[1]["position"], 2);
+    let video_id = items[0]["id"].as_i64().unwrap();
+    let doc_id = items[1]["id"].as_i64().unwrap();
+
+    // 调整顺序，保留已有链接
+    let (status, body) = send(
+        &app,
+        put_json(
+            &links_url,
+            Some(&s.teacher_token),
+            json!({ "links": [
+                { "id": doc_id, "link_type": "document", "title": "实验指导", "url": "https://docs.example.com/lab" },
+                { "id": video_id, "link_type": "video", "title": "第一讲 讲解视频", "url": "https://video.example.com/1" }
+            ] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["id"], doc_id);
+
+    // 作业详情包含外部资源
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{}", s.homework.id),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["links"][1]["id"], video_id);
+
+    // 学生打开两次视频，教师打开不计入统计
+    for token in [&s.student_token, &s.student_token, &s.teacher_token] {
+        let (status, body) = send(
+            &app,
+            post_json(
+                &format!("{links_url}/{video_id}/open"),
+                Some(token),
+                json!({}),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["url"], "https://video.example.com/1");
+    }
+
+    let (status, _) = send(
+        &app,
+        get(&format!("{links_url}/stats"), Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        get(&format!("{links_url}/stats"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let stats = &body["data"];
+    assert_eq!(stats["total_students"], 1);
+    assert_eq!(stats["links"][0]["opened_students"], 0);
+    assert_eq!(stats["links"][1]["opened_students"], 1);
+    assert_eq!(stats["links"][1]["total_opens"], 2);
+    assert_eq!(stats["links"][1]["open_rate"], 100.0);
+    assert_eq!(stats["students"][0]["user_id"], s.student.id);
+    assert_eq!(stats["students"][0]["opened_link_ids"], json!([video_id]));
+
+    // 删除链接后打开记录随之删除
+    let (status, _) = send(
+        &app,
+        put_json(&links_url, Some(&s.teacher_token), json!({ "links": [] })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        post_json(
+            &format!("{links_url}/{video_id}/open"),
+            Some(&s.student_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::HomeworkLinkNotFound as i32);
+}
+
+#[actix_web::test]
+async fn test_homework_links_validation() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("hwlinkcheck").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let links_url = format!("/api/v1/homeworks/{}/links", s.homework.id);
+
+    for url in [
+        "javascript:alert(1)",
+        "ftp://example.com/a.pdf",
+        "https://",
+        "example.com",
+    ] {
+        let (status, body) = send(
+            &app,
+            put_json(
+                &links_url,
+                Some(&s.teacher_token),
+                json!({ "links": [{ "title": "资料", "url": url }] }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{url}");
+        assert_eq!(body["code"], ErrorCode::HomeworkLinkInvalid as i32);
+    }
+
+    // 空标题与不属于该作业的链接 ID
+    for link in [
+        json!({ "title": "  ", "url": "https://example.com" }),
+        json!({ "id": 999_999, "title": "资料", "url": "https://example.com" }),
+    ] {
+        let (status, body) = send(
+            &app,
+            put_json(
+                &links_url,
+                Some(&s.teacher_token),
+                json!({ "links": [link] }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], ErrorCode::HomeworkLinkInvalid as i32);
+    }
+
+    // 未指定类型时默认为 other
+    let (status, body) = send(
+        &app,
+        put_json(
+            &links_url,
+            Some(&s.teacher_token),
+            json!({ "links": [{ "title": "课程主页", "url": "HTTPS://course.example.com" }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["link_type"], "other");
+}
@@ -0,0 +1,197 @@
+//! 多部分作业集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_homework_parts_submission_and_rollup() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("parts").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+    let parts_url = format!("/api/v1/homeworks/{homework_id}/parts");
+
+    // 学生不能设置分题
+    let (status, _) = send(
+        &app,
+        put_json(
+            &parts_url,
+            Some(&s.student_token),
+            json!({ "parts": [{ "title": "第一题", "max_score": 40.0 }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &parts_url,
+            Some(&s.teacher_token),
+            json!({ "parts": [{ "title": "第一题", "max_score": 0.0 }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::HomeworkPartInvalid as i32);
+
+    // 第二题已过截止时间
+    let (status, body) = send(
+        &app,
+        put_json(
+            &parts_url,
+            Some(&s.teacher_token),
+            json!({ "parts": [
+                { "title": "第一题", "description": "选择题", "max_score": 40.0 },
+                { "title": "第二题", "max_score": 60.0, "deadline": "2020-01-01T00:00:00Z" }
+            ] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["total_max_score"], 100.0);
+    let part_a = body["data"]["items"][0]["id"].as_i64().unwrap();
+    let part_b = body["data"]["items"][1]["id"].as_i64().unwrap();
+    assert_eq!(body["data"]["items"][1]["position"], 2);
+
+    // 作业详情附带分题
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["parts"].as_array().unwrap().len(), 2);
+    assert_eq!(body["data"]["max_score"], 100.0);
+
+    // 未指定分题
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "答案" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::SubmissionPartRequired as i32);
+
+    let mut submission_ids = Vec::new();
+    for (part_id, late) in [(part_a, false), (part_b, true)] {
+        let (status, body) = send(
+            &app,
+            post_json(
+                "/api/v1/submissions",
+                Some(&s.student_token),
+                json!({ "homework_id": homework_id, "part_id": part_id, "content": "答案" }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["data"]["part_id"], part_id);
+        assert_eq!(body["data"]["is_late"], late);
+        submission_ids.push(body["data"]["id"].as_i64().unwrap());
+    }
+
+    // 分数超出分题满分
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission_ids[0], "score": 50.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::GradeScoreOutOfRange as i32);
+
+    for (submission_id, score) in [(submission_ids[0], 35.0), (submission_ids[1], 50.0)] {
+        let (status, _) = send(
+            &app,
+            post_json(
+                "/api/v1/grades",
+                Some(&s.teacher_token),
+                json!({ "submission_id": submission_id, "score": score }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // 学生查看自己的汇总得分
+    let (status, body) = send(
+        &app,
+        get(&format!("{parts_url}/scores"), Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["total_score"], 85.0);
+    assert_eq!(body["data"]["max_score"], 100.0);
+    assert_eq!(body["data"]["fully_graded"], true);
+    assert_eq!(body["data"]["parts"][1]["is_late"], true);
+
+    // 学生不能查看他人得分
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("{parts_url}/scores?user_id={}", s.teacher.id),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 统计按学生汇总总分，并给出分题统计
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/stats"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["submitted_count"], 1);
+    assert_eq!(body["data"]["graded_count"], 1);
+    assert_eq!(body["data"]["late_count"], 1);
+    assert_eq!(body["data"]["score_stats"]["average"], 85.0);
+    let parts = body["data"]["parts"].as_array().unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0]["average_score"], 35.0);
+    assert_eq!(parts[1]["late_count"], 1);
+
+    // 已有提交的分题不能删除
+    let (status, body) = send(
+        &app,
+        put_json(
+            &parts_url,
+            Some(&s.teacher_token),
+            json!({ "parts": [{ "id": part_b, "title": "第二题", "max_score": 60.0 }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::HomeworkPartInUse as i32);
+}
@@ -0,0 +1,174 @@
+//! 作业前置条件集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_homework_prerequisites_lock_submission() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("prereq").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let first = s.homework.id;
+    let second = ctx
+        .create_homework(&s.teacher, &s.class, "第二次作业")
+        .await
+        .id;
+    let prerequisites_url = format!("/api/v1/homeworks/{second}/prerequisites");
+
+    // 学生不能设置前置条件
+    let (status, _) = send(
+        &app,
+        put_json(
+            &prerequisites_url,
+            Some(&s.student_token),
+            json!({ "prerequisites": [{ "homework_id": first }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &prerequisites_url,
+            Some(&s.teacher_token),
+            json!({ "prerequisites": [
+                { "homework_id": first, "requirement": "passed", "min_score": 60.0 }
+            ] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["prerequisite_id"], first);
+    assert_eq!(body["data"]["items"][0]["pass_score"], 60.0);
+
+    // 反向依赖形成循环
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{first}/prerequisites"),
+            Some(&s.teacher_token),
+            json!({ "prerequisites": [{ "homework_id": second }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::HomeworkPrerequisiteCycle as i32);
+
+    // 以自身为前置
+    let (status, body) = send(
+        &app,
+        put_json(
+            &prerequisites_url,
+            Some(&s.teacher_token),
+            json!({ "prerequisites": [{ "homework_id": second }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::HomeworkPrerequisiteInvalid as i32);
+
+    // 学生列表标记锁定，提交被拒绝
+    let list_url = format!("/api/v1/homeworks?class_id={}", s.class.id);
+    let (status, body) = send(&app, get(&list_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    let locked = |id: i64| {
+        items
+            .iter()
+            .find(|item| item["id"] == id)
+            .map(|item| item["is_locked"].clone())
+    };
+    assert_eq!(locked(first), Some(json!(false)));
+    assert_eq!(locked(second), Some(json!(true)));
+
+    let submit = |homework_id: i64| {
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "答案" }),
+        )
+        .to_request()
+    };
+    let (status, body) = send(&app, submit(second)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        body["code"],
+        ErrorCode::SubmissionPrerequisitesNotMet as i32
+    );
+
+    // 前置作业已提交但未及格，仍然锁定
+    let (status, body) = send(&app, submit(first)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let submission_id = body["data"]["id"].as_i64().unwrap();
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission_id, "score": 50.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grade_id = body["data"]["id"].as_i64().unwrap();
+
+    let (status, body) = send(
+        &app,
+        get(&prerequisites_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["locked"], true);
+    assert_eq!(body["data"]["items"][0]["satisfied"], false);
+
+    // 改分及格后解锁
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/grades/{grade_id}"),
+            Some(&s.teacher_token),
+            json!({ "score": 75.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &app,
+        get(&prerequisites_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["locked"], false);
+    assert_eq!(body["data"]["items"][0]["satisfied"], true);
+
+    let (status, _) = send(&app, submit(second)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 清空前置条件
+    let (status, body) = send(
+        &app,
+        put_json(
+            &prerequisites_url,
+            Some(&s.teacher_token),
+            json!({ "prerequisites": [] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 0);
+}
@@ -0,0 +1,112 @@
+//! 数据一致性检查集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, post_json, send, token_for};
+use rust_hwsystem_next::models::organizations::requests::CreateOrganizationRequest;
+use rust_hwsystem_next::models::system::responses::{IntegrityCheckKind, IntegrityCheckResult};
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_integrity_check_detects_and_repairs_citation_drift() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("integrity").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 没有任何引用的文件，引用计数却为 1
+    let file = ctx
+        .storage
+        .upload_file(
+            "notes.pdf",
+            "notes-stored.pdf",
+            &1024,
+            "application/pdf",
+            None,
+            s.teacher.id,
+        )
+        .await
+        .unwrap();
+    assert!(ctx.storage.increment_file_citation(file.id).await.unwrap());
+
+    let mismatch = |checks: &[IntegrityCheckResult]| {
+        checks
+            .iter()
+            .find(|c| c.check == IntegrityCheckKind::CitationCountMismatch)
+            .cloned()
+            .unwrap()
+    };
+
+    // 只检查不修复
+    let checks = ctx.storage.run_integrity_checks(false).await.unwrap();
+    let result = mismatch(&checks);
+    assert_eq!(result.found, 1);
+    assert_eq!(result.repaired, 0);
+    assert_eq!(result.sample_ids, vec![file.id]);
+    assert!(
+        checks
+            .iter()
+            .all(|c| c.check == IntegrityCheckKind::CitationCountMismatch || c.found == 0)
+    );
+
+    // 修复后再次检查不再有问题
+    let checks = ctx.storage.run_integrity_checks(true).await.unwrap();
+    assert_eq!(mismatch(&checks).repaired, 1);
+    let checks = ctx.storage.run_integrity_checks(false).await.unwrap();
+    assert!(checks.iter().all(|c| c.found == 0));
+
+    // 接口以后台任务执行，仅管理员可触发
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/integrity-check",
+            Some(&s.admin_token),
+            json!({ "repair": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(body["data"]["kind"], "integrity_check");
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/integrity-check",
+            Some(&s.teacher_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 检查覆盖全部组织，组织管理员无权触发
+    let org = ctx
+        .storage
+        .create_organization(CreateOrganizationRequest {
+            name: "integrity-org".to_string(),
+            slug: "integrity-org".to_string(),
+            description: None,
+        })
+        .await
+        .expect("Failed to create organization");
+    let org_admin = token_for(
+        &ctx.create_org_user("integrity_org_admin", UserRole::Admin, Some(org.id))
+            .await,
+    );
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/admin/integrity-check",
+            Some(&org_admin),
+            json!({ "repair": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
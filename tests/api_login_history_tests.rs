@@ -0,0 +1,99 @@
+//! 登录历史集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::http::header::USER_AGENT;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TEST_PASSWORD, TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+const WINDOWS_CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+const IPHONE_SAFARI: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1";
+
+#[actix_web::test]
+async fn test_login_history_records_devices() {
+    let ctx = TestContext::new().await;
+    ctx.create_user("dave", UserRole::User).await;
+    let (_, admin_token) = ctx.create_user_with_token("root", UserRole::Admin).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let mut token = String::new();
+    for (password, user_agent, peer) in [
+        ("wrong-password", WINDOWS_CHROME, "203.0.113.5:4000"),
+        (TEST_PASSWORD, WINDOWS_CHROME, "203.0.113.5:4000"),
+        (TEST_PASSWORD, WINDOWS_CHROME, "203.0.113.9:4000"),
+        (TEST_PASSWORD, IPHONE_SAFARI, "198.51.100.7:4000"),
+    ] {
+        let (status, body) = send(
+            &app,
+            post_json(
+                "/api/v1/auth/login",
+                None,
+                json!({ "username": "dave", "password": password }),
+            )
+            .insert_header((USER_AGENT, user_agent))
+            .peer_addr(peer.parse().unwrap())
+            .to_request(),
+        )
+        .await;
+        if password == TEST_PASSWORD {
+            assert_eq!(status, StatusCode::OK);
+            token = body["data"]["access_token"].as_str().unwrap().to_string();
+        } else {
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/auth/me/login-history", Some(&token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 4);
+
+    // 按时间倒序：新设备且来自新网段，标记为可疑
+    assert_eq!(items[0]["browser"], "Safari");
+    assert_eq!(items[0]["os"], "iOS");
+    assert_eq!(items[0]["device_type"], "mobile");
+    assert_eq!(items[0]["ip_address"], "198.51.100.7");
+    assert_eq!(items[0]["new_device"], true);
+    assert_eq!(items[0]["new_ip"], true);
+    assert_eq!(items[0]["suspicious"], true);
+
+    // 同一网段内更换地址、同一设备不做标记
+    assert_eq!(items[1]["new_device"], false);
+    assert_eq!(items[1]["new_ip"], false);
+    assert_eq!(items[1]["suspicious"], false);
+
+    // 首次成功登录没有可比较的记录
+    assert_eq!(items[2]["success"], true);
+    assert_eq!(items[2]["new_device"], false);
+    assert_eq!(items[3]["success"], false);
+    assert_eq!(items[3]["browser"], "Chrome");
+
+    // 普通用户不能查看管理视图
+    let (status, _) = send(
+        &app,
+        get("/api/v1/users/login-history", Some(&token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        get(
+            "/api/v1/users/login-history?suspicious=true",
+            Some(&admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["pagination"]["total"], 1);
+    assert_eq!(body["data"]["items"][0]["ip_address"], "198.51.100.7");
+}
@@ -0,0 +1,198 @@
+//! 班级私信集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_class_direct_messages() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("dm").await;
+    let classmate = ctx.create_user("dm_classmate", UserRole::User).await;
+    ctx.join_class(&classmate, &s.class, ClassUserRole::Student)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let message = |recipient_id: i64, content: &str| json!({ "class_id": s.class.id, "recipient_id": recipient_id, "content": content });
+
+    // 学生给教师发私信
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&s.student_token),
+            message(s.teacher.id, "老师，第二题怎么做？"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["recipient_id"], s.teacher.id);
+
+    // 空内容
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&s.student_token),
+            message(s.teacher.id, "   "),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::MessageContentInvalid as i32);
+
+    // 非班级成员
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&s.student_token),
+            message(s.outsider.id, "你好"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::MessageNotAllowed as i32);
+
+    // 学生之间默认不能私信
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&s.student_token),
+            message(classmate.id, "一起写作业吗"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::MessageNotAllowed as i32);
+
+    // 教师开启后允许
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/classes/{}", s.class.id),
+            Some(&s.teacher_token),
+            json!({ "allow_student_messages": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["allow_student_messages"], true);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&s.student_token),
+            message(classmate.id, "一起写作业吗"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 教师查看会话与未读数
+    let (status, body) = send(
+        &app,
+        get("/api/v1/messages/unread-count", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["unread_count"], 1);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/messages/conversations", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["total_unread"], 1);
+    assert_eq!(body["data"]["items"][0]["peer"]["id"], s.student.id);
+    assert_eq!(body["data"]["items"][0]["unread_count"], 1);
+
+    // 学生有两个会话
+    let (status, body) = send(
+        &app,
+        get("/api/v1/messages/conversations", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 2);
+    assert_eq!(body["data"]["total_unread"], 0);
+
+    // 教师回复并标记已读
+    let conversation_url = format!(
+        "/api/v1/messages/conversations/{}/{}",
+        s.class.id, s.student.id
+    );
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/messages",
+            Some(&s.teacher_token),
+            message(s.student.id, "先看课件第三章"),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("{conversation_url}/read"),
+            Some(&s.teacher_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["marked"], 1);
+
+    let (status, body) = send(
+        &app,
+        get(&conversation_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["sender_id"], s.teacher.id);
+    assert!(items[1]["read_at"].is_string());
+    assert_eq!(body["data"]["has_more"], false);
+
+    // 超出每页上限时报错，不再静默截断
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("{conversation_url}?size=1000"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::PageSizeExceeded as i32);
+
+    // 非班级成员不能查看会话
+    let (status, _) = send(
+        &app,
+        get(&conversation_url, Some(&s.outsider_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
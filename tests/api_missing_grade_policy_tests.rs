@@ -0,0 +1,101 @@
+//! 缺交零分策略集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_missing_grade_policy_assigns_and_reverts_zero() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("missgrade").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+
+    let peer = ctx.create_user("missgrade_peer", UserRole::User).await;
+    ctx.join_class(&peer, &s.class, ClassUserRole::Student)
+        .await;
+    ctx.create_submission(&peer, &s.homework, "答案").await;
+
+    let policy_url = format!("/api/v1/homeworks/{homework_id}/missing-grade-policy");
+    let (status, body) = send(&app, get(&policy_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::MissingGradePolicyNotFound as i32);
+
+    // 学生不能设置策略
+    let cutoff = (Utc::now() - Duration::hours(1)).to_rfc3339();
+    let (status, _) = send(
+        &app,
+        put_json(
+            &policy_url,
+            Some(&s.student_token),
+            json!({ "cutoff_at": cutoff }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 截止时间已过，保存后立即执行
+    let (status, body) = send(
+        &app,
+        put_json(
+            &policy_url,
+            Some(&s.teacher_token),
+            json!({ "cutoff_at": cutoff }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["homework_id"], homework_id);
+    assert!(body["data"]["applied_at"].is_string());
+
+    // 零分计入成绩，但学生仍视为未提交
+    let stats_url = format!("/api/v1/homeworks/{homework_id}/stats");
+    let (status, body) = send(&app, get(&stats_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let stats = &body["data"];
+    assert_eq!(stats["submitted_count"], 1);
+    assert_eq!(stats["graded_count"], 0);
+    assert_eq!(stats["score_stats"]["max"], 0.0);
+    assert_eq!(stats["unsubmitted_students"][0]["id"], s.student.id);
+
+    // 撤销后恢复为未评分
+    let revert_url = format!(
+        "/api/v1/homeworks/{homework_id}/missing-grades/{}",
+        s.student.id
+    );
+    let (status, _) = send(
+        &app,
+        delete(&revert_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        delete(&revert_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::AutoGradeNotFound as i32);
+
+    let (_, body) = send(&app, get(&stats_url, Some(&s.teacher_token)).to_request()).await;
+    assert!(body["data"]["score_stats"].is_null());
+
+    let (status, _) = send(
+        &app,
+        delete(&policy_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, get(&policy_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
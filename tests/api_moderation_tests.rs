@@ -0,0 +1,114 @@
+//! 内容审核集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send, set_setting};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_moderation_flags_and_blocks_content() {
+    let ctx = TestContext::new().await;
+    ctx.init_dynamic_config().await;
+    let s = ctx.class_scenario("moderation").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submit = |content: &str| {
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": s.homework.id, "content": content }),
+        )
+        .to_request()
+    };
+    let flags_path = format!("/api/v1/classes/{}/moderation-flags", s.class.id);
+
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "moderation.banned_words", r#"["作弊"]"#).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "moderation.mode", "flag").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 标记模式：提交成功，生成待复核标记
+    let (status, body) = send(&app, submit("答案里提到了作弊")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let submission_id = body["data"]["id"].as_i64().unwrap();
+
+    let (status, body) = send(&app, get(&flags_path, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["content_type"], "submission");
+    assert_eq!(items[0]["content_id"], submission_id);
+    assert_eq!(items[0]["source"], "word_list");
+    assert_eq!(items[0]["reason"], "作弊");
+    assert_eq!(items[0]["blocked"], false);
+    assert_eq!(items[0]["status"], "pending");
+    let flag_id = items[0]["id"].as_i64().unwrap();
+
+    // 拦截模式：提交被拒绝，仍记录标记
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "moderation.mode", "block").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, submit("再次提到作弊")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ContentModerationBlocked as i32);
+
+    let (status, body) = send(&app, submit("正常的答案")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["content"], "正常的答案");
+
+    // 教师复核
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("{flags_path}/{flag_id}"),
+            Some(&s.teacher_token),
+            json!({ "status": "dismissed" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "dismissed");
+    assert_eq!(body["data"]["reviewed_by"], s.teacher.id);
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("{flags_path}?status=pending"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["blocked"], true);
+    assert!(items[0]["content_id"].is_null());
+
+    // 学生无权查看审核标记
+    let (status, _) = send(&app, get(&flags_path, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "moderation.mode", "off").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
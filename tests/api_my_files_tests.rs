@@ -0,0 +1,154 @@
+//! 本人上传文件列表集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::submissions::requests::CreateSubmissionRequest;
+
+#[actix_web::test]
+async fn test_list_and_delete_my_files() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("myfiles").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let upload = |name: &'static str, stored: &'static str, size: i64| {
+        let storage = ctx.storage.clone();
+        let user_id = s.student.id;
+        async move {
+            storage
+                .upload_file(name, stored, &size, "application/pdf", None, user_id)
+                .await
+                .unwrap()
+        }
+    };
+    let attachment = upload("报告.pdf", "myfiles_attachment.pdf", 2048).await;
+    let avatar = upload("头像.png", "myfiles_avatar.png", 256).await;
+    let draft = upload("草稿.pdf", "myfiles_draft.pdf", 512).await;
+
+    ctx.storage
+        .create_submission(
+            s.student.id,
+            CreateSubmissionRequest {
+                homework_id: s.homework.id,
+                part_id: None,
+                content: "答案".to_string(),
+                attachments: Some(vec![attachment.download_token.clone()]),
+                signed_timestamp: None,
+                client_time: None,
+                self_assessment: None,
+            },
+        )
+        .await
+        .unwrap();
+    let (status, _) = send(
+        &app,
+        put_json(
+            "/api/v1/auth/me",
+            Some(&s.student_token),
+            json!({ "avatar_url": format!("/api/v1/files/download/{}", avatar.download_token) }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/auth/me/files", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    let find = |token: &str| {
+        items
+            .iter()
+            .find(|i| i["download_token"] == token)
+            .unwrap()
+            .clone()
+    };
+    let refs = find(&attachment.download_token)["references"].clone();
+    assert_eq!(refs[0]["kind"], "submission");
+    assert_eq!(refs[0]["homework_id"], s.homework.id);
+    assert_eq!(refs[0]["title"], s.homework.title.as_str());
+    assert_eq!(
+        find(&avatar.download_token)["references"][0]["kind"],
+        "avatar"
+    );
+    assert_eq!(
+        find(&draft.download_token)["references"]
+            .as_array()
+            .unwrap()
+            .len(),
+        0
+    );
+
+    let usage = &body["data"]["usage"];
+    assert_eq!(usage["file_count"], 3);
+    assert_eq!(usage["total_size"], 2048 + 256 + 512);
+    assert_eq!(usage["unreferenced_count"], 1);
+    assert_eq!(usage["unreferenced_size"], 512);
+    assert_eq!(usage["breakdown"][0]["kind"], "submission");
+    assert_eq!(usage["breakdown"][0]["total_size"], 2048);
+
+    // 仅列出可删除的文件，用量统计不受过滤影响
+    let (status, body) = send(
+        &app,
+        get(
+            "/api/v1/auth/me/files?unreferenced=true",
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["pagination"]["total"], 1);
+    assert_eq!(
+        body["data"]["items"][0]["download_token"],
+        draft.download_token
+    );
+    assert_eq!(body["data"]["usage"]["file_count"], 3);
+
+    // 被引用的文件不能删除
+    let (status, body) = send(
+        &app,
+        delete(
+            &format!("/api/v1/auth/me/files/{}", attachment.download_token),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::FileInUse as i32);
+    assert_eq!(body["data"][0]["kind"], "submission");
+
+    // 他人的文件视为不存在
+    let draft_url = format!("/api/v1/auth/me/files/{}", draft.download_token);
+    let (status, body) = send(
+        &app,
+        delete(&draft_url, Some(&s.outsider_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::FileNotFound as i32);
+
+    let (status, _) = send(
+        &app,
+        delete(&draft_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        ctx.storage
+            .get_file_by_token(&draft.download_token)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
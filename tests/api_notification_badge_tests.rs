@@ -0,0 +1,82 @@
+//! 未读通知数量轮询接口集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::http::header::{ETAG, IF_NONE_MATCH};
+use actix_web::test;
+
+use common::{TestContext, build_app, get, put_json, send};
+use rust_hwsystem_next::models::notifications::entities::NotificationType;
+use rust_hwsystem_next::services::notifications::trigger::deliver_notifications;
+use serde_json::json;
+
+#[actix_web::test]
+async fn test_unread_count_etag_and_invalidation() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("badge").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let unread = "/api/v1/notifications/unread-count";
+
+    let resp = test::call_service(&app, get(unread, Some(&s.student_token)).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let etag = resp
+        .headers()
+        .get(ETAG)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["data"]["unread_count"], 0);
+
+    // 数量未变化时返回 304
+    let resp = test::call_service(
+        &app,
+        get(unread, Some(&s.student_token))
+            .insert_header((IF_NONE_MATCH, etag.as_str()))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    // 新通知写入后缓存失效，旧 ETag 不再命中
+    let created = deliver_notifications(
+        &ctx.storage,
+        &[s.student.id],
+        &NotificationType::HomeworkCreated,
+        "新作业发布".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(created, 1);
+
+    let resp = test::call_service(
+        &app,
+        get(unread, Some(&s.student_token))
+            .insert_header((IF_NONE_MATCH, etag.as_str()))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["data"]["unread_count"], 1);
+
+    // 全部已读后立即反映
+    let (status, _) = send(
+        &app,
+        put_json(
+            "/api/v1/notifications/read-all",
+            Some(&s.student_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, get(unread, Some(&s.student_token)).to_request()).await;
+    assert_eq!(body["data"]["unread_count"], 0);
+}
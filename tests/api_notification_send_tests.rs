@@ -0,0 +1,146 @@
+//! 群发班级通知集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send, token_for};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_send_notification_by_filter() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("broadcast").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let url = "/api/v1/notifications";
+
+    let late_student = ctx.create_user("broadcast_late", UserRole::User).await;
+    ctx.join_class(&late_student, &s.class, ClassUserRole::Student)
+        .await;
+    ctx.create_submission(&s.student, &s.homework, "已完成")
+        .await;
+
+    // 学生不能群发
+    let (status, _) = send(
+        &app,
+        post_json(
+            url,
+            Some(&s.student_token),
+            json!({ "class_id": s.class.id, "title": "提醒" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 标题为空、指定接收人为空、筛选其他班级的作业
+    let other_class = ctx.create_class(&s.teacher, "其他班级").await;
+    let other_homework = ctx
+        .create_homework(&s.teacher, &other_class, "其他作业")
+        .await;
+    for body in [
+        json!({ "class_id": s.class.id, "title": "  " }),
+        json!({ "class_id": s.class.id, "title": "提醒", "filter": { "user_ids": [] } }),
+        json!({
+            "class_id": s.class.id,
+            "title": "提醒",
+            "filter": { "not_submitted_homework_id": other_homework.id }
+        }),
+    ] {
+        let (status, resp) = send(
+            &app,
+            post_json(url, Some(&s.teacher_token), body).to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(resp["code"], ErrorCode::NotificationRequestInvalid as i32);
+    }
+
+    // 只有未提交的学生收到提醒
+    let (status, body) = send(
+        &app,
+        post_json(
+            url,
+            Some(&s.teacher_token),
+            json!({
+                "class_id": s.class.id,
+                "title": "请尽快提交作业",
+                "content": "今晚截止",
+                "filter": { "role": "student", "not_submitted_homework_id": s.homework.id }
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["recipient_count"], 1);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/notifications", Some(&token_for(&late_student))).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    let announcement = items
+        .iter()
+        .find(|n| n["notification_type"] == "class_announcement")
+        .expect("未提交的学生应收到通知");
+    assert!(
+        announcement["title"]
+            .as_str()
+            .unwrap()
+            .contains("请尽快提交作业")
+    );
+    assert_eq!(announcement["content"], "今晚截止");
+    assert_eq!(announcement["reference_id"], s.class.id);
+
+    let (_, body) = send(
+        &app,
+        get("/api/v1/notifications", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert!(
+        body["data"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|n| n["notification_type"] != "class_announcement")
+    );
+
+    // 管理员不带筛选条件时发送给全部班级成员
+    let (status, body) = send(
+        &app,
+        post_json(
+            url,
+            Some(&s.admin_token),
+            json!({ "class_id": s.class.id, "title": "系统维护通知" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["recipient_count"], 3);
+
+    // 指定用户时忽略非班级成员，发送者本人不会收到
+    let (status, body) = send(
+        &app,
+        post_json(
+            url,
+            Some(&s.teacher_token),
+            json!({
+                "class_id": s.class.id,
+                "title": "单独提醒",
+                "filter": { "user_ids": [s.student.id, s.teacher.id, s.outsider.id] }
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["recipient_count"], 1);
+}
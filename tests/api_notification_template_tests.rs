@@ -0,0 +1,125 @@
+//! 通知模板集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, put_json, send, set_setting};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::notifications::entities::NotificationType;
+use rust_hwsystem_next::services::notifications::templates::render_notification;
+
+#[actix_web::test]
+async fn test_notification_templates_override_builtin_text() {
+    let ctx = TestContext::new().await;
+    ctx.init_dynamic_config().await;
+    let s = ctx.class_scenario("ntpl").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let vars = [("homework_title", "链表".to_string())];
+
+    // 未配置模板时使用内置文案
+    let (title, content) =
+        render_notification(&ctx.storage, &NotificationType::HomeworkCreated, &vars).await;
+    assert_eq!(title, "新作业发布：链表");
+    assert_eq!(content.as_deref(), Some("作业「链表」已发布，请及时查看"));
+
+    // 非管理员无权管理模板
+    let (status, _) = send(
+        &app,
+        get("/api/v1/notifications/templates", Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 使用未声明的变量被拒绝
+    let (status, body) = send(
+        &app,
+        put_json(
+            "/api/v1/notifications/templates/homework_created/en",
+            Some(&s.admin_token),
+            json!({ "title": "New homework: {homework_title} ({score})" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::NotificationTemplateInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            "/api/v1/notifications/templates/homework_created/en",
+            Some(&s.admin_token),
+            json!({
+                "title": "New homework: {homework_title}",
+                "content": "Please finish {homework_title} on time"
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["locale"], "en");
+
+    // 当前语言仍为 zh-CN，英文模板不生效
+    let (title, _) =
+        render_notification(&ctx.storage, &NotificationType::HomeworkCreated, &vars).await;
+    assert_eq!(title, "新作业发布：链表");
+
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "notification.locale", "en").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (title, content) =
+        render_notification(&ctx.storage, &NotificationType::HomeworkCreated, &vars).await;
+    assert_eq!(title, "New homework: 链表");
+    assert_eq!(content.as_deref(), Some("Please finish 链表 on time"));
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/notifications/templates", Some(&s.admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["active_locale"], "en");
+    let item = body["data"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["notification_type"] == "homework_created")
+        .unwrap();
+    assert_eq!(item["variables"], json!(["homework_title"]));
+    assert_eq!(item["overrides"].as_array().unwrap().len(), 1);
+
+    // 删除后恢复内置文案
+    let (status, _) = send(
+        &app,
+        delete(
+            "/api/v1/notifications/templates/homework_created/en",
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (title, _) =
+        render_notification(&ctx.storage, &NotificationType::HomeworkCreated, &vars).await;
+    assert_eq!(title, "新作业发布：链表");
+
+    let (status, body) = send(
+        &app,
+        delete(
+            "/api/v1/notifications/templates/homework_created/en",
+            Some(&s.admin_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::NotificationTemplateNotFound as i32);
+}
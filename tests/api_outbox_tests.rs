@@ -0,0 +1,135 @@
+//! 事务发件箱集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::notifications::entities::NotificationType;
+use rust_hwsystem_next::models::outbox::entities::OutboxEvent;
+use rust_hwsystem_next::services::outbox::relay_due_events;
+
+#[actix_web::test]
+async fn test_outbox_events_written_with_business_changes() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("outbox").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 95.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grade_id = body["data"]["id"].as_i64().unwrap();
+
+    // 作业发布、提交与评分各自在事务中写入了发件箱事件
+    let now = chrono::Utc::now().timestamp();
+    let pending = ctx.storage.list_due_outbox_events(now, 100).await.unwrap();
+    let events: Vec<OutboxEvent> = pending.iter().map(|m| m.event.clone().unwrap()).collect();
+    assert_eq!(events.len(), 4);
+    assert!(events.iter().any(|e| matches!(
+        e,
+        OutboxEvent::ClassNotification { class_id, notification_type: NotificationType::HomeworkCreated, .. }
+            if *class_id == s.class.id
+    )));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        OutboxEvent::HomeworkImEvent { homework_id, .. } if *homework_id == s.homework.id
+    )));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        OutboxEvent::Notification { user_ids, notification_type: NotificationType::SubmissionReceived, .. }
+            if user_ids == &vec![s.teacher.id]
+    )));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        OutboxEvent::Notification { user_ids, reference_id, notification_type: NotificationType::GradeReceived, .. }
+            if user_ids == &vec![s.student.id] && *reference_id == Some(grade_id)
+    )));
+
+    // 投递前尚未生成通知
+    let unread = "/api/v1/notifications/unread-count";
+    let (_, body) = send(&app, get(unread, Some(&s.student_token)).to_request()).await;
+    assert_eq!(body["data"]["unread_count"], 0);
+
+    assert_eq!(relay_due_events(&ctx.storage).await, 4);
+
+    let (_, body) = send(&app, get(unread, Some(&s.student_token)).to_request()).await;
+    assert_eq!(body["data"]["unread_count"], 2);
+    let (_, body) = send(&app, get(unread, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(body["data"]["unread_count"], 1);
+
+    // 已投递的事件不会重复投递
+    let now = chrono::Utc::now().timestamp();
+    assert!(
+        ctx.storage
+            .list_due_outbox_events(now, 100)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(relay_due_events(&ctx.storage).await, 0);
+}
+
+#[actix_web::test]
+async fn test_homework_notification_fan_out_tracked_as_job() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("outboxjob").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&s.teacher_token),
+            json!({ "class_id": s.class.id, "title": "第二次作业" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["title"], "第二次作业");
+    assert_eq!(
+        body["data"]["notification_job"]["kind"],
+        "homework_notification"
+    );
+    assert_eq!(body["data"]["notification_job"]["status"], "pending");
+    let job_url = format!(
+        "/api/v1/jobs/{}",
+        body["data"]["notification_job"]["id"].as_str().unwrap()
+    );
+
+    // 任务只对发起人可见
+    let (status, _) = send(&app, get(&job_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    relay_due_events(&ctx.storage).await;
+
+    let (status, body) = send(&app, get(&job_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "completed");
+    assert!(body["data"]["download_url"].is_null());
+
+    let (status, _) = send(
+        &app,
+        get(&format!("{job_url}/download"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, body) = send(
+        &app,
+        get("/api/v1/notifications/unread-count", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["unread_count"], 2);
+}
@@ -0,0 +1,206 @@
+//! 按角色划分的接口权限矩阵与导出接口集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{ClassScenario, TestContext, build_app, get, post_json, send, send_raw};
+
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// 权限矩阵中的角色
+#[derive(Debug, Clone, Copy)]
+enum Actor {
+    Anonymous,
+    Admin,
+    Teacher,
+    Student,
+    Outsider,
+}
+
+impl Actor {
+    fn token(self, s: &ClassScenario) -> Option<&str> {
+        match self {
+            Actor::Anonymous => None,
+            Actor::Admin => Some(&s.admin_token),
+            Actor::Teacher => Some(&s.teacher_token),
+            Actor::Student => Some(&s.student_token),
+            Actor::Outsider => Some(&s.outsider_token),
+        }
+    }
+}
+
+#[actix_web::test]
+async fn test_read_permission_matrix() {
+    use Actor::*;
+
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("matrix").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let class_path = format!("/api/v1/classes/{}", s.class.id);
+    let students_path = format!("/api/v1/classes/{}/students", s.class.id);
+    let homework_path = format!("/api/v1/homeworks/{}", s.homework.id);
+    let stats_path = format!("/api/v1/homeworks/{}/stats", s.homework.id);
+
+    let cases: Vec<(&str, Vec<(Actor, StatusCode)>)> = vec![
+        (
+            class_path.as_str(),
+            vec![
+                (Anonymous, StatusCode::UNAUTHORIZED),
+                (Admin, StatusCode::OK),
+                (Teacher, StatusCode::OK),
+                (Student, StatusCode::OK),
+                (Outsider, StatusCode::FORBIDDEN),
+            ],
+        ),
+        (
+            students_path.as_str(),
+            vec![
+                (Anonymous, StatusCode::UNAUTHORIZED),
+                (Admin, StatusCode::OK),
+                (Teacher, StatusCode::OK),
+                (Student, StatusCode::FORBIDDEN),
+                (Outsider, StatusCode::FORBIDDEN),
+            ],
+        ),
+        (
+            homework_path.as_str(),
+            vec![
+                (Anonymous, StatusCode::UNAUTHORIZED),
+                (Teacher, StatusCode::OK),
+                (Student, StatusCode::OK),
+                (Outsider, StatusCode::FORBIDDEN),
+            ],
+        ),
+        (
+            stats_path.as_str(),
+            vec![
+                (Teacher, StatusCode::OK),
+                (Admin, StatusCode::OK),
+                (Student, StatusCode::FORBIDDEN),
+            ],
+        ),
+        (
+            "/api/v1/users",
+            vec![
+                (Anonymous, StatusCode::UNAUTHORIZED),
+                (Admin, StatusCode::OK),
+                (Teacher, StatusCode::FORBIDDEN),
+                (Student, StatusCode::FORBIDDEN),
+            ],
+        ),
+        (
+            "/api/v1/system/admin/settings",
+            vec![
+                (Admin, StatusCode::OK),
+                (Teacher, StatusCode::FORBIDDEN),
+                (Student, StatusCode::FORBIDDEN),
+            ],
+        ),
+        (
+            "/api/v1/system/admin/job-queues",
+            vec![
+                (Admin, StatusCode::OK),
+                (Teacher, StatusCode::FORBIDDEN),
+                (Student, StatusCode::FORBIDDEN),
+            ],
+        ),
+    ];
+
+    for (path, expectations) in cases {
+        for (actor, expected) in expectations {
+            let (status, _) = send(&app, get(path, actor.token(&s)).to_request()).await;
+            assert_eq!(status, expected, "GET {path} as {actor:?}");
+        }
+    }
+}
+
+#[actix_web::test]
+async fn test_write_permission_matrix() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("write_matrix").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 学生不能创建班级
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/classes",
+            Some(&s.student_token),
+            json!({ "name": "学生的班级" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 学生不能布置作业
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/homeworks",
+            Some(&s.student_token),
+            json!({ "class_id": s.class.id, "title": "越权作业" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 学生不能评分
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.student_token),
+            json!({ "submission_id": submission.id, "score": 100.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_class_report_export() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("export").await;
+    ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let path = format!("/api/v1/classes/{}/export", s.class.id);
+
+    for token in [&s.teacher_token, &s.admin_token] {
+        let (status, content_type, body) =
+            send_raw(&app, get(&path, Some(token)).to_request()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, XLSX_CONTENT_TYPE);
+        // xlsx 本质是 zip 包
+        assert!(body.starts_with(b"PK"));
+    }
+
+    for token in [&s.student_token, &s.outsider_token] {
+        let (status, _, _) = send_raw(&app, get(&path, Some(token)).to_request()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
+
+#[actix_web::test]
+async fn test_homework_stats_export() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("stats_export").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let path = format!("/api/v1/homeworks/{}/stats/export", s.homework.id);
+
+    let (status, content_type, body) =
+        send_raw(&app, get(&path, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, XLSX_CONTENT_TYPE);
+    assert!(body.starts_with(b"PK"));
+
+    let (status, _, _) = send_raw(&app, get(&path, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
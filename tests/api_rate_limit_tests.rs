@@ -0,0 +1,56 @@
+//! 速率限制响应头集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, post_json};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_rate_limit_headers_and_exceeded_payload() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let login = || {
+        post_json(
+            "/api/v1/auth/login",
+            None,
+            json!({ "username": "nobody", "password": "wrong-password" }),
+        )
+        .peer_addr("192.0.2.44:4000".parse().unwrap())
+        .to_request()
+    };
+
+    // 登录端点预设 5 次/分钟，每个响应都带有限额头
+    for remaining in (0..5).rev() {
+        let resp = test::call_service(&app, login()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let headers = resp.headers();
+        assert_eq!(headers.get("X-RateLimit-Limit").unwrap(), "5");
+        assert_eq!(
+            headers.get("X-RateLimit-Remaining").unwrap(),
+            remaining.to_string().as_str()
+        );
+        let reset: u64 = headers
+            .get("X-RateLimit-Reset")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(reset <= 60);
+    }
+
+    let resp = test::call_service(&app, login()).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers().get("X-RateLimit-Remaining").unwrap(), "0");
+    assert!(resp.headers().contains_key("Retry-After"));
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], ErrorCode::RateLimitExceeded as i32);
+    assert_eq!(body["data"]["limit"], 5);
+    assert_eq!(body["data"]["remaining"], 0);
+    assert_eq!(body["data"]["window_secs"], 60);
+    assert_eq!(body["data"]["policy"], "fixed_window");
+}
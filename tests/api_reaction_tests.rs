@@ -0,0 +1,218 @@
+//! 表情回应集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+/// 👍 的 URL 编码
+const THUMBS_UP_ENCODED: &str = "%F0%9F%91%8D";
+
+#[actix_web::test]
+async fn test_announcement_reactions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("react").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let feed_url = format!("/api/v1/classes/{}/feed", s.class.id);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/notifications",
+            Some(&s.teacher_token),
+            json!({ "class_id": s.class.id, "title": "周五测验" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, get(&feed_url, Some(&s.student_token)).to_request()).await;
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items[0]["event_type"], "announcement");
+    let announcement_id = items[0]["id"].as_i64().unwrap();
+    let homework_event_id = items
+        .iter()
+        .find(|item| item["event_type"] == "homework_created")
+        .and_then(|item| item["id"].as_i64())
+        .unwrap();
+
+    // 重复添加同一表情只计一次
+    for _ in 0..2 {
+        let (status, body) = send(
+            &app,
+            post_json(
+                "/api/v1/reactions",
+                Some(&s.student_token),
+                json!({ "target_type": "announcement", "target_id": announcement_id, "emoji": "👍" }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["data"]["counts"],
+            json!([{ "emoji": "👍", "count": 1 }])
+        );
+        assert_eq!(body["data"]["mine"], json!(["👍"]));
+    }
+    for emoji in ["👍", "🎉"] {
+        let (status, _) = send(
+            &app,
+            post_json(
+                "/api/v1/reactions",
+                Some(&s.teacher_token),
+                json!({ "target_type": "announcement", "target_id": announcement_id, "emoji": emoji }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // 动态流中的公告附带回应汇总
+    let (_, body) = send(&app, get(&feed_url, Some(&s.student_token)).to_request()).await;
+    let reactions = &body["data"]["items"][0]["reactions"];
+    assert_eq!(
+        reactions["counts"],
+        json!([{ "emoji": "👍", "count": 2 }, { "emoji": "🎉", "count": 1 }])
+    );
+    assert_eq!(reactions["mine"], json!(["👍"]));
+
+    // 取消回应
+    let (status, body) = send(
+        &app,
+        delete(
+            &format!(
+                "/api/v1/reactions?target_type=announcement&target_id={announcement_id}&emoji={THUMBS_UP_ENCODED}"
+            ),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"]["counts"],
+        json!([{ "emoji": "👍", "count": 1 }, { "emoji": "🎉", "count": 1 }])
+    );
+    assert_eq!(body["data"]["mine"], json!([]));
+
+    // 非法表情、非公告动态、非班级成员
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/reactions",
+            Some(&s.student_token),
+            json!({ "target_type": "announcement", "target_id": announcement_id, "emoji": "ok" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ReactionEmojiInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/reactions",
+            Some(&s.student_token),
+            json!({ "target_type": "announcement", "target_id": homework_event_id, "emoji": "👍" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::ReactionTargetNotFound as i32);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/reactions",
+            Some(&s.outsider_token),
+            json!({ "target_type": "announcement", "target_id": announcement_id, "emoji": "👍" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_grade_comment_reactions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("reactgrade").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 95.0, "comment": "思路清晰" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grade_id = body["data"]["id"].as_i64().unwrap();
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/reactions",
+            Some(&s.student_token),
+            json!({ "target_type": "grade_comment", "target_id": grade_id, "emoji": "❤️" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 学生查看自己的成绩时附带回应汇总
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{}/grade", submission.id),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"]["reactions"]["counts"],
+        json!([{ "emoji": "❤️", "count": 1 }])
+    );
+    assert_eq!(body["data"]["reactions"]["mine"], json!(["❤️"]));
+
+    // 教师查看时 mine 为空
+    let (_, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/grades/{grade_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["reactions"]["counts"][0]["count"], 1);
+    assert_eq!(body["data"]["reactions"]["mine"], json!([]));
+
+    // 其他学生不能回应
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/reactions",
+            Some(&s.outsider_token),
+            json!({ "target_type": "grade_comment", "target_id": grade_id, "emoji": "❤️" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,200 @@
+//! 注册策略集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::{Value, json};
+
+use common::{TestContext, build_app, get, post_json, send, set_setting};
+use rust_hwsystem_next::models::ErrorCode;
+
+fn register_body(username: &str, email: &str, extra: Value) -> Value {
+    let mut body = json!({
+        "username": username,
+        "email": email,
+        "password": "Passw0rd!Strong",
+        "role": "admin"
+    });
+    if let (Some(body), Some(extra)) = (body.as_object_mut(), extra.as_object()) {
+        body.extend(extra.clone());
+    }
+    body
+}
+
+#[actix_web::test]
+async fn test_registration_policy_enforced() {
+    let ctx = TestContext::new().await;
+    ctx.init_dynamic_config().await;
+    let s = ctx.class_scenario("regpolicy").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/system/registration-policy", None).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["mode"], "open");
+    assert_eq!(body["data"]["captcha_required"], false);
+
+    // 开放注册时客户端指定的角色被忽略
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            register_body("reg_open", "reg_open@example.com", json!({})),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["role"], "user");
+
+    // 关闭注册
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "registration.mode", "closed").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            register_body("reg_closed", "reg_closed@example.com", json!({})),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::RegistrationClosed as i32);
+
+    // 邀请制：缺少邀请码被拒绝，使用班级邀请码注册后自动加入班级
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "registration.mode", "invite_only").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            register_body("reg_invite", "reg_invite@example.com", json!({})),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::RegistrationInviteRequired as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            register_body(
+                "reg_invite",
+                "reg_invite@example.com",
+                json!({ "invite_code": s.class.invite_code }),
+            ),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let user_id = body["data"]["id"].as_i64().unwrap();
+    let membership = ctx
+        .storage
+        .get_class_user_by_user_id_and_class_id(user_id, s.class.id)
+        .await
+        .unwrap();
+    assert!(membership.is_some());
+
+    // 邮箱域名白名单
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "registration.mode", "open").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        set_setting(
+            &s.admin_token,
+            "registration.email_domains",
+            r#"["@school.edu"]"#,
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            register_body("reg_domain", "reg_domain@gmail.com", json!({})),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        body["code"],
+        ErrorCode::RegistrationEmailDomainNotAllowed as i32
+    );
+
+    // 人机验证（需配置服务商才生效）与默认角色
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "captcha.provider", "turnstile").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "registration.captcha_required", "true").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "registration.default_role", "admin").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(
+        &app,
+        set_setting(&s.admin_token, "registration.default_role", "teacher").to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            register_body("reg_captcha", "reg_captcha@school.edu", json!({})),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::CaptchaRequired as i32);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/system/registration-policy", None).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["email_domains"], json!(["school.edu"]));
+    assert_eq!(body["data"]["default_role"], "teacher");
+    assert_eq!(body["data"]["captcha_required"], true);
+}
@@ -0,0 +1,179 @@
+//! 退回重交集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_request_resubmission_reopens_homework() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("resubmit").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+    let stats_url = format!("/api/v1/homeworks/{homework_id}/stats");
+
+    // 作业已截止
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.teacher_token),
+            json!({ "deadline": "2020-01-01T00:00:00Z" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "初稿" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["is_late"], true);
+    let submission_id = body["data"]["id"].as_i64().unwrap();
+    let resubmit_url = format!("/api/v1/submissions/{submission_id}/request-resubmission");
+    let reopen_until = (chrono::Utc::now() + chrono::Duration::days(3)).to_rfc3339();
+
+    // 学生不能退回提交
+    let (status, _) = send(
+        &app,
+        post_json(
+            &resubmit_url,
+            Some(&s.student_token),
+            json!({ "comment": "重做", "reopen_until": reopen_until }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 意见为空或期限超出上限
+    let too_long = (chrono::Utc::now() + chrono::Duration::days(31)).to_rfc3339();
+    for body in [
+        json!({ "comment": "  ", "reopen_until": reopen_until }),
+        json!({ "comment": "重做", "reopen_until": too_long }),
+        json!({ "comment": "重做", "reopen_until": "2020-01-01T00:00:00Z" }),
+    ] {
+        let (status, resp) = send(
+            &app,
+            post_json(&resubmit_url, Some(&s.teacher_token), body).to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(resp["code"], ErrorCode::ResubmissionRequestInvalid as i32);
+    }
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &resubmit_url,
+            Some(&s.teacher_token),
+            json!({ "comment": "第二题缺少推导过程", "reopen_until": reopen_until }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["student_id"], s.student.id);
+    assert!(body["data"]["fulfilled_at"].is_null());
+
+    // 已退回的提交不能重复退回
+    let (status, body) = send(
+        &app,
+        post_json(
+            &resubmit_url,
+            Some(&s.teacher_token),
+            json!({ "comment": "重做", "reopen_until": reopen_until }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["code"],
+        ErrorCode::SubmissionStatusTransitionInvalid as i32
+    );
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{submission_id}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "returned");
+
+    // 退回后计为未提交
+    let (status, body) = send(&app, get(&stats_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["submitted_count"], 0);
+    assert_eq!(
+        body["data"]["unsubmitted_students"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // 重新开放期限内重交不计迟交
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "修改稿" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["is_late"], false);
+    assert_eq!(body["data"]["status"], "pending");
+
+    let (status, body) = send(&app, get(&stats_url, Some(&s.teacher_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["submitted_count"], 1);
+    assert_eq!(body["data"]["late_count"], 0);
+
+    // 退回请求已完成，再次提交按原截止时间计迟交
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "第三稿" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["is_late"], true);
+
+    // 旧版本不能被退回
+    let (status, _) = send(
+        &app,
+        post_json(
+            &resubmit_url,
+            Some(&s.teacher_token),
+            json!({ "comment": "重做", "reopen_until": reopen_until }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
@@ -0,0 +1,202 @@
+//! 角色申请（教师身份审核）集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+const MY_REQUESTS: &str = "/api/v1/users/me/role-requests";
+const QUEUE: &str = "/api/v1/users/role-requests";
+
+#[actix_web::test]
+async fn test_role_request_approve_flow() {
+    let ctx = TestContext::new().await;
+    let (applicant, applicant_token) = ctx.create_user_with_token("rr_user", UserRole::User).await;
+    let (_, admin_token) = ctx
+        .create_user_with_token("rr_admin", UserRole::Admin)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 提交申请
+    let (status, body) = send(
+        &app,
+        post_json(
+            MY_REQUESTS,
+            Some(&applicant_token),
+            json!({ "reason": "某某中学数学教师" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["status"], "pending");
+    assert_eq!(body["data"]["requested_role"], "teacher");
+    let request_id = body["data"]["id"].as_i64().unwrap();
+
+    // 已有待审核申请时不能重复提交
+    let (status, body) = send(
+        &app,
+        post_json(
+            MY_REQUESTS,
+            Some(&applicant_token),
+            json!({ "reason": "再次申请" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::RoleRequestPending as i32);
+
+    // 申请人不能访问审核队列
+    let (status, _) = send(&app, get(QUEUE, Some(&applicant_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 管理员在审核队列中看到申请
+    let (status, body) = send(
+        &app,
+        get(&format!("{QUEUE}?status=pending"), Some(&admin_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["username"], "rr_user");
+
+    // 通过申请后用户角色变为教师
+    let review_path = format!("{QUEUE}/{request_id}/review");
+    let (status, body) = send(
+        &app,
+        post_json(&review_path, Some(&admin_token), json!({ "approve": true })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "approved");
+    let user = ctx
+        .storage
+        .get_user_by_id(applicant.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.role, UserRole::Teacher);
+
+    // 已审核的申请不能再次审核
+    let (status, body) = send(
+        &app,
+        post_json(
+            &review_path,
+            Some(&admin_token),
+            json!({ "approve": false }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], ErrorCode::RoleRequestAlreadyReviewed as i32);
+}
+
+#[actix_web::test]
+async fn test_role_request_reject_keeps_role() {
+    let ctx = TestContext::new().await;
+    let (applicant, applicant_token) = ctx.create_user_with_token("rj_user", UserRole::User).await;
+    let (_, admin_token) = ctx
+        .create_user_with_token("rj_admin", UserRole::Admin)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (_, body) = send(
+        &app,
+        post_json(
+            MY_REQUESTS,
+            Some(&applicant_token),
+            json!({ "reason": "想当老师" }),
+        )
+        .to_request(),
+    )
+    .await;
+    let request_id = body["data"]["id"].as_i64().unwrap();
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &format!("{QUEUE}/{request_id}/review"),
+            Some(&admin_token),
+            json!({ "approve": false, "comment": "请补充教师资格证明" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "rejected");
+    assert_eq!(body["data"]["review_comment"], "请补充教师资格证明");
+
+    let user = ctx
+        .storage
+        .get_user_by_id(applicant.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.role, UserRole::User);
+
+    // 申请人可查看自己的申请记录，被拒绝后可重新申请
+    let (status, body) = send(&app, get(MY_REQUESTS, Some(&applicant_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["status"], "rejected");
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            MY_REQUESTS,
+            Some(&applicant_token),
+            json!({ "reason": "已补充证明" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[actix_web::test]
+async fn test_role_request_validation() {
+    let ctx = TestContext::new().await;
+    let (_, teacher_token) = ctx
+        .create_user_with_token("rv_teacher", UserRole::Teacher)
+        .await;
+    let (_, user_token) = ctx.create_user_with_token("rv_user", UserRole::User).await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 教师无需申请
+    let (status, body) = send(
+        &app,
+        post_json(MY_REQUESTS, Some(&teacher_token), json!({ "reason": "x" })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::RoleRequestNotAllowed as i32);
+
+    // 申请说明不能为空
+    let (status, _) = send(
+        &app,
+        post_json(MY_REQUESTS, Some(&user_token), json!({ "reason": "  " })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 附件必须是自己上传的文件
+    let (status, body) = send(
+        &app,
+        post_json(
+            MY_REQUESTS,
+            Some(&user_token),
+            json!({ "reason": "证明见附件", "attachment": "no-such-token" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::FileNotFound as i32);
+}
@@ -0,0 +1,219 @@
+//! 评分量规库集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_rubric_library() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("rubric").await;
+    let (colleague, colleague_token) = ctx
+        .create_user_with_token("rubric_colleague", UserRole::Teacher)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_url = format!("/api/v1/homeworks/{}/rubric", s.homework.id);
+
+    let criteria = json!([
+        { "title": "论证", "points": 6.0, "levels": [
+            { "label": "充分", "points": 6.0 },
+            { "label": "不足", "points": 2.0 }
+        ] },
+        { "title": "表达", "points": 4.0 }
+    ]);
+
+    // 学生不能使用量规库；等级分值不能超过维度满分
+    let (status, _) = send(
+        &app,
+        get("/api/v1/rubrics", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/rubrics",
+            Some(&s.teacher_token),
+            json!({ "title": "论文", "criteria": [
+                { "title": "论证", "points": 5.0, "levels": [{ "label": "满分", "points": 8.0 }] }
+            ] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::RubricInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/rubrics",
+            Some(&s.teacher_token),
+            json!({ "title": "论文评分", "criteria": criteria }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["version"], 1);
+    assert_eq!(body["data"]["total_points"], 10.0);
+    let rubric_id = body["data"]["id"].as_i64().unwrap();
+    let rubric_url = format!("/api/v1/rubrics/{rubric_id}");
+
+    // 未公开的量规对同事不可见
+    let (status, body) = send(&app, get(&rubric_url, Some(&colleague_token)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::RubricNotFound as i32);
+
+    // 通过分享码导入副本
+    let (status, body) = send(
+        &app,
+        post_json(
+            &format!("{rubric_url}/share-code"),
+            Some(&s.teacher_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let share_code = body["data"]["share_code"].as_str().unwrap().to_string();
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/rubrics/import",
+            Some(&colleague_token),
+            json!({ "share_code": share_code.to_lowercase() }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["owner_id"], colleague.id);
+    assert_eq!(body["data"]["source_rubric_id"], rubric_id);
+    assert_eq!(body["data"]["total_points"], 10.0);
+
+    // 作业使用版本 1 的快照
+    let (status, body) = send(
+        &app,
+        put_json(
+            &homework_url,
+            Some(&s.teacher_token),
+            json!({ "rubric_id": rubric_id }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["rubric_version"], 1);
+
+    // 修改评分维度生成版本 2，作业上的量规不变
+    let (status, body) = send(
+        &app,
+        put_json(
+            &rubric_url,
+            Some(&s.teacher_token),
+            json!({ "criteria": [{ "title": "论证", "points": 20.0 }], "org_visible": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["version"], 2);
+    assert_eq!(body["data"]["total_points"], 20.0);
+    let (status, body) = send(
+        &app,
+        get(&format!("{rubric_url}/versions"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 2);
+
+    let (status, body) = send(
+        &app,
+        get(&homework_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["rubric_version"], 1);
+    assert_eq!(body["data"]["total_points"], 10.0);
+    assert_eq!(body["data"]["criteria"].as_array().unwrap().len(), 2);
+
+    // 组织可见后同事可以看到，但看不到分享码；同事不能修改
+    let (status, body) = send(
+        &app,
+        get("/api/v1/rubrics?scope=org", Some(&colleague_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert!(items[0]["share_code"].is_null());
+    let (status, _) = send(
+        &app,
+        put_json(
+            &rubric_url,
+            Some(&colleague_token),
+            json!({ "title": "改名" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 停用分享码后无法再导入
+    let (status, _) = send(
+        &app,
+        delete(&format!("{rubric_url}/share-code"), Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/rubrics/import",
+            Some(&colleague_token),
+            json!({ "share_code": share_code }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::RubricShareCodeInvalid as i32);
+
+    // 删除量规后作业快照保留
+    let (status, _) = send(
+        &app,
+        delete(&rubric_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        get(&homework_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["rubric_id"].is_null());
+    assert_eq!(body["data"]["title"], "论文评分");
+
+    let (status, _) = send(
+        &app,
+        delete(&homework_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        get(&homework_url, Some(&s.teacher_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::HomeworkRubricNotFound as i32);
+}
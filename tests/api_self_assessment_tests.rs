@@ -0,0 +1,168 @@
+//! 提交自评集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_self_assessment_required_and_aggregated() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("selfassess").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.teacher_token),
+            json!({ "require_self_assessment": true }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["require_self_assessment"], true);
+
+    // 未填写自评
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "答案" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::SelfAssessmentRequired as i32);
+
+    // 把握程度超出范围
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({
+                "homework_id": homework_id,
+                "content": "答案",
+                "self_assessment": { "confidence": 6, "time_spent_minutes": 30 }
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::SelfAssessmentInvalid as i32);
+
+    let mut submission_id = 0;
+    for (confidence, minutes) in [(2, 90), (4, 40)] {
+        let (status, body) = send(
+            &app,
+            post_json(
+                "/api/v1/submissions",
+                Some(&s.student_token),
+                json!({
+                    "homework_id": homework_id,
+                    "content": "答案",
+                    "self_assessment": {
+                        "confidence": confidence,
+                        "time_spent_minutes": minutes,
+                        "difficulties": "  第三题不会  "
+                    }
+                }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        submission_id = body["data"]["id"].as_i64().unwrap();
+    }
+
+    // 教师批改时可见自评
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{submission_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["self_assessment"]["confidence"], 4);
+    assert_eq!(
+        body["data"]["self_assessment"]["difficulties"],
+        "第三题不会"
+    );
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!(
+                "/api/v1/homeworks/{homework_id}/submissions/user/{}",
+                s.student.id
+            ),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][1]["self_assessment"]["confidence"], 2);
+
+    // 统计只计入最新提交的自评，且不含学生身份
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/stats"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let stats = &body["data"]["self_assessment"];
+    assert_eq!(stats["response_count"], 1);
+    assert_eq!(stats["average_time_spent_minutes"], 40.0);
+    assert_eq!(stats["confidence_distribution"], json!([0, 0, 0, 1, 0]));
+}
+
+#[actix_web::test]
+async fn test_self_assessment_optional_by_default() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("selfassess_opt").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let submission = ctx.create_submission(&s.student, &s.homework, "答案").await;
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{}", submission.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["self_assessment"].is_null());
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{}/stats", s.homework.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["self_assessment"].is_null());
+}
@@ -0,0 +1,116 @@
+//! 学习目标集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_student_goal_progress_on_dashboard() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("goal").await;
+    ctx.create_homework(&s.teacher, &s.class, "goal 作业二")
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let goal_path = format!("/api/v1/users/me/goals/{}", s.class.id);
+
+    // 目标值必须在 (0, 100] 范围内
+    let (status, _) = send(
+        &app,
+        put_json(
+            &goal_path,
+            Some(&s.student_token),
+            json!({ "target_percent": 120.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 教师与班级外用户不能设置目标
+    for token in [&s.teacher_token, &s.outsider_token] {
+        let (status, _) = send(
+            &app,
+            put_json(&goal_path, Some(token), json!({ "target_percent": 80.0 })).to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    let (status, body) = send(
+        &app,
+        put_json(
+            &goal_path,
+            Some(&s.student_token),
+            json!({ "target_percent": 80.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["target_percent"], 80.0);
+
+    // 两份满分 100 的作业，第一份得 70 分：剩余作业需要 90% 才能达到 80%
+    let submission = ctx
+        .create_submission(&s.student, &s.homework, "我的答案")
+        .await;
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission.id, "score": 70.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/auth/me/dashboard", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let goal = &body["data"]["goals"][0];
+    assert_eq!(goal["class_id"], s.class.id);
+    assert_eq!(goal["current_percent"], 70.0);
+    assert_eq!(goal["required_percent"], 90.0);
+    assert_eq!(goal["best_possible_percent"], 85.0);
+    assert_eq!(goal["remaining_count"], 1);
+    assert_eq!(goal["status"], "behind");
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/users/me/goals", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    // 删除目标后仪表盘不再显示
+    let (status, _) = send(
+        &app,
+        delete(&goal_path, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &app,
+        delete(&goal_path, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::StudentGoalNotFound as i32);
+
+    let (_, body) = send(
+        &app,
+        get("/api/v1/auth/me/dashboard", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(body["data"]["goals"].as_array().unwrap().len(), 0);
+}
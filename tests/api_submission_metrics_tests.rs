@@ -0,0 +1,102 @@
+//! 提交字数统计集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+
+use common::{TestContext, build_app, get, send};
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_submission_content_metrics() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("metrics").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+
+    let other = ctx.create_user("metrics_other", UserRole::User).await;
+    ctx.join_class(&other, &s.class, ClassUserRole::Student)
+        .await;
+    ctx.create_submission(&s.student, &s.homework, "Hello world\n你好")
+        .await;
+    ctx.create_submission(&other, &s.homework, " 短 ").await;
+
+    // 提交列表带字数
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions?homework_id={homework_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"]["items"].as_array().unwrap();
+    let item = items
+        .iter()
+        .find(|item| item["creator_id"] == s.student.id)
+        .unwrap();
+    assert_eq!(item["word_count"], 4);
+    assert_eq!(item["char_count"], 12);
+    assert_eq!(item["attachment_count"], 0);
+
+    // 提交概览带字数
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/submissions/summary"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let summary = body["data"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["creator"]["id"] == other.id)
+        .unwrap();
+    assert_eq!(summary["latest_submission"]["word_count"], 1);
+    assert_eq!(summary["latest_submission"]["char_count"], 1);
+
+    // 作业统计汇总最短、平均、最长
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{homework_id}/stats"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let content = &body["data"]["content_stats"];
+    assert_eq!(content["word_count"]["min"], 1);
+    assert_eq!(content["word_count"]["max"], 4);
+    assert_eq!(content["word_count"]["average"], 2.5);
+    assert_eq!(content["char_count"]["max"], 12);
+    assert_eq!(content["attachment_count"]["max"], 0);
+}
+
+#[actix_web::test]
+async fn test_homework_stats_without_submissions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("metricsempty").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks/{}/stats", s.homework.id),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["content_stats"].is_null());
+}
@@ -0,0 +1,124 @@
+//! 提交外部引用集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_submission_references() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("subref").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let homework_id = s.homework.id;
+
+    // 仅接受附件的作业，外部引用可代替附件
+    let (status, _) = send(
+        &app,
+        put_json(
+            &format!("/api/v1/homeworks/{homework_id}"),
+            Some(&s.teacher_token),
+            json!({ "submission_mode": "attachment" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 格式无效的引用
+    for reference in [
+        json!({ "kind": "git", "url": "https://github.com/a/b" }),
+        json!({ "kind": "git", "url": "https://github.com/a/b", "commit": "main" }),
+        json!({ "kind": "git", "url": "javascript:alert(1)", "commit": "a1b2c3d" }),
+        json!({ "kind": "document", "url": "https://docs.qq.com/doc/x", "commit": "a1b2c3d" }),
+    ] {
+        let (status, body) = send(
+            &app,
+            post_json(
+                "/api/v1/submissions",
+                Some(&s.student_token),
+                json!({ "homework_id": homework_id, "content": "", "references": [reference] }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], ErrorCode::SubmissionReferenceInvalid as i32);
+    }
+
+    // 既无附件也无引用
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": homework_id, "content": "" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::SubmissionAttachmentRequired as i32);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({
+                "homework_id": homework_id,
+                "content": "",
+                "references": [
+                    { "kind": "git", "url": " https://github.com/a/b ", "commit": "A1B2C3D" },
+                    { "kind": "document", "url": "https://docs.qq.com/doc/x" }
+                ]
+            }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let submission_id = body["data"]["id"].as_i64().unwrap();
+
+    // 教师批改时在提交详情与提交历史中看到引用
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{submission_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let references = body["data"]["references"].as_array().unwrap();
+    assert_eq!(references.len(), 2);
+    assert_eq!(references[0]["kind"], "git");
+    assert_eq!(references[0]["url"], "https://github.com/a/b");
+    assert_eq!(references[0]["commit_hash"], "a1b2c3d");
+    assert!(references[0]["metadata_fetched_at"].is_null());
+    assert_eq!(references[1]["kind"], "document");
+    assert!(references[1]["commit_hash"].is_null());
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!(
+                "/api/v1/homeworks/{homework_id}/submissions/user/{}",
+                s.student.id
+            ),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"]["items"][0]["references"][1]["url"],
+        "https://docs.qq.com/doc/x"
+    );
+}
@@ -0,0 +1,205 @@
+//! 提交状态工作流集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, put_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+
+#[actix_web::test]
+async fn test_class_submission_workflow_transitions() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("workflow").await;
+    let app = test::init_service(build_app(&ctx)).await;
+    let workflow_url = format!("/api/v1/classes/{}/submission-workflow", s.class.id);
+
+    // 默认工作流：只有内置状态
+    let (status, body) = send(
+        &app,
+        get(&workflow_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["customized"], false);
+    assert_eq!(body["data"]["statuses"].as_array().unwrap().len(), 4);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/submissions",
+            Some(&s.student_token),
+            json!({ "homework_id": s.homework.id, "content": "答案" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let submission_id = body["data"]["id"].as_i64().unwrap();
+    let status_url = format!("/api/v1/submissions/{submission_id}/status");
+
+    // 未自定义时不能转换
+    let (status, body) = send(
+        &app,
+        post_json(
+            &status_url,
+            Some(&s.teacher_token),
+            json!({ "status": "needs_revision" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["code"],
+        ErrorCode::SubmissionStatusTransitionInvalid as i32
+    );
+
+    let workflow = json!({
+        "statuses": [
+            { "key": "needs_revision", "label": "需要修改" },
+            { "key": "revised", "label": "已修改" }
+        ],
+        "transitions": [
+            { "from": "pending", "to": "needs_revision" },
+            { "from": "needs_revision", "to": "revised", "roles": ["student"] }
+        ]
+    });
+
+    // 学生不能设置工作流；无效定义被拒绝
+    let (status, _) = send(
+        &app,
+        put_json(&workflow_url, Some(&s.student_token), workflow.clone()).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(
+        &app,
+        put_json(
+            &workflow_url,
+            Some(&s.teacher_token),
+            json!({ "statuses": [{ "key": "graded", "label": "重名" }] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::SubmissionWorkflowInvalid as i32);
+
+    let (status, body) = send(
+        &app,
+        put_json(&workflow_url, Some(&s.teacher_token), workflow).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["customized"], true);
+    assert_eq!(body["data"]["transitions"][0]["roles"], json!(["teacher"]));
+
+    // 学生不能执行仅限教师的转换
+    let (status, _) = send(
+        &app,
+        post_json(
+            &status_url,
+            Some(&s.student_token),
+            json!({ "status": "needs_revision" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            &status_url,
+            Some(&s.teacher_token),
+            json!({ "status": "needs_revision" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["from"], "pending");
+    assert_eq!(body["data"]["label"], "需要修改");
+
+    // 提交详情与列表筛选返回自定义状态
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{submission_id}"),
+            Some(&s.student_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "needs_revision");
+
+    let (status, body) = send(
+        &app,
+        get(
+            &format!(
+                "/api/v1/submissions?homework_id={}&status=needs_revision",
+                s.homework.id
+            ),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 1);
+
+    // 学生可以转换自己的提交
+    let (status, _) = send(
+        &app,
+        post_json(
+            &status_url,
+            Some(&s.student_token),
+            json!({ "status": "revised" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 评分清除自定义状态
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&s.teacher_token),
+            json!({ "submission_id": submission_id, "score": 90.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/submissions/{submission_id}"),
+            Some(&s.teacher_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "graded");
+
+    // 恢复默认工作流
+    let (status, body) = send(
+        &app,
+        put_json(
+            &workflow_url,
+            Some(&s.teacher_token),
+            json!({ "statuses": [] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["customized"], false);
+}
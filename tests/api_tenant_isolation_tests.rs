@@ -0,0 +1,324 @@
+//! 组织管理员的租户隔离测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, put_json, send, token_for};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::organizations::requests::CreateOrganizationRequest;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_org_admin_cannot_access_other_org_records() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let mut org_ids = Vec::new();
+    for slug in ["org-a", "org-b"] {
+        let org = ctx
+            .storage
+            .create_organization(CreateOrganizationRequest {
+                name: slug.to_string(),
+                slug: slug.to_string(),
+                description: None,
+            })
+            .await
+            .expect("Failed to create organization");
+        org_ids.push(Some(org.id));
+    }
+    let (org_a, org_b) = (org_ids[0], org_ids[1]);
+
+    // 班级归属教师所在的组织 A
+    let teacher = ctx
+        .create_org_user("tenant_teacher", UserRole::Teacher, org_a)
+        .await;
+    let student = ctx
+        .create_org_user("tenant_student", UserRole::User, org_a)
+        .await;
+    let admin_a = token_for(
+        &ctx.create_org_user("tenant_admin_a", UserRole::Admin, org_a)
+            .await,
+    );
+    let admin_b = token_for(
+        &ctx.create_org_user("tenant_admin_b", UserRole::Admin, org_b)
+            .await,
+    );
+
+    let class = ctx.create_class(&teacher, "组织 A 班级").await;
+    assert_eq!(class.org_id, org_a);
+    ctx.join_class(&student, &class, ClassUserRole::Student)
+        .await;
+    let homework = ctx.create_homework(&teacher, &class, "组织 A 作业").await;
+    let submission = ctx.create_submission(&student, &homework, "答案").await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&token_for(&teacher)),
+            json!({ "submission_id": submission.id, "score": 80.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grade_id = body["data"]["id"].as_i64().unwrap();
+
+    let submission_url = format!("/api/v1/submissions/{}", submission.id);
+    let grade_url = format!("/api/v1/grades/{grade_id}");
+    let homework_url = format!("/api/v1/homeworks/{}", homework.id);
+
+    // 本组织管理员正常访问
+    for url in [&submission_url, &grade_url, &homework_url] {
+        let (status, _) = send(&app, get(url, Some(&admin_a)).to_request()).await;
+        assert_eq!(status, StatusCode::OK, "{url}");
+    }
+
+    // 其他组织的管理员按资源不存在处理
+    for (url, code) in [
+        (&submission_url, ErrorCode::SubmissionNotFound),
+        (&grade_url, ErrorCode::GradeNotFound),
+        (&homework_url, ErrorCode::HomeworkNotFound),
+    ] {
+        let (status, body) = send(&app, get(url, Some(&admin_b)).to_request()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{url}");
+        assert_eq!(body["code"], code as i32);
+    }
+
+    let (status, _) = send(
+        &app,
+        get(&format!("{submission_url}/grade"), Some(&admin_b)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/grades",
+            Some(&admin_b),
+            json!({ "submission_id": submission.id, "score": 0.0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = send(
+        &app,
+        put_json(&grade_url, Some(&admin_b), json!({ "score": 0.0 })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::GradeNotFound as i32);
+
+    let (status, _) = send(
+        &app,
+        put_json(&homework_url, Some(&admin_b), json!({ "title": "改名" })).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    for url in [&submission_url, &homework_url] {
+        let (status, _) = send(&app, delete(url, Some(&admin_b)).to_request()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{url}");
+    }
+
+    // 列表只返回本组织的记录
+    for url in [
+        "/api/v1/homeworks".to_string(),
+        format!("/api/v1/submissions?homework_id={}", homework.id),
+        format!("/api/v1/grades?homework_id={}", homework.id),
+    ] {
+        let (status, body) = send(&app, get(&url, Some(&admin_b)).to_request()).await;
+        assert_eq!(status, StatusCode::OK, "{url}");
+        assert_eq!(body["data"]["items"].as_array().unwrap().len(), 0, "{url}");
+
+        let (status, body) = send(&app, get(&url, Some(&admin_a)).to_request()).await;
+        assert_eq!(status, StatusCode::OK, "{url}");
+        assert_eq!(body["data"]["items"].as_array().unwrap().len(), 1, "{url}");
+    }
+
+    // 越权请求未修改任何数据
+    let grade = ctx
+        .storage
+        .get_grade_by_id(grade_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(grade.score, 80.0);
+    assert!(
+        ctx.storage
+            .get_submission_by_id(submission.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    let homework = ctx
+        .storage
+        .get_homework_by_id(homework.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(homework.title, "组织 A 作业");
+}
+
+#[actix_web::test]
+async fn test_org_admin_cannot_manage_other_org_class_members() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let mut org_ids = Vec::new();
+    for slug in ["member-a", "member-b"] {
+        let org = ctx
+            .storage
+            .create_organization(CreateOrganizationRequest {
+                name: slug.to_string(),
+                slug: slug.to_string(),
+                description: None,
+            })
+            .await
+            .expect("Failed to create organization");
+        org_ids.push(Some(org.id));
+    }
+    let (org_a, org_b) = (org_ids[0], org_ids[1]);
+
+    let teacher = ctx
+        .create_org_user("member_teacher", UserRole::Teacher, org_a)
+        .await;
+    let student = ctx
+        .create_org_user("member_student", UserRole::User, org_a)
+        .await;
+    let admin_a = token_for(
+        &ctx.create_org_user("member_admin_a", UserRole::Admin, org_a)
+            .await,
+    );
+    let admin_b = token_for(
+        &ctx.create_org_user("member_admin_b", UserRole::Admin, org_b)
+            .await,
+    );
+
+    let class = ctx.create_class(&teacher, "组织 A 班级").await;
+    ctx.join_class(&student, &class, ClassUserRole::Student)
+        .await;
+    let members_url = format!("/api/v1/classes/{}/students", class.id);
+    let member_url = format!("{members_url}/{}", student.id);
+
+    // 其他组织的管理员无法查看、修改或移除成员
+    for url in [&members_url, &member_url] {
+        let (status, body) = send(&app, get(url, Some(&admin_b)).to_request()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{url}");
+        assert_eq!(body["code"], ErrorCode::ClassNotFound as i32);
+    }
+    let (status, _) = send(
+        &app,
+        put_json(
+            &member_url,
+            Some(&admin_b),
+            json!({ "role": "class_representative" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, delete(&member_url, Some(&admin_b)).to_request()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let member = ctx
+        .storage
+        .get_class_user_by_user_id_and_class_id(student.id, class.id)
+        .await
+        .unwrap()
+        .expect("member should remain");
+    assert_eq!(member.role, ClassUserRole::Student);
+
+    // 本组织管理员正常访问
+    for url in [&members_url, &member_url] {
+        let (status, _) = send(&app, get(url, Some(&admin_a)).to_request()).await;
+        assert_eq!(status, StatusCode::OK, "{url}");
+    }
+}
+
+#[actix_web::test]
+async fn test_org_admin_cannot_change_platform_settings() {
+    let ctx = TestContext::new().await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let org = ctx
+        .storage
+        .create_organization(CreateOrganizationRequest {
+            name: "settings-org".to_string(),
+            slug: "settings-org".to_string(),
+            description: None,
+        })
+        .await
+        .expect("Failed to create organization");
+    let org_admin = token_for(
+        &ctx.create_org_user("settings_org_admin", UserRole::Admin, Some(org.id))
+            .await,
+    );
+    let teacher = ctx
+        .create_org_user("settings_teacher", UserRole::Teacher, Some(org.id))
+        .await;
+
+    // 系统设置全平台共享，组织管理员默认无权访问
+    let (status, _) = send(
+        &app,
+        get("/api/v1/system/admin/settings", Some(&org_admin)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        put_json(
+            "/api/v1/system/admin/settings/upload.max_size",
+            Some(&org_admin),
+            json!({ "value": "1" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 组织管理员默认拥有本组织范围的权限
+    let (status, body) = send(
+        &app,
+        get("/api/v1/users/me/permissions", Some(&org_admin)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["is_super_admin"], false);
+    assert_eq!(
+        body["data"]["permissions"],
+        json!(["user_manage", "class_manage"])
+    );
+
+    // 只能委派本组织范围的权限
+    let url = format!("/api/v1/users/{}/permissions", teacher.id);
+    let (status, _) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&org_admin),
+            json!({ "permissions": ["system_settings"] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        put_json(
+            &url,
+            Some(&org_admin),
+            json!({ "permissions": ["user_manage"] }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
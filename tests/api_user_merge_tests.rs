@@ -0,0 +1,156 @@
+//! 重复账号合并集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, get, post_json, send};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::users::entities::{UserRole, UserStatus};
+
+const MERGE_URL: &str = "/api/v1/admin/users/merge";
+
+#[actix_web::test]
+async fn test_merge_users_moves_data_to_target() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("merge").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 重复账号同时在同一班级（课代表）和另一个班级中，并且提交过同一份作业
+    let duplicate = s.outsider.clone();
+    ctx.join_class(&duplicate, &s.class, ClassUserRole::ClassRepresentative)
+        .await;
+    let other_class = ctx.create_class(&s.teacher, "merge 另一个班级").await;
+    ctx.join_class(&duplicate, &other_class, ClassUserRole::Student)
+        .await;
+    ctx.create_submission(&s.student, &s.homework, "第一次提交")
+        .await;
+    ctx.create_submission(&duplicate, &s.homework, "重复账号提交")
+        .await;
+
+    // 目标账号尚不在另一个班级，成员关系查询结果被缓存
+    let feed_url = format!("/api/v1/classes/{}/feed", other_class.id);
+    let (status, _) = send(&app, get(&feed_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 非管理员无权合并
+    let (status, _) = send(
+        &app,
+        post_json(
+            MERGE_URL,
+            Some(&s.teacher_token),
+            json!({ "source_user_id": duplicate.id, "target_user_id": s.student.id }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            MERGE_URL,
+            Some(&s.admin_token),
+            json!({ "source_user_id": duplicate.id, "target_user_id": s.student.id }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let data = &body["data"];
+    assert_eq!(data["class_memberships"], 1);
+    assert_eq!(data["merged_memberships"], 1);
+    assert_eq!(data["submissions"], 1);
+    assert_eq!(data["renumbered_homeworks"], 1);
+
+    // 同班级成员关系保留较高的角色
+    let member = ctx
+        .storage
+        .get_class_user_by_user_id_and_class_id(s.student.id, s.class.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.role, ClassUserRole::ClassRepresentative);
+    assert!(
+        ctx.storage
+            .get_class_user_by_user_id_and_class_id(s.student.id, other_class.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        ctx.storage
+            .get_class_user_by_user_id_and_class_id(duplicate.id, s.class.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // 合并后成员缓存已清除，目标账号立即可以访问转入的班级
+    let (status, _) = send(&app, get(&feed_url, Some(&s.student_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 提交按时间重新编号，最新版本来自重复账号
+    let latest = ctx
+        .storage
+        .get_latest_submission(s.homework.id, s.student.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.version, 2);
+    assert_eq!(latest.content.as_deref(), Some("重复账号提交"));
+
+    // 源账号被停用
+    let source = ctx
+        .storage
+        .get_user_by_id(duplicate.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(source.status, UserStatus::Suspended);
+}
+
+#[actix_web::test]
+async fn test_merge_users_validation() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("mergecheck").await;
+    let (other_admin, _) = ctx
+        .create_user_with_token("mergecheck_admin2", UserRole::Admin)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    for (source, target) in [
+        (s.student.id, s.student.id),
+        (s.admin.id, s.student.id),
+        (other_admin.id, s.student.id),
+    ] {
+        let (status, body) = send(
+            &app,
+            post_json(
+                MERGE_URL,
+                Some(&s.admin_token),
+                json!({ "source_user_id": source, "target_user_id": target }),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], ErrorCode::UserMergeInvalid as i32);
+    }
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            MERGE_URL,
+            Some(&s.admin_token),
+            json!({ "source_user_id": 999_999, "target_user_id": s.student.id }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::UserNotFound as i32);
+}
@@ -0,0 +1,213 @@
+//! 家长/导师只读查看集成测试
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+
+use common::{TestContext, build_app, delete, get, post_json, send, token_for};
+use rust_hwsystem_next::models::ErrorCode;
+use rust_hwsystem_next::models::users::entities::UserRole;
+
+#[actix_web::test]
+async fn test_viewer_registers_with_invitation_and_reads_summary() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("viewer").await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/viewer/invitations",
+            Some(&s.student_token),
+            json!({ "note": "妈妈" }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["status"], "pending");
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+    let grant_id = body["data"]["id"].as_i64().unwrap();
+
+    let register = |username: &str| {
+        post_json(
+            "/api/v1/auth/register",
+            None,
+            json!({
+                "username": username,
+                "email": format!("{username}@example.com"),
+                "password": "Passw0rd!Strong",
+                "viewer_invitation": token,
+            }),
+        )
+        .to_request()
+    };
+    let (status, body) = send(&app, register("viewer_parent")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["role"], "viewer");
+
+    // 邀请只能使用一次
+    let (status, body) = send(&app, register("viewer_parent2")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::ViewerInvitationInvalid as i32);
+
+    let viewer = ctx
+        .storage
+        .get_user_by_username("viewer_parent")
+        .await
+        .unwrap()
+        .unwrap();
+    let viewer_token = token_for(&viewer);
+
+    let (status, body) = send(
+        &app,
+        get("/api/v1/viewer/students", Some(&viewer_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["student"]["id"], s.student.id);
+
+    let summary_url = format!("/api/v1/viewer/students/{}/summary", s.student.id);
+    let (status, body) = send(&app, get(&summary_url, Some(&viewer_token)).to_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["student"]["username"], s.student.username);
+    assert_eq!(body["data"]["class_progress"][0]["class_id"], s.class.id);
+    assert!(body["data"].get("unread_notifications").is_none());
+
+    // 未授权的学生与其他接口均不可访问
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/viewer/students/{}/summary", s.outsider.id),
+            Some(&viewer_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ViewerAccessDenied as i32);
+    let (status, body) = send(
+        &app,
+        get(
+            &format!("/api/v1/homeworks?class_id={}", s.class.id),
+            Some(&viewer_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], ErrorCode::ViewerAccessDenied as i32);
+    let (status, _) = send(
+        &app,
+        get("/api/v1/auth/me", Some(&viewer_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 学生查看并撤销授权
+    let (status, body) = send(
+        &app,
+        get("/api/v1/viewer/grants", Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["items"][0]["status"], "active");
+    assert_eq!(body["data"]["items"][0]["viewer"]["id"], viewer.id);
+
+    let grant_url = format!("/api/v1/viewer/grants/{grant_id}");
+    let (status, _) = send(
+        &app,
+        delete(&grant_url, Some(&s.outsider_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        delete(&grant_url, Some(&s.student_token)).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(&app, get(&summary_url, Some(&viewer_token)).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_existing_viewer_accepts_invitation() {
+    let ctx = TestContext::new().await;
+    let s = ctx.class_scenario("viewerok").await;
+    let (_, viewer_token) = ctx
+        .create_user_with_token("viewerok_mentor", UserRole::Viewer)
+        .await;
+    let app = test::init_service(build_app(&ctx)).await;
+
+    // 只有学生可以发出邀请，有效期受限
+    let (status, _) = send(
+        &app,
+        post_json("/api/v1/viewer/invitations", Some(&viewer_token), json!({})).to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/viewer/invitations",
+            Some(&s.student_token),
+            json!({ "expires_in_days": 0 }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, body) = send(
+        &app,
+        post_json(
+            "/api/v1/viewer/invitations",
+            Some(&s.student_token),
+            json!({}),
+        )
+        .to_request(),
+    )
+    .await;
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+
+    // 只有查看账号可以接受邀请
+    let (status, _) = send(
+        &app,
+        post_json(
+            "/api/v1/viewer/invitations/accept",
+            Some(&s.outsider_token),
+            json!({ "token": token }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        post_json(
+            "/api/v1/viewer/invitations/accept",
+            Some(&viewer_token),
+            json!({ "token": token }),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["student_id"], s.student.id);
+
+    let (status, _) = send(
+        &app,
+        get(
+            &format!("/api/v1/viewer/students/{}/summary", s.student.id),
+            Some(&viewer_token),
+        )
+        .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -1,10 +1,357 @@
-//! 测试工具模块
+//! 集成测试工具模块
 //!
-//! 提供测试所需的通用工具。
+//! 基于内存 SQLite 启动完整应用，提供用户、班级、作业等测试夹具，
+//! 以及带认证的请求辅助函数。每个测试应创建独立的 [`TestContext`]，互不共享数据。
 
-/// 初始化测试环境
-pub fn init_test_env() {
-    // 设置测试环境变量
-    std::env::set_var("APP_ENV", "development");
-    std::env::set_var("RUST_LOG", "debug");
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use actix_web::test::{self, TestRequest};
+use actix_web::{App, Error, web};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use rust_hwsystem_next::cache::ObjectCache;
+use rust_hwsystem_next::cache::object_cache::moka::MokaCacheWrapper;
+use rust_hwsystem_next::middlewares::rate_limit::set_limit_override;
+use rust_hwsystem_next::models::AppStartTime;
+use rust_hwsystem_next::models::class_users::entities::ClassUserRole;
+use rust_hwsystem_next::models::classes::entities::Class;
+use rust_hwsystem_next::models::classes::requests::CreateClassRequest;
+use rust_hwsystem_next::models::homeworks::entities::Homework;
+use rust_hwsystem_next::models::homeworks::requests::CreateHomeworkRequest;
+use rust_hwsystem_next::models::submissions::entities::Submission;
+use rust_hwsystem_next::models::submissions::requests::CreateSubmissionRequest;
+use rust_hwsystem_next::models::users::entities::{User, UserRole};
+use rust_hwsystem_next::models::users::requests::CreateUserRequest;
+use rust_hwsystem_next::routes;
+use rust_hwsystem_next::services::system::DynamicConfig;
+use rust_hwsystem_next::storage::Storage;
+use rust_hwsystem_next::storage::sea_orm_storage::SeaOrmStorage;
+use rust_hwsystem_next::utils::jwt::JwtUtils;
+use rust_hwsystem_next::utils::password::hash_password;
+use rust_hwsystem_next::utils::{json_error_handler, query_error_handler};
+
+/// 测试用户的统一密码
+pub const TEST_PASSWORD: &str = "Passw0rd!test";
+
+/// 测试上下文：独立的内存数据库与缓存
+pub struct TestContext {
+    pub storage: Arc<dyn Storage>,
+    pub cache: Arc<dyn ObjectCache>,
+}
+
+impl TestContext {
+    /// 创建新的测试上下文（内存 SQLite，已执行全部迁移）
+    pub async fn new() -> Self {
+        // 测试请求没有真实的客户端 IP，全部落在同一个限流桶中，放宽认证类限流
+        for prefix in ["login", "register", "refresh"] {
+            set_limit_override(prefix, 10_000);
+        }
+
+        let storage = SeaOrmStorage::new_with_url(":memory:")
+            .await
+            .expect("Failed to create in-memory storage");
+        let cache = MokaCacheWrapper::new().expect("Failed to create cache");
+
+        Self {
+            storage: Arc::new(storage),
+            cache: Arc::new(cache),
+        }
+    }
+
+    /// 创建指定角色的用户，用户名同时用作邮箱前缀
+    pub async fn create_user(&self, username: &str, role: UserRole) -> User {
+        self.create_org_user(username, role, None).await
+    }
+
+    /// 在指定组织中创建用户（`org_id` 为空表示默认租户）
+    pub async fn create_org_user(
+        &self,
+        username: &str,
+        role: UserRole,
+        org_id: Option<i64>,
+    ) -> User {
+        self.storage
+            .create_user(CreateUserRequest {
+                username: username.to_string(),
+                email: format!("{username}@example.com"),
+                password: hash_password(TEST_PASSWORD).expect("Failed to hash password"),
+                role,
+                display_name: Some(username.to_string()),
+                avatar_url: None,
+                org_id,
+            })
+            .await
+            .expect("Failed to create user")
+    }
+
+    /// 创建用户并返回其访问令牌
+    pub async fn create_user_with_token(&self, username: &str, role: UserRole) -> (User, String) {
+        let user = self.create_user(username, role).await;
+        let token = token_for(&user);
+        (user, token)
+    }
+
+    /// 初始化空的动态配置缓存
+    ///
+    /// 测试环境未从数据库加载配置，初始化后管理员通过 [`set_setting`] 的修改即时生效。
+    pub async fn init_dynamic_config(&self) {
+        DynamicConfig::init(vec![]).await;
+    }
+}
+
+/// 为用户签发访问令牌（绕过登录接口）
+pub fn token_for(user: &User) -> String {
+    JwtUtils::generate_access_token(user.id, &user.role.to_string())
+        .expect("Failed to generate access token")
+}
+
+/// 按生产配置组装应用（不含 CORS、压缩等与业务无关的中间件）
+pub fn build_app(
+    ctx: &TestContext,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error = Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .app_data(web::QueryConfig::default().error_handler(query_error_handler))
+        .app_data(web::JsonConfig::default().error_handler(json_error_handler))
+        .app_data(web::Data::new(ctx.storage.clone()))
+        .app_data(web::Data::new(ctx.cache.clone()))
+        .app_data(web::Data::new(AppStartTime {
+            start_datetime: chrono::Utc::now(),
+        }))
+        .configure(routes::configure_api_routes)
+}
+
+/// 构造 GET 请求，`token` 为空时不附带认证头
+pub fn get(path: &str, token: Option<&str>) -> TestRequest {
+    with_token(TestRequest::get().uri(path), token)
+}
+
+/// 构造带 JSON 请求体的 POST 请求
+pub fn post_json(path: &str, token: Option<&str>, body: Value) -> TestRequest {
+    with_token(TestRequest::post().uri(path).set_json(body), token)
+}
+
+/// 构造带 JSON 请求体的 PUT 请求
+pub fn put_json(path: &str, token: Option<&str>, body: Value) -> TestRequest {
+    with_token(TestRequest::put().uri(path).set_json(body), token)
+}
+
+/// 构造修改系统设置的请求（`token` 须为管理员令牌）
+pub fn set_setting(token: &str, key: &str, value: &str) -> TestRequest {
+    put_json(
+        &format!("/api/v1/system/admin/settings/{key}"),
+        Some(token),
+        json!({ "value": value }),
+    )
+}
+
+/// 构造 DELETE 请求
+pub fn delete(path: &str, token: Option<&str>) -> TestRequest {
+    with_token(TestRequest::delete().uri(path), token)
+}
+
+fn with_token(req: TestRequest, token: Option<&str>) -> TestRequest {
+    match token {
+        Some(token) => req.insert_header((AUTHORIZATION, format!("Bearer {token}"))),
+        None => req,
+    }
+}
+
+/// 发送请求并解析 JSON 响应体（非 JSON 响应体返回 `Value::Null`）
+pub async fn send<S, R, B>(app: &S, req: R) -> (StatusCode, Value)
+where
+    S: Service<R, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    let body = test::read_body(resp).await;
+    let value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    (status, value)
+}
+
+/// 发送请求并返回原始响应体与 Content-Type（用于文件导出等二进制响应）
+pub async fn send_raw<S, R, B>(app: &S, req: R) -> (StatusCode, String, Vec<u8>)
+where
+    S: Service<R, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = test::read_body(resp).await.to_vec();
+    (status, content_type, body)
+}
+
+/// 标准课堂场景：一名管理员、一名教师、一名班级内学生、一名班级外用户，
+/// 以及教师负责的一个班级和其中一份作业
+pub struct ClassScenario {
+    pub admin: User,
+    pub admin_token: String,
+    pub teacher: User,
+    pub teacher_token: String,
+    pub student: User,
+    pub student_token: String,
+    pub outsider: User,
+    pub outsider_token: String,
+    pub class: Class,
+    pub homework: Homework,
+}
+
+impl TestContext {
+    /// 创建班级并将教师加入 class_users
+    pub async fn create_class(&self, teacher: &User, name: &str) -> Class {
+        let class = self
+            .storage
+            .create_class(CreateClassRequest {
+                teacher_id: Some(teacher.id),
+                name: name.to_string(),
+                description: None,
+            })
+            .await
+            .expect("Failed to create class");
+        self.join_class(teacher, &class, ClassUserRole::Teacher)
+            .await;
+        class
+    }
+
+    /// 以指定班级角色加入班级
+    pub async fn join_class(&self, user: &User, class: &Class, role: ClassUserRole) {
+        self.storage
+            .join_class(user.id, class.id, role, None)
+            .await
+            .expect("Failed to join class");
+    }
+
+    /// 在班级中布置作业（无截止时间，满分 100）
+    pub async fn create_homework(&self, teacher: &User, class: &Class, title: &str) -> Homework {
+        self.insert_homework(teacher, class, title, None).await
+    }
+
+    /// 在班级中布置带截止时间的作业（满分 100）
+    pub async fn create_homework_due(
+        &self,
+        teacher: &User,
+        class: &Class,
+        title: &str,
+        deadline: DateTime<Utc>,
+    ) -> Homework {
+        self.insert_homework(teacher, class, title, Some(deadline))
+            .await
+    }
+
+    async fn insert_homework(
+        &self,
+        teacher: &User,
+        class: &Class,
+        title: &str,
+        deadline: Option<DateTime<Utc>>,
+    ) -> Homework {
+        self.storage
+            .create_homework(
+                teacher.id,
+                CreateHomeworkRequest {
+                    class_id: class.id,
+                    title: title.to_string(),
+                    description: None,
+                    max_score: Some(100.0),
+                    deadline,
+                    allow_late: None,
+                    submission_mode: None,
+                    max_content_length: None,
+                    exam_mode: None,
+                    require_self_assessment: None,
+                    show_grade_context: None,
+                    estimated_minutes: None,
+                    difficulty: None,
+                    grace_minutes: None,
+                    attachments: None,
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create homework")
+    }
+
+    /// 学生提交作业
+    pub async fn create_submission(
+        &self,
+        student: &User,
+        homework: &Homework,
+        content: &str,
+    ) -> Submission {
+        self.storage
+            .create_submission(
+                student.id,
+                CreateSubmissionRequest {
+                    homework_id: homework.id,
+                    part_id: None,
+                    content: content.to_string(),
+                    attachments: None,
+                    signed_timestamp: None,
+                    client_time: None,
+                    self_assessment: None,
+                    references: None,
+                },
+                vec![],
+                0,
+            )
+            .await
+            .expect("Failed to create submission")
+    }
+
+    /// 搭建标准课堂场景，用户名以 `prefix` 区分
+    pub async fn class_scenario(&self, prefix: &str) -> ClassScenario {
+        let (admin, admin_token) = self
+            .create_user_with_token(&format!("{prefix}_admin"), UserRole::Admin)
+            .await;
+        let (teacher, teacher_token) = self
+            .create_user_with_token(&format!("{prefix}_teacher"), UserRole::Teacher)
+            .await;
+        let (student, student_token) = self
+            .create_user_with_token(&format!("{prefix}_student"), UserRole::User)
+            .await;
+        let (outsider, outsider_token) = self
+            .create_user_with_token(&format!("{prefix}_outsider"), UserRole::User)
+            .await;
+
+        let class = self.create_class(&teacher, &format!("{prefix} 班级")).await;
+        self.join_class(&student, &class, ClassUserRole::Student)
+            .await;
+        let homework = self
+            .create_homework(&teacher, &class, &format!("{prefix} 作业"))
+            .await;
+
+        ClassScenario {
+            admin,
+            admin_token,
+            teacher,
+            teacher_token,
+            student,
+            student_token,
+            outsider,
+            outsider_token,
+            class,
+            homework,
+        }
+    }
 }
@@ -1,7 +1,8 @@
 //! WebSocket 服务单元测试
 
 use rust_hwsystem_next::services::websocket::{
-    ConnectionManager, WsMessage, get_online_count, is_user_online,
+    ConnectionLimit, ConnectionManager, NotificationPayload, WsMessage, disconnect_user,
+    get_online_count, is_user_online,
 };
 
 #[test]
@@ -44,6 +45,41 @@ fn test_send_to_user() {
     assert!(received.is_ok());
 }
 
+#[test]
+fn test_connection_limits() {
+    let manager = ConnectionManager::get();
+
+    // 单用户上限
+    let rx1 = manager.try_register(400, 0, 2).unwrap();
+    let _rx2 = manager.try_register(400, 0, 2).unwrap();
+    assert_eq!(
+        manager.try_register(400, 0, 2).err(),
+        Some(ConnectionLimit::PerUser)
+    );
+
+    // 断开一个连接后可以重新连接
+    manager.unregister(400, rx1);
+    assert!(manager.try_register(400, 0, 2).is_ok());
+
+    // 全局上限（本测试持有连接，总数至少为 1）
+    assert_eq!(
+        manager.try_register(401, 1, 0).err(),
+        Some(ConnectionLimit::Global)
+    );
+    assert!(!manager.is_online(401));
+}
+
+#[test]
+fn test_unregister_removes_offline_user() {
+    let manager = ConnectionManager::get();
+    let rx = manager.register(410);
+    assert!(manager.is_online(410));
+
+    manager.unregister(410, rx);
+    assert!(!manager.is_online(410));
+    assert!(!manager.send_to_user(410, WsMessage::Ping));
+}
+
 #[test]
 fn test_send_to_offline_user() {
     let manager = ConnectionManager::get();
@@ -78,9 +114,49 @@ fn test_ws_message_serialization() {
     assert!(json.contains("Test error"));
 }
 
+#[test]
+fn test_auth_message_deserialization() {
+    let msg: WsMessage = serde_json::from_str(r#"{"type":"auth","token":"abc"}"#).unwrap();
+    assert!(matches!(msg, WsMessage::Auth { token } if token == "abc"));
+
+    let msg: WsMessage = serde_json::from_str(r#"{"type":"token_refresh","token":"def"}"#).unwrap();
+    assert!(matches!(msg, WsMessage::TokenRefresh { token } if token == "def"));
+}
+
+#[test]
+fn test_disconnect_user() {
+    let manager = ConnectionManager::get();
+    let mut rx = manager.register(300);
+
+    disconnect_user(300, "账号已停用");
+
+    let received = rx.try_recv().unwrap();
+    assert!(matches!(received, WsMessage::SessionRevoked { reason } if reason == "账号已停用"));
+}
+
 #[test]
 fn test_helper_functions() {
     // 测试辅助函数
     let _ = get_online_count();
     let _ = is_user_online(12345);
 }
+
+#[test]
+fn test_notifications_batch_serialization() {
+    let payload = NotificationPayload {
+        id: 7,
+        notification_type: "homework_created".to_string(),
+        title: "新作业发布".to_string(),
+        content: None,
+        reference_type: None,
+        reference_id: None,
+        created_at: chrono::Utc::now(),
+    };
+    let batch = WsMessage::Notifications {
+        payloads: vec![payload.clone(), NotificationPayload { id: 8, ..payload }],
+    };
+    let json: serde_json::Value = serde_json::to_value(&batch).unwrap();
+    assert_eq!(json["type"], "notifications");
+    assert_eq!(json["payloads"].as_array().unwrap().len(), 2);
+    assert_eq!(json["payloads"][1]["id"], 8);
+}